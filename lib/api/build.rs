@@ -147,6 +147,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WalConfigDiff.wal_capacity_mb", "range(min = 1)"),
             ("WalConfigDiff.wal_retain_closed", "range(min = 1)"),
             ("OptimizersConfigDiff.deleted_threshold", "range(min = 0.0, max = 1.0)"),
+            ("OptimizersConfigDiff.graph_heal_threshold", "range(min = 0.0, max = 1.0)"),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "range(min = 100)"),
             ("OptimizersConfigDiff.max_segment_size", "range(min = 1)"),
             ("VectorsConfig.config", ""),
@@ -154,6 +155,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("VectorParams.size", "range(min = 1, max = 65536)"),
             ("VectorParams.hnsw_config", ""),
             ("VectorParams.quantization_config", ""),
+            ("VectorParams.vamana_config", ""),
+            ("VamanaConfig.max_degree", "range(min = 4)"),
+            ("VamanaConfig.build_list_size", "range(min = 4)"),
+            ("VamanaConfig.beam_width", "range(min = 1)"),
+            ("VectorParams.ivf_config", ""),
+            ("IvfConfig.num_lists", "range(min = 1)"),
+            ("IvfConfig.nprobe", "range(min = 1)"),
+            ("IvfConfig.pq", ""),
+            ("IvfPqConfig.num_subvectors", "range(min = 1)"),
             ("VectorParamsMap.map", ""),
             ("VectorParamsDiff.hnsw_config", ""),
             ("VectorParamsDiff.quantization_config", ""),
@@ -161,6 +171,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
             ("ScalarQuantization.quantile", "range(min = 0.5, max = 1.0)"),
+            ("ResidualQuantization.stages", "range(min = 1, max = 8)"),
+            ("AnisotropicQuantization.parallel_weight", "range(min = 1.0, max = 100.0)"),
             ("UpdateCollectionClusterSetupRequest.timeout", "range(min = 1)"),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
             ("StrictModeConfig.max_query_limit", "range(min = 1)"),
@@ -200,6 +212,8 @@ fn configure_validation(builder: Builder) -> Builder {
         // Service: points.proto
         .validates(&[
             ("AcornSearchParams.max_selectivity", "range(min = 0.0, max = 1.0)"),
+            ("EarlyTerminationParams.epsilon", "range(min = 0.0)"),
+            ("EarlyTerminationParams.patience", "range(min = 1)"),
             ("PointsSelector.points_selector_one_of", ""),
             ("UpsertPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("UpsertPoints.points", ""),
@@ -221,6 +235,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ClearPayloadPoints.points", ""),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
+            ("PointsChangesRequest.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("CreateFieldIndexCollection.field_name", "length(min = 1)"),
             ("CreateFieldIndexCollection.field_index_params", ""),
@@ -244,6 +259,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.timeout", "range(min = 1)"),
             ("SearchParams.quantization", ""),
             ("SearchParams.acorn", ""),
+            ("SearchParams.target_recall", "range(min = 0.0, max = 1.0)"),
+            ("SearchParams.nprobe", "range(min = 1)"),
+            ("SearchParams.early_termination", ""),
+            ("SearchParams.sparse_max_posting_length", "range(min = 1)"),
             ("QuantizationSearchParams.oversampling", "range(min = 1.0)"),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("ScrollPoints.filter", ""),
@@ -321,11 +340,15 @@ fn configure_validation(builder: Builder) -> Builder {
             ("Mmr.diversity", "range(min = 0.0, max = 1.0)"),
             ("Mmr.candidates_limit", "range(max = 16_384)"),
             ("Rrf.k", "range(min = 1)"),
+            ("HybridQuery.dense", "length(min = 1)"),
+            ("HybridQuery.sparse", ""),
+            ("HybridQuery.alpha", "range(min = 0.0, max = 1.0)"),
             ("Query.variant", ""),
             ("PrefetchQuery.prefetch", ""),
             ("PrefetchQuery.query", ""),
             ("PrefetchQuery.filter", ""),
             ("PrefetchQuery.params", ""),
+            ("PrefetchQuery.quantization", ""),
             ("PrefetchQuery.limit", "range(min = 1)"),
             ("QueryPoints.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("QueryPoints.limit", "range(min = 1)"),
@@ -396,6 +419,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPointsInternal.timeout", "range(min = 1)"),
             ("FacetCountsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
             ("FacetCountsInternal.timeout", "range(min = 1)"),
+            ("TextQueryStatisticsInternal.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name_legacy\")"),
+            ("TextQueryStatisticsInternal.timeout", "range(min = 1)"),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
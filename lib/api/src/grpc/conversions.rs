@@ -6,6 +6,8 @@ use ahash::AHashSet;
 use chrono::{NaiveDateTime, Timelike};
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_data::HardwareData;
+use common::request_profile::{RequestProfileData, StageProfile};
+use common::search_diagnostics::SearchDiagnosticsData;
 use common::types::ScoreType;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
@@ -14,7 +16,7 @@ use segment::data_types::index::{
     BoolIndexType, DatetimeIndexType, FloatIndexType, GeoIndexType, IntegerIndexType,
     KeywordIndexType, SnowballLanguage, TextIndexType, UuidIndexType,
 };
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, NamedMultiDenseVector, VectorInternal};
 use segment::data_types::{facets as segment_facets, vectors as segment_vectors};
 use segment::index::query_optimization::rescore_formula::parsed_formula::{
@@ -28,13 +30,15 @@ use uuid::Uuid;
 
 use super::qdrant::{
     BinaryQuantization, BoolIndexParams, CompressionRatio, DatetimeIndexParams, DatetimeRange,
-    Direction, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal, FieldType,
+    DimRange, Direction, FacetHit, FacetHitInternal, FacetValue, FacetValueInternal, FieldType,
     FloatIndexParams, GeoIndexParams, GeoLineString, GroupId, HardwareUsage, HasVectorCondition,
-    KeywordIndexParams, LookupLocation, MaxOptimizationThreads, MultiVectorComparator,
-    MultiVectorConfig, OrderBy, OrderValue, Range, RawVector, RecommendStrategy, RetrievedPoint,
-    SearchMatrixPair, SearchPointGroups, SearchPoints, ShardKeySelector, StartFrom,
-    StrictModeMultivector, StrictModeMultivectorConfig, StrictModeSparse, StrictModeSparseConfig,
-    UuidIndexParams, VectorsOutput, WithLookup, raw_query, start_from,
+    HybridQuery, IvfConfig, IvfPqConfig, KeywordIndexParams, LookupLocation,
+    MaxOptimizationThreads, MultiVectorComparator, MultiVectorConfig, MultiVectorOverflowPolicy,
+    OrderBy, OrderValue, Range, RawVector, RecommendStrategy, RetrievedPoint, SearchMatrixPair,
+    SearchPointGroups, SearchPoints, ShardKeySelector, StartFrom, StrictModeMultivector,
+    StrictModeMultivectorConfig, StrictModeSparse, StrictModeSparseConfig, TextQuery,
+    TextQueryInternal, TextQueryStatistics, UuidIndexParams, VamanaConfig, VectorsOutput,
+    WithLookup, raw_query, start_from,
 };
 use super::stemming_algorithm::StemmingParams;
 use super::{Expression, Formula, RecoQuery, SnowballParams, StemmingAlgorithm, Usage};
@@ -45,18 +49,18 @@ use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
-    AcornSearchParams, CollectionDescription, CollectionOperationResponse, Condition, Distance,
-    FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, HasIdCondition,
-    HealthCheckReply, HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition,
-    ListCollectionsResponse, ListShardKeysResponse, Match, MinShould, NamedVectors,
-    NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams,
-    PayloadSchemaInfo, PayloadSchemaType, PointId, PointStruct, PointsOperationResponse,
-    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
-    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
-    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, ShardKeyDescription, StopwordsSet,
-    StrictModeConfig, TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal,
-    ValuesCount, VectorsSelector, WithPayloadSelector, WithVectorsSelector, shard_key,
-    with_vectors_selector,
+    AcornSearchParams, AnisotropicQuantization, CollectionDescription, CollectionOperationResponse,
+    Condition, Distance, EarlyTerminationParams, FieldCondition, Filter, GeoBoundingBox, GeoPoint,
+    GeoPolygon, GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
+    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListShardKeysResponse, Match,
+    MinShould, NamedVectors, NestedCondition, PayloadExcludeSelector, PayloadIncludeSelector,
+    PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId, PointStruct,
+    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
+    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
+    RepeatedStrings, ResidualQuantization, ScalarQuantization, ScoredPoint, SearchParams, ShardKey,
+    ShardKeyDescription, StopwordsSet, StrictModeConfig, TextIndexParams, TokenizerType,
+    UpdateResult, UpdateResultInternal, ValuesCount, VectorsSelector, WithPayloadSelector,
+    WithVectorsSelector, shard_key, with_vectors_selector,
 };
 use crate::grpc::{
     self, BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding, DecayParamsExpression,
@@ -215,6 +219,7 @@ impl From<segment::data_types::index::IntegerIndexParams> for PayloadIndexParams
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         PayloadIndexParams {
             index_params: Some(IndexParams::IntegerIndexParams(IntegerIndexParams {
@@ -223,6 +228,7 @@ impl From<segment::data_types::index::IntegerIndexParams> for PayloadIndexParams
                 is_principal,
                 on_disk,
                 enable_hnsw,
+                enable_hnsw_ranges,
             })),
         }
     }
@@ -235,12 +241,14 @@ impl From<segment::data_types::index::FloatIndexParams> for PayloadIndexParams {
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         PayloadIndexParams {
             index_params: Some(IndexParams::FloatIndexParams(FloatIndexParams {
                 on_disk,
                 is_principal,
                 enable_hnsw,
+                enable_hnsw_ranges,
             })),
         }
     }
@@ -342,12 +350,14 @@ impl From<segment::data_types::index::DatetimeIndexParams> for PayloadIndexParam
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         PayloadIndexParams {
             index_params: Some(IndexParams::DatetimeIndexParams(DatetimeIndexParams {
                 on_disk,
                 is_principal,
                 enable_hnsw,
+                enable_hnsw_ranges,
             })),
         }
     }
@@ -435,9 +445,18 @@ impl From<segment::data_types::index::StemmingAlgorithm> for StemmingAlgorithm {
                 let segment::data_types::index::SnowballParams {
                     r#type: _,
                     language,
+                    additional_languages,
                 } = snowball_params;
                 let language = language.to_string();
-                StemmingParams::Snowball(SnowballParams { language })
+                let additional_languages = additional_languages
+                    .into_iter()
+                    .flatten()
+                    .map(|language| language.to_string())
+                    .collect();
+                StemmingParams::Snowball(SnowballParams {
+                    language,
+                    additional_languages,
+                })
             }
         };
 
@@ -503,6 +522,7 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::index::IntegerIndexPar
             is_principal,
             on_disk,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         Ok(segment::data_types::index::IntegerIndexParams {
             r#type: IntegerIndexType::Integer,
@@ -511,6 +531,7 @@ impl TryFrom<IntegerIndexParams> for segment::data_types::index::IntegerIndexPar
             is_principal,
             on_disk,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 }
@@ -522,12 +543,14 @@ impl TryFrom<FloatIndexParams> for segment::data_types::index::FloatIndexParams
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         Ok(segment::data_types::index::FloatIndexParams {
             r#type: FloatIndexType::Float,
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 }
@@ -634,13 +657,23 @@ impl TryFrom<StemmingParams> for segment::data_types::index::StemmingAlgorithm {
     fn try_from(value: StemmingParams) -> Result<Self, Self::Error> {
         match value {
             StemmingParams::Snowball(params) => {
-                let language = SnowballLanguage::from_str(&params.language).map_err(|_| {
-                    Status::invalid_argument(format!("Language {:?} not found.", params.language))
-                })?;
+                let parse_language = |language: &str| {
+                    SnowballLanguage::from_str(language).map_err(|_| {
+                        Status::invalid_argument(format!("Language {language:?} not found."))
+                    })
+                };
+                let language = parse_language(&params.language)?;
+                let additional_languages = params
+                    .additional_languages
+                    .iter()
+                    .map(|language| parse_language(language))
+                    .collect::<Result<Vec<_>, _>>()?;
                 Ok(segment::data_types::index::StemmingAlgorithm::Snowball(
                     segment::data_types::index::SnowballParams {
                         r#type: segment::data_types::index::Snowball::Snowball,
                         language,
+                        additional_languages: (!additional_languages.is_empty())
+                            .then_some(additional_languages),
                     },
                 ))
             }
@@ -670,12 +703,14 @@ impl TryFrom<DatetimeIndexParams> for segment::data_types::index::DatetimeIndexP
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         } = params;
         Ok(segment::data_types::index::DatetimeIndexParams {
             r#type: DatetimeIndexType::Datetime,
             on_disk,
             is_principal,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 }
@@ -909,6 +944,26 @@ impl From<segment::types::AcornSearchParams> for AcornSearchParams {
     }
 }
 
+impl From<EarlyTerminationParams> for segment::types::EarlyTerminationParams {
+    fn from(params: EarlyTerminationParams) -> Self {
+        let EarlyTerminationParams { epsilon, patience } = params;
+        Self {
+            epsilon: OrderedFloat(epsilon),
+            patience: patience as usize,
+        }
+    }
+}
+
+impl From<segment::types::EarlyTerminationParams> for EarlyTerminationParams {
+    fn from(params: segment::types::EarlyTerminationParams) -> Self {
+        let segment::types::EarlyTerminationParams { epsilon, patience } = params;
+        Self {
+            epsilon: epsilon.into_inner(),
+            patience: patience as u64,
+        }
+    }
+}
+
 impl From<SearchParams> for segment::types::SearchParams {
     fn from(params: SearchParams) -> Self {
         let SearchParams {
@@ -917,6 +972,10 @@ impl From<SearchParams> for segment::types::SearchParams {
             quantization,
             indexed_only,
             acorn,
+            target_recall,
+            nprobe,
+            early_termination,
+            sparse_max_posting_length,
         } = params;
         Self {
             hnsw_ef: hnsw_ef.map(|x| x as usize),
@@ -924,6 +983,10 @@ impl From<SearchParams> for segment::types::SearchParams {
             quantization: quantization.map(|q| q.into()),
             indexed_only: indexed_only.unwrap_or(false),
             acorn: acorn.map(segment::types::AcornSearchParams::from),
+            target_recall: target_recall.map(OrderedFloat),
+            nprobe: nprobe.map(|x| x as usize),
+            early_termination: early_termination.map(segment::types::EarlyTerminationParams::from),
+            sparse_max_posting_length: sparse_max_posting_length.map(|x| x as usize),
         }
     }
 }
@@ -936,6 +999,10 @@ impl From<segment::types::SearchParams> for SearchParams {
             quantization,
             indexed_only,
             acorn,
+            target_recall,
+            nprobe,
+            early_termination,
+            sparse_max_posting_length,
         } = params;
         Self {
             hnsw_ef: hnsw_ef.map(|x| x as u64),
@@ -943,6 +1010,10 @@ impl From<segment::types::SearchParams> for SearchParams {
             quantization: quantization.map(|q| q.into()),
             indexed_only: Some(indexed_only),
             acorn: acorn.map(AcornSearchParams::from),
+            target_recall: target_recall.map(|OrderedFloat(x)| x),
+            nprobe: nprobe.map(|x| x as u64),
+            early_termination: early_termination.map(EarlyTerminationParams::from),
+            sparse_max_posting_length: sparse_max_posting_length.map(|x| x as u64),
         }
     }
 }
@@ -966,6 +1037,7 @@ impl TryFrom<PointStruct> for rest::PointStruct {
             id,
             vectors,
             payload,
+            ttl,
         } = value;
 
         // empty payload means None in PointStruct
@@ -986,6 +1058,7 @@ impl TryFrom<PointStruct> for rest::PointStruct {
                 .try_into()?,
             vector: vector_struct,
             payload: converted_payload,
+            ttl,
         })
     }
 }
@@ -1186,9 +1259,11 @@ impl From<segment::types::ScalarQuantization> for ScalarQuantization {
         ScalarQuantization {
             r#type: match config.r#type {
                 segment::types::ScalarType::Int8 => QuantizationType::Int8 as i32,
+                segment::types::ScalarType::Int4 => QuantizationType::Int4 as i32,
             },
             quantile: config.quantile,
             always_ram: config.always_ram,
+            drop_originals: config.drop_originals,
         }
     }
 }
@@ -1201,17 +1276,20 @@ impl TryFrom<ScalarQuantization> for segment::types::ScalarQuantization {
             r#type,
             quantile,
             always_ram,
+            drop_originals,
         } = value;
         Ok(segment::types::ScalarQuantization {
             scalar: segment::types::ScalarQuantizationConfig {
                 r#type: match QuantizationType::try_from(r#type).ok() {
                     Some(QuantizationType::Int8) => segment::types::ScalarType::Int8,
+                    Some(QuantizationType::Int4) => segment::types::ScalarType::Int4,
                     Some(QuantizationType::UnknownQuantization) | None => {
                         return Err(Status::invalid_argument("Unknown quantization type"));
                     }
                 },
                 quantile,
                 always_ram,
+                drop_originals,
             },
         })
     }
@@ -1223,6 +1301,7 @@ impl From<segment::types::ProductQuantization> for ProductQuantization {
         let segment::types::ProductQuantizationConfig {
             compression,
             always_ram,
+            drop_originals,
         } = product;
         ProductQuantization {
             compression: match compression {
@@ -1233,6 +1312,7 @@ impl From<segment::types::ProductQuantization> for ProductQuantization {
                 segment::types::CompressionRatio::X64 => CompressionRatio::X64 as i32,
             },
             always_ram,
+            drop_originals,
         }
     }
 }
@@ -1244,6 +1324,7 @@ impl TryFrom<ProductQuantization> for segment::types::ProductQuantization {
         let ProductQuantization {
             compression,
             always_ram,
+            drop_originals,
         } = value;
         Ok(segment::types::ProductQuantization {
             product: segment::types::ProductQuantizationConfig {
@@ -1260,6 +1341,111 @@ impl TryFrom<ProductQuantization> for segment::types::ProductQuantization {
                     Ok(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
                 },
                 always_ram,
+                drop_originals,
+            },
+        })
+    }
+}
+
+impl From<segment::types::ResidualQuantization> for ResidualQuantization {
+    fn from(value: segment::types::ResidualQuantization) -> Self {
+        let segment::types::ResidualQuantization { residual } = value;
+        let segment::types::ResidualQuantizationConfig {
+            compression,
+            stages,
+            always_ram,
+        } = residual;
+        ResidualQuantization {
+            compression: match compression {
+                segment::types::CompressionRatio::X4 => CompressionRatio::X4 as i32,
+                segment::types::CompressionRatio::X8 => CompressionRatio::X8 as i32,
+                segment::types::CompressionRatio::X16 => CompressionRatio::X16 as i32,
+                segment::types::CompressionRatio::X32 => CompressionRatio::X32 as i32,
+                segment::types::CompressionRatio::X64 => CompressionRatio::X64 as i32,
+            },
+            stages: stages as u64,
+            always_ram,
+        }
+    }
+}
+
+impl TryFrom<ResidualQuantization> for segment::types::ResidualQuantization {
+    type Error = Status;
+
+    fn try_from(value: ResidualQuantization) -> Result<Self, Self::Error> {
+        let ResidualQuantization {
+            compression,
+            stages,
+            always_ram,
+        } = value;
+        Ok(segment::types::ResidualQuantization {
+            residual: segment::types::ResidualQuantizationConfig {
+                compression: match CompressionRatio::try_from(compression) {
+                    Err(_) => {
+                        return Err(Status::invalid_argument(
+                            "Unknown compression ratio".to_string(),
+                        ));
+                    }
+                    Ok(CompressionRatio::X4) => segment::types::CompressionRatio::X4,
+                    Ok(CompressionRatio::X8) => segment::types::CompressionRatio::X8,
+                    Ok(CompressionRatio::X16) => segment::types::CompressionRatio::X16,
+                    Ok(CompressionRatio::X32) => segment::types::CompressionRatio::X32,
+                    Ok(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
+                },
+                stages: stages as usize,
+                always_ram,
+            },
+        })
+    }
+}
+
+impl From<segment::types::AnisotropicQuantization> for AnisotropicQuantization {
+    fn from(value: segment::types::AnisotropicQuantization) -> Self {
+        let segment::types::AnisotropicQuantization { anisotropic } = value;
+        let segment::types::AnisotropicQuantizationConfig {
+            compression,
+            parallel_weight,
+            always_ram,
+        } = anisotropic;
+        AnisotropicQuantization {
+            compression: match compression {
+                segment::types::CompressionRatio::X4 => CompressionRatio::X4 as i32,
+                segment::types::CompressionRatio::X8 => CompressionRatio::X8 as i32,
+                segment::types::CompressionRatio::X16 => CompressionRatio::X16 as i32,
+                segment::types::CompressionRatio::X32 => CompressionRatio::X32 as i32,
+                segment::types::CompressionRatio::X64 => CompressionRatio::X64 as i32,
+            },
+            parallel_weight,
+            always_ram,
+        }
+    }
+}
+
+impl TryFrom<AnisotropicQuantization> for segment::types::AnisotropicQuantization {
+    type Error = Status;
+
+    fn try_from(value: AnisotropicQuantization) -> Result<Self, Self::Error> {
+        let AnisotropicQuantization {
+            compression,
+            parallel_weight,
+            always_ram,
+        } = value;
+        Ok(segment::types::AnisotropicQuantization {
+            anisotropic: segment::types::AnisotropicQuantizationConfig {
+                compression: match CompressionRatio::try_from(compression) {
+                    Err(_) => {
+                        return Err(Status::invalid_argument(
+                            "Unknown compression ratio".to_string(),
+                        ));
+                    }
+                    Ok(CompressionRatio::X4) => segment::types::CompressionRatio::X4,
+                    Ok(CompressionRatio::X8) => segment::types::CompressionRatio::X8,
+                    Ok(CompressionRatio::X16) => segment::types::CompressionRatio::X16,
+                    Ok(CompressionRatio::X32) => segment::types::CompressionRatio::X32,
+                    Ok(CompressionRatio::X64) => segment::types::CompressionRatio::X64,
+                },
+                parallel_weight,
+                always_ram,
             },
         })
     }
@@ -1360,6 +1546,18 @@ impl From<segment::types::QuantizationConfig> for QuantizationConfig {
                     binary.into(),
                 )),
             },
+            segment::types::QuantizationConfig::Residual(residual) => Self {
+                quantization: Some(super::qdrant::quantization_config::Quantization::Residual(
+                    residual.into(),
+                )),
+            },
+            segment::types::QuantizationConfig::Anisotropic(anisotropic) => Self {
+                quantization: Some(
+                    super::qdrant::quantization_config::Quantization::Anisotropic(
+                        anisotropic.into(),
+                    ),
+                ),
+            },
         }
     }
 }
@@ -1381,6 +1579,12 @@ impl TryFrom<QuantizationConfig> for segment::types::QuantizationConfig {
             super::qdrant::quantization_config::Quantization::Binary(config) => Ok(
                 segment::types::QuantizationConfig::Binary(config.try_into()?),
             ),
+            super::qdrant::quantization_config::Quantization::Residual(config) => Ok(
+                segment::types::QuantizationConfig::Residual(config.try_into()?),
+            ),
+            super::qdrant::quantization_config::Quantization::Anisotropic(config) => Ok(
+                segment::types::QuantizationConfig::Anisotropic(config.try_into()?),
+            ),
         }
     }
 }
@@ -1412,6 +1616,7 @@ impl TryFrom<BinaryQuantizationQueryEncoding> for segment::types::BinaryQuantiza
                     Setting::Scalar8Bits => {
                         segment::types::BinaryQuantizationQueryEncoding::Scalar8Bits
                     }
+                    Setting::Float32 => segment::types::BinaryQuantizationQueryEncoding::Float32,
                 }
             }
         };
@@ -1436,6 +1641,9 @@ impl From<segment::types::BinaryQuantizationQueryEncoding> for BinaryQuantizatio
             segment::types::BinaryQuantizationQueryEncoding::Scalar8Bits => {
                 Variant::Setting(Setting::Scalar8Bits.into())
             }
+            segment::types::BinaryQuantizationQueryEncoding::Float32 => {
+                Variant::Setting(Setting::Float32.into())
+            }
         };
 
         Self {
@@ -1446,9 +1654,16 @@ impl From<segment::types::BinaryQuantizationQueryEncoding> for BinaryQuantizatio
 
 impl From<segment::types::MultiVectorConfig> for MultiVectorConfig {
     fn from(value: segment::types::MultiVectorConfig) -> Self {
-        let segment::types::MultiVectorConfig { comparator } = value;
+        let segment::types::MultiVectorConfig {
+            comparator,
+            max_vectors_per_point,
+            overflow_policy,
+        } = value;
         Self {
             comparator: MultiVectorComparator::from(comparator) as i32,
+            max_vectors_per_point: max_vectors_per_point.map(|max| max as u64),
+            overflow_policy: overflow_policy
+                .map(|policy| MultiVectorOverflowPolicy::from(policy) as i32),
         }
     }
 }
@@ -1465,11 +1680,23 @@ impl TryFrom<MultiVectorConfig> for segment::types::MultiVectorConfig {
     type Error = Status;
 
     fn try_from(value: MultiVectorConfig) -> Result<Self, Self::Error> {
-        let MultiVectorConfig { comparator } = value;
+        let MultiVectorConfig {
+            comparator,
+            max_vectors_per_point,
+            overflow_policy,
+        } = value;
         let comparator = MultiVectorComparator::try_from(comparator)
             .map_err(|_| Status::invalid_argument("Unknown multi vector comparator"))?;
+        let overflow_policy = overflow_policy
+            .map(|policy| {
+                MultiVectorOverflowPolicy::try_from(policy)
+                    .map_err(|_| Status::invalid_argument("Unknown multi vector overflow policy"))
+            })
+            .transpose()?;
         Ok(segment::types::MultiVectorConfig {
             comparator: segment::types::MultiVectorComparator::from(comparator),
+            max_vectors_per_point: max_vectors_per_point.map(|max| max as usize),
+            overflow_policy: overflow_policy.map(segment::types::MultiVectorOverflowPolicy::from),
         })
     }
 }
@@ -1482,6 +1709,54 @@ impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     }
 }
 
+impl From<segment::types::MultiVectorOverflowPolicy> for MultiVectorOverflowPolicy {
+    fn from(value: segment::types::MultiVectorOverflowPolicy) -> Self {
+        match value {
+            segment::types::MultiVectorOverflowPolicy::Reject => MultiVectorOverflowPolicy::Reject,
+            segment::types::MultiVectorOverflowPolicy::Truncate => {
+                MultiVectorOverflowPolicy::Truncate
+            }
+            segment::types::MultiVectorOverflowPolicy::AveragePool => {
+                MultiVectorOverflowPolicy::AveragePool
+            }
+        }
+    }
+}
+
+impl From<MultiVectorOverflowPolicy> for segment::types::MultiVectorOverflowPolicy {
+    fn from(value: MultiVectorOverflowPolicy) -> Self {
+        match value {
+            MultiVectorOverflowPolicy::Reject => segment::types::MultiVectorOverflowPolicy::Reject,
+            MultiVectorOverflowPolicy::Truncate => {
+                segment::types::MultiVectorOverflowPolicy::Truncate
+            }
+            MultiVectorOverflowPolicy::AveragePool => {
+                segment::types::MultiVectorOverflowPolicy::AveragePool
+            }
+        }
+    }
+}
+
+impl From<segment::types::DimRange> for DimRange {
+    fn from(value: segment::types::DimRange) -> Self {
+        let segment::types::DimRange { min, max } = value;
+        Self {
+            min: min as u64,
+            max: max as u64,
+        }
+    }
+}
+
+impl From<DimRange> for segment::types::DimRange {
+    fn from(value: DimRange) -> Self {
+        let DimRange { min, max } = value;
+        Self {
+            min: min as usize,
+            max: max as usize,
+        }
+    }
+}
+
 fn conditions_helper_from_grpc(
     conditions: Vec<Condition>,
 ) -> Result<Option<Vec<segment::types::Condition>>, tonic::Status> {
@@ -2060,8 +2335,12 @@ impl From<segment::types::Match> for Match {
             segment::types::Match::Text(segment::types::MatchText { text }) => {
                 MatchValue::Text(text)
             }
-            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase }) => {
-                MatchValue::Phrase(phrase)
+            segment::types::Match::Phrase(segment::types::MatchPhrase { phrase, slop }) => {
+                // gRPC phrase has no separate slop, it is passed with the proximity syntax
+                match slop {
+                    Some(slop) => MatchValue::Phrase(format!("\"{phrase}\"~{slop}")),
+                    None => MatchValue::Phrase(phrase),
+                }
             }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Strings(strings) => {
@@ -2172,6 +2451,128 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
     }
 }
 
+impl TryFrom<TextQuery> for segment::data_types::text_query::TextQuery {
+    type Error = Status;
+
+    fn try_from(value: TextQuery) -> Result<Self, Self::Error> {
+        let TextQuery { key, text } = value;
+        Ok(Self {
+            key: json::json_path_from_proto(&key)?,
+            text,
+        })
+    }
+}
+
+impl From<segment::data_types::text_query::TextQuery> for TextQuery {
+    fn from(value: segment::data_types::text_query::TextQuery) -> Self {
+        let segment::data_types::text_query::TextQuery { key, text } = value;
+        Self {
+            key: key.to_string(),
+            text,
+        }
+    }
+}
+
+impl From<TextQueryStatistics> for segment::data_types::text_query::TextQueryStatistics {
+    fn from(value: TextQueryStatistics) -> Self {
+        let TextQueryStatistics {
+            points_count,
+            total_length,
+            doc_frequencies,
+        } = value;
+        Self {
+            points_count: points_count as usize,
+            total_length: total_length as usize,
+            doc_frequencies: doc_frequencies
+                .into_iter()
+                .map(|(token, frequency)| (token, frequency as usize))
+                .collect(),
+        }
+    }
+}
+
+impl From<segment::data_types::text_query::TextQueryStatistics> for TextQueryStatistics {
+    fn from(value: segment::data_types::text_query::TextQueryStatistics) -> Self {
+        let segment::data_types::text_query::TextQueryStatistics {
+            points_count,
+            total_length,
+            doc_frequencies,
+        } = value;
+        Self {
+            points_count: points_count as u64,
+            total_length: total_length as u64,
+            doc_frequencies: doc_frequencies
+                .into_iter()
+                .map(|(token, frequency)| (token, frequency as u64))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<TextQueryInternal> for segment::data_types::text_query::TextQueryInternal {
+    type Error = Status;
+
+    fn try_from(value: TextQueryInternal) -> Result<Self, Self::Error> {
+        let TextQueryInternal { query, statistics } = value;
+        let query = query.ok_or_else(|| Status::invalid_argument("text query is missing"))?;
+        Ok(Self {
+            query: query.try_into()?,
+            statistics: statistics.map(Into::into),
+        })
+    }
+}
+
+impl From<segment::data_types::text_query::TextQueryInternal> for TextQueryInternal {
+    fn from(value: segment::data_types::text_query::TextQueryInternal) -> Self {
+        let segment::data_types::text_query::TextQueryInternal { query, statistics } = value;
+        Self {
+            query: Some(query.into()),
+            statistics: statistics.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<HybridQuery> for segment::index::hybrid_index::HybridQuery {
+    type Error = Status;
+
+    fn try_from(value: HybridQuery) -> Result<Self, Self::Error> {
+        let HybridQuery {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse,
+            alpha,
+        } = value;
+        let sparse = sparse.ok_or_else(|| Status::invalid_argument("sparse vector is missing"))?;
+        Ok(Self {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse: sparse.into(),
+            alpha,
+        })
+    }
+}
+
+impl From<segment::index::hybrid_index::HybridQuery> for HybridQuery {
+    fn from(value: segment::index::hybrid_index::HybridQuery) -> Self {
+        let segment::index::hybrid_index::HybridQuery {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse,
+            alpha,
+        } = value;
+        Self {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse: Some(sparse.into()),
+            alpha,
+        }
+    }
+}
+
 impl From<segment::data_types::order_by::StartFrom> for StartFrom {
     fn from(value: segment::data_types::order_by::StartFrom) -> Self {
         Self {
@@ -2271,6 +2672,9 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = hnsw_config;
         Self {
             m: m.unwrap_or_default() as usize,
@@ -2280,6 +2684,97 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             on_disk,
             payload_m: payload_m.map(|x| x as usize),
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
+        }
+    }
+}
+
+impl From<VamanaConfig> for segment::types::VamanaConfig {
+    fn from(vamana_config: VamanaConfig) -> Self {
+        let VamanaConfig {
+            max_degree,
+            build_list_size,
+            beam_width,
+            full_scan_threshold,
+            on_disk,
+        } = vamana_config;
+        let default = segment::types::VamanaConfig::default();
+        Self {
+            max_degree: max_degree.map_or(default.max_degree, |x| x as usize),
+            build_list_size: build_list_size.map_or(default.build_list_size, |x| x as usize),
+            beam_width: beam_width.map(|x| x as usize),
+            full_scan_threshold: full_scan_threshold
+                .map_or(default.full_scan_threshold, |x| x as usize),
+            on_disk,
+        }
+    }
+}
+
+impl From<segment::types::VamanaConfig> for VamanaConfig {
+    fn from(vamana_config: segment::types::VamanaConfig) -> Self {
+        let segment::types::VamanaConfig {
+            max_degree,
+            build_list_size,
+            beam_width,
+            full_scan_threshold,
+            on_disk,
+        } = vamana_config;
+        Self {
+            max_degree: Some(max_degree as u64),
+            build_list_size: Some(build_list_size as u64),
+            beam_width: beam_width.map(|x| x as u64),
+            full_scan_threshold: Some(full_scan_threshold as u64),
+            on_disk,
+        }
+    }
+}
+
+impl From<IvfConfig> for segment::types::IvfConfig {
+    fn from(ivf_config: IvfConfig) -> Self {
+        let IvfConfig {
+            num_lists,
+            nprobe,
+            pq,
+            full_scan_threshold,
+            on_disk,
+        } = ivf_config;
+        let default = segment::types::IvfConfig::default();
+        Self {
+            num_lists: num_lists.map(|x| x as usize),
+            nprobe: nprobe.map(|x| x as usize),
+            pq: pq.map(
+                |IvfPqConfig { num_subvectors }| segment::types::IvfPqConfig {
+                    num_subvectors: num_subvectors as usize,
+                },
+            ),
+            full_scan_threshold: full_scan_threshold
+                .map_or(default.full_scan_threshold, |x| x as usize),
+            on_disk,
+        }
+    }
+}
+
+impl From<segment::types::IvfConfig> for IvfConfig {
+    fn from(ivf_config: segment::types::IvfConfig) -> Self {
+        let segment::types::IvfConfig {
+            num_lists,
+            nprobe,
+            pq,
+            full_scan_threshold,
+            on_disk,
+        } = ivf_config;
+        Self {
+            num_lists: num_lists.map(|x| x as u64),
+            nprobe: nprobe.map(|x| x as u64),
+            pq: pq.map(
+                |segment::types::IvfPqConfig { num_subvectors }| IvfPqConfig {
+                    num_subvectors: num_subvectors as u64,
+                },
+            ),
+            full_scan_threshold: Some(full_scan_threshold as u64),
+            on_disk,
         }
     }
 }
@@ -2684,6 +3179,7 @@ impl From<PointsOperationResponseInternal> for PointsOperationResponse {
             usage: Some(Usage {
                 hardware: hardware_usage,
                 inference: inference_usage,
+                search_diagnostics: None,
             }),
         }
     }
@@ -2700,6 +3196,7 @@ impl From<PointsOperationResponse> for PointsOperationResponseInternal {
         let Usage {
             hardware,
             inference,
+            search_diagnostics: _,
         } = usage.unwrap_or_default();
         Self {
             result: result.map(Into::into),
@@ -3286,6 +3783,138 @@ impl From<HardwareUsage> for HardwareData {
     }
 }
 
+impl From<SearchDiagnosticsData> for grpc::SearchDiagnostics {
+    fn from(value: SearchDiagnosticsData) -> Self {
+        let SearchDiagnosticsData {
+            visited_nodes,
+            scored_vectors,
+            hops_per_layer,
+            early_terminations,
+            graph_searches,
+            plain_searches_exact,
+            plain_searches_index_disabled,
+            plain_searches_small_segment,
+            plain_searches_small_cardinality,
+            plain_searches_not_indexed,
+        } = value;
+
+        Self {
+            visited_nodes: visited_nodes as u64,
+            scored_vectors: scored_vectors as u64,
+            hops_per_layer: hops_per_layer.into_iter().map(|hops| hops as u64).collect(),
+            early_terminations: early_terminations as u64,
+            graph_searches: graph_searches as u64,
+            plain_searches_exact: plain_searches_exact as u64,
+            plain_searches_index_disabled: plain_searches_index_disabled as u64,
+            plain_searches_small_segment: plain_searches_small_segment as u64,
+            plain_searches_small_cardinality: plain_searches_small_cardinality as u64,
+            plain_searches_not_indexed: plain_searches_not_indexed as u64,
+        }
+    }
+}
+
+impl From<grpc::SearchDiagnostics> for SearchDiagnosticsData {
+    fn from(value: grpc::SearchDiagnostics) -> Self {
+        let grpc::SearchDiagnostics {
+            visited_nodes,
+            scored_vectors,
+            hops_per_layer,
+            early_terminations,
+            graph_searches,
+            plain_searches_exact,
+            plain_searches_index_disabled,
+            plain_searches_small_segment,
+            plain_searches_small_cardinality,
+            plain_searches_not_indexed,
+        } = value;
+
+        SearchDiagnosticsData {
+            visited_nodes: visited_nodes as usize,
+            scored_vectors: scored_vectors as usize,
+            hops_per_layer: hops_per_layer
+                .into_iter()
+                .map(|hops| hops as usize)
+                .collect(),
+            early_terminations: early_terminations as usize,
+            graph_searches: graph_searches as usize,
+            plain_searches_exact: plain_searches_exact as usize,
+            plain_searches_index_disabled: plain_searches_index_disabled as usize,
+            plain_searches_small_segment: plain_searches_small_segment as usize,
+            plain_searches_small_cardinality: plain_searches_small_cardinality as usize,
+            plain_searches_not_indexed: plain_searches_not_indexed as usize,
+        }
+    }
+}
+
+impl From<StageProfile> for grpc::StageProfileInternal {
+    fn from(value: StageProfile) -> Self {
+        let StageProfile { time, candidates } = value;
+
+        Self {
+            time_ns: time.as_nanos() as u64,
+            candidates: candidates as u64,
+        }
+    }
+}
+
+impl From<grpc::StageProfileInternal> for StageProfile {
+    fn from(value: grpc::StageProfileInternal) -> Self {
+        let grpc::StageProfileInternal {
+            time_ns,
+            candidates,
+        } = value;
+
+        StageProfile {
+            time: std::time::Duration::from_nanos(time_ns),
+            candidates: candidates as usize,
+        }
+    }
+}
+
+impl From<RequestProfileData> for grpc::QueryProfileInternal {
+    fn from(value: RequestProfileData) -> Self {
+        let RequestProfileData {
+            filter_evaluation,
+            graph_traversal,
+            payload_fetch,
+            rescoring,
+            shard_merge,
+        } = value;
+
+        Self {
+            filter_evaluation: Some(filter_evaluation.into()),
+            graph_traversal: Some(graph_traversal.into()),
+            payload_fetch: Some(payload_fetch.into()),
+            rescoring: Some(rescoring.into()),
+            shard_merge: Some(shard_merge.into()),
+        }
+    }
+}
+
+impl From<grpc::QueryProfileInternal> for RequestProfileData {
+    fn from(value: grpc::QueryProfileInternal) -> Self {
+        let grpc::QueryProfileInternal {
+            filter_evaluation,
+            graph_traversal,
+            payload_fetch,
+            rescoring,
+            shard_merge,
+        } = value;
+
+        let stage = |stage: Option<grpc::StageProfileInternal>| {
+            stage.map(StageProfile::from).unwrap_or_default()
+        };
+
+        RequestProfileData {
+            filter_evaluation: stage(filter_evaluation),
+            graph_traversal: stage(graph_traversal),
+            payload_fetch: stage(payload_fetch),
+            rescoring: stage(rescoring),
+            shard_merge: stage(shard_merge),
+        }
+    }
+}
+
 impl From<NaiveFeedbackCoefficients> for grpc::NaiveFeedbackStrategy {
     fn from(value: NaiveFeedbackCoefficients) -> Self {
         let NaiveFeedbackCoefficients { a, b, c } = value;
@@ -3451,3 +4080,24 @@ impl From<Modifier> for grpc::Modifier {
         }
     }
 }
+
+impl From<grpc::Bm25Params> for Bm25Params {
+    fn from(value: grpc::Bm25Params) -> Self {
+        let grpc::Bm25Params { k1, b } = value;
+        let default = Bm25Params::default();
+        Self {
+            k1: k1.map_or(default.k1, OrderedFloat),
+            b: b.map_or(default.b, OrderedFloat),
+        }
+    }
+}
+
+impl From<Bm25Params> for grpc::Bm25Params {
+    fn from(value: Bm25Params) -> Self {
+        let Bm25Params { k1, b } = value;
+        Self {
+            k1: Some(k1.into_inner()),
+            b: Some(b.into_inner()),
+        }
+    }
+}
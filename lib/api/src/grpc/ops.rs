@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::grpc::{HardwareUsage, InferenceUsage, ModelUsage, Usage};
+use crate::grpc::{HardwareUsage, InferenceUsage, ModelUsage, SearchDiagnostics, Usage};
 
 impl HardwareUsage {
     pub fn add(&mut self, other: Self) {
@@ -53,19 +53,29 @@ impl Usage {
         Self {
             hardware,
             inference,
+            search_diagnostics: None,
         }
     }
 
+    pub fn with_search_diagnostics(
+        mut self,
+        search_diagnostics: Option<SearchDiagnostics>,
+    ) -> Self {
+        self.search_diagnostics = search_diagnostics;
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         let Usage {
             hardware,
             inference,
+            search_diagnostics,
         } = self;
 
         let hardware_empty = hardware.as_ref().is_none_or(|h| h.is_empty());
         let inference_empty = inference.as_ref().is_none_or(|i| i.is_empty());
 
-        hardware_empty && inference_empty
+        hardware_empty && inference_empty && search_diagnostics.is_none()
     }
 
     pub fn into_non_empty(self) -> Option<Self> {
@@ -76,6 +86,7 @@ impl Usage {
         Usage {
             hardware,
             inference: None,
+            search_diagnostics: None,
         }
     }
 
@@ -83,6 +94,7 @@ impl Usage {
         Usage {
             hardware: None,
             inference,
+            search_diagnostics: None,
         }
     }
 }
@@ -0,0 +1,334 @@
+//! Signing of requests between peers of a cluster.
+//!
+//! Every internal request carries a timestamp, a random nonce, and HMAC-SHA256 with a key shared
+//! by all peers. Signature covers the timestamp, the nonce, the authority of the receiving peer,
+//! the path and the digest of the body, so a captured request can't be redirected to another peer,
+//! another method, or sent with another payload.
+//!
+//! Receiving peer rejects requests with invalid signature, with timestamp out of the allowed clock
+//! skew, and with nonce it has already seen, so captured requests can't be replayed.
+
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use data_encoding::HEXLOWER;
+use parking_lot::Mutex;
+use ring::{digest, hmac};
+use tonic::Status;
+use tonic::body::BoxBody;
+use tonic::codegen::http::{HeaderMap, HeaderValue, Request};
+use tonic::codegen::{Body, Bytes};
+
+pub const PEER_TIMESTAMP_HEADER: &str = "qdrant-peer-timestamp";
+pub const PEER_NONCE_HEADER: &str = "qdrant-peer-nonce";
+pub const PEER_SIGNATURE_HEADER: &str = "qdrant-peer-signature";
+
+/// Maximum difference between clocks of the peers
+pub const MAX_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PeerSignatureError {
+    #[error("Request is not signed, missing {0} header")]
+    Missing(&'static str),
+    #[error("Invalid {0} header")]
+    Invalid(&'static str),
+    #[error("Request signature does not match")]
+    SignatureMismatch,
+    #[error("Request timestamp is out of allowed clock skew")]
+    Expired,
+    #[error("Request nonce was already used")]
+    Replayed,
+    #[error("Address of this peer is not known yet")]
+    UnknownAuthority,
+}
+
+fn signing_key(secret: &str) -> hmac::Key {
+    hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes())
+}
+
+fn signed_message(timestamp: u64, nonce: &str, authority: &str, path: &str, body: &[u8]) -> String {
+    let body_digest = digest::digest(&digest::SHA256, body);
+    format!(
+        "{timestamp}.{nonce}.{authority}.{path}.{}",
+        HEXLOWER.encode(body_digest.as_ref()),
+    )
+}
+
+/// Read the whole body of a request, so it can be signed or verified
+pub async fn collect_body<B>(mut body: B) -> Result<Vec<u8>, B::Error>
+where
+    B: Body<Data = Bytes> + Unpin,
+{
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        bytes.extend_from_slice(&chunk?);
+    }
+    Ok(bytes)
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Signs outgoing requests to other peers
+#[derive(Clone)]
+pub struct PeerRequestSigner {
+    key: hmac::Key,
+}
+
+impl PeerRequestSigner {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: signing_key(secret),
+        }
+    }
+
+    /// Sign request to the peer with the given `authority`, as it is registered in consensus
+    pub fn sign(&self, headers: &mut HeaderMap, authority: &str, path: &str, body: &[u8]) {
+        let timestamp = unix_timestamp();
+        let nonce = uuid::Uuid::new_v4().simple().to_string();
+        let message = signed_message(timestamp, &nonce, authority, path, body);
+        let signature = hmac::sign(&self.key, message.as_bytes());
+
+        // Timestamp, nonce and hex string are always valid ASCII header values
+        headers.insert(PEER_TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+        headers.insert(PEER_NONCE_HEADER, nonce.parse().unwrap());
+        headers.insert(
+            PEER_SIGNATURE_HEADER,
+            HEXLOWER.encode(signature.as_ref()).parse().unwrap(),
+        );
+    }
+
+    /// Buffer the body of outgoing request and sign it
+    pub async fn sign_request(
+        &self,
+        request: Request<BoxBody>,
+        authority: &str,
+    ) -> Result<Request<BoxBody>, Status> {
+        let (mut parts, body) = request.into_parts();
+        let body = collect_body(body).await?;
+        self.sign(&mut parts.headers, authority, parts.uri.path(), &body);
+
+        let body = tonic::transport::Body::from(body)
+            .map_err(|err| Status::from_error(Box::new(err)))
+            .boxed_unsync();
+        Ok(Request::from_parts(parts, body))
+    }
+}
+
+/// Verifies incoming requests from other peers
+pub struct PeerRequestVerifier {
+    key: hmac::Key,
+    nonces: Mutex<SeenNonces>,
+}
+
+/// Nonces of accepted requests, kept for as long as their timestamps are acceptable.
+///
+/// Nonces are stored in two generations, which are rotated every `2 * MAX_CLOCK_SKEW`, so every
+/// nonce is kept for at least the whole time window, in which its request is accepted.
+struct SeenNonces {
+    current: HashSet<String>,
+    previous: HashSet<String>,
+    rotated_at: u64,
+}
+
+impl SeenNonces {
+    fn insert(&mut self, nonce: &str, now: u64) -> bool {
+        if now.saturating_sub(self.rotated_at) >= 2 * MAX_CLOCK_SKEW.as_secs() {
+            self.previous = std::mem::take(&mut self.current);
+            self.rotated_at = now;
+        }
+
+        if self.previous.contains(nonce) {
+            return false;
+        }
+        self.current.insert(nonce.to_string())
+    }
+}
+
+impl PeerRequestVerifier {
+    pub fn new(secret: &str) -> Self {
+        Self {
+            key: signing_key(secret),
+            nonces: Mutex::new(SeenNonces {
+                current: HashSet::new(),
+                previous: HashSet::new(),
+                rotated_at: unix_timestamp(),
+            }),
+        }
+    }
+
+    /// Verify request received by the peer with the given `authority`, as it is registered in
+    /// consensus
+    pub fn verify(
+        &self,
+        headers: &HeaderMap,
+        authority: &str,
+        path: &str,
+        body: &[u8],
+    ) -> Result<(), PeerSignatureError> {
+        self.verify_at(headers, authority, path, body, unix_timestamp())
+    }
+
+    fn verify_at(
+        &self,
+        headers: &HeaderMap,
+        authority: &str,
+        path: &str,
+        body: &[u8],
+        now: u64,
+    ) -> Result<(), PeerSignatureError> {
+        let header = |name: &'static str| {
+            headers
+                .get(name)
+                .ok_or(PeerSignatureError::Missing(name))?
+                .to_str()
+                .map_err(|_| PeerSignatureError::Invalid(name))
+        };
+
+        let timestamp: u64 = header(PEER_TIMESTAMP_HEADER)?
+            .parse()
+            .map_err(|_| PeerSignatureError::Invalid(PEER_TIMESTAMP_HEADER))?;
+        let nonce = header(PEER_NONCE_HEADER)?;
+        let signature = HEXLOWER
+            .decode(header(PEER_SIGNATURE_HEADER)?.as_bytes())
+            .map_err(|_| PeerSignatureError::Invalid(PEER_SIGNATURE_HEADER))?;
+
+        hmac::verify(
+            &self.key,
+            signed_message(timestamp, nonce, authority, path, body).as_bytes(),
+            &signature,
+        )
+        .map_err(|_| PeerSignatureError::SignatureMismatch)?;
+
+        if timestamp.abs_diff(now) > MAX_CLOCK_SKEW.as_secs() {
+            return Err(PeerSignatureError::Expired);
+        }
+
+        if !self.nonces.lock().insert(nonce, now) {
+            return Err(PeerSignatureError::Replayed);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER_A: &str = "peer-a:6335";
+    const PEER_B: &str = "peer-b:6335";
+    const PATH: &str = "/qdrant.PointsInternal/Upsert";
+    const BODY: &[u8] = b"points";
+
+    fn signed_headers(signer: &PeerRequestSigner, authority: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        signer.sign(&mut headers, authority, PATH, BODY);
+        headers
+    }
+
+    #[test]
+    fn test_peer_request_signature() {
+        let signer = PeerRequestSigner::new("cluster_secret");
+        let verifier = PeerRequestVerifier::new("cluster_secret");
+        let now = unix_timestamp();
+
+        let headers = signed_headers(&signer, PEER_A);
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, PATH, BODY, now),
+            Ok(())
+        );
+
+        // Same request can't be replayed
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, PATH, BODY, now),
+            Err(PeerSignatureError::Replayed),
+        );
+        assert_eq!(
+            verifier.verify_at(
+                &headers,
+                PEER_A,
+                PATH,
+                BODY,
+                now + 2 * MAX_CLOCK_SKEW.as_secs(),
+            ),
+            Err(PeerSignatureError::Expired),
+        );
+
+        // Request from the past is rejected
+        let headers = signed_headers(&signer, PEER_A);
+        assert_eq!(
+            verifier.verify_at(
+                &headers,
+                PEER_A,
+                PATH,
+                BODY,
+                now + MAX_CLOCK_SKEW.as_secs() + 10,
+            ),
+            Err(PeerSignatureError::Expired),
+        );
+
+        // Request signed with another key is rejected
+        let headers = signed_headers(&PeerRequestSigner::new("other_secret"), PEER_A);
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, PATH, BODY, now),
+            Err(PeerSignatureError::SignatureMismatch),
+        );
+
+        // Timestamp can't be changed without the key
+        let mut headers = signed_headers(&signer, PEER_A);
+        headers.insert(PEER_TIMESTAMP_HEADER, (now - 1).into());
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, PATH, BODY, now),
+            Err(PeerSignatureError::SignatureMismatch),
+        );
+
+        assert_eq!(
+            verifier.verify_at(&HeaderMap::new(), PEER_A, PATH, BODY, now),
+            Err(PeerSignatureError::Missing(PEER_TIMESTAMP_HEADER)),
+        );
+    }
+
+    #[test]
+    fn test_request_signed_for_another_peer() {
+        let signer = PeerRequestSigner::new("cluster_secret");
+        let verifier_b = PeerRequestVerifier::new("cluster_secret");
+        let now = unix_timestamp();
+
+        // Request signed for peer A can't be sent to peer B
+        let headers = signed_headers(&signer, PEER_A);
+        assert_eq!(
+            verifier_b.verify_at(&headers, PEER_B, PATH, BODY, now),
+            Err(PeerSignatureError::SignatureMismatch),
+        );
+
+        let headers = signed_headers(&signer, PEER_B);
+        assert_eq!(
+            verifier_b.verify_at(&headers, PEER_B, PATH, BODY, now),
+            Ok(()),
+        );
+    }
+
+    #[test]
+    fn test_request_with_changed_path_or_body() {
+        let signer = PeerRequestSigner::new("cluster_secret");
+        let verifier = PeerRequestVerifier::new("cluster_secret");
+        let now = unix_timestamp();
+
+        let headers = signed_headers(&signer, PEER_A);
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, "/qdrant.PointsInternal/Delete", BODY, now),
+            Err(PeerSignatureError::SignatureMismatch),
+        );
+
+        let headers = signed_headers(&signer, PEER_A);
+        assert_eq!(
+            verifier.verify_at(&headers, PEER_A, PATH, b"other points", now),
+            Err(PeerSignatureError::SignatureMismatch),
+        );
+    }
+}
@@ -305,7 +305,7 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
-        /// Match phrase text
+        /// Match phrase text, `"quick fox"~2` for proximity match
         #[prost(string, tag = "9")]
         Phrase(::prost::alloc::string::String),
         /// Match any word in the text
@@ -454,6 +454,36 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// Access pattern hint for memory-mapped vector storage.
+    /// If omitted - the default for the storage type will be used
+    #[prost(enumeration = "MmapAdvice", optional, tag = "8")]
+    pub mmap_advice: ::core::option::Option<i32>,
+    /// Range of dimensions of stored vectors, allows to store vectors shorter than `size`.
+    /// If omitted - all vectors must have exactly `size` dimensions
+    #[prost(message, optional, tag = "9")]
+    pub dim_range: ::core::option::Option<DimRange>,
+    /// If true - keep norms of Cosine vectors, so that vectors are retrieved as they were inserted.
+    /// Scoring still uses normalized vectors
+    #[prost(bool, optional, tag = "10")]
+    pub preserve_norm: ::core::option::Option<bool>,
+    /// Default for `rescore` of quantization search params, used by searches on this vector
+    /// which don't specify it explicitly
+    #[prost(bool, optional, tag = "11")]
+    pub quantization_rescore: ::core::option::Option<bool>,
+    /// Configuration of vector Vamana (DiskANN) graph.
+    /// If set - optimized segments are indexed with Vamana instead of HNSW
+    #[prost(message, optional, tag = "12")]
+    #[validate(nested)]
+    pub vamana_config: ::core::option::Option<VamanaConfig>,
+    /// Configuration of vector IVF (inverted file) index.
+    /// If set - optimized segments are indexed with IVF instead of HNSW
+    #[prost(message, optional, tag = "13")]
+    #[validate(nested)]
+    pub ivf_config: ::core::option::Option<IvfConfig>,
+    /// Number of previous versions of every vector to keep, so that points can be retrieved with
+    /// vectors of an older version. If omitted or 0 - no history is kept
+    #[prost(uint64, optional, tag = "14")]
+    pub max_versions: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -473,6 +503,9 @@ pub struct VectorParamsDiff {
     /// If set to false, the vectors will be loaded in RAM.
     #[prost(bool, optional, tag = "3")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Update default for `rescore` of quantization search params. If none - it is left unchanged.
+    #[prost(bool, optional, tag = "4")]
+    pub quantization_rescore: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -540,6 +573,17 @@ pub mod vectors_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bm25Params {
+    /// Term frequency saturation. Higher values let repeated terms contribute more. Default: 1.2
+    #[prost(double, optional, tag = "1")]
+    pub k1: ::core::option::Option<f64>,
+    /// Strength of document length normalization, from 0.0 (none) to 1.0 (full). Default: 0.75
+    #[prost(double, optional, tag = "2")]
+    pub b: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparseVectorParams {
     /// Configuration of sparse index
     #[prost(message, optional, tag = "1")]
@@ -547,6 +591,11 @@ pub struct SparseVectorParams {
     /// If set - apply modifier to the vector values
     #[prost(enumeration = "Modifier", optional, tag = "2")]
     pub modifier: ::core::option::Option<i32>,
+    /// Parameters of BM25 ranking, used with the `Idf` modifier.
+    /// Values of the sparse vectors are treated as term frequencies,
+    /// saturated and normalized by the vector length. If not set - only IDF is applied.
+    #[prost(message, optional, tag = "3")]
+    pub bm25: ::core::option::Option<Bm25Params>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -565,6 +614,23 @@ pub struct MultiVectorConfig {
     /// Comparator for multi-vector search
     #[prost(enumeration = "MultiVectorComparator", tag = "1")]
     pub comparator: i32,
+    /// Maximum number of vectors in a single multivector point. If not set, the number of vectors is not limited.
+    #[prost(uint64, optional, tag = "2")]
+    pub max_vectors_per_point: ::core::option::Option<u64>,
+    /// What to do with multivectors, which have more vectors than `max_vectors_per_point`. Default: Reject
+    #[prost(enumeration = "MultiVectorOverflowPolicy", optional, tag = "3")]
+    pub overflow_policy: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimRange {
+    /// Minimal dimension of stored vectors
+    #[prost(uint64, tag = "1")]
+    pub min: u64,
+    /// Maximal dimension of stored vectors
+    #[prost(uint64, tag = "2")]
+    pub max: u64,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -751,6 +817,84 @@ pub struct HnswConfigDiff {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[prost(bool, optional, tag = "7")]
     pub inline_storage: ::core::option::Option<bool>,
+    /// Store HNSW graph links with varint delta encoding of neighbor ids. Default: false.
+    /// Reduces disk and page cache footprint of the graph, at the cost of slower link decoding
+    /// during the search.
+    /// Not applied if `inline_storage` is enabled.
+    #[prost(bool, optional, tag = "8")]
+    pub varint_links: ::core::option::Option<bool>,
+    /// Use ACORN predicate-aware traversal for filtered searches, instead of building additional
+    /// payload-aware links. Default: false.
+    /// If enabled, `payload_m` links are not built, and filtered searches evaluate filters on
+    /// 2-hop neighbors, unless ACORN is disabled in search params.
+    #[prost(bool, optional, tag = "9")]
+    pub acorn: ::core::option::Option<bool>,
+    /// Seed of the random number generator, used to build the graph. Default: not set.
+    /// If set, level assignment and linking of points are deterministic for the same input,
+    /// so that builds can be reproduced. Points are linked in a single thread then.
+    #[prost(uint64, optional, tag = "10")]
+    pub seed: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VamanaConfig {
+    /// Maximal number of edges per node in the index graph. Default: 64
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(range(min = 4))]
+    pub max_degree: ::core::option::Option<u64>,
+    /// Size of the candidate list during the index building. Default: 100
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(range(min = 4))]
+    pub build_list_size: ::core::option::Option<u64>,
+    /// Number of candidates expanded at once during the search. Default: 4
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(range(min = 1))]
+    pub beam_width: ::core::option::Option<u64>,
+    /// Minimal size threshold (in KiloBytes) below which full-scan is preferred over graph search.
+    /// Default: 10000
+    #[prost(uint64, optional, tag = "4")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+    /// Store the graph on disk. If set to false, the graph will be loaded into RAM. Default: true
+    #[prost(bool, optional, tag = "5")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IvfConfig {
+    /// Number of inverted lists, i.e. k-means centroids vectors are clustered around.
+    /// Default: 4 * sqrt(number of vectors)
+    #[prost(uint64, optional, tag = "1")]
+    #[validate(range(min = 1))]
+    pub num_lists: ::core::option::Option<u64>,
+    /// Number of inverted lists scanned during the search, if not set in search params. Default: 8
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(range(min = 1))]
+    pub nprobe: ::core::option::Option<u64>,
+    /// If set - vectors in inverted lists are encoded with product quantization
+    #[prost(message, optional, tag = "3")]
+    #[validate(nested)]
+    pub pq: ::core::option::Option<IvfPqConfig>,
+    /// Minimal size threshold (in KiloBytes) below which full-scan is preferred over IVF search.
+    /// Default: 10000
+    #[prost(uint64, optional, tag = "4")]
+    pub full_scan_threshold: ::core::option::Option<u64>,
+    /// Store inverted lists on disk. If set to false, lists will be loaded into RAM. Default: false
+    #[prost(bool, optional, tag = "5")]
+    pub on_disk: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IvfPqConfig {
+    /// Number of sub-vectors each vector is split into, each sub-vector is encoded with 1 byte
+    #[prost(uint64, tag = "1")]
+    #[validate(range(min = 1))]
+    pub num_subvectors: u64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -862,6 +1006,20 @@ pub struct OptimizersConfigDiff {
     /// Default is disabled.
     #[prost(bool, optional, tag = "10")]
     pub prevent_unoptimized: ::core::option::Option<bool>,
+    /// Keep segments, replaced by optimizations, for this number of seconds.
+    /// Retained segments allow to run queries pinned to a past timestamp or operation number.
+    /// Retained segments take additional disk space.
+    /// Default is disabled.
+    #[prost(uint64, optional, tag = "11")]
+    pub segment_retention_sec: ::core::option::Option<u64>,
+    /// The minimal fraction of vectors deleted from the HNSW graph of a segment since it was built,
+    /// required to reroute graph links around deleted points in place.
+    /// Healing runs while there is nothing to optimize, and keeps search quality of segments
+    /// until they are vacuumed. Should be lower than `deleted_threshold`.
+    /// Default is disabled.
+    #[prost(double, optional, tag = "12")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub graph_heal_threshold: ::core::option::Option<f64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -878,6 +1036,9 @@ pub struct ScalarQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "3")]
     pub always_ram: ::core::option::Option<bool>,
+    /// If true - original vectors of non-appendable segments are dropped once quantized vectors are built
+    #[prost(bool, optional, tag = "4")]
+    pub drop_originals: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -890,6 +1051,9 @@ pub struct ProductQuantization {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[prost(bool, optional, tag = "2")]
     pub always_ram: ::core::option::Option<bool>,
+    /// If true - original vectors of non-appendable segments are dropped once quantized vectors are built
+    #[prost(bool, optional, tag = "3")]
+    pub drop_originals: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -918,6 +1082,7 @@ pub mod binary_quantization_query_encoding {
         Binary = 1,
         Scalar4Bits = 2,
         Scalar8Bits = 3,
+        Float32 = 4,
     }
     impl Setting {
         /// String value of the enum field names used in the ProtoBuf definition.
@@ -930,6 +1095,7 @@ pub mod binary_quantization_query_encoding {
                 Setting::Binary => "Binary",
                 Setting::Scalar4Bits => "Scalar4Bits",
                 Setting::Scalar8Bits => "Scalar8Bits",
+                Setting::Float32 => "Float32",
             }
         }
         /// Creates an enum from field names used in the ProtoBuf definition.
@@ -939,6 +1105,7 @@ pub mod binary_quantization_query_encoding {
                 "Binary" => Some(Self::Binary),
                 "Scalar4Bits" => Some(Self::Scalar4Bits),
                 "Scalar8Bits" => Some(Self::Scalar8Bits),
+                "Float32" => Some(Self::Float32),
                 _ => None,
             }
         }
@@ -972,8 +1139,40 @@ pub struct BinaryQuantization {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResidualQuantization {
+    /// Total compression ratio, shared between all stages
+    #[prost(enumeration = "CompressionRatio", tag = "1")]
+    pub compression: i32,
+    /// Number of quantization stages, each of them encodes the error left by previous ones
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1, max = 8))]
+    pub stages: u64,
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[prost(bool, optional, tag = "3")]
+    pub always_ram: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AnisotropicQuantization {
+    /// Compression ratio, the same as for product quantization
+    #[prost(enumeration = "CompressionRatio", tag = "1")]
+    pub compression: i32,
+    /// Weight of quantization error parallel to the original vector, relative to the orthogonal error. Only applies to dot product and cosine distances. Default: 4.0
+    #[prost(float, optional, tag = "2")]
+    #[validate(range(min = 1.0, max = 100.0))]
+    pub parallel_weight: ::core::option::Option<f32>,
+    /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
+    #[prost(bool, optional, tag = "3")]
+    pub always_ram: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantizationConfig {
-    #[prost(oneof = "quantization_config::Quantization", tags = "1, 2, 3")]
+    #[prost(oneof = "quantization_config::Quantization", tags = "1, 2, 3, 4, 5")]
     #[validate(nested)]
     pub quantization: ::core::option::Option<quantization_config::Quantization>,
 }
@@ -989,6 +1188,10 @@ pub mod quantization_config {
         Product(super::ProductQuantization),
         #[prost(message, tag = "3")]
         Binary(super::BinaryQuantization),
+        #[prost(message, tag = "4")]
+        Residual(super::ResidualQuantization),
+        #[prost(message, tag = "5")]
+        Anisotropic(super::AnisotropicQuantization),
     }
 }
 #[derive(validator::Validate)]
@@ -1001,7 +1204,7 @@ pub struct Disabled {}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct QuantizationConfigDiff {
-    #[prost(oneof = "quantization_config_diff::Quantization", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "quantization_config_diff::Quantization", tags = "1, 2, 3, 4, 5, 6")]
     #[validate(nested)]
     pub quantization: ::core::option::Option<quantization_config_diff::Quantization>,
 }
@@ -1019,6 +1222,10 @@ pub mod quantization_config_diff {
         Disabled(super::Disabled),
         #[prost(message, tag = "4")]
         Binary(super::BinaryQuantization),
+        #[prost(message, tag = "5")]
+        Residual(super::ResidualQuantization),
+        #[prost(message, tag = "6")]
+        Anisotropic(super::AnisotropicQuantization),
     }
 }
 #[derive(validator::Validate)]
@@ -1254,6 +1461,10 @@ pub struct UpdateCollection {
     /// already stored metadata
     #[prost(map = "string, message", tag = "10")]
     pub metadata: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// If true - write operations on points of the collection are rejected,
+    /// until it is set to false
+    #[prost(bool, optional, tag = "11")]
+    pub read_only: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -1366,6 +1577,9 @@ pub struct CollectionConfig {
     /// Arbitrary JSON metadata for the collection
     #[prost(map = "string, message", tag = "7")]
     pub metadata: ::std::collections::HashMap<::prost::alloc::string::String, Value>,
+    /// If true - write operations on points of the collection are rejected
+    #[prost(bool, tag = "8")]
+    pub read_only: bool,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1406,6 +1620,12 @@ pub struct IntegerIndexParams {
     /// Default: true.
     #[prost(bool, optional, tag = "5")]
     pub enable_hnsw: ::core::option::Option<bool>,
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[prost(bool, optional, tag = "6")]
+    pub enable_hnsw_ranges: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1423,6 +1643,12 @@ pub struct FloatIndexParams {
     /// Default: true.
     #[prost(bool, optional, tag = "3")]
     pub enable_hnsw: ::core::option::Option<bool>,
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[prost(bool, optional, tag = "4")]
+    pub enable_hnsw_ranges: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1511,6 +1737,10 @@ pub struct SnowballParams {
     /// Which language the algorithm should stem.
     #[prost(string, tag = "1")]
     pub language: ::prost::alloc::string::String,
+    /// Languages to stem with besides `language`. Each token is stemmed with a single
+    /// language, the one which produces the shortest stem.
+    #[prost(string, repeated, tag = "2")]
+    pub additional_languages: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1541,6 +1771,12 @@ pub struct DatetimeIndexParams {
     /// Default: true.
     #[prost(bool, optional, tag = "3")]
     pub enable_hnsw: ::core::option::Option<bool>,
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[prost(bool, optional, tag = "4")]
+    pub enable_hnsw_ranges: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -2129,6 +2365,10 @@ pub enum Datatype {
     Float32 = 1,
     Uint8 = 2,
     Float16 = 3,
+    Float64 = 4,
+    Int8 = 5,
+    Bfloat16 = 6,
+    Int4 = 7,
 }
 impl Datatype {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2141,6 +2381,10 @@ impl Datatype {
             Datatype::Float32 => "Float32",
             Datatype::Uint8 => "Uint8",
             Datatype::Float16 => "Float16",
+            Datatype::Float64 => "Float64",
+            Datatype::Int8 => "Int8",
+            Datatype::Bfloat16 => "Bfloat16",
+            Datatype::Int4 => "Int4",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2150,6 +2394,50 @@ impl Datatype {
             "Float32" => Some(Self::Float32),
             "Uint8" => Some(Self::Uint8),
             "Float16" => Some(Self::Float16),
+            "Float64" => Some(Self::Float64),
+            "Int8" => Some(Self::Int8),
+            "Bfloat16" => Some(Self::Bfloat16),
+            "Int4" => Some(Self::Int4),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MmapAdvice {
+    UnknownMmapAdvice = 0,
+    /// No special treatment, moderate readahead
+    MadvNormal = 1,
+    /// Expect page references in random order, no readahead
+    MadvRandom = 2,
+    /// Expect page references in sequential order, aggressive readahead
+    MadvSequential = 3,
+    /// Expect access in the near future, start reading pages ahead of time
+    MadvWillNeed = 4,
+}
+impl MmapAdvice {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MmapAdvice::UnknownMmapAdvice => "UnknownMmapAdvice",
+            MmapAdvice::MadvNormal => "MadvNormal",
+            MmapAdvice::MadvRandom => "MadvRandom",
+            MmapAdvice::MadvSequential => "MadvSequential",
+            MmapAdvice::MadvWillNeed => "MadvWillNeed",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "UnknownMmapAdvice" => Some(Self::UnknownMmapAdvice),
+            "MadvNormal" => Some(Self::MadvNormal),
+            "MadvRandom" => Some(Self::MadvRandom),
+            "MadvSequential" => Some(Self::MadvSequential),
+            "MadvWillNeed" => Some(Self::MadvWillNeed),
             _ => None,
         }
     }
@@ -2209,6 +2497,39 @@ impl MultiVectorComparator {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum MultiVectorOverflowPolicy {
+    /// Reject multivectors with too many vectors
+    Reject = 0,
+    /// Keep the first `max_vectors_per_point` vectors, drop the rest
+    Truncate = 1,
+    /// Keep the first `max_vectors_per_point - 1` vectors, replace the rest with their average
+    AveragePool = 2,
+}
+impl MultiVectorOverflowPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MultiVectorOverflowPolicy::Reject => "Reject",
+            MultiVectorOverflowPolicy::Truncate => "Truncate",
+            MultiVectorOverflowPolicy::AveragePool => "AveragePool",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Reject" => Some(Self::Reject),
+            "Truncate" => Some(Self::Truncate),
+            "AveragePool" => Some(Self::AveragePool),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum Distance {
     UnknownDistance = 0,
     Cosine = 1,
@@ -2336,6 +2657,7 @@ impl PayloadSchemaType {
 pub enum QuantizationType {
     UnknownQuantization = 0,
     Int8 = 1,
+    Int4 = 2,
 }
 impl QuantizationType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2346,6 +2668,7 @@ impl QuantizationType {
         match self {
             QuantizationType::UnknownQuantization => "UnknownQuantization",
             QuantizationType::Int8 => "Int8",
+            QuantizationType::Int4 => "Int4",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2353,6 +2676,7 @@ impl QuantizationType {
         match value {
             "UnknownQuantization" => Some(Self::UnknownQuantization),
             "Int8" => Some(Self::Int8),
+            "Int4" => Some(Self::Int4),
             _ => None,
         }
     }
@@ -5165,6 +5489,10 @@ pub struct GetPoints {
     /// If set, overrides global timeout setting for this request. Unit is seconds.
     #[prost(uint64, optional, tag = "8")]
     pub timeout: ::core::option::Option<u64>,
+    /// Return vectors as they were after the operation with this version.
+    /// Only available for vectors with `max_versions` configured, payloads are always the latest
+    #[prost(uint64, optional, tag = "9")]
+    pub at_version: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5576,6 +5904,21 @@ pub struct AcornSearchParams {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EarlyTerminationParams {
+    /// Minimal improvement of the total score of the top results, for an expansion to count as
+    /// progress.
+    #[prost(double, tag = "1")]
+    #[validate(range(min = 0.0))]
+    pub epsilon: f64,
+    /// Number of consecutive expansions without progress, after which the search stops.
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1))]
+    pub patience: u64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchParams {
     /// Params relevant to HNSW index. Size of the beam in a beam-search.
     /// Larger the value - more accurate the result, more time required for search.
@@ -5597,6 +5940,29 @@ pub struct SearchParams {
     #[prost(message, optional, tag = "5")]
     #[validate(nested)]
     pub acorn: ::core::option::Option<AcornSearchParams>,
+    /// Desired recall of the approximate search, from 0.0 to 1.0.
+    /// If set, the size of the beam is chosen for each segment from its recall calibration,
+    /// measured while building the index, and `hnsw_ef` is ignored.
+    /// Segments without calibration use `hnsw_ef`.
+    #[prost(double, optional, tag = "6")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: ::core::option::Option<f64>,
+    /// Params relevant to IVF index. Number of inverted lists scanned during the search.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(range(min = 1))]
+    pub nprobe: ::core::option::Option<u64>,
+    /// Params relevant to HNSW index. Stop the search once the top results converge,
+    /// instead of exhausting the beam.
+    #[prost(message, optional, tag = "8")]
+    #[validate(nested)]
+    pub early_termination: ::core::option::Option<EarlyTerminationParams>,
+    /// Params relevant to sparse index. Max length of posting lists, which are traversed in full.
+    /// Longer posting lists are only used to complete scores of candidates found in the shorter ones.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(range(min = 1))]
+    pub sparse_max_posting_length: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6509,12 +6875,49 @@ pub struct Rrf {
     #[prost(float, repeated, tag = "2")]
     pub weights: ::prost::alloc::vec::Vec<f32>,
 }
+/// Score points by BM25 relevance of a full-text indexed payload field
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQuery {
+    /// Payload key of the field, must have a full-text index
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Text to look for, tokenized the same way as the indexed field
+    #[prost(string, tag = "2")]
+    pub text: ::prost::alloc::string::String,
+}
+/// Score points by a dense and a sparse vector at once, fused in a single traversal
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HybridQuery {
+    /// Name of the dense vector, its index is used for the traversal
+    #[prost(string, tag = "1")]
+    pub dense_using: ::prost::alloc::string::String,
+    /// Dense part of the query
+    #[prost(float, repeated, tag = "2")]
+    #[validate(length(min = 1))]
+    pub dense: ::prost::alloc::vec::Vec<f32>,
+    /// Name of the sparse vector
+    #[prost(string, tag = "3")]
+    pub sparse_using: ::prost::alloc::string::String,
+    /// Sparse part of the query
+    #[prost(message, optional, tag = "4")]
+    #[validate(nested)]
+    pub sparse: ::core::option::Option<SparseVector>,
+    /// Weight of the dense score, from 0.0 (sparse only) to 1.0 (dense only)
+    #[prost(float, tag = "5")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub alpha: f32,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Query {
-    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11")]
+    #[prost(oneof = "query::Variant", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13")]
     #[validate(nested)]
     pub variant: ::core::option::Option<query::Variant>,
 }
@@ -6557,6 +6960,12 @@ pub mod query {
         /// Search with feedback from some oracle.
         #[prost(message, tag = "11")]
         RelevanceFeedback(super::RelevanceFeedbackInput),
+        /// Score points by BM25 relevance of a full-text indexed payload field.
+        #[prost(message, tag = "12")]
+        Text(super::TextQuery),
+        /// Score points by a dense and a sparse vector at once.
+        #[prost(message, tag = "13")]
+        Hybrid(super::HybridQuery),
     }
 }
 #[derive(validator::Validate)]
@@ -6597,6 +7006,11 @@ pub struct PrefetchQuery {
     /// If not specified - use the current collection and the 'using' vector.
     #[prost(message, optional, tag = "8")]
     pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Quantization search params for this prefetch.
+    /// If specified, overrides `params.quantization`.
+    #[prost(message, optional, tag = "9")]
+    #[validate(nested)]
+    pub quantization: ::core::option::Option<QuantizationSearchParams>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6662,6 +7076,9 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, report statistics of vector index searches in the response usage.
+    #[prost(bool, optional, tag = "16")]
+    pub with_search_diagnostics: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6684,6 +7101,9 @@ pub struct QueryBatchPoints {
     #[prost(uint64, optional, tag = "4")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, report statistics of vector index searches in the response usage.
+    #[prost(bool, optional, tag = "5")]
+    pub with_search_diagnostics: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -6758,6 +7178,9 @@ pub struct QueryPointGroups {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "17")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If true, report statistics of vector index searches in the response usage.
+    #[prost(bool, optional, tag = "18")]
+    pub with_search_diagnostics: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -7090,6 +7513,24 @@ pub struct UpdateBatchPoints {
     #[prost(uint64, optional, tag = "5")]
     pub timeout: ::core::option::Option<u64>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointsChangesRequest {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Position of the last processed change, by shard id.
+    /// Changes of these shards are streamed after the given positions,
+    /// changes of other shards are streamed starting from the new ones.
+    #[prost(map = "uint32, uint64", tag = "2")]
+    pub positions: ::std::collections::HashMap<u32, u64>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -7421,6 +7862,44 @@ pub struct UpdateBatchResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointsChange {
+    /// Id of the shard, where the change is committed
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Position of the change in the shard.
+    /// Positions are specific to the peer, which streams the changes.
+    #[prost(uint64, tag = "2")]
+    pub position: u64,
+    #[prost(oneof = "points_change::Operation", tags = "3, 4, 5, 6, 7, 8, 9, 10")]
+    pub operation: ::core::option::Option<points_change::Operation>,
+}
+/// Nested message and enum types in `PointsChange`.
+pub mod points_change {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Operation {
+        #[prost(message, tag = "3")]
+        Upsert(super::UpsertPoints),
+        #[prost(message, tag = "4")]
+        Delete(super::DeletePoints),
+        #[prost(message, tag = "5")]
+        SetPayload(super::SetPayloadPoints),
+        #[prost(message, tag = "6")]
+        OverwritePayload(super::SetPayloadPoints),
+        #[prost(message, tag = "7")]
+        DeletePayload(super::DeletePayloadPoints),
+        #[prost(message, tag = "8")]
+        ClearPayload(super::ClearPayloadPoints),
+        #[prost(message, tag = "9")]
+        UpdateVectors(super::UpdatePointVectors),
+        #[prost(message, tag = "10")]
+        DeleteVectors(super::DeletePointVectors),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct FacetResponse {
     #[prost(message, repeated, tag = "1")]
     pub hits: ::prost::alloc::vec::Vec<FacetHit>,
@@ -7496,6 +7975,9 @@ pub struct PointStruct {
     #[prost(message, optional, tag = "4")]
     #[validate(nested)]
     pub vectors: ::core::option::Option<Vectors>,
+    /// Time to live of the point in seconds. Expired points are deleted automatically
+    #[prost(uint64, optional, tag = "5")]
+    pub ttl: ::core::option::Option<u64>,
 }
 /// ---
 ///
@@ -7508,6 +7990,8 @@ pub struct Usage {
     pub hardware: ::core::option::Option<HardwareUsage>,
     #[prost(message, optional, tag = "2")]
     pub inference: ::core::option::Option<InferenceUsage>,
+    #[prost(message, optional, tag = "3")]
+    pub search_diagnostics: ::core::option::Option<SearchDiagnostics>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -7542,6 +8026,42 @@ pub struct HardwareUsage {
     #[prost(uint64, tag = "7")]
     pub vector_io_write: u64,
 }
+/// Statistics of vector index searches, summed over all segments and shards
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchDiagnostics {
+    /// Number of graph nodes reached through links, including ones rejected by the filter
+    #[prost(uint64, tag = "1")]
+    pub visited_nodes: u64,
+    /// Number of vectors scored during graph traversal
+    #[prost(uint64, tag = "2")]
+    pub scored_vectors: u64,
+    /// Number of nodes whose links were expanded, by graph layer starting from 0
+    #[prost(uint64, repeated, tag = "3")]
+    pub hops_per_layer: ::prost::alloc::vec::Vec<u64>,
+    /// Number of query vectors searched in HNSW graph
+    #[prost(uint64, tag = "4")]
+    pub graph_searches: u64,
+    /// Number of query vectors searched without graph, because exact search was requested
+    #[prost(uint64, tag = "5")]
+    pub plain_searches_exact: u64,
+    /// Number of query vectors searched without graph, because HNSW graph has no links
+    #[prost(uint64, tag = "6")]
+    pub plain_searches_index_disabled: u64,
+    /// Number of query vectors searched without graph, because the segment is small
+    #[prost(uint64, tag = "7")]
+    pub plain_searches_small_segment: u64,
+    /// Number of query vectors searched without graph, because few points match the filter
+    #[prost(uint64, tag = "8")]
+    pub plain_searches_small_cardinality: u64,
+    /// Number of query vectors searched without graph, because the segment is not indexed yet
+    #[prost(uint64, tag = "9")]
+    pub plain_searches_not_indexed: u64,
+    /// Number of graph searches stopped early, because top results converged
+    #[prost(uint64, tag = "10")]
+    pub early_terminations: u64,
+}
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -8641,6 +9161,31 @@ pub mod points_client {
                 .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
             self.inner.unary(req, path, codec).await
         }
+        /// Stream changes of points, committed to the shards of the collection,
+        /// which are located on this peer.
+        /// Stream can be resumed from the last processed positions.
+        pub async fn changes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PointsChangesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::PointsChange>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/qdrant.Points/Changes");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "Changes"));
+            self.inner.server_streaming(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -8889,6 +9434,19 @@ pub mod points_server {
             tonic::Response<super::SearchMatrixOffsetsResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the Changes method.
+        type ChangesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::PointsChange, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Stream changes of points, committed to the shards of the collection,
+        /// which are located on this peer.
+        /// Stream can be resumed from the last processed positions.
+        async fn changes(
+            &self,
+            request: tonic::Request<super::PointsChangesRequest>,
+        ) -> std::result::Result<tonic::Response<Self::ChangesStream>, tonic::Status>;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -10223,6 +10781,53 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/Changes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ChangesSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ServerStreamingService<super::PointsChangesRequest>
+                    for ChangesSvc<T> {
+                        type Response = super::PointsChange;
+                        type ResponseStream = T::ChangesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PointsChangesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::changes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ChangesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -10897,6 +11502,31 @@ pub struct MmrInternal {
     #[prost(uint32, tag = "3")]
     pub candidates_limit: u32,
 }
+/// Statistics of the text query tokens over the indexed documents, used for BM25 scoring
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQueryStatistics {
+    /// Number of indexed documents
+    #[prost(uint64, tag = "1")]
+    pub points_count: u64,
+    /// Total length of the indexed documents, in tokens
+    #[prost(uint64, tag = "2")]
+    pub total_length: u64,
+    /// Number of indexed documents containing each of the query tokens
+    #[prost(map = "string, uint64", tag = "3")]
+    pub doc_frequencies: ::std::collections::HashMap<::prost::alloc::string::String, u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQueryInternal {
+    #[prost(message, optional, tag = "1")]
+    pub query: ::core::option::Option<TextQuery>,
+    /// Statistics collected over the whole collection, if any
+    #[prost(message, optional, tag = "2")]
+    pub statistics: ::core::option::Option<TextQueryStatistics>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -10929,7 +11559,7 @@ pub mod query_shard_points {
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct Query {
-        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5, 6, 7")]
+        #[prost(oneof = "query::Score", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
         pub score: ::core::option::Option<query::Score>,
     }
     /// Nested message and enum types in `Query`.
@@ -10959,6 +11589,12 @@ pub mod query_shard_points {
             /// Parameterized RRF fusion
             #[prost(message, tag = "7")]
             Rrf(super::super::Rrf),
+            /// BM25 relevance of a full-text indexed field
+            #[prost(message, tag = "8")]
+            Text(super::super::TextQueryInternal),
+            /// Fused relevance of a dense and a sparse vector
+            #[prost(message, tag = "9")]
+            Hybrid(super::super::HybridQuery),
         }
     }
     #[derive(serde::Serialize)]
@@ -10999,6 +11635,10 @@ pub struct QueryBatchPointsInternal {
     #[prost(uint64, optional, tag = "4")]
     #[validate(range(min = 1))]
     pub timeout: ::core::option::Option<u64>,
+    #[prost(bool, optional, tag = "5")]
+    pub with_search_diagnostics: ::core::option::Option<bool>,
+    #[prost(bool, optional, tag = "6")]
+    pub with_profile: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -11027,6 +11667,36 @@ pub struct QueryBatchResponseInternal {
     pub hardware_usage: ::core::option::Option<HardwareUsage>,
     #[prost(message, optional, tag = "6")]
     pub inference_usage: ::core::option::Option<InferenceUsage>,
+    #[prost(message, optional, tag = "7")]
+    pub search_diagnostics: ::core::option::Option<SearchDiagnostics>,
+    #[prost(message, optional, tag = "8")]
+    pub profile: ::core::option::Option<QueryProfileInternal>,
+}
+/// Measurements of a single stage of query processing
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StageProfileInternal {
+    #[prost(uint64, tag = "1")]
+    pub time_ns: u64,
+    #[prost(uint64, tag = "2")]
+    pub candidates: u64,
+}
+/// Per-stage timings of a query, collected on a remote shard
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryProfileInternal {
+    #[prost(message, optional, tag = "1")]
+    pub filter_evaluation: ::core::option::Option<StageProfileInternal>,
+    #[prost(message, optional, tag = "2")]
+    pub graph_traversal: ::core::option::Option<StageProfileInternal>,
+    #[prost(message, optional, tag = "3")]
+    pub payload_fetch: ::core::option::Option<StageProfileInternal>,
+    #[prost(message, optional, tag = "4")]
+    pub rescoring: ::core::option::Option<StageProfileInternal>,
+    #[prost(message, optional, tag = "5")]
+    pub shard_merge: ::core::option::Option<StageProfileInternal>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -11097,6 +11767,37 @@ pub struct FacetResponseInternal {
     #[prost(message, optional, tag = "3")]
     pub usage: ::core::option::Option<HardwareUsage>,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQueryStatisticsInternal {
+    #[prost(string, tag = "1")]
+    #[validate(
+        length(min = 1, max = 255),
+        custom(function = "common::validation::validate_collection_name_legacy")
+    )]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub query: ::core::option::Option<TextQuery>,
+    #[prost(uint32, tag = "3")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(range(min = 1))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TextQueryStatisticsResponseInternal {
+    #[prost(message, optional, tag = "1")]
+    pub statistics: ::core::option::Option<TextQueryStatistics>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    #[prost(message, optional, tag = "3")]
+    pub usage: ::core::option::Option<HardwareUsage>,
+}
 /// Controls how an update operation waits for completion.
 /// When present, fully overrides the `wait` boolean from the wrapped public message.
 /// When absent, the `wait` boolean is used (backward compatible with older nodes).
@@ -11683,6 +12384,33 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn text_query_statistics(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TextQueryStatisticsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::TextQueryStatisticsResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/TextQueryStatistics",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.PointsInternal", "TextQueryStatistics"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -11816,6 +12544,13 @@ pub mod points_internal_server {
             tonic::Response<super::FacetResponseInternal>,
             tonic::Status,
         >;
+        async fn text_query_statistics(
+            &self,
+            request: tonic::Request<super::TextQueryStatisticsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::TextQueryStatisticsResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -12780,6 +13515,56 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/TextQueryStatistics" => {
+                    #[allow(non_camel_case_types)]
+                    struct TextQueryStatisticsSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::TextQueryStatisticsInternal>
+                    for TextQueryStatisticsSvc<T> {
+                        type Response = super::TextQueryStatisticsResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TextQueryStatisticsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::text_query_statistics(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TextQueryStatisticsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -1,17 +1,20 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::num::NonZeroUsize;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 use rand::{RngExt, rng};
 use tokio::select;
-use tonic::codegen::InterceptedService;
+use tonic::body::BoxBody;
+use tonic::codegen::{BoxFuture, InterceptedService, Service, StdError, http};
 use tonic::service::Interceptor;
-use tonic::transport::{Channel, ClientTlsConfig, Error as TonicError, Uri};
+use tonic::transport::{Body, Channel, ClientTlsConfig, Error as TonicError, Uri};
 use tonic::{Code, Request, Status};
 
 use crate::grpc::dynamic_channel_pool::DynamicChannelPool;
 use crate::grpc::dynamic_pool::CountedItem;
+use crate::grpc::peer_signature::PeerRequestSigner;
 use crate::grpc::qdrant::HealthCheckRequest;
 use crate::grpc::qdrant::qdrant_client::QdrantClient;
 
@@ -92,6 +95,72 @@ impl Interceptor for AddTimeout {
     }
 }
 
+/// Channel to another peer, which signs requests, if peers of the cluster share a signing key.
+///
+/// Signature covers the body of the request, so the body is buffered before sending.
+#[derive(Clone)]
+pub struct SignedChannel {
+    channel: Channel,
+    /// Authority of the peer, as it is registered in consensus
+    authority: String,
+    request_signer: Option<PeerRequestSigner>,
+}
+
+impl SignedChannel {
+    pub fn new(channel: Channel, uri: &Uri, request_signer: Option<PeerRequestSigner>) -> Self {
+        Self {
+            channel,
+            authority: uri
+                .authority()
+                .map(|authority| authority.to_string())
+                .unwrap_or_default(),
+            request_signer,
+        }
+    }
+}
+
+impl Service<http::Request<BoxBody>> for SignedChannel {
+    type Response = http::Response<Body>;
+    type Error = StdError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.channel.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        // Take the channel, which was polled for readiness, and leave a fresh clone instead
+        let clone = self.channel.clone();
+        let mut channel = std::mem::replace(&mut self.channel, clone);
+
+        let Some(request_signer) = self.request_signer.clone() else {
+            return Box::pin(async move { channel.call(request).await.map_err(Into::into) });
+        };
+        let authority = self.authority.clone();
+
+        Box::pin(async move {
+            let request = request_signer.sign_request(request, &authority).await?;
+            channel.call(request).await.map_err(Into::into)
+        })
+    }
+}
+
+/// Client channel to another peer
+pub type PeerChannel = InterceptedService<SignedChannel, AddTimeout>;
+
+/// Create client channel to the peer at `uri`
+pub fn peer_channel(
+    channel: Channel,
+    uri: &Uri,
+    timeout: Duration,
+    request_signer: Option<PeerRequestSigner>,
+) -> PeerChannel {
+    InterceptedService::new(
+        SignedChannel::new(channel, uri, request_signer),
+        AddTimeout::new(timeout),
+    )
+}
+
 /// Holds a pool of channels established for a set of URIs.
 /// Channel are shared by cloning them.
 /// Make the `pool_size` larger to increase throughput.
@@ -101,6 +170,7 @@ pub struct TransportChannelPool {
     grpc_timeout: Duration,
     connection_timeout: Duration,
     tls_config: Option<ClientTlsConfig>,
+    request_signer: Option<PeerRequestSigner>,
 }
 
 impl Default for TransportChannelPool {
@@ -111,6 +181,7 @@ impl Default for TransportChannelPool {
             grpc_timeout: DEFAULT_GRPC_TIMEOUT,
             connection_timeout: DEFAULT_CONNECT_TIMEOUT,
             tls_config: None,
+            request_signer: None,
         }
     }
 }
@@ -121,6 +192,7 @@ impl TransportChannelPool {
         connection_timeout: Duration,
         pool_size: usize,
         tls_config: Option<ClientTlsConfig>,
+        request_signer: Option<PeerRequestSigner>,
     ) -> Self {
         Self {
             uri_to_pool: Default::default(),
@@ -128,9 +200,19 @@ impl TransportChannelPool {
             connection_timeout,
             pool_size: NonZeroUsize::new(pool_size).unwrap(),
             tls_config,
+            request_signer,
         }
     }
 
+    /// Signer of requests to other peers, if configured
+    pub fn request_signer(&self) -> Option<&PeerRequestSigner> {
+        self.request_signer.as_ref()
+    }
+
+    fn peer_channel(&self, channel: Channel, uri: &Uri, timeout: Duration) -> PeerChannel {
+        peer_channel(channel, uri, timeout, self.request_signer.clone())
+    }
+
     async fn _init_pool_for_uri(&self, uri: Uri) -> Result<DynamicChannelPool, TonicError> {
         DynamicChannelPool::new(
             uri,
@@ -205,7 +287,11 @@ impl TransportChannelPool {
                 None => return HealthCheckError::NoChannel,
                 Some(Err(tonic_error)) => return HealthCheckError::ConnectionError(tonic_error),
                 Some(Ok(channel)) => {
-                    let mut client = QdrantClient::new(channel.item().clone());
+                    let mut client = QdrantClient::new(self.peer_channel(
+                        channel.item().clone(),
+                        uri,
+                        HEALTH_CHECK_TIMEOUT,
+                    ));
 
                     let resp: Result<_, Status> = select! {
                         res = client.health_check(HealthCheckRequest {}) => {
@@ -237,7 +323,7 @@ impl TransportChannelPool {
     async fn make_request<T, O: Future<Output = Result<T, Status>>>(
         &self,
         uri: &Uri,
-        f: &impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
+        f: &impl Fn(PeerChannel) -> O,
         timeout: Duration,
     ) -> Result<T, RequestFailure> {
         let channel = match self.get_or_create_pooled_channel(uri).await {
@@ -247,11 +333,10 @@ impl TransportChannelPool {
             }
         };
 
-        let intercepted_channel =
-            InterceptedService::new(channel.item().clone(), AddTimeout::new(timeout));
+        let peer_channel = self.peer_channel(channel.item().clone(), uri, timeout);
 
         let result: RequestFailure = select! {
-            res = f(intercepted_channel) => {
+            res = f(peer_channel) => {
                 match res {
                     Ok(body) => {
                         channel.report_success();
@@ -283,7 +368,7 @@ impl TransportChannelPool {
     pub async fn with_channel_timeout<T, O: Future<Output = Result<T, Status>>>(
         &self,
         uri: &Uri,
-        f: impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
+        f: impl Fn(PeerChannel) -> O,
         timeout: Option<Duration>,
         retries: usize,
     ) -> Result<T, RequestError<Status>> {
@@ -399,7 +484,7 @@ impl TransportChannelPool {
     pub async fn with_channel<T, O: Future<Output = Result<T, Status>>>(
         &self,
         uri: &Uri,
-        f: impl Fn(InterceptedService<Channel, AddTimeout>) -> O,
+        f: impl Fn(PeerChannel) -> O,
     ) -> Result<T, RequestError<Status>> {
         self.with_channel_timeout(uri, f, None, DEFAULT_RETRIES)
             .await
@@ -81,6 +81,8 @@ impl Validate for grpc::quantization_config::Quantization {
             Quantization::Scalar(scalar) => scalar.validate(),
             Quantization::Product(product) => product.validate(),
             Quantization::Binary(binary) => binary.validate(),
+            Quantization::Residual(residual) => residual.validate(),
+            Quantization::Anisotropic(anisotropic) => anisotropic.validate(),
         }
     }
 }
@@ -92,6 +94,8 @@ impl Validate for grpc::quantization_config_diff::Quantization {
             Quantization::Scalar(scalar) => scalar.validate(),
             Quantization::Product(product) => product.validate(),
             Quantization::Binary(binary) => binary.validate(),
+            Quantization::Residual(residual) => residual.validate(),
+            Quantization::Anisotropic(anisotropic) => anisotropic.validate(),
             Quantization::Disabled(_) => Ok(()),
         }
     }
@@ -368,9 +372,11 @@ impl Validate for super::qdrant::query::Variant {
             grpc::query::Variant::Formula(q) => q.validate(),
             grpc::query::Variant::Rrf(q) => q.validate(),
             grpc::query::Variant::RelevanceFeedback(q) => q.validate(),
+            grpc::query::Variant::Hybrid(q) => q.validate(),
             grpc::query::Variant::Sample(_)
             | grpc::query::Variant::Fusion(_)
-            | grpc::query::Variant::OrderBy(_) => Ok(()),
+            | grpc::query::Variant::OrderBy(_)
+            | grpc::query::Variant::Text(_) => Ok(()),
         }
     }
 }
@@ -507,6 +513,7 @@ impl Validate for super::qdrant::IntegerIndexParams {
             is_principal: _,
             on_disk: _,
             enable_hnsw: _,
+            enable_hnsw_ranges: _,
         } = &self;
         validate_integer_index_params(lookup, range)
     }
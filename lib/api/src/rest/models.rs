@@ -58,6 +58,10 @@ pub struct Usage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hardware: Option<HardwareUsage>,
     pub inference: Option<InferenceUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<QueryProfile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_diagnostics: Option<SearchDiagnostics>,
 }
 
 impl Usage {
@@ -65,9 +69,14 @@ impl Usage {
         let Usage {
             hardware,
             inference,
+            profile,
+            search_diagnostics,
         } = self;
 
-        hardware.is_none() && inference.is_none()
+        hardware.is_none()
+            && inference.is_none()
+            && profile.is_none()
+            && search_diagnostics.is_none()
     }
 }
 
@@ -75,6 +84,60 @@ fn is_usage_none_or_empty(u: &Option<Usage>) -> bool {
     u.as_ref().is_none_or(|usage| usage.is_empty())
 }
 
+/// Time spent in each stage of query processing, collected if `profile` parameter is set
+#[derive(Debug, Default, Serialize, JsonSchema, Anonymize, Clone)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct QueryProfile {
+    /// Cardinality estimation and construction of filter conditions
+    pub filter_evaluation: StageProfile,
+    /// Search in HNSW graph or plain scoring of points
+    pub graph_traversal: StageProfile,
+    /// Reading payload and vectors of the found points
+    pub payload_fetch: StageProfile,
+    /// Rescoring with original vectors or with a formula
+    pub rescoring: StageProfile,
+    /// Merging results from multiple shards
+    pub shard_merge: StageProfile,
+}
+
+#[derive(Debug, Default, Serialize, JsonSchema, Anonymize, Clone)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct StageProfile {
+    /// Time spent in the stage, summed over all segments and shards. Unit is seconds.
+    pub time: f64,
+    /// Number of candidate points produced by the stage
+    pub candidates: usize,
+}
+
+/// Statistics of vector index searches, summed over all segments and shards
+#[derive(Debug, Default, Serialize, JsonSchema, Anonymize, Clone)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct SearchDiagnostics {
+    /// Number of graph nodes reached through links, including ones rejected by the filter
+    pub visited_nodes: usize,
+    /// Number of vectors scored during graph traversal
+    pub scored_vectors: usize,
+    /// Number of nodes whose links were expanded, by graph layer starting from 0
+    pub hops_per_layer: Vec<usize>,
+    /// Number of graph searches stopped early, because top results converged
+    pub early_terminations: usize,
+    /// Number of query vectors searched in HNSW graph
+    pub graph_searches: usize,
+    /// Number of query vectors searched without graph, because exact search was requested
+    pub plain_searches_exact: usize,
+    /// Number of query vectors searched without graph, because HNSW graph has no links
+    pub plain_searches_index_disabled: usize,
+    /// Number of query vectors searched without graph, because the segment is small
+    pub plain_searches_small_segment: usize,
+    /// Number of query vectors searched without graph, because few points match the filter
+    pub plain_searches_small_cardinality: usize,
+    /// Number of query vectors searched without graph, because the segment is not indexed yet
+    pub plain_searches_not_indexed: usize,
+}
+
 /// Usage of the hardware resources, spent to process the request
 #[derive(Debug, Default, Serialize, JsonSchema, Anonymize, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -9,11 +9,13 @@ use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
 use segment::data_types::index::{StemmingAlgorithm, StopwordsInterface, TokenizerType};
 use segment::data_types::order_by::OrderByInterface;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{DenseVector, MultiDenseVector};
+use segment::index::hybrid_index::HybridQuery;
 use segment::json_path::JsonPath;
 use segment::types::{
-    Condition, Filter, GeoPoint, IntPayloadType, Payload, PointIdType, SearchParams, ShardKey,
-    VectorNameBuf, WithPayloadInterface, WithVector,
+    Condition, Filter, GeoPoint, IntPayloadType, Payload, PointIdType, QuantizationSearchParams,
+    SearchParams, ShardKey, VectorNameBuf, WithPayloadInterface, WithVector,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -649,6 +651,12 @@ pub enum Query {
     /// Sample points from the collection, non-deterministically.
     Sample(SampleQuery),
 
+    /// Score points by BM25 relevance of a full-text indexed payload field.
+    Text(TextRelevanceQuery),
+
+    /// Score points by a dense and a sparse vector at once, fusing their scores.
+    Hybrid(HybridRelevanceQuery),
+
     /// Use feedback from an oracle to improve the results
     RelevanceFeedback(RelevanceFeedbackQuery),
 }
@@ -723,6 +731,20 @@ pub struct SampleQuery {
     pub sample: Sample,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct TextRelevanceQuery {
+    #[validate(nested)]
+    pub text: TextQuery,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridRelevanceQuery {
+    #[validate(nested)]
+    pub hybrid: HybridQuery,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct RelevanceFeedbackQuery {
@@ -775,6 +797,10 @@ pub struct Prefetch {
     #[validate(nested)]
     pub params: Option<SearchParams>,
 
+    /// Quantization search params for this prefetch. If specified, overrides `params.quantization`.
+    #[validate(nested)]
+    pub quantization: Option<QuantizationSearchParams>,
+
     /// Return points with scores better than this threshold.
     pub score_threshold: Option<ScoreType>,
 
@@ -1392,6 +1418,9 @@ pub struct PointStruct {
     pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// Time to live of the point in seconds. Expired points are deleted automatically (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u64>,
 }
 
 /// Defines the mode of the upsert operation
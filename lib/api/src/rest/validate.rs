@@ -42,6 +42,8 @@ impl Validate for Query {
             Query::Formula(formula) => formula.validate(),
             Query::OrderBy(order_by) => order_by.validate(),
             Query::Sample(sample) => sample.validate(),
+            Query::Text(text) => text.validate(),
+            Query::Hybrid(hybrid) => hybrid.validate(),
             Query::RelevanceFeedback(feedback) => feedback.validate(),
         }
     }
@@ -64,6 +64,8 @@ fn setup() -> (TempDir, LocalShard, Runtime) {
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         },
         wal_config,
         hnsw_config: Default::default(),
@@ -71,6 +73,7 @@ fn setup() -> (TempDir, LocalShard, Runtime) {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -125,6 +128,7 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: (i as u64).into(),
             vector: VectorStructInternal::from(vectors).into(),
             payload: Some(Payload(payload_map)),
+            expires_at: None,
         };
         points.push(point);
     }
@@ -42,6 +42,7 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: (i as u64).into(),
             vector: VectorStructInternal::from(vectors).into(),
             payload: Some(Payload(payload_map)),
+            expires_at: None,
         };
         points.push(point);
     }
@@ -82,6 +83,8 @@ fn batch_search_bench(c: &mut Criterion) {
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         },
         wal_config,
         hnsw_config: Default::default(),
@@ -89,6 +92,7 @@ fn batch_search_bench(c: &mut Criterion) {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
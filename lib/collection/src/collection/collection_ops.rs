@@ -1,13 +1,21 @@
 use std::cmp;
+use std::collections::HashMap;
 use std::sync::{Arc, LazyLock};
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::disk::dir_disk_size;
 use common::types::DeferredBehavior;
 use futures::{TryStreamExt as _, future};
-use segment::types::{Payload, QuantizationConfig, StrictModeConfig};
+use segment::types::{
+    Payload, PointIdType, QuantizationConfig, QuantizationQualityStats, QuantizedVectorData,
+    SegmentDiskUsage, SegmentMemoryUsage, StrictModeConfig, VectorNameBuf,
+};
+use segment::utils::mem::MappedFilesResidency;
 use semver::Version;
 use shard::count::CountRequestInternal;
 use shard::operations::optimization::{OptimizationsRequestOptions, OptimizationsResponse};
+use tokio::task::spawn_blocking;
+use tokio_util::task::AbortOnDropHandle;
 
 use super::Collection;
 use crate::operations::config_diff::*;
@@ -158,6 +166,16 @@ impl Collection {
                         .quantization_config
                         .replace(QuantizationConfig::Binary(binary));
                 }
+                QuantizationConfigDiff::Residual(residual) => {
+                    config
+                        .quantization_config
+                        .replace(QuantizationConfig::Residual(residual));
+                }
+                QuantizationConfigDiff::Anisotropic(anisotropic) => {
+                    config
+                        .quantization_config
+                        .replace(QuantizationConfig::Anisotropic(anisotropic));
+                }
                 QuantizationConfigDiff::Disabled(_) => {
                     config.quantization_config = None;
                 }
@@ -183,6 +201,13 @@ impl Collection {
         Ok(())
     }
 
+    /// Switch read-only mode, in which write operations from clients are rejected, and save it to disk.
+    pub async fn set_read_only(&self, read_only: bool) -> CollectionResult<()> {
+        self.collection_config.write().await.read_only = read_only;
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates the strict mode configuration and saves it to disk.
     pub async fn update_strict_mode_config(
         &self,
@@ -457,6 +482,130 @@ impl Collection {
         Ok(info)
     }
 
+    /// Estimate RAM used by local shards of the collection, broken down by component.
+    pub async fn memory_usage(&self) -> CollectionResult<CollectionMemoryUsage> {
+        let residency = AbortOnDropHandle::new(spawn_blocking(MappedFilesResidency::load)).await?;
+        let residency = Arc::new(residency);
+
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut usage = SegmentMemoryUsage::default();
+        for shard in shards_holder.all_shards() {
+            if let Some(shard_usage) = shard
+                .calculate_local_memory_usage(residency.clone())
+                .await?
+            {
+                usage += shard_usage;
+            }
+        }
+
+        Ok(CollectionMemoryUsage::from(usage))
+    }
+
+    /// Calculate disk space taken by the collection on this peer, broken down by component.
+    pub async fn disk_usage(&self) -> CollectionResult<CollectionDiskUsage> {
+        let mut segments = SegmentDiskUsage::default();
+        let mut wal_bytes = 0;
+
+        {
+            let shards_holder = self.shards_holder.read().await;
+            for shard in shards_holder.all_shards() {
+                if let Some((shard_segments, shard_wal_bytes)) =
+                    shard.calculate_local_disk_usage().await?
+                {
+                    segments += shard_segments;
+                    wal_bytes += shard_wal_bytes;
+                }
+            }
+        }
+
+        // Snapshots may be kept in a remote storage, only count local ones
+        let snapshots_path = self.snapshots_path.clone();
+        let snapshots_bytes = AbortOnDropHandle::new(spawn_blocking(move || {
+            if snapshots_path.exists() {
+                dir_disk_size(snapshots_path)
+            } else {
+                Ok(0)
+            }
+        }))
+        .await??;
+
+        Ok(CollectionDiskUsage::new(
+            segments,
+            wal_bytes,
+            snapshots_bytes,
+        ))
+    }
+
+    /// Retrain quantization of local shards of the collection on current data.
+    ///
+    /// Quantized vectors are rebuilt in the background, segments are searchable meanwhile.
+    /// Returns the number of local shards, in which retraining was started.
+    pub async fn requantize(&self) -> usize {
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut started = 0;
+        for shard in shards_holder.all_shards() {
+            if shard.requantize_local().await {
+                started += 1;
+            }
+        }
+        started
+    }
+
+    /// Estimate quality of quantized vectors in local shards of the collection.
+    ///
+    /// Up to `sample_size` points of each segment are used as queries, top `limit` results of
+    /// search with quantized vectors are compared with exact ones.
+    pub async fn quantization_quality(
+        &self,
+        sample_size: usize,
+        limit: usize,
+    ) -> CollectionResult<CollectionQuantizationQuality> {
+        let mut stats: HashMap<VectorNameBuf, QuantizationQualityStats> = HashMap::new();
+
+        {
+            let shards_holder = self.shards_holder.read().await;
+            for shard in shards_holder.all_shards() {
+                let Some(shard_stats) = shard
+                    .estimate_local_quantization_quality(sample_size, limit)
+                    .await?
+                else {
+                    continue;
+                };
+                for (vector_name, vector_stats) in shard_stats {
+                    *stats.entry(vector_name).or_default() += vector_stats;
+                }
+            }
+        }
+
+        let vectors = stats
+            .into_iter()
+            .map(|(vector_name, vector_stats)| (vector_name, vector_stats.into()))
+            .collect();
+        Ok(CollectionQuantizationQuality { vectors })
+    }
+
+    /// Quantized representation of vector `vector_name` of point `point_id`, stored on this peer.
+    pub async fn quantized_vector(
+        &self,
+        vector_name: VectorNameBuf,
+        point_id: PointIdType,
+    ) -> CollectionResult<QuantizedVectorData> {
+        let shards_holder = self.shards_holder.read().await;
+        for shard in shards_holder.all_shards() {
+            if let Some(quantized) = shard
+                .local_quantized_vector(vector_name.clone(), point_id)
+                .await?
+            {
+                return Ok(quantized);
+            }
+        }
+        Err(CollectionError::PointNotFound {
+            missed_point_id: point_id,
+        })
+    }
+
     pub async fn optimizations(
         &self,
         options: OptimizationsRequestOptions,
@@ -6,6 +6,7 @@ pub mod mmr;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
+pub mod recall_estimation;
 mod resharding;
 mod search;
 mod shard_transfer;
@@ -13,6 +14,7 @@ mod sharding_keys;
 mod snapshots;
 mod state_management;
 mod telemetry;
+mod text_query;
 
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -20,11 +22,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
+use api::grpc::qdrant::PointsChange;
 use clean::ShardCleanTasks;
 use common::budget::ResourceBudget;
 use common::save_on_disk::SaveOnDisk;
 use common::storage_version::StorageVersion;
-use segment::types::{SeqNumberType, ShardKey};
+use segment::types::{SeqNumberType, ShardKey, VectorNameBuf};
 use semver::Version;
 use shard::operations::optimization::{OptimizationsRequestOptions, OptimizationsResponse};
 use tokio::runtime::Handle;
@@ -32,6 +35,7 @@ use tokio::sync::{Mutex, RwLock};
 
 use crate::collection::collection_ops::ABORT_TRANSFERS_ON_SHARD_DROP_FIX_FROM_VERSION;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::recall_estimation::RecallEstimate;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::collection_size_stats::{
     CollectionSizeAtomicStats, CollectionSizeStats, CollectionSizeStatsCache,
@@ -45,6 +49,7 @@ use crate::operations::types::{CollectionError, CollectionResult, NodeType, Opti
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::collection_shard_distribution::CollectionShardDistribution;
+use crate::shards::conversions::points_change_from_operation;
 use crate::shards::local_shard::clock_map::RecoveryPoint;
 use crate::shards::replica_set::replica_set_state::ReplicaState;
 use crate::shards::replica_set::replica_set_state::ReplicaState::{
@@ -89,6 +94,8 @@ pub struct Collection {
     collection_stats_cache: CollectionSizeStatsCache,
     // Background tasks to clean shards
     shard_clean_tasks: ShardCleanTasks,
+    // Latest results of recall estimation, by vector name
+    recall_estimates: parking_lot::Mutex<HashMap<VectorNameBuf, RecallEstimate>>,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -196,6 +203,7 @@ impl Collection {
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            recall_estimates: Default::default(),
         })
     }
 
@@ -313,6 +321,7 @@ impl Collection {
             optimizer_resource_budget,
             collection_stats_cache,
             shard_clean_tasks: Default::default(),
+            recall_estimates: Default::default(),
         }
     }
 
@@ -573,6 +582,51 @@ impl Collection {
         replica_set.get_wal_entries(count).await
     }
 
+    /// Read changes of points, committed to the local replica of the shard, starting at `from`
+    /// position. If `from` is not set, reading starts after the last committed change.
+    ///
+    /// Positions are specific to the replica on this peer.
+    /// Returns changes, and position to continue reading from.
+    pub async fn read_shard_changes(
+        &self,
+        shard_id: ShardId,
+        from: Option<SeqNumberType>,
+        limit: usize,
+    ) -> CollectionResult<(Vec<PointsChange>, SeqNumberType)> {
+        let shard_holder = self.shards_holder.read().await;
+
+        let Some(replica_set) = shard_holder.get_shard(shard_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        let (operations, next) =
+            replica_set
+                .read_wal_changes(from, limit)
+                .await
+                .map_err(|err| match err {
+                    CollectionError::BadRequest { description } => {
+                        CollectionError::bad_request(format!("Shard {shard_id}: {description}"))
+                    }
+                    err => err,
+                })?;
+        drop(shard_holder);
+
+        let mut changes = Vec::with_capacity(operations.len());
+        for (position, operation) in operations {
+            let change = points_change_from_operation(
+                self.name().to_string(),
+                shard_id,
+                position,
+                operation,
+            )?;
+            changes.extend(change);
+        }
+
+        Ok((changes, next))
+    }
+
     /// Get optimizations info from the local shard only.
     ///
     /// Used by the internal gRPC handler to serve requests from remote peers.
@@ -149,6 +149,13 @@ impl Collection {
         shard_keys_selection: Option<ShardKey>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<UpdateResult> {
+        if self.collection_config.read().await.read_only {
+            return Err(CollectionError::bad_request(format!(
+                "Collection {} is in read-only mode, write operations are rejected",
+                self.id,
+            )));
+        }
+
         let shard_holder = self.shards_holder.clone().read_owned().await;
         let start_time = std::time::Instant::now();
 
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::request_profile::ProfileStage;
 use futures::{TryFutureExt, future};
 use itertools::{Either, Itertools};
 use rand::RngExt;
@@ -24,6 +25,7 @@ use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::common::transpose_iterator::transposed_iter;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::time_travel::AsOf;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{
@@ -58,6 +60,7 @@ impl Collection {
                 vec![request],
                 read_consistency,
                 shard_selection,
+                None,
                 timeout,
                 hw_measurement_acc,
             )
@@ -148,6 +151,7 @@ impl Collection {
         batch_request: Arc<Vec<ShardQueryRequest>>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        as_of: Option<AsOf>,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
@@ -174,36 +178,45 @@ impl Collection {
         let all_searches = target_shards.iter().map(|(shard, shard_key)| {
             let shard_key = shard_key.cloned();
             let request_clone = Arc::clone(&batch_request);
-            shard
-                .query_batch(
+            let shard_query = match as_of {
+                None => future::Either::Left(shard.query_batch(
                     request_clone,
                     read_consistency,
                     shard_selection.is_shard_id(),
                     timeout,
                     hw_measurement_acc.clone(),
-                )
-                .and_then(move |mut shard_responses| async move {
-                    if shard_key.is_none() {
-                        return Ok(shard_responses);
-                    }
-                    shard_responses
-                        .iter_mut()
-                        .flatten()
-                        .flatten()
-                        .for_each(|point| point.shard_key.clone_from(&shard_key));
+                )),
+                Some(as_of) => future::Either::Right(shard.query_batch_as_of(
+                    request_clone,
+                    as_of,
+                    timeout,
+                    hw_measurement_acc.clone(),
+                )),
+            };
+            shard_query.and_then(move |mut shard_responses| async move {
+                if shard_key.is_none() {
+                    return Ok(shard_responses);
+                }
+                shard_responses
+                    .iter_mut()
+                    .flatten()
+                    .flatten()
+                    .for_each(|point| point.shard_key.clone_from(&shard_key));
 
-                    Ok(shard_responses)
-                })
+                Ok(shard_responses)
+            })
         });
         future::try_join_all(all_searches).await
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(batch_size = requests_batch.len())))]
     async fn do_query_batch(
         &self,
         requests_batch: Vec<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
+        as_of: Option<AsOf>,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
@@ -239,7 +252,9 @@ impl Collection {
         let is_required_transfer_large_enough =
             require_transfers > used_transfers.saturating_mul(PAYLOAD_TRANSFERS_FACTOR_THRESHOLD);
 
-        if metadata_required && is_required_transfer_large_enough {
+        // Payload of pinned queries must be read from the same past state, so it can't be
+        // retrieved separately
+        if metadata_required && is_required_transfer_large_enough && as_of.is_none() {
             // If there is a significant offset, we need to retrieve the whole result
             // set without payload first and then retrieve the payload.
             // It is required to do this because the payload might be too large to send over the
@@ -257,6 +272,7 @@ impl Collection {
                     without_payload_batch,
                     read_consistency,
                     &shard_selection,
+                    None,
                     timeout,
                     hw_measurement_acc.clone(),
                 )
@@ -283,6 +299,7 @@ impl Collection {
                 requests_batch,
                 read_consistency,
                 &shard_selection,
+                as_of,
                 timeout,
                 hw_measurement_acc.clone(),
             )
@@ -293,14 +310,24 @@ impl Collection {
     /// This function is used to query the collection. It will return a list of scored points.
     async fn do_query_batch_impl(
         &self,
-        requests_batch: Vec<ShardQueryRequest>,
+        mut requests_batch: Vec<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
+        as_of: Option<AsOf>,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let instant = Instant::now();
 
+        self.fill_text_query_statistics(
+            &mut requests_batch,
+            shard_selection,
+            read_consistency,
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
         let requests_batch = Arc::new(requests_batch);
 
         let all_shards_results = self
@@ -308,7 +335,8 @@ impl Collection {
                 requests_batch.clone(),
                 read_consistency,
                 shard_selection,
-                timeout,
+                as_of,
+                timeout.map(|timeout| timeout.saturating_sub(instant.elapsed())),
                 hw_measurement_acc.clone(),
             )
             .await?;
@@ -318,10 +346,18 @@ impl Collection {
             .map(|(shards_results, request)| async {
                 // shards_results shape: [num_shards, num_intermediate_results, num_points]
                 // merged_intermediates shape: [num_intermediate_results, num_points]
+                let merge_start = Instant::now();
                 let merged_intermediates = self
                     .merge_intermediate_results_from_shards(request, shards_results)
                     .await?;
 
+                let profile = hw_measurement_acc.request_profile();
+                profile.add_time(ProfileStage::ShardMerge, merge_start.elapsed());
+                profile.add_candidates(
+                    ProfileStage::ShardMerge,
+                    merged_intermediates.iter().map(Vec::len).sum(),
+                );
+
                 let result = self
                     .intermediates_to_final_list(
                         merged_intermediates,
@@ -424,7 +460,9 @@ impl Collection {
             | Some(ScoringQuery::Vector(_))
             | Some(ScoringQuery::OrderBy(_))
             | Some(ScoringQuery::Formula(_))
-            | Some(ScoringQuery::Sample(_)) => {
+            | Some(ScoringQuery::Sample(_))
+            | Some(ScoringQuery::Text(_))
+            | Some(ScoringQuery::Hybrid(_)) => {
                 // Otherwise, it will be a list with a single list of scored points.
                 debug_assert_eq!(intermediates.len(), 1);
                 intermediates.pop().ok_or_else(|| {
@@ -451,6 +489,56 @@ impl Collection {
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<Arc<Collection>>>,
+    {
+        self.query_batch_pinned(
+            requests_batch,
+            collection_by_name,
+            read_consistency,
+            None,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    }
+
+    /// Same as [`Collection::query_batch`], but queries the collection as it was at `as_of`.
+    ///
+    /// Referenced points are resolved against the current state of the collections.
+    pub async fn query_batch_as_of<F, Fut>(
+        &self,
+        requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        collection_by_name: F,
+        as_of: AsOf,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<Arc<Collection>>>,
+    {
+        self.query_batch_pinned(
+            requests_batch,
+            collection_by_name,
+            None,
+            Some(as_of),
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    }
+
+    async fn query_batch_pinned<F, Fut>(
+        &self,
+        requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        as_of: Option<AsOf>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
     where
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<Arc<Collection>>>,
@@ -509,6 +597,7 @@ impl Collection {
                     shard_requests,
                     read_consistency,
                     shard_selection,
+                    as_of,
                     timeout,
                     hw_measurement_acc.clone(),
                 ));
@@ -532,11 +621,21 @@ impl Collection {
     /// Otherwise, it will be a list with a single list of scored points.
     pub async fn query_batch_internal(
         &self,
-        requests: Vec<ShardQueryRequest>,
+        mut requests: Vec<ShardQueryRequest>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        // Statistics are usually collected by the coordinating peer already
+        self.fill_text_query_statistics(
+            &mut requests,
+            shard_selection,
+            None,
+            timeout,
+            hw_measurement_acc.clone(),
+        )
+        .await?;
+
         let requests_arc = Arc::new(requests);
 
         // Results from all shards
@@ -546,6 +645,7 @@ impl Collection {
                 Arc::clone(&requests_arc),
                 None,
                 shard_selection,
+                None,
                 timeout,
                 hw_measurement_acc,
             )
@@ -737,7 +837,9 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
         | Some(ScoringQuery::Vector(_))
         | Some(ScoringQuery::OrderBy(_))
         | Some(ScoringQuery::Formula(_))
-        | Some(ScoringQuery::Sample(_)) => {
+        | Some(ScoringQuery::Sample(_))
+        | Some(ScoringQuery::Text(_))
+        | Some(ScoringQuery::Hybrid(_)) => {
             // Otherwise, we expect the root result
             vec![IntermediateQueryInfo {
                 scoring_query: request.query.as_ref(),
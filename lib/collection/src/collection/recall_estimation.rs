@@ -0,0 +1,279 @@
+//! Estimation of approximate search recall on a sample of points.
+//!
+//! Vectors of randomly sampled points are used as synthetic queries. Each query is executed
+//! twice: once with exact search and once with the regular (approximate) search. The share of
+//! exact results found by the approximate search is the measured recall. The sampled point itself
+//! is excluded from both results, as it is trivially found by either search.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use ahash::AHashSet;
+use chrono::{DateTime, Utc};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::types::{
+    Condition, Filter, HasVectorCondition, PointIdType, ScoredPoint, SearchParams, VectorName,
+    VectorNameBuf, WithPayloadInterface, WithVector,
+};
+use serde::Serialize;
+
+use crate::collection::Collection;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+use crate::operations::universal_query::collection_query::{
+    CollectionQueryRequest, Query, VectorInputInternal, VectorQuery,
+};
+use crate::operations::universal_query::shard_query::{
+    SampleInternal, ScoringQuery, ShardQueryRequest,
+};
+
+/// Result of the latest recall estimation of a single named vector.
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+#[anonymize(false)]
+pub struct RecallEstimate {
+    /// Average share of exact search results, found by approximate search
+    pub recall: f64,
+    /// Number of sampled queries used for the estimation
+    pub sampled_queries: usize,
+    /// Number of results requested in each query
+    pub limit: usize,
+    pub measured_at: DateTime<Utc>,
+}
+
+impl Collection {
+    /// Measure recall of approximate search for every dense vector of the collection,
+    /// and keep the results for telemetry.
+    pub async fn estimate_recall(
+        &self,
+        sample_size: usize,
+        limit: usize,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<()> {
+        if sample_size == 0 || limit == 0 {
+            return Ok(());
+        }
+
+        let vector_names: Vec<VectorNameBuf> = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .vectors
+            .params_iter()
+            .map(|(name, _)| name.to_owned())
+            .collect();
+
+        for vector_name in vector_names {
+            let estimate = self
+                .estimate_vector_recall(&vector_name, sample_size, limit, timeout)
+                .await?;
+
+            let mut recall_estimates = self.recall_estimates.lock();
+            match estimate {
+                Some(estimate) => recall_estimates.insert(vector_name, estimate),
+                None => recall_estimates.remove(&vector_name),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Latest recall estimations by vector name.
+    pub fn recall_estimates(&self) -> HashMap<VectorNameBuf, RecallEstimate> {
+        self.recall_estimates.lock().clone()
+    }
+
+    async fn estimate_vector_recall(
+        &self,
+        using: &VectorName,
+        sample_size: usize,
+        limit: usize,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Option<RecallEstimate>> {
+        // Internal job, usage is not reported to anyone
+        let hw_measurement_acc = HwMeasurementAcc::disposable();
+
+        let has_vector = Filter::new_must(Condition::HasVector(HasVectorCondition::from(
+            using.to_owned(),
+        )));
+
+        let sampling_query = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(SampleInternal::Random)),
+            filter: Some(has_vector),
+            score_threshold: None,
+            limit: sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Selector(vec![using.to_owned()]),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        let sampled_points = self
+            .query(
+                sampling_query,
+                None,
+                ShardSelectorInternal::All,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+
+        let query_vectors: Vec<_> = sampled_points
+            .into_iter()
+            .filter_map(|point| {
+                let vector = point.vector?.get(using)?.to_owned();
+                Some((point.id, vector))
+            })
+            .take(sample_size)
+            .collect();
+
+        if query_vectors.is_empty() {
+            return Ok(None);
+        }
+
+        let make_queries = |exact: bool| {
+            query_vectors
+                .iter()
+                .map(|(_, vector)| {
+                    let query = CollectionQueryRequest {
+                        prefetch: vec![],
+                        query: Some(Query::Vector(VectorQuery::Nearest(
+                            VectorInputInternal::Vector(vector.clone()),
+                        ))),
+                        using: using.to_owned(),
+                        filter: None,
+                        score_threshold: None,
+                        // One extra result to make up for the excluded query point
+                        limit: limit + 1,
+                        offset: 0,
+                        params: exact.then(|| SearchParams {
+                            exact: true,
+                            ..Default::default()
+                        }),
+                        with_vector: WithVector::Bool(false),
+                        with_payload: WithPayloadInterface::Bool(false),
+                        lookup_from: None,
+                    };
+                    (query, ShardSelectorInternal::All)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Queries have no `lookup_from`, so no other collections are needed
+        let collection_by_name = |_name: String| async move { None };
+
+        let exact_results = self
+            .query_batch(
+                make_queries(true),
+                collection_by_name,
+                None,
+                timeout,
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+
+        let approximate_results = self
+            .query_batch(
+                make_queries(false),
+                collection_by_name,
+                None,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        let recalls: Vec<f64> = query_vectors
+            .iter()
+            .zip(exact_results.iter().zip(&approximate_results))
+            .filter_map(|((query_id, _), (exact, approximate))| {
+                query_recall(*query_id, exact, approximate, limit)
+            })
+            .collect();
+
+        if recalls.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(RecallEstimate {
+            recall: recalls.iter().sum::<f64>() / recalls.len() as f64,
+            sampled_queries: recalls.len(),
+            limit,
+            measured_at: Utc::now(),
+        }))
+    }
+}
+
+/// Share of top `limit` `exact` results present in top `limit` `approximate` results.
+///
+/// The query point `query_id` is excluded from both results.
+/// Returns `None` if there are no exact results to compare with.
+fn query_recall(
+    query_id: PointIdType,
+    exact: &[ScoredPoint],
+    approximate: &[ScoredPoint],
+    limit: usize,
+) -> Option<f64> {
+    let exact: Vec<PointIdType> = exact
+        .iter()
+        .map(|point| point.id)
+        .filter(|&id| id != query_id)
+        .take(limit)
+        .collect();
+
+    if exact.is_empty() {
+        return None;
+    }
+
+    let found: AHashSet<PointIdType> = approximate
+        .iter()
+        .map(|point| point.id)
+        .filter(|&id| id != query_id)
+        .take(limit)
+        .collect();
+
+    let matched = exact.iter().filter(|id| found.contains(id)).count();
+
+    Some(matched as f64 / exact.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_scored_point(id: u64) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score: 0.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    #[test]
+    fn test_query_recall() {
+        let exact: Vec<_> = [1, 2, 3, 4].into_iter().map(make_scored_point).collect();
+        let approximate: Vec<_> = [1, 3, 5, 6].into_iter().map(make_scored_point).collect();
+
+        let query_id = 100.into();
+        assert_eq!(query_recall(query_id, &exact, &approximate, 4), Some(0.5));
+        assert_eq!(query_recall(query_id, &exact, &exact, 4), Some(1.0));
+        assert_eq!(query_recall(query_id, &[], &approximate, 4), None);
+        assert_eq!(query_recall(query_id, &exact, &approximate, 2), Some(0.5));
+    }
+
+    #[test]
+    fn test_query_recall_excludes_query_point() {
+        let exact: Vec<_> = [1, 2, 3].into_iter().map(make_scored_point).collect();
+        let approximate: Vec<_> = [1, 2, 4].into_iter().map(make_scored_point).collect();
+
+        // Query point is found by both searches, it must not inflate the recall
+        assert_eq!(query_recall(1.into(), &exact, &approximate, 2), Some(0.5));
+        assert_eq!(query_recall(1.into(), &exact[..1], &approximate, 2), None);
+    }
+}
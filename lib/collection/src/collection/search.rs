@@ -228,6 +228,7 @@ impl Collection {
             ids: search_result.iter().map(|x| x.id).collect(),
             with_payload,
             with_vector,
+            at_version: None,
         };
         let retrieved_records = self
             .retrieve(
@@ -31,7 +31,10 @@ use crate::shards::shard_path;
 
 impl Collection {
     pub fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
-        SnapshotStorageManager::new(&self.shared_storage_config.snapshots_config)
+        SnapshotStorageManager::new(
+            &self.shared_storage_config.snapshots_config,
+            &self.shared_storage_config.object_storage,
+        )
     }
 
     pub async fn list_snapshots(&self) -> CollectionResult<Vec<SnapshotDescription>> {
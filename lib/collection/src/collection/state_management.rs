@@ -145,6 +145,7 @@ impl Collection {
                 strict_mode_config,
                 uuid: _,
                 metadata,
+                read_only,
             } = &new_config;
 
             let is_core_config_updated = params != &config.params
@@ -154,13 +155,16 @@ impl Collection {
 
             let is_metadata_updated = metadata != &config.metadata;
 
+            let is_read_only_updated = read_only != &config.read_only;
+
             let is_wal_config_updated = wal_config != &config.wal_config;
             let is_strict_mode_config_updated = strict_mode_config != &config.strict_mode_config;
 
             let is_config_updated = is_core_config_updated
                 || is_wal_config_updated
                 || is_strict_mode_config_updated
-                || is_metadata_updated;
+                || is_metadata_updated
+                || is_read_only_updated;
 
             if !is_config_updated {
                 return Ok(());
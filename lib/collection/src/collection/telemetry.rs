@@ -34,6 +34,7 @@ impl Collection {
         };
 
         let shard_clean_tasks = self.clean_local_shards_statuses();
+        let recall = self.recall_estimates();
 
         Ok(CollectionTelemetry {
             id: self.name().to_string(),
@@ -45,6 +46,7 @@ impl Collection {
             transfers,
             resharding,
             shard_clean_tasks: (!shard_clean_tasks.is_empty()).then_some(shard_clean_tasks),
+            recall: (!recall.is_empty()).then_some(recall),
         })
     }
 }
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::TryStreamExt;
+use futures::future::try_join_all;
+use futures::stream::FuturesUnordered;
+use itertools::Itertools as _;
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
+use segment::types::ShardKey;
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CollectionResult;
+use crate::operations::universal_query::shard_query::ShardQueryRequest;
+use crate::shards::replica_set::ShardReplicaSet;
+
+impl Collection {
+    /// Collect statistics of the text query tokens over all selected shards
+    pub async fn text_query_statistics(
+        &self,
+        query: TextQuery,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        Self::text_query_statistics_of_shards(
+            Arc::new(query),
+            &target_shards,
+            read_consistency,
+            shard_selection.is_shard_id(),
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    }
+
+    /// Fill the statistics of text queries in the batch, which were not collected yet.
+    ///
+    /// Statistics are merged over all selected shards, so the scores of points from different
+    /// shards are comparable.
+    pub(super) async fn fill_text_query_statistics(
+        &self,
+        requests: &mut [ShardQueryRequest],
+        shard_selection: &ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<()> {
+        let text_queries = requests
+            .iter_mut()
+            .flat_map(ShardQueryRequest::text_queries_mut)
+            .filter(|text_query| text_query.statistics.is_none())
+            .collect_vec();
+        if text_queries.is_empty() {
+            return Ok(());
+        }
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(shard_selection)?;
+
+        let fill_statistics = text_queries.into_iter().map(|text_query| {
+            let target_shards = &target_shards;
+            let hw_measurement_acc = hw_measurement_acc.clone();
+            async move {
+                let statistics = Self::text_query_statistics_of_shards(
+                    Arc::new(text_query.query.clone()),
+                    target_shards,
+                    read_consistency,
+                    shard_selection.is_shard_id(),
+                    timeout,
+                    hw_measurement_acc,
+                )
+                .await?;
+                text_query.statistics = Some(statistics);
+                CollectionResult::Ok(())
+            }
+        });
+        try_join_all(fill_statistics).await?;
+
+        Ok(())
+    }
+
+    async fn text_query_statistics_of_shards(
+        query: Arc<TextQuery>,
+        target_shards: &[(&ShardReplicaSet, Option<&ShardKey>)],
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let mut shards_statistics_f = target_shards
+            .iter()
+            .map(|(shard, _shard_key)| {
+                shard.text_query_statistics(
+                    query.clone(),
+                    read_consistency,
+                    local_only,
+                    timeout,
+                    hw_measurement_acc.clone(),
+                )
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        let mut merged = TextQueryStatistics::default();
+        while let Some(statistics) = shards_statistics_f.try_next().await? {
+            merged.merge(&statistics);
+        }
+
+        Ok(merged)
+    }
+}
@@ -147,26 +147,31 @@ mod tests {
                 id: 11.into(),
                 vector: VectorStructPersisted::from(VectorStructInternal::from(vec11)),
                 payload: None,
+                expires_at: None,
             },
             PointStructPersisted {
                 id: 12.into(),
                 vector: VectorStructPersisted::from(VectorStructInternal::from(vec12)),
                 payload: None,
+                expires_at: None,
             },
             PointStructPersisted {
                 id: 13.into(),
                 vector: VectorStructPersisted::from(VectorStructInternal::from(vec13)),
                 payload: Some(payload_json! { "color": "red" }),
+                expires_at: None,
             },
             PointStructPersisted {
                 id: 14.into(),
                 vector: VectorStructPersisted::Single(vec![0., 0., 0., 0.]),
                 payload: None,
+                expires_at: None,
             },
             PointStructPersisted {
                 id: 500.into(),
                 vector: VectorStructPersisted::Single(vec![2., 0., 2., 0.]),
                 payload: None,
+                expires_at: None,
             },
         ];
 
@@ -199,11 +204,13 @@ mod tests {
                 id: 1.into(),
                 vector: VectorStructPersisted::Single(vec![2., 2., 2., 2.]),
                 payload: None,
+                expires_at: None,
             },
             PointStructPersisted {
                 id: 500.into(),
                 vector: VectorStructPersisted::Single(vec![2., 0., 2., 0.]),
                 payload: None,
+                expires_at: None,
             },
         ];
 
@@ -8,7 +8,9 @@ pub use shard::optimizers::config_mismatch_optimizer::ConfigMismatchOptimizer;
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::sync::atomic::AtomicBool;
 
+    use common::budget::ResourceBudget;
     use itertools::Itertools;
     use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
     use segment::entry::ReadSegmentEntry;
@@ -30,6 +32,7 @@ mod tests {
     use crate::collection_manager::fixtures::{random_multi_vec_segment, random_segment};
     use crate::collection_manager::holders::segment_holder::SegmentHolder;
     use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
+    use crate::update_workers::UpdateWorkers;
 
     fn dense_map_from_segment(
         segment_config: &SegmentConfig,
@@ -45,6 +48,8 @@ mod tests {
                     .unwrap_or(DenseVectorOptimizerConfig {
                         on_disk: None,
                         hnsw_config: HnswConfig::default(),
+                        vamana_config: None,
+                        ivf_config: None,
                         quantization_config: None,
                     });
                 (name.clone(), cfg)
@@ -127,6 +132,9 @@ mod tests {
             on_disk: None,
             payload_m: None,
             inline_storage: None,
+            varint_links: None,
+            acorn: None,
+            seed: None,
         };
 
         let mut dense_overrides = HashMap::new();
@@ -135,6 +143,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config,
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: None,
             },
         );
@@ -182,6 +192,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: changed_hnsw_config,
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: None,
             },
         );
@@ -273,6 +285,9 @@ mod tests {
             on_disk: None,
             payload_m: None,
             inline_storage: None,
+            varint_links: None,
+            acorn: None,
+            seed: None,
         };
 
         let mut hnsw_config_vector1 = hnsw_config_collection;
@@ -288,6 +303,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: Some(true),
                 hnsw_config: hnsw_config_vector1,
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: None,
             },
         );
@@ -296,6 +313,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: hnsw_config_vector2,
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: None,
             },
         );
@@ -342,6 +361,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: hnsw_config_vector2_changed,
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: None,
             },
         );
@@ -385,22 +406,19 @@ mod tests {
             });
     }
 
-    /// This test the config mismatch optimizer for a changed vector specific HNSW config
-    ///
-    /// Similar to `test_hnsw_config_mismatch` but for multi vector segment with a vector specific
-    /// change.
+    /// This test the config mismatch for a changed vector specific quantization config
     ///
     /// It tests whether:
-    /// - the condition check for HNSW mismatches works for a vector specific change
-    /// - optimized segments (and vector storages) use the updated configuration
+    /// - quantization of an indexed segment is rebuilt in place, without the optimizer
+    /// - segments use the updated configuration
     ///
     /// In short, this is what happens in this test:
     /// - create randomized multi segment as base
     /// - use indexing optimizer to build index for our segment
     /// - test config mismatch condition: should not trigger yet
-    /// - change HNSW config for vector2
-    /// - test config mismatch condition: should trigger due to HNSW change
-    /// - optimize segment with config mismatch optimizer
+    /// - change quantization config for vector2
+    /// - test config mismatch condition: should not trigger, quantization is rebuilt in place
+    /// - rebuild quantization in place
     /// - assert segment uses changed configuration
     #[test]
     fn test_quantization_config_mismatch_vector_specific() {
@@ -418,6 +436,7 @@ mod tests {
                     r#type: ScalarType::Int8,
                     quantile: Some(0.99),
                     always_ram: Some(true),
+                    drop_originals: None,
                 },
             });
 
@@ -444,6 +463,7 @@ mod tests {
                     r#type: ScalarType::Int8,
                     quantile: Some(0.91),
                     always_ram: None,
+                    drop_originals: None,
                 },
             });
 
@@ -453,6 +473,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: HnswConfig::default(),
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: Some(quantization_config_vector1.clone()),
             },
         );
@@ -461,6 +483,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: HnswConfig::default(),
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: Some(quantization_config_collection.clone()),
             },
         );
@@ -503,6 +527,7 @@ mod tests {
             product: ProductQuantizationConfig {
                 compression: CompressionRatio::X32,
                 always_ram: Some(true),
+                drop_originals: None,
             },
         });
         dense_overrides.insert(
@@ -510,6 +535,8 @@ mod tests {
             DenseVectorOptimizerConfig {
                 on_disk: None,
                 hnsw_config: HnswConfig::default(),
+                vamana_config: None,
+                ivf_config: None,
                 quantization_config: Some(quantization_config_vector2.clone()),
             },
         );
@@ -524,16 +551,30 @@ mod tests {
             HnswGlobalConfig::default(),
         );
 
-        // Run mismatch optimizer again, make sure it optimizes now
+        // Mismatch optimizer should not optimize, only quantized vectors have to be rebuilt
         let suggested_to_optimize =
             config_mismatch_optimizer.plan_optimizations_for_test(&locked_holder);
-        let suggested_to_optimize = suggested_to_optimize.into_iter().exactly_one().unwrap();
-        assert_eq!(suggested_to_optimize.len(), 1);
-        let changed = config_mismatch_optimizer
-            .optimize_for_test(locked_holder.clone(), suggested_to_optimize);
-        assert!(changed > 0, "optimizer should have rebuilt this segment");
+        assert_eq!(suggested_to_optimize.len(), 0);
+
+        let quantization_configs = HashMap::from([
+            (
+                VectorNameBuf::from(VECTOR1_NAME),
+                quantization_config_vector1.clone(),
+            ),
+            (
+                VectorNameBuf::from(VECTOR2_NAME),
+                quantization_config_vector2.clone(),
+            ),
+        ]);
+        let rebuilt = UpdateWorkers::rebuild_quantization_fn(
+            locked_holder.clone(),
+            quantization_configs,
+            ResourceBudget::default(),
+            &AtomicBool::new(false),
+        );
+        assert!(rebuilt, "quantization of this segment should be rebuilt");
 
-        // Ensure new segment has changed quantization config
+        // Ensure segment has changed quantization config
         locked_holder
             .read()
             .iter_original()
@@ -629,6 +629,9 @@ mod tests {
             on_disk: None,
             payload_m: None,
             inline_storage: None,
+            varint_links: None,
+            acorn: None,
+            seed: None,
         };
 
         {
@@ -60,6 +60,8 @@ mod tests {
                 DenseVectorOptimizerConfig {
                     on_disk: None,
                     hnsw_config: HnswConfig::default(),
+                    vamana_config: None,
+                    ivf_config: None,
                     quantization_config: None,
                 },
             );
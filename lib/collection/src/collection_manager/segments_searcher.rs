@@ -4,6 +4,7 @@ use std::time::{Duration, Instant};
 
 use ahash::AHashMap;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::request_profile::ProfileStage;
 use common::types::{DeferredBehavior, ScoreType};
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt, TryStreamExt};
@@ -14,15 +15,15 @@ use segment::data_types::modifier::Modifier;
 use segment::data_types::query_context::{FormulaContext, QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::QueryVector;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, VectorName,
-    WithPayload, WithPayloadInterface, WithVector,
+    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
+    VectorName, WithPayload, WithPayloadInterface, WithVector,
 };
 use shard::common::stopping_guard::StoppingGuard;
 use shard::optimizers::config::DEFAULT_INDEXING_THRESHOLD_KB;
 use shard::query::query_context::{fill_query_context, init_query_context};
 use shard::query::query_enum::QueryEnum;
 use shard::retrieve::record_internal::RecordInternal;
-use shard::retrieve::retrieve_blocking::retrieve_blocking;
+use shard::retrieve::retrieve_blocking::{retrieve_at_version_blocking, retrieve_blocking};
 use shard::search::CoreSearchRequestBatch;
 use shard::search_result_aggregator::BatchResultAggregator;
 use shard::segment_holder::locked::LockedSegmentHolder;
@@ -197,6 +198,12 @@ impl SegmentsSearcher {
                     .map(|params| params.modifier == Some(Modifier::Idf))
                     .unwrap_or(false)
             },
+            |vector_name| {
+                collection_config
+                    .params
+                    .get_sparse_vector_params_opt(vector_name)
+                    .and_then(|params| params.bm25)
+            },
         );
         let is_stopped = is_stopped_guard.get_is_stopped().clone();
         // Do blocking calls in a blocking task: `segment.get().read()` calls might block async runtime
@@ -208,6 +215,7 @@ impl SegmentsSearcher {
         Ok(task)
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn search(
         segments: LockedSegmentHolder,
         batch_request: Arc<CoreSearchRequestBatch>,
@@ -256,7 +264,12 @@ impl SegmentsSearcher {
                         let cpu_utilization = query_context_arc_segment
                             .hardware_usage_accumulator()
                             .cpu_utilization();
+                        // Blocking tasks don't inherit the current span, pass it explicitly
+                        #[cfg(feature = "tracing")]
+                        let span = tracing::Span::current();
                         move || {
+                            #[cfg(feature = "tracing")]
+                            let _span = span.enter();
                             cpu_utilization.measure(|| {
                                 let segment_query_context =
                                     query_context_arc_segment.get_segment_query_context();
@@ -394,6 +407,34 @@ impl SegmentsSearcher {
         timeout: Duration,
         hw_measurement_acc: HwMeasurementAcc,
         deferred_behavior: DeferredBehavior,
+    ) -> CollectionResult<AHashMap<PointIdType, RecordInternal>> {
+        Self::retrieve_at_version(
+            segments,
+            points,
+            with_payload,
+            with_vector,
+            None,
+            runtime_handle,
+            timeout,
+            hw_measurement_acc,
+            deferred_behavior,
+        )
+        .await
+    }
+
+    /// Same as [`Self::retrieve`], but vectors are fetched as they were after the operation with
+    /// `at_version`, if it is set.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn retrieve_at_version(
+        segments: LockedSegmentHolder,
+        points: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        at_version: Option<SeqNumberType>,
+        runtime_handle: &Handle,
+        timeout: Duration,
+        hw_measurement_acc: HwMeasurementAcc,
+        deferred_behavior: DeferredBehavior,
     ) -> CollectionResult<AHashMap<PointIdType, RecordInternal>> {
         let stopping_guard = StoppingGuard::new();
         let points = runtime_handle.spawn_blocking({
@@ -404,16 +445,21 @@ impl SegmentsSearcher {
             let is_stopped = stopping_guard.get_is_stopped();
             // TODO create one Task per segment level retrieve
             move || {
-                retrieve_blocking(
-                    segments,
-                    &points,
-                    &with_payload,
-                    &with_vector,
-                    timeout,
-                    &is_stopped,
-                    hw_measurement_acc,
-                    deferred_behavior,
-                )
+                let profile = hw_measurement_acc.request_profile();
+                profile.add_candidates(ProfileStage::PayloadFetch, points.len());
+                profile.measure(ProfileStage::PayloadFetch, || {
+                    retrieve_at_version_blocking(
+                        segments,
+                        &points,
+                        &with_payload,
+                        &with_vector,
+                        at_version,
+                        timeout,
+                        &is_stopped,
+                        hw_measurement_acc,
+                        deferred_behavior,
+                    )
+                })
             }
         });
         Ok(AbortOnDropHandle::new(points).await??)
@@ -475,6 +521,7 @@ impl SegmentsSearcher {
     /// Rescore results with a formula that can reference payload values.
     ///
     /// Aggregates rescores from the segments.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn rescore_with_formula(
         segments: LockedSegmentHolder,
         arc_ctx: Arc<FormulaContext>,
@@ -502,12 +549,15 @@ impl SegmentsSearcher {
                         let arc_ctx = arc_ctx.clone();
                         let hw_counter = hw_measurement_acc.get_counter_cell();
                         let cpu_utilization = hw_measurement_acc.cpu_utilization();
+                        let profile = hw_measurement_acc.request_profile();
                         move || {
                             cpu_utilization.measure(|| {
-                                segment
-                                    .get()
-                                    .read()
-                                    .rescore_with_formula(arc_ctx, &hw_counter)
+                                profile.measure(ProfileStage::Rescoring, || {
+                                    segment
+                                        .get()
+                                        .read()
+                                        .rescore_with_formula(arc_ctx, &hw_counter)
+                                })
                             })
                         }
                     });
@@ -614,6 +664,7 @@ fn effective_limit(limit: usize, ef_limit: usize, poisson_sampling: usize) -> us
 /// Collection Result of:
 /// * Vector of ScoredPoints for each request in the batch
 /// * Vector of boolean indicating if the segment have further points to search
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 fn search_in_segment(
     segment: LockedSegment,
     request: Arc<CoreSearchRequestBatch>,
@@ -755,7 +806,7 @@ fn get_hnsw_ef_construct(config: &SegmentConfig, vector_name: &VectorName) -> Op
         .vector_data
         .get(vector_name)
         .and_then(|config| match &config.index {
-            Indexes::Plain {} => None,
+            Indexes::Plain {} | Indexes::Vamana(_) | Indexes::Ivf(_) => None,
             Indexes::Hnsw(hnsw) => Some(hnsw),
         })
         .map(|hnsw| hnsw.ef_construct)
@@ -69,11 +69,13 @@ fn test_update_proxy_segments() {
                 id: (100 * i + 1).into(),
                 vector: VectorStructPersisted::from(VectorStructInternal::from(vectors[0].clone())),
                 payload: None,
+                expires_at: None,
             },
             PointStructPersisted {
                 id: (100 * i + 2).into(),
                 vector: VectorStructPersisted::from(VectorStructInternal::from(vectors[1].clone())),
                 payload: None,
+                expires_at: None,
             },
         ];
         upsert_points(&segments.read(), 1000 + i, &points, &hw_counter).unwrap();
@@ -128,11 +130,13 @@ fn test_move_points_to_copy_on_write() {
             id: 1.into(),
             vector: VectorStructPersisted::from(vec![0.0, 0.0, 0.0, 0.0]),
             payload: None,
+            expires_at: None,
         },
         PointStructPersisted {
             id: 2.into(),
             vector: VectorStructPersisted::from(vec![0.0, 0.0, 0.0, 0.0]),
             payload: None,
+            expires_at: None,
         },
     ];
 
@@ -145,11 +149,13 @@ fn test_move_points_to_copy_on_write() {
             id: 2.into(),
             vector: VectorStructPersisted::from(vec![0.0, 0.0, 0.0, 0.0]),
             payload: None,
+            expires_at: None,
         },
         PointStructPersisted {
             id: 3.into(),
             vector: VectorStructPersisted::from(vec![0.0, 0.0, 0.0, 0.0]),
             payload: None,
+            expires_at: None,
         },
     ];
 
@@ -238,6 +244,7 @@ fn test_upsert_points_in_smallest_segment() {
                 0.0, 0.0, 0.0, 0.0,
             ])),
             payload: None,
+            expires_at: None,
         })
         .collect();
     upsert_points(&segments.read(), 1000, &points, &hw_counter).unwrap();
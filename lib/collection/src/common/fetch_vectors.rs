@@ -38,6 +38,7 @@ pub async fn retrieve_points(
                 ids,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vector_names),
+                at_version: None,
             },
             read_consistency,
             shard_selector,
@@ -0,0 +1,216 @@
+//! Object storage providers, shared by everything that keeps cold data outside of the node:
+//! snapshots, archived WAL, and so on.
+//!
+//! Credentials and retry policy are configured once per node, features only select a provider.
+
+use std::time::Duration;
+
+use object_store::aws::AmazonS3Builder;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use object_store::{BackoffConfig, ObjectStore, RetryConfig};
+use serde::Deserialize;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectStorageProvider {
+    /// Local file system
+    #[default]
+    Local,
+    /// Amazon S3, or S3-compatible storage
+    S3,
+    /// Google Cloud Storage
+    Gcs,
+    /// Azure Blob Storage
+    Azure,
+}
+
+/// Credentials and connection settings of the object storage providers.
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct ObjectStorageConfig {
+    #[serde(default)]
+    pub s3: Option<S3Config>,
+    #[serde(default)]
+    pub gcs: Option<GcsConfig>,
+    #[serde(default)]
+    pub azure: Option<AzureConfig>,
+    /// Retry policy for requests to any provider
+    #[serde(default)]
+    pub retry: ObjectStorageRetryConfig,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct S3Config {
+    pub bucket: String,
+    pub region: Option<String>,
+    pub access_key: Option<String>,
+    pub secret_key: Option<String>,
+    pub endpoint_url: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct GcsConfig {
+    pub bucket: String,
+    /// Path to the service account JSON file
+    pub service_account_path: Option<String>,
+    /// Service account JSON, as an alternative to `service_account_path`
+    pub service_account_key: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct AzureConfig {
+    pub account: String,
+    pub container: String,
+    pub access_key: Option<String>,
+    /// Custom endpoint, e.g. for Azurite emulator
+    pub endpoint_url: Option<String>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct ObjectStorageRetryConfig {
+    /// Maximum number of retries of a single request
+    #[serde(default = "default_max_retries")]
+    pub max_retries: usize,
+    /// Stop retrying a request after this time since the first attempt
+    #[serde(default = "default_retry_timeout_sec")]
+    pub retry_timeout_sec: u64,
+    /// Delay before the first retry, doubled on each next retry
+    #[serde(default = "default_init_backoff_ms")]
+    pub init_backoff_ms: u64,
+    /// Maximum delay between retries
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for ObjectStorageRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: default_max_retries(),
+            retry_timeout_sec: default_retry_timeout_sec(),
+            init_backoff_ms: default_init_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+        }
+    }
+}
+
+const fn default_max_retries() -> usize {
+    10
+}
+
+const fn default_retry_timeout_sec() -> u64 {
+    180
+}
+
+const fn default_init_backoff_ms() -> u64 {
+    100
+}
+
+const fn default_max_backoff_ms() -> u64 {
+    15_000
+}
+
+impl From<&ObjectStorageRetryConfig> for RetryConfig {
+    fn from(config: &ObjectStorageRetryConfig) -> Self {
+        RetryConfig {
+            backoff: BackoffConfig {
+                init_backoff: Duration::from_millis(config.init_backoff_ms),
+                max_backoff: Duration::from_millis(config.max_backoff_ms),
+                ..BackoffConfig::default()
+            },
+            max_retries: config.max_retries,
+            retry_timeout: Duration::from_secs(config.retry_timeout_sec),
+        }
+    }
+}
+
+impl ObjectStorageConfig {
+    /// Build a client of the given remote provider.
+    ///
+    /// Settings, which are not configured explicitly, are taken from the environment variables
+    /// of the provider, e.g. `AWS_ACCESS_KEY_ID`.
+    pub fn build_client(
+        &self,
+        provider: ObjectStorageProvider,
+    ) -> CollectionResult<Box<dyn ObjectStore>> {
+        match provider {
+            ObjectStorageProvider::Local => Err(CollectionError::service_error(
+                "Local file system is not a remote object storage",
+            )),
+            ObjectStorageProvider::S3 => self.build_s3_client(),
+            ObjectStorageProvider::Gcs => self.build_gcs_client(),
+            ObjectStorageProvider::Azure => self.build_azure_client(),
+        }
+    }
+
+    fn build_s3_client(&self) -> CollectionResult<Box<dyn ObjectStore>> {
+        let mut builder = AmazonS3Builder::from_env().with_retry(RetryConfig::from(&self.retry));
+        if let Some(s3_config) = &self.s3 {
+            builder = builder.with_bucket_name(&s3_config.bucket);
+
+            if let Some(access_key) = &s3_config.access_key {
+                builder = builder.with_access_key_id(access_key);
+            }
+            if let Some(secret_key) = &s3_config.secret_key {
+                builder = builder.with_secret_access_key(secret_key);
+            }
+            if let Some(region) = &s3_config.region {
+                builder = builder.with_region(region);
+            }
+            if let Some(endpoint_url) = &s3_config.endpoint_url {
+                builder = builder.with_endpoint(endpoint_url);
+                if endpoint_url.starts_with("http://") {
+                    builder = builder.with_allow_http(true);
+                }
+            }
+        }
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create S3 client: {e}"))
+        })?;
+        Ok(Box::new(client))
+    }
+
+    fn build_gcs_client(&self) -> CollectionResult<Box<dyn ObjectStore>> {
+        let mut builder =
+            GoogleCloudStorageBuilder::from_env().with_retry(RetryConfig::from(&self.retry));
+        if let Some(gcs_config) = &self.gcs {
+            builder = builder.with_bucket_name(&gcs_config.bucket);
+
+            if let Some(service_account_path) = &gcs_config.service_account_path {
+                builder = builder.with_service_account_path(service_account_path);
+            }
+            if let Some(service_account_key) = &gcs_config.service_account_key {
+                builder = builder.with_service_account_key(service_account_key);
+            }
+        }
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create GCS client: {e}"))
+        })?;
+        Ok(Box::new(client))
+    }
+
+    fn build_azure_client(&self) -> CollectionResult<Box<dyn ObjectStore>> {
+        let mut builder =
+            MicrosoftAzureBuilder::from_env().with_retry(RetryConfig::from(&self.retry));
+        if let Some(azure_config) = &self.azure {
+            builder = builder
+                .with_account(&azure_config.account)
+                .with_container_name(&azure_config.container);
+
+            if let Some(access_key) = &azure_config.access_key {
+                builder = builder.with_access_key(access_key);
+            }
+            if let Some(endpoint_url) = &azure_config.endpoint_url {
+                builder = builder.with_endpoint(endpoint_url.clone());
+                if endpoint_url.starts_with("http://") {
+                    builder = builder.with_allow_http(true);
+                }
+            }
+        }
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create Azure client: {e}"))
+        })?;
+        Ok(Box::new(client))
+    }
+}
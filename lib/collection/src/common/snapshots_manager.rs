@@ -4,11 +4,12 @@ use common::tempfile_ext::MaybeTempPath;
 use fs_err as fs;
 use fs_err::tokio as tokio_fs;
 use object_store::ObjectStoreExt;
-use object_store::aws::AmazonS3Builder;
 use serde::Deserialize;
 use tempfile::TempPath;
 use tokio::io::AsyncWriteExt;
 
+pub use super::object_storage::S3Config;
+use super::object_storage::{ObjectStorageConfig, ObjectStorageProvider};
 use super::snapshot_stream::{SnapShotStreamLocalFS, SnapshotStream};
 use crate::common::file_utils::move_file;
 use crate::common::sha_256::hash_file;
@@ -21,24 +22,29 @@ use crate::operations::types::{CollectionError, CollectionResult};
 #[derive(Clone, Deserialize, Debug, Default)]
 pub struct SnapshotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
+    /// Deprecated, use `storage.object_storage.s3` instead.
+    /// Used only if S3 is not configured in the object storage.
     pub s3_config: Option<S3Config>,
+    /// If set, closed WAL segments are continuously shipped into the snapshots storage.
+    #[serde(default)]
+    pub wal_archive: Option<WalArchiveConfig>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum SnapshotsStorageConfig {
-    #[default]
-    Local,
-    S3,
+/// Where to store snapshots
+pub type SnapshotsStorageConfig = ObjectStorageProvider;
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct WalArchiveConfig {
+    /// Path relative to the snapshots path (or key prefix in object storage) to archive into.
+    #[serde(default = "default_wal_archive_path")]
+    pub path: PathBuf,
+    /// How long archived WAL segments are kept. If not set, archived segments are never removed.
+    #[serde(default)]
+    pub retention_sec: Option<u64>,
 }
 
-#[derive(Clone, Deserialize, Debug, Default)]
-pub struct S3Config {
-    pub bucket: String,
-    pub region: Option<String>,
-    pub access_key: Option<String>,
-    pub secret_key: Option<String>,
-    pub endpoint_url: Option<String>,
+fn default_wal_archive_path() -> PathBuf {
+    PathBuf::from("wal_archive")
 }
 
 pub struct SnapshotStorageCloud {
@@ -49,46 +55,44 @@ pub struct SnapshotStorageLocalFS;
 
 pub enum SnapshotStorageManager {
     LocalFS(SnapshotStorageLocalFS),
-    // Assuming that we can have common operations for all cloud storages
-    S3(SnapshotStorageCloud),
-    // <TODO> : Implement other cloud storage
-    // GCS(SnapshotStorageCloud),
-    // AZURE(SnapshotStorageCloud),
+    /// Any remote object storage: S3, GCS or Azure
+    Cloud(SnapshotStorageCloud),
+}
+
+impl SnapshotsConfig {
+    /// Build a client of the remote object storage, where snapshots are stored.
+    pub(crate) fn build_client(
+        &self,
+        object_storage: &ObjectStorageConfig,
+    ) -> CollectionResult<Box<dyn object_store::ObjectStore>> {
+        if object_storage.s3.is_none() && self.s3_config.is_some() {
+            let object_storage = ObjectStorageConfig {
+                s3: self.s3_config.clone(),
+                ..object_storage.clone()
+            };
+            return object_storage.build_client(self.snapshots_storage);
+        }
+
+        object_storage.build_client(self.snapshots_storage)
+    }
 }
 
 impl SnapshotStorageManager {
-    pub fn new(snapshots_config: &SnapshotsConfig) -> CollectionResult<Self> {
+    pub fn new(
+        snapshots_config: &SnapshotsConfig,
+        object_storage: &ObjectStorageConfig,
+    ) -> CollectionResult<Self> {
         match snapshots_config.snapshots_storage {
             SnapshotsStorageConfig::Local => {
                 Ok(SnapshotStorageManager::LocalFS(SnapshotStorageLocalFS))
             }
-            SnapshotsStorageConfig::S3 => {
-                let mut builder = AmazonS3Builder::from_env();
-                if let Some(s3_config) = &snapshots_config.s3_config {
-                    builder = builder.with_bucket_name(&s3_config.bucket);
-
-                    if let Some(access_key) = &s3_config.access_key {
-                        builder = builder.with_access_key_id(access_key);
-                    }
-                    if let Some(secret_key) = &s3_config.secret_key {
-                        builder = builder.with_secret_access_key(secret_key);
-                    }
-                    if let Some(region) = &s3_config.region {
-                        builder = builder.with_region(region);
-                    }
-                    if let Some(endpoint_url) = &s3_config.endpoint_url {
-                        builder = builder.with_endpoint(endpoint_url);
-                        if endpoint_url.starts_with("http://") {
-                            builder = builder.with_allow_http(true);
-                        }
-                    }
-                }
-                let client: Box<dyn object_store::ObjectStore> =
-                    Box::new(builder.build().map_err(|e| {
-                        CollectionError::service_error(format!("Failed to create S3 client: {e}"))
-                    })?);
-
-                Ok(SnapshotStorageManager::S3(SnapshotStorageCloud { client }))
+            SnapshotsStorageConfig::S3
+            | SnapshotsStorageConfig::Gcs
+            | SnapshotsStorageConfig::Azure => {
+                let client = snapshots_config.build_client(object_storage)?;
+                Ok(SnapshotStorageManager::Cloud(SnapshotStorageCloud {
+                    client,
+                }))
             }
         }
     }
@@ -98,7 +102,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
         }
@@ -112,7 +116,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
         }
@@ -133,7 +137,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
         }
@@ -148,7 +152,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
         }
@@ -163,7 +167,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(_storage_impl) => {
                 SnapshotStorageLocalFS::get_snapshot_path(snapshots_path, snapshot_name)
             }
-            SnapshotStorageManager::S3(_storage_impl) => Ok(
+            SnapshotStorageManager::Cloud(_storage_impl) => Ok(
                 SnapshotStorageCloud::get_snapshot_path(snapshots_path, snapshot_name),
             ),
         }
@@ -178,7 +182,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(_storage_impl) => {
                 SnapshotStorageLocalFS::get_full_snapshot_path(snapshots_path, snapshot_name)
             }
-            SnapshotStorageManager::S3(_storage_impl) => Ok(
+            SnapshotStorageManager::Cloud(_storage_impl) => Ok(
                 SnapshotStorageCloud::get_full_snapshot_path(snapshots_path, snapshot_name),
             ),
         }
@@ -193,7 +197,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(_storage_impl) => {
                 SnapshotStorageLocalFS::get_snapshot_file(snapshot_path, temp_dir)
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl
                     .get_snapshot_file(snapshot_path, temp_dir)
                     .await
@@ -209,7 +213,7 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(_storage_impl) => {
                 Ok(SnapshotStorageLocalFS::get_snapshot_stream(snapshot_path))
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::Cloud(storage_impl) => {
                 storage_impl.get_snapshot_stream(snapshot_path).await
             }
         }
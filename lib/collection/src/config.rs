@@ -224,18 +224,20 @@ impl CollectionParams {
                     .then_some(params)
             })
             .map(|params| {
-                let element_bytes = match params.datatype {
-                    Some(Datatype::Float16) => 2,
-                    Some(Datatype::Uint8) => 1,
-                    Some(Datatype::Float32) | None => 4,
+                let element_bits = match params.datatype {
+                    Some(Datatype::Float64) => 64,
+                    Some(Datatype::Float16 | Datatype::Bfloat16) => 16,
+                    Some(Datatype::Uint8 | Datatype::Int8) => 8,
+                    Some(Datatype::Int4) => 4,
+                    Some(Datatype::Float32) | None => 32,
                 };
 
                 let dim = params.size.get() as usize;
 
                 let vector_bytes = if params.multivector_config.is_some() {
-                    element_bytes * dim * MULTIVECTOR_SIZE
+                    (element_bits * dim).div_ceil(8) * MULTIVECTOR_SIZE
                 } else {
-                    element_bytes * dim
+                    (element_bits * dim).div_ceil(8)
                 };
 
                 let deferred_from = threshold_bytes.div_ceil(vector_bytes);
@@ -284,6 +286,9 @@ pub struct CollectionConfigInternal {
     /// such as creation time, migration data, inference model info, etc.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Payload>,
+    /// If true, write operations from clients are rejected
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
 impl CollectionConfigInternal {
@@ -359,6 +364,21 @@ impl CollectionConfigInternal {
         self.params
             .to_base_segment_config(self.quantization_config.as_ref())
     }
+
+    /// Effective quantization config of each dense vector, which has quantization enabled
+    pub fn vector_quantization_configs(&self) -> HashMap<VectorNameBuf, QuantizationConfig> {
+        self.params
+            .vectors
+            .params_iter()
+            .filter_map(|(vector_name, params)| {
+                let quantization_config = params
+                    .quantization_config
+                    .as_ref()
+                    .or(self.quantization_config.as_ref())?;
+                Some((vector_name.to_owned(), quantization_config.clone()))
+            })
+            .collect()
+    }
 }
 
 impl CollectionParams {
@@ -509,6 +529,7 @@ impl CollectionParams {
                 hnsw_config,
                 quantization_config,
                 on_disk,
+                quantization_rescore,
             } = update_params.clone();
 
             if let Some(hnsw_diff) = hnsw_config {
@@ -530,6 +551,12 @@ impl CollectionParams {
                     QuantizationConfigDiff::Binary(binary) => {
                         Some(QuantizationConfig::Binary(binary))
                     }
+                    QuantizationConfigDiff::Residual(residual) => {
+                        Some(QuantizationConfig::Residual(residual))
+                    }
+                    QuantizationConfigDiff::Anisotropic(anisotropic) => {
+                        Some(QuantizationConfig::Anisotropic(anisotropic))
+                    }
                     QuantizationConfigDiff::Disabled(_) => None,
                 }
             }
@@ -537,6 +564,10 @@ impl CollectionParams {
             if let Some(on_disk) = on_disk {
                 vector_params.on_disk = Some(on_disk);
             }
+
+            if let Some(quantization_rescore) = quantization_rescore {
+                vector_params.quantization_rescore = Some(quantization_rescore);
+            }
         }
         Ok(())
     }
@@ -548,12 +579,20 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                bm25,
+            } = update_params.clone();
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
             }
 
+            if let Some(bm25) = bm25 {
+                sparse_vector_params.bm25 = Some(bm25);
+            }
+
             if let Some(index) = index {
                 if let Some(existing_index) = &mut sparse_vector_params.index {
                     existing_index.update_from_other(index);
@@ -592,6 +631,13 @@ impl CollectionParams {
                     on_disk,
                     datatype,
                     multivector_config,
+                    mmap_advice,
+                    dim_range,
+                    preserve_norm,
+                    quantization_rescore: _,
+                    vamana_config: _,
+                    ivf_config: _,
+                    max_versions,
                 } = params;
 
                 (
@@ -614,6 +660,10 @@ impl CollectionParams {
                         },
                         multivector_config: *multivector_config,
                         datatype: datatype.map(VectorStorageDatatype::from),
+                        mmap_advice: *mmap_advice,
+                        dim_range: *dim_range,
+                        preserve_norm: *preserve_norm,
+                        max_versions: *max_versions,
                     },
                 )
             })
@@ -644,6 +694,7 @@ impl CollectionParams {
                             },
                             storage_type: params.storage_type(),
                             modifier: params.modifier,
+                            bm25: params.bm25,
                         },
                     )
                 })
@@ -61,6 +61,7 @@ where
         ids,
         with_payload: request.with_payload,
         with_vector: request.with_vectors.unwrap_or_default(),
+        at_version: None,
     };
 
     let result = collection
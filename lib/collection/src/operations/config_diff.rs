@@ -7,7 +7,8 @@ use std::num::NonZeroU32;
 use api::rest::MaxOptimizationThreads;
 use schemars::JsonSchema;
 use segment::types::{
-    BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization, StrictModeConfig,
+    AnisotropicQuantization, BinaryQuantization, HnswConfig, ProductQuantization,
+    ResidualQuantization, ScalarQuantization, StrictModeConfig,
 };
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationErrors};
@@ -73,6 +74,23 @@ pub struct HnswConfigDiff {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub inline_storage: Option<bool>,
+    /// Store HNSW graph links with varint delta encoding of neighbor ids. Default: false.
+    /// Reduces disk and page cache footprint of the graph, at the cost of slower link decoding
+    /// during the search.
+    /// Not applied if `inline_storage` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub varint_links: Option<bool>,
+    /// Use ACORN predicate-aware traversal for filtered searches, instead of building additional
+    /// payload-aware links. Default: false.
+    /// If enabled, `payload_m` links are not built, and filtered searches evaluate filters on
+    /// 2-hop neighbors, unless ACORN is disabled in search params.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acorn: Option<bool>,
+    /// Seed of the random number generator, used to build the graph. Default: not set.
+    /// If set, level assignment and linking of points are deterministic for the same input,
+    /// so that builds can be reproduced. Points are linked in a single thread then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
@@ -168,6 +186,22 @@ pub struct OptimizersConfigDiff {
     /// Default is disabled.
     #[serde(default)]
     pub prevent_unoptimized: Option<bool>,
+
+    /// Keep segments, replaced by optimizations, for this number of seconds.
+    /// Retained segments allow to run queries pinned to a past timestamp or operation number.
+    /// Retained segments take additional disk space.
+    /// Default is disabled.
+    #[serde(default)]
+    pub segment_retention_sec: Option<u64>,
+
+    /// The minimal fraction of vectors deleted from the HNSW graph of a segment since it was built,
+    /// required to reroute graph links around deleted points in place.
+    /// Healing runs while there is nothing to optimize, and keeps search quality of segments
+    /// until they are vacuumed. Should be lower than `deleted_threshold`.
+    /// Default is disabled.
+    #[serde(default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub graph_heal_threshold: Option<f64>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -183,6 +217,8 @@ impl std::hash::Hash for OptimizersConfigDiff {
             flush_interval_sec,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = self;
 
         deleted_threshold.map(f64::to_le_bytes).hash(state);
@@ -194,6 +230,8 @@ impl std::hash::Hash for OptimizersConfigDiff {
         flush_interval_sec.hash(state);
         max_optimization_threads.hash(state);
         prevent_unoptimized.hash(state);
+        segment_retention_sec.hash(state);
+        graph_heal_threshold.map(f64::to_le_bytes).hash(state);
     }
 }
 
@@ -209,6 +247,9 @@ impl DiffConfig<HnswConfigDiff> for HnswConfig {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = diff;
 
         HnswConfig {
@@ -219,6 +260,9 @@ impl DiffConfig<HnswConfigDiff> for HnswConfig {
             on_disk: on_disk.or(self.on_disk),
             payload_m: payload_m.or(self.payload_m),
             inline_storage: inline_storage.or(self.inline_storage),
+            varint_links: varint_links.or(self.varint_links),
+            acorn: acorn.or(self.acorn),
+            seed: seed.or(self.seed),
         }
     }
 }
@@ -233,6 +277,9 @@ impl DiffConfig<HnswConfigDiff> for HnswConfigDiff {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = diff;
 
         HnswConfigDiff {
@@ -243,6 +290,9 @@ impl DiffConfig<HnswConfigDiff> for HnswConfigDiff {
             on_disk: on_disk.or(self.on_disk),
             payload_m: payload_m.or(self.payload_m),
             inline_storage: inline_storage.or(self.inline_storage),
+            varint_links: varint_links.or(self.varint_links),
+            acorn: acorn.or(self.acorn),
+            seed: seed.or(self.seed),
         }
     }
 }
@@ -259,6 +309,8 @@ impl DiffConfig<OptimizersConfigDiff> for OptimizersConfig {
             flush_interval_sec,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = diff;
 
         OptimizersConfig {
@@ -273,6 +325,8 @@ impl DiffConfig<OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: max_optimization_threads
                 .map_or(self.max_optimization_threads, From::from),
             prevent_unoptimized: prevent_unoptimized.or(self.prevent_unoptimized),
+            segment_retention_sec: segment_retention_sec.or(self.segment_retention_sec),
+            graph_heal_threshold: graph_heal_threshold.or(self.graph_heal_threshold),
         }
     }
 }
@@ -388,6 +442,9 @@ impl From<HnswConfig> for HnswConfigDiff {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = config;
 
         HnswConfigDiff {
@@ -398,6 +455,9 @@ impl From<HnswConfig> for HnswConfigDiff {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         }
     }
 }
@@ -455,6 +515,8 @@ impl From<OptimizersConfig> for OptimizersConfigDiff {
             flush_interval_sec,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = config;
 
         Self {
@@ -468,6 +530,8 @@ impl From<OptimizersConfig> for OptimizersConfigDiff {
             flush_interval_sec: Some(flush_interval_sec),
             max_optimization_threads: max_optimization_threads.map(MaxOptimizationThreads::Threads),
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         }
     }
 }
@@ -484,6 +548,8 @@ pub enum QuantizationConfigDiff {
     Scalar(ScalarQuantization),
     Product(ProductQuantization),
     Binary(BinaryQuantization),
+    Residual(ResidualQuantization),
+    Anisotropic(AnisotropicQuantization),
     Disabled(Disabled),
 }
 
@@ -499,6 +565,8 @@ impl Validate for QuantizationConfigDiff {
             QuantizationConfigDiff::Scalar(scalar) => scalar.validate(),
             QuantizationConfigDiff::Product(product) => product.validate(),
             QuantizationConfigDiff::Binary(binary) => binary.validate(),
+            QuantizationConfigDiff::Residual(residual) => residual.validate(),
+            QuantizationConfigDiff::Anisotropic(anisotropic) => anisotropic.validate(),
             QuantizationConfigDiff::Disabled(_) => Ok(()),
         }
     }
@@ -557,6 +625,8 @@ mod tests {
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
@@ -582,6 +652,8 @@ mod tests {
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         };
 
         let update: OptimizersConfigDiff = serde_json::from_str(json_diff).unwrap();
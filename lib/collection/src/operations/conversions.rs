@@ -18,11 +18,11 @@ use api::rest::{
 };
 use itertools::Itertools;
 use segment::common::operation_error::OperationError;
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::data_types::vectors::{VectorInternal, VectorStructInternal};
 use segment::types::{
-    Distance, Filter, HnswConfig, MultiVectorConfig, QuantizationConfig, StrictModeConfigOutput,
-    WithPayloadInterface,
+    DimRange, Distance, Filter, HnswConfig, MmapAdvice, MultiVectorConfig, QuantizationConfig,
+    StrictModeConfigOutput, WithPayloadInterface,
 };
 use shard::retrieve::record_internal::RecordInternal;
 use tonic::Status;
@@ -247,6 +247,9 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = value;
         Self {
             m: m.map(|v| v as usize),
@@ -256,6 +259,9 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             on_disk,
             payload_m: payload_m.map(|v| v as usize),
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         }
     }
 }
@@ -270,6 +276,9 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = value;
         Self {
             m: m.map(|v| v as u64),
@@ -279,6 +288,9 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             on_disk,
             payload_m: payload_m.map(|v| v as u64),
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         }
     }
 }
@@ -345,6 +357,8 @@ impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             deprecated_max_optimization_threads,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = value;
         Ok(Self {
             deleted_threshold,
@@ -362,6 +376,8 @@ impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
                     .map(TryFrom::try_from)
                     .transpose()?),
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         })
     }
 }
@@ -379,6 +395,10 @@ impl TryFrom<api::grpc::qdrant::QuantizationConfigDiff> for QuantizationConfigDi
                 Quantization::Scalar(scalar) => Ok(Self::Scalar(scalar.try_into()?)),
                 Quantization::Product(product) => Ok(Self::Product(product.try_into()?)),
                 Quantization::Binary(binary) => Ok(Self::Binary(binary.try_into()?)),
+                Quantization::Residual(residual) => Ok(Self::Residual(residual.try_into()?)),
+                Quantization::Anisotropic(anisotropic) => {
+                    Ok(Self::Anisotropic(anisotropic.try_into()?))
+                }
                 Quantization::Disabled(_) => Ok(Self::new_disabled()),
             },
         }
@@ -407,6 +427,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             quantization_config,
             strict_mode_config,
             metadata,
+            read_only,
         } = config;
 
         let OptimizersConfig {
@@ -420,6 +441,8 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             flush_interval_sec,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = optimizer_config;
 
         let HnswConfig {
@@ -430,6 +453,9 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         } = hnsw_config;
 
         let CollectionParams {
@@ -514,6 +540,9 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     on_disk,
                     payload_m: payload_m.map(|v| v as u64),
                     inline_storage,
+                    varint_links,
+                    acorn,
+                    seed,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(deleted_threshold),
@@ -526,6 +555,8 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     deprecated_max_optimization_threads: max_optimization_threads.map(|x| x as u64),
                     max_optimization_threads: Some(From::from(max_optimization_threads)),
                     prevent_unoptimized,
+                    segment_retention_sec,
+                    graph_heal_threshold,
                 }),
                 wal_config: wal_config.map(|wal_config| {
                     let WalConfig {
@@ -546,6 +577,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 metadata: metadata
                     .map(api::conversions::json::payload_to_proto)
                     .unwrap_or_default(),
+                read_only,
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -638,6 +670,8 @@ impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             deprecated_max_optimization_threads,
             max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         } = optimizer_config;
 
         let converted_max_optimization_threads: Option<usize> =
@@ -660,6 +694,8 @@ impl TryFrom<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             flush_interval_sec: flush_interval_sec.unwrap_or_default(),
             max_optimization_threads: converted_max_optimization_threads,
             prevent_unoptimized,
+            segment_retention_sec,
+            graph_heal_threshold,
         })
     }
 }
@@ -732,6 +768,13 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
             on_disk,
             datatype,
             multivector_config,
+            mmap_advice,
+            dim_range,
+            preserve_norm,
+            quantization_rescore,
+            vamana_config,
+            ivf_config,
+            max_versions,
         } = vector_params;
         Ok(Self {
             size: NonZeroU64::new(size).ok_or_else(|| {
@@ -747,10 +790,34 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
             multivector_config: multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            mmap_advice: convert_mmap_advice_from_proto(mmap_advice)?,
+            dim_range: dim_range.map(DimRange::from),
+            preserve_norm,
+            quantization_rescore,
+            vamana_config: vamana_config.map(Into::into),
+            ivf_config: ivf_config.map(Into::into),
+            max_versions: max_versions.map(|v| v as usize),
         })
     }
 }
 
+fn convert_mmap_advice_from_proto(mmap_advice: Option<i32>) -> Result<Option<MmapAdvice>, Status> {
+    let Some(mmap_advice_int) = mmap_advice else {
+        return Ok(None);
+    };
+    let grpc_mmap_advice =
+        api::grpc::qdrant::MmapAdvice::try_from(mmap_advice_int).map_err(|_| {
+            Status::invalid_argument(format!("Cannot convert mmap advice: {mmap_advice_int}"))
+        })?;
+    match grpc_mmap_advice {
+        api::grpc::qdrant::MmapAdvice::MadvNormal => Ok(Some(MmapAdvice::Normal)),
+        api::grpc::qdrant::MmapAdvice::MadvRandom => Ok(Some(MmapAdvice::Random)),
+        api::grpc::qdrant::MmapAdvice::MadvSequential => Ok(Some(MmapAdvice::Sequential)),
+        api::grpc::qdrant::MmapAdvice::MadvWillNeed => Ok(Some(MmapAdvice::WillNeed)),
+        api::grpc::qdrant::MmapAdvice::UnknownMmapAdvice => Ok(None),
+    }
+}
+
 fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>, Status> {
     if let Some(datatype_int) = datatype {
         let grpc_datatype = api::grpc::qdrant::Datatype::try_from(datatype_int);
@@ -759,6 +826,10 @@ fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>
                 api::grpc::qdrant::Datatype::Uint8 => Ok(Some(Datatype::Uint8)),
                 api::grpc::qdrant::Datatype::Float32 => Ok(Some(Datatype::Float32)),
                 api::grpc::qdrant::Datatype::Float16 => Ok(Some(Datatype::Float16)),
+                api::grpc::qdrant::Datatype::Float64 => Ok(Some(Datatype::Float64)),
+                api::grpc::qdrant::Datatype::Int8 => Ok(Some(Datatype::Int8)),
+                api::grpc::qdrant::Datatype::Bfloat16 => Ok(Some(Datatype::Bfloat16)),
+                api::grpc::qdrant::Datatype::Int4 => Ok(Some(Datatype::Int4)),
                 api::grpc::qdrant::Datatype::Default => Ok(None),
             }
         } else {
@@ -779,11 +850,13 @@ impl TryFrom<api::grpc::qdrant::VectorParamsDiff> for VectorParamsDiff {
             hnsw_config,
             quantization_config,
             on_disk,
+            quantization_rescore,
         } = vector_params;
         Ok(Self {
             hnsw_config: hnsw_config.map(Into::into),
             quantization_config: quantization_config.map(TryInto::try_into).transpose()?,
             on_disk,
+            quantization_rescore,
         })
     }
 }
@@ -794,7 +867,11 @@ impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
     fn try_from(
         sparse_vector_params: api::grpc::qdrant::SparseVectorParams,
     ) -> Result<Self, Self::Error> {
-        let api::grpc::qdrant::SparseVectorParams { index, modifier } = sparse_vector_params;
+        let api::grpc::qdrant::SparseVectorParams {
+            index,
+            modifier,
+            bm25,
+        } = sparse_vector_params;
         Ok(Self {
             index: index
                 .map(|index_config| -> Result<_, Status> {
@@ -810,13 +887,18 @@ impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                     // XXX: Invalid values silently converted to None
                     api::grpc::qdrant::Modifier::try_from(x).ok())
                 .map(Modifier::from),
+            bm25: bm25.map(Bm25Params::from),
         })
     }
 }
 
 impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
     fn from(sparse_vector_params: SparseVectorParams) -> Self {
-        let SparseVectorParams { index, modifier } = sparse_vector_params;
+        let SparseVectorParams {
+            index,
+            modifier,
+            bm25,
+        } = sparse_vector_params;
         Self {
             index: index.map(|index_config| {
                 let SparseIndexParams {
@@ -831,6 +913,7 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
                 }
             }),
             modifier: modifier.map(|modifier| api::grpc::qdrant::Modifier::from(modifier) as i32),
+            bm25: bm25.map(api::grpc::qdrant::Bm25Params::from),
         }
     }
 }
@@ -851,6 +934,12 @@ fn grpc_to_segment_quantization_config(
         api::grpc::qdrant::quantization_config::Quantization::Binary(config) => {
             Ok(QuantizationConfig::Binary(config.try_into()?))
         }
+        api::grpc::qdrant::quantization_config::Quantization::Residual(config) => {
+            Ok(QuantizationConfig::Residual(config.try_into()?))
+        }
+        api::grpc::qdrant::quantization_config::Quantization::Anisotropic(config) => {
+            Ok(QuantizationConfig::Anisotropic(config.try_into()?))
+        }
     }
 }
 
@@ -1404,6 +1493,13 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             on_disk,
             datatype,
             multivector_config,
+            mmap_advice,
+            dim_range,
+            preserve_norm,
+            quantization_rescore,
+            vamana_config,
+            ivf_config,
+            max_versions,
         } = value;
         api::grpc::qdrant::VectorParams {
             size: size.get(),
@@ -1419,6 +1515,18 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             on_disk,
             datatype: datatype.map(|dt| api::grpc::qdrant::Datatype::from(dt).into()),
             multivector_config: multivector_config.map(api::grpc::qdrant::MultiVectorConfig::from),
+            mmap_advice: mmap_advice.map(|advice| match advice {
+                MmapAdvice::Normal => api::grpc::qdrant::MmapAdvice::MadvNormal.into(),
+                MmapAdvice::Random => api::grpc::qdrant::MmapAdvice::MadvRandom.into(),
+                MmapAdvice::Sequential => api::grpc::qdrant::MmapAdvice::MadvSequential.into(),
+                MmapAdvice::WillNeed => api::grpc::qdrant::MmapAdvice::MadvWillNeed.into(),
+            }),
+            dim_range: dim_range.map(api::grpc::qdrant::DimRange::from),
+            preserve_norm,
+            quantization_rescore,
+            vamana_config: vamana_config.map(api::grpc::qdrant::VamanaConfig::from),
+            ivf_config: ivf_config.map(api::grpc::qdrant::IvfConfig::from),
+            max_versions: max_versions.map(|v| v as u64),
         }
     }
 }
@@ -1429,6 +1537,10 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
             Datatype::Float32 => api::grpc::qdrant::Datatype::Float32,
             Datatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
             Datatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+            Datatype::Float64 => api::grpc::qdrant::Datatype::Float64,
+            Datatype::Int8 => api::grpc::qdrant::Datatype::Int8,
+            Datatype::Bfloat16 => api::grpc::qdrant::Datatype::Bfloat16,
+            Datatype::Int4 => api::grpc::qdrant::Datatype::Int4,
         }
     }
 }
@@ -1863,6 +1975,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
             quantization_config,
             strict_mode_config,
             metadata,
+            read_only,
         } = config;
         Ok(Self {
             params: match params {
@@ -1963,6 +2076,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
             } else {
                 Some(api::conversions::json::proto_to_payloads(metadata)?)
             },
+            read_only,
         })
     }
 }
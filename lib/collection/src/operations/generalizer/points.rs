@@ -31,12 +31,14 @@ impl Generalizer for PointRequestInternal {
             ids,
             with_payload,
             with_vector,
+            at_version,
         } = self;
 
         Self {
             ids: ids.clone(),
             with_payload: with_payload.clone(),
             with_vector: with_vector.clone(),
+            at_version: *at_version,
         }
     }
 }
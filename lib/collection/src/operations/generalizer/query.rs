@@ -1,4 +1,5 @@
 use segment::data_types::vectors::{MultiDenseVectorInternal, NamedQuery, VectorInternal};
+use segment::index::hybrid_index::HybridQuery;
 use segment::vector_storage::query::{
     ContextPair, ContextQuery, DiscoverQuery, FeedbackItem, NaiveFeedbackCoefficients,
     NaiveFeedbackQuery, RecoQuery,
@@ -77,11 +78,33 @@ impl Generalizer for ScoringQuery {
             ScoringQuery::OrderBy(_) => self.clone(),
             ScoringQuery::Formula(_) => self.clone(),
             ScoringQuery::Sample(_) => self.clone(),
+            ScoringQuery::Text(_) => self.clone(),
+            ScoringQuery::Hybrid(hybrid) => ScoringQuery::Hybrid(hybrid.remove_details()),
             ScoringQuery::Mmr(mmr) => ScoringQuery::Mmr(mmr.remove_details()),
         }
     }
 }
 
+impl Generalizer for HybridQuery {
+    fn remove_details(&self) -> Self {
+        let Self {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse,
+            alpha,
+        } = self;
+
+        Self {
+            dense_using: dense_using.clone(),
+            dense: vec![dense.len() as f32],
+            sparse_using: sparse_using.clone(),
+            sparse: SparseVector::new(vec![sparse.len() as DimId], vec![0.0]).unwrap(),
+            alpha: *alpha,
+        }
+    }
+}
+
 impl Generalizer for MmrInternal {
     fn remove_details(&self) -> Self {
         let Self {
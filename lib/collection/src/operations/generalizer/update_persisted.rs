@@ -94,12 +94,14 @@ impl Generalizer for PointStructPersisted {
             id: _, // ignore actual id for generalization
             vector,
             payload,
+            expires_at,
         } = self;
 
         Self {
             id: PointIdType::NumId(0),
             vector: vector.remove_details(),
             payload: payload.as_ref().map(|p| p.remove_details()),
+            expires_at: *expires_at,
         }
     }
 }
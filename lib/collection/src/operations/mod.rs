@@ -13,6 +13,7 @@ pub mod snapshot_ops;
 pub mod snapshot_storage_ops;
 #[cfg(feature = "staging")]
 pub mod staging;
+pub mod time_travel;
 pub mod types;
 pub mod universal_query;
 pub mod validation;
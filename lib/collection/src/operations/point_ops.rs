@@ -339,6 +339,7 @@ mod tests {
                 id: *id,
                 vector: VectorStructPersisted::from(vec![0.1, 0.2, 0.3]),
                 payload: None,
+                expires_at: None,
             })
             .collect();
 
@@ -6,6 +6,7 @@ use std::time::Duration;
 use common::load_concurrency::LoadConcurrencyConfig;
 use segment::types::HnswGlobalConfig;
 
+use crate::common::object_storage::ObjectStorageConfig;
 use crate::common::snapshots_manager::SnapshotsConfig;
 use crate::operations::types::NodeType;
 use crate::shards::transfer::ShardTransferMethod;
@@ -39,6 +40,7 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: PathBuf,
     pub snapshots_config: SnapshotsConfig,
+    pub object_storage: ObjectStorageConfig,
     pub hnsw_global_config: HnswGlobalConfig,
     pub load_concurrency_config: LoadConcurrencyConfig,
     pub search_thread_count: usize,
@@ -59,6 +61,7 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: PathBuf::from(DEFAULT_SNAPSHOTS_PATH),
             snapshots_config: default::Default::default(),
+            object_storage: ObjectStorageConfig::default(),
             hnsw_global_config: HnswGlobalConfig::default(),
             load_concurrency_config: LoadConcurrencyConfig::default(),
             search_thread_count: common::defaults::search_thread_count(common::cpu::get_num_cpus()),
@@ -81,6 +84,7 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: PathBuf,
         snapshots_config: SnapshotsConfig,
+        object_storage: ObjectStorageConfig,
         hnsw_global_config: HnswGlobalConfig,
         load_concurrency_config: LoadConcurrencyConfig,
         search_thread_count: usize,
@@ -102,6 +106,7 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            object_storage,
             hnsw_global_config,
             load_concurrency_config,
             search_thread_count,
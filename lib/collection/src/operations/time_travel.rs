@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use segment::types::SeqNumberType;
+use serde::{Deserialize, Serialize};
+
+/// Past state of the collection, which a query is pinned to.
+///
+/// Requires `segment_retention_sec` to be configured, and is only available within the retention
+/// window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AsOf {
+    /// State of the collection at the given time, in RFC 3339 format
+    Timestamp(DateTime<Utc>),
+    /// State of the collection after the operation with the given number was applied
+    Operation(SeqNumberType),
+}
@@ -22,12 +22,13 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::{CancelledError, OperationError};
 use segment::data_types::groups::GroupId;
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, DenseVector};
 use segment::types::{
-    Distance, Filter, HnswConfig, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType,
-    PointIdType, QuantizationConfig, SearchParams, SeqNumberType, ShardKey,
-    SparseVectorStorageType, StrictModeConfigOutput, VectorName, VectorNameBuf,
+    DimRange, Distance, Filter, HnswConfig, IvfConfig, MmapAdvice, MultiVectorConfig, Payload,
+    PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig, QuantizationQualityStats,
+    SearchParams, SegmentDiskUsage, SegmentMemoryUsage, SeqNumberType, ShardKey,
+    SparseVectorStorageType, StrictModeConfigOutput, VamanaConfig, VectorName, VectorNameBuf,
     VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
@@ -178,6 +179,9 @@ pub struct CollectionConfig {
     /// such as creation time, migration data, inference model info, etc.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Payload>,
+    /// If true - write operations on points of the collection are rejected
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
 impl From<CollectionConfigInternal> for CollectionConfig {
@@ -192,6 +196,7 @@ impl From<CollectionConfigInternal> for CollectionConfig {
             // Internal UUID to identify unique collections in consensus snapshots
             uuid: _,
             metadata,
+            read_only,
         } = config;
 
         CollectionConfig {
@@ -202,6 +207,7 @@ impl From<CollectionConfigInternal> for CollectionConfig {
             quantization_config,
             strict_mode_config: strict_mode_config.map(StrictModeConfigOutput::from),
             metadata,
+            read_only,
         }
     }
 }
@@ -324,6 +330,76 @@ pub struct ShardInfoInternal {
     pub update_queue: ShardUpdateQueueInfo,
 }
 
+/// Estimated RAM usage of the collection on this peer, by component
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CollectionMemoryUsage {
+    /// Sum of all components
+    pub total_bytes: usize,
+    #[serde(flatten)]
+    pub components: SegmentMemoryUsage,
+}
+
+impl From<SegmentMemoryUsage> for CollectionMemoryUsage {
+    fn from(components: SegmentMemoryUsage) -> Self {
+        Self {
+            total_bytes: components.total_bytes(),
+            components,
+        }
+    }
+}
+
+/// Disk space taken by the collection on this peer, by component
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CollectionDiskUsage {
+    /// Sum of all components
+    pub total_bytes: u64,
+    #[serde(flatten)]
+    pub segments: SegmentDiskUsage,
+    /// Write-ahead logs of local shards
+    pub wal_bytes: u64,
+    /// Snapshots of the collection, if stored on local disk
+    pub snapshots_bytes: u64,
+}
+
+impl CollectionDiskUsage {
+    pub fn new(segments: SegmentDiskUsage, wal_bytes: u64, snapshots_bytes: u64) -> Self {
+        Self {
+            total_bytes: segments.total_bytes() + wal_bytes + snapshots_bytes,
+            segments,
+            wal_bytes,
+            snapshots_bytes,
+        }
+    }
+}
+
+/// Estimated quality of quantized vectors of the collection on this peer
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CollectionQuantizationQuality {
+    /// Quality by vector name, vectors without quantization are omitted
+    pub vectors: BTreeMap<VectorNameBuf, QuantizationQuality>,
+}
+
+/// Agreement of search with quantized vectors with exact search on original vectors
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct QuantizationQuality {
+    /// Number of sampled points, used as queries
+    pub sampled_queries: usize,
+    /// Average share of exact top results, which are also found with quantized vectors
+    pub recall: Option<f64>,
+    /// Pearson correlation of quantized scores with exact ones
+    pub score_correlation: Option<f64>,
+}
+
+impl From<QuantizationQualityStats> for QuantizationQuality {
+    fn from(stats: QuantizationQualityStats) -> Self {
+        Self {
+            sampled_queries: stats.queries,
+            recall: stats.recall(),
+            score_correlation: stats.score_correlation(),
+        }
+    }
+}
+
 /// Current clustering distribution for the collection
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct CollectionClusterInfo {
@@ -578,6 +654,11 @@ pub struct PointRequestInternal {
     /// Options for specifying which vectors to include into response. Default is false.
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// Return vectors as they were after the operation with this version.
+    /// Only available for vectors with `max_versions` configured, other vectors are returned only
+    /// if the point was not changed after this version. Payloads are always the latest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub at_version: Option<SeqNumberType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -1135,6 +1216,9 @@ impl From<OperationError> for CollectionError {
             OperationError::VectorNameNotExists { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
+            OperationError::VectorNameRemoved { .. } => Self::BadInput {
+                description: format!("{err}"),
+            },
             OperationError::PointIdError { missed_point_id } => {
                 Self::PointNotFound { missed_point_id }
             }
@@ -1171,6 +1255,9 @@ impl From<OperationError> for CollectionError {
             },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
             OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingFullTextIndexForTextQuery { .. } => {
+                Self::bad_input(format!("{err}"))
+            }
             OperationError::VariableTypeError { .. } => Self::bad_input(format!("{err}")),
             OperationError::NonFiniteNumber { .. } => Self::bad_input(format!("{err}")),
             OperationError::RocksDbColumnFamilyNotFound { .. } => Self::ServiceError {
@@ -1400,6 +1487,10 @@ pub enum Datatype {
     Float32,
     Uint8,
     Float16,
+    Float64,
+    Int8,
+    Bfloat16,
+    Int4,
 }
 
 impl From<Datatype> for VectorStorageDatatype {
@@ -1408,6 +1499,10 @@ impl From<Datatype> for VectorStorageDatatype {
             Datatype::Float32 => VectorStorageDatatype::Float32,
             Datatype::Uint8 => VectorStorageDatatype::Uint8,
             Datatype::Float16 => VectorStorageDatatype::Float16,
+            Datatype::Float64 => VectorStorageDatatype::Float64,
+            Datatype::Int8 => VectorStorageDatatype::Int8,
+            Datatype::Bfloat16 => VectorStorageDatatype::Bfloat16,
+            Datatype::Int4 => VectorStorageDatatype::Int4,
         }
     }
 }
@@ -1418,6 +1513,12 @@ impl From<Datatype> for VectorStorageDatatype {
 )]
 #[serde(rename_all = "snake_case")]
 #[anonymize(false)]
+#[validate(schema(function = "validate_vector_params_datatype"))]
+#[validate(schema(function = "validate_vector_params_dim_range"))]
+#[validate(schema(function = "validate_vector_params_preserve_norm"))]
+#[validate(schema(function = "validate_vector_params_multivector_config"))]
+#[validate(schema(function = "validate_vector_params_index_type"))]
+#[validate(schema(function = "validate_vector_params_max_versions"))]
 pub struct VectorParams {
     /// Size of a vectors used
     #[validate(custom(function = "validate_nonzerou64_range_min_1_max_65536"))]
@@ -1451,10 +1552,71 @@ pub struct VectorParams {
     ///   2 bytes.
     /// - For `uint8` datatype - vectors are stored as unsigned 8-bit integers, 1 byte.
     ///   It expects vector elements to be in range `[0, 255]`.
+    /// - For `float64` datatype - vectors are stored as double-precision floating point numbers,
+    ///   8 bytes. Only available for dense vectors stored in mmap or chunked mmap storage.
+    /// - For `int8` datatype - vectors are stored as signed 8-bit integers, 1 byte, with per-vector
+    ///   scale and zero-point. Vectors are quantized on insertion, so the actual vector data does
+    ///   not need to conform to the byte range. Only available for dense vectors.
+    /// - For `bfloat16` datatype - vectors are stored as brain floating point numbers, 2 bytes.
+    ///   Keeps the range of `float32` at a lower precision. Only available for dense vectors
+    ///   stored in mmap or chunked mmap storage.
+    /// - For `int4` datatype - vectors are stored as unsigned 4-bit integers, two per byte, with
+    ///   per-vector scale and zero-point. Vectors are quantized on insertion. Takes half of the
+    ///   memory of `int8` at a lower precision, best used with rescoring. Only available for
+    ///   dense vectors.
     pub datatype: Option<Datatype>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivector_config: Option<MultiVectorConfig>,
+
+    /// Hint for the OS on how memory-mapped vector storage is going to be accessed.
+    /// Only applies to vectors served from mmap storages.
+    /// If none - the default for the storage type is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmap_advice: Option<MmapAdvice>,
+
+    /// Range of dimensions of stored vectors, allows to store vectors shorter than `size`,
+    /// e.g. truncated Matryoshka embeddings. Similarity is computed over the stored prefix of
+    /// vectors, query vectors must always have the full `size`.
+    /// If none - all vectors must have exactly `size` dimensions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim_range: Option<DimRange>,
+
+    /// If true, norms of vectors are kept next to the normalized vectors, so that vectors are
+    /// retrieved as they were inserted. Scoring still uses normalized vectors.
+    /// Only available for `Cosine` distance and dense vectors of floating point datatypes.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preserve_norm: Option<bool>,
+
+    /// Default for `rescore` of quantization search params, used by searches on this vector
+    /// which don't specify it explicitly.
+    /// If none - rescoring is decided by the segment, based on the storage of original vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization_rescore: Option<bool>,
+
+    /// Custom params for Vamana (DiskANN) index. If set, optimized segments are indexed with
+    /// Vamana instead of HNSW, which keeps the graph on disk and requires less RAM.
+    /// If none - HNSW index is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub vamana_config: Option<VamanaConfig>,
+
+    /// Custom params for IVF (inverted file) index. If set, optimized segments are indexed with
+    /// IVF instead of HNSW, which trades recall for faster builds and higher search throughput.
+    /// If none - HNSW index is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub ivf_config: Option<IvfConfig>,
+
+    /// Number of previous versions of every vector to keep, so that points can be retrieved with
+    /// vectors as they were at an older operation, see `at_version` of retrieve requests.
+    /// History is kept per segment and is reset when segments are optimized.
+    /// Only vectors are versioned, payloads are always the latest.
+    /// Not available for multivectors and vectors of variable dimension.
+    /// If none or 0 - no history is kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_versions: Option<usize>,
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1464,6 +1626,131 @@ pub fn validate_nonzerou64_range_min_1_max_65536(
     validate_range_generic(value.get(), Some(1), Some(65536))
 }
 
+fn validate_vector_params_datatype(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.multivector_config.is_none() {
+        return Ok(());
+    }
+    match params.datatype {
+        Some(Datatype::Float64) => Err(ValidationError::new(
+            "float64 datatype is not supported for multivectors",
+        )),
+        Some(Datatype::Int8) => Err(ValidationError::new(
+            "int8 datatype is not supported for multivectors",
+        )),
+        Some(Datatype::Bfloat16) => Err(ValidationError::new(
+            "bfloat16 datatype is not supported for multivectors",
+        )),
+        Some(Datatype::Int4) => Err(ValidationError::new(
+            "int4 datatype is not supported for multivectors",
+        )),
+        Some(Datatype::Float32 | Datatype::Uint8 | Datatype::Float16) | None => Ok(()),
+    }
+}
+
+fn validate_vector_params_dim_range(params: &VectorParams) -> Result<(), ValidationError> {
+    let Some(dim_range) = params.dim_range else {
+        return Ok(());
+    };
+    if params.multivector_config.is_some() {
+        return Err(ValidationError::new(
+            "dim_range is not supported for multivectors",
+        ));
+    }
+    if params.datatype == Some(Datatype::Int8) {
+        return Err(ValidationError::new(
+            "dim_range is not supported for int8 datatype",
+        ));
+    }
+    if params.datatype == Some(Datatype::Int4) {
+        return Err(ValidationError::new(
+            "dim_range is not supported for int4 datatype",
+        ));
+    }
+    dim_range
+        .check_size(params.size.get() as usize)
+        .map_err(|err| ValidationError::new("invalid_dim_range").with_message(err.into()))
+}
+
+fn validate_vector_params_preserve_norm(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.preserve_norm != Some(true) {
+        return Ok(());
+    }
+    if params.distance != Distance::Cosine {
+        return Err(ValidationError::new(
+            "preserve_norm is only supported for Cosine distance",
+        ));
+    }
+    if params.multivector_config.is_some() {
+        return Err(ValidationError::new(
+            "preserve_norm is not supported for multivectors",
+        ));
+    }
+    if params.dim_range.is_some() {
+        return Err(ValidationError::new(
+            "preserve_norm is not supported together with dim_range",
+        ));
+    }
+    match params.datatype {
+        Some(Datatype::Uint8) => Err(ValidationError::new(
+            "preserve_norm is not supported for uint8 datatype",
+        )),
+        Some(Datatype::Int8) => Err(ValidationError::new(
+            "preserve_norm is not supported for int8 datatype",
+        )),
+        Some(Datatype::Int4) => Err(ValidationError::new(
+            "preserve_norm is not supported for int4 datatype",
+        )),
+        Some(Datatype::Float32 | Datatype::Float16 | Datatype::Float64 | Datatype::Bfloat16)
+        | None => Ok(()),
+    }
+}
+
+fn validate_vector_params_multivector_config(params: &VectorParams) -> Result<(), ValidationError> {
+    let Some(multivector_config) = &params.multivector_config else {
+        return Ok(());
+    };
+    if multivector_config.max_vectors_per_point == Some(0) {
+        return Err(ValidationError::new(
+            "max_vectors_per_point must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_vector_params_index_type(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.ivf_config.is_none() {
+        return Ok(());
+    }
+    if params.vamana_config.is_some() {
+        return Err(ValidationError::new(
+            "only one of vamana_config and ivf_config can be set",
+        ));
+    }
+    if params.multivector_config.is_some() {
+        return Err(ValidationError::new(
+            "IVF index is not supported for multivectors",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_vector_params_max_versions(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.max_versions.unwrap_or(0) == 0 {
+        return Ok(());
+    }
+    if params.multivector_config.is_some() {
+        return Err(ValidationError::new(
+            "max_versions is not supported for multivectors",
+        ));
+    }
+    if params.dim_range.is_some() {
+        return Err(ValidationError::new(
+            "max_versions is not supported together with dim_range",
+        ));
+    }
+    Ok(())
+}
+
 /// Is considered empty if `None` or if diff has no field specified
 fn is_hnsw_diff_empty(hnsw_config: &Option<HnswConfigDiff>) -> bool {
     hnsw_config.is_none() || *hnsw_config == Some(HnswConfigDiff::default())
@@ -1483,6 +1770,13 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Parameters of BM25 ranking, used with the `idf` modifier.
+    /// Values of the sparse vectors are treated as term frequencies, saturated and normalized by
+    /// the vector length. If none - only IDF is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub bm25: Option<Bm25Params>,
 }
 
 impl SparseVectorParams {
@@ -1752,6 +2046,13 @@ impl From<&VectorParams> for VectorParamsBase {
             on_disk: _,
             datatype: _,
             multivector_config: _,
+            mmap_advice: _,
+            dim_range: _,
+            preserve_norm: _,
+            quantization_rescore: _,
+            vamana_config: _,
+            ivf_config: _,
+            max_versions: _,
         } = params;
         Self {
             size: size.get() as _, // TODO!?
@@ -1770,6 +2071,10 @@ impl From<&segment::types::VectorDataConfig> for VectorParamsBase {
             quantization_config: _,
             multivector_config: _,
             datatype: _,
+            mmap_advice: _,
+            dim_range: _,
+            preserve_norm: _,
+            max_versions: _,
         } = config;
         Self { size, distance }
     }
@@ -1793,6 +2098,9 @@ pub struct VectorParamsDiff {
     /// If true, vectors are served from disk, improving RAM usage at the cost of latency
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+    /// Update default for `rescore` of quantization search params. If none - it is left unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantization_rescore: Option<bool>,
 }
 
 /// Vector update params for multiple vectors
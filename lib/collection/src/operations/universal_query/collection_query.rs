@@ -4,7 +4,9 @@ use common::types::ScoreType;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::{TextQuery, TextQueryInternal};
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, NamedQuery, VectorInternal, VectorRef};
+use segment::index::hybrid_index::HybridQuery;
 use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
 use segment::types::{
@@ -102,6 +104,12 @@ pub enum Query {
 
     /// Sample points
     Sample(SampleInternal),
+
+    /// Score points by BM25 relevance to a full-text query
+    Text(TextQuery),
+
+    /// Score points by a dense and a sparse vector at once
+    Hybrid(HybridQuery),
 }
 
 impl Query {
@@ -126,6 +134,8 @@ impl Query {
             Query::OrderBy(order_by) => ScoringQuery::OrderBy(order_by),
             Query::Formula(formula) => ScoringQuery::Formula(ParsedFormula::try_from(formula)?),
             Query::Sample(sample) => ScoringQuery::Sample(sample),
+            Query::Text(text) => ScoringQuery::Text(TextQueryInternal::from(text)),
+            Query::Hybrid(hybrid) => ScoringQuery::Hybrid(hybrid),
         };
 
         Ok(scoring_query)
@@ -138,7 +148,12 @@ impl Query {
                 .into_iter()
                 .copied()
                 .collect(),
-            Self::Fusion(_) | Self::OrderBy(_) | Self::Formula(_) | Self::Sample(_) => Vec::new(),
+            Self::Fusion(_)
+            | Self::OrderBy(_)
+            | Self::Formula(_)
+            | Self::Sample(_)
+            | Self::Text(_)
+            | Self::Hybrid(_) => Vec::new(),
         }
     }
 }
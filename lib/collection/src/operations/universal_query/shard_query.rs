@@ -31,6 +31,10 @@ pub fn query_result_order(
             // Euclidean scores can be negated within the formula
             ScoringQuery::Formula(_formula) => Some(Order::LargeBetter),
             ScoringQuery::OrderBy(order_by) => Some(Order::from(order_by.direction())),
+            // BM25 scores are larger for more relevant points
+            ScoringQuery::Text(_) => Some(Order::LargeBetter),
+            // Fused scores are larger for more relevant points
+            ScoringQuery::Hybrid(_) => Some(Order::LargeBetter),
             // Random sample does not require ordering
             ScoringQuery::Sample(SampleInternal::Random) => None,
             // MMR cannot be reordered
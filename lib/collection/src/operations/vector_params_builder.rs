@@ -1,6 +1,8 @@
 use std::num::NonZeroU64;
 
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
+use segment::types::{
+    DimRange, Distance, IvfConfig, MmapAdvice, MultiVectorConfig, QuantizationConfig, VamanaConfig,
+};
 
 use crate::operations::config_diff::HnswConfigDiff;
 use crate::operations::types::{Datatype, VectorParams};
@@ -20,6 +22,13 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivector_config: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                quantization_rescore: None,
+                vamana_config: None,
+                ivf_config: None,
+                max_versions: None,
             },
         }
     }
@@ -49,6 +58,41 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_mmap_advice(mut self, mmap_advice: MmapAdvice) -> Self {
+        self.vector_params.mmap_advice = Some(mmap_advice);
+        self
+    }
+
+    pub fn with_dim_range(mut self, dim_range: DimRange) -> Self {
+        self.vector_params.dim_range = Some(dim_range);
+        self
+    }
+
+    pub fn with_preserve_norm(mut self, preserve_norm: bool) -> Self {
+        self.vector_params.preserve_norm = Some(preserve_norm);
+        self
+    }
+
+    pub fn with_quantization_rescore(mut self, quantization_rescore: bool) -> Self {
+        self.vector_params.quantization_rescore = Some(quantization_rescore);
+        self
+    }
+
+    pub fn with_vamana_config(mut self, vamana_config: VamanaConfig) -> Self {
+        self.vector_params.vamana_config = Some(vamana_config);
+        self
+    }
+
+    pub fn with_ivf_config(mut self, ivf_config: IvfConfig) -> Self {
+        self.vector_params.ivf_config = Some(ivf_config);
+        self
+    }
+
+    pub fn with_max_versions(mut self, max_versions: usize) -> Self {
+        self.vector_params.max_versions = Some(max_versions);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
@@ -485,6 +485,7 @@ mod test {
                     id: 1.into(),
                     vector: api::rest::VectorStruct::Single(vec![0.1, 0.2, 0.3, 0.4]),
                     payload: None,
+                    ttl: None,
                 };
                 5
             ],
@@ -500,6 +501,7 @@ mod test {
                     id: 1.into(),
                     vector: api::rest::VectorStruct::Single(vec![0.1, 0.2, 0.3, 0.4]),
                     payload: None,
+                    ttl: None,
                 };
                 4
             ],
@@ -603,6 +605,7 @@ mod test {
             strict_mode_config: Some(strict_mode_config.clone()),
             uuid: None,
             metadata: None,
+            read_only: false,
         };
 
         let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -98,6 +98,22 @@ pub struct OptimizersConfig {
     /// Default is disabled.
     #[serde(default)]
     pub prevent_unoptimized: Option<bool>,
+
+    /// Keep segments, replaced by optimizations, for this number of seconds.
+    /// Retained segments allow to run queries pinned to a past timestamp or operation number.
+    /// Retained segments take additional disk space.
+    /// Default is disabled.
+    #[serde(default)]
+    pub segment_retention_sec: Option<u64>,
+
+    /// The minimal fraction of vectors deleted from the HNSW graph of a segment since it was built,
+    /// required to reroute graph links around deleted points in place.
+    /// Healing runs while there is nothing to optimize, and keeps search quality of segments
+    /// until they are vacuumed. Should be lower than `deleted_threshold`.
+    /// Default is disabled.
+    #[serde(default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub graph_heal_threshold: Option<f64>,
 }
 
 fn default_deleted_threshold() -> f64 {
@@ -122,6 +138,8 @@ impl OptimizersConfig {
             flush_interval_sec: 60,
             max_optimization_threads: Some(0),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         }
     }
 
@@ -198,6 +216,13 @@ pub fn build_segment_optimizer_config(
                 on_disk,
                 datatype,
                 multivector_config,
+                mmap_advice,
+                dim_range,
+                preserve_norm,
+                quantization_rescore: _,
+                vamana_config,
+                ivf_config,
+                max_versions,
             } = params;
 
             (
@@ -207,12 +232,18 @@ pub fn build_segment_optimizer_config(
                     distance: *distance,
                     on_disk: *on_disk,
                     hnsw_config: global_hnsw_config.update_opt(hnsw_config.as_ref()),
+                    vamana_config: *vamana_config,
+                    ivf_config: *ivf_config,
                     quantization_config: quantization_config
                         .as_ref()
                         .or(global_quantization_config.as_ref())
                         .cloned(),
                     multivector_config: *multivector_config,
                     datatype: datatype.map(VectorStorageDatatype::from),
+                    mmap_advice: *mmap_advice,
+                    dim_range: *dim_range,
+                    preserve_norm: *preserve_norm,
+                    max_versions: *max_versions,
                 },
             )
         })
@@ -225,7 +256,11 @@ pub fn build_segment_optimizer_config(
             config
                 .iter()
                 .map(|(name, params)| {
-                    let SparseVectorParams { index, modifier } = params;
+                    let SparseVectorParams {
+                        index,
+                        modifier,
+                        bm25,
+                    } = params;
 
                     (
                         name.clone(),
@@ -237,6 +272,7 @@ pub fn build_segment_optimizer_config(
                                 .map(VectorStorageDatatype::from),
                             storage_type: params.storage_type(),
                             modifier: *modifier,
+                            bm25: *bm25,
                         },
                     )
                 })
@@ -4,12 +4,11 @@ use std::time::Duration;
 
 use api::grpc::qdrant::WaitOnConsensusCommitRequest;
 use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
-use api::grpc::transport_channel_pool::{AddTimeout, TransportChannelPool};
+use api::grpc::transport_channel_pool::{PeerChannel, TransportChannelPool};
 use futures::Future;
 use futures::future::try_join_all;
 use semver::Version;
-use tonic::codegen::InterceptedService;
-use tonic::transport::{Channel, Uri};
+use tonic::transport::Uri;
 use tonic::{Request, Status};
 use url::Url;
 
@@ -28,11 +27,18 @@ pub struct ChannelService {
     /// Indicates whether the TLS is enabled for the public REST API.
     pub rest_tls_enabled: bool,
 
-    /// Instance wide API key if configured, must be used with care.
-    pub api_key: Option<String>,
+    /// Instance wide API keys if configured, must be used with care.
+    /// Shared between clones, as keys can be rotated at runtime.
+    local_api_keys: Arc<parking_lot::RwLock<LocalApiKeys>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct LocalApiKeys {
+    /// Instance wide API key
+    api_key: Option<String>,
 
     /// Alternative API key, works the same as `api_key`. Intended for rolling key updates.
-    pub alt_api_key: Option<String>,
+    alt_api_key: Option<String>,
 }
 
 impl ChannelService {
@@ -49,9 +55,25 @@ impl ChannelService {
             channel_pool: Default::default(),
             current_rest_port,
             rest_tls_enabled,
+            local_api_keys: Arc::new(parking_lot::RwLock::new(LocalApiKeys {
+                api_key,
+                alt_api_key,
+            })),
+        }
+    }
+
+    /// Any of the API keys of this instance, which is accepted by it.
+    pub fn local_api_key(&self) -> Option<String> {
+        let keys = self.local_api_keys.read();
+        keys.api_key.clone().or_else(|| keys.alt_api_key.clone())
+    }
+
+    /// Replace API keys of this instance after rotation.
+    pub fn set_local_api_keys(&self, api_key: Option<String>, alt_api_key: Option<String>) {
+        *self.local_api_keys.write() = LocalApiKeys {
             api_key,
             alt_api_key,
-        }
+        };
     }
 
     pub async fn remove_peer(&self, peer_id: PeerId) {
@@ -153,7 +175,7 @@ impl ChannelService {
     pub async fn with_qdrant_client<T, O: Future<Output = Result<T, Status>>>(
         &self,
         peer_id: PeerId,
-        f: impl Fn(QdrantInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
+        f: impl Fn(QdrantInternalClient<PeerChannel>) -> O,
     ) -> CollectionResult<T> {
         let address = self
             .id_to_address
@@ -272,8 +294,7 @@ impl Default for ChannelService {
             channel_pool: Default::default(),
             current_rest_port: 6333,
             rest_tls_enabled: false,
-            api_key: None,
-            alt_api_key: None,
+            local_api_keys: Default::default(),
         }
     }
 }
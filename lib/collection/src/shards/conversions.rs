@@ -1,29 +1,32 @@
 use api::conversions::json::payload_to_proto;
 use api::grpc::conversions::convert_shard_key_from_grpc_opt;
+use api::grpc::qdrant::points_change::Operation;
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
     DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
+    PointsChange, PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal,
+    SyncPoints, SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
     UpsertPointsInternal, Vectors, VectorsSelector,
 };
 use segment::data_types::vectors::VectorStructInternal;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, PayloadFieldSchema, PointIdType, ScoredPoint, VectorNameBuf};
+use segment::types::{
+    Filter, PayloadFieldSchema, PointIdType, ScoredPoint, SeqNumberType, VectorNameBuf,
+};
 use tonic::Status;
 
 use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
 use crate::operations::point_ops::{
-    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointSyncOperation,
-    WriteOrdering,
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointOperations,
+    PointSyncOperation, WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
-use crate::operations::vector_ops::UpdateVectorsOp;
-use crate::operations::{ClockTag, CreateIndex};
+use crate::operations::vector_ops::{UpdateVectorsOp, VectorOperations};
+use crate::operations::{ClockTag, CollectionUpdateOperations, CreateIndex};
 use crate::shards::shard::ShardId;
 use crate::shards::shard_trait::WaitUntil;
 
@@ -496,6 +499,146 @@ pub fn internal_delete_index(
     }
 }
 
+/// Convert operation, committed to the shard, into the change of points, streamed to clients.
+///
+/// Returns `None` for operations, which don't change points by themselves, like operations on
+/// payload indexes and synchronization of points during shard transfers.
+pub fn points_change_from_operation(
+    collection_name: String,
+    shard_id: ShardId,
+    position: SeqNumberType,
+    operation: CollectionUpdateOperations,
+) -> CollectionResult<Option<PointsChange>> {
+    let wait = WaitUntil::Wal;
+
+    let operation = match operation {
+        CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
+            PointOperations::UpsertPoints(points) => {
+                internal_upsert_points(None, None, collection_name, points, wait, None, None)?
+                    .upsert_points
+                    .map(Operation::Upsert)
+            }
+            PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                internal_conditional_upsert_points(
+                    None,
+                    None,
+                    collection_name,
+                    conditional_upsert,
+                    wait,
+                    None,
+                    None,
+                )?
+                .upsert_points
+                .map(Operation::Upsert)
+            }
+            PointOperations::DeletePoints { ids } => {
+                internal_delete_points(None, None, collection_name, ids, wait, None, None)
+                    .delete_points
+                    .map(Operation::Delete)
+            }
+            PointOperations::DeletePointsByFilter(filter) => internal_delete_points_by_filter(
+                None,
+                None,
+                collection_name,
+                filter,
+                wait,
+                None,
+                None,
+            )
+            .delete_points
+            .map(Operation::Delete),
+            PointOperations::SyncPoints(_) => None,
+        },
+        CollectionUpdateOperations::VectorOperation(vector_ops) => match vector_ops {
+            VectorOperations::UpdateVectors(update_vectors) => internal_update_vectors(
+                None,
+                None,
+                collection_name,
+                update_vectors,
+                wait,
+                None,
+                None,
+            )?
+            .update_vectors
+            .map(Operation::UpdateVectors),
+            VectorOperations::DeleteVectors(ids, vector_names) => internal_delete_vectors(
+                None,
+                None,
+                collection_name,
+                ids.points,
+                vector_names,
+                wait,
+                None,
+                None,
+            )
+            .delete_vectors
+            .map(Operation::DeleteVectors),
+            VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
+                internal_delete_vectors_by_filter(
+                    None,
+                    None,
+                    collection_name,
+                    filter,
+                    vector_names,
+                    wait,
+                    None,
+                    None,
+                )
+                .delete_vectors
+                .map(Operation::DeleteVectors)
+            }
+        },
+        CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
+            PayloadOps::SetPayload(set_payload) => {
+                internal_set_payload(None, None, collection_name, set_payload, wait, None, None)
+                    .set_payload_points
+                    .map(Operation::SetPayload)
+            }
+            PayloadOps::OverwritePayload(set_payload) => {
+                internal_set_payload(None, None, collection_name, set_payload, wait, None, None)
+                    .set_payload_points
+                    .map(Operation::OverwritePayload)
+            }
+            PayloadOps::DeletePayload(delete_payload) => internal_delete_payload(
+                None,
+                None,
+                collection_name,
+                delete_payload,
+                wait,
+                None,
+                None,
+            )
+            .delete_payload_points
+            .map(Operation::DeletePayload),
+            PayloadOps::ClearPayload { points } => {
+                internal_clear_payload(None, None, collection_name, points, wait, None, None)
+                    .clear_payload_points
+                    .map(Operation::ClearPayload)
+            }
+            PayloadOps::ClearPayloadByFilter(filter) => internal_clear_payload_by_filter(
+                None,
+                None,
+                collection_name,
+                filter,
+                wait,
+                None,
+                None,
+            )
+            .clear_payload_points
+            .map(Operation::ClearPayload),
+        },
+        CollectionUpdateOperations::FieldIndexOperation(_) => None,
+        #[cfg(feature = "staging")]
+        CollectionUpdateOperations::StagingOperation(_) => None,
+    };
+
+    Ok(operation.map(|operation| PointsChange {
+        shard_id,
+        position,
+        operation: Some(operation),
+    }))
+}
+
 pub fn try_scored_point_from_grpc(
     point: api::grpc::qdrant::ScoredPoint,
     with_payload: bool,
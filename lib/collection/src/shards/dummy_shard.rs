@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::DeferredBehavior;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, SizeStats, WithPayload, WithPayloadInterface, WithVector,
@@ -208,5 +209,15 @@ impl ShardOperation for DummyShard {
         self.dummy("facet")
     }
 
+    async fn text_query_statistics(
+        &self,
+        _: Arc<TextQuery>,
+        _search_runtime_handle: &Handle,
+        _: Option<Duration>,
+        _: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        self.dummy("text_query_statistics")
+    }
+
     async fn stop_gracefully(self) {}
 }
@@ -9,6 +9,7 @@ use common::tar_ext;
 use common::types::{DeferredBehavior, TelemetryDetail};
 use parking_lot::Mutex as ParkingMutex;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
@@ -282,10 +283,11 @@ impl ForwardProxyShard {
 
         let next_page_offset = (batch.len() >= limit).then(|| batch.pop().unwrap().id);
 
-        let points = batch
+        let mut points = batch
             .into_iter()
             .map(PointStructPersisted::try_from)
             .collect::<Result<Vec<PointStructPersisted>, String>>()?;
+        self.set_point_expirations(&mut points);
 
         Ok((points, next_page_offset))
     }
@@ -363,6 +365,7 @@ impl ForwardProxyShard {
             ids,
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
+            at_version: None,
         };
         let batch = self
             .wrapped_shard
@@ -377,14 +380,33 @@ impl ForwardProxyShard {
             )
             .await?;
 
-        let points = batch
+        let mut points = batch
             .into_iter()
             .map(PointStructPersisted::try_from)
             .collect::<Result<Vec<PointStructPersisted>, String>>()?;
+        self.set_point_expirations(&mut points);
 
         Ok((points, next_page_offset))
     }
 
+    /// Set expiration times of points to transfer, which are not part of retrieved records
+    fn set_point_expirations(&self, points: &mut [PointStructPersisted]) {
+        let segments = self.wrapped_shard.segments();
+        let segments = segments.read();
+        for point in points {
+            // Take the expiration of the latest version of the point
+            point.expires_at = segments
+                .iter()
+                .filter_map(|(_, segment)| {
+                    let segment = segment.get_read().read();
+                    let version = segment.point_version(point.id)?;
+                    Some((version, segment.point_expiration(point.id)))
+                })
+                .max_by_key(|(version, _)| *version)
+                .and_then(|(_, expires_at)| expires_at);
+        }
+    }
+
     pub fn deconstruct(self) -> (LocalShard, RemoteShard) {
         (self.wrapped_shard, self.remote_shard)
     }
@@ -733,6 +755,19 @@ impl ShardOperation for ForwardProxyShard {
             .await
     }
 
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .text_query_statistics(query, search_runtime_handle, timeout, hw_measurement_acc)
+            .await
+    }
+
     async fn stop_gracefully(self) {
         self.wrapped_shard.stop_gracefully().await
     }
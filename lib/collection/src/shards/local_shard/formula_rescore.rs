@@ -7,6 +7,7 @@ use segment::data_types::query_context::FormulaContext;
 use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::ScoredPoint;
 use shard::common::stopping_guard::StoppingGuard;
+use shard::segment_holder::locked::LockedSegmentHolder;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
@@ -15,6 +16,7 @@ use crate::operations::types::{CollectionError, CollectionResult};
 impl LocalShard {
     pub async fn rescore_with_formula(
         &self,
+        segments: &LockedSegmentHolder,
         formula: ParsedFormula,
         prefetches_results: Vec<Vec<ScoredPoint>>,
         limit: usize,
@@ -35,7 +37,7 @@ impl LocalShard {
         let arc_ctx = Arc::new(ctx);
 
         let future = SegmentsSearcher::rescore_with_formula(
-            self.segments.clone(),
+            segments.clone(),
             arc_ctx,
             &self.search_runtime,
             hw_measurement_acc,
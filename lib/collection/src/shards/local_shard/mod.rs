@@ -6,6 +6,7 @@ pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
+pub(super) mod time_travel;
 
 mod snapshot;
 mod telemetry;
@@ -55,6 +56,7 @@ use shard::files::{NEWEST_CLOCKS_PATH, OLDEST_CLOCKS_PATH, ShardDataFiles};
 use shard::operations::CollectionUpdateOperations;
 use shard::operations::optimization::{OptimizationSegmentInfo, PendingOptimization};
 use shard::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use shard::retention::SegmentRetention;
 use shard::segment_holder::locked::LockedSegmentHolder;
 use shard::wal::SerdeWal;
 use tokio::runtime::Handle;
@@ -157,6 +159,7 @@ impl LocalShard {
             newest_clocks_path: newest_clocks_path_from,
             oldest_clocks_path: oldest_clocks_path_from,
             applied_seq_path: applied_seq_path_from,
+            retained_segments_path: retained_segments_path_from,
         } = shard_data_files_from;
 
         let ShardDataFiles {
@@ -165,6 +168,7 @@ impl LocalShard {
             newest_clocks_path: newest_clocks_path_to,
             oldest_clocks_path: oldest_clocks_path_to,
             applied_seq_path: applied_seq_path_to,
+            retained_segments_path: retained_segments_path_to,
         } = shard_data_files_to;
 
         move_dir(wal_from, wal_to).await?;
@@ -182,6 +186,10 @@ impl LocalShard {
             move_file(applied_seq_path_from, applied_seq_path_to).await?;
         }
 
+        if retained_segments_path_from.exists() {
+            move_dir(retained_segments_path_from, retained_segments_path_to).await?;
+        }
+
         Ok(())
     }
 
@@ -202,6 +210,7 @@ impl LocalShard {
             newest_clocks_path,
             oldest_clocks_path,
             applied_seq_path,
+            retained_segments_path,
         } = shard_data_files;
 
         if wal_path.exists() {
@@ -224,6 +233,10 @@ impl LocalShard {
             tokio_fs::remove_file(applied_seq_path).await?;
         }
 
+        if retained_segments_path.exists() {
+            tokio_fs::remove_dir_all(retained_segments_path).await?;
+        }
+
         Ok(())
     }
 
@@ -282,6 +295,7 @@ impl LocalShard {
                 .optimizer_config
                 .prevent_unoptimized
                 .unwrap_or_default(),
+            config.optimizer_config.graph_heal_threshold,
             clocks.clone(),
             shard_path.into(),
             scroll_read_lock.clone(),
@@ -292,6 +306,9 @@ impl LocalShard {
         let (update_sender, update_receiver) =
             mpsc::channel(shared_storage_config.update_queue_size);
         update_handler.run_workers(update_receiver);
+        update_handler
+            .run_quantization_rebuild(config.vector_quantization_configs())
+            .await;
 
         let read_rate_limiter = config.strict_mode_config.as_ref().and_then(|strict_mode| {
             strict_mode
@@ -473,6 +490,12 @@ impl LocalShard {
         }
 
         clear_temp_segments(shard_path);
+        let retention = SegmentRetention::load(
+            &shard::files::retained_segments_path(shard_path),
+            effective_optimizers_config.segment_retention_sec,
+        )?;
+        segment_holder.retention = Some(Arc::new(retention));
+
         let optimizers = build_optimizers(
             shard_path,
             &collection_config_read.params,
@@ -654,6 +677,12 @@ impl LocalShard {
             segment_holder.add_new(segment);
         }
 
+        let retention = SegmentRetention::load(
+            &shard::files::retained_segments_path(shard_path),
+            effective_optimizers_config.segment_retention_sec,
+        )?;
+        segment_holder.retention = Some(Arc::new(retention));
+
         let wal: SerdeWal<OperationWithClockTag> =
             SerdeWal::new(&wal_path, (&config.wal_config).into())?;
 
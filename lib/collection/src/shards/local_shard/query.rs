@@ -14,6 +14,7 @@ use segment::common::score_fusion::{ScoreFusion, score_fusion};
 use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
 use shard::query::planned_query::RescoreStages;
 use shard::search::CoreSearchRequestBatch;
+use shard::segment_holder::locked::LockedSegmentHolder;
 use tokio::runtime::Handle;
 
 use super::LocalShard;
@@ -59,6 +60,7 @@ impl PrefetchResults {
 impl LocalShard {
     pub async fn do_planned_query(
         &self,
+        segments: &LockedSegmentHolder,
         request: PlannedQuery,
         search_runtime_handle: &Handle,
         timeout: Duration,
@@ -66,6 +68,7 @@ impl LocalShard {
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
         let start_time = std::time::Instant::now();
         let searches_f = self.do_search(
+            segments,
             Arc::new(CoreSearchRequestBatch {
                 searches: request.searches,
             }),
@@ -75,6 +78,7 @@ impl LocalShard {
         );
 
         let scrolls_f = self.query_scroll_batch(
+            segments,
             Arc::new(request.scrolls),
             search_runtime_handle,
             timeout,
@@ -90,6 +94,7 @@ impl LocalShard {
 
         let plans_futures = request.root_plans.into_iter().map(|root_plan| {
             self.resolve_plan(
+                segments,
                 root_plan,
                 &prefetch_holder,
                 search_runtime_handle,
@@ -108,6 +113,7 @@ impl LocalShard {
     /// This function always filters out deferred points.
     async fn fill_with_payload_or_vectors(
         &self,
+        segments: &LockedSegmentHolder,
         query_response: ShardQueryResponse,
         with_payload: WithPayloadInterface,
         with_vector: WithVector,
@@ -129,7 +135,7 @@ impl LocalShard {
         let records_map = tokio::time::timeout(
             timeout,
             SegmentsSearcher::retrieve(
-                self.segments.clone(),
+                segments.clone(),
                 &point_ids,
                 &(&with_payload).into(),
                 &with_vector,
@@ -165,6 +171,7 @@ impl LocalShard {
 
     async fn resolve_plan(
         &self,
+        segments: &LockedSegmentHolder,
         root_plan: RootPlan,
         prefetch_holder: &PrefetchResults,
         search_runtime_handle: &Handle,
@@ -180,6 +187,7 @@ impl LocalShard {
         // resolve merging plan
         let results = self
             .recurse_prefetch(
+                segments,
                 merge_plan,
                 prefetch_holder,
                 search_runtime_handle,
@@ -191,6 +199,7 @@ impl LocalShard {
 
         // fetch payloads and vectors if required
         self.fill_with_payload_or_vectors(
+            segments,
             results,
             with_payload,
             with_vector,
@@ -202,6 +211,7 @@ impl LocalShard {
 
     fn recurse_prefetch<'a>(
         &'a self,
+        segments: &'a LockedSegmentHolder,
         merge_plan: MergePlan,
         prefetch_holder: &'a PrefetchResults,
         search_runtime_handle: &'a Handle,
@@ -231,6 +241,7 @@ impl LocalShard {
                     Source::Prefetch(prefetch) => {
                         let merged = self
                             .recurse_prefetch(
+                                segments,
                                 *prefetch,
                                 prefetch_holder,
                                 search_runtime_handle,
@@ -257,6 +268,7 @@ impl LocalShard {
                 let rescored = if let Some(rescore_params) = shard_level {
                     let rescored = self
                         .rescore(
+                            segments,
                             sources,
                             rescore_params,
                             search_runtime_handle,
@@ -286,6 +298,7 @@ impl LocalShard {
     /// Rescore list of scored points
     async fn rescore(
         &self,
+        segments: &LockedSegmentHolder,
         sources: Vec<Vec<ScoredPoint>>,
         rescore_params: RescoreParams,
         search_runtime_handle: &Handle,
@@ -321,6 +334,7 @@ impl LocalShard {
                 };
 
                 self.query_scroll_batch(
+                    segments,
                     Arc::new(vec![scroll_request]),
                     search_runtime_handle,
                     timeout,
@@ -334,6 +348,67 @@ impl LocalShard {
                     )
                 })
             }
+            ScoringQuery::Text(text_query) => {
+                // create single scroll request for rescoring query
+                let filter = filter_with_sources_ids(sources.into_iter());
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload: false.into(),
+                    with_vector: false.into(),
+                    scroll_order: ScrollOrder::ByText {
+                        query: text_query,
+                        score_threshold: score_threshold.map(OrderedFloat::into_inner),
+                    },
+                };
+
+                self.query_scroll_batch(
+                    segments,
+                    Arc::new(vec![scroll_request]),
+                    search_runtime_handle,
+                    timeout,
+                    hw_counter_acc.clone(),
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with text query didn't return expected batch of results",
+                    )
+                })
+            }
+            ScoringQuery::Hybrid(hybrid_query) => {
+                // create single scroll request for rescoring query
+                let filter = filter_with_sources_ids(sources.into_iter());
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload: false.into(),
+                    with_vector: false.into(),
+                    scroll_order: ScrollOrder::ByHybrid {
+                        query: hybrid_query,
+                        params,
+                        score_threshold: score_threshold.map(OrderedFloat::into_inner),
+                    },
+                };
+
+                self.query_scroll_batch(
+                    segments,
+                    Arc::new(vec![scroll_request]),
+                    search_runtime_handle,
+                    timeout,
+                    hw_counter_acc.clone(),
+                )
+                .await?
+                .pop()
+                .ok_or_else(|| {
+                    CollectionError::service_error(
+                        "Rescoring with hybrid query didn't return expected batch of results",
+                    )
+                })
+            }
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
                 let filter = filter_with_sources_ids(sources.into_iter());
@@ -353,6 +428,7 @@ impl LocalShard {
                 };
 
                 self.do_search(
+                    segments,
                     Arc::new(rescoring_core_search_request),
                     search_runtime_handle,
                     timeout,
@@ -369,6 +445,7 @@ impl LocalShard {
             }
             ScoringQuery::Formula(formula) => {
                 self.rescore_with_formula(
+                    segments,
                     formula,
                     sources,
                     limit,
@@ -393,6 +470,7 @@ impl LocalShard {
                     };
 
                     self.query_scroll_batch(
+                        segments,
                         Arc::new(vec![scroll_request]),
                         search_runtime_handle,
                         timeout,
@@ -409,6 +487,7 @@ impl LocalShard {
             },
             ScoringQuery::Mmr(mmr) => {
                 self.mmr_rescore(
+                    segments,
                     sources,
                     mmr,
                     limit,
@@ -453,6 +532,7 @@ impl LocalShard {
     /// Maximal Marginal Relevance rescoring
     async fn mmr_rescore(
         &self,
+        segments: &LockedSegmentHolder,
         sources: Vec<Vec<ScoredPoint>>,
         mmr: MmrInternal,
         limit: usize,
@@ -464,6 +544,7 @@ impl LocalShard {
 
         let points_with_vector = self
             .fill_with_payload_or_vectors(
+                segments,
                 sources,
                 false.into(),
                 WithVector::from(mmr.using.clone()),
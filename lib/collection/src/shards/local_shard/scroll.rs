@@ -4,7 +4,7 @@ use std::time::{Duration, Instant};
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::types::DeferredBehavior;
+use common::types::{DeferredBehavior, ScoreType};
 use futures::future::try_join_all;
 use itertools::Itertools as _;
 use rand::RngExt;
@@ -12,11 +12,16 @@ use rand::distr::weighted::WeightedIndex;
 use rand::rngs::StdRng;
 use segment::common::operation_error::OperationResult;
 use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::text_query::{TextQuery, TextQueryInternal, TextQueryStatistics};
+use segment::index::hybrid_index::HybridQuery;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, ScoredPoint, SearchParams, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use shard::common::stopping_guard::StoppingGuard;
 use shard::retrieve::record_internal::RecordInternal;
+use shard::search_result_aggregator::BatchResultAggregator;
+use shard::segment_holder::locked::LockedSegmentHolder;
 use tokio::runtime::Handle;
 use tokio_util::task::AbortOnDropHandle;
 
@@ -31,6 +36,7 @@ impl LocalShard {
     /// Basic parallel batching, it is conveniently used for the universal query API.
     pub(super) async fn query_scroll_batch(
         &self,
+        segments: &LockedSegmentHolder,
         batch: Arc<Vec<QueryScrollRequestInternal>>,
         search_runtime_handle: &Handle,
         timeout: Duration,
@@ -42,6 +48,7 @@ impl LocalShard {
 
         let scrolls = batch.iter().map(|request| {
             self.query_scroll(
+                segments,
                 request,
                 search_runtime_handle,
                 timeout,
@@ -62,6 +69,7 @@ impl LocalShard {
     /// Scroll a single page, to be used for the universal query API only.
     async fn query_scroll(
         &self,
+        segments: &LockedSegmentHolder,
         request: &QueryScrollRequestInternal,
         search_runtime_handle: &Handle,
         timeout: Duration,
@@ -82,6 +90,7 @@ impl LocalShard {
         let record_results = match scroll_order {
             ScrollOrder::ById => {
                 self.internal_scroll_by_id(
+                    segments,
                     offset_id,
                     limit,
                     with_payload,
@@ -96,6 +105,7 @@ impl LocalShard {
             }
             ScrollOrder::ByField(order_by) => {
                 self.internal_scroll_by_field(
+                    segments,
                     limit,
                     with_payload,
                     with_vector,
@@ -110,6 +120,7 @@ impl LocalShard {
             }
             ScrollOrder::Random => {
                 self.scroll_randomly(
+                    segments,
                     limit,
                     with_payload,
                     with_vector,
@@ -120,6 +131,48 @@ impl LocalShard {
                 )
                 .await?
             }
+            ScrollOrder::ByText {
+                query,
+                score_threshold,
+            } => {
+                // Points are scored by relevance, not just ordered
+                return self
+                    .scroll_by_text(
+                        segments,
+                        limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                        query,
+                        *score_threshold,
+                        timeout,
+                        hw_measurement_acc,
+                    )
+                    .await;
+            }
+            ScrollOrder::ByHybrid {
+                query,
+                params,
+                score_threshold,
+            } => {
+                // Points are scored by relevance, not just ordered
+                return self
+                    .scroll_by_hybrid(
+                        segments,
+                        limit,
+                        with_payload,
+                        with_vector,
+                        filter.as_ref(),
+                        search_runtime_handle,
+                        query,
+                        params.as_ref(),
+                        *score_threshold,
+                        timeout,
+                        hw_measurement_acc,
+                    )
+                    .await;
+            }
         };
 
         let point_results = record_results
@@ -141,6 +194,7 @@ impl LocalShard {
     #[allow(clippy::too_many_arguments)]
     pub async fn internal_scroll_by_id(
         &self,
+        segments: &LockedSegmentHolder,
         offset: Option<ExtendedPointId>,
         limit: usize,
         with_payload_interface: &WithPayloadInterface,
@@ -154,7 +208,7 @@ impl LocalShard {
         let start = Instant::now();
         let stopping_guard = StoppingGuard::new();
         let update_operation_lock = self.update_operation_lock.read().await;
-        let segments = self.segments.clone();
+        let segments = segments.clone();
         let (non_appendable, appendable) = {
             let Some(segments_guard) = segments.try_read_for(timeout) else {
                 return Err(CollectionError::timeout(timeout, "internal_scroll_by_id"));
@@ -234,6 +288,7 @@ impl LocalShard {
     #[allow(clippy::too_many_arguments)]
     pub async fn internal_scroll_by_field(
         &self,
+        segments: &LockedSegmentHolder,
         limit: usize,
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
@@ -246,7 +301,7 @@ impl LocalShard {
     ) -> CollectionResult<Vec<RecordInternal>> {
         let start = Instant::now();
         let stopping_guard = StoppingGuard::new();
-        let segments = self.segments.clone();
+        let segments = segments.clone();
 
         let update_operation_lock = self.update_operation_lock.read().await;
         let (non_appendable, appendable) = {
@@ -347,9 +402,320 @@ impl LocalShard {
         Ok(ordered_records)
     }
 
+    /// Collect statistics of the text query tokens over the given segments.
+    pub(super) async fn collect_text_query_statistics(
+        segments: &[LockedSegment],
+        text_query: &TextQuery,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+        hw_counter: &HardwareCounterCell,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let collect_statistics = |segment: LockedSegment| {
+            let text_query = text_query.clone();
+
+            let hw_counter = hw_counter.fork();
+            let task = search_runtime_handle.spawn_blocking(move || {
+                segment
+                    .get()
+                    .read()
+                    .text_query_statistics(&text_query, &hw_counter)
+            });
+            AbortOnDropHandle::new(task)
+        };
+
+        let all_statistics = tokio::time::timeout(
+            timeout,
+            try_join_all(segments.iter().cloned().map(collect_statistics)),
+        )
+        .await
+        .map_err(|_| CollectionError::timeout(timeout, "text_query_statistics"))??;
+
+        let statistics = all_statistics.into_iter().process_results(|iter| {
+            iter.fold(TextQueryStatistics::default(), |mut merged, statistics| {
+                merged.merge(&statistics);
+                merged
+            })
+        })?;
+
+        Ok(statistics)
+    }
+
+    /// Score points by BM25 relevance to the text query, the most relevant first.
+    ///
+    /// Statistics of the query tokens are usually collected over all shards of the collection.
+    /// If not, they are collected over all segments of this shard before scoring, so the scores
+    /// of points from different segments are comparable.
+    #[allow(clippy::too_many_arguments)]
+    async fn scroll_by_text(
+        &self,
+        segments: &LockedSegmentHolder,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        text_query: &TextQueryInternal,
+        score_threshold: Option<ScoreType>,
+        timeout: Duration,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let start = Instant::now();
+        let stopping_guard = StoppingGuard::new();
+        let segments = segments.clone();
+
+        let update_operation_lock = self.update_operation_lock.read().await;
+        let (non_appendable, appendable) = {
+            let Some(segments_guard) = segments.try_read_for(timeout) else {
+                return Err(CollectionError::timeout(timeout, "scroll_by_text"));
+            };
+            segments_guard.split_segments()
+        };
+        let all_segments = non_appendable.into_iter().chain(appendable).collect_vec();
+
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+
+        let TextQueryInternal {
+            query: text_query,
+            statistics,
+        } = text_query;
+
+        let statistics = match statistics {
+            Some(statistics) => statistics.clone(),
+            None => {
+                Self::collect_text_query_statistics(
+                    &all_segments,
+                    text_query,
+                    search_runtime_handle,
+                    timeout,
+                    &hw_counter,
+                )
+                .await?
+            }
+        };
+        let statistics = Arc::new(statistics);
+
+        let read_text_scored_filtered =
+            |segment: LockedSegment, hw_counter: &HardwareCounterCell| {
+                let is_stopped = stopping_guard.get_is_stopped();
+                let filter = filter.cloned();
+                let text_query = text_query.clone();
+                let statistics = statistics.clone();
+
+                let hw_counter = hw_counter.fork();
+                let cpu_utilization = hw_counter.cpu_utilization();
+                let task = search_runtime_handle.spawn_blocking(move || {
+                    let work = || {
+                        segment.get().read().read_text_scored_filtered(
+                            limit,
+                            filter.as_ref(),
+                            &text_query,
+                            &statistics,
+                            &is_stopped,
+                            &hw_counter,
+                            DeferredBehavior::Exclude,
+                        )
+                    };
+                    match cpu_utilization {
+                        Some(cu) => cu.measure(work),
+                        None => work(),
+                    }
+                });
+                AbortOnDropHandle::new(task)
+            };
+
+        let scoring_timeout = timeout.saturating_sub(start.elapsed());
+        let all_reads = tokio::time::timeout(
+            scoring_timeout,
+            try_join_all(
+                all_segments
+                    .into_iter()
+                    .map(|segment| read_text_scored_filtered(segment, &hw_counter)),
+            ),
+        )
+        .await
+        .map_err(|_| CollectionError::timeout(scoring_timeout, "scroll_by_text"))??;
+
+        let segments_results: Vec<_> = all_reads.into_iter().try_collect()?;
+
+        // Same point may be present in multiple segments during optimization,
+        // only the latest version of it is scored
+        let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+        aggregator.update_point_versions(segments_results.iter().flatten());
+        aggregator.update_batch_results(0, segments_results.into_iter().flatten());
+        let mut top =
+            aggregator.into_topk().into_iter().next().ok_or_else(|| {
+                CollectionError::service_error("expected first result of aggregator")
+            })?;
+        top.retain(|point| score_threshold.is_none_or(|threshold| point.score >= threshold));
+
+        let point_ids = top.iter().map(|point| point.id).collect_vec();
+        let with_payload = WithPayload::from(with_payload_interface);
+
+        // update timeout
+        let timeout = timeout.saturating_sub(start.elapsed());
+
+        // Fetch with the requested vector and payload
+        let mut records_map = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::retrieve(
+                segments,
+                &point_ids,
+                &with_payload,
+                with_vector,
+                search_runtime_handle,
+                timeout,
+                hw_measurement_acc,
+                DeferredBehavior::Exclude,
+            ),
+        )
+        .await
+        .map_err(|_| CollectionError::timeout(timeout, "retrieve"))??;
+
+        drop(update_operation_lock);
+
+        let scored_points = top
+            .into_iter()
+            .filter_map(|point| {
+                let record = records_map.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    ..point
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+
+    /// Score points by the dense and sparse vectors of the hybrid query, the most relevant first.
+    #[allow(clippy::too_many_arguments)]
+    async fn scroll_by_hybrid(
+        &self,
+        segments: &LockedSegmentHolder,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        search_runtime_handle: &Handle,
+        hybrid_query: &HybridQuery,
+        params: Option<&SearchParams>,
+        score_threshold: Option<ScoreType>,
+        timeout: Duration,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<ScoredPoint>> {
+        let start = Instant::now();
+        let stopping_guard = StoppingGuard::new();
+        let segments = segments.clone();
+
+        let update_operation_lock = self.update_operation_lock.read().await;
+        let (non_appendable, appendable) = {
+            let Some(segments_guard) = segments.try_read_for(timeout) else {
+                return Err(CollectionError::timeout(timeout, "scroll_by_hybrid"));
+            };
+            segments_guard.split_segments()
+        };
+
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+
+        let hybrid_search = |segment: LockedSegment, hw_counter: &HardwareCounterCell| {
+            let is_stopped = stopping_guard.get_is_stopped();
+            let filter = filter.cloned();
+            let hybrid_query = hybrid_query.clone();
+            let params = params.copied();
+
+            let hw_counter = hw_counter.fork();
+            let cpu_utilization = hw_counter.cpu_utilization();
+            let task = search_runtime_handle.spawn_blocking(move || {
+                let work = || {
+                    segment.get().read().hybrid_search(
+                        &hybrid_query,
+                        filter.as_ref(),
+                        limit,
+                        params.as_ref(),
+                        &is_stopped,
+                        &hw_counter,
+                    )
+                };
+                match cpu_utilization {
+                    Some(cu) => cu.measure(work),
+                    None => work(),
+                }
+            });
+            AbortOnDropHandle::new(task)
+        };
+
+        let all_searches = tokio::time::timeout(
+            timeout,
+            try_join_all(
+                non_appendable
+                    .into_iter()
+                    .chain(appendable)
+                    .map(|segment| hybrid_search(segment, &hw_counter)),
+            ),
+        )
+        .await
+        .map_err(|_| CollectionError::timeout(timeout, "scroll_by_hybrid"))??;
+
+        let segments_results: Vec<_> = all_searches.into_iter().try_collect()?;
+
+        // Same point may be present in multiple segments during optimization,
+        // only the latest version of it is scored
+        let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+        aggregator.update_point_versions(segments_results.iter().flatten());
+        aggregator.update_batch_results(0, segments_results.into_iter().flatten());
+        let mut top =
+            aggregator.into_topk().into_iter().next().ok_or_else(|| {
+                CollectionError::service_error("expected first result of aggregator")
+            })?;
+        top.retain(|point| score_threshold.is_none_or(|threshold| point.score >= threshold));
+
+        let point_ids = top.iter().map(|point| point.id).collect_vec();
+        let with_payload = WithPayload::from(with_payload_interface);
+
+        // update timeout
+        let timeout = timeout.saturating_sub(start.elapsed());
+
+        // Fetch with the requested vector and payload
+        let mut records_map = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::retrieve(
+                segments,
+                &point_ids,
+                &with_payload,
+                with_vector,
+                search_runtime_handle,
+                timeout,
+                hw_measurement_acc,
+                DeferredBehavior::Exclude,
+            ),
+        )
+        .await
+        .map_err(|_| CollectionError::timeout(timeout, "retrieve"))??;
+
+        drop(update_operation_lock);
+
+        let scored_points = top
+            .into_iter()
+            .filter_map(|point| {
+                let record = records_map.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    ..point
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn scroll_randomly(
         &self,
+        segments: &LockedSegmentHolder,
         limit: usize,
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
@@ -360,7 +726,7 @@ impl LocalShard {
     ) -> CollectionResult<Vec<RecordInternal>> {
         let start = Instant::now();
         let stopping_guard = StoppingGuard::new();
-        let segments = self.segments.clone();
+        let segments = segments.clone();
 
         let update_operation_lock = self.update_operation_lock.read().await;
         let (non_appendable, appendable) = {
@@ -5,11 +5,13 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::types::ScoredPoint;
 use shard::common::stopping_guard::StoppingGuard;
 use shard::query::query_enum::QueryEnum;
-use shard::search::CoreSearchRequestBatch;
+use shard::search::{CoreSearchRequest, CoreSearchRequestBatch};
+use shard::segment_holder::locked::LockedSegmentHolder;
 use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::config::CollectionParams;
 use crate::operations::types::{CollectionError, CollectionResult};
 
 // Chunk requests for parallelism in certain scenarios
@@ -29,6 +31,7 @@ const CHUNK_SIZE: usize = 16;
 impl LocalShard {
     pub async fn do_search(
         &self,
+        segments: &LockedSegmentHolder,
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Duration,
@@ -41,7 +44,7 @@ impl LocalShard {
         let skip_batching = if core_request.searches.len() <= CHUNK_SIZE {
             // Don't batch if we have few searches, prevents cloning request
             true
-        } else if self.segments.read().len() > self.shared_storage_config.search_thread_count {
+        } else if segments.read().len() > self.shared_storage_config.search_thread_count {
             // Don't batch if we have more segments than search threads
             // Not a perfect condition, but it helps to prevent consuming a lot of search threads
             // if the number of segments is large
@@ -59,6 +62,7 @@ impl LocalShard {
         if skip_batching {
             return self
                 .do_search_impl(
+                    segments,
                     core_request,
                     search_runtime_handle,
                     timeout,
@@ -78,6 +82,7 @@ impl LocalShard {
                     searches: chunk.to_vec(),
                 };
                 self.do_search_impl(
+                    segments,
                     Arc::new(core_request),
                     search_runtime_handle,
                     timeout,
@@ -98,6 +103,7 @@ impl LocalShard {
 
     async fn do_search_impl(
         &self,
+        segments: &LockedSegmentHolder,
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Duration,
@@ -105,10 +111,12 @@ impl LocalShard {
         is_stopped_guard: &StoppingGuard,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let start = std::time::Instant::now();
-        let (query_context, collection_params) = {
+        let (core_request, query_context, collection_params) = {
             let collection_config = self.collection_config.read().await;
+            let core_request =
+                apply_quantization_rescore_defaults(core_request, &collection_config.params);
             let query_context_opt = SegmentsSearcher::prepare_query_context(
-                self.segments.clone(),
+                segments.clone(),
                 &core_request,
                 &collection_config,
                 timeout,
@@ -123,14 +131,18 @@ impl LocalShard {
                 return Ok(vec![]);
             };
 
-            (query_context, collection_config.params.clone())
+            (
+                core_request,
+                query_context,
+                collection_config.params.clone(),
+            )
         };
 
         // update timeout
         let timeout = timeout.saturating_sub(start.elapsed());
 
         let search_request = SegmentsSearcher::search(
-            self.segments.clone(),
+            segments.clone(),
             core_request.clone(),
             search_runtime_handle,
             true,
@@ -181,3 +193,152 @@ impl LocalShard {
         Ok(top_results)
     }
 }
+
+/// Fill in `rescore` of quantization search params from the config of the searched vector,
+/// for searches which don't specify it explicitly.
+///
+/// Request is only cloned if there is any default to apply.
+fn apply_quantization_rescore_defaults(
+    core_request: Arc<CoreSearchRequestBatch>,
+    collection_params: &CollectionParams,
+) -> Arc<CoreSearchRequestBatch> {
+    let default_rescore = |req: &CoreSearchRequest| {
+        let rescore = req
+            .params
+            .and_then(|params| params.quantization)
+            .and_then(|quantization| quantization.rescore);
+        if rescore.is_some() {
+            return None;
+        }
+        collection_params
+            .vectors
+            .get_params(req.query.get_vector_name())
+            .and_then(|params| params.quantization_rescore)
+    };
+
+    if !core_request
+        .searches
+        .iter()
+        .any(|req| default_rescore(req).is_some())
+    {
+        return core_request;
+    }
+
+    let searches = core_request
+        .searches
+        .iter()
+        .map(|req| {
+            let mut req = req.clone();
+            if let Some(rescore) = default_rescore(&req) {
+                let mut params = req.params.unwrap_or_default();
+                let mut quantization = params.quantization.unwrap_or_default();
+                quantization.rescore = Some(rescore);
+                params.quantization = Some(quantization);
+                req.params = Some(params);
+            }
+            req
+        })
+        .collect();
+
+    Arc::new(CoreSearchRequestBatch { searches })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use segment::data_types::vectors::{NamedQuery, VectorInternal};
+    use segment::types::{Distance, QuantizationSearchParams, SearchParams};
+
+    use super::*;
+    use crate::operations::types::VectorsConfig;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    fn collection_params() -> CollectionParams {
+        CollectionParams {
+            vectors: VectorsConfig::Multi(BTreeMap::from([
+                (
+                    "rescored".into(),
+                    VectorParamsBuilder::new(2, Distance::Dot)
+                        .with_quantization_rescore(true)
+                        .build(),
+                ),
+                (
+                    "plain".into(),
+                    VectorParamsBuilder::new(2, Distance::Dot).build(),
+                ),
+            ])),
+            ..CollectionParams::empty()
+        }
+    }
+
+    fn search(using: &str, params: Option<SearchParams>) -> CoreSearchRequest {
+        CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedQuery::new(VectorInternal::from(vec![1.0, 0.0]), using)),
+            filter: None,
+            params,
+            limit: 10,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        }
+    }
+
+    fn rescore(request: &CoreSearchRequest) -> Option<bool> {
+        request
+            .params
+            .and_then(|params| params.quantization)
+            .and_then(|quantization| quantization.rescore)
+    }
+
+    #[test]
+    fn test_apply_quantization_rescore_defaults() {
+        let explicit = SearchParams {
+            hnsw_ef: Some(64),
+            quantization: Some(QuantizationSearchParams {
+                rescore: Some(false),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let unset = SearchParams {
+            hnsw_ef: Some(64),
+            quantization: Some(QuantizationSearchParams {
+                oversampling: Some(2.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![
+                search("rescored", None),
+                search("rescored", Some(explicit)),
+                search("rescored", Some(unset)),
+                search("plain", None),
+            ],
+        });
+
+        let applied = apply_quantization_rescore_defaults(request, &collection_params());
+        let rescores = applied.searches.iter().map(rescore).collect::<Vec<_>>();
+        // Explicit `rescore` of the request wins over the default of the vector
+        assert_eq!(rescores, [Some(true), Some(false), Some(true), None]);
+
+        // Other params of the request are kept
+        let params = applied.searches[2].params.unwrap();
+        assert_eq!(params.hnsw_ef, Some(64));
+        assert_eq!(params.quantization.unwrap().oversampling, Some(2.0));
+        assert_eq!(applied.searches[3].params, None);
+    }
+
+    #[test]
+    fn test_apply_quantization_rescore_defaults_unchanged() {
+        let request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![search("plain", None), search("unknown", None)],
+        });
+
+        let applied = apply_quantization_rescore_defaults(request.clone(), &collection_params());
+        // Request is not cloned, if there is nothing to apply
+        assert!(Arc::ptr_eq(&request, &applied));
+    }
+}
@@ -6,6 +6,7 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::DeferredBehavior;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -197,6 +198,7 @@ impl ShardOperation for LocalShard {
         let result = match order_by {
             None => {
                 self.internal_scroll_by_id(
+                    &self.segments,
                     *offset,
                     limit,
                     with_payload.as_ref().unwrap_or(&default_with_payload),
@@ -211,6 +213,7 @@ impl ShardOperation for LocalShard {
             }
             Some(order_by) => {
                 self.internal_scroll_by_field(
+                    &self.segments,
                     limit,
                     with_payload.as_ref().unwrap_or(&default_with_payload),
                     with_vector,
@@ -250,6 +253,7 @@ impl ShardOperation for LocalShard {
     ) -> CollectionResult<Vec<RecordInternal>> {
         let timeout = self.timeout_or_default_search_timeout(timeout);
         self.internal_scroll_by_id(
+            &self.segments,
             offset,
             limit,
             with_payload_interface,
@@ -281,8 +285,14 @@ impl ShardOperation for LocalShard {
             request.searches.iter().map(|s| s.search_rate_cost()).sum()
         })?;
         let timeout = self.timeout_or_default_search_timeout(timeout);
-        self.do_search(request, search_runtime_handle, timeout, hw_measurement_acc)
-            .await
+        self.do_search(
+            &self.segments,
+            request,
+            search_runtime_handle,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
     }
 
     /// This call is rate limited by the read rate limiter.
@@ -354,11 +364,12 @@ impl ShardOperation for LocalShard {
         let cpu_utilization = hw_measurement_acc.cpu_utilization();
         let records_map = tokio::time::timeout(
             timeout,
-            SegmentsSearcher::retrieve(
+            SegmentsSearcher::retrieve_at_version(
                 self.segments.clone(),
                 &request.ids,
                 with_payload,
                 with_vector,
+                request.at_version,
                 search_runtime_handle,
                 timeout,
                 hw_measurement_acc,
@@ -410,6 +421,7 @@ impl ShardOperation for LocalShard {
         let cpu_utilization = hw_measurement_acc.cpu_utilization();
         let result = self
             .do_planned_query(
+                &self.segments,
                 planned_query,
                 search_runtime_handle,
                 timeout,
@@ -479,6 +491,35 @@ impl ShardOperation for LocalShard {
         Ok(FacetResponse { hits })
     }
 
+    /// This call is rate limited by the read rate limiter.
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        // Check read rate limiter before proceeding
+        self.check_read_rate_limiter(&hw_measurement_acc, "text_query_statistics", || BASE_COST)?;
+
+        let timeout = self.timeout_or_default_search_timeout(timeout);
+        let segments: Vec<_> = self
+            .segments
+            .read()
+            .non_appendable_then_appendable_segments()
+            .collect();
+
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+        Self::collect_text_query_statistics(
+            &segments,
+            &query,
+            search_runtime_handle,
+            timeout,
+            &hw_counter,
+        )
+        .await
+    }
+
     /// Finishes ongoing update tasks
     async fn stop_gracefully(mut self) {
         {
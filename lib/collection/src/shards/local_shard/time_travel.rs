@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use shard::segment_holder::locked::LockedSegmentHolder;
+use tokio::runtime::Handle;
+use tokio_util::task::AbortOnDropHandle;
+
+use super::LocalShard;
+use crate::operations::time_travel::AsOf;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::planned_query::PlannedQuery;
+use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+
+impl LocalShard {
+    /// Query the shard as it was at `as_of`, using segments retained after optimizations.
+    ///
+    /// This call is rate limited by the read rate limiter.
+    pub async fn query_batch_as_of(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        as_of: AsOf,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        let start_time = Instant::now();
+        let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
+
+        // Check read rate limiter before proceeding
+        self.check_read_rate_limiter(&hw_measurement_acc, "query_batch_as_of", || {
+            planned_query
+                .searches
+                .iter()
+                .map(|s| s.search_rate_cost())
+                .chain(planned_query.scrolls.iter().map(|s| s.scroll_rate_cost()))
+                .sum()
+        })?;
+        let timeout = self.timeout_or_default_search_timeout(timeout);
+
+        let segments = self.segments.clone();
+        let view_task =
+            search_runtime_handle.spawn_blocking(move || Self::pinned_view(&segments, as_of));
+        let view = tokio::time::timeout(timeout, AbortOnDropHandle::new(view_task))
+            .await
+            .map_err(|_| CollectionError::timeout(timeout, "query_batch_as_of"))???;
+
+        let timeout = timeout.saturating_sub(start_time.elapsed());
+        self.do_planned_query(
+            &view,
+            planned_query,
+            search_runtime_handle,
+            timeout,
+            hw_measurement_acc,
+        )
+        .await
+    }
+
+    /// Build a holder of segments, which hides changes applied after `as_of`
+    fn pinned_view(
+        segments: &LockedSegmentHolder,
+        as_of: AsOf,
+    ) -> CollectionResult<LockedSegmentHolder> {
+        let segments = segments.read();
+        let Some(retention) = segments
+            .retention
+            .as_ref()
+            .filter(|retention| retention.is_enabled())
+        else {
+            return Err(CollectionError::bad_request(
+                "Time-travel queries require `segment_retention_sec` to be configured",
+            ));
+        };
+
+        let version = match as_of {
+            AsOf::Operation(version) => version,
+            AsOf::Timestamp(time) => retention.version_at(time).ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Time {time} is outside of the retention window",
+                ))
+            })?,
+        };
+
+        if retention
+            .oldest_version()
+            .is_none_or(|oldest| version < oldest)
+        {
+            return Err(CollectionError::bad_request(format!(
+                "Operation {version} is outside of the retention window",
+            )));
+        }
+
+        let view = retention.view(&segments, version)?;
+        Ok(LockedSegmentHolder::new(view))
+    }
+}
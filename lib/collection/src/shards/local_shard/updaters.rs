@@ -15,6 +15,12 @@ impl LocalShard {
         let _ = self.update_sender.load().try_send(UpdateSignal::Nop);
     }
 
+    /// Retrain quantization of non-appendable segments on their current data in the background
+    pub async fn requantize(&self) {
+        let update_handler = self.update_handler.lock().await;
+        update_handler.run_requantization().await;
+    }
+
     /// Stops flush worker only.
     /// This is useful for testing purposes to prevent background flushes.
     #[cfg(feature = "testing")]
@@ -81,7 +87,15 @@ impl LocalShard {
             .optimizer_config
             .prevent_unoptimized
             .unwrap_or_default();
+        update_handler.graph_heal_threshold = config.optimizer_config.graph_heal_threshold;
         update_handler.run_workers(update_receiver);
+        update_handler
+            .run_quantization_rebuild(config.vector_quantization_configs())
+            .await;
+
+        if let Some(retention) = &self.segments.read().retention {
+            retention.set_window(config.optimizer_config.segment_retention_sec);
+        }
 
         self.optimizers.store(new_optimizers);
 
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
+use segment::types::SeqNumberType;
 use tokio::sync::{Mutex, mpsc};
 
-use crate::operations::types::CollectionResult;
+use crate::operations::CollectionUpdateOperations;
+use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::local_shard::LocalShard;
 use crate::update_handler::UpdateSignal;
 
@@ -18,6 +20,48 @@ impl LocalShard {
         self.wal.set_normal_retention().await;
     }
 
+    /// Read up to `limit` operations, written to the WAL, starting at `from` position.
+    /// If `from` is not set, reading starts after the last written operation.
+    ///
+    /// Returns operations with their positions, and position to continue reading from.
+    pub async fn read_wal_changes(
+        &self,
+        from: Option<SeqNumberType>,
+        limit: usize,
+    ) -> CollectionResult<(
+        Vec<(SeqNumberType, CollectionUpdateOperations)>,
+        SeqNumberType,
+    )> {
+        let wal = self.wal.wal.lock().await;
+
+        let first = wal.first_closed_index();
+        let next = wal.first_index() + wal.len(false);
+        let from = from.unwrap_or(next);
+
+        if from < first {
+            return Err(CollectionError::bad_request(format!(
+                "Position {from} is no longer available in WAL, \
+                 oldest available position is {first}",
+            )));
+        }
+        if from > next {
+            return Err(CollectionError::bad_request(format!(
+                "Position {from} is ahead of WAL, next position is {next}",
+            )));
+        }
+
+        let to = next.min(from.saturating_add(limit as u64));
+        let changes = wal
+            .read_range(from..to)
+            .map(|entry| entry.map(|(op_num, operation)| (op_num, operation.operation)))
+            .collect::<shard::wal::Result<Vec<_>>>()
+            .map_err(|err| {
+                CollectionError::service_error(format!("Failed to read WAL entries: {err}"))
+            })?;
+
+        Ok((changes, to))
+    }
+
     /// Truncate unapplied WAL records.
     /// Returns amount of removed records.
     pub async fn truncate_unapplied_wal(&self) -> CollectionResult<usize> {
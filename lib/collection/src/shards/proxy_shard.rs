@@ -10,6 +10,7 @@ use common::tar_ext;
 use common::types::{DeferredBehavior, TelemetryDetail};
 use parking_lot::Mutex as ParkingMutex;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
@@ -393,6 +394,19 @@ impl ShardOperation for ProxyShard {
             .await
     }
 
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .text_query_statistics(query, search_runtime_handle, timeout, hw_measurement_acc)
+            .await
+    }
+
     async fn stop_gracefully(self) {
         let local_shard = self.wrapped_shard;
         local_shard.stop_gracefully().await;
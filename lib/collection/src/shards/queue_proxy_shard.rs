@@ -9,6 +9,7 @@ use common::tar_ext;
 use common::types::{DeferredBehavior, TelemetryDetail};
 use parking_lot::Mutex as ParkingMutex;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, SizeStats, SnapshotFormat, WithPayload,
@@ -433,6 +434,19 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .text_query_statistics(query, search_runtime_handle, timeout, hw_measurement_acc)
+            .await
+    }
+
     async fn stop_gracefully(mut self) {
         if let Some(inner) = self.inner.take() {
             debug_assert!(
@@ -813,6 +827,19 @@ impl ShardOperation for Inner {
             .await
     }
 
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .text_query_statistics(query, search_runtime_handle, timeout, hw_measurement_acc)
+            .await
+    }
+
     async fn stop_gracefully(self) {
         self.wrapped_shard.stop_gracefully().await
     }
@@ -15,13 +15,16 @@ use api::grpc::qdrant::{
     HealthCheckRequest, InitiateShardTransferRequest, QueryBatchPointsInternal,
     QueryBatchResponseInternal, QueryShardPoints, RecoverShardSnapshotRequest,
     RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal, SearchBatchResponse,
-    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    ShardSnapshotLocation, TextQueryStatisticsInternal, UpdateShardCutoffPointRequest,
+    WaitForShardStateRequest,
 };
-use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
+use api::grpc::transport_channel_pool::{MAX_GRPC_CHANNEL_TIMEOUT, PeerChannel};
 use api::grpc::update_operation::Update;
 use api::grpc::{UpdateBatchInternal, UpdateOperation, WithPayloadSelector};
 use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::request_profile::RequestProfileData;
+use common::search_diagnostics::SearchDiagnosticsData;
 use common::types::{DeferredBehavior, TelemetryDetail};
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -30,6 +33,7 @@ use segment::common::operation_time_statistics::{
 };
 use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
 };
@@ -41,8 +45,7 @@ use shard::scroll::ScrollRequestInternal;
 use shard::search::CoreSearchRequestBatch;
 use tokio::runtime::Handle;
 use tonic::Status;
-use tonic::codegen::InterceptedService;
-use tonic::transport::{Channel, Uri};
+use tonic::transport::Uri;
 use url::Url;
 
 use super::conversions::{
@@ -134,7 +137,7 @@ impl RemoteShard {
 
     async fn with_points_client<T, O: Future<Output = Result<T, Status>>>(
         &self,
-        f: impl Fn(PointsInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
+        f: impl Fn(PointsInternalClient<PeerChannel>) -> O,
     ) -> CollectionResult<T> {
         let current_address = self.current_address()?;
         self.channel_service
@@ -150,7 +153,7 @@ impl RemoteShard {
 
     async fn with_collections_client<T, O: Future<Output = Result<T, Status>>>(
         &self,
-        f: impl Fn(CollectionsInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
+        f: impl Fn(CollectionsInternalClient<PeerChannel>) -> O,
     ) -> CollectionResult<T> {
         let current_address = self.current_address()?;
         self.channel_service
@@ -166,7 +169,7 @@ impl RemoteShard {
 
     async fn with_shard_snapshots_client_timeout<T, O: Future<Output = Result<T, Status>>>(
         &self,
-        f: impl Fn(ShardSnapshotsClient<InterceptedService<Channel, AddTimeout>>) -> O,
+        f: impl Fn(ShardSnapshotsClient<PeerChannel>) -> O,
         timeout: Option<Duration>,
         retries: usize,
     ) -> CollectionResult<T> {
@@ -189,7 +192,7 @@ impl RemoteShard {
 
     async fn with_qdrant_client<T, Fut: Future<Output = Result<T, Status>>>(
         &self,
-        f: impl Fn(QdrantClient<InterceptedService<Channel, AddTimeout>>) -> Fut,
+        f: impl Fn(QdrantClient<PeerChannel>) -> Fut,
     ) -> CollectionResult<T> {
         let current_address = self.current_address()?;
         self.channel_service
@@ -1296,6 +1299,7 @@ impl ShardOperation for RemoteShard {
             read_consistency: None,
             shard_key_selector: None,
             timeout: processed_timeout.map(|t| t.as_secs()),
+            at_version: request.at_version,
         };
         let get_request = &GetPointsInternal {
             get_points: Some(get_points),
@@ -1338,6 +1342,10 @@ impl ShardOperation for RemoteShard {
         timer.set_success(false);
 
         let requests = requests.as_ref();
+        let search_diagnostics = hw_measurement_acc.search_diagnostics();
+        let with_search_diagnostics = search_diagnostics.is_enabled();
+        let profile = hw_measurement_acc.request_profile();
+        let with_profile = profile.is_enabled();
 
         let batch_response = self
             .with_points_client(|mut client| async move {
@@ -1351,6 +1359,8 @@ impl ShardOperation for RemoteShard {
                     query_points,
                     shard_id: Some(self.id),
                     timeout: processed_timeout.map(|t| t.as_secs()),
+                    with_search_diagnostics: Some(with_search_diagnostics),
+                    with_profile: Some(with_profile),
                 };
 
                 let mut request = tonic::Request::new(request.clone());
@@ -1369,12 +1379,22 @@ impl ShardOperation for RemoteShard {
             time: _,
             hardware_usage,
             inference_usage: _, // Remote shards don't have inference usage, so we can ignore it
+            search_diagnostics: remote_search_diagnostics,
+            profile: remote_profile,
         } = batch_response;
 
         if let Some(hw_usage) = hardware_usage {
             hw_measurement_acc.accumulate_request(hw_usage);
         }
 
+        if let Some(remote_search_diagnostics) = remote_search_diagnostics {
+            search_diagnostics.accumulate(&SearchDiagnosticsData::from(remote_search_diagnostics));
+        }
+
+        if let Some(remote_profile) = remote_profile {
+            profile.accumulate(&RequestProfileData::from(remote_profile));
+        }
+
         let result = results
             .into_iter()
             .zip(requests.iter())
@@ -1458,6 +1478,53 @@ impl ShardOperation for RemoteShard {
         Ok(result)
     }
 
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        let processed_timeout = Self::process_read_timeout(timeout, "text_query_statistics")?;
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let response = self
+            .with_points_client(|mut client| async move {
+                let request = &TextQueryStatisticsInternal {
+                    collection_name: self.collection_id.clone(),
+                    query: Some(api::grpc::qdrant::TextQuery::from(query.as_ref().clone())),
+                    shard_id: self.id,
+                    timeout: processed_timeout.map(|t| t.as_secs()),
+                };
+
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.text_query_statistics(request).await
+            })
+            .await?
+            .into_inner();
+
+        if let Some(hw_usage) = response.usage {
+            hw_measurement_acc.accumulate_request(hw_usage);
+        }
+
+        let statistics = response
+            .statistics
+            .map(TextQueryStatistics::from)
+            .ok_or_else(|| {
+                CollectionError::service_error("Unexpected empty TextQueryStatistics".to_string())
+            })?;
+
+        timer.set_success(true);
+
+        Ok(statistics)
+    }
+
     async fn stop_gracefully(self) {
         // No background operations to stop on RemoteShard
     }
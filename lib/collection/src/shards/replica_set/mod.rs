@@ -17,12 +17,19 @@ use std::time::Duration;
 
 use common::budget::ResourceBudget;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::disk::dir_disk_size;
 use common::rate_limiting::RateLimiter;
 use common::save_on_disk::SaveOnDisk;
 use common::types::DeferredBehavior;
 use replica_set_state::{ReplicaSetState, ReplicaState};
-use segment::types::{ExtendedPointId, Filter, SeqNumberType, ShardKey};
+use segment::types::{
+    ExtendedPointId, Filter, PointIdType, QuantizationQualityStats, QuantizedVectorData,
+    SegmentDiskUsage, SegmentMemoryUsage, SeqNumberType, ShardKey, VectorNameBuf,
+};
+use segment::utils::mem::MappedFilesResidency;
 use serde::{Deserialize, Serialize};
+use shard::common::stopping_guard::StoppingGuard;
+use shard::locked_segment::LockedSegment;
 use shard::operations::optimization::{
     OptimizationsRequestOptions, OptimizationsResponse, OptimizationsSummary,
 };
@@ -1328,8 +1335,31 @@ impl ShardReplicaSet {
         local.get_wal_entries(count).await
     }
 
+    /// Read operations from the WAL of the local shard, see [`LocalShard::read_wal_changes`].
+    pub(crate) async fn read_wal_changes(
+        &self,
+        from: Option<SeqNumberType>,
+        limit: usize,
+    ) -> CollectionResult<(
+        Vec<(SeqNumberType, CollectionUpdateOperations)>,
+        SeqNumberType,
+    )> {
+        let local = self.local.read().await;
+
+        let Some(local) = local.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local.read_wal_changes(from, limit).await
+    }
+
     pub(crate) fn get_snapshots_storage_manager(&self) -> CollectionResult<SnapshotStorageManager> {
-        SnapshotStorageManager::new(&self.shared_storage_config.snapshots_config)
+        SnapshotStorageManager::new(
+            &self.shared_storage_config.snapshots_config,
+            &self.shared_storage_config.object_storage,
+        )
     }
 
     pub(crate) async fn trigger_optimizers(&self) -> bool {
@@ -1387,6 +1417,185 @@ impl ShardReplicaSet {
         }))
     }
 
+    /// Estimate RAM used by all segments of the local shard.
+    ///
+    /// Returns `None` if there is no local shard.
+    pub(crate) async fn calculate_local_memory_usage(
+        &self,
+        residency: Arc<MappedFilesResidency>,
+    ) -> CollectionResult<Option<SegmentMemoryUsage>> {
+        let Some(segments) = self.local.read().await.as_ref().and_then(|i| match i {
+            Shard::Local(local) => Some(
+                local
+                    .segments
+                    .read()
+                    .iter()
+                    .map(|i| i.1.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_) => {
+                None
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let handle = spawn_blocking(move || {
+            let mut usage = SegmentMemoryUsage::default();
+            for segment in segments {
+                usage += segment.get().read().memory_usage(&residency);
+            }
+            usage
+        });
+
+        Ok(Some(AbortOnDropHandle::new(handle).await?))
+    }
+
+    /// Calculate disk space taken by all segments and the WAL of the local shard.
+    ///
+    /// Returns `None` if there is no local shard.
+    pub(crate) async fn calculate_local_disk_usage(
+        &self,
+    ) -> CollectionResult<Option<(SegmentDiskUsage, u64)>> {
+        let Some(segments) = self.local.read().await.as_ref().and_then(|i| match i {
+            Shard::Local(local) => Some(
+                local
+                    .segments
+                    .read()
+                    .iter()
+                    .map(|i| i.1.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_) => {
+                None
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let wal_path = LocalShard::wal_path(&self.shard_path);
+
+        let handle = spawn_blocking(move || {
+            let mut usage = SegmentDiskUsage::default();
+            for segment in segments {
+                usage += segment.get().read().disk_usage();
+            }
+            let wal_bytes = dir_disk_size(wal_path)?;
+            Ok::<_, CollectionError>((usage, wal_bytes))
+        });
+
+        Ok(Some(AbortOnDropHandle::new(handle).await??))
+    }
+
+    /// Estimate quality of quantized vectors in segments of the local shard.
+    ///
+    /// Returns `None` if there is no local shard. Vectors without quantization are omitted.
+    pub(crate) async fn estimate_local_quantization_quality(
+        &self,
+        sample_size: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<HashMap<VectorNameBuf, QuantizationQualityStats>>> {
+        let Some(segments) = self.local.read().await.as_ref().and_then(|i| match i {
+            Shard::Local(local) => Some(
+                local
+                    .segments
+                    .read()
+                    .iter()
+                    .filter_map(|(_, segment)| match segment {
+                        LockedSegment::Original(segment) => Some(segment.clone()),
+                        LockedSegment::Proxy(_) => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_) => {
+                None
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let stopping_guard = StoppingGuard::new();
+        let is_stopped = stopping_guard.get_is_stopped();
+        let handle = spawn_blocking(move || {
+            let mut quality: HashMap<VectorNameBuf, QuantizationQualityStats> = HashMap::new();
+            for segment in segments {
+                let segment = segment.read();
+                for vector_name in segment.segment_config.vector_data.keys() {
+                    let Some(stats) = segment.estimate_quantization_quality(
+                        vector_name,
+                        sample_size,
+                        limit,
+                        &is_stopped,
+                    )?
+                    else {
+                        continue;
+                    };
+                    *quality.entry(vector_name.clone()).or_default() += stats;
+                }
+            }
+            Ok::<_, CollectionError>(quality)
+        });
+
+        Ok(Some(AbortOnDropHandle::new(handle).await??))
+    }
+
+    /// Quantized representation of vector `vector_name` of point `point_id` in the local shard.
+    ///
+    /// Returns `None` if there is no local shard, or the point is not found in it. Segments
+    /// under optimization are not checked.
+    pub(crate) async fn local_quantized_vector(
+        &self,
+        vector_name: VectorNameBuf,
+        point_id: PointIdType,
+    ) -> CollectionResult<Option<QuantizedVectorData>> {
+        let Some(segments) = self.local.read().await.as_ref().and_then(|i| match i {
+            Shard::Local(local) => Some(
+                local
+                    .segments
+                    .read()
+                    .iter()
+                    .filter_map(|(_, segment)| match segment {
+                        LockedSegment::Original(segment) => Some(segment.clone()),
+                        LockedSegment::Proxy(_) => None,
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_) => {
+                None
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let handle = spawn_blocking(move || {
+            for segment in segments {
+                let quantized = segment.read().quantized_vector(&vector_name, point_id)?;
+                if quantized.is_some() {
+                    return Ok(quantized);
+                }
+            }
+            Ok::<_, CollectionError>(None)
+        });
+
+        AbortOnDropHandle::new(handle).await?
+    }
+
+    /// Retrain quantization of the local shard in the background.
+    ///
+    /// Returns `false` if there is no local shard, or it is proxied by a shard transfer.
+    pub(crate) async fn requantize_local(&self) -> bool {
+        match self.local.read().await.as_ref() {
+            Some(Shard::Local(local)) => {
+                local.requantize().await;
+                true
+            }
+            Some(
+                Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_),
+            )
+            | None => false,
+        }
+    }
+
     pub(crate) fn payload_index_schema(&self) -> Arc<SaveOnDisk<PayloadIndexSchema>> {
         self.payload_index_schema.clone()
     }
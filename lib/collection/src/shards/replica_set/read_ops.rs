@@ -5,6 +5,7 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::DeferredBehavior;
 use futures::FutureExt as _;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::types::*;
 use shard::count::CountRequestInternal;
 use shard::retrieve::record_internal::RecordInternal;
@@ -13,8 +14,10 @@ use shard::search::CoreSearchRequestBatch;
 
 use super::ShardReplicaSet;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::time_travel::AsOf;
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::shards::shard::Shard;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -234,6 +237,7 @@ impl ShardReplicaSet {
         }
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(shard_id = self.shard_id)))]
     pub async fn query_batch(
         &self,
         requests: Arc<Vec<ShardQueryRequest>>,
@@ -260,6 +264,48 @@ impl ShardReplicaSet {
         .await
     }
 
+    /// Query the local replica as it was at `as_of`.
+    ///
+    /// Past states of the shard are kept by each replica independently, so the query is served
+    /// only by an active replica on this peer.
+    pub async fn query_batch_as_of(
+        &self,
+        requests: Arc<Vec<ShardQueryRequest>>,
+        as_of: AsOf,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        let is_active = self
+            .peer_state(self.this_peer_id())
+            .is_some_and(|state| state.is_active());
+
+        let local = self.local.read().await;
+        match local.as_ref() {
+            Some(Shard::Local(local_shard)) if is_active => {
+                local_shard
+                    .query_batch_as_of(
+                        requests,
+                        as_of,
+                        &self.search_runtime,
+                        timeout,
+                        hw_measurement_acc,
+                    )
+                    .await
+            }
+            Some(
+                Shard::Local(_)
+                | Shard::Proxy(_)
+                | Shard::ForwardProxy(_)
+                | Shard::QueueProxy(_)
+                | Shard::Dummy(_),
+            )
+            | None => Err(CollectionError::bad_request(format!(
+                "Time-travel queries require an active replica of shard {} on this peer",
+                self.shard_id,
+            ))),
+        }
+    }
+
     pub async fn facet(
         &self,
         request: Arc<FacetParams>,
@@ -281,4 +327,31 @@ impl ShardReplicaSet {
         )
         .await
     }
+
+    pub async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        read_consistency: Option<ReadConsistency>,
+        local_only: bool,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics> {
+        self.execute_and_resolve_read_operation(
+            |shard| {
+                let query = query.clone();
+                let search_runtime = self.search_runtime.clone();
+
+                let hw_acc = hw_measurement_acc.clone();
+                async move {
+                    shard
+                        .text_query_statistics(query, &search_runtime, timeout, hw_acc)
+                        .await
+                }
+                .boxed()
+            },
+            read_consistency,
+            local_only,
+        )
+        .await
+    }
 }
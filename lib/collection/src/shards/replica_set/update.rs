@@ -901,6 +901,8 @@ mod tests {
         flush_interval_sec: 30,
         max_optimization_threads: Some(2),
         prevent_unoptimized: None,
+        segment_retention_sec: None,
+        graph_heal_threshold: None,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -930,6 +932,7 @@ mod tests {
             strict_mode_config: None,
             uuid: None,
             metadata: None,
+            read_only: false,
         };
 
         let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
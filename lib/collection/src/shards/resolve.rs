@@ -5,6 +5,7 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 use segment::data_types::facets::{FacetResponse, FacetValue};
+use segment::data_types::text_query::TextQueryStatistics;
 use segment::types::{Payload, ScoredPoint};
 use shard::retrieve::record_internal::RecordInternal;
 use tinyvec::TinyVec;
@@ -133,6 +134,16 @@ impl Resolve for FacetResponse {
     }
 }
 
+impl Resolve for TextQueryStatistics {
+    /// Statistics only weight the scores, so the replica with the most indexed points is used
+    fn resolve(responses: Vec<Self>, _condition: ResolveCondition) -> Self {
+        responses
+            .into_iter()
+            .max_by_key(|statistics| statistics.points_count)
+            .unwrap_or_default()
+    }
+}
+
 impl Resolve for Vec<RecordInternal> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         Resolver::resolve(records, |record| record.id, record_eq, condition)
@@ -17,9 +17,9 @@ use tokio::sync::oneshot;
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
 use crate::collection_manager::optimizers::TrackerLog;
-use crate::operations::OperationWithClockTag;
 use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
 use crate::operations::types::{CollectionError, CollectionResult, OptimizersStatus};
+use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::{LocalShard, LocalShardOptimizations};
@@ -419,6 +419,35 @@ impl Shard {
         local.get_wal_entries(count).await
     }
 
+    pub async fn read_wal_changes(
+        &self,
+        from: Option<SeqNumberType>,
+        limit: usize,
+    ) -> CollectionResult<(
+        Vec<(SeqNumberType, CollectionUpdateOperations)>,
+        SeqNumberType,
+    )> {
+        let local = match self {
+            Shard::Local(local) => local,
+            Shard::Proxy(proxy) => &proxy.wrapped_shard,
+            Shard::ForwardProxy(proxy) => &proxy.wrapped_shard,
+
+            Shard::QueueProxy(proxy) => match proxy.wrapped_shard() {
+                Some(wrapped) => wrapped,
+                None => {
+                    return Err(CollectionError::service_error(format!(
+                        "Cannot read WAL changes on {}",
+                        self.variant_name(),
+                    )));
+                }
+            },
+
+            Shard::Dummy(dummy) => return Err(dummy.dummy_error("read_wal_changes")),
+        };
+
+        local.read_wal_changes(from, limit).await
+    }
+
     pub async fn set_extended_wal_retention(&self) {
         match self {
             Shard::Local(local) => local.set_extended_wal_retention().await,
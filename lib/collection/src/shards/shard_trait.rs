@@ -5,6 +5,7 @@ use async_trait::async_trait;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::DeferredBehavior;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::types::*;
 use shard::count::CountRequestInternal;
 use shard::retrieve::record_internal::RecordInternal;
@@ -166,6 +167,15 @@ pub trait ShardOperation {
         hw_measurement_acc: HwMeasurementAcc,
     ) -> CollectionResult<FacetResponse>;
 
+    /// Collect statistics of the text query tokens over the shard, required for BM25 scoring
+    async fn text_query_statistics(
+        &self,
+        query: Arc<TextQuery>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> CollectionResult<TextQueryStatistics>;
+
     /// Signal `Stop` to all background operations gracefully
     /// and wait till they are finished.
     async fn stop_gracefully(self);
@@ -242,10 +242,7 @@ pub(super) async fn transfer_snapshot(
     log::trace!("Transferring and recovering shard {shard_id} snapshot on peer {remote_peer_id}");
 
     // Since we are providing access to local instance, any of the API keys can be used
-    let local_api_key = channel_service
-        .api_key
-        .as_deref()
-        .or(channel_service.alt_api_key.as_deref());
+    let local_api_key = channel_service.local_api_key();
 
     remote_shard
         .recover_shard_snapshot_from_url(
@@ -254,7 +251,7 @@ pub(super) async fn transfer_snapshot(
             &shard_download_url,
             SnapshotPriority::ShardTransfer,
             // Provide API key here so the remote can access our snapshot
-            local_api_key,
+            local_api_key.as_deref(),
         )
         .await
         .map_err(|err| {
@@ -9,6 +9,7 @@ use segment::types::{
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::collection::recall_estimation::RecallEstimate;
 use crate::config::{CollectionConfigInternal, CollectionParams, WalConfig};
 use crate::operations::types::{OptimizersStatus, ReshardingInfo, ShardStatus, ShardTransferInfo};
 use crate::optimizers_builder::OptimizersConfig;
@@ -41,6 +42,10 @@ pub struct CollectionTelemetry {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[anonymize(false)]
     pub shard_clean_tasks: Option<HashMap<ShardId, ShardCleanStatusTelemetry>>,
+
+    /// Latest recall of approximate search, measured by the recall estimation job
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recall: Option<HashMap<VectorNameBuf, RecallEstimate>>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -171,6 +176,7 @@ impl From<CollectionConfigInternal> for CollectionConfigTelemetry {
             strict_mode_config,
             uuid,
             metadata,
+            read_only: _,
         } = config;
         CollectionConfigTelemetry {
             params,
@@ -442,6 +448,7 @@ mod internal_conversions {
                 transfers,
                 resharding,
                 shard_clean_tasks,
+                recall: None, // Not provided in internal service
             })
         }
     }
@@ -456,6 +463,7 @@ mod internal_conversions {
                 transfers,
                 resharding,
                 shard_clean_tasks,
+                recall: _,
             } = value;
 
             grpc::CollectionTelemetry {
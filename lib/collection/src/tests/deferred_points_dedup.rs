@@ -34,6 +34,7 @@ fn random_points() -> Vec<PointStructPersisted> {
             id: i.into(),
             vector: VectorStructInternal::from(random_vector(&mut rng, DIM)).into(),
             payload: None,
+            expires_at: None,
         })
         .collect()
 }
@@ -76,6 +77,8 @@ async fn build_shard() -> (LocalShard, TempDir) {
         flush_interval_sec: 0,
         max_optimization_threads: Some(2),
         prevent_unoptimized: Some(true),
+        segment_retention_sec: None,
+        graph_heal_threshold: None,
     };
 
     let config = CollectionConfigInternal {
@@ -87,6 +90,7 @@ async fn build_shard() -> (LocalShard, TempDir) {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let payload_index_schema_file = collection_dir.path().join("payload.json");
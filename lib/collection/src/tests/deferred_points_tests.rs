@@ -41,6 +41,7 @@ fn make_upsert_op(point_id: u64) -> CollectionUpdateOperations {
             ])
             .into(),
             payload: None,
+            expires_at: None,
         }]),
     ))
 }
@@ -74,6 +75,7 @@ async fn retrieve_point(shard: &LocalShard, point_id: u64) -> bool {
         ids: vec![point_id.into()],
         with_payload: None,
         with_vector: WithVector::Bool(false),
+        at_version: None,
     });
     let current_runtime = Handle::current();
     let retrieved = shard
@@ -24,6 +24,8 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
     prevent_unoptimized: None,
+    segment_retention_sec: None,
+    graph_heal_threshold: None,
 };
 
 pub fn create_collection_config_with_dim(dim: usize) -> CollectionConfigInternal {
@@ -52,6 +54,7 @@ pub fn create_collection_config_with_dim(dim: usize) -> CollectionConfigInternal
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     }
 }
 
@@ -67,6 +70,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
             payload: Some(
                 serde_json::from_str(r#"{ "location": { "lat": 10.12, "lon": 32.12  } }"#).unwrap(),
             ),
+            expires_at: None,
         },
         PointStructPersisted {
             id: 2.into(),
@@ -74,6 +78,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
             payload: Some(
                 serde_json::from_str(r#"{ "location": { "lat": 11.12, "lon": 34.82  } }"#).unwrap(),
             ),
+            expires_at: None,
         },
         PointStructPersisted {
             id: 3.into(),
@@ -81,6 +86,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
             payload: Some(
                 serde_json::from_str(r#"{ "location": [ { "lat": 12.12, "lon": 34.82  }, { "lat": 12.2, "lon": 12.82  }] }"#).unwrap(),
             ),
+            expires_at: None,
         },
         PointStructPersisted {
             id: 4.into(),
@@ -88,6 +94,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
             payload: Some(
                 serde_json::from_str(r#"{ "location": { "lat": 13.12, "lon": 34.82  } }"#).unwrap(),
             ),
+            expires_at: None,
         },
         PointStructPersisted {
             id: 5.into(),
@@ -95,6 +102,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
             payload: Some(
                 serde_json::from_str(r#"{ "location": { "lat": 14.12, "lon": 32.12  } }"#).unwrap(),
             ),
+            expires_at: None,
         },
     ];
 
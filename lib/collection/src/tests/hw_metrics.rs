@@ -87,6 +87,7 @@ async fn test_hw_metrics_cancellation() {
         let hw_counter = HwMeasurementAcc::new_with_metrics_drain(outer_hw.clone());
         let search_res = shard
             .do_search(
+                &shard.segments(),
                 Arc::new(req),
                 &current_runtime,
                 Duration::from_millis(10), // Very short duration to hit timeout before the search finishes
@@ -128,6 +129,7 @@ fn make_random_points_upsert_op(len: usize) -> CollectionUpdateOperations {
             id: segment::types::ExtendedPointId::NumId(i),
             vector: VectorStructInternal::from(rand_vector).into(),
             payload: None,
+            expires_at: None,
         });
     }
 
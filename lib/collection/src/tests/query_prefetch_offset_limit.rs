@@ -63,6 +63,7 @@ async fn fixture() -> Collection {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -113,6 +114,7 @@ async fn fixture() -> Collection {
                 (0..DIM).map(|_| rng().random_range(0.0..1.0)).collect(),
             ),
             payload: None,
+            expires_at: None,
         })
         .collect();
     let operation = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
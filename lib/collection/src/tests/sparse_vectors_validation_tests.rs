@@ -33,6 +33,7 @@ fn wrong_point_struct() -> PointStruct {
         id: 0.into(),
         vector: VectorStruct::Named(vector_data),
         payload: None,
+        ttl: None,
     }
 }
 
@@ -319,6 +319,7 @@ async fn test_truncate_unapplied_wal() {
             id: i.into(),
             vector: VectorStructInternal::from(vec![i as f32, 2.0, 3.0, 4.0]).into(),
             payload: None,
+            expires_at: None,
         };
         let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
             PointInsertOperationsInternal::PointsList(vec![point]),
@@ -351,6 +352,7 @@ async fn test_truncate_unapplied_wal() {
         ids: all_point_ids,
         with_payload: None,
         with_vector: WithVector::Bool(false),
+        at_version: None,
     });
 
     let retrieved = shard
@@ -398,6 +400,7 @@ async fn test_truncate_unapplied_wal() {
         id: 99999.into(),
         vector: VectorStructInternal::from(vec![99.0, 99.0, 99.0, 99.0]).into(),
         payload: None,
+        expires_at: None,
     };
     let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
         PointInsertOperationsInternal::PointsList(vec![new_point]),
@@ -473,6 +476,7 @@ async fn test_wal_replay_loads_pending_to_queue() {
             id: i.into(),
             vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0]).into(),
             payload: None,
+            expires_at: None,
         };
         let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
             PointInsertOperationsInternal::PointsList(vec![point]),
@@ -628,6 +632,7 @@ async fn test_wal_replay_with_smaller_queue_size() {
             id: i.into(),
             vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0]).into(),
             payload: None,
+            expires_at: None,
         };
         let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
             PointInsertOperationsInternal::PointsList(vec![point]),
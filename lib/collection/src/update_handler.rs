@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
@@ -7,7 +8,7 @@ use common::budget::ResourceBudget;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::save_on_disk::SaveOnDisk;
 use parking_lot::Mutex;
-use segment::types::SeqNumberType;
+use segment::types::{QuantizationConfig, SeqNumberType, VectorNameBuf};
 use shard::operations::CollectionUpdateOperations;
 use shard::segment_holder::locked::LockedSegmentHolder;
 use tokio::runtime::Handle;
@@ -20,7 +21,7 @@ use crate::collection_manager::optimizers::TrackerLog;
 use crate::collection_manager::optimizers::segment_optimizer::{
     SegmentOptimizer, plan_optimizations,
 };
-use crate::common::stoppable_task::StoppableTaskHandle;
+use crate::common::stoppable_task::{StoppableTaskHandle, spawn_stoppable};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::CollectionResult;
 use crate::shards::CollectionId;
@@ -29,6 +30,7 @@ use crate::shards::update_tracker::UpdateTracker;
 use crate::update_workers::UpdateWorkers;
 use crate::update_workers::applied_seq::AppliedSeqHandler;
 use crate::update_workers::internal_update_result::InternalUpdateResult;
+use crate::wal_archive::WalArchiver;
 use crate::wal_delta::LockedWal;
 
 pub type Optimizer = dyn SegmentOptimizer + Sync + Send;
@@ -117,6 +119,11 @@ pub struct UpdateHandler {
     /// If enabled, use deferred points to skip them from read/search while they are not optimized.
     pub prevent_unoptimized: bool,
 
+    /// Fraction of deleted points in HNSW graphs, at which they are healed while there is nothing
+    /// to optimize. Healing is disabled if not set.
+    /// This parameter depends on the optimizer config and should be updated accordingly.
+    pub graph_heal_threshold: Option<f64>,
+
     /// Highest and cutoff clocks for the shard WAL.
     clocks: LocalShardClocks,
     shard_path: PathBuf,
@@ -153,6 +160,7 @@ impl UpdateHandler {
         flush_interval_sec: u64,
         max_optimization_threads: Option<usize>,
         prevent_unoptimized: bool,
+        graph_heal_threshold: Option<f64>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
         scroll_read_lock: Arc<tokio::sync::RwLock<()>>,
@@ -180,6 +188,7 @@ impl UpdateHandler {
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
             prevent_unoptimized,
+            graph_heal_threshold,
             clocks,
             shard_path,
             has_triggered_optimizers: Default::default(),
@@ -212,6 +221,7 @@ impl UpdateHandler {
                 self.scroll_read_lock.clone(),
                 self.update_tracker.clone(),
                 optimization_finished_sender,
+                self.graph_heal_threshold,
             ),
         ));
 
@@ -247,6 +257,16 @@ impl UpdateHandler {
         let clocks = self.clocks.clone();
         let flush_interval_sec = self.flush_interval_sec;
         let shard_path = self.shard_path.clone();
+        let wal_archiver = WalArchiver::new(
+            &self.shared_storage_config,
+            &self.collection_name,
+            &shard_path,
+        )
+        .unwrap_or_else(|err| {
+            log::error!("Failed to set up WAL archiving, archiving is disabled: {err}");
+            None
+        })
+        .map(Arc::new);
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(UpdateWorkers::flush_worker_fn(
             segments,
@@ -256,6 +276,7 @@ impl UpdateHandler {
             flush_interval_sec,
             flush_rx,
             shard_path,
+            wal_archiver,
         )));
 
         self.flush_stop = Some(flush_tx);
@@ -285,6 +306,48 @@ impl UpdateHandler {
         }
     }
 
+    /// Retrain quantization of non-appendable segments in the background.
+    ///
+    /// The task shares the resource budget with optimizations, and is tracked along with them,
+    /// so it is stopped together with optimizations.
+    pub async fn run_requantization(&self) {
+        let segments = self.segments.clone();
+        let resource_budget = self.optimizer_resource_budget.clone();
+
+        let handle = {
+            let _runtime = self.runtime_handle.enter();
+            spawn_stoppable(move |stopped| {
+                UpdateWorkers::requantize_fn(segments, resource_budget, stopped)
+            })
+        };
+        self.optimization_handles.lock().await.push(handle);
+    }
+
+    /// Rebuild quantized vectors of non-appendable segments in the background, if they don't
+    /// match `quantization_configs` and can be rebuilt without optimizing the whole segment.
+    ///
+    /// Like requantization, the task is tracked along with optimizations.
+    pub async fn run_quantization_rebuild(
+        &self,
+        quantization_configs: HashMap<VectorNameBuf, QuantizationConfig>,
+    ) {
+        let segments = self.segments.clone();
+        let resource_budget = self.optimizer_resource_budget.clone();
+
+        let handle = {
+            let _runtime = self.runtime_handle.enter();
+            spawn_stoppable(move |stopped| {
+                UpdateWorkers::rebuild_quantization_fn(
+                    segments,
+                    quantization_configs,
+                    resource_budget,
+                    stopped,
+                )
+            })
+        };
+        self.optimization_handles.lock().await.push(handle);
+    }
+
     /// Gracefully wait before all optimizations stop
     /// If some optimization is in progress - it will be finished before shutdown.
     /// Returns the receiver with any pending update operations. None if there were no update worker.
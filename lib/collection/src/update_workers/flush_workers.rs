@@ -4,15 +4,19 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use std::time::Duration;
 
+use common::counter::hardware_counter::HardwareCounterCell;
 use common::panic;
 use segment::common::operation_error::OperationResult;
+use segment::segment::point_expirations::unix_timestamp_now;
 use segment::types::SeqNumberType;
 use shard::segment_holder::locked::LockedSegmentHolder;
 use shard::wal::WalError;
 use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 
 use crate::shards::local_shard::LocalShardClocks;
 use crate::update_workers::UpdateWorkers;
+use crate::wal_archive::WalArchiver;
 use crate::wal_delta::LockedWal;
 
 impl UpdateWorkers {
@@ -36,6 +40,7 @@ impl UpdateWorkers {
         wal_keep_from: Arc<AtomicU64>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
+        wal_archiver: Option<Arc<WalArchiver>>,
     ) {
         log::trace!("Attempting flushing");
         let wal_flush_job = wal.blocking_lock().flush_async();
@@ -62,6 +67,17 @@ impl UpdateWorkers {
             return;
         }
 
+        // Delete expired points, deletions are persisted with the next flush of segments
+        let now = unix_timestamp_now();
+        match segments
+            .read()
+            .delete_expired_points(now, &HardwareCounterCell::disposable())
+        {
+            Ok(0) => {}
+            Ok(deleted) => log::debug!("Deleted {deleted} expired points"),
+            Err(err) => log::warn!("Failed to delete expired points: {err}"),
+        }
+
         let confirmed_version = Self::flush_segments(segments.clone());
         let confirmed_version = match confirmed_version {
             Ok(version) => version,
@@ -74,6 +90,14 @@ impl UpdateWorkers {
             }
         };
 
+        // Remember the version at this moment, to resolve timestamps of pinned queries
+        let retention = segments.read().retention.clone();
+        if let Some(retention) = retention
+            && let Err(err) = retention.checkpoint(confirmed_version)
+        {
+            log::warn!("Failed to update segment retention: {err}");
+        }
+
         // Acknowledge confirmed version in WAL, but don't acknowledge the specified
         // `keep_from` index or higher.
         // This is to prevent truncating WAL entries that other bits of code still depend on
@@ -93,7 +117,16 @@ impl UpdateWorkers {
             segments.write().report_optimizer_error(err);
         }
 
-        if let Err(err) = wal.blocking_lock().ack(ack) {
+        let mut wal = wal.blocking_lock();
+
+        // Stage closed segments for archiving before acknowledging, which may remove them
+        if let Some(wal_archiver) = &wal_archiver
+            && let Err(err) = wal_archiver.stage_closed_segments(&wal)
+        {
+            log::warn!("Failed to stage WAL segments for archiving: {err}");
+        }
+
+        if let Err(err) = wal.ack(ack) {
             log::warn!("Failed to acknowledge WAL version: {err}");
             segments.write().report_optimizer_error(err);
         }
@@ -108,7 +141,11 @@ impl UpdateWorkers {
         flush_interval_sec: u64,
         mut stop_receiver: oneshot::Receiver<()>,
         shard_path: PathBuf,
+        wal_archiver: Option<Arc<WalArchiver>>,
     ) {
+        // Upload of archived WAL segments, runs in the background to not delay flushes
+        let mut wal_archive_task: Option<JoinHandle<()>> = None;
+
         loop {
             tokio::select! {
                 biased;
@@ -126,6 +163,7 @@ impl UpdateWorkers {
             let wal_keep_from_clone = wal_keep_from.clone();
             let clocks_clone = clocks.clone();
             let shard_path_clone = shard_path.clone();
+            let wal_archiver_clone = wal_archiver.clone();
 
             tokio::task::spawn_blocking(move || {
                 Self::flush_worker_internal(
@@ -134,12 +172,28 @@ impl UpdateWorkers {
                     wal_keep_from_clone,
                     clocks_clone,
                     shard_path_clone,
+                    wal_archiver_clone,
                 )
             })
             .await
             .unwrap_or_else(|error| {
                 log::error!("Flush worker failed: {error}",);
             });
+
+            let Some(wal_archiver) = &wal_archiver else {
+                continue;
+            };
+            if wal_archive_task
+                .as_ref()
+                .is_none_or(|task| task.is_finished())
+            {
+                let wal_archiver = wal_archiver.clone();
+                wal_archive_task = Some(tokio::spawn(async move {
+                    if let Err(err) = wal_archiver.upload_staged().await {
+                        log::warn!("Failed to archive WAL segments: {err}");
+                    }
+                }));
+            }
         }
     }
 }
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourceBudget;
+use common::cpu::get_num_cpus;
+use parking_lot::RwLock;
+use segment::common::operation_error::OperationError;
+use segment::segment::Segment;
+use segment::types::VectorNameBuf;
+use shard::locked_segment::LockedSegment;
+use shard::segment_holder::SegmentId;
+use shard::segment_holder::locked::LockedSegmentHolder;
+
+use crate::update_workers::UpdateWorkers;
+
+impl UpdateWorkers {
+    /// Whether HNSW graph of any vector in non-appendable segments has more deleted points than
+    /// `heal_threshold` allows.
+    pub(crate) fn graph_healing_required(
+        segments: &LockedSegmentHolder,
+        heal_threshold: f64,
+    ) -> bool {
+        !Self::segments_to_heal(segments, heal_threshold).is_empty()
+    }
+
+    /// Reroute links of HNSW graphs around deleted points in non-appendable segments, where the
+    /// fraction of deleted points exceeds `heal_threshold`.
+    ///
+    /// Segments stay searchable with the current graphs until the healed ones are built.
+    /// Segments under optimization are skipped.
+    ///
+    /// Returns `true` if any graph was healed.
+    pub(crate) fn heal_graphs_fn(
+        segments: LockedSegmentHolder,
+        heal_threshold: f64,
+        resource_budget: ResourceBudget,
+        stopped: &AtomicBool,
+    ) -> bool {
+        let segments_to_heal = Self::segments_to_heal(&segments, heal_threshold);
+
+        // Don't take resources from optimizations if there is nothing to do
+        if segments_to_heal.is_empty() {
+            return false;
+        }
+
+        let Some(permit) = resource_budget.acquire(get_num_cpus(), 1, stopped) else {
+            return false;
+        };
+        let max_threads = permit.num_cpus as usize;
+
+        let mut healed = false;
+        for (segment_id, segment, vector_names) in segments_to_heal {
+            for vector_name in vector_names {
+                // Keep the segment searchable while the graph is healed
+                let heal_result = segment
+                    .read()
+                    .heal_graph(&vector_name, max_threads, stopped);
+                let result = heal_result.and_then(|healed_graph| match healed_graph {
+                    Some(healed_graph) => segment
+                        .write()
+                        .replace_healed_graph(&vector_name, healed_graph)
+                        .map(|()| true),
+                    None => Ok(false),
+                });
+
+                match result {
+                    Ok(true) => {
+                        log::debug!(
+                            "Healed HNSW graph of vector '{vector_name}' in segment {segment_id}",
+                        );
+                        healed = true;
+                    }
+                    Ok(false) => {}
+                    Err(OperationError::Cancelled { .. }) => return healed,
+                    Err(err) => log::warn!(
+                        "Failed to heal HNSW graph of vector '{vector_name}' \
+                         in segment {segment_id}: {err}",
+                    ),
+                }
+            }
+        }
+
+        healed
+    }
+
+    /// Original non-appendable segments with vectors, which have HNSW graphs to heal
+    fn segments_to_heal(
+        segments: &LockedSegmentHolder,
+        heal_threshold: f64,
+    ) -> Vec<(SegmentId, Arc<RwLock<Segment>>, Vec<VectorNameBuf>)> {
+        segments
+            .read()
+            .iter()
+            .filter_map(|(segment_id, segment)| match segment {
+                LockedSegment::Original(segment) => Some((segment_id, segment.clone())),
+                LockedSegment::Proxy(_) => None,
+            })
+            .filter_map(|(segment_id, segment)| {
+                let vector_names: Vec<_> = {
+                    let segment_guard = segment.read();
+                    if segment_guard.appendable_flag {
+                        return None;
+                    }
+                    segment_guard
+                        .segment_config
+                        .vector_data
+                        .keys()
+                        .filter(|vector_name| {
+                            segment_guard
+                                .graph_deleted_ratio(vector_name)
+                                .is_some_and(|ratio| ratio > heal_threshold)
+                        })
+                        .cloned()
+                        .collect()
+                };
+                (!vector_names.is_empty()).then_some((segment_id, segment, vector_names))
+            })
+            .collect()
+    }
+}
@@ -1,7 +1,9 @@
 pub mod applied_seq;
 pub mod flush_workers;
+mod graph_healing_worker;
 pub mod internal_update_result;
 mod optimization_worker;
+mod requantize_worker;
 mod update_worker;
 
 pub struct UpdateWorkers {}
@@ -58,6 +58,7 @@ impl UpdateWorkers {
         update_operation_lock: Arc<tokio::sync::RwLock<()>>,
         update_tracker: UpdateTracker,
         optimization_finished_sender: watch::Sender<()>,
+        graph_heal_threshold: Option<f64>,
     ) {
         let Some(some_optimizer) = optimizers.first() else {
             debug_assert!(false, "No optimizers configured");
@@ -186,6 +187,25 @@ impl UpdateWorkers {
                 limit,
             )
             .await;
+
+            // Heal HNSW graphs of segments with deleted points while there is nothing to optimize
+            if let Some(graph_heal_threshold) = graph_heal_threshold {
+                let mut handles = optimization_handles.lock().await;
+                if handles.is_empty()
+                    && Self::graph_healing_required(&segments, graph_heal_threshold)
+                {
+                    let segments = segments.clone();
+                    let resource_budget = optimizer_resource_budget.clone();
+                    handles.push(spawn_stoppable(move |stopped| {
+                        Self::heal_graphs_fn(
+                            segments,
+                            graph_heal_threshold,
+                            resource_budget,
+                            stopped,
+                        )
+                    }));
+                }
+            }
         }
     }
 
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourceBudget;
+use common::cpu::get_num_cpus;
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::segment::Segment;
+use segment::types::{QuantizationConfig, VectorName, VectorNameBuf};
+use segment::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use shard::locked_segment::LockedSegment;
+use shard::segment_holder::locked::LockedSegmentHolder;
+
+use crate::update_workers::UpdateWorkers;
+
+impl UpdateWorkers {
+    /// Retrain quantization of all vectors in non-appendable segments on their current data.
+    ///
+    /// Segments stay searchable with the old quantized vectors until the new ones are built.
+    /// Segments under optimization are skipped, their quantization is rebuilt by the optimizer.
+    ///
+    /// Returns `true` if quantization of any vector was retrained.
+    pub(crate) fn requantize_fn(
+        segments: LockedSegmentHolder,
+        resource_budget: ResourceBudget,
+        stopped: &AtomicBool,
+    ) -> bool {
+        Self::requantize_segments(
+            segments,
+            resource_budget,
+            stopped,
+            |_segment, _vector_name| true,
+            |segment, vector_name, max_threads, stopped| {
+                segment.requantize(vector_name, max_threads, stopped)
+            },
+        )
+    }
+
+    /// Rebuild quantized vectors of non-appendable segments, which don't match the target
+    /// `quantization_configs` anymore, without rebuilding the whole segments.
+    ///
+    /// Only quantized data files are rebuilt, vector storages and HNSW graphs are kept. Segments
+    /// which can't be rebuilt in place are left to the config mismatch optimizer.
+    ///
+    /// Returns `true` if quantization of any vector was rebuilt.
+    pub(crate) fn rebuild_quantization_fn(
+        segments: LockedSegmentHolder,
+        quantization_configs: HashMap<VectorNameBuf, QuantizationConfig>,
+        resource_budget: ResourceBudget,
+        stopped: &AtomicBool,
+    ) -> bool {
+        Self::requantize_segments(
+            segments,
+            resource_budget,
+            stopped,
+            |segment, vector_name| {
+                quantization_configs.get(vector_name).is_some_and(|config| {
+                    segment.requires_quantization_rebuild(vector_name, config)
+                })
+            },
+            |segment, vector_name, max_threads, stopped| {
+                let Some(config) = quantization_configs.get(vector_name) else {
+                    return Ok(None);
+                };
+                segment.rebuild_quantization(vector_name, config, max_threads, stopped)
+            },
+        )
+    }
+
+    /// Build new quantized vectors with `requantize` for vectors of original segments selected by
+    /// `filter`, and replace current quantized vectors with them.
+    fn requantize_segments(
+        segments: LockedSegmentHolder,
+        resource_budget: ResourceBudget,
+        stopped: &AtomicBool,
+        filter: impl Fn(&Segment, &VectorName) -> bool,
+        requantize: impl Fn(
+            &Segment,
+            &VectorName,
+            usize,
+            &AtomicBool,
+        ) -> OperationResult<Option<QuantizedVectors>>,
+    ) -> bool {
+        let original_segments: Vec<_> = segments
+            .read()
+            .iter()
+            .filter_map(|(segment_id, segment)| match segment {
+                LockedSegment::Original(segment) => Some((segment_id, segment.clone())),
+                LockedSegment::Proxy(_) => None,
+            })
+            .filter_map(|(segment_id, segment)| {
+                let vector_names: Vec<_> = {
+                    let segment_guard = segment.read();
+                    segment_guard
+                        .segment_config
+                        .vector_data
+                        .keys()
+                        .filter(|vector_name| filter(&segment_guard, vector_name))
+                        .cloned()
+                        .collect()
+                };
+                (!vector_names.is_empty()).then_some((segment_id, segment, vector_names))
+            })
+            .collect();
+
+        // Don't take resources from optimizations if there is nothing to do
+        if original_segments.is_empty() {
+            return false;
+        }
+
+        let Some(permit) = resource_budget.acquire(get_num_cpus(), 1, stopped) else {
+            return false;
+        };
+        let max_threads = permit.num_cpus as usize;
+
+        let mut requantized = false;
+        for (segment_id, segment, vector_names) in original_segments {
+            for vector_name in vector_names {
+                // Keep the segment searchable while quantized vectors are built
+                let requantize_result =
+                    requantize(&segment.read(), &vector_name, max_threads, stopped);
+                let result =
+                    requantize_result.and_then(|quantized_vectors| match quantized_vectors {
+                        Some(quantized_vectors) => segment
+                            .write()
+                            .replace_quantized_vectors(&vector_name, quantized_vectors)
+                            .map(|()| true),
+                        None => Ok(false),
+                    });
+
+                match result {
+                    Ok(true) => {
+                        log::debug!(
+                            "Rebuilt quantization of vector '{vector_name}' in segment {segment_id}",
+                        );
+                        requantized = true;
+                    }
+                    Ok(false) => {}
+                    Err(OperationError::Cancelled { .. }) => return requantized,
+                    Err(err) => log::warn!(
+                        "Failed to rebuild quantization of vector '{vector_name}' \
+                         in segment {segment_id}: {err}",
+                    ),
+                }
+            }
+        }
+
+        requantized
+    }
+}
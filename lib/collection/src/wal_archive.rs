@@ -0,0 +1,184 @@
+//! Continuous archiving of closed WAL segments into the snapshots storage.
+//!
+//! Archiving happens in two steps:
+//! 1. While the WAL is locked by the flush worker, closed segments are hard-linked into a local
+//!    staging directory. This is cheap, and keeps segment data around even if the WAL truncates
+//!    the segment right after.
+//! 2. Staged segments are uploaded in the background and removed from the staging directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use fs_err as fs;
+use fs_err::tokio as tokio_fs;
+use futures::StreamExt;
+use object_store::ObjectStoreExt;
+use object_store::local::LocalFileSystem;
+use parking_lot::Mutex;
+use shard::wal::SerdeWal;
+
+use crate::common::snapshots_manager::SnapshotsStorageConfig;
+use crate::operations::OperationWithClockTag;
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::snapshot_storage_ops::{multipart_upload, trim_dot_slash};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Directory inside the shard, holding closed WAL segments which are not uploaded yet.
+const WAL_ARCHIVE_STAGING_DIR: &str = "wal_archive_staging";
+
+pub struct WalArchiver {
+    client: Arc<dyn object_store::ObjectStore>,
+    /// Location of this shard's archived segments within `client`
+    prefix: PathBuf,
+    staging_path: PathBuf,
+    retention: Option<Duration>,
+    /// Start index of the most recently staged segment
+    last_staged: Mutex<Option<u64>>,
+}
+
+impl WalArchiver {
+    /// Create an archiver for the given shard, if WAL archiving is configured.
+    pub fn new(
+        shared_storage_config: &SharedStorageConfig,
+        collection_name: &str,
+        shard_path: &Path,
+    ) -> CollectionResult<Option<Self>> {
+        let snapshots_config = &shared_storage_config.snapshots_config;
+        let Some(archive_config) = &snapshots_config.wal_archive else {
+            return Ok(None);
+        };
+
+        let shard_id = shard_path.file_name().ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Invalid shard path for WAL archiving: {}",
+                shard_path.display(),
+            ))
+        })?;
+
+        let archive_path = shared_storage_config
+            .snapshots_path
+            .join(&archive_config.path);
+        let shard_prefix = Path::new(collection_name).join(shard_id);
+
+        let (client, prefix): (Arc<dyn object_store::ObjectStore>, _) =
+            match snapshots_config.snapshots_storage {
+                SnapshotsStorageConfig::Local => {
+                    fs::create_dir_all(&archive_path)?;
+                    let client = LocalFileSystem::new_with_prefix(&archive_path).map_err(|e| {
+                        CollectionError::service_error(format!(
+                            "Failed to open WAL archive directory {}: {e}",
+                            archive_path.display(),
+                        ))
+                    })?;
+                    (Arc::new(client), shard_prefix)
+                }
+                SnapshotsStorageConfig::S3
+                | SnapshotsStorageConfig::Gcs
+                | SnapshotsStorageConfig::Azure => {
+                    let client =
+                        snapshots_config.build_client(&shared_storage_config.object_storage)?;
+                    (Arc::from(client), archive_path.join(shard_prefix))
+                }
+            };
+
+        Ok(Some(Self {
+            client,
+            prefix,
+            staging_path: shard_path.join(WAL_ARCHIVE_STAGING_DIR),
+            retention: archive_config.retention_sec.map(Duration::from_secs),
+            last_staged: Mutex::new(None),
+        }))
+    }
+
+    /// Stage all closed WAL segments, which were not staged yet, for upload.
+    ///
+    /// Must be called before acknowledging the WAL, so segments are not truncated before
+    /// being staged.
+    pub fn stage_closed_segments(
+        &self,
+        wal: &SerdeWal<OperationWithClockTag>,
+    ) -> CollectionResult<()> {
+        let mut last_staged = self.last_staged.lock();
+
+        for (start_index, segment_path) in wal.closed_segment_files() {
+            if last_staged.is_some_and(|last_staged| start_index <= last_staged) {
+                continue;
+            }
+
+            fs::create_dir_all(&self.staging_path)?;
+            let staged_path = self.staging_path.join(format!("closed-{start_index}"));
+
+            if !staged_path.exists()
+                && let Err(err) = fs::hard_link(segment_path, &staged_path)
+            {
+                log::debug!(
+                    "Failed to hard link WAL segment for archiving, copying instead: {err}"
+                );
+                fs::copy(segment_path, &staged_path)?;
+            }
+
+            *last_staged = Some(start_index);
+        }
+
+        Ok(())
+    }
+
+    /// Upload staged segments and remove archived segments past the retention period.
+    pub async fn upload_staged(&self) -> CollectionResult<()> {
+        let mut entries = match tokio_fs::read_dir(&self.staging_path).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut staged = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            staged.push(entry.path());
+        }
+
+        for staged_path in staged {
+            let Some(file_name) = staged_path.file_name() else {
+                continue;
+            };
+            let target_path = self.prefix.join(file_name);
+            multipart_upload(self.client.as_ref(), &staged_path, &target_path).await?;
+            tokio_fs::remove_file(&staged_path).await?;
+            log::trace!("Archived WAL segment {}", target_path.display());
+        }
+
+        self.remove_expired().await
+    }
+
+    async fn remove_expired(&self) -> CollectionResult<()> {
+        let Some(retention) = self.retention else {
+            return Ok(());
+        };
+
+        let prefix = trim_dot_slash(&self.prefix)?;
+        let now = SystemTime::now();
+
+        let mut expired = Vec::new();
+        let mut list_stream = self.client.list(Some(&prefix));
+        while let Some(meta) = list_stream.next().await.transpose().map_err(|e| {
+            CollectionError::service_error(format!("Failed to list archived WAL: {e}"))
+        })? {
+            let age = now
+                .duration_since(SystemTime::from(meta.last_modified))
+                .unwrap_or_default();
+            if age > retention {
+                expired.push(meta.location);
+            }
+        }
+
+        for location in expired {
+            self.client.delete(&location).await.map_err(|e| {
+                CollectionError::service_error(format!(
+                    "Failed to delete archived WAL segment {location}: {e}"
+                ))
+            })?;
+        }
+
+        Ok(())
+    }
+}
@@ -350,6 +350,7 @@ mod tests {
                 id: id.into(),
                 vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0]).into(),
                 payload: None,
+                expires_at: None,
             }]),
         ))
     }
@@ -1425,6 +1426,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            expires_at: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -1471,6 +1473,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            expires_at: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -294,6 +294,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         ids: vec![1.into(), 2.into()],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        at_version: None,
     };
     let retrieved = loaded_collection
         .retrieve(
@@ -350,11 +351,13 @@ fn test_deserialization2() {
             id: 0.into(),
             vector: VectorStructPersisted::from(vec![1.0, 0.0, 1.0, 1.0]),
             payload: None,
+            expires_at: None,
         },
         PointStructPersisted {
             id: 1.into(),
             vector: VectorStructPersisted::from(vec![1.0, 0.0, 1.0, 0.0]),
             payload: None,
+            expires_at: None,
         },
     ];
 
@@ -33,6 +33,8 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
     prevent_unoptimized: None,
+    segment_retention_sec: None,
+    graph_heal_threshold: None,
 };
 
 #[cfg(test)]
@@ -58,6 +60,7 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let snapshot_path = collection_path.join("snapshots");
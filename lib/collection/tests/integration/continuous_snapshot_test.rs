@@ -54,6 +54,7 @@ async fn test_continuous_snapshot() {
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -132,6 +133,7 @@ async fn test_continuous_snapshot() {
                         id: i.into(),
                         vector: VectorStructPersisted::Single(vec![i as f32, 0.0, 0.0, 0.0]),
                         payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+                        expires_at: None,
                     };
                     let insert_points =
                         CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -156,6 +158,7 @@ async fn test_continuous_snapshot() {
                         ids: vec![i.into()],
                         with_payload: None,
                         with_vector: WithVector::Bool(false),
+                        at_version: None,
                     };
                     let hw_counter = HwMeasurementAcc::new();
                     let retrieve_result = collection
@@ -202,6 +205,7 @@ async fn test_continuous_snapshot() {
                         ids: vec![i.into()],
                         with_payload: Some(true.into()),
                         with_vector: WithVector::Bool(true),
+                        at_version: None,
                     };
                     let hw_counter = HwMeasurementAcc::new();
                     let retrieve_result = collection
@@ -64,6 +64,7 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         strict_mode_config: Default::default(),
         uuid: None,
         metadata: None,
+        read_only: false,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -104,6 +105,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStructPersisted::from(VectorStructInternal::from(vectors)),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            expires_at: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -234,6 +236,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
                 ids: vec![6.into()],
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vec![VECTOR1_NAME.to_owned()]),
+                at_version: None,
             },
             None,
             &ShardSelectorInternal::All,
@@ -32,6 +32,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStructPersisted::Single(vec![i as f32, 0.0, 0.0, 0.0]),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            expires_at: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
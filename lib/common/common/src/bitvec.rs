@@ -1,3 +1,4 @@
+use bitvec::field::BitField;
 use bitvec::order::{BitOrder, Lsb0};
 use bitvec::store::BitStore;
 
@@ -26,6 +27,51 @@ pub fn bitvec_set_deleted(bitvec: &mut BitVec, point_id: PointOffsetType, delete
     false
 }
 
+/// Set deleted state for all points set in `keys`, updating the bitvec a word at a time.
+///
+/// Grows bitvec automatically if it is not big enough.
+///
+/// Calls `on_deleted` for every point which was not deleted before, and returns their number.
+pub fn bitvec_set_deleted_batch(
+    bitvec: &mut BitVec,
+    keys: &BitSlice,
+    mut on_deleted: impl FnMut(PointOffsetType),
+) -> usize {
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    let Some(last_key) = keys.last_one() else {
+        return 0;
+    };
+    if bitvec.len() <= last_key {
+        bitvec.resize(last_key + 1, false);
+    }
+
+    let mut newly_deleted = 0;
+    for (word_idx, keys_chunk) in keys[..=last_key].chunks(WORD_BITS).enumerate() {
+        let keys_word: u64 = keys_chunk.load_le();
+        if keys_word == 0 {
+            continue;
+        }
+
+        let start = word_idx * WORD_BITS;
+        let deleted_chunk = &mut bitvec[start..start + keys_chunk.len()];
+        let deleted_word: u64 = deleted_chunk.load_le();
+
+        let mut changed = keys_word & !deleted_word;
+        if changed == 0 {
+            continue;
+        }
+        deleted_chunk.store_le(deleted_word | keys_word);
+        newly_deleted += changed.count_ones() as usize;
+
+        while changed != 0 {
+            on_deleted((start + changed.trailing_zeros() as usize) as PointOffsetType);
+            changed &= changed - 1;
+        }
+    }
+    newly_deleted
+}
+
 pub trait BitSliceExt {
     /// Get a single bit from the slice.
     /// A convenience wrapper around [`BitSlice::get`].
@@ -38,3 +84,38 @@ impl<T: BitStore, O: BitOrder> BitSliceExt for bitvec::slice::BitSlice<T, O> {
         self.get(index).as_deref().copied()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitvec_set_deleted_batch() {
+        let mut bitvec = BitVec::repeat(false, 100);
+        let mut expected = bitvec.clone();
+        for key in [3, 64, 99] {
+            bitvec_set_deleted(&mut bitvec, key, true);
+            bitvec_set_deleted(&mut expected, key, true);
+        }
+
+        let mut keys = BitVec::repeat(false, 200);
+        for key in [0, 3, 63, 64, 65, 130, 150] {
+            keys.set(key, true);
+        }
+
+        let mut changed = Vec::new();
+        let count = bitvec_set_deleted_batch(&mut bitvec, &keys, |key| changed.push(key));
+        for key in keys.iter_ones() {
+            bitvec_set_deleted(&mut expected, key as PointOffsetType, true);
+        }
+
+        assert_eq!(count, 5);
+        assert_eq!(changed, vec![0, 63, 65, 130, 150]);
+        assert_eq!(bitvec.len(), 151);
+        assert_eq!(bitvec, expected);
+
+        // Nothing changes on repeated delete
+        assert_eq!(bitvec_set_deleted_batch(&mut bitvec, &keys, |_| {}), 0);
+        assert_eq!(bitvec, expected);
+    }
+}
@@ -31,6 +31,9 @@ pub struct ResourceBudget {
     io_semaphore: Arc<Semaphore>,
     /// Total IO budget, available and leased out.
     io_budget: usize,
+
+    /// Whether permits should pin threads of their tasks to NUMA nodes.
+    numa_aware: bool,
 }
 
 impl ResourceBudget {
@@ -40,9 +43,18 @@ impl ResourceBudget {
             cpu_budget,
             io_semaphore: Arc::new(Semaphore::new(io_budget)),
             io_budget,
+            numa_aware: false,
         }
     }
 
+    /// Make permits of this budget pin threads of their tasks to NUMA nodes.
+    ///
+    /// Has no effect on machines with a single NUMA node.
+    pub fn with_numa_aware(mut self, numa_aware: bool) -> Self {
+        self.numa_aware = numa_aware;
+        self
+    }
+
     /// Returns the total CPU budget.
     pub fn available_cpu_budget(&self) -> usize {
         self.cpu_budget
@@ -125,12 +137,9 @@ impl ResourceBudget {
         let (num_cpus, cpu_permit) = self.try_acquire_cpu(desired_cpus)?;
         let (num_io, io_permit) = self.try_acquire_io(desired_io)?;
 
-        Some(ResourcePermit::new(
-            num_cpus as u32,
-            cpu_permit,
-            num_io as u32,
-            io_permit,
-        ))
+        let mut permit = ResourcePermit::new(num_cpus as u32, cpu_permit, num_io as u32, io_permit);
+        permit.numa_aware = self.numa_aware;
+        Some(permit)
     }
 
     /// Acquire Resources permit for optimization task from global Resource budget.
@@ -263,6 +272,10 @@ pub struct ResourcePermit {
     /// Semaphore permit.
     io_permit: Option<OwnedSemaphorePermit>,
 
+    /// Whether threads using this permit should be pinned to NUMA nodes, to keep their memory
+    /// accesses local. See [`crate::numa`].
+    pub numa_aware: bool,
+
     /// A callback, which should be called when the permit is changed manually.
     /// Originally used to notify the task manager that a permit is available
     /// and schedule more optimization tasks.
@@ -288,6 +301,7 @@ impl ResourcePermit {
             cpu_permit,
             num_io: io_count,
             io_permit,
+            numa_aware: false,
             on_manual_release: None,
         }
     }
@@ -300,6 +314,7 @@ impl ResourcePermit {
     pub fn merge(&mut self, mut other: Self) {
         self.num_cpus += other.num_cpus;
         self.num_io += other.num_io;
+        self.numa_aware |= other.numa_aware;
 
         // Merge optional semaphore permits
         self.cpu_permit = match (self.cpu_permit.take(), other.cpu_permit.take()) {
@@ -336,6 +351,7 @@ impl ResourcePermit {
             cpu_permit: None,
             num_io: 0,
             io_permit: None,
+            numa_aware: false,
             on_manual_release: None,
         }
     }
@@ -399,6 +415,7 @@ impl Drop for ResourcePermit {
             cpu_permit,
             num_io: _,
             io_permit,
+            numa_aware: _,
             on_manual_release: _, // Only explicit release() should call the callback
         } = self;
 
@@ -4,6 +4,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use super::hardware_counter::HardwareCounterCell;
 use super::hardware_data::HardwareData;
 use crate::cpu_utilization::CpuUtilization;
+use crate::request_profile::RequestProfile;
+use crate::search_diagnostics::SearchDiagnostics;
 
 /// Data structure, that routes hardware measurement counters to specific location.
 /// Shared drain MUST NOT create its own counters, but only hold a reference to the existing one,
@@ -90,9 +92,15 @@ impl Default for HwSharedDrain {
 pub struct HwMeasurementAcc {
     request_drain: Arc<HwSharedDrain>,
     metrics_drain: Arc<HwSharedDrain>,
+    /// Aggregated measurements of the API key or token subject, which issued the request.
+    subject_drain: Option<Arc<HwSharedDrain>>,
     /// If this is set to true, the accumulator will not accumulate any values.
     disposable: bool,
     cpu_utilization: CpuUtilization,
+    /// Per-stage timings of the request, only collected if requested by the user.
+    profile: RequestProfile,
+    /// Statistics of vector index searches, only collected if requested by the user.
+    search_diagnostics: SearchDiagnostics,
 }
 
 impl HwMeasurementAcc {
@@ -101,8 +109,11 @@ impl HwMeasurementAcc {
         Self {
             request_drain: Arc::new(HwSharedDrain::default()),
             metrics_drain: Arc::new(HwSharedDrain::default()),
+            subject_drain: None,
             disposable: false,
             cpu_utilization: CpuUtilization::new(),
+            profile: RequestProfile::disabled(),
+            search_diagnostics: SearchDiagnostics::disabled(),
         }
     }
 
@@ -114,8 +125,11 @@ impl HwMeasurementAcc {
         Self {
             request_drain: Arc::new(HwSharedDrain::default()),
             metrics_drain: Arc::new(HwSharedDrain::default()),
+            subject_drain: None,
             disposable: true,
             cpu_utilization: CpuUtilization::new(),
+            profile: RequestProfile::disabled(),
+            search_diagnostics: SearchDiagnostics::disabled(),
         }
     }
 
@@ -137,19 +151,59 @@ impl HwMeasurementAcc {
         Self {
             request_drain: Arc::new(HwSharedDrain::default()),
             metrics_drain,
+            subject_drain: None,
             disposable: false,
             cpu_utilization: CpuUtilization::new(),
+            profile: RequestProfile::disabled(),
+            search_diagnostics: SearchDiagnostics::disabled(),
         }
     }
 
+    /// Additionally accumulate measurements into the drain of the request issuer.
+    pub fn with_subject_drain(mut self, subject_drain: Option<Arc<HwSharedDrain>>) -> Self {
+        self.subject_drain = subject_drain;
+        self
+    }
+
     pub fn cpu_utilization(&self) -> CpuUtilization {
         self.cpu_utilization.clone()
     }
 
+    /// Enable collection of per-stage timings for this request.
+    pub fn with_profile(mut self, enabled: bool) -> Self {
+        self.profile = if enabled {
+            RequestProfile::enabled()
+        } else {
+            RequestProfile::disabled()
+        };
+        self
+    }
+
+    pub fn request_profile(&self) -> RequestProfile {
+        self.profile.clone()
+    }
+
+    /// Enable collection of vector index search statistics for this request.
+    pub fn with_search_diagnostics(mut self, enabled: bool) -> Self {
+        self.search_diagnostics = if enabled {
+            SearchDiagnostics::enabled()
+        } else {
+            SearchDiagnostics::disabled()
+        };
+        self
+    }
+
+    pub fn search_diagnostics(&self) -> SearchDiagnostics {
+        self.search_diagnostics.clone()
+    }
+
     pub fn accumulate<T: Into<HardwareData>>(&self, src: T) {
         let src = src.into();
         self.request_drain.accumulate_from_hw_data(src);
         self.metrics_drain.accumulate_from_hw_data(src);
+        if let Some(subject_drain) = &self.subject_drain {
+            subject_drain.accumulate_from_hw_data(src);
+        }
     }
 
     /// Accumulate usage values for request drain only.
@@ -223,8 +277,63 @@ impl Clone for HwMeasurementAcc {
         Self {
             request_drain: self.request_drain.clone(),
             metrics_drain: self.metrics_drain.clone(),
+            subject_drain: self.subject_drain.clone(),
             disposable: self.disposable,
             cpu_utilization: self.cpu_utilization.clone(),
+            profile: self.profile.clone(),
+            search_diagnostics: self.search_diagnostics.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_drain_accumulates_usage() {
+        let subject_drain = Arc::new(HwSharedDrain::default());
+        let accumulator = HwMeasurementAcc::new().with_subject_drain(Some(subject_drain.clone()));
+
+        accumulator.clone().accumulate(HardwareData {
+            cpu: 3,
+            payload_io_read: 5,
+            ..Default::default()
+        });
+
+        {
+            let counter = accumulator.get_counter_cell();
+            counter.cpu_counter().incr_delta(4);
+        }
+
+        assert_eq!(accumulator.get_cpu(), 7);
+        assert_eq!(subject_drain.get_cpu(), 7);
+        assert_eq!(subject_drain.get_payload_io_read(), 5);
+    }
+
+    #[test]
+    fn test_subject_drain_skips_remote_usage() {
+        let subject_drain = Arc::new(HwSharedDrain::default());
+        let accumulator = HwMeasurementAcc::new().with_subject_drain(Some(subject_drain.clone()));
+
+        accumulator.accumulate_request(HardwareData {
+            cpu: 10,
+            ..Default::default()
+        });
+
+        assert_eq!(accumulator.get_cpu(), 10);
+        assert_eq!(subject_drain.get_cpu(), 0);
+    }
+
+    #[test]
+    fn test_accumulate_without_subject_drain() {
+        let accumulator = HwMeasurementAcc::new().with_subject_drain(None);
+
+        accumulator.accumulate(HardwareData {
+            cpu: 2,
+            ..Default::default()
+        });
+
+        assert_eq!(accumulator.get_cpu(), 2);
+    }
+}
@@ -2,6 +2,8 @@ use super::counter_cell::CounterCell;
 use super::hardware_accumulator::HwMeasurementAcc;
 use super::hardware_data::HardwareData;
 use crate::cpu_utilization::CpuUtilization;
+use crate::request_profile::RequestProfile;
+use crate::search_diagnostics::SearchDiagnostics;
 
 /// Collection of different types of hardware measurements.
 ///
@@ -92,6 +94,22 @@ impl HardwareCounterCell {
         self.accumulator.as_ref().map(|a| a.cpu_utilization())
     }
 
+    /// Profile of the request this counter belongs to. Disabled, if there is no accumulator.
+    pub fn request_profile(&self) -> RequestProfile {
+        self.accumulator
+            .as_ref()
+            .map(|a| a.request_profile())
+            .unwrap_or_default()
+    }
+
+    /// Search diagnostics of the request this counter belongs to. Disabled, if there is no accumulator.
+    pub fn search_diagnostics(&self) -> SearchDiagnostics {
+        self.accumulator
+            .as_ref()
+            .map(|a| a.search_diagnostics())
+            .unwrap_or_default()
+    }
+
     pub fn new_accumulator(&self) -> HwMeasurementAcc {
         self.accumulator
             .clone()
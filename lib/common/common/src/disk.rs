@@ -15,16 +15,7 @@ pub fn dir_disk_size(path: impl Into<PathBuf>) -> std::io::Result<u64> {
             let size = if metadata.is_dir() {
                 dir_disk_size(fs::read_dir(file.path())?)?
             } else {
-                #[cfg(unix)]
-                {
-                    const BLOCK_SIZE: u64 = 512; // aka DEV_BSIZE
-                    use std::os::unix::fs::MetadataExt;
-                    metadata.blocks() * BLOCK_SIZE
-                }
-                #[cfg(not(unix))]
-                {
-                    metadata.len()
-                }
+                metadata_disk_size(&metadata)
             };
             Ok(acc + size)
         })
@@ -33,6 +24,27 @@ pub fn dir_disk_size(path: impl Into<PathBuf>) -> std::io::Result<u64> {
     dir_disk_size(fs::read_dir(path.into())?)
 }
 
+/// How many bytes a file takes on disk.
+///
+/// Note: on non-unix systems, this function returns the apparent/logical
+/// file size rather than actual disk usage.
+pub fn file_disk_size(path: impl AsRef<Path>) -> std::io::Result<u64> {
+    Ok(metadata_disk_size(&fs::metadata(path.as_ref())?))
+}
+
+fn metadata_disk_size(metadata: &std::fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        const BLOCK_SIZE: u64 = 512; // aka DEV_BSIZE
+        use std::os::unix::fs::MetadataExt;
+        metadata.blocks() * BLOCK_SIZE
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.len()
+    }
+}
+
 /// List all files in the given directory recursively.
 ///
 /// Notes:
@@ -15,4 +15,4 @@ pub use ops::{
 pub use safe_delete::{
     safe_delete_in_tmp, safe_delete_with_suffix, sync_parent_dir, sync_parent_dir_async,
 };
-pub use sync::bulk_sync_dir;
+pub use sync::{bulk_sync_dir, defer_sync, is_sync_deferred};
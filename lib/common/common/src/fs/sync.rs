@@ -1,8 +1,35 @@
+use std::cell::Cell;
 use std::io;
 use std::path::Path;
 
 use fs_err::File;
 
+thread_local! {
+    static SYNC_DEFERRED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run `f` with syncing of file data by flushers deferred on the current thread.
+///
+/// Flushers only schedule writing of their data instead of waiting for it, so the caller must
+/// sync the files afterwards, e.g. with a single [`bulk_sync_dir`] for many flushers.
+pub fn defer_sync<T>(f: impl FnOnce() -> T) -> T {
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SYNC_DEFERRED.set(self.0);
+        }
+    }
+
+    let _restore = Restore(SYNC_DEFERRED.replace(true));
+    f()
+}
+
+/// Whether flushers should skip syncing file data on the current thread, see [`defer_sync`]
+pub fn is_sync_deferred() -> bool {
+    SYNC_DEFERRED.get()
+}
+
 /// Commits filesystem caches for the given directory.
 ///
 /// On Linux, it commits the entire filesystem containing the directory.
@@ -25,12 +25,15 @@ pub mod maybe_uninit;
 pub mod mmap;
 pub mod mmap_hashmap;
 pub mod num_traits;
+pub mod numa;
 pub mod panic;
 pub mod process_counter;
 pub mod progress_tracker;
 pub mod rate_limiting;
+pub mod request_profile;
 pub mod save_on_disk;
 pub mod scope_tracker;
+pub mod search_diagnostics;
 pub mod small_uint;
 pub mod sort_utils;
 pub mod stable_hash;
@@ -44,4 +47,5 @@ pub mod typelevel;
 pub mod types;
 pub mod universal_io;
 pub mod validation;
+pub mod varint_links;
 pub mod zeros;
@@ -32,6 +32,7 @@ use memmap2::MmapMut;
 use super::advice::{Advice, AdviceSetting, Madviseable};
 use super::ops;
 use crate::bitvec::BitSlice;
+use crate::fs::is_sync_deferred;
 
 /// Result for mmap errors.
 type Result<T> = std::result::Result<T, Error>;
@@ -166,7 +167,11 @@ where
             move || {
                 // flushing a zero-sized mmap can cause panicking on some systems
                 if !mmap.is_empty() {
-                    mmap.flush()?;
+                    if is_sync_deferred() {
+                        mmap.flush_async()?;
+                    } else {
+                        mmap.flush()?;
+                    }
                 }
                 Ok(())
             }
@@ -8,7 +8,7 @@ pub use mmap_readonly::{MmapSliceReadOnly, MmapTypeReadOnly};
 pub use mmap_rw::{Error, MmapBitSlice, MmapFlusher, MmapSlice, MmapType};
 pub use ops::{
     MULTI_MMAP_IS_SUPPORTED, MULTI_MMAP_SUPPORT_CHECK_RESULT, TEMP_FILE_EXTENSION,
-    create_and_ensure_length, open_read_mmap, open_write_mmap,
+    create_and_ensure_length, open_read_mmap, open_write_mmap, thread_major_page_faults,
 };
 #[expect(deprecated, reason = "Re-exports of deprecated items")]
 pub use ops::{
@@ -51,6 +51,23 @@ pub static MULTI_MMAP_IS_SUPPORTED: LazyLock<bool> = LazyLock::new(|| {
 /// If multi-mmap support is checked at Qdrant startup, the result is stored in this cell.
 pub static MULTI_MMAP_SUPPORT_CHECK_RESULT: OnceLock<bool> = OnceLock::new();
 
+/// Number of major page faults of the current thread, i.e. faults which had to read a page from
+/// disk. Always 0 on platforms other than Linux.
+#[cfg(target_os = "linux")]
+pub fn thread_major_page_faults() -> u64 {
+    // SAFETY: `rusage` is a plain C struct, all zeros is a valid value
+    let mut usage: nix::libc::rusage = unsafe { mem::zeroed() };
+    // SAFETY: getrusage with RUSAGE_THREAD is always valid for the calling thread, and only
+    // writes into `usage`
+    let ret = unsafe { nix::libc::getrusage(nix::libc::RUSAGE_THREAD, &mut usage) };
+    if ret == 0 { usage.ru_majflt as u64 } else { 0 }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn thread_major_page_faults() -> u64 {
+    0
+}
+
 pub fn create_and_ensure_length(path: &Path, length: usize) -> io::Result<File> {
     if path.exists() {
         let file = OpenOptions::new()
@@ -0,0 +1,177 @@
+//! NUMA topology of the machine, used to pin threads of CPU-heavy jobs to NUMA nodes.
+//!
+//! On multi-socket machines memory is attached to sockets, and accessing memory of another socket
+//! is slower. Threads pinned to a node allocate and reuse memory of that node, if the allocator
+//! places pages on first touch, which is the default policy on Linux.
+
+use std::cell::Cell;
+use std::io;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Sysfs directory, which holds a `nodeN` directory for each NUMA node.
+#[cfg(target_os = "linux")]
+const SYSFS_NODES_PATH: &str = "/sys/devices/system/node";
+
+static GLOBAL_TOPOLOGY: LazyLock<Option<NumaTopology>> = LazyLock::new(NumaTopology::detect);
+
+thread_local! {
+    /// NUMA node the current thread is pinned to.
+    static CURRENT_NODE: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// CPUs of NUMA nodes of the machine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaTopology {
+    /// CPUs of each node, ordered by node ID. Nodes without CPUs are skipped.
+    nodes: Vec<Vec<usize>>,
+}
+
+impl NumaTopology {
+    /// Topology of this machine, detected once.
+    ///
+    /// Returns `None` if the machine has a single NUMA node, or topology can't be detected.
+    pub fn global() -> Option<&'static NumaTopology> {
+        GLOBAL_TOPOLOGY.as_ref()
+    }
+
+    fn detect() -> Option<Self> {
+        #[cfg(target_os = "linux")]
+        {
+            match Self::read_from(Path::new(SYSFS_NODES_PATH)) {
+                Ok(topology) => (topology.num_nodes() > 1).then_some(topology),
+                Err(err) => {
+                    log::debug!("Failed to detect NUMA topology, ignoring: {err}");
+                    None
+                }
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            None
+        }
+    }
+
+    /// Read topology from sysfs `path`, holding a `nodeN/cpulist` file for each node.
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn read_from(path: &Path) -> io::Result<Self> {
+        let mut nodes = Vec::new();
+        for entry in fs_err::read_dir(path)? {
+            let entry = entry?;
+            let Some(node_id) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("node"))
+                .and_then(|node_id| node_id.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            let cpu_list = fs_err::read_to_string(entry.path().join("cpulist"))?;
+            let cpus = parse_cpu_list(cpu_list.trim()).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid CPU list of NUMA node {node_id}: {cpu_list:?}"),
+                )
+            })?;
+            if !cpus.is_empty() {
+                nodes.push((node_id, cpus));
+            }
+        }
+        nodes.sort_unstable_by_key(|(node_id, _)| *node_id);
+        Ok(Self {
+            nodes: nodes.into_iter().map(|(_, cpus)| cpus).collect(),
+        })
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Node for the `thread_index`-th thread out of `num_threads`.
+    ///
+    /// Threads are split between nodes in contiguous blocks of equal size, so that neighboring
+    /// threads share a node.
+    pub fn node_for_thread(&self, thread_index: usize, num_threads: usize) -> usize {
+        (thread_index * self.num_nodes() / num_threads.max(1)).min(self.num_nodes() - 1)
+    }
+
+    /// Pin the current thread to CPUs of the `node`.
+    #[cfg(target_os = "linux")]
+    pub fn pin_current_thread(&self, node: usize) -> nix::Result<()> {
+        use nix::sched::{CpuSet, sched_setaffinity};
+        use nix::unistd::Pid;
+
+        let mut cpu_set = CpuSet::new();
+        for &cpu in &self.nodes[node] {
+            cpu_set.set(cpu)?;
+        }
+        // PID 0 is the calling thread
+        sched_setaffinity(Pid::from_raw(0), &cpu_set)?;
+        CURRENT_NODE.set(Some(node));
+        Ok(())
+    }
+}
+
+/// NUMA node the current thread is pinned to with [`NumaTopology::pin_current_thread`].
+pub fn current_node() -> Option<usize> {
+    CURRENT_NODE.get()
+}
+
+/// Parse a CPU list in the kernel format, such as `0-3,8,10-11`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cpu_list(cpu_list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    if cpu_list.is_empty() {
+        return Some(cpus);
+    }
+    for range in cpu_list.split(',') {
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?);
+                if start > end {
+                    return None;
+                }
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(range.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list(""), Some(vec![]));
+        assert_eq!(parse_cpu_list("5"), Some(vec![5]));
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11"),
+            Some(vec![0, 1, 2, 3, 8, 10, 11])
+        );
+        assert_eq!(parse_cpu_list("3-1"), None);
+        assert_eq!(parse_cpu_list("0-"), None);
+        assert_eq!(parse_cpu_list("a"), None);
+    }
+
+    #[test]
+    fn test_read_topology() {
+        let dir = tempfile::tempdir().unwrap();
+        for (node, cpu_list) in [("node1", "4-7\n"), ("node0", "0-3\n"), ("node2", "\n")] {
+            fs_err::create_dir(dir.path().join(node)).unwrap();
+            fs_err::write(dir.path().join(node).join("cpulist"), cpu_list).unwrap();
+        }
+        fs_err::create_dir(dir.path().join("power")).unwrap();
+
+        let topology = NumaTopology::read_from(dir.path()).unwrap();
+        assert_eq!(topology.nodes, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+
+        let nodes: Vec<_> = (0..6)
+            .map(|thread_index| topology.node_for_thread(thread_index, 6))
+            .collect();
+        assert_eq!(nodes, vec![0, 0, 0, 1, 1, 1]);
+        assert_eq!(topology.node_for_thread(0, 1), 0);
+    }
+}
@@ -1,13 +1,13 @@
 use std::time::{Duration, Instant};
 
 /// A rate limiter based on the token bucket algorithm.
-/// Designed to limit the number of requests per minute.
+/// Designed to limit the number of requests per minute or per second.
 /// The bucket is refilled at a constant rate of `tokens_per_sec` tokens per second.
-/// The bucket has a maximum capacity of `capacity_per_minute` tokens to allow for bursts.
+/// The bucket has a maximum capacity of `capacity` tokens to allow for bursts.
 #[derive(Debug)]
 pub struct RateLimiter {
     // Maximum tokens the bucket can hold.
-    capacity_per_minute: u64,
+    capacity: u64,
     // Tokens added per second.
     tokens_per_sec: f64,
     // Current tokens in the bucket.
@@ -21,21 +21,56 @@ impl RateLimiter {
     pub fn new_per_minute(requests_num: usize) -> Self {
         let tokens_per_sec = requests_num as f64 / 60.0;
         RateLimiter {
-            capacity_per_minute: requests_num as u64,
+            capacity: requests_num as u64,
             tokens_per_sec,
             tokens: requests_num as f64, // Start with a full bucket to allow burst at the beginning.
             last_check: Instant::now(),
         }
     }
 
+    /// Create a new rate limiter for `requests_num` requests per second.
+    pub fn new_per_second(requests_num: usize) -> Self {
+        RateLimiter {
+            capacity: requests_num as u64,
+            tokens_per_sec: requests_num as f64,
+            tokens: requests_num as f64, // Start with a full bucket to allow burst at the beginning.
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Maximum number of tokens the bucket can hold.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Number of tokens left in the bucket at the last consumption attempt.
+    pub fn tokens_available(&self) -> f64 {
+        self.tokens
+    }
+
+    /// Whether the bucket is refilled to its capacity, so it is equivalent to a new one.
+    pub fn is_full(&self) -> bool {
+        let elapsed = self.last_check.elapsed();
+        self.tokens + self.tokens_per_sec * elapsed.as_secs_f64() >= self.capacity as f64
+    }
+
     /// Attempt to consume a given number of tokens.
     ///
     /// Returns:
     /// - `Ok(())` if allowed and consumes the tokens.
     /// - `Err(RateLimitError)` if denied.
     pub fn try_consume(&mut self, tokens: f64) -> Result<(), RateLimitError> {
+        self.check(tokens)?;
+        self.tokens -= tokens; // Consume `cost` tokens.
+        Ok(()) // Request allowed.
+    }
+
+    /// Check whether a given number of tokens can be consumed, without consuming them.
+    ///
+    /// Allows to check multiple buckets before consuming from any of them.
+    pub fn check(&mut self, tokens: f64) -> Result<(), RateLimitError> {
         // Consumer wants more than maximum capacity, that's impossible
-        if tokens > self.capacity_per_minute as f64 {
+        if tokens > self.capacity as f64 {
             return Err(RateLimitError::AlwaysOverBudget(
                 "request larger than rate limiter capacity, please try to split your request",
             ));
@@ -47,13 +82,12 @@ impl RateLimiter {
 
         // Refill tokens based on elapsed time.
         self.tokens += self.tokens_per_sec * elapsed.as_secs_f64();
-        if self.tokens > self.capacity_per_minute as f64 {
-            self.tokens = self.capacity_per_minute as f64;
+        if self.tokens > self.capacity as f64 {
+            self.tokens = self.capacity as f64;
         }
 
         if self.tokens >= tokens {
-            self.tokens -= tokens; // Consume `cost` tokens.
-            Ok(()) // Request allowed.
+            Ok(())
         } else {
             let missing_tokens = tokens - self.tokens;
             let retry_after = Duration::from_secs_f64(missing_tokens / self.tokens_per_sec);
@@ -99,7 +133,7 @@ mod tests {
     #[test]
     fn test_rate_one_per_minute() {
         let mut limiter = RateLimiter::new_per_minute(1);
-        assert_eq!(limiter.capacity_per_minute, 1);
+        assert_eq!(limiter.capacity, 1);
         assert_eq_floats(limiter.tokens_per_sec, 0.016, 0.001);
         assert_eq!(limiter.tokens, 1.0);
 
@@ -113,7 +147,7 @@ mod tests {
     #[test]
     fn test_rate_more_per_minute() {
         let mut limiter = RateLimiter::new_per_minute(600);
-        assert_eq!(limiter.capacity_per_minute, 600);
+        assert_eq!(limiter.capacity, 600);
         assert_eq!(limiter.tokens_per_sec, 10.0);
         assert_eq!(limiter.tokens, 600.0);
 
@@ -124,6 +158,39 @@ mod tests {
         assert!((589.0..=590.0).contains(&limiter.tokens));
     }
 
+    #[test]
+    fn test_rate_per_second() {
+        let mut limiter = RateLimiter::new_per_second(2);
+        assert_eq!(limiter.capacity(), 2);
+        assert_eq!(limiter.tokens_per_sec, 2.0);
+
+        assert_eq!(limiter.try_consume(1.0), Ok(()));
+        assert_eq!(limiter.try_consume(1.0), Ok(()));
+        assert!(limiter.tokens_available() < 1.0);
+
+        // rate limit reached
+        match limiter.try_consume(1.0) {
+            Err(RateLimitError::Retry(RetryError { retry_after, .. })) => {
+                assert!(retry_after <= Duration::from_millis(500));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_check_does_not_consume() {
+        let mut limiter = RateLimiter::new_per_minute(1);
+        assert!(limiter.is_full());
+
+        assert_eq!(limiter.check(1.0), Ok(()));
+        assert_eq!(limiter.check(1.0), Ok(()));
+        assert!(limiter.is_full());
+
+        assert_eq!(limiter.try_consume(1.0), Ok(()));
+        assert!(limiter.check(1.0).is_err());
+        assert!(!limiter.is_full());
+    }
+
     #[test]
     fn test_rate_huge_request() {
         let mut limiter = RateLimiter::new_per_minute(100);
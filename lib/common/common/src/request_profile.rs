@@ -0,0 +1,211 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Stage of query processing, tracked by [`RequestProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileStage {
+    /// Cardinality estimation and construction of filter contexts
+    FilterEvaluation,
+    /// Search in HNSW graph, or plain scoring of points
+    GraphTraversal,
+    /// Reading payload and vectors of the found points
+    PayloadFetch,
+    /// Rescoring with original vectors or with a formula
+    Rescoring,
+    /// Merging results from multiple segments and shards
+    ShardMerge,
+}
+
+impl ProfileStage {
+    const COUNT: usize = 5;
+
+    fn index(self) -> usize {
+        match self {
+            ProfileStage::FilterEvaluation => 0,
+            ProfileStage::GraphTraversal => 1,
+            ProfileStage::PayloadFetch => 2,
+            ProfileStage::Rescoring => 3,
+            ProfileStage::ShardMerge => 4,
+        }
+    }
+}
+
+/// Collects time spent in each [`ProfileStage`] and the number of candidates handled by it,
+/// for a single request.
+///
+/// Profiling is disabled by default, in which case all measurements are no-ops.
+/// Cheap to clone — clones share the same inner counters via a single `Arc`.
+#[derive(Debug, Clone, Default)]
+pub struct RequestProfile {
+    inner: Option<Arc<RequestProfileInner>>,
+}
+
+#[derive(Debug, Default)]
+struct RequestProfileInner {
+    time_ns: [AtomicU64; ProfileStage::COUNT],
+    candidates: [AtomicUsize; ProfileStage::COUNT],
+}
+
+/// Measurements of a single [`ProfileStage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageProfile {
+    pub time: Duration,
+    pub candidates: usize,
+}
+
+/// Snapshot of measurements of all stages of a [`RequestProfile`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestProfileData {
+    pub filter_evaluation: StageProfile,
+    pub graph_traversal: StageProfile,
+    pub payload_fetch: StageProfile,
+    pub rescoring: StageProfile,
+    pub shard_merge: StageProfile,
+}
+
+impl RequestProfile {
+    /// Create an enabled profile.
+    pub fn enabled() -> Self {
+        Self {
+            inner: Some(Arc::new(RequestProfileInner::default())),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Run the closure, accounting its wall time to the given stage.
+    pub fn measure<F, R>(&self, stage: ProfileStage, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let Some(inner) = &self.inner else {
+            return f();
+        };
+
+        let start = Instant::now();
+        let result = f();
+        inner.time_ns[stage.index()]
+            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+
+        result
+    }
+
+    pub fn add_time(&self, stage: ProfileStage, time: Duration) {
+        if let Some(inner) = &self.inner {
+            inner.time_ns[stage.index()].fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub fn add_candidates(&self, stage: ProfileStage, candidates: usize) {
+        if let Some(inner) = &self.inner {
+            inner.candidates[stage.index()].fetch_add(candidates, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns measurements of the given stage, or `None` if profiling is disabled.
+    pub fn stage(&self, stage: ProfileStage) -> Option<StageProfile> {
+        let inner = self.inner.as_ref()?;
+        Some(StageProfile {
+            time: Duration::from_nanos(inner.time_ns[stage.index()].load(Ordering::Relaxed)),
+            candidates: inner.candidates[stage.index()].load(Ordering::Relaxed),
+        })
+    }
+
+    /// Returns measurements of all stages, or `None` if profiling is disabled.
+    pub fn data(&self) -> Option<RequestProfileData> {
+        Some(RequestProfileData {
+            filter_evaluation: self.stage(ProfileStage::FilterEvaluation)?,
+            graph_traversal: self.stage(ProfileStage::GraphTraversal)?,
+            payload_fetch: self.stage(ProfileStage::PayloadFetch)?,
+            rescoring: self.stage(ProfileStage::Rescoring)?,
+            shard_merge: self.stage(ProfileStage::ShardMerge)?,
+        })
+    }
+
+    /// Add measurements collected elsewhere, e.g. on a remote shard.
+    pub fn accumulate(&self, data: &RequestProfileData) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let RequestProfileData {
+            filter_evaluation,
+            graph_traversal,
+            payload_fetch,
+            rescoring,
+            shard_merge,
+        } = data;
+
+        for (stage, profile) in [
+            (ProfileStage::FilterEvaluation, filter_evaluation),
+            (ProfileStage::GraphTraversal, graph_traversal),
+            (ProfileStage::PayloadFetch, payload_fetch),
+            (ProfileStage::Rescoring, rescoring),
+            (ProfileStage::ShardMerge, shard_merge),
+        ] {
+            self.add_time(stage, profile.time);
+            self.add_candidates(stage, profile.candidates);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_profile_is_noop() {
+        let profile = RequestProfile::disabled();
+
+        let result = profile.measure(ProfileStage::GraphTraversal, || 42);
+        profile.add_candidates(ProfileStage::GraphTraversal, 10);
+
+        assert_eq!(result, 42);
+        assert_eq!(profile.stage(ProfileStage::GraphTraversal), None);
+    }
+
+    #[test]
+    fn test_cloned_shares_state() {
+        let profile = RequestProfile::enabled();
+        let cloned = profile.clone();
+
+        profile.add_candidates(ProfileStage::Rescoring, 3);
+        cloned.add_candidates(ProfileStage::Rescoring, 4);
+        cloned.add_time(ProfileStage::ShardMerge, Duration::from_millis(5));
+
+        let rescoring = profile.stage(ProfileStage::Rescoring).unwrap();
+        assert_eq!(rescoring.candidates, 7);
+
+        let merge = profile.stage(ProfileStage::ShardMerge).unwrap();
+        assert_eq!(merge.time, Duration::from_millis(5));
+        assert_eq!(merge.candidates, 0);
+    }
+
+    #[test]
+    fn test_accumulate_remote_data() {
+        let remote = RequestProfile::enabled();
+        remote.add_candidates(ProfileStage::GraphTraversal, 10);
+        remote.add_time(ProfileStage::GraphTraversal, Duration::from_millis(2));
+        let remote_data = remote.data().unwrap();
+
+        let profile = RequestProfile::enabled();
+        profile.add_candidates(ProfileStage::GraphTraversal, 5);
+        profile.accumulate(&remote_data);
+
+        let data = profile.data().unwrap();
+        assert_eq!(data.graph_traversal.candidates, 15);
+        assert_eq!(data.graph_traversal.time, Duration::from_millis(2));
+        assert_eq!(data.rescoring, StageProfile::default());
+
+        let disabled = RequestProfile::disabled();
+        disabled.accumulate(&remote_data);
+        assert_eq!(disabled.data(), None);
+    }
+}
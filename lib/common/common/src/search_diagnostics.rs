@@ -0,0 +1,265 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of graph layers, for which hops are tracked separately.
+/// Hops on higher layers are accounted to the last tracked one.
+const MAX_TRACKED_LAYERS: usize = 16;
+
+/// Strategy, chosen by the vector index for a single query vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// Search in HNSW graph
+    Graph,
+    /// Plain search, because exact search was requested
+    PlainExact,
+    /// Plain search, because the HNSW graph has no links
+    PlainIndexDisabled,
+    /// Plain search, because the segment has too few vectors for graph search to pay off
+    PlainSmallSegment,
+    /// Plain search, because too few points match the filter
+    PlainSmallCardinality,
+    /// Plain search, because the segment is not indexed yet
+    PlainNotIndexed,
+}
+
+impl SearchStrategy {
+    const COUNT: usize = 6;
+
+    fn index(self) -> usize {
+        match self {
+            SearchStrategy::Graph => 0,
+            SearchStrategy::PlainExact => 1,
+            SearchStrategy::PlainIndexDisabled => 2,
+            SearchStrategy::PlainSmallSegment => 3,
+            SearchStrategy::PlainSmallCardinality => 4,
+            SearchStrategy::PlainNotIndexed => 5,
+        }
+    }
+}
+
+/// Collects statistics of vector index searches performed for a single request: how much
+/// of the graph was traversed and why segments were searched with or without the graph.
+///
+/// Collection is disabled by default, in which case all updates are no-ops.
+/// Cheap to clone — clones share the same inner counters via a single `Arc`.
+#[derive(Debug, Clone, Default)]
+pub struct SearchDiagnostics {
+    inner: Option<Arc<SearchDiagnosticsInner>>,
+}
+
+#[derive(Debug, Default)]
+struct SearchDiagnosticsInner {
+    visited_nodes: AtomicUsize,
+    scored_vectors: AtomicUsize,
+    hops_per_layer: [AtomicUsize; MAX_TRACKED_LAYERS],
+    early_terminations: AtomicUsize,
+    searches: [AtomicUsize; SearchStrategy::COUNT],
+}
+
+/// Snapshot of [`SearchDiagnostics`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchDiagnosticsData {
+    /// Graph nodes reached through links, including ones rejected by the filter
+    pub visited_nodes: usize,
+    /// Vectors scored during graph traversal
+    pub scored_vectors: usize,
+    /// Nodes whose links were expanded, by graph layer starting from 0.
+    /// Trailing layers without hops are omitted.
+    pub hops_per_layer: Vec<usize>,
+    /// Graph searches stopped early, because top results converged
+    pub early_terminations: usize,
+    pub graph_searches: usize,
+    pub plain_searches_exact: usize,
+    pub plain_searches_index_disabled: usize,
+    pub plain_searches_small_segment: usize,
+    pub plain_searches_small_cardinality: usize,
+    pub plain_searches_not_indexed: usize,
+}
+
+impl SearchDiagnostics {
+    /// Create enabled diagnostics.
+    pub fn enabled() -> Self {
+        Self {
+            inner: Some(Arc::new(SearchDiagnosticsInner::default())),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn add_visited_nodes(&self, count: usize) {
+        if let Some(inner) = &self.inner {
+            inner.visited_nodes.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    pub fn add_scored_vectors(&self, count: usize) {
+        if let Some(inner) = &self.inner {
+            inner.scored_vectors.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    /// Account expansion of a single node on the given graph layer.
+    pub fn add_hop(&self, level: usize) {
+        if let Some(inner) = &self.inner {
+            inner.hops_per_layer[level.min(MAX_TRACKED_LAYERS - 1)].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Account a graph search, stopped early because top results converged.
+    pub fn add_early_termination(&self) {
+        if let Some(inner) = &self.inner {
+            inner.early_terminations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Account `count` query vectors searched with the given strategy.
+    pub fn add_searches(&self, strategy: SearchStrategy, count: usize) {
+        if let Some(inner) = &self.inner {
+            inner.searches[strategy.index()].fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    /// Add statistics collected elsewhere, e.g. on a remote shard.
+    pub fn accumulate(&self, data: &SearchDiagnosticsData) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+
+        let SearchDiagnosticsData {
+            visited_nodes,
+            scored_vectors,
+            hops_per_layer,
+            early_terminations,
+            graph_searches,
+            plain_searches_exact,
+            plain_searches_index_disabled,
+            plain_searches_small_segment,
+            plain_searches_small_cardinality,
+            plain_searches_not_indexed,
+        } = data;
+
+        inner
+            .visited_nodes
+            .fetch_add(*visited_nodes, Ordering::Relaxed);
+        inner
+            .scored_vectors
+            .fetch_add(*scored_vectors, Ordering::Relaxed);
+        for (level, hops) in hops_per_layer.iter().enumerate() {
+            inner.hops_per_layer[level.min(MAX_TRACKED_LAYERS - 1)]
+                .fetch_add(*hops, Ordering::Relaxed);
+        }
+        inner
+            .early_terminations
+            .fetch_add(*early_terminations, Ordering::Relaxed);
+        for (strategy, count) in [
+            (SearchStrategy::Graph, graph_searches),
+            (SearchStrategy::PlainExact, plain_searches_exact),
+            (
+                SearchStrategy::PlainIndexDisabled,
+                plain_searches_index_disabled,
+            ),
+            (
+                SearchStrategy::PlainSmallSegment,
+                plain_searches_small_segment,
+            ),
+            (
+                SearchStrategy::PlainSmallCardinality,
+                plain_searches_small_cardinality,
+            ),
+            (SearchStrategy::PlainNotIndexed, plain_searches_not_indexed),
+        ] {
+            inner.searches[strategy.index()].fetch_add(*count, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns collected statistics, or `None` if diagnostics are disabled.
+    pub fn data(&self) -> Option<SearchDiagnosticsData> {
+        let inner = self.inner.as_ref()?;
+
+        let mut hops_per_layer: Vec<usize> = inner
+            .hops_per_layer
+            .iter()
+            .map(|hops| hops.load(Ordering::Relaxed))
+            .collect();
+        while hops_per_layer.last() == Some(&0) {
+            hops_per_layer.pop();
+        }
+
+        let searches =
+            |strategy: SearchStrategy| inner.searches[strategy.index()].load(Ordering::Relaxed);
+
+        Some(SearchDiagnosticsData {
+            visited_nodes: inner.visited_nodes.load(Ordering::Relaxed),
+            scored_vectors: inner.scored_vectors.load(Ordering::Relaxed),
+            hops_per_layer,
+            early_terminations: inner.early_terminations.load(Ordering::Relaxed),
+            graph_searches: searches(SearchStrategy::Graph),
+            plain_searches_exact: searches(SearchStrategy::PlainExact),
+            plain_searches_index_disabled: searches(SearchStrategy::PlainIndexDisabled),
+            plain_searches_small_segment: searches(SearchStrategy::PlainSmallSegment),
+            plain_searches_small_cardinality: searches(SearchStrategy::PlainSmallCardinality),
+            plain_searches_not_indexed: searches(SearchStrategy::PlainNotIndexed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_diagnostics_is_noop() {
+        let diagnostics = SearchDiagnostics::disabled();
+
+        diagnostics.add_hop(0);
+        diagnostics.add_searches(SearchStrategy::Graph, 1);
+
+        assert_eq!(diagnostics.data(), None);
+    }
+
+    #[test]
+    fn test_accumulate() {
+        let diagnostics = SearchDiagnostics::enabled();
+        let cloned = diagnostics.clone();
+
+        diagnostics.add_hop(0);
+        cloned.add_hop(2);
+        cloned.add_hop(MAX_TRACKED_LAYERS + 5);
+        cloned.add_early_termination();
+        diagnostics.add_searches(SearchStrategy::PlainSmallCardinality, 2);
+
+        let remote = SearchDiagnosticsData {
+            visited_nodes: 10,
+            scored_vectors: 7,
+            hops_per_layer: vec![3],
+            early_terminations: 2,
+            graph_searches: 1,
+            ..Default::default()
+        };
+        cloned.accumulate(&remote);
+
+        let mut expected_hops = vec![0; MAX_TRACKED_LAYERS];
+        expected_hops[0] = 4;
+        expected_hops[2] = 1;
+        expected_hops[MAX_TRACKED_LAYERS - 1] = 1;
+
+        assert_eq!(
+            diagnostics.data().unwrap(),
+            SearchDiagnosticsData {
+                visited_nodes: 10,
+                scored_vectors: 7,
+                hops_per_layer: expected_hops,
+                early_terminations: 3,
+                graph_searches: 1,
+                plain_searches_small_cardinality: 2,
+                ..Default::default()
+            },
+        );
+    }
+}
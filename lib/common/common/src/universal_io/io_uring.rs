@@ -14,6 +14,7 @@ use fs_err as fs;
 use fs_err::os::unix::fs::OpenOptionsExt;
 
 use super::*;
+use crate::fs::is_sync_deferred;
 use crate::generic_consts::AccessPattern;
 use crate::maybe_uninit::assume_init_vec;
 
@@ -21,7 +22,9 @@ thread_local! {
     static IO_URING: io::Result<RefCell<IoUring>> = init_io_uring().map(RefCell::new);
 }
 
-const IO_URING_QUEUE_LENGTH: u32 = 16;
+/// Large enough to submit reads of all neighbors of an HNSW node at once,
+/// so that a graph traversal step waits for the disk only once.
+const IO_URING_QUEUE_LENGTH: u32 = 64;
 
 fn init_io_uring() -> io::Result<IoUring> {
     let io_uring = IoUring::new(IO_URING_QUEUE_LENGTH)
@@ -301,7 +304,12 @@ impl<T: bytemuck::Pod + 'static> UniversalWrite<T> for IoUringFile {
 
     fn flusher(&self) -> Flusher {
         let file = self.file.clone();
-        Box::new(move || Ok(file.sync_all()?))
+        Box::new(move || {
+            if !is_sync_deferred() {
+                file.sync_all()?;
+            }
+            Ok(())
+        })
     }
 }
 
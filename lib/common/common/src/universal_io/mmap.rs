@@ -5,6 +5,8 @@ use std::{fs, slice};
 
 use memmap2::MmapRaw;
 
+use crate::fs::is_sync_deferred;
+
 use super::*;
 use crate::generic_consts::AccessPattern;
 use crate::mmap::{MULTI_MMAP_IS_SUPPORTED, Madviseable as _};
@@ -134,7 +136,11 @@ where
         let flusher = move || {
             // flushing empty mmap returns error on some platforms
             if mmap.len() > 0 {
-                mmap.flush()?;
+                if is_sync_deferred() {
+                    mmap.flush_async()?;
+                } else {
+                    mmap.flush()?;
+                }
             }
 
             Ok(())
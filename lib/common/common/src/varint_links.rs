@@ -0,0 +1,160 @@
+/// Bits of a varint byte, which hold the value.
+const VALUE_BITS: u32 = 7;
+
+/// Set in all bytes of a varint, except the last one.
+const CONTINUATION_BIT: u8 = 1 << VALUE_BITS;
+
+/// A specialized packer to pack HNSW graph links as varints.
+///
+/// Similar to [`crate::bitpacking_links::pack_links`], it assumes that the
+/// first `m` (or `m0`) values could be re-ordered for better compression.
+/// These values are sorted and delta-encoded, so that neighbor ids close to
+/// each other take a single byte. The rest of the values are written as is.
+///
+/// Each value is a LEB128 varint, so no header is needed and the number of
+/// values is the number of bytes without the continuation bit.
+///
+/// Parameters:
+/// - `sorted_count` is `m` (or `m0`) for this layer.
+/// - `raw_links` is in/out parameter. Input: links to pack, output: same links,
+///   but re-ordered.
+pub fn pack_varint_links(links: &mut Vec<u8>, raw_links: &mut [u32], sorted_count: usize) {
+    let sorted_count = raw_links.len().min(sorted_count);
+    raw_links[..sorted_count].sort_unstable();
+
+    let mut previous = 0;
+    for (i, &value) in raw_links.iter().enumerate() {
+        if i < sorted_count {
+            write_varint(links, value - previous);
+            previous = value;
+        } else {
+            write_varint(links, value);
+        }
+    }
+}
+
+/// Returns an iterator over links packed with [`pack_varint_links`].
+#[inline]
+pub fn iterate_varint_links(links: &[u8], sorted_count: usize) -> VarintLinksIterator<'_> {
+    VarintLinksIterator {
+        links,
+        remaining_sorted: sorted_count,
+        current_delta: 0,
+    }
+}
+
+#[inline]
+fn write_varint(links: &mut Vec<u8>, mut value: u32) {
+    while value >= u32::from(CONTINUATION_BIT) {
+        links.push(value as u8 | CONTINUATION_BIT);
+        value >>= VALUE_BITS;
+    }
+    links.push(value as u8);
+}
+
+/// Iterator over links packed with [`pack_varint_links`].
+/// Created by [`iterate_varint_links`].
+pub struct VarintLinksIterator<'a> {
+    links: &'a [u8],
+    remaining_sorted: usize,
+    current_delta: u32,
+}
+
+impl VarintLinksIterator<'_> {
+    #[inline]
+    fn read_varint(&mut self) -> Option<u32> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let (&byte, rest) = self.links.split_first()?;
+            self.links = rest;
+            value |= u32::from(byte & !CONTINUATION_BIT) << shift;
+            if byte & CONTINUATION_BIT == 0 {
+                return Some(value);
+            }
+            shift += VALUE_BITS;
+        }
+    }
+}
+
+impl Iterator for VarintLinksIterator<'_> {
+    type Item = u32;
+
+    #[inline]
+    fn next(&mut self) -> Option<u32> {
+        let value = self.read_varint()?;
+        if self.remaining_sorted > 0 {
+            self.remaining_sorted -= 1;
+            self.current_delta = self.current_delta.wrapping_add(value);
+            return Some(self.current_delta);
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self
+            .links
+            .iter()
+            .filter(|&&byte| byte & CONTINUATION_BIT == 0)
+            .count();
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for VarintLinksIterator<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools as _;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng as _};
+    use rstest::rstest;
+
+    use super::*;
+    use crate::iterator_ext::{check_exact_size_iterator_len, check_iterator_fold};
+
+    #[rstest]
+    #[case::only_unsorted(0, 1..100)]
+    #[case::only_sorted(100, 1..100)]
+    #[case::empty(10, 0..1)]
+    #[case::both(10, 10..100)]
+    fn test_random(#[case] sorted_count: usize, #[case] total_count: std::ops::Range<usize>) {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        for _ in 0..1_000 {
+            let total_count = rng.random_range(total_count.clone());
+            let bits = rng.random_range(8..=32);
+
+            let mut raw_links_orig = gen_unique_values(&mut rng, total_count, bits);
+            let mut raw_links_updated = raw_links_orig.clone();
+            let mut links = Vec::new();
+            pack_varint_links(&mut links, &mut raw_links_updated, sorted_count);
+
+            let unpacked = iterate_varint_links(&links, sorted_count).collect_vec();
+
+            raw_links_orig[..sorted_count.min(total_count)].sort_unstable();
+            assert_eq!(raw_links_orig, unpacked);
+            assert_eq!(raw_links_updated, unpacked);
+
+            check_iterator_fold(|| iterate_varint_links(&links, sorted_count));
+            check_exact_size_iterator_len(iterate_varint_links(&links, sorted_count));
+        }
+    }
+
+    #[test]
+    fn test_varint_bounds() {
+        let mut raw_links = vec![0, 127, 128, 16_383, 16_384, u32::MAX];
+        let mut links = Vec::new();
+        pack_varint_links(&mut links, &mut raw_links, 0);
+        assert_eq!(links.len(), 1 + 1 + 2 + 2 + 3 + 5);
+        assert_eq!(iterate_varint_links(&links, 0).collect_vec(), raw_links);
+    }
+
+    /// Generate `count` unique values in range `[0, 2^bits)`.
+    fn gen_unique_values(rng: &mut StdRng, count: usize, bits: u8) -> Vec<u32> {
+        std::iter::repeat_with(|| rng.random_range(0..1u64 << bits) as u32)
+            .unique()
+            .take(count)
+            .collect::<Vec<u32>>()
+    }
+}
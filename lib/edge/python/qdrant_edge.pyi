@@ -18,7 +18,9 @@ Payload = Dict[str, Any]
 JsonPath = str
 WithPayloadType = Union[bool, List[str], "PayloadSelector"]
 WithVectorType = Union[bool, List[str]]
-ScoringQueryType = Union["Query", "Fusion", "OrderBy", "Formula", "Sample", "Mmr"]
+ScoringQueryType = Union[
+    "Query", "Fusion", "OrderBy", "Formula", "Sample", "Mmr", "TextQuery", "HybridQuery"
+]
 ConditionType = Union[
     "FieldCondition",
     "IsEmptyCondition",
@@ -33,9 +35,15 @@ MatchType = Union[
 ]
 RangeType = Union["RangeFloat", "RangeDateTime"]
 QuantizationConfigType = Union[
-    "ScalarQuantizationConfig", "ProductQuantizationConfig", "BinaryQuantizationConfig"
+    "ScalarQuantizationConfig",
+    "ProductQuantizationConfig",
+    "BinaryQuantizationConfig",
+    "ResidualQuantizationConfig",
+    "AnisotropicQuantizationConfig",
+]
+IndexType = Union[
+    "PlainIndexConfig", "HnswIndexConfig", "VamanaIndexConfig", "IvfIndexConfig"
 ]
-IndexType = Union["PlainIndexConfig", "HnswIndexConfig"]
 StartFromType = Union[int, float, str]
 ExpressionType = "Expression"
 
@@ -365,6 +373,7 @@ class EdgeSparseVectorParams:
         full_scan_threshold: Optional[int] = None,
         on_disk: Optional[bool] = None,
         modifier: Optional["Modifier"] = None,
+        bm25: Optional["Bm25Params"] = None,
         datatype: Optional["VectorStorageDatatype"] = None,
     ) -> None:
         """
@@ -374,6 +383,7 @@ class EdgeSparseVectorParams:
             full_scan_threshold: Threshold for full scan vs index search.
             on_disk: If True, sparse index on disk; otherwise in RAM.
             modifier: Optional modifier (e.g., IDF).
+            bm25: BM25 ranking parameters, used with the IDF modifier.
             datatype: Storage datatype.
         """
         ...
@@ -393,11 +403,43 @@ class EdgeSparseVectorParams:
         """Modifier."""
         ...
 
+    @property
+    def bm25(self) -> Optional["Bm25Params"]:
+        """BM25 ranking parameters."""
+        ...
+
     @property
     def datatype(self) -> Optional["VectorStorageDatatype"]:
         """Storage datatype."""
         ...
 
+class Bm25Params:
+    """BM25 term frequency saturation and document length normalization of sparse vectors."""
+
+    def __init__(
+        self,
+        k1: Optional[float] = None,
+        b: Optional[float] = None,
+    ) -> None:
+        """
+        Create Bm25Params.
+
+        Args:
+            k1: Term frequency saturation. Default: 1.2
+            b: Strength of document length normalization, from 0.0 to 1.0. Default: 0.75
+        """
+        ...
+
+    @property
+    def k1(self) -> float:
+        """Term frequency saturation."""
+        ...
+
+    @property
+    def b(self) -> float:
+        """Strength of document length normalization."""
+        ...
+
 class EdgeOptimizersConfig:
     """Optimizer-related configuration for EdgeConfig."""
 
@@ -472,6 +514,9 @@ class HnswIndexConfig:
         on_disk: Optional[bool] = None,
         payload_m: Optional[int] = None,
         inline_storage: Optional[bool] = None,
+        varint_links: Optional[bool] = None,
+        acorn: Optional[bool] = None,
+        seed: Optional[int] = None,
     ) -> None:
         """
         Create an HnswIndexConfig.
@@ -484,6 +529,9 @@ class HnswIndexConfig:
             on_disk: Whether to store on disk.
             payload_m: Payload index m value.
             inline_storage: Whether to use inline storage.
+            varint_links: Whether to store graph links with varint delta encoding.
+            acorn: Whether to use ACORN for filtered searches instead of payload-aware links.
+            seed: Seed of the random number generator, to build the graph deterministically.
         """
         ...
 
@@ -522,15 +570,133 @@ class HnswIndexConfig:
         """Inline storage flag."""
         ...
 
+    @property
+    def varint_links(self) -> Optional[bool]:
+        """Varint links flag."""
+        ...
+
+    @property
+    def acorn(self) -> Optional[bool]:
+        """ACORN flag."""
+        ...
+
+    @property
+    def seed(self) -> Optional[int]:
+        """Graph build seed."""
+        ...
+
+class VamanaIndexConfig:
+    """Configuration for Vamana (DiskANN) index."""
+
+    def __init__(
+        self,
+        full_scan_threshold: int,
+        max_degree: int = 64,
+        build_list_size: int = 100,
+        beam_width: Optional[int] = None,
+        on_disk: Optional[bool] = None,
+    ) -> None:
+        """
+        Create a VamanaIndexConfig.
+
+        Args:
+            full_scan_threshold: Threshold for full scan.
+            max_degree: Maximal number of edges per node.
+            build_list_size: Number of candidates during index construction.
+            beam_width: Number of candidates expanded at once during search.
+            on_disk: Whether to keep the graph on disk.
+        """
+        ...
+
+    @property
+    def max_degree(self) -> int:
+        """Maximal number of edges per node."""
+        ...
+
+    @property
+    def build_list_size(self) -> int:
+        """Candidate list size during construction."""
+        ...
+
+    @property
+    def beam_width(self) -> Optional[int]:
+        """Beam width of the search."""
+        ...
+
+    @property
+    def full_scan_threshold(self) -> int:
+        """Full scan threshold."""
+        ...
+
+    @property
+    def on_disk(self) -> Optional[bool]:
+        """On-disk flag."""
+        ...
+
+class IvfIndexConfig:
+    """Configuration for IVF (inverted file) index."""
+
+    def __init__(
+        self,
+        full_scan_threshold: int,
+        num_lists: Optional[int] = None,
+        nprobe: Optional[int] = None,
+        pq_num_subvectors: Optional[int] = None,
+        on_disk: Optional[bool] = None,
+    ) -> None:
+        """
+        Create an IvfIndexConfig.
+
+        Args:
+            full_scan_threshold: Threshold for full scan.
+            num_lists: Number of inverted lists (k-means centroids).
+            nprobe: Number of lists scanned during search.
+            pq_num_subvectors: If set, encode vectors in lists with product quantization.
+            on_disk: Whether to keep the lists on disk.
+        """
+        ...
+
+    @property
+    def num_lists(self) -> Optional[int]:
+        """Number of inverted lists."""
+        ...
+
+    @property
+    def nprobe(self) -> Optional[int]:
+        """Number of lists scanned during search."""
+        ...
+
+    @property
+    def pq_num_subvectors(self) -> Optional[int]:
+        """Number of product quantization sub-vectors."""
+        ...
+
+    @property
+    def full_scan_threshold(self) -> int:
+        """Full scan threshold."""
+        ...
+
+    @property
+    def on_disk(self) -> Optional[bool]:
+        """On-disk flag."""
+        ...
+
 class MultiVectorConfig:
     """Configuration for multi-vector storage."""
 
-    def __init__(self, comparator: "MultiVectorComparator") -> None:
+    def __init__(
+        self,
+        comparator: "MultiVectorComparator",
+        max_vectors_per_point: Optional[int] = None,
+        overflow_policy: Optional["MultiVectorOverflowPolicy"] = None,
+    ) -> None:
         """
         Create a MultiVectorConfig.
 
         Args:
             comparator: Multi-vector comparator.
+            max_vectors_per_point: Maximum number of vectors in a single multivector point.
+            overflow_policy: What to do with multivectors, which have more vectors than allowed.
         """
         ...
 
@@ -539,6 +705,16 @@ class MultiVectorConfig:
         """Comparator."""
         ...
 
+    @property
+    def max_vectors_per_point(self) -> Optional[int]:
+        """Maximum number of vectors in a single multivector point."""
+        ...
+
+    @property
+    def overflow_policy(self) -> Optional["MultiVectorOverflowPolicy"]:
+        """Policy for multivectors with too many vectors."""
+        ...
+
 # ============================================================================
 # Quantization Configuration
 # ============================================================================
@@ -551,6 +727,7 @@ class ScalarQuantizationConfig:
         type: "ScalarType",
         quantile: Optional[float] = None,
         always_ram: Optional[bool] = None,
+        drop_originals: Optional[bool] = None,
     ) -> None:
         """
         Create a ScalarQuantizationConfig.
@@ -559,6 +736,7 @@ class ScalarQuantizationConfig:
             type: Scalar type (e.g., Int8).
             quantile: Quantile for normalization.
             always_ram: Whether to keep in RAM.
+            drop_originals: Whether to drop original vectors once quantized.
         """
         ...
 
@@ -577,6 +755,11 @@ class ScalarQuantizationConfig:
         """Always RAM flag."""
         ...
 
+    @property
+    def drop_originals(self) -> Optional[bool]:
+        """Drop originals flag."""
+        ...
+
 class ProductQuantizationConfig:
     """Configuration for product quantization."""
 
@@ -584,6 +767,7 @@ class ProductQuantizationConfig:
         self,
         compression: "CompressionRatio",
         always_ram: Optional[bool] = None,
+        drop_originals: Optional[bool] = None,
     ) -> None:
         """
         Create a ProductQuantizationConfig.
@@ -591,6 +775,7 @@ class ProductQuantizationConfig:
         Args:
             compression: Compression ratio.
             always_ram: Whether to keep in RAM.
+            drop_originals: Whether to drop original vectors once quantized.
         """
         ...
 
@@ -604,6 +789,11 @@ class ProductQuantizationConfig:
         """Always RAM flag."""
         ...
 
+    @property
+    def drop_originals(self) -> Optional[bool]:
+        """Drop originals flag."""
+        ...
+
 class BinaryQuantizationConfig:
     """Configuration for binary quantization."""
 
@@ -638,6 +828,74 @@ class BinaryQuantizationConfig:
         """Query encoding."""
         ...
 
+class ResidualQuantizationConfig:
+    """Configuration for residual quantization."""
+
+    def __init__(
+        self,
+        compression: "CompressionRatio",
+        stages: int,
+        always_ram: Optional[bool] = None,
+    ) -> None:
+        """
+        Create a ResidualQuantizationConfig.
+
+        Args:
+            compression: Total compression ratio of all stages.
+            stages: Number of quantization stages.
+            always_ram: Whether to keep in RAM.
+        """
+        ...
+
+    @property
+    def compression(self) -> "CompressionRatio":
+        """Compression ratio."""
+        ...
+
+    @property
+    def stages(self) -> int:
+        """Number of stages."""
+        ...
+
+    @property
+    def always_ram(self) -> Optional[bool]:
+        """Always RAM flag."""
+        ...
+
+class AnisotropicQuantizationConfig:
+    """Configuration for anisotropic vector quantization."""
+
+    def __init__(
+        self,
+        compression: "CompressionRatio",
+        parallel_weight: Optional[float] = None,
+        always_ram: Optional[bool] = None,
+    ) -> None:
+        """
+        Create an AnisotropicQuantizationConfig.
+
+        Args:
+            compression: Compression ratio.
+            parallel_weight: Weight of the error parallel to the original vector.
+            always_ram: Whether to keep in RAM.
+        """
+        ...
+
+    @property
+    def compression(self) -> "CompressionRatio":
+        """Compression ratio."""
+        ...
+
+    @property
+    def parallel_weight(self) -> Optional[float]:
+        """Parallel error weight."""
+        ...
+
+    @property
+    def always_ram(self) -> Optional[bool]:
+        """Always RAM flag."""
+        ...
+
 # ============================================================================
 # Enums
 # ============================================================================
@@ -656,16 +914,28 @@ class VectorStorageDatatype(Enum):
     Float32 = ...
     Float16 = ...
     Uint8 = ...
+    Float64 = ...
+    Int8 = ...
+    Bfloat16 = ...
+    Int4 = ...
 
 class MultiVectorComparator(Enum):
     """Multi-vector comparison methods."""
 
     MaxSim = ...
 
+class MultiVectorOverflowPolicy(Enum):
+    """Policies for multivectors with more vectors than allowed."""
+
+    Reject = ...
+    Truncate = ...
+    AveragePool = ...
+
 class ScalarType(Enum):
     """Scalar quantization types."""
 
     Int8 = ...
+    Int4 = ...
 
 class CompressionRatio(Enum):
     """Product quantization compression ratios."""
@@ -690,6 +960,7 @@ class BinaryQuantizationQueryEncoding(Enum):
     Binary = ...
     Scalar4Bits = ...
     Scalar8Bits = ...
+    Float32 = ...
 
 class Modifier(Enum):
     """Sparse vector modifiers."""
@@ -775,6 +1046,11 @@ class Point:
         """Payload."""
         ...
 
+    @property
+    def expires_at(self) -> Optional[int]:
+        """Expiration time of the point, in seconds since the Unix epoch."""
+        ...
+
 class PointVectors:
     """Point ID with associated vectors for update operations."""
 
@@ -977,6 +1253,7 @@ class IntegerIndexParams:
         is_principal: Optional[bool] = None,
         on_disk: Optional[bool] = None,
         enable_hnsw: Optional[bool] = None,
+        enable_hnsw_ranges: Optional[bool] = None,
     ) -> None:
         """
         Create IntegerIndexParams.
@@ -987,6 +1264,7 @@ class IntegerIndexParams:
             is_principal: Whether this field is a principal identifier.
             on_disk: Whether to store index on disk.
             enable_hnsw: Whether to enable HNSW index for this field.
+            enable_hnsw_ranges: Whether to build HNSW links for wider ranges of values.
         """
         ...
 
@@ -1015,6 +1293,11 @@ class IntegerIndexParams:
         """Whether to enable HNSW index."""
         ...
 
+    @property
+    def enable_hnsw_ranges(self) -> Optional[bool]:
+        """Whether to build HNSW links for wider ranges of values."""
+        ...
+
 class FloatIndexParams:
     """Index parameters for float fields."""
 
@@ -1023,6 +1306,7 @@ class FloatIndexParams:
         is_principal: Optional[bool] = None,
         on_disk: Optional[bool] = None,
         enable_hnsw: Optional[bool] = None,
+        enable_hnsw_ranges: Optional[bool] = None,
     ) -> None:
         """
         Create FloatIndexParams.
@@ -1031,6 +1315,7 @@ class FloatIndexParams:
             is_principal: Whether this field is a principal identifier.
             on_disk: Whether to store index on disk.
             enable_hnsw: Whether to enable HNSW index for this field.
+            enable_hnsw_ranges: Whether to build HNSW links for wider ranges of values.
         """
         ...
 
@@ -1049,6 +1334,11 @@ class FloatIndexParams:
         """Whether to enable HNSW index."""
         ...
 
+    @property
+    def enable_hnsw_ranges(self) -> Optional[bool]:
+        """Whether to build HNSW links for wider ranges of values."""
+        ...
+
 class GeoIndexParams:
     """Index parameters for geo fields."""
 
@@ -1111,6 +1401,7 @@ class DatetimeIndexParams:
         is_principal: Optional[bool] = None,
         on_disk: Optional[bool] = None,
         enable_hnsw: Optional[bool] = None,
+        enable_hnsw_ranges: Optional[bool] = None,
     ) -> None:
         """
         Create DatetimeIndexParams.
@@ -1119,6 +1410,7 @@ class DatetimeIndexParams:
             is_principal: Whether this field is a principal identifier.
             on_disk: Whether to store index on disk.
             enable_hnsw: Whether to enable HNSW index for this field.
+            enable_hnsw_ranges: Whether to build HNSW links for wider ranges of values.
         """
         ...
 
@@ -1137,6 +1429,11 @@ class DatetimeIndexParams:
         """Whether to enable HNSW index."""
         ...
 
+    @property
+    def enable_hnsw_ranges(self) -> Optional[bool]:
+        """Whether to build HNSW links for wider ranges of values."""
+        ...
+
 class UuidIndexParams:
     """Index parameters for UUID fields."""
 
@@ -1331,12 +1628,18 @@ StemmingAlgorithm = Union["SnowballParams"]
 class SnowballParams:
     """Snowball stemming algorithm parameters."""
 
-    def __init__(self, language: "SnowballLanguage") -> None:
+    def __init__(
+        self,
+        language: "SnowballLanguage",
+        additional_languages: Optional[List["SnowballLanguage"]] = None,
+    ) -> None:
         """
         Create SnowballParams.
 
         Args:
             language: Snowball language.
+            additional_languages: Languages to stem with besides `language`.
+                Each token is stemmed with the language producing the shortest stem.
         """
         ...
 
@@ -1345,6 +1648,11 @@ class SnowballParams:
         """Snowball language."""
         ...
 
+    @property
+    def additional_languages(self) -> Optional[List["SnowballLanguage"]]:
+        """Additional snowball languages."""
+        ...
+
 class SnowballLanguage(Enum):
     """Snowball stemmer languages."""
 
@@ -1734,6 +2042,10 @@ class SearchParams:
         quantization: Optional["QuantizationSearchParams"] = None,
         indexed_only: bool = False,
         acorn: Optional["AcornSearchParams"] = None,
+        target_recall: Optional[float] = None,
+        nprobe: Optional[int] = None,
+        early_termination: Optional["EarlyTerminationParams"] = None,
+        sparse_max_posting_length: Optional[int] = None,
     ) -> None:
         """
         Create SearchParams.
@@ -1744,6 +2056,10 @@ class SearchParams:
             quantization: Quantization search parameters.
             indexed_only: Whether to search only indexed vectors.
             acorn: Acorn search parameters.
+            target_recall: Desired recall, used to choose ef for each segment.
+            nprobe: Number of inverted lists scanned by IVF search.
+            early_termination: Stop HNSW search once top results converge.
+            sparse_max_posting_length: Max length of sparse posting lists traversed in full.
         """
         ...
 
@@ -1772,6 +2088,26 @@ class SearchParams:
         """Acorn parameters."""
         ...
 
+    @property
+    def target_recall(self) -> Optional[float]:
+        """Desired recall."""
+        ...
+
+    @property
+    def nprobe(self) -> Optional[int]:
+        """IVF nprobe parameter."""
+        ...
+
+    @property
+    def early_termination(self) -> Optional["EarlyTerminationParams"]:
+        """Early termination parameters."""
+        ...
+
+    @property
+    def sparse_max_posting_length(self) -> Optional[int]:
+        """Max length of sparse posting lists traversed in full."""
+        ...
+
 class QuantizationSearchParams:
     """Parameters for quantization during search."""
 
@@ -1833,6 +2169,33 @@ class AcornSearchParams:
         """Maximum selectivity."""
         ...
 
+class EarlyTerminationParams:
+    """Parameters for early termination of HNSW search."""
+
+    def __init__(
+        self,
+        epsilon: float,
+        patience: int,
+    ) -> None:
+        """
+        Create EarlyTerminationParams.
+
+        Args:
+            epsilon: Minimal improvement of the total score of top results per expansion.
+            patience: Number of consecutive expansions without improvement before stopping.
+        """
+        ...
+
+    @property
+    def epsilon(self) -> float:
+        """Minimal score improvement."""
+        ...
+
+    @property
+    def patience(self) -> int:
+        """Number of expansions without improvement."""
+        ...
+
 # ============================================================================
 # Query Types
 # ============================================================================
@@ -1940,6 +2303,77 @@ class OrderBy:
         """Starting value."""
         ...
 
+class TextQuery:
+    """Score points by BM25 relevance of a full-text indexed payload field."""
+
+    def __init__(self, key: JsonPath, text: str) -> None:
+        """
+        Create a TextQuery.
+
+        Args:
+            key: Payload field path, must have a full-text index.
+            text: Text to look for.
+        """
+        ...
+
+    @property
+    def key(self) -> str:
+        """Field key."""
+        ...
+
+    @property
+    def text(self) -> str:
+        """Text to look for."""
+        ...
+
+class HybridQuery:
+    """Score points by a dense and a sparse vector at once, in a single traversal."""
+
+    def __init__(
+        self,
+        dense_using: str,
+        dense: List[float],
+        sparse_using: str,
+        sparse: SparseVector,
+        alpha: float,
+    ) -> None:
+        """
+        Create a HybridQuery.
+
+        Args:
+            dense_using: Name of the dense vector.
+            dense: Dense part of the query.
+            sparse_using: Name of the sparse vector.
+            sparse: Sparse part of the query.
+            alpha: Weight of the dense score, from 0.0 (sparse only) to 1.0 (dense only).
+        """
+        ...
+
+    @property
+    def dense_using(self) -> str:
+        """Name of the dense vector."""
+        ...
+
+    @property
+    def dense(self) -> List[float]:
+        """Dense part of the query."""
+        ...
+
+    @property
+    def sparse_using(self) -> str:
+        """Name of the sparse vector."""
+        ...
+
+    @property
+    def sparse(self) -> SparseVector:
+        """Sparse part of the query."""
+        ...
+
+    @property
+    def alpha(self) -> float:
+        """Weight of the dense score."""
+        ...
+
 class Mmr:
     """Maximal Marginal Relevance for result diversification."""
 
@@ -2581,14 +3015,15 @@ class MatchTextAny:
         ...
 
 class MatchPhrase:
-    """Match exact phrase."""
+    """Match exact phrase, or tokens of the phrase close to each other with slop."""
 
-    def __init__(self, phrase: str) -> None:
+    def __init__(self, phrase: str, slop: Optional[int] = None) -> None:
         """
         Create a MatchPhrase.
 
         Args:
-            phrase: Phrase to match.
+            phrase: Phrase to match, `"quick fox"~2` for proximity match.
+            slop: Max number of other tokens between the phrase tokens.
         """
         ...
 
@@ -2597,6 +3032,11 @@ class MatchPhrase:
         """Phrase."""
         ...
 
+    @property
+    def slop(self) -> Optional[int]:
+        """Slop."""
+        ...
+
 class MatchAny:
     """Match any of the values."""
 
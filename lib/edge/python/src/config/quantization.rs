@@ -21,6 +21,8 @@ impl FromPyObject<'_, '_> for PyQuantizationConfig {
             Scalar(PyScalarQuantizationConfig),
             Product(PyProductQuantizationConfig),
             Binary(PyBinaryQuantizationConfig),
+            Residual(PyResidualQuantizationConfig),
+            Anisotropic(PyAnisotropicQuantizationConfig),
         }
 
         let conf = match conf.extract()? {
@@ -33,6 +35,14 @@ impl FromPyObject<'_, '_> for PyQuantizationConfig {
             Helper::Binary(binary) => QuantizationConfig::Binary(BinaryQuantization {
                 binary: BinaryQuantizationConfig::from(binary),
             }),
+            Helper::Residual(residual) => QuantizationConfig::Residual(ResidualQuantization {
+                residual: ResidualQuantizationConfig::from(residual),
+            }),
+            Helper::Anisotropic(anisotropic) => {
+                QuantizationConfig::Anisotropic(AnisotropicQuantization {
+                    anisotropic: AnisotropicQuantizationConfig::from(anisotropic),
+                })
+            }
         };
 
         Ok(Self(conf))
@@ -55,6 +65,12 @@ impl<'py> IntoPyObject<'py> for PyQuantizationConfig {
             QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                 PyBinaryQuantizationConfig(binary).into_bound_py_any(py)
             }
+            QuantizationConfig::Residual(ResidualQuantization { residual }) => {
+                PyResidualQuantizationConfig(residual).into_bound_py_any(py)
+            }
+            QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                PyAnisotropicQuantizationConfig(anisotropic).into_bound_py_any(py)
+            }
         }
     }
 }
@@ -71,6 +87,12 @@ impl Repr for PyQuantizationConfig {
             QuantizationConfig::Binary(binary) => {
                 PyBinaryQuantizationConfig::wrap_ref(&binary.binary).fmt(f)
             }
+            QuantizationConfig::Residual(residual) => {
+                PyResidualQuantizationConfig::wrap_ref(&residual.residual).fmt(f)
+            }
+            QuantizationConfig::Anisotropic(anisotropic) => {
+                PyAnisotropicQuantizationConfig::wrap_ref(&anisotropic.anisotropic).fmt(f)
+            }
         }
     }
 }
@@ -84,12 +106,18 @@ pub struct PyScalarQuantizationConfig(ScalarQuantizationConfig);
 #[pymethods]
 impl PyScalarQuantizationConfig {
     #[new]
-    #[pyo3(signature = (r#type, quantile = None, always_ram = None))]
-    pub fn new(r#type: PyScalarType, quantile: Option<f32>, always_ram: Option<bool>) -> Self {
+    #[pyo3(signature = (r#type, quantile = None, always_ram = None, drop_originals = None))]
+    pub fn new(
+        r#type: PyScalarType,
+        quantile: Option<f32>,
+        always_ram: Option<bool>,
+        drop_originals: Option<bool>,
+    ) -> Self {
         Self(ScalarQuantizationConfig {
             r#type: ScalarType::from(r#type),
             quantile,
             always_ram,
+            drop_originals,
         })
     }
 
@@ -108,6 +136,11 @@ impl PyScalarQuantizationConfig {
         self.0.always_ram
     }
 
+    #[getter]
+    pub fn drop_originals(&self) -> Option<bool> {
+        self.0.drop_originals
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -120,6 +153,7 @@ impl PyScalarQuantizationConfig {
             r#type: _,
             quantile: _,
             always_ram: _,
+            drop_originals: _,
         } = self.0;
     }
 }
@@ -128,6 +162,7 @@ impl PyScalarQuantizationConfig {
 #[derive(Copy, Clone, Debug)]
 pub enum PyScalarType {
     Int8,
+    Int4,
 }
 
 #[pymethods]
@@ -141,6 +176,7 @@ impl Repr for PyScalarType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let repr = match self {
             Self::Int8 => "Int8",
+            Self::Int4 => "Int4",
         };
 
         f.simple_enum::<Self>(repr)
@@ -151,6 +187,7 @@ impl From<ScalarType> for PyScalarType {
     fn from(scalar_type: ScalarType) -> Self {
         match scalar_type {
             ScalarType::Int8 => PyScalarType::Int8,
+            ScalarType::Int4 => PyScalarType::Int4,
         }
     }
 }
@@ -159,6 +196,7 @@ impl From<PyScalarType> for ScalarType {
     fn from(scalar_type: PyScalarType) -> Self {
         match scalar_type {
             PyScalarType::Int8 => ScalarType::Int8,
+            PyScalarType::Int4 => ScalarType::Int4,
         }
     }
 }
@@ -172,11 +210,16 @@ pub struct PyProductQuantizationConfig(ProductQuantizationConfig);
 #[pymethods]
 impl PyProductQuantizationConfig {
     #[new]
-    #[pyo3(signature = (compression, always_ram = None))]
-    pub fn new(compression: PyCompressionRatio, always_ram: Option<bool>) -> Self {
+    #[pyo3(signature = (compression, always_ram = None, drop_originals = None))]
+    pub fn new(
+        compression: PyCompressionRatio,
+        always_ram: Option<bool>,
+        drop_originals: Option<bool>,
+    ) -> Self {
         Self(ProductQuantizationConfig {
             compression: CompressionRatio::from(compression),
             always_ram,
+            drop_originals,
         })
     }
 
@@ -190,6 +233,11 @@ impl PyProductQuantizationConfig {
         self.0.always_ram
     }
 
+    #[getter]
+    pub fn drop_originals(&self) -> Option<bool> {
+        self.0.drop_originals
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -201,6 +249,109 @@ impl PyProductQuantizationConfig {
         let ProductQuantizationConfig {
             compression: _,
             always_ram: _,
+            drop_originals: _,
+        } = self.0;
+    }
+}
+
+#[pyclass(name = "ResidualQuantizationConfig", from_py_object)]
+#[derive(Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyResidualQuantizationConfig(ResidualQuantizationConfig);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyResidualQuantizationConfig {
+    #[new]
+    #[pyo3(signature = (compression, stages, always_ram = None))]
+    pub fn new(compression: PyCompressionRatio, stages: usize, always_ram: Option<bool>) -> Self {
+        Self(ResidualQuantizationConfig {
+            compression: CompressionRatio::from(compression),
+            stages,
+            always_ram,
+        })
+    }
+
+    #[getter]
+    pub fn compression(&self) -> PyCompressionRatio {
+        PyCompressionRatio::from(self.0.compression)
+    }
+
+    #[getter]
+    pub fn stages(&self) -> usize {
+        self.0.stages
+    }
+
+    #[getter]
+    pub fn always_ram(&self) -> Option<bool> {
+        self.0.always_ram
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyResidualQuantizationConfig {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let ResidualQuantizationConfig {
+            compression: _,
+            stages: _,
+            always_ram: _,
+        } = self.0;
+    }
+}
+
+#[pyclass(name = "AnisotropicQuantizationConfig", from_py_object)]
+#[derive(Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyAnisotropicQuantizationConfig(AnisotropicQuantizationConfig);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyAnisotropicQuantizationConfig {
+    #[new]
+    #[pyo3(signature = (compression, parallel_weight = None, always_ram = None))]
+    pub fn new(
+        compression: PyCompressionRatio,
+        parallel_weight: Option<f32>,
+        always_ram: Option<bool>,
+    ) -> Self {
+        Self(AnisotropicQuantizationConfig {
+            compression: CompressionRatio::from(compression),
+            parallel_weight,
+            always_ram,
+        })
+    }
+
+    #[getter]
+    pub fn compression(&self) -> PyCompressionRatio {
+        PyCompressionRatio::from(self.0.compression)
+    }
+
+    #[getter]
+    pub fn parallel_weight(&self) -> Option<f32> {
+        self.0.parallel_weight
+    }
+
+    #[getter]
+    pub fn always_ram(&self) -> Option<bool> {
+        self.0.always_ram
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyAnisotropicQuantizationConfig {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let AnisotropicQuantizationConfig {
+            compression: _,
+            parallel_weight: _,
+            always_ram: _,
         } = self.0;
     }
 }
@@ -373,6 +524,7 @@ pub enum PyBinaryQuantizationQueryEncoding {
     Binary,
     Scalar4Bits,
     Scalar8Bits,
+    Float32,
 }
 
 #[pymethods]
@@ -389,6 +541,7 @@ impl Repr for PyBinaryQuantizationQueryEncoding {
             Self::Binary => "Binary",
             Self::Scalar4Bits => "Scalar4Bits",
             Self::Scalar8Bits => "Scalar8Bits",
+            Self::Float32 => "Float32",
         };
 
         f.simple_enum::<Self>(repr)
@@ -406,6 +559,7 @@ impl From<BinaryQuantizationQueryEncoding> for PyBinaryQuantizationQueryEncoding
             BinaryQuantizationQueryEncoding::Scalar8Bits => {
                 PyBinaryQuantizationQueryEncoding::Scalar8Bits
             }
+            BinaryQuantizationQueryEncoding::Float32 => PyBinaryQuantizationQueryEncoding::Float32,
         }
     }
 }
@@ -421,6 +575,7 @@ impl From<PyBinaryQuantizationQueryEncoding> for BinaryQuantizationQueryEncoding
             PyBinaryQuantizationQueryEncoding::Scalar8Bits => {
                 BinaryQuantizationQueryEncoding::Scalar8Bits
             }
+            PyBinaryQuantizationQueryEncoding::Float32 => BinaryQuantizationQueryEncoding::Float32,
         }
     }
 }
@@ -1,9 +1,11 @@
 use std::collections::HashMap;
 use std::fmt;
 
+use derive_more::Into;
 use edge::EdgeSparseVectorParams;
+use ordered_float::OrderedFloat;
 use pyo3::prelude::*;
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::types::VectorStorageDatatype;
 
 use super::vector_data::*;
@@ -31,17 +33,19 @@ impl PyEdgeSparseVectorParams {
 #[pymethods]
 impl PyEdgeSparseVectorParams {
     #[new]
-    #[pyo3(signature = (full_scan_threshold=None, on_disk=None, modifier=None, datatype=None))]
+    #[pyo3(signature = (full_scan_threshold=None, on_disk=None, modifier=None, bm25=None, datatype=None))]
     pub fn new(
         full_scan_threshold: Option<usize>,
         on_disk: Option<bool>,
         modifier: Option<PyModifier>,
+        bm25: Option<PyBm25Params>,
         datatype: Option<PyVectorStorageDatatype>,
     ) -> Self {
         Self(EdgeSparseVectorParams {
             full_scan_threshold,
             on_disk,
             modifier: modifier.map(Modifier::from),
+            bm25: bm25.map(Bm25Params::from),
             datatype: datatype.map(VectorStorageDatatype::from),
         })
     }
@@ -61,6 +65,11 @@ impl PyEdgeSparseVectorParams {
         self.0.modifier.map(PyModifier::from)
     }
 
+    #[getter]
+    pub fn bm25(&self) -> Option<PyBm25Params> {
+        self.0.bm25.map(PyBm25Params)
+    }
+
     #[getter]
     pub fn datatype(&self) -> Option<PyVectorStorageDatatype> {
         self.0.datatype.map(PyVectorStorageDatatype::from)
@@ -71,6 +80,19 @@ impl PyEdgeSparseVectorParams {
     }
 }
 
+impl PyEdgeSparseVectorParams {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let EdgeSparseVectorParams {
+            full_scan_threshold: _,
+            on_disk: _,
+            modifier: _,
+            bm25: _,
+            datatype: _,
+        } = self.0;
+    }
+}
+
 impl<'py> IntoPyObject<'py> for &PyEdgeSparseVectorParams {
     type Target = PyEdgeSparseVectorParams;
     type Output = Bound<'py, Self::Target>;
@@ -81,6 +103,45 @@ impl<'py> IntoPyObject<'py> for &PyEdgeSparseVectorParams {
     }
 }
 
+#[pyclass(name = "Bm25Params", from_py_object)]
+#[derive(Copy, Clone, Debug, Into)]
+pub struct PyBm25Params(Bm25Params);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyBm25Params {
+    #[new]
+    #[pyo3(signature = (k1=None, b=None))]
+    pub fn new(k1: Option<f64>, b: Option<f64>) -> Self {
+        let default = Bm25Params::default();
+        Self(Bm25Params {
+            k1: k1.map_or(default.k1, OrderedFloat),
+            b: b.map_or(default.b, OrderedFloat),
+        })
+    }
+
+    #[getter]
+    pub fn k1(&self) -> f64 {
+        self.0.k1.into_inner()
+    }
+
+    #[getter]
+    pub fn b(&self) -> f64 {
+        self.0.b.into_inner()
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyBm25Params {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let Bm25Params { k1: _, b: _ } = self.0;
+    }
+}
+
 #[pyclass(name = "Modifier", from_py_object)]
 #[derive(Copy, Clone, Debug)]
 pub enum PyModifier {
@@ -73,18 +73,24 @@ impl FromPyObject<'_, '_> for PyIndexes {
         enum Helper {
             Plain(PyPlainIndexConfig),
             Hnsw(PyHnswIndexConfig),
+            Vamana(PyVamanaIndexConfig),
+            Ivf(PyIvfIndexConfig),
         }
 
         fn _variants(indexes: Indexes) {
             match indexes {
                 Indexes::Plain {} => (),
                 Indexes::Hnsw(_) => (),
+                Indexes::Vamana(_) => (),
+                Indexes::Ivf(_) => (),
             }
         }
 
         let indexes = match indexes.extract()? {
             Helper::Plain(_) => Indexes::Plain {},
             Helper::Hnsw(hnsw) => Indexes::Hnsw(HnswConfig::from(hnsw)),
+            Helper::Vamana(vamana) => Indexes::Vamana(VamanaConfig::from(vamana)),
+            Helper::Ivf(ivf) => Indexes::Ivf(IvfConfig::from(ivf)),
         };
 
         Ok(Self(indexes))
@@ -100,6 +106,8 @@ impl<'py> IntoPyObject<'py> for PyIndexes {
         match self.0 {
             Indexes::Plain {} => PyPlainIndexConfig.into_bound_py_any(py),
             Indexes::Hnsw(hnsw) => PyHnswIndexConfig(hnsw).into_bound_py_any(py),
+            Indexes::Vamana(vamana) => PyVamanaIndexConfig(vamana).into_bound_py_any(py),
+            Indexes::Ivf(ivf) => PyIvfIndexConfig(ivf).into_bound_py_any(py),
         }
     }
 }
@@ -109,6 +117,8 @@ impl Repr for PyIndexes {
         match &self.0 {
             Indexes::Plain {} => PyPlainIndexConfig.fmt(f),
             Indexes::Hnsw(hnsw) => PyHnswIndexConfig::wrap_ref(hnsw).fmt(f),
+            Indexes::Vamana(vamana) => PyVamanaIndexConfig::wrap_ref(vamana).fmt(f),
+            Indexes::Ivf(ivf) => PyIvfIndexConfig::wrap_ref(ivf).fmt(f),
         }
     }
 }
@@ -139,7 +149,7 @@ pub struct PyHnswIndexConfig(pub HnswConfig);
 #[pymethods]
 impl PyHnswIndexConfig {
     #[new]
-    #[pyo3(signature = (m, ef_construct, full_scan_threshold, max_indexing_threads=0, on_disk=None, payload_m=None, inline_storage=None))]
+    #[pyo3(signature = (m, ef_construct, full_scan_threshold, max_indexing_threads=0, on_disk=None, payload_m=None, inline_storage=None, varint_links=None, acorn=None, seed=None))]
     pub fn new(
         m: usize,
         ef_construct: usize,
@@ -148,6 +158,9 @@ impl PyHnswIndexConfig {
         on_disk: Option<bool>,
         payload_m: Option<usize>,
         inline_storage: Option<bool>,
+        varint_links: Option<bool>,
+        acorn: Option<bool>,
+        seed: Option<u64>,
     ) -> Self {
         Self(HnswConfig {
             m,
@@ -157,6 +170,9 @@ impl PyHnswIndexConfig {
             on_disk,
             payload_m,
             inline_storage,
+            varint_links,
+            acorn,
+            seed,
         })
     }
 
@@ -195,6 +211,21 @@ impl PyHnswIndexConfig {
         self.0.inline_storage
     }
 
+    #[getter]
+    pub fn varint_links(&self) -> Option<bool> {
+        self.0.varint_links
+    }
+
+    #[getter]
+    pub fn acorn(&self) -> Option<bool> {
+        self.0.acorn
+    }
+
+    #[getter]
+    pub fn seed(&self) -> Option<u64> {
+        self.0.seed
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -211,6 +242,147 @@ impl PyHnswIndexConfig {
             on_disk: _,
             payload_m: _,
             inline_storage: _,
+            varint_links: _,
+            acorn: _,
+            seed: _,
+        } = self.0;
+    }
+}
+
+#[pyclass(name = "VamanaIndexConfig", from_py_object)]
+#[derive(Copy, Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyVamanaIndexConfig(pub VamanaConfig);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyVamanaIndexConfig {
+    #[new]
+    #[pyo3(signature = (full_scan_threshold, max_degree=64, build_list_size=100, beam_width=None, on_disk=None))]
+    pub fn new(
+        full_scan_threshold: usize,
+        max_degree: usize,
+        build_list_size: usize,
+        beam_width: Option<usize>,
+        on_disk: Option<bool>,
+    ) -> Self {
+        Self(VamanaConfig {
+            max_degree,
+            build_list_size,
+            beam_width,
+            full_scan_threshold,
+            on_disk,
+        })
+    }
+
+    #[getter]
+    pub fn max_degree(&self) -> usize {
+        self.0.max_degree
+    }
+
+    #[getter]
+    pub fn build_list_size(&self) -> usize {
+        self.0.build_list_size
+    }
+
+    #[getter]
+    pub fn beam_width(&self) -> Option<usize> {
+        self.0.beam_width
+    }
+
+    #[getter]
+    pub fn full_scan_threshold(&self) -> usize {
+        self.0.full_scan_threshold
+    }
+
+    #[getter]
+    pub fn on_disk(&self) -> Option<bool> {
+        self.0.on_disk
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyVamanaIndexConfig {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let VamanaConfig {
+            max_degree: _,
+            build_list_size: _,
+            beam_width: _,
+            full_scan_threshold: _,
+            on_disk: _,
+        } = self.0;
+    }
+}
+
+#[pyclass(name = "IvfIndexConfig", from_py_object)]
+#[derive(Copy, Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyIvfIndexConfig(pub IvfConfig);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyIvfIndexConfig {
+    #[new]
+    #[pyo3(signature = (full_scan_threshold, num_lists=None, nprobe=None, pq_num_subvectors=None, on_disk=None))]
+    pub fn new(
+        full_scan_threshold: usize,
+        num_lists: Option<usize>,
+        nprobe: Option<usize>,
+        pq_num_subvectors: Option<usize>,
+        on_disk: Option<bool>,
+    ) -> Self {
+        Self(IvfConfig {
+            num_lists,
+            nprobe,
+            pq: pq_num_subvectors.map(|num_subvectors| IvfPqConfig { num_subvectors }),
+            full_scan_threshold,
+            on_disk,
+        })
+    }
+
+    #[getter]
+    pub fn num_lists(&self) -> Option<usize> {
+        self.0.num_lists
+    }
+
+    #[getter]
+    pub fn nprobe(&self) -> Option<usize> {
+        self.0.nprobe
+    }
+
+    #[getter]
+    pub fn pq_num_subvectors(&self) -> Option<usize> {
+        self.0.pq.map(|pq| pq.num_subvectors)
+    }
+
+    #[getter]
+    pub fn full_scan_threshold(&self) -> usize {
+        self.0.full_scan_threshold
+    }
+
+    #[getter]
+    pub fn on_disk(&self) -> Option<bool> {
+        self.0.on_disk
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyIvfIndexConfig {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let IvfConfig {
+            num_lists: _,
+            nprobe: _,
+            pq: _,
+            full_scan_threshold: _,
+            on_disk: _,
         } = self.0;
     }
 }
@@ -224,9 +396,16 @@ pub struct PyMultiVectorConfig(MultiVectorConfig);
 #[pymethods]
 impl PyMultiVectorConfig {
     #[new]
-    pub fn new(comparator: PyMultiVectorComparator) -> Self {
+    #[pyo3(signature = (comparator, max_vectors_per_point=None, overflow_policy=None))]
+    pub fn new(
+        comparator: PyMultiVectorComparator,
+        max_vectors_per_point: Option<usize>,
+        overflow_policy: Option<PyMultiVectorOverflowPolicy>,
+    ) -> Self {
         Self(MultiVectorConfig {
             comparator: MultiVectorComparator::from(comparator),
+            max_vectors_per_point,
+            overflow_policy: overflow_policy.map(MultiVectorOverflowPolicy::from),
         })
     }
 
@@ -235,6 +414,18 @@ impl PyMultiVectorConfig {
         PyMultiVectorComparator::from(self.0.comparator)
     }
 
+    #[getter]
+    pub fn max_vectors_per_point(&self) -> Option<usize> {
+        self.0.max_vectors_per_point
+    }
+
+    #[getter]
+    pub fn overflow_policy(&self) -> Option<PyMultiVectorOverflowPolicy> {
+        self.0
+            .overflow_policy
+            .map(PyMultiVectorOverflowPolicy::from)
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -243,7 +434,11 @@ impl PyMultiVectorConfig {
 impl PyMultiVectorConfig {
     fn _getters(self) {
         // Every field should have a getter method
-        let MultiVectorConfig { comparator: _ } = self.0;
+        let MultiVectorConfig {
+            comparator: _,
+            max_vectors_per_point: _,
+            overflow_policy: _,
+        } = self.0;
     }
 }
 
@@ -286,12 +481,63 @@ impl From<PyMultiVectorComparator> for MultiVectorComparator {
     }
 }
 
+#[pyclass(name = "MultiVectorOverflowPolicy", from_py_object)]
+#[derive(Copy, Clone, Debug)]
+pub enum PyMultiVectorOverflowPolicy {
+    Reject,
+    Truncate,
+    AveragePool,
+}
+
+#[pymethods]
+impl PyMultiVectorOverflowPolicy {
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl Repr for PyMultiVectorOverflowPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let repr = match self {
+            Self::Reject => "Reject",
+            Self::Truncate => "Truncate",
+            Self::AveragePool => "AveragePool",
+        };
+
+        f.simple_enum::<Self>(repr)
+    }
+}
+
+impl From<MultiVectorOverflowPolicy> for PyMultiVectorOverflowPolicy {
+    fn from(policy: MultiVectorOverflowPolicy) -> Self {
+        match policy {
+            MultiVectorOverflowPolicy::Reject => PyMultiVectorOverflowPolicy::Reject,
+            MultiVectorOverflowPolicy::Truncate => PyMultiVectorOverflowPolicy::Truncate,
+            MultiVectorOverflowPolicy::AveragePool => PyMultiVectorOverflowPolicy::AveragePool,
+        }
+    }
+}
+
+impl From<PyMultiVectorOverflowPolicy> for MultiVectorOverflowPolicy {
+    fn from(policy: PyMultiVectorOverflowPolicy) -> Self {
+        match policy {
+            PyMultiVectorOverflowPolicy::Reject => MultiVectorOverflowPolicy::Reject,
+            PyMultiVectorOverflowPolicy::Truncate => MultiVectorOverflowPolicy::Truncate,
+            PyMultiVectorOverflowPolicy::AveragePool => MultiVectorOverflowPolicy::AveragePool,
+        }
+    }
+}
+
 #[pyclass(name = "VectorStorageDatatype", from_py_object)]
 #[derive(Copy, Clone, Debug)]
 pub enum PyVectorStorageDatatype {
     Float32,
     Float16,
     Uint8,
+    Float64,
+    Int8,
+    Bfloat16,
+    Int4,
 }
 
 #[pymethods]
@@ -307,6 +553,10 @@ impl Repr for PyVectorStorageDatatype {
             Self::Float32 => "Float32",
             Self::Float16 => "Float16",
             Self::Uint8 => "Uint8",
+            Self::Float64 => "Float64",
+            Self::Int8 => "Int8",
+            Self::Bfloat16 => "Bfloat16",
+            Self::Int4 => "Int4",
         };
 
         f.simple_enum::<Self>(repr)
@@ -319,6 +569,10 @@ impl From<VectorStorageDatatype> for PyVectorStorageDatatype {
             VectorStorageDatatype::Float32 => PyVectorStorageDatatype::Float32,
             VectorStorageDatatype::Float16 => PyVectorStorageDatatype::Float16,
             VectorStorageDatatype::Uint8 => PyVectorStorageDatatype::Uint8,
+            VectorStorageDatatype::Float64 => PyVectorStorageDatatype::Float64,
+            VectorStorageDatatype::Int8 => PyVectorStorageDatatype::Int8,
+            VectorStorageDatatype::Bfloat16 => PyVectorStorageDatatype::Bfloat16,
+            VectorStorageDatatype::Int4 => PyVectorStorageDatatype::Int4,
         }
     }
 }
@@ -329,6 +583,10 @@ impl From<PyVectorStorageDatatype> for VectorStorageDatatype {
             PyVectorStorageDatatype::Float32 => VectorStorageDatatype::Float32,
             PyVectorStorageDatatype::Float16 => VectorStorageDatatype::Float16,
             PyVectorStorageDatatype::Uint8 => VectorStorageDatatype::Uint8,
+            PyVectorStorageDatatype::Float64 => VectorStorageDatatype::Float64,
+            PyVectorStorageDatatype::Int8 => VectorStorageDatatype::Int8,
+            PyVectorStorageDatatype::Bfloat16 => VectorStorageDatatype::Bfloat16,
+            PyVectorStorageDatatype::Int4 => VectorStorageDatatype::Int4,
         }
     }
 }
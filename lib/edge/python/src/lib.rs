@@ -36,16 +36,17 @@ mod qdrant_edge {
     use super::PyEdgeShard;
     #[pymodule_export]
     use super::config::quantization::{
-        PyBinaryQuantizationConfig, PyBinaryQuantizationEncoding,
+        PyAnisotropicQuantizationConfig, PyBinaryQuantizationConfig, PyBinaryQuantizationEncoding,
         PyBinaryQuantizationQueryEncoding, PyCompressionRatio, PyProductQuantizationConfig,
-        PyScalarQuantizationConfig, PyScalarType,
+        PyResidualQuantizationConfig, PyScalarQuantizationConfig, PyScalarType,
     };
     #[pymodule_export]
-    use super::config::sparse_vector_data::{PyEdgeSparseVectorParams, PyModifier};
+    use super::config::sparse_vector_data::{PyBm25Params, PyEdgeSparseVectorParams, PyModifier};
     #[pymodule_export]
     use super::config::vector_data::{
-        PyDistance, PyEdgeVectorParams, PyHnswIndexConfig, PyMultiVectorComparator,
-        PyMultiVectorConfig, PyPlainIndexConfig, PyVectorStorageDatatype,
+        PyDistance, PyEdgeVectorParams, PyHnswIndexConfig, PyIvfIndexConfig,
+        PyMultiVectorComparator, PyMultiVectorConfig, PyMultiVectorOverflowPolicy,
+        PyPlainIndexConfig, PyVamanaIndexConfig, PyVectorStorageDatatype,
     };
     #[pymodule_export]
     use super::config::{PyEdgeConfig, PyEdgeOptimizersConfig};
@@ -55,13 +56,15 @@ mod qdrant_edge {
     use super::facet::{PyFacetHit, PyFacetRequest, PyFacetResponse};
     #[pymodule_export]
     use super::query::{
-        PyDirection, PyFusion, PyMmr, PyOrderBy, PyPrefetch, PyQueryRequest, PySample,
+        PyDirection, PyFusion, PyHybridQuery, PyMmr, PyOrderBy, PyPrefetch, PyQueryRequest,
+        PySample, PyTextQuery,
     };
     #[pymodule_export]
     use super::scroll::PyScrollRequest;
     #[pymodule_export]
     use super::search::{
-        PyAcornSearchParams, PyQuantizationSearchParams, PySearchParams, PySearchRequest,
+        PyAcornSearchParams, PyEarlyTerminationParams, PyQuantizationSearchParams, PySearchParams,
+        PySearchRequest,
     };
     #[pymodule_export]
     use super::types::filter::{
@@ -7,7 +7,9 @@ use pyo3::IntoPyObjectExt;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use segment::data_types::order_by::{Direction, OrderBy, OrderByInterface, StartFrom};
+use segment::data_types::text_query::{TextQuery, TextQueryInternal};
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, VectorInternal};
+use segment::index::hybrid_index::HybridQuery;
 use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::json_path::JsonPath;
 use shard::query::query_enum::QueryEnum;
@@ -243,6 +245,8 @@ impl FromPyObject<'_, '_> for PyScoringQuery {
             Formula(PyFormula),
             Sample(PySample),
             Mmr(PyMmr),
+            Text(PyTextQuery),
+            Hybrid(PyHybridQuery),
         }
 
         fn _variants(query: ScoringQuery) {
@@ -253,6 +257,8 @@ impl FromPyObject<'_, '_> for PyScoringQuery {
                 ScoringQuery::Formula(_) => {}
                 ScoringQuery::Sample(_) => {}
                 ScoringQuery::Mmr(_) => {}
+                ScoringQuery::Text(_) => {}
+                ScoringQuery::Hybrid(_) => {}
             }
         }
 
@@ -263,6 +269,10 @@ impl FromPyObject<'_, '_> for PyScoringQuery {
             Helper::Formula(formula) => ScoringQuery::Formula(ParsedFormula::from(formula)),
             Helper::Sample(sample) => ScoringQuery::Sample(SampleInternal::from(sample)),
             Helper::Mmr(mmr) => ScoringQuery::Mmr(MmrInternal::from(mmr)),
+            Helper::Text(text) => {
+                ScoringQuery::Text(TextQueryInternal::from(TextQuery::from(text)))
+            }
+            Helper::Hybrid(hybrid) => ScoringQuery::Hybrid(HybridQuery::from(hybrid)),
         };
 
         Ok(Self(query))
@@ -282,6 +292,8 @@ impl<'py> IntoPyObject<'py> for PyScoringQuery {
             ScoringQuery::Formula(formula) => PyFormula(formula).into_bound_py_any(py),
             ScoringQuery::Sample(sample) => PySample::from(sample).into_bound_py_any(py),
             ScoringQuery::Mmr(mmr) => PyMmr(mmr).into_bound_py_any(py),
+            ScoringQuery::Text(text) => PyTextQuery(text.query).into_bound_py_any(py),
+            ScoringQuery::Hybrid(hybrid) => PyHybridQuery(hybrid).into_bound_py_any(py),
         }
     }
 }
@@ -305,6 +317,8 @@ impl Repr for PyScoringQuery {
             ScoringQuery::Formula(_formula) => f.unimplemented(), // TODO!
             ScoringQuery::Sample(sample) => PySample::from(*sample).fmt(f),
             ScoringQuery::Mmr(mmr) => PyMmr::wrap_ref(mmr).fmt(f),
+            ScoringQuery::Text(text) => PyTextQuery::wrap_ref(&text.query).fmt(f),
+            ScoringQuery::Hybrid(hybrid) => PyHybridQuery::wrap_ref(hybrid).fmt(f),
         }
     }
 }
@@ -433,6 +447,112 @@ impl From<PyOrderBy> for OrderByInterface {
     }
 }
 
+#[pyclass(name = "TextQuery", from_py_object)]
+#[derive(Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyTextQuery(TextQuery);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyTextQuery {
+    #[new]
+    pub fn new(key: PyJsonPath, text: String) -> Self {
+        Self(TextQuery {
+            key: JsonPath::from(key),
+            text,
+        })
+    }
+
+    #[getter]
+    pub fn key(&self) -> &PyJsonPath {
+        PyJsonPath::wrap_ref(&self.0.key)
+    }
+
+    #[getter]
+    pub fn text(&self) -> &str {
+        &self.0.text
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyTextQuery {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let TextQuery { key: _, text: _ } = self.0;
+    }
+}
+
+#[pyclass(name = "HybridQuery", from_py_object)]
+#[derive(Clone, Debug, Into, TransparentWrapper)]
+#[repr(transparent)]
+pub struct PyHybridQuery(HybridQuery);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyHybridQuery {
+    #[new]
+    pub fn new(
+        dense_using: String,
+        dense: Vec<f32>,
+        sparse_using: String,
+        sparse: PySparseVector,
+        alpha: f32,
+    ) -> Self {
+        Self(HybridQuery {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse: sparse.0,
+            alpha,
+        })
+    }
+
+    #[getter]
+    pub fn dense_using(&self) -> &str {
+        &self.0.dense_using
+    }
+
+    #[getter]
+    pub fn dense(&self) -> &[f32] {
+        &self.0.dense
+    }
+
+    #[getter]
+    pub fn sparse_using(&self) -> &str {
+        &self.0.sparse_using
+    }
+
+    #[getter]
+    pub fn sparse(&self) -> &PySparseVector {
+        PySparseVector::wrap_ref(&self.0.sparse)
+    }
+
+    #[getter]
+    pub fn alpha(&self) -> f32 {
+        self.0.alpha
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyHybridQuery {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let HybridQuery {
+            dense_using: _,
+            dense: _,
+            sparse_using: _,
+            sparse: _,
+            alpha: _,
+        } = self.0;
+    }
+}
+
 #[pyclass(name = "Direction", from_py_object)]
 #[derive(Copy, Clone, Debug)]
 pub enum PyDirection {
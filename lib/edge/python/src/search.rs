@@ -124,6 +124,10 @@ impl PySearchParams {
         quantization = None,
         indexed_only = false,
         acorn = None,
+        target_recall = None,
+        nprobe = None,
+        early_termination = None,
+        sparse_max_posting_length = None,
     ))]
     pub fn new(
         hnsw_ef: Option<usize>,
@@ -131,6 +135,10 @@ impl PySearchParams {
         quantization: Option<PyQuantizationSearchParams>,
         indexed_only: bool,
         acorn: Option<PyAcornSearchParams>,
+        target_recall: Option<f64>,
+        nprobe: Option<usize>,
+        early_termination: Option<PyEarlyTerminationParams>,
+        sparse_max_posting_length: Option<usize>,
     ) -> Self {
         Self(SearchParams {
             hnsw_ef,
@@ -138,6 +146,10 @@ impl PySearchParams {
             quantization: quantization.map(QuantizationSearchParams::from),
             indexed_only,
             acorn: acorn.map(AcornSearchParams::from),
+            target_recall: target_recall.map(OrderedFloat),
+            nprobe,
+            early_termination: early_termination.map(EarlyTerminationParams::from),
+            sparse_max_posting_length,
         })
     }
 
@@ -166,6 +178,26 @@ impl PySearchParams {
         self.0.acorn.map(PyAcornSearchParams)
     }
 
+    #[getter]
+    pub fn target_recall(&self) -> Option<f64> {
+        self.0.target_recall.map(|OrderedFloat(x)| x)
+    }
+
+    #[getter]
+    pub fn nprobe(&self) -> Option<usize> {
+        self.0.nprobe
+    }
+
+    #[getter]
+    pub fn early_termination(&self) -> Option<PyEarlyTerminationParams> {
+        self.0.early_termination.map(PyEarlyTerminationParams)
+    }
+
+    #[getter]
+    pub fn sparse_max_posting_length(&self) -> Option<usize> {
+        self.0.sparse_max_posting_length
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -180,6 +212,10 @@ impl PySearchParams {
             quantization: _,
             indexed_only: _,
             acorn: _,
+            target_recall: _,
+            nprobe: _,
+            early_termination: _,
+            sparse_max_posting_length: _,
         } = self.0;
     }
 }
@@ -274,3 +310,44 @@ impl PyAcornSearchParams {
         } = self.0;
     }
 }
+
+#[pyclass(name = "EarlyTerminationParams", from_py_object)]
+#[derive(Copy, Clone, Debug, Into)]
+pub struct PyEarlyTerminationParams(EarlyTerminationParams);
+
+#[pyclass_repr]
+#[pymethods]
+impl PyEarlyTerminationParams {
+    #[new]
+    #[pyo3(signature = (epsilon, patience))]
+    pub fn new(epsilon: f64, patience: usize) -> Self {
+        Self(EarlyTerminationParams {
+            epsilon: OrderedFloat(epsilon),
+            patience,
+        })
+    }
+
+    #[getter]
+    pub fn epsilon(&self) -> f64 {
+        self.0.epsilon.into_inner()
+    }
+
+    #[getter]
+    pub fn patience(&self) -> usize {
+        self.0.patience
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl PyEarlyTerminationParams {
+    fn _getters(self) {
+        // Every field should have a getter method
+        let EarlyTerminationParams {
+            epsilon: _,
+            patience: _,
+        } = self.0;
+    }
+}
@@ -246,8 +246,9 @@ pub struct PyMatchPhrase(pub MatchPhrase);
 #[pymethods]
 impl PyMatchPhrase {
     #[new]
-    pub fn new(phrase: String) -> Self {
-        Self(MatchPhrase { phrase })
+    #[pyo3(signature = (phrase, slop = None))]
+    pub fn new(phrase: String, slop: Option<u32>) -> Self {
+        Self(MatchPhrase { phrase, slop })
     }
 
     #[getter]
@@ -255,6 +256,11 @@ impl PyMatchPhrase {
         &self.0.phrase
     }
 
+    #[getter]
+    pub fn slop(&self) -> Option<u32> {
+        self.0.slop
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -263,7 +269,7 @@ impl PyMatchPhrase {
 impl PyMatchPhrase {
     fn _getters(self) {
         // Every field should have a getter method
-        let MatchPhrase { phrase: _ } = self.0;
+        let MatchPhrase { phrase: _, slop: _ } = self.0;
     }
 }
 
@@ -255,13 +255,14 @@ pub struct PyIntegerIndexParams(IntegerIndexParams);
 #[pymethods]
 impl PyIntegerIndexParams {
     #[new]
-    #[pyo3(signature = (lookup = None, range = None, is_principal = None, on_disk = None, enable_hnsw = None))]
+    #[pyo3(signature = (lookup = None, range = None, is_principal = None, on_disk = None, enable_hnsw = None, enable_hnsw_ranges = None))]
     pub fn new(
         lookup: Option<bool>,
         range: Option<bool>,
         is_principal: Option<bool>,
         on_disk: Option<bool>,
         enable_hnsw: Option<bool>,
+        enable_hnsw_ranges: Option<bool>,
     ) -> Self {
         Self(IntegerIndexParams {
             r#type: Default::default(),
@@ -270,6 +271,7 @@ impl PyIntegerIndexParams {
             is_principal,
             on_disk,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 
@@ -297,6 +299,11 @@ impl PyIntegerIndexParams {
     pub fn enable_hnsw(&self) -> Option<bool> {
         self.0.enable_hnsw
     }
+
+    #[getter]
+    pub fn enable_hnsw_ranges(&self) -> Option<bool> {
+        self.0.enable_hnsw_ranges
+    }
 }
 
 impl PyIntegerIndexParams {
@@ -309,6 +316,7 @@ impl PyIntegerIndexParams {
             is_principal: _,
             on_disk: _,
             enable_hnsw: _,
+            enable_hnsw_ranges: _,
         } = self.0;
     }
 }
@@ -322,17 +330,19 @@ pub struct PyFloatIndexParams(FloatIndexParams);
 #[pymethods]
 impl PyFloatIndexParams {
     #[new]
-    #[pyo3(signature = (is_principal = None, on_disk = None, enable_hnsw = None))]
+    #[pyo3(signature = (is_principal = None, on_disk = None, enable_hnsw = None, enable_hnsw_ranges = None))]
     pub fn new(
         is_principal: Option<bool>,
         on_disk: Option<bool>,
         enable_hnsw: Option<bool>,
+        enable_hnsw_ranges: Option<bool>,
     ) -> Self {
         Self(FloatIndexParams {
             r#type: Default::default(),
             is_principal,
             on_disk,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 
@@ -350,6 +360,11 @@ impl PyFloatIndexParams {
     pub fn enable_hnsw(&self) -> Option<bool> {
         self.0.enable_hnsw
     }
+
+    #[getter]
+    pub fn enable_hnsw_ranges(&self) -> Option<bool> {
+        self.0.enable_hnsw_ranges
+    }
 }
 
 impl PyFloatIndexParams {
@@ -360,6 +375,7 @@ impl PyFloatIndexParams {
             is_principal: _,
             on_disk: _,
             enable_hnsw: _,
+            enable_hnsw_ranges: _,
         } = self.0;
     }
 }
@@ -453,17 +469,19 @@ pub struct PyDatetimeIndexParams(DatetimeIndexParams);
 #[pymethods]
 impl PyDatetimeIndexParams {
     #[new]
-    #[pyo3(signature = (is_principal = None, on_disk = None, enable_hnsw = None))]
+    #[pyo3(signature = (is_principal = None, on_disk = None, enable_hnsw = None, enable_hnsw_ranges = None))]
     pub fn new(
         is_principal: Option<bool>,
         on_disk: Option<bool>,
         enable_hnsw: Option<bool>,
+        enable_hnsw_ranges: Option<bool>,
     ) -> Self {
         Self(DatetimeIndexParams {
             r#type: Default::default(),
             is_principal,
             on_disk,
             enable_hnsw,
+            enable_hnsw_ranges,
         })
     }
 
@@ -481,6 +499,11 @@ impl PyDatetimeIndexParams {
     pub fn enable_hnsw(&self) -> Option<bool> {
         self.0.enable_hnsw
     }
+
+    #[getter]
+    pub fn enable_hnsw_ranges(&self) -> Option<bool> {
+        self.0.enable_hnsw_ranges
+    }
 }
 
 impl PyDatetimeIndexParams {
@@ -491,6 +514,7 @@ impl PyDatetimeIndexParams {
             is_principal: _,
             on_disk: _,
             enable_hnsw: _,
+            enable_hnsw_ranges: _,
         } = self.0;
     }
 }
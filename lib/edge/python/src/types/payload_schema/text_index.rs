@@ -490,10 +490,16 @@ pub struct PySnowballParams(SnowballParams);
 #[pymethods]
 impl PySnowballParams {
     #[new]
-    pub fn new(language: PySnowballLanguage) -> Self {
+    #[pyo3(signature = (language, additional_languages = None))]
+    pub fn new(
+        language: PySnowballLanguage,
+        additional_languages: Option<Vec<PySnowballLanguage>>,
+    ) -> Self {
         Self(SnowballParams {
             r#type: Default::default(),
             language: SnowballLanguage::from(language),
+            additional_languages: additional_languages
+                .map(|languages| languages.into_iter().map(SnowballLanguage::from).collect()),
         })
     }
 
@@ -501,6 +507,17 @@ impl PySnowballParams {
     pub fn language(&self) -> PySnowballLanguage {
         PySnowballLanguage::from(self.0.language)
     }
+
+    #[getter]
+    pub fn additional_languages(&self) -> Option<Vec<PySnowballLanguage>> {
+        self.0.additional_languages.as_ref().map(|languages| {
+            languages
+                .iter()
+                .copied()
+                .map(PySnowballLanguage::from)
+                .collect()
+        })
+    }
 }
 
 impl PySnowballParams {
@@ -509,6 +526,7 @@ impl PySnowballParams {
         let SnowballParams {
             r#type: _, // not relevant for Qdrant Edge
             language: _,
+            additional_languages: _,
         } = self.0;
     }
 }
@@ -22,6 +22,7 @@ impl PyPoint {
             id: PointIdType::from(id),
             vector: VectorStructPersisted::from(vector),
             payload: payload.map(Payload::from),
+            expires_at: None,
         };
 
         Self(point)
@@ -42,6 +43,11 @@ impl PyPoint {
         self.0.payload.as_ref().map(PyPayload::wrap_ref)
     }
 
+    #[getter]
+    pub fn expires_at(&self) -> Option<u64> {
+        self.0.expires_at
+    }
+
     pub fn __repr__(&self) -> String {
         self.repr()
     }
@@ -54,6 +60,7 @@ impl PyPoint {
             id: _,
             vector: _,
             payload: _,
+            expires_at: _,
         } = self.0;
     }
 }
@@ -4,7 +4,7 @@
 //! is supported via `EdgeVectorParams::quantization_config`; when set it overrides the
 //! global `EdgeShardConfig::quantization_config` for that vector.
 
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use segment::types::{
     Distance, HnswConfig, Indexes, MultiVectorConfig, QuantizationConfig, SparseVectorDataConfig,
@@ -63,6 +63,10 @@ impl EdgeVectorParams {
             quantization_config,
             multivector_config: *multivector_config,
             datatype: *datatype,
+            mmap_advice: None,
+            dim_range: None,
+            preserve_norm: None,
+            max_versions: None,
         }
     }
 
@@ -83,6 +87,8 @@ impl EdgeVectorParams {
         DenseVectorOptimizerConfig {
             on_disk: *on_disk,
             hnsw_config: hnsw_config.unwrap_or(*global_hnsw_config),
+            vamana_config: None,
+            ivf_config: None,
             quantization_config: quantization_config
                 .clone()
                 .or_else(|| global_quantization_config.cloned()),
@@ -98,6 +104,10 @@ impl EdgeVectorParams {
             quantization_config, // edge uses global only
             multivector_config,
             datatype,
+            mmap_advice: _,   // edge uses defaults of the storage type
+            dim_range: _,     // edge stores vectors of fixed dimension only
+            preserve_norm: _, // edge normalizes vectors on insert
+            max_versions: _,  // edge keeps no history of vectors
         } = v;
         Self {
             size: *size,
@@ -107,7 +117,7 @@ impl EdgeVectorParams {
             datatype: *datatype,
             quantization_config: quantization_config.clone(),
             hnsw_config: match index {
-                Indexes::Plain {} => None,
+                Indexes::Plain {} | Indexes::Vamana(_) | Indexes::Ivf(_) => None,
                 Indexes::Hnsw(hnsw_config) => Some(*hnsw_config),
             },
         }
@@ -127,6 +137,9 @@ pub struct EdgeSparseVectorParams {
     pub on_disk: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+    /// BM25 ranking parameters, used with the `idf` modifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bm25: Option<Bm25Params>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub datatype: Option<VectorStorageDatatype>,
 }
@@ -137,6 +150,7 @@ impl EdgeSparseVectorParams {
             full_scan_threshold,
             on_disk: _,
             modifier,
+            bm25,
             datatype,
         } = self;
         SparseVectorDataConfig {
@@ -147,6 +161,7 @@ impl EdgeSparseVectorParams {
             },
             storage_type: SparseVectorStorageType::Mmap,
             modifier: *modifier,
+            bm25: *bm25,
         }
     }
 
@@ -157,6 +172,7 @@ impl EdgeSparseVectorParams {
             full_scan_threshold: _,
             on_disk,
             modifier: _,
+            bm25: _,
             datatype: _,
         } = self;
         shard::optimizers::config::SparseVectorOptimizerConfig { on_disk: *on_disk }
@@ -167,6 +183,7 @@ impl EdgeSparseVectorParams {
             index,
             storage_type: _, // edge uses on_disk from index_type
             modifier,
+            bm25,
         } = s;
         let SparseIndexConfig {
             full_scan_threshold,
@@ -177,6 +194,7 @@ impl EdgeSparseVectorParams {
             full_scan_threshold: *full_scan_threshold,
             on_disk: Some(index_type.is_on_disk()),
             modifier: *modifier,
+            bm25: *bm25,
             datatype: *datatype,
         }
     }
@@ -12,6 +12,7 @@ mod snapshots;
 mod types;
 pub use types::*;
 mod update;
+mod vocabulary;
 
 use std::num::NonZero;
 use std::path::{Path, PathBuf};
@@ -732,6 +732,7 @@ mod tests {
                 VectorInternal::from(vec![id as f32]),
             )]))),
             payload: None,
+            expires_at: None,
         }
     }
 
@@ -226,6 +226,43 @@ impl EdgeShard {
                 self.query_scroll(&scroll_request)
             }
 
+            ScoringQuery::Text(text_query) => {
+                // create single scroll request for rescoring query
+                let filter = filter_by_point_ids(&sources);
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload: false.into(),
+                    with_vector: false.into(),
+                    scroll_order: ScrollOrder::ByText {
+                        query: text_query,
+                        score_threshold: score_threshold.map(OrderedFloat::into_inner),
+                    },
+                };
+
+                self.query_scroll(&scroll_request)
+            }
+
+            ScoringQuery::Hybrid(hybrid_query) => {
+                // create single scroll request for rescoring query
+                let filter = filter_by_point_ids(&sources);
+
+                let scroll_request = QueryScrollRequestInternal {
+                    limit,
+                    filter: Some(filter),
+                    with_payload: false.into(),
+                    with_vector: false.into(),
+                    scroll_order: ScrollOrder::ByHybrid {
+                        query: hybrid_query,
+                        params,
+                        score_threshold: score_threshold.map(OrderedFloat::into_inner),
+                    },
+                };
+
+                self.query_scroll(&scroll_request)
+            }
+
             ScoringQuery::Vector(query_enum) => {
                 // create single search request for rescoring query
                 let filter = filter_by_point_ids(&sources);
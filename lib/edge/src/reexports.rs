@@ -14,19 +14,22 @@ mod reexports_from_qdrant_crates {
         DEFAULT_VECTOR_NAME, NamedQuery, TypedMultiDenseVector,
     };
     pub use segment::index::query_optimization::rescore_formula::parsed_formula::DecayKind;
+    pub use segment::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
     pub use segment::json_path::JsonPath;
     pub use segment::types::{
-        AcornSearchParams, AnyVariants, BinaryQuantizationConfig, BinaryQuantizationEncoding,
-        BinaryQuantizationQueryEncoding, CompressionRatio, Condition, DateTimeWrapper, Distance,
-        ExtendedPointId as PointId, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-        GeoRadius, HasIdCondition, HasVectorCondition, HnswConfig as HnswIndexConfig,
-        IsEmptyCondition, IsNullCondition, Match, MatchAny, MatchExcept, MatchPhrase, MatchText,
-        MatchTextAny, MatchValue, MinShould, MultiVectorComparator, MultiVectorConfig, Nested,
-        NestedCondition, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadSchemaParams,
-        PayloadSchemaType, PayloadSelector, PayloadSelectorExclude, PayloadSelectorInclude,
-        ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams, Range,
-        RangeInterface, ScalarQuantizationConfig, ScalarType, ScoredPoint, SearchParams,
-        ValueVariants, ValuesCount, VectorStorageDatatype, WithPayloadInterface, WithVector,
+        AcornSearchParams, AnisotropicQuantizationConfig, AnyVariants, BinaryQuantizationConfig,
+        BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding, CompressionRatio, Condition,
+        DateTimeWrapper, Distance, ExtendedPointId as PointId, FieldCondition, Filter,
+        GeoBoundingBox, GeoPoint, GeoPolygon, GeoRadius, HasIdCondition, HasVectorCondition,
+        HnswConfig as HnswIndexConfig, IsEmptyCondition, IsNullCondition, Match, MatchAny,
+        MatchExcept, MatchPhrase, MatchText, MatchTextAny, MatchValue, MinShould,
+        MultiVectorComparator, MultiVectorConfig, Nested, NestedCondition, Payload,
+        PayloadFieldSchema, PayloadIndexInfo, PayloadSchemaParams, PayloadSchemaType,
+        PayloadSelector, PayloadSelectorExclude, PayloadSelectorInclude, ProductQuantizationConfig,
+        QuantizationConfig, QuantizationSearchParams, Range, RangeInterface,
+        ResidualQuantizationConfig, ScalarQuantizationConfig, ScalarType, ScoredPoint,
+        SearchParams, ValueVariants, ValuesCount, VectorStorageDatatype, WithPayloadInterface,
+        WithVector,
     };
     pub use segment::vector_storage::query::{
         ContextPair, ContextQuery, DiscoverQuery, FeedbackItem,
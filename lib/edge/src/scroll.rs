@@ -2,18 +2,21 @@ use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 
 use common::counter::hardware_accumulator::HwMeasurementAcc;
-use common::types::DeferredBehavior;
+use common::types::{DeferredBehavior, ScoreType};
 use itertools::Itertools as _;
 use rand::RngExt;
 use rand::distr::weighted::WeightedIndex;
 use rand::rngs::StdRng;
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::text_query::{TextQueryInternal, TextQueryStatistics};
+use segment::index::hybrid_index::HybridQuery;
 use segment::types::*;
 use shard::query::scroll::{QueryScrollRequestInternal, ScrollOrder};
 use shard::retrieve::record_internal::RecordInternal;
 use shard::retrieve::retrieve_blocking::retrieve_blocking;
 use shard::scroll::ScrollRequestInternal;
+use shard::search_result_aggregator::BatchResultAggregator;
 
 use super::EdgeShard;
 use crate::DEFAULT_EDGE_TIMEOUT;
@@ -110,6 +113,38 @@ impl EdgeShard {
                 filter.as_ref(),
                 HwMeasurementAcc::disposable_edge(),
             )?,
+            ScrollOrder::ByText {
+                query,
+                score_threshold,
+            } => {
+                // Points are scored by relevance, not just ordered
+                return self.scroll_by_text(
+                    *limit,
+                    with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    query,
+                    *score_threshold,
+                    HwMeasurementAcc::disposable_edge(),
+                );
+            }
+            ScrollOrder::ByHybrid {
+                query,
+                params,
+                score_threshold,
+            } => {
+                // Points are scored by relevance, not just ordered
+                return self.scroll_by_hybrid(
+                    *limit,
+                    with_payload,
+                    with_vector,
+                    filter.as_ref(),
+                    query,
+                    params.as_ref(),
+                    *score_threshold,
+                    HwMeasurementAcc::disposable_edge(),
+                );
+            }
         };
 
         let point_results = records
@@ -235,6 +270,168 @@ impl EdgeShard {
         Ok(ordered_points)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn scroll_by_text(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        text_query: &TextQueryInternal,
+        score_threshold: Option<ScoreType>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let (non_appendable, appendable) = self.segments.read().split_segments();
+        let all_segments = non_appendable.into_iter().chain(appendable).collect_vec();
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+
+        let TextQueryInternal {
+            query: text_query,
+            statistics,
+        } = text_query;
+
+        // Scores must be based on the statistics of all segments to be comparable
+        let statistics = match statistics {
+            Some(statistics) => statistics.clone(),
+            None => all_segments
+                .iter()
+                .map(|segment| {
+                    segment
+                        .get()
+                        .read()
+                        .text_query_statistics(text_query, &hw_counter)
+                })
+                .process_results(|iter| {
+                    iter.fold(TextQueryStatistics::default(), |mut merged, statistics| {
+                        merged.merge(&statistics);
+                        merged
+                    })
+                })?,
+        };
+
+        let read_results: Vec<_> = all_segments
+            .iter()
+            .map(|segment| {
+                segment.get().read().read_text_scored_filtered(
+                    limit,
+                    filter,
+                    text_query,
+                    &statistics,
+                    &AtomicBool::new(false),
+                    &hw_counter,
+                    DeferredBehavior::Exclude,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Same point may be present in multiple segments during optimization,
+        // only the latest version of it is scored
+        let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+        aggregator.update_point_versions(read_results.iter().flatten());
+        aggregator.update_batch_results(0, read_results.into_iter().flatten());
+        let mut top =
+            aggregator.into_topk().into_iter().next().ok_or_else(|| {
+                OperationError::service_error("expected first result of aggregator")
+            })?;
+        top.retain(|point| score_threshold.is_none_or(|threshold| point.score >= threshold));
+
+        let point_ids = top.iter().map(|point| point.id).collect_vec();
+        let mut points = retrieve_blocking(
+            self.segments.clone(),
+            &point_ids,
+            &WithPayload::from(with_payload_interface),
+            with_vector,
+            DEFAULT_EDGE_TIMEOUT,
+            &AtomicBool::new(false),
+            hw_measurement_acc,
+            DeferredBehavior::Exclude,
+        )?;
+
+        let scored_points = top
+            .into_iter()
+            .filter_map(|point| {
+                let record = points.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    ..point
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn scroll_by_hybrid(
+        &self,
+        limit: usize,
+        with_payload_interface: &WithPayloadInterface,
+        with_vector: &WithVector,
+        filter: Option<&Filter>,
+        hybrid_query: &HybridQuery,
+        params: Option<&SearchParams>,
+        score_threshold: Option<ScoreType>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let (non_appendable, appendable) = self.segments.read().split_segments();
+        let hw_counter = hw_measurement_acc.get_counter_cell();
+
+        let segments_results: Vec<_> = non_appendable
+            .into_iter()
+            .chain(appendable)
+            .map(|segment| {
+                segment.get().read().hybrid_search(
+                    hybrid_query,
+                    filter,
+                    limit,
+                    params,
+                    &AtomicBool::new(false),
+                    &hw_counter,
+                )
+            })
+            .collect::<Result<_, _>>()?;
+
+        // Same point may be present in multiple segments during optimization,
+        // only the latest version of it is scored
+        let mut aggregator = BatchResultAggregator::new(std::iter::once(limit));
+        aggregator.update_point_versions(segments_results.iter().flatten());
+        aggregator.update_batch_results(0, segments_results.into_iter().flatten());
+        let mut top =
+            aggregator.into_topk().into_iter().next().ok_or_else(|| {
+                OperationError::service_error("expected first result of aggregator")
+            })?;
+        top.retain(|point| score_threshold.is_none_or(|threshold| point.score >= threshold));
+
+        let point_ids = top.iter().map(|point| point.id).collect_vec();
+        let mut points = retrieve_blocking(
+            self.segments.clone(),
+            &point_ids,
+            &WithPayload::from(with_payload_interface),
+            with_vector,
+            DEFAULT_EDGE_TIMEOUT,
+            &AtomicBool::new(false),
+            hw_measurement_acc,
+            DeferredBehavior::Exclude,
+        )?;
+
+        let scored_points = top
+            .into_iter()
+            .filter_map(|point| {
+                let record = points.remove(&point.id)?;
+                Some(ScoredPoint {
+                    payload: record.payload,
+                    vector: record.vector,
+                    shard_key: record.shard_key,
+                    ..point
+                })
+            })
+            .collect();
+
+        Ok(scored_points)
+    }
+
     fn scroll_randomly(
         &self,
         limit: usize,
@@ -29,6 +29,13 @@ impl EdgeShard {
                     .get(vector_name)
                     .is_some_and(|v| v.modifier == Some(Modifier::Idf))
             },
+            |vector_name| {
+                self.config
+                    .read()
+                    .sparse_vectors
+                    .get(vector_name)
+                    .and_then(|v| v.bm25)
+            },
         );
         let [search] = searches;
         let Some(context) = fill_query_context(
@@ -20,6 +20,7 @@ impl PointStruct {
             id: id.into(),
             vector: VectorStructPersisted::from(vectors.into().0),
             payload: Some(payload),
+            expires_at: None,
         })
     }
 }
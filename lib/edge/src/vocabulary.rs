@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+
+use segment::common::operation_error::OperationResult;
+use segment::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
+use shard::update::register_sparse_vocabulary;
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
+
+use crate::EdgeShard;
+
+impl EdgeShard {
+    /// Register token mappings in the vocabulary of the sparse vector `vector_name`.
+    ///
+    /// Tokens which are already registered can't be mapped to a different dimension.
+    pub fn register_sparse_vocabulary(
+        &self,
+        vector_name: &str,
+        mapping: &BTreeMap<String, DimId>,
+    ) -> OperationResult<()> {
+        let _update_guard = self.segments.acquire_updates_lock();
+        let segments = self.segments.read();
+        register_sparse_vocabulary(&segments, vector_name, mapping)?;
+        Ok(())
+    }
+
+    /// Convert token keyed vector into a sparse vector of `vector_name`, using its vocabulary.
+    ///
+    /// Use [`UnknownTokens::Error`] for inserted vectors and [`UnknownTokens::Skip`] for queries.
+    pub fn remap_sparse_vector(
+        &self,
+        vector_name: &str,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector> {
+        self.segments
+            .read()
+            .remap_sparse_vector(vector_name, vector, unknown_tokens)
+    }
+}
@@ -25,8 +25,10 @@ fn encode_dot_bench(c: &mut Criterion) {
         distance_type: DistanceType::Dot,
         invert: false,
     };
-    let quantized_vector_size =
-        EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+    let quantized_vector_size = EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+        &vector_parameters,
+        ScalarQuantizationMethod::Int8,
+    );
     let i8_encoded = EncodedVectorsU8::encode(
         (0..vectors_count).map(|i| &list[i * vector_dim..(i + 1) * vector_dim]),
         TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -130,8 +132,10 @@ fn encode_l1_bench(c: &mut Criterion) {
         distance_type: DistanceType::L1,
         invert: true,
     };
-    let quantized_vector_size =
-        EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+    let quantized_vector_size = EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+        &vector_parameters,
+        ScalarQuantizationMethod::Int8,
+    );
     let i8_encoded = EncodedVectorsU8::encode(
         (0..vectors_count).map(|i| &list[i * vector_dim..(i + 1) * vector_dim]),
         TestEncodedStorageBuilder::new(None, quantized_vector_size),
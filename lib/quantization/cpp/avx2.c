@@ -62,6 +62,36 @@ EXPORT float impl_score_dot_avx(
     return mul_scalar;
 }
 
+EXPORT float impl_score_dot_int4_avx(
+    const uint8_t* query_ptr,
+    const uint8_t* vector_ptr,
+    uint32_t dim
+) {
+    const __m128i* v_ptr = (const __m128i*)vector_ptr;
+    const __m256i* q_ptr = (const __m256i*)query_ptr;
+
+    __m128i mask_low = _mm_set1_epi8(0x0F);
+    __m256i ones = _mm256_set1_epi16(1);
+    __m256i mul = _mm256_setzero_si256();
+    // every 16 bytes of vector hold 32 components, query has codes of low nibbles first
+    for (uint32_t _i = 0; _i < dim / 32; _i++) {
+        __m128i v = _mm_loadu_si128(v_ptr);
+        __m256i q = _mm256_loadu_si256(q_ptr);
+        v_ptr++;
+        q_ptr++;
+
+        __m128i v_low = _mm_and_si128(v, mask_low);
+        __m128i v_high = _mm_and_si128(_mm_srli_epi16(v, 4), mask_low);
+        __m256i v_unpacked = _mm256_inserti128_si256(_mm256_castsi128_si256(v_low), v_high, 1);
+
+        __m256i s = _mm256_maddubs_epi16(v_unpacked, q);
+        mul = _mm256_add_epi32(mul, _mm256_madd_epi16(s, ones));
+    }
+    __m256 mul_ps = _mm256_cvtepi32_ps(mul);
+    HSUM256_PS(mul_ps, mul_scalar);
+    return mul_scalar;
+}
+
 EXPORT float impl_score_l1_avx(
     const uint8_t* query_ptr,
     const uint8_t* vector_ptr,
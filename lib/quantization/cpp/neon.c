@@ -28,6 +28,34 @@ EXPORT float impl_score_dot_neon(
     return (float)vaddvq_u32(vaddq_u32(mul1, mul2));
 }
 
+EXPORT float impl_score_dot_int4_neon(
+    const uint8_t* query_ptr,
+    const uint8_t* vector_ptr,
+    uint32_t dim
+) {
+    uint32x4_t mul1 = vdupq_n_u32(0);
+    uint32x4_t mul2 = vdupq_n_u32(0);
+    uint8x16_t mask_low = vdupq_n_u8(0x0F);
+    // every 16 bytes of vector hold 32 components, query has codes of low nibbles first
+    for (uint32_t _i = 0; _i < dim / 32; _i++) {
+        uint8x16_t v = vld1q_u8(vector_ptr);
+        uint8x16_t q_low = vld1q_u8(query_ptr);
+        uint8x16_t q_high = vld1q_u8(query_ptr + 16);
+        vector_ptr += 16;
+        query_ptr += 32;
+
+        uint8x16_t v_low = vandq_u8(v, mask_low);
+        uint8x16_t v_high = vshrq_n_u8(v, 4);
+        uint16x8_t mul_low = vmull_u8(vget_low_u8(q_low), vget_low_u8(v_low));
+        mul_low = vmlal_u8(mul_low, vget_low_u8(q_high), vget_low_u8(v_high));
+        uint16x8_t mul_high = vmull_u8(vget_high_u8(q_low), vget_high_u8(v_low));
+        mul_high = vmlal_u8(mul_high, vget_high_u8(q_high), vget_high_u8(v_high));
+        mul1 = vpadalq_u16(mul1, mul_low);
+        mul2 = vpadalq_u16(mul2, mul_high);
+    }
+    return (float)vaddvq_u32(vaddq_u32(mul1, mul2));
+}
+
 EXPORT uint32_t impl_xor_popcnt_neon_uint128(
     const uint8_t* query_ptr,
     const uint8_t* vector_ptr,
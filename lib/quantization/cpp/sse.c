@@ -51,6 +51,38 @@ EXPORT float impl_score_dot_sse(
     return mul_scalar;
 }
 
+EXPORT float impl_score_dot_int4_sse(
+    const uint8_t* query_ptr,
+    const uint8_t* vector_ptr,
+    uint32_t dim
+) {
+    const __m128i* v_ptr = (const __m128i*)vector_ptr;
+    const __m128i* q_ptr = (const __m128i*)query_ptr;
+
+    __m128i mask_low = _mm_set1_epi8(0x0F);
+    __m128i ones = _mm_set1_epi16(1);
+    __m128i mul = _mm_setzero_si128();
+    // every 16 bytes of vector hold 32 components, query has codes of low nibbles first
+    for (uint32_t _i = 0; _i < dim / 32; _i++) {
+        __m128i v = _mm_loadu_si128(v_ptr);
+        __m128i q_low = _mm_loadu_si128(q_ptr);
+        __m128i q_high = _mm_loadu_si128(q_ptr + 1);
+        v_ptr++;
+        q_ptr += 2;
+
+        __m128i v_low = _mm_and_si128(v, mask_low);
+        __m128i v_high = _mm_and_si128(_mm_srli_epi16(v, 4), mask_low);
+        __m128i s = _mm_add_epi16(
+            _mm_maddubs_epi16(v_low, q_low),
+            _mm_maddubs_epi16(v_high, q_high)
+        );
+        mul = _mm_add_epi32(mul, _mm_madd_epi16(s, ones));
+    }
+    __m128 mul_ps = _mm_cvtepi32_ps(mul);
+    HSUM128_PS(mul_ps, mul_scalar);
+    return mul_scalar;
+}
+
 EXPORT uint32_t impl_xor_popcnt_sse_uint128(
     const uint8_t* query_ptr,
     const uint8_t* vector_ptr,
@@ -0,0 +1,328 @@
+//! Anisotropic loss for product quantization, as proposed in
+//! "Accelerating Large-Scale Inference with Anisotropic Vector Quantization" (Guo et al., 2020).
+//!
+//! Quantization error parallel to the original vector changes its inner products with similar
+//! queries much more than the orthogonal error does. Anisotropic loss weights the parallel part
+//! of the residual `parallel_weight` times more than the orthogonal one:
+//!
+//! `loss = |r_orth|^2 + parallel_weight * |r_par|^2 = |r|^2 + (parallel_weight - 1) * (r·x)^2 / |x|^2`
+//!
+//! where `r = x - x'` is the residual of vector `x` and its quantized approximation `x'`.
+//! Chunk codes and centroid positions are chosen to minimize this loss instead of euclidean
+//! distance, the encoded format and scoring are the same as for product quantization.
+
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rayon::prelude::*;
+
+use crate::EncodingError;
+
+/// Number of vectors, on which centroids are refined
+pub const TRAINING_SAMPLE_SIZE: usize = 2048;
+/// Rounds of alternating code assignment and centroid update
+const TRAINING_ITERATIONS: usize = 4;
+/// Max rounds of coordinate descent over chunks when assigning codes of a vector
+const ENCODING_ROUNDS: usize = 4;
+
+/// Encode single vector, choosing chunk codes which minimize anisotropic loss.
+///
+/// # Arguments
+/// * `vector_data` - Original vector data
+/// * `vector_division` - Division of original vector into chunks
+/// * `centroids` - Centroid positions (flattened by chunks; for similarity to vector data format)
+/// * `parallel_weight` - Weight of the residual parallel to the vector, relative to the orthogonal one
+/// * `encoded_vector` - Encoded result as a preallocated vector
+pub fn encode_vector(
+    vector_data: &[f32],
+    vector_division: &[Range<usize>],
+    centroids: &[Vec<f32>],
+    parallel_weight: f32,
+    encoded_vector: &mut Vec<u8>,
+) {
+    encoded_vector.clear();
+    encoded_vector.extend(
+        assign_codes(vector_data, vector_division, centroids, parallel_weight)
+            .into_iter()
+            .map(|code| code as u8),
+    );
+}
+
+/// Move centroids to minimize anisotropic loss of `sample` vectors.
+///
+/// Codes of sample vectors are assigned with current centroids, then each centroid is moved to
+/// the position with the least total loss of vectors assigned to it. Steps are repeated for a
+/// fixed number of iterations.
+pub fn refine_centroids(
+    sample: &[Vec<f32>],
+    vector_division: &[Range<usize>],
+    centroids: &mut [Vec<f32>],
+    parallel_weight: f32,
+    max_threads: usize,
+    stopped: &AtomicBool,
+) -> Result<(), EncodingError> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .thread_name(|idx| format!("anisotropic-training-{idx}"))
+        .num_threads(std::cmp::max(1, max_threads))
+        .build()
+        .map_err(|e| {
+            EncodingError::EncodingError(format!(
+                "Failed anisotropic training while thread pool init: {e}"
+            ))
+        })?;
+
+    // Weight of squared residual projection onto the vector in the loss
+    let projection_weights: Vec<f32> = sample
+        .iter()
+        .map(|vector| projection_weight(vector, parallel_weight).unwrap_or(0.0))
+        .collect();
+
+    for _ in 0..TRAINING_ITERATIONS {
+        if stopped.load(Ordering::Relaxed) {
+            return Err(EncodingError::Stopped);
+        }
+
+        let current_centroids: &[Vec<f32>] = centroids;
+        let codes: Vec<Vec<usize>> = pool.install(|| {
+            sample
+                .par_iter()
+                .map(|vector| {
+                    assign_codes(vector, vector_division, current_centroids, parallel_weight)
+                })
+                .collect()
+        });
+
+        // Residual projection of each vector onto itself, kept up to date while chunks move
+        let mut projections: Vec<f32> = sample
+            .iter()
+            .zip(&codes)
+            .map(|(vector, codes)| {
+                vector_division
+                    .iter()
+                    .zip(codes)
+                    .map(|(range, &code)| chunk_projection(vector, &centroids[code], range))
+                    .sum()
+            })
+            .collect();
+
+        for (chunk_index, range) in vector_division.iter().enumerate() {
+            let chunk_projections: Vec<f32> = sample
+                .iter()
+                .zip(&codes)
+                .map(|(vector, codes)| {
+                    chunk_projection(vector, &centroids[codes[chunk_index]], range)
+                })
+                .collect();
+
+            let mut systems = vec![LinearSystem::new(range.len()); centroids.len()];
+            for (i, (vector, codes)) in sample.iter().zip(&codes).enumerate() {
+                let chunk = &vector[range.clone()];
+                // Residual projection with this chunk's centroid at the origin
+                let target = projections[i] - chunk_projections[i]
+                    + chunk.iter().map(|x| x * x).sum::<f32>();
+                systems[codes[chunk_index]].add(chunk, projection_weights[i], target);
+            }
+
+            for (centroid, system) in centroids.iter_mut().zip(systems) {
+                if let Some(position) = system.solve() {
+                    centroid[range.clone()].copy_from_slice(&position);
+                }
+            }
+
+            for (i, (vector, codes)) in sample.iter().zip(&codes).enumerate() {
+                projections[i] += chunk_projection(vector, &centroids[codes[chunk_index]], range)
+                    - chunk_projections[i];
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Weight of squared residual projection in the loss, `None` if the loss is isotropic
+fn projection_weight(vector_data: &[f32], parallel_weight: f32) -> Option<f32> {
+    let sqr_norm: f32 = vector_data.iter().map(|x| x * x).sum();
+    (sqr_norm > f32::EPSILON && parallel_weight != 1.0).then(|| (parallel_weight - 1.0) / sqr_norm)
+}
+
+/// Projection of chunk `range` of the residual onto the same chunk of the vector
+fn chunk_projection(vector_data: &[f32], centroid: &[f32], range: &Range<usize>) -> f32 {
+    vector_data[range.clone()]
+        .iter()
+        .zip(&centroid[range.clone()])
+        .map(|(x, c)| (x - c) * x)
+        .sum()
+}
+
+/// Chunk codes of a vector, minimizing anisotropic loss by coordinate descent over chunks
+fn assign_codes(
+    vector_data: &[f32],
+    vector_division: &[Range<usize>],
+    centroids: &[Vec<f32>],
+    parallel_weight: f32,
+) -> Vec<usize> {
+    let centroids_count = centroids.len();
+
+    // Squared residual and its projection onto the vector, for each centroid of each chunk
+    let mut sqr_residuals = Vec::with_capacity(vector_division.len() * centroids_count);
+    let mut projections = Vec::with_capacity(vector_division.len() * centroids_count);
+    for range in vector_division {
+        let chunk = &vector_data[range.clone()];
+        for centroid in centroids {
+            let (sqr_residual, projection) = chunk.iter().zip(&centroid[range.clone()]).fold(
+                (0.0, 0.0),
+                |(sqr_residual, projection), (x, c)| {
+                    let residual = x - c;
+                    (
+                        sqr_residual + residual * residual,
+                        projection + residual * x,
+                    )
+                },
+            );
+            sqr_residuals.push(sqr_residual);
+            projections.push(projection);
+        }
+    }
+
+    // Start from nearest centroids, as product quantization does
+    let mut codes: Vec<usize> = sqr_residuals
+        .chunks_exact(centroids_count)
+        .map(|chunk_sqr_residuals| argmin(chunk_sqr_residuals.iter().copied()))
+        .collect();
+
+    let Some(projection_weight) = projection_weight(vector_data, parallel_weight) else {
+        return codes;
+    };
+
+    let mut total_sqr_residual: f32 = codes
+        .iter()
+        .enumerate()
+        .map(|(chunk_index, &code)| sqr_residuals[chunk_index * centroids_count + code])
+        .sum();
+    let mut total_projection: f32 = codes
+        .iter()
+        .enumerate()
+        .map(|(chunk_index, &code)| projections[chunk_index * centroids_count + code])
+        .sum();
+
+    for _ in 0..ENCODING_ROUNDS {
+        let mut changed = false;
+        for (chunk_index, code) in codes.iter_mut().enumerate() {
+            let chunk_sqr_residuals =
+                &sqr_residuals[chunk_index * centroids_count..][..centroids_count];
+            let chunk_projections =
+                &projections[chunk_index * centroids_count..][..centroids_count];
+
+            let other_sqr_residual = total_sqr_residual - chunk_sqr_residuals[*code];
+            let other_projection = total_projection - chunk_projections[*code];
+            let best_code = argmin(chunk_sqr_residuals.iter().zip(chunk_projections).map(
+                |(sqr_residual, projection)| {
+                    let total_projection = other_projection + projection;
+                    sqr_residual + projection_weight * total_projection * total_projection
+                },
+            ));
+
+            if best_code != *code {
+                *code = best_code;
+                changed = true;
+            }
+            total_sqr_residual = other_sqr_residual + chunk_sqr_residuals[*code];
+            total_projection = other_projection + chunk_projections[*code];
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    codes
+}
+
+fn argmin(values: impl Iterator<Item = f32>) -> usize {
+    let mut min_value = f32::MAX;
+    let mut min_index = 0;
+    for (index, value) in values.enumerate() {
+        if value < min_value {
+            min_value = value;
+            min_index = index;
+        }
+    }
+    min_index
+}
+
+/// Normal equations for position `c` of a centroid, minimizing total loss of assigned chunks `a`:
+///
+/// `sum(|a - c|^2 + w * (t - c·a)^2)`
+///
+/// where `t - c·a` is the residual projection onto the whole vector.
+#[derive(Clone)]
+struct LinearSystem {
+    dim: usize,
+    count: usize,
+    /// Row-major `dim x dim` matrix of `sum(w * a * a^T)`
+    matrix: Vec<f64>,
+    /// `sum(a + w * t * a)`
+    rhs: Vec<f64>,
+}
+
+impl LinearSystem {
+    fn new(dim: usize) -> Self {
+        Self {
+            dim,
+            count: 0,
+            matrix: vec![0.0; dim * dim],
+            rhs: vec![0.0; dim],
+        }
+    }
+
+    fn add(&mut self, chunk: &[f32], weight: f32, target: f32) {
+        self.count += 1;
+        let (weight, target) = (f64::from(weight), f64::from(target));
+        for (i, &a_i) in chunk.iter().enumerate() {
+            let a_i = f64::from(a_i);
+            self.rhs[i] += a_i + weight * target * a_i;
+            for (j, &a_j) in chunk.iter().enumerate() {
+                self.matrix[i * self.dim + j] += weight * a_i * f64::from(a_j);
+            }
+        }
+    }
+
+    /// Solve `(count * I + matrix) * c = rhs` by gaussian elimination.
+    ///
+    /// Returns `None` if no chunks were assigned to the centroid.
+    fn solve(mut self) -> Option<Vec<f32>> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let dim = self.dim;
+        for i in 0..dim {
+            self.matrix[i * dim + i] += self.count as f64;
+        }
+
+        // Matrix is symmetric positive definite, so elimination is stable without pivoting
+        for pivot in 0..dim {
+            let pivot_value = self.matrix[pivot * dim + pivot];
+            for row in pivot + 1..dim {
+                let factor = self.matrix[row * dim + pivot] / pivot_value;
+                if factor == 0.0 {
+                    continue;
+                }
+                for column in pivot..dim {
+                    self.matrix[row * dim + column] -= factor * self.matrix[pivot * dim + column];
+                }
+                self.rhs[row] -= factor * self.rhs[pivot];
+            }
+        }
+
+        let mut solution = vec![0.0; dim];
+        for row in (0..dim).rev() {
+            let known: f64 = (row + 1..dim)
+                .map(|column| self.matrix[row * dim + column] * solution[column])
+                .sum();
+            solution[row] = (self.rhs[row] - known) / self.matrix[row * dim + row];
+        }
+
+        Some(solution.into_iter().map(|value| value as f32).collect())
+    }
+}
@@ -49,6 +49,7 @@ pub enum QueryEncoding {
     SameAsStorage,
     Scalar4bits,
     Scalar8bits,
+    Float32,
 }
 
 impl QueryEncoding {
@@ -61,6 +62,7 @@ pub enum EncodedQueryBQ<TBitsStoreType: BitsStoreType> {
     Binary(EncodedBinVector<TBitsStoreType>),
     Scalar4bits(EncodedScalarVector<TBitsStoreType>),
     Scalar8bits(EncodedScalarVector<TBitsStoreType>),
+    Float32(EncodedFloatVector),
 }
 
 pub struct EncodedBinVector<TBitsStoreType: BitsStoreType> {
@@ -107,6 +109,20 @@ pub struct EncodedScalarVector<TBitsStoreType: BitsStoreType> {
     pub encoded_vector: Vec<TBitsStoreType>,
 }
 
+/// Not quantized query, scored against Binary Quantized (BQ) vectors
+///
+/// Query is normalized into a range [0; 1] the same way as scalar query encodings do,
+/// but values are kept as floats. XOR of a query value `q` and BQ bit `b` is `|q - b|`,
+/// so the sum of XORs over all dimensions is:
+///
+///   sum(q_i) + sum(1 - 2 * q_i for i where b_i == 1)
+///
+/// `query_sum` is the first term, `weights` are `1 - 2 * q_i` for each bit of the BQ vector.
+pub struct EncodedFloatVector {
+    pub weights: Vec<f32>,
+    pub query_sum: f32,
+}
+
 #[derive(Serialize, Deserialize)]
 struct Metadata {
     vector_parameters: VectorParameters,
@@ -444,6 +460,7 @@ impl<TBitsStoreType: BitsStoreType, TStorage: EncodedStorage>
         let query_encoding_needs_stats = match query_encoding {
             QueryEncoding::SameAsStorage => storage_encoding_needs_states,
             QueryEncoding::Scalar4bits | QueryEncoding::Scalar8bits => true,
+            QueryEncoding::Float32 => storage_encoding_needs_states,
         };
 
         let vector_stats = if storage_encoding_needs_states || query_encoding_needs_stats {
@@ -671,23 +688,23 @@ impl<TBitsStoreType: BitsStoreType, TStorage: EncodedStorage>
             QueryEncoding::Scalar4bits => EncodedQueryBQ::Scalar4bits(
                 Self::encode_scalar_query_vector(query, encoding, (u8::BITS / 2) as usize),
             ),
+            QueryEncoding::Float32 => {
+                EncodedQueryBQ::Float32(Self::encode_float_query_vector(query, encoding))
+            }
         }
     }
 
-    fn encode_scalar_query_vector(
-        query: &[f32],
-        encoding: Encoding,
-        bits_count: usize,
-    ) -> EncodedScalarVector<TBitsStoreType> {
+    /// Extend query to match the layout of BQ vector bits for the given storage encoding
+    fn extend_query(query: &[f32], encoding: Encoding) -> Cow<'_, [f32]> {
         match encoding {
-            Encoding::OneBit => Self::_encode_scalar_query_vector(query, bits_count),
+            Encoding::OneBit => Cow::Borrowed(query),
             Encoding::TwoBits => {
                 // For two bits encoding we need to extend the query vector
                 let mut extended_query = Vec::with_capacity(query.len() * 2);
                 // Copy the original query vector twice: for first and second bits in 2bit BQ encoding
                 extended_query.extend_from_slice(query);
                 extended_query.extend_from_slice(query);
-                Self::_encode_scalar_query_vector(&extended_query, bits_count)
+                Cow::Owned(extended_query)
             }
             Encoding::OneAndHalfBits => {
                 // For one and half bits encoding we need to extend the query vector
@@ -699,11 +716,48 @@ impl<TBitsStoreType: BitsStoreType, TStorage: EncodedStorage>
                         .chunks(2)
                         .map(|v| if v.len() == 2 { v[0].max(v[1]) } else { v[0] }),
                 );
-                Self::_encode_scalar_query_vector(&extended_query, bits_count)
+                Cow::Owned(extended_query)
             }
         }
     }
 
+    fn encode_scalar_query_vector(
+        query: &[f32],
+        encoding: Encoding,
+        bits_count: usize,
+    ) -> EncodedScalarVector<TBitsStoreType> {
+        Self::_encode_scalar_query_vector(&Self::extend_query(query, encoding), bits_count)
+    }
+
+    fn encode_float_query_vector(query: &[f32], encoding: Encoding) -> EncodedFloatVector {
+        let extended_query = Self::extend_query(query, encoding);
+
+        // Use the same [0; 1] normalization as scalar query encodings,
+        // so scores are comparable between query encodings
+        let max_abs_value = extended_query.iter().map(|x| x.abs()).fold(0.0, f32::max);
+        let normalize = |value: f32| {
+            if max_abs_value > f32::EPSILON {
+                (value / max_abs_value + 1.0) / 2.0
+            } else {
+                0.5
+            }
+        };
+
+        // Pad weights to the whole storage size, padding bits of BQ vectors are always zero
+        let storage_size = TBitsStoreType::get_storage_size(extended_query.len().max(1))
+            * std::mem::size_of::<TBitsStoreType>()
+            * u8::BITS as usize;
+        let mut weights = vec![0.0; storage_size];
+        let mut query_sum = 0.0;
+        for (weight, &value) in weights.iter_mut().zip(extended_query.iter()) {
+            let normalized = normalize(value);
+            query_sum += normalized;
+            *weight = 1.0 - 2.0 * normalized;
+        }
+
+        EncodedFloatVector { weights, query_sum }
+    }
+
     fn _encode_scalar_query_vector(
         query: &[f32],
         bits_count: usize,
@@ -788,6 +842,28 @@ impl<TBitsStoreType: BitsStoreType, TStorage: EncodedStorage>
             (xor_product as f32) / (((1 << query_bits_count) - 1) as f32)
         };
 
+        self.metric_from_xor_product(xor_product)
+    }
+
+    fn calculate_metric_float(&self, vector: &[u8], query: &EncodedFloatVector) -> f32 {
+        // Bits are stored in little-endian order, so bit `i` of BQ vector is
+        // bit `i % 8` of byte `i / 8` regardless of `TBitsStoreType`
+        let mut xor_product = query.query_sum;
+        for (&byte, weights) in vector
+            .iter()
+            .zip(query.weights.chunks_exact(u8::BITS as usize))
+        {
+            let mut bits = byte;
+            while bits != 0 {
+                xor_product += weights[bits.trailing_zeros() as usize];
+                bits &= bits - 1;
+            }
+        }
+
+        self.metric_from_xor_product(xor_product)
+    }
+
+    fn metric_from_xor_product(&self, xor_product: f32) -> f32 {
         let dim = self.metadata.vector_parameters.dim as f32;
         let zeros_count = dim - xor_product;
 
@@ -973,6 +1049,9 @@ impl<TBitsStoreType: BitsStoreType, TStorage: EncodedStorage> EncodedVectors
                 &encoded_vector.encoded_vector,
                 u8::BITS as usize / 2,
             ),
+            EncodedQueryBQ::Float32(encoded_vector) => {
+                self.calculate_metric_float(bytes, encoded_vector)
+            }
         }
     }
 }
@@ -19,8 +19,11 @@ use fs_err as fs;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
+use crate::anisotropic;
 use crate::encoded_storage::{EncodedStorage, EncodedStorageBuilder};
-use crate::encoded_vectors::{EncodedVectors, VectorParameters, validate_vector_parameters};
+use crate::encoded_vectors::{
+    DistanceType, EncodedVectors, VectorParameters, validate_vector_parameters,
+};
 use crate::kmeans::kmeans;
 use crate::{ConditionalVariable, EncodingError};
 
@@ -73,6 +76,68 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
         max_kmeans_threads: usize,
         meta_path: Option<&Path>,
         stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        Self::encode_impl(
+            data,
+            storage_builder,
+            vector_parameters,
+            count,
+            chunk_size,
+            None,
+            max_kmeans_threads,
+            meta_path,
+            stopped,
+        )
+    }
+
+    /// Encode vector data using product quantization with anisotropic loss.
+    ///
+    /// Centroids and codes are chosen to preserve inner products of vectors with similar queries,
+    /// see [`anisotropic`]. Encoded vectors are scored as with plain product quantization.
+    /// For distances other than dot product, vectors are encoded as with [`Self::encode`].
+    ///
+    /// # Arguments
+    /// * `parallel_weight` - Weight of the residual parallel to the vector, relative to the orthogonal one
+    ///
+    /// Other arguments are the same as for [`Self::encode`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn encode_anisotropic<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone + Send,
+        storage_builder: impl EncodedStorageBuilder<Storage = TStorage> + Send,
+        vector_parameters: &VectorParameters,
+        count: usize,
+        chunk_size: usize,
+        parallel_weight: f32,
+        max_kmeans_threads: usize,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        let parallel_weight =
+            (vector_parameters.distance_type == DistanceType::Dot).then_some(parallel_weight);
+        Self::encode_impl(
+            data,
+            storage_builder,
+            vector_parameters,
+            count,
+            chunk_size,
+            parallel_weight,
+            max_kmeans_threads,
+            meta_path,
+            stopped,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn encode_impl<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone + Send,
+        mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage> + Send,
+        vector_parameters: &VectorParameters,
+        count: usize,
+        chunk_size: usize,
+        parallel_weight: Option<f32>,
+        max_kmeans_threads: usize,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
     ) -> Result<Self, EncodingError> {
         debug_assert!(validate_vector_parameters(data.clone(), vector_parameters).is_ok());
 
@@ -80,7 +145,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
         let vector_division = Self::get_vector_division(vector_parameters.dim, chunk_size);
 
         // then, find flattened centroid positions
-        let centroids = Self::find_centroids(
+        let mut centroids = Self::find_centroids(
             data.clone(),
             &vector_division,
             vector_parameters,
@@ -90,12 +155,29 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
             stopped,
         )?;
 
+        // with not more vectors than centroids, vectors themselves are centroids already
+        if let Some(parallel_weight) = parallel_weight
+            && count > CENTROIDS_COUNT
+        {
+            let sample =
+                Self::sample_vectors(data.clone(), count, anisotropic::TRAINING_SAMPLE_SIZE);
+            anisotropic::refine_centroids(
+                &sample,
+                &vector_division,
+                &mut centroids,
+                parallel_weight,
+                max_kmeans_threads,
+                stopped,
+            )?;
+        }
+
         // finally, encode data
         Self::encode_storage(
             data,
             &mut storage_builder,
             &vector_division,
             &centroids,
+            parallel_weight,
             max_kmeans_threads,
             stopped,
         )?;
@@ -172,6 +254,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
     /// * `storage_builder` - Builder of encoded data container
     /// * `vector_division` - Division of original vector into chunks
     /// * `centroids` - Centroid positions (flattened by chunks; for similarity to vector data format)
+    /// * `parallel_weight` - If set, codes are chosen to minimize anisotropic loss with this weight
     /// * `max_threads` - Max allowed threads for encoding process
     /// * `stopped` - Atomic bool that indicates if encoding should be stopped
     ///
@@ -183,6 +266,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
         storage_builder: &'b mut (impl EncodedStorageBuilder<Storage = TStorage> + Send),
         vector_division: &'b [Range<usize>],
         centroids: &'b [Vec<f32>],
+        parallel_weight: Option<f32>,
         max_threads: usize,
         stopped: &AtomicBool,
     ) -> Result<(), EncodingError> {
@@ -202,6 +286,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
                     storage_builder,
                     vector_division,
                     centroids,
+                    parallel_weight,
                     max_threads,
                     stopped,
                 )
@@ -216,6 +301,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
         storage_builder: &'b mut (impl EncodedStorageBuilder<Storage = TStorage> + Send),
         vector_division: &'b [Range<usize>],
         centroids: &'b [Vec<f32>],
+        parallel_weight: Option<f32>,
         max_threads: usize,
         stopped: &'b AtomicBool,
     ) -> Result<(), EncodingError> {
@@ -246,12 +332,21 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
                         return;
                     }
 
-                    Self::encode_vector(
-                        vector.as_ref(),
-                        vector_division,
-                        centroids,
-                        &mut encoded_vector,
-                    );
+                    match parallel_weight {
+                        Some(parallel_weight) => anisotropic::encode_vector(
+                            vector.as_ref(),
+                            vector_division,
+                            centroids,
+                            parallel_weight,
+                            &mut encoded_vector,
+                        ),
+                        None => Self::encode_vector(
+                            vector.as_ref(),
+                            vector_division,
+                            centroids,
+                            &mut encoded_vector,
+                        ),
+                    }
                     // wait for permission from prev thread to use storage
                     let is_disconnected = condvar.wait();
                     // push encoded vector to storage
@@ -336,6 +431,30 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
     /// * `centroids_count` - Count of centroids for each chunk
     /// * `max_kmeans_threads` - Max allowed threads for kmeans process
     /// * `stopped` - Atomic bool that indicates if encoding should be stopped
+    /// Take up to `sample_size` random vectors out of `count`
+    fn sample_vectors<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a>,
+        count: usize,
+        sample_size: usize,
+    ) -> Vec<Vec<f32>> {
+        let sample_size = sample_size.min(count);
+        let permutor = permutation_iterator::Permutor::new(count as u64);
+        let mut selected_vectors: Vec<usize> =
+            permutor.map(|i| i as usize).take(sample_size).collect();
+        selected_vectors.sort_unstable();
+
+        let mut sample = Vec::with_capacity(sample_size);
+        for (vector_index, vector_data) in data.enumerate() {
+            if sample.len() == sample_size {
+                break;
+            }
+            if vector_index == selected_vectors[sample.len()] {
+                sample.push(vector_data.as_ref().to_vec());
+            }
+        }
+        sample
+    }
+
     fn find_centroids<'a>(
         data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
         vector_division: &[Range<usize>],
@@ -493,6 +612,17 @@ impl<TStorage: EncodedStorage> EncodedVectorsPQ<TStorage> {
         self.encoded_vectors.get_vector_data(i)
     }
 
+    /// Reconstruct approximate original vector from centroids of its chunks
+    pub fn decode_vector(&self, i: PointOffsetType) -> Vec<f32> {
+        let codes = self.encoded_vectors.get_vector_data(i);
+        let mut vector = Vec::with_capacity(self.metadata.vector_parameters.dim);
+        for (range, &code) in self.metadata.vector_division.iter().zip(codes.iter()) {
+            // Centroids are flattened by chunks, take chunk of the centroid using `range`
+            vector.extend_from_slice(&self.metadata.centroids[code as usize][range.clone()]);
+        }
+        vector
+    }
+
     pub fn layout(&self) -> Layout {
         Layout::from_size_align(self.metadata.vector_division.len(), align_of::<u8>()).unwrap()
     }
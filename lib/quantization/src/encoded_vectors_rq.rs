@@ -0,0 +1,594 @@
+use std::alloc::Layout;
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::atomic_save_json;
+use common::mmap::MmapFlusher;
+use common::typelevel::True;
+use common::types::PointOffsetType;
+use fs_err as fs;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::EncodingError;
+use crate::encoded_storage::{EncodedStorage, EncodedStorageBuilder};
+use crate::encoded_vectors::{
+    DistanceType, EncodedVectors, VectorParameters, validate_vector_parameters,
+};
+use crate::encoded_vectors_pq::{
+    CENTROIDS_COUNT, KMEANS_ACCURACY, KMEANS_MAX_ITERATIONS, KMEANS_SAMPLE_SIZE,
+};
+use crate::kmeans::kmeans;
+
+/// Count of vectors, encoded in parallel at once
+const ENCODING_BATCH_SIZE: usize = 1024;
+
+/// Each encoded vector starts with squared norm of its reconstruction, as `f32`
+const HEADER_SIZE: usize = size_of::<f32>();
+
+/// Residual quantization.
+///
+/// Like product quantization, vectors are divided into chunks, and each chunk is replaced by
+/// an index of the nearest centroid. But encoding is done in several stages: each next stage
+/// has its own centroids, which approximate the residual error left by previous stages.
+/// A chunk is reconstructed as a sum of centroids of all stages.
+pub struct EncodedVectorsRQ<TStorage: EncodedStorage> {
+    encoded_vectors: TStorage,
+    metadata: Metadata,
+    metadata_path: Option<PathBuf>,
+}
+
+/// RQ lookup table
+/// Lookup table is a dot product of each query chunk with each centroid of each stage
+/// related to this chunk
+pub struct EncodedQueryRQ {
+    lut: Vec<f32>,
+    query: Vec<f32>,
+    sqr_norm: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Metadata {
+    /// Centroid positions of each stage (flattened by chunks; for similarity to vector data format)
+    pub codebooks: Vec<Vec<Vec<f32>>>,
+    pub vector_division: Vec<Range<usize>>,
+    pub vector_parameters: VectorParameters,
+}
+
+impl<TStorage: EncodedStorage> EncodedVectorsRQ<TStorage> {
+    pub fn storage(&self) -> &TStorage {
+        &self.encoded_vectors
+    }
+
+    /// Encode vector data using residual quantization.
+    ///
+    /// # Arguments
+    /// * `data` - iterator over original vector data
+    /// * `storage_builder` - encoding result storage builder
+    /// * `vector_parameters` - parameters of original vector data (dimension, distance, etc)
+    /// * `chunk_size` - Max size of f32 chunk that replaced by centroid indexes (in original vector dimension)
+    /// * `stages` - Count of quantization stages, each stage takes one byte per chunk
+    /// * `max_threads` - Max allowed threads for kmeans and encoding process
+    /// * `stopped` - Atomic bool that indicates if encoding should be stopped
+    #[allow(clippy::too_many_arguments)]
+    pub fn encode<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone + Send,
+        mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage> + Send,
+        vector_parameters: &VectorParameters,
+        count: usize,
+        chunk_size: usize,
+        stages: usize,
+        max_threads: usize,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        debug_assert!(validate_vector_parameters(data.clone(), vector_parameters).is_ok());
+
+        if stages == 0 {
+            return Err(EncodingError::ArgumentsError(
+                "Residual quantization requires at least one stage".to_string(),
+            ));
+        }
+
+        let vector_division = Self::get_vector_division(vector_parameters.dim, chunk_size);
+
+        let codebooks = Self::find_codebooks(
+            data.clone(),
+            &vector_division,
+            count,
+            stages,
+            max_threads,
+            stopped,
+        )?;
+
+        Self::encode_storage(
+            data,
+            &mut storage_builder,
+            &vector_division,
+            &codebooks,
+            max_threads,
+            stopped,
+        )?;
+
+        let encoded_vectors = storage_builder
+            .build()
+            .map_err(|e| EncodingError::EncodingError(format!("Failed to build storage: {e}",)))?;
+
+        let metadata = Metadata {
+            codebooks,
+            vector_division,
+            vector_parameters: vector_parameters.clone(),
+        };
+        if let Some(meta_path) = meta_path {
+            meta_path
+                .parent()
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "Path must have a parent directory",
+                    )
+                })
+                .and_then(fs::create_dir_all)
+                .map_err(|e| {
+                    EncodingError::EncodingError(format!(
+                        "Failed to create metadata directory: {e}",
+                    ))
+                })?;
+            atomic_save_json(meta_path, &metadata).map_err(|e| {
+                EncodingError::EncodingError(format!("Failed to save metadata: {e}",))
+            })?;
+        }
+
+        if !stopped.load(Ordering::Relaxed) {
+            Ok(Self {
+                encoded_vectors,
+                metadata,
+                metadata_path: meta_path.map(PathBuf::from),
+            })
+        } else {
+            Err(EncodingError::Stopped)
+        }
+    }
+
+    pub fn load(encoded_vectors: TStorage, meta_path: &Path) -> std::io::Result<Self> {
+        let contents = fs::read_to_string(meta_path)?;
+        let metadata: Metadata = serde_json::from_str(&contents)?;
+        let result = Self {
+            encoded_vectors,
+            metadata,
+            metadata_path: Some(meta_path.to_path_buf()),
+        };
+        Ok(result)
+    }
+
+    pub fn get_quantized_vector_size(
+        vector_parameters: &VectorParameters,
+        chunk_size: usize,
+        stages: usize,
+    ) -> usize {
+        HEADER_SIZE + (0..vector_parameters.dim).step_by(chunk_size).count() * stages
+    }
+
+    fn get_vector_division(dim: usize, chunk_size: usize) -> Vec<Range<usize>> {
+        (0..dim)
+            .step_by(chunk_size)
+            .map(|i| i..std::cmp::min(i + chunk_size, dim))
+            .collect()
+    }
+
+    /// Find centroids of all stages.
+    ///
+    /// Centroids of the first stage are found on a random sample of vectors, centroids of each
+    /// next stage are found on residuals of the sample, left after subtracting nearest centroids
+    /// of previous stages.
+    fn find_codebooks<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        vector_division: &[Range<usize>],
+        count: usize,
+        stages: usize,
+        max_kmeans_threads: usize,
+        stopped: &AtomicBool,
+    ) -> Result<Vec<Vec<Vec<f32>>>, EncodingError> {
+        let sample_size = KMEANS_SAMPLE_SIZE.min(count);
+
+        // find random subset of data as random non-intersected indexes
+        let permutor = permutation_iterator::Permutor::new(count as u64);
+        let mut selected_vectors: Vec<usize> =
+            permutor.map(|i| i as usize).take(sample_size).collect();
+        selected_vectors.sort_unstable();
+
+        let mut residuals: Vec<Vec<f32>> = Vec::with_capacity(sample_size);
+        if sample_size > 0 {
+            for (vector_index, vector_data) in data.enumerate() {
+                if vector_index == selected_vectors[residuals.len()] {
+                    residuals.push(vector_data.as_ref().to_vec());
+                    if residuals.len() == sample_size {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut codebooks = Vec::with_capacity(stages);
+        for _ in 0..stages {
+            if stopped.load(Ordering::Relaxed) {
+                return Err(EncodingError::Stopped);
+            }
+
+            let mut centroids = vec![vec![]; CENTROIDS_COUNT];
+            for range in vector_division {
+                let data_subset: Vec<f32> = residuals
+                    .iter()
+                    .flat_map(|residual| residual[range.clone()].iter().copied())
+                    .collect();
+
+                let chunk_centroids = if residuals.len() <= CENTROIDS_COUNT {
+                    // if there are not enough vectors, set centroids as residual positions
+                    // and fill empty centroids just with zeros
+                    let mut chunk_centroids = data_subset;
+                    chunk_centroids.resize(CENTROIDS_COUNT * range.len(), 0.0);
+                    chunk_centroids
+                } else {
+                    kmeans(
+                        &data_subset,
+                        CENTROIDS_COUNT,
+                        range.len(),
+                        KMEANS_MAX_ITERATIONS,
+                        max_kmeans_threads.max(1),
+                        KMEANS_ACCURACY,
+                        stopped,
+                    )?
+                };
+
+                for (centroid, centroid_data) in centroids
+                    .iter_mut()
+                    .zip(chunk_centroids.chunks_exact(range.len()))
+                {
+                    centroid.extend_from_slice(centroid_data);
+                }
+            }
+
+            // next stage approximates what is left after this one
+            for residual in &mut residuals {
+                for range in vector_division {
+                    Self::subtract_nearest_centroid(residual, range, &centroids);
+                }
+            }
+
+            codebooks.push(centroids);
+        }
+
+        Ok(codebooks)
+    }
+
+    /// Encode whole storage
+    ///
+    /// Vectors are encoded in parallel by batches, encoded batches are pushed to storage in order.
+    fn encode_storage<'a>(
+        data: impl Iterator<Item = impl AsRef<[f32]> + 'a>,
+        storage_builder: &mut impl EncodedStorageBuilder<Storage = TStorage>,
+        vector_division: &[Range<usize>],
+        codebooks: &[Vec<Vec<f32>>],
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> Result<(), EncodingError> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("rq-encoding-{idx}"))
+            .num_threads(std::cmp::max(1, max_threads))
+            .build()
+            .map_err(|e| {
+                EncodingError::EncodingError(format!(
+                    "Failed RQ encoding while thread pool init: {e}"
+                ))
+            })?;
+
+        let mut encode_batch = |batch: &mut Vec<Vec<f32>>| {
+            if stopped.load(Ordering::Relaxed) {
+                return Err(EncodingError::Stopped);
+            }
+            let encoded_batch: Vec<Vec<u8>> = pool.install(|| {
+                batch
+                    .par_iter()
+                    .map(|vector| Self::encode_vector(vector, vector_division, codebooks))
+                    .collect()
+            });
+            for encoded_vector in encoded_batch {
+                storage_builder
+                    .push_vector_data(&encoded_vector)
+                    .map_err(|e| {
+                        EncodingError::EncodingError(format!("Failed to push encoded vector: {e}",))
+                    })?;
+            }
+            batch.clear();
+            Ok(())
+        };
+
+        let mut batch = Vec::with_capacity(ENCODING_BATCH_SIZE);
+        for vector in data {
+            batch.push(vector.as_ref().to_vec());
+            if batch.len() == ENCODING_BATCH_SIZE {
+                encode_batch(&mut batch)?;
+            }
+        }
+        encode_batch(&mut batch)
+    }
+
+    /// Encode single vector from `&[f32]` into `Vec<u8>`.
+    ///
+    /// For each chunk, nearest centroid of each stage is found for the residual left by
+    /// previous stages. Encoded vector is the squared norm of the reconstructed vector,
+    /// followed by centroid indexes of all stages of the first chunk, then the second one, etc.
+    fn encode_vector(
+        vector_data: &[f32],
+        vector_division: &[Range<usize>],
+        codebooks: &[Vec<Vec<f32>>],
+    ) -> Vec<u8> {
+        let mut encoded_vector = vec![0; HEADER_SIZE + vector_division.len() * codebooks.len()];
+        let mut residual = vector_data.to_vec();
+        let codes = &mut encoded_vector[HEADER_SIZE..];
+        for (range, chunk_codes) in vector_division
+            .iter()
+            .zip(codes.chunks_exact_mut(codebooks.len()))
+        {
+            for (code, centroids) in chunk_codes.iter_mut().zip(codebooks) {
+                *code = Self::subtract_nearest_centroid(&mut residual, range, centroids) as u8;
+            }
+        }
+
+        // reconstructed vector is what was subtracted from the original one
+        let sqr_norm: f32 = vector_data
+            .iter()
+            .zip(&residual)
+            .map(|(v, r)| (v - r).powi(2))
+            .sum();
+        encoded_vector[..HEADER_SIZE].copy_from_slice(&sqr_norm.to_le_bytes());
+        encoded_vector
+    }
+
+    /// Find centroid nearest to chunk `range` of `residual`, subtract it from the chunk and
+    /// return its index.
+    fn subtract_nearest_centroid(
+        residual: &mut [f32],
+        range: &Range<usize>,
+        centroids: &[Vec<f32>],
+    ) -> usize {
+        let subvector = &mut residual[range.clone()];
+        let mut min_distance = f32::MAX;
+        let mut min_centroid_index = 0;
+        for (centroid_index, centroid) in centroids.iter().enumerate() {
+            // as in product quantization, use euclid metric for any similarity function
+            let distance = subvector
+                .iter()
+                .zip(&centroid[range.clone()])
+                .map(|(a, b)| (a - b).powi(2))
+                .sum();
+            if distance < min_distance {
+                min_distance = distance;
+                min_centroid_index = centroid_index;
+            }
+        }
+        for (r, c) in subvector
+            .iter_mut()
+            .zip(&centroids[min_centroid_index][range.clone()])
+        {
+            *r -= c;
+        }
+        min_centroid_index
+    }
+
+    pub fn get_quantized_vector(&self, i: PointOffsetType) -> Cow<'_, [u8]> {
+        self.encoded_vectors.get_vector_data(i)
+    }
+
+    /// Centroid indices of vector `i`, stages of each chunk go one after another
+    pub fn get_quantized_codes(&self, i: PointOffsetType) -> Vec<u8> {
+        self.encoded_vectors.get_vector_data(i)[HEADER_SIZE..].to_vec()
+    }
+
+    /// Reconstruct approximate original vector as a sum of centroids of all stages
+    pub fn decode_vector(&self, i: PointOffsetType) -> Vec<f32> {
+        let encoded_vector = self.encoded_vectors.get_vector_data(i);
+        self.decode_codes(&encoded_vector[HEADER_SIZE..])
+    }
+
+    fn decode_codes(&self, codes: &[u8]) -> Vec<f32> {
+        let codebooks = &self.metadata.codebooks;
+        let mut vector = vec![0.0; self.metadata.vector_parameters.dim];
+        for (range, chunk_codes) in self
+            .metadata
+            .vector_division
+            .iter()
+            .zip(codes.chunks_exact(codebooks.len()))
+        {
+            for (&code, centroids) in chunk_codes.iter().zip(codebooks) {
+                for (v, c) in vector[range.clone()]
+                    .iter_mut()
+                    .zip(&centroids[code as usize][range.clone()])
+                {
+                    *v += c;
+                }
+            }
+        }
+        vector
+    }
+
+    /// Dot product of the query with the reconstructed vector, using the lookup table
+    fn lut_dot(query: &EncodedQueryRQ, codes: &[u8]) -> f32 {
+        codes
+            .iter()
+            .enumerate()
+            .map(|(i, &code)| query.lut[i * CENTROIDS_COUNT + code as usize])
+            .sum()
+    }
+
+    pub fn layout(&self) -> Layout {
+        Layout::from_size_align(self.quantized_vector_size(), align_of::<u8>()).unwrap()
+    }
+
+    pub fn get_metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
+impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsRQ<TStorage> {
+    type EncodedQuery = EncodedQueryRQ;
+
+    fn is_on_disk(&self) -> bool {
+        self.encoded_vectors.is_on_disk()
+    }
+
+    fn encode_query(&self, query: &[f32]) -> EncodedQueryRQ {
+        let codebooks = &self.metadata.codebooks;
+        // Manhattan distance can't be split into per-centroid parts, it's scored on decoded vectors
+        let lut = match self.metadata.vector_parameters.distance_type {
+            DistanceType::Dot | DistanceType::L2 => {
+                let mut lut = Vec::with_capacity(
+                    self.metadata.vector_division.len() * codebooks.len() * CENTROIDS_COUNT,
+                );
+                for range in &self.metadata.vector_division {
+                    let subquery = &query[range.clone()];
+                    for centroids in codebooks {
+                        for centroid in centroids {
+                            lut.push(
+                                DistanceType::Dot.distance(subquery, &centroid[range.clone()]),
+                            );
+                        }
+                    }
+                }
+                lut
+            }
+            DistanceType::L1 => vec![],
+        };
+        EncodedQueryRQ {
+            lut,
+            query: query.to_vec(),
+            sqr_norm: query.iter().map(|x| x * x).sum(),
+        }
+    }
+
+    fn score_point(
+        &self,
+        query: &EncodedQueryRQ,
+        i: PointOffsetType,
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        let encoded_vector = self.encoded_vectors.get_vector_data(i);
+
+        self.score_bytes(True, query, &encoded_vector, hw_counter)
+    }
+
+    /// Score two points inside encoded data by their indexes
+    /// To find score, this method decodes both encoded vectors.
+    fn score_internal(
+        &self,
+        i: PointOffsetType,
+        j: PointOffsetType,
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        hw_counter
+            .vector_io_read()
+            .incr_delta(self.quantized_vector_size() * 2);
+        hw_counter
+            .cpu_counter()
+            .incr_delta(self.metadata.vector_parameters.dim * self.metadata.codebooks.len() * 2);
+
+        let vector_i = self.decode_vector(i);
+        let vector_j = self.decode_vector(j);
+        let distance = self
+            .metadata
+            .vector_parameters
+            .distance_type
+            .distance(&vector_i, &vector_j);
+        if self.metadata.vector_parameters.invert {
+            -distance
+        } else {
+            distance
+        }
+    }
+
+    fn quantized_vector_size(&self) -> usize {
+        HEADER_SIZE + self.metadata.vector_division.len() * self.metadata.codebooks.len()
+    }
+
+    fn encode_internal_vector(&self, id: PointOffsetType) -> Option<EncodedQueryRQ> {
+        // Decoded vector is scored as accurately, as it is scored by `score_internal`
+        Some(self.encode_query(&self.decode_vector(id)))
+    }
+
+    fn upsert_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: &[f32],
+        _hw_counter: &HardwareCounterCell,
+    ) -> std::io::Result<()> {
+        debug_assert!(false, "RQ does not support upsert_vector",);
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "RQ does not support upsert_vector",
+        ))
+    }
+
+    fn vectors_count(&self) -> usize {
+        self.encoded_vectors.vectors_count()
+    }
+
+    fn flusher(&self) -> MmapFlusher {
+        self.encoded_vectors.flusher()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.encoded_vectors.files();
+        if let Some(meta_path) = &self.metadata_path {
+            files.push(meta_path.clone());
+        }
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = self.encoded_vectors.immutable_files();
+        if let Some(meta_path) = &self.metadata_path {
+            files.push(meta_path.clone());
+        }
+        files
+    }
+
+    type SupportsBytes = True;
+    fn score_bytes(
+        &self,
+        _: Self::SupportsBytes,
+        query: &Self::EncodedQuery,
+        bytes: &[u8],
+        hw_counter: &HardwareCounterCell,
+    ) -> f32 {
+        let (header, codes) = bytes.split_at(HEADER_SIZE);
+        let vector_parameters = &self.metadata.vector_parameters;
+
+        let distance = match vector_parameters.distance_type {
+            DistanceType::Dot => {
+                hw_counter.cpu_counter().incr_delta(codes.len());
+                Self::lut_dot(query, codes)
+            }
+            DistanceType::L2 => {
+                hw_counter.cpu_counter().incr_delta(codes.len());
+                // |q - x|^2 = |q|^2 + |x|^2 - 2 * <q, x>
+                let sqr_norm = f32::from_le_bytes(header.try_into().unwrap());
+                query.sqr_norm + sqr_norm - 2.0 * Self::lut_dot(query, codes)
+            }
+            DistanceType::L1 => {
+                hw_counter
+                    .cpu_counter()
+                    .incr_delta(vector_parameters.dim * self.metadata.codebooks.len());
+                DistanceType::L1.distance(&query.query, &self.decode_codes(codes))
+            }
+        };
+
+        if vector_parameters.invert {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
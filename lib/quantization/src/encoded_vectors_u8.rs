@@ -21,11 +21,19 @@ use crate::quantile::{find_min_max_from_iter, find_quantile_interval};
 pub const ALIGNMENT: usize = 16;
 // Each encoded vector stores an additional f32 at the beginning. Define it's size here.
 const ADDITIONAL_CONSTANT_SIZE: usize = std::mem::size_of::<f32>();
-
-#[derive(Clone, PartialEq, Debug)]
+// Int4 packs two components per byte, keep packed data aligned the same way as int8 one.
+const INT4_ALIGNMENT: usize = 2 * ALIGNMENT;
+// Each int4 encoded vector starts with its own alpha, offset, sum of codes and squared norm.
+const INT4_ADDITIONAL_CONSTANT_SIZE: usize = 4 * std::mem::size_of::<f32>();
+// Number of levels of 4-bit vector codes and of 7-bit int4 query codes.
+const INT4_VECTOR_LEVELS: f32 = 15.0;
+const INT4_QUERY_LEVELS: f32 = 127.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum ScalarQuantizationMethod {
     Int8,
-    // Future methods can be added here
+    /// Two components per byte, each vector quantized within its own min/max range
+    Int4,
 }
 
 pub struct EncodedVectorsU8<TStorage: EncodedStorage> {
@@ -34,48 +42,50 @@ pub struct EncodedVectorsU8<TStorage: EncodedStorage> {
     metadata_path: Option<PathBuf>,
 }
 
-pub struct EncodedQueryU8 {
+pub enum EncodedQueryU8 {
+    Int8(EncodedQueryInt8),
+    Int4(EncodedQueryInt4),
+}
+
+pub struct EncodedQueryInt8 {
     offset: f32,
     encoded_query: Vec<u8>,
 }
 
+/// Query for int4 vectors, quantized to 7 bits to keep precision of the query side.
+///
+/// Codes are grouped per 16 bytes of packed vector data: first codes of the low nibbles,
+/// then codes of the high nibbles.
+pub struct EncodedQueryInt4 {
+    alpha: f32,
+    offset: f32,
+    /// Sum of query components
+    sum: f32,
+    /// Squared norm of query
+    sqr_norm: f32,
+    encoded_query: Vec<u8>,
+}
+
+// Int8 goes first: untagged int8 metadata would be deserialized as int4 one otherwise.
 #[derive(Serialize, Deserialize)]
 #[serde(untagged)]
 enum Metadata {
     Int8(MetadataInt8),
+    Int4(MetadataInt4),
 }
 
 impl Metadata {
     pub fn vector_parameters(&self) -> &VectorParameters {
         match self {
             Metadata::Int8(meta) => &meta.vector_parameters,
+            Metadata::Int4(meta) => &meta.vector_parameters,
         }
     }
 
-    pub fn actual_dim(&self) -> usize {
-        match self {
-            Metadata::Int8(meta) => meta.actual_dim,
-        }
-    }
-
-    pub fn postprocess_score(&self, score: f32, query_offset: f32, vector_offset: f32) -> f32 {
-        match self {
-            Metadata::Int8(metadata) => {
-                metadata.postprocess_score(score, query_offset, vector_offset)
-            }
-        }
-    }
-
-    pub fn postprocess_internal_score(
-        &self,
-        score: f32,
-        query_offset: f32,
-        vector_offset: f32,
-    ) -> f32 {
+    pub fn method(&self) -> ScalarQuantizationMethod {
         match self {
-            Metadata::Int8(metadata) => {
-                metadata.postprocess_internal_score(score, query_offset, vector_offset)
-            }
+            Metadata::Int8(_) => ScalarQuantizationMethod::Int8,
+            Metadata::Int4(_) => ScalarQuantizationMethod::Int4,
         }
     }
 }
@@ -131,6 +141,232 @@ impl MetadataInt8 {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct MetadataInt4 {
+    actual_dim: usize,
+    vector_parameters: VectorParameters,
+}
+
+/// Constants stored in front of 4-bit codes of each vector.
+///
+/// Component `i` is restored as `offset + alpha * code_i`.
+struct VectorHeaderInt4 {
+    alpha: f32,
+    offset: f32,
+    codes_sum: f32,
+    sqr_norm: f32,
+}
+
+impl VectorHeaderInt4 {
+    fn to_bytes(&self) -> [u8; INT4_ADDITIONAL_CONSTANT_SIZE] {
+        let mut bytes = [0; INT4_ADDITIONAL_CONSTANT_SIZE];
+        for (chunk, value) in bytes.chunks_exact_mut(size_of::<f32>()).zip([
+            self.alpha,
+            self.offset,
+            self.codes_sum,
+            self.sqr_norm,
+        ]) {
+            chunk.copy_from_slice(&value.to_ne_bytes());
+        }
+        bytes
+    }
+
+    #[inline]
+    fn parse_vec_data(data: &[u8]) -> (Self, *const u8) {
+        debug_assert!(data.len() >= INT4_ADDITIONAL_CONSTANT_SIZE);
+        unsafe {
+            let header_ptr = data.as_ptr().cast::<f32>();
+            let header = Self {
+                alpha: header_ptr.read_unaligned(),
+                offset: header_ptr.add(1).read_unaligned(),
+                codes_sum: header_ptr.add(2).read_unaligned(),
+                sqr_norm: header_ptr.add(3).read_unaligned(),
+            };
+            (header, data.as_ptr().add(INT4_ADDITIONAL_CONSTANT_SIZE))
+        }
+    }
+}
+
+impl MetadataInt4 {
+    fn encode_vector(&self, vector: &[f32]) -> Vec<u8> {
+        let (alpha, offset) = alpha_offset_from_values(vector, INT4_VECTOR_LEVELS);
+
+        let mut packed = vec![0u8; self.actual_dim / 2];
+        let mut codes_sum = 0.0;
+        let mut sqr_norm = 0.0;
+        for (i, &value) in vector.iter().enumerate() {
+            let code = encode_value(value, alpha, offset, INT4_VECTOR_LEVELS);
+            let decoded = offset + alpha * f32::from(code);
+            codes_sum += f32::from(code);
+            sqr_norm += decoded * decoded;
+            // Padding keeps zero codes, so it doesn't contribute to scores
+            packed[i / 2] |= code << (4 * (i % 2));
+        }
+
+        let header = VectorHeaderInt4 {
+            alpha,
+            offset,
+            codes_sum,
+            sqr_norm,
+        };
+        let mut encoded_vector = Vec::with_capacity(INT4_ADDITIONAL_CONSTANT_SIZE + packed.len());
+        encoded_vector.extend_from_slice(&header.to_bytes());
+        encoded_vector.extend_from_slice(&packed);
+        encoded_vector
+    }
+
+    fn encode_query(&self, query: &[f32]) -> EncodedQueryInt4 {
+        let (alpha, offset) = alpha_offset_from_values(query, INT4_QUERY_LEVELS);
+        let mut encoded_query = vec![0u8; self.actual_dim];
+        for (i, &value) in query.iter().enumerate() {
+            encoded_query[int4_query_position(i)] =
+                encode_value(value, alpha, offset, INT4_QUERY_LEVELS);
+        }
+        EncodedQueryInt4 {
+            alpha,
+            offset,
+            sum: query.iter().sum(),
+            sqr_norm: query.iter().map(|&x| x * x).sum(),
+            encoded_query,
+        }
+    }
+
+    /// Build query from an encoded vector without any precision loss
+    fn encode_internal_vector(&self, data: &[u8]) -> EncodedQueryInt4 {
+        // 4-bit codes scaled by 8 still fit into 7-bit query codes
+        const SCALE: u8 = 8;
+
+        let (header, v_ptr) = VectorHeaderInt4::parse_vec_data(data);
+        let mut encoded_query = vec![0u8; self.actual_dim];
+        for i in 0..self.vector_parameters.dim {
+            encoded_query[int4_query_position(i)] = int4_code(v_ptr, i) * SCALE;
+        }
+        EncodedQueryInt4 {
+            alpha: header.alpha / f32::from(SCALE),
+            offset: header.offset,
+            sum: self.vector_parameters.dim as f32 * header.offset
+                + header.alpha * header.codes_sum,
+            sqr_norm: header.sqr_norm,
+            encoded_query,
+        }
+    }
+
+    fn decode_vector(&self, data: &[u8]) -> Vec<f32> {
+        let (header, v_ptr) = VectorHeaderInt4::parse_vec_data(data);
+        (0..self.vector_parameters.dim)
+            .map(|i| header.offset + header.alpha * f32::from(int4_code(v_ptr, i)))
+            .collect()
+    }
+
+    /// Score query against encoded vector, `score_dot` sums products of query and vector codes
+    #[inline]
+    fn score_query(
+        &self,
+        query: &EncodedQueryInt4,
+        data: &[u8],
+        score_dot: impl FnOnce(*const u8, *const u8, usize) -> f32,
+    ) -> f32 {
+        let (header, v_ptr) = VectorHeaderInt4::parse_vec_data(data);
+        let q_ptr = query.encoded_query.as_ptr();
+
+        // (offset_v + alpha_v * v) * q = offset_v * q + alpha_v * (offset_q * v + alpha_q * v * q)
+        let dot = || {
+            let codes_dot = score_dot(q_ptr, v_ptr, self.actual_dim);
+            header.offset * query.sum
+                + header.alpha * (query.offset * header.codes_sum + query.alpha * codes_dot)
+        };
+        let score = match self.vector_parameters.distance_type {
+            DistanceType::Dot => dot(),
+            DistanceType::L2 => header.sqr_norm + query.sqr_norm - 2.0 * dot(),
+            DistanceType::L1 => (0..self.vector_parameters.dim)
+                .map(|i| {
+                    let vector_value =
+                        header.offset + header.alpha * f32::from(int4_code(v_ptr, i));
+                    let query_value = query.offset
+                        + query.alpha * f32::from(query.encoded_query[int4_query_position(i)]);
+                    (vector_value - query_value).abs()
+                })
+                .sum(),
+        };
+        self.postprocess_score(score)
+    }
+
+    fn score_internal(&self, data_1: &[u8], data_2: &[u8]) -> f32 {
+        let (header_1, ptr_1) = VectorHeaderInt4::parse_vec_data(data_1);
+        let (header_2, ptr_2) = VectorHeaderInt4::parse_vec_data(data_2);
+
+        let dot = || {
+            let codes_dot = impl_score_dot_int4_internal(ptr_1, ptr_2, self.actual_dim / 2) as f32;
+            self.vector_parameters.dim as f32 * header_1.offset * header_2.offset
+                + header_1.offset * header_2.alpha * header_2.codes_sum
+                + header_2.offset * header_1.alpha * header_1.codes_sum
+                + header_1.alpha * header_2.alpha * codes_dot
+        };
+        let score = match self.vector_parameters.distance_type {
+            DistanceType::Dot => dot(),
+            DistanceType::L2 => header_1.sqr_norm + header_2.sqr_norm - 2.0 * dot(),
+            DistanceType::L1 => (0..self.vector_parameters.dim)
+                .map(|i| {
+                    let value_1 = header_1.offset + header_1.alpha * f32::from(int4_code(ptr_1, i));
+                    let value_2 = header_2.offset + header_2.alpha * f32::from(int4_code(ptr_2, i));
+                    (value_1 - value_2).abs()
+                })
+                .sum(),
+        };
+        self.postprocess_score(score)
+    }
+
+    #[inline]
+    fn postprocess_score(&self, score: f32) -> f32 {
+        if self.vector_parameters.invert {
+            -score
+        } else {
+            score
+        }
+    }
+}
+
+/// Find quantization step and offset to fit `values` into `levels` steps
+fn alpha_offset_from_values(values: &[f32], levels: f32) -> (f32, f32) {
+    let (min, max) = find_min_max_from_iter(std::iter::once(values));
+    if max > min {
+        ((max - min) / levels, min)
+    } else if min <= max {
+        // All values are equal
+        (0.0, min)
+    } else {
+        // No values
+        (0.0, 0.0)
+    }
+}
+
+#[inline]
+fn encode_value(value: f32, alpha: f32, offset: f32, levels: f32) -> u8 {
+    if alpha == 0.0 {
+        return 0;
+    }
+    ((value - offset) / alpha).clamp(0.0, levels).round() as u8
+}
+
+/// Code of `i`-th component from packed int4 vector data
+#[inline]
+fn int4_code(v_ptr: *const u8, i: usize) -> u8 {
+    let byte = unsafe { *v_ptr.add(i / 2) };
+    (byte >> (4 * (i % 2))) & 0x0F
+}
+
+/// Position of `i`-th component in encoded int4 query.
+///
+/// Each 16 bytes of packed vector hold 32 components: low nibbles are even components,
+/// high nibbles are odd ones. Query stores codes for low nibbles first to be multiplied
+/// by unpacked nibbles directly.
+#[inline]
+fn int4_query_position(i: usize) -> usize {
+    let block_start = i - i % INT4_ALIGNMENT;
+    let in_block = i % INT4_ALIGNMENT;
+    block_start + (in_block % 2) * ALIGNMENT + in_block / 2
+}
+
 impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
     pub fn storage(&self) -> &TStorage {
         &self.encoded_vectors
@@ -147,8 +383,36 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         meta_path: Option<&Path>,
         stopped: &AtomicBool,
     ) -> Result<Self, EncodingError> {
-        assert_eq!(method, ScalarQuantizationMethod::Int8);
-        let actual_dim = Self::get_actual_dim(vector_parameters);
+        match method {
+            ScalarQuantizationMethod::Int8 => Self::encode_int8(
+                orig_data,
+                storage_builder,
+                vector_parameters,
+                count,
+                quantile,
+                meta_path,
+                stopped,
+            ),
+            ScalarQuantizationMethod::Int4 => Self::encode_int4(
+                orig_data,
+                storage_builder,
+                vector_parameters,
+                meta_path,
+                stopped,
+            ),
+        }
+    }
+
+    fn encode_int8<'a>(
+        orig_data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage>,
+        vector_parameters: &VectorParameters,
+        count: usize,
+        quantile: Option<f32>,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        let actual_dim = Self::get_actual_dim(vector_parameters, ScalarQuantizationMethod::Int8);
 
         if count == 0 {
             let metadata = Metadata::Int8(MetadataInt8 {
@@ -158,25 +422,7 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                 multiplier: 0.0,
                 vector_parameters: vector_parameters.clone(),
             });
-            if let Some(meta_path) = meta_path {
-                meta_path
-                    .parent()
-                    .ok_or_else(|| {
-                        std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            "Path must have a parent directory",
-                        )
-                    })
-                    .and_then(fs::create_dir_all)
-                    .map_err(|e| {
-                        EncodingError::EncodingError(format!(
-                            "Failed to create metadata directory: {e}",
-                        ))
-                    })?;
-                atomic_save_json(meta_path, &metadata).map_err(|e| {
-                    EncodingError::EncodingError(format!("Failed to save metadata: {e}",))
-                })?;
-            }
+            Self::save_metadata(meta_path, &metadata)?;
             return Ok(EncodedVectorsU8 {
                 encoded_vectors: storage_builder.build().map_err(|e| {
                     EncodingError::EncodingError(format!("Failed to build storage: {e}",))
@@ -285,26 +531,51 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
             .map_err(|e| EncodingError::EncodingError(format!("Failed to build storage: {e}",)))?;
 
         let metadata = Metadata::Int8(metadata);
-        if let Some(meta_path) = meta_path {
-            meta_path
-                .parent()
-                .ok_or_else(|| {
-                    std::io::Error::new(
-                        std::io::ErrorKind::InvalidInput,
-                        "Path must have a parent directory",
-                    )
-                })
-                .and_then(fs::create_dir_all)
+        Self::save_metadata(meta_path, &metadata)?;
+
+        Ok(EncodedVectorsU8 {
+            encoded_vectors,
+            metadata,
+            metadata_path: meta_path.map(PathBuf::from),
+        })
+    }
+
+    /// Encode vectors with 4 bits per component.
+    ///
+    /// Every vector is quantized within its own min/max range, so no quantile is used.
+    fn encode_int4<'a>(
+        orig_data: impl Iterator<Item = impl AsRef<[f32]> + 'a> + Clone,
+        mut storage_builder: impl EncodedStorageBuilder<Storage = TStorage>,
+        vector_parameters: &VectorParameters,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<Self, EncodingError> {
+        debug_assert!(validate_vector_parameters(orig_data.clone(), vector_parameters).is_ok());
+        let metadata = MetadataInt4 {
+            actual_dim: Self::get_actual_dim(vector_parameters, ScalarQuantizationMethod::Int4),
+            vector_parameters: vector_parameters.clone(),
+        };
+
+        for vector in orig_data {
+            if stopped.load(Ordering::Relaxed) {
+                return Err(EncodingError::Stopped);
+            }
+
+            let encoded_vector = metadata.encode_vector(vector.as_ref());
+            storage_builder
+                .push_vector_data(&encoded_vector)
                 .map_err(|e| {
-                    EncodingError::EncodingError(format!(
-                        "Failed to create metadata directory: {e}",
-                    ))
+                    EncodingError::EncodingError(format!("Failed to push encoded vector: {e}",))
                 })?;
-            atomic_save_json(meta_path, &metadata).map_err(|e| {
-                EncodingError::EncodingError(format!("Failed to save metadata: {e}",))
-            })?;
         }
 
+        let encoded_vectors = storage_builder
+            .build()
+            .map_err(|e| EncodingError::EncodingError(format!("Failed to build storage: {e}",)))?;
+
+        let metadata = Metadata::Int4(metadata);
+        Self::save_metadata(meta_path, &metadata)?;
+
         Ok(EncodedVectorsU8 {
             encoded_vectors,
             metadata,
@@ -312,6 +583,26 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         })
     }
 
+    fn save_metadata(meta_path: Option<&Path>, metadata: &Metadata) -> Result<(), EncodingError> {
+        let Some(meta_path) = meta_path else {
+            return Ok(());
+        };
+        meta_path
+            .parent()
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "Path must have a parent directory",
+                )
+            })
+            .and_then(fs::create_dir_all)
+            .map_err(|e| {
+                EncodingError::EncodingError(format!("Failed to create metadata directory: {e}",))
+            })?;
+        atomic_save_json(meta_path, metadata)
+            .map_err(|e| EncodingError::EncodingError(format!("Failed to save metadata: {e}",)))
+    }
+
     pub fn load(encoded_vectors: TStorage, meta_path: &Path) -> std::io::Result<Self> {
         let contents = fs::read_to_string(meta_path)?;
         let metadata: Metadata = serde_json::from_str(&contents)?;
@@ -324,8 +615,8 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
     }
 
     pub fn score_point_simple(&self, query: &EncodedQueryU8, bytes: &[u8]) -> f32 {
-        match &self.metadata {
-            Metadata::Int8(metadata) => {
+        match (&self.metadata, query) {
+            (Metadata::Int8(metadata), EncodedQueryU8::Int8(query)) => {
                 let (vector_offset, v_ptr) = Self::parse_vec_data(bytes);
                 let q_ptr = query.encoded_query.as_ptr();
 
@@ -336,9 +627,14 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                     DistanceType::L1 => impl_score_l1(q_ptr, v_ptr, metadata.actual_dim),
                 };
 
-                self.metadata
-                    .postprocess_score(score as f32, query.offset, vector_offset)
+                metadata.postprocess_score(score as f32, query.offset, vector_offset)
             }
+            (Metadata::Int4(metadata), EncodedQueryU8::Int4(query)) => {
+                metadata.score_query(query, bytes, |q_ptr, v_ptr, dim| {
+                    impl_score_dot_int4(q_ptr, v_ptr, dim) as f32
+                })
+            }
+            _ => unreachable!("query is encoded with another quantization method"),
         }
     }
 
@@ -353,16 +649,19 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                     }
                     DistanceType::L1 => impl_score_l1(q_ptr, v_ptr, metadata.actual_dim),
                 };
-                self.metadata
-                    .postprocess_internal_score(score as f32, query_offset, vector_offset)
+                metadata.postprocess_internal_score(score as f32, query_offset, vector_offset)
             }
+            Metadata::Int4(metadata) => metadata.score_internal(
+                &self.encoded_vectors.get_vector_data(i),
+                &self.encoded_vectors.get_vector_data(j),
+            ),
         }
     }
 
     #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
     pub fn score_point_neon(&self, query: &EncodedQueryU8, bytes: &[u8]) -> f32 {
-        match &self.metadata {
-            Metadata::Int8(metadata) => {
+        match (&self.metadata, query) {
+            (Metadata::Int8(metadata), EncodedQueryU8::Int8(query)) => {
                 let (vector_offset, v_ptr) = Self::parse_vec_data(bytes);
                 let q_ptr = query.encoded_query.as_ptr();
 
@@ -374,9 +673,14 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_neon(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_score(score as f32, query.offset, vector_offset)
+                metadata.postprocess_score(score as f32, query.offset, vector_offset)
             }
+            (Metadata::Int4(metadata), EncodedQueryU8::Int4(query)) => {
+                metadata.score_query(query, bytes, |q_ptr, v_ptr, dim| unsafe {
+                    impl_score_dot_int4_neon(q_ptr, v_ptr, dim as u32)
+                })
+            }
+            _ => unreachable!("query is encoded with another quantization method"),
         }
     }
 
@@ -395,16 +699,19 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_neon(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_internal_score(score as f32, query_offset, vector_offset)
+                metadata.postprocess_internal_score(score as f32, query_offset, vector_offset)
             }
+            Metadata::Int4(metadata) => metadata.score_internal(
+                &self.encoded_vectors.get_vector_data(i),
+                &self.encoded_vectors.get_vector_data(j),
+            ),
         }
     }
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     pub fn score_point_sse(&self, query: &EncodedQueryU8, bytes: &[u8]) -> f32 {
-        match &self.metadata {
-            Metadata::Int8(metadata) => {
+        match (&self.metadata, query) {
+            (Metadata::Int8(metadata), EncodedQueryU8::Int8(query)) => {
                 let (vector_offset, v_ptr) = Self::parse_vec_data(bytes);
                 let q_ptr = query.encoded_query.as_ptr();
 
@@ -416,9 +723,14 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_sse(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_score(score as f32, query.offset, vector_offset)
+                metadata.postprocess_score(score as f32, query.offset, vector_offset)
+            }
+            (Metadata::Int4(metadata), EncodedQueryU8::Int4(query)) => {
+                metadata.score_query(query, bytes, |q_ptr, v_ptr, dim| unsafe {
+                    impl_score_dot_int4_sse(q_ptr, v_ptr, dim as u32)
+                })
             }
+            _ => unreachable!("query is encoded with another quantization method"),
         }
     }
 
@@ -437,16 +749,19 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_sse(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_internal_score(score as f32, query_offset, vector_offset)
+                metadata.postprocess_internal_score(score as f32, query_offset, vector_offset)
             }
+            Metadata::Int4(metadata) => metadata.score_internal(
+                &self.encoded_vectors.get_vector_data(i),
+                &self.encoded_vectors.get_vector_data(j),
+            ),
         }
     }
 
     #[cfg(target_arch = "x86_64")]
     pub fn score_point_avx(&self, query: &EncodedQueryU8, bytes: &[u8]) -> f32 {
-        match &self.metadata {
-            Metadata::Int8(metadata) => {
+        match (&self.metadata, query) {
+            (Metadata::Int8(metadata), EncodedQueryU8::Int8(query)) => {
                 let (vector_offset, v_ptr) = Self::parse_vec_data(bytes);
                 let q_ptr = query.encoded_query.as_ptr();
 
@@ -458,9 +773,14 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_avx(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_score(score as f32, query.offset, vector_offset)
+                metadata.postprocess_score(score as f32, query.offset, vector_offset)
+            }
+            (Metadata::Int4(metadata), EncodedQueryU8::Int4(query)) => {
+                metadata.score_query(query, bytes, |q_ptr, v_ptr, dim| unsafe {
+                    impl_score_dot_int4_avx(q_ptr, v_ptr, dim as u32)
+                })
             }
+            _ => unreachable!("query is encoded with another quantization method"),
         }
     }
 
@@ -479,9 +799,12 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
                         impl_score_l1_avx(q_ptr, v_ptr, metadata.actual_dim as u32)
                     },
                 };
-                self.metadata
-                    .postprocess_internal_score(score as f32, query_offset, vector_offset)
+                metadata.postprocess_internal_score(score as f32, query_offset, vector_offset)
             }
+            Metadata::Int4(metadata) => metadata.score_internal(
+                &self.encoded_vectors.get_vector_data(i),
+                &self.encoded_vectors.get_vector_data(j),
+            ),
         }
     }
 
@@ -522,32 +845,93 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         Layout::from_size_align(self.quantized_vector_size(), align_of::<u8>()).unwrap()
     }
 
+    pub fn method(&self) -> ScalarQuantizationMethod {
+        self.metadata.method()
+    }
+
+    /// Offset and codes of an encoded vector, for int4 the offset is minimum of the vector
     pub fn get_quantized_vector_offset_and_code(&self, i: PointOffsetType) -> (f32, &[u8]) {
-        let (offset, v_ptr) = self.get_vec_ptr(i);
-        let vector_data_size = self.metadata.actual_dim();
+        let (offset, v_ptr, vector_data_size) = match &self.metadata {
+            Metadata::Int8(metadata) => {
+                let (offset, v_ptr) = self.get_vec_ptr(i);
+                (offset, v_ptr, metadata.actual_dim)
+            }
+            Metadata::Int4(metadata) => {
+                let data = self.encoded_vectors.get_vector_data(i);
+                let (header, v_ptr) = VectorHeaderInt4::parse_vec_data(&data);
+                (header.offset, v_ptr, metadata.actual_dim / 2)
+            }
+        };
         let code = unsafe { std::slice::from_raw_parts(v_ptr, vector_data_size) };
         (offset, code)
     }
 
-    pub fn get_quantized_vector_size(vector_parameters: &VectorParameters) -> usize {
-        let actual_dim = Self::get_actual_dim(vector_parameters);
-        actual_dim + ADDITIONAL_CONSTANT_SIZE
+    /// Scale and offset to restore components of vector `i` as `offset + scale * code`.
+    ///
+    /// Int8 vectors share them, int4 vectors are scaled individually.
+    pub fn get_scale_and_offset(&self, i: PointOffsetType) -> (f32, f32) {
+        match &self.metadata {
+            Metadata::Int8(metadata) => (metadata.alpha, metadata.offset),
+            Metadata::Int4(_) => {
+                let data = self.encoded_vectors.get_vector_data(i);
+                let (header, _) = VectorHeaderInt4::parse_vec_data(&data);
+                (header.alpha, header.offset)
+            }
+        }
+    }
+
+    /// Reconstruct approximate original vector from its quantized data
+    pub fn decode_vector(&self, i: PointOffsetType) -> Vec<f32> {
+        match &self.metadata {
+            Metadata::Int8(metadata) => {
+                let data = self.encoded_vectors.get_vector_data(i);
+                // Skip vector offset and alignment padding
+                data[ADDITIONAL_CONSTANT_SIZE..][..metadata.vector_parameters.dim]
+                    .iter()
+                    .map(|&code| f32::from(code) * metadata.alpha + metadata.offset)
+                    .collect()
+            }
+            Metadata::Int4(metadata) => {
+                metadata.decode_vector(&self.encoded_vectors.get_vector_data(i))
+            }
+        }
+    }
+
+    pub fn get_quantized_vector_size(
+        vector_parameters: &VectorParameters,
+        method: ScalarQuantizationMethod,
+    ) -> usize {
+        let actual_dim = Self::get_actual_dim(vector_parameters, method);
+        match method {
+            ScalarQuantizationMethod::Int8 => actual_dim + ADDITIONAL_CONSTANT_SIZE,
+            ScalarQuantizationMethod::Int4 => actual_dim / 2 + INT4_ADDITIONAL_CONSTANT_SIZE,
+        }
     }
 
+    /// Multiplier of raw int8 scores, int4 vectors are scaled individually and have none
     pub fn get_multiplier(&self) -> f32 {
         match &self.metadata {
             Metadata::Int8(meta) => meta.multiplier,
+            Metadata::Int4(_) => 1.0,
         }
     }
 
     pub fn get_shift(&self) -> f32 {
         match &self.metadata {
             Metadata::Int8(metadata) => metadata.get_shift(),
+            Metadata::Int4(_) => 0.0,
         }
     }
 
-    pub fn get_actual_dim(vector_parameters: &VectorParameters) -> usize {
-        vector_parameters.dim + (ALIGNMENT - vector_parameters.dim % ALIGNMENT) % ALIGNMENT
+    pub fn get_actual_dim(
+        vector_parameters: &VectorParameters,
+        method: ScalarQuantizationMethod,
+    ) -> usize {
+        let alignment = match method {
+            ScalarQuantizationMethod::Int8 => ALIGNMENT,
+            ScalarQuantizationMethod::Int4 => INT4_ALIGNMENT,
+        };
+        vector_parameters.dim + (alignment - vector_parameters.dim % alignment) % alignment
     }
 
     fn encode_int8_query(metadata: &MetadataInt8, query: &[f32]) -> EncodedQueryU8 {
@@ -582,10 +966,10 @@ impl<TStorage: EncodedStorage> EncodedVectorsU8<TStorage> {
         } else {
             offset
         };
-        EncodedQueryU8 {
+        EncodedQueryU8::Int8(EncodedQueryInt8 {
             offset,
             encoded_query: query,
-        }
+        })
     }
 }
 
@@ -599,6 +983,7 @@ impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsU8<TStorage> {
     fn encode_query(&self, query: &[f32]) -> EncodedQueryU8 {
         match &self.metadata {
             Metadata::Int8(meta) => Self::encode_int8_query(meta, query),
+            Metadata::Int4(meta) => EncodedQueryU8::Int4(meta.encode_query(query)),
         }
     }
 
@@ -647,9 +1032,7 @@ impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsU8<TStorage> {
     fn quantized_vector_size(&self) -> usize {
         // Actual_dim rounds up vector_dimension to the next multiple of ALIGNMENT.
         // Also add scaling factor to the tally.
-        match &self.metadata {
-            Metadata::Int8(_) => self.metadata.actual_dim() + ADDITIONAL_CONSTANT_SIZE,
-        }
+        Self::get_quantized_vector_size(self.metadata.vector_parameters(), self.metadata.method())
     }
 
     fn encode_internal_vector(&self, id: PointOffsetType) -> Option<EncodedQueryU8> {
@@ -658,13 +1041,16 @@ impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsU8<TStorage> {
                 let (vector_offset, q_ptr) = self.get_vec_ptr(id);
                 // Remove shift from offset because encoded query should not have it, it's contained in vector data only.
                 let query_offset = vector_offset - metadata.get_shift();
-                Some(EncodedQueryU8 {
+                Some(EncodedQueryU8::Int8(EncodedQueryInt8 {
                     offset: query_offset,
                     encoded_query: unsafe {
                         std::slice::from_raw_parts(q_ptr, metadata.actual_dim).to_vec()
                     },
-                })
+                }))
             }
+            Metadata::Int4(metadata) => Some(EncodedQueryU8::Int4(
+                metadata.encode_internal_vector(&self.encoded_vectors.get_vector_data(id)),
+            )),
         }
     }
 
@@ -717,7 +1103,7 @@ impl<TStorage: EncodedStorage> EncodedVectors for EncodedVectorsU8<TStorage> {
             .cpu_counter()
             .incr_delta(self.metadata.vector_parameters().dim);
 
-        debug_assert!(bytes.len() >= ADDITIONAL_CONSTANT_SIZE + self.metadata.actual_dim());
+        debug_assert!(bytes.len() >= self.quantized_vector_size());
 
         #[cfg(target_arch = "x86_64")]
         if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
@@ -758,17 +1144,50 @@ fn impl_score_l1(q_ptr: *const u8, v_ptr: *const u8, actual_dim: usize) -> i32 {
     }
 }
 
+/// Dot product of int4 query codes and packed vector codes, see [`int4_query_position`]
+fn impl_score_dot_int4(q_ptr: *const u8, v_ptr: *const u8, actual_dim: usize) -> i32 {
+    unsafe {
+        let mut score = 0i32;
+        for block in 0..actual_dim / INT4_ALIGNMENT {
+            let q_block = q_ptr.add(block * INT4_ALIGNMENT);
+            let v_block = v_ptr.add(block * ALIGNMENT);
+            for i in 0..ALIGNMENT {
+                let v = *v_block.add(i);
+                score += i32::from(v & 0x0F) * i32::from(*q_block.add(i));
+                score += i32::from(v >> 4) * i32::from(*q_block.add(ALIGNMENT + i));
+            }
+        }
+        score
+    }
+}
+
+/// Dot product of codes of two packed int4 vectors
+fn impl_score_dot_int4_internal(ptr_1: *const u8, ptr_2: *const u8, packed_size: usize) -> i32 {
+    unsafe {
+        let mut score = 0i32;
+        for i in 0..packed_size {
+            let (v_1, v_2) = (*ptr_1.add(i), *ptr_2.add(i));
+            score += i32::from(v_1 & 0x0F) * i32::from(v_2 & 0x0F);
+            score += i32::from(v_1 >> 4) * i32::from(v_2 >> 4);
+        }
+        score
+    }
+}
+
 #[cfg(target_arch = "x86_64")]
 unsafe extern "C" {
     fn impl_score_dot_avx(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
     fn impl_score_l1_avx(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
+    fn impl_score_dot_int4_avx(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
 
     fn impl_score_dot_sse(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
     fn impl_score_l1_sse(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
+    fn impl_score_dot_int4_sse(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
 }
 
 #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
 unsafe extern "C" {
     fn impl_score_dot_neon(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
     fn impl_score_l1_neon(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
+    fn impl_score_dot_int4_neon(query_ptr: *const u8, vector_ptr: *const u8, dim: u32) -> f32;
 }
@@ -1,7 +1,9 @@
+pub mod anisotropic;
 pub mod encoded_storage;
 pub mod encoded_vectors;
 pub mod encoded_vectors_binary;
 pub mod encoded_vectors_pq;
+pub mod encoded_vectors_rq;
 pub mod encoded_vectors_u8;
 pub mod kmeans;
 pub mod p_square;
@@ -14,6 +16,7 @@ use std::sync::{Arc, Condvar, Mutex};
 pub use encoded_storage::{EncodedStorage, EncodedStorageBuilder};
 pub use encoded_vectors::{DistanceType, EncodedVectors, VectorParameters};
 pub use encoded_vectors_pq::{EncodedQueryPQ, EncodedVectorsPQ};
+pub use encoded_vectors_rq::{EncodedQueryRQ, EncodedVectorsRQ};
 pub use encoded_vectors_u8::{EncodedQueryU8, EncodedVectorsU8};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -26,7 +26,10 @@ mod tests {
         let data_path = dir.path().join("data.bin");
         let meta_path = dir.path().join("meta.json");
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                ScalarQuantizationMethod::Int8,
+            );
         let _encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(Some(data_path.as_path()), quantized_vector_size),
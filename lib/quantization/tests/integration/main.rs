@@ -5,6 +5,8 @@ pub mod metrics;
 #[cfg(test)]
 pub mod stop_condition;
 #[cfg(test)]
+pub mod test_anisotropic;
+#[cfg(test)]
 pub mod test_avx2;
 #[cfg(test)]
 pub mod test_binary;
@@ -15,6 +17,8 @@ pub mod test_neon;
 #[cfg(test)]
 pub mod test_pq;
 #[cfg(test)]
+pub mod test_rq;
+#[cfg(test)]
 pub mod test_simple;
 #[cfg(test)]
 pub mod test_sse;
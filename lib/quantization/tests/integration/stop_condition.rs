@@ -30,7 +30,10 @@ mod tests {
         let zero_vector = vec![0.0; vector_dim];
 
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                ScalarQuantizationMethod::Int8,
+            );
         assert_eq!(
             EncodedVectorsU8::encode(
                 (0..vectors_count).map(|_| &zero_vector),
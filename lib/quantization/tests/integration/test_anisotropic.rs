@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use common::counter::hardware_counter::HardwareCounterCell;
+    use quantization::encoded_storage::{TestEncodedStorage, TestEncodedStorageBuilder};
+    use quantization::encoded_vectors::{DistanceType, EncodedVectors, VectorParameters};
+    use quantization::encoded_vectors_pq::EncodedVectorsPQ;
+    use rand::{RngExt, SeedableRng};
+
+    use crate::metrics::dot_similarity;
+
+    const VECTORS_COUNT: usize = 1000;
+    const VECTOR_DIM: usize = 64;
+    const CHUNK_SIZE: usize = 4;
+    const ERROR: f32 = VECTOR_DIM as f32 * 0.05;
+
+    fn random_vectors(count: usize) -> (Vec<Vec<f32>>, Vec<f32>) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let vector_data = (0..count)
+            .map(|_| {
+                (0..VECTOR_DIM)
+                    .map(|_| rng.random_range(-1.0..1.0))
+                    .collect()
+            })
+            .collect();
+        let query = (0..VECTOR_DIM)
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+        (vector_data, query)
+    }
+
+    fn encode(
+        vector_data: &[Vec<f32>],
+        parallel_weight: Option<f32>,
+    ) -> EncodedVectorsPQ<TestEncodedStorage> {
+        let vector_parameters = VectorParameters {
+            dim: VECTOR_DIM,
+            deprecated_count: None,
+            distance_type: DistanceType::Dot,
+            invert: false,
+        };
+        let quantized_vector_size =
+            EncodedVectorsPQ::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                CHUNK_SIZE,
+            );
+        let storage_builder = TestEncodedStorageBuilder::new(None, quantized_vector_size);
+        match parallel_weight {
+            Some(parallel_weight) => EncodedVectorsPQ::encode_anisotropic(
+                vector_data.iter(),
+                storage_builder,
+                &vector_parameters,
+                vector_data.len(),
+                CHUNK_SIZE,
+                parallel_weight,
+                2,
+                None,
+                &AtomicBool::new(false),
+            ),
+            None => EncodedVectorsPQ::encode(
+                vector_data.iter(),
+                storage_builder,
+                &vector_parameters,
+                vector_data.len(),
+                CHUNK_SIZE,
+                2,
+                None,
+                &AtomicBool::new(false),
+            ),
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn test_anisotropic_dot() {
+        let (vector_data, query) = random_vectors(VECTORS_COUNT);
+        let encoded = encode(&vector_data, Some(4.0));
+        let query_pq = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&query_pq, index as u32, &counter);
+            let orginal_score = dot_similarity(&query, vector);
+            assert!((score - orginal_score).abs() < ERROR);
+        }
+    }
+
+    /// Anisotropic loss reduces error of inner products with queries close to the vectors
+    #[test]
+    fn test_anisotropic_parallel_error() {
+        let (vector_data, _) = random_vectors(VECTORS_COUNT);
+
+        let counter = HardwareCounterCell::new();
+        let mean_self_score_error = |parallel_weight: Option<f32>| {
+            let encoded = encode(&vector_data, parallel_weight);
+            let total_error: f32 = vector_data
+                .iter()
+                .enumerate()
+                .map(|(index, vector)| {
+                    let query_pq = encoded.encode_query(vector);
+                    let score = encoded.score_point(&query_pq, index as u32, &counter);
+                    (score - dot_similarity(vector, vector)).abs()
+                })
+                .sum();
+            total_error / VECTORS_COUNT as f32
+        };
+
+        let product_error = mean_self_score_error(None);
+        let anisotropic_error = mean_self_score_error(Some(10.0));
+        assert!(anisotropic_error < product_error);
+    }
+}
@@ -232,4 +232,221 @@ mod tests {
             );
         }
     }
+
+    fn encode_bq<TBitsStoreType: BitsStoreType>(
+        vector_data: &[Vec<f32>],
+        distance_type: DistanceType,
+        invert: bool,
+        encoding: Encoding,
+        query_encoding: QueryEncoding,
+    ) -> EncodedVectorsBin<TBitsStoreType, TestEncodedStorage> {
+        let vector_dim = vector_data.first().map_or(0, |vector| vector.len());
+        let quantized_vector_size = EncodedVectorsBin::<TBitsStoreType, TestEncodedStorage>::get_quantized_vector_size_from_params(
+            vector_dim,
+            encoding,
+        );
+        EncodedVectorsBin::<TBitsStoreType, _>::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &VectorParameters {
+                dim: vector_dim,
+                deprecated_count: None,
+                distance_type,
+                invert,
+            },
+            encoding,
+            query_encoding,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap()
+    }
+
+    /// Straightforward float scoring of a query against the bits of a BQ vector
+    fn float_reference_score(
+        query: &[f32],
+        bq_vector: &[u8],
+        encoding: Encoding,
+        distance_type: DistanceType,
+        invert: bool,
+    ) -> f32 {
+        let extended_query: Vec<f32> = match encoding {
+            Encoding::OneBit => query.to_vec(),
+            Encoding::TwoBits => query.iter().chain(query.iter()).copied().collect(),
+            Encoding::OneAndHalfBits => query
+                .iter()
+                .copied()
+                .chain(
+                    query
+                        .chunks(2)
+                        .map(|v| v.iter().copied().fold(f32::MIN, f32::max)),
+                )
+                .collect(),
+        };
+
+        let max_abs_value = query.iter().map(|x| x.abs()).fold(0.0, f32::max);
+        let xor_product: f32 = extended_query
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                let normalized = if max_abs_value > f32::EPSILON {
+                    (value / max_abs_value + 1.0) / 2.0
+                } else {
+                    0.5
+                };
+                let bit = (bq_vector[i / 8] >> (i % 8)) & 1;
+                (normalized - bit as f32).abs()
+            })
+            .sum();
+
+        let zeros_count = query.len() as f32 - xor_product;
+        match (distance_type, invert) {
+            (DistanceType::Dot, false) | (DistanceType::L1 | DistanceType::L2, true) => {
+                zeros_count - xor_product
+            }
+            (DistanceType::Dot, true) | (DistanceType::L1 | DistanceType::L2, false) => {
+                xor_product - zeros_count
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary_float_query() {
+        for distance_type in [DistanceType::Dot, DistanceType::L1, DistanceType::L2] {
+            for invert in [false, true] {
+                for encoding in Encoding::iter() {
+                    test_binary_float_query_impl::<u8>(0, distance_type, invert, encoding);
+                    test_binary_float_query_impl::<u8>(1, distance_type, invert, encoding);
+                    test_binary_float_query_impl::<u8>(65, distance_type, invert, encoding);
+                    test_binary_float_query_impl::<u128>(1, distance_type, invert, encoding);
+                    test_binary_float_query_impl::<u128>(601, distance_type, invert, encoding);
+                }
+            }
+        }
+    }
+
+    fn test_binary_float_query_impl<TBitsStoreType: BitsStoreType>(
+        vector_dim: usize,
+        distance_type: DistanceType,
+        invert: bool,
+        encoding: Encoding,
+    ) {
+        let vectors_count = 128;
+        let error = (vector_dim as f32).max(1.0) * 1e-4;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(44);
+        let vector_data: Vec<Vec<f32>> = (0..vectors_count)
+            .map(|_| generate_vector(vector_dim, &mut rng))
+            .collect();
+
+        let encoded = encode_bq::<TBitsStoreType>(
+            &vector_data,
+            distance_type,
+            invert,
+            encoding,
+            QueryEncoding::Float32,
+        );
+
+        let query: Vec<f32> = generate_vector(vector_dim, &mut rng);
+        let query_encoded = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for index in 0..vectors_count {
+            let score = encoded.score_point(&query_encoded, index as u32, &counter);
+            let bq_vector = encoded.get_quantized_vector(index as u32);
+            let reference_score =
+                float_reference_score(&query, &bq_vector, encoding, distance_type, invert);
+            assert!(
+                (score - reference_score).abs() <= error,
+                "{distance_type:?} {encoding:?} invert={invert}: score {score} != reference {reference_score}",
+            );
+        }
+    }
+
+    #[test]
+    fn test_binary_float_query_same_as_storage() {
+        for distance_type in [DistanceType::Dot, DistanceType::L1, DistanceType::L2] {
+            for invert in [false, true] {
+                for encoding in Encoding::iter() {
+                    test_binary_float_query_same_as_storage_impl::<u8>(
+                        33,
+                        distance_type,
+                        invert,
+                        encoding,
+                    );
+                    test_binary_float_query_same_as_storage_impl::<u128>(
+                        3 * 129,
+                        distance_type,
+                        invert,
+                        encoding,
+                    );
+                }
+            }
+        }
+    }
+
+    /// For a query of +-1 values the float query is exactly the binary query,
+    /// so both query encodings must produce the same scores and the same ordering
+    fn test_binary_float_query_same_as_storage_impl<TBitsStoreType: BitsStoreType>(
+        vector_dim: usize,
+        distance_type: DistanceType,
+        invert: bool,
+        encoding: Encoding,
+    ) {
+        let vectors_count = 128;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(45);
+        let generate_sign_vector = |rng: &mut rand::rngs::StdRng| -> Vec<f32> {
+            (0..vector_dim)
+                .map(|_| if rng.random_bool(0.5) { 1.0 } else { -1.0 })
+                .collect()
+        };
+
+        // Add negated copies, so every dimension has zero mean and all values
+        // are far enough from it to get the same bits in 2bit and 1.5bit encodings
+        let mut vector_data: Vec<Vec<f32>> = (0..vectors_count / 2)
+            .map(|_| generate_sign_vector(&mut rng))
+            .collect();
+        let negated: Vec<Vec<f32>> = vector_data
+            .iter()
+            .map(|vector| vector.iter().map(|x| -x).collect())
+            .collect();
+        vector_data.extend(negated);
+
+        let encoded_binary = encode_bq::<TBitsStoreType>(
+            &vector_data,
+            distance_type,
+            invert,
+            encoding,
+            QueryEncoding::SameAsStorage,
+        );
+        let encoded_float = encode_bq::<TBitsStoreType>(
+            &vector_data,
+            distance_type,
+            invert,
+            encoding,
+            QueryEncoding::Float32,
+        );
+
+        let query = generate_sign_vector(&mut rng);
+        let query_binary = encoded_binary.encode_query(&query);
+        let query_float = encoded_float.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        let binary_scores: Vec<f32> = (0..vectors_count)
+            .map(|index| encoded_binary.score_point(&query_binary, index as u32, &counter))
+            .collect();
+        let float_scores: Vec<f32> = (0..vectors_count)
+            .map(|index| encoded_float.score_point(&query_float, index as u32, &counter))
+            .collect();
+
+        assert_eq!(
+            binary_scores, float_scores,
+            "{distance_type:?} {encoding:?} invert={invert}",
+        );
+        assert_eq!(
+            get_top(&binary_scores, vectors_count, false),
+            get_top(&float_scores, vectors_count, false),
+        );
+    }
 }
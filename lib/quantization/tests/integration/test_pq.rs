@@ -408,4 +408,46 @@ mod tests {
             println!("Finished iteration {i}");
         }
     }
+
+    #[test]
+    fn test_pq_decode() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let vector_data: Vec<Vec<f32>> = (0..VECTORS_COUNT)
+            .map(|_| (0..VECTOR_DIM).map(|_| rng.random()).collect())
+            .collect();
+
+        let vector_parameters = VectorParameters {
+            dim: VECTOR_DIM,
+            deprecated_count: None,
+            distance_type: DistanceType::L2,
+            invert: false,
+        };
+        let quantized_vector_size =
+            EncodedVectorsPQ::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                2,
+            );
+        let encoded = EncodedVectorsPQ::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &vector_parameters,
+            VECTORS_COUNT,
+            2,
+            1,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let decoded = encoded.decode_vector(index as u32);
+            assert_eq!(decoded.len(), VECTOR_DIM);
+
+            // Decoded vector is scored the same way as the encoded one
+            let query_pq = encoded.encode_query(vector);
+            let score = encoded.score_point(&query_pq, index as u32, &counter);
+            assert!((score - l2_similarity(vector, &decoded)).abs() < ERROR);
+        }
+    }
 }
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use common::counter::hardware_counter::HardwareCounterCell;
+    use quantization::encoded_storage::{TestEncodedStorage, TestEncodedStorageBuilder};
+    use quantization::encoded_vectors::{DistanceType, EncodedVectors, VectorParameters};
+    use quantization::encoded_vectors_rq::EncodedVectorsRQ;
+    use rand::{RngExt, SeedableRng};
+
+    use crate::metrics::{dot_similarity, l1_similarity, l2_similarity};
+
+    const VECTORS_COUNT: usize = 513;
+    const VECTOR_DIM: usize = 65;
+    const ERROR: f32 = VECTOR_DIM as f32 * 0.05;
+
+    fn random_vectors(count: usize) -> (Vec<Vec<f32>>, Vec<f32>) {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let vector_data = (0..count)
+            .map(|_| (0..VECTOR_DIM).map(|_| rng.random()).collect())
+            .collect();
+        let query = (0..VECTOR_DIM).map(|_| rng.random()).collect();
+        (vector_data, query)
+    }
+
+    fn encode(
+        vector_data: &[Vec<f32>],
+        distance_type: DistanceType,
+        invert: bool,
+        chunk_size: usize,
+        stages: usize,
+    ) -> EncodedVectorsRQ<TestEncodedStorage> {
+        let vector_parameters = VectorParameters {
+            dim: VECTOR_DIM,
+            deprecated_count: None,
+            distance_type,
+            invert,
+        };
+        let quantized_vector_size =
+            EncodedVectorsRQ::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                chunk_size,
+                stages,
+            );
+        EncodedVectorsRQ::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &vector_parameters,
+            vector_data.len(),
+            chunk_size,
+            stages,
+            2,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rq_dot() {
+        let (vector_data, query) = random_vectors(VECTORS_COUNT);
+        let encoded = encode(&vector_data, DistanceType::Dot, false, 2, 2);
+        let query_rq = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&query_rq, index as u32, &counter);
+            let orginal_score = dot_similarity(&query, vector);
+            assert!((score - orginal_score).abs() < ERROR);
+        }
+    }
+
+    #[test]
+    fn test_rq_l2_inverted() {
+        let (vector_data, query) = random_vectors(VECTORS_COUNT);
+        let encoded = encode(&vector_data, DistanceType::L2, true, 2, 2);
+        let query_rq = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&query_rq, index as u32, &counter);
+            let orginal_score = -l2_similarity(&query, vector);
+            assert!((score - orginal_score).abs() < ERROR);
+        }
+    }
+
+    #[test]
+    fn test_rq_l1() {
+        let (vector_data, query) = random_vectors(VECTORS_COUNT);
+        let encoded = encode(&vector_data, DistanceType::L1, false, 2, 2);
+        let query_rq = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&query_rq, index as u32, &counter);
+            let orginal_score = l1_similarity(&query, vector);
+            assert!((score - orginal_score).abs() < ERROR);
+        }
+    }
+
+    /// Each stage reduces the error left by previous ones
+    #[test]
+    fn test_rq_stages_accuracy() {
+        let (vector_data, query) = random_vectors(VECTORS_COUNT);
+
+        let counter = HardwareCounterCell::new();
+        let mean_error = |stages: usize| {
+            let encoded = encode(&vector_data, DistanceType::Dot, false, 4, stages);
+            let query_rq = encoded.encode_query(&query);
+            let total_error: f32 = vector_data
+                .iter()
+                .enumerate()
+                .map(|(index, vector)| {
+                    let score = encoded.score_point(&query_rq, index as u32, &counter);
+                    (score - dot_similarity(&query, vector)).abs()
+                })
+                .sum();
+            total_error / VECTORS_COUNT as f32
+        };
+
+        let one_stage_error = mean_error(1);
+        let two_stages_error = mean_error(2);
+        let three_stages_error = mean_error(3);
+        assert!(two_stages_error < one_stage_error);
+        assert!(three_stages_error < two_stages_error);
+    }
+
+    #[test]
+    fn test_rq_dot_internal() {
+        let (vector_data, _) = random_vectors(VECTORS_COUNT);
+        let encoded = encode(&vector_data, DistanceType::Dot, false, 2, 2);
+
+        let counter = HardwareCounterCell::new();
+        let query_rq = encoded.encode_internal_vector(0).unwrap();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_internal(0, index as u32, &counter);
+            let orginal_score = dot_similarity(&vector_data[0], vector);
+            assert!((score - orginal_score).abs() < ERROR);
+
+            // Query made of the stored vector scores the same as internal scoring
+            let query_score = encoded.score_point(&query_rq, index as u32, &counter);
+            assert!((score - query_score).abs() < 1e-3);
+        }
+    }
+
+    /// With less vectors than centroids, vectors themselves are centroids of the first stage
+    #[test]
+    fn test_rq_few_vectors() {
+        let (vector_data, query) = random_vectors(10);
+        let encoded = encode(&vector_data, DistanceType::L2, false, 4, 2);
+        let query_rq = encoded.encode_query(&query);
+
+        let counter = HardwareCounterCell::new();
+        for (index, vector) in vector_data.iter().enumerate() {
+            let score = encoded.score_point(&query_rq, index as u32, &counter);
+            let orginal_score = l2_similarity(&query, vector);
+            assert!((score - orginal_score).abs() < 1e-3);
+            assert_eq!(&encoded.decode_vector(index as u32), vector);
+        }
+    }
+}
@@ -13,6 +13,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_dot_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -33,7 +34,10 @@ mod tests {
             invert: false,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -57,6 +61,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_l2_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -77,7 +82,10 @@ mod tests {
             invert: false,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -101,6 +109,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_l1_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -125,7 +134,10 @@ mod tests {
             invert: false,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -149,6 +161,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_dot_inverted_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -169,7 +182,10 @@ mod tests {
             invert: true,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -193,6 +209,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_l2_inverted_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -213,7 +230,10 @@ mod tests {
             invert: true,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -237,6 +257,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_l1_inverted_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -261,7 +282,10 @@ mod tests {
             invert: true,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -285,6 +309,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_dot_internal_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count: usize = 129;
         let vector_dim = 65;
@@ -304,7 +329,10 @@ mod tests {
             invert: false,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -327,6 +355,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_dot_inverted_internal_simple(#[case] method: ScalarQuantizationMethod) {
         let vectors_count: usize = 129;
         let vector_dim = 65;
@@ -346,7 +375,10 @@ mod tests {
             invert: true,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -369,6 +401,7 @@ mod tests {
 
     #[rstest]
     #[case(ScalarQuantizationMethod::Int8)]
+    #[case(ScalarQuantizationMethod::Int4)]
     fn test_u8_large_quantile(#[case] method: ScalarQuantizationMethod) {
         let vectors_count = 129;
         let vector_dim = 65;
@@ -389,7 +422,10 @@ mod tests {
             invert: false,
         };
         let quantized_vector_size =
-            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(&vector_parameters);
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
         let encoded = EncodedVectorsU8::encode(
             vector_data.iter(),
             TestEncodedStorageBuilder::new(None, quantized_vector_size),
@@ -438,6 +474,7 @@ mod tests {
             let quantized_vector_size =
                 EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
                     &vector_parameters,
+                    method,
                 );
 
             let encoded = EncodedVectorsU8::encode(
@@ -446,7 +483,7 @@ mod tests {
                 &vector_parameters,
                 vectors_count,
                 Some(1.0 - f32::EPSILON), // almost 1.0 value, but not 1.0
-                method.clone(),
+                method,
                 None,
                 &AtomicBool::new(false),
             )
@@ -469,4 +506,54 @@ mod tests {
             }
         }
     }
+
+    #[rstest]
+    #[case(ScalarQuantizationMethod::Int8, 127.0)]
+    #[case(ScalarQuantizationMethod::Int4, 15.0)]
+    fn test_u8_decode(#[case] method: ScalarQuantizationMethod, #[case] levels: f32) {
+        let vectors_count = 129;
+        let vector_dim = 65;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let vector_data: Vec<Vec<f32>> = (0..vectors_count)
+            .map(|_| {
+                (0..vector_dim)
+                    .map(|_| rng.random_range(-1.0..1.0))
+                    .collect()
+            })
+            .collect();
+
+        let vector_parameters = VectorParameters {
+            dim: vector_dim,
+            deprecated_count: None,
+            distance_type: DistanceType::L2,
+            invert: false,
+        };
+        let quantized_vector_size =
+            EncodedVectorsU8::<TestEncodedStorage>::get_quantized_vector_size(
+                &vector_parameters,
+                method,
+            );
+        let encoded = EncodedVectorsU8::encode(
+            vector_data.iter(),
+            TestEncodedStorageBuilder::new(None, quantized_vector_size),
+            &vector_parameters,
+            vectors_count,
+            None,
+            method,
+            None,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+
+        // Every element is rounded to the nearest level over the range of at most 2.0
+        let error = 1.0 / levels + f32::EPSILON;
+        for (index, vector) in vector_data.iter().enumerate() {
+            let decoded = encoded.decode_vector(index as u32);
+            assert_eq!(decoded.len(), vector_dim);
+            for (original, decoded) in vector.iter().zip(&decoded) {
+                assert!((original - decoded).abs() <= error);
+            }
+        }
+    }
 }
@@ -43,6 +43,7 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
                     TOP,
                     EF,
                     SearchAlgorithm::Hnsw,
+                    None,
                     scorer,
                     None,
                     &DEFAULT_STOPPED,
@@ -69,6 +70,7 @@ fn hnsw_build_asymptotic(c: &mut Criterion) {
                     TOP,
                     EF,
                     SearchAlgorithm::Hnsw,
+                    None,
                     scorer,
                     None,
                     &DEFAULT_STOPPED,
@@ -373,6 +373,9 @@ fn build_hnsw_index<R: Rng + ?Sized>(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let open_args = HnswIndexOpenArgs {
@@ -43,6 +43,7 @@ fn hnsw_benchmark(c: &mut Criterion) {
                         TOP,
                         EF,
                         SearchAlgorithm::Hnsw,
+                        None,
                         scorer,
                         None,
                         &DEFAULT_STOPPED,
@@ -66,6 +67,7 @@ fn hnsw_benchmark(c: &mut Criterion) {
                         TOP,
                         EF,
                         SearchAlgorithm::Hnsw,
+                        None,
                         scorer,
                         None,
                         &DEFAULT_STOPPED,
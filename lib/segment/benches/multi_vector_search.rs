@@ -83,6 +83,10 @@ fn make_segment_index<R: Rng + ?Sized>(rng: &mut R, distance: Distance) -> HNSWI
                 quantization_config: None,
                 multivector_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -110,6 +114,9 @@ fn make_segment_index<R: Rng + ?Sized>(rng: &mut R, distance: Distance) -> HNSWI
         on_disk: None,
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
     let permit_cpu_count = get_num_indexing_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(ResourcePermit::dummy(permit_cpu_count as u32));
@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::AccessPattern;
+use common::types::PointOffsetType;
+use gridstore::Gridstore;
+use gridstore::config::{Compression, StorageOptions};
+
+use crate::common::Flusher;
+use crate::common::operation_error::{OperationError, OperationResult};
+
+const STORAGE_PATH: &str = "blob_storage";
+
+/// Blobs are expected to be much larger than payloads, so use larger blocks
+const BLOB_BLOCK_SIZE_BYTES: usize = 1024;
+
+/// Binary blobs of points, addressed by internal id.
+///
+/// Blobs are stored in chunked mmap pages of a [`Gridstore`]. Blobs are usually compressed
+/// already, so they are stored as is.
+///
+/// Most segments have no blobs, so the storage is only created on disk with the first blob.
+#[derive(Debug)]
+pub struct MmapBlobStorage {
+    path: PathBuf,
+    storage: Option<Gridstore<Vec<u8>>>,
+}
+
+impl MmapBlobStorage {
+    /// Open blob storage of the segment, if it exists
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = storage_dir(segment_path);
+        let storage = if path.exists() {
+            let storage = Gridstore::open(path.clone()).map_err(|err| {
+                OperationError::service_error(format!("Failed to open mmap blob storage: {err}"))
+            })?;
+            Some(storage)
+        } else {
+            None
+        };
+
+        Ok(Self { path, storage })
+    }
+
+    fn get_or_create_storage(&mut self) -> OperationResult<&mut Gridstore<Vec<u8>>> {
+        if self.storage.is_none() {
+            let options = StorageOptions {
+                block_size_bytes: Some(BLOB_BLOCK_SIZE_BYTES),
+                compression: Some(Compression::None),
+                ..Default::default()
+            };
+            let storage = Gridstore::open_or_create(self.path.clone(), options).map_err(|err| {
+                OperationError::service_error(format!("Failed to create mmap blob storage: {err}"))
+            })?;
+            self.storage = Some(storage);
+        }
+        Ok(self.storage.as_mut().unwrap())
+    }
+
+    pub fn get<P: AccessPattern>(
+        &self,
+        point_id: PointOffsetType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<Vec<u8>>> {
+        let Some(storage) = &self.storage else {
+            return Ok(None);
+        };
+        Ok(storage.get_value::<P>(point_id, hw_counter)?)
+    }
+
+    /// Set or remove the blob of the point.
+    ///
+    /// Returns `true` if the point had a blob before.
+    pub fn set(
+        &mut self,
+        point_id: PointOffsetType,
+        blob: Option<&[u8]>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        match blob {
+            Some(blob) => {
+                let storage = self.get_or_create_storage()?;
+                let existed = storage.put_value(
+                    point_id,
+                    &blob.to_vec(),
+                    hw_counter.ref_payload_io_write_counter(),
+                )?;
+                Ok(existed)
+            }
+            None => self.delete(point_id),
+        }
+    }
+
+    /// Remove the blob of the point.
+    ///
+    /// Returns `true` if the point had a blob.
+    pub fn delete(&mut self, point_id: PointOffsetType) -> OperationResult<bool> {
+        let Some(storage) = &mut self.storage else {
+            return Ok(false);
+        };
+        Ok(storage.delete_value(point_id)?.is_some())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let Some(storage) = &self.storage else {
+            return Box::new(|| Ok(()));
+        };
+        let storage_flusher = storage.flusher();
+        Box::new(move || {
+            storage_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush mmap blob gridstore: {err}"))
+            })
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.storage
+            .as_ref()
+            .map(Gridstore::files)
+            .unwrap_or_default()
+    }
+
+    pub fn immutable_files(&self) -> Vec<PathBuf> {
+        self.storage
+            .as_ref()
+            .map(Gridstore::immutable_files)
+            .unwrap_or_default()
+    }
+
+    pub fn get_storage_size_bytes(&self) -> usize {
+        self.storage
+            .as_ref()
+            .map_or(0, Gridstore::get_storage_size_bytes)
+    }
+
+    /// Populate all pages in the mmap.
+    /// Block until all pages are populated.
+    pub fn populate(&self) -> OperationResult<()> {
+        if let Some(storage) = &self.storage {
+            storage.populate()?;
+        }
+        Ok(())
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        if let Some(storage) = &self.storage {
+            storage.clear_cache()?;
+        }
+        Ok(())
+    }
+}
+
+/// Get storage directory for the blob storage of a segment
+pub fn storage_dir<P: AsRef<Path>>(segment_path: P) -> PathBuf {
+    segment_path.as_ref().join(STORAGE_PATH)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::generic_consts::Random;
+
+    use super::*;
+
+    #[test]
+    fn test_blob_storage_persistence() {
+        let dir = tempfile::Builder::new()
+            .prefix("blob_storage")
+            .tempdir()
+            .unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = MmapBlobStorage::open(dir.path()).unwrap();
+        assert!(storage.files().is_empty());
+        assert!(!storage.delete(0).unwrap());
+
+        let large_blob: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+        assert!(!storage.set(0, Some(b"first"), &hw_counter).unwrap());
+        assert!(storage.set(0, Some(b"second"), &hw_counter).unwrap());
+        storage.set(1, Some(&large_blob), &hw_counter).unwrap();
+        storage.set(2, Some(b"deleted"), &hw_counter).unwrap();
+        assert!(storage.set(2, None, &hw_counter).unwrap());
+        storage.flusher()().unwrap();
+        assert!(!storage.files().is_empty());
+
+        let storage = MmapBlobStorage::open(dir.path()).unwrap();
+        assert_eq!(
+            storage.get::<Random>(0, &hw_counter).unwrap().as_deref(),
+            Some(b"second".as_slice()),
+        );
+        assert_eq!(
+            storage.get::<Random>(1, &hw_counter).unwrap(),
+            Some(large_blob),
+        );
+        assert_eq!(storage.get::<Random>(2, &hw_counter).unwrap(), None);
+        assert_eq!(storage.get::<Random>(3, &hw_counter).unwrap(), None);
+    }
+}
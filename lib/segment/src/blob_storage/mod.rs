@@ -0,0 +1,8 @@
+//! Storage of raw binary attachments of points, such as original images or compressed text.
+//!
+//! Blobs are opaque to Qdrant: they are neither indexed nor searchable, only stored next to the
+//! point and returned on retrieval.
+
+mod mmap_blob_storage;
+
+pub use mmap_blob_storage::{MmapBlobStorage, storage_dir};
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use common::bitvec::{BitSlice, BitVec};
+use common::bitvec::{BitSlice, BitVec, bitvec_set_deleted_batch};
 use common::types::PointOffsetType;
 
 use super::buffered_dynamic_flags::BufferedDynamicFlags;
@@ -98,6 +98,21 @@ impl BitvecFlags {
         self.bitvec.replace(index_usize, value)
     }
 
+    /// Set all flags, which are set in `indices`, to true. Grows the bitvec if needed.
+    /// Returns the number of flags which were false before.
+    pub fn set_trues(&mut self, indices: &BitSlice) -> usize {
+        let mut changed = Vec::new();
+        let count = bitvec_set_deleted_batch(&mut self.bitvec, indices, |index| {
+            changed.push(index);
+        });
+        self.len = self.len.max(self.bitvec.len());
+
+        // queue writes in buffer, only flags which did change need to be persisted
+        self.storage.buffer_set_many(&changed, true);
+
+        count
+    }
+
     pub fn clear_cache(&self) -> OperationResult<()> {
         self.storage.clear_cache()?;
         Ok(())
@@ -107,6 +122,10 @@ impl BitvecFlags {
         self.storage.files()
     }
 
+    pub fn estimate_flush_size(&self) -> usize {
+        self.storage.estimate_flush_size()
+    }
+
     pub fn flusher(&self) -> Flusher {
         self.storage.flusher()
     }
@@ -114,6 +133,7 @@ impl BitvecFlags {
 
 #[cfg(test)]
 mod tests {
+    use common::bitvec::BitVec;
     use common::types::PointOffsetType;
 
     use crate::common::flags::bitvec_flags::BitvecFlags;
@@ -184,4 +204,59 @@ mod tests {
             assert_eq!(all_indices, expected_all);
         }
     }
+
+    #[test]
+    fn test_bitvec_flags_set_trues_persistence() {
+        let dir = tempfile::Builder::new()
+            .prefix("bitvec_flags_set_trues")
+            .tempdir()
+            .unwrap();
+
+        let mut indices = BitVec::repeat(false, 150);
+        for i in [1, 63, 64, 100, 149] {
+            indices.set(i, true);
+        }
+
+        {
+            let mmap_flags = DynamicMmapFlags::open(dir.path(), false).unwrap();
+            let mut bitvec_flags = BitvecFlags::new(mmap_flags);
+            bitvec_flags.set(1, true);
+            bitvec_flags.set(10, true);
+
+            assert_eq!(bitvec_flags.set_trues(&indices), 4);
+            assert_eq!(bitvec_flags.set_trues(&indices), 0);
+            assert_eq!(bitvec_flags.len(), 150);
+
+            let flusher = bitvec_flags.flusher();
+            flusher().unwrap();
+        }
+
+        let mmap_flags = DynamicMmapFlags::open(dir.path(), true).unwrap();
+        let bitvec_flags = BitvecFlags::new(mmap_flags);
+        let iter_trues: Vec<_> = bitvec_flags.iter_trues().collect();
+        assert_eq!(iter_trues, vec![1, 10, 63, 64, 100, 149]);
+        assert_eq!(bitvec_flags.len(), 150);
+    }
+
+    #[test]
+    fn test_bitvec_flags_estimate_flush_size() {
+        let dir = tempfile::Builder::new()
+            .prefix("bitvec_flags_flush_size")
+            .tempdir()
+            .unwrap();
+
+        let mmap_flags = DynamicMmapFlags::open(dir.path(), false).unwrap();
+        let mut bitvec_flags = BitvecFlags::new(mmap_flags);
+        assert_eq!(bitvec_flags.estimate_flush_size(), 0);
+
+        for i in 0..10 {
+            bitvec_flags.set(i, true);
+        }
+        // Changing the same flag again does not add to the pending changes
+        bitvec_flags.set(0, false);
+        assert_eq!(bitvec_flags.estimate_flush_size(), 2);
+
+        bitvec_flags.flusher()().unwrap();
+        assert_eq!(bitvec_flags.estimate_flush_size(), 0);
+    }
 }
@@ -41,6 +41,12 @@ impl BufferedDynamicFlags {
         self.buffer.write().insert(index, value);
     }
 
+    /// Same as [`Self::buffer_set`] for many indices, taking the buffer lock once
+    pub fn buffer_set_many(&self, indices: &[PointOffsetType], value: bool) {
+        let mut buffer = self.buffer.write();
+        buffer.extend(indices.iter().map(|&index| (index, value)));
+    }
+
     pub fn clear_cache(&self) -> OperationResult<()> {
         self.storage.lock().clear_cache()?;
         Ok(())
@@ -50,6 +56,11 @@ impl BufferedDynamicFlags {
         self.storage.lock().files()
     }
 
+    /// Upper bound of bytes, changed by pending flags
+    pub fn estimate_flush_size(&self) -> usize {
+        self.buffer.read().len().div_ceil(u8::BITS as usize)
+    }
+
     pub fn flusher(&self) -> Flusher {
         let updates = {
             let buffer_guard = self.buffer.read();
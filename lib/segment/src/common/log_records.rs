@@ -0,0 +1,63 @@
+//! Framing of records in append-only logs.
+//!
+//! Every record is prefixed with its length and CRC32C checksum, so that a record, which was cut
+//! short by a crash, is detected on replay.
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use fs_err::File;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Size of the record header: length of the encoded record and its CRC32C checksum
+const RECORD_HEADER_SIZE: usize = 2 * size_of::<u32>();
+
+/// Open a log file for appending, creating it if it does not exist
+pub fn open_log(log_path: &Path) -> io::Result<File> {
+    File::options().create(true).append(true).open(log_path)
+}
+
+pub fn write_record<W: Write, T: Serialize>(mut writer: W, record: &T) -> OperationResult<()> {
+    let encoded = bincode::serialize(record).map_err(|err| {
+        OperationError::service_error(format!("Cannot serialize log record: {err}"))
+    })?;
+    writer.write_all(&(encoded.len() as u32).to_le_bytes())?;
+    writer.write_all(&crc32c::crc32c(&encoded).to_le_bytes())?;
+    writer.write_all(&encoded)?;
+    Ok(())
+}
+
+/// Read the next record from the log, along with its size in bytes.
+///
+/// Returns `None` at the end of the log, or if the record is incomplete or corrupted.
+pub fn read_record<R: Read, T: DeserializeOwned>(
+    reader: &mut R,
+) -> OperationResult<Option<(T, usize)>> {
+    let mut header = [0; RECORD_HEADER_SIZE];
+    match reader.read_exact(&mut header) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err.into()),
+    }
+    let (len, checksum) = header.split_at(size_of::<u32>());
+    let len = u32::from_le_bytes(len.try_into().unwrap()) as usize;
+    let checksum = u32::from_le_bytes(checksum.try_into().unwrap());
+
+    // Length of a corrupted record may be garbage, so don't allocate it upfront
+    let mut encoded = Vec::new();
+    reader.take(len as u64).read_to_end(&mut encoded)?;
+    if encoded.len() < len {
+        return Ok(None);
+    }
+    if crc32c::crc32c(&encoded) != checksum {
+        return Ok(None);
+    }
+
+    let Ok(record) = bincode::deserialize(&encoded) else {
+        return Ok(None);
+    };
+    Ok(Some((record, RECORD_HEADER_SIZE + len)))
+}
@@ -1,6 +1,7 @@
 pub mod anonymize;
 pub mod error_logging;
 pub mod flags;
+pub mod log_records;
 pub mod macros;
 pub mod mmap_bitslice_buffered_update_wrapper;
 pub mod mmap_slice_buffered_update_wrapper;
@@ -66,27 +67,27 @@ fn check_query_vector(
 ) -> OperationResult<()> {
     match query_vector {
         QueryVector::Nearest(vector) => {
-            check_vector_against_config(VectorRef::from(vector), vector_config)?
+            check_query_vector_against_config(VectorRef::from(vector), vector_config)?
         }
         QueryVector::RecommendBestScore(reco_query)
         | QueryVector::RecommendSumScores(reco_query) => {
             reco_query.flat_iter().try_for_each(|vector| {
-                check_vector_against_config(VectorRef::from(vector), vector_config)
+                check_query_vector_against_config(VectorRef::from(vector), vector_config)
             })?
         }
         QueryVector::Discover(discover_query) => {
             discover_query.flat_iter().try_for_each(|vector| {
-                check_vector_against_config(VectorRef::from(vector), vector_config)
+                check_query_vector_against_config(VectorRef::from(vector), vector_config)
             })?
         }
         QueryVector::Context(context_query) => {
             context_query.flat_iter().try_for_each(|vector| {
-                check_vector_against_config(VectorRef::from(vector), vector_config)
+                check_query_vector_against_config(VectorRef::from(vector), vector_config)
             })?
         }
         QueryVector::FeedbackNaive(feedback_query) => {
             feedback_query.flat_iter().try_for_each(|vector| {
-                check_vector_against_config(VectorRef::from(vector), vector_config)
+                check_query_vector_against_config(VectorRef::from(vector), vector_config)
             })?
         }
     }
@@ -200,7 +201,10 @@ fn check_vector_against_config(
         VectorRef::Dense(vector) => {
             // Check dimensionality
             let dim = vector_config.size;
-            if vector.len() != dim {
+            let is_in_dim_range = vector_config
+                .dim_range
+                .is_some_and(|dim_range| dim_range.contains(vector.len()));
+            if vector.len() != dim && !is_in_dim_range {
                 return Err(OperationError::WrongVectorDimension {
                     expected_dim: dim,
                     received_dim: vector.len(),
@@ -225,6 +229,24 @@ fn check_vector_against_config(
     }
 }
 
+/// Check if the given query vector is compatible with the given configuration.
+///
+/// Dense query vectors must have the full dimension, even if stored vectors may be shorter.
+fn check_query_vector_against_config(
+    vector: VectorRef,
+    vector_config: &VectorDataConfig,
+) -> OperationResult<()> {
+    if let VectorRef::Dense(vector) = vector
+        && vector.len() != vector_config.size
+    {
+        return Err(OperationError::WrongVectorDimension {
+            expected_dim: vector_config.size,
+            received_dim: vector.len(),
+        });
+    }
+    check_vector_against_config(vector, vector_config)
+}
+
 fn check_sparse_vector_against_config(
     vector: VectorRef,
     _vector_config: &SparseVectorDataConfig,
@@ -27,6 +27,8 @@ pub enum OperationError {
     },
     #[error("Not existing vector name error: {received_name}")]
     VectorNameNotExists { received_name: VectorNameBuf },
+    #[error("Vector name {vector_name} is removed")]
+    VectorNameRemoved { vector_name: VectorNameBuf },
     #[error("No point with id {missed_point_id}")]
     PointIdError { missed_point_id: PointIdType },
     #[error(
@@ -67,6 +69,10 @@ pub enum OperationError {
         "No appropriate index for faceting: `{key}`. Please create one to facet on this field. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Match conditions"
     )]
     MissingMapIndexForFacet { key: String },
+    #[error(
+        "No full-text index for text query key: `{key}`. Please create one to score points by text. Check https://qdrant.tech/documentation/concepts/indexing/#full-text-index"
+    )]
+    MissingFullTextIndexForTextQuery { key: String },
     #[error(
         "Expected {expected_type} value for {field_name} in the payload and/or in the formula defaults. Error: {description}"
     )]
@@ -125,6 +131,12 @@ impl OperationError {
         }
     }
 
+    pub fn vector_name_removed(vector_name: impl Into<String>) -> Self {
+        Self::VectorNameRemoved {
+            vector_name: vector_name.into(),
+        }
+    }
+
     pub fn timeout(timeout: Duration, operation: impl Into<String>) -> Self {
         Self::Timeout {
             description: format!(
@@ -72,6 +72,18 @@ impl DatabaseColumnScheduledUpdateWrapper {
             .retain(|point_id, a| flushed.inserted.get(point_id).is_none_or(|b| a != b));
     }
 
+    /// Number of bytes of keys and values, which are not persisted yet
+    pub fn pending_size_bytes(&self) -> usize {
+        let pending_guard = self.pending_operations.read();
+        let inserted_size: usize = pending_guard
+            .inserted
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum();
+        let deleted_size: usize = pending_guard.deleted.iter().map(Vec::len).sum();
+        inserted_size + deleted_size
+    }
+
     pub fn flusher(&self) -> Flusher {
         let PendingOperations { deleted, inserted } = self.pending_operations.read().clone();
 
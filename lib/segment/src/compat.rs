@@ -68,6 +68,10 @@ impl From<SegmentConfigV5> for SegmentConfig {
                     },
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 };
 
                 (vector_name, new_data)
@@ -177,6 +181,9 @@ mod tests {
                             on_disk: None,
                             payload_m: Some(10),
                             inline_storage: None,
+                            varint_links: None,
+                            acorn: None,
+                            seed: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -193,6 +200,7 @@ mod tests {
                                 r#type: Default::default(),
                                 quantile: Some(0.99),
                                 always_ram: Some(true),
+                                drop_originals: None,
                             },
                         })),
                         on_disk: None,
@@ -209,6 +217,9 @@ mod tests {
                 on_disk: None,
                 payload_m: None,
                 inline_storage: None,
+                varint_links: None,
+                acorn: None,
+                seed: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: None,
@@ -220,14 +231,18 @@ mod tests {
         eprintln!("new = {new_segment:#?}");
 
         match &new_segment.vector_data.get("vec1").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Vamana(_) | Indexes::Ivf(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 20);
             }
         }
 
         match &new_segment.vector_data.get("vec2").unwrap().index {
-            Indexes::Plain { .. } => panic!("expected HNSW index"),
+            Indexes::Plain { .. } | Indexes::Vamana(_) | Indexes::Ivf(_) => {
+                panic!("expected HNSW index")
+            }
             Indexes::Hnsw(hnsw) => {
                 assert_eq!(hnsw.m, 25);
             }
@@ -269,6 +284,7 @@ mod tests {
                                 r#type: Default::default(),
                                 quantile: Some(0.99),
                                 always_ram: Some(true),
+                                drop_originals: None,
                             },
                         })),
                         on_disk: None,
@@ -285,6 +301,9 @@ mod tests {
                 on_disk: None,
                 payload_m: None,
                 inline_storage: None,
+                varint_links: None,
+                acorn: None,
+                seed: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: None,
@@ -293,6 +312,7 @@ mod tests {
                     r#type: Default::default(),
                     quantile: Some(0.95),
                     always_ram: Some(true),
+                    drop_originals: None,
                 },
             })),
         };
@@ -327,6 +347,12 @@ mod tests {
                 QuantizationConfig::Binary(_) => {
                     panic!("expected scalar quantization")
                 }
+                QuantizationConfig::Residual(_) => {
+                    panic!("expected scalar quantization")
+                }
+                QuantizationConfig::Anisotropic(_) => {
+                    panic!("expected scalar quantization")
+                }
             },
             _ => {
                 panic!("expected quantization")
@@ -74,6 +74,13 @@ pub struct IntegerIndexParams {
     /// Default: true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enable_hnsw: Option<bool>,
+
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_hnsw_ranges: Option<bool>,
 }
 
 impl Validate for IntegerIndexParams {
@@ -85,6 +92,7 @@ impl Validate for IntegerIndexParams {
             is_principal: _,
             on_disk: _,
             enable_hnsw: _,
+            enable_hnsw_ranges: _,
         } = &self;
         validate_integer_index_params(lookup, range)
     }
@@ -162,6 +170,13 @@ pub struct FloatIndexParams {
     /// Default: true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enable_hnsw: Option<bool>,
+
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_hnsw_ranges: Option<bool>,
 }
 
 // Geo
@@ -268,6 +283,24 @@ pub enum Snowball {
 pub struct SnowballParams {
     pub r#type: Snowball,
     pub language: SnowballLanguage,
+    /// Stem tokens with stemmers of these languages too. Each token is stemmed with a single
+    /// language, the one which produces the shortest stem, preferring `language` on ties.
+    /// Same stemmers are applied to indexed documents and queries. Default: none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_languages: Option<Vec<SnowballLanguage>>,
+}
+
+impl SnowballParams {
+    /// All languages to stem with, in order of preference, without duplicates
+    pub fn languages(&self) -> Vec<SnowballLanguage> {
+        let mut languages = vec![self.language];
+        for language in self.additional_languages.iter().flatten() {
+            if !languages.contains(language) {
+                languages.push(*language);
+            }
+        }
+        languages
+    }
 }
 
 /// Different stemming algorithms with their configs.
@@ -542,6 +575,13 @@ pub struct DatetimeIndexParams {
     /// Default: true.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub enable_hnsw: Option<bool>,
+
+    /// Build additional HNSW links for wider ranges of values, on top of links of `enable_hnsw`.
+    /// If true, range filters matching many points keep HNSW performance, at the cost of longer
+    /// index building.
+    /// Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_hnsw_ranges: Option<bool>,
 }
 
 #[cfg(test)]
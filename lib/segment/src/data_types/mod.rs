@@ -10,5 +10,6 @@ pub mod order_by;
 pub mod primitive;
 pub mod query_context;
 pub mod segment_record;
+pub mod text_query;
 pub mod tiny_map;
 pub mod vectors;
@@ -1,5 +1,7 @@
+use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 use crate::common::anonymize::Anonymize;
 
@@ -15,3 +17,45 @@ pub enum Modifier {
     None,
     Idf,
 }
+
+/// Parameters of BM25 ranking for sparse vectors with the `idf` modifier.
+///
+/// Values of the stored sparse vectors are treated as term frequencies: they are saturated with
+/// `k1` and normalized by the length of the vector (sum of its values) relative to the average
+/// length in the collection, with strength `b`. The IDF weights the query as usual.
+#[derive(
+    Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Anonymize, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct Bm25Params {
+    /// Term frequency saturation. Higher values let repeated terms contribute more.
+    /// Default: 1.2
+    #[serde(default = "default_bm25_k1")]
+    #[validate(range(min = 0.0))]
+    #[anonymize(false)]
+    pub k1: OrderedFloat<f64>,
+
+    /// Strength of document length normalization, from 0.0 (none) to 1.0 (full).
+    /// Default: 0.75
+    #[serde(default = "default_bm25_b")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    #[anonymize(false)]
+    pub b: OrderedFloat<f64>,
+}
+
+impl Default for Bm25Params {
+    fn default() -> Self {
+        Self {
+            k1: default_bm25_k1(),
+            b: default_bm25_b(),
+        }
+    }
+}
+
+const fn default_bm25_k1() -> OrderedFloat<f64> {
+    OrderedFloat(1.2)
+}
+
+const fn default_bm25_b() -> OrderedFloat<f64> {
+    OrderedFloat(0.75)
+}
@@ -7,7 +7,8 @@ use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
 use super::vectors::{
     DenseVector, MultiDenseVectorInternal, TypedMultiDenseVector, TypedMultiDenseVectorRef,
-    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorInternal, VectorRef,
+    VectorElementType, VectorElementTypeBfloat16, VectorElementTypeByte, VectorElementTypeDouble,
+    VectorElementTypeHalf, VectorInternal, VectorRef,
 };
 use crate::common::operation_error::OperationError;
 use crate::types::{VectorDataConfig, VectorName, VectorNameBuf, VectorStorageDatatype};
@@ -359,8 +360,18 @@ impl<'a> NamedVectors<'a> {
         dense_vector: DenseVector,
         config: &VectorDataConfig,
     ) -> DenseVector {
+        // Storage normalizes vectors itself, keeping their norms
+        if config.preserve_norm() {
+            return dense_vector;
+        }
         match config.datatype {
-            Some(VectorStorageDatatype::Float32) | None => config
+            // Int8 and Int4 vectors are quantized from preprocessed float vectors
+            Some(
+                VectorStorageDatatype::Float32
+                | VectorStorageDatatype::Int8
+                | VectorStorageDatatype::Int4,
+            )
+            | None => config
                 .distance
                 .preprocess_vector::<VectorElementType>(dense_vector),
             Some(VectorStorageDatatype::Uint8) => config
@@ -369,6 +380,12 @@ impl<'a> NamedVectors<'a> {
             Some(VectorStorageDatatype::Float16) => config
                 .distance
                 .preprocess_vector::<VectorElementTypeHalf>(dense_vector),
+            Some(VectorStorageDatatype::Float64) => config
+                .distance
+                .preprocess_vector::<VectorElementTypeDouble>(dense_vector),
+            Some(VectorStorageDatatype::Bfloat16) => config
+                .distance
+                .preprocess_vector::<VectorElementTypeBfloat16>(dense_vector),
         }
     }
 }
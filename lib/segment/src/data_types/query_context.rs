@@ -7,8 +7,10 @@ use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::cow::SimpleCow;
 use common::types::{PointOffsetType, ScoreType};
+use sparse::common::bm25::Bm25;
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::data_types::modifier::Bm25Params;
 use crate::data_types::tiny_map;
 use crate::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use crate::types::{ScoredPoint, VectorName, VectorNameBuf};
@@ -22,6 +24,13 @@ pub struct QueryIdfStats {
 
     /// Number of indexed vectors per vector name.
     pub indexed_vectors: tiny_map::TinyMap<VectorNameBuf, usize>,
+
+    /// Parameters of BM25 ranking per vector name, if configured along with `idf`.
+    pub bm25: tiny_map::TinyMap<VectorNameBuf, Bm25Params>,
+
+    /// Total length of indexed vectors per vector name, collected over all segments.
+    /// Required for BM25 document length normalization.
+    pub total_doc_lengths: tiny_map::TinyMap<VectorNameBuf, f64>,
 }
 
 #[derive(Debug)]
@@ -105,6 +114,15 @@ impl QueryContext {
         }
     }
 
+    /// Enable BM25 ranking for sparse vectors, which already require `idf` statistics,
+    /// so the document length statistics can be collected.
+    pub fn init_bm25(&mut self, vector_name: &VectorName, params: Bm25Params) {
+        self.idf_stats.bm25.insert(vector_name.to_owned(), params);
+        self.idf_stats
+            .total_doc_lengths
+            .insert(vector_name.to_owned(), 0.0);
+    }
+
     pub fn mut_idf_stats(&mut self) -> &mut QueryIdfStats {
         &mut self.idf_stats
     }
@@ -157,6 +175,13 @@ impl<'a> SegmentQueryContext<'a> {
                 .indexed_vectors
                 .get(vector_name)
                 .copied(),
+            bm25: self.query_context.idf_stats.bm25.get(vector_name).copied(),
+            total_doc_length: self
+                .query_context
+                .idf_stats
+                .total_doc_lengths
+                .get(vector_name)
+                .copied(),
             deleted_points: self.deleted_points,
             hardware_counter: self.hardware_counter.fork(),
             deferred_internal_id,
@@ -194,6 +219,10 @@ pub struct VectorQueryContext<'a> {
 
     indexed_vectors: Option<usize>,
 
+    bm25: Option<Bm25Params>,
+
+    total_doc_length: Option<f64>,
+
     deleted_points: Option<&'a BitSlice>,
 
     hardware_counter: HardwareCounterCell,
@@ -250,6 +279,26 @@ impl VectorQueryContext<'_> {
         self.idf.is_some() && self.indexed_vectors.is_some()
     }
 
+    /// BM25 normalization of the document weights, with the average document length
+    /// over all segments.
+    pub fn bm25(&self) -> Option<Bm25> {
+        let Bm25Params { k1, b } = self.bm25?;
+        let total_doc_length = self.total_doc_length?;
+        let indexed_vectors = self.indexed_vectors?;
+
+        let avg_doc_length = if indexed_vectors > 0 {
+            total_doc_length / indexed_vectors as f64
+        } else {
+            0.0
+        };
+
+        Some(Bm25 {
+            k1: k1.into_inner() as f32,
+            b: b.into_inner() as f32,
+            avg_doc_length: avg_doc_length as f32,
+        })
+    }
+
     pub fn deferred_internal_id(&self) -> Option<PointOffsetType> {
         self.deferred_internal_id
     }
@@ -263,6 +312,8 @@ impl Default for VectorQueryContext<'_> {
             is_stopped: None,
             idf: None,
             indexed_vectors: None,
+            bm25: None,
+            total_doc_length: None,
             deleted_points: None,
             hardware_counter: HardwareCounterCell::new(),
             deferred_internal_id: None,
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sparse::common::bm25::Bm25;
+use validator::Validate;
+
+use crate::data_types::modifier::Bm25Params;
+use crate::json_path::JsonPath;
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct TextQuery {
+    /// Payload key to score by, must have a full-text index
+    pub key: JsonPath,
+
+    /// Text to look for, tokenized the same way as the indexed field
+    #[validate(length(min = 1))]
+    pub text: String,
+}
+
+/// Text query with the statistics of its tokens over the whole collection.
+///
+/// Statistics are collected from all shards before scoring any of them, so the scores of points
+/// from different shards are comparable. If not collected yet, the shard collects them over its
+/// own segments.
+#[derive(Clone, Debug, PartialEq, Hash, Serialize)]
+pub struct TextQueryInternal {
+    pub query: TextQuery,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<TextQueryStatistics>,
+}
+
+impl From<TextQuery> for TextQueryInternal {
+    fn from(query: TextQuery) -> Self {
+        Self {
+            query,
+            statistics: None,
+        }
+    }
+}
+
+/// Statistics of the text query tokens over the indexed documents, required for BM25 scoring.
+///
+/// Collected over all segments before scoring any of them, so the scores of points from different
+/// segments are comparable.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct TextQueryStatistics {
+    /// Number of indexed documents
+    pub points_count: usize,
+    /// Total length of the indexed documents, in tokens
+    pub total_length: usize,
+    /// Number of indexed documents containing each of the query tokens
+    pub doc_frequencies: BTreeMap<String, usize>,
+}
+
+impl TextQueryStatistics {
+    pub fn merge(&mut self, other: &Self) {
+        let Self {
+            points_count,
+            total_length,
+            doc_frequencies,
+        } = other;
+
+        self.points_count += points_count;
+        self.total_length += total_length;
+        for (token, frequency) in doc_frequencies {
+            *self.doc_frequencies.entry(token.clone()).or_default() += frequency;
+        }
+    }
+
+    /// Inverse document frequency of the query token, zero for tokens of no document
+    pub fn idf(&self, token: &str) -> f32 {
+        let df = self.doc_frequencies.get(token).copied().unwrap_or(0);
+        if df == 0 {
+            return 0.0;
+        }
+        let n = self.points_count.max(df) as f32;
+        let df = df as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    /// Term frequency normalization with the default BM25 parameters
+    pub fn bm25(&self) -> Bm25 {
+        let Bm25Params { k1, b } = Bm25Params::default();
+
+        let avg_doc_length = if self.points_count > 0 {
+            self.total_length as f32 / self.points_count as f32
+        } else {
+            0.0
+        };
+
+        Bm25 {
+            k1: k1.into_inner() as f32,
+            b: b.into_inner() as f32,
+            avg_doc_length,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statistics(
+        points_count: usize,
+        total_length: usize,
+        dfs: &[(&str, usize)],
+    ) -> TextQueryStatistics {
+        TextQueryStatistics {
+            points_count,
+            total_length,
+            doc_frequencies: dfs
+                .iter()
+                .map(|(token, df)| ((*token).to_owned(), *df))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_statistics() {
+        let mut merged = statistics(10, 100, &[("cat", 2), ("dog", 1)]);
+        merged.merge(&statistics(5, 20, &[("cat", 3), ("fish", 4)]));
+
+        assert_eq!(
+            merged,
+            statistics(15, 120, &[("cat", 5), ("dog", 1), ("fish", 4)]),
+        );
+        assert_eq!(merged.bm25().avg_doc_length, 8.0);
+    }
+
+    #[test]
+    fn test_idf() {
+        let statistics = statistics(100, 1000, &[("rare", 1), ("common", 90)]);
+
+        assert!(statistics.idf("rare") > statistics.idf("common"));
+        assert!(statistics.idf("common") > 0.0);
+        assert_eq!(statistics.idf("unknown"), 0.0);
+    }
+}
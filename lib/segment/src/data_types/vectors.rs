@@ -3,7 +3,7 @@ use std::hash::Hash;
 use std::mem;
 use std::slice::ChunksExactMut;
 
-use half::f16;
+use half::{bf16, f16};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use schemars::JsonSchema;
@@ -254,6 +254,10 @@ pub type VectorElementType = f32;
 
 pub type VectorElementTypeHalf = f16;
 
+pub type VectorElementTypeDouble = f64;
+
+pub type VectorElementTypeBfloat16 = bf16;
+
 pub type VectorElementTypeByte = u8;
 
 pub const DEFAULT_VECTOR_NAME: &VectorName = "";
@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -6,6 +6,8 @@ use std::sync::atomic::AtomicBool;
 use ahash::AHashMap;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::{DeferredBehavior, TelemetryDetail};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
 use uuid::Uuid;
 
 use crate::common::Flusher;
@@ -16,16 +18,21 @@ use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
 use crate::data_types::query_context::{FormulaContext, QueryContext, SegmentQueryContext};
 use crate::data_types::segment_record::SegmentRecord;
+use crate::data_types::text_query::{TextQuery, TextQueryStatistics};
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::snapshot_entry::SnapshotEntry;
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::hybrid_index::HybridQuery;
+use crate::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
     ExtendedPointId, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef,
-    PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorName, VectorNameBuf, WithPayload, WithVector,
+    PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentDiskUsage, SegmentInfo,
+    SegmentMemoryUsage, SegmentType, SeqNumberType, VectorName, VectorNameBuf, WithPayload,
+    WithVector,
 };
+use crate::utils::mem::MappedFilesResidency;
 
 /// Define all operations on segment that do not require mutable access.
 ///
@@ -42,6 +49,14 @@ pub trait ReadSegmentEntry: SnapshotEntry {
     /// Returns `None` if point does not exist or is soft-deleted.
     fn point_version(&self, point_id: PointIdType) -> Option<SeqNumberType>;
 
+    /// Get expiration timestamp of specified point, in seconds since the Unix epoch
+    ///
+    /// Returns `None` if point does not exist or never expires.
+    fn point_expiration(&self, point_id: PointIdType) -> Option<u64>;
+
+    /// Check if there are points, which are expired at `now` and not deleted yet
+    fn has_expired_points(&self, now: u64) -> bool;
+
     #[allow(clippy::too_many_arguments)]
     fn search_batch(
         &self,
@@ -71,6 +86,19 @@ pub trait ReadSegmentEntry: SnapshotEntry {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<Option<VectorInternal>>;
 
+    /// Vector of the point, as it was after the operation with `version`.
+    ///
+    /// Previous versions are only available for vectors with history enabled, see
+    /// [`crate::types::VectorDataConfig::max_versions`]. Other vectors are returned only if the
+    /// point was not changed after `version`.
+    fn vector_at_version(
+        &self,
+        vector_name: &VectorName,
+        point_id: PointIdType,
+        version: SeqNumberType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<VectorInternal>>;
+
     fn all_vectors(
         &self,
         point_id: PointIdType,
@@ -100,6 +128,28 @@ pub trait ReadSegmentEntry: SnapshotEntry {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<Payload>;
 
+    /// Retrieve binary blob attached to the point
+    /// If the point has no blob, return `None`
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<Vec<u8>>>;
+
+    /// Convert token keyed sparse vector into dimension ids, using the vocabulary of `vector_name`
+    fn remap_sparse_vector(
+        &self,
+        vector_name: &VectorName,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector>;
+
+    /// All token mappings registered in the vocabulary of sparse vector `vector_name`
+    fn sparse_vocabulary(
+        &self,
+        vector_name: &VectorName,
+    ) -> OperationResult<BTreeMap<String, DimId>>;
+
     /// Iterator over all points in segment in ascending order.
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
@@ -131,6 +181,49 @@ pub trait ReadSegmentEntry: SnapshotEntry {
         deferred_behavior: DeferredBehavior,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>>;
 
+    /// Collect statistics of the text query tokens over the full-text index of the `query.key`.
+    ///
+    /// Statistics of all segments are merged and used to score points in each of them.
+    /// Will fail if there is no full-text index for the key.
+    fn text_query_statistics(
+        &self,
+        query: &TextQuery,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<TextQueryStatistics>;
+
+    /// Return points which satisfies filtering condition, scored by BM25 relevance to the text
+    /// query, the most relevant first. Points without any of the query tokens are not returned.
+    ///
+    /// Returned points have no payload and vectors.
+    /// Will fail if there is no full-text index for the `query.key`.
+    /// Cancelled by `is_stopped` flag.
+    #[allow(clippy::too_many_arguments)]
+    fn read_text_scored_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+        deferred_behavior: DeferredBehavior,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
+    /// Search by the dense and sparse parts of the hybrid query at once, the most relevant first.
+    ///
+    /// Returned points have no payload and vectors.
+    /// Will fail if any of the `query` vectors does not exist in the segment.
+    /// Cancelled by `is_stopped` flag.
+    fn hybrid_search(
+        &self,
+        query: &HybridQuery,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Vec<ScoredPoint>>;
+
     /// Return random points which satisfies filtering condition.
     ///
     /// Cancelled by `is_stopped` flag.
@@ -223,6 +316,12 @@ pub trait ReadSegmentEntry: SnapshotEntry {
     /// This returns `SegmentInfo` with some non size-related data (like `schema`) unset to improve performance.
     fn size_info(&self) -> SegmentInfo;
 
+    /// Estimate RAM used by components of the segment.
+    fn memory_usage(&self, residency: &MappedFilesResidency) -> SegmentMemoryUsage;
+
+    /// Disk space taken by components of the segment.
+    fn disk_usage(&self) -> SegmentDiskUsage;
+
     /// Get segment configuration
     fn config(&self) -> &SegmentConfig;
 
@@ -300,6 +399,17 @@ pub trait NonAppendableSegmentEntry: StorageSegmentEntry {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<bool>;
 
+    /// Delete points, which are expired at `now`, the same way as [`Self::delete_point`] does.
+    ///
+    /// Only the id tracker is updated, vectors of expired points are reclaimed by the optimizer.
+    /// Expiration is not a versioned operation, it is applied by each replica independently.
+    /// Returns number of deleted points.
+    fn delete_expired_points(
+        &mut self,
+        now: u64,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<usize>;
+
     /// Delete field index, if exists
     fn delete_field_index(
         &mut self,
@@ -432,4 +542,32 @@ pub trait SegmentEntry: NonAppendableSegmentEntry {
         point_id: PointIdType,
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<bool>;
+
+    /// Set expiration timestamp of the point, in seconds since the Unix epoch.
+    /// `None` removes the expiration.
+    fn set_point_expiration(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        expires_at: Option<u64>,
+    ) -> OperationResult<bool>;
+
+    /// Attach binary blob to the point, replacing the previous one.
+    /// `None` removes the blob.
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        blob: Option<&[u8]>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool>;
+
+    /// Register token mappings in the vocabulary of sparse vector `vector_name`.
+    ///
+    /// Returns `true` if any new token was registered.
+    fn register_sparse_vocabulary(
+        &mut self,
+        vector_name: &VectorName,
+        mapping: &BTreeMap<String, DimId>,
+    ) -> OperationResult<bool>;
 }
@@ -0,0 +1,170 @@
+//! Multi-threaded exact search.
+//!
+//! Exact search scores every point of a segment, so on large segments it is bound by CPU.
+//! Points are split into contiguous blocks, which are scored concurrently on a dedicated pool.
+//! Within a block, vectors are read and scored in batches for all queries at once, so each batch
+//! is scored by batch kernels of the vector storage while it is still in cache. Top results of
+//! the blocks are merged at the end.
+
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use itertools::Itertools as _;
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
+use crate::common::operation_error::OperationResult;
+use crate::data_types::vectors::QueryVector;
+use crate::index::hnsw_index::point_scorer::BatchFilteredSearcher;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::vector_storage::VectorStorageEnum;
+
+/// Minimal number of points in a block. Smaller blocks don't pay off the scheduling overhead.
+pub const EXACT_SEARCH_MIN_BLOCK_SIZE: usize = 16 * 1024;
+
+static EXACT_SEARCH_POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+
+fn exact_search_pool() -> Option<&'static ThreadPool> {
+    EXACT_SEARCH_POOL
+        .get_or_init(|| {
+            rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("exact-search-{idx}"))
+                .num_threads(common::cpu::get_num_cpus())
+                .build()
+                .inspect_err(|err| {
+                    log::error!("Failed to create exact search pool, searching in place: {err}");
+                })
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Score all `points` against `queries` with original vectors, and return `top` results for
+/// each query.
+///
+/// Points are scored in parallel, unless there are too few of them to fill two blocks.
+pub fn search_exact(
+    queries: &[&QueryVector],
+    vector_storage: &VectorStorageEnum,
+    points: &[PointOffsetType],
+    top: usize,
+    point_deleted: &BitSlice,
+    hardware_counter: HardwareCounterCell,
+    is_stopped: &AtomicBool,
+) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+    let search_block = |block: &[PointOffsetType],
+                        hardware_counter: HardwareCounterCell|
+     -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let searcher = BatchFilteredSearcher::new(
+            queries,
+            vector_storage,
+            None,
+            None,
+            top,
+            point_deleted,
+            hardware_counter,
+        )?;
+        Ok(searcher.peek_top_iter(block.iter().copied(), is_stopped)?)
+    };
+
+    let pool = match exact_search_pool() {
+        Some(pool) if points.len() >= 2 * EXACT_SEARCH_MIN_BLOCK_SIZE => pool,
+        _ => return search_block(points, hardware_counter),
+    };
+
+    let block_size = points
+        .len()
+        .div_ceil(pool.current_num_threads())
+        .max(EXACT_SEARCH_MIN_BLOCK_SIZE);
+    let blocks = points
+        .chunks(block_size)
+        .map(|block| (block, hardware_counter.fork()))
+        .collect_vec();
+
+    let block_results: Vec<Vec<Vec<ScoredPointOffset>>> = pool.install(|| {
+        blocks
+            .into_par_iter()
+            .map(|(block, hardware_counter)| search_block(block, hardware_counter))
+            .collect::<OperationResult<_>>()
+    })?;
+
+    let results = (0..queries.len())
+        .map(|query_idx| {
+            peek_top_largest_iterable(
+                block_results
+                    .iter()
+                    .flat_map(|block_result| block_result[query_idx].iter().copied()),
+                top,
+            )
+        })
+        .collect();
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use common::bitvec::BitVec;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::fixtures::index_fixtures::{TestRawScorerProducer, random_vector};
+    use crate::types::Distance;
+    use crate::vector_storage::DEFAULT_STOPPED;
+
+    #[test]
+    fn test_parallel_search_matches_single_block() {
+        let num_vectors = 3 * EXACT_SEARCH_MIN_BLOCK_SIZE + 7;
+        let dim = 4;
+        let top = 10;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder =
+            TestRawScorerProducer::new(dim, Distance::Dot, num_vectors, false, &mut rng);
+        let queries: Vec<QueryVector> = (0..3)
+            .map(|_| random_vector(&mut rng, dim).into())
+            .collect();
+        let query_refs = queries.iter().collect_vec();
+
+        let mut point_deleted = BitVec::repeat(false, num_vectors);
+        for point_id in (0..num_vectors).step_by(5) {
+            point_deleted.set(point_id, true);
+        }
+        let points = (0..num_vectors as PointOffsetType).collect_vec();
+
+        let results = search_exact(
+            &query_refs,
+            vector_holder.storage(),
+            &points,
+            top,
+            &point_deleted,
+            HardwareCounterCell::new(),
+            &DEFAULT_STOPPED,
+        )
+        .unwrap();
+
+        let expected = BatchFilteredSearcher::new(
+            &query_refs,
+            vector_holder.storage(),
+            None,
+            None,
+            top,
+            &point_deleted,
+            HardwareCounterCell::new(),
+        )
+        .unwrap()
+        .peek_top_iter(points.iter().copied(), &DEFAULT_STOPPED)
+        .unwrap();
+
+        assert_eq!(results, expected);
+        assert!(
+            results
+                .iter()
+                .flatten()
+                .all(|scored| !point_deleted[scored.idx as usize])
+        );
+    }
+}
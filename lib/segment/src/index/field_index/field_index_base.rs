@@ -182,15 +182,21 @@ impl FieldIndex {
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BoolIndex(_) => None,
-            FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
-                Some(Match::Text(MatchText { text })) => Some(
-                    full_text_index.check_payload_match::<false>(payload_value, text, hw_counter),
-                ),
-                Some(Match::Phrase(MatchPhrase { phrase })) => Some(
-                    full_text_index.check_payload_match::<true>(payload_value, phrase, hw_counter),
-                ),
-                _ => None,
-            },
+            FieldIndex::FullTextIndex(full_text_index) => {
+                let query_opt = match &condition.r#match {
+                    Some(Match::Text(MatchText { text })) => {
+                        full_text_index.parse_text_query(text, hw_counter)
+                    }
+                    Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                        full_text_index.parse_phrase_query(phrase, *slop, hw_counter)
+                    }
+                    _ => return None,
+                };
+                // Queries with unseen tokens can't match
+                Some(query_opt.is_some_and(|query| {
+                    full_text_index.check_payload_match(payload_value, &query, hw_counter)
+                }))
+            }
             FieldIndex::UuidIndex(_) => None,
             FieldIndex::UuidMapIndex(_) => None,
             FieldIndex::NullIndex(_) => None,
@@ -271,6 +277,30 @@ impl FieldIndex {
             .payload_blocks(threshold, key)
     }
 
+    /// Blocks of wider ranges of numeric and datetime values, see
+    /// [`NumericIndexInner::coarse_range_blocks`](super::numeric_index::NumericIndexInner::coarse_range_blocks).
+    ///
+    /// Empty for other indexes.
+    pub fn coarse_range_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Vec<PayloadBlockCondition> {
+        match self {
+            FieldIndex::IntIndex(index) => index.inner().coarse_range_blocks(threshold, key),
+            FieldIndex::DatetimeIndex(index) => index.inner().coarse_range_blocks(threshold, key),
+            FieldIndex::FloatIndex(index) => index.inner().coarse_range_blocks(threshold, key),
+            FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BoolIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::NullIndex(_) => Vec::new(),
+        }
+    }
+
     pub fn add_point(
         &mut self,
         id: PointOffsetType,
@@ -394,6 +424,22 @@ impl FieldIndex {
         }
     }
 
+    pub fn as_full_text(&self) -> Option<&FullTextIndex> {
+        match self {
+            FieldIndex::FullTextIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::BoolIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::NullIndex(_) => None,
+        }
+    }
+
     pub fn as_facet_index(&self) -> Option<FacetIndexEnum<'_>> {
         match self {
             FieldIndex::KeywordIndex(index) => Some(FacetIndexEnum::Keyword(index)),
@@ -174,10 +174,12 @@ impl ImmutableInvertedIndex {
         }
     }
 
-    /// Iterate over point ids whose documents contain all given tokens in the same order they are provided
+    /// Iterate over point ids whose documents contain all given tokens in the same order they are provided,
+    /// with at most `slop` other tokens in between
     pub fn filter_has_phrase<'a>(
         &'a self,
         phrase: Document,
+        slop: u32,
     ) -> impl Iterator<Item = PointOffsetType> + 'a {
         // in case of mmap immutable index, deleted points are still in the postings
         let is_active = move |idx| {
@@ -190,6 +192,7 @@ impl ImmutableInvertedIndex {
             ImmutablePostings::WithPositions(postings) => {
                 Either::Right(intersect_compressed_postings_phrase_iterator(
                     phrase,
+                    slop,
                     |token_id| postings.get(*token_id as usize).map(PostingList::view),
                     is_active,
                 ))
@@ -199,8 +202,14 @@ impl ImmutableInvertedIndex {
         }
     }
 
-    /// Checks if the point document contains all given tokens in the same order they are provided
-    pub fn check_has_phrase(&self, phrase: &Document, point_id: PointOffsetType) -> bool {
+    /// Checks if the point document contains all given tokens in the same order they are provided,
+    /// with at most `slop` other tokens in between
+    pub fn check_has_phrase(
+        &self,
+        phrase: &Document,
+        slop: u32,
+        point_id: PointOffsetType,
+    ) -> bool {
         // in case of mmap immutable index, deleted points are still in the postings
         if self
             .point_to_tokens_count
@@ -212,7 +221,7 @@ impl ImmutableInvertedIndex {
 
         match &self.postings {
             ImmutablePostings::WithPositions(postings) => {
-                check_compressed_postings_phrase(phrase, point_id, |token_id| {
+                check_compressed_postings_phrase(phrase, slop, point_id, |token_id| {
                     postings.get(*token_id as usize).map(PostingList::view)
                 })
             }
@@ -265,7 +274,7 @@ impl InvertedIndex for ImmutableInvertedIndex {
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
         match query {
             ParsedQuery::AllTokens(tokens) => Box::new(self.filter_has_all(tokens)),
-            ParsedQuery::Phrase(tokens) => Box::new(self.filter_has_phrase(tokens)),
+            ParsedQuery::Phrase(tokens, slop) => Box::new(self.filter_has_phrase(tokens, slop)),
             ParsedQuery::AnyTokens(tokens) => Box::new(self.filter_has_any(tokens)),
         }
     }
@@ -274,6 +283,18 @@ impl InvertedIndex for ImmutableInvertedIndex {
         self.postings.posting_len(token_id)
     }
 
+    fn token_frequencies(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_> {
+        let Some(frequencies) = self.postings.iter_frequencies(token_id) else {
+            return Box::new(std::iter::empty());
+        };
+
+        // in case of immutable index, deleted documents are still in the postings
+        Box::new(frequencies.filter(|&(point_id, _)| !self.values_is_empty(point_id)))
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &token_id)| {
             self.postings
@@ -285,7 +306,7 @@ impl InvertedIndex for ImmutableInvertedIndex {
     fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         match parsed_query {
             ParsedQuery::AllTokens(tokens) => self.check_has_subset(tokens, point_id),
-            ParsedQuery::Phrase(phrase) => self.check_has_phrase(phrase, point_id),
+            ParsedQuery::Phrase(phrase, slop) => self.check_has_phrase(phrase, *slop, point_id),
             ParsedQuery::AnyTokens(tokens) => self.check_has_any(tokens, point_id),
         }
     }
@@ -303,6 +324,11 @@ impl InvertedIndex for ImmutableInvertedIndex {
             .unwrap_or(0)
     }
 
+    fn total_values_count(&self) -> usize {
+        // Counts of deleted points are reset to zero
+        self.point_to_tokens_count.iter().sum()
+    }
+
     fn points_count(&self) -> usize {
         self.points_count
     }
@@ -1,4 +1,3 @@
-#[cfg(test)]
 use common::types::PointOffsetType;
 use posting_list::PostingList;
 
@@ -31,6 +30,30 @@ impl ImmutablePostings {
         }
     }
 
+    /// Iterate over the points in the posting of the token, with the number of its occurrences.
+    ///
+    /// Without positional information, each document counts as a single occurrence.
+    pub fn iter_frequencies(
+        &self,
+        token_id: TokenId,
+    ) -> Option<Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_>> {
+        match self {
+            ImmutablePostings::Ids(postings) => postings.get(token_id as usize).map(|posting| {
+                Box::new(posting.iter().map(|elem| (elem.id, 1)))
+                    as Box<dyn Iterator<Item = (PointOffsetType, u32)>>
+            }),
+            ImmutablePostings::WithPositions(postings) => {
+                postings.get(token_id as usize).map(|posting| {
+                    Box::new(
+                        posting
+                            .iter()
+                            .map(|elem| (elem.id, elem.value.len() as u32)),
+                    ) as Box<dyn Iterator<Item = (PointOffsetType, u32)>>
+                })
+            }
+        }
+    }
+
     #[cfg(test)]
     pub fn iter_ids(
         &self,
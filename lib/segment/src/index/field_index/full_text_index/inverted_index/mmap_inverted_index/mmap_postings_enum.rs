@@ -1,4 +1,3 @@
-#[cfg(test)]
 use common::types::PointOffsetType;
 
 use crate::index::field_index::full_text_index::inverted_index::TokenId;
@@ -27,6 +26,27 @@ impl MmapPostingsEnum {
         }
     }
 
+    /// Iterate over the points in the posting of the token, with the number of its occurrences.
+    ///
+    /// Without positional information, each document counts as a single occurrence.
+    pub fn iter_frequencies<'a>(
+        &'a self,
+        token_id: TokenId,
+    ) -> Option<Box<dyn Iterator<Item = (PointOffsetType, u32)> + 'a>> {
+        match self {
+            MmapPostingsEnum::Ids(postings) => postings.get(token_id).map(|view| {
+                Box::new(view.into_iter().map(|elem| (elem.id, 1)))
+                    as Box<dyn Iterator<Item = (PointOffsetType, u32)>>
+            }),
+            MmapPostingsEnum::WithPositions(postings) => postings.get(token_id).map(|view| {
+                Box::new(
+                    view.into_iter()
+                        .map(|elem| (elem.id, elem.value.len() as u32)),
+                ) as Box<dyn Iterator<Item = (PointOffsetType, u32)>>
+            }),
+        }
+    }
+
     #[cfg(test)]
     pub fn iter_ids<'a>(
         &'a self,
@@ -325,10 +325,12 @@ impl MmapInvertedIndex {
         }
     }
 
-    /// Iterate over point ids whose documents contain all given tokens in the same order they are provided
+    /// Iterate over point ids whose documents contain all given tokens in the same order they are provided,
+    /// with at most `slop` other tokens in between
     pub fn filter_has_phrase<'a>(
         &'a self,
         phrase: Document,
+        slop: u32,
     ) -> impl Iterator<Item = PointOffsetType> + 'a {
         // in case of mmap immutable index, deleted points are still in the postings
         let is_active = move |idx| self.is_active(idx);
@@ -337,6 +339,7 @@ impl MmapInvertedIndex {
             MmapPostingsEnum::WithPositions(postings) => {
                 Either::Right(intersect_compressed_postings_phrase_iterator(
                     phrase,
+                    slop,
                     |token_id| postings.get(*token_id),
                     is_active,
                 ))
@@ -346,7 +349,12 @@ impl MmapInvertedIndex {
         }
     }
 
-    pub fn check_has_phrase(&self, phrase: &Document, point_id: PointOffsetType) -> bool {
+    pub fn check_has_phrase(
+        &self,
+        phrase: &Document,
+        slop: u32,
+        point_id: PointOffsetType,
+    ) -> bool {
         // in case of mmap immutable index, deleted points are still in the postings
         if !self.is_active(point_id) {
             return false;
@@ -354,7 +362,7 @@ impl MmapInvertedIndex {
 
         match &self.storage.postings {
             MmapPostingsEnum::WithPositions(postings) => {
-                check_compressed_postings_phrase(phrase, point_id, |token_id| {
+                check_compressed_postings_phrase(phrase, slop, point_id, |token_id| {
                     postings.get(*token_id)
                 })
             }
@@ -463,7 +471,7 @@ impl InvertedIndex for MmapInvertedIndex {
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
         match query {
             ParsedQuery::AllTokens(tokens) => self.filter_has_all(tokens),
-            ParsedQuery::Phrase(phrase) => Box::new(self.filter_has_phrase(phrase)),
+            ParsedQuery::Phrase(phrase, slop) => Box::new(self.filter_has_phrase(phrase, slop)),
             ParsedQuery::AnyTokens(tokens) => Box::new(self.filter_has_any(tokens)),
         }
     }
@@ -476,6 +484,18 @@ impl InvertedIndex for MmapInvertedIndex {
         self.storage.postings.posting_len(token_id)
     }
 
+    fn token_frequencies(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_> {
+        let Some(frequencies) = self.storage.postings.iter_frequencies(token_id) else {
+            return Box::new(std::iter::empty());
+        };
+
+        // in case of mmap immutable index, deleted points are still in the postings
+        Box::new(frequencies.filter(|&(point_id, _)| self.is_active(point_id)))
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.iter_vocab().filter_map(move |(token, &token_id)| {
             self.storage
@@ -488,7 +508,7 @@ impl InvertedIndex for MmapInvertedIndex {
     fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         match parsed_query {
             ParsedQuery::AllTokens(tokens) => self.check_has_subset(tokens, point_id),
-            ParsedQuery::Phrase(phrase) => self.check_has_phrase(phrase, point_id),
+            ParsedQuery::Phrase(phrase, slop) => self.check_has_phrase(phrase, *slop, point_id),
             ParsedQuery::AnyTokens(tokens) => self.check_has_any(tokens, point_id),
         }
     }
@@ -528,6 +548,11 @@ impl InvertedIndex for MmapInvertedIndex {
             .unwrap_or(0)
     }
 
+    fn total_values_count(&self) -> usize {
+        // Counts of deleted points are reset to zero
+        self.storage.point_to_tokens_count.iter().sum()
+    }
+
     fn points_count(&self) -> usize {
         self.active_points_count
     }
@@ -10,10 +10,11 @@ mod postings_iterator;
 use std::cmp::min;
 use std::collections::HashMap;
 
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet};
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use itertools::Itertools;
+use sparse::common::bm25::Bm25;
 
 use crate::common::operation_error::OperationResult;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
@@ -116,10 +117,11 @@ impl Document {
         self.0.iter().copied().collect()
     }
 
-    /// Checks if the current document contains the given phrase.
+    /// Checks if the current document contains the given phrase,
+    /// with at most `slop` other tokens in between.
     ///
     /// Returns false if the phrase is empty
-    pub fn has_phrase(&self, phrase: &Document) -> bool {
+    pub fn has_phrase(&self, phrase: &Document, slop: u32) -> bool {
         let doc = self.0.as_slice();
         let phrase = phrase.0.as_slice();
 
@@ -127,11 +129,50 @@ impl Document {
             return false;
         }
 
+        if slop > 0 {
+            return has_phrase_within_slop(doc.len(), |i| i as u32, |i| doc[i], phrase, slop);
+        }
+
         // simple check for tokens in the same order as phrase
         doc.windows(phrase.len()).any(|window| window == phrase)
     }
 }
 
+/// Checks if a document contains the `phrase` tokens in the same order,
+/// with at most `slop` other positions in between, in total.
+///
+/// Document is given by its length and accessors of the position and token of the i-th entry,
+/// entries must be ordered by position.
+fn has_phrase_within_slop(
+    len: usize,
+    position: impl Fn(usize) -> u32,
+    token: impl Fn(usize) -> TokenId,
+    phrase: &[TokenId],
+    slop: u32,
+) -> bool {
+    let Some((first, rest)) = phrase.split_first() else {
+        return false;
+    };
+    let max_span = (rest.len() as u32).saturating_add(slop);
+
+    (0..len)
+        .filter(|&start| token(start) == *first)
+        .any(|start| {
+            let max_position = position(start).saturating_add(max_span);
+            let mut next = start + 1;
+            // Taking the earliest occurrence of each token leaves the most room for the next ones
+            rest.iter().all(|phrase_token| {
+                while next < len && position(next) <= max_position {
+                    next += 1;
+                    if token(next - 1) == *phrase_token {
+                        return true;
+                    }
+                }
+                false
+            })
+        })
+}
+
 impl IntoIterator for Document {
     type Item = TokenId;
     type IntoIter = std::vec::IntoIter<TokenId>;
@@ -160,7 +201,9 @@ pub enum ParsedQuery {
     AnyTokens(TokenSet),
 
     /// All these tokens must be present in the document, in the same order as this query.
-    Phrase(Document),
+    ///
+    /// With at most this slop of other tokens in between them, 0 for an exact phrase.
+    Phrase(Document, u32),
 }
 
 pub trait InvertedIndex {
@@ -219,6 +262,36 @@ pub trait InvertedIndex {
     fn get_posting_len(&self, token_id: TokenId, hw_counter: &HardwareCounterCell)
     -> Option<usize>;
 
+    /// Iterate over the points whose documents contain the token, with the number of its
+    /// occurrences in the document.
+    ///
+    /// Occurrences are only known with positional information, otherwise each document counts as
+    /// a single occurrence.
+    fn token_frequencies(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_>;
+
+    /// Score the points, whose documents contain any of the tokens, by BM25 relevance.
+    ///
+    /// Each token is given with its IDF weight. Length of a document is its number of distinct
+    /// tokens, same as the [`InvertedIndex::values_count`].
+    fn score_bm25(
+        &self,
+        weighted_tokens: &[(TokenId, f32)],
+        bm25: &Bm25,
+    ) -> AHashMap<PointOffsetType, ScoreType> {
+        let mut scores = AHashMap::new();
+        for &(token_id, idf) in weighted_tokens {
+            for (point_id, frequency) in self.token_frequencies(token_id) {
+                let doc_length = self.values_count(point_id) as f32;
+                *scores.entry(point_id).or_default() +=
+                    idf * bm25.weight(frequency as f32, doc_length);
+            }
+        }
+        scores
+    }
+
     fn estimate_cardinality(
         &self,
         query: &ParsedQuery,
@@ -229,8 +302,8 @@ pub trait InvertedIndex {
             ParsedQuery::AllTokens(tokens) => {
                 self.estimate_has_subset_cardinality(tokens, condition, hw_counter)
             }
-            ParsedQuery::Phrase(phrase) => {
-                self.estimate_has_phrase_cardinality(phrase, condition, hw_counter)
+            ParsedQuery::Phrase(phrase, slop) => {
+                self.estimate_has_phrase_cardinality(phrase, *slop, condition, hw_counter)
             }
             ParsedQuery::AnyTokens(tokens) => {
                 self.estimate_has_any_cardinality(tokens, condition, hw_counter)
@@ -326,6 +399,7 @@ pub trait InvertedIndex {
     fn estimate_has_phrase_cardinality(
         &self,
         phrase: &Document,
+        slop: u32,
         condition: &FieldCondition,
         hw_counter: &HardwareCounterCell,
     ) -> CardinalityEstimation {
@@ -339,14 +413,19 @@ pub trait InvertedIndex {
         let subset_estimation =
             self.estimate_has_subset_cardinality(&tokenset, condition, hw_counter);
 
-        // But we can restrict it by considering the phrase length
-        let phrase_sq = phrase.len() * phrase.len();
+        // But we can restrict it by considering the phrase length,
+        // sloppy phrases are less restrictive than exact ones
+        let phrase_factor = if slop == 0 {
+            phrase.len() * phrase.len()
+        } else {
+            phrase.len()
+        };
 
         CardinalityEstimation {
             primary_clauses: vec![PrimaryCondition::Condition(Box::new(condition.clone()))],
-            min: subset_estimation.min / phrase_sq,
-            exp: subset_estimation.exp / phrase_sq,
-            max: subset_estimation.max / phrase_sq,
+            min: subset_estimation.min / phrase_factor,
+            exp: subset_estimation.exp / phrase_factor,
+            max: subset_estimation.max / phrase_factor,
         }
     }
 
@@ -380,6 +459,9 @@ pub trait InvertedIndex {
 
     fn values_count(&self, point_id: PointOffsetType) -> usize;
 
+    /// Sum of the [`InvertedIndex::values_count`] of all points
+    fn total_values_count(&self) -> usize;
+
     fn points_count(&self) -> usize;
 
     fn get_token_id(&self, token: &str, hw_counter: &HardwareCounterCell) -> Option<TokenId>;
@@ -705,4 +787,27 @@ mod tests {
             assert_eq!(imm_filtered, imm_mmap_filtered);
         }
     }
+
+    #[test]
+    fn test_document_has_phrase_with_slop() {
+        // quick brown fox jumps over the lazy dog
+        let document = Document(vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let quick_fox = Document(vec![1, 3]);
+        assert!(!document.has_phrase(&quick_fox, 0));
+        assert!(document.has_phrase(&quick_fox, 1));
+
+        // Order of the tokens matters regardless of slop
+        let fox_quick = Document(vec![3, 1]);
+        assert!(!document.has_phrase(&fox_quick, 10));
+
+        let quick_fox_dog = Document(vec![1, 3, 8]);
+        assert!(!document.has_phrase(&quick_fox_dog, 4));
+        assert!(document.has_phrase(&quick_fox_dog, 5));
+
+        // Later occurrence of the first token can be closer to the rest
+        let document = Document(vec![1, 2, 2, 2, 1, 2, 3]);
+        assert!(document.has_phrase(&quick_fox, 1));
+        assert!(!document.has_phrase(&Document(vec![]), 1));
+    }
 }
@@ -101,6 +101,7 @@ impl MutableInvertedIndex {
     pub fn filter_has_phrase(
         &self,
         phrase: Document,
+        slop: u32,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let Some(point_to_doc) = self.point_to_doc.as_ref() else {
             // Return empty iterator when not enabled
@@ -114,7 +115,7 @@ impl MutableInvertedIndex {
                     .as_ref()
                     .expect("if it passed the intersection filter, it must exist");
 
-                doc.has_phrase(&phrase)
+                doc.has_phrase(&phrase, slop)
             });
 
         Box::new(iter)
@@ -229,7 +230,7 @@ impl InvertedIndex for MutableInvertedIndex {
     ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         match query {
             ParsedQuery::AllTokens(tokens) => Box::new(self.filter_has_all(tokens)),
-            ParsedQuery::Phrase(phrase) => self.filter_has_phrase(phrase),
+            ParsedQuery::Phrase(phrase, slop) => self.filter_has_phrase(phrase, slop),
             ParsedQuery::AnyTokens(tokens) => Box::new(self.filter_has_any(tokens)),
         }
     }
@@ -238,6 +239,25 @@ impl InvertedIndex for MutableInvertedIndex {
         self.postings.get(token_id as usize).map(|x| x.len())
     }
 
+    fn token_frequencies(
+        &self,
+        token_id: TokenId,
+    ) -> Box<dyn Iterator<Item = (PointOffsetType, u32)> + '_> {
+        let Some(posting) = self.postings.get(token_id as usize) else {
+            return Box::new(std::iter::empty());
+        };
+
+        Box::new(posting.iter().map(move |point_id| {
+            let frequency = self.get_document(point_id).map_or(1, |doc| {
+                doc.tokens()
+                    .iter()
+                    .filter(|&&token| token == token_id)
+                    .count() as u32
+            });
+            (point_id, frequency)
+        }))
+    }
+
     fn vocab_with_postings_len_iter(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
         self.vocab.iter().filter_map(|(token, &posting_idx)| {
             self.postings
@@ -256,13 +276,13 @@ impl InvertedIndex for MutableInvertedIndex {
                 // Check that all tokens are in document
                 doc.has_subset(query)
             }
-            ParsedQuery::Phrase(document) => {
+            ParsedQuery::Phrase(document, slop) => {
                 let Some(doc) = self.get_document(point_id) else {
                     return false;
                 };
 
                 // Check that all tokens are in document, in order
-                doc.has_phrase(document)
+                doc.has_phrase(document, *slop)
             }
             ParsedQuery::AnyTokens(query) => {
                 let Some(doc) = self.get_tokens(point_id) else {
@@ -284,6 +304,14 @@ impl InvertedIndex for MutableInvertedIndex {
         self.get_tokens(point_id).map(|x| x.len()).unwrap_or(0)
     }
 
+    fn total_values_count(&self) -> usize {
+        self.point_to_tokens
+            .iter()
+            .flatten()
+            .map(TokenSet::len)
+            .sum()
+    }
+
     fn points_count(&self) -> usize {
         self.points_count
     }
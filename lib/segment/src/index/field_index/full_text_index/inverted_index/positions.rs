@@ -1,13 +1,19 @@
 use posting_list::{PostingValue, UnsizedHandler, UnsizedValue};
 use zerocopy::{FromBytes, IntoBytes};
 
-use crate::index::field_index::full_text_index::inverted_index::{Document, TokenId};
+use crate::index::field_index::full_text_index::inverted_index::{
+    Document, TokenId, has_phrase_within_slop,
+};
 
 /// Represents a list of positions of a token in a document.
 #[derive(Default, Clone, Debug)]
 pub struct Positions(Vec<u32>);
 
 impl Positions {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
@@ -71,7 +77,10 @@ impl PartialDocument {
     }
 
     /// Returns true if any sequential window of tokens match the given phrase.
-    pub fn has_phrase(&self, phrase: &Document) -> bool {
+    ///
+    /// With non-zero `slop`, tokens of the window may be separated by at most `slop` other
+    /// positions in total.
+    pub fn has_phrase(&self, phrase: &Document, slop: u32) -> bool {
         match phrase.tokens() {
             // no tokens in query -> no match
             [] => false,
@@ -79,6 +88,15 @@ impl PartialDocument {
             // single token -> match if any token matches
             [token] => self.0.iter().any(|tok_pos| tok_pos.token_id == *token),
 
+            // multiple tokens with slop -> match if tokens are close enough, in order
+            phrase if slop > 0 => has_phrase_within_slop(
+                self.0.len(),
+                |i| self.0[i].position,
+                |i| self.0[i].token_id,
+                phrase,
+                slop,
+            ),
+
             // multiple tokens -> match if any sequential window matches
             phrase => self.sequential_windows(phrase.len()).any(|seq_window| {
                 seq_window
@@ -87,6 +87,7 @@ pub fn merge_compressed_postings_iterator<'a, V: PostingValue + 'a>(
 /// Returns an iterator over the points that match the given phrase query.
 pub fn intersect_compressed_postings_phrase_iterator<'a>(
     phrase: Document,
+    slop: u32,
     token_to_posting: impl Fn(&TokenId) -> Option<PostingListView<'a, Positions>>,
     is_active: impl Fn(PointOffsetType) -> bool + 'a,
 ) -> impl Iterator<Item = PointOffsetType> + 'a {
@@ -132,6 +133,7 @@ pub fn intersect_compressed_postings_phrase_iterator<'a>(
             phrase_in_all_postings(
                 elem.id,
                 &phrase,
+                slop,
                 initial_tokens_positions,
                 &mut posting_iterators,
             )
@@ -143,7 +145,8 @@ pub fn intersect_compressed_postings_phrase_iterator<'a>(
 
 /// Reconstructs a partial document from the posting lists (which contain positions)
 ///
-/// Returns true if the document contains the entire phrase, in the same order.
+/// Returns true if the document contains the entire phrase, in the same order,
+/// with at most `slop` other tokens in between.
 ///
 /// # Arguments
 ///
@@ -151,6 +154,7 @@ pub fn intersect_compressed_postings_phrase_iterator<'a>(
 fn phrase_in_all_postings<'a>(
     id: PointOffsetType,
     phrase: &Document,
+    slop: u32,
     initial_tokens_positions: Vec<TokenPosition>,
     posting_iterators: &mut Vec<(TokenId, PostingIterator<'a, Positions>)>,
 ) -> bool {
@@ -171,11 +175,12 @@ fn phrase_in_all_postings<'a>(
         debug_assert!(!other.value.is_empty());
         tokens_positions.extend(other.value.to_token_positions(*token_id))
     }
-    PartialDocument::new(tokens_positions).has_phrase(phrase)
+    PartialDocument::new(tokens_positions).has_phrase(phrase, slop)
 }
 
 pub fn check_compressed_postings_phrase<'a>(
     phrase: &Document,
+    slop: u32,
     point_id: PointOffsetType,
     token_to_posting: impl Fn(&TokenId) -> Option<PostingListView<'a, Positions>>,
 ) -> bool {
@@ -190,7 +195,7 @@ pub fn check_compressed_postings_phrase<'a>(
         return false;
     };
 
-    phrase_in_all_postings(point_id, phrase, Vec::new(), &mut posting_iterators)
+    phrase_in_all_postings(point_id, phrase, slop, Vec::new(), &mut posting_iterators)
 }
 
 #[cfg(test)]
@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+
 use ahash::AHashSet;
 
 use crate::data_types::index::{Language, StopwordsInterface};
+use crate::index::field_index::full_text_index::tokenizers::ascii_folding::fold_to_ascii_cow;
 
 pub mod arabic;
 pub mod azerbaijani;
@@ -100,6 +103,16 @@ impl StopwordsFilter {
         this
     }
 
+    /// Fold the stopwords to ASCII, so they match tokens of an index with ASCII folding enabled
+    pub fn with_ascii_folding(self) -> Self {
+        let stopwords = self
+            .stopwords
+            .into_iter()
+            .map(|word| fold_to_ascii_cow(Cow::Owned(word)).into_owned())
+            .collect();
+        Self { stopwords }
+    }
+
     /// Check if a token is a stopword
     pub fn is_stopword(&self, token: &str) -> bool {
         self.stopwords.contains(token)
@@ -218,6 +231,20 @@ mod tests {
         assert!(!filter.is_stopword("WORLD"));
     }
 
+    #[test]
+    fn test_ascii_folded_stopwords() {
+        let option = Some(StopwordsInterface::new_set(&[Language::German], &["Déjà"]));
+
+        let filter = StopwordsFilter::new(&option, true);
+        assert!(filter.is_stopword("für"));
+        assert!(!filter.is_stopword("fur"));
+
+        // Folded tokens are matched by folded stopwords
+        let filter = StopwordsFilter::new(&option, true).with_ascii_folding();
+        assert!(filter.is_stopword("fur"));
+        assert!(filter.is_stopword("deja"));
+    }
+
     #[test]
     fn test_language_stopwords_case_sensitivity() {
         let option = Some(StopwordsInterface::Language(Language::English));
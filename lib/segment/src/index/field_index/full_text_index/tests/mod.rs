@@ -267,7 +267,7 @@ fn test_phrase_matching() {
 
         // Test phrase matching (should only match documents with exact phrase in order)
         let phrase_query = index
-            .parse_phrase_query("quick brown fox", &hw_counter)
+            .parse_phrase_query("quick brown fox", None, &hw_counter)
             .unwrap();
         assert!(index.check_match(&phrase_query, 0));
         assert!(index.check_match(&phrase_query, 2));
@@ -282,7 +282,7 @@ fn test_phrase_matching() {
 
         // Test phrase that doesn't exist
         let missing_query = index
-            .parse_phrase_query("fox brown quick", &hw_counter)
+            .parse_phrase_query("fox brown quick", None, &hw_counter)
             .unwrap();
         let missing_results: Vec<_> = index.filter_query(missing_query, &hw_counter).collect();
 
@@ -290,13 +290,14 @@ fn test_phrase_matching() {
         assert_eq!(missing_results.len(), 0);
 
         // Test valid phrase up to a token that doesn't exist
-        let query_with_unknown_token = index.parse_phrase_query("quick brown bird", &hw_counter);
+        let query_with_unknown_token =
+            index.parse_phrase_query("quick brown bird", None, &hw_counter);
         // the phrase query is not valid because it contains an unknown token
         assert!(query_with_unknown_token.is_none());
 
         // Test repeated words
         let phrase_query = index
-            .parse_phrase_query("brown brown fox", &hw_counter)
+            .parse_phrase_query("brown brown fox", None, &hw_counter)
             .unwrap();
         assert!(index.check_match(&phrase_query, 4));
 
@@ -304,12 +305,116 @@ fn test_phrase_matching() {
         let filter_results: Vec<_> = index.filter_query(phrase_query, &hw_counter).collect();
         assert_eq!(filter_results.len(), 1);
         assert!(filter_results.contains(&4));
+
+        // Test proximity syntax, allowing one other token in between, but still in order
+        let proximity_query = index
+            .parse_phrase_query("\"quick fox\"~1", None, &hw_counter)
+            .unwrap();
+        let proximity_results: Vec<_> = index.filter_query(proximity_query, &hw_counter).collect();
+        assert_eq!(proximity_results, vec![0, 2]);
+
+        // Test explicit slop
+        let proximity_query = index
+            .parse_phrase_query("the dog", Some(2), &hw_counter)
+            .unwrap();
+        assert!(index.check_match(&proximity_query, 0));
+        // Document 1 has three other tokens in between
+        assert!(!index.check_match(&proximity_query, 1));
+        let proximity_results: Vec<_> = index.filter_query(proximity_query, &hw_counter).collect();
+        assert_eq!(proximity_results, vec![0, 3]);
+
+        // Explicit slop overrides the one of the syntax
+        let exact_query = index
+            .parse_phrase_query("\"quick fox\"~1", Some(0), &hw_counter)
+            .unwrap();
+        let exact_results: Vec<_> = index.filter_query(exact_query, &hw_counter).collect();
+        assert!(exact_results.is_empty());
     };
 
     check_matching(mutable_index);
     check_matching(mmap_index);
 }
 
+#[test]
+fn test_bm25_scoring() {
+    let hw_counter = HardwareCounterCell::default();
+
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::default(),
+        min_token_len: None,
+        max_token_len: None,
+        lowercase: Some(true),
+        on_disk: None,
+        phrase_matching: Some(true), // Keep token positions, to know the term frequencies
+        stopwords: None,
+        stemmer: None,
+        ascii_folding: None,
+        enable_hnsw: None,
+    };
+
+    let mut mutable_index =
+        FullTextIndex::builder_gridstore(temp_dir.path().to_path_buf(), config.clone())
+            .make_empty()
+            .unwrap();
+
+    let mut mmap_builder =
+        FullTextIndex::builder_mmap(temp_dir.path().to_path_buf(), config.clone(), true);
+    mmap_builder.init().unwrap();
+
+    let documents = vec![
+        (0, "apple banana".to_string()),
+        (1, "apple apple apple banana cherry".to_string()),
+        (2, "cherry durian".to_string()),
+        (3, "banana".to_string()),
+    ];
+
+    for (point_id, text) in documents {
+        mutable_index
+            .add_many(point_id, vec![text.clone()], &hw_counter)
+            .unwrap();
+        mmap_builder
+            .add_many(point_id, vec![text], &hw_counter)
+            .unwrap();
+    }
+
+    let mmap_index = mmap_builder.finalize().unwrap();
+
+    let check_scoring = |index: FullTextIndex| {
+        let score = |text: &str| {
+            let statistics = index.text_query_statistics(text, &hw_counter);
+            index.score_text_query(text, &statistics, &hw_counter)
+        };
+
+        let statistics = index.text_query_statistics("apple durian", &hw_counter);
+        assert_eq!(statistics.points_count, 4);
+        assert_eq!(statistics.doc_frequencies["apple"], 2);
+        assert_eq!(statistics.doc_frequencies["durian"], 1);
+
+        // Only points containing the token are scored, more frequent token scores higher
+        let scores = score("Apple");
+        assert_eq!(scores.len(), 2);
+        assert!(scores[&1] > scores[&0]);
+
+        // Rarer token scores higher
+        let scores = score("durian banana");
+        assert_eq!(scores.len(), 4);
+        assert!(scores[&2] > scores[&3]);
+
+        // Point containing more of the query tokens scores higher
+        let scores = score("apple cherry");
+        assert!(scores[&1] > scores[&0]);
+        assert!(scores[&1] > scores[&2]);
+
+        // Unknown tokens don't score anything
+        assert!(score("grape").is_empty());
+    };
+
+    check_scoring(mutable_index);
+    check_scoring(mmap_index);
+}
+
 #[test]
 fn test_ascii_folding_in_full_text_index_word() {
     let hw_counter = HardwareCounterCell::default();
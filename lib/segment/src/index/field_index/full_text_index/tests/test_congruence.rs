@@ -264,7 +264,7 @@ pub fn to_parsed_query(
 
     let parsed = match is_phrase {
         false => ParsedQuery::AllTokens(tokens.collect::<Option<TokenSet>>()?),
-        true => ParsedQuery::Phrase(tokens.collect::<Option<Document>>()?),
+        true => ParsedQuery::Phrase(tokens.collect::<Option<Document>>()?, 0),
     };
 
     Some(parsed)
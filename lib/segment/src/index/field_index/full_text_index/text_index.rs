@@ -1,11 +1,12 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 #[cfg(feature = "rocksdb")]
 use std::sync::Arc;
 
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet};
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 #[cfg(feature = "rocksdb")]
 use parking_lot::RwLock;
 #[cfg(feature = "rocksdb")]
@@ -25,6 +26,7 @@ use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDelet
 #[cfg(feature = "rocksdb")]
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::data_types::index::TextIndexParams;
+use crate::data_types::text_query::TextQueryStatistics;
 use crate::index::field_index::full_text_index::inverted_index::Document;
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndexBuilderTrait, PayloadBlockCondition, PayloadFieldIndex,
@@ -168,6 +170,76 @@ impl FullTextIndex {
         }
     }
 
+    fn get_posting_len(
+        &self,
+        token_id: TokenId,
+        hw_counter: &HardwareCounterCell,
+    ) -> Option<usize> {
+        match self {
+            Self::Mutable(index) => index.inverted_index.get_posting_len(token_id, hw_counter),
+            Self::Immutable(index) => index.inverted_index.get_posting_len(token_id, hw_counter),
+            Self::Mmap(index) => index.inverted_index.get_posting_len(token_id, hw_counter),
+        }
+    }
+
+    fn total_values_count(&self) -> usize {
+        match self {
+            Self::Mutable(index) => index.inverted_index.total_values_count(),
+            Self::Immutable(index) => index.inverted_index.total_values_count(),
+            Self::Mmap(index) => index.inverted_index.total_values_count(),
+        }
+    }
+
+    /// Collect statistics of the tokens of the query `text` over the indexed documents
+    pub fn text_query_statistics(
+        &self,
+        text: &str,
+        hw_counter: &HardwareCounterCell,
+    ) -> TextQueryStatistics {
+        let mut doc_frequencies = BTreeMap::new();
+        self.get_tokenizer().tokenize_query(text, |token| {
+            let doc_frequency = self
+                .get_token(token.as_ref(), hw_counter)
+                .and_then(|token_id| self.get_posting_len(token_id, hw_counter))
+                .unwrap_or(0);
+            doc_frequencies.insert(token.into_owned(), doc_frequency);
+        });
+
+        TextQueryStatistics {
+            points_count: self.points_count(),
+            total_length: self.total_values_count(),
+            doc_frequencies,
+        }
+    }
+
+    /// Score the points by BM25 relevance of their documents to the query `text`.
+    ///
+    /// Only points, which contain any of the query tokens, are scored.
+    /// Token weights are taken from the `statistics`, which may cover more than this index.
+    pub fn score_text_query(
+        &self,
+        text: &str,
+        statistics: &TextQueryStatistics,
+        hw_counter: &HardwareCounterCell,
+    ) -> AHashMap<PointOffsetType, ScoreType> {
+        let mut seen_tokens = AHashSet::new();
+        let mut weighted_tokens = Vec::new();
+        self.get_tokenizer().tokenize_query(text, |token| {
+            if let Some(token_id) = self.get_token(token.as_ref(), hw_counter)
+                && seen_tokens.insert(token_id)
+            {
+                weighted_tokens.push((token_id, statistics.idf(token.as_ref())));
+            }
+        });
+
+        let bm25 = statistics.bm25();
+        match self {
+            Self::Mutable(index) => index.inverted_index.score_bm25(&weighted_tokens, &bm25),
+            Self::Immutable(index) => index.inverted_index.score_bm25(&weighted_tokens, &bm25),
+            Self::Mmap(index) => index.inverted_index.score_bm25(&weighted_tokens, &bm25),
+        }
+    }
+
     fn get_tokenizer(&self) -> &Tokenizer {
         match self {
             Self::Mutable(index) => &index.tokenizer,
@@ -280,14 +352,20 @@ impl FullTextIndex {
 
     /// Tries to parse a phrase query. If there are any unseen tokens, returns `None`
     ///
-    /// Preserves token order
+    /// Preserves token order. Proximity phrases can be written as `"quick fox"~2`,
+    /// explicit `slop` takes precedence over the one of the syntax.
     pub fn parse_phrase_query(
         &self,
         phrase: &str,
+        slop: Option<u32>,
         hw_counter: &HardwareCounterCell,
     ) -> Option<ParsedQuery> {
+        let (phrase, phrase_slop) = split_phrase_slop(phrase);
         let document = self.parse_document(phrase, hw_counter)?;
-        Some(ParsedQuery::Phrase(document))
+        Some(ParsedQuery::Phrase(
+            document,
+            slop.or(phrase_slop).unwrap_or_default(),
+        ))
     }
 
     /// Tries to parse a query. If there are any unseen tokens, returns `None`
@@ -364,33 +442,25 @@ impl FullTextIndex {
         self.filter_query(parsed_query, hw_counter)
     }
 
-    /// Checks the text directly against the payload value
-    pub fn check_payload_match<const IS_PHRASE: bool>(
+    /// Checks the parsed query directly against the payload value
+    pub fn check_payload_match(
         &self,
         payload_value: &serde_json::Value,
-        text: &str,
+        query: &ParsedQuery,
         hw_counter: &HardwareCounterCell,
     ) -> bool {
-        let query_opt = if IS_PHRASE {
-            self.parse_phrase_query(text, hw_counter)
-        } else {
-            self.parse_text_query(text, hw_counter)
-        };
-
-        let Some(query) = query_opt else {
-            return false;
-        };
-
         FullTextIndex::get_values(payload_value)
             .iter()
-            .any(|value| match &query {
+            .any(|value| match query {
                 ParsedQuery::AllTokens(query) => {
                     let tokenset = self.parse_tokenset(value, hw_counter);
                     tokenset.has_subset(query)
                 }
-                ParsedQuery::Phrase(query) => {
+                ParsedQuery::Phrase(query, slop) => {
                     let document = self.parse_document(value, hw_counter);
-                    document.map(|doc| doc.has_phrase(query)).unwrap_or(false)
+                    document
+                        .map(|doc| doc.has_phrase(query, *slop))
+                        .unwrap_or(false)
                 }
                 ParsedQuery::AnyTokens(query) => {
                     let tokenset = self.parse_tokenset(value, hw_counter);
@@ -457,6 +527,22 @@ impl FullTextIndex {
     }
 }
 
+/// Splits the proximity syntax `"quick fox"~2` into the phrase and its slop.
+///
+/// Phrases without the syntax are returned as is.
+fn split_phrase_slop(phrase: &str) -> (&str, Option<u32>) {
+    let Some((quoted, slop)) = phrase.trim().rsplit_once('~') else {
+        return (phrase, None);
+    };
+    let inner = quoted
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'));
+    match (inner, slop.parse()) {
+        (Some(inner), Ok(slop)) => (inner, Some(slop)),
+        _ => (phrase, None),
+    }
+}
+
 #[cfg(feature = "rocksdb")]
 pub struct FullTextIndexRocksDbBuilder {
     mutable_index: MutableFullTextIndex,
@@ -597,8 +683,8 @@ impl PayloadFieldIndex for FullTextIndex {
     ) -> OperationResult<Option<Box<dyn Iterator<Item = PointOffsetType> + 'a>>> {
         let parsed_query_opt = match &condition.r#match {
             Some(Match::Text(MatchText { text })) => self.parse_text_query(text, hw_counter),
-            Some(Match::Phrase(MatchPhrase { phrase })) => {
-                self.parse_phrase_query(phrase, hw_counter)
+            Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                self.parse_phrase_query(phrase, *slop, hw_counter)
             }
             _ => return Ok(None),
         };
@@ -617,8 +703,8 @@ impl PayloadFieldIndex for FullTextIndex {
     ) -> OperationResult<Option<CardinalityEstimation>> {
         let parsed_query_opt = match &condition.r#match {
             Some(Match::Text(MatchText { text })) => self.parse_text_query(text, hw_counter),
-            Some(Match::Phrase(MatchPhrase { phrase })) => {
-                self.parse_phrase_query(phrase, hw_counter)
+            Some(Match::Phrase(MatchPhrase { phrase, slop })) => {
+                self.parse_phrase_query(phrase, *slop, hw_counter)
             }
             _ => return Ok(None),
         };
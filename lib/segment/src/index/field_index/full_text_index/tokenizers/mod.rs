@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::sync::Arc;
-mod ascii_folding;
+pub(super) mod ascii_folding;
 mod japanese;
 mod multilingual;
 mod stemmer;
@@ -177,7 +177,12 @@ impl Tokenizer {
 
         let lowercase = lowercase.unwrap_or(true);
         let ascii_folding = ascii_folding.unwrap_or(false);
-        let stopwords_filter = Arc::new(StopwordsFilter::new(stopwords, lowercase));
+        let mut stopwords_filter = StopwordsFilter::new(stopwords, lowercase);
+        if ascii_folding {
+            // Tokens are folded before stopwords removal, so the stopwords must be folded too
+            stopwords_filter = stopwords_filter.with_ascii_folding();
+        }
+        let stopwords_filter = Arc::new(stopwords_filter);
 
         let tokens_processor = TokensProcessor::new(
             lowercase,
@@ -245,6 +250,7 @@ mod tests {
         Stemmer::from_algorithm(&StemmingAlgorithm::Snowball(SnowballParams {
             r#type: Snowball::Snowball,
             language,
+            additional_languages: None,
         }))
     }
 
@@ -468,6 +474,30 @@ mod tests {
         assert!(tokens.contains(&Cow::Borrowed("dog")));
     }
 
+    #[test]
+    fn test_tokenizer_with_ascii_folded_stopwords() {
+        let text = "Häuser für Déjà vu";
+        let params = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            ascii_folding: Some(true),
+            on_disk: None,
+            phrase_matching: None,
+            stopwords: Some(StopwordsInterface::new_set(&[Language::German], &["déjà"])),
+            stemmer: None,
+            enable_hnsw: None,
+        };
+
+        let tokenizer = Tokenizer::new_from_text_index_params(&params);
+
+        let mut tokens = Vec::new();
+        tokenizer.tokenize_doc(text, |token| tokens.push(token.to_string()));
+        assert_eq!(tokens, vec!["hauser", "vu"]);
+    }
+
     #[test]
     fn test_tokenizer_can_handle_apostrophes_parametrized() {
         use crate::data_types::index::TokenizerType;
@@ -829,4 +859,73 @@ mod tests {
         PrefixTokenizer::tokenize(input, &tokens_processor, |i| out.push(i.to_string()));
         assert_eq!(out, vec!["int", "inte", "pro", "proc", "liv", "live"]);
     }
+
+    #[test]
+    fn test_stemming_multiple_languages() {
+        let params = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            ascii_folding: None,
+            on_disk: None,
+            phrase_matching: None,
+            stopwords: None,
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: Snowball::Snowball,
+                language: SnowballLanguage::English,
+                additional_languages: Some(vec![
+                    SnowballLanguage::German,
+                    SnowballLanguage::English,
+                ]),
+            })),
+            enable_hnsw: None,
+        };
+        let tokenizer = Tokenizer::new_from_text_index_params(&params);
+
+        let input = "Running Häuser";
+        let mut doc_tokens = Vec::new();
+        tokenizer.tokenize_doc(input, |token| doc_tokens.push(token.to_string()));
+        assert_eq!(doc_tokens, vec!["run", "haus"]);
+
+        // Queries go through the same stemmers
+        let mut query_tokens = Vec::new();
+        tokenizer.tokenize_query(input, |token| query_tokens.push(token.to_string()));
+        assert_eq!(query_tokens, doc_tokens);
+    }
+
+    #[test]
+    fn test_stemming_mixed_languages() {
+        let stemmer = Stemmer::from_algorithm(&StemmingAlgorithm::Snowball(SnowballParams {
+            r#type: Snowball::Snowball,
+            language: SnowballLanguage::English,
+            additional_languages: Some(vec![SnowballLanguage::German]),
+        }));
+        let stem = |token: &str| stemmer.stem(Cow::Borrowed(token)).into_owned();
+
+        // Each token is stemmed with one language only, German stemmer doesn't
+        // stem the result of English one again ("univers" would become "univ")
+        assert_eq!(stem("universities"), "univers");
+        assert_eq!(stem("häuser"), "haus");
+        assert_eq!(stem("running"), "run");
+        assert_eq!(stem("gärten"), "gart");
+
+        // Different forms of a word get the same stem in both languages
+        for (first, second) in [
+            ("universities", "university"),
+            ("gardens", "garden"),
+            ("running", "runs"),
+            ("häuser", "haus"),
+            ("kindern", "kinder"),
+        ] {
+            assert_eq!(stem(first), stem(second), "{first} vs {second}");
+        }
+
+        // Tokens not stemmed by any language are kept as is
+        assert!(matches!(
+            stemmer.stem(Cow::Borrowed("qdrant")),
+            Cow::Borrowed("qdrant"),
+        ));
+    }
 }
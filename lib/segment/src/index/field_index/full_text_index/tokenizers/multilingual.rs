@@ -187,6 +187,7 @@ mod test {
                 SnowballParams {
                     r#type: Default::default(),
                     language: SnowballLanguage::English,
+                    additional_languages: None,
                 },
             ))),
             None,
@@ -4,13 +4,14 @@ use std::sync::Arc;
 
 use rust_stemmers::Algorithm;
 
-use crate::data_types::index::{SnowballLanguage, SnowballParams, StemmingAlgorithm};
+use crate::data_types::index::{SnowballLanguage, StemmingAlgorithm};
 
 /// Abstraction to handle different stemming libraries and algorithms with a clean API.
 #[derive(Clone)]
 pub enum Stemmer {
     // TODO(rocksdb): Remove `Clone` and this Arc once rocksdb has been removed!
-    Snowball(Arc<rust_stemmers::Stemmer>),
+    /// Snowball stemmers of one or more languages, in order of preference
+    Snowball(Arc<[rust_stemmers::Stemmer]>),
 }
 
 impl std::fmt::Debug for Stemmer {
@@ -24,12 +25,13 @@ impl std::fmt::Debug for Stemmer {
 impl Stemmer {
     pub fn from_algorithm(config: &StemmingAlgorithm) -> Self {
         match config {
-            StemmingAlgorithm::Snowball(SnowballParams {
-                r#type: _,
-                language,
-            }) => Self::Snowball(Arc::new(rust_stemmers::Stemmer::create(Algorithm::from(
-                *language,
-            )))),
+            StemmingAlgorithm::Snowball(params) => Self::Snowball(
+                params
+                    .languages()
+                    .into_iter()
+                    .map(|language| rust_stemmers::Stemmer::create(Algorithm::from(language)))
+                    .collect(),
+            ),
         }
     }
 
@@ -38,14 +40,28 @@ impl Stemmer {
     pub fn try_default_from_language(language: &str) -> Option<Self> {
         let language = SnowballLanguage::from_str(language).ok()?;
 
-        Some(Self::Snowball(Arc::new(rust_stemmers::Stemmer::create(
+        Some(Self::Snowball(Arc::new([rust_stemmers::Stemmer::create(
             Algorithm::from(language),
-        ))))
+        )])))
     }
 
     pub fn stem<'a>(&self, input: Cow<'a, str>) -> Cow<'a, str> {
         match self {
-            Stemmer::Snowball(algorithm) => algorithm.stem_cow(input),
+            Stemmer::Snowball(stemmers) => match stemmers.as_ref() {
+                [stemmer] => stemmer.stem_cow(input),
+                stemmers => {
+                    // Stem each token with a single language, the one which strips the most.
+                    // Chaining stemmers would stem a token again with another language,
+                    // so different forms of a word could end up with different stems.
+                    let stem = stemmers
+                        .iter()
+                        .map(|stemmer| stemmer.stem(&input))
+                        .min_by_key(|stem| stem.len())
+                        .filter(|stem| **stem != *input)
+                        .map(Cow::into_owned);
+                    stem.map_or(input, Cow::Owned)
+                }
+            },
         }
     }
 }
@@ -56,6 +56,9 @@ use crate::types::{
 
 const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
 const HISTOGRAM_PRECISION: f64 = 0.01;
+/// Number of blocks of a level, merged into a single block of the next level,
+/// see [`NumericIndexInner::coarse_range_blocks`].
+pub const RANGE_LEVEL_FACTOR: usize = 4;
 
 pub trait StreamRange<T> {
     fn stream_range(
@@ -322,6 +325,104 @@ where
         }
     }
 
+    /// Convert threshold on number of points into threshold on number of values.
+    fn effective_threshold(&self, threshold: usize) -> usize {
+        let value_per_point =
+            self.total_unique_values_count() as f64 / self.get_points_count() as f64;
+        (threshold as f64 * value_per_point) as usize
+    }
+
+    /// Split values into consecutive ranges, holding about `block_size` values each.
+    fn range_blocks(&self, block_size: usize, key: PayloadKeyType) -> Vec<PayloadBlockCondition> {
+        let mut lower_bound = Unbounded;
+        let mut pre_lower_bound: Option<Bound<T>> = None;
+        let mut payload_conditions = Vec::new();
+
+        loop {
+            let upper_bound = self
+                .get_histogram()
+                .get_range_by_size(lower_bound, block_size);
+
+            if let Some(pre_lower_bound) = pre_lower_bound {
+                let range = Range {
+                    lt: match upper_bound {
+                        Excluded(val) => Some(OrderedFloat(val.to_f64())),
+                        _ => None,
+                    },
+                    gt: match pre_lower_bound {
+                        Excluded(val) => Some(OrderedFloat(val.to_f64())),
+                        _ => None,
+                    },
+                    gte: match pre_lower_bound {
+                        Included(val) => Some(OrderedFloat(val.to_f64())),
+                        _ => None,
+                    },
+                    lte: match upper_bound {
+                        Included(val) => Some(OrderedFloat(val.to_f64())),
+                        _ => None,
+                    },
+                };
+                let cardinality = self.range_cardinality(&RangeInterface::Float(range));
+                let condition = PayloadBlockCondition {
+                    condition: FieldCondition::new_range(key.clone(), range),
+                    cardinality: cardinality.exp,
+                };
+
+                payload_conditions.push(condition);
+            } else if upper_bound == Unbounded {
+                // One block covers all points
+                payload_conditions.push(PayloadBlockCondition {
+                    condition: FieldCondition::new_range(
+                        key.clone(),
+                        Range {
+                            gte: None,
+                            lte: None,
+                            lt: None,
+                            gt: None,
+                        },
+                    ),
+                    cardinality: self.get_points_count(),
+                });
+            }
+
+            pre_lower_bound = Some(lower_bound);
+
+            lower_bound = match upper_bound {
+                Included(val) => Excluded(val),
+                Excluded(val) => Excluded(val),
+                Unbounded => break,
+            };
+        }
+        payload_conditions
+    }
+
+    /// Blocks of wider ranges, than blocks of [`PayloadFieldIndex::payload_blocks`].
+    ///
+    /// Blocks are built on several levels, each level merges [`RANGE_LEVEL_FACTOR`] blocks of the
+    /// previous one. A range filter, which matches more values than a single payload block, is
+    /// covered by a block of a similar size on some level, so it can still be searched with HNSW.
+    pub fn coarse_range_blocks(
+        &self,
+        threshold: usize,
+        key: PayloadKeyType,
+    ) -> Vec<PayloadBlockCondition> {
+        let total_values = self.total_unique_values_count();
+        let mut block_size = self.effective_threshold(threshold) / 2;
+        let mut blocks = Vec::new();
+        loop {
+            block_size = block_size.saturating_mul(RANGE_LEVEL_FACTOR);
+            if block_size == 0 || block_size >= total_values {
+                break;
+            }
+            let level_blocks = self.range_blocks(block_size, key.clone());
+            if level_blocks.len() <= 1 {
+                break;
+            }
+            blocks.extend(level_blocks);
+        }
+        blocks
+    }
+
     fn range_cardinality(&self, range: &RangeInterface) -> CardinalityEstimation {
         let max_values_per_point = self.max_values_per_point();
         if max_values_per_point == 0 {
@@ -990,69 +1091,7 @@ where
         threshold: usize,
         key: PayloadKeyType,
     ) -> Box<dyn Iterator<Item = OperationResult<PayloadBlockCondition>> + '_> {
-        let mut lower_bound = Unbounded;
-        let mut pre_lower_bound: Option<Bound<T>> = None;
-        let mut payload_conditions = Vec::new();
-
-        let value_per_point =
-            self.total_unique_values_count() as f64 / self.get_points_count() as f64;
-        let effective_threshold = (threshold as f64 * value_per_point) as usize;
-
-        loop {
-            let upper_bound = self
-                .get_histogram()
-                .get_range_by_size(lower_bound, effective_threshold / 2);
-
-            if let Some(pre_lower_bound) = pre_lower_bound {
-                let range = Range {
-                    lt: match upper_bound {
-                        Excluded(val) => Some(OrderedFloat(val.to_f64())),
-                        _ => None,
-                    },
-                    gt: match pre_lower_bound {
-                        Excluded(val) => Some(OrderedFloat(val.to_f64())),
-                        _ => None,
-                    },
-                    gte: match pre_lower_bound {
-                        Included(val) => Some(OrderedFloat(val.to_f64())),
-                        _ => None,
-                    },
-                    lte: match upper_bound {
-                        Included(val) => Some(OrderedFloat(val.to_f64())),
-                        _ => None,
-                    },
-                };
-                let cardinality = self.range_cardinality(&RangeInterface::Float(range));
-                let condition = PayloadBlockCondition {
-                    condition: FieldCondition::new_range(key.clone(), range),
-                    cardinality: cardinality.exp,
-                };
-
-                payload_conditions.push(condition);
-            } else if upper_bound == Unbounded {
-                // One block covers all points
-                payload_conditions.push(PayloadBlockCondition {
-                    condition: FieldCondition::new_range(
-                        key.clone(),
-                        Range {
-                            gte: None,
-                            lte: None,
-                            lt: None,
-                            gt: None,
-                        },
-                    ),
-                    cardinality: self.get_points_count(),
-                });
-            }
-
-            pre_lower_bound = Some(lower_bound);
-
-            lower_bound = match upper_bound {
-                Included(val) => Excluded(val),
-                Excluded(val) => Excluded(val),
-                Unbounded => break,
-            };
-        }
+        let payload_conditions = self.range_blocks(self.effective_threshold(threshold) / 2, key);
         Box::new(payload_conditions.into_iter().map(Ok))
     }
 }
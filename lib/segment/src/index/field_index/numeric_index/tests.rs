@@ -317,6 +317,43 @@ fn test_payload_blocks(#[case] index_type: IndexType) {
     eprintln!("threshold {threshold}, blocks.len() = {:#?}", blocks.len());
 }
 
+#[rstest]
+#[cfg_attr(feature = "rocksdb", case(IndexType::Mutable))]
+#[case(IndexType::MutableGridstore)]
+#[cfg_attr(feature = "rocksdb", case(IndexType::Immutable))]
+#[case(IndexType::Mmap)]
+#[case(IndexType::RamMmap)]
+fn test_coarse_range_blocks(#[case] index_type: IndexType) {
+    let (_temp_dir, index) = random_index(1000, 2, index_type);
+    let threshold = 100;
+    let payload_blocks = index
+        .inner()
+        .payload_blocks(threshold, JsonPath::new("test"))
+        .map(Result::unwrap)
+        .collect_vec();
+    let coarse_blocks = index
+        .inner()
+        .coarse_range_blocks(threshold, JsonPath::new("test"));
+    assert!(!coarse_blocks.is_empty());
+    assert!(coarse_blocks.len() < payload_blocks.len());
+    for block in &coarse_blocks {
+        assert!(block.condition.range.is_some());
+    }
+
+    let max_cardinality = |blocks: &[PayloadBlockCondition]| {
+        blocks.iter().map(|block| block.cardinality).max().unwrap()
+    };
+    assert!(max_cardinality(&coarse_blocks) > max_cardinality(&payload_blocks));
+
+    // Threshold above the number of points, a single payload block is enough
+    assert!(
+        index
+            .inner()
+            .coarse_range_blocks(10_000, JsonPath::new("test"))
+            .is_empty()
+    );
+}
+
 #[rstest]
 #[cfg_attr(feature = "rocksdb", case(IndexType::Mutable))]
 #[case(IndexType::MutableGridstore)]
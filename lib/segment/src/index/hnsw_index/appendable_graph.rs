@@ -0,0 +1,328 @@
+//! HNSW graph of appendable segments, built online as vectors are inserted.
+//!
+//! Appendable segments are searched by full scan until the optimizer builds a proper HNSW index
+//! for them. With the appendable graph, each inserted vector is linked into an in-RAM graph right
+//! away, so that fresh data is searched approximately too. The graph is not persisted, it is
+//! rebuilt from the vector storage when the segment is loaded.
+//!
+//! Linking is bounded by [`AppendableHnswConfig::indexing_threshold`]. Vectors inserted beyond
+//! it, as well as vectors changed after they were linked, are found by full scan instead.
+
+use std::cmp::max;
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+
+use bitvec::vec::BitVec;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::types::{PointOffsetType, ScoredPointOffset};
+use serde::{Deserialize, Serialize};
+
+use super::HnswM;
+use super::graph_layers::GraphLayersBase as _;
+use super::graph_layers_builder::GraphLayersBuilder;
+use super::point_scorer::FilteredScorer;
+use crate::common::BYTES_IN_KB;
+use crate::common::operation_error::{CancellableResult, OperationResult};
+use crate::types::DEFAULT_HNSW_EF_CONSTRUCT;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+const DEFAULT_M: usize = 16;
+
+/// Number of extra entry points of the graph, kept for filtered searches
+const ENTRY_POINTS_NUM: usize = 10;
+
+static APPENDABLE_HNSW_CONFIG: OnceLock<AppendableHnswConfig> = OnceLock::new();
+
+/// Configuration of HNSW graphs, built online in appendable segments
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct AppendableHnswConfig {
+    /// Maximum size (in kilobytes) of vectors of a segment, which are linked into the graph.
+    /// Vectors inserted beyond it are searched by full scan, until the segment is indexed
+    /// by the optimizer.
+    pub indexing_threshold: usize,
+    /// Number of edges per node in the graph
+    #[serde(default = "default_m")]
+    pub m: usize,
+    /// Number of neighbours to consider when linking a new vector
+    #[serde(default = "default_ef_construct")]
+    pub ef_construct: usize,
+}
+
+const fn default_m() -> usize {
+    DEFAULT_M
+}
+
+const fn default_ef_construct() -> usize {
+    DEFAULT_HNSW_EF_CONSTRUCT
+}
+
+/// Enable HNSW graphs, built online in appendable segments.
+///
+/// Must only be called once at startup, otherwise a warning is logged and `config` is ignored.
+pub fn init_appendable_hnsw(config: AppendableHnswConfig) {
+    if APPENDABLE_HNSW_CONFIG.set(config).is_err() {
+        log::warn!("Appendable HNSW is already initialized, ignoring new configuration");
+    }
+}
+
+pub fn appendable_hnsw_config() -> Option<AppendableHnswConfig> {
+    APPENDABLE_HNSW_CONFIG.get().copied()
+}
+
+pub struct AppendableGraph {
+    config: AppendableHnswConfig,
+    graph: GraphLayersBuilder,
+    /// Number of points, which were ever linked into the graph
+    graph_point_count: usize,
+    /// Points, which are linked into the graph with their current vectors
+    linked: BitVec,
+}
+
+impl fmt::Debug for AppendableGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppendableGraph")
+            .field("config", &self.config)
+            .field("graph_point_count", &self.graph_point_count)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AppendableGraph {
+    pub fn new(config: AppendableHnswConfig) -> Self {
+        let graph = GraphLayersBuilder::new_with_params(
+            0,
+            HnswM::new2(config.m),
+            config.ef_construct,
+            ENTRY_POINTS_NUM,
+            true,
+            false,
+        );
+        Self {
+            config,
+            graph,
+            graph_point_count: 0,
+            linked: BitVec::new(),
+        }
+    }
+
+    /// Default size of the beam, used for search
+    pub fn ef(&self) -> usize {
+        self.config.ef_construct
+    }
+
+    fn is_in_graph(&self, point_id: PointOffsetType) -> bool {
+        self.graph
+            .links_layers()
+            .get(point_id as usize)
+            .is_some_and(|layers| !layers.is_empty())
+    }
+
+    fn is_linked(&self, point_id: PointOffsetType) -> bool {
+        self.linked
+            .get(point_id as usize)
+            .is_some_and(|linked| *linked)
+    }
+
+    fn is_full(&self, vector_storage: &VectorStorageEnum) -> bool {
+        let vector_size_bytes = vector_storage
+            .size_of_available_vectors_in_bytes()
+            .checked_div(vector_storage.available_vector_count())
+            .unwrap_or(0);
+        let threshold_bytes = self.config.indexing_threshold.saturating_mul(BYTES_IN_KB);
+        self.graph_point_count.saturating_mul(vector_size_bytes) >= threshold_bytes
+    }
+
+    /// Link the vector of `point_id`, which was just inserted into `vector_storage`.
+    pub fn add_point(
+        &mut self,
+        point_id: PointOffsetType,
+        vector_storage: &VectorStorageEnum,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if self.is_in_graph(point_id) {
+            // Links of a changed vector lead to neighbours of its old value,
+            // points can't be linked twice, so it is found by full scan from now on
+            self.linked.set(point_id as usize, false);
+            return Ok(());
+        }
+
+        if self.is_full(vector_storage) {
+            return Ok(());
+        }
+
+        let level = self.graph.get_random_layer(&mut rand::rng());
+        self.graph.set_levels(point_id, level);
+
+        // Only deleted vectors are excluded, the point may not be mapped in the ID tracker yet
+        let points_scorer = FilteredScorer::new_internal(
+            point_id,
+            vector_storage,
+            None,
+            None,
+            vector_storage.deleted_vector_bitslice(),
+            hw_counter.fork(),
+        )?;
+        self.graph.link_new_point(point_id, points_scorer);
+        self.graph_point_count += 1;
+
+        if self.linked.len() <= point_id as usize {
+            self.linked.resize(point_id as usize + 1, false);
+        }
+        self.linked.set(point_id as usize, true);
+        Ok(())
+    }
+
+    /// Points which are not linked with their current vectors, in ascending order
+    pub fn unlinked_points(
+        &self,
+        total_vector_count: usize,
+    ) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.linked
+            .iter_zeros()
+            .chain(self.linked.len()..total_vector_count)
+            .map(|point_id| point_id as PointOffsetType)
+    }
+
+    /// Search for `top` closest points among linked ones.
+    pub fn search(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        let entry_point = self
+            .graph
+            .get_entry_points()
+            .get_entry_point(|point_id| points_scorer.filters().check_vector(point_id));
+        let Some(entry_point) = entry_point else {
+            return Ok(Vec::new());
+        };
+
+        let zero_level_entry = self.graph.search_entry(
+            entry_point.point_id,
+            entry_point.level,
+            0,
+            &mut points_scorer,
+            is_stopped,
+        )?;
+        let nearest = self.graph.search_on_level(
+            zero_level_entry,
+            0,
+            max(ef, top),
+            None,
+            &mut points_scorer,
+            is_stopped,
+        )?;
+        Ok(nearest
+            .into_iter_sorted()
+            .filter(|scored_point| self.is_linked(scored_point.idx))
+            .take(top)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+    use itertools::Itertools as _;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+    use crate::fixtures::index_fixtures::{TestRawScorerProducer, random_vector};
+    use crate::types::Distance;
+    use crate::vector_storage::DEFAULT_STOPPED;
+
+    const NUM_VECTORS: usize = 1000;
+    const DIM: usize = 8;
+
+    fn build_graph(
+        vector_holder: &TestRawScorerProducer,
+        indexing_threshold: usize,
+    ) -> AppendableGraph {
+        let mut graph = AppendableGraph::new(AppendableHnswConfig {
+            indexing_threshold,
+            m: 8,
+            ef_construct: 32,
+        });
+        let hw_counter = HardwareCounterCell::new();
+        for point_id in 0..NUM_VECTORS as PointOffsetType {
+            graph
+                .add_point(point_id, vector_holder.storage(), &hw_counter)
+                .unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_appendable_graph_search() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder =
+            TestRawScorerProducer::new(DIM, Distance::Cosine, NUM_VECTORS, false, &mut rng);
+        let graph = build_graph(&vector_holder, usize::MAX);
+        assert_eq!(graph.unlinked_points(NUM_VECTORS).count(), 0);
+
+        let top = 5;
+        let query = random_vector(&mut rng, DIM);
+        let scorer = vector_holder.scorer(query.clone());
+        let mut reference_top = FixedLengthPriorityQueue::new(top);
+        for idx in 0..NUM_VECTORS as PointOffsetType {
+            reference_top.push(ScoredPointOffset {
+                idx,
+                score: scorer.score_point(idx),
+            });
+        }
+
+        let graph_search = graph
+            .search(top, 64, vector_holder.scorer(query), &DEFAULT_STOPPED)
+            .unwrap();
+        assert_eq!(reference_top.into_sorted_vec(), graph_search);
+    }
+
+    #[test]
+    fn test_appendable_graph_unlinked_points() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder =
+            TestRawScorerProducer::new(DIM, Distance::Cosine, NUM_VECTORS, false, &mut rng);
+
+        // Half of the vectors fit into the threshold
+        let vector_size_bytes = DIM * size_of::<f32>();
+        let indexing_threshold = NUM_VECTORS / 2 * vector_size_bytes / BYTES_IN_KB;
+        let mut graph = build_graph(&vector_holder, indexing_threshold);
+        let linked_count = indexing_threshold * BYTES_IN_KB / vector_size_bytes;
+        assert_eq!(
+            graph.unlinked_points(NUM_VECTORS).collect_vec(),
+            (linked_count as PointOffsetType..NUM_VECTORS as PointOffsetType).collect_vec(),
+        );
+
+        // Changed vectors are not searched through the graph anymore
+        let changed_point = 0;
+        graph
+            .add_point(
+                changed_point,
+                vector_holder.storage(),
+                &HardwareCounterCell::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            graph.unlinked_points(NUM_VECTORS).next(),
+            Some(changed_point),
+        );
+
+        let query = random_vector(&mut rng, DIM);
+        let graph_search = graph
+            .search(
+                NUM_VECTORS,
+                NUM_VECTORS,
+                vector_holder.scorer(query),
+                &DEFAULT_STOPPED,
+            )
+            .unwrap();
+        assert!(!graph_search.is_empty());
+        assert!(graph_search.iter().all(|scored_point| {
+            scored_point.idx != changed_point && (scored_point.idx as usize) < linked_count
+        }));
+    }
+}
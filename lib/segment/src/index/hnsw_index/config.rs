@@ -29,6 +29,13 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Number of vectors left linked in the graph after it was healed.
+    /// Deleted vectors are unlinked by healing, but still count as indexed.
+    #[serde(default)]
+    pub linked_vector_count: Option<usize>,
+    /// Filtered searches use ACORN by default, payload-aware links are not built.
+    #[serde(default)]
+    pub acorn: bool,
 }
 
 impl HnswGraphConfig {
@@ -39,6 +46,7 @@ impl HnswGraphConfig {
         max_indexing_threads: usize,
         payload_m: Option<usize>,
         indexed_vector_count: usize,
+        acorn: bool,
     ) -> Self {
         HnswGraphConfig {
             m,
@@ -50,6 +58,8 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            linked_vector_count: None,
+            acorn,
         }
     }
 
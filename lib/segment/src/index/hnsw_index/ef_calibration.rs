@@ -0,0 +1,198 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::{atomic_save_json, read_json};
+use common::generic_consts::Random;
+use common::types::PointOffsetType;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+use super::graph_layers::{GraphLayers, SearchAlgorithm};
+use super::point_scorer::{BatchFilteredSearcher, FilteredScorer};
+use crate::common::operation_error::OperationResult;
+use crate::data_types::vectors::QueryVector;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+pub const EF_CALIBRATION_FILE: &str = "ef_calibration.json";
+
+/// Number of points of the segment, used as queries for calibration.
+const CALIBRATION_QUERIES: usize = 32;
+
+/// Number of nearest neighbours, for which recall is measured.
+const CALIBRATION_TOP: usize = 10;
+
+/// Values of `ef` to measure recall with, in ascending order.
+const CALIBRATION_EFS: [usize; 6] = [16, 32, 64, 128, 256, 512];
+
+/// Recall of the graph search, measured for several values of `ef` when the index is built.
+///
+/// Used to pick the smallest `ef` reaching the recall requested with
+/// [`SearchParams::target_recall`](crate::types::SearchParams::target_recall).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EfCalibration {
+    /// Number of nearest neighbours, for which recall was measured
+    pub top: usize,
+    /// Measured recall, by ascending `ef`
+    pub points: Vec<EfRecall>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EfRecall {
+    pub ef: usize,
+    pub recall: f64,
+}
+
+impl EfCalibration {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(EF_CALIBRATION_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+
+    /// Smallest calibrated `ef` with recall of at least `target_recall`.
+    ///
+    /// If no calibrated `ef` reaches the target, the largest one is returned.
+    pub fn ef_for_recall(&self, target_recall: f64) -> Option<usize> {
+        self.points
+            .iter()
+            .find(|point| point.recall >= target_recall)
+            .or_else(|| self.points.last())
+            .map(|point| point.ef)
+    }
+
+    /// Measure recall of searches in `graph`, using random points of the segment as queries.
+    ///
+    /// Returns `None` if the segment has too few points to measure recall.
+    pub fn calibrate<R: Rng + ?Sized>(
+        graph: &GraphLayers,
+        vector_storage: &VectorStorageEnum,
+        point_deleted: &BitSlice,
+        rng: &mut R,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Self>> {
+        let available_points: Vec<PointOffsetType> = point_deleted
+            .iter_zeros()
+            .map(|point_id| point_id as PointOffsetType)
+            .filter(|&point_id| point_id < vector_storage.total_vector_count() as PointOffsetType)
+            .collect();
+        if available_points.len() <= CALIBRATION_TOP * 2 {
+            return Ok(None);
+        }
+
+        let query_ids: Vec<PointOffsetType> = (0..CALIBRATION_QUERIES)
+            .map(|_| available_points[rng.random_range(0..available_points.len())])
+            .collect();
+        let queries: Vec<QueryVector> = query_ids
+            .iter()
+            .map(|&point_id| {
+                let vector = vector_storage.get_vector::<Random>(point_id);
+                vector.as_vec_ref().into()
+            })
+            .collect();
+
+        // One more result, as the query point itself is excluded
+        let top = CALIBRATION_TOP + 1;
+
+        let ground_truth = BatchFilteredSearcher::new(
+            &queries.iter().collect::<Vec<_>>(),
+            vector_storage,
+            None,
+            None,
+            top,
+            point_deleted,
+            HardwareCounterCell::disposable(),
+        )?
+        .peek_top_all(stopped, None)?;
+
+        let expected: Vec<Vec<PointOffsetType>> = ground_truth
+            .iter()
+            .zip(&query_ids)
+            .map(|(scored, &query_id)| {
+                scored
+                    .iter()
+                    .map(|scored| scored.idx)
+                    .filter(|&idx| idx != query_id)
+                    .take(CALIBRATION_TOP)
+                    .collect()
+            })
+            .collect();
+        let expected_total: usize = expected.iter().map(Vec::len).sum();
+
+        let mut points = Vec::with_capacity(CALIBRATION_EFS.len());
+        for ef in CALIBRATION_EFS {
+            let mut found_total = 0;
+            for (&query_id, expected) in query_ids.iter().zip(&expected) {
+                let scorer = FilteredScorer::new_internal(
+                    query_id,
+                    vector_storage,
+                    None,
+                    None,
+                    point_deleted,
+                    HardwareCounterCell::disposable(),
+                )?;
+                let found =
+                    graph.search(top, ef, SearchAlgorithm::Hnsw, None, scorer, None, stopped)?;
+                found_total += found
+                    .iter()
+                    .filter(|scored| expected.contains(&scored.idx))
+                    .count();
+            }
+
+            let recall = found_total as f64 / expected_total as f64;
+            points.push(EfRecall { ef, recall });
+            if recall >= 1.0 {
+                break;
+            }
+        }
+
+        Ok(Some(EfCalibration {
+            top: CALIBRATION_TOP,
+            points,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ef_for_recall() {
+        let calibration = EfCalibration {
+            top: CALIBRATION_TOP,
+            points: vec![
+                EfRecall {
+                    ef: 16,
+                    recall: 0.8,
+                },
+                EfRecall {
+                    ef: 32,
+                    recall: 0.9,
+                },
+                EfRecall {
+                    ef: 64,
+                    recall: 0.97,
+                },
+            ],
+        };
+
+        assert_eq!(calibration.ef_for_recall(0.5), Some(16));
+        assert_eq!(calibration.ef_for_recall(0.9), Some(32));
+        assert_eq!(calibration.ef_for_recall(0.95), Some(64));
+        assert_eq!(calibration.ef_for_recall(0.99), Some(64));
+
+        let empty = EfCalibration {
+            top: CALIBRATION_TOP,
+            points: Vec::new(),
+        };
+        assert_eq!(empty.ef_for_recall(0.9), None);
+    }
+}
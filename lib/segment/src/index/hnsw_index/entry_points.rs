@@ -38,6 +38,13 @@ impl EntryPoints {
             extra_entry_points: FixedLengthPriorityQueue::new(extra_entry_points),
         }
     }
+    /// All entry points, including extra ones
+    pub fn iter(&self) -> impl Iterator<Item = &EntryPoint> {
+        self.entry_points
+            .iter()
+            .chain(self.extra_entry_points.iter_unsorted())
+    }
+
     pub fn merge_from_other(&mut self, mut other: EntryPoints) {
         self.entry_points.append(&mut other.entry_points);
         // Do not merge `extra_entry_points` to prevent duplications
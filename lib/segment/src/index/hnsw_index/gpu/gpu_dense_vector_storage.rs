@@ -0,0 +1,418 @@
+//! Dense vectors mirrored in GPU memory.
+//!
+//! Exact search and rescoring read original vectors of many points per request. If all vectors
+//! of a segment fit in VRAM, they are uploaded once, and such requests are scored on GPU in
+//! batches, without reading vectors from RAM or disk.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType};
+use parking_lot::Mutex;
+
+use super::GPU_TIMEOUT;
+use super::gpu_vector_storage::GpuVectorStorage;
+use super::shader_builder::ShaderBuilder;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{VectorElementType, VectorElementTypeHalf, VectorInternal};
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Max number of points scored in a single dispatch.
+const SCORE_BATCH_SIZE: usize = 16 * 1024;
+
+/// Read-only GPU mirror of a dense vector storage with a batch scorer.
+pub struct DenseGpuVectorStorage {
+    gpu_vector_storage: GpuVectorStorage,
+    dim: usize,
+    distance: Distance,
+    pipeline: Arc<gpu::Pipeline>,
+    /// GPU context is not thread safe, so requests are scored one after another.
+    resources: Mutex<GpuScoringResources>,
+}
+
+struct GpuScoringResources {
+    context: gpu::Context,
+    batch_size: usize,
+    query_buffer: Arc<gpu::Buffer>,
+    query_staging_buffer: Arc<gpu::Buffer>,
+    point_ids_buffer: Arc<gpu::Buffer>,
+    point_ids_staging_buffer: Arc<gpu::Buffer>,
+    scores_buffer: Arc<gpu::Buffer>,
+    scores_staging_buffer: Arc<gpu::Buffer>,
+    descriptor_set: Arc<gpu::DescriptorSet>,
+}
+
+impl std::fmt::Debug for DenseGpuVectorStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DenseGpuVectorStorage")
+            .field("dim", &self.dim)
+            .field("distance", &self.distance)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DenseGpuVectorStorage {
+    pub fn new(
+        device: Arc<gpu::Device>,
+        vector_storage: &VectorStorageEnum,
+        // Force half precision for `f32` vectors.
+        force_half_precision: bool,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let VectorInternal::Dense(default_vector) = vector_storage.default_vector() else {
+            return Err(OperationError::from(gpu::GpuError::NotSupported(
+                "Only dense vectors can be scored on GPU".to_string(),
+            )));
+        };
+        if vector_storage.is_variable_dim() {
+            return Err(OperationError::from(gpu::GpuError::NotSupported(
+                "Vectors of variable dimension can't be scored on GPU".to_string(),
+            )));
+        }
+        // Byte vectors are scored against byte queries, which are not produced on CPU side
+        if vector_storage.datatype() == VectorStorageDatatype::Uint8 {
+            return Err(OperationError::from(gpu::GpuError::NotSupported(
+                "Uint8 vectors can't be scored on GPU".to_string(),
+            )));
+        }
+
+        let gpu_vector_storage = GpuVectorStorage::new(
+            device.clone(),
+            vector_storage,
+            None,
+            force_half_precision,
+            stopped,
+        )?;
+
+        let descriptor_set_layout = gpu::DescriptorSetLayout::builder()
+            .add_storage_buffer(0)
+            .add_storage_buffer(1)
+            .add_storage_buffer(2)
+            .build(device.clone())?;
+
+        let shader = ShaderBuilder::new(device.clone())
+            .with_shader_code(include_str!("shaders/run_score_points.comp"))
+            .with_parameters(&gpu_vector_storage)
+            .build("run_score_points.comp")?;
+
+        let pipeline = gpu::Pipeline::builder()
+            .add_descriptor_set_layout(0, descriptor_set_layout.clone())
+            .add_descriptor_set_layout(1, gpu_vector_storage.descriptor_set_layout())
+            .add_shader(shader)
+            .build(device.clone())?;
+
+        let batch_size = SCORE_BATCH_SIZE
+            .min(device.max_compute_work_group_count()[0])
+            .min(gpu_vector_storage.num_vectors())
+            .max(1);
+        let resources = GpuScoringResources::new(
+            device,
+            descriptor_set_layout,
+            batch_size,
+            gpu_vector_storage.dim() * element_size(gpu_vector_storage.element_type()),
+        )?;
+
+        Ok(Self {
+            gpu_vector_storage,
+            dim: default_vector.len(),
+            distance: vector_storage.distance(),
+            pipeline,
+            resources: Mutex::new(resources),
+        })
+    }
+
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    pub fn num_vectors(&self) -> usize {
+        self.gpu_vector_storage.num_vectors()
+    }
+
+    /// Score stored vectors of `point_ids` against `query`.
+    ///
+    /// Scores are equal to ones of CPU raw scorer, up to floating point precision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of `point_ids` is out of range.
+    pub fn score_points(
+        &self,
+        query: &[VectorElementType],
+        point_ids: &[PointOffsetType],
+    ) -> OperationResult<Vec<ScoreType>> {
+        if query.len() != self.dim {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: self.dim,
+                received_dim: query.len(),
+            });
+        }
+        if let Some(&point_id) = point_ids
+            .iter()
+            .find(|&&point_id| point_id as usize >= self.num_vectors())
+        {
+            return Err(OperationError::service_error(format!(
+                "Point {point_id} is out of range of GPU vector storage",
+            )));
+        }
+
+        // Stored vectors are preprocessed, zero padding doesn't change any of the distances
+        let mut query = self
+            .distance
+            .preprocess_vector::<VectorElementType>(query.to_vec());
+        query.resize(self.gpu_vector_storage.dim(), 0.0);
+
+        let mut resources = self.resources.lock();
+        match self.gpu_vector_storage.element_type() {
+            VectorStorageDatatype::Float16 => {
+                let query = VectorElementTypeHalf::slice_from_float_cow(query.into());
+                resources.query_staging_buffer.upload(query.as_ref(), 0)?;
+            }
+            _ => resources.query_staging_buffer.upload(query.as_slice(), 0)?,
+        }
+        resources.upload_query()?;
+
+        let mut scores = Vec::with_capacity(point_ids.len());
+        for batch in point_ids.chunks(resources.batch_size) {
+            scores.extend(resources.score_batch(
+                &self.pipeline,
+                &self.gpu_vector_storage,
+                batch,
+            )?);
+        }
+        Ok(scores)
+    }
+}
+
+/// Size of a vector element on GPU, byte vectors are rejected on construction.
+fn element_size(element_type: VectorStorageDatatype) -> usize {
+    match element_type {
+        VectorStorageDatatype::Float16 => size_of::<VectorElementTypeHalf>(),
+        _ => size_of::<VectorElementType>(),
+    }
+}
+
+impl GpuScoringResources {
+    fn new(
+        device: Arc<gpu::Device>,
+        descriptor_set_layout: Arc<gpu::DescriptorSetLayout>,
+        batch_size: usize,
+        query_size: usize,
+    ) -> OperationResult<Self> {
+        let query_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scoring query buffer",
+            gpu::BufferType::Storage,
+            query_size,
+        )?;
+        let query_staging_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scoring query staging buffer",
+            gpu::BufferType::CpuToGpu,
+            query_size,
+        )?;
+
+        let point_ids_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scoring point ids buffer",
+            gpu::BufferType::Storage,
+            batch_size * size_of::<PointOffsetType>(),
+        )?;
+        let point_ids_staging_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scoring point ids staging buffer",
+            gpu::BufferType::CpuToGpu,
+            point_ids_buffer.size(),
+        )?;
+
+        let scores_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scores buffer",
+            gpu::BufferType::Storage,
+            batch_size * size_of::<ScoreType>(),
+        )?;
+        let scores_staging_buffer = gpu::Buffer::new(
+            device.clone(),
+            "Scores staging buffer",
+            gpu::BufferType::GpuToCpu,
+            scores_buffer.size(),
+        )?;
+
+        let descriptor_set = gpu::DescriptorSet::builder(descriptor_set_layout)
+            .add_storage_buffer(0, query_buffer.clone())
+            .add_storage_buffer(1, point_ids_buffer.clone())
+            .add_storage_buffer(2, scores_buffer.clone())
+            .build()?;
+
+        Ok(Self {
+            context: gpu::Context::new(device)?,
+            batch_size,
+            query_buffer,
+            query_staging_buffer,
+            point_ids_buffer,
+            point_ids_staging_buffer,
+            scores_buffer,
+            scores_staging_buffer,
+            descriptor_set,
+        })
+    }
+
+    fn upload_query(&mut self) -> OperationResult<()> {
+        self.context.copy_gpu_buffer(
+            self.query_staging_buffer.clone(),
+            self.query_buffer.clone(),
+            0,
+            0,
+            self.query_buffer.size(),
+        )?;
+        self.context.run()?;
+        self.context.wait_finish(GPU_TIMEOUT)?;
+        Ok(())
+    }
+
+    fn score_batch(
+        &mut self,
+        pipeline: &Arc<gpu::Pipeline>,
+        gpu_vector_storage: &GpuVectorStorage,
+        point_ids: &[PointOffsetType],
+    ) -> OperationResult<Vec<ScoreType>> {
+        debug_assert!(point_ids.len() <= self.batch_size);
+        if point_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.point_ids_staging_buffer.upload(point_ids, 0)?;
+        self.context.copy_gpu_buffer(
+            self.point_ids_staging_buffer.clone(),
+            self.point_ids_buffer.clone(),
+            0,
+            0,
+            size_of_val(point_ids),
+        )?;
+        self.context.run()?;
+        self.context.wait_finish(GPU_TIMEOUT)?;
+
+        self.context.bind_pipeline(
+            pipeline.clone(),
+            &[
+                self.descriptor_set.clone(),
+                gpu_vector_storage.descriptor_set(),
+            ],
+        )?;
+        self.context.dispatch(point_ids.len(), 1, 1)?;
+        self.context
+            .barrier_buffers(std::slice::from_ref(&self.scores_buffer))?;
+        self.context.run()?;
+        self.context.wait_finish(GPU_TIMEOUT)?;
+
+        self.context.copy_gpu_buffer(
+            self.scores_buffer.clone(),
+            self.scores_staging_buffer.clone(),
+            0,
+            0,
+            point_ids.len() * size_of::<ScoreType>(),
+        )?;
+        self.context.run()?;
+        self.context.wait_finish(GPU_TIMEOUT)?;
+
+        Ok(self
+            .scores_staging_buffer
+            .download_vec::<ScoreType>(0, point_ids.len())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::counter::hardware_counter::HardwareCounterCell;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data_types::vectors::{QueryVector, VectorRef};
+    use crate::fixtures::index_fixtures::random_vector;
+    use crate::vector_storage::dense::volatile_dense_vector_storage::{
+        new_volatile_dense_half_vector_storage, new_volatile_dense_vector_storage,
+    };
+    use crate::vector_storage::{RawScorer, new_raw_scorer_for_test};
+
+    #[rstest]
+    #[case::cosine_f32(Distance::Cosine, VectorStorageDatatype::Float32, 273)]
+    #[case::dot_f32(Distance::Dot, VectorStorageDatatype::Float32, 17)]
+    #[case::euclid_f32(Distance::Euclid, VectorStorageDatatype::Float32, 130)]
+    #[case::manhattan_f32(Distance::Manhattan, VectorStorageDatatype::Float32, 64)]
+    #[case::cosine_f16(Distance::Cosine, VectorStorageDatatype::Float16, 273)]
+    #[case::euclid_f16(Distance::Euclid, VectorStorageDatatype::Float16, 130)]
+    fn test_gpu_score_points(
+        #[case] distance: Distance,
+        #[case] datatype: VectorStorageDatatype,
+        #[case] dim: usize,
+    ) {
+        let _ = env_logger::builder()
+            .is_test(true)
+            .filter_level(log::LevelFilter::Trace)
+            .try_init();
+
+        let num_vectors = SCORE_BATCH_SIZE + 33;
+        let mut rng = StdRng::seed_from_u64(42);
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = match datatype {
+            VectorStorageDatatype::Float16 => new_volatile_dense_half_vector_storage(dim, distance),
+            _ => new_volatile_dense_vector_storage(dim, distance),
+        };
+        for id in 0..num_vectors as PointOffsetType {
+            let vector =
+                distance.preprocess_vector::<VectorElementType>(random_vector(&mut rng, dim));
+            storage
+                .insert_vector(id, VectorRef::from(&vector), &hw_counter)
+                .unwrap();
+        }
+
+        let instance = gpu::GPU_TEST_INSTANCE.clone();
+        let device = gpu::Device::new_with_params(
+            instance.clone(),
+            &instance.physical_devices()[0],
+            0,
+            false,
+        )
+        .unwrap();
+        if datatype == VectorStorageDatatype::Float16 && !device.has_half_precision() {
+            return;
+        }
+
+        let gpu_storage =
+            DenseGpuVectorStorage::new(device, &storage, false, &false.into()).unwrap();
+        assert_eq!(gpu_storage.num_vectors(), num_vectors);
+
+        let query = random_vector(&mut rng, dim);
+        let point_ids: Vec<PointOffsetType> = (0..num_vectors as PointOffsetType)
+            .rev()
+            .step_by(3)
+            .collect();
+        let scores = gpu_storage.score_points(&query, &point_ids).unwrap();
+        assert_eq!(scores.len(), point_ids.len());
+
+        let scorer = new_raw_scorer_for_test(QueryVector::from(query), &storage).unwrap();
+        let precision = match datatype {
+            VectorStorageDatatype::Float16 => 5e-2,
+            _ => 1e-3,
+        };
+        for (&point_id, &score) in point_ids.iter().zip(&scores) {
+            let expected = scorer.score_point(point_id);
+            assert!(
+                (score - expected).abs() < precision * expected.abs().max(1.0),
+                "point {point_id}: {score} != {expected}",
+            );
+        }
+
+        let out_of_range = num_vectors as PointOffsetType;
+        assert!(
+            gpu_storage
+                .score_points(&random_vector(&mut rng, dim), &[out_of_range])
+                .is_err()
+        );
+    }
+}
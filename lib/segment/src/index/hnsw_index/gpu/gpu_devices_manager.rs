@@ -10,6 +10,8 @@ use crate::common::operation_error::OperationResult;
 /// Simple non-invasive permits to use GPU devices.
 pub struct GpuDevicesMaganer {
     devices: Vec<Mutex<Arc<gpu::Device>>>,
+    /// Device for exact search and rescoring, it is not locked by indexing.
+    rescoring_device: Option<Arc<gpu::Device>>,
     device_names: Vec<String>,
     wait_free: bool,
 }
@@ -36,6 +38,7 @@ impl GpuDevicesMaganer {
         allow_emulated: bool,
         wait_free: bool,
         parallel_indexes: usize,
+        rescoring: bool,
     ) -> OperationResult<Self> {
         let instance = gpu::Instance::builder().build()?;
 
@@ -103,6 +106,31 @@ impl GpuDevicesMaganer {
             );
         }
 
+        // Rescoring uses a queue after the ones of indexing, so it does not wait for indexing.
+        let rescoring_device = if rescoring {
+            device_indexes
+                .first()
+                .and_then(|&device_index| filtered_physical_devices.get(device_index))
+                .and_then(|physical_device| {
+                    gpu::Device::new_with_params(
+                        instance.clone(),
+                        physical_device,
+                        parallel_indexes,
+                        false,
+                    )
+                    .inspect_err(|err| {
+                        log::error!(
+                            "Failed to create GPU rescoring device: {:?}, error: {:?}",
+                            &physical_device.name,
+                            err
+                        );
+                    })
+                    .ok()
+                })
+        } else {
+            None
+        };
+
         // All found devices to include it to the telemetry.
         let device_names = instance
             .physical_devices()
@@ -112,6 +140,7 @@ impl GpuDevicesMaganer {
 
         Ok(Self {
             devices,
+            rescoring_device,
             device_names,
             wait_free,
         })
@@ -140,6 +169,11 @@ impl GpuDevicesMaganer {
         }
     }
 
+    /// Device to keep vectors of indexed segments in, for exact search and rescoring.
+    pub fn rescoring_device(&self) -> Option<Arc<gpu::Device>> {
+        self.rescoring_device.clone()
+    }
+
     /// Returns all found device names without filtering.
     pub fn all_found_device_names(&self) -> Vec<String> {
         self.device_names.clone()
@@ -14,6 +14,7 @@ use common::types::PointOffsetType;
 use gpu_multivectors::GpuMultivectors;
 use gpu_quantization::GpuQuantization;
 use quantization::encoded_vectors_binary::{BitsStoreType, EncodedVectorsBin};
+use quantization::encoded_vectors_u8::ScalarQuantizationMethod;
 use quantization::{EncodedStorage, EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8};
 use zerocopy::IntoBytes;
 
@@ -96,6 +97,12 @@ impl ShaderBuilderParameters for GpuVectorStorage {
             VectorStorageDatatype::Uint8 => {
                 defines.insert("VECTOR_STORAGE_ELEMENT_UINT8".to_owned(), None);
             }
+            // Float64 and Bfloat16 storages are rejected on construction,
+            // Int8 and Int4 storages are uploaded as floats
+            VectorStorageDatatype::Float64
+            | VectorStorageDatatype::Bfloat16
+            | VectorStorageDatatype::Int8
+            | VectorStorageDatatype::Int4 => {}
         }
 
         match self.distance {
@@ -302,6 +309,14 @@ impl GpuVectorStorage {
                 Some(GpuMultivectors::new_quantized(device, quantized_storage)?),
                 stopped,
             ),
+            QuantizedVectorStorage::RQRam(_)
+            | QuantizedVectorStorage::RQMmap(_)
+            | QuantizedVectorStorage::RQRamMulti(_)
+            | QuantizedVectorStorage::RQMmapMulti(_) => {
+                Err(OperationError::from(gpu::GpuError::NotSupported(
+                    "Residual quantization is not supported on GPU".to_string(),
+                )))
+            }
         }
     }
 
@@ -313,6 +328,11 @@ impl GpuVectorStorage {
         multivectors: Option<GpuMultivectors>,
         stopped: &AtomicBool,
     ) -> OperationResult<Self> {
+        if quantized_storage.method() != ScalarQuantizationMethod::Int8 {
+            return Err(OperationError::from(gpu::GpuError::NotSupported(
+                "Int4 scalar quantization is not supported on GPU".to_string(),
+            )));
+        }
         Self::new_typed::<VectorElementTypeByte>(
             device.clone(),
             distance,
@@ -419,6 +439,14 @@ impl GpuVectorStorage {
             VectorStorageEnum::DenseMemmapHalf(vector_storage) => {
                 Self::new_dense_f16(device, vector_storage.as_ref(), stopped)
             }
+            VectorStorageEnum::DenseMemmapDouble(_) => Err(OperationError::from(
+                gpu::GpuError::NotSupported("Float64 vectors are not supported on GPU".to_string()),
+            )),
+            VectorStorageEnum::DenseMemmapBfloat16(_) => {
+                Err(OperationError::from(gpu::GpuError::NotSupported(
+                    "Bfloat16 vectors are not supported on GPU".to_string(),
+                )))
+            }
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(vector_storage) => Self::new_dense_f32(
                 device,
@@ -446,6 +474,40 @@ impl GpuVectorStorage {
             VectorStorageEnum::DenseAppendableMemmapHalf(vector_storage) => {
                 Self::new_dense_f16(device, vector_storage.as_ref(), stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(_) => Err(OperationError::from(
+                gpu::GpuError::NotSupported("Float64 vectors are not supported on GPU".to_string()),
+            )),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(_) => {
+                Err(OperationError::from(gpu::GpuError::NotSupported(
+                    "Bfloat16 vectors are not supported on GPU".to_string(),
+                )))
+            }
+            // Int8 and Int4 vectors are uploaded dequantized
+            VectorStorageEnum::DenseAppendableMemmapInt8(vector_storage) => Self::new_dense_f32(
+                device,
+                vector_storage.as_ref(),
+                force_half_precision,
+                stopped,
+            ),
+            VectorStorageEnum::DenseAppendableMemmapInt4(vector_storage) => Self::new_dense_f32(
+                device,
+                vector_storage.as_ref(),
+                force_half_precision,
+                stopped,
+            ),
+            // Vectors without originals are uploaded reconstructed
+            VectorStorageEnum::DenseQuantized(vector_storage) => Self::new_dense_f32(
+                device,
+                vector_storage.as_ref(),
+                force_half_precision,
+                stopped,
+            ),
+            VectorStorageEnum::DenseVersioned(vector_storage) => Self::new_from_vector_storage(
+                device,
+                vector_storage.inner(),
+                force_half_precision,
+                stopped,
+            ),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::from(
                 gpu::GpuError::NotSupported("Sparse vectors are not supported on GPU".to_string()),
@@ -456,6 +518,9 @@ impl GpuVectorStorage {
             VectorStorageEnum::SparseMmap(_) => Err(OperationError::from(
                 gpu::GpuError::NotSupported("Sparse vectors are not supported on GPU".to_string()),
             )),
+            VectorStorageEnum::SparseAppendableWal(_) => Err(OperationError::from(
+                gpu::GpuError::NotSupported("Sparse vectors are not supported on GPU".to_string()),
+            )),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(vector_storage) => Self::new_multi_f32(
                 device.clone(),
@@ -497,6 +562,9 @@ impl GpuVectorStorage {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(vector_storage) => {
                 Self::new_multi_f16(device, vector_storage.as_ref(), stopped)
             }
+            VectorStorageEnum::Tombstone(vector_storage) => Err(
+                OperationError::vector_name_removed(vector_storage.vector_name()),
+            ),
         }
     }
 
@@ -877,4 +945,13 @@ impl GpuVectorStorage {
     pub fn num_vectors(&self) -> usize {
         self.num_vectors
     }
+
+    /// GPU aligned vector size in elements count.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn element_type(&self) -> VectorStorageDatatype {
+        self.element_type
+    }
 }
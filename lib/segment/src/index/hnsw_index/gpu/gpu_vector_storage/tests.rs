@@ -26,6 +26,10 @@ use crate::vector_storage::multi_dense::volatile_multi_dense_vector_storage::{
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectorsStorageType;
 use crate::vector_storage::{DEFAULT_STOPPED, RawScorer, new_raw_scorer_for_test};
 
+/// Max count of inner vectors in test multivectors.
+/// Larger than the batch size of inner vectors in shaders, to cover both full and partial batches.
+const MAX_MULTIVECTOR_SIZE: u8 = 9;
+
 #[derive(Debug, Clone, Copy)]
 enum TestElementType {
     Float32,
@@ -117,6 +121,7 @@ fn test_gpu_vector_storage_sq(
     let quantization_config = QuantizationConfig::Scalar(ScalarQuantization {
         scalar: ScalarQuantizationConfig {
             always_ram: Some(true),
+            drop_originals: None,
             r#type: crate::types::ScalarType::Int8,
             quantile: Some(0.99),
         },
@@ -307,6 +312,7 @@ fn test_gpu_vector_storage_pq(
     let quantization_config = QuantizationConfig::Product(ProductQuantization {
         product: ProductQuantizationConfig {
             always_ram: Some(true),
+            drop_originals: None,
             compression: crate::types::CompressionRatio::X8,
         },
     });
@@ -501,6 +507,48 @@ fn test_gpu_vector_storage_without_half(
     );
 }
 
+/// Shaders score inner vectors of the target multivector in batches.
+/// Check targets with a partial batch, exactly one batch, and more than one batch.
+#[rstest]
+fn test_gpu_vector_storage_multivector_batches(
+    #[values(1, 3, 4, 5, 8, 9)] target_size: usize,
+    #[values(Distance::Cosine, Distance::Dot, Distance::Euclid)] distance: Distance,
+) {
+    let _ = env_logger::builder()
+        .is_test(true)
+        .filter_level(log::LevelFilter::Trace)
+        .try_init();
+
+    let (dim, num_vectors) = (67, 257);
+    let storage_type = TestStorageType::Multi(TestElementType::Float32);
+
+    let mut rnd = StdRng::seed_from_u64(42);
+    let mut storage = new_volatile_multi_dense_vector_storage(dim, distance, Default::default());
+    for i in 0..num_vectors {
+        let num_vectors_per_points = if i == 0 {
+            target_size
+        } else {
+            1 + i % MAX_MULTIVECTOR_SIZE as usize
+        };
+        let mut vectors = vec![];
+        for _ in 0..num_vectors_per_points {
+            let vec = random_vector(&mut rnd, dim);
+            vectors.extend(distance.preprocess_vector::<VectorElementType>(vec));
+        }
+        let multivector = MultiDenseVectorInternal::new(vectors, dim);
+        storage
+            .insert_vector(
+                i as PointOffsetType,
+                VectorRef::from(&multivector),
+                &HardwareCounterCell::new(),
+            )
+            .unwrap();
+    }
+
+    let precision = get_precision(storage_type, dim, distance);
+    check_gpu_vector_storage_scores(&storage, storage_type, None, false, false, precision);
+}
+
 fn get_precision(storage_type: TestStorageType, dim: usize, distance: Distance) -> f32 {
     let distance_persision = match distance {
         Distance::Cosine => 0.01,
@@ -608,7 +656,7 @@ fn create_vector_storage_f32_multi(
         new_volatile_multi_dense_vector_storage(dim, distance, multivector_config);
     for i in 0..num_vectors {
         let mut vectors = vec![];
-        let num_vectors_per_points = 1 + rnd.random::<u8>() % 3;
+        let num_vectors_per_points = 1 + rnd.random::<u8>() % MAX_MULTIVECTOR_SIZE;
         for _ in 0..num_vectors_per_points {
             let vec = random_vector(&mut rnd, dim);
             let vec = distance.preprocess_vector::<VectorElementType>(vec);
@@ -634,7 +682,7 @@ fn create_vector_storage_f16_multi(
         new_volatile_multi_dense_vector_storage_half(dim, distance, multivector_config);
     for i in 0..num_vectors {
         let mut vectors = vec![];
-        let num_vectors_per_points = 1 + rnd.random::<u8>() % 3;
+        let num_vectors_per_points = 1 + rnd.random::<u8>() % MAX_MULTIVECTOR_SIZE;
         for _ in 0..num_vectors_per_points {
             let vec = random_vector(&mut rnd, dim);
             let vec = distance.preprocess_vector::<VectorElementTypeHalf>(vec);
@@ -660,7 +708,7 @@ fn create_vector_storage_u8_multi(
         new_volatile_multi_dense_vector_storage_byte(dim, distance, multivector_config);
     for i in 0..num_vectors {
         let mut vectors = vec![];
-        let num_vectors_per_points = 1 + rnd.random::<u8>() % 3;
+        let num_vectors_per_points = 1 + rnd.random::<u8>() % MAX_MULTIVECTOR_SIZE;
         for _ in 0..num_vectors_per_points {
             let vec = random_dense_byte_vector(&mut rnd, dim);
             let vec = distance.preprocess_vector::<VectorElementTypeByte>(vec);
@@ -686,15 +734,35 @@ fn test_gpu_vector_storage_impl(
     force_half_precision: bool,
     skip_half_support: bool,
     precision: f32,
+) {
+    let storage = create_vector_storage(storage_type, num_vectors, dim, distance);
+    check_gpu_vector_storage_scores(
+        &storage,
+        storage_type,
+        quantization_config,
+        force_half_precision,
+        skip_half_support,
+        precision,
+    );
+}
+
+/// Compare scores of all points against point 0, computed by shaders and on CPU
+fn check_gpu_vector_storage_scores(
+    storage: &VectorStorageEnum,
+    storage_type: TestStorageType,
+    quantization_config: Option<QuantizationConfig>,
+    force_half_precision: bool,
+    skip_half_support: bool,
+    precision: f32,
 ) {
     let test_point_id: PointOffsetType = 0;
+    let num_vectors = storage.total_vector_count();
 
     let dir = tempfile::Builder::new().prefix("db_dir").tempdir().unwrap();
-    let storage = create_vector_storage(storage_type, num_vectors, dim, distance);
 
     let quantized_vectors = quantization_config.as_ref().map(|quantization_config| {
         QuantizedVectors::create(
-            &storage,
+            storage,
             quantization_config,
             QuantizedVectorsStorageType::Immutable,
             dir.path(),
@@ -715,7 +783,7 @@ fn test_gpu_vector_storage_impl(
 
     let gpu_vector_storage = GpuVectorStorage::new(
         device.clone(),
-        &storage,
+        storage,
         quantized_vectors.as_ref(),
         force_half_precision,
         &DEFAULT_STOPPED,
@@ -822,7 +890,7 @@ fn test_gpu_vector_storage_impl(
             .raw_scorer(query, hardware_counter)
             .unwrap()
     } else {
-        new_raw_scorer_for_test(query, &storage).unwrap()
+        new_raw_scorer_for_test(query, storage).unwrap()
     };
 
     for (point_id, gpu_score) in gpu_scores.iter().enumerate() {
@@ -1,4 +1,5 @@
 pub mod batched_points;
+pub mod gpu_dense_vector_storage;
 pub mod gpu_devices_manager;
 pub mod gpu_graph_builder;
 pub mod gpu_insert_context;
@@ -216,6 +217,7 @@ mod tests {
                     top,
                     ef,
                     SearchAlgorithm::Hnsw,
+                    None,
                     scorer,
                     None,
                     &DEFAULT_STOPPED,
@@ -229,6 +231,7 @@ mod tests {
                     top,
                     ef,
                     SearchAlgorithm::Hnsw,
+                    None,
                     scorer,
                     None,
                     &DEFAULT_STOPPED,
@@ -0,0 +1,248 @@
+//! Portable format of HNSW graphs, used to export the graph of a segment and to import a graph
+//! built elsewhere, e.g. by an offline indexing pipeline.
+//!
+//! The format is [JSON Lines](https://jsonlines.org). The first line is a [`GraphHeader`], each
+//! following line is a [`GraphPoint`]:
+//!
+//! ```text
+//! {"format_version":1,"m":16,"m0":32,"entry_points":[{"id":42,"level":2}]}
+//! {"id":42,"links":[[1,7],[7],[]]}
+//! {"id":1,"links":[[42,7]]}
+//! {"id":7,"links":[[42,1],[42]]}
+//! ```
+//!
+//! Points are identified by their external IDs, so that a graph can be imported into any segment
+//! holding the same points. `links[level]` are neighbours of the point on `level`, the number of
+//! lists is the level of the point plus one. A neighbour on `level` must have at least this level.
+//!
+//! Only points with vectors are exported. On import, each point of the segment with a vector
+//! must be present in the graph.
+
+use std::io::{BufRead, Write};
+
+use common::bitvec::{BitSlice, BitSliceExt as _, BitVec};
+use common::types::PointOffsetType;
+use serde::{Deserialize, Serialize};
+
+use super::HnswM;
+use super::graph_layers::GraphLayers;
+use super::graph_layers_builder::GraphLayersBuilder;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::types::PointIdType;
+
+/// Version of the format, written by [`export_graph`].
+pub const GRAPH_FORMAT_VERSION: u32 = 1;
+
+/// First line of an exported graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphHeader {
+    pub format_version: u32,
+    /// Max number of links per point on levels above 0
+    pub m: usize,
+    /// Max number of links per point on level 0
+    pub m0: usize,
+    /// Points to start the search from, highest level first
+    pub entry_points: Vec<GraphEntryPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphEntryPoint {
+    pub id: PointIdType,
+    pub level: usize,
+}
+
+/// Links of a single point, one line per point of an exported graph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphPoint {
+    pub id: PointIdType,
+    /// Neighbours of the point, by level starting from 0
+    pub links: Vec<Vec<PointIdType>>,
+}
+
+/// Write `graph` in the portable format.
+///
+/// Points without external ID or without vector, as well as links to them, are skipped.
+pub(super) fn export_graph<W: Write>(
+    graph: &GraphLayers,
+    id_tracker: &IdTrackerEnum,
+    deleted_vectors: &BitSlice,
+    mut writer: W,
+) -> OperationResult<()> {
+    let external_id = |point_id: PointOffsetType| {
+        if deleted_vectors.get_bit(point_id as usize).unwrap_or(false) {
+            return None;
+        }
+        id_tracker.external_id(point_id)
+    };
+
+    let header = GraphHeader {
+        format_version: GRAPH_FORMAT_VERSION,
+        m: graph.hnsw_m.m,
+        m0: graph.hnsw_m.m0,
+        entry_points: graph
+            .entry_points
+            .iter()
+            .filter_map(|entry_point| {
+                Some(GraphEntryPoint {
+                    id: external_id(entry_point.point_id)?,
+                    level: entry_point.level,
+                })
+            })
+            .collect(),
+    };
+    serde_json::to_writer(&mut writer, &header)?;
+    writer.write_all(b"\n")?;
+
+    for point_id in 0..graph.num_points() as PointOffsetType {
+        let Some(id) = external_id(point_id) else {
+            continue;
+        };
+        let links = (0..=graph.point_level(point_id))
+            .map(|level| {
+                graph
+                    .links
+                    .links(point_id, level)
+                    .filter_map(external_id)
+                    .collect()
+            })
+            .collect();
+        serde_json::to_writer(&mut writer, &GraphPoint { id, links })?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read a graph in the portable format, mapping its points to points of `id_tracker`.
+///
+/// Returns the builder with all links set, and the header of the graph.
+pub(super) fn import_graph<R: BufRead>(
+    reader: R,
+    id_tracker: &IdTrackerEnum,
+    deleted_vectors: &BitSlice,
+    total_vector_count: usize,
+    ef_construct: usize,
+) -> OperationResult<(GraphLayersBuilder, GraphHeader)> {
+    let mut lines = reader.lines();
+
+    let header_line = lines
+        .next()
+        .ok_or_else(|| OperationError::validation_error("imported HNSW graph is empty"))??;
+    let header: GraphHeader = serde_json::from_str(&header_line)?;
+    if header.format_version != GRAPH_FORMAT_VERSION {
+        return Err(OperationError::validation_error(format!(
+            "unsupported version {} of imported HNSW graph, expected {GRAPH_FORMAT_VERSION}",
+            header.format_version,
+        )));
+    }
+
+    let internal_id = |id: PointIdType| {
+        id_tracker
+            .internal_id(id)
+            .filter(|&point_id| {
+                (point_id as usize) < total_vector_count
+                    && !deleted_vectors.get_bit(point_id as usize).unwrap_or(false)
+            })
+            .ok_or_else(|| {
+                OperationError::validation_error(format!(
+                    "point {id} of imported HNSW graph has no vector in the segment",
+                ))
+            })
+    };
+
+    if header.entry_points.is_empty() && header.m > 0 {
+        return Err(OperationError::validation_error(
+            "imported HNSW graph has no entry points",
+        ));
+    }
+
+    // Levels of all points must be known to validate links
+    let mut points = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let GraphPoint { id, links } = serde_json::from_str(&line)?;
+        if links.is_empty() {
+            return Err(OperationError::validation_error(format!(
+                "point {id} of imported HNSW graph has no list of links",
+            )));
+        }
+        let links = links
+            .into_iter()
+            .map(|level_links| level_links.into_iter().map(internal_id).collect())
+            .collect::<OperationResult<Vec<Vec<PointOffsetType>>>>()?;
+        points.push((id, internal_id(id)?, links));
+    }
+
+    let mut graph_layers_builder = GraphLayersBuilder::new_with_params(
+        total_vector_count,
+        HnswM::new(header.m, header.m0),
+        ef_construct,
+        header.entry_points.len(),
+        true,
+        false,
+    );
+
+    let mut imported = BitVec::repeat(false, total_vector_count);
+    for (id, point_id, links) in &points {
+        if imported.replace(*point_id as usize, true) {
+            return Err(OperationError::validation_error(format!(
+                "point {id} of imported HNSW graph is listed twice",
+            )));
+        }
+        graph_layers_builder.set_levels(*point_id, links.len() - 1);
+    }
+
+    let missing = id_tracker
+        .point_mappings()
+        .iter_internal_excluding(deleted_vectors)
+        .filter(|&point_id| !imported.get_bit(point_id as usize).unwrap_or(false))
+        .find_map(|point_id| id_tracker.external_id(point_id));
+    if let Some(missing) = missing {
+        return Err(OperationError::validation_error(format!(
+            "point {missing} of the segment is missing in imported HNSW graph",
+        )));
+    }
+
+    for (id, point_id, links) in points {
+        for (level, level_links) in links.into_iter().enumerate() {
+            if let Some(&link) = level_links
+                .iter()
+                .find(|&&link| graph_layers_builder.get_point_level(link) < level)
+            {
+                let link_id = id_tracker
+                    .external_id(link)
+                    .map_or_else(|| link.to_string(), |link_id| link_id.to_string());
+                return Err(OperationError::validation_error(format!(
+                    "link {id} -> {link_id} of imported HNSW graph is on level {level}, \
+                     which is above the level of the linked point",
+                )));
+            }
+            graph_layers_builder.links_layers()[point_id as usize][level]
+                .write()
+                .fill_from(level_links.into_iter());
+        }
+    }
+
+    {
+        let mut entry_points = graph_layers_builder.get_entry_points();
+        for entry_point in &header.entry_points {
+            let point_id = internal_id(entry_point.id)?;
+            if graph_layers_builder.get_point_level(point_id) != entry_point.level {
+                return Err(OperationError::validation_error(format!(
+                    "entry point {} of imported HNSW graph has level {}, but the point has level {}",
+                    entry_point.id,
+                    entry_point.level,
+                    graph_layers_builder.get_point_level(point_id),
+                )));
+            }
+            entry_points.new_point(point_id, entry_point.level, |_| true);
+        }
+    }
+
+    Ok((graph_layers_builder, header))
+}
@@ -0,0 +1,122 @@
+//! Health metrics of an HNSW graph, which help to decide whether the graph should be rebuilt.
+//!
+//! Search never visits deleted points, so connectivity is measured among non-deleted points only:
+//! a graph degrades as deletions cut paths between the remaining points.
+
+use std::collections::VecDeque;
+
+use common::bitvec::{BitSlice, BitSliceExt as _, BitVec};
+use common::types::PointOffsetType;
+
+use super::graph_layers::GraphLayers;
+use crate::telemetry::HnswGraphTelemetry;
+
+/// Collect health metrics of `graph`, with vectors of `deleted_vectors` excluded.
+pub(super) fn graph_health(graph: &GraphLayers, deleted_vectors: &BitSlice) -> HnswGraphTelemetry {
+    let num_points = graph.num_points();
+    let is_live =
+        |point_id: PointOffsetType| !deleted_vectors.get_bit(point_id as usize).unwrap_or(false);
+
+    // Number of points and number of their links, by level
+    let mut level_stats: Vec<(usize, usize)> = Vec::new();
+    let mut components = DisjointSets::new(num_points);
+    let mut live_points = 0;
+
+    for point_id in (0..num_points as PointOffsetType).filter(|&point_id| is_live(point_id)) {
+        live_points += 1;
+        let point_level = graph.point_level(point_id);
+        if level_stats.len() <= point_level {
+            level_stats.resize(point_level + 1, (0, 0));
+        }
+        for (level, (points, links)) in level_stats.iter_mut().enumerate().take(point_level + 1) {
+            *points += 1;
+            for link in graph.links.links(point_id, level) {
+                *links += 1;
+                if level == 0 && is_live(link) {
+                    components.union(point_id, link);
+                }
+            }
+        }
+    }
+
+    let connected_components = (0..num_points as PointOffsetType)
+        .filter(|&point_id| is_live(point_id) && components.find(point_id) == point_id)
+        .count();
+
+    let reachable_points = graph
+        .entry_points
+        .get_entry_point(is_live)
+        .map_or(0, |entry_point| {
+            count_reachable(graph, entry_point.point_id, is_live)
+        });
+
+    let deleted_points = num_points - live_points;
+
+    HnswGraphTelemetry {
+        vector_name: None,
+        points_count: num_points,
+        avg_out_degree: level_stats
+            .iter()
+            .map(|&(points, links)| links as f64 / points as f64)
+            .collect(),
+        connected_components,
+        orphaned_points: live_points - reachable_points,
+        deleted_percent: if num_points != 0 {
+            deleted_points as f64 * 100.0 / num_points as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Number of live points, reachable from `entry_point` by links of level 0.
+fn count_reachable(
+    graph: &GraphLayers,
+    entry_point: PointOffsetType,
+    is_live: impl Fn(PointOffsetType) -> bool,
+) -> usize {
+    let mut visited = BitVec::repeat(false, graph.num_points());
+    let mut queue = VecDeque::from([entry_point]);
+    visited.set(entry_point as usize, true);
+    let mut reachable = 0;
+
+    while let Some(point_id) = queue.pop_front() {
+        reachable += 1;
+        for link in graph.links.links(point_id, 0) {
+            if is_live(link) && !visited.replace(link as usize, true) {
+                queue.push_back(link);
+            }
+        }
+    }
+    reachable
+}
+
+/// Union-find over point IDs, to count connected components.
+struct DisjointSets {
+    parents: Vec<PointOffsetType>,
+}
+
+impl DisjointSets {
+    fn new(num_points: usize) -> Self {
+        Self {
+            parents: (0..num_points as PointOffsetType).collect(),
+        }
+    }
+
+    fn find(&mut self, mut point_id: PointOffsetType) -> PointOffsetType {
+        while self.parents[point_id as usize] != point_id {
+            // Path halving
+            let grandparent = self.parents[self.parents[point_id as usize] as usize];
+            self.parents[point_id as usize] = grandparent;
+            point_id = grandparent;
+        }
+        point_id
+    }
+
+    fn union(&mut self, a: PointOffsetType, b: PointOffsetType) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parents[a.max(b) as usize] = a.min(b);
+        }
+    }
+}
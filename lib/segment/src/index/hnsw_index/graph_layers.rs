@@ -46,8 +46,9 @@ use crate::common::operation_error::{
 use crate::common::utils::rev_range;
 use crate::index::hnsw_index::graph_links::{GraphLinksFormatParam, serialize_graph_links};
 use crate::index::hnsw_index::point_scorer::{FilteredBytesScorer, FilteredScorer, ScorerFilters};
-use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::hnsw_index::search_context::{ScoreConvergence, SearchContext};
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
+use crate::types::EarlyTerminationParams;
 use crate::vector_storage::RawScorer;
 use crate::vector_storage::query_scorer::QueryScorerBytes;
 
@@ -59,6 +60,7 @@ pub const HNSW_GRAPH_FILE: &str = "graph.bin";
 pub const HNSW_LINKS_FILE: &str = "links.bin";
 pub const COMPRESSED_HNSW_LINKS_FILE: &str = "links_compressed.bin";
 pub const COMPRESSED_WITH_VECTORS_HNSW_LINKS_FILE: &str = "links_comp_vec.bin";
+pub const COMPRESSED_VARINT_HNSW_LINKS_FILE: &str = "links_comp_varint.bin";
 
 /// Contents of the `graph.bin` file.
 #[derive(Deserialize, Serialize, Debug)]
@@ -104,19 +106,22 @@ pub trait GraphLayersBase {
 
     /// Beam search for closest points within a single graph layer.
     ///
+    /// If `convergence` is given, the search stops early once top results converge.
+    ///
     /// See [module docs](self) for comparison with other search functions.
     fn search_on_level(
         &self,
         level_entry: ScoredPointOffset,
         level: usize,
         ef: usize,
+        convergence: Option<ScoreConvergence>,
         points_scorer: &mut FilteredScorer,
         is_stopped: &AtomicBool,
     ) -> CancellableResult<FixedLengthPriorityQueue<ScoredPointOffset>> {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
 
-        let mut search_context = SearchContext::new(ef);
+        let mut search_context = SearchContext::new(ef).with_convergence(convergence);
         search_context.process_candidate(level_entry);
 
         let limit = self.get_m(level);
@@ -129,6 +134,7 @@ pub trait GraphLayersBase {
                 break;
             }
 
+            points_scorer.diagnostics().add_hop(level);
             points_ids.clear();
             self.for_each_link(candidate.idx, level, |link| {
                 if !visited_list.check(link) {
@@ -142,6 +148,11 @@ pub trait GraphLayersBase {
                     search_context.process_candidate(score_point);
                     visited_list.check_and_update_visited(score_point.idx);
                 });
+
+            if search_context.end_expansion() {
+                points_scorer.diagnostics().add_early_termination();
+                break;
+            }
         }
 
         Ok(search_context.nearest)
@@ -156,6 +167,7 @@ pub trait GraphLayersBase {
         level_entry: ScoredPointOffset,
         level: usize,
         ef: usize,
+        convergence: Option<ScoreConvergence>,
         points_scorer: &mut FilteredScorer,
         is_stopped: &AtomicBool,
     ) -> CancellableResult<FixedLengthPriorityQueue<ScoredPointOffset>> {
@@ -171,7 +183,7 @@ pub trait GraphLayersBase {
         // 1-hop neighbor.
         let mut hop2_visited_list = self.get_visited_list_from_pool();
 
-        let mut search_context = SearchContext::new(ef);
+        let mut search_context = SearchContext::new(ef).with_convergence(convergence);
         search_context.process_candidate(level_entry);
 
         // Limits are per every explored 1-hop or 2-hop neighbors, not total.
@@ -191,14 +203,17 @@ pub trait GraphLayersBase {
                 break;
             }
 
+            points_scorer.diagnostics().add_hop(level);
             to_explore.clear();
             to_score.clear();
+            let mut visited_count = 0;
 
             // Collect 1-hop neighbors (direct neighbors)
             _ = self.try_for_each_link(candidate.idx, level, |hop1| {
                 if hop1_visited_list.check_and_update_visited(hop1) {
                     return ControlFlow::Continue(());
                 }
+                visited_count += 1;
 
                 if points_scorer.filters().check_vector(hop1) {
                     to_score.push(hop1);
@@ -222,6 +237,7 @@ pub trait GraphLayersBase {
                     {
                         return ControlFlow::Continue(());
                     }
+                    visited_count += 1;
 
                     if points_scorer.filters().check_vector(hop2) {
                         hop1_visited_list.check_and_update_visited(hop2);
@@ -233,10 +249,16 @@ pub trait GraphLayersBase {
                     ControlFlow::Continue(())
                 });
             }
+            points_scorer.diagnostics().add_visited_nodes(visited_count);
 
             points_scorer
                 .score_points_unfiltered(&to_score)
                 .for_each(|score_point| search_context.process_candidate(score_point));
+
+            if search_context.end_expansion() {
+                points_scorer.diagnostics().add_early_termination();
+                break;
+            }
         }
 
         Ok(search_context.nearest)
@@ -297,6 +319,7 @@ pub trait GraphLayersBase {
         };
         while changed {
             changed = false;
+            points_scorer.diagnostics().add_hop(level);
 
             links.clear();
             self.for_each_link(current_point.idx, level, |link| {
@@ -364,6 +387,7 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
                 break;
             }
 
+            links_scorer.diagnostics().add_hop(level);
             points.clear();
             let (base_vector, links_iter) = self.links_with_vectors(candidate.idx, level);
             links_iter.for_each(|(link, link_vector)| {
@@ -433,6 +457,7 @@ pub trait GraphLayersWithVectors: GraphLayersBase {
         let mut current_point = entry_point;
         while changed {
             changed = false;
+            links_scorer.diagnostics().add_hop(level);
 
             links.clear();
             let (_, links_iter) = self.links_with_vectors(current_point.idx, level);
@@ -527,11 +552,13 @@ impl GraphLayers {
             })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn search(
         &self,
         top: usize,
         ef: usize,
         algorithm: SearchAlgorithm,
+        early_termination: Option<&EarlyTerminationParams>,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
         is_stopped: &AtomicBool,
@@ -549,13 +576,24 @@ impl GraphLayers {
             is_stopped,
         )?;
         let ef = max(ef, top);
+        let convergence = early_termination.map(|params| ScoreConvergence::new(top, params));
         let nearest = match algorithm {
-            SearchAlgorithm::Hnsw => {
-                self.search_on_level(zero_level_entry, 0, ef, &mut points_scorer, is_stopped)
-            }
-            SearchAlgorithm::Acorn => {
-                self.search_on_level_acorn(zero_level_entry, 0, ef, &mut points_scorer, is_stopped)
-            }
+            SearchAlgorithm::Hnsw => self.search_on_level(
+                zero_level_entry,
+                0,
+                ef,
+                convergence,
+                &mut points_scorer,
+                is_stopped,
+            ),
+            SearchAlgorithm::Acorn => self.search_on_level_acorn(
+                zero_level_entry,
+                0,
+                ef,
+                convergence,
+                &mut points_scorer,
+                is_stopped,
+            ),
         }?;
         Ok(nearest.into_iter_sorted().take(top).collect_vec())
     }
@@ -606,6 +644,7 @@ impl GraphLayers {
             GraphLinksFormat::CompressedWithVectors => {
                 path.join(COMPRESSED_WITH_VECTORS_HNSW_LINKS_FILE)
             }
+            GraphLinksFormat::CompressedVarint => path.join(COMPRESSED_VARINT_HNSW_LINKS_FILE),
         }
     }
 
@@ -640,6 +679,7 @@ impl GraphLayers {
     fn load_links(dir: &Path, on_disk: bool) -> OperationResult<GraphLinks> {
         for format in [
             GraphLinksFormat::CompressedWithVectors,
+            GraphLinksFormat::CompressedVarint,
             GraphLinksFormat::Compressed,
             GraphLinksFormat::Plain,
         ] {
@@ -661,8 +701,12 @@ impl GraphLayers {
         let compressed_path = Self::get_links_path(dir, GraphLinksFormat::Compressed);
         let compressed_with_vectors_path =
             Self::get_links_path(dir, GraphLinksFormat::CompressedWithVectors);
+        let compressed_varint_path = Self::get_links_path(dir, GraphLinksFormat::CompressedVarint);
 
-        if compressed_path.exists() || compressed_with_vectors_path.exists() {
+        if compressed_path.exists()
+            || compressed_with_vectors_path.exists()
+            || compressed_varint_path.exists()
+        {
             return Ok(());
         }
 
@@ -743,6 +787,7 @@ mod tests {
                 top,
                 ef,
                 SearchAlgorithm::Hnsw,
+                None,
                 scorer,
                 None,
                 &DEFAULT_STOPPED,
@@ -796,6 +841,7 @@ mod tests {
                 },
                 0,
                 32,
+                None,
                 &mut scorer,
                 &DEFAULT_STOPPED,
             )
@@ -818,6 +864,7 @@ mod tests {
     #[case::compressed((GraphLinksFormat::Compressed, false))]
     #[case::recompressed((GraphLinksFormat::Compressed, true))]
     #[case::compressed_with_vectors((GraphLinksFormat::CompressedWithVectors, false))]
+    #[case::compressed_varint((GraphLinksFormat::CompressedVarint, false))]
     fn test_save_and_load(#[case] (initial_format, compress): (GraphLinksFormat, bool)) {
         let distance = Distance::Cosine;
         let num_vectors = 100;
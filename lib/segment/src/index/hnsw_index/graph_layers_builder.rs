@@ -13,6 +13,8 @@ use common::types::{PointOffsetType, ScoredPointOffset};
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use rand::distr::Uniform;
 use rand::{Rng, RngExt};
+use rayon::ThreadPool;
+use rayon::iter::{IntoParallelRefMutIterator as _, ParallelIterator as _};
 
 use super::HnswM;
 use super::graph_layers::GraphLayerData;
@@ -107,7 +109,12 @@ impl GraphLayersBuilder {
     ///  - Return the fraction of reachable nodes to the total number of nodes in the sub-graph.
     ///
     /// Coin probability `q` is a parameter of this function. By default, it is 0.5.
-    pub fn subgraph_connectivity(&self, points: &[PointOffsetType], q: f32) -> f32 {
+    pub fn subgraph_connectivity<R: Rng + ?Sized>(
+        &self,
+        points: &[PointOffsetType],
+        q: f32,
+        rnd: &mut R,
+    ) -> f32 {
         if points.is_empty() {
             return 1.0;
         }
@@ -121,14 +128,12 @@ impl GraphLayersBuilder {
             point_selection.set(*point_id as usize, true);
         }
 
-        let mut rnd = rand::rng();
-
         // Try to get entry point from the entry points list
         // If not found, select the point with the highest level
         let entry_point = self
             .entry_points
             .lock()
-            .get_random_entry_point(&mut rnd, |point_id| {
+            .get_random_entry_point(rnd, |point_id| {
                 point_selection.get_bit(point_id as usize).unwrap_or(false)
             })
             .map(|ep| ep.point_id);
@@ -393,6 +398,7 @@ impl GraphLayersBuilder {
             while self.links_layers.len() <= point_id as usize {
                 self.links_layers.push(vec![]);
             }
+            self.ready_list.resize(self.links_layers.len(), false);
         }
         let point_layers = &mut self.links_layers[point_id as usize];
         while point_layers.len() <= level {
@@ -403,6 +409,25 @@ impl GraphLayersBuilder {
             .fetch_max(level, std::sync::atomic::Ordering::Relaxed);
     }
 
+    /// Allocate links of all points again from threads of the `pool`.
+    ///
+    /// Used if threads of the pool are pinned to NUMA nodes: each chunk of points gets its links
+    /// allocated by a thread of some node, so that the graph is spread over memory of all nodes,
+    /// instead of the node of the thread which created the builder.
+    ///
+    /// Must be called before any point is linked.
+    pub fn reallocate_links(&mut self, pool: &ThreadPool) {
+        let hnsw_m = self.hnsw_m;
+        pool.install(|| {
+            self.links_layers.par_iter_mut().for_each(|layers| {
+                for (level, links) in layers.iter_mut().enumerate() {
+                    debug_assert!(links.get_mut().links().is_empty());
+                    *links = RwLock::new(LinksContainer::with_capacity(hnsw_m.level_m(level)));
+                }
+            });
+        });
+    }
+
     pub fn link_new_point(&self, point_id: PointOffsetType, mut points_scorer: FilteredScorer) {
         // Check if there is an suitable entry point
         //   - entry point level if higher or equal
@@ -500,6 +525,7 @@ impl GraphLayersBuilder {
                 level_entry,
                 curr_level,
                 self.ef_construct,
+                None,
                 points_scorer,
                 &AtomicBool::new(false),
             )
@@ -768,6 +794,7 @@ mod tests {
                 top,
                 ef,
                 SearchAlgorithm::Hnsw,
+                None,
                 scorer,
                 None,
                 &DEFAULT_STOPPED,
@@ -781,6 +808,7 @@ mod tests {
     #[case::uncompressed(GraphLinksFormat::Plain)]
     #[case::compressed(GraphLinksFormat::Compressed)]
     #[case::compressed_with_vectors(GraphLinksFormat::CompressedWithVectors)]
+    #[case::compressed_varint(GraphLinksFormat::CompressedVarint)]
     fn test_add_points(#[case] format: GraphLinksFormat) {
         let distance = Distance::Cosine;
         let num_vectors = 1000;
@@ -824,7 +852,9 @@ mod tests {
             let link_container_from_builder = links_builder.links().to_vec();
             let m = match format {
                 GraphLinksFormat::Plain => 0,
-                GraphLinksFormat::Compressed | GraphLinksFormat::CompressedWithVectors => M * 2,
+                GraphLinksFormat::Compressed
+                | GraphLinksFormat::CompressedWithVectors
+                | GraphLinksFormat::CompressedVarint => M * 2,
             };
             assert_eq!(
                 normalize_links(m, links_orig.clone()),
@@ -881,6 +911,7 @@ mod tests {
                 top,
                 ef,
                 SearchAlgorithm::Hnsw,
+                None,
                 scorer,
                 None,
                 &DEFAULT_STOPPED,
@@ -25,17 +25,28 @@ pub struct GraphLayersHealer<'a> {
 }
 
 impl<'a> GraphLayersHealer<'a> {
+    /// Prepare healing of `graph_layers`, where points mapped to `None` by `old_to_new` are
+    /// deleted.
+    ///
+    /// Links beyond `m`, added by payload-specific graphs, are only kept if
+    /// `keep_additional_links` is set. Otherwise they are expected to be rebuilt afterwards.
     pub fn new(
         graph_layers: &GraphLayers,
         old_to_new: &'a [Option<PointOffsetType>],
         ef_construct: usize,
+        keep_additional_links: bool,
     ) -> Self {
         let mut to_heal = Vec::new();
         let links_layers = {
             graph_layers.links.to_edges_impl(|point_id, level| {
                 let level_m = graph_layers.hnsw_m.level_m(level);
+                let links_limit = if keep_additional_links {
+                    usize::MAX
+                } else {
+                    level_m
+                };
                 let mut container = LinksContainer::with_capacity(level_m);
-                container.fill_from(graph_layers.links.links(point_id, level).take(level_m));
+                container.fill_from(graph_layers.links.links(point_id, level).take(links_limit));
                 if container
                     .iter()
                     .any(|neighbor| old_to_new[neighbor as usize].is_none())
@@ -178,7 +189,7 @@ impl<'a> GraphLayersHealer<'a> {
         let scorer_fn = |a, b| scorer.score_internal(a, b);
         container.fill_from_sorted_with_heuristic(
             shortcuts.into_iter_sorted(),
-            level_m - valid_links.len(),
+            level_m.saturating_sub(valid_links.len()),
             scorer_fn,
         );
         for &link in &valid_links {
@@ -194,6 +205,10 @@ impl<'a> GraphLayersHealer<'a> {
         let mut items = ItemsBuffer::default();
         for other_point in container {
             let mut other_container = self.links_layers[other_point as usize][level].write();
+            // Links of points with additional payload links are not pruned
+            if other_container.links().len() > level_m {
+                continue;
+            }
             if !other_container.iter().any(|link| link == offset) {
                 other_container.connect_with_heuristic(
                     offset,
@@ -59,6 +59,7 @@ pub enum GraphLinksFormat {
     Plain,
     Compressed,
     CompressedWithVectors,
+    CompressedVarint,
 }
 
 /// Similar to [`GraphLinksFormat`], won't let you use `CompressedWithVectors`
@@ -68,6 +69,7 @@ pub enum GraphLinksFormatParam<'a> {
     Plain,
     Compressed,
     CompressedWithVectors(&'a dyn GraphLinksVectors),
+    CompressedVarint,
 }
 
 /// This trait lets the [`serialize_graph_links`] to access vector values.
@@ -166,6 +168,7 @@ impl GraphLinksFormat {
                 Some(v) => GraphLinksFormatParam::CompressedWithVectors(v),
                 None => panic!(),
             },
+            GraphLinksFormat::CompressedVarint => GraphLinksFormatParam::CompressedVarint,
         }
     }
 
@@ -184,12 +187,15 @@ impl GraphLinksFormat {
                 Some(v) => GraphLinksFormatParam::CompressedWithVectors(v),
                 None => GraphLinksFormatParam::Compressed,
             },
+            GraphLinksFormat::CompressedVarint => GraphLinksFormatParam::CompressedVarint,
         }
     }
 
     pub fn is_with_vectors(&self) -> bool {
         match self {
-            GraphLinksFormat::Plain | GraphLinksFormat::Compressed => false,
+            GraphLinksFormat::Plain
+            | GraphLinksFormat::Compressed
+            | GraphLinksFormat::CompressedVarint => false,
             GraphLinksFormat::CompressedWithVectors => true,
         }
     }
@@ -203,6 +209,7 @@ impl<'a> GraphLinksFormatParam<'a> {
             GraphLinksFormatParam::CompressedWithVectors(_) => {
                 GraphLinksFormat::CompressedWithVectors
             }
+            GraphLinksFormatParam::CompressedVarint => GraphLinksFormat::CompressedVarint,
         }
     }
 }
@@ -274,6 +281,7 @@ impl GraphLinks {
             CompressionInfo::CompressedWithVectors { .. } => {
                 GraphLinksFormat::CompressedWithVectors
             }
+            CompressionInfo::CompressedVarint { .. } => GraphLinksFormat::CompressedVarint,
         }
     }
 
@@ -482,6 +490,7 @@ mod tests {
     #[rstest]
     #[case::plain(GraphLinksFormat::Plain, 8, 8)]
     #[case::compressed(GraphLinksFormat::Compressed, 8, 8)]
+    #[case::compressed_varint(GraphLinksFormat::CompressedVarint, 8, 8)]
     #[case::comp_vec_1_16(GraphLinksFormat::CompressedWithVectors, 1, 16)]
     #[case::comp_vec_4_1(GraphLinksFormat::CompressedWithVectors, 4, 1)]
     #[case::comp_vec_4_16(GraphLinksFormat::CompressedWithVectors, 4, 16)]
@@ -516,6 +525,7 @@ mod tests {
     #[case::uncompressed(GraphLinksFormat::Plain)]
     #[case::compressed(GraphLinksFormat::Compressed)]
     #[case::compressed_with_vectors(GraphLinksFormat::CompressedWithVectors)]
+    #[case::compressed_varint(GraphLinksFormat::CompressedVarint)]
     fn test_graph_links_construction(#[case] format: GraphLinksFormat) {
         let hnsw_m = HnswM::new2(8);
 
@@ -19,12 +19,12 @@ pub(super) struct HeaderPlain {
     pub(super) zero_padding: [u8; 24],
 }
 
-/// File header for the compressed format.
+/// File header for the compressed and the compressed varint formats.
 #[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
 #[repr(C, align(8))]
 pub(super) struct HeaderCompressed {
     pub(super) point_count: LittleU64,
-    /// Should be [`HEADER_VERSION_COMPRESSED`].
+    /// Should be [`HEADER_VERSION_COMPRESSED`] or [`HEADER_VERSION_COMPRESSED_VARINT`].
     pub(super) version: LittleU64,
     pub(super) levels_count: LittleU64,
     pub(super) total_neighbors_bytes: LittleU64,
@@ -53,6 +53,7 @@ pub(super) struct HeaderCompressedWithVectors {
 
 pub(super) const HEADER_VERSION_COMPRESSED: u64 = 0xFFFF_FFFF_FFFF_FF01;
 pub(super) const HEADER_VERSION_COMPRESSED_WITH_VECTORS: u64 = 0xFFFF_FFFF_FFFF_FF02;
+pub(super) const HEADER_VERSION_COMPRESSED_VARINT: u64 = 0xFFFF_FFFF_FFFF_FF03;
 
 /// Packed representation of [`Layout`].
 #[derive(Copy, Clone, FromBytes, Immutable, IntoBytes, KnownLayout)]
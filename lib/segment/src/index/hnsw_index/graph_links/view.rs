@@ -8,13 +8,16 @@ use common::bitpacking_links::{
 };
 use common::bitpacking_ordered;
 use common::types::PointOffsetType;
+use common::varint_links::{VarintLinksIterator, iterate_varint_links};
 use integer_encoding::VarInt as _;
 use itertools::{Either, Itertools as _};
 use zerocopy::native_endian::U64 as NativeU64;
 use zerocopy::{FromBytes, Immutable};
 
 use super::GraphLinksFormat;
-use super::header::{HEADER_VERSION_COMPRESSED, HeaderCompressed, HeaderPlain};
+use super::header::{
+    HEADER_VERSION_COMPRESSED, HEADER_VERSION_COMPRESSED_VARINT, HeaderCompressed, HeaderPlain,
+};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::index::hnsw_index::HnswM;
 use crate::index::hnsw_index::graph_links::header::{
@@ -35,7 +38,10 @@ pub(super) struct GraphLinksView<'a> {
 }
 
 /// An iterator type returned by [`GraphLinksView::links`].
-pub type LinksIterator<'a> = Either<Copied<std::slice::Iter<'a, u32>>, PackedLinksIterator<'a>>;
+pub type LinksIterator<'a> = Either<
+    Either<Copied<std::slice::Iter<'a, u32>>, PackedLinksIterator<'a>>,
+    VarintLinksIterator<'a>,
+>;
 
 /// An iterator type returned by [`super::GraphLinks::links_with_vectors`].
 /// Iterates over pairs of ([`PointOffsetType`], `&[u8]`). The second element is
@@ -104,6 +110,23 @@ pub(super) enum CompressionInfo<'a> {
         link_vector_size: NonZero<usize>,
         link_vector_alignment: u8,
     },
+    CompressedVarint {
+        /// Links compressed with varint delta encoding.
+        ///
+        /// Same layout as [`CompressionInfo::Compressed`], but each node's
+        /// links are packed by [`common::varint_links::pack_varint_links`].
+        ///
+        /// ```text
+        /// [vvvvvvvvvvvvvvvvvvvv][vvvvvvvvvvvvvvvvvvvv][vvvvvvvvvvvvvvvvvvvv]...
+        /// [neighbors for node 0][neighbors for node 1][neighbors for node 2]...
+        /// ```
+        /// Where
+        /// 1. `v` are varint-encoded links, the first `m` (or `m0`) of them are
+        ///    sorted and delta-encoded.
+        neighbors: &'a [u8],
+        offsets: bitpacking_ordered::Reader<'a>,
+        hnsw_m: HnswM,
+    },
 }
 
 impl GraphLinksView<'_> {
@@ -115,6 +138,7 @@ impl GraphLinksView<'_> {
             GraphLinksFormat::Compressed => Self::load_compressed(data),
             GraphLinksFormat::Plain => Self::load_plain(data),
             GraphLinksFormat::CompressedWithVectors => Self::load_compressed_with_vectors(data),
+            GraphLinksFormat::CompressedVarint => Self::load_compressed_varint(data),
         }
     }
 
@@ -212,6 +236,32 @@ impl GraphLinksView<'_> {
         })
     }
 
+    fn load_compressed_varint(data: &[u8]) -> OperationResult<GraphLinksView<'_>> {
+        let (header, data) =
+            HeaderCompressed::ref_from_prefix(data).map_err(|_| error_unsufficent_size())?;
+        debug_assert_eq!(header.version.get(), HEADER_VERSION_COMPRESSED_VARINT);
+        let (level_offsets, data) = read_level_offsets(
+            data,
+            header.levels_count.get(),
+            header.offsets_parameters.length.get(),
+        )?;
+        let (reindex, data) = get_slice::<PointOffsetType>(data, header.point_count.get())?;
+        let (neighbors, data) = get_slice::<u8>(data, header.total_neighbors_bytes.get())?;
+        let (offsets, _bytes) = bitpacking_ordered::Reader::new(header.offsets_parameters, data)
+            .map_err(|e| {
+                OperationError::service_error(format!("Can't create decompressor: {e}"))
+            })?;
+        Ok(GraphLinksView {
+            reindex,
+            compression: CompressionInfo::CompressedVarint {
+                neighbors,
+                offsets,
+                hnsw_m: HnswM::new(header.m.get() as usize, header.m0.get() as usize),
+            },
+            level_offsets,
+        })
+    }
+
     /// Note: it is safe to use `idx + 1` on the result of this function,
     /// because `level_offsets` always contains an additional element.
     #[inline]
@@ -230,7 +280,8 @@ impl GraphLinksView<'_> {
             CompressionInfo::Uncompressed { offsets, .. } => {
                 offsets[idx].get() == offsets[idx + 1].get()
             }
-            CompressionInfo::Compressed { ref offsets, .. } => {
+            CompressionInfo::Compressed { ref offsets, .. }
+            | CompressionInfo::CompressedVarint { ref offsets, .. } => {
                 offsets.get(idx + 1).unwrap() == offsets.get(idx).unwrap()
             }
             CompressionInfo::CompressedWithVectors { .. } => {
@@ -245,7 +296,7 @@ impl GraphLinksView<'_> {
         match self.compression {
             CompressionInfo::Uncompressed { neighbors, offsets } => {
                 let neighbors_range = offsets[idx].get() as usize..offsets[idx + 1].get() as usize;
-                Either::Left(neighbors[neighbors_range].iter().copied())
+                Either::Left(Either::Left(neighbors[neighbors_range].iter().copied()))
             }
             CompressionInfo::Compressed {
                 neighbors,
@@ -255,15 +306,27 @@ impl GraphLinksView<'_> {
             } => {
                 let neighbors_range =
                     offsets.get(idx).unwrap() as usize..offsets.get(idx + 1).unwrap() as usize;
-                Either::Right(iterate_packed_links(
+                Either::Left(Either::Right(iterate_packed_links(
                     &neighbors[neighbors_range],
                     bits_per_unsorted,
                     hnsw_m.level_m(level),
-                ))
+                )))
             }
             CompressionInfo::CompressedWithVectors { .. } => {
                 // Not intended to be used outside of tests.
-                Either::Right(self.links_with_vectors(point_id, level).1)
+                Either::Left(Either::Right(self.links_with_vectors(point_id, level).1))
+            }
+            CompressionInfo::CompressedVarint {
+                neighbors,
+                ref offsets,
+                ref hnsw_m,
+            } => {
+                let neighbors_range =
+                    offsets.get(idx).unwrap() as usize..offsets.get(idx + 1).unwrap() as usize;
+                Either::Right(iterate_varint_links(
+                    &neighbors[neighbors_range],
+                    hnsw_m.level_m(level),
+                ))
             }
         }
     }
@@ -292,6 +355,7 @@ impl GraphLinksView<'_> {
         match self.compression {
             CompressionInfo::Uncompressed { .. } => unimplemented!(),
             CompressionInfo::Compressed { .. } => unimplemented!(),
+            CompressionInfo::CompressedVarint { .. } => unimplemented!(),
             CompressionInfo::CompressedWithVectors {
                 neighbors,
                 ref offsets,
@@ -380,6 +444,7 @@ impl GraphLinksView<'_> {
             CompressionInfo::Uncompressed { .. } => 0,
             CompressionInfo::Compressed { hnsw_m, .. } => hnsw_m.level_m(level),
             CompressionInfo::CompressedWithVectors { hnsw_m, .. } => hnsw_m.level_m(level),
+            CompressionInfo::CompressedVarint { hnsw_m, .. } => hnsw_m.level_m(level),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::io::{BufRead, Write};
 use std::ops::Deref as _;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -12,16 +13,23 @@ use common::cow::BoxCow;
 use common::cpu::linux_low_thread_priority;
 use common::flags::FeatureFlags;
 use common::fs::clear_disk_cache;
+#[cfg(target_os = "linux")]
+use common::numa::NumaTopology;
 use common::progress_tracker::ProgressTracker;
+use common::request_profile::ProfileStage;
+use common::search_diagnostics::SearchStrategy;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use fs_err as fs;
 use itertools::EitherOrBoth;
 use log::{debug, trace};
 use parking_lot::Mutex;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng as _};
 use rayon::ThreadPool;
 use rayon::prelude::*;
 
+#[cfg(feature = "gpu")]
+use super::gpu::gpu_dense_vector_storage::DenseGpuVectorStorage;
 #[cfg(feature = "gpu")]
 use super::gpu::gpu_devices_manager::LockedGpuDevice;
 use super::gpu::gpu_insert_context::GpuInsertContext;
@@ -36,23 +44,31 @@ use crate::common::operation_time_statistics::{
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorInternal, VectorRef};
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::exact_search::search_exact;
 use crate::index::hnsw_index::HnswM;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
+use crate::index::hnsw_index::ef_calibration::EfCalibration;
 #[cfg(feature = "gpu")]
 use crate::index::hnsw_index::gpu::gpu_graph_builder::GPU_MAX_VISITED_FLAGS_FACTOR;
 #[cfg(feature = "gpu")]
 use crate::index::hnsw_index::gpu::{get_gpu_groups_count, gpu_graph_builder::build_hnsw_on_gpu};
+use crate::index::hnsw_index::graph_export;
+use crate::index::hnsw_index::graph_health;
 use crate::index::hnsw_index::graph_layers::{
     GraphLayers, GraphLayersWithVectors, SearchAlgorithm,
 };
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
 use crate::index::hnsw_index::graph_layers_healer::GraphLayersHealer;
-use crate::index::hnsw_index::graph_links::{GraphLinksFormatParam, StorageGraphLinksVectors};
+use crate::index::hnsw_index::graph_links::{
+    GraphLinksFormat, GraphLinksFormatParam, StorageGraphLinksVectors,
+};
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
+#[cfg(feature = "gpu")]
+use crate::index::vector_index_search_common::is_rescoring_required;
 use crate::index::vector_index_search_common::{
     get_oversampled_top, is_quantized_search, postprocess_search_result,
 };
@@ -61,12 +77,16 @@ use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::payload_storage::FilterContext;
 use crate::segment_constructor::VectorIndexBuildArgs;
-use crate::telemetry::VectorIndexSearchesTelemetry;
+#[cfg(feature = "gpu")]
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::telemetry::{HnswGraphTelemetry, VectorIndexSearchesTelemetry};
 use crate::types::Condition::Field;
 use crate::types::{
     ACORN_MAX_SELECTIVITY_DEFAULT, FieldCondition, Filter, HnswConfig, HnswGlobalConfig,
     QuantizationSearchParams, SearchParams,
 };
+#[cfg(feature = "gpu")]
+use crate::vector_storage::check_deleted_condition;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoverQuery;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum, new_raw_scorer};
@@ -83,6 +103,15 @@ pub const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 256;
 
 const LINK_COMPRESSION_CONVERT_EXISTING: bool = false;
 
+/// Directory inside of the index, where a healed graph is built
+const HNSW_HEAL_PATH: &str = "heal";
+
+/// Graph, built by [`HNSWIndex::heal_graph`] to replace the current graph of the index
+pub struct HealedGraph {
+    graph: GraphLayers,
+    config: HnswGraphConfig,
+}
+
 #[derive(Debug)]
 pub struct HNSWIndex {
     id_tracker: Arc<AtomicRefCell<IdTrackerEnum>>,
@@ -92,8 +121,13 @@ pub struct HNSWIndex {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: GraphLayers,
+    /// Recall by `ef`, measured when the index was built
+    ef_calibration: Option<EfCalibration>,
     searches_telemetry: HNSWSearchesTelemetry,
     is_on_disk: bool,
+    /// Mirror of vectors in GPU memory, used for exact search and rescoring.
+    #[cfg(feature = "gpu")]
+    gpu_vectors: Option<DenseGpuVectorStorage>,
 }
 
 #[derive(Debug)]
@@ -141,6 +175,9 @@ impl HNSWIndex {
             hnsw_config,
         } = args;
 
+        // Apply the healed graph, if it was not moved in place completely
+        Self::finish_healing(path)?;
+
         let config_path = HnswGraphConfig::get_config_path(path);
         let config = if config_path.exists() {
             HnswGraphConfig::load(&config_path)?
@@ -165,6 +202,7 @@ impl HNSWIndex {
                 hnsw_config.max_indexing_threads,
                 hnsw_config.payload_m,
                 available_vectors,
+                hnsw_config.acorn.unwrap_or_default(),
             )
         };
 
@@ -174,6 +212,16 @@ impl HNSWIndex {
 
         let graph = GraphLayers::load(path, is_on_disk, do_convert)?;
 
+        let ef_calibration_path = EfCalibration::get_path(path);
+        let ef_calibration = if ef_calibration_path.exists() {
+            Some(EfCalibration::load(&ef_calibration_path)?)
+        } else {
+            None
+        };
+
+        #[cfg(feature = "gpu")]
+        let gpu_vectors = Self::open_gpu_vectors(&vector_storage.borrow());
+
         Ok(HNSWIndex {
             id_tracker,
             vector_storage,
@@ -182,8 +230,11 @@ impl HNSWIndex {
             config,
             path: path.to_owned(),
             graph,
+            ef_calibration,
             searches_telemetry: HNSWSearchesTelemetry::new(),
             is_on_disk,
+            #[cfg(feature = "gpu")]
+            gpu_vectors,
         })
     }
 
@@ -196,13 +247,393 @@ impl HNSWIndex {
         &self.graph
     }
 
+    #[cfg(test)]
+    pub(super) fn ef_calibration(&self) -> Option<&EfCalibration> {
+        self.ef_calibration.as_ref()
+    }
+
+    /// Write the graph in the portable format, see [`graph_export`](super::graph_export).
+    pub fn export_graph<W: Write>(&self, writer: W) -> OperationResult<()> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        graph_export::export_graph(
+            &self.graph,
+            &id_tracker,
+            vector_storage.deleted_vector_bitslice(),
+            writer,
+        )
+    }
+
+    /// Collect health metrics of the graph, see [`graph_health`](super::graph_health).
+    pub fn graph_telemetry(&self) -> HnswGraphTelemetry {
+        let vector_storage = self.vector_storage.borrow();
+        graph_health::graph_health(&self.graph, vector_storage.deleted_vector_bitslice())
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
 
+    /// Traverse the graph with a custom `points_scorer`, e.g. fusing scores of several vectors.
+    ///
+    /// Scores of the scorer are final, no oversampling or rescoring is applied.
+    pub fn search_with_scorer(
+        &self,
+        points_scorer: FilteredScorer,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let ef = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.ef);
+        let search_result = self.graph.search(
+            top,
+            std::cmp::max(ef, top),
+            SearchAlgorithm::Hnsw,
+            params.and_then(|params| params.early_termination.as_ref()),
+            points_scorer,
+            None,
+            is_stopped,
+        )?;
+        Ok(search_result)
+    }
+
+    /// Fraction of vectors linked in the graph, which were deleted since the graph was built or
+    /// healed.
+    pub fn deleted_linked_ratio(&self) -> f64 {
+        let linked_vector_count = self
+            .config
+            .linked_vector_count
+            .unwrap_or_else(|| self.indexed_vector_count());
+        let deleted_vector_count = linked_vector_count
+            .saturating_sub(self.vector_storage.borrow().available_vector_count());
+        if linked_vector_count != 0 {
+            deleted_vector_count as f64 / linked_vector_count as f64
+        } else {
+            0.0
+        }
+    }
+
+    fn get_heal_path(path: &Path) -> PathBuf {
+        path.join(HNSW_HEAL_PATH)
+    }
+
+    /// Reroute links of the graph around points, deleted since it was built or healed.
+    ///
+    /// The healed graph is built next to the current one, which stays in use meanwhile.
+    /// Apply it with [`HNSWIndex::replace_graph`].
+    ///
+    /// Returns `None` if there is no main graph to heal.
+    pub fn heal_graph(
+        &self,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<HealedGraph>> {
+        if self.config.m == 0 {
+            return Ok(None);
+        }
+
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_vectors = self.quantized_vectors.borrow();
+
+        // Point offsets stay the same, deleted points are mapped to nothing to get unlinked
+        let deleted_points = id_tracker.deleted_point_bitslice();
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let num_points = self.graph.num_points();
+        let old_to_new: Vec<Option<PointOffsetType>> = (0..num_points as PointOffsetType)
+            .map(|point_id| {
+                let is_deleted = deleted_points.get_bit(point_id as usize).unwrap_or(false)
+                    || deleted_vectors.get_bit(point_id as usize).unwrap_or(false);
+                (!is_deleted).then_some(point_id)
+            })
+            .collect();
+        let linked_vector_count = old_to_new.iter().flatten().count();
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-heal-{idx}"))
+            .num_threads(std::cmp::max(1, max_threads))
+            .build()?;
+
+        check_process_stopped(stopped)?;
+
+        // Keep links of payload-specific graphs, as they are not rebuilt
+        let mut healer =
+            GraphLayersHealer::new(&self.graph, &old_to_new, self.config.ef_construct, true);
+        healer.heal(&pool, &vector_storage, quantized_vectors.as_ref())?;
+
+        check_process_stopped(stopped)?;
+
+        let num_entries = std::cmp::max(
+            1,
+            num_points
+                .checked_div(self.config.full_scan_threshold)
+                .unwrap_or(0)
+                * 10,
+        );
+        let mut graph_layers_builder = GraphLayersBuilder::new_with_params(
+            num_points,
+            self.graph.hnsw_m,
+            self.config.ef_construct,
+            num_entries,
+            HNSW_USE_HEURISTIC,
+            false,
+        );
+        for point_id in old_to_new.iter().flatten().copied() {
+            graph_layers_builder.set_levels(point_id, self.graph.point_level(point_id));
+        }
+        healer.save_into_builder(&graph_layers_builder);
+
+        let graph_links_vectors = (self.graph.links.format()
+            == GraphLinksFormat::CompressedWithVectors)
+            .then(|| StorageGraphLinksVectors::try_new(&vector_storage, quantized_vectors.as_ref()))
+            .flatten();
+        let format_param = match (self.graph.links.format(), graph_links_vectors.as_ref()) {
+            (GraphLinksFormat::Plain, _) => GraphLinksFormatParam::Plain,
+            (GraphLinksFormat::CompressedVarint, _) => GraphLinksFormatParam::CompressedVarint,
+            (_, Some(v)) => GraphLinksFormatParam::CompressedWithVectors(v),
+            // Like on build, fall back to compressed links if vectors can't be inlined
+            (_, None) => GraphLinksFormatParam::Compressed,
+        };
+
+        let heal_path = Self::get_heal_path(&self.path);
+        if heal_path.exists() {
+            fs::remove_dir_all(&heal_path)?;
+        }
+        fs::create_dir_all(&heal_path)?;
+
+        let config = HnswGraphConfig {
+            linked_vector_count: Some(linked_vector_count),
+            ..self.config
+        };
+
+        graph_layers_builder
+            .into_graph_layers(&heal_path, format_param, self.is_on_disk)
+            .and_then(|graph| {
+                // Healing is complete once its config is saved
+                config.save(&HnswGraphConfig::get_config_path(&heal_path))?;
+                Ok(Some(HealedGraph { graph, config }))
+            })
+            .inspect_err(|_| {
+                // Remove incomplete graph right away, loading would discard it anyway
+                let _ = fs::remove_dir_all(&heal_path);
+            })
+    }
+
+    /// Replace the graph with one built by [`HNSWIndex::heal_graph`].
+    pub fn replace_graph(&mut self, healed_graph: HealedGraph) -> OperationResult<()> {
+        // Files of the current graph are replaced, but they stay mapped until dropped
+        Self::finish_healing(&self.path)?;
+
+        let HealedGraph { graph, config } = healed_graph;
+        self.graph = graph;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Move the graph, healed in [`Self::get_heal_path`], in place of the graph in `path`.
+    ///
+    /// Healing is complete once its config is saved, so the config is moved last. If the move
+    /// was interrupted, it is resumed. Incomplete healing is discarded.
+    fn finish_healing(path: &Path) -> OperationResult<()> {
+        let heal_path = Self::get_heal_path(path);
+        if !heal_path.exists() {
+            return Ok(());
+        }
+
+        let config_path = HnswGraphConfig::get_config_path(&heal_path);
+        if config_path.exists() {
+            // Links of another format would be loaded instead of the healed ones
+            let formats = [
+                GraphLinksFormat::Plain,
+                GraphLinksFormat::Compressed,
+                GraphLinksFormat::CompressedWithVectors,
+                GraphLinksFormat::CompressedVarint,
+            ];
+            let healed_format = formats
+                .into_iter()
+                .find(|&format| GraphLayers::get_links_path(&heal_path, format).exists());
+            if let Some(healed_format) = healed_format {
+                for format in formats
+                    .into_iter()
+                    .filter(|&format| format != healed_format)
+                {
+                    let links_path = GraphLayers::get_links_path(path, format);
+                    if links_path.exists() {
+                        fs::remove_file(&links_path)?;
+                    }
+                }
+            }
+
+            for entry in fs::read_dir(&heal_path)? {
+                let entry = entry?;
+                let from = entry.path();
+                if from == config_path {
+                    continue;
+                }
+                fs::rename(&from, path.join(entry.file_name()))?;
+            }
+            fs::rename(&config_path, HnswGraphConfig::get_config_path(path))?;
+        }
+
+        fs::remove_dir_all(&heal_path)?;
+        Ok(())
+    }
+
     pub fn build<R: Rng + ?Sized>(
         open_args: HnswIndexOpenArgs<'_>,
         build_args: VectorIndexBuildArgs<'_, R>,
+    ) -> OperationResult<Self> {
+        let Some(seed) = open_args.hnsw_config.seed else {
+            return Self::build_with_rng(open_args, build_args);
+        };
+
+        // Configured seed takes precedence over the provided RNG, to make the build reproducible
+        let mut seeded_rng = StdRng::seed_from_u64(seed);
+        let VectorIndexBuildArgs {
+            permit,
+            old_indices,
+            gpu_device: _,
+            rng: _,
+            stopped,
+            hnsw_global_config,
+            feature_flags,
+            progress,
+        } = build_args;
+        Self::build_with_rng(
+            open_args,
+            VectorIndexBuildArgs {
+                permit,
+                old_indices,
+                // Order of insertions on GPU is not deterministic
+                gpu_device: None,
+                rng: &mut seeded_rng,
+                stopped,
+                hnsw_global_config,
+                feature_flags,
+                progress,
+            },
+        )
+    }
+
+    /// Create the index from a graph in the portable format, instead of building it.
+    ///
+    /// See [`graph_export`](super::graph_export) for the format and its requirements.
+    pub fn import<Rd: BufRead>(
+        open_args: HnswIndexOpenArgs<'_>,
+        reader: Rd,
+    ) -> OperationResult<Self> {
+        if HnswGraphConfig::get_config_path(open_args.path).exists()
+            || GraphLayers::get_path(open_args.path).exists()
+        {
+            return Err(OperationError::service_error(format!(
+                "HNSW index already exists at {:?}, can't import graph",
+                open_args.path,
+            )));
+        }
+
+        let HnswIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            hnsw_config,
+        } = open_args;
+
+        fs::create_dir_all(path)?;
+
+        let id_tracker_ref = id_tracker.borrow();
+        let vector_storage_ref = vector_storage.borrow();
+        let quantized_vectors_ref = quantized_vectors.borrow();
+
+        let total_vector_count = vector_storage_ref.total_vector_count();
+
+        let full_scan_threshold = vector_storage_ref
+            .size_of_available_vectors_in_bytes()
+            .checked_div(total_vector_count)
+            .and_then(|avg_vector_size| {
+                hnsw_config
+                    .full_scan_threshold
+                    .saturating_mul(BYTES_IN_KB)
+                    .checked_div(avg_vector_size)
+            })
+            .unwrap_or(1);
+
+        let (graph_layers_builder, header) = graph_export::import_graph(
+            reader,
+            &id_tracker_ref,
+            vector_storage_ref.deleted_vector_bitslice(),
+            total_vector_count,
+            hnsw_config.ef_construct,
+        )?;
+
+        // Imported graph has no payload-aware links, so filtered searches use ACORN instead
+        let mut config = HnswGraphConfig::new(
+            header.m,
+            hnsw_config.ef_construct,
+            full_scan_threshold,
+            hnsw_config.max_indexing_threads,
+            hnsw_config.payload_m,
+            total_vector_count,
+            true,
+        );
+        config.m0 = header.m0;
+        config
+            .indexed_vector_count
+            .replace(vector_storage_ref.available_vector_count());
+
+        let is_on_disk = hnsw_config.on_disk.unwrap_or(false);
+
+        let graph_links_vectors = hnsw_config
+            .inline_storage
+            .unwrap_or_default()
+            .then(|| {
+                StorageGraphLinksVectors::try_new(
+                    &vector_storage_ref,
+                    quantized_vectors_ref.as_ref(),
+                )
+            })
+            .flatten();
+        let format_param = match graph_links_vectors.as_ref() {
+            Some(v) => GraphLinksFormatParam::CompressedWithVectors(v),
+            None if hnsw_config.varint_links.unwrap_or_default() => {
+                GraphLinksFormatParam::CompressedVarint
+            }
+            None => GraphLinksFormatParam::Compressed,
+        };
+
+        let graph = graph_layers_builder.into_graph_layers(path, format_param, is_on_disk)?;
+        config.save(&HnswGraphConfig::get_config_path(path))?;
+
+        drop(id_tracker_ref);
+        drop(vector_storage_ref);
+        drop(quantized_vectors_ref);
+
+        #[cfg(feature = "gpu")]
+        let gpu_vectors = Self::open_gpu_vectors(&vector_storage.borrow());
+
+        Ok(HNSWIndex {
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            config,
+            path: path.to_owned(),
+            graph,
+            // Imported graph is not calibrated, searches fall back to `hnsw_ef`
+            ef_calibration: None,
+            searches_telemetry: HNSWSearchesTelemetry::new(),
+            is_on_disk,
+            #[cfg(feature = "gpu")]
+            gpu_vectors,
+        })
+    }
+
+    fn build_with_rng<R: Rng + ?Sized>(
+        open_args: HnswIndexOpenArgs<'_>,
+        build_args: VectorIndexBuildArgs<'_, R>,
     ) -> OperationResult<Self> {
         if HnswGraphConfig::get_config_path(open_args.path).exists()
             || GraphLayers::get_path(open_args.path).exists()
@@ -261,6 +692,7 @@ impl HNSWIndex {
             hnsw_config.max_indexing_threads,
             hnsw_config.payload_m,
             total_vector_count,
+            hnsw_config.acorn.unwrap_or_default(),
         );
 
         #[allow(unused_mut)]
@@ -277,8 +709,12 @@ impl HNSWIndex {
         // Progress subtasks
         let progress_migrate = build_main_graph.then(|| progress.subtask("migrate"));
         let progress_main_graph = build_main_graph.then(|| progress.subtask("main_graph"));
-        let additional_links_params: Option<(ProgressTracker, Vec<(ProgressTracker, JsonPath)>)> =
-            (payload_m.m > 0)
+        let additional_links_params: Option<(
+            ProgressTracker,
+            Vec<(ProgressTracker, JsonPath, bool)>,
+        )> =
+            // With ACORN, filtered searches don't rely on payload-aware links
+            (payload_m.m > 0 && !config.acorn)
                 .then(|| payload_index_ref.indexed_fields())
                 .filter(|fields| !fields.is_empty())
                 .map(|fields| {
@@ -288,7 +724,11 @@ impl HNSWIndex {
                         .filter_map(|(field, payload_schema)| {
                             let subtask_name = format!("{}:{field}", payload_schema.name());
                             if payload_schema.enable_hnsw() {
-                                Some((progress_additional_links.subtask(subtask_name), field))
+                                Some((
+                                    progress_additional_links.subtask(subtask_name),
+                                    field,
+                                    payload_schema.enable_hnsw_ranges(),
+                                ))
                             } else {
                                 debug!("enable_hnsw=false. Skip building additional index for field {}", &field);
                                 None
@@ -346,9 +786,18 @@ impl HNSWIndex {
             HNSW_USE_HEURISTIC,
         );
 
+        // Seeded builds link points in a single thread, so that the order of insertions is fixed
+        let num_threads = if hnsw_config.seed.is_some() {
+            1
+        } else {
+            permit.num_cpus as usize
+        };
+        // Spread building threads over NUMA nodes, if the optimizer is configured to
+        #[cfg(target_os = "linux")]
+        let numa_topology = permit.numa_aware.then(NumaTopology::global).flatten();
         let pool = rayon::ThreadPoolBuilder::new()
             .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(permit.num_cpus as usize)
+            .num_threads(num_threads)
             .spawn_handler(|thread| {
                 let mut b = thread::Builder::new();
                 if let Some(name) = thread.name() {
@@ -357,7 +806,9 @@ impl HNSWIndex {
                 if let Some(stack_size) = thread.stack_size() {
                     b = b.stack_size(stack_size);
                 }
-                b.spawn(|| {
+                #[cfg(target_os = "linux")]
+                let thread_index = thread.index();
+                b.spawn(move || {
                     // On Linux, use lower thread priority so we interfere less with serving traffic
                     #[cfg(target_os = "linux")]
                     if let Err(err) = linux_low_thread_priority() {
@@ -366,6 +817,16 @@ impl HNSWIndex {
                         );
                     }
 
+                    #[cfg(target_os = "linux")]
+                    if let Some(topology) = numa_topology {
+                        let node = topology.node_for_thread(thread_index, num_threads);
+                        if let Err(err) = topology.pin_current_thread(node) {
+                            log::debug!(
+                                "Failed to pin HNSW building thread to NUMA node {node}, ignoring: {err}"
+                            );
+                        }
+                    }
+
                     thread.run()
                 })?;
                 Ok(())
@@ -389,6 +850,12 @@ impl HNSWIndex {
             graph_layers_builder.set_levels(vector_id, level);
         }
 
+        // Let pinned threads allocate links, so that graph memory is spread over NUMA nodes
+        #[cfg(target_os = "linux")]
+        if numa_topology.is_some() {
+            graph_layers_builder.reallocate_links(&pool);
+        }
+
         // Try to build the main graph on GPU if possible.
         // Store created gpu vectors to reuse them for payload links.
         #[cfg(feature = "gpu")]
@@ -438,6 +905,7 @@ impl HNSWIndex {
                     old_index.graph(),
                     &old_index.old_to_new,
                     config.ef_construct,
+                    false,
                 );
                 let old_vector_storage = old_index.index.vector_storage.borrow();
                 let old_quantized_vectors = old_index.index.quantized_vectors.borrow();
@@ -530,9 +998,9 @@ impl HNSWIndex {
 
             let required_connectivity = if average_links_per_0_level_int >= 4 {
                 let global_graph_connectivity = [
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
-                    graph_layers_builder.subgraph_connectivity(&all_points, percolation),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, rng),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, rng),
+                    graph_layers_builder.subgraph_connectivity(&all_points, percolation, rng),
                 ];
 
                 debug!("graph connectivity: {global_graph_connectivity:?} @ {percolation}");
@@ -573,7 +1041,9 @@ impl HNSWIndex {
             #[cfg(not(feature = "gpu"))]
             let mut gpu_insert_context = None;
 
-            for (index_pos, (field_progress, field)) in indexed_fields.into_iter().enumerate() {
+            for (index_pos, (field_progress, field, enable_hnsw_ranges)) in
+                indexed_fields.into_iter().enumerate()
+            {
                 field_progress.start();
 
                 debug!("building additional index for field {}", &field);
@@ -593,7 +1063,17 @@ impl HNSWIndex {
 
                 let counter = field_progress.track_progress(None);
 
-                for payload_block in payload_index_ref.payload_blocks(&field, full_scan_threshold) {
+                // Blocks of wider ranges cover range filters, matching many payload blocks at once
+                let coarse_range_blocks = if enable_hnsw_ranges {
+                    payload_index_ref.coarse_range_blocks(&field, full_scan_threshold)
+                } else {
+                    Vec::new()
+                };
+
+                let payload_blocks = payload_index_ref
+                    .payload_blocks(&field, full_scan_threshold)
+                    .chain(coarse_range_blocks.into_iter().map(Ok));
+                for payload_block in payload_blocks {
                     let payload_block = payload_block?;
                     check_process_stopped(stopped)?;
 
@@ -626,8 +1106,11 @@ impl HNSWIndex {
                         && let Some(required_connectivity) = required_connectivity
                     {
                         // Always build for tenants
-                        let graph_connectivity = graph_layers_builder
-                            .subgraph_connectivity(&points_to_index, percolation);
+                        let graph_connectivity = graph_layers_builder.subgraph_connectivity(
+                            &points_to_index,
+                            percolation,
+                            rng,
+                        );
 
                         if graph_connectivity >= required_connectivity {
                             trace!(
@@ -694,6 +1177,9 @@ impl HNSWIndex {
             .flatten();
         let format_param = match graph_links_vectors.as_ref() {
             Some(v) => GraphLinksFormatParam::CompressedWithVectors(v),
+            None if hnsw_config.varint_links.unwrap_or_default() => {
+                GraphLinksFormatParam::CompressedVarint
+            }
             None => GraphLinksFormatParam::Compressed,
         };
 
@@ -713,6 +1199,16 @@ impl HNSWIndex {
 
         debug!("finish additional payload field indexing");
 
+        let ef_calibration = if config.m > 0 {
+            EfCalibration::calibrate(&graph, &vector_storage_ref, deleted_bitslice, rng, stopped)?
+        } else {
+            None
+        };
+        if let Some(ef_calibration) = &ef_calibration {
+            debug!("ef calibration: {:?}", ef_calibration.points);
+            ef_calibration.save(&EfCalibration::get_path(path))?;
+        }
+
         config.save(&HnswGraphConfig::get_config_path(path))?;
 
         drop(id_tracker_ref);
@@ -728,8 +1224,12 @@ impl HNSWIndex {
             config,
             path: path.to_owned(),
             graph,
+            ef_calibration,
             searches_telemetry: HNSWSearchesTelemetry::new(),
             is_on_disk,
+            // Vectors are mirrored to GPU once the built index is loaded
+            #[cfg(feature = "gpu")]
+            gpu_vectors: None,
         })
     }
 
@@ -1017,6 +1517,132 @@ impl HNSWIndex {
         }
     }
 
+    /// Mirror vectors to GPU, if GPU rescoring is enabled and vectors are supported.
+    #[cfg(feature = "gpu")]
+    fn open_gpu_vectors(vector_storage: &VectorStorageEnum) -> Option<DenseGpuVectorStorage> {
+        use crate::index::hnsw_index::gpu::{GPU_DEVICES_MANAGER, get_gpu_force_half_precision};
+
+        let device = GPU_DEVICES_MANAGER.read().as_ref()?.rescoring_device()?;
+        if vector_storage.total_vector_count() < SINGLE_THREADED_HNSW_BUILD_THRESHOLD {
+            return None;
+        }
+
+        let timer = std::time::Instant::now();
+        match DenseGpuVectorStorage::new(
+            device,
+            vector_storage,
+            get_gpu_force_half_precision(),
+            &AtomicBool::new(false),
+        ) {
+            Ok(gpu_vectors) => {
+                debug!(
+                    "Uploaded vectors for GPU rescoring in {:?}",
+                    timer.elapsed()
+                );
+                Some(gpu_vectors)
+            }
+            Err(err) => {
+                log::warn!("Failed to upload vectors to GPU, rescoring on CPU instead: {err}");
+                None
+            }
+        }
+    }
+
+    /// Rescore `search_result` with vectors in GPU memory.
+    ///
+    /// Returns `None` if rescoring is not required, or it has to be done on CPU.
+    #[cfg(feature = "gpu")]
+    #[allow(clippy::too_many_arguments)]
+    fn rescore_on_gpu(
+        &self,
+        search_result: &[ScoredPointOffset],
+        vector_storage: &VectorStorageEnum,
+        deleted_points: &BitSlice,
+        quantized_vectors: Option<&QuantizedVectors>,
+        vector: &QueryVector,
+        params: Option<&SearchParams>,
+        top: usize,
+    ) -> Option<Vec<ScoredPointOffset>> {
+        let gpu_vectors = self.gpu_vectors.as_ref()?;
+        if !is_rescoring_required(quantized_vectors, params) {
+            return None;
+        }
+        let QueryVector::Nearest(VectorInternal::Dense(query)) = vector else {
+            return None;
+        };
+
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let point_ids: Vec<_> = search_result
+            .iter()
+            .map(|scored_point| scored_point.idx)
+            .filter(|&idx| check_deleted_condition(idx, deleted_vectors, deleted_points))
+            .collect();
+
+        match gpu_vectors.score_points(query, &point_ids) {
+            Ok(scores) => Some(peek_top_largest_iterable(
+                point_ids
+                    .into_iter()
+                    .zip(scores)
+                    .map(|(idx, score)| ScoredPointOffset { idx, score }),
+                top,
+            )),
+            Err(err) => {
+                log::warn!("Failed to rescore on GPU, rescoring on CPU instead: {err}");
+                None
+            }
+        }
+    }
+
+    /// Exact search over `points` with vectors in GPU memory.
+    ///
+    /// Returns `None` if any of the queries has to be scored on CPU.
+    #[cfg(feature = "gpu")]
+    fn search_plain_on_gpu(
+        &self,
+        gpu_vectors: &DenseGpuVectorStorage,
+        query_vectors: &[&QueryVector],
+        points: &[PointOffsetType],
+        top: usize,
+        vector_query_context: &VectorQueryContext,
+    ) -> Option<Vec<Vec<ScoredPointOffset>>> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+
+        let deleted_points = vector_query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let point_ids: Vec<_> = points
+            .iter()
+            .copied()
+            .filter(|&idx| check_deleted_condition(idx, deleted_vectors, deleted_points))
+            .collect();
+
+        let mut search_results = Vec::with_capacity(query_vectors.len());
+        for query_vector in query_vectors {
+            let QueryVector::Nearest(VectorInternal::Dense(query)) = query_vector else {
+                return None;
+            };
+            if vector_query_context.is_stopped().load(Ordering::Relaxed) {
+                return None;
+            }
+            let scores = gpu_vectors
+                .score_points(query, &point_ids)
+                .inspect_err(|err| {
+                    log::warn!("Failed to search on GPU, searching on CPU instead: {err}");
+                })
+                .ok()?;
+            search_results.push(peek_top_largest_iterable(
+                point_ids
+                    .iter()
+                    .zip(scores)
+                    .map(|(&idx, score)| ScoredPointOffset { idx, score }),
+                top,
+            ));
+        }
+        Some(search_results)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn search_with_graph(
         &self,
@@ -1027,12 +1653,16 @@ impl HNSWIndex {
         custom_entry_points: Option<&[PointOffsetType]>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
-        let ef = params
-            .and_then(|params| params.hnsw_ef)
+        let target_recall_ef = params
+            .and_then(|params| params.target_recall)
+            .zip(self.ef_calibration.as_ref())
+            .and_then(|(target_recall, calibration)| calibration.ef_for_recall(*target_recall));
+        let ef = target_recall_ef
+            .or_else(|| params.and_then(|params| params.hnsw_ef))
             .unwrap_or(self.config.ef);
         let acorn_enabled = params
             .and_then(|params| params.acorn)
-            .is_some_and(|acorn| acorn.enable);
+            .map_or(self.config.acorn, |acorn| acorn.enable);
         let acorn_max_selectivity = params
             .and_then(|params| params.acorn)
             .and_then(|acorn| acorn.max_selectivity)
@@ -1135,10 +1765,14 @@ impl HNSWIndex {
             )?))
         };
 
+        let profile = hw_counter.request_profile();
+
         let regular_search = || -> OperationResult<Vec<ScoredPointOffset>> {
-            let filter_context = filter
-                .map(|f| payload_index.filter_context(f, &hw_counter))
-                .transpose()?;
+            let filter_context = profile.measure(ProfileStage::FilterEvaluation, || {
+                filter
+                    .map(|f| payload_index.filter_context(f, &hw_counter))
+                    .transpose()
+            })?;
             let points_scorer = Self::construct_search_scorer(
                 vector,
                 &vector_storage,
@@ -1149,25 +1783,44 @@ impl HNSWIndex {
                 filter_context,
             )?;
 
-            let search_result = self.graph.search(
-                oversampled_top,
-                ef,
-                algorithm,
-                points_scorer,
-                custom_entry_points,
-                &is_stopped,
-            )?;
+            let search_result = profile.measure(ProfileStage::GraphTraversal, || {
+                self.graph.search(
+                    oversampled_top,
+                    ef,
+                    algorithm,
+                    params.and_then(|params| params.early_termination.as_ref()),
+                    points_scorer,
+                    custom_entry_points,
+                    &is_stopped,
+                )
+            })?;
+            profile.add_candidates(ProfileStage::GraphTraversal, search_result.len());
 
-            postprocess_search_result(
-                search_result,
-                id_tracker.deleted_point_bitslice(),
-                &vector_storage,
-                quantized_vectors.as_ref(),
-                vector,
-                params,
-                top,
-                vector_query_context.hardware_counter(),
-            )
+            profile.measure(ProfileStage::Rescoring, || {
+                #[cfg(feature = "gpu")]
+                if let Some(search_result) = self.rescore_on_gpu(
+                    &search_result,
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    quantized_vectors.as_ref(),
+                    vector,
+                    params,
+                    top,
+                ) {
+                    return Ok(search_result);
+                }
+
+                postprocess_search_result(
+                    search_result,
+                    id_tracker.deleted_point_bitslice(),
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    vector,
+                    params,
+                    top,
+                    vector_query_context.hardware_counter(),
+                )
+            })
         };
 
         // Try to use graph with vectors first.
@@ -1211,6 +1864,46 @@ impl HNSWIndex {
         top: usize,
         params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        #[cfg(feature = "gpu")]
+        if let Some(gpu_vectors) = &self.gpu_vectors
+            && params.is_some_and(|params| params.exact)
+        {
+            let points: Vec<_> = points.collect();
+            if let Some(search_results) = self.search_plain_on_gpu(
+                gpu_vectors,
+                query_vectors,
+                &points,
+                top,
+                vector_query_context,
+            ) {
+                return Ok(search_results);
+            }
+            return self.search_plain_iterator_batched_on_cpu(
+                query_vectors,
+                points.into_iter(),
+                top,
+                params,
+                vector_query_context,
+            );
+        }
+
+        self.search_plain_iterator_batched_on_cpu(
+            query_vectors,
+            points,
+            top,
+            params,
+            vector_query_context,
+        )
+    }
+
+    fn search_plain_iterator_batched_on_cpu(
+        &self,
+        query_vectors: &[&QueryVector],
+        points: impl Iterator<Item = PointOffsetType>,
+        top: usize,
+        params: Option<&SearchParams>,
+        vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -1223,29 +1916,67 @@ impl HNSWIndex {
         let is_stopped = vector_query_context.is_stopped();
         let oversampled_top = get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
-        let batch_filtered_searcher = Self::construct_batch_searcher(
-            query_vectors,
-            &vector_storage,
-            quantized_vectors.as_ref(),
-            oversampled_top,
-            deleted_points,
-            params,
-            vector_query_context.hardware_counter(),
-            None,
-        )?;
-        let mut search_results = batch_filtered_searcher.peek_top_iter(points, &is_stopped)?;
-        for (search_result, query_vector) in search_results.iter_mut().zip(query_vectors) {
-            *search_result = postprocess_search_result(
-                std::mem::take(search_result),
-                id_tracker.deleted_point_bitslice(),
+        let profile = vector_query_context.hardware_counter().request_profile();
+        let mut search_results = profile.measure(ProfileStage::GraphTraversal, || {
+            // Exact search scores every point, split them between threads
+            if params.is_some_and(|params| params.exact) {
+                let points: Vec<_> = points.collect();
+                return search_exact(
+                    query_vectors,
+                    &vector_storage,
+                    &points,
+                    oversampled_top,
+                    deleted_points,
+                    vector_query_context.hardware_counter(),
+                    &is_stopped,
+                );
+            }
+
+            let batch_filtered_searcher = Self::construct_batch_searcher(
+                query_vectors,
                 &vector_storage,
                 quantized_vectors.as_ref(),
-                query_vector,
+                oversampled_top,
+                deleted_points,
                 params,
-                top,
                 vector_query_context.hardware_counter(),
+                None,
             )?;
-        }
+            Ok(batch_filtered_searcher.peek_top_iter(points, &is_stopped)?)
+        })?;
+        profile.add_candidates(
+            ProfileStage::GraphTraversal,
+            search_results.iter().map(Vec::len).sum(),
+        );
+        profile.measure(ProfileStage::Rescoring, || {
+            for (search_result, query_vector) in search_results.iter_mut().zip(query_vectors) {
+                #[cfg(feature = "gpu")]
+                if let Some(rescored) = self.rescore_on_gpu(
+                    search_result,
+                    &vector_storage,
+                    id_tracker.deleted_point_bitslice(),
+                    quantized_vectors.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                ) {
+                    *search_result = rescored;
+                    continue;
+                }
+
+                *search_result = postprocess_search_result(
+                    std::mem::take(search_result),
+                    id_tracker.deleted_point_bitslice(),
+                    &vector_storage,
+                    quantized_vectors.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    vector_query_context.hardware_counter(),
+                )?;
+            }
+            OperationResult::Ok(())
+        })?;
         Ok(search_results)
     }
 
@@ -1291,7 +2022,11 @@ impl HNSWIndex {
 
         let id_tracker = self.id_tracker.borrow();
         let payload_index = self.payload_index.borrow();
-        let query_cardinality = payload_index.estimate_cardinality(filter, hw_counter)?;
+        let query_cardinality = hw_counter
+            .request_profile()
+            .measure(ProfileStage::FilterEvaluation, || {
+                payload_index.estimate_cardinality(filter, hw_counter)
+            })?;
         let point_mappings = id_tracker.point_mappings();
         // Assume query is already estimated to be small enough so we can iterate over all matched ids
         let filtered_points = payload_index.iter_filtered_points(
@@ -1416,9 +2151,12 @@ impl VectorIndex for HNSWIndex {
 
         // If neither `m` nor `payload_m` is set, HNSW doesn't have any links.
         // And if so, we need to fall back to plain search (optionally, with quantization).
+        // With ACORN, `payload_m` links are not built.
 
-        let is_hnsw_disabled = self.config.m == 0 && self.config.payload_m.unwrap_or(0) == 0;
+        let is_hnsw_disabled =
+            self.config.m == 0 && (self.config.acorn || self.config.payload_m.unwrap_or(0) == 0);
         let exact = params.map(|params| params.exact).unwrap_or(false);
+        let diagnostics = query_context.hardware_counter().search_diagnostics();
 
         let exact_params = if exact {
             params.map(|params| {
@@ -1453,12 +2191,21 @@ impl VectorIndex for HNSWIndex {
                     } else {
                         &self.searches_telemetry.unfiltered_plain
                     });
+                    let strategy = if exact {
+                        SearchStrategy::PlainExact
+                    } else if is_hnsw_disabled {
+                        SearchStrategy::PlainIndexDisabled
+                    } else {
+                        SearchStrategy::PlainSmallSegment
+                    };
+                    diagnostics.add_searches(strategy, vectors.len());
 
                     let params_ref = if exact { exact_params.as_ref() } else { params };
                     self.search_plain_unfiltered_batched(vectors, top, params_ref, query_context)
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
+                    diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
                     self.search_vectors_with_graph(vectors, None, top, params, query_context)
                 }
             }
@@ -1474,6 +2221,12 @@ impl VectorIndex for HNSWIndex {
                     } else {
                         &self.searches_telemetry.filtered_plain
                     });
+                    let strategy = if exact {
+                        SearchStrategy::PlainExact
+                    } else {
+                        SearchStrategy::PlainIndexDisabled
+                    };
+                    diagnostics.add_searches(strategy, vectors.len());
 
                     let params_ref = if exact { exact_params.as_ref() } else { params };
 
@@ -1492,9 +2245,12 @@ impl VectorIndex for HNSWIndex {
                 let available_vector_count = vector_storage.available_vector_count();
 
                 let hw_counter = query_context.hardware_counter();
+                let profile = hw_counter.request_profile();
 
-                let query_point_cardinality =
-                    payload_index.estimate_cardinality(query_filter, &hw_counter)?;
+                let query_point_cardinality = profile
+                    .measure(ProfileStage::FilterEvaluation, || {
+                        payload_index.estimate_cardinality(query_filter, &hw_counter)
+                    })?;
                 let query_cardinality = adjust_to_available_vectors(
                     query_point_cardinality,
                     available_vector_count,
@@ -1505,6 +2261,7 @@ impl VectorIndex for HNSWIndex {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    diagnostics.add_searches(SearchStrategy::PlainSmallCardinality, vectors.len());
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -1518,6 +2275,7 @@ impl VectorIndex for HNSWIndex {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
                     return self.search_vectors_with_graph(
                         vectors,
                         filter,
@@ -1527,24 +2285,31 @@ impl VectorIndex for HNSWIndex {
                     );
                 }
 
-                let filter_context = payload_index.filter_context(query_filter, &hw_counter)?;
-
                 // Fast cardinality estimation is not enough, do sample estimation of cardinality
-                let id_tracker = self.id_tracker.borrow();
-                if sample_check_cardinality(
-                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
-                    |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
-                    available_vector_count, // Check cardinality among available vectors
-                ) {
+                let is_cardinality_large =
+                    profile.measure(ProfileStage::FilterEvaluation, || {
+                        let filter_context =
+                            payload_index.filter_context(query_filter, &hw_counter)?;
+                        let id_tracker = self.id_tracker.borrow();
+                        OperationResult::Ok(sample_check_cardinality(
+                            id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                            |idx| filter_context.check(idx),
+                            self.config.full_scan_threshold,
+                            available_vector_count, // Check cardinality among available vectors
+                        ))
+                    })?;
+
+                if is_cardinality_large {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
                     self.search_vectors_with_graph(vectors, filter, top, params, query_context)
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    diagnostics.add_searches(SearchStrategy::PlainSmallCardinality, vectors.len());
                     self.search_vectors_plain(vectors, query_filter, top, params, query_context)
                 }
             }
@@ -1573,6 +2338,10 @@ impl VectorIndex for HNSWIndex {
         if config_path.exists() {
             files.push(config_path);
         }
+        let ef_calibration_path = EfCalibration::get_path(&self.path);
+        if ef_calibration_path.exists() {
+            files.push(ef_calibration_path);
+        }
         files
     }
 
@@ -1,9 +1,13 @@
 use common::defaults::thread_count_for_hnsw;
 
+pub mod appendable_graph;
 mod build_cache;
 pub mod build_condition_checker;
 mod config;
+pub mod ef_calibration;
 mod entry_points;
+pub mod graph_export;
+mod graph_health;
 pub mod graph_layers;
 pub mod graph_layers_builder;
 mod graph_layers_healer;
@@ -11,7 +15,7 @@ pub mod graph_links;
 pub mod hnsw;
 mod links_container;
 pub mod point_scorer;
-mod search_context;
+pub(crate) mod search_context;
 
 #[cfg(feature = "gpu")]
 pub mod gpu;
@@ -5,6 +5,7 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::cow::BoxCow;
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::generic_consts::Random;
+use common::search_diagnostics::SearchDiagnostics;
 use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use smallvec::SmallVec;
 
@@ -55,6 +56,7 @@ pub struct FilteredScorer<'a> {
     filters: ScorerFilters<'a>,
     /// Temporary buffer for scores.
     scores_buffer: Vec<ScoreType>,
+    diagnostics: SearchDiagnostics,
 }
 
 pub struct ScorerFilters<'a> {
@@ -91,6 +93,7 @@ impl<'a> ScorerFilters<'a> {
 pub struct FilteredBytesScorer<'a> {
     scorer_bytes: &'a dyn QueryScorerBytes,
     filters: ScorerFilters<'a>,
+    diagnostics: &'a SearchDiagnostics,
 }
 
 impl<'a> FilteredBytesScorer<'a> {
@@ -99,16 +102,22 @@ impl<'a> FilteredBytesScorer<'a> {
         points: &mut Vec<(PointOffsetType, &[u8])>,
         limit: usize,
     ) -> impl Iterator<Item = ScoredPointOffset> {
+        self.diagnostics.add_visited_nodes(points.len());
         points.retain(|(point_id, _)| self.filters.check_vector(*point_id));
         if limit != 0 {
             points.truncate(limit);
         }
+        self.diagnostics.add_scored_vectors(points.len());
 
         points.iter().map(|&(idx, bytes)| ScoredPointOffset {
             idx,
             score: self.scorer_bytes.score_bytes(bytes),
         })
     }
+
+    pub fn diagnostics(&self) -> &SearchDiagnostics {
+        self.diagnostics
+    }
 }
 
 impl<'a> FilteredScorer<'a> {
@@ -123,6 +132,7 @@ impl<'a> FilteredScorer<'a> {
         point_deleted: &'a BitSlice,
         hardware_counter: HardwareCounterCell,
     ) -> OperationResult<Self> {
+        let diagnostics = hardware_counter.search_diagnostics();
         let raw_scorer = match quantized_vectors {
             Some(quantized_vectors) => quantized_vectors.raw_scorer(query, hardware_counter)?,
             None => new_raw_scorer(query, vectors, hardware_counter)?,
@@ -135,6 +145,7 @@ impl<'a> FilteredScorer<'a> {
                 vec_deleted: vectors.deleted_vector_bitslice(),
             },
             scores_buffer: Vec::new(),
+            diagnostics,
         })
     }
 
@@ -153,6 +164,7 @@ impl<'a> FilteredScorer<'a> {
             let query: QueryVector = query.as_vec_ref().into();
             query
         };
+        let diagnostics = hardware_counter.search_diagnostics();
         let raw_scorer = match quantized_vectors {
             Some(quantized_vectors) => quantized_vectors
                 .raw_internal_scorer(point_id, hardware_counter)
@@ -172,9 +184,34 @@ impl<'a> FilteredScorer<'a> {
                 vec_deleted: vectors.deleted_vector_bitslice(),
             },
             scores_buffer: Vec::new(),
+            diagnostics,
         })
     }
 
+    /// Create a new filtered scorer with a custom `raw_scorer`, e.g. fusing scores of several
+    /// vectors of a point.
+    ///
+    /// Points marked in `vec_deleted` are skipped, so it should belong to the vector traversed by
+    /// the index.
+    pub fn with_raw_scorer(
+        raw_scorer: Box<dyn RawScorer + 'a>,
+        filter_context: Option<BoxCow<'a, dyn FilterContext + 'a>>,
+        point_deleted: &'a BitSlice,
+        vec_deleted: &'a BitSlice,
+        hardware_counter: HardwareCounterCell,
+    ) -> Self {
+        FilteredScorer {
+            raw_scorer,
+            filters: ScorerFilters {
+                filter_context,
+                point_deleted,
+                vec_deleted,
+            },
+            scores_buffer: Vec::new(),
+            diagnostics: hardware_counter.search_diagnostics(),
+        }
+    }
+
     /// Create a new filtered scorer for testing purposes.
     ///
     /// # Panics
@@ -194,6 +231,7 @@ impl<'a> FilteredScorer<'a> {
                 vec_deleted: vector_storage.deleted_vector_bitslice(),
             },
             scores_buffer: Vec::new(),
+            diagnostics: SearchDiagnostics::disabled(),
         }
     }
 
@@ -210,9 +248,15 @@ impl<'a> FilteredScorer<'a> {
         Some(FilteredBytesScorer {
             scorer_bytes: self.raw_scorer.scorer_bytes()?,
             filters: self.filters.as_borrowed(),
+            diagnostics: &self.diagnostics,
         })
     }
 
+    /// Statistics of the search this scorer is used for.
+    pub fn diagnostics(&self) -> &SearchDiagnostics {
+        &self.diagnostics
+    }
+
     /// Filters and calculates scores for the given slice of points IDs.
     ///
     /// For performance reasons this method mutates `point_ids`.
@@ -228,6 +272,7 @@ impl<'a> FilteredScorer<'a> {
         point_ids: &mut Vec<PointOffsetType>,
         limit: usize,
     ) -> impl Iterator<Item = ScoredPointOffset> {
+        self.diagnostics.add_visited_nodes(point_ids.len());
         point_ids.retain(|point_id| self.filters.check_vector(*point_id));
         if limit != 0 {
             point_ids.truncate(limit);
@@ -240,6 +285,7 @@ impl<'a> FilteredScorer<'a> {
         &mut self,
         point_ids: &[PointOffsetType],
     ) -> impl Iterator<Item = ScoredPointOffset> {
+        self.diagnostics.add_scored_vectors(point_ids.len());
         if self.scores_buffer.len() < point_ids.len() {
             self.scores_buffer.resize(point_ids.len(), 0.0);
         }
@@ -252,6 +298,7 @@ impl<'a> FilteredScorer<'a> {
     }
 
     pub fn score_point(&self, point_id: PointOffsetType) -> ScoreType {
+        self.diagnostics.add_scored_vectors(1);
         self.raw_scorer.score_point(point_id)
     }
 
@@ -310,6 +357,29 @@ impl<'a> BatchFilteredSearcher<'a> {
         })
     }
 
+    /// Create a new filtered searcher with a single custom `raw_scorer`, see
+    /// [`FilteredScorer::with_raw_scorer`].
+    pub fn with_raw_scorer(
+        raw_scorer: Box<dyn RawScorer + 'a>,
+        filter_context: Option<BoxCow<'a, dyn FilterContext + 'a>>,
+        top: usize,
+        point_deleted: &'a BitSlice,
+        vec_deleted: &'a BitSlice,
+    ) -> Self {
+        let scorer_batch = smallvec::smallvec![BatchSearch {
+            raw_scorer,
+            pq: FixedLengthPriorityQueue::new(top),
+        }];
+        Self {
+            scorer_batch,
+            filters: ScorerFilters {
+                filter_context,
+                point_deleted,
+                vec_deleted,
+            },
+        }
+    }
+
     /// Create a new batched filtered searcher for testing purposes.
     ///
     /// # Panics
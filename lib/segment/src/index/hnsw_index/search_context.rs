@@ -4,12 +4,16 @@ use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{ScoreType, ScoredPointOffset};
 use num_traits::float::FloatCore;
 
+use crate::types::EarlyTerminationParams;
+
 /// Structure that holds context of the search
 pub struct SearchContext {
     /// Overall nearest points found so far
     pub nearest: FixedLengthPriorityQueue<ScoredPointOffset>,
     /// Current candidates to process
     pub candidates: BinaryHeap<ScoredPointOffset>,
+    /// Tracks convergence of top results, if early termination is requested
+    convergence: Option<ScoreConvergence>,
 }
 
 impl SearchContext {
@@ -17,9 +21,16 @@ impl SearchContext {
         SearchContext {
             nearest: FixedLengthPriorityQueue::new(ef),
             candidates: BinaryHeap::new(),
+            convergence: None,
         }
     }
 
+    /// Stop the search once top results converge, see [`ScoreConvergence`].
+    pub fn with_convergence(mut self, convergence: Option<ScoreConvergence>) -> Self {
+        self.convergence = convergence;
+        self
+    }
+
     pub fn lower_bound(&self) -> ScoreType {
         match self.nearest.top() {
             None => ScoreType::min_value(),
@@ -37,5 +48,63 @@ impl SearchContext {
         if was_added {
             self.candidates.push(score_point);
         }
+        if let Some(convergence) = &mut self.convergence {
+            convergence.process_candidate(score_point);
+        }
+    }
+
+    /// Finish expansion of a candidate.
+    ///
+    /// Returns `true` if top results have converged, and the search should stop.
+    pub fn end_expansion(&mut self) -> bool {
+        self.convergence
+            .as_mut()
+            .is_some_and(ScoreConvergence::end_expansion)
+    }
+}
+
+/// Detects convergence of the top results of a beam search.
+///
+/// Results converge once the total score of the best `top` points improves by less than
+/// `epsilon` for `patience` consecutive expansions.
+pub struct ScoreConvergence {
+    epsilon: ScoreType,
+    patience: usize,
+    /// Best `top` points found so far
+    best: FixedLengthPriorityQueue<ScoredPointOffset>,
+    /// Improvement of the total score of `best` during the current expansion
+    improvement: ScoreType,
+    /// Number of consecutive expansions without progress
+    stale_expansions: usize,
+}
+
+impl ScoreConvergence {
+    pub fn new(top: usize, params: &EarlyTerminationParams) -> Self {
+        let EarlyTerminationParams { epsilon, patience } = *params;
+        Self {
+            epsilon: epsilon.0 as ScoreType,
+            patience,
+            best: FixedLengthPriorityQueue::new(top),
+            improvement: 0.0,
+            stale_expansions: 0,
+        }
+    }
+
+    fn process_candidate(&mut self, score_point: ScoredPointOffset) {
+        match self.best.push(score_point) {
+            // Top results are incomplete yet, so any new point is a progress
+            None => self.improvement = ScoreType::INFINITY,
+            Some(removed) => self.improvement += score_point.score - removed.score,
+        }
+    }
+
+    fn end_expansion(&mut self) -> bool {
+        if self.improvement < self.epsilon {
+            self.stale_expansions += 1;
+        } else {
+            self.stale_expansions = 0;
+        }
+        self.improvement = 0.0;
+        self.stale_expansions >= self.patience
     }
 }
@@ -1,15 +1,32 @@
 mod test_compact_graph_layer;
+mod test_deterministic_build;
+mod test_early_termination;
+mod test_ef_calibration;
 mod test_graph_connectivity;
+mod test_graph_export;
+mod test_graph_health;
 
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::flags::FeatureFlags;
+use common::progress_tracker::ProgressTracker;
 use common::types::PointOffsetType;
+use itertools::Itertools as _;
 use rand::Rng;
+use tempfile::{Builder, TempDir};
 
 use super::graph_links::GraphLinksFormat;
+use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
 use crate::fixtures::index_fixtures::TestRawScorerProducer;
 use crate::index::hnsw_index::HnswM;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
-use crate::types::Distance;
+use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::segment::Segment;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::types::{Distance, HnswConfig, HnswGlobalConfig};
 
 pub(crate) fn create_graph_layer_builder_fixture<R: Rng + ?Sized>(
     num_vectors: usize,
@@ -67,3 +84,61 @@ pub(crate) fn create_graph_layer_fixture<R: Rng + ?Sized>(
     );
     (vector_holder, graph_layers)
 }
+
+/// Assert that both graphs have the same levels and links for all points.
+pub(crate) fn assert_graph_layers_eq(left: &GraphLayers, right: &GraphLayers) {
+    assert_eq!(left.num_points(), right.num_points());
+    for point_id in 0..left.num_points() as PointOffsetType {
+        let level = left.point_level(point_id);
+        assert_eq!(
+            level,
+            right.point_level(point_id),
+            "level of point {point_id}"
+        );
+        for level in 0..=level {
+            assert_eq!(
+                left.links.links(point_id, level).collect_vec(),
+                right.links.links(point_id, level).collect_vec(),
+                "links of point {point_id} on level {level}",
+            );
+        }
+    }
+}
+
+/// Build HNSW index for the default vector of `segment` in a new temporary directory.
+pub(crate) fn build_hnsw_index_fixture<R: Rng + ?Sized>(
+    segment: &Segment,
+    hnsw_config: HnswConfig,
+    rng: &mut R,
+) -> (TempDir, HNSWIndex) {
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    let permit = Arc::new(ResourcePermit::dummy(
+        hnsw_config.max_indexing_threads as u32,
+    ));
+
+    let index = HNSWIndex::build(
+        HnswIndexOpenArgs {
+            path: hnsw_dir.path(),
+            id_tracker: segment.id_tracker.clone(),
+            vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            quantized_vectors: Default::default(),
+            payload_index: segment.payload_index.clone(),
+            hnsw_config,
+        },
+        VectorIndexBuildArgs {
+            permit,
+            old_indices: &[],
+            gpu_device: None,
+            rng,
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+            progress: ProgressTracker::new_for_test(),
+        },
+    )
+    .unwrap();
+    (hnsw_dir, index)
+}
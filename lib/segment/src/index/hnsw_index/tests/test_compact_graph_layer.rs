@@ -43,6 +43,7 @@ fn search_in_builder(
             zero_level_entry,
             0,
             max(top, ef),
+            None,
             &mut points_scorer,
             &DEFAULT_STOPPED,
         )
@@ -100,6 +101,7 @@ fn test_compact_graph_layers(#[case] format: GraphLinksFormat) {
                     top,
                     ef,
                     SearchAlgorithm::Hnsw,
+                    None,
                     scorer,
                     None,
                     &DEFAULT_STOPPED,
@@ -0,0 +1,58 @@
+use common::counter::hardware_counter::HardwareCounterCell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::Builder;
+
+use super::{assert_graph_layers_eq, build_hnsw_index_fixture};
+use crate::data_types::vectors::only_default_vector;
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{Distance, HnswConfig, SeqNumberType};
+
+#[test]
+fn test_deterministic_build() {
+    let dim = 16;
+    let num_vectors: u64 = 1_000;
+    let distance = Distance::Cosine;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, distance).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 10,
+        max_indexing_threads: 4,
+        on_disk: Some(false),
+        payload_m: None,
+        inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: Some(42),
+    };
+
+    // Provided RNGs are different, but the configured seed takes precedence
+    let (_first_dir, first) =
+        build_hnsw_index_fixture(&segment, hnsw_config, &mut StdRng::seed_from_u64(1));
+    let (_second_dir, second) =
+        build_hnsw_index_fixture(&segment, hnsw_config, &mut StdRng::seed_from_u64(2));
+
+    assert_graph_layers_eq(first.graph(), second.graph());
+}
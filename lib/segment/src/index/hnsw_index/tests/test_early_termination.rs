@@ -0,0 +1,105 @@
+use common::bitvec::BitVec;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use common::search_diagnostics::SearchDiagnosticsData;
+use common::types::ScoredPointOffset;
+use itertools::Itertools;
+use ordered_float::OrderedFloat;
+use rand::SeedableRng;
+use rand::prelude::StdRng;
+
+use crate::data_types::vectors::DenseVector;
+use crate::fixtures::index_fixtures::{TestRawScorerProducer, random_vector};
+use crate::index::hnsw_index::graph_layers::{GraphLayers, SearchAlgorithm};
+use crate::index::hnsw_index::graph_links::GraphLinksFormat;
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::tests::create_graph_layer_fixture;
+use crate::types::{Distance, EarlyTerminationParams};
+use crate::vector_storage::DEFAULT_STOPPED;
+
+const NUM_VECTORS: usize = 1000;
+const NUM_QUERIES: usize = 50;
+const TOP: usize = 5;
+const EF: usize = 100;
+
+/// Search all `queries`, and return results with search diagnostics.
+fn search_all(
+    graph: &GraphLayers,
+    vector_holder: &TestRawScorerProducer,
+    queries: &[DenseVector],
+    early_termination: Option<EarlyTerminationParams>,
+) -> (Vec<Vec<ScoredPointOffset>>, SearchDiagnosticsData) {
+    let hw_acc = HwMeasurementAcc::new().with_search_diagnostics(true);
+    let deleted_points = BitVec::repeat(false, NUM_VECTORS);
+
+    let results = queries
+        .iter()
+        .map(|query| {
+            let scorer = FilteredScorer::new(
+                query.clone().into(),
+                vector_holder.storage(),
+                None,
+                None,
+                &deleted_points,
+                hw_acc.get_counter_cell(),
+            )
+            .unwrap();
+            graph
+                .search(
+                    TOP,
+                    EF,
+                    SearchAlgorithm::Hnsw,
+                    early_termination.as_ref(),
+                    scorer,
+                    None,
+                    &DEFAULT_STOPPED,
+                )
+                .unwrap()
+        })
+        .collect_vec();
+
+    (results, hw_acc.search_diagnostics().data().unwrap())
+}
+
+#[test]
+fn test_early_termination() {
+    let mut rng = StdRng::seed_from_u64(42);
+    let (vector_holder, graph) = create_graph_layer_fixture(
+        NUM_VECTORS,
+        16,
+        8,
+        GraphLinksFormat::Plain,
+        false,
+        false,
+        Distance::Cosine,
+        &mut rng,
+    );
+    let queries = (0..NUM_QUERIES)
+        .map(|_| random_vector(&mut rng, 8))
+        .collect_vec();
+
+    let (reference, reference_diagnostics) = search_all(&graph, &vector_holder, &queries, None);
+    assert_eq!(reference_diagnostics.early_terminations, 0);
+
+    // Top results never get worse, so zero epsilon never stops the search
+    let never = EarlyTerminationParams {
+        epsilon: OrderedFloat(0.0),
+        patience: 1,
+    };
+    let (results, diagnostics) = search_all(&graph, &vector_holder, &queries, Some(never));
+    assert_eq!(results, reference);
+    assert_eq!(diagnostics.early_terminations, 0);
+    assert_eq!(
+        diagnostics.hops_per_layer,
+        reference_diagnostics.hops_per_layer
+    );
+
+    // Any expansion, which doesn't fill top results, counts as stale
+    let eager = EarlyTerminationParams {
+        epsilon: OrderedFloat(f64::INFINITY),
+        patience: 3,
+    };
+    let (results, diagnostics) = search_all(&graph, &vector_holder, &queries, Some(eager));
+    assert_eq!(diagnostics.early_terminations, NUM_QUERIES);
+    assert!(diagnostics.hops_per_layer[0] < reference_diagnostics.hops_per_layer[0]);
+    assert!(results.iter().all(|result| result.len() == TOP));
+}
@@ -0,0 +1,70 @@
+use common::counter::hardware_counter::HardwareCounterCell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::Builder;
+
+use super::build_hnsw_index_fixture;
+use crate::data_types::vectors::only_default_vector;
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::VectorIndex;
+use crate::index::hnsw_index::ef_calibration::EfCalibration;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{Distance, HnswConfig, SeqNumberType};
+
+#[test]
+fn test_ef_calibration_on_build() {
+    let dim = 16;
+    let num_vectors: u64 = 1_000;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 10,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
+    };
+
+    let (hnsw_dir, hnsw_index) = build_hnsw_index_fixture(&segment, hnsw_config, &mut rng);
+
+    let calibration = hnsw_index.ef_calibration().unwrap();
+    assert!(!calibration.points.is_empty());
+    assert!(calibration.points.is_sorted_by_key(|point| point.ef));
+    assert!(
+        calibration
+            .points
+            .iter()
+            .all(|point| (0.0..=1.0).contains(&point.recall))
+    );
+
+    let calibration_path = EfCalibration::get_path(hnsw_dir.path());
+    assert!(hnsw_index.files().contains(&calibration_path));
+    assert_eq!(
+        &EfCalibration::load(&calibration_path).unwrap(),
+        calibration
+    );
+}
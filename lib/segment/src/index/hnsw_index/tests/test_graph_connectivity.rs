@@ -61,6 +61,9 @@ fn test_graph_connectivity() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = get_num_indexing_threads(hnsw_config.max_indexing_threads);
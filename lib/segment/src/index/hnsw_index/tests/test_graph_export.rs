@@ -0,0 +1,93 @@
+use common::counter::hardware_counter::HardwareCounterCell;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::Builder;
+
+use super::{assert_graph_layers_eq, build_hnsw_index_fixture};
+use crate::common::operation_error::OperationError;
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::segment::Segment;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{Distance, HnswConfig, SeqNumberType};
+
+const NUM_VECTORS: u64 = 500;
+const DIM: usize = 16;
+
+fn hnsw_config() -> HnswConfig {
+    HnswConfig {
+        m: 8,
+        ef_construct: 32,
+        full_scan_threshold: 10,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
+    }
+}
+
+fn import(segment: &Segment, graph: &[u8]) -> Result<HNSWIndex, OperationError> {
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    HNSWIndex::import(
+        HnswIndexOpenArgs {
+            path: hnsw_dir.path(),
+            id_tracker: segment.id_tracker.clone(),
+            vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+                .vector_storage
+                .clone(),
+            quantized_vectors: Default::default(),
+            payload_index: segment.payload_index.clone(),
+            hnsw_config: hnsw_config(),
+        },
+        graph,
+    )
+}
+
+#[test]
+fn test_graph_export_import() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), DIM, Distance::Cosine).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(&mut rng, DIM);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                (n * 3).into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let (_hnsw_dir, hnsw_index) = build_hnsw_index_fixture(&segment, hnsw_config(), &mut rng);
+
+    let mut exported = Vec::new();
+    hnsw_index.export_graph(&mut exported).unwrap();
+    assert_eq!(
+        exported.iter().filter(|&&byte| byte == b'\n').count(),
+        NUM_VECTORS as usize + 1,
+    );
+
+    let imported = import(&segment, &exported).unwrap();
+    assert_graph_layers_eq(hnsw_index.graph(), imported.graph());
+
+    // Each point of the segment must be in the graph
+    let last_line_start = exported[..exported.len() - 1]
+        .iter()
+        .rposition(|&byte| byte == b'\n')
+        .unwrap();
+    assert!(matches!(
+        import(&segment, &exported[..=last_line_start]),
+        Err(OperationError::ValidationError { .. }),
+    ));
+}
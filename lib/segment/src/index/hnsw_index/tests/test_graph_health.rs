@@ -0,0 +1,51 @@
+use common::bitvec::BitVec;
+use common::types::PointOffsetType;
+
+use crate::index::hnsw_index::HnswM;
+use crate::index::hnsw_index::graph_health::graph_health;
+use crate::index::hnsw_index::graph_layers::GraphLayers;
+use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::graph_links::GraphLinksFormatParam;
+
+/// Links of each point by level: `{0, 1, 2}` is reachable from entry point 0, `{3, 4}` is a
+/// separate component, and 5 has no links at all.
+const LINKS: [&[&[PointOffsetType]]; 6] =
+    [&[&[1], &[]], &[&[0, 2]], &[&[1]], &[&[4]], &[&[3]], &[&[]]];
+
+fn graph_fixture() -> GraphLayers {
+    let mut builder = GraphLayersBuilder::new(LINKS.len(), HnswM::new2(4), 16, 1, true);
+    for (point_id, point_links) in LINKS.iter().enumerate() {
+        builder.set_levels(point_id as PointOffsetType, point_links.len() - 1);
+    }
+    for (point_id, point_links) in LINKS.iter().enumerate() {
+        for (level, level_links) in point_links.iter().enumerate() {
+            builder.links_layers()[point_id][level]
+                .write()
+                .fill_from(level_links.iter().copied());
+        }
+    }
+    builder.get_entry_points().new_point(0, 1, |_| true);
+    builder.into_graph_layers_ram(GraphLinksFormatParam::Plain)
+}
+
+#[test]
+fn test_graph_health() {
+    let graph = graph_fixture();
+
+    let mut deleted = BitVec::repeat(false, LINKS.len());
+    let health = graph_health(&graph, &deleted);
+    assert_eq!(health.points_count, 6);
+    assert_eq!(health.avg_out_degree, vec![1.0, 0.0]);
+    assert_eq!(health.connected_components, 3);
+    assert_eq!(health.orphaned_points, 3);
+    assert_eq!(health.deleted_percent, 0.0);
+
+    // Deleted point 1 splits the first component, and cuts point 2 from the entry point
+    deleted.set(1, true);
+    let health = graph_health(&graph, &deleted);
+    assert_eq!(health.points_count, 6);
+    assert_eq!(health.avg_out_degree, vec![0.8, 0.0]);
+    assert_eq!(health.connected_components, 4);
+    assert_eq!(health.orphaned_points, 4);
+    assert!((health.deleted_percent - 100.0 / 6.0).abs() < 1e-9);
+}
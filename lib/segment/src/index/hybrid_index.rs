@@ -0,0 +1,241 @@
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::cow::BoxCow;
+use common::generic_consts::Random;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use ordered_float::OrderedFloat;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use validator::Validate;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::{DenseVector, QueryVector, VectorInternal};
+use crate::index::VectorIndexEnum;
+use crate::index::hnsw_index::point_scorer::{BatchFilteredSearcher, FilteredScorer};
+use crate::payload_storage::FilterContext;
+use crate::types::{SearchParams, VectorNameBuf};
+use crate::vector_storage::query_scorer::QueryScorerBytes;
+use crate::vector_storage::{RawScorer, VectorStorage, VectorStorageEnum, new_raw_scorer};
+
+/// Query for the dense and sparse parts of the same vector family.
+///
+/// Scores of the parts are fused as `alpha * dense + (1 - alpha) * sparse`.
+#[derive(Deserialize, Serialize, JsonSchema, Validate, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HybridQuery {
+    /// Name of the dense vector, its index is used for the traversal
+    pub dense_using: VectorNameBuf,
+
+    /// Dense part of the query
+    #[validate(length(min = 1))]
+    pub dense: DenseVector,
+
+    /// Name of the sparse vector
+    pub sparse_using: VectorNameBuf,
+
+    /// Sparse part of the query
+    #[validate(nested)]
+    pub sparse: SparseVector,
+
+    /// Weight of the dense score, from 0.0 (sparse only) to 1.0 (dense only)
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub alpha: ScoreType,
+}
+
+impl Hash for HybridQuery {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Self {
+            dense_using,
+            dense,
+            sparse_using,
+            sparse,
+            alpha,
+        } = self;
+        dense_using.hash(state);
+        for element in dense {
+            OrderedFloat(*element).hash(state);
+        }
+        sparse_using.hash(state);
+        let SparseVector { indices, values } = sparse;
+        indices.hash(state);
+        for value in values {
+            OrderedFloat(*value).hash(state);
+        }
+        OrderedFloat(*alpha).hash(state);
+    }
+}
+
+/// Hybrid index over the dense and sparse vectors of the same vector family.
+///
+/// Both parts of a point are scored together in a single traversal: over the HNSW graph of the
+/// dense vector if it is indexed, or over all points otherwise. This avoids running two separate
+/// searches and fusing their results afterwards, which loses points ranked low by either part.
+pub struct HybridIndex<'a> {
+    dense_index: &'a VectorIndexEnum,
+    dense_storage: &'a VectorStorageEnum,
+    sparse_storage: &'a VectorStorageEnum,
+}
+
+impl<'a> HybridIndex<'a> {
+    pub fn new(
+        dense_index: &'a VectorIndexEnum,
+        dense_storage: &'a VectorStorageEnum,
+        sparse_storage: &'a VectorStorageEnum,
+    ) -> Self {
+        Self {
+            dense_index,
+            dense_storage,
+            sparse_storage,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn search(
+        &self,
+        query: &HybridQuery,
+        filter_context: Option<Box<dyn FilterContext + 'a>>,
+        point_deleted: &'a BitSlice,
+        top: usize,
+        params: Option<&SearchParams>,
+        deferred_internal_id: Option<PointOffsetType>,
+        hw_counter: HardwareCounterCell,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        if !(0.0..=1.0).contains(&query.alpha) {
+            return Err(OperationError::validation_error(format!(
+                "hybrid alpha must be in range [0.0, 1.0], got {}",
+                query.alpha,
+            )));
+        }
+
+        let mut sparse_query = query.sparse.clone();
+        if !sparse_query.is_sorted() {
+            sparse_query.sort_by_indices();
+        }
+
+        let raw_scorer = Box::new(FusedRawScorer {
+            dense: new_raw_scorer(
+                QueryVector::Nearest(VectorInternal::Dense(query.dense.clone())),
+                self.dense_storage,
+                hw_counter.fork(),
+            )?,
+            sparse_query,
+            sparse_storage: self.sparse_storage,
+            alpha: query.alpha,
+        });
+
+        let filter_context = filter_context.map(BoxCow::Owned);
+        // Points are traversed by the dense part, so they must have a dense vector
+        let vec_deleted = self.dense_storage.deleted_vector_bitslice();
+        let exact = params.is_some_and(|params| params.exact);
+
+        let mut search_result = match self.dense_index {
+            VectorIndexEnum::Hnsw(hnsw_index) if !exact => {
+                let points_scorer = FilteredScorer::with_raw_scorer(
+                    raw_scorer,
+                    filter_context,
+                    point_deleted,
+                    vec_deleted,
+                    hw_counter,
+                );
+                hnsw_index.search_with_scorer(points_scorer, top, params, is_stopped)?
+            }
+            _ => {
+                let searcher = BatchFilteredSearcher::with_raw_scorer(
+                    raw_scorer,
+                    filter_context,
+                    top,
+                    point_deleted,
+                    vec_deleted,
+                );
+                searcher
+                    .peek_top_all(is_stopped, deferred_internal_id)?
+                    .pop()
+                    .unwrap_or_default()
+            }
+        };
+
+        // Graph traversal is not aware of deferred points
+        if let Some(deferred_internal_id) = deferred_internal_id {
+            search_result.retain(|scored| scored.idx < deferred_internal_id);
+        }
+
+        Ok(search_result)
+    }
+}
+
+/// Raw scorer, which scores a point by both its dense and sparse vector.
+///
+/// Points without a sparse vector get a zero sparse score, so they can still be found by the
+/// dense part.
+struct FusedRawScorer<'a> {
+    dense: Box<dyn RawScorer + 'a>,
+    sparse_query: SparseVector,
+    sparse_storage: &'a VectorStorageEnum,
+    alpha: ScoreType,
+}
+
+impl FusedRawScorer<'_> {
+    fn fuse(&self, dense_score: ScoreType, sparse_score: ScoreType) -> ScoreType {
+        self.alpha * dense_score + (1.0 - self.alpha) * sparse_score
+    }
+
+    fn sparse_score(&self, point: PointOffsetType) -> ScoreType {
+        let Some(vector) = self.sparse_storage.get_vector_opt::<Random>(point) else {
+            return 0.0;
+        };
+        let Ok(vector) = <&SparseVector>::try_from(vector.as_vec_ref()) else {
+            return 0.0;
+        };
+        self.sparse_query.score(vector).unwrap_or_default()
+    }
+
+    fn sparse_score_internal(
+        &self,
+        point_a: PointOffsetType,
+        point_b: PointOffsetType,
+    ) -> ScoreType {
+        let (Some(vector_a), Some(vector_b)) = (
+            self.sparse_storage.get_vector_opt::<Random>(point_a),
+            self.sparse_storage.get_vector_opt::<Random>(point_b),
+        ) else {
+            return 0.0;
+        };
+        let (Ok(vector_a), Ok(vector_b)) = (
+            <&SparseVector>::try_from(vector_a.as_vec_ref()),
+            <&SparseVector>::try_from(vector_b.as_vec_ref()),
+        ) else {
+            return 0.0;
+        };
+        vector_a.score(vector_b).unwrap_or_default()
+    }
+}
+
+impl RawScorer for FusedRawScorer<'_> {
+    fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoreType]) {
+        self.dense.score_points(points, scores);
+        for (&point, score) in points.iter().zip(scores.iter_mut()) {
+            *score = self.fuse(*score, self.sparse_score(point));
+        }
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.fuse(self.dense.score_point(point), self.sparse_score(point))
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.fuse(
+            self.dense.score_internal(point_a, point_b),
+            self.sparse_score_internal(point_a, point_b),
+        )
+    }
+
+    fn scorer_bytes(&self) -> Option<&dyn QueryScorerBytes> {
+        // Fused scores can't be computed from the dense vector bytes alone
+        None
+    }
+}
@@ -0,0 +1,314 @@
+//! On-disk layout of IVF inverted lists.
+//!
+//! The file starts with the [`InvertedListsHeader`], followed by sections, each aligned to 8
+//! bytes:
+//!
+//! ```text
+//! ┌────────┬───────────┬─────────┬─────┬────────────┬───────┐
+//! │ header │ centroids │ offsets │ ids │ codebooks? │ codes?│
+//! └────────┴───────────┴─────────┴─────┴────────────┴───────┘
+//! centroids: [f32; num_lists * dim]
+//! offsets:   [u64; num_lists + 1], list `i` holds ids `offsets[i]..offsets[i + 1]`
+//! ids:       [u32; point_count], point offsets of all lists
+//! codebooks: [f32; dim * PQ_CENTROIDS], only if vectors are product quantized
+//! codes:     [u8; point_count * num_subvectors], in the same order as ids
+//! ```
+//!
+//! Centroids, offsets and codebooks are read into RAM, ids and codes are read from the file
+//! during the search.
+
+use std::path::{Path, PathBuf};
+
+use common::fs::atomic_save;
+use common::mmap::{Advice, AdviceSetting, Madviseable, open_read_mmap};
+use common::types::{PointOffsetType, ScoreType};
+use memmap2::Mmap;
+use zerocopy::little_endian::{F32 as LittleF32, U32 as LittleU32, U64 as LittleU64};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use super::kmeans::{nearest_centroid, similarity};
+use super::product_quantizer::{PQ_CENTROIDS, ProductQuantizer};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::VectorElementType;
+use crate::types::Distance;
+
+pub const IVF_LISTS_FILE: &str = "ivf_lists.bin";
+
+const IVF_LISTS_VERSION: u64 = 1;
+
+const SECTION_ALIGNMENT: usize = size_of::<u64>();
+
+#[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
+#[repr(C)]
+struct InvertedListsHeader {
+    version: LittleU64,
+    dim: LittleU64,
+    num_lists: LittleU64,
+    point_count: LittleU64,
+    /// Number of product quantization sub-vectors, `0` if vectors are not quantized
+    num_subvectors: LittleU64,
+}
+
+/// Byte ranges of sections in the file.
+#[derive(Debug, Clone, Copy)]
+struct SectionsLayout {
+    centroids: usize,
+    offsets: usize,
+    ids: usize,
+    codebooks: usize,
+    codes: usize,
+    end: usize,
+}
+
+impl SectionsLayout {
+    fn new(dim: usize, num_lists: usize, point_count: usize, num_subvectors: usize) -> Self {
+        let align = |offset: usize| offset.next_multiple_of(SECTION_ALIGNMENT);
+        let centroids = align(size_of::<InvertedListsHeader>());
+        let offsets = align(centroids + num_lists * dim * size_of::<f32>());
+        let ids = align(offsets + (num_lists + 1) * size_of::<u64>());
+        let codebooks = align(ids + point_count * size_of::<u32>());
+        let (codes, end) = if num_subvectors == 0 {
+            (codebooks, codebooks)
+        } else {
+            let codes = align(codebooks + dim * PQ_CENTROIDS * size_of::<f32>());
+            (codes, codes + point_count * num_subvectors)
+        };
+        Self {
+            centroids,
+            offsets,
+            ids,
+            codebooks,
+            codes,
+            end,
+        }
+    }
+}
+
+/// Inverted lists of points, clustered around centroids.
+#[derive(Debug)]
+pub struct InvertedLists {
+    mmap: Mmap,
+    layout: SectionsLayout,
+    dim: usize,
+    centroids: Vec<VectorElementType>,
+    offsets: Vec<usize>,
+    quantizer: Option<ProductQuantizer>,
+}
+
+impl InvertedLists {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(IVF_LISTS_FILE)
+    }
+
+    pub fn files(path: &Path) -> Vec<PathBuf> {
+        vec![Self::get_path(path)]
+    }
+
+    /// Write inverted lists into the file in `path`.
+    ///
+    /// If `quantizer` is given, `codes` hold codes of all points, indexed by point offset.
+    pub fn save(
+        path: &Path,
+        dim: usize,
+        centroids: &[VectorElementType],
+        lists: &[Vec<PointOffsetType>],
+        quantizer: Option<(&ProductQuantizer, &[u8])>,
+    ) -> OperationResult<()> {
+        let num_lists = lists.len();
+        debug_assert_eq!(centroids.len(), num_lists * dim);
+        let point_count = lists.iter().map(Vec::len).sum();
+        let num_subvectors = quantizer.map_or(0, |(quantizer, _)| quantizer.num_subvectors());
+        let layout = SectionsLayout::new(dim, num_lists, point_count, num_subvectors);
+
+        let header = InvertedListsHeader {
+            version: LittleU64::new(IVF_LISTS_VERSION),
+            dim: LittleU64::new(dim as u64),
+            num_lists: LittleU64::new(num_lists as u64),
+            point_count: LittleU64::new(point_count as u64),
+            num_subvectors: LittleU64::new(num_subvectors as u64),
+        };
+
+        let mut data = vec![0u8; layout.end];
+        data[..size_of::<InvertedListsHeader>()].copy_from_slice(header.as_bytes());
+
+        let write_f32s = |data: &mut [u8], start: usize, values: &[VectorElementType]| {
+            for (i, &value) in values.iter().enumerate() {
+                let offset = start + i * size_of::<f32>();
+                data[offset..offset + size_of::<f32>()]
+                    .copy_from_slice(LittleF32::new(value).as_bytes());
+            }
+        };
+        write_f32s(&mut data, layout.centroids, centroids);
+
+        let mut list_start = 0;
+        for (list_id, list) in lists.iter().enumerate() {
+            for (i, &point_id) in list.iter().enumerate() {
+                let offset = layout.ids + (list_start + i) * size_of::<u32>();
+                data[offset..offset + size_of::<u32>()]
+                    .copy_from_slice(LittleU32::new(point_id).as_bytes());
+            }
+            if let Some((_, codes)) = quantizer {
+                for (i, &point_id) in list.iter().enumerate() {
+                    let point_codes =
+                        &codes[point_id as usize * num_subvectors..][..num_subvectors];
+                    let offset = layout.codes + (list_start + i) * num_subvectors;
+                    data[offset..offset + num_subvectors].copy_from_slice(point_codes);
+                }
+            }
+
+            let offset = layout.offsets + list_id * size_of::<u64>();
+            data[offset..offset + size_of::<u64>()]
+                .copy_from_slice(LittleU64::new(list_start as u64).as_bytes());
+            list_start += list.len();
+        }
+        let offset = layout.offsets + num_lists * size_of::<u64>();
+        data[offset..offset + size_of::<u64>()]
+            .copy_from_slice(LittleU64::new(list_start as u64).as_bytes());
+
+        if let Some((quantizer, _)) = quantizer {
+            write_f32s(&mut data, layout.codebooks, quantizer.codebooks());
+        }
+
+        atomic_save(&Self::get_path(path), |writer| {
+            std::io::Write::write_all(writer, &data)
+        })?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path, on_disk: bool) -> OperationResult<Self> {
+        let populate = !on_disk;
+        let mmap = open_read_mmap(
+            &Self::get_path(path),
+            AdviceSetting::Advice(Advice::Random),
+            populate,
+        )?;
+
+        let (header, _) = InvertedListsHeader::ref_from_prefix(&mmap).map_err(|_| {
+            OperationError::service_error("IVF lists file is too small for the header")
+        })?;
+        if header.version.get() != IVF_LISTS_VERSION {
+            return Err(OperationError::service_error(format!(
+                "unsupported IVF lists version {}",
+                header.version.get(),
+            )));
+        }
+
+        let dim = header.dim.get() as usize;
+        let num_lists = header.num_lists.get() as usize;
+        let point_count = header.point_count.get() as usize;
+        let num_subvectors = header.num_subvectors.get() as usize;
+        let layout = SectionsLayout::new(dim, num_lists, point_count, num_subvectors);
+        if mmap.len() < layout.end {
+            return Err(OperationError::service_error(
+                "IVF lists file has unexpected layout",
+            ));
+        }
+
+        let read_f32s = |start: usize, count: usize| -> Vec<VectorElementType> {
+            <[LittleF32]>::ref_from_bytes(&mmap[start..start + count * size_of::<f32>()])
+                .expect("section size is a multiple of f32")
+                .iter()
+                .map(|value| value.get())
+                .collect()
+        };
+
+        let centroids = read_f32s(layout.centroids, num_lists * dim);
+        let offsets = <[LittleU64]>::ref_from_bytes(
+            &mmap[layout.offsets..][..(num_lists + 1) * size_of::<u64>()],
+        )
+        .expect("section size is a multiple of u64")
+        .iter()
+        .map(|offset| offset.get() as usize)
+        .collect();
+        let quantizer = (num_subvectors > 0).then(|| {
+            ProductQuantizer::new(
+                dim,
+                num_subvectors,
+                read_f32s(layout.codebooks, dim * PQ_CENTROIDS),
+            )
+        });
+
+        Ok(Self {
+            mmap,
+            layout,
+            dim,
+            centroids,
+            offsets,
+            quantizer,
+        })
+    }
+
+    pub fn num_lists(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn quantizer(&self) -> Option<&ProductQuantizer> {
+        self.quantizer.as_ref()
+    }
+
+    /// Lists with centroids closest to `query`, closest first.
+    pub fn nearest_lists(
+        &self,
+        query: &[VectorElementType],
+        distance: Distance,
+        nprobe: usize,
+    ) -> Vec<usize> {
+        if self.num_lists() == 0 {
+            return Vec::new();
+        }
+        if nprobe == 1 {
+            return vec![nearest_centroid(&self.centroids, self.dim, query, distance).0];
+        }
+        let mut scores: Vec<(usize, ScoreType)> = self
+            .centroids
+            .chunks_exact(self.dim)
+            .map(|centroid| similarity(distance, query, centroid))
+            .enumerate()
+            .collect();
+        let nprobe = nprobe.min(scores.len());
+        if nprobe < scores.len() {
+            scores.select_nth_unstable_by(nprobe, |(_, a), (_, b)| b.total_cmp(a));
+            scores.truncate(nprobe);
+        }
+        scores.sort_unstable_by(|(_, a), (_, b)| b.total_cmp(a));
+        scores.into_iter().map(|(list_id, _)| list_id).collect()
+    }
+
+    /// Point offsets in the list.
+    pub fn list(&self, list_id: usize) -> impl Iterator<Item = PointOffsetType> + '_ {
+        let (start, end) = (self.offsets[list_id], self.offsets[list_id + 1]);
+        <[LittleU32]>::ref_from_bytes(
+            &self.mmap[self.layout.ids + start * size_of::<u32>()..]
+                [..(end - start) * size_of::<u32>()],
+        )
+        .expect("section size is a multiple of u32")
+        .iter()
+        .map(|point_id| point_id.get())
+    }
+
+    /// Product quantization codes of points in the list, in the same order as [`Self::list`].
+    pub fn list_codes(&self, list_id: usize) -> impl Iterator<Item = &[u8]> + '_ {
+        let num_subvectors = self
+            .quantizer
+            .as_ref()
+            .map_or(1, ProductQuantizer::num_subvectors);
+        let (start, end) = (self.offsets[list_id], self.offsets[list_id + 1]);
+        let codes = if self.quantizer.is_some() {
+            &self.mmap[self.layout.codes + start * num_subvectors..]
+                [..(end - start) * num_subvectors]
+        } else {
+            &[]
+        };
+        codes.chunks_exact(num_subvectors)
+    }
+
+    /// Populate the disk cache with the lists.
+    pub fn populate(&self) -> OperationResult<()> {
+        self.mmap.populate();
+        Ok(())
+    }
+}
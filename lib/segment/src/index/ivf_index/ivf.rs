@@ -0,0 +1,718 @@
+use std::borrow::Cow;
+use std::ops::Deref as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use atomic_refcell::AtomicRefCell;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::cow::BoxCow;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::fs::clear_disk_cache;
+use common::generic_consts::Random;
+use common::search_diagnostics::SearchStrategy;
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
+use fs_err as fs;
+use log::debug;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, RngExt, SeedableRng};
+use rayon::prelude::*;
+
+use super::config::IvfIndexConfig;
+use super::inverted_lists::InvertedLists;
+use super::kmeans::{kmeans, nearest_centroid};
+use super::product_quantizer::ProductQuantizer;
+use crate::common::BYTES_IN_KB;
+use crate::common::operation_error::{
+    CancellableResult, OperationError, OperationResult, check_process_stopped,
+};
+use crate::common::operation_time_statistics::{
+    OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{
+    DenseVector, QueryVector, VectorElementType, VectorInternal, VectorRef,
+};
+use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::hnsw_index::point_scorer::{BatchFilteredSearcher, FilteredScorer};
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_search_common::{
+    get_oversampled_top, is_quantized_search, postprocess_search_result,
+};
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{DEFAULT_IVF_NPROBE, Filter, IvfConfig, QuantizationSearchParams, SearchParams};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Number of k-means iterations to find centroids of inverted lists.
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Number of vectors per inverted list, sampled to train centroids and codebooks.
+const TRAINING_SAMPLE_SIZE_PER_LIST: usize = 64;
+
+/// Minimal number of vectors, sampled to train centroids and codebooks.
+const MIN_TRAINING_SAMPLE_SIZE: usize = 10_000;
+
+/// Number of candidates per requested result, selected with product quantization codes to be
+/// rescored.
+const PQ_RESCORE_MULTIPLIER: usize = 4;
+
+/// Inverted file index.
+///
+/// Vectors are clustered around k-means centroids, and the search only scans inverted lists of
+/// the `nprobe` centroids closest to the query. Optionally, vectors in lists are encoded with
+/// product quantization, so that lists are scanned without reading the vectors.
+#[derive(Debug)]
+pub struct IvfIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerEnum>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: IvfIndexConfig,
+    nprobe: usize,
+    path: PathBuf,
+    lists: InvertedLists,
+    is_on_disk: bool,
+    searches_telemetry: IvfSearchesTelemetry,
+}
+
+#[derive(Debug)]
+struct IvfSearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_lists: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl IvfSearchesTelemetry {
+    fn new() -> Self {
+        Self {
+            unfiltered_plain: OperationDurationsAggregator::new(),
+            unfiltered_lists: OperationDurationsAggregator::new(),
+            small_cardinality: OperationDurationsAggregator::new(),
+            large_cardinality: OperationDurationsAggregator::new(),
+            exact_filtered: OperationDurationsAggregator::new(),
+            exact_unfiltered: OperationDurationsAggregator::new(),
+        }
+    }
+}
+
+pub struct IvfIndexOpenArgs<'a> {
+    pub path: &'a Path,
+    pub id_tracker: Arc<AtomicRefCell<IdTrackerEnum>>,
+    pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    pub ivf_config: IvfConfig,
+}
+
+impl IvfIndex {
+    pub fn open(args: IvfIndexOpenArgs<'_>) -> OperationResult<Self> {
+        let IvfIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            ivf_config,
+        } = args;
+
+        let config = IvfIndexConfig::load(&IvfIndexConfig::get_config_path(path))?;
+        let is_on_disk = ivf_config.on_disk.unwrap_or_default();
+        let lists = InvertedLists::load(path, is_on_disk)?;
+
+        Ok(IvfIndex {
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            config,
+            nprobe: ivf_config.nprobe.unwrap_or(DEFAULT_IVF_NPROBE),
+            path: path.to_owned(),
+            lists,
+            is_on_disk,
+            searches_telemetry: IvfSearchesTelemetry::new(),
+        })
+    }
+
+    pub fn build<R: Rng + ?Sized>(
+        open_args: IvfIndexOpenArgs<'_>,
+        build_args: VectorIndexBuildArgs<'_, R>,
+    ) -> OperationResult<Self> {
+        if IvfIndexConfig::get_config_path(open_args.path).exists()
+            || InvertedLists::get_path(open_args.path).exists()
+        {
+            log::warn!(
+                "IVF index already exists at {:?}, skipping building",
+                open_args.path
+            );
+            debug_assert!(false);
+            return Self::open(open_args);
+        }
+
+        let VectorIndexBuildArgs {
+            permit,
+            old_indices: _,
+            gpu_device: _,
+            rng,
+            stopped,
+            hnsw_global_config: _,
+            feature_flags: _,
+            progress,
+        } = build_args;
+        let path = open_args.path;
+        let ivf_config = open_args.ivf_config;
+
+        fs::create_dir_all(path)?;
+
+        let id_tracker_ref = open_args.id_tracker.borrow();
+        let vector_storage_ref = open_args.vector_storage.borrow();
+
+        if vector_storage_ref.try_multi_vector_config().is_some() {
+            return Err(OperationError::validation_error(
+                "IVF index is not supported for multivectors",
+            ));
+        }
+
+        let total_vector_count = vector_storage_ref.total_vector_count();
+        let distance = vector_storage_ref.distance();
+
+        let full_scan_threshold = vector_storage_ref
+            .size_of_available_vectors_in_bytes()
+            .checked_div(total_vector_count)
+            .and_then(|avg_vector_size| {
+                ivf_config
+                    .full_scan_threshold
+                    .saturating_mul(BYTES_IN_KB)
+                    .checked_div(avg_vector_size)
+            })
+            .unwrap_or(1);
+
+        let deleted_bitslice = vector_storage_ref.deleted_vector_bitslice();
+        let ids: Vec<PointOffsetType> = id_tracker_ref
+            .point_mappings()
+            .iter_internal_excluding(deleted_bitslice)
+            .collect();
+
+        let num_lists = ivf_config
+            .num_lists
+            .unwrap_or_else(|| (4.0 * (ids.len() as f64).sqrt()).round() as usize)
+            .clamp(1, ids.len().max(1));
+        let num_subvectors = ivf_config.pq.map(|pq| pq.num_subvectors);
+
+        debug!(
+            "building IVF index with {num_lists} lists for {} vectors with {} CPUs",
+            ids.len(),
+            permit.num_cpus,
+        );
+
+        let (dim, centroids, lists, quantizer, codes) = if ids.is_empty() {
+            (0, Vec::new(), Vec::new(), None, Vec::new())
+        } else {
+            let read_vector = |point_id, dim: usize| -> OperationResult<DenseVector> {
+                let vector = vector_storage_ref.get_vector::<Random>(point_id);
+                let CowVector::Dense(vector) = vector else {
+                    return Err(OperationError::service_error(
+                        "IVF index supports only dense vectors",
+                    ));
+                };
+                // Stored vectors may be shorter than `dim`, if dimension range is configured
+                let mut vector = Cow::into_owned(vector);
+                vector.resize(dim, 0.0);
+                Ok(vector)
+            };
+            let dim = match vector_storage_ref.get_vector::<Random>(ids[0]) {
+                CowVector::Dense(vector) => vector.len(),
+                _ => 0,
+            };
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("ivf-build-{idx}"))
+                .num_threads(permit.num_cpus as usize)
+                .build()?;
+
+            let progress_training = progress.subtask("training");
+            let progress_assignment = progress.subtask("assignment");
+
+            progress_training.start();
+            let timer = std::time::Instant::now();
+            let sample_size = (num_lists * TRAINING_SAMPLE_SIZE_PER_LIST)
+                .max(MIN_TRAINING_SAMPLE_SIZE)
+                .min(ids.len());
+            let mut training_vectors: Vec<VectorElementType> =
+                Vec::with_capacity(sample_size * dim);
+            for i in rand::seq::index::sample(rng, ids.len(), sample_size) {
+                check_process_stopped(stopped)?;
+                training_vectors.extend_from_slice(&read_vector(ids[i], dim)?);
+            }
+
+            // Build RNG can't be shared with the pool threads, so training gets its own one
+            let mut training_rng = StdRng::seed_from_u64(rng.random());
+            let (centroids, quantizer) = pool.install(|| {
+                let centroids = kmeans(
+                    &training_vectors,
+                    dim,
+                    num_lists,
+                    distance,
+                    KMEANS_ITERATIONS,
+                    &mut training_rng,
+                    stopped,
+                )?;
+                let quantizer = num_subvectors
+                    .map(|num_subvectors| {
+                        ProductQuantizer::train(
+                            &training_vectors,
+                            dim,
+                            num_subvectors,
+                            &mut training_rng,
+                            stopped,
+                        )
+                    })
+                    .transpose()?;
+                Ok::<_, OperationError>((centroids, quantizer))
+            })?;
+            drop(training_vectors);
+            drop(progress_training);
+            debug!("Finish IVF training in time {:?}", timer.elapsed());
+
+            progress_assignment.start();
+            let counter = progress_assignment.track_progress(Some(ids.len() as u64));
+            let counter = counter.deref();
+            let timer = std::time::Instant::now();
+            let code_size = quantizer
+                .as_ref()
+                .map_or(0, ProductQuantizer::num_subvectors);
+            let assignments: Vec<(usize, Vec<u8>)> = pool.install(|| {
+                ids.par_iter()
+                    .map(|&point_id| {
+                        check_process_stopped(stopped)?;
+                        let vector = read_vector(point_id, dim)?;
+                        let (list_id, _) = nearest_centroid(&centroids, dim, &vector, distance);
+                        let mut point_codes = vec![0; code_size];
+                        if let Some(quantizer) = &quantizer {
+                            quantizer.encode(&vector, &mut point_codes);
+                        }
+                        counter.fetch_add(1, Ordering::Relaxed);
+                        Ok::<_, OperationError>((list_id, point_codes))
+                    })
+                    .collect::<Result<_, _>>()
+            })?;
+            drop(progress_assignment);
+            debug!("Finish IVF assignment in time {:?}", timer.elapsed());
+
+            let mut lists = vec![Vec::new(); centroids.len() / dim.max(1)];
+            let mut codes = vec![0; total_vector_count * code_size];
+            for (&point_id, (list_id, point_codes)) in ids.iter().zip(assignments) {
+                lists[list_id].push(point_id);
+                codes[point_id as usize * code_size..][..code_size].copy_from_slice(&point_codes);
+            }
+
+            (dim, centroids, lists, quantizer, codes)
+        };
+
+        InvertedLists::save(
+            path,
+            dim,
+            &centroids,
+            &lists,
+            quantizer
+                .as_ref()
+                .map(|quantizer| (quantizer, codes.as_slice())),
+        )?;
+
+        let config = IvfIndexConfig {
+            num_lists: lists.len(),
+            pq_num_subvectors: quantizer.as_ref().map(ProductQuantizer::num_subvectors),
+            full_scan_threshold,
+            indexed_vector_count: ids.len(),
+        };
+        config.save(&IvfIndexConfig::get_config_path(path))?;
+
+        drop(id_tracker_ref);
+        drop(vector_storage_ref);
+
+        Self::open(open_args)
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        self.is_on_disk
+    }
+
+    /// Read underlying data from disk into disk cache.
+    pub fn populate(&self) -> OperationResult<()> {
+        self.lists.populate()
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        for file in InvertedLists::files(&self.path) {
+            clear_disk_cache(&file)?;
+        }
+        Ok(())
+    }
+
+    /// Dense vector of a nearest neighbor query, the only kind of queries served by the lists.
+    fn nearest_dense_query(query: &QueryVector) -> Option<&DenseVector> {
+        match query {
+            QueryVector::Nearest(VectorInternal::Dense(vector)) => Some(vector),
+            _ => None,
+        }
+    }
+
+    fn search_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_storage = self.quantized_vectors.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let quantization_enabled = is_quantized_search(quantized_storage.as_ref(), params);
+        let quantized_vectors = quantization_enabled
+            .then_some(quantized_storage.as_ref())
+            .flatten();
+        let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
+        let batch_searcher = BatchFilteredSearcher::new(
+            vectors,
+            &vector_storage,
+            quantized_vectors,
+            None,
+            oversampled_top,
+            deleted_points,
+            query_context.hardware_counter(),
+        )?;
+
+        let search_results = match filter {
+            Some(filter) => {
+                let payload_index = self.payload_index.borrow();
+                let filtered_ids =
+                    payload_index.query_points(filter, &hw_counter, &is_stopped, None)?;
+                batch_searcher.peek_top_iter(filtered_ids.iter().copied(), &is_stopped)?
+            }
+            None => batch_searcher.peek_top_all(&is_stopped, None)?,
+        };
+
+        search_results
+            .into_iter()
+            .zip(vectors)
+            .map(|(search_result, query_vector)| {
+                postprocess_search_result(
+                    search_result,
+                    deleted_points,
+                    &vector_storage,
+                    quantized_storage.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    query_context.hardware_counter(),
+                )
+            })
+            .collect()
+    }
+
+    fn search_with_lists(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let nprobe = params
+            .and_then(|params| params.nprobe)
+            .unwrap_or(self.nprobe);
+
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_storage = self.quantized_vectors.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let quantization_enabled = is_quantized_search(quantized_storage.as_ref(), params);
+        let quantized_vectors = quantization_enabled
+            .then_some(quantized_storage.as_ref())
+            .flatten();
+        let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
+        let distance = vector_storage.distance();
+
+        let filter_context = filter
+            .map(|filter| payload_index.filter_context(filter, &hw_counter))
+            .transpose()?;
+
+        vectors
+            .iter()
+            .map(|&query_vector| {
+                let query = Self::nearest_dense_query(query_vector).ok_or_else(|| {
+                    OperationError::service_error("IVF lists only serve nearest dense queries")
+                })?;
+                let query = distance.preprocess_vector::<VectorElementType>(query.clone());
+                let nearest_lists = self.lists.nearest_lists(&query, distance, nprobe);
+
+                let mut points_scorer = FilteredScorer::new(
+                    query_vector.to_owned(),
+                    &vector_storage,
+                    quantized_vectors,
+                    filter_context.as_deref().map(BoxCow::Borrowed),
+                    deleted_points,
+                    query_context.hardware_counter(),
+                )?;
+                let search_result = match self.lists.quantizer() {
+                    None => self.scan_lists(
+                        &nearest_lists,
+                        oversampled_top,
+                        &mut points_scorer,
+                        &is_stopped,
+                    )?,
+                    Some(quantizer) => self.scan_quantized_lists(
+                        &nearest_lists,
+                        &quantizer.lookup_table(&query, distance),
+                        oversampled_top,
+                        &mut points_scorer,
+                        &is_stopped,
+                    )?,
+                };
+                postprocess_search_result(
+                    search_result,
+                    deleted_points,
+                    &vector_storage,
+                    quantized_storage.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    query_context.hardware_counter(),
+                )
+            })
+            .collect()
+    }
+
+    /// Score all points of the lists with the scorer.
+    fn scan_lists(
+        &self,
+        lists: &[usize],
+        top: usize,
+        points_scorer: &mut FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        let mut result = FixedLengthPriorityQueue::new(top);
+        let mut points_ids = Vec::new();
+        for &list_id in lists {
+            check_process_stopped(is_stopped)?;
+            points_ids.clear();
+            points_ids.extend(self.lists.list(list_id));
+            points_scorer
+                .score_points(&mut points_ids, 0)
+                .for_each(|scored| {
+                    result.push(scored);
+                });
+        }
+        Ok(result.into_sorted_vec())
+    }
+
+    /// Score all points of the lists with product quantization codes, and rescore the best of
+    /// them with the scorer.
+    fn scan_quantized_lists(
+        &self,
+        lists: &[usize],
+        lookup_table: &[ScoreType],
+        top: usize,
+        points_scorer: &mut FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        let mut candidates = FixedLengthPriorityQueue::new(top * PQ_RESCORE_MULTIPLIER);
+        for &list_id in lists {
+            check_process_stopped(is_stopped)?;
+            for (idx, codes) in self.lists.list(list_id).zip(self.lists.list_codes(list_id)) {
+                if points_scorer.filters().check_vector(idx) {
+                    candidates.push(ScoredPointOffset {
+                        idx,
+                        score: ProductQuantizer::score(lookup_table, codes),
+                    });
+                }
+            }
+        }
+
+        let candidate_ids: Vec<PointOffsetType> = candidates
+            .into_iter_sorted()
+            .map(|candidate| candidate.idx)
+            .collect();
+        let mut result = FixedLengthPriorityQueue::new(top);
+        points_scorer
+            .score_points_unfiltered(&candidate_ids)
+            .for_each(|scored| {
+                result.push(scored);
+            });
+        Ok(result.into_sorted_vec())
+    }
+}
+
+impl VectorIndex for IvfIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        if top == 0 {
+            return Ok(vec![vec![]; vectors.len()]);
+        }
+
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let diagnostics = query_context.hardware_counter().search_diagnostics();
+
+        let exact_params = exact.then(|| {
+            let mut params = params.copied().unwrap_or_default();
+            // disable quantization for exact search
+            params.quantization = Some(QuantizationSearchParams {
+                ignore: true,
+                rescore: Some(false),
+                oversampling: None,
+            });
+            params
+        });
+
+        let tm = &self.searches_telemetry;
+
+        if exact {
+            let _timer = ScopeDurationMeasurer::new(if filter.is_some() {
+                &tm.exact_filtered
+            } else {
+                &tm.exact_unfiltered
+            });
+            diagnostics.add_searches(SearchStrategy::PlainExact, vectors.len());
+            return self.search_plain(vectors, filter, top, exact_params.as_ref(), query_context);
+        }
+
+        // Lists are clustered around dense vectors, other queries scan all points
+        if !vectors
+            .iter()
+            .all(|query| Self::nearest_dense_query(query).is_some())
+        {
+            let _timer = ScopeDurationMeasurer::new(if filter.is_some() {
+                &tm.small_cardinality
+            } else {
+                &tm.unfiltered_plain
+            });
+            diagnostics.add_searches(SearchStrategy::PlainIndexDisabled, vectors.len());
+            return self.search_plain(vectors, filter, top, params, query_context);
+        }
+
+        let Some(query_filter) = filter else {
+            let available_vector_count = self.vector_storage.borrow().available_vector_count();
+            return if available_vector_count < self.config.full_scan_threshold {
+                let _timer = ScopeDurationMeasurer::new(&tm.unfiltered_plain);
+                diagnostics.add_searches(SearchStrategy::PlainSmallSegment, vectors.len());
+                self.search_plain(vectors, None, top, params, query_context)
+            } else {
+                let _timer = ScopeDurationMeasurer::new(&tm.unfiltered_lists);
+                diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
+                self.search_with_lists(vectors, None, top, params, query_context)
+            };
+        };
+
+        let is_cardinality_large = {
+            let payload_index = self.payload_index.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            let id_tracker = self.id_tracker.borrow();
+            let available_vector_count = vector_storage.available_vector_count();
+            let hw_counter = query_context.hardware_counter();
+
+            let query_cardinality = adjust_to_available_vectors(
+                payload_index.estimate_cardinality(query_filter, &hw_counter)?,
+                available_vector_count,
+                id_tracker.available_point_count(),
+            );
+
+            if query_cardinality.max < self.config.full_scan_threshold {
+                false
+            } else if query_cardinality.min > self.config.full_scan_threshold {
+                true
+            } else {
+                let filter_context = payload_index.filter_context(query_filter, &hw_counter)?;
+                sample_check_cardinality(
+                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                    |idx| filter_context.check(idx),
+                    self.config.full_scan_threshold,
+                    available_vector_count,
+                )
+            }
+        };
+
+        if is_cardinality_large {
+            let _timer = ScopeDurationMeasurer::new(&tm.large_cardinality);
+            diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
+            self.search_with_lists(vectors, filter, top, params, query_context)
+        } else {
+            let _timer = ScopeDurationMeasurer::new(&tm.small_cardinality);
+            diagnostics.add_searches(SearchStrategy::PlainSmallCardinality, vectors.len());
+            self.search_plain(vectors, filter, top, params, query_context)
+        }
+    }
+
+    fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(detail),
+            filtered_plain: OperationDurationStatistics::default(),
+            unfiltered_hnsw: tm.unfiltered_lists.lock().get_statistics(detail),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(detail),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(detail),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(detail),
+            filtered_sparse: Default::default(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
+            unfiltered_sparse: Default::default(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = InvertedLists::files(&self.path);
+        files.push(IvfIndexConfig::get_config_path(&self.path));
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        self.files()
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config.indexed_vector_count
+    }
+
+    fn size_of_searchable_vectors_in_bytes(&self) -> usize {
+        self.vector_storage
+            .borrow()
+            .size_of_available_vectors_in_bytes()
+    }
+
+    fn update_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: Option<VectorRef>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update IVF index"))
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::atomic::AtomicBool;
+
+use common::types::ScoreType;
+use rand::{Rng, RngExt};
+use rayon::prelude::*;
+
+use crate::common::operation_error::{CancellableResult, check_process_stopped};
+use crate::data_types::vectors::VectorElementType;
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use crate::types::Distance;
+
+/// Similarity of two dense vectors, greater the value - closer the vectors.
+pub(super) fn similarity(
+    distance: Distance,
+    v1: &[VectorElementType],
+    v2: &[VectorElementType],
+) -> ScoreType {
+    match distance {
+        Distance::Cosine => CosineMetric::similarity(v1, v2),
+        Distance::Euclid => EuclidMetric::similarity(v1, v2),
+        Distance::Dot => DotProductMetric::similarity(v1, v2),
+        Distance::Manhattan => ManhattanMetric::similarity(v1, v2),
+    }
+}
+
+/// Index of the centroid closest to `vector`, along with its score.
+///
+/// `centroids` are laid out contiguously, `dim` elements each.
+pub(super) fn nearest_centroid(
+    centroids: &[VectorElementType],
+    dim: usize,
+    vector: &[VectorElementType],
+    distance: Distance,
+) -> (usize, ScoreType) {
+    centroids
+        .chunks_exact(dim)
+        .map(|centroid| similarity(distance, vector, centroid))
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .unwrap_or((0, ScoreType::MIN))
+}
+
+/// Cluster `vectors` around up to `k` centroids with Lloyd's algorithm.
+///
+/// `vectors` are laid out contiguously, `dim` elements each. Centroids are initialized with
+/// k-means++, and a centroid, which loses all of its vectors, is moved to a random vector.
+/// Assignment is parallelized with the current rayon pool.
+///
+/// Returns centroids, laid out the same way as `vectors`.
+pub(super) fn kmeans<R: Rng + ?Sized>(
+    vectors: &[VectorElementType],
+    dim: usize,
+    k: usize,
+    distance: Distance,
+    iterations: usize,
+    rng: &mut R,
+    stopped: &AtomicBool,
+) -> CancellableResult<Vec<VectorElementType>> {
+    let num_vectors = vectors.len() / dim;
+    let k = k.min(num_vectors);
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut centroids = kmeans_plus_plus_init(vectors, dim, k, distance, rng, stopped)?;
+
+    let mut sums = vec![0.0; k * dim];
+    let mut counts = vec![0usize; k];
+
+    for _ in 0..iterations {
+        check_process_stopped(stopped)?;
+
+        let assignments: Vec<usize> = vectors
+            .par_chunks_exact(dim)
+            .map(|vector| nearest_centroid(&centroids, dim, vector, distance).0)
+            .collect();
+
+        sums.fill(0.0);
+        counts.fill(0);
+        for (vector, &cluster) in vectors.chunks_exact(dim).zip(&assignments) {
+            counts[cluster] += 1;
+            let sum = &mut sums[cluster * dim..(cluster + 1) * dim];
+            sum.iter_mut().zip(vector).for_each(|(s, x)| *s += x);
+        }
+
+        for (cluster, &count) in counts.iter().enumerate() {
+            let centroid = &mut centroids[cluster * dim..(cluster + 1) * dim];
+            if count == 0 {
+                let i = rng.random_range(0..num_vectors);
+                centroid.copy_from_slice(&vectors[i * dim..(i + 1) * dim]);
+                continue;
+            }
+            let sum = &sums[cluster * dim..(cluster + 1) * dim];
+            centroid
+                .iter_mut()
+                .zip(sum)
+                .for_each(|(c, s)| *c = s / count as VectorElementType);
+            if distance == Distance::Cosine {
+                let normalized = distance.preprocess_vector::<VectorElementType>(centroid.to_vec());
+                centroid.copy_from_slice(&normalized);
+            }
+        }
+    }
+
+    Ok(centroids)
+}
+
+/// Select `k` initial centroids out of `vectors`, each next one is sampled with probability
+/// proportional to its distance to the closest centroid selected so far.
+///
+/// The distance is the gap between self-similarity of a vector and its similarity to the
+/// centroid, which is the squared distance for Euclid.
+fn kmeans_plus_plus_init<R: Rng + ?Sized>(
+    vectors: &[VectorElementType],
+    dim: usize,
+    k: usize,
+    distance: Distance,
+    rng: &mut R,
+    stopped: &AtomicBool,
+) -> CancellableResult<Vec<VectorElementType>> {
+    let num_vectors = vectors.len() / dim;
+    let self_similarities: Vec<ScoreType> = vectors
+        .par_chunks_exact(dim)
+        .map(|vector| similarity(distance, vector, vector))
+        .collect();
+
+    let mut centroids = Vec::with_capacity(k * dim);
+    let mut gaps = vec![ScoreType::INFINITY; num_vectors];
+    let mut cumulative = Vec::with_capacity(num_vectors);
+
+    let mut selected = rng.random_range(0..num_vectors);
+    for _ in 0..k {
+        check_process_stopped(stopped)?;
+
+        let centroid = &vectors[selected * dim..(selected + 1) * dim];
+        centroids.extend_from_slice(centroid);
+        vectors
+            .par_chunks_exact(dim)
+            .zip(&self_similarities)
+            .zip(&mut gaps)
+            .for_each(|((vector, &self_similarity), gap)| {
+                let centroid_gap =
+                    (self_similarity - similarity(distance, vector, centroid)).max(0.0);
+                *gap = gap.min(centroid_gap);
+            });
+
+        cumulative.clear();
+        let mut total = 0.0;
+        cumulative.extend(gaps.iter().map(|&gap| {
+            total += gap;
+            total
+        }));
+        selected = if total > 0.0 {
+            let threshold = rng.random_range(0.0..total);
+            cumulative
+                .partition_point(|&sum| sum <= threshold)
+                .min(num_vectors - 1)
+        } else {
+            rng.random_range(0..num_vectors)
+        };
+    }
+
+    Ok(centroids)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::*;
+
+    #[test]
+    fn test_kmeans_finds_separated_clusters() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 2;
+        let centers = [[-10.0, -10.0], [10.0, 10.0], [10.0, -10.0]];
+
+        let vectors: Vec<VectorElementType> = (0..300)
+            .flat_map(|i| {
+                let center = centers[i % centers.len()];
+                [
+                    center[0] + rng.random_range(-1.0..1.0),
+                    center[1] + rng.random_range(-1.0..1.0),
+                ]
+            })
+            .collect();
+
+        let stopped = AtomicBool::new(false);
+        let centroids = kmeans(&vectors, dim, 3, Distance::Euclid, 10, &mut rng, &stopped).unwrap();
+        assert_eq!(centroids.len(), 3 * dim);
+
+        for center in centers {
+            let (cluster, _) = nearest_centroid(&centroids, dim, &center, Distance::Euclid);
+            let centroid = &centroids[cluster * dim..(cluster + 1) * dim];
+            let error = -similarity(Distance::Euclid, centroid, &center);
+            assert!(error < 1.0, "centroid {centroid:?} is far from {center:?}");
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod config;
+pub mod inverted_lists;
+pub mod ivf;
+mod kmeans;
+mod product_quantizer;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,174 @@
+use std::ops::Range;
+use std::sync::atomic::AtomicBool;
+
+use common::types::ScoreType;
+use rand::Rng;
+
+use super::kmeans::{kmeans, nearest_centroid, similarity};
+use crate::common::operation_error::CancellableResult;
+use crate::data_types::vectors::VectorElementType;
+use crate::types::Distance;
+
+/// Number of centroids of each sub-vector, so that a sub-vector is encoded with a single byte.
+pub const PQ_CENTROIDS: usize = 256;
+
+/// Number of k-means iterations to train codebooks.
+const PQ_KMEANS_ITERATIONS: usize = 10;
+
+/// Product quantizer, which splits vectors into sub-vectors and encodes each of them with the
+/// closest centroid of its own codebook.
+///
+/// Codebooks are laid out by sub-vectors: the codebook of a sub-vector covering dimensions
+/// `start..end` holds [`PQ_CENTROIDS`] centroids of `end - start` elements, starting at
+/// `start * PQ_CENTROIDS`.
+#[derive(Debug)]
+pub struct ProductQuantizer {
+    dim: usize,
+    num_subvectors: usize,
+    codebooks: Vec<VectorElementType>,
+}
+
+impl ProductQuantizer {
+    pub fn new(dim: usize, num_subvectors: usize, codebooks: Vec<VectorElementType>) -> Self {
+        debug_assert_eq!(codebooks.len(), dim * PQ_CENTROIDS);
+        Self {
+            dim,
+            num_subvectors,
+            codebooks,
+        }
+    }
+
+    /// Train codebooks on `vectors`, laid out contiguously, `dim` elements each.
+    ///
+    /// Number of sub-vectors is capped by `dim`, sub-vectors may differ in size by one.
+    pub fn train<R: Rng + ?Sized>(
+        vectors: &[VectorElementType],
+        dim: usize,
+        num_subvectors: usize,
+        rng: &mut R,
+        stopped: &AtomicBool,
+    ) -> CancellableResult<Self> {
+        let num_subvectors = num_subvectors.clamp(1, dim);
+        let mut quantizer = Self::new(dim, num_subvectors, vec![0.0; dim * PQ_CENTROIDS]);
+
+        for subvector in 0..num_subvectors {
+            let range = quantizer.subvector_range(subvector);
+            let subvectors: Vec<VectorElementType> = vectors
+                .chunks_exact(dim)
+                .flat_map(|vector| &vector[range.clone()])
+                .copied()
+                .collect();
+            // Centroids are averages of sub-vectors, so they are trained with Euclid distance
+            let centroids = kmeans(
+                &subvectors,
+                range.len(),
+                PQ_CENTROIDS,
+                Distance::Euclid,
+                PQ_KMEANS_ITERATIONS,
+                rng,
+                stopped,
+            )?;
+            let codebook = quantizer.codebook_range(subvector);
+            quantizer.codebooks[codebook.start..codebook.start + centroids.len()]
+                .copy_from_slice(&centroids);
+        }
+
+        Ok(quantizer)
+    }
+
+    pub fn num_subvectors(&self) -> usize {
+        self.num_subvectors
+    }
+
+    pub fn codebooks(&self) -> &[VectorElementType] {
+        &self.codebooks
+    }
+
+    fn subvector_range(&self, subvector: usize) -> Range<usize> {
+        subvector * self.dim / self.num_subvectors..(subvector + 1) * self.dim / self.num_subvectors
+    }
+
+    fn codebook_range(&self, subvector: usize) -> Range<usize> {
+        let range = self.subvector_range(subvector);
+        range.start * PQ_CENTROIDS..range.end * PQ_CENTROIDS
+    }
+
+    /// Encode `vector` into `codes`, one byte per sub-vector.
+    pub fn encode(&self, vector: &[VectorElementType], codes: &mut [u8]) {
+        debug_assert_eq!(codes.len(), self.num_subvectors);
+        for (subvector, code) in codes.iter_mut().enumerate() {
+            let range = self.subvector_range(subvector);
+            let (centroid, _) = nearest_centroid(
+                &self.codebooks[self.codebook_range(subvector)],
+                range.len(),
+                &vector[range],
+                Distance::Euclid,
+            );
+            *code = centroid as u8;
+        }
+    }
+
+    /// Scores of all centroids of all codebooks against the corresponding sub-vectors of `query`.
+    ///
+    /// Scores are additive over sub-vectors for all distances: cosine query and vectors are
+    /// normalized, so that cosine similarity is the dot product.
+    pub fn lookup_table(&self, query: &[VectorElementType], distance: Distance) -> Vec<ScoreType> {
+        let distance = match distance {
+            Distance::Cosine => Distance::Dot,
+            distance => distance,
+        };
+        (0..self.num_subvectors)
+            .flat_map(|subvector| {
+                let range = self.subvector_range(subvector);
+                let query = &query[range.clone()];
+                self.codebooks[self.codebook_range(subvector)]
+                    .chunks_exact(range.len())
+                    .map(move |centroid| similarity(distance, query, centroid))
+            })
+            .collect()
+    }
+
+    /// Approximate score of an encoded vector, using the table from [`Self::lookup_table`].
+    pub fn score(lookup_table: &[ScoreType], codes: &[u8]) -> ScoreType {
+        codes
+            .iter()
+            .enumerate()
+            .map(|(subvector, &code)| lookup_table[subvector * PQ_CENTROIDS + code as usize])
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn test_product_quantizer_approximates_scores() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 9;
+        let num_subvectors = 4;
+        let vectors: Vec<VectorElementType> = (0..1000 * dim)
+            .map(|_| rng.random_range(-1.0..1.0))
+            .collect();
+
+        let stopped = AtomicBool::new(false);
+        let quantizer =
+            ProductQuantizer::train(&vectors, dim, num_subvectors, &mut rng, &stopped).unwrap();
+
+        let query: Vec<VectorElementType> = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let lookup_table = quantizer.lookup_table(&query, Distance::Dot);
+        let mut codes = vec![0; num_subvectors];
+        for vector in vectors.chunks_exact(dim).take(100) {
+            quantizer.encode(vector, &mut codes);
+            let approximate = ProductQuantizer::score(&lookup_table, &codes);
+            let exact = similarity(Distance::Dot, &query, vector);
+            assert!(
+                (approximate - exact).abs() < 1.0,
+                "approximate {approximate} is far from exact {exact}",
+            );
+        }
+    }
+}
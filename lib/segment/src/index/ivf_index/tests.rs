@@ -0,0 +1,142 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::flags::FeatureFlags;
+use common::progress_tracker::ProgressTracker;
+use common::types::ScoredPointOffset;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rstest::rstest;
+use tempfile::Builder;
+
+use super::ivf::{IvfIndex, IvfIndexOpenArgs};
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, QueryVector, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::VectorIndex;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{
+    Distance, HnswGlobalConfig, IvfConfig, IvfPqConfig, SearchParams, SeqNumberType,
+};
+
+fn recall(results: &[Vec<ScoredPointOffset>], exact_results: &[Vec<ScoredPointOffset>]) -> f64 {
+    let found: usize = results
+        .iter()
+        .zip(exact_results)
+        .map(|(result, exact_result)| {
+            result
+                .iter()
+                .filter(|scored| exact_result.iter().any(|exact| exact.idx == scored.idx))
+                .count()
+        })
+        .sum();
+    let total: usize = exact_results.iter().map(Vec::len).sum();
+    found as f64 / total as f64
+}
+
+#[rstest]
+#[case::flat(None, 1.0)]
+#[case::pq(Some(IvfPqConfig { num_subvectors: 8 }), 0.9)]
+fn test_ivf_search_recall(#[case] pq: Option<IvfPqConfig>, #[case] min_recall: f64) {
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+    let num_lists = 32;
+    let num_queries = 20;
+    let top = 10;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let ivf_dir = Builder::new().prefix("ivf_dir").tempdir().unwrap();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let ivf_config = IvfConfig {
+        num_lists: Some(num_lists),
+        nprobe: None,
+        pq,
+        full_scan_threshold: 0,
+        on_disk: Some(true),
+    };
+    let open_args = || IvfIndexOpenArgs {
+        path: ivf_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: segment.payload_index.clone(),
+        ivf_config,
+    };
+
+    let stopped = AtomicBool::new(false);
+    let index = IvfIndex::build(
+        open_args(),
+        VectorIndexBuildArgs {
+            permit: Arc::new(ResourcePermit::dummy(2)),
+            old_indices: &[],
+            gpu_device: None,
+            rng: &mut rng,
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+            progress: ProgressTracker::new_for_test(),
+        },
+    )
+    .unwrap();
+    assert_eq!(index.indexed_vector_count(), num_vectors as usize);
+
+    let queries: Vec<QueryVector> = (0..num_queries)
+        .map(|_| random_vector(&mut rng, dim).into())
+        .collect();
+    let queries: Vec<&QueryVector> = queries.iter().collect();
+
+    let query_context = VectorQueryContext::default();
+    let all_lists_params = SearchParams {
+        nprobe: Some(num_lists),
+        ..Default::default()
+    };
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+
+    let all_lists_results = index
+        .search(&queries, None, top, Some(&all_lists_params), &query_context)
+        .unwrap();
+    let exact_results = index
+        .search(&queries, None, top, Some(&exact_params), &query_context)
+        .unwrap();
+
+    // Probing all lists scans every point, only product quantization may lose some of them
+    let all_lists_recall = recall(&all_lists_results, &exact_results);
+    assert!(
+        all_lists_recall >= min_recall,
+        "recall {all_lists_recall} is too low",
+    );
+
+    // Reopened index reads the same lists from disk
+    drop(index);
+    let reopened = IvfIndex::open(open_args()).unwrap();
+    let reopened_results = reopened
+        .search(&queries, None, top, Some(&all_lists_params), &query_context)
+        .unwrap();
+    assert_eq!(reopened_results, all_lists_results);
+}
@@ -109,6 +109,15 @@ pub trait PayloadIndex {
         threshold: usize,
     ) -> Box<dyn Iterator<Item = OperationResult<PayloadBlockCondition>> + '_>;
 
+    /// Conditions for blocks of wider ranges of a numeric field, on top of
+    /// [`payload_blocks`](Self::payload_blocks). Empty if the field is not numeric.
+    /// Required for building HNSW index
+    fn coarse_range_blocks(
+        &self,
+        field: PayloadKeyTypeRef,
+        threshold: usize,
+    ) -> Vec<PayloadBlockCondition>;
+
     /// Overwrite payload for point_id. If payload already exists, replace it.
     fn overwrite_payload(
         &mut self,
@@ -202,6 +202,14 @@ impl PayloadIndex for PlainPayloadIndex {
         Box::new(std::iter::empty())
     }
 
+    fn coarse_range_blocks(
+        &self,
+        _field: PayloadKeyTypeRef,
+        _threshold: usize,
+    ) -> Vec<PayloadBlockCondition> {
+        vec![]
+    }
+
     fn overwrite_payload(
         &mut self,
         _point_id: PointOffsetType,
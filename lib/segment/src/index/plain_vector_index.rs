@@ -1,20 +1,24 @@
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 use atomic_refcell::AtomicRefCell;
 use common::counter::hardware_counter::HardwareCounterCell;
+use common::search_diagnostics::SearchStrategy;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use parking_lot::Mutex;
 
-use super::hnsw_index::point_scorer::BatchFilteredSearcher;
+use super::hnsw_index::appendable_graph::{AppendableGraph, appendable_hnsw_config};
+use super::hnsw_index::point_scorer::{BatchFilteredSearcher, FilteredScorer};
 use crate::common::BYTES_IN_KB;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationResult, check_process_stopped};
 use crate::common::operation_time_statistics::{
     OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
 };
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorRef};
 use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::exact_search::search_exact;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::vector_index_search_common::{
     get_oversampled_top, is_quantized_search, postprocess_search_result,
@@ -31,8 +35,11 @@ pub struct PlainVectorIndex {
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// HNSW graph of inserted vectors, only maintained in appendable segments
+    appendable_graph: Option<AppendableGraph>,
     filtered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
     unfiltered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_graph_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
 impl PlainVectorIndex {
@@ -47,11 +54,136 @@ impl PlainVectorIndex {
             vector_storage,
             quantized_vectors,
             payload_index,
+            appendable_graph: None,
             filtered_searches_telemetry: OperationDurationsAggregator::new(),
             unfiltered_searches_telemetry: OperationDurationsAggregator::new(),
+            unfiltered_graph_searches_telemetry: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Link stored vectors into an HNSW graph, which is then maintained on updates.
+    ///
+    /// Only for indexes of appendable segments. Does nothing, unless appendable HNSW is enabled.
+    pub fn init_appendable_graph(&mut self, stopped: &AtomicBool) -> OperationResult<()> {
+        let Some(config) = appendable_hnsw_config() else {
+            return Ok(());
+        };
+
+        let vector_storage = self.vector_storage.borrow();
+        let hw_counter = HardwareCounterCell::disposable();
+        let mut graph = AppendableGraph::new(config);
+        for point_id in 0..vector_storage.total_vector_count() as PointOffsetType {
+            check_process_stopped(stopped)?;
+            if !vector_storage.is_deleted_vector(point_id) {
+                graph.add_point(point_id, &vector_storage, &hw_counter)?;
+            }
+        }
+        drop(vector_storage);
+
+        self.appendable_graph = Some(graph);
+        Ok(())
+    }
+
+    /// Search with the appendable graph, if it can serve all queries.
+    ///
+    /// Vectors, which are not linked into the graph, are searched by full scan.
+    fn search_with_appendable_graph(
+        &self,
+        query_vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Option<Vec<Vec<ScoredPointOffset>>>> {
+        let Some(graph) = &self.appendable_graph else {
+            return Ok(None);
+        };
+        // Filtered searches would need payload links in the graph, discovery needs its own
+        // traversal, and exact searches are done by full scan anyway
+        if filter.is_some()
+            || params.is_some_and(|params| params.exact)
+            || query_vectors
+                .iter()
+                .any(|query| matches!(query, QueryVector::Discover(_)))
+        {
+            return Ok(None);
+        }
+
+        let is_stopped = query_context.is_stopped();
+
+        let _timer = ScopeDurationMeasurer::new(&self.unfiltered_graph_searches_telemetry);
+        query_context
+            .hardware_counter()
+            .search_diagnostics()
+            .add_searches(SearchStrategy::Graph, query_vectors.len());
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_storage = self.quantized_vectors.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let quantization_enabled = is_quantized_search(quantized_storage.as_ref(), params);
+        let quantized_vectors = quantization_enabled
+            .then_some(quantized_storage.as_ref())
+            .flatten();
+        let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
+        let ef = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or_else(|| graph.ef());
+        let deferred_internal_id = query_context
+            .deferred_internal_id()
+            .unwrap_or(PointOffsetType::MAX);
+
+        let unlinked_points = graph
+            .unlinked_points(vector_storage.total_vector_count())
+            .take_while(|&point_id| point_id < deferred_internal_id);
+        let unlinked_results = BatchFilteredSearcher::new(
+            query_vectors,
+            &vector_storage,
+            quantized_vectors,
+            None,
+            oversampled_top,
+            deleted_points,
+            query_context.hardware_counter(),
+        )?
+        .peek_top_iter(unlinked_points, &is_stopped)?;
+
+        let search_results = query_vectors
+            .iter()
+            .zip(unlinked_results)
+            .map(|(&query_vector, mut search_result)| {
+                let points_scorer = FilteredScorer::new(
+                    query_vector.to_owned(),
+                    &vector_storage,
+                    quantized_vectors,
+                    None,
+                    deleted_points,
+                    query_context.hardware_counter(),
+                )?;
+                let graph_result = graph.search(oversampled_top, ef, points_scorer, &is_stopped)?;
+                search_result.extend(
+                    graph_result
+                        .into_iter()
+                        .filter(|scored_point| scored_point.idx < deferred_internal_id),
+                );
+                search_result.sort_unstable();
+                search_result.reverse();
+
+                postprocess_search_result(
+                    search_result,
+                    deleted_points,
+                    &vector_storage,
+                    quantized_storage.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    query_context.hardware_counter(),
+                )
+            })
+            .collect::<OperationResult<_>>()?;
+        Ok(Some(search_results))
+    }
+
     pub fn is_small_enough_for_unindexed_search(
         &self,
         search_optimized_threshold_kb: usize,
@@ -103,9 +235,18 @@ impl VectorIndex for PlainVectorIndex {
             return Ok(vec![vec![]; query_vectors.len()]);
         }
 
+        if let Some(search_results) =
+            self.search_with_appendable_graph(query_vectors, filter, top, params, query_context)?
+        {
+            return Ok(search_results);
+        }
+
         let is_stopped = query_context.is_stopped();
 
         let hw_counter = query_context.hardware_counter();
+        hw_counter
+            .search_diagnostics()
+            .add_searches(SearchStrategy::PlainNotIndexed, query_vectors.len());
 
         let _timer = ScopeDurationMeasurer::new(if filter.is_some() {
             &self.filtered_searches_telemetry
@@ -123,30 +264,55 @@ impl VectorIndex for PlainVectorIndex {
             .then_some(quantized_storage.as_ref())
             .flatten();
         let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
-        let batch_searcher = BatchFilteredSearcher::new(
-            query_vectors,
-            &vector_storage,
-            quantized_vectors,
-            None,
-            oversampled_top,
-            deleted_points,
-            query_context.hardware_counter(),
-        )?;
-
         let deferred_internal_id = query_context.deferred_internal_id();
 
-        let mut search_results = match filter {
-            Some(filter) => {
-                let payload_index = self.payload_index.borrow();
-                let filtered_ids_vec = payload_index.query_points(
+        let filtered_ids_vec = filter
+            .map(|filter| {
+                self.payload_index.borrow().query_points(
                     filter,
                     &hw_counter,
                     &is_stopped,
                     deferred_internal_id,
-                )?;
-                batch_searcher.peek_top_iter(filtered_ids_vec.iter().copied(), &is_stopped)?
+                )
+            })
+            .transpose()?;
+
+        let mut search_results = if params.is_some_and(|params| params.exact) {
+            // Exact search scores every point, split them between threads
+            let points = filtered_ids_vec.unwrap_or_else(|| {
+                deleted_points
+                    .iter_zeros()
+                    .map(|point_id| point_id as PointOffsetType)
+                    .take_while(|&point_id| {
+                        point_id < deferred_internal_id.unwrap_or(PointOffsetType::MAX)
+                    })
+                    .collect()
+            });
+            search_exact(
+                query_vectors,
+                &vector_storage,
+                &points,
+                oversampled_top,
+                deleted_points,
+                query_context.hardware_counter(),
+                &is_stopped,
+            )?
+        } else {
+            let batch_searcher = BatchFilteredSearcher::new(
+                query_vectors,
+                &vector_storage,
+                quantized_vectors,
+                None,
+                oversampled_top,
+                deleted_points,
+                query_context.hardware_counter(),
+            )?;
+            match filtered_ids_vec {
+                Some(filtered_ids_vec) => {
+                    batch_searcher.peek_top_iter(filtered_ids_vec.into_iter(), &is_stopped)?
+                }
+                None => batch_searcher.peek_top_all(&is_stopped, deferred_internal_id)?,
             }
-            None => batch_searcher.peek_top_all(&is_stopped, deferred_internal_id)?,
         };
 
         for (search_result, query_vector) in search_results.iter_mut().zip(query_vectors) {
@@ -175,7 +341,10 @@ impl VectorIndex for PlainVectorIndex {
                 .filtered_searches_telemetry
                 .lock()
                 .get_statistics(detail),
-            unfiltered_hnsw: OperationDurationStatistics::default(),
+            unfiltered_hnsw: self
+                .unfiltered_graph_searches_telemetry
+                .lock()
+                .get_statistics(detail),
             filtered_small_cardinality: OperationDurationStatistics::default(),
             filtered_large_cardinality: OperationDurationStatistics::default(),
             filtered_exact: OperationDurationStatistics::default(),
@@ -214,6 +383,10 @@ impl VectorIndex for PlainVectorIndex {
             if let Some(quantized_vectors) = quantized_vectors.as_mut() {
                 quantized_vectors.upsert_vector(id, vector, hw_counter)?;
             }
+
+            if let Some(appendable_graph) = &mut self.appendable_graph {
+                appendable_graph.add_point(id, &vector_storage, hw_counter)?;
+            }
         } else {
             if id as usize >= vector_storage.total_vector_count() {
                 debug_assert!(id as usize == vector_storage.total_vector_count());
@@ -24,8 +24,8 @@ pub fn get_match_checkers(
         Match::TextAny(MatchTextAny { text_any }) => {
             get_match_text_checker(text_any, TextQueryType::TextAny, index, hw_acc)
         }
-        Match::Phrase(MatchPhrase { phrase }) => {
-            get_match_text_checker(phrase, TextQueryType::Phrase, index, hw_acc)
+        Match::Phrase(MatchPhrase { phrase, slop }) => {
+            get_match_text_checker(phrase, TextQueryType::Phrase { slop }, index, hw_acc)
         }
         Match::Any(MatchAny { any }) => get_match_any_checker(any, index, hw_acc),
         Match::Except(MatchExcept { except }) => get_match_except_checker(except, index, hw_acc),
@@ -256,7 +256,7 @@ fn get_match_except_checker(
 }
 
 enum TextQueryType {
-    Phrase,
+    Phrase { slop: Option<u32> },
     Text,
     TextAny,
 }
@@ -271,7 +271,9 @@ fn get_match_text_checker(
     match index {
         FieldIndex::FullTextIndex(full_text_index) => {
             let query_opt = match query_type {
-                TextQueryType::Phrase => full_text_index.parse_phrase_query(&text, &hw_counter),
+                TextQueryType::Phrase { slop } => {
+                    full_text_index.parse_phrase_query(&text, slop, &hw_counter)
+                }
                 TextQueryType::Text => full_text_index.parse_text_query(&text, &hw_counter),
                 TextQueryType::TextAny => full_text_index.parse_text_any_query(&text, &hw_counter),
             };
@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+use common::fs::{atomic_save_bin, read_bin};
+use common::types::PointOffsetType;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+
+use crate::common::operation_error::OperationResult;
+
+const DOC_LENGTHS_FILE_NAME: &str = "doc_lengths.bin";
+
+/// Lengths of the indexed sparse vectors, required for BM25 document length normalization.
+///
+/// Length of a vector is the sum of its values, i.e. the number of terms for term frequencies.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct DocLengths {
+    lengths: Vec<f32>,
+    total: f64,
+}
+
+impl DocLengths {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let path = Self::file_path(path);
+        Ok(read_bin(&path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        let path = Self::file_path(path);
+        Ok(atomic_save_bin(&path, self)?)
+    }
+
+    pub fn file_path(path: &Path) -> PathBuf {
+        path.join(DOC_LENGTHS_FILE_NAME)
+    }
+
+    /// Set the length of the vector `id`, `None` if the vector is removed from the index
+    pub fn set(&mut self, id: PointOffsetType, vector: Option<&SparseVector>) {
+        let length = vector.map_or(0.0, |vector| vector.values.iter().sum());
+
+        let id = id as usize;
+        if id >= self.lengths.len() {
+            if length == 0.0 {
+                return;
+            }
+            self.lengths.resize(id + 1, 0.0);
+        }
+        self.total += f64::from(length) - f64::from(self.lengths[id]);
+        self.lengths[id] = length;
+    }
+
+    /// Lengths of the vectors by point offset
+    pub fn lengths(&self) -> &[f32] {
+        &self.lengths
+    }
+
+    /// Sum of the lengths of all indexed vectors
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+}
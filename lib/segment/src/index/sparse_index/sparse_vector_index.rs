@@ -1,10 +1,10 @@
-use std::borrow::Cow;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use atomic_refcell::AtomicRefCell;
+use common::bitvec::BitSliceExt as _;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::generic_consts::Random;
 use common::storage_version::{StorageVersion as _, VERSION_FILE};
@@ -15,10 +15,13 @@ use semver::Version;
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::SparseVector;
 use sparse::common::types::DimId;
-use sparse::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
+use sparse::index::inverted_index::inverted_index_spilling_builder::{
+    DEFAULT_SPILL_MEMORY_BUDGET, InvertedIndexSpillingBuilder,
+};
 use sparse::index::inverted_index::{INDEX_FILE_NAME, InvertedIndex, OLD_INDEX_FILE_NAME};
 use sparse::index::search_context::SearchContext;
 
+use super::doc_lengths::DocLengths;
 use super::indices_tracker::IndicesTracker;
 use super::sparse_index_config::SparseIndexType;
 use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
@@ -52,6 +55,7 @@ pub struct SparseVectorIndex<TInvertedIndex: InvertedIndex> {
     inverted_index: TInvertedIndex,
     searches_telemetry: SparseSearchesTelemetry,
     indices_tracker: IndicesTracker,
+    doc_lengths: DocLengths,
     scores_memory_pool: ScoresMemoryPool,
     deferred_internal_id: Option<PointOffsetType>,
 }
@@ -107,50 +111,53 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
 
         let config_path = SparseIndexConfig::get_config_path(path);
 
-        let (config, inverted_index, indices_tracker) = if !config.index_type.is_persisted() {
-            // RAM mutable case - build inverted index from scratch and use provided config
-            fs::create_dir_all(path)?;
-            let (inverted_index, indices_tracker) = Self::build_inverted_index(
-                &id_tracker,
-                &vector_storage,
-                path,
-                stopped,
-                tick_progress,
-            )?;
-            (config, inverted_index, indices_tracker)
-        } else {
-            Self::try_load(path).or_else(|e| {
-                if fs::exists(path).unwrap_or(true) {
-                    log::warn!("Failed to load {path:?}, rebuilding: {e}");
-
-                    // Drop index completely.
-                    fs::remove_dir_all(path)?;
-                }
-
+        let (config, inverted_index, indices_tracker, doc_lengths) =
+            if !config.index_type.is_persisted() {
+                // RAM mutable case - build inverted index from scratch and use provided config
                 fs::create_dir_all(path)?;
-
-                let (inverted_index, indices_tracker) = Self::build_inverted_index(
+                let (inverted_index, indices_tracker, doc_lengths) = Self::build_inverted_index(
                     &id_tracker,
                     &vector_storage,
                     path,
                     stopped,
                     tick_progress,
                 )?;
+                (config, inverted_index, indices_tracker, doc_lengths)
+            } else {
+                Self::try_load(path, &id_tracker, &vector_storage, stopped).or_else(|e| {
+                    if fs::exists(path).unwrap_or(true) {
+                        log::warn!("Failed to load {path:?}, rebuilding: {e}");
 
-                config.save(&config_path)?;
-                inverted_index.save(path)?;
-                indices_tracker.save(path)?;
+                        // Drop index completely.
+                        fs::remove_dir_all(path)?;
+                    }
 
-                // Save the version as the last step to mark a successful rebuild.
-                // NOTE: index in the original format (Qdrant <=v1.9 / sparse <=v0.1.0) lacks of the
-                // version file. To distinguish between index in original format and partially
-                // written index in the current format, the index file name is changed from
-                // `inverted_index.data` to `inverted_index.dat`.
-                TInvertedIndex::Version::save(path)?;
+                    fs::create_dir_all(path)?;
 
-                OperationResult::Ok((config, inverted_index, indices_tracker))
-            })?
-        };
+                    let (inverted_index, indices_tracker, doc_lengths) =
+                        Self::build_inverted_index(
+                            &id_tracker,
+                            &vector_storage,
+                            path,
+                            stopped,
+                            tick_progress,
+                        )?;
+
+                    config.save(&config_path)?;
+                    inverted_index.save(path)?;
+                    indices_tracker.save(path)?;
+                    doc_lengths.save(path)?;
+
+                    // Save the version as the last step to mark a successful rebuild.
+                    // NOTE: index in the original format (Qdrant <=v1.9 / sparse <=v0.1.0) lacks of the
+                    // version file. To distinguish between index in original format and partially
+                    // written index in the current format, the index file name is changed from
+                    // `inverted_index.data` to `inverted_index.dat`.
+                    TInvertedIndex::Version::save(path)?;
+
+                    OperationResult::Ok((config, inverted_index, indices_tracker, doc_lengths))
+                })?
+            };
 
         let searches_telemetry = SparseSearchesTelemetry::new();
         let path = path.to_path_buf();
@@ -164,6 +171,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             inverted_index,
             searches_telemetry,
             indices_tracker,
+            doc_lengths,
             scores_memory_pool,
             deferred_internal_id,
         })
@@ -171,7 +179,15 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
 
     fn try_load(
         path: &Path,
-    ) -> OperationResult<(SparseIndexConfig, TInvertedIndex, IndicesTracker)> {
+        id_tracker: &AtomicRefCell<IdTrackerEnum>,
+        vector_storage: &AtomicRefCell<VectorStorageEnum>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<(
+        SparseIndexConfig,
+        TInvertedIndex,
+        IndicesTracker,
+        DocLengths,
+    )> {
         let mut stored_version = TInvertedIndex::Version::load(path)?;
 
         // Simple migration mechanism for 0.1.0.
@@ -194,7 +210,40 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         let loaded_config = SparseIndexConfig::load(&SparseIndexConfig::get_config_path(path))?;
         let inverted_index = TInvertedIndex::open(path)?;
         let indices_tracker = IndicesTracker::open(path)?;
-        Ok((loaded_config, inverted_index, indices_tracker))
+        let doc_lengths = if fs::exists(DocLengths::file_path(path))? {
+            DocLengths::open(path)?
+        } else {
+            // Indexes built before BM25 support have no document lengths, compute them once
+            // instead of rebuilding the whole index
+            let doc_lengths = Self::compute_doc_lengths(id_tracker, vector_storage, stopped)?;
+            doc_lengths.save(path)?;
+            doc_lengths
+        };
+        Ok((loaded_config, inverted_index, indices_tracker, doc_lengths))
+    }
+
+    fn compute_doc_lengths(
+        id_tracker: &AtomicRefCell<IdTrackerEnum>,
+        vector_storage: &AtomicRefCell<VectorStorageEnum>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<DocLengths> {
+        let borrowed_vector_storage = vector_storage.borrow();
+        let borrowed_id_tracker = id_tracker.borrow();
+        let deleted_bitslice = borrowed_vector_storage.deleted_vector_bitslice();
+
+        let mut doc_lengths = DocLengths::default();
+        for id in borrowed_id_tracker
+            .point_mappings()
+            .iter_internal_excluding(deleted_bitslice)
+        {
+            check_process_stopped(stopped)?;
+            // Vectors lost in a crash are recovered by the WAL, same as in `build_inverted_index`
+            if let Some(vector) = borrowed_vector_storage.get_vector_opt::<Random>(id) {
+                let vector: &SparseVector = vector.as_vec_ref().try_into()?;
+                doc_lengths.set(id, Some(vector));
+            }
+        }
+        Ok(doc_lengths)
     }
 
     fn build_inverted_index(
@@ -203,13 +252,15 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         path: &Path,
         stopped: &AtomicBool,
         mut tick_progress: impl FnMut(),
-    ) -> OperationResult<(TInvertedIndex, IndicesTracker)> {
+    ) -> OperationResult<(TInvertedIndex, IndicesTracker, DocLengths)> {
         let borrowed_vector_storage = vector_storage.borrow();
         let borrowed_id_tracker = id_tracker.borrow();
         let deleted_bitslice = borrowed_vector_storage.deleted_vector_bitslice();
 
-        let mut ram_index_builder = InvertedIndexBuilder::new();
+        let mut index_builder =
+            InvertedIndexSpillingBuilder::new(path, DEFAULT_SPILL_MEMORY_BUDGET)?;
         let mut indices_tracker = IndicesTracker::default();
+        let mut doc_lengths = DocLengths::default();
         for id in borrowed_id_tracker
             .point_mappings()
             .iter_internal_excluding(deleted_bitslice)
@@ -235,15 +286,17 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                         continue;
                     }
                     indices_tracker.register_indices(vector);
+                    doc_lengths.set(id, Some(vector));
                     let vector = indices_tracker.remap_vector(vector.to_owned());
-                    ram_index_builder.add(id, vector);
+                    index_builder.add(id, vector)?;
                 }
             }
             tick_progress();
         }
         Ok((
-            TInvertedIndex::from_ram_index(Cow::Owned(ram_index_builder.build()), path)?,
+            TInvertedIndex::from_spilling_builder(index_builder, path)?,
             indices_tracker,
+            doc_lengths,
         ))
     }
 
@@ -403,6 +456,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             &is_stopped,
             &hw_counter,
         );
+        if let Some(bm25) = vector_query_context.bm25() {
+            search_context = search_context.with_bm25(bm25, self.doc_lengths.lengths());
+        }
         let search_result = search_context.plain_search(&ids);
         Ok(search_result)
     }
@@ -413,6 +469,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         sparse_vector: &SparseVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let vector_storage = self.vector_storage.borrow();
@@ -445,7 +502,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             memory_handle,
             &is_stopped,
             &hw_counter,
-        );
+        )
+        .with_max_posting_length(params.and_then(|params| params.sparse_max_posting_length));
+        if let Some(bm25) = vector_query_context.bm25() {
+            search_context = search_context.with_bm25(bm25, self.doc_lengths.lengths());
+        }
 
         match filter {
             Some(filter) => {
@@ -465,6 +526,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         vector: &SparseVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -494,12 +556,12 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse);
-                    self.search_sparse(vector, Some(filter), top, vector_query_context)
+                    self.search_sparse(vector, Some(filter), top, params, vector_query_context)
                 }
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
-                self.search_sparse(vector, filter, top, vector_query_context)
+                self.search_sparse(vector, filter, top, params, vector_query_context)
             }
         }
     }
@@ -509,6 +571,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         query_vector: &QueryVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -521,6 +584,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 vector.try_into()?,
                 filter,
                 top,
+                params,
                 prefiltered_points,
                 vector_query_context,
             ),
@@ -561,6 +625,60 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             }
         }
     }
+
+    /// Sum of the lengths of the indexed vectors, for BM25 document length normalization
+    pub fn total_doc_length(&self) -> f64 {
+        self.doc_lengths.total()
+    }
+
+    /// Fraction of vectors in the inverted index, which were deleted since the index was built or
+    /// compacted.
+    ///
+    /// Estimated from the counts of available points and vectors, as empty vectors are not
+    /// indexed.
+    pub fn deleted_indexed_ratio(&self) -> f64 {
+        let indexed_vector_count = self.inverted_index.vector_count();
+        let available_count = std::cmp::min(
+            self.id_tracker.borrow().available_point_count(),
+            self.vector_storage.borrow().available_vector_count(),
+        );
+        let deleted_vector_count = indexed_vector_count.saturating_sub(available_count);
+        if indexed_vector_count != 0 {
+            deleted_vector_count as f64 / indexed_vector_count as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Remove deleted points and vectors from the posting lists of the inverted index, see
+    /// [`InvertedIndex::compact`].
+    ///
+    /// Returns the number of removed posting elements.
+    pub fn compact(&mut self) -> OperationResult<usize> {
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let deleted_points = id_tracker.deleted_point_bitslice();
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+        let is_deleted = |id: PointOffsetType| {
+            deleted_points.get_bit(id as usize).unwrap_or(false)
+                || deleted_vectors.get_bit(id as usize).unwrap_or(false)
+        };
+
+        let removed_elements = self.inverted_index.compact(is_deleted)?;
+        if removed_elements == 0 {
+            return Ok(0);
+        }
+
+        // Removed vectors must not count into the average document length anymore
+        for id in 0..self.doc_lengths.lengths().len() as PointOffsetType {
+            if is_deleted(id) {
+                self.doc_lengths.set(id, None);
+            }
+        }
+        self.doc_lengths.save(&self.path)?;
+
+        Ok(removed_elements)
+    }
 }
 
 impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedIndex> {
@@ -569,7 +687,7 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
-        _params: Option<&SearchParams>,
+        params: Option<&SearchParams>,
         query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let mut results = Vec::with_capacity(vectors.len());
@@ -598,9 +716,23 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
                     Ok(vector)
                 })?;
 
-                self.search_query(&vector, filter, top, &mut prefiltered_points, query_context)?
+                self.search_query(
+                    &vector,
+                    filter,
+                    top,
+                    params,
+                    &mut prefiltered_points,
+                    query_context,
+                )?
             } else {
-                self.search_query(vector, filter, top, &mut prefiltered_points, query_context)?
+                self.search_query(
+                    vector,
+                    filter,
+                    top,
+                    params,
+                    &mut prefiltered_points,
+                    query_context,
+                )?
             };
 
             results.push(search_results);
@@ -620,6 +752,7 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
 
         let mut all_files = vec![
             IndicesTracker::file_path(&self.path),
+            DocLengths::file_path(&self.path),
             self.path.join(VERSION_FILE),
         ];
         all_files.retain(|f| f.exists());
@@ -705,10 +838,12 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
         // do not upsert empty or deferred vectors into the index
         if !vector.is_empty() {
             self.indices_tracker.register_indices(&vector);
+            self.doc_lengths.set(id, Some(&vector));
             let vector = self.indices_tracker.remap_vector(vector);
             let old_vector = old_vector.map(|v| self.indices_tracker.remap_vector(v));
             self.inverted_index.upsert(id, vector, old_vector);
         } else if let Some(old_vector) = old_vector {
+            self.doc_lengths.set(id, None);
             // Make sure empty vectors do not interfere with the index
             if !old_vector.is_empty() {
                 let old_vector = self.indices_tracker.remap_vector(old_vector);
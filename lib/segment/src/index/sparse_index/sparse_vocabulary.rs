@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use common::mmap_hashmap::MmapHashMap;
+use fs_err as fs;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::VectorName;
+
+/// Sparse vector with dimensions keyed by token strings instead of dimension ids.
+///
+/// Converted into a regular [`SparseVector`] with the [`SparseVocabulary`] of the vector.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TokenSparseVector {
+    /// Tokens of the non-zero dimensions
+    pub tokens: Vec<String>,
+    /// Values of the non-zero dimensions
+    pub values: Vec<DimWeight>,
+}
+
+/// How to handle tokens, which are not present in the vocabulary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownTokens {
+    /// Fail the conversion, used for inserted vectors which must not lose dimensions
+    Error,
+    /// Drop the token, used for queries as the token can't match any stored dimension
+    Skip,
+}
+
+/// Mapping of token strings to dimension ids of a sparse vector.
+///
+/// Mappings are registered by the user and are never changed or removed afterwards, so vectors
+/// remapped at different times stay comparable. Vocabulary is stored as an immutable mmap hashmap,
+/// which is rewritten on every registration.
+///
+/// Most sparse vectors have no vocabulary, so the file is only created with the first mapping.
+#[derive(Debug)]
+pub struct SparseVocabulary {
+    path: PathBuf,
+    vocab: Option<MmapHashMap<str, DimId>>,
+}
+
+impl SparseVocabulary {
+    /// Open vocabulary of the sparse vector `vector_name` in the segment, if it exists
+    pub fn open(segment_path: &Path, vector_name: &VectorName) -> OperationResult<Self> {
+        let path = vocabulary_path(segment_path, vector_name);
+        let vocab = if path.exists() {
+            Some(MmapHashMap::open(&path, false)?)
+        } else {
+            None
+        };
+        Ok(Self { path, vocab })
+    }
+
+    pub fn len(&self) -> usize {
+        self.vocab.as_ref().map_or(0, |vocab| vocab.keys_count())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Dimension id of the `token`
+    pub fn get(&self, token: &str) -> OperationResult<Option<DimId>> {
+        let Some(vocab) = &self.vocab else {
+            return Ok(None);
+        };
+        Ok(vocab.get(token)?.and_then(|ids| ids.first().copied()))
+    }
+
+    /// Iterate over all registered tokens and their dimension ids
+    pub fn iter(&self) -> impl Iterator<Item = (&str, DimId)> {
+        self.vocab.iter().flat_map(|vocab| {
+            vocab
+                .iter()
+                .filter_map(|(token, ids)| Some((token, *ids.first()?)))
+        })
+    }
+
+    /// Register new token mappings.
+    ///
+    /// Tokens which are already registered with the same id are ignored. Registering an existing
+    /// token with a different id is an error, as it would change the meaning of stored vectors.
+    ///
+    /// Returns `true` if any new token was registered.
+    pub fn register<'a>(
+        &mut self,
+        mapping: impl IntoIterator<Item = (&'a str, DimId)>,
+    ) -> OperationResult<bool> {
+        let mut merged: BTreeMap<String, DimId> = self
+            .iter()
+            .map(|(token, dim_id)| (token.to_owned(), dim_id))
+            .collect();
+
+        let mut changed = false;
+        for (token, dim_id) in mapping {
+            match merged.get(token) {
+                Some(&existing) if existing != dim_id => {
+                    return Err(OperationError::validation_error(format!(
+                        "token {token:?} is already mapped to dimension {existing}, can't map it to {dim_id}",
+                    )));
+                }
+                Some(_) => {}
+                None => {
+                    merged.insert(token.to_owned(), dim_id);
+                    changed = true;
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(false);
+        }
+
+        // Write into a temporary file first, so the vocabulary is replaced atomically
+        let tmp_path = self.path.with_extension("tmp");
+        MmapHashMap::<str, DimId>::create(
+            &tmp_path,
+            merged
+                .iter()
+                .map(|(token, dim_id)| (token.as_str(), std::iter::once(*dim_id))),
+        )?;
+
+        self.vocab = None;
+        fs::rename(&tmp_path, &self.path)?;
+        self.vocab = Some(MmapHashMap::open(&self.path, false)?);
+
+        Ok(true)
+    }
+
+    /// Convert token keyed vector into a sparse vector with dimension ids.
+    ///
+    /// Values of tokens mapped to the same dimension are summed up.
+    pub fn remap(
+        &self,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector> {
+        let TokenSparseVector { tokens, values } = vector;
+
+        if tokens.len() != values.len() {
+            return Err(OperationError::validation_error(format!(
+                "tokens and values must be the same length, got {} tokens and {} values",
+                tokens.len(),
+                values.len(),
+            )));
+        }
+
+        let mut dimensions = BTreeMap::<DimId, DimWeight>::new();
+        for (token, value) in tokens.iter().zip(values) {
+            match (self.get(token)?, unknown_tokens) {
+                (Some(dim_id), _) => *dimensions.entry(dim_id).or_default() += value,
+                (None, UnknownTokens::Skip) => {}
+                (None, UnknownTokens::Error) => {
+                    return Err(OperationError::validation_error(format!(
+                        "token {token:?} is not registered in the sparse vocabulary",
+                    )));
+                }
+            }
+        }
+
+        let (indices, values) = dimensions.into_iter().unzip();
+        Ok(SparseVector { indices, values })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        if self.vocab.is_some() {
+            vec![self.path.clone()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+pub fn vocabulary_path(segment_path: &Path, vector_name: &VectorName) -> PathBuf {
+    segment_path.join(format!("sparse_vocabulary-{vector_name}.dat"))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn token_vector(tokens: &[&str], values: &[DimWeight]) -> TokenSparseVector {
+        TokenSparseVector {
+            tokens: tokens.iter().map(|token| (*token).to_owned()).collect(),
+            values: values.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_register_and_reopen() {
+        let dir = Builder::new()
+            .prefix("sparse_vocabulary")
+            .tempdir()
+            .unwrap();
+
+        let mut vocabulary = SparseVocabulary::open(dir.path(), "text").unwrap();
+        assert!(vocabulary.is_empty());
+        assert!(vocabulary.files().is_empty());
+
+        assert!(vocabulary.register([("cat", 1), ("dog", 2)]).unwrap());
+        // Same mappings again are a no-op
+        assert!(!vocabulary.register([("cat", 1)]).unwrap());
+        assert!(vocabulary.register([("dog", 2), ("fish", 7)]).unwrap());
+
+        // Existing tokens can't be remapped
+        assert!(vocabulary.register([("cat", 3)]).is_err());
+
+        let vocabulary = SparseVocabulary::open(dir.path(), "text").unwrap();
+        assert_eq!(vocabulary.len(), 3);
+        assert_eq!(vocabulary.get("cat").unwrap(), Some(1));
+        assert_eq!(vocabulary.get("fish").unwrap(), Some(7));
+        assert_eq!(vocabulary.get("bird").unwrap(), None);
+        assert_eq!(vocabulary.files().len(), 1);
+
+        // Vocabularies of other vectors are independent
+        let other = SparseVocabulary::open(dir.path(), "other").unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn test_remap() {
+        let dir = Builder::new()
+            .prefix("sparse_vocabulary")
+            .tempdir()
+            .unwrap();
+
+        let mut vocabulary = SparseVocabulary::open(dir.path(), "text").unwrap();
+        vocabulary
+            .register([("cat", 10), ("kitten", 10), ("dog", 2)])
+            .unwrap();
+
+        let vector = token_vector(&["kitten", "dog", "cat"], &[1.0, 2.0, 0.5]);
+        let remapped = vocabulary.remap(&vector, UnknownTokens::Error).unwrap();
+        assert_eq!(remapped.indices, vec![2, 10]);
+        assert_eq!(remapped.values, vec![2.0, 1.5]);
+
+        let vector = token_vector(&["cat", "bird"], &[1.0, 2.0]);
+        assert!(vocabulary.remap(&vector, UnknownTokens::Error).is_err());
+        let remapped = vocabulary.remap(&vector, UnknownTokens::Skip).unwrap();
+        assert_eq!(remapped.indices, vec![10]);
+        assert_eq!(remapped.values, vec![1.0]);
+
+        let vector = token_vector(&["cat"], &[1.0, 2.0]);
+        assert!(vocabulary.remap(&vector, UnknownTokens::Skip).is_err());
+    }
+}
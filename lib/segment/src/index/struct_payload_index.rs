@@ -1035,6 +1035,19 @@ impl PayloadIndex for StructPayloadIndex {
         }
     }
 
+    fn coarse_range_blocks(
+        &self,
+        field: PayloadKeyTypeRef,
+        threshold: usize,
+    ) -> Vec<PayloadBlockCondition> {
+        self.field_indexes
+            .get(field)
+            .into_iter()
+            .flatten()
+            .flat_map(|field_index| field_index.coarse_range_blocks(threshold, field.to_owned()))
+            .collect()
+    }
+
     fn overwrite_payload(
         &mut self,
         point_id: PointOffsetType,
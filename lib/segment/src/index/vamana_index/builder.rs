@@ -0,0 +1,178 @@
+use std::sync::atomic::AtomicBool;
+
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
+use parking_lot::RwLock;
+
+use super::graph::beam_search;
+use crate::common::operation_error::CancellableResult;
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::visited_pool::VisitedPool;
+use crate::types::Distance;
+
+/// Beam width used while building the graph. Links are in RAM, so there is no I/O to batch.
+const BUILD_BEAM_WIDTH: usize = 1;
+
+/// Vamana graph, which is being built in RAM.
+pub(super) struct VamanaGraphBuilder {
+    max_degree: usize,
+    list_size: usize,
+    distance: Distance,
+    entry_point: PointOffsetType,
+    links: Vec<RwLock<Vec<PointOffsetType>>>,
+    visited_pool: VisitedPool,
+}
+
+impl VamanaGraphBuilder {
+    pub fn new(
+        num_points: usize,
+        max_degree: usize,
+        list_size: usize,
+        distance: Distance,
+        entry_point: PointOffsetType,
+    ) -> Self {
+        Self {
+            max_degree,
+            list_size,
+            distance,
+            entry_point,
+            links: std::iter::repeat_with(|| RwLock::new(Vec::with_capacity(max_degree)))
+                .take(num_points)
+                .collect(),
+            visited_pool: VisitedPool::new(),
+        }
+    }
+
+    /// Search neighbors of the point in the current graph, prune them with `alpha` and link the
+    /// point to them in both directions.
+    ///
+    /// Larger `alpha` keeps more long-range links, which shortens search paths.
+    pub fn link_point(
+        &self,
+        point_id: PointOffsetType,
+        alpha: f32,
+        mut points_scorer: FilteredScorer,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<()> {
+        let mut candidates = if point_id == self.entry_point {
+            Vec::new()
+        } else {
+            beam_search(
+                &self.visited_pool,
+                self.links.len(),
+                self.entry_point,
+                self.list_size,
+                BUILD_BEAM_WIDTH,
+                &mut points_scorer,
+                |link_point_id, links| {
+                    links.extend_from_slice(&self.links[link_point_id as usize].read())
+                },
+                |_| {},
+                is_stopped,
+            )?
+        };
+
+        let current_links = self.links[point_id as usize].read().clone();
+        candidates.extend(current_links.into_iter().map(|idx| ScoredPointOffset {
+            idx,
+            score: points_scorer.score_point(idx),
+        }));
+        candidates.retain(|candidate| {
+            candidate.idx != point_id && points_scorer.filters().check_vector(candidate.idx)
+        });
+
+        let new_links =
+            self.robust_prune(candidates, alpha, |a, b| points_scorer.score_internal(a, b));
+        self.links[point_id as usize].write().clone_from(&new_links);
+
+        for neighbor in new_links {
+            let mut neighbor_links = self.links[neighbor as usize].write();
+            if neighbor_links.contains(&point_id) {
+                continue;
+            }
+            if neighbor_links.len() < self.max_degree {
+                neighbor_links.push(point_id);
+                continue;
+            }
+
+            let candidates = neighbor_links
+                .iter()
+                .copied()
+                .chain(std::iter::once(point_id))
+                .map(|idx| ScoredPointOffset {
+                    idx,
+                    score: points_scorer.score_internal(neighbor, idx),
+                })
+                .collect();
+            *neighbor_links =
+                self.robust_prune(candidates, alpha, |a, b| points_scorer.score_internal(a, b));
+        }
+
+        Ok(())
+    }
+
+    /// Select up to `max_degree` links out of `candidates`, scored against the linked point.
+    ///
+    /// A candidate is dropped if an already selected link is closer to it by the factor of
+    /// `alpha` than the linked point is.
+    fn robust_prune(
+        &self,
+        mut candidates: Vec<ScoredPointOffset>,
+        alpha: f32,
+        score_internal: impl Fn(PointOffsetType, PointOffsetType) -> ScoreType,
+    ) -> Vec<PointOffsetType> {
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        candidates.dedup_by_key(|candidate| candidate.idx);
+
+        let mut selected: Vec<PointOffsetType> = Vec::with_capacity(self.max_degree);
+        for candidate in candidates {
+            if selected.len() >= self.max_degree {
+                break;
+            }
+            let candidate_distance = prune_distance(self.distance, candidate.score);
+            let is_covered = selected.iter().any(|&link| {
+                let link_distance =
+                    prune_distance(self.distance, score_internal(link, candidate.idx));
+                alpha * link_distance <= candidate_distance
+            });
+            if !is_covered {
+                selected.push(candidate.idx);
+            }
+        }
+        selected
+    }
+
+    pub fn into_links(self) -> Vec<Vec<PointOffsetType>> {
+        self.links.into_iter().map(RwLock::into_inner).collect()
+    }
+}
+
+/// Non-negative distance, which is smaller for closer points, so that it can be scaled by `alpha`.
+///
+/// Euclidean and Manhattan scores are negated distances. Cosine and dot product scores are
+/// converted to cosine distance, which is exact for normalized vectors.
+fn prune_distance(distance: Distance, score: ScoreType) -> ScoreType {
+    match distance {
+        Distance::Euclid | Distance::Manhattan => -score,
+        Distance::Cosine | Distance::Dot => (1.0 - score).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prune_distance_is_monotonic() {
+        for distance in [
+            Distance::Cosine,
+            Distance::Dot,
+            Distance::Euclid,
+            Distance::Manhattan,
+        ] {
+            let closer = prune_distance(distance, -0.1);
+            let further = prune_distance(distance, -0.5);
+            assert!(closer >= 0.0);
+            assert!(closer <= further, "{distance:?}");
+        }
+    }
+}
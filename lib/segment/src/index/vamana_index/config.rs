@@ -0,0 +1,35 @@
+use std::path::{Path, PathBuf};
+
+use common::fs::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+
+pub const VAMANA_INDEX_CONFIG_FILE: &str = "vamana_config.json";
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+pub struct VamanaGraphConfig {
+    /// Maximal number of links of a point
+    pub max_degree: usize,
+    /// Size of the candidate list during the build
+    pub build_list_size: usize,
+    /// We prefer a full scan search upto (excluding) this number of vectors.
+    ///
+    /// Note: this is number of vectors, not KiloBytes.
+    pub full_scan_threshold: usize,
+    pub indexed_vector_count: usize,
+}
+
+impl VamanaGraphConfig {
+    pub fn get_config_path(path: &Path) -> PathBuf {
+        path.join(VAMANA_INDEX_CONFIG_FILE)
+    }
+
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_json(path)?)
+    }
+
+    pub fn save(&self, path: &Path) -> OperationResult<()> {
+        Ok(atomic_save_json(path, self)?)
+    }
+}
@@ -0,0 +1,333 @@
+//! On-disk layout of the Vamana graph.
+//!
+//! The file starts with a page holding the [`VamanaGraphHeader`], followed by fixed-size records
+//! of all points, in the order of point offsets:
+//!
+//! ```text
+//! ┌────────┬──────────────────────────┬──────────────────────────┬─────┐
+//! │ header │ page 1                   │ page 2                   │ ... │
+//! │        │ [record 0] [record 1] .. │ [record k] [record k+1]  │     │
+//! └────────┴──────────────────────────┴──────────────────────────┴─────┘
+//! record: [degree: u32] [neighbor: u32; max_degree]
+//! ```
+//!
+//! Records never cross a page boundary, so that links of a point are read from disk with a single
+//! I/O operation during the search.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::fs::atomic_save;
+use common::mmap::{Advice, AdviceSetting, Madviseable, open_read_mmap};
+use common::types::{PointOffsetType, ScoredPointOffset};
+use memmap2::Mmap;
+use zerocopy::little_endian::{U32 as LittleU32, U64 as LittleU64};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+use crate::common::operation_error::{
+    CancellableResult, OperationError, OperationResult, check_process_stopped,
+};
+use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::visited_pool::VisitedPool;
+use crate::payload_storage::FilterContext;
+
+pub const VAMANA_GRAPH_FILE: &str = "vamana_graph.bin";
+
+/// Size of a page in the graph file. Records are aligned to pages.
+const PAGE_SIZE: usize = 4096;
+
+const VAMANA_GRAPH_VERSION: u64 = 1;
+
+#[derive(FromBytes, Immutable, IntoBytes, KnownLayout)]
+#[repr(C)]
+struct VamanaGraphHeader {
+    version: LittleU64,
+    point_count: LittleU64,
+    max_degree: LittleU64,
+    entry_point: LittleU64,
+    /// Number of records in a page
+    records_per_page: LittleU64,
+    /// Size of a page with records, a multiple of [`PAGE_SIZE`]
+    page_size: LittleU64,
+}
+
+/// Placement of records in the graph file.
+#[derive(Debug, Clone, Copy)]
+struct RecordLayout {
+    max_degree: usize,
+    records_per_page: usize,
+    page_size: usize,
+}
+
+impl RecordLayout {
+    fn new(max_degree: usize) -> Self {
+        let record_size = Self::record_size(max_degree);
+        let (records_per_page, page_size) = if record_size <= PAGE_SIZE {
+            (PAGE_SIZE / record_size, PAGE_SIZE)
+        } else {
+            (1, record_size.next_multiple_of(PAGE_SIZE))
+        };
+        Self {
+            max_degree,
+            records_per_page,
+            page_size,
+        }
+    }
+
+    fn record_size(max_degree: usize) -> usize {
+        size_of::<u32>() * (max_degree + 1)
+    }
+
+    fn record_offset(&self, point_id: PointOffsetType) -> usize {
+        let point_id = point_id as usize;
+        PAGE_SIZE
+            + (point_id / self.records_per_page) * self.page_size
+            + (point_id % self.records_per_page) * Self::record_size(self.max_degree)
+    }
+
+    fn file_size(&self, point_count: usize) -> usize {
+        PAGE_SIZE + point_count.div_ceil(self.records_per_page) * self.page_size
+    }
+}
+
+/// Single-layer proximity graph, searched with beam search.
+#[derive(Debug)]
+pub struct VamanaGraph {
+    mmap: Mmap,
+    layout: RecordLayout,
+    point_count: usize,
+    entry_point: Option<PointOffsetType>,
+    visited_pool: VisitedPool,
+}
+
+impl VamanaGraph {
+    pub fn get_path(path: &Path) -> PathBuf {
+        path.join(VAMANA_GRAPH_FILE)
+    }
+
+    pub fn files(path: &Path) -> Vec<PathBuf> {
+        vec![Self::get_path(path)]
+    }
+
+    /// Write links of all points into the graph file in `path`.
+    pub fn save(
+        path: &Path,
+        links: &[Vec<PointOffsetType>],
+        max_degree: usize,
+        entry_point: Option<PointOffsetType>,
+    ) -> OperationResult<()> {
+        let layout = RecordLayout::new(max_degree);
+        let header = VamanaGraphHeader {
+            version: LittleU64::new(VAMANA_GRAPH_VERSION),
+            point_count: LittleU64::new(links.len() as u64),
+            max_degree: LittleU64::new(max_degree as u64),
+            // Point count is never a valid entry point
+            entry_point: LittleU64::new(entry_point.map_or(links.len() as u64, u64::from)),
+            records_per_page: LittleU64::new(layout.records_per_page as u64),
+            page_size: LittleU64::new(layout.page_size as u64),
+        };
+
+        let mut data = vec![0u8; layout.file_size(links.len())];
+        data[..size_of::<VamanaGraphHeader>()].copy_from_slice(header.as_bytes());
+        for (point_id, point_links) in links.iter().enumerate() {
+            debug_assert!(point_links.len() <= max_degree);
+            let offset = layout.record_offset(point_id as PointOffsetType);
+            let record = &mut data[offset..offset + RecordLayout::record_size(max_degree)];
+            let degree = point_links.len().min(max_degree);
+            record[..size_of::<u32>()].copy_from_slice(LittleU32::new(degree as u32).as_bytes());
+            for (i, &link) in point_links.iter().take(degree).enumerate() {
+                let start = size_of::<u32>() * (i + 1);
+                record[start..start + size_of::<u32>()]
+                    .copy_from_slice(LittleU32::new(link).as_bytes());
+            }
+        }
+
+        atomic_save(&Self::get_path(path), |writer| {
+            std::io::Write::write_all(writer, &data)
+        })?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path, on_disk: bool) -> OperationResult<Self> {
+        let populate = !on_disk;
+        let mmap = open_read_mmap(
+            &Self::get_path(path),
+            AdviceSetting::Advice(Advice::Random),
+            populate,
+        )?;
+
+        let (header, _) = VamanaGraphHeader::ref_from_prefix(&mmap).map_err(|_| {
+            OperationError::service_error("Vamana graph file is too small for the header")
+        })?;
+        if header.version.get() != VAMANA_GRAPH_VERSION {
+            return Err(OperationError::service_error(format!(
+                "unsupported Vamana graph version {}",
+                header.version.get(),
+            )));
+        }
+
+        let point_count = header.point_count.get() as usize;
+        let layout = RecordLayout::new(header.max_degree.get() as usize);
+        if layout.records_per_page != header.records_per_page.get() as usize
+            || layout.page_size != header.page_size.get() as usize
+            || mmap.len() < layout.file_size(point_count)
+        {
+            return Err(OperationError::service_error(
+                "Vamana graph file has unexpected layout",
+            ));
+        }
+
+        let entry_point = header.entry_point.get();
+        let entry_point = (entry_point < point_count as u64).then_some(entry_point as u32);
+
+        Ok(Self {
+            mmap,
+            layout,
+            point_count,
+            entry_point,
+            visited_pool: VisitedPool::new(),
+        })
+    }
+
+    pub fn num_points(&self) -> usize {
+        self.point_count
+    }
+
+    pub fn entry_point(&self) -> Option<PointOffsetType> {
+        self.entry_point
+    }
+
+    pub fn max_degree(&self) -> usize {
+        self.layout.max_degree
+    }
+
+    /// Neighbors of the point, read from a single record.
+    pub fn links(&self, point_id: PointOffsetType) -> impl Iterator<Item = PointOffsetType> + '_ {
+        let neighbors = if (point_id as usize) < self.point_count {
+            let offset = self.layout.record_offset(point_id);
+            let record_size = RecordLayout::record_size(self.layout.max_degree);
+            let record = <[LittleU32]>::ref_from_bytes(&self.mmap[offset..offset + record_size])
+                .expect("record size is a multiple of u32");
+            let degree = (record[0].get() as usize).min(self.layout.max_degree);
+            &record[1..=degree]
+        } else {
+            &[]
+        };
+        neighbors.iter().map(|link| link.get())
+    }
+
+    /// Beam search for the `top` nearest points, see [`beam_search`].
+    ///
+    /// The graph is traversed through all points, only points matching `filter` are returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn search(
+        &self,
+        top: usize,
+        list_size: usize,
+        beam_width: usize,
+        points_scorer: &mut FilteredScorer,
+        filter: Option<&dyn FilterContext>,
+        is_stopped: &AtomicBool,
+    ) -> CancellableResult<Vec<ScoredPointOffset>> {
+        let Some(entry_point) = self.entry_point else {
+            return Ok(Vec::new());
+        };
+        let mut result = FixedLengthPriorityQueue::new(top);
+        beam_search(
+            &self.visited_pool,
+            self.point_count,
+            entry_point,
+            list_size.max(top),
+            beam_width,
+            points_scorer,
+            |point_id, links| links.extend(self.links(point_id)),
+            |scored| {
+                if filter.is_none_or(|filter| filter.check(scored.idx)) {
+                    result.push(scored);
+                }
+            },
+            is_stopped,
+        )?;
+        Ok(result.into_sorted_vec())
+    }
+
+    /// Populate the disk cache with the graph.
+    pub fn populate(&self) -> OperationResult<()> {
+        self.mmap.populate();
+        Ok(())
+    }
+}
+
+/// Greedy search in a single-layer graph, which expands up to `beam_width` best candidates at
+/// once. Links of all expanded points are scored in a single batch, which lets the storage read
+/// them with fewer round trips.
+///
+/// Each point, which is not deleted, is passed to `on_scored` once it is scored.
+/// Returns all expanded points with their scores.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn beam_search(
+    visited_pool: &VisitedPool,
+    num_points: usize,
+    entry_point: PointOffsetType,
+    list_size: usize,
+    beam_width: usize,
+    points_scorer: &mut FilteredScorer,
+    mut links: impl FnMut(PointOffsetType, &mut Vec<PointOffsetType>),
+    mut on_scored: impl FnMut(ScoredPointOffset),
+    is_stopped: &AtomicBool,
+) -> CancellableResult<Vec<ScoredPointOffset>> {
+    let mut visited_list = visited_pool.get(num_points);
+    visited_list.check_and_update_visited(entry_point);
+
+    // Entry point is expanded even if deleted, to keep the graph reachable
+    let entry = ScoredPointOffset {
+        idx: entry_point,
+        score: points_scorer.score_point(entry_point),
+    };
+    if points_scorer.filters().check_vector(entry_point) {
+        on_scored(entry);
+    }
+
+    let mut search_context = SearchContext::new(list_size);
+    search_context.process_candidate(entry);
+
+    let mut expanded = Vec::new();
+    let mut beam = Vec::with_capacity(beam_width);
+    let mut points_ids = Vec::new();
+
+    loop {
+        check_process_stopped(is_stopped)?;
+
+        beam.clear();
+        while beam.len() < beam_width.max(1) {
+            match search_context.candidates.pop() {
+                Some(candidate) if candidate.score >= search_context.lower_bound() => {
+                    beam.push(candidate)
+                }
+                _ => break,
+            }
+        }
+        if beam.is_empty() {
+            break;
+        }
+
+        points_ids.clear();
+        for candidate in &beam {
+            points_scorer.diagnostics().add_hop(0);
+            links(candidate.idx, &mut points_ids);
+        }
+        points_ids.retain(|&point_id| !visited_list.check_and_update_visited(point_id));
+        expanded.extend_from_slice(&beam);
+
+        points_scorer
+            .score_points(&mut points_ids, 0)
+            .for_each(|score_point| {
+                on_scored(score_point);
+                search_context.process_candidate(score_point);
+            });
+    }
+
+    Ok(expanded)
+}
@@ -0,0 +1,7 @@
+mod builder;
+mod config;
+pub mod graph;
+pub mod vamana;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::budget::ResourcePermit;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::flags::FeatureFlags;
+use common::progress_tracker::ProgressTracker;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tempfile::Builder;
+
+use super::vamana::{VamanaIndex, VamanaIndexOpenArgs};
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, QueryVector, only_default_vector};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::VectorIndex;
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
+use crate::types::{Distance, HnswGlobalConfig, SearchParams, SeqNumberType, VamanaConfig};
+
+#[test]
+fn test_vamana_search_recall() {
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+    let num_queries = 20;
+    let top = 10;
+
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let vamana_dir = Builder::new().prefix("vamana_dir").tempdir().unwrap();
+
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rng, dim);
+        segment
+            .upsert_point(
+                n as SeqNumberType,
+                n.into(),
+                only_default_vector(&vector),
+                &hw_counter,
+            )
+            .unwrap();
+    }
+
+    let vamana_config = VamanaConfig {
+        max_degree: 16,
+        build_list_size: 32,
+        beam_width: None,
+        full_scan_threshold: 0,
+        on_disk: Some(true),
+    };
+    let open_args = || VamanaIndexOpenArgs {
+        path: vamana_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: segment.payload_index.clone(),
+        vamana_config,
+    };
+
+    let stopped = AtomicBool::new(false);
+    let index = VamanaIndex::build(
+        open_args(),
+        VectorIndexBuildArgs {
+            permit: Arc::new(ResourcePermit::dummy(2)),
+            old_indices: &[],
+            gpu_device: None,
+            rng: &mut rng,
+            stopped: &stopped,
+            hnsw_global_config: &HnswGlobalConfig::default(),
+            feature_flags: FeatureFlags::default(),
+            progress: ProgressTracker::new_for_test(),
+        },
+    )
+    .unwrap();
+    assert_eq!(index.indexed_vector_count(), num_vectors as usize);
+
+    let queries: Vec<QueryVector> = (0..num_queries)
+        .map(|_| random_vector(&mut rng, dim).into())
+        .collect();
+    let queries: Vec<&QueryVector> = queries.iter().collect();
+
+    let query_context = VectorQueryContext::default();
+    let graph_params = SearchParams {
+        hnsw_ef: Some(64),
+        ..Default::default()
+    };
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+
+    let graph_results = index
+        .search(&queries, None, top, Some(&graph_params), &query_context)
+        .unwrap();
+    let exact_results = index
+        .search(&queries, None, top, Some(&exact_params), &query_context)
+        .unwrap();
+
+    let found: usize = graph_results
+        .iter()
+        .zip(&exact_results)
+        .map(|(graph_result, exact_result)| {
+            graph_result
+                .iter()
+                .filter(|scored| exact_result.iter().any(|exact| exact.idx == scored.idx))
+                .count()
+        })
+        .sum();
+    let recall = found as f64 / (num_queries * top) as f64;
+    assert!(recall >= 0.9, "recall {recall} is too low");
+
+    // Reopened index reads the same graph from disk
+    drop(index);
+    let reopened = VamanaIndex::open(open_args()).unwrap();
+    let reopened_results = reopened
+        .search(&queries, None, top, Some(&graph_params), &query_context)
+        .unwrap();
+    assert_eq!(reopened_results, graph_results);
+}
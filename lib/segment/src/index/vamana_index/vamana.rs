@@ -0,0 +1,581 @@
+use std::ops::Deref as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+use atomic_refcell::AtomicRefCell;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::clear_disk_cache;
+use common::search_diagnostics::SearchStrategy;
+use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use fs_err as fs;
+use log::debug;
+use parking_lot::Mutex;
+use rand::seq::SliceRandom as _;
+use rand::{Rng, RngExt};
+use rayon::prelude::*;
+
+use super::builder::VamanaGraphBuilder;
+use super::config::VamanaGraphConfig;
+use super::graph::VamanaGraph;
+use crate::common::BYTES_IN_KB;
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::common::operation_time_statistics::{
+    OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use crate::data_types::query_context::VectorQueryContext;
+use crate::data_types::vectors::{QueryVector, VectorRef};
+use crate::id_tracker::{IdTracker, IdTrackerEnum};
+use crate::index::hnsw_index::hnsw::SINGLE_THREADED_HNSW_BUILD_THRESHOLD;
+use crate::index::hnsw_index::point_scorer::{BatchFilteredSearcher, FilteredScorer};
+use crate::index::query_estimator::adjust_to_available_vectors;
+use crate::index::sample_estimation::sample_check_cardinality;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_search_common::{
+    get_oversampled_top, is_quantized_search, postprocess_search_result,
+};
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::segment_constructor::VectorIndexBuildArgs;
+use crate::telemetry::VectorIndexSearchesTelemetry;
+use crate::types::{
+    DEFAULT_VAMANA_BEAM_WIDTH, Filter, QuantizationSearchParams, SearchParams, VamanaConfig,
+};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Pruning factor of the second build pass. Values above 1 keep long-range links.
+const VAMANA_ALPHA: f32 = 1.2;
+
+/// Number of points, sampled to find the entry point of the graph.
+const MEDOID_SAMPLE_SIZE: usize = 256;
+
+/// Single-layer proximity graph index, following DiskANN.
+///
+/// The graph is stored in page-aligned records and read from disk during the search. Quantized
+/// vectors, if configured, are used to navigate the graph, and results are rescored with
+/// original vectors.
+#[derive(Debug)]
+pub struct VamanaIndex {
+    id_tracker: Arc<AtomicRefCell<IdTrackerEnum>>,
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    config: VamanaGraphConfig,
+    beam_width: usize,
+    path: PathBuf,
+    graph: VamanaGraph,
+    is_on_disk: bool,
+    searches_telemetry: VamanaSearchesTelemetry,
+}
+
+#[derive(Debug)]
+struct VamanaSearchesTelemetry {
+    unfiltered_plain: Arc<Mutex<OperationDurationsAggregator>>,
+    unfiltered_graph: Arc<Mutex<OperationDurationsAggregator>>,
+    small_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
+    exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl VamanaSearchesTelemetry {
+    fn new() -> Self {
+        Self {
+            unfiltered_plain: OperationDurationsAggregator::new(),
+            unfiltered_graph: OperationDurationsAggregator::new(),
+            small_cardinality: OperationDurationsAggregator::new(),
+            large_cardinality: OperationDurationsAggregator::new(),
+            exact_filtered: OperationDurationsAggregator::new(),
+            exact_unfiltered: OperationDurationsAggregator::new(),
+        }
+    }
+}
+
+pub struct VamanaIndexOpenArgs<'a> {
+    pub path: &'a Path,
+    pub id_tracker: Arc<AtomicRefCell<IdTrackerEnum>>,
+    pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    pub vamana_config: VamanaConfig,
+}
+
+impl VamanaIndex {
+    pub fn open(args: VamanaIndexOpenArgs<'_>) -> OperationResult<Self> {
+        let VamanaIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            vamana_config,
+        } = args;
+
+        let config = VamanaGraphConfig::load(&VamanaGraphConfig::get_config_path(path))?;
+        let is_on_disk = vamana_config.on_disk.unwrap_or(true);
+        let graph = VamanaGraph::load(path, is_on_disk)?;
+
+        Ok(VamanaIndex {
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            config,
+            beam_width: vamana_config
+                .beam_width
+                .unwrap_or(DEFAULT_VAMANA_BEAM_WIDTH),
+            path: path.to_owned(),
+            graph,
+            is_on_disk,
+            searches_telemetry: VamanaSearchesTelemetry::new(),
+        })
+    }
+
+    pub fn build<R: Rng + ?Sized>(
+        open_args: VamanaIndexOpenArgs<'_>,
+        build_args: VectorIndexBuildArgs<'_, R>,
+    ) -> OperationResult<Self> {
+        if VamanaGraphConfig::get_config_path(open_args.path).exists()
+            || VamanaGraph::get_path(open_args.path).exists()
+        {
+            log::warn!(
+                "Vamana index already exists at {:?}, skipping building",
+                open_args.path
+            );
+            debug_assert!(false);
+            return Self::open(open_args);
+        }
+
+        let VectorIndexBuildArgs {
+            permit,
+            old_indices: _,
+            gpu_device: _,
+            rng,
+            stopped,
+            hnsw_global_config: _,
+            feature_flags: _,
+            progress,
+        } = build_args;
+        let path = open_args.path;
+        let vamana_config = open_args.vamana_config;
+
+        fs::create_dir_all(path)?;
+
+        let id_tracker_ref = open_args.id_tracker.borrow();
+        let vector_storage_ref = open_args.vector_storage.borrow();
+        let quantized_vectors_ref = open_args.quantized_vectors.borrow();
+
+        let total_vector_count = vector_storage_ref.total_vector_count();
+
+        let full_scan_threshold = vector_storage_ref
+            .size_of_available_vectors_in_bytes()
+            .checked_div(total_vector_count)
+            .and_then(|avg_vector_size| {
+                vamana_config
+                    .full_scan_threshold
+                    .saturating_mul(BYTES_IN_KB)
+                    .checked_div(avg_vector_size)
+            })
+            .unwrap_or(1);
+
+        let deleted_bitslice = vector_storage_ref.deleted_vector_bitslice();
+        let mut ids: Vec<PointOffsetType> = id_tracker_ref
+            .point_mappings()
+            .iter_internal_excluding(deleted_bitslice)
+            .collect();
+
+        debug!(
+            "building Vamana graph for {} vectors with {} CPUs",
+            ids.len(),
+            permit.num_cpus,
+        );
+
+        let points_scorer_for = |point_id| {
+            FilteredScorer::new_internal(
+                point_id,
+                vector_storage_ref.deref(),
+                quantized_vectors_ref.as_ref(),
+                None,
+                id_tracker_ref.deleted_point_bitslice(),
+                // No need to accumulate hardware, since this is an internal operation
+                HardwareCounterCell::disposable(),
+            )
+        };
+
+        let (links, entry_point) = if ids.is_empty() {
+            (vec![Vec::new(); total_vector_count], None)
+        } else {
+            let entry_point = Self::approximate_medoid(&ids, &points_scorer_for, rng)?;
+            let builder = VamanaGraphBuilder::new(
+                total_vector_count,
+                vamana_config.max_degree,
+                vamana_config.build_list_size,
+                vector_storage_ref.distance(),
+                entry_point,
+            );
+
+            let pool = rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("vamana-build-{idx}"))
+                .num_threads(permit.num_cpus as usize)
+                .build()?;
+
+            let progress_main_graph = progress.subtask("main_graph");
+            progress_main_graph.start();
+            let counter = progress_main_graph.track_progress(Some(ids.len() as u64 * 2));
+            let counter = counter.deref();
+
+            let timer = std::time::Instant::now();
+
+            // First pass builds a dense graph of short links, second pass adds long-range links
+            for alpha in [1.0, VAMANA_ALPHA] {
+                ids.shuffle(rng);
+
+                let insert_point = |point_id| {
+                    check_process_stopped(stopped)?;
+                    let points_scorer = points_scorer_for(point_id)?;
+                    builder.link_point(point_id, alpha, points_scorer, stopped)?;
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    Ok::<_, OperationError>(())
+                };
+
+                let (first_few_ids, other_ids) =
+                    ids.split_at(ids.len().min(SINGLE_THREADED_HNSW_BUILD_THRESHOLD));
+                for &point_id in first_few_ids {
+                    insert_point(point_id)?;
+                }
+                pool.install(|| other_ids.par_iter().try_for_each(|&id| insert_point(id)))?;
+            }
+
+            drop(progress_main_graph);
+            debug!("Finish Vamana graph in time {:?}", timer.elapsed());
+
+            (builder.into_links(), Some(entry_point))
+        };
+
+        VamanaGraph::save(path, &links, vamana_config.max_degree, entry_point)?;
+
+        let config = VamanaGraphConfig {
+            max_degree: vamana_config.max_degree,
+            build_list_size: vamana_config.build_list_size,
+            full_scan_threshold,
+            indexed_vector_count: ids.len(),
+        };
+        config.save(&VamanaGraphConfig::get_config_path(path))?;
+
+        drop(id_tracker_ref);
+        drop(vector_storage_ref);
+        drop(quantized_vectors_ref);
+
+        Self::open(open_args)
+    }
+
+    /// Point with the largest sum of scores to a random sample of points.
+    ///
+    /// Searches start from this point, so it should be close to the center of the dataset.
+    fn approximate_medoid<'a, R: Rng + ?Sized>(
+        ids: &[PointOffsetType],
+        points_scorer_for: impl Fn(PointOffsetType) -> OperationResult<FilteredScorer<'a>>,
+        rng: &mut R,
+    ) -> OperationResult<PointOffsetType> {
+        let sample: Vec<PointOffsetType> = (0..ids.len().min(MEDOID_SAMPLE_SIZE))
+            .map(|_| ids[rng.random_range(0..ids.len())])
+            .collect();
+
+        let points_scorer = points_scorer_for(sample[0])?;
+        let medoid = sample
+            .iter()
+            .copied()
+            .max_by(|&a, &b| {
+                let score_sum = |point_id| -> f32 {
+                    sample
+                        .iter()
+                        .map(|&other| points_scorer.score_internal(point_id, other))
+                        .sum()
+                };
+                score_sum(a).total_cmp(&score_sum(b))
+            })
+            .unwrap_or(sample[0]);
+        Ok(medoid)
+    }
+
+    pub fn is_on_disk(&self) -> bool {
+        self.is_on_disk
+    }
+
+    /// Read underlying data from disk into disk cache.
+    pub fn populate(&self) -> OperationResult<()> {
+        self.graph.populate()
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        for file in VamanaGraph::files(&self.path) {
+            clear_disk_cache(&file)?;
+        }
+        Ok(())
+    }
+
+    fn search_plain(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_storage = self.quantized_vectors.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let quantization_enabled = is_quantized_search(quantized_storage.as_ref(), params);
+        let quantized_vectors = quantization_enabled
+            .then_some(quantized_storage.as_ref())
+            .flatten();
+        let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
+        let batch_searcher = BatchFilteredSearcher::new(
+            vectors,
+            &vector_storage,
+            quantized_vectors,
+            None,
+            oversampled_top,
+            deleted_points,
+            query_context.hardware_counter(),
+        )?;
+
+        let search_results = match filter {
+            Some(filter) => {
+                let payload_index = self.payload_index.borrow();
+                let filtered_ids =
+                    payload_index.query_points(filter, &hw_counter, &is_stopped, None)?;
+                batch_searcher.peek_top_iter(filtered_ids.iter().copied(), &is_stopped)?
+            }
+            None => batch_searcher.peek_top_all(&is_stopped, None)?,
+        };
+
+        search_results
+            .into_iter()
+            .zip(vectors)
+            .map(|(search_result, query_vector)| {
+                postprocess_search_result(
+                    search_result,
+                    deleted_points,
+                    &vector_storage,
+                    quantized_storage.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    query_context.hardware_counter(),
+                )
+            })
+            .collect()
+    }
+
+    fn search_with_graph(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let list_size = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.build_list_size);
+
+        let is_stopped = query_context.is_stopped();
+        let hw_counter = query_context.hardware_counter();
+
+        let vector_storage = self.vector_storage.borrow();
+        let quantized_storage = self.quantized_vectors.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or_else(|| id_tracker.deleted_point_bitslice());
+        let quantization_enabled = is_quantized_search(quantized_storage.as_ref(), params);
+        let quantized_vectors = quantization_enabled
+            .then_some(quantized_storage.as_ref())
+            .flatten();
+        let oversampled_top = get_oversampled_top(quantized_storage.as_ref(), params, top);
+
+        // Graph has no payload-aware links, so it is traversed through all points and only
+        // matching points are collected
+        let filter_context = filter
+            .map(|filter| payload_index.filter_context(filter, &hw_counter))
+            .transpose()?;
+
+        vectors
+            .iter()
+            .map(|&query_vector| {
+                let mut points_scorer = FilteredScorer::new(
+                    query_vector.to_owned(),
+                    &vector_storage,
+                    quantized_vectors,
+                    None,
+                    deleted_points,
+                    query_context.hardware_counter(),
+                )?;
+                let search_result = self.graph.search(
+                    oversampled_top,
+                    list_size,
+                    self.beam_width,
+                    &mut points_scorer,
+                    filter_context.as_deref(),
+                    &is_stopped,
+                )?;
+                postprocess_search_result(
+                    search_result,
+                    deleted_points,
+                    &vector_storage,
+                    quantized_storage.as_ref(),
+                    query_vector,
+                    params,
+                    top,
+                    query_context.hardware_counter(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl VectorIndex for VamanaIndex {
+    fn search(
+        &self,
+        vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        if top == 0 {
+            return Ok(vec![vec![]; vectors.len()]);
+        }
+
+        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let diagnostics = query_context.hardware_counter().search_diagnostics();
+
+        let exact_params = exact.then(|| {
+            let mut params = params.copied().unwrap_or_default();
+            // disable quantization for exact search
+            params.quantization = Some(QuantizationSearchParams {
+                ignore: true,
+                rescore: Some(false),
+                oversampling: None,
+            });
+            params
+        });
+
+        let tm = &self.searches_telemetry;
+
+        if exact {
+            let _timer = ScopeDurationMeasurer::new(if filter.is_some() {
+                &tm.exact_filtered
+            } else {
+                &tm.exact_unfiltered
+            });
+            diagnostics.add_searches(SearchStrategy::PlainExact, vectors.len());
+            return self.search_plain(vectors, filter, top, exact_params.as_ref(), query_context);
+        }
+
+        let Some(query_filter) = filter else {
+            let available_vector_count = self.vector_storage.borrow().available_vector_count();
+            return if available_vector_count < self.config.full_scan_threshold {
+                let _timer = ScopeDurationMeasurer::new(&tm.unfiltered_plain);
+                diagnostics.add_searches(SearchStrategy::PlainSmallSegment, vectors.len());
+                self.search_plain(vectors, None, top, params, query_context)
+            } else {
+                let _timer = ScopeDurationMeasurer::new(&tm.unfiltered_graph);
+                diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
+                self.search_with_graph(vectors, None, top, params, query_context)
+            };
+        };
+
+        let is_cardinality_large = {
+            let payload_index = self.payload_index.borrow();
+            let vector_storage = self.vector_storage.borrow();
+            let id_tracker = self.id_tracker.borrow();
+            let available_vector_count = vector_storage.available_vector_count();
+            let hw_counter = query_context.hardware_counter();
+
+            let query_cardinality = adjust_to_available_vectors(
+                payload_index.estimate_cardinality(query_filter, &hw_counter)?,
+                available_vector_count,
+                id_tracker.available_point_count(),
+            );
+
+            if query_cardinality.max < self.config.full_scan_threshold {
+                false
+            } else if query_cardinality.min > self.config.full_scan_threshold {
+                true
+            } else {
+                let filter_context = payload_index.filter_context(query_filter, &hw_counter)?;
+                sample_check_cardinality(
+                    id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
+                    |idx| filter_context.check(idx),
+                    self.config.full_scan_threshold,
+                    available_vector_count,
+                )
+            }
+        };
+
+        if is_cardinality_large {
+            let _timer = ScopeDurationMeasurer::new(&tm.large_cardinality);
+            diagnostics.add_searches(SearchStrategy::Graph, vectors.len());
+            self.search_with_graph(vectors, filter, top, params, query_context)
+        } else {
+            let _timer = ScopeDurationMeasurer::new(&tm.small_cardinality);
+            diagnostics.add_searches(SearchStrategy::PlainSmallCardinality, vectors.len());
+            self.search_plain(vectors, filter, top, params, query_context)
+        }
+    }
+
+    fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
+        let tm = &self.searches_telemetry;
+        VectorIndexSearchesTelemetry {
+            index_name: None,
+            unfiltered_plain: tm.unfiltered_plain.lock().get_statistics(detail),
+            filtered_plain: OperationDurationStatistics::default(),
+            unfiltered_hnsw: tm.unfiltered_graph.lock().get_statistics(detail),
+            filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(detail),
+            filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(detail),
+            filtered_exact: tm.exact_filtered.lock().get_statistics(detail),
+            filtered_sparse: Default::default(),
+            unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
+            unfiltered_sparse: Default::default(),
+        }
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = VamanaGraph::files(&self.path);
+        files.push(VamanaGraphConfig::get_config_path(&self.path));
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        self.files()
+    }
+
+    fn indexed_vector_count(&self) -> usize {
+        self.config.indexed_vector_count
+    }
+
+    fn size_of_searchable_vectors_in_bytes(&self) -> usize {
+        self.vector_storage
+            .borrow()
+            .size_of_available_vectors_in_bytes()
+    }
+
+    fn update_vector(
+        &mut self,
+        _id: PointOffsetType,
+        _vector: Option<VectorRef>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error("Cannot update Vamana index"))
+    }
+}
@@ -13,8 +13,10 @@ use sparse::index::inverted_index::inverted_index_mmap::InvertedIndexMmap;
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 
 use super::hnsw_index::hnsw::HNSWIndex;
+use super::ivf_index::ivf::IvfIndex;
 use super::plain_vector_index::PlainVectorIndex;
 use super::sparse_index::sparse_vector_index::SparseVectorIndex;
+use super::vamana_index::vamana::VamanaIndex;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, VectorRef};
@@ -68,6 +70,8 @@ pub trait VectorIndex {
 pub enum VectorIndexEnum {
     Plain(PlainVectorIndex),
     Hnsw(HNSWIndex),
+    Vamana(VamanaIndex),
+    Ivf(IvfIndex),
     SparseRam(SparseVectorIndex<InvertedIndexRam>),
     SparseImmutableRam(SparseVectorIndex<InvertedIndexImmutableRam>),
     SparseMmap(SparseVectorIndex<InvertedIndexMmap>),
@@ -86,6 +90,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => false,
             Self::Hnsw(_) => true,
+            Self::Vamana(_) => true,
+            Self::Ivf(_) => true,
             Self::SparseRam(_) => true,
             Self::SparseImmutableRam(_) => true,
             Self::SparseMmap(_) => true,
@@ -104,6 +110,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => false,
             Self::Hnsw(index) => index.is_on_disk(),
+            Self::Vamana(index) => index.is_on_disk(),
+            Self::Ivf(index) => index.is_on_disk(),
             Self::SparseRam(index) => index.inverted_index().is_on_disk(),
             Self::SparseImmutableRam(index) => index.inverted_index().is_on_disk(),
             Self::SparseMmap(index) => index.inverted_index().is_on_disk(),
@@ -120,6 +128,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => {}
             Self::Hnsw(index) => index.populate()?,
+            Self::Vamana(index) => index.populate()?,
+            Self::Ivf(index) => index.populate()?,
             Self::SparseRam(_) => {}
             Self::SparseImmutableRam(_) => {}
             Self::SparseMmap(index) => index.inverted_index().populate()?,
@@ -137,6 +147,8 @@ impl VectorIndexEnum {
         match self {
             Self::Plain(_) => {}
             Self::Hnsw(index) => index.clear_cache()?,
+            Self::Vamana(index) => index.clear_cache()?,
+            Self::Ivf(index) => index.clear_cache()?,
             Self::SparseRam(_) => {}
             Self::SparseImmutableRam(_) => {}
             Self::SparseMmap(index) => index.inverted_index().clear_cache()?,
@@ -156,7 +168,7 @@ impl VectorIndexEnum {
         hw_counter: &HardwareCounterCell,
     ) {
         match self {
-            Self::Plain(_) | Self::Hnsw(_) => (),
+            Self::Plain(_) | Self::Hnsw(_) | Self::Vamana(_) | Self::Ivf(_) => (),
             Self::SparseRam(index) => index.fill_idf_statistics(idf, hw_counter),
             Self::SparseImmutableRam(index) => index.fill_idf_statistics(idf, hw_counter),
             Self::SparseMmap(index) => index.fill_idf_statistics(idf, hw_counter),
@@ -175,10 +187,28 @@ impl VectorIndexEnum {
         }
     }
 
+    /// Sum of the lengths of the indexed sparse vectors, for BM25 document length normalization
+    pub fn total_doc_length(&self) -> f64 {
+        match self {
+            Self::Plain(_) | Self::Hnsw(_) | Self::Vamana(_) | Self::Ivf(_) => 0.0,
+            Self::SparseRam(index) => index.total_doc_length(),
+            Self::SparseImmutableRam(index) => index.total_doc_length(),
+            Self::SparseMmap(index) => index.total_doc_length(),
+            Self::SparseCompressedImmutableRamF32(index) => index.total_doc_length(),
+            Self::SparseCompressedImmutableRamF16(index) => index.total_doc_length(),
+            Self::SparseCompressedImmutableRamU8(index) => index.total_doc_length(),
+            Self::SparseCompressedMmapF32(index) => index.total_doc_length(),
+            Self::SparseCompressedMmapF16(index) => index.total_doc_length(),
+            Self::SparseCompressedMmapU8(index) => index.total_doc_length(),
+        }
+    }
+
     pub fn indexed_vectors(&self) -> usize {
         match self {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::Hnsw(index) => index.indexed_vector_count(),
+            Self::Vamana(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.inverted_index().vector_count(),
             Self::SparseImmutableRam(index) => index.inverted_index().vector_count(),
             Self::SparseMmap(index) => index.inverted_index().vector_count(),
@@ -208,6 +238,12 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::Hnsw(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
+            VectorIndexEnum::Vamana(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
+            VectorIndexEnum::Ivf(index) => {
+                index.search(vectors, filter, top, params, query_context)
+            }
             VectorIndexEnum::SparseRam(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
@@ -242,6 +278,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::Hnsw(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::Vamana(index) => index.get_telemetry_data(detail),
+            VectorIndexEnum::Ivf(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseImmutableRam(index) => index.get_telemetry_data(detail),
             VectorIndexEnum::SparseMmap(index) => index.get_telemetry_data(detail),
@@ -264,6 +302,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.files(),
             VectorIndexEnum::Hnsw(index) => index.files(),
+            VectorIndexEnum::Vamana(index) => index.files(),
+            VectorIndexEnum::Ivf(index) => index.files(),
             VectorIndexEnum::SparseRam(index) => index.files(),
             VectorIndexEnum::SparseImmutableRam(index) => index.files(),
             VectorIndexEnum::SparseMmap(index) => index.files(),
@@ -280,6 +320,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             VectorIndexEnum::Plain(index) => index.immutable_files(),
             VectorIndexEnum::Hnsw(index) => index.immutable_files(),
+            VectorIndexEnum::Vamana(index) => index.immutable_files(),
+            VectorIndexEnum::Ivf(index) => index.immutable_files(),
             VectorIndexEnum::SparseRam(index) => index.immutable_files(),
             VectorIndexEnum::SparseImmutableRam(index) => index.immutable_files(),
             VectorIndexEnum::SparseMmap(index) => index.immutable_files(),
@@ -296,6 +338,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.indexed_vector_count(),
             Self::Hnsw(index) => index.indexed_vector_count(),
+            Self::Vamana(index) => index.indexed_vector_count(),
+            Self::Ivf(index) => index.indexed_vector_count(),
             Self::SparseRam(index) => index.indexed_vector_count(),
             Self::SparseImmutableRam(index) => index.indexed_vector_count(),
             Self::SparseMmap(index) => index.indexed_vector_count(),
@@ -312,6 +356,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::Hnsw(index) => index.size_of_searchable_vectors_in_bytes(),
+            Self::Vamana(index) => index.size_of_searchable_vectors_in_bytes(),
+            Self::Ivf(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseRam(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseImmutableRam(index) => index.size_of_searchable_vectors_in_bytes(),
             Self::SparseMmap(index) => index.size_of_searchable_vectors_in_bytes(),
@@ -339,6 +385,8 @@ impl VectorIndex for VectorIndexEnum {
         match self {
             Self::Plain(index) => index.update_vector(id, vector, hw_counter),
             Self::Hnsw(index) => index.update_vector(id, vector, hw_counter),
+            Self::Vamana(index) => index.update_vector(id, vector, hw_counter),
+            Self::Ivf(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseRam(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseImmutableRam(index) => index.update_vector(id, vector, hw_counter),
             Self::SparseMmap(index) => index.update_vector(id, vector, hw_counter),
@@ -44,29 +44,36 @@ pub fn get_oversampled_top(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn postprocess_search_result(
-    mut search_result: Vec<ScoredPointOffset>,
-    point_deleted: &BitSlice,
-    vector_storage: &VectorStorageEnum,
+/// Whether results of quantized search have to be rescored with original vectors
+pub fn is_rescoring_required(
     quantized_vectors: Option<&QuantizedVectors>,
-    vector: &QueryVector,
     params: Option<&SearchParams>,
-    top: usize,
-    hardware_counter: HardwareCounterCell,
-) -> OperationResult<Vec<ScoredPointOffset>> {
+) -> bool {
     let quantization_enabled = is_quantized_search(quantized_vectors, params);
 
     let default_rescoring = quantized_vectors
         .as_ref()
         .map(|q| q.default_rescoring())
         .unwrap_or(false);
-    let rescore = quantization_enabled
+    quantization_enabled
         && params
             .and_then(|p| p.quantization)
             .and_then(|q| q.rescore)
-            .unwrap_or(default_rescoring);
-    if rescore {
+            .unwrap_or(default_rescoring)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn postprocess_search_result(
+    mut search_result: Vec<ScoredPointOffset>,
+    point_deleted: &BitSlice,
+    vector_storage: &VectorStorageEnum,
+    quantized_vectors: Option<&QuantizedVectors>,
+    vector: &QueryVector,
+    params: Option<&SearchParams>,
+    top: usize,
+    hardware_counter: HardwareCounterCell,
+) -> OperationResult<Vec<ScoredPointOffset>> {
+    if is_rescoring_required(quantized_vectors, params) {
         let mut scorer = FilteredScorer::new(
             vector.to_owned(),
             vector_storage,
@@ -1,6 +1,7 @@
 //! Structures for fast and tread-safe way to check if some points were visited or not
 
 use common::defaults::POOL_KEEP_LIMIT;
+use common::numa::{self, NumaTopology};
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 
@@ -93,22 +94,36 @@ impl<'a> VisitedListHandle<'a> {
 /// Keeps a list of `VisitedList` which could be requested and released from multiple threads
 ///
 /// If there are more requests than lists - creates a new list, but only keeps max defined amount.
+///
+/// On NUMA machines, lists are kept separately for each node, so that threads pinned to a node
+/// reuse lists allocated in memory of that node. Unpinned threads share the first pool.
 #[derive(Debug)]
 pub struct VisitedPool {
-    pool: RwLock<Vec<VisitedList>>,
+    pools: Box<[RwLock<Vec<VisitedList>>]>,
 }
 
 impl VisitedPool {
     pub fn new() -> Self {
+        let num_nodes = NumaTopology::global().map_or(1, NumaTopology::num_nodes);
         VisitedPool {
-            pool: RwLock::new(Vec::with_capacity(*POOL_KEEP_LIMIT)),
+            pools: (0..num_nodes)
+                .map(|_| RwLock::new(Vec::with_capacity(*POOL_KEEP_LIMIT)))
+                .collect(),
         }
     }
 
+    /// Pool of the NUMA node the current thread is pinned to
+    fn pool(&self) -> &RwLock<Vec<VisitedList>> {
+        let node = numa::current_node()
+            .filter(|&node| node < self.pools.len())
+            .unwrap_or(0);
+        &self.pools[node]
+    }
+
     pub fn get(&self, num_points: usize) -> VisitedListHandle<'_> {
         // If there are more concurrent requests, a new temporary list is created dynamically.
         // This limit is implemented to prevent memory leakage.
-        match self.pool.write().pop() {
+        match self.pool().write().pop() {
             None => VisitedListHandle::new(self, VisitedList::new(num_points)),
             Some(data) => {
                 let mut visited_list = VisitedListHandle::new(self, data);
@@ -120,7 +135,7 @@ impl VisitedPool {
     }
 
     fn return_back(&self, data: VisitedList) {
-        let mut pool = self.pool.write();
+        let mut pool = self.pool().write();
         if pool.len() < *POOL_KEEP_LIMIT {
             pool.push(data);
         }
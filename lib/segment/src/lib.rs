@@ -1,3 +1,4 @@
+pub mod blob_storage;
 pub mod common;
 pub mod entry;
 #[cfg(feature = "testing")]
@@ -160,7 +160,7 @@ impl ValueChecker for Match {
                 }
                 _ => false,
             },
-            Match::Text(MatchText { text }) | Match::Phrase(MatchPhrase { phrase: text }) => {
+            Match::Text(MatchText { text }) | Match::Phrase(MatchPhrase { phrase: text, .. }) => {
                 match payload {
                     Value::String(stored) => stored.contains(text),
                     _ => false,
@@ -0,0 +1,40 @@
+use crate::common::operation_error::OperationResult;
+use crate::index::VectorIndexEnum;
+use crate::segment::Segment;
+use crate::types::VectorName;
+
+impl Segment {
+    /// Fraction of vectors in the sparse index of `vector_name`, which were deleted since the
+    /// index was built or compacted.
+    ///
+    /// Returns `None` if the vector has no sparse index, which supports compaction.
+    pub fn sparse_index_deleted_ratio(&self, vector_name: &VectorName) -> Option<f64> {
+        let vector_data = self.readable_vector_data(vector_name).ok()?;
+        match &*vector_data.vector_index.borrow() {
+            VectorIndexEnum::SparseCompressedMmapF32(index) => Some(index.deleted_indexed_ratio()),
+            VectorIndexEnum::SparseCompressedMmapF16(index) => Some(index.deleted_indexed_ratio()),
+            VectorIndexEnum::SparseCompressedMmapU8(index) => Some(index.deleted_indexed_ratio()),
+            _ => None,
+        }
+    }
+
+    /// Rewrite posting lists of the sparse index of `vector_name` without deleted points, instead
+    /// of rebuilding the whole segment.
+    ///
+    /// Returns the number of removed posting elements, or `None` if the vector has no sparse index,
+    /// which supports compaction.
+    #[allow(clippy::needless_pass_by_ref_mut)] // ensure single access to AtomicRefCell vector index
+    pub fn compact_sparse_index(
+        &mut self,
+        vector_name: &VectorName,
+    ) -> OperationResult<Option<usize>> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let removed_elements = match &mut *vector_data.vector_index.borrow_mut() {
+            VectorIndexEnum::SparseCompressedMmapF32(index) => index.compact()?,
+            VectorIndexEnum::SparseCompressedMmapF16(index) => index.compact()?,
+            VectorIndexEnum::SparseCompressedMmapU8(index) => index.compact()?,
+            _ => return Ok(None),
+        };
+        Ok(Some(removed_elements))
+    }
+}
@@ -0,0 +1,69 @@
+use std::ops::Deref as _;
+use std::path::PathBuf;
+
+use common::disk::file_disk_size;
+
+use super::Segment;
+use crate::id_tracker::IdTracker;
+use crate::index::VectorIndex;
+use crate::payload_storage::PayloadStorage;
+use crate::types::SegmentDiskUsage;
+use crate::vector_storage::VectorStorage;
+
+impl Segment {
+    pub(super) fn calculate_disk_usage(&self) -> SegmentDiskUsage {
+        let mut usage = SegmentDiskUsage::default();
+
+        for (vector_name, vector_data) in &self.vector_data {
+            usage.vector_storage_bytes +=
+                files_disk_usage(vector_data.vector_storage.borrow().files());
+
+            let vector_index_bytes = files_disk_usage(vector_data.vector_index.borrow().files());
+            if self
+                .segment_config
+                .sparse_vector_data
+                .contains_key(vector_name)
+            {
+                usage.sparse_index_bytes += vector_index_bytes;
+            } else {
+                usage.hnsw_index_bytes += vector_index_bytes;
+            }
+
+            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().deref() {
+                usage.quantized_vectors_bytes += files_disk_usage(quantized_vectors.files());
+            }
+        }
+
+        let payload_index = self.payload_index.borrow();
+        for (field, indexes) in &payload_index.field_indexes {
+            let field_bytes = indexes
+                .iter()
+                .map(|index| files_disk_usage(index.files()))
+                .sum::<u64>();
+            *usage.payload_index_bytes.entry(field.clone()).or_default() += field_bytes;
+        }
+
+        usage.payload_storage_bytes = files_disk_usage(self.payload_storage.borrow().files());
+        usage.id_tracker_bytes = files_disk_usage(self.id_tracker.borrow().files());
+
+        usage
+    }
+
+    /// Estimated number of bytes, written to disk by the next flush of vector storages
+    pub fn estimate_vectors_flush_size(&self) -> u64 {
+        self.vector_data
+            .values()
+            .map(|vector_data| vector_data.vector_storage.borrow().estimate_flush_size() as u64)
+            .sum()
+    }
+}
+
+/// Total disk space taken by the given files.
+///
+/// Files which are missing, e.g. removed concurrently, are not counted.
+fn files_disk_usage(files: Vec<PathBuf>) -> u64 {
+    files
+        .into_iter()
+        .map(|path| file_disk_size(path).unwrap_or(0))
+        .sum()
+}
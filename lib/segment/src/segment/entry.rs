@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -6,10 +6,14 @@ use std::sync::atomic::AtomicBool;
 use ahash::AHashMap;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::fs::safe_delete_with_suffix;
+use common::generic_consts::Random;
 use common::types::{DeferredBehavior, TelemetryDetail};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
 use uuid::Uuid;
 
 use super::Segment;
+use super::flush_coordinator::FlushGroup;
 use crate::common::operation_error::{OperationError, OperationResult, SegmentFailedState};
 use crate::common::{
     Flusher, check_named_vectors, check_query_vectors, check_stopped, check_vector_name,
@@ -22,23 +26,29 @@ use crate::data_types::query_context::{
     FormulaContext, QueryContext, QueryIdfStats, SegmentQueryContext,
 };
 use crate::data_types::segment_record::{NamedVectorsOwned, SegmentRecord};
+use crate::data_types::text_query::{TextQuery, TextQueryStatistics};
 use crate::data_types::vectors::{QueryVector, VectorInternal};
 use crate::entry::entry_point::{
     NonAppendableSegmentEntry, ReadSegmentEntry, SegmentEntry, StorageSegmentEntry,
 };
 use crate::id_tracker::{IdTracker, PointMappingsGuard};
 use crate::index::field_index::{CardinalityEstimation, FieldIndex};
+use crate::index::hybrid_index::HybridQuery;
 use crate::index::query_estimator::adjust_for_deferred_points;
-use crate::index::{BuildIndexResult, PayloadIndex, VectorIndex};
+use crate::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
+use crate::index::{BuildIndexResult, PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::payload_storage::PayloadStorage;
-use crate::telemetry::SegmentTelemetry;
+use crate::telemetry::{HnswGraphTelemetry, SegmentTelemetry, VectorStorageTelemetry};
 use crate::types::{
     ExtendedPointId, Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
-    PayloadKeyTypeRef, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentType, SeqNumberType, VectorDataInfo, VectorName, VectorNameBuf, WithPayload, WithVector,
+    PayloadKeyTypeRef, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentDiskUsage,
+    SegmentInfo, SegmentMemoryUsage, SegmentType, SeqNumberType, VectorDataInfo, VectorName,
+    VectorNameBuf, WithPayload, WithVector,
 };
+use crate::utils::mem::MappedFilesResidency;
 use crate::vector_storage::VectorStorage;
+use crate::vector_storage::versioned_vector_storage::VersionedVectorStorage;
 
 /// This is a basic implementation of the trait, meaning that it implements the _actual_ operations with data and not
 /// any kind of proxy or wrapping.
@@ -58,6 +68,15 @@ impl ReadSegmentEntry for Segment {
             .and_then(|internal_id| id_tracker.internal_version(internal_id))
     }
 
+    fn point_expiration(&self, point_id: PointIdType) -> Option<u64> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id)?;
+        self.point_expirations.get(internal_id)
+    }
+
+    fn has_expired_points(&self, now: u64) -> bool {
+        self.point_expirations.expired(now).next().is_some()
+    }
+
     fn search_batch(
         &self,
         vector_name: &VectorName,
@@ -70,10 +89,7 @@ impl ReadSegmentEntry for Segment {
         query_context: &SegmentQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
-        let vector_data = &self
-            .vector_data
-            .get(vector_name)
-            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        let vector_data = self.readable_vector_data(vector_name)?;
         let vector_query_context =
             query_context.get_vector_context(vector_name, self.deferred_internal_id());
         let internal_results = vector_data.vector_index.borrow().search(
@@ -144,15 +160,48 @@ impl ReadSegmentEntry for Segment {
         Ok(vector_opt)
     }
 
+    fn vector_at_version(
+        &self,
+        vector_name: &VectorName,
+        point_id: PointIdType,
+        version: SeqNumberType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<VectorInternal>> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let vector_storage = vector_data.vector_storage.borrow();
+
+        if let Some(versioned) = vector_storage.as_versioned()
+            && versioned.has_history(internal_id)
+        {
+            let vector = versioned.get_vector_at_version(internal_id, version);
+            if let Some(vector) = &vector
+                && vector_storage.is_on_disk()
+            {
+                hw_counter
+                    .vector_io_read()
+                    .incr_delta(vector.estimate_size_in_bytes());
+            }
+            return Ok(vector.map(|vector| vector.to_owned()));
+        }
+        drop(vector_storage);
+
+        let point_version = self.id_tracker.borrow().internal_version(internal_id);
+        if point_version.is_some_and(|point_version| point_version > version) {
+            return Ok(None);
+        }
+        self.vector_by_offset(vector_name, internal_id, hw_counter)
+    }
+
     fn all_vectors(
         &self,
         point_id: PointIdType,
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<NamedVectors<'_>> {
         let mut result = NamedVectors::default();
-        for vector_name in self.vector_data.keys() {
-            if let Some(vec) = self.vector(vector_name, point_id, hw_counter)? {
-                result.insert(vector_name.clone(), vec);
+        for vector_name in self.vector_names() {
+            if let Some(vec) = self.vector(&vector_name, point_id, hw_counter)? {
+                result.insert(vector_name, vec);
             }
         }
         Ok(result)
@@ -167,6 +216,36 @@ impl ReadSegmentEntry for Segment {
         self.payload_by_offset(internal_id, hw_counter)
     }
 
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<Vec<u8>>> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.blob_storage.get::<Random>(internal_id, hw_counter)
+    }
+
+    fn remap_sparse_vector(
+        &self,
+        vector_name: &VectorName,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector> {
+        self.get_sparse_vocabulary(vector_name)?
+            .remap(vector, unknown_tokens)
+    }
+
+    fn sparse_vocabulary(
+        &self,
+        vector_name: &VectorName,
+    ) -> OperationResult<BTreeMap<String, DimId>> {
+        let vocabulary = self.get_sparse_vocabulary(vector_name)?;
+        Ok(vocabulary
+            .iter()
+            .map(|(token, dim_id)| (token.to_owned(), dim_id))
+            .collect())
+    }
+
     fn retrieve(
         &self,
         point_ids: &[PointIdType],
@@ -209,7 +288,7 @@ impl ReadSegmentEntry for Segment {
 
         match with_vector {
             WithVector::Bool(true) => {
-                for vector_name in self.vector_data.keys() {
+                for vector_name in &self.vector_names() {
                     self.read_vectors(
                         vector_name,
                         point_ids,
@@ -349,6 +428,47 @@ impl ReadSegmentEntry for Segment {
         }
     }
 
+    fn text_query_statistics(
+        &self,
+        query: &TextQuery,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<TextQueryStatistics> {
+        self.collect_text_query_statistics(query, hw_counter)
+    }
+
+    fn read_text_scored_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+        deferred_behavior: DeferredBehavior,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        self.filtered_read_by_text_score(
+            limit,
+            filter,
+            query,
+            statistics,
+            is_stopped,
+            hw_counter,
+            deferred_behavior,
+        )
+    }
+
+    fn hybrid_search(
+        &self,
+        query: &HybridQuery,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        self.do_hybrid_search(query, filter, top, params, is_stopped, hw_counter)
+    }
+
     fn read_random_filtered(
         &self,
         limit: usize,
@@ -395,10 +515,7 @@ impl ReadSegmentEntry for Segment {
 
     fn available_vectors_size_in_bytes(&self, vector_name: &VectorName) -> OperationResult<usize> {
         check_vector_name(vector_name, &self.segment_config)?;
-        let vector_data = self
-            .vector_data
-            .get(vector_name)
-            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        let vector_data = self.readable_vector_data(vector_name)?;
         let size = vector_data
             .vector_index
             .borrow()
@@ -459,6 +576,14 @@ impl ReadSegmentEntry for Segment {
         self.segment_type
     }
 
+    fn memory_usage(&self, residency: &MappedFilesResidency) -> SegmentMemoryUsage {
+        self.estimate_memory_usage(residency)
+    }
+
+    fn disk_usage(&self) -> SegmentDiskUsage {
+        self.calculate_disk_usage()
+    }
+
     fn size_info(&self) -> SegmentInfo {
         let num_vectors = self
             .vector_data
@@ -568,7 +693,11 @@ impl ReadSegmentEntry for Segment {
     }
 
     fn vector_names(&self) -> HashSet<VectorNameBuf> {
-        self.vector_data.keys().cloned().collect()
+        self.vector_data
+            .iter()
+            .filter(|(_, vector_data)| !vector_data.is_removed())
+            .map(|(vector_name, _)| vector_name.clone())
+            .collect()
     }
 
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> SegmentTelemetry {
@@ -582,11 +711,34 @@ impl ReadSegmentEntry for Segment {
             })
             .collect();
 
+        let vector_storages = self
+            .vector_data
+            .iter()
+            .map(|(k, v)| VectorStorageTelemetry {
+                vector_name: Some(k.clone()),
+                stats: v.vector_storage.borrow().read_stats(),
+            })
+            .collect();
+
+        let hnsw_graphs = self
+            .vector_data
+            .iter()
+            .filter_map(|(k, v)| match &*v.vector_index.borrow() {
+                VectorIndexEnum::Hnsw(index) => Some(HnswGraphTelemetry {
+                    vector_name: Some(k.clone()),
+                    ..index.graph_telemetry()
+                }),
+                _ => None,
+            })
+            .collect();
+
         SegmentTelemetry {
             info: self.info(),
             config: self.config().clone(),
             vector_index_searches,
             payload_field_indices: self.payload_index.borrow().get_telemetry_data(),
+            vector_storages,
+            hnsw_graphs,
         }
     }
 
@@ -598,6 +750,8 @@ impl ReadSegmentEntry for Segment {
         let QueryIdfStats {
             idf,
             indexed_vectors,
+            bm25: _,
+            total_doc_lengths,
         } = query_context.mut_idf_stats();
 
         for (vector_name, idf) in idf.iter_mut() {
@@ -613,6 +767,10 @@ impl ReadSegmentEntry for Segment {
                 }
 
                 vector_index.fill_idf_statistics(idf, &hw_counter);
+
+                if let Some(total_doc_length) = total_doc_lengths.get_mut(vector_name) {
+                    *total_doc_length += vector_index.total_doc_length();
+                }
             }
         }
     }
@@ -705,6 +863,8 @@ impl StorageSegmentEntry for Segment {
         let state = self.get_state();
         let segment_path = self.segment_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
+        let point_expirations_flusher = self.point_expirations.flusher();
+        let blob_storage_flusher = self.blob_storage.flusher();
         let payload_index_flusher = self.payload_index.borrow().flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
         let persisted_version = self.persisted_version.clone();
@@ -773,28 +933,25 @@ impl StorageSegmentEntry for Segment {
                         "Failed to flush id_tracker mapping: {err}"
                     )),
                 })?;
+                // Flush expirations after mapping, so that expirations of deleted points are only
+                // dropped once the deletion is persisted.
+                point_expirations_flusher().map_err(|err| {
+                    OperationError::service_error(format!(
+                        "Failed to flush point expirations: {err}"
+                    ))
+                })?;
+                // Vectors and payloads are independent of each other, flush them concurrently
+                // and sync segment files once, before versions are flushed.
+                let mut data_flush_group = FlushGroup::default();
                 for vector_storage_flusher in vector_storage_flushers {
-                    vector_storage_flusher().map_err(|err| match err {
-                        OperationError::Cancelled { .. } => err,
-                        _ => OperationError::service_error(format!(
-                            "Failed to flush vector_storage: {err}"
-                        )),
-                    })?;
+                    data_flush_group.add("vector_storage", vector_storage_flusher);
                 }
                 for quantization_flusher in quantization_flushers {
-                    quantization_flusher().map_err(|err| match err {
-                        OperationError::Cancelled { .. } => err,
-                        _ => OperationError::service_error(format!(
-                            "Failed to flush quantized vectors: {err}"
-                        )),
-                    })?;
+                    data_flush_group.add("quantized vectors", quantization_flusher);
                 }
-                payload_index_flusher().map_err(|err| match err {
-                    OperationError::Cancelled { .. } => err,
-                    _ => OperationError::service_error(format!(
-                        "Failed to flush payload_index: {err}"
-                    )),
-                })?;
+                data_flush_group.add("payload_index", payload_index_flusher);
+                data_flush_group.add("blob_storage", blob_storage_flusher);
+                data_flush_group.commit(&segment_path)?;
                 // Id Tracker contains versions of points. We need to flush it after vector_storage and payload_index flush.
                 // This is because vector_storage and payload_index flush are not atomic.
                 // If payload or vector flush fails, we will be able to recover data from WAL.
@@ -890,6 +1047,22 @@ impl NonAppendableSegmentEntry for Segment {
         }
     }
 
+    fn delete_expired_points(
+        &mut self,
+        now: u64,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<usize> {
+        let expired: Vec<_> = self.point_expirations.expired(now).collect();
+
+        // Vectors are kept, same as for regular point deletion, see `delete_point_internal`
+        for &internal_id in &expired {
+            // Also drops the expiration
+            self.delete_point_internal(internal_id, hw_counter)?;
+        }
+
+        Ok(expired.len())
+    }
+
     fn delete_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
         self.handle_segment_version_and_failure(op_num, |segment| {
             segment.payload_index.borrow_mut().drop_index(key)?;
@@ -1038,10 +1211,13 @@ impl SegmentEntry for Segment {
                         .vector_data
                         .get(vector_name)
                         .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
-                    let mut vector_storage = vector_data.vector_storage.borrow_mut();
-                    let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    let is_deleted = vector_data
+                        .vector_storage
+                        .borrow_mut()
+                        .delete_vector(internal_id)?;
 
                     if is_deleted {
+                        vector_data.update_dedup_index(internal_id);
                         segment
                             .version_tracker
                             .set_vector(vector_name, Some(op_num));
@@ -1151,6 +1327,59 @@ impl SegmentEntry for Segment {
             }),
         })
     }
+
+    fn set_point_expiration(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        expires_at: Option<u64>,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment.point_expirations.set(internal_id, expires_at);
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        blob: Option<&[u8]>,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment.blob_storage.set(internal_id, blob, hw_counter)?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
+    fn register_sparse_vocabulary(
+        &mut self,
+        vector_name: &VectorName,
+        mapping: &BTreeMap<String, DimId>,
+    ) -> OperationResult<bool> {
+        let vocabulary = self
+            .sparse_vocabularies
+            .get_mut(vector_name)
+            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        vocabulary.register(
+            mapping
+                .iter()
+                .map(|(token, &dim_id)| (token.as_str(), dim_id)),
+        )
+    }
 }
 
 // The alias is needed because of self_cell limitation.
@@ -0,0 +1,163 @@
+//! Group commit of segment components.
+//!
+//! Flushers of vector storages, quantized vectors, payload index and blob storage don't depend on
+//! each other, so instead of running them one after another, they are executed concurrently on a
+//! dedicated IO pool. Flushers skip syncing their own files, instead the segment directory is
+//! synced with a single barrier once all of them complete. Only then the caller may proceed with
+//! flushers, which mark the data as persisted.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use common::fs::{bulk_sync_dir, defer_sync};
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
+use crate::common::Flusher;
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Flushing is bound by IO, so the pool is not sized by the number of CPUs
+const FLUSH_POOL_THREADS: usize = 8;
+
+static FLUSH_POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+
+fn flush_pool() -> Option<&'static ThreadPool> {
+    FLUSH_POOL
+        .get_or_init(|| {
+            rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("segment-flush-{idx}"))
+                .num_threads(FLUSH_POOL_THREADS)
+                .build()
+                .inspect_err(|err| {
+                    log::error!("Failed to create segment flush pool, flushing in place: {err}");
+                })
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Flushers of segment components, committed together
+#[derive(Default)]
+pub(super) struct FlushGroup {
+    flushers: Vec<(&'static str, Flusher)>,
+}
+
+impl FlushGroup {
+    /// Add flusher of a component, `component` is used in error messages
+    pub fn add(&mut self, component: &'static str, flusher: Flusher) {
+        self.flushers.push((component, flusher));
+    }
+
+    /// Run all flushers of the group with syncing deferred, and sync `dir` once all of them succeed.
+    ///
+    /// All flushers run to completion, even if some of them fail. If any flusher is cancelled,
+    /// the cancellation is returned, so that the caller does not consider data persisted.
+    pub fn commit(self, dir: &Path) -> OperationResult<()> {
+        let Self { flushers } = self;
+
+        let run = |(component, flusher): (&'static str, Flusher)| {
+            defer_sync(flusher).map_err(|err| match err {
+                OperationError::Cancelled { .. } => err,
+                _ => OperationError::service_error(format!("Failed to flush {component}: {err}")),
+            })
+        };
+
+        let results: Vec<OperationResult<()>> = match flush_pool() {
+            Some(pool) if flushers.len() > 1 => {
+                pool.install(|| flushers.into_par_iter().map(run).collect())
+            }
+            _ => flushers.into_iter().map(run).collect(),
+        };
+
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(()) => {}
+                Err(err @ OperationError::Cancelled { .. }) => return Err(err),
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        if let Some(err) = first_error {
+            return Err(err);
+        }
+
+        bulk_sync_dir(dir).map_err(|err| {
+            OperationError::service_error(format!(
+                "Failed to sync {} after flush: {err}",
+                dir.display(),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use common::fs::is_sync_deferred;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_flush_group_runs_all_flushers() {
+        let dir = Builder::new().prefix("flush_group").tempdir().unwrap();
+        let flushed = Arc::new(AtomicUsize::new(0));
+
+        let mut group = FlushGroup::default();
+        for _ in 0..10 {
+            let flushed = flushed.clone();
+            group.add(
+                "component",
+                Box::new(move || {
+                    // Grouped flushers leave syncing to the group
+                    assert!(is_sync_deferred());
+                    flushed.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }),
+            );
+        }
+        group.commit(dir.path()).unwrap();
+        assert_eq!(flushed.load(Ordering::Relaxed), 10);
+        assert!(!is_sync_deferred());
+    }
+
+    #[test]
+    fn test_flush_group_error_precedence() {
+        let dir = Builder::new().prefix("flush_group").tempdir().unwrap();
+        let flushed = Arc::new(AtomicUsize::new(0));
+
+        let mut group = FlushGroup::default();
+        group.add(
+            "failing",
+            Box::new(|| Err(OperationError::service_error("disk is gone"))),
+        );
+        group.add(
+            "cancelled",
+            Box::new(|| Err(OperationError::cancelled("segment is dropped"))),
+        );
+        let flushed_clone = flushed.clone();
+        group.add(
+            "healthy",
+            Box::new(move || {
+                flushed_clone.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }),
+        );
+
+        let result = group.commit(dir.path());
+        assert!(matches!(result, Err(OperationError::Cancelled { .. })));
+        assert_eq!(flushed.load(Ordering::Relaxed), 1);
+
+        let mut group = FlushGroup::default();
+        group.add(
+            "payload_index",
+            Box::new(|| Err(OperationError::service_error("disk is gone"))),
+        );
+        let err = group.commit(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Failed to flush payload_index"));
+    }
+}
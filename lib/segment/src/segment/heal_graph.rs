@@ -0,0 +1,61 @@
+use std::sync::atomic::AtomicBool;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::index::VectorIndexEnum;
+use crate::index::hnsw_index::hnsw::HealedGraph;
+use crate::segment::Segment;
+use crate::types::VectorName;
+
+impl Segment {
+    /// Fraction of vectors linked in the HNSW graph of `vector_name`, which were deleted since
+    /// the graph was built or healed.
+    ///
+    /// Returns `None` if the vector is not indexed with HNSW.
+    pub fn graph_deleted_ratio(&self, vector_name: &VectorName) -> Option<f64> {
+        let vector_data = self.readable_vector_data(vector_name).ok()?;
+        match &*vector_data.vector_index.borrow() {
+            VectorIndexEnum::Hnsw(index) => Some(index.deleted_linked_ratio()),
+            _ => None,
+        }
+    }
+
+    /// Heal the HNSW graph of `vector_name`, rerouting its links around deleted points.
+    ///
+    /// The healed graph is built next to the current one, which stays in use meanwhile.
+    /// Apply it with [`Segment::replace_healed_graph`].
+    ///
+    /// Returns `None` if the vector is not indexed with HNSW, or if the segment is appendable:
+    /// points inserted during healing would not be linked in the healed graph.
+    pub fn heal_graph(
+        &self,
+        vector_name: &VectorName,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<HealedGraph>> {
+        if self.appendable_flag {
+            return Ok(None);
+        }
+
+        let vector_data = self.readable_vector_data(vector_name)?;
+        match &*vector_data.vector_index.borrow() {
+            VectorIndexEnum::Hnsw(index) => index.heal_graph(max_threads, stopped),
+            _ => Ok(None),
+        }
+    }
+
+    /// Replace the HNSW graph of `vector_name` with one built by [`Segment::heal_graph`].
+    #[allow(clippy::needless_pass_by_ref_mut)] // ensure single access to AtomicRefCell vector index
+    pub fn replace_healed_graph(
+        &mut self,
+        vector_name: &VectorName,
+        healed_graph: HealedGraph,
+    ) -> OperationResult<()> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        match &mut *vector_data.vector_index.borrow_mut() {
+            VectorIndexEnum::Hnsw(index) => index.replace_graph(healed_graph),
+            _ => Err(OperationError::service_error(format!(
+                "vector {vector_name} has no HNSW graph to replace",
+            ))),
+        }
+    }
+}
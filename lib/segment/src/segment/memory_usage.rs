@@ -0,0 +1,79 @@
+use std::ops::Deref as _;
+use std::path::PathBuf;
+
+use fs_err as fs;
+
+use super::Segment;
+use crate::id_tracker::IdTracker;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::PayloadStorage;
+use crate::types::SegmentMemoryUsage;
+use crate::utils::mem::MappedFilesResidency;
+use crate::vector_storage::VectorStorage;
+
+impl Segment {
+    pub(super) fn estimate_memory_usage(
+        &self,
+        residency: &MappedFilesResidency,
+    ) -> SegmentMemoryUsage {
+        let mut usage = SegmentMemoryUsage::default();
+
+        for vector_data in self.vector_data.values() {
+            let vector_storage = vector_data.vector_storage.borrow();
+            usage.vector_storage_bytes += files_memory_usage(
+                vector_storage.files(),
+                !vector_storage.is_on_disk(),
+                residency,
+            );
+            usage.cache_bytes += vector_storage.cache_usage_bytes();
+
+            let vector_index = vector_data.vector_index.borrow();
+            usage.vector_index_bytes +=
+                files_memory_usage(vector_index.files(), !vector_index.is_on_disk(), residency);
+
+            if let Some(quantized_vectors) = vector_data.quantized_vectors.borrow().deref() {
+                usage.quantized_vectors_bytes += files_memory_usage(
+                    quantized_vectors.files(),
+                    !quantized_vectors.is_on_disk(),
+                    residency,
+                );
+            }
+        }
+
+        // Field indexes are either memory-mapped, or loaded into RAM from their files
+        usage.payload_index_bytes =
+            files_memory_usage(self.payload_index.borrow().files(), true, residency);
+
+        let payload_storage = self.payload_storage.borrow();
+        usage.payload_storage_bytes = files_memory_usage(
+            payload_storage.files(),
+            !payload_storage.is_on_disk(),
+            residency,
+        );
+
+        // ID trackers keep their mappings in RAM, if they are not memory-mapped
+        usage.id_tracker_bytes =
+            files_memory_usage(self.id_tracker.borrow().files(), true, residency);
+
+        usage
+    }
+}
+
+/// Estimate RAM used by a component, based on its files.
+///
+/// Memory-mapped files count with their resident pages. Other files count with their full size,
+/// if the component keeps its data in RAM.
+fn files_memory_usage(
+    files: Vec<PathBuf>,
+    loaded_in_ram: bool,
+    residency: &MappedFilesResidency,
+) -> usize {
+    files
+        .into_iter()
+        .map(|path| match residency.resident_bytes(&path) {
+            Some(resident_bytes) => resident_bytes as usize,
+            None if loaded_in_ram => fs::metadata(&path).map_or(0, |meta| meta.len() as usize),
+            None => 0,
+        })
+        .sum()
+}
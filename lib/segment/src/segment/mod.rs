@@ -1,13 +1,23 @@
+mod compact_sparse_index;
+mod disk_usage;
 mod entry;
 mod facet;
+mod flush_coordinator;
 mod formula_rescore;
+mod heal_graph;
+mod memory_usage;
 mod order_by;
+mod quantization_quality;
+mod quantized_vector;
+mod requantize;
 mod sampling;
 mod scroll;
 mod search;
 mod segment_ops;
+mod text_query;
 mod version_tracker;
 
+pub mod point_expirations;
 pub mod snapshot;
 
 #[cfg(test)]
@@ -28,14 +38,18 @@ use parking_lot::Mutex;
 use rocksdb::DB;
 use uuid::Uuid;
 
+use self::point_expirations::PointExpirations;
 use self::version_tracker::VersionTracker;
+use crate::blob_storage::MmapBlobStorage;
 use crate::common::operation_error::SegmentFailedState;
 use crate::id_tracker::IdTrackerEnum;
 use crate::index::VectorIndexEnum;
+use crate::index::sparse_index::sparse_vocabulary::SparseVocabulary;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::types::{SegmentConfig, SegmentType, SeqNumberType, VectorNameBuf};
 use crate::vector_storage::VectorStorageEnum;
+use crate::vector_storage::dedup_index::VectorDedupIndex;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
@@ -84,6 +98,12 @@ pub struct Segment {
     pub vector_data: HashMap<VectorNameBuf, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
     pub payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
+    /// Expiration timestamps of points, which are deleted by [`Segment::delete_expired_points`]
+    pub(crate) point_expirations: PointExpirations,
+    /// Binary attachments of points, see [`Segment::set_blob`]
+    pub(crate) blob_storage: MmapBlobStorage,
+    /// Token vocabularies of sparse vectors, see [`Segment::register_sparse_vocabulary`]
+    pub(crate) sparse_vocabularies: HashMap<VectorNameBuf, SparseVocabulary>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -112,6 +132,8 @@ pub struct VectorData {
     pub vector_index: Arc<AtomicRefCell<VectorIndexEnum>>,
     pub vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     pub quantized_vectors: Arc<AtomicRefCell<Option<QuantizedVectors>>>,
+    /// Index of exact duplicates of stored vectors, only maintained if enabled
+    pub dedup_index: Arc<AtomicRefCell<Option<VectorDedupIndex>>>,
 }
 
 impl fmt::Debug for VectorData {
@@ -139,6 +161,7 @@ impl Drop for Segment {
                 vector_index,
                 vector_storage,
                 quantized_vectors,
+                dedup_index: _,
             } = vector_data;
 
             if let Err(e) = vector_index.borrow().clear_cache() {
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::fs::{atomic_save_json, read_json};
+use common::types::PointOffsetType;
+
+use crate::common::Flusher;
+use crate::common::operation_error::OperationResult;
+
+const POINT_EXPIRATIONS_FILE: &str = "point_expirations.json";
+
+/// Current time as seconds since the Unix epoch, to compare with expiration timestamps
+pub fn unix_timestamp_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Expiration timestamps of points in a segment.
+///
+/// Maps internal ids to seconds since the Unix epoch, after which the point is considered expired.
+/// Points without an entry never expire.
+#[derive(Debug)]
+pub struct PointExpirations {
+    path: PathBuf,
+    expirations: BTreeMap<PointOffsetType, u64>,
+    /// Number of changes applied to `expirations`
+    changes: u64,
+    /// Number of changes, which are persisted on disk
+    persisted_changes: Arc<AtomicU64>,
+}
+
+impl PointExpirations {
+    /// Load expirations from the segment directory, if the file exists
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path.join(POINT_EXPIRATIONS_FILE);
+        let expirations = if path.exists() {
+            read_json(&path)?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path,
+            expirations,
+            changes: 0,
+            persisted_changes: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    pub fn get(&self, internal_id: PointOffsetType) -> Option<u64> {
+        self.expirations.get(&internal_id).copied()
+    }
+
+    /// Set or clear the expiration of the point
+    pub fn set(&mut self, internal_id: PointOffsetType, expires_at: Option<u64>) {
+        let changed = match expires_at {
+            Some(expires_at) => {
+                self.expirations.insert(internal_id, expires_at) != Some(expires_at)
+            }
+            None => self.expirations.remove(&internal_id).is_some(),
+        };
+
+        if changed {
+            self.changes += 1;
+        }
+    }
+
+    pub fn is_expired(&self, internal_id: PointOffsetType, now: u64) -> bool {
+        self.get(internal_id)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Internal ids of points, which are expired at `now`
+    pub fn expired(&self, now: u64) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.expirations
+            .iter()
+            .filter(move |&(_, &expires_at)| expires_at <= now)
+            .map(|(&internal_id, _)| internal_id)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let changes = self.changes;
+        if self.persisted_changes.load(Ordering::Relaxed) >= changes {
+            return Box::new(|| Ok(()));
+        }
+
+        let path = self.path.clone();
+        let expirations = self.expirations.clone();
+        let persisted_changes = self.persisted_changes.clone();
+
+        Box::new(move || {
+            // Another flush may have persisted a newer state already
+            if persisted_changes.load(Ordering::Relaxed) >= changes {
+                return Ok(());
+            }
+            atomic_save_json(&path, &expirations)?;
+            persisted_changes.fetch_max(changes, Ordering::Relaxed);
+            Ok(())
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        if self.path.exists() {
+            vec![self.path.clone()]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_expirations_persistence() {
+        let dir = tempfile::Builder::new()
+            .prefix("point_expirations")
+            .tempdir()
+            .unwrap();
+
+        let mut expirations = PointExpirations::open(dir.path()).unwrap();
+        assert!(expirations.files().is_empty());
+
+        expirations.set(1, Some(100));
+        expirations.set(2, Some(200));
+        expirations.set(3, Some(300));
+        expirations.set(3, None);
+        expirations.flusher()().unwrap();
+
+        let expirations = PointExpirations::open(dir.path()).unwrap();
+        assert_eq!(expirations.get(1), Some(100));
+        assert_eq!(expirations.get(3), None);
+        assert!(expirations.is_expired(1, 100));
+        assert!(!expirations.is_expired(2, 100));
+        assert_eq!(expirations.expired(200).collect::<Vec<_>>(), [1, 2]);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
+use common::generic_consts::Random;
+use common::types::{PointOffsetType, ScoredPointOffset};
+
+use super::Segment;
+use crate::common::operation_error::{OperationResult, check_process_stopped};
+use crate::data_types::vectors::QueryVector;
+use crate::id_tracker::IdTracker;
+use crate::types::{QuantizationQualityStats, VectorName};
+use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::{VectorStorage, new_raw_scorer};
+
+impl Segment {
+    /// Estimate how well quantized vectors of `vector_name` approximate the original ones.
+    ///
+    /// Up to `sample_size` random points are used as queries. Each of them is scored against all
+    /// other points of the segment with both original and quantized vectors, comparing the top
+    /// `limit` results and all scores.
+    ///
+    /// Returns `None` if the vector is not quantized.
+    pub fn estimate_quantization_quality(
+        &self,
+        vector_name: &VectorName,
+        sample_size: usize,
+        limit: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<QuantizationQualityStats>> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let quantized_vectors = vector_data.quantized_vectors.borrow();
+        let Some(quantized_vectors) = quantized_vectors.as_ref() else {
+            return Ok(None);
+        };
+        let vector_storage = vector_data.vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let point_mappings = id_tracker.point_mappings();
+
+        let points: Vec<PointOffsetType> = point_mappings
+            .iter_internal_visible(self.deferred_internal_id())
+            .filter(|&point| !vector_storage.is_deleted_vector(point))
+            .collect();
+
+        let mut stats = QuantizationQualityStats::default();
+
+        // Query point itself is excluded from results
+        let limit = limit.min(points.len().saturating_sub(1));
+        if limit == 0 {
+            return Ok(Some(stats));
+        }
+
+        let queries: Vec<PointOffsetType> = point_mappings
+            .iter_random_visible(self.deferred_internal_id())
+            .map(|(_, point)| point)
+            .filter(|&point| !vector_storage.is_deleted_vector(point))
+            .take(sample_size)
+            .collect();
+
+        let mut exact_scores = [0.0; VECTOR_READ_BATCH_SIZE];
+        let mut quantized_scores = [0.0; VECTOR_READ_BATCH_SIZE];
+
+        for query_point in queries {
+            check_process_stopped(stopped)?;
+
+            let query =
+                QueryVector::from(vector_storage.get_vector::<Random>(query_point).to_owned());
+            let exact_scorer = new_raw_scorer(
+                query.clone(),
+                &vector_storage,
+                HardwareCounterCell::disposable(),
+            )?;
+            let quantized_scorer =
+                quantized_vectors.raw_scorer(query, HardwareCounterCell::disposable())?;
+
+            let mut exact_top = FixedLengthPriorityQueue::new(limit);
+            let mut quantized_top = FixedLengthPriorityQueue::new(limit);
+
+            for batch in points.chunks(VECTOR_READ_BATCH_SIZE) {
+                let exact_scores = &mut exact_scores[..batch.len()];
+                let quantized_scores = &mut quantized_scores[..batch.len()];
+                exact_scorer.score_points(batch, exact_scores);
+                quantized_scorer.score_points(batch, quantized_scores);
+
+                for ((&idx, &exact_score), &quantized_score) in batch
+                    .iter()
+                    .zip(exact_scores.iter())
+                    .zip(quantized_scores.iter())
+                {
+                    if idx == query_point {
+                        continue;
+                    }
+                    stats.add_scores(exact_score, quantized_score);
+                    exact_top.push(ScoredPointOffset {
+                        idx,
+                        score: exact_score,
+                    });
+                    quantized_top.push(ScoredPointOffset {
+                        idx,
+                        score: quantized_score,
+                    });
+                }
+            }
+
+            let exact_ids: HashSet<PointOffsetType> =
+                exact_top.iter_unsorted().map(|scored| scored.idx).collect();
+            let found = quantized_top
+                .iter_unsorted()
+                .filter(|scored| exact_ids.contains(&scored.idx))
+                .count();
+
+            stats.queries += 1;
+            stats.recall_sum += found as f64 / exact_ids.len() as f64;
+        }
+
+        Ok(Some(stats))
+    }
+}
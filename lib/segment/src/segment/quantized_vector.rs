@@ -0,0 +1,43 @@
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::id_tracker::IdTracker;
+use crate::types::{PointIdType, QuantizedVectorData, VectorName};
+use crate::vector_storage::VectorStorage;
+
+impl Segment {
+    /// Quantized representation of vector `vector_name` of point `point_id`.
+    ///
+    /// Returns `None` if the point is not in the segment, or it has no such vector.
+    pub fn quantized_vector(
+        &self,
+        vector_name: &VectorName,
+        point_id: PointIdType,
+    ) -> OperationResult<Option<QuantizedVectorData>> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let Some(internal_id) = self.id_tracker.borrow().internal_id(point_id) else {
+            return Ok(None);
+        };
+        if vector_data
+            .vector_storage
+            .borrow()
+            .is_deleted_vector(internal_id)
+        {
+            return Ok(None);
+        }
+
+        let quantized_vectors = vector_data.quantized_vectors.borrow();
+        let Some(quantized_vectors) = quantized_vectors.as_ref() else {
+            return Err(OperationError::validation_error(format!(
+                "vector {vector_name} of point {point_id} is not quantized",
+            )));
+        };
+        quantized_vectors
+            .get_quantized_vector_data(internal_id)
+            .map(Some)
+            .ok_or_else(|| {
+                OperationError::validation_error(format!(
+                    "quantized multivector {vector_name} can't be read",
+                ))
+            })
+    }
+}
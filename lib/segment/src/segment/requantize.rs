@@ -0,0 +1,180 @@
+use std::sync::atomic::AtomicBool;
+
+use fs_err as fs;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::segment::Segment;
+use crate::segment_constructor::get_vector_storage_path;
+use crate::types::{QuantizationConfig, VectorName};
+use crate::vector_storage::quantized::quantized_vectors::{
+    QuantizedVectors, QuantizedVectorsStorageType,
+};
+
+impl Segment {
+    /// Retrain quantization of vector `vector_name` on current vectors of the segment.
+    ///
+    /// New quantized vectors are built next to the current ones, which stay in use meanwhile.
+    /// Apply them with [`Segment::replace_quantized_vectors`].
+    ///
+    /// Returns `None` if the vector is not quantized, or if the segment is appendable: vectors
+    /// updated during retraining would not get into the new quantized vectors.
+    pub fn requantize(
+        &self,
+        vector_name: &VectorName,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<QuantizedVectors>> {
+        if self.appendable_flag {
+            return Ok(None);
+        }
+
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let (quantization_config, storage_type) = match &*vector_data.quantized_vectors.borrow() {
+            Some(quantized_vectors) => {
+                let config = quantized_vectors.config();
+                (config.quantization_config.clone(), config.storage_type)
+            }
+            None => return Ok(None),
+        };
+
+        self.build_requantized_vectors(
+            vector_name,
+            &quantization_config,
+            storage_type,
+            max_threads,
+            stopped,
+        )
+        .map(Some)
+    }
+
+    /// Whether quantized vectors of `vector_name` have to be rebuilt to match
+    /// `quantization_config`, and it can be done in place with [`Segment::rebuild_quantization`].
+    pub fn requires_quantization_rebuild(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+    ) -> bool {
+        if self.appendable_flag {
+            return false;
+        }
+
+        let Some(vector_config) = self.segment_config.vector_data.get(vector_name) else {
+            return false;
+        };
+
+        vector_config
+            .quantization_config
+            .as_ref()
+            .is_some_and(|current| current.mismatch_requires_rebuild(quantization_config))
+            && vector_config.can_requantize_in_place(quantization_config)
+    }
+
+    /// Rebuild quantized vectors of `vector_name` with changed `quantization_config`, keeping
+    /// vector storage and index of the segment as they are.
+    ///
+    /// Like with [`Segment::requantize`], current quantized vectors stay in use until new ones
+    /// are applied with [`Segment::replace_quantized_vectors`].
+    ///
+    /// Returns `None` if no rebuild is required, or if it can't be done in place.
+    pub fn rebuild_quantization(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<QuantizedVectors>> {
+        if !self.requires_quantization_rebuild(vector_name, quantization_config) {
+            return Ok(None);
+        }
+
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let storage_type = match &*vector_data.quantized_vectors.borrow() {
+            Some(quantized_vectors) => quantized_vectors.config().storage_type,
+            None => return Ok(None),
+        };
+
+        self.build_requantized_vectors(
+            vector_name,
+            quantization_config,
+            storage_type,
+            max_threads,
+            stopped,
+        )
+        .map(Some)
+    }
+
+    fn build_requantized_vectors(
+        &self,
+        vector_name: &VectorName,
+        quantization_config: &QuantizationConfig,
+        storage_type: QuantizedVectorsStorageType,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectors> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+
+        let vector_storage_path = get_vector_storage_path(&self.segment_path, vector_name);
+        let requantize_path = QuantizedVectors::get_requantize_path(&vector_storage_path);
+        if requantize_path.exists() {
+            fs::remove_dir_all(&requantize_path)?;
+        }
+        fs::create_dir_all(&requantize_path)?;
+
+        QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            quantization_config,
+            storage_type,
+            &requantize_path,
+            max_threads,
+            stopped,
+        )
+        .inspect_err(|_| {
+            // Remove incomplete quantized vectors right away, loading would discard them anyway
+            let _ = fs::remove_dir_all(&requantize_path);
+        })
+    }
+
+    /// Replace quantized vectors of `vector_name` with ones built by [`Segment::requantize`] or
+    /// [`Segment::rebuild_quantization`].
+    ///
+    /// If quantization config has changed, the segment config is updated accordingly.
+    #[allow(clippy::needless_pass_by_ref_mut)] // ensure single access to AtomicRefCell quantized vectors
+    pub fn replace_quantized_vectors(
+        &mut self,
+        vector_name: &VectorName,
+        quantized_vectors: QuantizedVectors,
+    ) -> OperationResult<()> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        if vector_data.quantized_vectors.borrow().is_none() {
+            return Err(OperationError::service_error(format!(
+                "vector {vector_name} has no quantized vectors to replace",
+            )));
+        }
+
+        let config = quantized_vectors.config().clone();
+        let quantization_config = config.quantization_config.clone();
+        drop(quantized_vectors);
+
+        // Files of current quantized vectors are replaced, but they stay mapped until dropped
+        let vector_storage_path = get_vector_storage_path(&self.segment_path, vector_name);
+        QuantizedVectors::finish_requantization(&vector_storage_path)?;
+
+        let quantized_vectors = QuantizedVectors::load_impl(
+            config,
+            &vector_data.vector_storage.borrow(),
+            &vector_storage_path,
+        )?;
+        *vector_data.quantized_vectors.borrow_mut() = Some(quantized_vectors);
+
+        // Quantized vectors are loaded with their own saved config, so if the state is not saved
+        // due to a crash, the segment is only picked up for a rebuild once again
+        if let Some(vector_config) = self.segment_config.vector_data.get_mut(vector_name)
+            && vector_config.quantization_config.as_ref() != Some(&quantization_config)
+        {
+            vector_config.quantization_config = Some(quantization_config);
+            self.save_current_state()?;
+        }
+
+        Ok(())
+    }
+}
@@ -4,20 +4,19 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::types::{DeferredBehavior, ScoredPointOffset};
 
 use super::Segment;
+use crate::common::check_vector;
 use crate::common::operation_error::{OperationError, OperationResult};
 #[cfg(feature = "testing")]
 use crate::data_types::query_context::QueryContext;
 use crate::data_types::segment_record::SegmentRecord;
-#[cfg(feature = "testing")]
-use crate::data_types::vectors::QueryVector;
-use crate::data_types::vectors::VectorStructInternal;
+use crate::data_types::vectors::{QueryVector, VectorInternal, VectorStructInternal};
 use crate::entry::ReadSegmentEntry;
 use crate::id_tracker::IdTracker;
+use crate::index::PayloadIndex;
+use crate::index::hybrid_index::{HybridIndex, HybridQuery};
 #[cfg(feature = "testing")]
 use crate::types::VectorName;
-#[cfg(feature = "testing")]
-use crate::types::{Filter, SearchParams};
-use crate::types::{ScoredPoint, WithPayload, WithVector};
+use crate::types::{Filter, ScoredPoint, SearchParams, WithPayload, WithVector};
 
 impl Segment {
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
@@ -103,6 +102,71 @@ impl Segment {
         Ok(results)
     }
 
+    /// Search by the dense and sparse vectors of the same vector family at once.
+    ///
+    /// Points are traversed over the dense index and scored by both parts, see [`HybridIndex`].
+    /// Returned points have no payload and vectors.
+    pub(super) fn do_hybrid_search(
+        &self,
+        query: &HybridQuery,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let HybridQuery {
+            dense_using: dense_vector_name,
+            sparse_using: sparse_vector_name,
+            ..
+        } = query;
+
+        check_vector(
+            dense_vector_name,
+            &QueryVector::Nearest(VectorInternal::Dense(query.dense.clone())),
+            &self.segment_config,
+        )?;
+        if !self
+            .segment_config
+            .sparse_vector_data
+            .contains_key(sparse_vector_name)
+        {
+            return Err(OperationError::vector_name_not_exists(sparse_vector_name));
+        }
+
+        let dense_data = self.readable_vector_data(dense_vector_name)?;
+        let sparse_data = self.readable_vector_data(sparse_vector_name)?;
+        let dense_index = dense_data.vector_index.borrow();
+        let dense_storage = dense_data.vector_storage.borrow();
+        let sparse_storage = sparse_data.vector_storage.borrow();
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let filter_context = filter
+            .map(|filter| payload_index.filter_context(filter, hw_counter))
+            .transpose()?;
+
+        let internal_result = HybridIndex::new(&dense_index, &dense_storage, &sparse_storage)
+            .search(
+                query,
+                filter_context,
+                id_tracker.deleted_point_bitslice(),
+                top,
+                params,
+                self.deferred_internal_id(),
+                hw_counter.fork(),
+                is_stopped,
+            )?;
+
+        self.process_search_result(
+            internal_result,
+            &WithPayload::from(false),
+            &WithVector::Bool(false),
+            hw_counter,
+            is_stopped,
+        )
+    }
+
     /// This function is a simplified version of `search_batch` intended for testing purposes.
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "testing")]
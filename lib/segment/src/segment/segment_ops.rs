@@ -51,10 +51,15 @@ impl Segment {
     ) -> OperationResult<()> {
         debug_assert!(self.is_appendable());
         check_named_vectors(vectors, &self.segment_config)?;
+        self.check_vectors_not_removed(vectors)?;
         for (vector_name, vector_data) in self.vector_data.iter_mut() {
             let vector = vectors.get(vector_name);
+            if vector.is_some() {
+                vector_data.set_insert_version(op_num);
+            }
             let mut vector_index = vector_data.vector_index.borrow_mut();
             vector_index.update_vector(internal_id, vector, hw_counter)?;
+            vector_data.update_dedup_index(internal_id);
             self.version_tracker.set_vector(vector_name, Some(op_num));
         }
         Ok(())
@@ -83,10 +88,13 @@ impl Segment {
     ) -> OperationResult<()> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
+        self.check_vectors_not_removed(&vectors)?;
         for (vector_name, new_vector) in vectors {
             let vector_data = &self.vector_data[vector_name.as_ref()];
+            vector_data.set_insert_version(op_num);
             let mut vector_index = vector_data.vector_index.borrow_mut();
             vector_index.update_vector(internal_id, Some(new_vector.as_vec_ref()), hw_counter)?;
+            vector_data.update_dedup_index(internal_id);
             self.version_tracker.set_vector(&vector_name, Some(op_num));
         }
         Ok(())
@@ -106,17 +114,36 @@ impl Segment {
     ) -> OperationResult<PointOffsetType> {
         debug_assert!(self.is_appendable());
         check_named_vectors(vectors, &self.segment_config)?;
+        self.check_vectors_not_removed(vectors)?;
         let new_index = self.id_tracker.borrow().total_point_count() as PointOffsetType;
         for (vector_name, vector_data) in self.vector_data.iter_mut() {
             let vector_opt = vectors.get(vector_name);
+            if vector_opt.is_some() {
+                vector_data.set_insert_version(op_num);
+            }
             let mut vector_index = vector_data.vector_index.borrow_mut();
             vector_index.update_vector(new_index, vector_opt, hw_counter)?;
+            vector_data.update_dedup_index(new_index);
             self.version_tracker.set_vector(vector_name, Some(op_num));
         }
         self.id_tracker.borrow_mut().set_link(point_id, new_index)?;
         Ok(new_index)
     }
 
+    /// Reject writes of vectors, which are removed from this segment
+    fn check_vectors_not_removed(&self, vectors: &NamedVectors) -> OperationResult<()> {
+        for vector_name in vectors.keys() {
+            let is_removed = self
+                .vector_data
+                .get(vector_name)
+                .is_some_and(|vector_data| vector_data.is_removed());
+            if is_removed {
+                return Err(OperationError::vector_name_removed(vector_name));
+            }
+        }
+        Ok(())
+    }
+
     /// Operation wrapped, which handles previous and new errors in the segment, automatically
     /// updates versions and skips operations if the segment version is too old
     ///
@@ -316,6 +343,8 @@ impl Segment {
         let is_point_already_deleted = id_tracker.is_deleted_point(internal_id);
 
         id_tracker.drop_internal(internal_id)?;
+        self.point_expirations.set(internal_id, None);
+        self.blob_storage.delete(internal_id)?;
 
         let deferred_point_status = self.deferred_point_status.as_mut();
 
@@ -423,10 +452,7 @@ impl Segment {
         mut callback: impl FnMut(PointOffsetType, VectorInternal),
     ) -> OperationResult<()> {
         check_vector_name(vector_name, &self.segment_config)?;
-        let vector_data = &self
-            .vector_data
-            .get(vector_name)
-            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        let vector_data = self.readable_vector_data(vector_name)?;
         let vector_storage = vector_data.vector_storage.borrow();
         let total_vectors = vector_storage.total_vector_count();
 
@@ -575,6 +601,7 @@ impl Segment {
     /// - external id without internal
     /// - internal id without version
     /// - internal id without vector
+    /// - vector files not matching their checksums
     ///
     /// A shard can still be consistent with an inconsistent segment as points are merged based on their version.
     ///
@@ -635,10 +662,20 @@ impl Segment {
             }
         }
 
+        // check vector files against checksums, to detect disk corruption
+        let mut has_corrupted_vectors = false;
+        for (vector_name, vector_data) in &self.vector_data {
+            if let Err(err) = vector_data.vector_storage.borrow().verify_integrity() {
+                log::error!("Vector storage '{vector_name}' is corrupted: {err}");
+                has_corrupted_vectors = true;
+            }
+        }
+
         let is_inconsistent = has_dangling_internal_ids
             || has_dangling_external_ids
             || has_internal_ids_without_version
-            || has_internal_ids_without_vector;
+            || has_internal_ids_without_vector
+            || has_corrupted_vectors;
 
         if is_inconsistent {
             Err(OperationError::service_error(
@@ -652,9 +689,7 @@ impl Segment {
     pub fn available_vector_count(&self, vector_name: &VectorName) -> OperationResult<usize> {
         check_vector_name(vector_name, &self.segment_config)?;
         Ok(self
-            .vector_data
-            .get(vector_name)
-            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?
+            .readable_vector_data(vector_name)?
             .vector_storage
             .borrow()
             .available_vector_count())
@@ -217,6 +217,12 @@ impl Segment {
 
         files.extend(self.payload_index.borrow().files());
         files.extend(self.payload_storage.borrow().files());
+        files.extend(self.point_expirations.files());
+        files.extend(self.blob_storage.files());
+
+        for vocabulary in self.sparse_vocabularies.values() {
+            files.extend(vocabulary.files());
+        }
 
         files
     }
@@ -255,6 +261,13 @@ pub fn snapshot_files(
     tar: &tar_ext::BuilderExt<impl Write + Seek>,
     include_if: impl Fn(&Path) -> bool,
 ) -> OperationResult<()> {
+    // Freeze vector storages first, archiving their large files takes most of the time
+    let vector_storage_views = segment
+        .vector_data
+        .values()
+        .map(|vector_data| vector_data.vector_storage.borrow().snapshot_view(temp_path))
+        .collect::<OperationResult<Vec<_>>>()?;
+
     // use temp_path for intermediary files
     let temp_path = temp_path.join(format!("segment-{}", Uuid::new_v4()));
 
@@ -281,7 +294,9 @@ pub fn snapshot_files(
 
     let tar = tar.descend(Path::new(SNAPSHOT_FILES_PATH))?;
 
-    for vector_data in segment.vector_data.values() {
+    for (vector_data, vector_storage_view) in
+        segment.vector_data.values().zip(&vector_storage_views)
+    {
         for file in vector_data.vector_index.borrow().files() {
             let stripped_path = strip_prefix(&file, &segment.segment_path)?;
 
@@ -291,12 +306,12 @@ pub fn snapshot_files(
             }
         }
 
-        for file in vector_data.vector_storage.borrow().files() {
-            let stripped_path = strip_prefix(&file, &segment.segment_path)?;
+        for (frozen_file, file) in vector_storage_view.files() {
+            let stripped_path = strip_prefix(file, &segment.segment_path)?;
 
             if include_if(stripped_path) {
-                tar.blocking_append_file(&file, stripped_path)
-                    .map_err(|err| failed_to_add("vector storage file", &file, err))?;
+                tar.blocking_append_file(frozen_file, stripped_path)
+                    .map_err(|err| failed_to_add("vector storage file", file, err))?;
             }
         }
 
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 
 use ahash::AHashSet;
@@ -16,6 +17,7 @@ use sparse::common::sparse_vector::SparseVector;
 use tempfile::{Builder, TempDir};
 
 use super::*;
+use crate::common::operation_error::OperationError;
 use crate::common::operation_error::OperationError::PointIdError;
 use crate::common::{check_named_vectors, check_vector, check_vector_name};
 use crate::data_types::facets::{FacetParams, FacetValue};
@@ -30,19 +32,26 @@ use crate::entry::entry_point::{
 };
 use crate::entry::{SnapshotEntry as _, StorageSegmentEntry as _};
 use crate::id_tracker::IdTracker;
+use crate::index::hybrid_index::HybridQuery;
 use crate::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use crate::json_path::JsonPath;
 use crate::segment_constructor::simple_segment_constructor::{
     VECTOR1_NAME, VECTOR2_NAME, build_multivec_segment, build_simple_segment,
 };
-use crate::segment_constructor::{build_segment, load_segment};
+use crate::segment_constructor::{build_segment, get_vector_storage_path, load_segment};
 use crate::types::{
-    Condition, Distance, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Indexes, Match,
-    Payload, PayloadContainer, PayloadFieldSchema, PayloadSchemaType, PointIdType, SearchParams,
-    SnapshotFormat, SparseVectorDataConfig, SparseVectorStorageType, ValueVariants,
+    CompressionRatio, Condition, Distance, ExtendedPointId, FieldCondition, Filter, HasIdCondition,
+    Indexes, Match, Payload, PayloadContainer, PayloadFieldSchema, PayloadSchemaType, PointIdType,
+    ProductQuantizationConfig, QuantizationConfig, ScalarQuantizationConfig, ScalarType,
+    SearchParams, SnapshotFormat, SparseVectorDataConfig, SparseVectorStorageType, ValueVariants,
     VectorDataConfig, VectorStorageType, WithPayload, WithVector,
 };
 use crate::utils::maybe_arc::MaybeArc;
+use crate::vector_storage::VectorStorage as _;
+use crate::vector_storage::dedup_index::VectorDedupIndex;
+use crate::vector_storage::quantized::quantized_vectors::{
+    QUANTIZED_CONFIG_PATH, QuantizedVectors, QuantizedVectorsStorageType,
+};
 use crate::vector_storage::query::{FeedbackItem, NaiveFeedbackCoefficients, NaiveFeedbackQuery};
 
 fn init_logger() {
@@ -777,6 +786,10 @@ fn create_deferred_segment(
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: HashMap::from_iter([
@@ -787,6 +800,7 @@ fn create_deferred_segment(
                         index: SparseIndexConfig::new(Some(1), SparseIndexType::MutableRam, None),
                         storage_type: SparseVectorStorageType::Mmap,
                         modifier: None,
+                        bm25: None,
                     },
                 ),
                 (
@@ -799,6 +813,7 @@ fn create_deferred_segment(
                         ),
                         storage_type: SparseVectorStorageType::Mmap,
                         modifier: None,
+                        bm25: None,
                     },
                 ),
             ]),
@@ -1507,3 +1522,722 @@ fn test_deleted_deferred_point_count() {
         assert_eq!(segment.available_point_count_without_deferred(), N_POINTS);
     }
 }
+
+#[test]
+fn test_delete_expired_points() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+    segment
+        .upsert_point(1, 1.into(), only_default_vector(&[1.0, 0.0]), &hw_counter)
+        .unwrap();
+    segment
+        .upsert_point(2, 2.into(), only_default_vector(&[0.0, 1.0]), &hw_counter)
+        .unwrap();
+
+    assert!(
+        segment
+            .set_point_expiration(3, 1.into(), Some(100))
+            .unwrap()
+    );
+    assert!(
+        segment
+            .set_point_expiration(4, 2.into(), Some(200))
+            .unwrap()
+    );
+    assert!(matches!(
+        segment.set_point_expiration(5, 3.into(), Some(100)),
+        Err(PointIdError { .. }),
+    ));
+
+    assert_eq!(segment.point_expiration(1.into()), Some(100));
+    assert!(!segment.has_expired_points(99));
+    assert!(segment.has_expired_points(100));
+
+    assert_eq!(segment.delete_expired_points(100, &hw_counter).unwrap(), 1);
+    assert!(!segment.has_point(1.into()));
+    assert!(segment.has_point(2.into()));
+    assert!(!segment.has_expired_points(100));
+
+    // Like regular deletions, expiration leaves vectors to the optimizer
+    let vector_storage = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .vector_storage
+        .borrow();
+    assert_eq!(vector_storage.deleted_vector_count(), 0);
+    drop(vector_storage);
+
+    // Expirations are persisted
+    segment.flush(true).unwrap();
+    let path = segment.segment_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path, Uuid::nil(), None, &AtomicBool::new(false)).unwrap();
+    assert_eq!(segment.point_expiration(1.into()), None);
+    assert_eq!(segment.point_expiration(2.into()), Some(200));
+}
+
+#[test]
+fn test_point_blobs() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+    segment
+        .upsert_point(1, 1.into(), only_default_vector(&[1.0, 0.0]), &hw_counter)
+        .unwrap();
+    segment
+        .upsert_point(2, 2.into(), only_default_vector(&[0.0, 1.0]), &hw_counter)
+        .unwrap();
+
+    segment
+        .set_blob(3, 1.into(), Some(b"first"), &hw_counter)
+        .unwrap();
+    segment
+        .set_blob(4, 1.into(), Some(b"second"), &hw_counter)
+        .unwrap();
+    segment
+        .set_blob(5, 2.into(), Some(b"deleted"), &hw_counter)
+        .unwrap();
+    assert!(matches!(
+        segment.set_blob(6, 3.into(), Some(b"missing"), &hw_counter),
+        Err(PointIdError { .. }),
+    ));
+
+    assert_eq!(
+        segment.blob(1.into(), &hw_counter).unwrap().as_deref(),
+        Some(b"second".as_slice()),
+    );
+
+    assert!(segment.set_blob(7, 2.into(), None, &hw_counter).unwrap());
+    assert_eq!(segment.blob(2.into(), &hw_counter).unwrap(), None);
+
+    // Blobs are persisted
+    segment.flush(true).unwrap();
+    let path = segment.segment_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path, Uuid::nil(), None, &AtomicBool::new(false)).unwrap();
+    assert_eq!(
+        segment.blob(1.into(), &hw_counter).unwrap().as_deref(),
+        Some(b"second".as_slice()),
+    );
+}
+
+#[test]
+fn test_find_duplicates() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+
+    let upsert = |segment: &mut Segment, op_num, point_id: u64, vector: &[f32]| {
+        segment
+            .upsert_point(
+                op_num,
+                point_id.into(),
+                only_default_vector(vector),
+                &hw_counter,
+            )
+            .unwrap();
+    };
+    upsert(&mut segment, 1, 1, &[1.0, 2.0]);
+    upsert(&mut segment, 2, 2, &[1.0, 2.0]);
+    upsert(&mut segment, 3, 3, &[2.0, 1.0]);
+
+    // Without the index, duplicates are found by a temporary one
+    let duplicates = segment
+        .find_duplicates(DEFAULT_VECTOR_NAME, &stopped)
+        .unwrap();
+    assert_eq!(duplicates, vec![vec![1.into(), 2.into()]]);
+
+    let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+    let dedup_index =
+        VectorDedupIndex::build(&vector_data.vector_storage.borrow(), &stopped).unwrap();
+    *vector_data.dedup_index.borrow_mut() = Some(dedup_index);
+
+    // Index is kept in sync with updates and deletions
+    upsert(&mut segment, 4, 3, &[1.0, 2.0]);
+    upsert(&mut segment, 5, 4, &[2.0, 1.0]);
+    upsert(&mut segment, 6, 5, &[2.0, 1.0]);
+    segment
+        .delete_vector(7, 1.into(), DEFAULT_VECTOR_NAME)
+        .unwrap();
+    segment.delete_point(8, 5.into(), &hw_counter).unwrap();
+
+    let duplicates = segment
+        .find_duplicates(DEFAULT_VECTOR_NAME, &stopped)
+        .unwrap();
+    assert_eq!(duplicates, vec![vec![2.into(), 3.into()]]);
+}
+
+#[test]
+fn test_remove_vector_name() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_multivec_segment(dir.path(), 2, 2, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+
+    segment
+        .upsert_point(
+            1,
+            1.into(),
+            NamedVectors::from_pairs([
+                (VECTOR1_NAME.into(), vec![1.0, 0.0]),
+                (VECTOR2_NAME.into(), vec![0.0, 1.0]),
+            ]),
+            &hw_counter,
+        )
+        .unwrap();
+
+    assert!(segment.remove_vector_name(VECTOR2_NAME).unwrap());
+    assert!(!segment.remove_vector_name(VECTOR2_NAME).unwrap());
+    assert!(matches!(
+        segment.remove_vector_name("missing"),
+        Err(OperationError::VectorNameNotExists { .. }),
+    ));
+
+    let assert_removed = |segment: &Segment| {
+        assert!(matches!(
+            segment.vector(VECTOR2_NAME, 1.into(), &hw_counter),
+            Err(OperationError::VectorNameRemoved { .. }),
+        ));
+        assert!(matches!(
+            segment.search(
+                VECTOR2_NAME,
+                &[1.0, 1.0].into(),
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                10,
+                None,
+            ),
+            Err(OperationError::VectorNameRemoved { .. }),
+        ));
+        assert_eq!(
+            segment.vector_names(),
+            HashSet::from([VECTOR1_NAME.to_owned()]),
+        );
+
+        let vectors = segment.all_vectors(1.into(), &hw_counter).unwrap();
+        assert!(vectors.get(VECTOR1_NAME).is_some());
+        assert!(vectors.get(VECTOR2_NAME).is_none());
+    };
+    assert_removed(&segment);
+
+    // Points can still be inserted, but not with the removed vector
+    segment
+        .upsert_point(
+            2,
+            2.into(),
+            NamedVectors::from_pairs([(VECTOR1_NAME.into(), vec![1.0, 1.0])]),
+            &hw_counter,
+        )
+        .unwrap();
+    assert!(matches!(
+        segment.upsert_point(
+            3,
+            3.into(),
+            NamedVectors::from_pairs([(VECTOR2_NAME.into(), vec![1.0, 1.0])]),
+            &hw_counter,
+        ),
+        Err(OperationError::VectorNameRemoved { .. }),
+    ));
+
+    // Removal is persisted
+    segment.flush(true).unwrap();
+    let path = segment.segment_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path, Uuid::nil(), None, &AtomicBool::new(false)).unwrap();
+    assert_removed(&segment);
+    assert_eq!(segment.available_point_count(), 2);
+}
+
+#[test]
+fn test_requantize() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 8, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for n in 0..100u64 {
+        let vector: Vec<f32> = (0..8).map(|_| rng.random_range(-1.0..1.0)).collect();
+        segment
+            .upsert_point(n, n.into(), only_default_vector(&vector), &hw_counter)
+            .unwrap();
+    }
+
+    // Vectors of appendable segments may change during retraining
+    assert!(
+        segment
+            .requantize(DEFAULT_VECTOR_NAME, 1, &stopped)
+            .unwrap()
+            .is_none()
+    );
+    segment.appendable_flag = false;
+
+    // Nothing to retrain without quantization
+    assert!(
+        segment
+            .requantize(DEFAULT_VECTOR_NAME, 1, &stopped)
+            .unwrap()
+            .is_none()
+    );
+
+    let vector_storage_path = get_vector_storage_path(&segment.segment_path, DEFAULT_VECTOR_NAME);
+    let quantization_config: QuantizationConfig = ProductQuantizationConfig {
+        compression: CompressionRatio::X4,
+        always_ram: None,
+        drop_originals: None,
+    }
+    .into();
+    {
+        let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+        let quantized_vectors = QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            &quantization_config,
+            QuantizedVectorsStorageType::Immutable,
+            &vector_storage_path,
+            1,
+            &stopped,
+        )
+        .unwrap();
+        *vector_data.quantized_vectors.borrow_mut() = Some(quantized_vectors);
+    }
+
+    let requantized = segment
+        .requantize(DEFAULT_VECTOR_NAME, 1, &stopped)
+        .unwrap()
+        .unwrap();
+    let requantize_path = QuantizedVectors::get_requantize_path(&vector_storage_path);
+    assert!(requantize_path.join(QUANTIZED_CONFIG_PATH).exists());
+
+    segment
+        .replace_quantized_vectors(DEFAULT_VECTOR_NAME, requantized)
+        .unwrap();
+    assert!(!requantize_path.exists());
+
+    let quantized_vectors = segment.vector_data[DEFAULT_VECTOR_NAME]
+        .quantized_vectors
+        .borrow();
+    let files = quantized_vectors.as_ref().unwrap().files();
+    assert!(!files.is_empty());
+    for file in files {
+        assert_eq!(file.parent(), Some(vector_storage_path.as_path()));
+        assert!(file.exists());
+    }
+}
+
+#[test]
+fn test_rebuild_quantization() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 8, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for n in 0..100u64 {
+        let vector: Vec<f32> = (0..8).map(|_| rng.random_range(-1.0..1.0)).collect();
+        segment
+            .upsert_point(n, n.into(), only_default_vector(&vector), &hw_counter)
+            .unwrap();
+    }
+
+    let vector_storage_path = get_vector_storage_path(&segment.segment_path, DEFAULT_VECTOR_NAME);
+    let quantization_config: QuantizationConfig = ScalarQuantizationConfig {
+        r#type: ScalarType::Int8,
+        quantile: None,
+        always_ram: None,
+        drop_originals: None,
+    }
+    .into();
+    {
+        let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+        let quantized_vectors = QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            &quantization_config,
+            QuantizedVectorsStorageType::Immutable,
+            &vector_storage_path,
+            1,
+            &stopped,
+        )
+        .unwrap();
+        *vector_data.quantized_vectors.borrow_mut() = Some(quantized_vectors);
+    }
+    segment
+        .segment_config
+        .vector_data
+        .get_mut(DEFAULT_VECTOR_NAME)
+        .unwrap()
+        .quantization_config = Some(quantization_config.clone());
+
+    let target_config: QuantizationConfig = ProductQuantizationConfig {
+        compression: CompressionRatio::X4,
+        always_ram: None,
+        drop_originals: None,
+    }
+    .into();
+
+    // Vectors of appendable segments may change during rebuild
+    assert!(!segment.requires_quantization_rebuild(DEFAULT_VECTOR_NAME, &target_config));
+    segment.appendable_flag = false;
+
+    // Nothing to rebuild if config is not changed
+    assert!(!segment.requires_quantization_rebuild(DEFAULT_VECTOR_NAME, &quantization_config));
+
+    // Dropping original vectors requires a full segment rebuild
+    let dropping_config: QuantizationConfig = ProductQuantizationConfig {
+        compression: CompressionRatio::X4,
+        always_ram: None,
+        drop_originals: Some(true),
+    }
+    .into();
+    assert!(!segment.requires_quantization_rebuild(DEFAULT_VECTOR_NAME, &dropping_config));
+    assert!(
+        segment
+            .rebuild_quantization(DEFAULT_VECTOR_NAME, &dropping_config, 1, &stopped)
+            .unwrap()
+            .is_none()
+    );
+
+    assert!(segment.requires_quantization_rebuild(DEFAULT_VECTOR_NAME, &target_config));
+    let rebuilt = segment
+        .rebuild_quantization(DEFAULT_VECTOR_NAME, &target_config, 1, &stopped)
+        .unwrap()
+        .unwrap();
+    segment
+        .replace_quantized_vectors(DEFAULT_VECTOR_NAME, rebuilt)
+        .unwrap();
+
+    assert_eq!(
+        segment
+            .segment_config
+            .quantization_config(DEFAULT_VECTOR_NAME),
+        Some(&target_config),
+    );
+    assert!(!segment.requires_quantization_rebuild(DEFAULT_VECTOR_NAME, &target_config));
+    assert_eq!(
+        segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .config()
+            .quantization_config,
+        target_config,
+    );
+
+    // Changed config is persisted
+    let path = segment.segment_path.clone();
+    drop(segment);
+    let segment = load_segment(&path, Uuid::nil(), None, &AtomicBool::new(false)).unwrap();
+    assert_eq!(
+        segment
+            .segment_config
+            .quantization_config(DEFAULT_VECTOR_NAME),
+        Some(&target_config),
+    );
+}
+
+#[test]
+fn test_estimate_quantization_quality() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 16, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for n in 0..200u64 {
+        let vector: Vec<f32> = (0..16).map(|_| rng.random_range(-1.0..1.0)).collect();
+        segment
+            .upsert_point(n, n.into(), only_default_vector(&vector), &hw_counter)
+            .unwrap();
+    }
+
+    // Nothing to estimate without quantization
+    assert!(
+        segment
+            .estimate_quantization_quality(DEFAULT_VECTOR_NAME, 10, 10, &stopped)
+            .unwrap()
+            .is_none()
+    );
+
+    let vector_storage_path = get_vector_storage_path(&segment.segment_path, DEFAULT_VECTOR_NAME);
+    let quantization_config: QuantizationConfig = ScalarQuantizationConfig {
+        r#type: ScalarType::Int8,
+        quantile: None,
+        always_ram: None,
+        drop_originals: None,
+    }
+    .into();
+    {
+        let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+        let quantized_vectors = QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            &quantization_config,
+            QuantizedVectorsStorageType::Immutable,
+            &vector_storage_path,
+            1,
+            &stopped,
+        )
+        .unwrap();
+        *vector_data.quantized_vectors.borrow_mut() = Some(quantized_vectors);
+    }
+
+    let stats = segment
+        .estimate_quantization_quality(DEFAULT_VECTOR_NAME, 10, 10, &stopped)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stats.queries, 10);
+    assert_eq!(stats.scores_count, 10 * 199);
+    assert!(stats.recall().unwrap() > 0.8);
+    assert!(stats.score_correlation().unwrap() > 0.99);
+
+    // Deleted points are neither sampled nor scored
+    for n in 0..150u64 {
+        segment
+            .delete_point(200 + n, n.into(), &hw_counter)
+            .unwrap();
+    }
+    let stats = segment
+        .estimate_quantization_quality(DEFAULT_VECTOR_NAME, 100, 10, &stopped)
+        .unwrap()
+        .unwrap();
+    assert_eq!(stats.queries, 50);
+    assert_eq!(stats.scores_count, 50 * 49);
+
+    stopped.store(true, std::sync::atomic::Ordering::Relaxed);
+    assert!(matches!(
+        segment.estimate_quantization_quality(DEFAULT_VECTOR_NAME, 10, 10, &stopped),
+        Err(OperationError::Cancelled { .. }),
+    ));
+}
+
+#[test]
+fn test_quantized_vector() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 16, Distance::Dot).unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let vectors: Vec<Vec<f32>> = (0..50)
+        .map(|_| (0..16).map(|_| rng.random_range(-1.0..1.0)).collect())
+        .collect();
+    for (n, vector) in vectors.iter().enumerate() {
+        let n = n as u64;
+        segment
+            .upsert_point(n, n.into(), only_default_vector(vector), &hw_counter)
+            .unwrap();
+    }
+
+    assert!(matches!(
+        segment.quantized_vector(DEFAULT_VECTOR_NAME, 1.into()),
+        Err(OperationError::ValidationError { .. }),
+    ));
+
+    let vector_storage_path = get_vector_storage_path(&segment.segment_path, DEFAULT_VECTOR_NAME);
+    let quantization_config: QuantizationConfig = ScalarQuantizationConfig {
+        r#type: ScalarType::Int8,
+        quantile: None,
+        always_ram: None,
+        drop_originals: None,
+    }
+    .into();
+    {
+        let vector_data = &segment.vector_data[DEFAULT_VECTOR_NAME];
+        let quantized_vectors = QuantizedVectors::create(
+            &vector_data.vector_storage.borrow(),
+            &quantization_config,
+            QuantizedVectorsStorageType::Immutable,
+            &vector_storage_path,
+            1,
+            &stopped,
+        )
+        .unwrap();
+        *vector_data.quantized_vectors.borrow_mut() = Some(quantized_vectors);
+    }
+
+    let quantized = segment
+        .quantized_vector(DEFAULT_VECTOR_NAME, 7.into())
+        .unwrap()
+        .unwrap();
+    assert_eq!(quantized.quantization_config, quantization_config);
+    assert_eq!(quantized.dim, 16);
+    assert_eq!(quantized.codes.len(), 16);
+
+    // Codes restore the original vector up to the quantization step
+    let (scale, offset) = (quantized.scale.unwrap(), quantized.offset.unwrap());
+    for (&code, &value) in quantized.codes.iter().zip(&vectors[7]) {
+        assert!((offset + scale * f32::from(code) - value).abs() <= scale);
+    }
+
+    assert!(
+        segment
+            .quantized_vector(DEFAULT_VECTOR_NAME, 100.into())
+            .unwrap()
+            .is_none()
+    );
+    segment.delete_point(100, 7.into(), &hw_counter).unwrap();
+    assert!(
+        segment
+            .quantized_vector(DEFAULT_VECTOR_NAME, 7.into())
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_hybrid_search() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hw_counter = HardwareCounterCell::new();
+    let stopped = AtomicBool::new(false);
+
+    let mut segment = build_segment(
+        dir.path(),
+        &SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::default(),
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
+                },
+            )]),
+            sparse_vector_data: HashMap::from([(
+                "sparse".to_string(),
+                SparseVectorDataConfig {
+                    index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
+                    storage_type: SparseVectorStorageType::Mmap,
+                    modifier: None,
+                    bm25: None,
+                },
+            )]),
+            payload_storage_type: Default::default(),
+        },
+        None,
+        true,
+    )
+    .unwrap();
+
+    let points = [
+        (1u64, vec![1.0, 0.0], Some(1.0)),
+        (2, vec![0.0, 1.0], Some(5.0)),
+        // Points without a sparse vector are still found by the dense part
+        (3, vec![0.5, 0.5], None),
+    ];
+    for (op_num, (point_id, dense, sparse)) in points.into_iter().enumerate() {
+        let mut vectors = only_default_vector(&dense);
+        if let Some(value) = sparse {
+            vectors.insert(
+                "sparse".to_string(),
+                VectorInternal::Sparse(SparseVector::new(vec![1], vec![value]).unwrap()),
+            );
+        }
+        segment
+            .upsert_point(op_num as u64, point_id.into(), vectors, &hw_counter)
+            .unwrap();
+    }
+
+    let search = |alpha| {
+        let query = HybridQuery {
+            dense_using: DEFAULT_VECTOR_NAME.to_owned(),
+            dense: vec![1.0, 0.0],
+            sparse_using: "sparse".to_string(),
+            sparse: SparseVector::new(vec![1], vec![1.0]).unwrap(),
+            alpha,
+        };
+        segment.hybrid_search(&query, None, 10, None, &stopped, &hw_counter)
+    };
+    let ids = |alpha| -> Vec<PointIdType> {
+        search(alpha)
+            .unwrap()
+            .into_iter()
+            .map(|point| point.id)
+            .collect()
+    };
+
+    // Dense only
+    assert_eq!(ids(1.0), vec![1.into(), 3.into(), 2.into()]);
+    // Sparse only
+    assert_eq!(ids(0.0), vec![2.into(), 1.into(), 3.into()]);
+
+    let result = search(0.5).unwrap();
+    assert_eq!(result[0].id, 2.into());
+    assert!((result[0].score - 2.5).abs() < 1e-6);
+    assert!((result[1].score - 1.0).abs() < 1e-6);
+    assert!((result[2].score - 0.25).abs() < 1e-6);
+
+    assert!(search(1.5).is_err());
+    assert!(search(-0.1).is_err());
+}
+
+#[test]
+fn test_vector_at_version() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hw_counter = HardwareCounterCell::new();
+
+    let mut segment = build_segment(
+        dir.path(),
+        &SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 2,
+                    distance: Distance::Dot,
+                    storage_type: VectorStorageType::ChunkedMmap,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: Some(1),
+                },
+            )]),
+            sparse_vector_data: Default::default(),
+            payload_storage_type: Default::default(),
+        },
+        None,
+        true,
+    )
+    .unwrap();
+
+    for (op_num, vector) in [(1, [1.0, 0.0]), (2, [0.0, 1.0]), (3, [1.0, 1.0])] {
+        segment
+            .upsert_point(op_num, 1.into(), only_default_vector(&vector), &hw_counter)
+            .unwrap();
+    }
+
+    let check = |segment: &Segment| {
+        let at_version = |version| {
+            segment
+                .vector_at_version(DEFAULT_VECTOR_NAME, 1.into(), version, &hw_counter)
+                .unwrap()
+        };
+        assert_eq!(at_version(4), Some(VectorInternal::from(vec![1.0, 1.0])));
+        assert_eq!(at_version(3), Some(VectorInternal::from(vec![1.0, 1.0])));
+        assert_eq!(at_version(2), Some(VectorInternal::from(vec![0.0, 1.0])));
+        // Only one previous version is kept
+        assert_eq!(at_version(1), None);
+    };
+    check(&segment);
+
+    // History is persisted
+    segment.flush(true).unwrap();
+    let path = segment.segment_path.clone();
+    drop(segment);
+
+    let segment = load_segment(&path, Uuid::nil(), None, &AtomicBool::new(false)).unwrap();
+    check(&segment);
+}
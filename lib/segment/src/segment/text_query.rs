@@ -0,0 +1,89 @@
+use std::sync::atomic::AtomicBool;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::iterator_ext::IteratorExt;
+use common::types::{DeferredBehavior, PointOffsetType, ScoredPointOffset};
+
+use super::Segment;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::text_query::{TextQuery, TextQueryStatistics};
+use crate::id_tracker::IdTracker;
+use crate::index::PayloadIndex;
+use crate::index::field_index::full_text_index::text_index::FullTextIndex;
+use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::spaces::tools::peek_top_largest_iterable;
+use crate::types::{Filter, ScoredPoint, WithPayload, WithVector};
+
+impl Segment {
+    fn full_text_index<'a>(
+        payload_index: &'a StructPayloadIndex,
+        query: &TextQuery,
+    ) -> OperationResult<&'a FullTextIndex> {
+        payload_index
+            .field_indexes
+            .get(&query.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_full_text()))
+            .ok_or_else(|| OperationError::MissingFullTextIndexForTextQuery {
+                key: query.key.to_string(),
+            })
+    }
+
+    pub fn collect_text_query_statistics(
+        &self,
+        query: &TextQuery,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<TextQueryStatistics> {
+        let payload_index = self.payload_index.borrow();
+        let full_text_index = Self::full_text_index(&payload_index, query)?;
+        Ok(full_text_index.text_query_statistics(&query.text, hw_counter))
+    }
+
+    /// Returned points have no payload and vectors.
+    #[allow(clippy::too_many_arguments)]
+    pub fn filtered_read_by_text_score(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+        deferred_behavior: DeferredBehavior,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let full_text_index = Self::full_text_index(&payload_index, query)?;
+        let scores = full_text_index.score_text_query(&query.text, statistics, hw_counter);
+
+        let filter_context = filter
+            .map(|filter| payload_index.filter_context(filter, hw_counter))
+            .transpose()?;
+
+        let effective_deferred_id = deferred_behavior
+            .apply(self.deferred_internal_id())
+            .unwrap_or(PointOffsetType::MAX);
+
+        let scored_points = scores
+            .into_iter()
+            .stop_if(is_stopped)
+            .filter(|&(internal_id, _)| internal_id < effective_deferred_id)
+            .filter(|&(internal_id, _)| !id_tracker.is_deleted_point(internal_id))
+            .filter(|&(internal_id, _)| {
+                filter_context
+                    .as_ref()
+                    .is_none_or(|filter_context| filter_context.check(internal_id))
+            })
+            .map(|(idx, score)| ScoredPointOffset { idx, score });
+
+        let page = peek_top_largest_iterable(scored_points, limit);
+
+        self.process_search_result(
+            page,
+            &WithPayload::from(false),
+            &WithVector::Bool(false),
+            hw_counter,
+            is_stopped,
+        )
+    }
+}
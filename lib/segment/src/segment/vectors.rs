@@ -1,15 +1,117 @@
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::iterator_ext::IteratorExt;
+use common::types::PointOffsetType;
 
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::VectorInternal;
 use crate::id_tracker::IdTracker;
-use crate::segment::Segment;
-use crate::types::{PointIdType, VectorName};
+use crate::index::VectorIndexEnum;
+use crate::index::plain_vector_index::PlainVectorIndex;
+use crate::index::sparse_index::sparse_vocabulary::SparseVocabulary;
+use crate::segment::{Segment, VectorData};
+use crate::segment_constructor::get_vector_storage_path;
+use crate::types::{PointIdType, SeqNumberType, VectorName};
+use crate::vector_storage::VectorStorageEnum;
+use crate::vector_storage::dedup_index::VectorDedupIndex;
+use crate::vector_storage::tombstone_vector_storage::create_tombstone_vector_storage;
+use crate::vector_storage::write_throttle::WriteThrottle;
+
+impl VectorData {
+    /// Whether the vector is removed, and only its tombstone is left
+    pub fn is_removed(&self) -> bool {
+        matches!(
+            *self.vector_storage.borrow(),
+            VectorStorageEnum::Tombstone(_)
+        )
+    }
+
+    /// Update deduplication index, if it is maintained, after the vector of `internal_id` was
+    /// changed in the storage
+    pub(crate) fn update_dedup_index(&self, internal_id: PointOffsetType) {
+        let mut dedup_index = self.dedup_index.borrow_mut();
+        let Some(dedup_index) = dedup_index.as_mut() else {
+            return;
+        };
+        if let Some(duplicate) = dedup_index.update(internal_id, &self.vector_storage.borrow()) {
+            log::trace!("Vector of point {internal_id} is a duplicate of point {duplicate}");
+        }
+    }
+
+    /// Set version of the operation, which inserts the next vector into the storage, to keep it
+    /// in the history of versioned storages
+    pub(crate) fn set_insert_version(&self, version: SeqNumberType) {
+        self.vector_storage.borrow_mut().set_insert_version(version);
+    }
+}
 
 impl Segment {
+    /// Apply backpressure from `write_throttle` to inserts into vector storages of this segment,
+    /// `None` to stop throttling
+    pub fn set_write_throttle(&self, write_throttle: Option<Arc<WriteThrottle>>) {
+        for vector_data in self.vector_data.values() {
+            vector_data
+                .vector_storage
+                .borrow_mut()
+                .set_write_throttle(write_throttle.clone());
+        }
+    }
+
+    /// Data of vector `vector_name`, which must exist and must not be removed
+    pub(super) fn readable_vector_data(
+        &self,
+        vector_name: &VectorName,
+    ) -> OperationResult<&VectorData> {
+        let vector_data = self
+            .vector_data
+            .get(vector_name)
+            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        if vector_data.is_removed() {
+            return Err(OperationError::vector_name_removed(vector_name));
+        }
+        Ok(vector_data)
+    }
+
+    /// Mark vector `vector_name` as removed.
+    ///
+    /// The storage of the vector is replaced with a tombstone right away, and indexes of the vector
+    /// are dropped. Files of the vector stay on disk until the segment is optimized. Reads of the
+    /// vector fail with [`OperationError::VectorNameRemoved`] afterwards.
+    ///
+    /// Returns `false` if the vector is already removed.
+    #[allow(clippy::needless_pass_by_ref_mut)] // ensure single access to AtomicRefCell vector storage
+    pub fn remove_vector_name(&mut self, vector_name: &VectorName) -> OperationResult<bool> {
+        let vector_data = self
+            .vector_data
+            .get(vector_name)
+            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))?;
+        if vector_data.is_removed() {
+            return Ok(false);
+        }
+
+        let vector_storage_path = get_vector_storage_path(&self.segment_path, vector_name);
+        let tombstone = create_tombstone_vector_storage(
+            &vector_storage_path,
+            vector_name,
+            &vector_data.vector_storage.borrow(),
+        )?;
+
+        // Storage is shared with the payload index, so it is replaced in place
+        *vector_data.vector_storage.borrow_mut() = tombstone;
+        *vector_data.quantized_vectors.borrow_mut() = None;
+        *vector_data.dedup_index.borrow_mut() = None;
+        *vector_data.vector_index.borrow_mut() = VectorIndexEnum::Plain(PlainVectorIndex::new(
+            self.id_tracker.clone(),
+            vector_data.vector_storage.clone(),
+            vector_data.quantized_vectors.clone(),
+            self.payload_index.clone(),
+        ));
+
+        Ok(true)
+    }
+
     pub(crate) fn read_vectors(
         &self,
         vector_names: &VectorName,
@@ -45,4 +147,46 @@ impl Segment {
         }
         Ok(())
     }
+
+    /// Find groups of points with exactly the same vector `vector_name`.
+    ///
+    /// Uses the deduplication index, if it is maintained, and builds a temporary one otherwise.
+    pub fn find_duplicates(
+        &self,
+        vector_name: &VectorName,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<Vec<PointIdType>>> {
+        let vector_data = self.readable_vector_data(vector_name)?;
+        let vector_storage = vector_data.vector_storage.borrow();
+
+        let duplicates = match vector_data.dedup_index.borrow().as_ref() {
+            Some(dedup_index) => dedup_index.duplicates(&vector_storage),
+            None => {
+                VectorDedupIndex::build(&vector_storage, is_stopped)?.duplicates(&vector_storage)
+            }
+        };
+
+        let id_tracker = self.id_tracker.borrow();
+        let duplicates = duplicates
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .filter_map(|internal_id| id_tracker.external_id(internal_id))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|group| group.len() > 1)
+            .collect();
+        Ok(duplicates)
+    }
+
+    /// Vocabulary of the sparse vector `vector_name`
+    pub(crate) fn get_sparse_vocabulary(
+        &self,
+        vector_name: &VectorName,
+    ) -> OperationResult<&SparseVocabulary> {
+        self.sparse_vocabularies
+            .get(vector_name)
+            .ok_or_else(|| OperationError::vector_name_not_exists(vector_name))
+    }
 }
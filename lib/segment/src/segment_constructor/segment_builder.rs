@@ -13,6 +13,7 @@ use bitvec::macros::internal::funty::Integral;
 use common::budget::ResourcePermit;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::flags::feature_flags;
+use common::generic_consts::Sequential;
 use common::progress_tracker::ProgressTracker;
 use common::small_uint::U24;
 use common::storage_version::StorageVersion;
@@ -26,10 +27,11 @@ use uuid::Uuid;
 #[cfg(feature = "rocksdb")]
 use super::rocksdb_builder::RocksDbBuilder;
 use super::{
-    create_mutable_id_tracker, create_payload_storage, create_sparse_vector_index,
-    create_sparse_vector_storage, get_payload_index_path, get_vector_index_path,
-    get_vector_storage_path, open_vector_storage,
+    config_within_memory_budget, create_mutable_id_tracker, create_payload_storage,
+    create_sparse_vector_index, create_sparse_vector_storage, get_payload_index_path,
+    get_vector_index_path, get_vector_storage_path, open_vector_storage,
 };
+use crate::blob_storage::MmapBlobStorage;
 use crate::common::error_logging::LogError;
 use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
 use crate::entry::ReadSegmentEntry;
@@ -39,10 +41,12 @@ use crate::id_tracker::in_memory_id_tracker::InMemoryIdTracker;
 use crate::id_tracker::{IdTracker, IdTrackerEnum, for_each_unique_point};
 use crate::index::field_index::FieldIndex;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
+use crate::index::sparse_index::sparse_vocabulary::SparseVocabulary;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndexEnum};
 use crate::payload_storage::PayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
+use crate::segment::point_expirations::{PointExpirations, unix_timestamp_now};
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::batched_reader::{BatchedVectorReader, PointData};
 use crate::segment_constructor::{
@@ -50,8 +54,9 @@ use crate::segment_constructor::{
 };
 use crate::types::{
     CompactExtendedPointId, ExtendedPointId, HnswGlobalConfig, PayloadFieldSchema, PayloadKeyType,
-    SegmentConfig, SegmentState, SeqNumberType, VectorNameBuf,
+    SegmentConfig, SegmentState, SeqNumberType, VectorNameBuf, VectorStorageType,
 };
+use crate::vector_storage::dense::quantized_dense_vector_storage::init_quantized_dense_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
 };
@@ -62,6 +67,9 @@ pub struct SegmentBuilder {
     version: SeqNumberType,
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
+    point_expirations: PointExpirations,
+    blob_storage: MmapBlobStorage,
+    sparse_vocabularies: HashMap<VectorNameBuf, SparseVocabulary>,
     vector_data: HashMap<VectorNameBuf, VectorData>,
     segment_config: SegmentConfig,
     hnsw_global_config: HnswGlobalConfig,
@@ -86,6 +94,7 @@ impl SegmentBuilder {
         hnsw_global_config: &HnswGlobalConfig,
     ) -> OperationResult<Self> {
         let temp_dir = create_temp_dir(temp_dir)?;
+        let segment_config = &config_within_memory_budget(segment_config);
 
         let id_tracker = if segment_config.is_appendable() {
             IdTrackerEnum::MutableIdTracker(create_mutable_id_tracker(temp_dir.path())?)
@@ -104,6 +113,7 @@ impl SegmentBuilder {
         )?;
 
         let mut vector_data = HashMap::new();
+        let mut sparse_vocabularies = HashMap::new();
 
         for (vector_name, vector_config) in &segment_config.vector_data {
             let vector_storage_path = get_vector_storage_path(temp_dir.path(), vector_name);
@@ -137,10 +147,14 @@ impl SegmentBuilder {
                 #[cfg(feature = "rocksdb")]
                 vector_name,
                 &sparse_vector_config.storage_type,
-                #[cfg(feature = "rocksdb")]
                 &Default::default(),
             )?;
 
+            sparse_vocabularies.insert(
+                vector_name.to_owned(),
+                SparseVocabulary::open(temp_dir.path(), vector_name)?,
+            );
+
             vector_data.insert(
                 vector_name.to_owned(),
                 VectorData {
@@ -150,10 +164,16 @@ impl SegmentBuilder {
             );
         }
 
+        let point_expirations = PointExpirations::open(temp_dir.path())?;
+        let blob_storage = MmapBlobStorage::open(temp_dir.path())?;
+
         Ok(SegmentBuilder {
             version: Default::default(), // default version is 0
             id_tracker,
             payload_storage,
+            point_expirations,
+            blob_storage,
+            sparse_vocabularies,
             vector_data,
             segment_config: segment_config.clone(),
             hnsw_global_config: hnsw_global_config.clone(),
@@ -307,6 +327,14 @@ impl SegmentBuilder {
         });
         drop(locked_id_trackers);
 
+        // Expired points are not carried over, which reclaims their space
+        let now = unix_timestamp_now();
+        points_to_insert.retain(|point_data| {
+            !segments[point_data.segment_index.get() as usize]
+                .point_expirations
+                .is_expired(point_data.internal_id, now)
+        });
+
         let payloads: Vec<_> = segments.iter().map(|i| i.payload_index.borrow()).collect();
 
         for defragment_key in &self.defragment_keys {
@@ -339,6 +367,16 @@ impl SegmentBuilder {
 
         let new_internal_range = internal_range_start..internal_range_end;
 
+        let points_locations = points_to_insert
+            .iter()
+            .map(|point_data| {
+                (
+                    point_data.segment_index.get() as usize,
+                    point_data.internal_id,
+                )
+            })
+            .collect_vec();
+
         for (vector_name, vector_data) in &mut self.vector_data {
             check_process_stopped(stopped)?;
 
@@ -360,12 +398,26 @@ impl SegmentBuilder {
                 })
                 .collect::<Result<Vec<_>, OperationError>>()?;
 
-            let mut vectors_iter: BatchedVectorReader =
-                BatchedVectorReader::new(&points_to_insert, &other_vector_storages);
+            let other_vector_storages_refs = other_vector_storages
+                .iter()
+                .map(|storage| storage.deref())
+                .collect_vec();
 
-            let internal_range = vector_data
-                .vector_storage
-                .update_from(&mut vectors_iter, stopped)?;
+            // Copy raw vectors in bulk if possible, fall back to copying vector by vector
+            let internal_range = match vector_data.vector_storage.update_from_same(
+                &other_vector_storages_refs,
+                &points_locations,
+                stopped,
+            )? {
+                Some(internal_range) => internal_range,
+                None => {
+                    let mut vectors_iter: BatchedVectorReader =
+                        BatchedVectorReader::new(&points_to_insert, &other_vector_storages);
+                    vector_data
+                        .vector_storage
+                        .update_from(&mut vectors_iter, stopped)?
+                }
+            };
 
             if new_internal_range != internal_range {
                 debug_assert!(
@@ -419,6 +471,7 @@ impl SegmentBuilder {
                             .set_internal_version(new_internal_id, point_data.version)?;
                         self.payload_storage
                             .clear(existing_internal_id, &hw_counter)?;
+                        self.blob_storage.delete(existing_internal_id)?;
 
                         existing_internal_id
                     } else {
@@ -448,6 +501,20 @@ impl SegmentBuilder {
                     &HardwareCounterCell::disposable(),
                 )?;
             }
+
+            let expires_at = segments[point_data.segment_index.get() as usize]
+                .point_expirations
+                .get(old_internal_id);
+            self.point_expirations.set(new_internal_id, expires_at);
+
+            // Propagate blob to new segment
+            let blob = segments[point_data.segment_index.get() as usize]
+                .blob_storage
+                .get::<Sequential>(old_internal_id, &hw_counter)?;
+            if let Some(blob) = blob {
+                self.blob_storage
+                    .set(new_internal_id, Some(&blob), &hw_counter)?;
+            }
         }
 
         for payload in payloads {
@@ -456,6 +523,15 @@ impl SegmentBuilder {
             }
         }
 
+        // Vocabularies are shared by all points, merge them from every source segment
+        for segment in segments {
+            for (vector_name, vocabulary) in &mut self.sparse_vocabularies {
+                if let Some(other) = segment.sparse_vocabularies.get(vector_name) {
+                    vocabulary.register(other.iter())?;
+                }
+            }
+        }
+
         Ok(true)
     }
 
@@ -489,13 +565,16 @@ impl SegmentBuilder {
         hw_counter: &HardwareCounterCell,
         progress_segment: ProgressTracker,
     ) -> Result<Segment, OperationError> {
-        let temp_dir = {
+        let (temp_dir, dropped_vector_files) = {
             let SegmentBuilder {
                 version,
                 id_tracker,
                 payload_storage,
+                point_expirations,
+                blob_storage,
+                sparse_vocabularies: _,
                 mut vector_data,
-                segment_config,
+                mut segment_config,
                 hnsw_global_config,
                 temp_dir,
                 indexed_fields,
@@ -518,6 +597,8 @@ impl SegmentBuilder {
             let appendable_flag = segment_config.is_appendable();
 
             payload_storage.flusher()()?;
+            point_expirations.flusher()()?;
+            blob_storage.flusher()()?;
             let payload_storage_arc = Arc::new(AtomicRefCell::new(payload_storage));
 
             let id_tracker = match id_tracker {
@@ -618,6 +699,10 @@ impl SegmentBuilder {
             // Arc permit to share it with each vector store
             let permit = Arc::new(permit);
 
+            // Vectors, which are only kept quantized, and files of their original storages
+            let mut dropped_vectors = Vec::new();
+            let mut dropped_vector_files = Vec::new();
+
             progress_vector_index.start();
             for (vector_name, vector_config) in &segment_config.vector_data {
                 let vector_storage = vector_storages_arc.remove(vector_name).unwrap();
@@ -658,9 +743,35 @@ impl SegmentBuilder {
                 // Index if always loaded on-disk=true from build function
                 // So we may clear unconditionally
                 index.clear_cache()?;
+
+                // Original vectors are not needed anymore once the index is built. Vectors of
+                // variable dimension, or with preserved norms, are kept: quantized vectors don't
+                // know their lengths and norms.
+                let drop_originals = quantized_vectors.borrow().as_ref().is_some_and(|q| {
+                    q.config().quantization_config.drop_originals() && !q.is_multivector()
+                });
+                if drop_originals
+                    && !vector_config.is_appendable()
+                    && vector_config.dim_range.is_none()
+                    && !vector_config.preserve_norm()
+                {
+                    let vector_storage = vector_storage.borrow();
+                    init_quantized_dense_vector_storage(
+                        &get_vector_storage_path(temp_dir.path(), vector_name),
+                        &vector_storage,
+                    )?;
+                    dropped_vector_files.extend(vector_storage.files());
+                    dropped_vectors.push(vector_name.clone());
+                }
             }
             drop(progress_vector_index);
 
+            for vector_name in dropped_vectors {
+                if let Some(vector_config) = segment_config.vector_data.get_mut(&vector_name) {
+                    vector_config.storage_type = VectorStorageType::Quantized;
+                }
+            }
+
             progress_sparse_vector_index.start();
             for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
                 let vector_index_path = get_vector_index_path(temp_dir.path(), vector_name);
@@ -724,9 +835,14 @@ impl SegmentBuilder {
             // After version is saved, segment can be loaded on restart
             SegmentVersion::save(temp_dir.path())?;
             // All temp data is evicted from RAM
-            temp_dir
+            (temp_dir, dropped_vector_files)
         };
 
+        // Vector storages are closed, drop original vectors which are only kept quantized
+        for path in dropped_vector_files {
+            fs::remove_file(&path)?;
+        }
+
         // Move fully constructed segment into collection directory and load back to RAM
         let destination_path = segments_path.join(segment_uuid.to_string());
         fs::rename(temp_dir.keep(), &destination_path)
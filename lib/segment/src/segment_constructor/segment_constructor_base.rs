@@ -25,12 +25,14 @@ use rand::Rng;
 #[cfg(feature = "rocksdb")]
 use rocksdb::DB;
 use serde::Deserialize;
+use sparse::common::sparse_vector::SparseVector;
 use uuid::Uuid;
 
 #[cfg(feature = "rocksdb")]
 use super::rocksdb_builder::RocksDbBuilder;
+use crate::blob_storage::MmapBlobStorage;
 use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
-use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::data_types::vectors::{DEFAULT_VECTOR_NAME, MultiDenseVectorInternal, VectorInternal};
 use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::id_tracker::mutable_id_tracker::MutableIdTracker;
 #[cfg(feature = "rocksdb")]
@@ -39,18 +41,22 @@ use crate::id_tracker::{IdTracker, IdTrackerEnum};
 use crate::index::VectorIndexEnum;
 use crate::index::hnsw_index::gpu::gpu_devices_manager::LockedGpuDevice;
 use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::index::ivf_index::ivf::{IvfIndex, IvfIndexOpenArgs};
 use crate::index::plain_vector_index::PlainVectorIndex;
 use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::{
     self, SparseVectorIndex, SparseVectorIndexOpenArgs,
 };
+use crate::index::sparse_index::sparse_vocabulary::SparseVocabulary;
 use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vamana_index::vamana::{VamanaIndex, VamanaIndexOpenArgs};
 use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 #[cfg(feature = "rocksdb")]
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 #[cfg(feature = "rocksdb")]
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
+use crate::segment::point_expirations::PointExpirations;
 use crate::segment::{
     DeferredPointStatus, SEGMENT_STATE_FILE, Segment, SegmentVersion, VectorData,
 };
@@ -59,20 +65,38 @@ use crate::types::MultiVectorConfig;
 use crate::types::{
     Distance, HnswGlobalConfig, Indexes, PayloadStorageType, SegmentConfig, SegmentState,
     SegmentType, SeqNumberType, SparseVectorStorageType, VectorDataConfig, VectorName,
-    VectorStorageDatatype, VectorStorageType,
+    VectorNameBuf, VectorStorageDatatype, VectorStorageType,
 };
+use crate::vector_storage::background_populate::{
+    is_background_populate_enabled, populate_in_background,
+};
+use crate::vector_storage::dedup_index::{VectorDedupIndex, get_vector_dedup_index};
+use crate::vector_storage::dense::appendable_dense_vector_storage::{
+    open_appendable_memmap_norm_preserving_vector_storage,
+    open_appendable_memmap_variable_dim_vector_storage,
+};
+use crate::vector_storage::dense::appendable_int4_dense_vector_storage::open_appendable_memmap_vector_storage_int4;
+use crate::vector_storage::dense::appendable_int8_dense_vector_storage::open_appendable_memmap_vector_storage_int8;
 use crate::vector_storage::dense::dense_vector_storage::{
-    open_dense_vector_storage, open_dense_vector_storage_byte, open_dense_vector_storage_half,
+    open_dense_vector_storage, open_dense_vector_storage_bfloat16, open_dense_vector_storage_byte,
+    open_dense_vector_storage_double, open_dense_vector_storage_half,
 };
+use crate::vector_storage::dense::quantized_dense_vector_storage::open_quantized_dense_vector_storage;
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_vector_storage;
+use crate::vector_storage::memory_budget::MemoryBudget;
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
     open_appendable_memmap_multi_vector_storage, open_appendable_memmap_vector_storage,
 };
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::open_simple_multi_dense_vector_storage;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::sparse::appendable_wal_sparse_vector_storage::open_appendable_wal_sparse_vector_storage;
 use crate::vector_storage::sparse::mmap_sparse_vector_storage::MmapSparseVectorStorage;
+use crate::vector_storage::tombstone_vector_storage::{
+    is_removed_vector_storage, open_tombstone_vector_storage,
+};
+use crate::vector_storage::versioned_vector_storage::VersionedDenseVectorStorage;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 pub const PAYLOAD_INDEX_PATH: &str = "payload_index";
@@ -102,6 +126,13 @@ pub fn get_vector_index_path(segment_path: &Path, vector_name: &VectorName) -> P
     segment_path.join(get_vector_name_with_prefix(VECTOR_INDEX_PATH, vector_name))
 }
 
+/// Advice configured for the vector, or `default` for the storage type if not configured
+fn mmap_advice_setting(vector_config: &VectorDataConfig, default: AdviceSetting) -> AdviceSetting {
+    vector_config
+        .mmap_advice
+        .map_or(default, |advice| AdviceSetting::from(Advice::from(advice)))
+}
+
 fn open_mmap_vector_storage(
     vector_storage_path: &Path,
     vector_config: &VectorDataConfig,
@@ -120,24 +151,77 @@ fn open_mmap_vector_storage(
             madvise,
             populate,
         )
+    } else if vector_config.dim_range.is_some() {
+        // there are no mmap storages of vectors with variable dimension, appendable only
+        open_appendable_memmap_variable_dim_vector_storage(
+            storage_element_type,
+            vector_storage_path,
+            vector_config.size,
+            vector_config.distance,
+            madvise,
+            populate,
+        )
+    } else if vector_config.preserve_norm() {
+        // there are no mmap storages keeping norms of vectors, appendable only
+        open_appendable_memmap_norm_preserving_vector_storage(
+            storage_element_type,
+            vector_storage_path,
+            vector_config.size,
+            vector_config.distance,
+            madvise,
+            populate,
+        )
     } else {
         match storage_element_type {
             VectorStorageDatatype::Float32 => open_dense_vector_storage(
                 vector_storage_path,
                 vector_config.size,
                 vector_config.distance,
+                madvise,
                 populate,
             ),
             VectorStorageDatatype::Uint8 => open_dense_vector_storage_byte(
                 vector_storage_path,
                 vector_config.size,
                 vector_config.distance,
+                madvise,
                 populate,
             ),
             VectorStorageDatatype::Float16 => open_dense_vector_storage_half(
                 vector_storage_path,
                 vector_config.size,
                 vector_config.distance,
+                madvise,
+                populate,
+            ),
+            VectorStorageDatatype::Float64 => open_dense_vector_storage_double(
+                vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                madvise,
+                populate,
+            ),
+            VectorStorageDatatype::Bfloat16 => open_dense_vector_storage_bfloat16(
+                vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                madvise,
+                populate,
+            ),
+            // Int8 and Int4 vectors are always kept in chunked mmap, next to their quantization
+            // params
+            VectorStorageDatatype::Int8 => open_appendable_memmap_vector_storage_int8(
+                vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                madvise,
+                populate,
+            ),
+            VectorStorageDatatype::Int4 => open_appendable_memmap_vector_storage_int4(
+                vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                madvise,
                 populate,
             ),
         }
@@ -161,6 +245,24 @@ fn open_chunked_mmap_vector_storage(
             madvise,
             populate,
         )
+    } else if vector_config.dim_range.is_some() {
+        open_appendable_memmap_variable_dim_vector_storage(
+            storage_element_type,
+            vector_storage_path,
+            vector_config.size,
+            vector_config.distance,
+            madvise,
+            populate,
+        )
+    } else if vector_config.preserve_norm() {
+        open_appendable_memmap_norm_preserving_vector_storage(
+            storage_element_type,
+            vector_storage_path,
+            vector_config.size,
+            vector_config.distance,
+            madvise,
+            populate,
+        )
     } else {
         open_appendable_memmap_vector_storage(
             storage_element_type,
@@ -173,6 +275,72 @@ fn open_chunked_mmap_vector_storage(
     }
 }
 
+/// Open in-RAM vector storage with `open`, populating it either in place, or in background if
+/// enabled. Background population allows to serve the storage before all its pages are resident.
+fn open_in_ram_vector_storage(
+    open: impl FnOnce(bool) -> OperationResult<VectorStorageEnum>,
+) -> OperationResult<VectorStorageEnum> {
+    if !is_background_populate_enabled() {
+        return open(true);
+    }
+    let vector_storage = open(false)?;
+    populate_in_background(vector_storage.files());
+    Ok(vector_storage)
+}
+
+/// Config for a new segment, which respects the [`MemoryBudget`] of in-RAM vector storages.
+///
+/// If the budget is exceeded, vectors of the new segment are kept in mmap instead of RAM.
+pub(crate) fn config_within_memory_budget(config: &SegmentConfig) -> SegmentConfig {
+    let mut config = config.clone();
+    if !MemoryBudget::global().is_exceeded() {
+        return config;
+    }
+
+    for (vector_name, vector_config) in &mut config.vector_data {
+        let fallback_storage_type = match vector_config.storage_type {
+            VectorStorageType::Memory | VectorStorageType::InRamChunkedMmap => {
+                VectorStorageType::ChunkedMmap
+            }
+            VectorStorageType::InRamMmap => VectorStorageType::Mmap,
+            VectorStorageType::Mmap
+            | VectorStorageType::ChunkedMmap
+            | VectorStorageType::Quantized => continue,
+        };
+        log::debug!(
+            "Memory budget of in-RAM vector storages is exceeded, storing vectors {vector_name} in {fallback_storage_type:?} instead of {:?}",
+            vector_config.storage_type,
+        );
+        vector_config.storage_type = fallback_storage_type;
+    }
+
+    config
+}
+
+/// Norms can only be kept by storages of single dense Cosine vectors of fixed dimension.
+///
+/// Vectors are not normalized on insert if norms are preserved, so any other storage would keep
+/// them unnormalized.
+fn check_preserve_norm_supported(vector_config: &VectorDataConfig) -> OperationResult<()> {
+    if vector_config.distance != Distance::Cosine {
+        return Err(OperationError::service_error(format!(
+            "Norms of vectors can't be preserved for {:?} distance",
+            vector_config.distance,
+        )));
+    }
+    if vector_config.multivector_config.is_some() {
+        return Err(OperationError::service_error(
+            "Norms of vectors can't be preserved for multivectors",
+        ));
+    }
+    if vector_config.dim_range.is_some() {
+        return Err(OperationError::service_error(
+            "Norms of vectors can't be preserved for vectors of variable dimension",
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn open_vector_storage(
     #[cfg(feature = "rocksdb")] db_builder: &mut RocksDbBuilder,
     vector_config: &VectorDataConfig,
@@ -180,6 +348,63 @@ pub(crate) fn open_vector_storage(
     vector_storage_path: &Path,
     #[cfg(feature = "rocksdb")] vector_name: &VectorName,
 ) -> OperationResult<VectorStorageEnum> {
+    let max_versions = vector_config.max_versions();
+    if max_versions > 0 {
+        check_max_versions_supported(vector_config)?;
+    }
+
+    let vector_storage = open_unversioned_vector_storage(
+        #[cfg(feature = "rocksdb")]
+        db_builder,
+        vector_config,
+        #[cfg(feature = "rocksdb")]
+        stopped,
+        vector_storage_path,
+        #[cfg(feature = "rocksdb")]
+        vector_name,
+    )?;
+
+    if max_versions == 0 {
+        return Ok(vector_storage);
+    }
+
+    Ok(VectorStorageEnum::DenseVersioned(Box::new(
+        VersionedDenseVectorStorage::open(vector_storage_path, vector_storage, max_versions)?,
+    )))
+}
+
+/// History of vectors is delta-encoded against current vectors, which requires original dense
+/// vectors of fixed dimension.
+fn check_max_versions_supported(vector_config: &VectorDataConfig) -> OperationResult<()> {
+    if vector_config.multivector_config.is_some() {
+        return Err(OperationError::service_error(
+            "History of vectors can't be kept for multivectors",
+        ));
+    }
+    if vector_config.dim_range.is_some() {
+        return Err(OperationError::service_error(
+            "History of vectors can't be kept for vectors of variable dimension",
+        ));
+    }
+    if vector_config.storage_type == VectorStorageType::Quantized {
+        return Err(OperationError::service_error(
+            "Quantized vector storage doesn't support history of vectors",
+        ));
+    }
+    Ok(())
+}
+
+fn open_unversioned_vector_storage(
+    #[cfg(feature = "rocksdb")] db_builder: &mut RocksDbBuilder,
+    vector_config: &VectorDataConfig,
+    #[cfg(feature = "rocksdb")] stopped: &AtomicBool,
+    vector_storage_path: &Path,
+    #[cfg(feature = "rocksdb")] vector_name: &VectorName,
+) -> OperationResult<VectorStorageEnum> {
+    if vector_config.preserve_norm() {
+        check_preserve_norm_supported(vector_config)?;
+    }
+
     match vector_config.storage_type {
         // In memory - RocksDB disabled
         #[cfg(not(feature = "rocksdb"))]
@@ -190,6 +415,16 @@ pub(crate) fn open_vector_storage(
         // In memory - RocksDB enabled
         #[cfg(feature = "rocksdb")]
         VectorStorageType::Memory => {
+            if vector_config.dim_range.is_some() {
+                return Err(OperationError::service_error(
+                    "In-memory vector storage doesn't support vectors of variable dimension",
+                ));
+            }
+            if vector_config.preserve_norm() {
+                return Err(OperationError::service_error(
+                    "In-memory vector storage doesn't support preserving norms of vectors",
+                ));
+            }
             let storage_element_type = vector_config.datatype.unwrap_or_default();
             use crate::common::rocksdb_wrapper::DB_VECTOR_CF;
 
@@ -221,29 +456,59 @@ pub(crate) fn open_vector_storage(
         VectorStorageType::Mmap => open_mmap_vector_storage(
             vector_storage_path,
             vector_config,
-            AdviceSetting::Global,
+            mmap_advice_setting(vector_config, AdviceSetting::Global),
             false,
         ),
-        VectorStorageType::InRamMmap => open_mmap_vector_storage(
-            vector_storage_path,
-            vector_config,
-            AdviceSetting::from(Advice::Normal),
-            true,
-        ),
+        VectorStorageType::InRamMmap => open_in_ram_vector_storage(|populate| {
+            open_mmap_vector_storage(
+                vector_storage_path,
+                vector_config,
+                mmap_advice_setting(vector_config, AdviceSetting::from(Advice::Normal)),
+                populate,
+            )
+        }),
 
         // Chunked mmap on disk, appendable
         VectorStorageType::ChunkedMmap => open_chunked_mmap_vector_storage(
             vector_storage_path,
             vector_config,
-            AdviceSetting::Global,
+            mmap_advice_setting(vector_config, AdviceSetting::Global),
             false,
         ),
-        VectorStorageType::InRamChunkedMmap => open_chunked_mmap_vector_storage(
-            vector_storage_path,
-            vector_config,
-            AdviceSetting::from(Advice::Normal),
-            true,
-        ),
+        VectorStorageType::InRamChunkedMmap => open_in_ram_vector_storage(|populate| {
+            open_chunked_mmap_vector_storage(
+                vector_storage_path,
+                vector_config,
+                mmap_advice_setting(vector_config, AdviceSetting::from(Advice::Normal)),
+                populate,
+            )
+        }),
+
+        // Quantized vectors only, not appendable
+        VectorStorageType::Quantized => {
+            if vector_config.multivector_config.is_some() {
+                return Err(OperationError::service_error(
+                    "Quantized vector storage doesn't support multivectors",
+                ));
+            }
+            if vector_config.dim_range.is_some() {
+                return Err(OperationError::service_error(
+                    "Quantized vector storage doesn't support vectors of variable dimension",
+                ));
+            }
+            if vector_config.preserve_norm() {
+                return Err(OperationError::service_error(
+                    "Quantized vector storage doesn't support preserving norms of vectors",
+                ));
+            }
+            open_quantized_dense_vector_storage(
+                vector_storage_path,
+                vector_config.size,
+                vector_config.distance,
+                vector_config.datatype.unwrap_or_default(),
+                false,
+            )
+        }
     }
 }
 
@@ -340,6 +605,24 @@ pub(crate) fn open_vector_index(
             payload_index,
             hnsw_config: *hnsw_config,
         })?),
+        Indexes::Vamana(vamana_config) => {
+            VectorIndexEnum::Vamana(VamanaIndex::open(VamanaIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                vamana_config: *vamana_config,
+            })?)
+        }
+        Indexes::Ivf(ivf_config) => VectorIndexEnum::Ivf(IvfIndex::open(IvfIndexOpenArgs {
+            path,
+            id_tracker,
+            vector_storage,
+            quantized_vectors,
+            payload_index,
+            ivf_config: *ivf_config,
+        })?),
     })
 }
 
@@ -373,6 +656,28 @@ pub(crate) fn build_vector_index<R: Rng + ?Sized>(
             },
             build_args,
         )?),
+        Indexes::Vamana(vamana_config) => VectorIndexEnum::Vamana(VamanaIndex::build(
+            VamanaIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                vamana_config: *vamana_config,
+            },
+            build_args,
+        )?),
+        Indexes::Ivf(ivf_config) => VectorIndexEnum::Ivf(IvfIndex::build(
+            IvfIndexOpenArgs {
+                path,
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                ivf_config: *ivf_config,
+            },
+            build_args,
+        )?),
     })
 }
 
@@ -397,6 +702,17 @@ pub(crate) fn create_sparse_vector_index(
             })?
         }
 
+        (
+            _,
+            a @ (VectorStorageDatatype::Float64
+            | VectorStorageDatatype::Int8
+            | VectorStorageDatatype::Bfloat16
+            | VectorStorageDatatype::Int4),
+            _,
+        ) => Err(OperationError::ValidationError {
+            description: format!("{a:?} datatype is not supported for sparse vectors"),
+        })?,
+
         (SparseIndexType::MutableRam, _, _) => {
             VectorIndexEnum::SparseRam(SparseVectorIndex::open(args)?)
         }
@@ -438,7 +754,7 @@ pub(crate) fn create_sparse_vector_storage(
     path: &Path,
     #[cfg(feature = "rocksdb")] vector_name: &VectorName,
     storage_type: &SparseVectorStorageType,
-    #[cfg(feature = "rocksdb")] stopped: &AtomicBool,
+    stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     match storage_type {
         #[cfg(feature = "rocksdb")]
@@ -456,6 +772,42 @@ pub(crate) fn create_sparse_vector_storage(
             let mmap_storage = MmapSparseVectorStorage::open_or_create(path)?;
             Ok(VectorStorageEnum::SparseMmap(mmap_storage))
         }
+        SparseVectorStorageType::AppendableWal => {
+            open_appendable_wal_sparse_vector_storage(path, stopped)
+        }
+    }
+}
+
+/// Build deduplication index of vectors of an appendable segment, if enabled
+fn open_dedup_index(
+    vector_storage: &VectorStorageEnum,
+    appendable_flag: bool,
+    stopped: &AtomicBool,
+) -> OperationResult<Option<VectorDedupIndex>> {
+    if !appendable_flag || !get_vector_dedup_index() {
+        return Ok(None);
+    }
+    VectorDedupIndex::build(vector_storage, stopped).map(Some)
+}
+
+/// Data of a removed vector, which only has a tombstone storage and no indexes
+fn removed_vector_data(
+    vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerEnum>>,
+    payload_index: &Arc<AtomicRefCell<StructPayloadIndex>>,
+) -> VectorData {
+    let quantized_vectors = sp(None);
+    let vector_index = sp(VectorIndexEnum::Plain(PlainVectorIndex::new(
+        id_tracker.clone(),
+        vector_storage.clone(),
+        quantized_vectors.clone(),
+        payload_index.clone(),
+    )));
+    VectorData {
+        vector_index,
+        vector_storage,
+        quantized_vectors,
+        dedup_index: sp(None),
     }
 }
 
@@ -504,16 +856,27 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
 
         let started = Instant::now();
-        let vector_storage = sp(open_vector_storage(
-            #[cfg(feature = "rocksdb")]
-            &mut db_builder,
-            vector_config,
-            #[cfg(feature = "rocksdb")]
-            stopped,
-            &vector_storage_path,
-            #[cfg(feature = "rocksdb")]
-            vector_name,
-        )?);
+        let vector_storage = if is_removed_vector_storage(&vector_storage_path) {
+            let default_vector = match vector_config.multivector_config {
+                Some(_) => {
+                    VectorInternal::from(MultiDenseVectorInternal::placeholder(vector_config.size))
+                }
+                None => VectorInternal::from(vec![1.0; vector_config.size]),
+            };
+            open_tombstone_vector_storage(&vector_storage_path, vector_name, default_vector)?
+        } else {
+            open_vector_storage(
+                #[cfg(feature = "rocksdb")]
+                &mut db_builder,
+                vector_config,
+                #[cfg(feature = "rocksdb")]
+                stopped,
+                &vector_storage_path,
+                #[cfg(feature = "rocksdb")]
+                vector_name,
+            )?
+        };
+        let vector_storage = sp(vector_storage);
         log_load_timing(
             segment_path,
             &format!("vector_storage dense '{vector_name}'"),
@@ -527,16 +890,21 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
 
         let started = Instant::now();
-        let vector_storage = sp(create_sparse_vector_storage(
-            #[cfg(feature = "rocksdb")]
-            &mut db_builder,
-            &vector_storage_path,
-            #[cfg(feature = "rocksdb")]
-            vector_name,
-            &sparse_config.storage_type,
-            #[cfg(feature = "rocksdb")]
-            stopped,
-        )?);
+        let vector_storage = if is_removed_vector_storage(&vector_storage_path) {
+            let default_vector = VectorInternal::from(SparseVector::default());
+            open_tombstone_vector_storage(&vector_storage_path, vector_name, default_vector)?
+        } else {
+            create_sparse_vector_storage(
+                #[cfg(feature = "rocksdb")]
+                &mut db_builder,
+                &vector_storage_path,
+                #[cfg(feature = "rocksdb")]
+                vector_name,
+                &sparse_config.storage_type,
+                stopped,
+            )?
+        };
+        let vector_storage = sp(vector_storage);
         log_load_timing(
             segment_path,
             &format!("vector_storage sparse '{vector_name}'"),
@@ -563,6 +931,14 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_storage = vector_storages.remove(vector_name).unwrap();
 
+        if is_removed_vector_storage(&vector_storage_path) {
+            vector_data.insert(
+                vector_name.to_owned(),
+                removed_vector_data(vector_storage, &id_tracker, &payload_index),
+            );
+            continue;
+        }
+
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
@@ -578,6 +954,8 @@ fn create_segment(
         let quantized_vectors = sp(
             if let Some(quantization_config) = config.quantization_config(vector_name) {
                 let quantized_data_path = vector_storage_path;
+                // Complete retraining of quantized vectors, if it was interrupted by a restart
+                QuantizedVectors::finish_requantization(&quantized_data_path)?;
                 QuantizedVectors::load(
                     quantization_config,
                     &vector_storage.borrow(),
@@ -595,7 +973,7 @@ fn create_segment(
         );
 
         let started = Instant::now();
-        let vector_index: Arc<AtomicRefCell<VectorIndexEnum>> = sp(open_vector_index(
+        let mut vector_index = open_vector_index(
             vector_config,
             VectorIndexOpenArgs {
                 path: &vector_index_path,
@@ -604,13 +982,29 @@ fn create_segment(
                 payload_index: payload_index.clone(),
                 quantized_vectors: quantized_vectors.clone(),
             },
-        )?);
+        )?;
+        if appendable_flag && let VectorIndexEnum::Plain(plain_index) = &mut vector_index {
+            plain_index.init_appendable_graph(stopped)?;
+        }
+        let vector_index = sp(vector_index);
         log_load_timing(
             segment_path,
             &format!("vector_index dense '{vector_name}'"),
             started,
         );
 
+        let started = Instant::now();
+        let dedup_index = sp(open_dedup_index(
+            &vector_storage.borrow(),
+            appendable_flag,
+            stopped,
+        )?);
+        log_load_timing(
+            segment_path,
+            &format!("dedup_index '{vector_name}'"),
+            started,
+        );
+
         check_process_stopped(stopped)?;
 
         vector_data.insert(
@@ -619,6 +1013,7 @@ fn create_segment(
                 vector_index,
                 vector_storage,
                 quantized_vectors,
+                dedup_index,
             },
         );
     }
@@ -628,6 +1023,14 @@ fn create_segment(
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
         let vector_storage = vector_storages.remove(vector_name).unwrap();
 
+        if is_removed_vector_storage(&vector_storage_path) {
+            vector_data.insert(
+                vector_name.to_owned(),
+                removed_vector_data(vector_storage, &id_tracker, &payload_index),
+            );
+            continue;
+        }
+
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
         let vector_count = vector_storage.borrow().total_vector_count();
@@ -655,6 +1058,18 @@ fn create_segment(
             started,
         );
 
+        let started = Instant::now();
+        let dedup_index = sp(open_dedup_index(
+            &vector_storage.borrow(),
+            appendable_flag,
+            stopped,
+        )?);
+        log_load_timing(
+            segment_path,
+            &format!("dedup_index '{vector_name}'"),
+            started,
+        );
+
         check_process_stopped(stopped)?;
 
         vector_data.insert(
@@ -663,6 +1078,7 @@ fn create_segment(
                 vector_storage,
                 vector_index,
                 quantized_vectors: sp(None),
+                dedup_index,
             },
         );
     }
@@ -687,6 +1103,9 @@ fn create_segment(
         appendable_flag,
         payload_index,
         payload_storage,
+        point_expirations: PointExpirations::open(segment_path)?,
+        blob_storage: MmapBlobStorage::open(segment_path)?,
+        sparse_vocabularies: open_sparse_vocabularies(segment_path, config)?,
         segment_config: config.clone(),
         error_status: None,
         #[cfg(feature = "rocksdb")]
@@ -704,6 +1123,20 @@ fn create_segment(
     Ok(segment)
 }
 
+fn open_sparse_vocabularies(
+    segment_path: &Path,
+    config: &SegmentConfig,
+) -> OperationResult<HashMap<VectorNameBuf, SparseVocabulary>> {
+    config
+        .sparse_vector_data
+        .keys()
+        .map(|vector_name| {
+            let vocabulary = SparseVocabulary::open(segment_path, vector_name)?;
+            Ok((vector_name.to_owned(), vocabulary))
+        })
+        .collect()
+}
+
 fn create_segment_id_tracker(
     mutable_id_tracker: bool,
     segment_path: &Path,
@@ -924,6 +1357,7 @@ pub fn build_segment(
     let uuid = Uuid::new_v4();
     let segment_path = segments_path.join(uuid.to_string());
     let stopped = AtomicBool::new(false);
+    let config = config_within_memory_budget(config);
 
     fs::create_dir_all(&segment_path)?;
     let segment = create_segment(
@@ -932,7 +1366,7 @@ pub fn build_segment(
         &segment_path,
         uuid,
         deferred_internal_id,
-        config,
+        &config,
         &stopped,
         true,
     )?;
@@ -37,6 +37,10 @@ pub fn build_simple_segment(
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -66,6 +70,10 @@ pub fn build_simple_segment_with_payload_storage(
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -93,6 +101,10 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivector_config: None,
             datatype: None,
+            mmap_advice: None,
+            dim_range: None,
+            preserve_norm: None,
+            max_versions: None,
         },
     );
     vectors_config.insert(
@@ -105,6 +117,10 @@ pub fn build_multivec_segment(
             quantization_config: None,
             multivector_config: None,
             datatype: None,
+            mmap_advice: None,
+            dim_range: None,
+            preserve_norm: None,
+            max_versions: None,
         },
     );
 
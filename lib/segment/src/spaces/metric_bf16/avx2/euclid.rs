@@ -0,0 +1,76 @@
+use std::arch::x86_64::*;
+
+use common::types::ScoreType;
+
+use super::load_bf16_ps;
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+use crate::spaces::simple_avx::hsum256_ps_avx;
+
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+#[target_feature(enable = "fma")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn avx_euclid_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    debug_assert!(v1.len() == v2.len());
+
+    unsafe {
+        let n = v1.len();
+        let m = n - (n % 16);
+        let mut ptr1: *const __m128i = v1.as_ptr().cast::<__m128i>();
+        let mut ptr2: *const __m128i = v2.as_ptr().cast::<__m128i>();
+        let mut sum256_1: __m256 = _mm256_setzero_ps();
+        let mut sum256_2: __m256 = _mm256_setzero_ps();
+
+        let mut i: usize = 0;
+        while i < m {
+            let sub256_1: __m256 = _mm256_sub_ps(load_bf16_ps(ptr1), load_bf16_ps(ptr2));
+            sum256_1 = _mm256_fmadd_ps(sub256_1, sub256_1, sum256_1);
+
+            let sub256_2: __m256 =
+                _mm256_sub_ps(load_bf16_ps(ptr1.add(1)), load_bf16_ps(ptr2.add(1)));
+            sum256_2 = _mm256_fmadd_ps(sub256_2, sub256_2, sum256_2);
+
+            ptr1 = ptr1.add(2);
+            ptr2 = ptr2.add(2);
+            i += 16;
+        }
+
+        let result = hsum256_ps_avx(_mm256_add_ps(sum256_1, sum256_2));
+        -(result
+            + v1[m..]
+                .iter()
+                .zip(&v2[m..])
+                .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+                .sum::<f32>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_spaces_avx() {
+        use half::bf16;
+
+        use super::*;
+        use crate::spaces::metric_bf16::simple_euclid::*;
+
+        if is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("avx2")
+            && is_x86_feature_detected!("fma")
+        {
+            let v1: Vec<bf16> = (0..67).map(|i| bf16::from_f32(i as f32 * 0.25)).collect();
+            let v2: Vec<bf16> = (0..67)
+                .map(|i| bf16::from_f32(8.0 - i as f32 * 0.5))
+                .collect();
+
+            let euclid_simd = unsafe { avx_euclid_similarity_bf16(&v1, &v2) };
+            let euclid = euclid_similarity_bf16(&v1, &v2);
+            assert!((euclid_simd - euclid).abs() / euclid.abs() < 0.0005);
+        } else {
+            println!("avx test skipped");
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::arch::x86_64::*;
+
+pub mod dot;
+pub mod euclid;
+pub mod manhattan;
+
+/// Load 8 bfloat16 values as single-precision floats.
+///
+/// Bfloat16 is the upper half of the bits of a single-precision float, so shifting is enough.
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn load_bf16_ps(ptr: *const __m128i) -> __m256 {
+    unsafe {
+        let widened = _mm256_cvtepu16_epi32(_mm_loadu_si128(ptr));
+        _mm256_castsi256_ps(_mm256_slli_epi32(widened, 16))
+    }
+}
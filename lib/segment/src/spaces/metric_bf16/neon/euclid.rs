@@ -0,0 +1,71 @@
+use std::arch::aarch64::*;
+
+use common::types::ScoreType;
+
+use super::load_bf16_f32x4x2;
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+
+#[target_feature(enable = "neon")]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe fn neon_euclid_similarity_bf16(
+    v1: &[VectorElementTypeBfloat16],
+    v2: &[VectorElementTypeBfloat16],
+) -> ScoreType {
+    debug_assert!(v1.len() == v2.len());
+
+    unsafe {
+        let n = v1.len();
+        let m = n - (n % 8);
+        let mut ptr1 = v1.as_ptr();
+        let mut ptr2 = v2.as_ptr();
+        let mut sum1 = vdupq_n_f32(0.);
+        let mut sum2 = vdupq_n_f32(0.);
+
+        let mut i: usize = 0;
+        while i < m {
+            let (a1, a2) = load_bf16_f32x4x2(ptr1);
+            let (b1, b2) = load_bf16_f32x4x2(ptr2);
+            let sub1 = vsubq_f32(a1, b1);
+            let sub2 = vsubq_f32(a2, b2);
+            sum1 = vfmaq_f32(sum1, sub1, sub1);
+            sum2 = vfmaq_f32(sum2, sub2, sub2);
+            ptr1 = ptr1.add(8);
+            ptr2 = ptr2.add(8);
+            i += 8;
+        }
+
+        let result = vaddvq_f32(vaddq_f32(sum1, sum2));
+        -(result
+            + v1[m..]
+                .iter()
+                .zip(&v2[m..])
+                .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+                .sum::<f32>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::arch::is_aarch64_feature_detected;
+
+    use half::bf16;
+
+    use super::*;
+    use crate::spaces::metric_bf16::simple_euclid::euclid_similarity_bf16;
+
+    #[test]
+    fn test_spaces_neon() {
+        if is_aarch64_feature_detected!("neon") {
+            let v1: Vec<bf16> = (0..67).map(|i| bf16::from_f32(i as f32 * 0.25)).collect();
+            let v2: Vec<bf16> = (0..67)
+                .map(|i| bf16::from_f32(8.0 - i as f32 * 0.5))
+                .collect();
+
+            let score_simd = unsafe { neon_euclid_similarity_bf16(&v1, &v2) };
+            let score = euclid_similarity_bf16(&v1, &v2);
+            assert!((score_simd - score).abs() / score.abs() < 0.0005);
+        } else {
+            println!("neon test skipped");
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use std::arch::aarch64::*;
+
+use crate::data_types::vectors::VectorElementTypeBfloat16;
+
+pub mod dot;
+pub mod euclid;
+pub mod manhattan;
+
+/// Load 8 bfloat16 values as two vectors of single-precision floats.
+///
+/// Bfloat16 is the upper half of the bits of a single-precision float, so shifting is enough.
+#[target_feature(enable = "neon")]
+#[inline]
+unsafe fn load_bf16_f32x4x2(ptr: *const VectorElementTypeBfloat16) -> (float32x4_t, float32x4_t) {
+    unsafe {
+        let raw = vld1q_u16(ptr.cast::<u16>());
+        (
+            vreinterpretq_f32_u32(vshll_n_u16::<16>(vget_low_u16(raw))),
+            vreinterpretq_f32_u32(vshll_high_n_u16::<16>(raw)),
+        )
+    }
+}
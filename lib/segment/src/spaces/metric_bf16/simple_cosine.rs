@@ -0,0 +1,21 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorElementTypeBfloat16};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::{CosineMetric, DotProductMetric};
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeBfloat16> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeBfloat16], v2: &[VectorElementTypeBfloat16]) -> ScoreType {
+        // Vectors are normalized on insertion
+        <DotProductMetric as Metric<VectorElementTypeBfloat16>>::similarity(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        <CosineMetric as Metric<VectorElementType>>::preprocess(vector)
+    }
+}
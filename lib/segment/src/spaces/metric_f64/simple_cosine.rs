@@ -0,0 +1,22 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorElementTypeDouble};
+use crate::spaces::metric::Metric;
+use crate::spaces::metric_f64::simple_dot::dot_similarity_double;
+use crate::spaces::simple::CosineMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeDouble> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeDouble], v2: &[VectorElementTypeDouble]) -> ScoreType {
+        // Vectors are normalized on insertion
+        dot_similarity_double(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        <CosineMetric as Metric<VectorElementType>>::preprocess(vector)
+    }
+}
@@ -0,0 +1,27 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeDouble};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::DotProductMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeDouble> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeDouble], v2: &[VectorElementTypeDouble]) -> ScoreType {
+        dot_similarity_double(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn dot_similarity_double(
+    v1: &[VectorElementTypeDouble],
+    v2: &[VectorElementTypeDouble],
+) -> ScoreType {
+    v1.iter().zip(v2).map(|(a, b)| a * b).sum::<f64>() as ScoreType
+}
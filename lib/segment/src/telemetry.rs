@@ -4,6 +4,7 @@ use serde::Serialize;
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
 use crate::types::{SegmentConfig, SegmentInfo, VectorNameBuf};
+use crate::vector_storage::read_stats::VectorStorageStats;
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
 pub struct SegmentTelemetry {
@@ -11,6 +12,42 @@ pub struct SegmentTelemetry {
     pub config: SegmentConfig,
     pub vector_index_searches: Vec<VectorIndexSearchesTelemetry>,
     pub payload_field_indices: Vec<PayloadIndexTelemetry>,
+    pub vector_storages: Vec<VectorStorageTelemetry>,
+    /// Health of HNSW graphs, to decide whether they should be rebuilt
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub hnsw_graphs: Vec<HnswGraphTelemetry>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct HnswGraphTelemetry {
+    #[anonymize(value = None)]
+    pub vector_name: Option<VectorNameBuf>,
+
+    /// Number of points in the graph, including deleted ones
+    pub points_count: usize,
+
+    /// Average number of links of non-deleted points, by level starting from 0
+    #[anonymize(false)]
+    pub avg_out_degree: Vec<f64>,
+
+    /// Number of connected components on level 0, formed by non-deleted points
+    pub connected_components: usize,
+
+    /// Number of non-deleted points, which can't be reached from the entry point on level 0
+    pub orphaned_points: usize,
+
+    /// Percentage of points in the graph, whose vectors are deleted
+    #[anonymize(false)]
+    pub deleted_percent: f64,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
+pub struct VectorStorageTelemetry {
+    #[anonymize(value = None)]
+    pub vector_name: Option<VectorNameBuf>,
+
+    #[serde(flatten)]
+    pub stats: VectorStorageStats,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
@@ -11,6 +11,7 @@ use std::sync::Arc;
 
 use ahash::AHashSet;
 use bytemuck::{Pod, Zeroable};
+use common::mmap::Advice;
 use common::stable_hash::StableHash;
 use common::types::{PointOffsetType, ScoreType};
 use ecow::EcoString;
@@ -35,7 +36,7 @@ use crate::data_types::index::{
     BoolIndexParams, DatetimeIndexParams, FloatIndexParams, GeoIndexParams, IntegerIndexParams,
     KeywordIndexParams, TextIndexParams, UuidIndexParams,
 };
-use crate::data_types::modifier::Modifier;
+use crate::data_types::modifier::{Bm25Params, Modifier};
 use crate::data_types::order_by::OrderValue;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::tiny_map::TinyMap;
@@ -493,6 +494,218 @@ pub struct SegmentInfo {
     pub deferred_internal_id: Option<PointOffsetType>,
 }
 
+/// Estimated RAM usage of segment components, in bytes.
+///
+/// Memory-mapped files are accounted by their resident pages, files of components loaded into
+/// RAM are accounted by their size on disk.
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, JsonSchema, Anonymize, PartialEq, Eq,
+)]
+#[anonymize(false)]
+pub struct SegmentMemoryUsage {
+    pub vector_storage_bytes: usize,
+    pub quantized_vectors_bytes: usize,
+    pub vector_index_bytes: usize,
+    pub payload_index_bytes: usize,
+    pub payload_storage_bytes: usize,
+    pub id_tracker_bytes: usize,
+    /// Vectors of on-disk storages, cached in RAM
+    pub cache_bytes: usize,
+}
+
+impl SegmentMemoryUsage {
+    pub fn total_bytes(&self) -> usize {
+        let Self {
+            vector_storage_bytes,
+            quantized_vectors_bytes,
+            vector_index_bytes,
+            payload_index_bytes,
+            payload_storage_bytes,
+            id_tracker_bytes,
+            cache_bytes,
+        } = self;
+
+        vector_storage_bytes
+            + quantized_vectors_bytes
+            + vector_index_bytes
+            + payload_index_bytes
+            + payload_storage_bytes
+            + id_tracker_bytes
+            + cache_bytes
+    }
+}
+
+impl std::ops::AddAssign for SegmentMemoryUsage {
+    fn add_assign(&mut self, other: Self) {
+        let Self {
+            vector_storage_bytes,
+            quantized_vectors_bytes,
+            vector_index_bytes,
+            payload_index_bytes,
+            payload_storage_bytes,
+            id_tracker_bytes,
+            cache_bytes,
+        } = other;
+
+        self.vector_storage_bytes += vector_storage_bytes;
+        self.quantized_vectors_bytes += quantized_vectors_bytes;
+        self.vector_index_bytes += vector_index_bytes;
+        self.payload_index_bytes += payload_index_bytes;
+        self.payload_storage_bytes += payload_storage_bytes;
+        self.id_tracker_bytes += id_tracker_bytes;
+        self.cache_bytes += cache_bytes;
+    }
+}
+
+/// Disk space taken by segment components, in bytes.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SegmentDiskUsage {
+    pub vector_storage_bytes: u64,
+    pub quantized_vectors_bytes: u64,
+    /// HNSW graphs of dense vectors
+    pub hnsw_index_bytes: u64,
+    /// Inverted indexes of sparse vectors
+    pub sparse_index_bytes: u64,
+    pub payload_storage_bytes: u64,
+    /// Payload indexes, by field
+    pub payload_index_bytes: HashMap<PayloadKeyType, u64>,
+    pub id_tracker_bytes: u64,
+}
+
+impl SegmentDiskUsage {
+    pub fn total_bytes(&self) -> u64 {
+        let Self {
+            vector_storage_bytes,
+            quantized_vectors_bytes,
+            hnsw_index_bytes,
+            sparse_index_bytes,
+            payload_storage_bytes,
+            payload_index_bytes,
+            id_tracker_bytes,
+        } = self;
+
+        vector_storage_bytes
+            + quantized_vectors_bytes
+            + hnsw_index_bytes
+            + sparse_index_bytes
+            + payload_storage_bytes
+            + payload_index_bytes.values().sum::<u64>()
+            + id_tracker_bytes
+    }
+}
+
+impl std::ops::AddAssign for SegmentDiskUsage {
+    fn add_assign(&mut self, other: Self) {
+        let Self {
+            vector_storage_bytes,
+            quantized_vectors_bytes,
+            hnsw_index_bytes,
+            sparse_index_bytes,
+            payload_storage_bytes,
+            payload_index_bytes,
+            id_tracker_bytes,
+        } = other;
+
+        self.vector_storage_bytes += vector_storage_bytes;
+        self.quantized_vectors_bytes += quantized_vectors_bytes;
+        self.hnsw_index_bytes += hnsw_index_bytes;
+        self.sparse_index_bytes += sparse_index_bytes;
+        self.payload_storage_bytes += payload_storage_bytes;
+        for (field, bytes) in payload_index_bytes {
+            *self.payload_index_bytes.entry(field).or_default() += bytes;
+        }
+        self.id_tracker_bytes += id_tracker_bytes;
+    }
+}
+
+/// Agreement of quantized scores with exact ones, accumulated over sampled queries.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QuantizationQualityStats {
+    /// Number of sampled queries
+    pub queries: usize,
+    /// Sum of recall of all queries
+    pub recall_sum: f64,
+    /// Number of scored pairs of query and point
+    pub scores_count: usize,
+    pub exact_scores_sum: f64,
+    pub quantized_scores_sum: f64,
+    pub exact_scores_sqr_sum: f64,
+    pub quantized_scores_sqr_sum: f64,
+    pub scores_product_sum: f64,
+}
+
+impl QuantizationQualityStats {
+    pub fn add_scores(&mut self, exact_score: ScoreType, quantized_score: ScoreType) {
+        let (exact_score, quantized_score) = (f64::from(exact_score), f64::from(quantized_score));
+        self.scores_count += 1;
+        self.exact_scores_sum += exact_score;
+        self.quantized_scores_sum += quantized_score;
+        self.exact_scores_sqr_sum += exact_score * exact_score;
+        self.quantized_scores_sqr_sum += quantized_score * quantized_score;
+        self.scores_product_sum += exact_score * quantized_score;
+    }
+
+    /// Average recall of quantized search, `None` if no queries were sampled
+    pub fn recall(&self) -> Option<f64> {
+        (self.queries > 0).then(|| self.recall_sum / self.queries as f64)
+    }
+
+    /// Pearson correlation of exact and quantized scores, `None` if it is not defined
+    pub fn score_correlation(&self) -> Option<f64> {
+        let n = self.scores_count as f64;
+        let covariance =
+            n * self.scores_product_sum - self.exact_scores_sum * self.quantized_scores_sum;
+        let exact_variance = n * self.exact_scores_sqr_sum - self.exact_scores_sum.powi(2);
+        let quantized_variance =
+            n * self.quantized_scores_sqr_sum - self.quantized_scores_sum.powi(2);
+        let denominator = (exact_variance * quantized_variance).sqrt();
+        (denominator > 0.0).then(|| (covariance / denominator).clamp(-1.0, 1.0))
+    }
+}
+
+impl std::ops::AddAssign for QuantizationQualityStats {
+    fn add_assign(&mut self, other: Self) {
+        let Self {
+            queries,
+            recall_sum,
+            scores_count,
+            exact_scores_sum,
+            quantized_scores_sum,
+            exact_scores_sqr_sum,
+            quantized_scores_sqr_sum,
+            scores_product_sum,
+        } = other;
+
+        self.queries += queries;
+        self.recall_sum += recall_sum;
+        self.scores_count += scores_count;
+        self.exact_scores_sum += exact_scores_sum;
+        self.quantized_scores_sum += quantized_scores_sum;
+        self.exact_scores_sqr_sum += exact_scores_sqr_sum;
+        self.quantized_scores_sqr_sum += quantized_scores_sqr_sum;
+        self.scores_product_sum += scores_product_sum;
+    }
+}
+
+/// Quantized representation of a vector, as it is stored and scored
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct QuantizedVectorData {
+    /// Quantization the vector is encoded with
+    pub quantization_config: QuantizationConfig,
+    /// Dimension of the original vector
+    pub dim: usize,
+    /// Encoded vector: one byte per component for int8 scalar quantization, two 4-bit codes
+    /// per byte for int4 (lower bits first), centroid indices for product and residual
+    /// quantization, or packed bits for binary quantization
+    pub codes: Vec<u8>,
+    /// Scalar quantization only: component `i` is restored as `offset + scale * codes[i]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<f32>,
+    /// Scalar quantization only: component `i` is restored as `offset + scale * codes[i]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<f32>,
+}
+
 #[derive(Debug, Default)]
 pub struct SizeStats {
     pub num_vectors: usize,
@@ -583,6 +796,26 @@ pub struct AcornSearchParams {
     pub max_selectivity: Option<OrderedFloat<f64>>,
 }
 
+/// Parameters of early termination of the HNSW search.
+///
+/// The search stops once the total score of the top results improves by less than `epsilon`
+/// for `patience` consecutive expansions of graph nodes. Trades marginal recall for lower
+/// tail latency.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq, Default, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct EarlyTerminationParams {
+    /// Minimal improvement of the total score of the top results, for an expansion to count as
+    /// progress.
+    #[validate(range(min = 0.0))]
+    pub epsilon: OrderedFloat<f64>,
+
+    /// Number of consecutive expansions without progress, after which the search stops.
+    #[validate(range(min = 1))]
+    pub patience: usize,
+}
+
 /// Additional parameters of the search
 #[derive(
     Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Default, Hash,
@@ -615,6 +848,56 @@ pub struct SearchParams {
     #[validate(nested)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub acorn: Option<AcornSearchParams>,
+
+    /// Desired recall of the approximate search, from 0.0 to 1.0.
+    ///
+    /// If set, the size of the beam is chosen for each segment from its recall calibration,
+    /// measured while building the index, and `hnsw_ef` is ignored.
+    /// Segments without calibration use `hnsw_ef`.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub target_recall: Option<OrderedFloat<f64>>,
+
+    /// Params relevant to IVF index
+    /// Number of inverted lists scanned during the search. Larger the value - more accurate the
+    /// result, more time required for search.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub nprobe: Option<usize>,
+
+    /// Params relevant to HNSW index
+    /// Stop the search once the top results converge, instead of exhausting the beam.
+    #[serde(default)]
+    #[validate(nested)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub early_termination: Option<EarlyTerminationParams>,
+
+    /// Params relevant to sparse index
+    /// Max length of posting lists, which are traversed in full. Longer posting lists are only
+    /// used to complete scores of candidates found in the shorter ones. Larger the value - more
+    /// accurate the result, more time required for search.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub sparse_max_posting_length: Option<usize>,
+}
+
+impl SearchParams {
+    /// Replace quantization params of `params` with `quantization`, if given.
+    pub fn with_quantization_override(
+        params: Option<Self>,
+        quantization: Option<QuantizationSearchParams>,
+    ) -> Option<Self> {
+        match quantization {
+            None => params,
+            Some(quantization) => Some(Self {
+                quantization: Some(quantization),
+                ..params.unwrap_or_default()
+            }),
+        }
+    }
 }
 
 /// Configuration for vectors.
@@ -635,6 +918,12 @@ pub enum Indexes {
     /// Use filterable HNSW index for approximate search. Is very fast even on a very huge collections,
     /// but require additional space to store index and additional time to build it.
     Hnsw(HnswConfig),
+    /// Use single-layer Vamana graph (DiskANN), laid out for reading from disk. Requires less RAM
+    /// than HNSW on large collections, at the cost of slower build and filtered search.
+    Vamana(VamanaConfig),
+    /// Use inverted file index: vectors are clustered around k-means centroids, and only the
+    /// closest clusters are scanned during search. Fast to build and search, at the cost of recall.
+    Ivf(IvfConfig),
 }
 
 impl Indexes {
@@ -642,6 +931,8 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(_) => true,
+            Indexes::Vamana(_) => true,
+            Indexes::Ivf(_) => true,
         }
     }
 
@@ -649,6 +940,8 @@ impl Indexes {
         match self {
             Indexes::Plain {} => false,
             Indexes::Hnsw(config) => config.on_disk.unwrap_or_default(),
+            Indexes::Vamana(config) => config.on_disk.unwrap_or(true),
+            Indexes::Ivf(config) => config.on_disk.unwrap_or_default(),
         }
     }
 }
@@ -691,6 +984,23 @@ pub struct HnswConfig {
     /// Requires quantized vectors to be enabled. Multi-vectors are not supported.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub inline_storage: Option<bool>,
+    /// Store HNSW graph links with varint delta encoding of neighbor ids. Default: false.
+    /// Reduces disk and page cache footprint of the graph, at the cost of slower link decoding
+    /// during the search.
+    /// Not applied if `inline_storage` is enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub varint_links: Option<bool>,
+    /// Use ACORN predicate-aware traversal for filtered searches, instead of building additional
+    /// payload-aware links. Default: false.
+    /// If enabled, `payload_m` links are not built, and filtered searches evaluate filters on
+    /// 2-hop neighbors, unless ACORN is disabled in search params.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acorn: Option<bool>,
+    /// Seed of the random number generator, used to build the graph. Default: not set.
+    /// If set, level assignment and linking of points are deterministic for the same input,
+    /// so that builds can be reproduced. Points are linked in a single thread then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
 }
 
 impl HnswConfig {
@@ -711,6 +1021,9 @@ impl HnswConfig {
             payload_m,
             on_disk,
             inline_storage,
+            varint_links,
+            acorn,
+            seed: _,
         } = *self;
 
         m != other.m
@@ -722,6 +1035,169 @@ impl HnswConfig {
             // to flip this flag
             || on_disk != other.on_disk
             || inline_storage != other.inline_storage
+            || varint_links != other.varint_links
+            || acorn != other.acorn
+    }
+}
+
+/// Config of Vamana index
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate, Anonymize,
+)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct VamanaConfig {
+    /// Maximal number of edges per node in the index graph. Larger the value - more accurate the
+    /// search, more space required. Default: 64
+    #[validate(range(min = 4))]
+    #[serde(default = "default_vamana_max_degree")]
+    pub max_degree: usize,
+    /// Size of the candidate list during the index building. Larger the value - more accurate the
+    /// search, more time required to build index. Default: 100
+    #[validate(range(min = 4))]
+    #[serde(default = "default_vamana_build_list_size")]
+    pub build_list_size: usize,
+    /// Number of candidates expanded at once during the search. Larger the value - less round
+    /// trips to disk, but more vectors are scored. Default: 4
+    #[validate(range(min = 1))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beam_width: Option<usize>,
+    /// Minimal size threshold (in KiloBytes) below which full-scan is preferred over graph search.
+    /// Same as `full_scan_threshold` of HNSW index. Default: 10000
+    #[serde(
+        alias = "full_scan_threshold_kb",
+        default = "default_vamana_full_scan_threshold"
+    )]
+    pub full_scan_threshold: usize,
+    /// Store Vamana graph on disk. If set to false, graph is loaded into RAM. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+pub const DEFAULT_VAMANA_BEAM_WIDTH: usize = 4;
+
+const fn default_vamana_max_degree() -> usize {
+    64
+}
+
+const fn default_vamana_build_list_size() -> usize {
+    100
+}
+
+const fn default_vamana_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
+impl Default for VamanaConfig {
+    fn default() -> Self {
+        VamanaConfig {
+            max_degree: default_vamana_max_degree(),
+            build_list_size: default_vamana_build_list_size(),
+            beam_width: None,
+            full_scan_threshold: default_vamana_full_scan_threshold(),
+            on_disk: None,
+        }
+    }
+}
+
+impl VamanaConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        let VamanaConfig {
+            max_degree,
+            build_list_size,
+            beam_width: _,
+            full_scan_threshold,
+            on_disk,
+        } = *self;
+
+        max_degree != other.max_degree
+            || build_list_size != other.build_list_size
+            || full_scan_threshold != other.full_scan_threshold
+            || on_disk != other.on_disk
+    }
+}
+
+/// Config of IVF index
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate, Anonymize,
+)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct IvfConfig {
+    /// Number of inverted lists, i.e. k-means centroids vectors are clustered around.
+    /// If not set, `4 * sqrt(number of vectors)` is used.
+    #[validate(range(min = 1))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_lists: Option<usize>,
+    /// Number of inverted lists scanned during the search, if not set in search params.
+    /// Larger the value - more accurate the search, more time required. Default: 8
+    #[validate(range(min = 1))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nprobe: Option<usize>,
+    /// If set, vectors in inverted lists are encoded with product quantization, and scanned
+    /// lists are scored with the codes. Best candidates are rescored with original vectors.
+    #[validate(nested)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pq: Option<IvfPqConfig>,
+    /// Minimal size threshold (in KiloBytes) below which full-scan is preferred over IVF search.
+    /// Same as `full_scan_threshold` of HNSW index. Default: 10000
+    #[serde(
+        alias = "full_scan_threshold_kb",
+        default = "default_ivf_full_scan_threshold"
+    )]
+    pub full_scan_threshold: usize,
+    /// Store inverted lists on disk. If set to false, lists are loaded into RAM. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_disk: Option<bool>,
+}
+
+/// Product quantization of vectors in IVF inverted lists
+#[derive(
+    Copy, Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate, Anonymize,
+)]
+#[serde(rename_all = "snake_case")]
+#[anonymize(false)]
+pub struct IvfPqConfig {
+    /// Number of sub-vectors each vector is split into. Each sub-vector is encoded with 1 byte.
+    /// Larger the value - more accurate the search, more space required.
+    #[validate(range(min = 1))]
+    pub num_subvectors: usize,
+}
+
+pub const DEFAULT_IVF_NPROBE: usize = 8;
+
+const fn default_ivf_full_scan_threshold() -> usize {
+    DEFAULT_FULL_SCAN_THRESHOLD
+}
+
+impl Default for IvfConfig {
+    fn default() -> Self {
+        IvfConfig {
+            num_lists: None,
+            nprobe: None,
+            pq: None,
+            full_scan_threshold: default_ivf_full_scan_threshold(),
+            on_disk: None,
+        }
+    }
+}
+
+impl IvfConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        let IvfConfig {
+            num_lists,
+            nprobe: _,
+            pq,
+            full_scan_threshold,
+            on_disk,
+        } = *self;
+
+        num_lists != other.num_lists
+            || pq != other.pq
+            || full_scan_threshold != other.full_scan_threshold
+            || on_disk != other.on_disk
     }
 }
 
@@ -762,6 +1238,7 @@ pub enum CompressionRatio {
 pub enum ScalarType {
     #[default]
     Int8,
+    Int4,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
@@ -769,6 +1246,8 @@ pub enum ScalarType {
 pub struct ScalarQuantizationConfig {
     /// Type of quantization to use
     /// If `int8` - 8 bit quantization will be used
+    /// If `int4` - 4 bit quantization will be used, each vector is quantized within its own range of values
+    /// and `quantile` is not used. Search results are rescored with original vectors by default
     pub r#type: ScalarType,
     /// Quantile for quantization. Expected value range in [0.5, 1.0]. If not set - use the whole range of values
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -777,6 +1256,11 @@ pub struct ScalarQuantizationConfig {
     /// If true - quantized vectors always will be stored in RAM, ignoring the config of main storage
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+    /// If true - original vectors of non-appendable segments are dropped once quantized vectors are built.
+    /// Vectors are reconstructed approximately from quantized data on read, rescoring is not possible.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drop_originals: Option<bool>,
 }
 
 impl ScalarQuantizationConfig {
@@ -803,6 +1287,12 @@ pub struct ProductQuantizationConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub always_ram: Option<bool>,
+
+    /// If true - original vectors of non-appendable segments are dropped once quantized vectors are built.
+    /// Vectors are reconstructed approximately from quantized data on read, rescoring is not possible.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drop_originals: Option<bool>,
 }
 
 impl ProductQuantizationConfig {
@@ -822,6 +1312,91 @@ pub struct ProductQuantization {
     pub product: ProductQuantizationConfig,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ResidualQuantizationConfig {
+    /// Total compression ratio, shared between all stages
+    pub compression: CompressionRatio,
+
+    /// Number of quantization stages. Each stage encodes the error left by previous ones.
+    /// More stages give better accuracy at the cost of slower scoring.
+    #[validate(range(min = 1, max = 8))]
+    pub stages: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_ram: Option<bool>,
+}
+
+impl ResidualQuantizationConfig {
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Returns true only if both conditions are met:
+    /// - this configuration does not match `other`
+    /// - to effectively change the configuration, a quantization rebuild is required
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self != other
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ResidualQuantization {
+    #[validate(nested)]
+    pub residual: ResidualQuantizationConfig,
+}
+
+pub const DEFAULT_ANISOTROPIC_PARALLEL_WEIGHT: f32 = 4.0;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct AnisotropicQuantizationConfig {
+    /// Compression ratio, the same as for product quantization
+    pub compression: CompressionRatio,
+
+    /// Weight of quantization error parallel to the original vector, relative to the orthogonal error.
+    /// Parallel error changes inner products with similar queries the most, so higher weight
+    /// improves accuracy of top scores at the cost of others. Only applies to dot product and cosine
+    /// distances, vectors are encoded as with product quantization otherwise. Expected value range
+    /// in [1.0, 100.0], 1.0 is the same as product quantization. Default: 4.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1.0, max = 100.0))]
+    pub parallel_weight: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_ram: Option<bool>,
+}
+
+impl AnisotropicQuantizationConfig {
+    pub fn parallel_weight(&self) -> f32 {
+        self.parallel_weight
+            .unwrap_or(DEFAULT_ANISOTROPIC_PARALLEL_WEIGHT)
+    }
+
+    /// Detect configuration mismatch against `other` that requires rebuilding
+    ///
+    /// Returns true only if both conditions are met:
+    /// - this configuration does not match `other`
+    /// - to effectively change the configuration, a quantization rebuild is required
+    pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
+        self != other
+    }
+}
+
+impl Hash for AnisotropicQuantizationConfig {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.compression.hash(state);
+        self.parallel_weight.map(f32::to_bits).hash(state);
+        self.always_ram.hash(state);
+    }
+}
+
+impl Eq for AnisotropicQuantizationConfig {}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct AnisotropicQuantization {
+    #[validate(nested)]
+    pub anisotropic: AnisotropicQuantizationConfig,
+}
+
 impl Hash for ScalarQuantizationConfig {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.always_ram.hash(state);
@@ -875,6 +1450,8 @@ pub enum QuantizationConfig {
     Scalar(ScalarQuantization),
     Product(ProductQuantization),
     Binary(BinaryQuantization),
+    Residual(ResidualQuantization),
+    Anisotropic(AnisotropicQuantization),
 }
 
 impl QuantizationConfig {
@@ -898,6 +1475,21 @@ impl QuantizationConfig {
     pub fn supports_appendable(&self) -> bool {
         matches!(self, QuantizationConfig::Binary(_))
     }
+
+    /// Whether original vectors are dropped once quantized vectors are built
+    pub fn drop_originals(&self) -> bool {
+        match self {
+            QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
+                scalar.drop_originals == Some(true)
+            }
+            QuantizationConfig::Product(ProductQuantization { product }) => {
+                product.drop_originals == Some(true)
+            }
+            QuantizationConfig::Binary(_)
+            | QuantizationConfig::Residual(_)
+            | QuantizationConfig::Anisotropic(_) => false,
+        }
+    }
 }
 
 impl Validate for QuantizationConfig {
@@ -906,6 +1498,8 @@ impl Validate for QuantizationConfig {
             QuantizationConfig::Scalar(scalar) => scalar.validate(),
             QuantizationConfig::Product(product) => product.validate(),
             QuantizationConfig::Binary(binary) => binary.validate(),
+            QuantizationConfig::Residual(residual) => residual.validate(),
+            QuantizationConfig::Anisotropic(anisotropic) => anisotropic.validate(),
         }
     }
 }
@@ -921,6 +1515,7 @@ pub enum BinaryQuantizationQueryEncoding {
     Binary,
     Scalar4Bits,
     Scalar8Bits,
+    Float32,
 }
 
 impl From<ScalarQuantizationConfig> for QuantizationConfig {
@@ -941,6 +1536,20 @@ impl From<BinaryQuantizationConfig> for QuantizationConfig {
     }
 }
 
+impl From<ResidualQuantizationConfig> for QuantizationConfig {
+    fn from(config: ResidualQuantizationConfig) -> Self {
+        QuantizationConfig::Residual(ResidualQuantization { residual: config })
+    }
+}
+
+impl From<AnisotropicQuantizationConfig> for QuantizationConfig {
+    fn from(config: AnisotropicQuantizationConfig) -> Self {
+        QuantizationConfig::Anisotropic(AnisotropicQuantization {
+            anisotropic: config,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Default, Hash)]
 pub struct StrictModeSparse {
     /// Max length of sparse vector
@@ -1348,6 +1957,9 @@ impl Default for HnswConfig {
             on_disk: Some(false),
             payload_m: None,
             inline_storage: None,
+            varint_links: None,
+            acorn: None,
+            seed: None,
         }
     }
 }
@@ -1554,6 +2166,9 @@ pub enum VectorStorageType {
     /// Storage in a single mmap file, not appendable
     /// Pre-fetched into RAM on load
     InRamMmap,
+    /// Only scalar or product quantized vectors are stored, not appendable
+    /// Original vectors are dropped, vectors are reconstructed approximately on read
+    Quantized,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -1577,6 +2192,43 @@ pub enum VectorStorageDatatype {
     Float16,
     // Unsigned 8-bit integer
     Uint8,
+    // Double-precision floating point
+    Float64,
+    // Signed 8-bit integer with per-vector scale and zero-point
+    Int8,
+    // Brain floating point, 16 bits with the exponent range of single precision
+    Bfloat16,
+    // Unsigned 4-bit integer, packed two per byte, with per-vector scale and zero-point
+    Int4,
+}
+
+/// Hint for the OS on how memory-mapped vector storage files are going to be accessed
+///
+/// See [`madvise(2)`](https://man7.org/linux/man-pages/man2/madvise.2.html) man page.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Anonymize, Eq, PartialEq, Copy, Clone, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MmapAdvice {
+    /// No special treatment, moderate readahead
+    Normal,
+    /// Expect page references in random order, no readahead
+    Random,
+    /// Expect page references in sequential order, aggressive readahead
+    Sequential,
+    /// Expect access in the near future, start reading pages ahead of time
+    WillNeed,
+}
+
+impl From<MmapAdvice> for Advice {
+    fn from(advice: MmapAdvice) -> Self {
+        match advice {
+            MmapAdvice::Normal => Advice::Normal,
+            MmapAdvice::Random => Advice::Random,
+            MmapAdvice::Sequential => Advice::Sequential,
+            MmapAdvice::WillNeed => Advice::WillNeed,
+        }
+    }
 }
 
 #[derive(
@@ -1586,12 +2238,25 @@ pub enum VectorStorageDatatype {
 pub struct MultiVectorConfig {
     /// How to compare multivector points
     pub comparator: MultiVectorComparator,
+    /// Maximum number of vectors in a single multivector point.
+    /// If not set, the number of vectors is not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_vectors_per_point: Option<usize>,
+    /// What to do with multivectors, which have more vectors than `max_vectors_per_point`.
+    /// Default: reject
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overflow_policy: Option<MultiVectorOverflowPolicy>,
 }
 
 impl MultiVectorConfig {
     fn check_compatible(&self, other: &Self) -> Result<(), String> {
         // Assert multi-vector config fields
-        let Self { comparator } = self;
+        // Limit of vectors is only enforced on insertion, stored multivectors are compatible
+        let Self {
+            comparator,
+            max_vectors_per_point: _,
+            overflow_policy: _,
+        } = self;
 
         if *comparator != other.comparator {
             return Err(format!(
@@ -1613,6 +2278,20 @@ pub enum MultiVectorComparator {
     MaxSim,
 }
 
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Anonymize, Eq, PartialEq, Copy, Clone, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiVectorOverflowPolicy {
+    /// Reject multivectors with too many vectors
+    #[default]
+    Reject,
+    /// Keep the first `max_vectors_per_point` vectors, drop the rest
+    Truncate,
+    /// Keep the first `max_vectors_per_point - 1` vectors, replace the rest with their average
+    AveragePool,
+}
+
 impl VectorStorageType {
     /// Convert user-facing `on_disk` (true = store on disk) to appendable vector storage type.
     /// Returns `ChunkedMmap` or `InRamChunkedMmap`.
@@ -1628,11 +2307,44 @@ impl VectorStorageType {
     pub fn is_on_disk(&self) -> bool {
         match self {
             Self::Memory | Self::InRamChunkedMmap | Self::InRamMmap => false,
-            Self::Mmap | Self::ChunkedMmap => true,
+            Self::Mmap | Self::ChunkedMmap | Self::Quantized => true,
         }
     }
 }
 
+/// Range of dimensions of vectors, stored under a single name
+///
+/// Allows to store vectors truncated to a prefix of a different length, e.g. Matryoshka
+/// embeddings. Shorter vectors are padded with zeros up to the size of the vectors in storage,
+/// similarity is computed over the stored prefix only.
+#[derive(
+    Debug, Deserialize, Serialize, JsonSchema, Anonymize, Eq, PartialEq, Copy, Clone, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub struct DimRange {
+    /// Minimal dimension of stored vectors
+    pub min: usize,
+    /// Maximal dimension of stored vectors, must not exceed the size of the vectors
+    pub max: usize,
+}
+
+impl DimRange {
+    pub fn contains(&self, dim: usize) -> bool {
+        (self.min..=self.max).contains(&dim)
+    }
+
+    /// Check that the range is not empty, and fits vectors of the given `size`
+    pub fn check_size(&self, size: usize) -> Result<(), String> {
+        let Self { min, max } = *self;
+        if min == 0 || min > max || max > size {
+            return Err(format!(
+                "Invalid dim range {min}..={max}, expected 1 <= min <= max <= {size}",
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Config of single vector data storage
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Anonymize)]
 #[serde(rename_all = "snake_case")]
@@ -1653,6 +2365,22 @@ pub struct VectorDataConfig {
     /// Vector specific configuration to set specific storage element type
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub datatype: Option<VectorStorageDatatype>,
+    /// Access pattern hint for memory-mapped storages. If none - the default for the storage type
+    /// is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mmap_advice: Option<MmapAdvice>,
+    /// Range of dimensions of stored vectors, if vectors may be shorter than `size`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim_range: Option<DimRange>,
+    /// Keep norms of Cosine vectors, so that vectors can be retrieved as they were inserted.
+    /// Vectors are normalized by the storage instead of on insert.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preserve_norm: Option<bool>,
+    /// Number of previous versions of every vector to keep, so that vectors can be retrieved as
+    /// they were at an older operation. If none or 0 - no history is kept.
+    /// Only supported for dense single vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_versions: Option<usize>,
 }
 
 impl VectorDataConfig {
@@ -1663,6 +2391,8 @@ impl VectorDataConfig {
         let is_index_appendable = match self.index {
             Indexes::Plain {} => true,
             Indexes::Hnsw(_) => false,
+            Indexes::Vamana(_) => false,
+            Indexes::Ivf(_) => false,
         };
         let is_storage_appendable = match self.storage_type {
             VectorStorageType::Memory => true,
@@ -1670,10 +2400,32 @@ impl VectorDataConfig {
             VectorStorageType::ChunkedMmap => true,
             VectorStorageType::InRamChunkedMmap => true,
             VectorStorageType::InRamMmap => false,
+            VectorStorageType::Quantized => false,
         };
         is_index_appendable && is_storage_appendable
     }
 
+    /// Whether norms of vectors are kept next to normalized vectors
+    pub fn preserve_norm(&self) -> bool {
+        self.preserve_norm.unwrap_or(false)
+    }
+
+    /// Number of previous versions of every vector to keep
+    pub fn max_versions(&self) -> usize {
+        self.max_versions.unwrap_or(0)
+    }
+
+    /// Whether quantized vectors can be rebuilt with `target` config in place, keeping vector
+    /// storage and index as they are.
+    ///
+    /// Rebuilding requires original vectors, so they must not be dropped now or with the target
+    /// config.
+    pub fn can_requantize_in_place(&self, target: &QuantizationConfig) -> bool {
+        self.quantization_config.is_some()
+            && self.storage_type != VectorStorageType::Quantized
+            && !target.drop_originals()
+    }
+
     pub fn check_compatible(&self, other: &Self) -> Result<(), String> {
         // Size and distance have to be the same for both segments.
         // Storage type, index and quantization config can be different.
@@ -1687,6 +2439,10 @@ impl VectorDataConfig {
             quantization_config: _,
             multivector_config,
             datatype,
+            mmap_advice: _,
+            dim_range,
+            preserve_norm: _,
+            max_versions: _,
         } = self;
 
         if *size != other.size {
@@ -1711,6 +2467,21 @@ impl VectorDataConfig {
             ));
         }
 
+        if *dim_range != other.dim_range {
+            return Err(format!(
+                "Incompatible configs: expected dim range {dim_range:?}, but got {other_dim_range:?}",
+                other_dim_range = other.dim_range
+            ));
+        }
+
+        if self.preserve_norm() != other.preserve_norm() {
+            return Err(format!(
+                "Incompatible configs: expected preserve norm {this_preserve_norm}, but got {other_preserve_norm}",
+                this_preserve_norm = self.preserve_norm(),
+                other_preserve_norm = other.preserve_norm(),
+            ));
+        }
+
         match (multivector_config, &other.multivector_config) {
             (None, None) => {}
             (Some(this), Some(other)) => {
@@ -1739,17 +2510,18 @@ pub enum SparseVectorStorageType {
     /// Storage in memory maps (gridstore storage)
     #[default]
     Mmap,
+    /// Storage in memory, persisted in an append-only log
+    AppendableWal,
 }
 
 impl SparseVectorStorageType {
     /// Whether this storage type is a mmap on disk
     pub fn is_on_disk(&self) -> bool {
         match self {
-            // Both options are on disk, but we keep it explicit for the case if someone adds a new
-            // storage type in the future
             #[cfg(feature = "rocksdb")]
             Self::OnDisk => true,
             Self::Mmap => true,
+            Self::AppendableWal => false,
         }
     }
 }
@@ -1771,6 +2543,11 @@ pub struct SparseVectorDataConfig {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Parameters of BM25 ranking, used with the `idf` modifier.
+    /// If none - only IDF is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bm25: Option<Bm25Params>,
 }
 
 /// If the storage type is not in config, it means it is the OnDisk variant
@@ -1798,6 +2575,7 @@ impl SparseVectorDataConfig {
             index: _,
             storage_type: _,
             modifier,
+            bm25,
         } = self;
 
         if modifier != &other.modifier {
@@ -1807,6 +2585,13 @@ impl SparseVectorDataConfig {
             ));
         }
 
+        if bm25 != &other.bm25 {
+            return Err(format!(
+                "Incompatible configs: expected sparse vector BM25 params {bm25:?}, but got {other_bm25:?}",
+                other_bm25 = other.bm25
+            ));
+        }
+
         Ok(())
     }
 }
@@ -2220,6 +3005,19 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Bool(params) => params.enable_hnsw.unwrap_or(true),
         }
     }
+
+    pub fn enable_hnsw_ranges(&self) -> bool {
+        match self {
+            PayloadSchemaParams::Integer(params) => params.enable_hnsw_ranges.unwrap_or_default(),
+            PayloadSchemaParams::Float(params) => params.enable_hnsw_ranges.unwrap_or_default(),
+            PayloadSchemaParams::Datetime(params) => params.enable_hnsw_ranges.unwrap_or_default(),
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Uuid(_)
+            | PayloadSchemaParams::Text(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Bool(_) => false,
+        }
+    }
 }
 
 impl Validate for PayloadSchemaParams {
@@ -2378,6 +3176,13 @@ impl PayloadFieldSchema {
             PayloadFieldSchema::FieldParams(p) => p.enable_hnsw(),
         }
     }
+
+    pub fn enable_hnsw_ranges(&self) -> bool {
+        match self {
+            PayloadFieldSchema::FieldType(_) => false,
+            PayloadFieldSchema::FieldParams(p) => p.enable_hnsw_ranges(),
+        }
+    }
 }
 
 impl From<PayloadSchemaType> for PayloadFieldSchema {
@@ -2525,16 +3330,24 @@ impl<S: Into<String>> From<S> for MatchText {
 }
 
 /// Full-text phrase match of the string.
+///
+/// Proximity match can be requested with `"quick fox"~2` syntax of the phrase, or with `slop`.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub struct MatchPhrase {
     pub phrase: String,
+    /// Max number of other tokens allowed between the tokens of the phrase, in total.
+    /// Tokens must still appear in the same order as in the phrase.
+    /// Overrides the slop of the `"..."~N` syntax. Default: 0, exact phrase.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slop: Option<u32>,
 }
 
 impl<S: Into<String>> From<S> for MatchPhrase {
     fn from(text: S) -> Self {
         MatchPhrase {
             phrase: text.into(),
+            slop: None,
         }
     }
 }
@@ -2589,6 +3402,7 @@ impl Match {
     pub fn new_phrase(phrase: &str) -> Self {
         Self::Phrase(MatchPhrase {
             phrase: phrase.into(),
+            slop: None,
         })
     }
 
@@ -2619,7 +3433,9 @@ impl From<MatchInterface> for Match {
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
             }),
-            MatchInterface::Phrase(MatchPhrase { phrase }) => Self::Phrase(MatchPhrase { phrase }),
+            MatchInterface::Phrase(MatchPhrase { phrase, slop }) => {
+                Self::Phrase(MatchPhrase { phrase, slop })
+            }
         }
     }
 }
@@ -4409,6 +5225,33 @@ mod tests {
         assert!(filter.is_err())
     }
 
+    #[test]
+    fn test_vector_data_config_mmap_advice() {
+        // Configs persisted before `mmap_advice` was introduced
+        let config = r#"
+        {
+            "size": 4,
+            "distance": "Dot",
+            "storage_type": "Mmap",
+            "index": { "type": "plain", "options": {} },
+            "quantization_config": null
+        }
+        "#;
+        let mut config: VectorDataConfig = serde_json::from_str(config).unwrap();
+        assert_eq!(config.mmap_advice, None);
+        assert!(
+            !serde_json::to_string(&config)
+                .unwrap()
+                .contains("mmap_advice")
+        );
+
+        config.mmap_advice = Some(MmapAdvice::WillNeed);
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""mmap_advice":"will_need""#));
+        let config: VectorDataConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.mmap_advice, Some(MmapAdvice::WillNeed));
+    }
+
     #[test]
     fn test_parse_match_query() {
         let query = r#"
@@ -5317,6 +6160,47 @@ mod tests {
         let deserialized: Filter = serde_cbor::from_slice(&cbor_bytes).unwrap();
         assert_eq!(filter, deserialized);
     }
+
+    #[test]
+    fn test_search_params_with_quantization_override() {
+        let quantization = QuantizationSearchParams {
+            ignore: false,
+            rescore: Some(true),
+            oversampling: Some(2.0),
+        };
+        let params = SearchParams {
+            hnsw_ef: Some(128),
+            exact: false,
+            quantization: Some(QuantizationSearchParams {
+                ignore: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // No override keeps params as is
+        assert_eq!(
+            SearchParams::with_quantization_override(Some(params), None),
+            Some(params),
+        );
+        assert_eq!(SearchParams::with_quantization_override(None, None), None);
+
+        // Override replaces quantization params only
+        assert_eq!(
+            SearchParams::with_quantization_override(Some(params), Some(quantization)),
+            Some(SearchParams {
+                quantization: Some(quantization),
+                ..params
+            }),
+        );
+        assert_eq!(
+            SearchParams::with_quantization_override(None, Some(quantization)),
+            Some(SearchParams {
+                quantization: Some(quantization),
+                ..Default::default()
+            }),
+        );
+    }
 }
 
 fn shard_key_string_example() -> String {
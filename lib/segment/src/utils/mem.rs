@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
 #[derive(Debug)]
 pub struct Mem {
     #[cfg(target_os = "linux")]
@@ -160,3 +163,58 @@ mod sysinfo_mem {
         }
     }
 }
+
+/// Resident memory of memory-mapped files of the current process.
+///
+/// Takes a snapshot of `/proc/self/smaps` on creation, so it can be shared for all segments
+/// within a single request. Empty on non-Linux platforms.
+#[derive(Debug, Default)]
+pub struct MappedFilesResidency {
+    resident_bytes: HashMap<PathBuf, u64>,
+}
+
+impl MappedFilesResidency {
+    pub fn load() -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            match Self::load_smaps() {
+                Ok(residency) => residency,
+                Err(err) => {
+                    log::warn!("Failed to read memory mappings of current process: {err}");
+                    Self::default()
+                }
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        Self::default()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn load_smaps() -> procfs::ProcResult<Self> {
+        use procfs::process::{MMapPath, Process};
+
+        let mut resident_bytes = HashMap::new();
+
+        for map in Process::myself()?.smaps()? {
+            let MMapPath::Path(path) = map.pathname else {
+                continue;
+            };
+            let rss = map.extension.map.get("Rss").copied().unwrap_or(0);
+            *resident_bytes.entry(path).or_insert(0) += rss;
+        }
+
+        Ok(Self { resident_bytes })
+    }
+
+    /// Resident bytes of the given file, or `None` if the file is not memory-mapped.
+    pub fn resident_bytes(&self, path: &Path) -> Option<u64> {
+        if let Some(bytes) = self.resident_bytes.get(path) {
+            return Some(*bytes);
+        }
+
+        // Mappings are listed with absolute paths
+        let path = fs_err::canonicalize(path).ok()?;
+        self.resident_bytes.get(&path).copied()
+    }
+}
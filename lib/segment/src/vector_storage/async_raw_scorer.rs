@@ -15,11 +15,10 @@ use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::Distance;
 use crate::vector_storage::dense::dense_vector_storage::DenseVectorStorageImpl;
-use crate::vector_storage::dense::immutable_dense_vectors::ImmutableDenseVectors;
 use crate::vector_storage::query::NaiveFeedbackQuery;
 use crate::vector_storage::query_scorer::QueryScorer;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
-use crate::vector_storage::{RawScorer, VectorStorage as _};
+use crate::vector_storage::{DenseVectorStorage as _, RawScorer, VectorStorage as _};
 
 pub fn new<'a, T, Storage>(
     query: QueryVector,
@@ -44,7 +43,7 @@ where
     Scorer: QueryScorer<TVector = [T]>,
 {
     query_scorer: Scorer,
-    storage: &'a ImmutableDenseVectors<T, Storage>,
+    storage: &'a DenseVectorStorageImpl<T, Storage>,
 }
 
 impl<'a, T, Storage, Scorer> AsyncRawScorerImpl<'a, T, Storage, Scorer>
@@ -53,7 +52,7 @@ where
     Storage: UniversalRead<T>,
     Scorer: QueryScorer<TVector = [T]>,
 {
-    fn new(query_scorer: Scorer, storage: &'a ImmutableDenseVectors<T, Storage>) -> Self {
+    fn new(query_scorer: Scorer, storage: &'a DenseVectorStorageImpl<T, Storage>) -> Self {
         Self {
             query_scorer,
             storage,
@@ -71,7 +70,7 @@ where
         assert_eq!(points.len(), scores.len());
 
         self.storage
-            .read_vectors_async::<Random>(points, |idx, _point_id, other_vector| {
+            .get_dense_batch::<Random>(points, |idx, other_vector| {
                 scores[idx] = self.query_scorer.score(other_vector);
             })
             .unwrap();
@@ -229,8 +228,5 @@ where
     Storage: UniversalRead<T>,
     Scorer: QueryScorer<TVector = [T]> + 'a,
 {
-    Box::new(AsyncRawScorerImpl::new(
-        query_scorer,
-        storage.get_mmap_vectors(),
-    ))
+    Box::new(AsyncRawScorerImpl::new(query_scorer, storage))
 }
@@ -0,0 +1,267 @@
+//! Background population of in-RAM vector storages.
+//!
+//! Instead of blocking segment loading until all pages of a storage are resident, files of the
+//! storage are read by a small pool of low-priority threads. Reading a file brings its pages into
+//! the page cache, so that later accesses through mmap do not hit the disk. Searches are served
+//! while population is in progress, they are just slower until it completes.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, mpsc};
+use std::thread;
+
+use common::rate_limiting::{RateLimitError, RateLimiter, RetryError};
+use fs_err as fs;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::common::anonymize::Anonymize;
+
+/// Number of bytes read from a file in one go
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+const DEFAULT_POPULATE_THREADS: usize = 1;
+
+static BACKGROUND_POPULATOR: OnceLock<BackgroundPopulator> = OnceLock::new();
+
+/// Configuration of background population of in-RAM vector storages
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundPopulateConfig {
+    /// Number of threads, reading storage files in background
+    #[serde(default = "default_populate_threads")]
+    pub threads: usize,
+    /// Maximum number of bytes per second, read by all threads together.
+    /// If not set, reading is not limited.
+    #[serde(default)]
+    pub max_bytes_per_sec: Option<usize>,
+}
+
+const fn default_populate_threads() -> usize {
+    DEFAULT_POPULATE_THREADS
+}
+
+#[derive(Serialize, Clone, Debug, Default, JsonSchema, Anonymize)]
+#[anonymize(false)]
+pub struct BackgroundPopulateTelemetry {
+    /// Number of files, waiting to be populated
+    pub pending_files: usize,
+    /// Number of files, populated since startup
+    pub populated_files: usize,
+    /// Total size of files, scheduled for population since startup
+    pub scheduled_bytes: usize,
+    /// Number of bytes, read into page cache since startup
+    pub populated_bytes: usize,
+}
+
+#[derive(Debug, Default)]
+struct PopulateProgress {
+    pending_files: AtomicUsize,
+    populated_files: AtomicUsize,
+    scheduled_bytes: AtomicUsize,
+    populated_bytes: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct BackgroundPopulator {
+    sender: mpsc::Sender<PathBuf>,
+    progress: Arc<PopulateProgress>,
+}
+
+impl BackgroundPopulator {
+    fn spawn(config: BackgroundPopulateConfig) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let progress = Arc::new(PopulateProgress::default());
+
+        let max_bytes_per_sec = config.max_bytes_per_sec.map(|bytes| bytes.max(1));
+        let limiter = max_bytes_per_sec
+            .map(|bytes_per_sec| Arc::new(Mutex::new(RateLimiter::new_per_second(bytes_per_sec))));
+        // Single read must fit into the rate limiter capacity
+        let buffer_size = max_bytes_per_sec.map_or(READ_BUFFER_SIZE, |bytes_per_sec| {
+            READ_BUFFER_SIZE.min(bytes_per_sec)
+        });
+
+        for idx in 0..config.threads.max(1) {
+            let receiver = receiver.clone();
+            let limiter = limiter.clone();
+            let progress = progress.clone();
+            let spawn_result = thread::Builder::new()
+                .name(format!("populate-{idx}"))
+                .spawn(move || {
+                    #[cfg(target_os = "linux")]
+                    if let Err(err) = common::cpu::linux_low_thread_priority() {
+                        log::debug!(
+                            "Failed to set low thread priority for background populate, ignoring: {err}"
+                        );
+                    }
+                    populate_worker(&receiver, limiter.as_deref(), buffer_size, &progress);
+                });
+            if let Err(err) = spawn_result {
+                log::error!("Failed to spawn background populate thread: {err}");
+            }
+        }
+
+        Self { sender, progress }
+    }
+
+    fn schedule(&self, files: Vec<PathBuf>) {
+        for file in files {
+            let size = fs::metadata(&file).map_or(0, |metadata| metadata.len() as usize);
+            self.progress.pending_files.fetch_add(1, Ordering::Relaxed);
+            self.progress
+                .scheduled_bytes
+                .fetch_add(size, Ordering::Relaxed);
+            if self.sender.send(file).is_err() {
+                // All workers are gone, nothing will populate the file
+                self.progress.pending_files.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn telemetry(&self) -> BackgroundPopulateTelemetry {
+        let PopulateProgress {
+            pending_files,
+            populated_files,
+            scheduled_bytes,
+            populated_bytes,
+        } = self.progress.as_ref();
+
+        BackgroundPopulateTelemetry {
+            pending_files: pending_files.load(Ordering::Relaxed),
+            populated_files: populated_files.load(Ordering::Relaxed),
+            scheduled_bytes: scheduled_bytes.load(Ordering::Relaxed),
+            populated_bytes: populated_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn populate_worker(
+    receiver: &Mutex<mpsc::Receiver<PathBuf>>,
+    limiter: Option<&Mutex<RateLimiter>>,
+    buffer_size: usize,
+    progress: &PopulateProgress,
+) {
+    let mut buffer = vec![0u8; buffer_size];
+    loop {
+        let Ok(path) = receiver.lock().recv() else {
+            // Populator is dropped
+            return;
+        };
+
+        // File might be removed by an optimizer in the meantime, which is fine
+        if let Err(err) = populate_file(&path, limiter, &mut buffer, progress) {
+            log::debug!("Failed to populate {} in background: {err}", path.display());
+        }
+
+        progress.pending_files.fetch_sub(1, Ordering::Relaxed);
+        progress.populated_files.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn populate_file(
+    path: &Path,
+    limiter: Option<&Mutex<RateLimiter>>,
+    buffer: &mut [u8],
+    progress: &PopulateProgress,
+) -> std::io::Result<()> {
+    let mut file = fs::File::open(path)?;
+    loop {
+        if let Some(limiter) = limiter {
+            wait_for_budget(limiter, buffer.len());
+        }
+        let read = file.read(buffer)?;
+        if read == 0 {
+            return Ok(());
+        }
+        progress.populated_bytes.fetch_add(read, Ordering::Relaxed);
+    }
+}
+
+fn wait_for_budget(limiter: &Mutex<RateLimiter>, bytes: usize) {
+    loop {
+        let result = limiter.lock().try_consume(bytes as f64);
+        match result {
+            Ok(()) => return,
+            Err(RateLimitError::Retry(RetryError { retry_after, .. })) => {
+                thread::sleep(retry_after);
+            }
+            // Buffer is never larger than the capacity
+            Err(RateLimitError::AlwaysOverBudget(_)) => return,
+        }
+    }
+}
+
+/// Enable background population of in-RAM vector storages.
+///
+/// Must only be called once at startup, otherwise a warning is logged and `config` is ignored.
+pub fn init_background_populate(config: BackgroundPopulateConfig) {
+    if BACKGROUND_POPULATOR.get().is_some() {
+        log::warn!("Background populate is already initialized, ignoring new configuration");
+        return;
+    }
+    // Workers of a populator, which lost the race, exit as soon as it is dropped
+    let _ = BACKGROUND_POPULATOR.set(BackgroundPopulator::spawn(config));
+}
+
+pub fn is_background_populate_enabled() -> bool {
+    BACKGROUND_POPULATOR.get().is_some()
+}
+
+/// Read `files` into page cache in background.
+///
+/// Returns `false` if background populate is not enabled, and files must be populated in place.
+pub fn populate_in_background(files: Vec<PathBuf>) -> bool {
+    let Some(populator) = BACKGROUND_POPULATOR.get() else {
+        return false;
+    };
+    populator.schedule(files);
+    true
+}
+
+pub fn background_populate_telemetry() -> Option<BackgroundPopulateTelemetry> {
+    BACKGROUND_POPULATOR
+        .get()
+        .map(BackgroundPopulator::telemetry)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_background_populate() {
+        let dir = Builder::new().prefix("populate_dir").tempdir().unwrap();
+        let file_size = 3 * 1024 + 17;
+        let files = (0..3)
+            .map(|idx| {
+                let path = dir.path().join(format!("file_{idx}"));
+                fs::write(&path, vec![idx as u8; file_size]).unwrap();
+                path
+            })
+            .chain(std::iter::once(dir.path().join("missing")))
+            .collect();
+
+        let populator = BackgroundPopulator::spawn(BackgroundPopulateConfig {
+            threads: 2,
+            max_bytes_per_sec: Some(1024 * 1024),
+        });
+        populator.schedule(files);
+
+        let started = Instant::now();
+        while populator.telemetry().pending_files > 0 {
+            assert!(started.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let telemetry = populator.telemetry();
+        assert_eq!(telemetry.populated_files, 4);
+        assert_eq!(telemetry.scheduled_bytes, 3 * file_size);
+        assert_eq!(telemetry.populated_bytes, 3 * file_size);
+    }
+}
@@ -1,9 +1,12 @@
 use std::borrow::Cow;
 use std::cmp::max;
+use std::collections::BTreeMap;
+use std::io::{Read as _, Seek as _, SeekFrom};
 use std::mem::MaybeUninit;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::fs::{atomic_save_json, clear_disk_cache};
 use common::generic_consts::{AccessPattern, Random, Sequential};
@@ -19,16 +22,23 @@ use common::universal_io::{
 use fs_err as fs;
 use memmap2::MmapMut;
 use num_traits::AsPrimitive;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
 use crate::common::Flusher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::vector_storage::common::{CHUNK_SIZE, PAGE_SIZE_BYTES, VECTOR_READ_BATCH_SIZE};
+use crate::vector_storage::memory_budget::{MemoryBudget, MemoryReservation};
 use crate::vector_storage::query_scorer::is_read_with_prefetch_efficient;
+use crate::vector_storage::read_stats::{VectorStorageReadStats, VectorStorageStats};
 use crate::vector_storage::{VectorOffset, VectorOffsetType};
 
 const CONFIG_FILE_NAME: &str = "config.json";
 const STATUS_FILE_NAME: &str = "status.dat";
+const CHECKSUMS_FILE_NAME: &str = "checksums.json";
+
+/// Chunks are checksummed in blocks of this size, so that a flush only re-reads changed blocks
+const CHECKSUM_BLOCK_SIZE: usize = 1024 * 1024;
 
 const MMAP_CHUNKS_PATTERN_START: &str = "chunk_";
 const MMAP_CHUNKS_PATTERN_END: &str = ".mmap"; // TODO: rename for other storages?
@@ -47,12 +57,23 @@ struct ChunkedVectorsConfig {
     populate: Option<bool>,
 }
 
+/// CRC32C checksums of chunk files: chunk index -> block index -> checksum
+type ChunkChecksums = BTreeMap<usize, BTreeMap<usize, u32>>;
+
 #[derive(Debug)]
 pub struct ChunkedVectors<T: Copy + Sized + 'static, S: UniversalWrite<T>> {
     config: ChunkedVectorsConfig,
     status: MmapType<Status>,
     chunks: Vec<TypedStorage<S, T>>,
     directory: PathBuf,
+    /// CRC32C checksums of blocks of chunk files, computed at flush
+    checksums: Arc<Mutex<ChunkChecksums>>,
+    /// Blocks changed since the last flush, as `(chunk index, block index)`.
+    /// Their checksums are outdated.
+    dirty_blocks: Arc<Mutex<AHashSet<(usize, usize)>>>,
+    /// Memory, occupied by chunks, if they are populated into RAM
+    memory_reservation: Option<MemoryReservation>,
+    read_stats: VectorStorageReadStats,
 }
 
 impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
@@ -64,6 +85,10 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
         directory.join(STATUS_FILE_NAME)
     }
 
+    fn checksums_file(directory: &Path) -> PathBuf {
+        directory.join(CHECKSUMS_FILE_NAME)
+    }
+
     pub fn ensure_status_file(directory: &Path) -> OperationResult<MmapMut> {
         let status_file = Self::status_file(directory);
         if !status_file.exists() {
@@ -113,6 +138,15 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
         }
     }
 
+    fn load_checksums(directory: &Path) -> OperationResult<ChunkChecksums> {
+        match read_json_via::<MmapFile, ChunkChecksums>(&Self::checksums_file(directory)) {
+            Ok(checksums) => Ok(checksums),
+            // Storages created before checksums were introduced
+            Err(UniversalIoError::NotFound { .. }) => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn create_config(
         config_file: &Path,
         dim: usize,
@@ -151,11 +185,19 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
 
         let config = Self::ensure_config(directory, dim, populate)?;
         let chunks = read_chunks(directory, advice, populate.unwrap_or_default())?;
+        let checksums = Self::load_checksums(directory)?;
+        let memory_reservation = populate
+            .unwrap_or_default()
+            .then(|| MemoryBudget::global().reserve(chunks.len() * config.chunk_size_bytes));
         let vectors = Self {
             status,
             config,
             chunks,
             directory: directory.to_owned(),
+            checksums: Arc::new(Mutex::new(checksums)),
+            dirty_blocks: Arc::new(Mutex::new(AHashSet::new())),
+            memory_reservation,
+            read_stats: VectorStorageReadStats::default(),
         };
         Ok(vectors)
     }
@@ -195,9 +237,23 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
         )?;
 
         self.chunks.push(chunk);
+        self.update_memory_reservation();
         Ok(())
     }
 
+    fn update_memory_reservation(&mut self) {
+        if let Some(memory_reservation) = &mut self.memory_reservation {
+            memory_reservation.resize(self.chunks.len() * self.config.chunk_size_bytes);
+        }
+    }
+
+    /// Number of bytes of populated chunks, 0 if chunks are not kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.memory_reservation
+            .as_ref()
+            .map_or(0, MemoryReservation::bytes)
+    }
+
     pub fn insert(
         &mut self,
         key: VectorOffsetType,
@@ -237,9 +293,15 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
             self.add_chunk()?;
         }
 
+        let start_byte = chunk_offset * size_of::<T>();
+        let end_byte = start_byte + size_of_val(vectors);
+        self.dirty_blocks.lock().extend(
+            (start_byte / CHECKSUM_BLOCK_SIZE..end_byte.div_ceil(CHECKSUM_BLOCK_SIZE))
+                .map(|block_idx| (chunk_idx, block_idx)),
+        );
         let chunk = &mut self.chunks[chunk_idx];
 
-        chunk.write((chunk_offset * size_of::<T>()) as u64, vectors)?;
+        chunk.write(start_byte as u64, vectors)?;
 
         hw_counter
             .vector_io_write_counter()
@@ -307,6 +369,8 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
 
         let use_sequential =
             force_sequential || elements_length * size_of::<T>() > PAGE_SIZE_BYTES * 4;
+        self.read_stats
+            .record_read(use_sequential, elements_length * size_of::<T>());
 
         if use_sequential {
             chunk.read::<Sequential>(range).ok()
@@ -323,38 +387,119 @@ impl<T: Sized + Copy + 'static, S: UniversalWrite<T>> ChunkedVectors<T, S> {
         // Fetching all vectors first then scoring them is more cache friendly
         // then fetching and scoring in a single loop.
         let mut vectors_buffer = [const { MaybeUninit::uninit() }; VECTOR_READ_BATCH_SIZE];
-        let vectors = maybe_uninit_fill_from(
-            &mut vectors_buffer,
-            keys.iter().map(|&key| {
-                self.get_many_impl(key.offset(), 1, do_sequential_read)
-                    .unwrap_or_else(|| panic!("Vector {key} not found"))
-            }),
-        )
-        .0;
+        let vectors_buffer = &mut vectors_buffer;
+        let vectors = self.read_stats.measure_batch(move || {
+            maybe_uninit_fill_from(
+                vectors_buffer,
+                keys.iter().map(|&key| {
+                    self.get_many_impl(key.offset(), 1, do_sequential_read)
+                        .unwrap_or_else(|| panic!("Vector {key} not found"))
+                }),
+            )
+            .0
+        });
 
         for (i, vec) in vectors.iter().enumerate() {
             f(i, vec.as_ref());
         }
     }
 
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.read_stats.stats()
+    }
+
+    /// Number of bytes of chunk blocks, changed since the last flush
+    pub fn estimate_flush_size(&self) -> usize {
+        self.dirty_blocks.lock().len() * CHECKSUM_BLOCK_SIZE
+    }
+
     pub fn flusher(&self) -> Flusher {
         Box::new({
             let status_flusher = self.status.flusher();
             let chunks_flushers: Vec<_> = self.chunks.iter().map(|chunk| chunk.flusher()).collect();
+            let directory = self.directory.clone();
+            let chunk_size_bytes = self.config.chunk_size_bytes;
+            let checksums = self.checksums.clone();
+            let dirty_blocks = self.dirty_blocks.clone();
             move || {
-                for flusher in chunks_flushers {
-                    flusher()?;
+                // Take dirty blocks before flushing them, so that blocks changed during the
+                // flush stay dirty until the next one.
+                // Blocks of chunks, added after this flusher was created, are flushed next time.
+                let flushed_blocks: AHashSet<_> = {
+                    let mut dirty_blocks = dirty_blocks.lock();
+                    let (flushed, rest) = dirty_blocks
+                        .drain()
+                        .partition(|&(chunk_idx, _)| chunk_idx < chunks_flushers.len());
+                    *dirty_blocks = rest;
+                    flushed
+                };
+                let result = flush_chunks(
+                    &directory,
+                    chunks_flushers,
+                    chunk_size_bytes,
+                    &checksums,
+                    &flushed_blocks,
+                );
+                if result.is_err() {
+                    dirty_blocks.lock().extend(flushed_blocks);
                 }
+                result?;
                 status_flusher()?;
                 Ok(())
             }
         })
     }
 
+    /// Check chunk files against checksums, computed at flush
+    ///
+    /// Blocks changed since the last flush and blocks without checksums are skipped.
+    /// Must not run concurrently with a flush.
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        let dirty_blocks = self.dirty_blocks.lock().clone();
+        let checksums = self.checksums.lock().clone();
+
+        let mut buffer = Vec::new();
+        let mut corrupted_chunks = Vec::new();
+        for (&chunk_idx, chunk_checksums) in &checksums {
+            if chunk_idx >= self.chunks.len() {
+                continue;
+            }
+            let mut file = fs::File::open(chunk_name(&self.directory, chunk_idx))?;
+            for (&block_idx, &expected) in chunk_checksums {
+                if dirty_blocks.contains(&(chunk_idx, block_idx)) {
+                    continue;
+                }
+                let actual = block_checksum(
+                    &mut file,
+                    self.config.chunk_size_bytes,
+                    block_idx,
+                    &mut buffer,
+                )?;
+                if actual != expected {
+                    corrupted_chunks.push(chunk_idx);
+                    break;
+                }
+            }
+        }
+
+        if corrupted_chunks.is_empty() {
+            Ok(())
+        } else {
+            Err(OperationError::service_error(format!(
+                "Checksum mismatch in chunks {corrupted_chunks:?} of {}",
+                self.directory.display(),
+            )))
+        }
+    }
+
     pub fn files(&self) -> Vec<PathBuf> {
         let mut files = Vec::new();
         files.push(Self::config_file(&self.directory));
         files.push(Self::status_file(&self.directory));
+        let checksums_file = Self::checksums_file(&self.directory);
+        if checksums_file.exists() {
+            files.push(checksums_file);
+        }
         for chunk_idx in 0..self.chunks.len() {
             files.push(chunk_name(&self.directory, chunk_idx));
         }
@@ -456,6 +601,70 @@ pub fn read_chunks<T: Sized + Copy + 'static, S: UniversalWrite<T>>(
     Ok(result)
 }
 
+/// Flush chunks, and recompute checksums of `blocks`, changed since the last flush.
+///
+/// Checksums of a chunk are computed right after its flush, so that they cover the flushed bytes.
+/// Only changed blocks are read back.
+fn flush_chunks(
+    directory: &Path,
+    chunks_flushers: Vec<Flusher>,
+    chunk_size_bytes: usize,
+    checksums: &Mutex<ChunkChecksums>,
+    blocks: &AHashSet<(usize, usize)>,
+) -> OperationResult<()> {
+    let mut blocks_by_chunk: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for &(chunk_idx, block_idx) in blocks {
+        blocks_by_chunk
+            .entry(chunk_idx)
+            .or_default()
+            .push(block_idx);
+    }
+
+    let mut new_checksums = Vec::with_capacity(blocks.len());
+    let mut buffer = Vec::new();
+    for (chunk_idx, flusher) in chunks_flushers.into_iter().enumerate() {
+        flusher()?;
+
+        let Some(chunk_blocks) = blocks_by_chunk.get(&chunk_idx) else {
+            continue;
+        };
+        let mut file = fs::File::open(chunk_name(directory, chunk_idx))?;
+        for &block_idx in chunk_blocks {
+            let checksum = block_checksum(&mut file, chunk_size_bytes, block_idx, &mut buffer)?;
+            new_checksums.push((chunk_idx, block_idx, checksum));
+        }
+    }
+
+    if new_checksums.is_empty() {
+        return Ok(());
+    }
+
+    let mut checksums = checksums.lock();
+    for (chunk_idx, block_idx, checksum) in new_checksums {
+        checksums
+            .entry(chunk_idx)
+            .or_default()
+            .insert(block_idx, checksum);
+    }
+    atomic_save_json(&directory.join(CHECKSUMS_FILE_NAME), &*checksums)?;
+    Ok(())
+}
+
+/// Checksum of block `block_idx` of a chunk file, `buffer` is reused between calls
+fn block_checksum(
+    file: &mut fs::File,
+    chunk_size_bytes: usize,
+    block_idx: usize,
+    buffer: &mut Vec<u8>,
+) -> OperationResult<u32> {
+    let start = block_idx * CHECKSUM_BLOCK_SIZE;
+    let length = CHECKSUM_BLOCK_SIZE.min(chunk_size_bytes.saturating_sub(start));
+    buffer.resize(length, 0);
+    file.seek(SeekFrom::Start(start as u64))?;
+    file.read_exact(buffer)?;
+    Ok(crc32c::crc32c(buffer))
+}
+
 pub fn chunk_name(directory: &Path, chunk_id: usize) -> PathBuf {
     directory.join(format!(
         "{MMAP_CHUNKS_PATTERN_START}{chunk_id}{MMAP_CHUNKS_PATTERN_END}",
@@ -561,4 +770,76 @@ mod tests {
             chunked_mmap.flusher()().unwrap();
         }
     }
+
+    #[test]
+    fn test_chunk_checksums() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let dim = 500;
+        let num_vectors = 1000;
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let hw_counter = HardwareCounterCell::new();
+
+        {
+            let mut chunked_mmap: ChunkedVectors<VectorElementType, MmapFile> =
+                ChunkedVectors::open(dir.path(), dim, AdviceSetting::Global, Some(false)).unwrap();
+
+            for _ in 0..num_vectors {
+                chunked_mmap
+                    .push(&random_vector(&mut rng, dim), &hw_counter)
+                    .unwrap();
+            }
+            chunked_mmap.flusher()().unwrap();
+            assert!(chunked_mmap.chunks.len() > 1);
+            assert_eq!(chunked_mmap.estimate_flush_size(), 0);
+            chunked_mmap.verify_integrity().unwrap();
+
+            // Unflushed changes are not verified, only the changed block is checksummed again
+            chunked_mmap
+                .insert(0, &random_vector(&mut rng, dim), &hw_counter)
+                .unwrap();
+            assert_eq!(chunked_mmap.estimate_flush_size(), CHECKSUM_BLOCK_SIZE);
+            chunked_mmap.verify_integrity().unwrap();
+            chunked_mmap.flusher()().unwrap();
+        }
+
+        // Corrupt the second chunk on disk
+        let chunk_path = chunk_name(dir.path(), 1);
+        let mut chunk_data = fs::read(&chunk_path).unwrap();
+        chunk_data[0] ^= 0xFF;
+        fs::write(&chunk_path, chunk_data).unwrap();
+
+        let chunked_mmap: ChunkedVectors<VectorElementType, MmapFile> =
+            ChunkedVectors::open(dir.path(), dim, AdviceSetting::Global, Some(false)).unwrap();
+        let err = chunked_mmap.verify_integrity().unwrap_err();
+        assert!(err.to_string().contains("[1]"), "{err}");
+    }
+
+    #[test]
+    fn test_read_stats() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let dim = 16;
+        let mut rng = StdRng::seed_from_u64(42);
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut chunked_mmap: ChunkedVectors<VectorElementType, MmapFile> =
+            ChunkedVectors::open(dir.path(), dim, AdviceSetting::Global, Some(false)).unwrap();
+        for _ in 0..100 {
+            chunked_mmap
+                .push(&random_vector(&mut rng, dim), &hw_counter)
+                .unwrap();
+        }
+        assert_eq!(chunked_mmap.read_stats(), VectorStorageStats::default());
+
+        let vector_size = dim * size_of::<VectorElementType>();
+        chunked_mmap.get::<Random>(3).unwrap();
+        chunked_mmap.get::<Sequential>(4).unwrap();
+        chunked_mmap.for_each_in_batch(&[10 as VectorOffsetType, 50, 90], |_, _| {});
+
+        let stats = chunked_mmap.read_stats();
+        assert_eq!(stats.sequential_reads + stats.random_reads, 5);
+        assert!(stats.random_reads >= 1);
+        assert!(stats.sequential_reads >= 1);
+        assert_eq!(stats.bytes_read, 5 * vector_size);
+    }
 }
@@ -0,0 +1,154 @@
+//! Content-hash index of stored vectors, used to detect exact duplicates.
+//!
+//! Vectors are hashed in the form they are stored in, e.g. after conversion into the storage
+//! datatype, so that vectors which are only equal after quantization into bytes are duplicates
+//! too. Hashes may collide, so vectors with equal hashes are always compared with each other
+//! before they are reported as duplicates.
+
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ahash::AHashMap;
+use common::generic_consts::Random;
+use common::types::PointOffsetType;
+use ordered_float::OrderedFloat;
+use sparse::common::sparse_vector::SparseVector;
+
+use crate::common::check_stopped;
+use crate::common::operation_error::OperationResult;
+use crate::data_types::vectors::VectorRef;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+static VECTOR_DEDUP_INDEX: AtomicBool = AtomicBool::new(false);
+
+/// Maintain deduplication indexes of vectors in appendable segments
+pub fn set_vector_dedup_index(enabled: bool) {
+    VECTOR_DEDUP_INDEX.store(enabled, Ordering::Relaxed);
+}
+
+pub fn get_vector_dedup_index() -> bool {
+    VECTOR_DEDUP_INDEX.load(Ordering::Relaxed)
+}
+
+/// Index of stored vectors by hash of their content
+#[derive(Debug, Default)]
+pub struct VectorDedupIndex {
+    keys_by_hash: AHashMap<u64, Vec<PointOffsetType>>,
+    hash_by_key: AHashMap<PointOffsetType, u64>,
+    hasher: ahash::RandomState,
+}
+
+impl VectorDedupIndex {
+    /// Build index over all vectors of `vector_storage`, which are not deleted
+    pub fn build(
+        vector_storage: &VectorStorageEnum,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Self> {
+        let mut index = Self::default();
+        for key in 0..vector_storage.total_vector_count() as PointOffsetType {
+            check_stopped(stopped)?;
+            if !vector_storage.is_deleted_vector(key) {
+                index.insert(key, vector_storage);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Update index after the vector of `key` was inserted, updated or deleted in
+    /// `vector_storage`.
+    ///
+    /// Returns another key with exactly the same vector, if there is any.
+    pub fn update(
+        &mut self,
+        key: PointOffsetType,
+        vector_storage: &VectorStorageEnum,
+    ) -> Option<PointOffsetType> {
+        self.remove(key);
+        if vector_storage.is_deleted_vector(key) {
+            return None;
+        }
+        self.insert(key, vector_storage)
+    }
+
+    fn insert(
+        &mut self,
+        key: PointOffsetType,
+        vector_storage: &VectorStorageEnum,
+    ) -> Option<PointOffsetType> {
+        let vector = vector_storage.get_vector::<Random>(key);
+        let vector = vector.as_vec_ref();
+        let hash = self.hash_vector(vector);
+
+        let keys = self.keys_by_hash.entry(hash).or_default();
+        let duplicate = keys
+            .iter()
+            .copied()
+            .find(|&other| vector_storage.get_vector::<Random>(other).as_vec_ref() == vector);
+        keys.push(key);
+        self.hash_by_key.insert(key, hash);
+
+        duplicate
+    }
+
+    pub fn remove(&mut self, key: PointOffsetType) {
+        let Some(hash) = self.hash_by_key.remove(&key) else {
+            return;
+        };
+        if let Some(keys) = self.keys_by_hash.get_mut(&hash) {
+            keys.retain(|&other| other != key);
+            if keys.is_empty() {
+                self.keys_by_hash.remove(&hash);
+            }
+        }
+    }
+
+    /// Groups of keys with exactly the same vectors, each group has 2 or more keys
+    pub fn duplicates(&self, vector_storage: &VectorStorageEnum) -> Vec<Vec<PointOffsetType>> {
+        let mut groups = Vec::new();
+        for keys in self.keys_by_hash.values().filter(|keys| keys.len() > 1) {
+            // Split keys with colliding hashes into groups of equal vectors
+            let mut remaining = keys.clone();
+            while let Some(first) = remaining.pop() {
+                let first_vector = vector_storage.get_vector::<Random>(first);
+                let first_vector = first_vector.as_vec_ref();
+                let (mut group, rest): (Vec<_>, Vec<_>) =
+                    remaining.into_iter().partition(|&other| {
+                        vector_storage.get_vector::<Random>(other).as_vec_ref() == first_vector
+                    });
+                remaining = rest;
+                if !group.is_empty() {
+                    group.push(first);
+                    group.sort_unstable();
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+
+    fn hash_vector(&self, vector: VectorRef) -> u64 {
+        let mut state = self.hasher.build_hasher();
+        match vector {
+            VectorRef::Dense(vector) => {
+                hash_elements(vector, &mut state);
+            }
+            VectorRef::Sparse(vector) => {
+                let SparseVector { indices, values } = vector;
+                indices.hash(&mut state);
+                hash_elements(values, &mut state);
+            }
+            VectorRef::MultiDense(vector) => {
+                vector.dim.hash(&mut state);
+                hash_elements(vector.flattened_vectors, &mut state);
+            }
+        }
+        state.finish()
+    }
+}
+
+fn hash_elements(elements: &[f32], state: &mut impl Hasher) {
+    elements.len().hash(state);
+    for &element in elements {
+        OrderedFloat(element).hash(state);
+    }
+}
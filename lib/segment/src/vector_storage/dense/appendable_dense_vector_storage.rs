@@ -1,11 +1,12 @@
 use std::borrow::Cow;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use common::bitvec::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::generic_consts::AccessPattern;
+use common::generic_consts::{AccessPattern, Random, Sequential};
 use common::mmap::AdviceSetting;
 use common::types::PointOffsetType;
 use common::universal_io::MmapFile;
@@ -14,22 +15,32 @@ use fs_err as fs;
 use crate::common::Flusher;
 use crate::common::flags::bitvec_flags::BitvecFlags;
 use crate::common::flags::dynamic_mmap_flags::DynamicMmapFlags;
-use crate::common::operation_error::{OperationResult, check_process_stopped};
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::spaces::tools::is_length_zero_or_normalized;
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::read_stats::VectorStorageStats;
+use crate::vector_storage::write_throttle::WriteThrottle;
 use crate::vector_storage::{
     DenseVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
 };
 
 const VECTORS_DIR_PATH: &str = "vectors";
 const DELETED_DIR_PATH: &str = "deleted";
+const LENGTHS_DIR_PATH: &str = "lengths";
+const NORMS_DIR_PATH: &str = "norms";
 
 #[derive(Debug)]
 pub struct AppendableMmapDenseVectorStorage<T: PrimitiveVectorElement> {
+    /// Vectors, shorter ones are padded with zeros up to the full dimension
     vectors: ChunkedVectors<T, MmapFile>,
+    /// Number of stored elements of every vector, if vectors have variable dimension
+    lengths: Option<ChunkedVectors<u32, MmapFile>>,
+    /// Norms of vectors before normalization, if vectors are retrieved as they were inserted
+    norms: Option<ChunkedVectors<f32, MmapFile>>,
     /// Flags marking deleted vectors
     ///
     /// Structure grows dynamically, but may be smaller than actual number of vectors. Must not
@@ -37,10 +48,133 @@ pub struct AppendableMmapDenseVectorStorage<T: PrimitiveVectorElement> {
     deleted: BitvecFlags,
     distance: Distance,
     deleted_count: usize,
+    /// Backpressure on inserts, if dirty pages are not flushed fast enough
+    write_throttle: Option<Arc<WriteThrottle>>,
     _phantom: std::marker::PhantomData<T>,
 }
 
 impl<T: PrimitiveVectorElement> AppendableMmapDenseVectorStorage<T> {
+    /// Number of bytes of vectors, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+            + self
+                .lengths
+                .as_ref()
+                .map_or(0, ChunkedVectors::ram_usage_bytes)
+            + self
+                .norms
+                .as_ref()
+                .map_or(0, ChunkedVectors::ram_usage_bytes)
+    }
+
+    /// Pad `vector` with zeros up to the full dimension, if the storage keeps vectors of variable
+    /// dimension
+    fn pad_vector<'a>(
+        &self,
+        vector: Cow<'a, [VectorElementType]>,
+    ) -> OperationResult<Cow<'a, [VectorElementType]>> {
+        let dim = self.vectors.dim();
+        if self.lengths.is_none() || vector.len() == dim {
+            return Ok(vector);
+        }
+        if vector.len() > dim {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: dim,
+                received_dim: vector.len(),
+            });
+        }
+        let mut padded = vector.into_owned();
+        padded.resize(dim, 0.0);
+        Ok(Cow::Owned(padded))
+    }
+
+    fn set_stored_len(
+        &mut self,
+        key: VectorOffsetType,
+        len: usize,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if let Some(lengths) = &mut self.lengths {
+            lengths.insert(key, &[len as u32], hw_counter)?;
+        }
+        Ok(())
+    }
+
+    /// Normalize `vector`, if norms of vectors are kept. Returns the vector to store and its norm.
+    fn split_norm<'a>(
+        &self,
+        vector: Cow<'a, [VectorElementType]>,
+    ) -> (Cow<'a, [VectorElementType]>, f32) {
+        if self.norms.is_none() {
+            return (vector, 1.0);
+        }
+        let length: f32 = vector.iter().map(|x| x * x).sum();
+        // Vectors of zero length, or normalized already, are stored as is
+        if is_length_zero_or_normalized(length) {
+            return (vector, 1.0);
+        }
+        let norm = length.sqrt();
+        let normalized = vector.iter().map(|x| x / norm).collect();
+        (Cow::Owned(normalized), norm)
+    }
+
+    fn set_stored_norm(
+        &mut self,
+        key: VectorOffsetType,
+        norm: f32,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if let Some(norms) = &mut self.norms {
+            norms.insert(key, &[norm], hw_counter)?;
+        }
+        Ok(())
+    }
+
+    /// Norm of the vector before normalization, `1.0` if norms are not kept
+    fn stored_norm(&self, key: PointOffsetType) -> f32 {
+        self.norms
+            .as_ref()
+            .and_then(|norms| norms.get::<Random>(key as VectorOffsetType))
+            .map_or(1.0, |norm| norm[0])
+    }
+
+    /// Scale a normalized vector back to the norm it was inserted with
+    fn restore_norm<'a>(
+        &self,
+        key: PointOffsetType,
+        vector: Cow<'a, [VectorElementType]>,
+    ) -> Cow<'a, [VectorElementType]> {
+        let norm = self.stored_norm(key);
+        if norm == 1.0 {
+            return vector;
+        }
+        Cow::Owned(vector.iter().map(|x| x * norm).collect())
+    }
+
+    /// Strip padding of a vector of variable dimension
+    fn truncate_padding<'a>(
+        &self,
+        key: PointOffsetType,
+        vector: Cow<'a, [VectorElementType]>,
+    ) -> Cow<'a, [VectorElementType]> {
+        let len = self.stored_vector_len(key);
+        match vector {
+            Cow::Borrowed(vector) => Cow::Borrowed(&vector[..len.min(vector.len())]),
+            Cow::Owned(mut vector) => {
+                vector.truncate(len);
+                Cow::Owned(vector)
+            }
+        }
+    }
+
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.vectors.read_stats()
+    }
+
+    pub fn set_write_throttle(&mut self, write_throttle: Option<Arc<WriteThrottle>>) {
+        self.write_throttle = write_throttle;
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -66,6 +200,12 @@ impl<T: PrimitiveVectorElement> AppendableMmapDenseVectorStorage<T> {
     pub fn populate(&self) -> OperationResult<()> {
         // deleted bitvec is already loaded
         self.vectors.populate()?;
+        if let Some(lengths) = &self.lengths {
+            lengths.populate()?;
+        }
+        if let Some(norms) = &self.norms {
+            norms.populate()?;
+        }
         Ok(())
     }
 
@@ -73,8 +213,97 @@ impl<T: PrimitiveVectorElement> AppendableMmapDenseVectorStorage<T> {
     pub fn clear_cache(&self) -> OperationResult<()> {
         self.deleted.clear_cache()?;
         self.vectors.clear_cache()?;
+        if let Some(lengths) = &self.lengths {
+            lengths.clear_cache()?;
+        }
+        if let Some(norms) = &self.norms {
+            norms.clear_cache()?;
+        }
         Ok(())
     }
+
+    /// Check vector files against checksums, computed at flush
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        self.vectors.verify_integrity()?;
+        if let Some(lengths) = &self.lengths {
+            lengths.verify_integrity()?;
+        }
+        if let Some(norms) = &self.norms {
+            norms.verify_integrity()?;
+        }
+        Ok(())
+    }
+
+    /// Append vectors from storages of the same type, copying raw elements of consecutive
+    /// offsets at once instead of converting every vector.
+    ///
+    /// `points` are pairs of index in `others` and offset of the vector in that storage.
+    ///
+    /// Returns `None` without changing anything, if dimensions of the storages differ, or if
+    /// only some of them keep vectors of variable dimension or norms of vectors.
+    pub fn update_from_same(
+        &mut self,
+        others: &[&Self],
+        points: &[(usize, PointOffsetType)],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Range<PointOffsetType>>> {
+        if others.iter().any(|other| {
+            other.vectors.dim() != self.vectors.dim()
+                || other.lengths.is_some() != self.lengths.is_some()
+                || other.norms.is_some() != self.norms.is_some()
+        }) {
+            return Ok(None);
+        }
+
+        let start_index = self.vectors.len() as PointOffsetType;
+        let disposed_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+
+        let runs = points.chunk_by(|&(prev_storage, prev_key), &(storage, key)| {
+            prev_storage == storage && prev_key.checked_add(1) == Some(key)
+        });
+        for run in runs {
+            check_process_stopped(stopped)?;
+
+            let (storage_idx, first_key) = run[0];
+            let other = others[storage_idx];
+
+            let mut copied = 0;
+            while copied < run.len() {
+                let src_key = first_key as usize + copied;
+                let dst_key = self.vectors.len();
+                // Copies must not cross chunk boundaries of either storage
+                let count = (run.len() - copied)
+                    .min(other.vectors.get_remaining_chunk_keys(src_key))
+                    .min(self.vectors.get_remaining_chunk_keys(dst_key));
+
+                let vectors = other
+                    .vectors
+                    .get_many::<Sequential>(src_key, count)
+                    .ok_or_else(|| {
+                        OperationError::service_error(format!(
+                            "Vectors {src_key}..{} not found in source storage",
+                            src_key + count,
+                        ))
+                    })?;
+                self.vectors
+                    .insert_many(dst_key, &vectors, count, &disposed_hw)?;
+
+                for i in 0..count {
+                    let src_key = (src_key + i) as PointOffsetType;
+                    let len = other.stored_vector_len(src_key);
+                    self.set_stored_len(dst_key + i, len, &disposed_hw)?;
+                    let norm = other.stored_norm(src_key);
+                    self.set_stored_norm(dst_key + i, norm, &disposed_hw)?;
+                    let deleted = other.is_deleted_vector(src_key);
+                    self.set_deleted((dst_key + i) as PointOffsetType, deleted);
+                }
+                copied += count;
+            }
+        }
+
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(Some(start_index..end_index))
+    }
 }
 
 impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for AppendableMmapDenseVectorStorage<T> {
@@ -91,6 +320,19 @@ impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for AppendableMmapDenseVec
     fn for_each_in_dense_batch<F: FnMut(usize, &[T])>(&self, keys: &[PointOffsetType], f: F) {
         self.vectors.for_each_in_batch(keys, f);
     }
+
+    fn is_variable_dim(&self) -> bool {
+        self.lengths.is_some()
+    }
+
+    fn stored_vector_len(&self, key: PointOffsetType) -> usize {
+        let Some(lengths) = &self.lengths else {
+            return self.vectors.dim();
+        };
+        lengths
+            .get::<Random>(key as VectorOffsetType)
+            .map_or(self.vectors.dim(), |len| len[0] as usize)
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStorage<T> {
@@ -111,16 +353,18 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStora
     }
 
     fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
-        self.vectors
-            .get::<P>(key as VectorOffsetType)
-            .map(|slice| CowVector::from(T::slice_to_float_cow(slice)))
-            .expect("Vector not found")
+        self.get_vector_opt::<P>(key).expect("Vector not found")
     }
 
     fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
-        self.vectors
-            .get::<P>(key as VectorOffsetType)
-            .map(|slice| CowVector::from(T::slice_to_float_cow(slice)))
+        let mut vector = T::slice_to_float_cow(self.vectors.get::<P>(key as VectorOffsetType)?);
+        if self.lengths.is_some() {
+            vector = self.truncate_padding(key, vector);
+        }
+        if self.norms.is_some() {
+            vector = self.restore_norm(key, vector);
+        }
+        Some(CowVector::from(vector))
     }
 
     fn insert_vector(
@@ -130,9 +374,16 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStora
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let vector: &[VectorElementType] = vector.try_into()?;
-        let vector = T::slice_from_float_cow(Cow::from(vector));
+        let len = vector.len();
+        let (vector, norm) = self.split_norm(Cow::from(vector));
+        let vector = T::slice_from_float_cow(self.pad_vector(vector)?);
+        if let Some(write_throttle) = &self.write_throttle {
+            write_throttle.throttle(self.estimate_flush_size(), size_of_val(vector.as_ref()));
+        }
         self.vectors
             .insert(key as VectorOffsetType, vector.as_ref(), hw_counter)?;
+        self.set_stored_len(key as VectorOffsetType, len, hw_counter)?;
+        self.set_stored_norm(key as VectorOffsetType, norm, hw_counter)?;
         self.set_deleted(key, false);
         Ok(())
     }
@@ -147,8 +398,14 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStora
         for (other_vector, other_deleted) in other_vectors {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
-            let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            let other_vector: Cow<[VectorElementType]> = Cow::try_from(other_vector)?;
+            let len = other_vector.len();
+            // Vectors of storages, which keep norms, are read as they were inserted
+            let (other_vector, norm) = self.split_norm(other_vector);
+            let other_vector = T::slice_from_float_cow(self.pad_vector(other_vector)?);
             let new_id = self.vectors.push(other_vector.as_ref(), &disposed_hw)?;
+            self.set_stored_len(new_id, len, &disposed_hw)?;
+            self.set_stored_norm(new_id, norm, &disposed_hw)?;
             self.set_deleted(new_id as PointOffsetType, other_deleted);
         }
         let end_index = self.vectors.len() as PointOffsetType;
@@ -158,29 +415,69 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapDenseVectorStora
     fn flusher(&self) -> Flusher {
         Box::new({
             let vectors_flusher = self.vectors.flusher();
+            let lengths_flusher = self.lengths.as_ref().map(ChunkedVectors::flusher);
+            let norms_flusher = self.norms.as_ref().map(ChunkedVectors::flusher);
             let deleted_flusher = self.deleted.flusher();
             move || {
                 vectors_flusher()?;
+                if let Some(lengths_flusher) = lengths_flusher {
+                    lengths_flusher()?;
+                }
+                if let Some(norms_flusher) = norms_flusher {
+                    norms_flusher()?;
+                }
                 deleted_flusher()?;
                 Ok(())
             }
         })
     }
 
+    fn estimate_flush_size(&self) -> usize {
+        self.vectors.estimate_flush_size()
+            + self
+                .lengths
+                .as_ref()
+                .map_or(0, ChunkedVectors::estimate_flush_size)
+            + self
+                .norms
+                .as_ref()
+                .map_or(0, ChunkedVectors::estimate_flush_size)
+            + self.deleted.estimate_flush_size()
+    }
+
     fn files(&self) -> Vec<PathBuf> {
         let mut files = self.vectors.files();
+        if let Some(lengths) = &self.lengths {
+            files.extend(lengths.files());
+        }
+        if let Some(norms) = &self.norms {
+            files.extend(norms.files());
+        }
         files.extend(self.deleted.files());
         files
     }
 
     fn immutable_files(&self) -> Vec<PathBuf> {
-        self.vectors.immutable_files()
+        let mut files = self.vectors.immutable_files();
+        if let Some(lengths) = &self.lengths {
+            files.extend(lengths.immutable_files());
+        }
+        if let Some(norms) = &self.norms {
+            files.extend(norms.immutable_files());
+        }
+        files
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
         Ok(self.set_deleted(key, true))
     }
 
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = self.deleted.set_trues(keys);
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         self.deleted.get(key)
     }
@@ -238,6 +535,150 @@ pub fn open_appendable_memmap_vector_storage_half(
     )))
 }
 
+pub fn open_appendable_memmap_vector_storage_double(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_appendable_memmap_vector_storage_impl(path, dim, distance, madvise, populate)?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapDouble(Box::new(
+        storage,
+    )))
+}
+
+pub fn open_appendable_memmap_vector_storage_bfloat16(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_appendable_memmap_vector_storage_impl(path, dim, distance, madvise, populate)?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapBfloat16(Box::new(
+        storage,
+    )))
+}
+
+/// Data, optionally kept next to the vectors
+#[derive(Debug, Clone, Copy, Default)]
+struct StorageExtras {
+    /// Keep lengths of vectors of variable dimension
+    variable_dim: bool,
+    /// Keep norms of vectors, and normalize vectors on insert
+    preserve_norm: bool,
+}
+
+/// Open storage of dense vectors with variable dimension, at most `dim`
+///
+/// Shorter vectors are padded with zeros, their lengths are kept next to them.
+pub fn open_appendable_memmap_variable_dim_vector_storage(
+    storage_element_type: VectorStorageDatatype,
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let extras = StorageExtras {
+        variable_dim: true,
+        preserve_norm: false,
+    };
+    open_appendable_memmap_vector_storage_enum_with_extras(
+        storage_element_type,
+        path,
+        dim,
+        distance,
+        madvise,
+        populate,
+        extras,
+    )
+}
+
+/// Open storage of dense vectors, which normalizes vectors on insert and keeps their norms
+///
+/// Scoring uses normalized vectors, while vectors are read as they were inserted.
+pub fn open_appendable_memmap_norm_preserving_vector_storage(
+    storage_element_type: VectorStorageDatatype,
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let extras = StorageExtras {
+        variable_dim: false,
+        preserve_norm: true,
+    };
+    open_appendable_memmap_vector_storage_enum_with_extras(
+        storage_element_type,
+        path,
+        dim,
+        distance,
+        madvise,
+        populate,
+        extras,
+    )
+}
+
+fn open_appendable_memmap_vector_storage_enum_with_extras(
+    storage_element_type: VectorStorageDatatype,
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+    extras: StorageExtras,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = match storage_element_type {
+        VectorStorageDatatype::Float32 => VectorStorageEnum::DenseAppendableMemmap(Box::new(
+            open_appendable_memmap_vector_storage_with_extras(
+                path, dim, distance, madvise, populate, extras,
+            )?,
+        )),
+        VectorStorageDatatype::Uint8 if !extras.preserve_norm => {
+            VectorStorageEnum::DenseAppendableMemmapByte(Box::new(
+                open_appendable_memmap_vector_storage_with_extras(
+                    path, dim, distance, madvise, populate, extras,
+                )?,
+            ))
+        }
+        VectorStorageDatatype::Float16 => VectorStorageEnum::DenseAppendableMemmapHalf(Box::new(
+            open_appendable_memmap_vector_storage_with_extras(
+                path, dim, distance, madvise, populate, extras,
+            )?,
+        )),
+        VectorStorageDatatype::Float64 => VectorStorageEnum::DenseAppendableMemmapDouble(Box::new(
+            open_appendable_memmap_vector_storage_with_extras(
+                path, dim, distance, madvise, populate, extras,
+            )?,
+        )),
+        VectorStorageDatatype::Bfloat16 => VectorStorageEnum::DenseAppendableMemmapBfloat16(
+            Box::new(open_appendable_memmap_vector_storage_with_extras(
+                path, dim, distance, madvise, populate, extras,
+            )?),
+        ),
+        VectorStorageDatatype::Uint8
+        | VectorStorageDatatype::Int8
+        | VectorStorageDatatype::Int4 => {
+            let feature = if extras.preserve_norm {
+                "preserving norms of vectors"
+            } else {
+                "vectors of variable dimension"
+            };
+            return Err(OperationError::service_error(format!(
+                "{storage_element_type:?} vector storage doesn't support {feature}",
+            )));
+        }
+    };
+    Ok(storage)
+}
+
 pub fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
     path: &Path,
     dim: usize,
@@ -245,21 +686,53 @@ pub fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
     madvise: AdviceSetting,
     populate: bool,
 ) -> OperationResult<AppendableMmapDenseVectorStorage<T>> {
+    open_appendable_memmap_vector_storage_with_extras(
+        path,
+        dim,
+        distance,
+        madvise,
+        populate,
+        StorageExtras::default(),
+    )
+}
+
+fn open_appendable_memmap_vector_storage_with_extras<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+    extras: StorageExtras,
+) -> OperationResult<AppendableMmapDenseVectorStorage<T>> {
+    let StorageExtras {
+        variable_dim,
+        preserve_norm,
+    } = extras;
+
     fs::create_dir_all(path)?;
 
     let vectors_path = path.join(VECTORS_DIR_PATH);
     let deleted_path = path.join(DELETED_DIR_PATH);
 
     let vectors = ChunkedVectors::open(&vectors_path, dim, madvise, Some(populate))?;
+    let lengths = variable_dim
+        .then(|| ChunkedVectors::open(&path.join(LENGTHS_DIR_PATH), 1, madvise, Some(populate)))
+        .transpose()?;
+    let norms = preserve_norm
+        .then(|| ChunkedVectors::open(&path.join(NORMS_DIR_PATH), 1, madvise, Some(populate)))
+        .transpose()?;
 
     let deleted = BitvecFlags::new(DynamicMmapFlags::open(&deleted_path, populate)?);
     let deleted_count = deleted.count_trues();
 
     Ok(AppendableMmapDenseVectorStorage {
         vectors,
+        lengths,
+        norms,
         deleted,
         distance,
         deleted_count,
+        write_throttle: None,
         _phantom: Default::default(),
     })
 }
@@ -268,10 +741,14 @@ pub fn open_appendable_memmap_vector_storage_impl<T: PrimitiveVectorElement>(
 #[cfg(any(test, feature = "rocksdb"))]
 pub(crate) fn find_storage_files(vector_storage_path: &Path) -> OperationResult<Vec<PathBuf>> {
     let vectors_path = vector_storage_path.join(VECTORS_DIR_PATH);
+    let lengths_path = vector_storage_path.join(LENGTHS_DIR_PATH);
+    let norms_path = vector_storage_path.join(NORMS_DIR_PATH);
     let deleted_path = vector_storage_path.join(DELETED_DIR_PATH);
 
     let mut files = vec![];
     files.extend(common::disk::list_files(&vectors_path)?);
+    files.extend(common::disk::list_files(&lengths_path)?);
+    files.extend(common::disk::list_files(&norms_path)?);
     files.extend(common::disk::list_files(&deleted_path)?);
     Ok(files)
 }
@@ -336,4 +813,200 @@ mod tests {
             "find_storage_files must find same files that storage reports",
         );
     }
+
+    #[test]
+    fn test_estimate_flush_size() {
+        const DIM: usize = 128;
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let mut storage = open_appendable_memmap_vector_storage_impl::<VectorElementType>(
+            dir.path(),
+            DIM,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+        let hw_counter = HardwareCounterCell::disposable();
+        assert_eq!(storage.estimate_flush_size(), 0);
+
+        // Whole chunk of the inserted vector, and its deleted flag are written
+        let point = vec![1.0; DIM];
+        storage
+            .insert_vector(0, VectorRef::from(&point), &hw_counter)
+            .unwrap();
+        storage
+            .insert_vector(1, VectorRef::from(&point), &hw_counter)
+            .unwrap();
+        assert_eq!(
+            storage.estimate_flush_size(),
+            storage.vectors.max_vector_size_bytes() + 1,
+        );
+
+        storage.flusher()().unwrap();
+        assert_eq!(storage.estimate_flush_size(), 0);
+
+        // Only the deleted flag is written
+        storage.delete_vector(1).unwrap();
+        assert_eq!(storage.estimate_flush_size(), 1);
+    }
+
+    #[test]
+    fn test_update_from_same() {
+        const POINT_COUNT: PointOffsetType = 1500;
+        const DIM: usize = 128;
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let open = |name: &str| {
+            open_appendable_memmap_vector_storage_impl::<VectorElementType>(
+                &dir.path().join(name),
+                DIM,
+                Distance::Dot,
+                AdviceSetting::Global,
+                false,
+            )
+            .unwrap()
+        };
+
+        let mut rng = StdRng::seed_from_u64(RAND_SEED);
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let mut sources = [open("source_a"), open("source_b")];
+        for source in &mut sources {
+            for internal_id in 0..POINT_COUNT {
+                let point = std::iter::repeat_with(|| rng.random_range(-1.0..1.0))
+                    .take(DIM)
+                    .collect::<Vec<_>>();
+                source
+                    .insert_vector(internal_id, VectorRef::from(&point), &hw_counter)
+                    .unwrap();
+                if rng.random_bool(0.1) {
+                    source.delete_vector(internal_id).unwrap();
+                }
+            }
+        }
+
+        // Long consecutive runs crossing chunk boundaries, interleaved with single points
+        let points: Vec<_> = (0..POINT_COUNT)
+            .map(|key| (0, key))
+            .filter(|&(_, key)| key % 700 != 0)
+            .chain((0..POINT_COUNT).rev().step_by(7).map(|key| (1, key)))
+            .collect();
+
+        let mut storage = open("target");
+        let others = sources.iter().collect::<Vec<_>>();
+        let range = storage
+            .update_from_same(&others, &points, &AtomicBool::new(false))
+            .unwrap()
+            .unwrap();
+        assert_eq!(range, 0..points.len() as PointOffsetType);
+
+        for (new_key, &(source_idx, old_key)) in points.iter().enumerate() {
+            let new_key = new_key as PointOffsetType;
+            let source = &sources[source_idx];
+            assert_eq!(
+                storage.get_dense::<Random>(new_key),
+                source.get_dense::<Random>(old_key),
+            );
+            assert_eq!(
+                storage.is_deleted_vector(new_key),
+                source.is_deleted_vector(old_key),
+            );
+        }
+
+        // Storages of other dimensions are not copied
+        let other_dim = open_appendable_memmap_vector_storage_impl::<VectorElementType>(
+            &dir.path().join("other_dim"),
+            DIM / 2,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+        let result = storage
+            .update_from_same(&[&other_dim], &[(0, 0)], &AtomicBool::new(false))
+            .unwrap();
+        assert!(result.is_none());
+        assert_eq!(storage.total_vector_count(), points.len());
+    }
+
+    #[test]
+    fn test_preserve_norm() {
+        const DIM: usize = 16;
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let open = |name: &str| {
+            let extras = StorageExtras {
+                preserve_norm: true,
+                ..Default::default()
+            };
+            open_appendable_memmap_vector_storage_with_extras::<VectorElementType>(
+                &dir.path().join(name),
+                DIM,
+                Distance::Cosine,
+                AdviceSetting::Global,
+                false,
+                extras,
+            )
+            .unwrap()
+        };
+
+        let mut rng = StdRng::seed_from_u64(RAND_SEED);
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let mut originals: Vec<Vec<VectorElementType>> = (0..10)
+            .map(|_| {
+                std::iter::repeat_with(|| rng.random_range(-10.0..10.0))
+                    .take(DIM)
+                    .collect()
+            })
+            .collect();
+        originals.push(vec![0.0; DIM]);
+
+        let assert_preserved = |storage: &AppendableMmapDenseVectorStorage<VectorElementType>| {
+            for (key, original) in originals.iter().enumerate() {
+                let key = key as PointOffsetType;
+                let stored = storage.get_dense::<Random>(key);
+                let expected =
+                    Distance::Cosine.preprocess_vector::<VectorElementType>(original.clone());
+                for (a, b) in stored.iter().zip(&expected) {
+                    assert!((a - b).abs() < 1e-5, "{stored:?} != {expected:?}");
+                }
+
+                let retrieved = storage.get_vector::<Random>(key);
+                let retrieved: &[VectorElementType] = retrieved.as_vec_ref().try_into().unwrap();
+                for (a, b) in retrieved.iter().zip(original) {
+                    assert!((a - b).abs() < 1e-4, "{retrieved:?} != {original:?}");
+                }
+            }
+        };
+
+        {
+            let mut storage = open("source");
+            for (key, original) in originals.iter().enumerate() {
+                storage
+                    .insert_vector(
+                        key as PointOffsetType,
+                        VectorRef::from(original),
+                        &hw_counter,
+                    )
+                    .unwrap();
+            }
+            assert_preserved(&storage);
+            storage.flusher()().unwrap();
+        }
+
+        // Norms are persisted
+        let source = open("source");
+        assert_preserved(&source);
+
+        // Norms are kept when copying vectors between storages
+        let mut target = open("target");
+        let mut iter = (0..originals.len() as PointOffsetType)
+            .map(|key| (source.get_vector::<Random>(key), false));
+        target
+            .update_from(&mut iter, &AtomicBool::new(false))
+            .unwrap();
+        assert_preserved(&target);
+    }
 }
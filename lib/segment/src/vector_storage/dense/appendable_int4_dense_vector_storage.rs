@@ -0,0 +1,608 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::{AccessPattern, Random, Sequential};
+use common::mmap::AdviceSetting;
+use common::types::PointOffsetType;
+use common::universal_io::MmapFile;
+use fs_err as fs;
+
+use crate::common::Flusher;
+use crate::common::flags::bitvec_flags::BitvecFlags;
+use crate::common::flags::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::read_stats::VectorStorageStats;
+use crate::vector_storage::write_throttle::WriteThrottle;
+use crate::vector_storage::{
+    DenseVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
+};
+
+const VECTORS_DIR_PATH: &str = "vectors";
+const PARAMS_DIR_PATH: &str = "quantization_params";
+const DELETED_DIR_PATH: &str = "deleted";
+
+/// Number of `f32` values stored per vector in the params storage: scale and zero-point
+const PARAMS_DIM: usize = 2;
+
+/// Largest 4-bit code
+const INT4_MAX: u8 = 0x0F;
+
+/// Number of bytes of a packed vector of dimension `dim`
+#[inline]
+pub fn packed_len(dim: usize) -> usize {
+    dim.div_ceil(2)
+}
+
+/// Pack 4-bit `codes` two per byte, the first code of a pair goes into the low nibble
+pub fn pack_int4(codes: &[u8]) -> Vec<u8> {
+    codes
+        .chunks(2)
+        .map(|pair| {
+            let low = pair[0] & INT4_MAX;
+            let high = pair.get(1).map_or(0, |&code| code & INT4_MAX);
+            low | (high << 4)
+        })
+        .collect()
+}
+
+/// Unpack first `dim` 4-bit codes from `packed`
+pub fn unpack_int4(packed: &[u8], dim: usize) -> impl Iterator<Item = u8> + '_ {
+    packed
+        .iter()
+        .flat_map(|&byte| [byte & INT4_MAX, byte >> 4])
+        .take(dim)
+}
+
+/// Parameters of asymmetric int4 quantization of a single vector.
+///
+/// Code `q` in range `[0, 15]` is dequantized as `scale * (q - zero_point)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Int4QuantizationParams {
+    pub scale: f32,
+    pub zero_point: f32,
+}
+
+impl Int4QuantizationParams {
+    /// Parameters, which map the range of `vector` onto all 16 codes
+    pub fn for_vector(vector: &[VectorElementType]) -> Self {
+        let (min, max) = vector
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &x| {
+                (min.min(x), max.max(x))
+            });
+
+        // Empty or constant vector, encode all elements as 0
+        if min >= max {
+            let value = if min.is_finite() { min } else { 0.0 };
+            return Self {
+                scale: 1.0,
+                zero_point: -value,
+            };
+        }
+
+        let scale = (max - min) / f32::from(INT4_MAX);
+        Self {
+            scale,
+            zero_point: -min / scale,
+        }
+    }
+
+    /// Quantize `vector` into packed 4-bit codes
+    pub fn quantize(&self, vector: &[VectorElementType]) -> Vec<u8> {
+        let codes: Vec<u8> = vector
+            .iter()
+            .map(|&x| {
+                (x / self.scale + self.zero_point)
+                    .round()
+                    .clamp(0.0, f32::from(INT4_MAX)) as u8
+            })
+            .collect();
+        pack_int4(&codes)
+    }
+
+    pub fn dequantize(&self, packed: &[u8], dim: usize) -> DenseVector {
+        unpack_int4(packed, dim)
+            .map(|q| self.scale * (f32::from(q) - self.zero_point))
+            .collect()
+    }
+
+    fn validate(&self) -> OperationResult<()> {
+        if !self.scale.is_finite() || self.scale == 0.0 || !self.zero_point.is_finite() {
+            return Err(OperationError::validation_error(format!(
+                "Invalid int4 quantization params: scale {}, zero point {}",
+                self.scale, self.zero_point,
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Appendable mmap storage of int4 dense vectors
+///
+/// Elements are packed two per byte, every vector has its own scale and zero-point, stored in a
+/// parallel chunked mmap. Nearest queries are scored on packed vectors directly, see
+/// [`Int4QueryScorer`](crate::vector_storage::query_scorer::int4_query_scorer::Int4QueryScorer).
+/// For everything else, vectors are dequantized into floats on read.
+#[derive(Debug)]
+pub struct AppendableMmapInt4DenseVectorStorage {
+    /// Packed vectors, `packed_len(dim)` bytes each
+    vectors: ChunkedVectors<u8, MmapFile>,
+    /// Scale and zero-point of every vector
+    params: ChunkedVectors<f32, MmapFile>,
+    /// Flags marking deleted vectors
+    ///
+    /// Structure grows dynamically, but may be smaller than actual number of vectors. Must not
+    /// depend on its length.
+    deleted: BitvecFlags,
+    dim: usize,
+    distance: Distance,
+    deleted_count: usize,
+    /// Backpressure on inserts, if dirty pages are not flushed fast enough
+    write_throttle: Option<Arc<WriteThrottle>>,
+}
+
+impl AppendableMmapInt4DenseVectorStorage {
+    /// Number of bytes of vectors and their quantization params, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes() + self.params.ram_usage_bytes()
+    }
+
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.vectors.read_stats()
+    }
+
+    pub fn set_write_throttle(&mut self, write_throttle: Option<Arc<WriteThrottle>>) {
+        self.write_throttle = write_throttle;
+    }
+
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if !deleted && self.vectors.len() <= key as usize {
+            return false;
+        }
+
+        // mark deletion
+        let previous = self.deleted.set(key, deleted);
+
+        // update counter
+        if !previous && deleted {
+            self.deleted_count += 1;
+        } else if previous && !deleted {
+            self.deleted_count -= 1;
+        }
+
+        previous
+    }
+
+    /// Insert vector, which is already quantized and packed by an external quantizer
+    pub fn insert_quantized(
+        &mut self,
+        key: PointOffsetType,
+        packed: &[u8],
+        params: Int4QuantizationParams,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if packed.len() != self.vectors.dim() {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: self.dim,
+                received_dim: packed.len() * 2,
+            });
+        }
+        params.validate()?;
+
+        let key = key as VectorOffsetType;
+        self.vectors.insert(key, packed, hw_counter)?;
+        self.params
+            .insert(key, &[params.scale, params.zero_point], hw_counter)?;
+        self.set_deleted(key as PointOffsetType, false);
+        Ok(())
+    }
+
+    /// Packed 4-bit elements of the vector, without dequantization
+    pub fn get_packed<P: AccessPattern>(&self, key: PointOffsetType) -> Option<Cow<'_, [u8]>> {
+        self.vectors.get::<P>(key as VectorOffsetType)
+    }
+
+    pub fn get_params<P: AccessPattern>(
+        &self,
+        key: PointOffsetType,
+    ) -> Option<Int4QuantizationParams> {
+        let params = self.params.get::<P>(key as VectorOffsetType)?;
+        Some(Int4QuantizationParams {
+            scale: params[0],
+            zero_point: params[1],
+        })
+    }
+
+    /// Read packed vectors for the given keys as a single batch, and call `f` for each of them
+    pub fn for_each_packed_in_batch(
+        &self,
+        keys: &[PointOffsetType],
+        mut f: impl FnMut(usize, &[u8], Int4QuantizationParams),
+    ) {
+        self.vectors.for_each_in_batch(keys, |idx, packed| {
+            let params = self
+                .get_params::<Random>(keys[idx])
+                .expect("quantization params not found");
+            f(idx, packed, params);
+        });
+    }
+
+    fn get_dequantized<P: AccessPattern>(&self, key: PointOffsetType) -> Option<DenseVector> {
+        let packed = self.get_packed::<P>(key)?;
+        let params = self.get_params::<P>(key)?;
+        Some(params.dequantize(&packed, self.dim))
+    }
+
+    fn push_quantized(
+        &mut self,
+        packed: &[u8],
+        params: Int4QuantizationParams,
+        deleted: bool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let new_id = self.vectors.push(packed, hw_counter)?;
+        self.params
+            .insert(new_id, &[params.scale, params.zero_point], hw_counter)?;
+        self.set_deleted(new_id as PointOffsetType, deleted);
+        Ok(())
+    }
+
+    /// Populate all pages in the mmap.
+    /// Block until all pages are populated.
+    pub fn populate(&self) -> OperationResult<()> {
+        // deleted bitvec is already loaded
+        self.vectors.populate()?;
+        self.params.populate()?;
+        Ok(())
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        self.deleted.clear_cache()?;
+        self.vectors.clear_cache()?;
+        self.params.clear_cache()?;
+        Ok(())
+    }
+
+    /// Check vector files against checksums, computed at flush
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        self.vectors.verify_integrity()?;
+        self.params.verify_integrity()?;
+        Ok(())
+    }
+
+    /// Append vectors from other int4 storages, keeping their packed elements and params as is.
+    ///
+    /// `points` are pairs of index in `others` and offset of the vector in that storage.
+    ///
+    /// Returns `None` without changing anything, if dimensions of the storages differ.
+    pub fn update_from_same(
+        &mut self,
+        others: &[&Self],
+        points: &[(usize, PointOffsetType)],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Range<PointOffsetType>>> {
+        if others.iter().any(|other| other.dim != self.dim) {
+            return Ok(None);
+        }
+
+        let start_index = self.vectors.len() as PointOffsetType;
+        let disposed_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+
+        for &(storage_idx, key) in points {
+            check_process_stopped(stopped)?;
+
+            let other = others[storage_idx];
+            let (Some(packed), Some(params)) = (
+                other.get_packed::<Sequential>(key),
+                other.get_params::<Sequential>(key),
+            ) else {
+                return Err(OperationError::service_error(format!(
+                    "Vector {key} not found in source storage",
+                )));
+            };
+            self.push_quantized(&packed, params, other.is_deleted_vector(key), &disposed_hw)?;
+        }
+
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(Some(start_index..end_index))
+    }
+}
+
+impl DenseVectorStorage<VectorElementType> for AppendableMmapInt4DenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> Cow<'_, [VectorElementType]> {
+        Cow::Owned(
+            self.get_dequantized::<P>(key)
+                .expect("mmap vector not found"),
+        )
+    }
+
+    fn size_of_available_vectors_in_bytes(&self) -> usize {
+        let vector_size = packed_len(self.dim) + PARAMS_DIM * size_of::<VectorElementType>();
+        self.available_vector_count() * vector_size
+    }
+}
+
+impl VectorStorage for AppendableMmapInt4DenseVectorStorage {
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Int4
+    }
+
+    fn is_on_disk(&self) -> bool {
+        self.vectors.is_on_disk()
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
+        self.get_vector_opt::<P>(key).expect("Vector not found")
+    }
+
+    fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
+        self.get_dequantized::<P>(key).map(CowVector::from)
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let vector: &[VectorElementType] = vector.try_into()?;
+        if vector.len() != self.dim {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: self.dim,
+                received_dim: vector.len(),
+            });
+        }
+        if let Some(write_throttle) = &self.write_throttle {
+            let bytes = packed_len(vector.len()) + PARAMS_DIM * size_of::<VectorElementType>();
+            write_throttle.throttle(self.estimate_flush_size(), bytes);
+        }
+        let params = Int4QuantizationParams::for_vector(vector);
+        self.insert_quantized(key, &params.quantize(vector), params, hw_counter)
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        let disposed_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+        for (other_vector, other_deleted) in other_vectors {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector: Cow<[VectorElementType]> = Cow::try_from(other_vector)?;
+            if other_vector.len() != self.dim {
+                return Err(OperationError::WrongVectorDimension {
+                    expected_dim: self.dim,
+                    received_dim: other_vector.len(),
+                });
+            }
+            let params = Int4QuantizationParams::for_vector(&other_vector);
+            self.push_quantized(
+                &params.quantize(&other_vector),
+                params,
+                other_deleted,
+                &disposed_hw,
+            )?;
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new({
+            let vectors_flusher = self.vectors.flusher();
+            let params_flusher = self.params.flusher();
+            let deleted_flusher = self.deleted.flusher();
+            move || {
+                vectors_flusher()?;
+                params_flusher()?;
+                deleted_flusher()?;
+                Ok(())
+            }
+        })
+    }
+
+    fn estimate_flush_size(&self) -> usize {
+        self.vectors.estimate_flush_size()
+            + self.params.estimate_flush_size()
+            + self.deleted.estimate_flush_size()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.vectors.files();
+        files.extend(self.params.files());
+        files.extend(self.deleted.files());
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = self.vectors.immutable_files();
+        files.extend(self.params.immutable_files());
+        files
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        Ok(self.set_deleted(key, true))
+    }
+
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = self.deleted.set_trues(keys);
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
+
+pub fn open_appendable_memmap_vector_storage_int4(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_appendable_memmap_int4_vector_storage_impl(path, dim, distance, madvise, populate)?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapInt4(Box::new(
+        storage,
+    )))
+}
+
+pub fn open_appendable_memmap_int4_vector_storage_impl(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<AppendableMmapInt4DenseVectorStorage> {
+    fs::create_dir_all(path)?;
+
+    let vectors_path = path.join(VECTORS_DIR_PATH);
+    let params_path = path.join(PARAMS_DIR_PATH);
+    let deleted_path = path.join(DELETED_DIR_PATH);
+
+    let vectors = ChunkedVectors::open(&vectors_path, packed_len(dim), madvise, Some(populate))?;
+    let params = ChunkedVectors::open(&params_path, PARAMS_DIM, madvise, Some(populate))?;
+
+    let deleted = BitvecFlags::new(DynamicMmapFlags::open(&deleted_path, populate)?);
+    let deleted_count = deleted.count_trues();
+
+    Ok(AppendableMmapInt4DenseVectorStorage {
+        vectors,
+        params,
+        deleted,
+        dim,
+        distance,
+        deleted_count,
+        write_throttle: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn open_storage(path: &Path) -> AppendableMmapInt4DenseVectorStorage {
+        open_appendable_memmap_int4_vector_storage_impl(
+            path,
+            5,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pack_int4() {
+        let codes = [1, 15, 0, 7, 9];
+        let packed = pack_int4(&codes);
+        assert_eq!(packed, vec![0xF1, 0x70, 0x09]);
+        assert_eq!(packed.len(), packed_len(codes.len()));
+        assert_eq!(unpack_int4(&packed, codes.len()).collect::<Vec<_>>(), codes);
+    }
+
+    #[test]
+    fn test_int4_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = open_storage(dir.path());
+
+        // Float vectors are quantized on insertion, odd dimension leaves half of a byte unused
+        let vector = vec![-1.0, 0.0, 0.25, 0.5, 1.0];
+        storage
+            .insert_vector(0, VectorRef::from(&vector), &hw_counter)
+            .unwrap();
+        assert_eq!(storage.get_packed::<Random>(0).unwrap().len(), 3);
+        let dense = storage.get_dense::<Random>(0);
+        let params = storage.get_params::<Random>(0).unwrap();
+        assert_eq!(dense.len(), vector.len());
+        for (stored, original) in dense.iter().zip(&vector) {
+            assert!((stored - original).abs() <= params.scale / 2.0 + 1e-6);
+        }
+
+        // Constant vectors are stored exactly
+        let constant = vec![3.0; 5];
+        storage
+            .insert_vector(1, VectorRef::from(&constant), &hw_counter)
+            .unwrap();
+        assert_eq!(storage.get_dense::<Random>(1).as_ref(), constant.as_slice());
+
+        // Pre-quantized vectors are dequantized with their own params
+        let params = Int4QuantizationParams {
+            scale: 0.5,
+            zero_point: 8.0,
+        };
+        let packed = pack_int4(&[8, 10, 6, 0, 15]);
+        storage
+            .insert_quantized(2, &packed, params, &hw_counter)
+            .unwrap();
+        assert_eq!(
+            storage.get_dense::<Random>(2).as_ref(),
+            &[0.0, 1.0, -1.0, -4.0, 3.5],
+        );
+
+        assert!(
+            storage
+                .insert_quantized(3, &[1, 2], params, &hw_counter)
+                .is_err()
+        );
+        assert!(
+            storage
+                .insert_vector(3, VectorRef::from(&vec![1.0; 4]), &hw_counter)
+                .is_err()
+        );
+
+        storage.delete_vector(1).unwrap();
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let storage = open_storage(dir.path());
+        assert_eq!(storage.total_vector_count(), 3);
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert_eq!(storage.get_params::<Random>(2), Some(params));
+        assert_eq!(
+            storage.get_packed::<Random>(2).unwrap().as_ref(),
+            packed.as_slice(),
+        );
+    }
+}
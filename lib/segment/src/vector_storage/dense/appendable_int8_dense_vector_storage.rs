@@ -0,0 +1,545 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::{AccessPattern, Sequential};
+use common::mmap::AdviceSetting;
+use common::types::PointOffsetType;
+use common::universal_io::MmapFile;
+use fs_err as fs;
+
+use crate::common::Flusher;
+use crate::common::flags::bitvec_flags::BitvecFlags;
+use crate::common::flags::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::chunked_vectors::ChunkedVectors;
+use crate::vector_storage::read_stats::VectorStorageStats;
+use crate::vector_storage::write_throttle::WriteThrottle;
+use crate::vector_storage::{
+    DenseVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
+};
+
+const VECTORS_DIR_PATH: &str = "vectors";
+const PARAMS_DIR_PATH: &str = "quantization_params";
+const DELETED_DIR_PATH: &str = "deleted";
+
+/// Number of `f32` values stored per vector in the params storage: scale and zero-point
+const PARAMS_DIM: usize = 2;
+
+/// Parameters of asymmetric int8 quantization of a single vector.
+///
+/// Element `q` is dequantized as `scale * (q - zero_point)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Int8QuantizationParams {
+    pub scale: f32,
+    pub zero_point: f32,
+}
+
+impl Int8QuantizationParams {
+    /// Parameters, which map the range of `vector` onto the whole int8 range
+    pub fn for_vector(vector: &[VectorElementType]) -> Self {
+        let (min, max) = vector
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &x| {
+                (min.min(x), max.max(x))
+            });
+
+        // Empty or constant vector, encode all elements as 0
+        if min >= max {
+            let value = if min.is_finite() { min } else { 0.0 };
+            return Self {
+                scale: 1.0,
+                zero_point: -value,
+            };
+        }
+
+        let scale = (max - min) / (f32::from(i8::MAX) - f32::from(i8::MIN));
+        Self {
+            scale,
+            zero_point: f32::from(i8::MIN) - min / scale,
+        }
+    }
+
+    pub fn quantize(&self, vector: &[VectorElementType]) -> Vec<i8> {
+        vector
+            .iter()
+            .map(|&x| {
+                (x / self.scale + self.zero_point)
+                    .round()
+                    .clamp(f32::from(i8::MIN), f32::from(i8::MAX)) as i8
+            })
+            .collect()
+    }
+
+    pub fn dequantize(&self, codes: &[i8]) -> DenseVector {
+        codes
+            .iter()
+            .map(|&q| self.scale * (f32::from(q) - self.zero_point))
+            .collect()
+    }
+
+    fn validate(&self) -> OperationResult<()> {
+        if !self.scale.is_finite() || self.scale == 0.0 || !self.zero_point.is_finite() {
+            return Err(OperationError::validation_error(format!(
+                "Invalid int8 quantization params: scale {}, zero point {}",
+                self.scale, self.zero_point,
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Appendable mmap storage of int8 dense vectors
+///
+/// Every vector has its own scale and zero-point, stored in a parallel chunked mmap. Vectors are
+/// dequantized into floats on read, so the storage acts as a float32 storage for scoring.
+#[derive(Debug)]
+pub struct AppendableMmapInt8DenseVectorStorage {
+    vectors: ChunkedVectors<i8, MmapFile>,
+    /// Scale and zero-point of every vector
+    params: ChunkedVectors<f32, MmapFile>,
+    /// Flags marking deleted vectors
+    ///
+    /// Structure grows dynamically, but may be smaller than actual number of vectors. Must not
+    /// depend on its length.
+    deleted: BitvecFlags,
+    distance: Distance,
+    deleted_count: usize,
+    /// Backpressure on inserts, if dirty pages are not flushed fast enough
+    write_throttle: Option<Arc<WriteThrottle>>,
+}
+
+impl AppendableMmapInt8DenseVectorStorage {
+    /// Number of bytes of vectors and their quantization params, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes() + self.params.ram_usage_bytes()
+    }
+
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.vectors.read_stats()
+    }
+
+    pub fn set_write_throttle(&mut self, write_throttle: Option<Arc<WriteThrottle>>) {
+        self.write_throttle = write_throttle;
+    }
+
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if !deleted && self.vectors.len() <= key as usize {
+            return false;
+        }
+
+        // mark deletion
+        let previous = self.deleted.set(key, deleted);
+
+        // update counter
+        if !previous && deleted {
+            self.deleted_count += 1;
+        } else if previous && !deleted {
+            self.deleted_count -= 1;
+        }
+
+        previous
+    }
+
+    /// Insert vector, which is already quantized by an external quantizer
+    pub fn insert_quantized(
+        &mut self,
+        key: PointOffsetType,
+        codes: &[i8],
+        params: Int8QuantizationParams,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if codes.len() != self.vectors.dim() {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: self.vectors.dim(),
+                received_dim: codes.len(),
+            });
+        }
+        params.validate()?;
+
+        let key = key as VectorOffsetType;
+        self.vectors.insert(key, codes, hw_counter)?;
+        self.params
+            .insert(key, &[params.scale, params.zero_point], hw_counter)?;
+        self.set_deleted(key as PointOffsetType, false);
+        Ok(())
+    }
+
+    /// Raw int8 elements of the vector, without dequantization
+    pub fn get_quantized<P: AccessPattern>(&self, key: PointOffsetType) -> Option<Cow<'_, [i8]>> {
+        self.vectors.get::<P>(key as VectorOffsetType)
+    }
+
+    pub fn get_params<P: AccessPattern>(
+        &self,
+        key: PointOffsetType,
+    ) -> Option<Int8QuantizationParams> {
+        let params = self.params.get::<P>(key as VectorOffsetType)?;
+        Some(Int8QuantizationParams {
+            scale: params[0],
+            zero_point: params[1],
+        })
+    }
+
+    fn get_dequantized<P: AccessPattern>(&self, key: PointOffsetType) -> Option<DenseVector> {
+        let codes = self.get_quantized::<P>(key)?;
+        let params = self.get_params::<P>(key)?;
+        Some(params.dequantize(&codes))
+    }
+
+    fn push_quantized(
+        &mut self,
+        codes: &[i8],
+        params: Int8QuantizationParams,
+        deleted: bool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let new_id = self.vectors.push(codes, hw_counter)?;
+        self.params
+            .insert(new_id, &[params.scale, params.zero_point], hw_counter)?;
+        self.set_deleted(new_id as PointOffsetType, deleted);
+        Ok(())
+    }
+
+    /// Populate all pages in the mmap.
+    /// Block until all pages are populated.
+    pub fn populate(&self) -> OperationResult<()> {
+        // deleted bitvec is already loaded
+        self.vectors.populate()?;
+        self.params.populate()?;
+        Ok(())
+    }
+
+    /// Drop disk cache.
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        self.deleted.clear_cache()?;
+        self.vectors.clear_cache()?;
+        self.params.clear_cache()?;
+        Ok(())
+    }
+
+    /// Check vector files against checksums, computed at flush
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        self.vectors.verify_integrity()?;
+        self.params.verify_integrity()?;
+        Ok(())
+    }
+
+    /// Append vectors from other int8 storages, keeping their quantized elements and params as is.
+    ///
+    /// `points` are pairs of index in `others` and offset of the vector in that storage.
+    ///
+    /// Returns `None` without changing anything, if dimensions of the storages differ.
+    pub fn update_from_same(
+        &mut self,
+        others: &[&Self],
+        points: &[(usize, PointOffsetType)],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Range<PointOffsetType>>> {
+        if others
+            .iter()
+            .any(|other| other.vectors.dim() != self.vectors.dim())
+        {
+            return Ok(None);
+        }
+
+        let start_index = self.vectors.len() as PointOffsetType;
+        let disposed_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+
+        for &(storage_idx, key) in points {
+            check_process_stopped(stopped)?;
+
+            let other = others[storage_idx];
+            let (Some(codes), Some(params)) = (
+                other.get_quantized::<Sequential>(key),
+                other.get_params::<Sequential>(key),
+            ) else {
+                return Err(OperationError::service_error(format!(
+                    "Vector {key} not found in source storage",
+                )));
+            };
+            self.push_quantized(&codes, params, other.is_deleted_vector(key), &disposed_hw)?;
+        }
+
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(Some(start_index..end_index))
+    }
+}
+
+impl DenseVectorStorage<VectorElementType> for AppendableMmapInt8DenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.vectors.dim()
+    }
+
+    fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> Cow<'_, [VectorElementType]> {
+        Cow::Owned(
+            self.get_dequantized::<P>(key)
+                .expect("mmap vector not found"),
+        )
+    }
+
+    fn size_of_available_vectors_in_bytes(&self) -> usize {
+        let vector_size =
+            self.vector_dim() * size_of::<i8>() + PARAMS_DIM * size_of::<VectorElementType>();
+        self.available_vector_count() * vector_size
+    }
+}
+
+impl VectorStorage for AppendableMmapInt8DenseVectorStorage {
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Int8
+    }
+
+    fn is_on_disk(&self) -> bool {
+        self.vectors.is_on_disk()
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.vectors.len()
+    }
+
+    fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
+        self.get_vector_opt::<P>(key).expect("Vector not found")
+    }
+
+    fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
+        self.get_dequantized::<P>(key).map(CowVector::from)
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let vector: &[VectorElementType] = vector.try_into()?;
+        if let Some(write_throttle) = &self.write_throttle {
+            let bytes =
+                vector.len() * size_of::<i8>() + PARAMS_DIM * size_of::<VectorElementType>();
+            write_throttle.throttle(self.estimate_flush_size(), bytes);
+        }
+        let params = Int8QuantizationParams::for_vector(vector);
+        self.insert_quantized(key, &params.quantize(vector), params, hw_counter)
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.vectors.len() as PointOffsetType;
+        let disposed_hw = HardwareCounterCell::disposable(); // This function is only used for internal operations.
+        for (other_vector, other_deleted) in other_vectors {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector: Cow<[VectorElementType]> = Cow::try_from(other_vector)?;
+            let params = Int8QuantizationParams::for_vector(&other_vector);
+            self.push_quantized(
+                &params.quantize(&other_vector),
+                params,
+                other_deleted,
+                &disposed_hw,
+            )?;
+        }
+        let end_index = self.vectors.len() as PointOffsetType;
+        Ok(start_index..end_index)
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new({
+            let vectors_flusher = self.vectors.flusher();
+            let params_flusher = self.params.flusher();
+            let deleted_flusher = self.deleted.flusher();
+            move || {
+                vectors_flusher()?;
+                params_flusher()?;
+                deleted_flusher()?;
+                Ok(())
+            }
+        })
+    }
+
+    fn estimate_flush_size(&self) -> usize {
+        self.vectors.estimate_flush_size()
+            + self.params.estimate_flush_size()
+            + self.deleted.estimate_flush_size()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.vectors.files();
+        files.extend(self.params.files());
+        files.extend(self.deleted.files());
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        let mut files = self.vectors.immutable_files();
+        files.extend(self.params.immutable_files());
+        files
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        Ok(self.set_deleted(key, true))
+    }
+
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = self.deleted.set_trues(keys);
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
+
+pub fn open_appendable_memmap_vector_storage_int8(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_appendable_memmap_int8_vector_storage_impl(path, dim, distance, madvise, populate)?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapInt8(Box::new(
+        storage,
+    )))
+}
+
+pub fn open_appendable_memmap_int8_vector_storage_impl(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<AppendableMmapInt8DenseVectorStorage> {
+    fs::create_dir_all(path)?;
+
+    let vectors_path = path.join(VECTORS_DIR_PATH);
+    let params_path = path.join(PARAMS_DIR_PATH);
+    let deleted_path = path.join(DELETED_DIR_PATH);
+
+    let vectors = ChunkedVectors::open(&vectors_path, dim, madvise, Some(populate))?;
+    let params = ChunkedVectors::open(&params_path, PARAMS_DIM, madvise, Some(populate))?;
+
+    let deleted = BitvecFlags::new(DynamicMmapFlags::open(&deleted_path, populate)?);
+    let deleted_count = deleted.count_trues();
+
+    Ok(AppendableMmapInt8DenseVectorStorage {
+        vectors,
+        params,
+        deleted,
+        distance,
+        deleted_count,
+        write_throttle: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::generic_consts::Random;
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn open_storage(path: &Path) -> AppendableMmapInt8DenseVectorStorage {
+        open_appendable_memmap_int8_vector_storage_impl(
+            path,
+            4,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_int8_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = open_storage(dir.path());
+
+        // Float vectors are quantized on insertion
+        let vector = vec![-1.0, 0.0, 0.5, 1.0];
+        storage
+            .insert_vector(0, VectorRef::from(&vector), &hw_counter)
+            .unwrap();
+        let dense = storage.get_dense::<Random>(0);
+        let params = storage.get_params::<Random>(0).unwrap();
+        for (stored, original) in dense.iter().zip(&vector) {
+            assert!((stored - original).abs() <= params.scale / 2.0 + f32::EPSILON);
+        }
+
+        // Constant vectors are stored exactly
+        let constant = vec![3.0; 4];
+        storage
+            .insert_vector(1, VectorRef::from(&constant), &hw_counter)
+            .unwrap();
+        assert_eq!(storage.get_dense::<Random>(1).as_ref(), constant.as_slice());
+
+        // Pre-quantized vectors are dequantized with their own params
+        let params = Int8QuantizationParams {
+            scale: 0.5,
+            zero_point: 10.0,
+        };
+        storage
+            .insert_quantized(2, &[10, 12, 8, -128], params, &hw_counter)
+            .unwrap();
+        assert_eq!(
+            storage.get_dense::<Random>(2).as_ref(),
+            &[0.0, 1.0, -1.0, -69.0],
+        );
+
+        assert!(
+            storage
+                .insert_quantized(3, &[1, 2, 3], params, &hw_counter)
+                .is_err()
+        );
+        let zero_scale = Int8QuantizationParams {
+            scale: 0.0,
+            zero_point: 0.0,
+        };
+        assert!(
+            storage
+                .insert_quantized(3, &[1, 2, 3, 4], zero_scale, &hw_counter)
+                .is_err()
+        );
+
+        storage.delete_vector(1).unwrap();
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let storage = open_storage(dir.path());
+        assert_eq!(storage.total_vector_count(), 3);
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert_eq!(storage.get_params::<Random>(2), Some(params));
+        assert_eq!(
+            storage.get_quantized::<Random>(2).unwrap().as_ref(),
+            &[10, 12, 8, -128],
+        );
+    }
+}
@@ -8,7 +8,9 @@ use common::bitvec::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::fs::clear_disk_cache;
 use common::generic_consts::AccessPattern;
-use common::mmap;
+#[cfg(target_os = "linux")]
+use common::generic_consts::Random;
+use common::mmap::{self, AdviceSetting};
 use common::types::PointOffsetType;
 use common::universal_io::{MmapFile, UniversalRead};
 use fs_err as fs;
@@ -22,7 +24,11 @@ use crate::data_types::vectors::VectorRef;
 use crate::types::{Distance, VectorStorageDatatype};
 #[cfg(target_os = "linux")]
 use crate::vector_storage::common::get_async_scorer;
+use crate::vector_storage::dense::hot_vectors_cache::HotVectorsCache;
 use crate::vector_storage::dense::immutable_dense_vectors::ImmutableDenseVectors;
+#[cfg(target_os = "linux")]
+use crate::vector_storage::query_scorer::is_read_with_prefetch_efficient;
+use crate::vector_storage::read_stats::VectorStorageStats;
 use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 const VECTORS_PATH: &str = "matrix.dat";
@@ -44,6 +50,13 @@ where
     deleted_path: PathBuf,
     vectors: Option<ImmutableDenseVectors<T, S>>,
     distance: Distance,
+    /// Advice for the vectors memmap, kept to reopen it after `update_from`
+    madvise: AdviceSetting,
+    /// Frequently read vectors, kept in RAM. Only used if vectors are not populated.
+    hot_vectors: Option<HotVectorsCache<T>>,
+    /// Vectors file for concurrent `pread`s of batches, if io_uring is not available
+    #[cfg(target_os = "linux")]
+    pread_file: Option<File>,
 }
 
 impl<T, S> DenseVectorStorageImpl<T, S>
@@ -65,12 +78,35 @@ where
         clear_disk_cache(&self.deleted_path)?;
         Ok(())
     }
+
+    /// Number of bytes of vectors, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors
+            .as_ref()
+            .map_or(0, ImmutableDenseVectors::ram_usage_bytes)
+    }
+
+    /// Number of bytes of vectors, kept in the hot vectors cache
+    pub fn cache_usage_bytes(&self) -> usize {
+        self.hot_vectors
+            .as_ref()
+            .map_or(0, HotVectorsCache::cached_bytes)
+    }
+
+    /// Reads of vectors from the file, vectors served from the hot vectors cache are not counted
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.vectors
+            .as_ref()
+            .map(ImmutableDenseVectors::read_stats)
+            .unwrap_or_default()
+    }
 }
 
 pub fn open_dense_vector_storage(
     path: &Path,
     dim: usize,
     distance: Distance,
+    madvise: AdviceSetting,
     populate: bool,
 ) -> OperationResult<VectorStorageEnum> {
     #[cfg(target_os = "linux")]
@@ -79,13 +115,14 @@ pub fn open_dense_vector_storage(
     #[cfg(not(target_os = "linux"))]
     let with_uring = false;
 
-    open_dense_vector_storage_with_uring(path, dim, distance, populate, with_uring)
+    open_dense_vector_storage_with_uring(path, dim, distance, madvise, populate, with_uring)
 }
 
 pub fn open_dense_vector_storage_with_uring(
     path: &Path,
     dim: usize,
     distance: Distance,
+    madvise: AdviceSetting,
     populate: bool,
     with_uring: bool,
 ) -> OperationResult<VectorStorageEnum> {
@@ -94,17 +131,20 @@ pub fn open_dense_vector_storage_with_uring(
 
     #[cfg(target_os = "linux")]
     if with_uring {
-        match open_dense_vector_storage_impl(path, dim, distance, populate) {
+        match open_dense_vector_storage_impl(path, dim, distance, madvise, populate) {
             Ok(uring_storage) => {
                 return Ok(VectorStorageEnum::DenseUring(Box::new(uring_storage)));
             }
             Err(err) => {
                 log::error!("failed to open io_uring based vector storage: {err}");
+                let pread_storage =
+                    open_dense_vector_storage_pread(path, dim, distance, madvise, populate)?;
+                return Ok(VectorStorageEnum::DenseMemmap(Box::new(pread_storage)));
             }
         }
     }
 
-    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, populate)?;
+    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
     Ok(VectorStorageEnum::DenseMemmap(Box::new(mmap_storage)))
 }
 
@@ -112,21 +152,25 @@ pub fn open_dense_vector_storage_half(
     path: &Path,
     dim: usize,
     distance: Distance,
+    madvise: AdviceSetting,
     populate: bool,
 ) -> OperationResult<VectorStorageEnum> {
     #[cfg(target_os = "linux")]
     if get_async_scorer() {
-        match open_dense_vector_storage_impl(path, dim, distance, populate) {
+        match open_dense_vector_storage_impl(path, dim, distance, madvise, populate) {
             Ok(uring_storage) => {
                 return Ok(VectorStorageEnum::DenseUringHalf(Box::new(uring_storage)));
             }
             Err(err) => {
                 log::error!("failed to open io_uring based vector storage: {err}");
+                let pread_storage =
+                    open_dense_vector_storage_pread(path, dim, distance, madvise, populate)?;
+                return Ok(VectorStorageEnum::DenseMemmapHalf(Box::new(pread_storage)));
             }
         }
     }
 
-    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, populate)?;
+    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
     Ok(VectorStorageEnum::DenseMemmapHalf(Box::new(mmap_storage)))
 }
 
@@ -134,28 +178,57 @@ pub fn open_dense_vector_storage_byte(
     path: &Path,
     dim: usize,
     distance: Distance,
+    madvise: AdviceSetting,
     populate: bool,
 ) -> OperationResult<VectorStorageEnum> {
     #[cfg(target_os = "linux")]
     if get_async_scorer() {
-        match open_dense_vector_storage_impl(path, dim, distance, populate) {
+        match open_dense_vector_storage_impl(path, dim, distance, madvise, populate) {
             Ok(uring_storage) => {
                 return Ok(VectorStorageEnum::DenseUringByte(Box::new(uring_storage)));
             }
             Err(err) => {
                 log::error!("failed to open io_uring based vector storage: {err}");
+                let pread_storage =
+                    open_dense_vector_storage_pread(path, dim, distance, madvise, populate)?;
+                return Ok(VectorStorageEnum::DenseMemmapByte(Box::new(pread_storage)));
             }
         }
     }
 
-    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, populate)?;
+    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
     Ok(VectorStorageEnum::DenseMemmapByte(Box::new(mmap_storage)))
 }
 
+pub fn open_dense_vector_storage_double(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
+    Ok(VectorStorageEnum::DenseMemmapDouble(Box::new(mmap_storage)))
+}
+
+pub fn open_dense_vector_storage_bfloat16(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let mmap_storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
+    Ok(VectorStorageEnum::DenseMemmapBfloat16(Box::new(
+        mmap_storage,
+    )))
+}
+
 fn open_dense_vector_storage_impl<T, S>(
     path: &Path,
     dim: usize,
     distance: Distance,
+    madvise: AdviceSetting,
     populate: bool,
 ) -> OperationResult<DenseVectorStorageImpl<T, S>>
 where
@@ -167,17 +240,42 @@ where
     let vectors_path = path.join(VECTORS_PATH);
     let deleted_path = path.join(DELETED_PATH);
 
-    let vectors = ImmutableDenseVectors::open(&vectors_path, &deleted_path, dim, populate)?;
+    let vectors =
+        ImmutableDenseVectors::open(&vectors_path, &deleted_path, dim, madvise, populate)?;
     let storage = DenseVectorStorageImpl {
         vectors_path,
         deleted_path,
         vectors: Some(vectors),
         distance,
+        madvise,
+        hot_vectors: (!populate)
+            .then(|| HotVectorsCache::new_if_enabled(dim))
+            .flatten(),
+        #[cfg(target_os = "linux")]
+        pread_file: None,
     };
 
     Ok(storage)
 }
 
+/// Open mmap based storage, which reads batches of vectors with concurrent `pread`s.
+/// Fallback for io_uring based storage, if io_uring is not available.
+#[cfg(target_os = "linux")]
+fn open_dense_vector_storage_pread<T: PrimitiveVectorElement>(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    madvise: AdviceSetting,
+    populate: bool,
+) -> OperationResult<DenseVectorStorageImpl<T>> {
+    let mut storage = open_dense_vector_storage_impl(path, dim, distance, madvise, populate)?;
+    // Populated vectors are read from RAM, reading them from the file would only be slower
+    if !populate {
+        storage.pread_file = Some(File::open(&storage.vectors_path)?);
+    }
+    Ok(storage)
+}
+
 impl<T, S> DenseVectorStorageImpl<T, S>
 where
     T: PrimitiveVectorElement,
@@ -186,6 +284,49 @@ where
     pub fn get_mmap_vectors(&self) -> &ImmutableDenseVectors<T, S> {
         self.vectors.as_ref().unwrap()
     }
+
+    fn try_get_mmap_vectors(&self) -> OperationResult<&ImmutableDenseVectors<T, S>> {
+        self.vectors
+            .as_ref()
+            .ok_or_else(|| OperationError::service_error("dense vector storage is not loaded"))
+    }
+
+    /// Read vectors of `keys` from the file in a single batch.
+    ///
+    /// Random reads are spread over the `pread` thread pool, if io_uring is not available.
+    /// Otherwise, the batch is read by the underlying storage, in one submission for io_uring.
+    fn read_batch<P: AccessPattern>(
+        &self,
+        vectors: &ImmutableDenseVectors<T, S>,
+        keys: &[PointOffsetType],
+        mut f: impl FnMut(usize, &[T]),
+    ) -> OperationResult<()> {
+        #[cfg(target_os = "linux")]
+        if let Some(pread_file) = &self.pread_file
+            && !P::IS_SEQUENTIAL
+            && !is_read_with_prefetch_efficient(keys)
+        {
+            return vectors.read_vectors_pread(pread_file, keys, f);
+        }
+
+        vectors.read_vectors_async::<P>(keys, |idx, _key, vector| f(idx, vector))
+    }
+
+    /// Read vector, serving it from the hot vectors cache if possible.
+    /// Sequential reads bypass the cache, so that full scans do not evict hot vectors.
+    fn get_dense_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<Cow<'_, [T]>> {
+        let vectors = self.vectors.as_ref().unwrap();
+        let Some(hot_vectors) = self.hot_vectors.as_ref().filter(|_| !P::IS_SEQUENTIAL) else {
+            return vectors.get_vector_opt::<P>(key);
+        };
+
+        if let Some(vector) = hot_vectors.get(key) {
+            return Some(Cow::Owned(vector));
+        }
+        let vector = vectors.get_vector_opt::<P>(key)?;
+        hot_vectors.record_miss(key, &vector);
+        Some(vector)
+    }
 }
 
 impl<T, S> DenseVectorStorage<T> for DenseVectorStorageImpl<T, S>
@@ -198,16 +339,51 @@ where
     }
 
     fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> Cow<'_, [T]> {
-        self.vectors
-            .as_ref()
-            .unwrap()
-            .get_vector_opt::<P>(key)
+        self.get_dense_opt::<P>(key)
             .unwrap_or_else(|| panic!("vector not found: {key}"))
     }
 
-    fn for_each_in_dense_batch<F: FnMut(usize, &[T])>(&self, keys: &[PointOffsetType], f: F) {
+    fn get_dense_batch<P: AccessPattern>(
+        &self,
+        keys: &[PointOffsetType],
+        mut f: impl FnMut(usize, &[T]),
+    ) -> OperationResult<()> {
+        let vectors = self.try_get_mmap_vectors()?;
+        let Some(hot_vectors) = self.hot_vectors.as_ref().filter(|_| !P::IS_SEQUENTIAL) else {
+            return self.read_batch::<P>(vectors, keys, f);
+        };
+
+        let (missed_keys, missed_positions) = serve_hot_vectors(hot_vectors, keys, &mut f);
+        self.read_batch::<P>(vectors, &missed_keys, |idx, vector| {
+            hot_vectors.record_miss(missed_keys[idx], vector);
+            f(missed_positions[idx], vector);
+        })
+    }
+
+    fn for_each_in_dense_batch<F: FnMut(usize, &[T])>(&self, keys: &[PointOffsetType], mut f: F) {
         let mmap_store = self.vectors.as_ref().unwrap();
-        mmap_store.for_each_in_batch(keys, f);
+        let read_batch = |keys: &[PointOffsetType], f: &mut dyn FnMut(usize, &[T])| {
+            #[cfg(target_os = "linux")]
+            if self.pread_file.is_some() {
+                self.read_batch::<Random>(mmap_store, keys, f)
+                    .expect("vector read from storage failed");
+                return;
+            }
+
+            mmap_store.for_each_in_batch(keys, f);
+        };
+
+        let Some(hot_vectors) = &self.hot_vectors else {
+            read_batch(keys, &mut f);
+            return;
+        };
+
+        // Serve hot vectors from RAM, read the rest from disk in a single batch
+        let (missed_keys, missed_positions) = serve_hot_vectors(hot_vectors, keys, &mut f);
+        read_batch(&missed_keys, &mut |idx, vector| {
+            hot_vectors.record_miss(missed_keys[idx], vector);
+            f(missed_positions[idx], vector);
+        });
     }
 }
 
@@ -233,10 +409,7 @@ where
     }
 
     fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
-        self.vectors
-            .as_ref()
-            .unwrap()
-            .get_vector_opt::<P>(key)
+        self.get_dense_opt::<P>(key)
             .map(|vector| T::slice_to_float_cow(vector).into())
             .expect("Vector not found")
     }
@@ -259,10 +432,7 @@ where
     }
 
     fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
-        self.vectors
-            .as_ref()
-            .unwrap()
-            .get_vector_opt::<P>(key)
+        self.get_dense_opt::<P>(key)
             .map(|vector| T::slice_to_float_cow(vector).into())
     }
 
@@ -314,6 +484,7 @@ where
             &self.vectors_path,
             &self.deleted_path,
             dim,
+            self.madvise,
             false, // No need to populate
         )?);
 
@@ -368,6 +539,28 @@ where
     }
 }
 
+/// Call `f` for vectors of `keys`, which are cached in `hot_vectors`.
+///
+/// Returns keys of vectors, missing in the cache, and their positions in `keys`.
+fn serve_hot_vectors<T: Copy>(
+    hot_vectors: &HotVectorsCache<T>,
+    keys: &[PointOffsetType],
+    mut f: impl FnMut(usize, &[T]),
+) -> (Vec<PointOffsetType>, Vec<usize>) {
+    let mut missed_keys = Vec::with_capacity(keys.len());
+    let mut missed_positions = Vec::with_capacity(keys.len());
+    for (idx, &key) in keys.iter().enumerate() {
+        if hot_vectors
+            .with_cached(key, |vector| f(idx, vector))
+            .is_none()
+        {
+            missed_keys.push(key);
+            missed_positions.push(idx);
+        }
+    }
+    (missed_keys, missed_positions)
+}
+
 /// Open a file shortly for appending
 fn open_append<P: AsRef<Path>>(path: P) -> io::Result<File> {
     let path = path.as_ref().to_path_buf();
@@ -409,7 +602,9 @@ mod tests {
             vec![1.0, 1.0, 0.0, 1.0],
             vec![1.0, 0.0, 0.0, 0.0],
         ];
-        let mut storage = open_dense_vector_storage(dir.path(), 4, Distance::Dot, false).unwrap();
+        let mut storage =
+            open_dense_vector_storage(dir.path(), 4, Distance::Dot, AdviceSetting::Global, false)
+                .unwrap();
         let mut id_tracker = create_id_tracker_fixture(points.len());
 
         // Assert this storage lists both the vector and deleted file
@@ -527,7 +722,9 @@ mod tests {
         ];
         let delete_mask = [false, false, true, true, false];
         let id_tracker = create_id_tracker_fixture(points.len());
-        let mut storage = open_dense_vector_storage(dir.path(), 4, Distance::Dot, false).unwrap();
+        let mut storage =
+            open_dense_vector_storage(dir.path(), 4, Distance::Dot, AdviceSetting::Global, false)
+                .unwrap();
 
         let hw_counter = HardwareCounterCell::new();
 
@@ -653,7 +850,9 @@ mod tests {
             vec![1.0, 0.0, 0.0, 0.0],
         ];
         let delete_mask = [false, false, true, true, false];
-        let mut storage = open_dense_vector_storage(dir.path(), 4, Distance::Dot, false).unwrap();
+        let mut storage =
+            open_dense_vector_storage(dir.path(), 4, Distance::Dot, AdviceSetting::Global, false)
+                .unwrap();
         let id_tracker = create_id_tracker_fixture(points.len());
 
         let hw_counter = HardwareCounterCell::new();
@@ -727,7 +926,9 @@ mod tests {
             vec![1.0, 1.0, 0.0, 1.0],
             vec![1.0, 0.0, 0.0, 0.0],
         ];
-        let mut storage = open_dense_vector_storage(dir.path(), 4, Distance::Dot, false).unwrap();
+        let mut storage =
+            open_dense_vector_storage(dir.path(), 4, Distance::Dot, AdviceSetting::Global, false)
+                .unwrap();
         let id_tracker = create_id_tracker_fixture(points.len());
 
         let hw_counter = HardwareCounterCell::new();
@@ -800,7 +1001,9 @@ mod tests {
             vec![1.0, 1.0, 0.0, 1.0],
             vec![1.0, 0.0, 0.0, 0.0],
         ];
-        let mut storage = open_dense_vector_storage(dir.path(), 4, Distance::Dot, false).unwrap();
+        let mut storage =
+            open_dense_vector_storage(dir.path(), 4, Distance::Dot, AdviceSetting::Global, false)
+                .unwrap();
 
         let hw_counter = HardwareCounterCell::new();
 
@@ -826,6 +1029,7 @@ mod tests {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into();
 
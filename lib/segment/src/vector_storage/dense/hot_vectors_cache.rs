@@ -0,0 +1,341 @@
+//! Hot/cold tiering of on-disk dense vectors.
+//!
+//! Every random read of a vector from an on-disk storage increments its access counter. Vectors,
+//! read at least [`HotVectorsCacheConfig::promote_threshold`] times, are promoted into an in-RAM
+//! cache, so that further reads do not hit the disk. Once the cache is full, cold vectors are
+//! evicted with the CLOCK algorithm: the clock hand sweeps over cached vectors, clearing their
+//! reference counters, and evicts the first vector which was not read since the previous sweep.
+
+use std::mem;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ahash::AHashMap;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::common::anonymize::Anonymize;
+
+const DEFAULT_PROMOTE_THRESHOLD: u8 = 2;
+
+/// Maximum value of the reference counter of a cached vector.
+/// Vectors, read more often, survive more sweeps of the clock hand.
+const MAX_REFERENCED: u8 = 3;
+
+/// Minimum number of tracked access counters of vectors, which are not cached
+const MIN_TRACKED_ACCESSES: usize = 1024;
+
+static HOT_VECTORS_CACHE_CONFIG: OnceLock<HotVectorsCacheConfig> = OnceLock::new();
+
+static STATS: CacheStats = CacheStats::new();
+
+/// Configuration of the in-RAM cache of frequently read on-disk dense vectors
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct HotVectorsCacheConfig {
+    /// Maximum size of cached vectors of each on-disk dense vector storage, in megabytes
+    pub max_size_mb: usize,
+    /// Number of reads, after which a vector is promoted into the cache
+    #[serde(default = "default_promote_threshold")]
+    pub promote_threshold: u8,
+}
+
+const fn default_promote_threshold() -> u8 {
+    DEFAULT_PROMOTE_THRESHOLD
+}
+
+#[derive(Serialize, Clone, Debug, Default, JsonSchema, Anonymize)]
+#[anonymize(false)]
+pub struct HotVectorsCacheTelemetry {
+    /// Number of vector reads, served from the cache
+    pub hits: usize,
+    /// Number of vector reads, served from disk
+    pub misses: usize,
+    /// Number of vectors, promoted into the cache
+    pub promotions: usize,
+    /// Number of cold vectors, evicted from the cache
+    pub evictions: usize,
+    /// Total size of vectors, currently cached by all storages
+    pub cached_bytes: usize,
+}
+
+#[derive(Debug)]
+struct CacheStats {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    promotions: AtomicUsize,
+    evictions: AtomicUsize,
+    cached_bytes: AtomicUsize,
+}
+
+impl CacheStats {
+    const fn new() -> Self {
+        Self {
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+            promotions: AtomicUsize::new(0),
+            evictions: AtomicUsize::new(0),
+            cached_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    fn telemetry(&self) -> HotVectorsCacheTelemetry {
+        let Self {
+            hits,
+            misses,
+            promotions,
+            evictions,
+            cached_bytes,
+        } = self;
+
+        HotVectorsCacheTelemetry {
+            hits: hits.load(Ordering::Relaxed),
+            misses: misses.load(Ordering::Relaxed),
+            promotions: promotions.load(Ordering::Relaxed),
+            evictions: evictions.load(Ordering::Relaxed),
+            cached_bytes: cached_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Enable caching of hot vectors of on-disk dense vector storages.
+///
+/// Must only be called once at startup, otherwise a warning is logged and `config` is ignored.
+pub fn init_hot_vectors_cache(config: HotVectorsCacheConfig) {
+    if HOT_VECTORS_CACHE_CONFIG.set(config).is_err() {
+        log::warn!("Hot vectors cache is already initialized, ignoring new configuration");
+    }
+}
+
+pub fn hot_vectors_cache_telemetry() -> Option<HotVectorsCacheTelemetry> {
+    HOT_VECTORS_CACHE_CONFIG.get()?;
+    Some(STATS.telemetry())
+}
+
+#[derive(Debug)]
+struct CacheSlot {
+    key: PointOffsetType,
+    /// Number of reads since the last sweep of the clock hand, up to [`MAX_REFERENCED`]
+    referenced: u8,
+}
+
+#[derive(Debug)]
+struct CacheState<T> {
+    /// Slot of every cached vector
+    slots_by_key: AHashMap<PointOffsetType, usize>,
+    slots: Vec<CacheSlot>,
+    /// Vectors of all slots, `dim` elements each
+    data: Vec<T>,
+    clock_hand: usize,
+    /// Access counters of vectors, which are not cached yet
+    access_counts: AHashMap<PointOffsetType, u8>,
+}
+
+/// In-RAM cache of frequently read vectors of a single on-disk storage
+#[derive(Debug)]
+pub struct HotVectorsCache<T> {
+    dim: usize,
+    /// Maximum number of cached vectors
+    capacity: usize,
+    promote_threshold: u8,
+    state: Mutex<CacheState<T>>,
+}
+
+impl<T: Copy> HotVectorsCache<T> {
+    /// Create cache for a storage of `dim`-dimensional vectors, if caching is enabled
+    pub fn new_if_enabled(dim: usize) -> Option<Self> {
+        Self::new(dim, *HOT_VECTORS_CACHE_CONFIG.get()?)
+    }
+
+    fn new(dim: usize, config: HotVectorsCacheConfig) -> Option<Self> {
+        let vector_size_bytes = dim * mem::size_of::<T>();
+        let capacity = (config.max_size_mb * 1024 * 1024)
+            .checked_div(vector_size_bytes)
+            .unwrap_or(0);
+        if capacity == 0 {
+            return None;
+        }
+
+        Some(Self {
+            dim,
+            capacity,
+            promote_threshold: config.promote_threshold.max(1),
+            state: Mutex::new(CacheState {
+                slots_by_key: AHashMap::new(),
+                slots: Vec::new(),
+                data: Vec::new(),
+                clock_hand: 0,
+                access_counts: AHashMap::new(),
+            }),
+        })
+    }
+
+    fn vector_size_bytes(&self) -> usize {
+        self.dim * mem::size_of::<T>()
+    }
+
+    /// Call `f` with the cached vector, if it is cached
+    pub fn with_cached<R>(&self, key: PointOffsetType, f: impl FnOnce(&[T]) -> R) -> Option<R> {
+        let mut state = self.state.lock();
+        let Some(&slot_idx) = state.slots_by_key.get(&key) else {
+            STATS.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        STATS.hits.fetch_add(1, Ordering::Relaxed);
+
+        let slot = &mut state.slots[slot_idx];
+        slot.referenced = (slot.referenced + 1).min(MAX_REFERENCED);
+
+        let offset = slot_idx * self.dim;
+        Some(f(&state.data[offset..offset + self.dim]))
+    }
+
+    pub fn get(&self, key: PointOffsetType) -> Option<Vec<T>> {
+        self.with_cached(key, <[T]>::to_vec)
+    }
+
+    /// Count a read of `vector` from disk, promoting it into the cache once it is hot
+    pub fn record_miss(&self, key: PointOffsetType, vector: &[T]) {
+        debug_assert_eq!(vector.len(), self.dim);
+        let mut state = self.state.lock();
+        // Concurrent reader may have promoted the vector already
+        if state.slots_by_key.contains_key(&key) {
+            return;
+        }
+
+        let access_count = state.access_counts.entry(key).or_default();
+        *access_count = access_count.saturating_add(1);
+        if *access_count < self.promote_threshold {
+            self.age_access_counts(&mut state);
+            return;
+        }
+        state.access_counts.remove(&key);
+
+        let slot_idx = if state.slots.len() < self.capacity {
+            state.slots.push(CacheSlot { key, referenced: 1 });
+            state.data.extend_from_slice(vector);
+            STATS
+                .cached_bytes
+                .fetch_add(self.vector_size_bytes(), Ordering::Relaxed);
+            state.slots.len() - 1
+        } else {
+            let slot_idx = Self::evict(&mut state);
+            state.slots[slot_idx] = CacheSlot { key, referenced: 1 };
+            let offset = slot_idx * self.dim;
+            state.data[offset..offset + self.dim].copy_from_slice(vector);
+            slot_idx
+        };
+        state.slots_by_key.insert(key, slot_idx);
+        STATS.promotions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Find a cold slot with the clock hand and evict its vector
+    fn evict(state: &mut CacheState<T>) -> usize {
+        loop {
+            let slot_idx = state.clock_hand;
+            state.clock_hand = (state.clock_hand + 1) % state.slots.len();
+
+            let slot = &mut state.slots[slot_idx];
+            if slot.referenced > 0 {
+                slot.referenced -= 1;
+                continue;
+            }
+
+            let evicted_key = slot.key;
+            state.slots_by_key.remove(&evicted_key);
+            STATS.evictions.fetch_add(1, Ordering::Relaxed);
+            return slot_idx;
+        }
+    }
+
+    /// Halve access counters once there are too many of them, forgetting vectors which were
+    /// read only once. Keeps memory of the counters bounded.
+    fn age_access_counts(&self, state: &mut CacheState<T>) {
+        if state.access_counts.len() <= MIN_TRACKED_ACCESSES.max(self.capacity * 4) {
+            return;
+        }
+        state.access_counts.retain(|_, access_count| {
+            *access_count /= 2;
+            *access_count > 0
+        });
+    }
+
+    /// Number of currently cached vectors
+    pub fn len(&self) -> usize {
+        self.state.lock().slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Size of currently cached vectors
+    pub fn cached_bytes(&self) -> usize {
+        self.len() * self.vector_size_bytes()
+    }
+}
+
+impl<T> Drop for HotVectorsCache<T> {
+    fn drop(&mut self) {
+        let cached_bytes = self.state.get_mut().slots.len() * self.dim * mem::size_of::<T>();
+        STATS
+            .cached_bytes
+            .fetch_sub(cached_bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(key: PointOffsetType) -> Vec<f32> {
+        vec![key as f32; 4]
+    }
+
+    #[test]
+    fn test_hot_vectors_promotion_and_eviction() {
+        let mut cache = HotVectorsCache::<f32>::new(
+            4,
+            HotVectorsCacheConfig {
+                max_size_mb: 1,
+                promote_threshold: 2,
+            },
+        )
+        .unwrap();
+        // Keep the test fast by limiting the cache to 2 vectors
+        cache.capacity = 2;
+
+        // Vector is promoted on the second read
+        assert_eq!(cache.get(1), None);
+        cache.record_miss(1, &vector(1));
+        assert_eq!(cache.get(1), None);
+        cache.record_miss(1, &vector(1));
+        assert_eq!(cache.get(1), Some(vector(1)));
+
+        cache.record_miss(2, &vector(2));
+        cache.record_miss(2, &vector(2));
+        assert_eq!(cache.len(), 2);
+
+        // Vector 1 is read more often, so vector 2 is evicted
+        for _ in 0..3 {
+            cache.get(1).unwrap();
+        }
+        cache.record_miss(3, &vector(3));
+        cache.record_miss(3, &vector(3));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(1), Some(vector(1)));
+        assert_eq!(cache.get(2), None);
+        assert_eq!(cache.get(3), Some(vector(3)));
+        assert_eq!(cache.cached_bytes(), 2 * 4 * mem::size_of::<f32>());
+    }
+
+    #[test]
+    fn test_cache_disabled_for_empty_size() {
+        let config = HotVectorsCacheConfig {
+            max_size_mb: 0,
+            promote_threshold: 1,
+        };
+        assert!(HotVectorsCache::<f32>::new(4, config).is_none());
+    }
+}
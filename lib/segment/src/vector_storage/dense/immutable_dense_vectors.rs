@@ -15,10 +15,14 @@ use common::universal_io::{
 use fs_err::{File, OpenOptions};
 
 use crate::common::error_logging::LogError;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+#[cfg(target_os = "linux")]
+use crate::vector_storage::dense::pread_pool;
+use crate::vector_storage::memory_budget::{MemoryBudget, MemoryReservation};
 use crate::vector_storage::query_scorer::is_read_with_prefetch_efficient;
+use crate::vector_storage::read_stats::{VectorStorageReadStats, VectorStorageStats};
 
 const HEADER_SIZE: usize = 4;
 const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
@@ -39,6 +43,9 @@ where
     deleted: MmapBitSlice,
     /// Current number of deleted vectors.
     pub deleted_count: usize,
+    /// Memory, occupied by vectors, if they are populated into RAM
+    memory_reservation: Option<MemoryReservation>,
+    read_stats: VectorStorageReadStats,
 }
 
 impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S> {
@@ -46,6 +53,7 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
         vectors_path: &Path,
         deleted_path: &Path,
         dim: usize,
+        madvise: AdviceSetting,
         populate: bool,
     ) -> OperationResult<Self> {
         // Allocate/open vectors file
@@ -60,7 +68,7 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
             need_sequential: true,
             disk_parallel: None,
             populate: Some(populate),
-            advice: None,
+            advice: Some(madvise),
             prevent_caching: None,
         };
         let storage = TypedStorage::open(vectors_path, options).map_err(|e| {
@@ -87,19 +95,34 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
         let deleted = MmapBitSlice::try_from(deleted_mmap, deleted_mmap_data_start())?;
         let deleted_count = deleted.count_ones();
 
+        let memory_reservation = populate.then(|| MemoryBudget::global().reserve(file_len));
+
         Ok(Self {
             dim,
             num_vectors,
             storage,
             deleted,
             deleted_count,
+            memory_reservation,
+            read_stats: VectorStorageReadStats::default(),
         })
     }
 
+    /// Number of bytes of populated vectors, 0 if vectors are not kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.memory_reservation
+            .as_ref()
+            .map_or(0, MemoryReservation::bytes)
+    }
+
     pub fn flusher(&self) -> MmapFlusher {
         self.deleted.flusher()
     }
 
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.read_stats.stats()
+    }
+
     /// Returns the byte offset within the file at which the vector for `key` begins.
     ///
     /// File layout:
@@ -123,6 +146,8 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
             length: self.dim as u64,
         };
 
+        self.read_stats
+            .record_read(P::IS_SEQUENTIAL, self.dim * size_of::<T>());
         self.storage
             .read::<P>(range)
             .expect("vector read from storage failed")
@@ -147,13 +172,16 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
         // than fetching and scoring in a single loop.
 
         let mut vectors_buffer = [const { MaybeUninit::uninit() }; VECTOR_READ_BATCH_SIZE];
-        let vectors = if is_read_with_prefetch_efficient(keys) {
-            let iter = keys.iter().map(|key| self.get_vector::<Sequential>(*key));
-            maybe_uninit_fill_from(&mut vectors_buffer, iter).0
-        } else {
-            let iter = keys.iter().map(|key| self.get_vector::<Random>(*key));
-            maybe_uninit_fill_from(&mut vectors_buffer, iter).0
-        };
+        let vectors_buffer = &mut vectors_buffer;
+        let vectors = self.read_stats.measure_batch(move || {
+            if is_read_with_prefetch_efficient(keys) {
+                let iter = keys.iter().map(|key| self.get_vector::<Sequential>(*key));
+                maybe_uninit_fill_from(vectors_buffer, iter).0
+            } else {
+                let iter = keys.iter().map(|key| self.get_vector::<Random>(*key));
+                maybe_uninit_fill_from(vectors_buffer, iter).0
+            }
+        });
 
         for (i, vec) in vectors.iter().enumerate() {
             f(i, vec);
@@ -197,15 +225,51 @@ impl<T: PrimitiveVectorElement, S: UniversalRead<T>> ImmutableDenseVectors<T, S>
             length: self.dim as _,
         });
 
-        self.storage.read_batch::<P>(ranges, |idx, vector| {
-            let point = points.get(idx).copied().expect("point ID tracked");
-            callback(idx, point, vector);
-            Ok(())
+        self.read_stats.measure_batch(|| {
+            self.storage.read_batch::<P>(ranges, |idx, vector| {
+                self.read_stats
+                    .record_read(P::IS_SEQUENTIAL, vector_size_bytes);
+                let point = points.get(idx).copied().expect("point ID tracked");
+                callback(idx, point, vector);
+                Ok(())
+            })
         })?;
 
         Ok(())
     }
 
+    /// Read vectors with `pread` from `file`, concurrently on the pread thread pool.
+    ///
+    /// Used instead of [`Self::read_vectors_async`], if io_uring is not available.
+    #[cfg(target_os = "linux")]
+    pub fn read_vectors_pread(
+        &self,
+        file: &File,
+        points: &[PointOffsetType],
+        mut callback: impl FnMut(usize, &[T]),
+    ) -> OperationResult<()> {
+        let byte_offsets: Vec<u64> = points
+            .iter()
+            .map(|&point| {
+                self.data_offset(point)
+                    .map(|offset| offset as u64)
+                    .ok_or_else(|| {
+                        OperationError::service_error(format!("vector not found: {point}"))
+                    })
+            })
+            .collect::<OperationResult<_>>()?;
+
+        let vectors = pread_pool::pread_batch::<T>(file.file(), &byte_offsets, self.dim)?;
+
+        let vector_size_bytes = size_of::<T>() * self.dim;
+        for (idx, vector) in vectors.chunks_exact(self.dim).enumerate() {
+            self.read_stats.record_read(false, vector_size_bytes);
+            callback(idx, vector);
+        }
+
+        Ok(())
+    }
+
     pub fn populate(&self) {
         if let Err(err) = self.storage.populate() {
             log::error!("Failed to populate vector storage: {err}");
@@ -1,6 +1,12 @@
 pub mod appendable_dense_vector_storage;
+pub mod appendable_int4_dense_vector_storage;
+pub mod appendable_int8_dense_vector_storage;
 pub mod dense_vector_storage;
+pub mod hot_vectors_cache;
 pub mod immutable_dense_vectors;
+#[cfg(target_os = "linux")]
+pub mod pread_pool;
+pub mod quantized_dense_vector_storage;
 #[cfg(feature = "rocksdb")]
 pub mod simple_dense_vector_storage;
 pub mod volatile_dense_vector_storage;
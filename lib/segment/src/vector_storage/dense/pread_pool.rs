@@ -0,0 +1,92 @@
+//! Concurrent reads of vectors with `pread`, used for batched reads if io_uring is unavailable.
+//!
+//! Reads of a batch are spread over a dedicated thread pool, so that vectors missing in the page
+//! cache are requested from the disk concurrently, similar to a single io_uring submission.
+
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt as _;
+use std::sync::OnceLock;
+
+use rayon::ThreadPool;
+use rayon::prelude::*;
+
+/// Number of reads in flight, same as the io_uring queue length.
+/// Enough to read all neighbors of an HNSW node concurrently.
+const PREAD_POOL_THREADS: usize = 64;
+
+static PREAD_POOL: OnceLock<Option<ThreadPool>> = OnceLock::new();
+
+fn pread_pool() -> Option<&'static ThreadPool> {
+    PREAD_POOL
+        .get_or_init(|| {
+            rayon::ThreadPoolBuilder::new()
+                .thread_name(|idx| format!("vector-pread-{idx}"))
+                .num_threads(PREAD_POOL_THREADS)
+                .build()
+                .inspect_err(|err| {
+                    log::error!("Failed to create pread pool, reading in place: {err}");
+                })
+                .ok()
+        })
+        .as_ref()
+}
+
+/// Read `len` elements at each of `byte_offsets` of `file`.
+///
+/// Returns elements of all reads, in the order of `byte_offsets`.
+pub fn pread_batch<T: bytemuck::Pod + Send>(
+    file: &File,
+    byte_offsets: &[u64],
+    len: usize,
+) -> io::Result<Vec<T>> {
+    let mut buffer = vec![T::zeroed(); byte_offsets.len() * len];
+    if buffer.is_empty() {
+        return Ok(buffer);
+    }
+
+    let read = |(items, &byte_offset): (&mut [T], &u64)| {
+        file.read_exact_at(bytemuck::cast_slice_mut(items), byte_offset)
+    };
+
+    match pread_pool() {
+        Some(pool) if byte_offsets.len() > 1 => pool.install(|| {
+            buffer
+                .par_chunks_mut(len)
+                .zip(byte_offsets)
+                .try_for_each(read)
+        })?,
+        _ => buffer
+            .chunks_mut(len)
+            .zip(byte_offsets)
+            .try_for_each(read)?,
+    }
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+
+    #[test]
+    fn test_pread_batch() {
+        let mut file = tempfile::tempfile().unwrap();
+        let items: Vec<u32> = (0..1024).collect();
+        file.write_all(bytemuck::cast_slice(&items)).unwrap();
+
+        let byte_offsets = [40, 0, 400, 4000];
+        let read = pread_batch::<u32>(&file, &byte_offsets, 4).unwrap();
+
+        let expected: Vec<u32> = [10, 0, 100, 1000]
+            .into_iter()
+            .flat_map(|start| start..start + 4)
+            .collect();
+        assert_eq!(read, expected);
+
+        // Reads past the end of the file fail
+        assert!(pread_batch::<u32>(&file, &[4094], 4).is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::AccessPattern;
+use common::types::PointOffsetType;
+use fs_err as fs;
+
+use crate::common::Flusher;
+use crate::common::flags::bitvec_flags::BitvecFlags;
+use crate::common::flags::dynamic_mmap_flags::DynamicMmapFlags;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{VectorElementType, VectorRef};
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
+use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
+
+const DELETED_DIR_PATH: &str = "quantized_deleted";
+
+/// Read-only storage of dense vectors, which only keeps scalar or product quantized vectors
+///
+/// Original vectors are dropped once quantized vectors are built. Vectors are reconstructed from
+/// their quantized data on read, so they only approximate the original vectors.
+#[derive(Debug)]
+pub struct QuantizedDenseVectorStorage {
+    quantized_vectors: QuantizedVectors,
+    dim: usize,
+    total_vector_count: usize,
+    /// Flags marking deleted vectors
+    deleted: BitvecFlags,
+    deleted_count: usize,
+    distance: Distance,
+    datatype: VectorStorageDatatype,
+}
+
+impl QuantizedDenseVectorStorage {
+    /// Number of bytes of quantized vectors, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        if self.quantized_vectors.get_storage().is_on_disk() {
+            0
+        } else {
+            self.size_of_available_vectors_in_bytes()
+        }
+    }
+
+    pub fn populate(&self) -> OperationResult<()> {
+        self.quantized_vectors.populate()
+    }
+
+    pub fn clear_cache(&self) -> OperationResult<()> {
+        self.quantized_vectors.clear_cache()?;
+        self.deleted.clear_cache()?;
+        Ok(())
+    }
+
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        let dim = self.quantized_vectors.config().vector_parameters.dim;
+        if dim != self.dim {
+            return Err(OperationError::service_error(format!(
+                "Quantized vectors have dimension {dim}, but vector storage expects {}",
+                self.dim,
+            )));
+        }
+        Ok(())
+    }
+
+    fn reconstruct(&self, key: PointOffsetType) -> Option<Vec<VectorElementType>> {
+        if key as usize >= self.total_vector_count {
+            return None;
+        }
+        self.quantized_vectors.reconstruct_vector(key)
+    }
+}
+
+impl DenseVectorStorage<VectorElementType> for QuantizedDenseVectorStorage {
+    fn vector_dim(&self) -> usize {
+        self.dim
+    }
+
+    fn get_dense<P: AccessPattern>(&self, key: PointOffsetType) -> Cow<'_, [VectorElementType]> {
+        Cow::Owned(self.reconstruct(key).expect("quantized vector not found"))
+    }
+
+    fn size_of_available_vectors_in_bytes(&self) -> usize {
+        self.quantized_vectors
+            .get_quantized_vector_layout()
+            .map_or(0, |layout| layout.size())
+            * self.available_vector_count()
+    }
+}
+
+impl VectorStorage for QuantizedDenseVectorStorage {
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        self.datatype
+    }
+
+    fn is_on_disk(&self) -> bool {
+        true
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.total_vector_count
+    }
+
+    fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
+        self.get_vector_opt::<P>(key).expect("Vector not found")
+    }
+
+    fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
+        self.reconstruct(key).map(CowVector::from)
+    }
+
+    fn insert_vector(
+        &mut self,
+        _key: PointOffsetType,
+        _vector: VectorRef,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        Err(OperationError::service_error(
+            "Cannot insert into vector storage without original vectors",
+        ))
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        _other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        _stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        Err(OperationError::service_error(
+            "Cannot update vector storage without original vectors",
+        ))
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.deleted.flusher()
+    }
+
+    fn estimate_flush_size(&self) -> usize {
+        self.deleted.estimate_flush_size()
+    }
+
+    /// Quantized files are owned by quantized vectors of the segment, only deleted flags are
+    /// listed here
+    fn files(&self) -> Vec<PathBuf> {
+        self.deleted.files()
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        if key as usize >= self.total_vector_count {
+            return Ok(false);
+        }
+        let was_deleted = self.deleted.set(key, true);
+        if !was_deleted {
+            self.deleted_count += 1;
+        }
+        Ok(!was_deleted)
+    }
+
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let keys = &keys[..keys.len().min(self.total_vector_count)];
+        let deleted = self.deleted.set_trues(keys);
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.get_bitslice()
+    }
+}
+
+/// Persist deleted flags of `vector_storage` for a [`QuantizedDenseVectorStorage`] at `path`,
+/// which replaces `vector_storage` once its original vectors are dropped.
+pub fn init_quantized_dense_vector_storage(
+    path: &Path,
+    vector_storage: &VectorStorageEnum,
+) -> OperationResult<()> {
+    fs::create_dir_all(path)?;
+    let mut deleted =
+        BitvecFlags::new(DynamicMmapFlags::open(&path.join(DELETED_DIR_PATH), false)?);
+    for key in vector_storage.deleted_vector_bitslice().iter_ones() {
+        deleted.set(key as PointOffsetType, true);
+    }
+    deleted.flusher()()?;
+    Ok(())
+}
+
+pub fn open_quantized_dense_vector_storage(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    datatype: VectorStorageDatatype,
+    populate: bool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_quantized_dense_vector_storage_impl(path, dim, distance, datatype, populate)?;
+    Ok(VectorStorageEnum::DenseQuantized(Box::new(storage)))
+}
+
+pub fn open_quantized_dense_vector_storage_impl(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+    datatype: VectorStorageDatatype,
+    populate: bool,
+) -> OperationResult<QuantizedDenseVectorStorage> {
+    let quantized_vectors = QuantizedVectors::load_without_originals(path, distance, datatype)?;
+    let total_vector_count = quantized_vectors.vectors_count();
+
+    let deleted = BitvecFlags::new(DynamicMmapFlags::open(
+        &path.join(DELETED_DIR_PATH),
+        populate,
+    )?);
+    let deleted_count = deleted.count_trues();
+
+    Ok(QuantizedDenseVectorStorage {
+        quantized_vectors,
+        dim,
+        total_vector_count,
+        deleted,
+        deleted_count,
+        distance,
+        datatype,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use common::generic_consts::Random;
+    use rand::prelude::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::types::{QuantizationConfig, ScalarQuantizationConfig, ScalarType};
+    use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
+    use crate::vector_storage::quantized::quantized_vectors::QuantizedVectorsStorageType;
+
+    #[test]
+    fn test_reconstruct_from_scalar_quantization() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 16;
+
+        let mut original = new_volatile_dense_vector_storage(dim, Distance::Dot);
+        let vectors: Vec<Vec<f32>> = (0..10)
+            .map(|_| (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect())
+            .collect();
+        for (key, vector) in vectors.iter().enumerate() {
+            original
+                .insert_vector(key as PointOffsetType, VectorRef::from(vector), &hw_counter)
+                .unwrap();
+        }
+        original.delete_vector(3).unwrap();
+
+        let quantization_config: QuantizationConfig = ScalarQuantizationConfig {
+            r#type: ScalarType::Int8,
+            quantile: None,
+            always_ram: None,
+            drop_originals: Some(true),
+        }
+        .into();
+        QuantizedVectors::create(
+            &original,
+            &quantization_config,
+            QuantizedVectorsStorageType::Immutable,
+            dir.path(),
+            1,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        init_quantized_dense_vector_storage(dir.path(), &original).unwrap();
+        drop(original);
+
+        let mut storage = open_quantized_dense_vector_storage_impl(
+            dir.path(),
+            dim,
+            Distance::Dot,
+            VectorStorageDatatype::Float32,
+            false,
+        )
+        .unwrap();
+        assert_eq!(storage.total_vector_count(), vectors.len());
+        assert_eq!(storage.deleted_vector_count(), 1);
+        assert!(storage.is_deleted_vector(3));
+
+        // Each element is rounded to the nearest of 128 levels over the range of at most 2.0
+        let max_error = 1.0 / 127.0 + f32::EPSILON;
+        for (key, vector) in vectors.iter().enumerate() {
+            let reconstructed = storage.get_dense::<Random>(key as PointOffsetType);
+            for (reconstructed, original) in reconstructed.iter().zip(vector) {
+                assert!((reconstructed - original).abs() <= max_error);
+            }
+        }
+
+        assert!(
+            storage
+                .insert_vector(0, VectorRef::from(&vectors[0]), &hw_counter)
+                .is_err()
+        );
+        assert!(storage.delete_vector(5).unwrap());
+        assert!(!storage.delete_vector(5).unwrap());
+        assert!(!storage.delete_vector(100).unwrap());
+        assert_eq!(storage.deleted_vector_count(), 2);
+    }
+}
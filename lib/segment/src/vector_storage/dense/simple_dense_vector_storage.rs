@@ -119,6 +119,18 @@ pub fn open_simple_dense_vector_storage(
             distance,
             stopped,
         ),
+        VectorStorageDatatype::Float64 => Err(OperationError::validation_error(
+            "Float64 datatype is not supported by in-memory vector storage",
+        )),
+        VectorStorageDatatype::Int8 => Err(OperationError::validation_error(
+            "Int8 datatype is not supported by in-memory vector storage",
+        )),
+        VectorStorageDatatype::Bfloat16 => Err(OperationError::validation_error(
+            "Bfloat16 datatype is not supported by in-memory vector storage",
+        )),
+        VectorStorageDatatype::Int4 => Err(OperationError::validation_error(
+            "Int4 datatype is not supported by in-memory vector storage",
+        )),
     }
 }
 
@@ -177,6 +189,11 @@ pub fn open_simple_dense_half_vector_storage(
 }
 
 impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
+    /// Number of bytes, allocated for vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -2,7 +2,9 @@ use std::borrow::Cow;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
 
-use common::bitvec::{BitSlice, BitSliceExt as _, BitVec, bitvec_set_deleted};
+use common::bitvec::{
+    BitSlice, BitSliceExt as _, BitVec, bitvec_set_deleted, bitvec_set_deleted_batch,
+};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::generic_consts::AccessPattern;
 use common::types::PointOffsetType;
@@ -57,6 +59,11 @@ impl<T: PrimitiveVectorElement> VolatileDenseVectorStorage<T> {
         }
     }
 
+    /// Number of bytes, allocated for vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -158,6 +165,12 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileDenseVectorStorage<T>
         Ok(is_deleted)
     }
 
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = bitvec_set_deleted_batch(&mut self.deleted, keys, |_| {});
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         self.deleted.get_bit(key as usize).unwrap_or(false)
     }
@@ -0,0 +1,139 @@
+//! Memory budget of vector storages, which keep their vectors in RAM.
+//!
+//! In-RAM storages reserve the memory they occupy from the global [`MemoryBudget`]. Once the
+//! configured limit is exceeded, newly created segments store their vectors in mmap instead of
+//! RAM. Already existing storages are not affected, the budget only limits further growth.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static MEMORY_BUDGET: MemoryBudget = MemoryBudget::new();
+
+/// Memory, occupied by in-RAM vector storages of all segments
+#[derive(Debug)]
+pub struct MemoryBudget {
+    /// Maximum number of bytes, `usize::MAX` if not limited
+    limit_bytes: AtomicUsize,
+    /// Number of bytes, reserved by all storages
+    used_bytes: AtomicUsize,
+}
+
+impl MemoryBudget {
+    const fn new() -> Self {
+        Self {
+            limit_bytes: AtomicUsize::new(usize::MAX),
+            used_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn global() -> &'static Self {
+        &MEMORY_BUDGET
+    }
+
+    /// Set maximum number of bytes for in-RAM vector storages, `None` to remove the limit
+    pub fn set_limit(&self, limit_bytes: Option<usize>) {
+        self.limit_bytes
+            .store(limit_bytes.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+
+    pub fn limit_bytes(&self) -> Option<usize> {
+        let limit_bytes = self.limit_bytes.load(Ordering::Relaxed);
+        (limit_bytes != usize::MAX).then_some(limit_bytes)
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Whether in-RAM storages occupy more memory than allowed.
+    /// New segments should not keep their vectors in RAM if so.
+    pub fn is_exceeded(&self) -> bool {
+        self.used_bytes() > self.limit_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Reserve `bytes` of the budget.
+    ///
+    /// Reservation may exceed the limit, it is up to the caller to check [`Self::is_exceeded`]
+    /// beforehand. Memory is released when the reservation is dropped.
+    pub fn reserve(&'static self, bytes: usize) -> MemoryReservation {
+        self.used_bytes.fetch_add(bytes, Ordering::Relaxed);
+        MemoryReservation {
+            budget: self,
+            bytes,
+        }
+    }
+}
+
+/// Memory, reserved by a single storage from a [`MemoryBudget`]
+#[derive(Debug)]
+pub struct MemoryReservation {
+    budget: &'static MemoryBudget,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Change reserved memory to `bytes`, e.g. after the storage has grown
+    pub fn resize(&mut self, bytes: usize) {
+        if bytes > self.bytes {
+            self.budget
+                .used_bytes
+                .fetch_add(bytes - self.bytes, Ordering::Relaxed);
+        } else {
+            self.budget
+                .used_bytes
+                .fetch_sub(self.bytes - bytes, Ordering::Relaxed);
+        }
+        self.bytes = bytes;
+    }
+}
+
+impl Default for MemoryReservation {
+    fn default() -> Self {
+        MemoryBudget::global().reserve(0)
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.budget
+            .used_bytes
+            .fetch_sub(self.bytes, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_reservation() {
+        // Global budget is shared with other tests, use a separate one
+        static BUDGET: MemoryBudget = MemoryBudget::new();
+        BUDGET.set_limit(Some(100));
+        assert_eq!(BUDGET.limit_bytes(), Some(100));
+
+        let mut first = BUDGET.reserve(60);
+        let second = BUDGET.reserve(40);
+        assert_eq!(BUDGET.used_bytes(), 100);
+        assert!(!BUDGET.is_exceeded());
+
+        first.resize(80);
+        assert_eq!(BUDGET.used_bytes(), 120);
+        assert!(BUDGET.is_exceeded());
+
+        first.resize(10);
+        assert_eq!(first.bytes(), 10);
+        assert_eq!(BUDGET.used_bytes(), 50);
+
+        drop(second);
+        assert_eq!(BUDGET.used_bytes(), 10);
+        drop(first);
+        assert_eq!(BUDGET.used_bytes(), 0);
+
+        BUDGET.set_limit(None);
+        assert_eq!(BUDGET.limit_bytes(), None);
+    }
+}
@@ -1,16 +1,24 @@
 #[cfg(target_os = "linux")]
 pub mod async_raw_scorer;
+pub mod background_populate;
 mod chunked_vectors;
 pub mod common;
+pub mod dedup_index;
 pub mod dense;
+pub mod memory_budget;
 pub mod multi_dense;
 pub mod quantized;
 pub mod query;
 pub mod query_scorer;
 pub mod raw_scorer;
+pub mod read_stats;
 pub mod sparse;
+pub mod storage_snapshot;
+pub mod tombstone_vector_storage;
 mod vector_storage_base;
+pub mod versioned_vector_storage;
 pub mod volatile_chunked_vectors;
+pub mod write_throttle;
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use common::bitvec::BitSlice;
@@ -23,9 +24,15 @@ use crate::data_types::vectors::{
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::chunked_vectors::ChunkedVectors;
 use crate::vector_storage::dense::appendable_dense_vector_storage::{
-    open_appendable_memmap_vector_storage_byte, open_appendable_memmap_vector_storage_full,
+    open_appendable_memmap_vector_storage_bfloat16, open_appendable_memmap_vector_storage_byte,
+    open_appendable_memmap_vector_storage_double, open_appendable_memmap_vector_storage_full,
     open_appendable_memmap_vector_storage_half,
 };
+use crate::vector_storage::dense::appendable_int4_dense_vector_storage::open_appendable_memmap_vector_storage_int4;
+use crate::vector_storage::dense::appendable_int8_dense_vector_storage::open_appendable_memmap_vector_storage_int8;
+use crate::vector_storage::multi_dense::limit_vectors_count;
+use crate::vector_storage::read_stats::VectorStorageStats;
+use crate::vector_storage::write_throttle::WriteThrottle;
 use crate::vector_storage::{
     MultiVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
 };
@@ -54,10 +61,25 @@ pub struct AppendableMmapMultiDenseVectorStorage<T: PrimitiveVectorElement> {
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
     deleted_count: usize,
+    /// Backpressure on inserts, if dirty pages are not flushed fast enough
+    write_throttle: Option<Arc<WriteThrottle>>,
     _phantom: std::marker::PhantomData<T>,
 }
 
 impl<T: PrimitiveVectorElement> AppendableMmapMultiDenseVectorStorage<T> {
+    /// Number of bytes of vectors and their offsets, kept in RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes() + self.offsets.ram_usage_bytes()
+    }
+
+    pub fn read_stats(&self) -> VectorStorageStats {
+        self.vectors.read_stats()
+    }
+
+    pub fn set_write_throttle(&mut self, write_throttle: Option<Arc<WriteThrottle>>) {
+        self.write_throttle = write_throttle;
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -93,6 +115,13 @@ impl<T: PrimitiveVectorElement> AppendableMmapMultiDenseVectorStorage<T> {
         self.offsets.clear_cache()?;
         Ok(())
     }
+
+    /// Check vector files against checksums, computed at flush
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        self.vectors.verify_integrity()?;
+        self.offsets.verify_integrity()?;
+        Ok(())
+    }
 }
 
 impl<T: PrimitiveVectorElement> MultiVectorStorage<T> for AppendableMmapMultiDenseVectorStorage<T> {
@@ -197,7 +226,8 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
-        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
+        let multi_vector = limit_vectors_count(&self.multi_vector_config, multi_vector)?;
+        let multi_vector = T::from_float_multivector(multi_vector);
         let multi_vector = multi_vector.as_vec_ref();
         assert_eq!(multi_vector.dim, self.vectors.dim());
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
@@ -208,6 +238,9 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
                  It's too large, maximum size is {max_vector_size_bytes}."
             )));
         }
+        if let Some(write_throttle) = &self.write_throttle {
+            write_throttle.throttle(self.estimate_flush_size(), multivector_size_in_bytes);
+        }
 
         let mut offset = self
             .offsets
@@ -279,6 +312,12 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
         })
     }
 
+    fn estimate_flush_size(&self) -> usize {
+        self.vectors.estimate_flush_size()
+            + self.offsets.estimate_flush_size()
+            + self.deleted.estimate_flush_size()
+    }
+
     fn files(&self) -> Vec<PathBuf> {
         let mut files = self.vectors.files();
         files.extend(self.offsets.files());
@@ -296,6 +335,12 @@ impl<T: PrimitiveVectorElement> VectorStorage for AppendableMmapMultiDenseVector
         Ok(self.set_deleted(key, true))
     }
 
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = self.deleted.set_trues(keys);
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         self.deleted.get(key)
     }
@@ -339,6 +384,34 @@ pub fn open_appendable_memmap_vector_storage(
             madvise,
             populate,
         ),
+        VectorStorageDatatype::Float64 => open_appendable_memmap_vector_storage_double(
+            vector_storage_path,
+            size,
+            distance,
+            madvise,
+            populate,
+        ),
+        VectorStorageDatatype::Int8 => open_appendable_memmap_vector_storage_int8(
+            vector_storage_path,
+            size,
+            distance,
+            madvise,
+            populate,
+        ),
+        VectorStorageDatatype::Bfloat16 => open_appendable_memmap_vector_storage_bfloat16(
+            vector_storage_path,
+            size,
+            distance,
+            madvise,
+            populate,
+        ),
+        VectorStorageDatatype::Int4 => open_appendable_memmap_vector_storage_int4(
+            vector_storage_path,
+            size,
+            distance,
+            madvise,
+            populate,
+        ),
     }
 }
 
@@ -376,6 +449,18 @@ pub fn open_appendable_memmap_multi_vector_storage(
             madvise,
             populate,
         ),
+        VectorStorageDatatype::Float64 => Err(OperationError::validation_error(
+            "Float64 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Int8 => Err(OperationError::validation_error(
+            "Int8 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Bfloat16 => Err(OperationError::validation_error(
+            "Bfloat16 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Int4 => Err(OperationError::validation_error(
+            "Int4 datatype is not supported for multivectors",
+        )),
     }
 }
 
@@ -472,6 +557,7 @@ pub fn open_appendable_memmap_multi_vector_storage_impl<T: PrimitiveVectorElemen
         distance,
         multi_vector_config,
         deleted_count,
+        write_throttle: None,
         _phantom: Default::default(),
     })
 }
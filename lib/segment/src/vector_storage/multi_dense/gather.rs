@@ -0,0 +1,179 @@
+//! Gathering of stored multivectors into aligned scratch memory.
+//!
+//! Inner vectors of a multivector, read from storage, usually start at arbitrary addresses, so
+//! every similarity computation of MaxSim scoring does unaligned loads. Before scoring, inner
+//! vectors are copied into a thread-local scratch buffer, aligned to [`SCRATCH_ALIGNMENT`] bytes.
+//! If the size of inner vectors is a multiple of the alignment, every inner vector is aligned.
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+use std::arch::aarch64::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+use std::cell::RefCell;
+use std::mem;
+
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::TypedMultiDenseVectorRef;
+
+/// Alignment of the scratch buffer, size of an AVX register
+pub const SCRATCH_ALIGNMENT: usize = 32;
+
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C, align(32))]
+struct AlignedBlock([u8; SCRATCH_ALIGNMENT]);
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<AlignedBlock>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Call `f` with a copy of `multi_vector`, gathered into aligned scratch memory.
+///
+/// If `multi_vector` is aligned already, `f` is called with it directly.
+pub fn with_gathered_multi_vector<T: PrimitiveVectorElement, R>(
+    multi_vector: TypedMultiDenseVectorRef<'_, T>,
+    f: impl FnOnce(TypedMultiDenseVectorRef<'_, T>) -> R,
+) -> R {
+    let src: &[u8] = bytemuck::cast_slice(multi_vector.flattened_vectors);
+    if src.as_ptr().align_offset(SCRATCH_ALIGNMENT) == 0 {
+        return f(multi_vector);
+    }
+
+    SCRATCH.with(|scratch| {
+        // Scratch is in use by the caller already, e.g. if `f` gathers too
+        let Ok(mut scratch) = scratch.try_borrow_mut() else {
+            return f(multi_vector);
+        };
+
+        let blocks = src.len().div_ceil(SCRATCH_ALIGNMENT);
+        if scratch.len() < blocks {
+            scratch.resize(blocks, AlignedBlock([0; SCRATCH_ALIGNMENT]));
+        }
+        let dst = &mut bytemuck::cast_slice_mut::<_, u8>(scratch.as_mut_slice())[..src.len()];
+        gather_bytes(dst, src);
+
+        let flattened_vectors: &[T] = bytemuck::cast_slice(&*dst);
+        debug_assert_eq!(flattened_vectors.len() * mem::size_of::<T>(), src.len(),);
+        f(TypedMultiDenseVectorRef {
+            flattened_vectors,
+            dim: multi_vector.dim,
+        })
+    })
+}
+
+/// Copy `src` into `dst`, which must be aligned to [`SCRATCH_ALIGNMENT`] bytes
+fn gather_bytes(dst: &mut [u8], src: &[u8]) {
+    debug_assert_eq!(dst.len(), src.len());
+    debug_assert_eq!(dst.as_ptr().align_offset(SCRATCH_ALIGNMENT), 0);
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx")
+            && is_x86_feature_detected!("avx2")
+            && src.len() >= SCRATCH_ALIGNMENT
+        {
+            unsafe { gather_bytes_avx2(dst, src) };
+            return;
+        }
+    }
+
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") && src.len() >= 16 {
+            unsafe { gather_bytes_neon(dst, src) };
+            return;
+        }
+    }
+
+    dst.copy_from_slice(src);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+#[target_feature(enable = "avx2")]
+unsafe fn gather_bytes_avx2(dst: &mut [u8], src: &[u8]) {
+    debug_assert!(is_x86_feature_detected!("avx"));
+    debug_assert!(is_x86_feature_detected!("avx2"));
+
+    let len = src.len();
+    let mut src_ptr = src.as_ptr();
+    let mut dst_ptr = dst.as_mut_ptr();
+
+    unsafe {
+        for _ in 0..len / 128 {
+            let a = _mm256_loadu_si256(src_ptr.cast::<__m256i>());
+            let b = _mm256_loadu_si256(src_ptr.add(32).cast::<__m256i>());
+            let c = _mm256_loadu_si256(src_ptr.add(64).cast::<__m256i>());
+            let d = _mm256_loadu_si256(src_ptr.add(96).cast::<__m256i>());
+            _mm256_store_si256(dst_ptr.cast::<__m256i>(), a);
+            _mm256_store_si256(dst_ptr.add(32).cast::<__m256i>(), b);
+            _mm256_store_si256(dst_ptr.add(64).cast::<__m256i>(), c);
+            _mm256_store_si256(dst_ptr.add(96).cast::<__m256i>(), d);
+            src_ptr = src_ptr.add(128);
+            dst_ptr = dst_ptr.add(128);
+        }
+        for _ in 0..(len % 128) / 32 {
+            let a = _mm256_loadu_si256(src_ptr.cast::<__m256i>());
+            _mm256_store_si256(dst_ptr.cast::<__m256i>(), a);
+            src_ptr = src_ptr.add(32);
+            dst_ptr = dst_ptr.add(32);
+        }
+    }
+
+    let tail = len - len % 32;
+    dst[tail..].copy_from_slice(&src[tail..]);
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+unsafe fn gather_bytes_neon(dst: &mut [u8], src: &[u8]) {
+    let len = src.len();
+    let mut src_ptr = src.as_ptr();
+    let mut dst_ptr = dst.as_mut_ptr();
+
+    unsafe {
+        for _ in 0..len / 64 {
+            let a = vld1q_u8(src_ptr);
+            let b = vld1q_u8(src_ptr.add(16));
+            let c = vld1q_u8(src_ptr.add(32));
+            let d = vld1q_u8(src_ptr.add(48));
+            vst1q_u8(dst_ptr, a);
+            vst1q_u8(dst_ptr.add(16), b);
+            vst1q_u8(dst_ptr.add(32), c);
+            vst1q_u8(dst_ptr.add(48), d);
+            src_ptr = src_ptr.add(64);
+            dst_ptr = dst_ptr.add(64);
+        }
+        for _ in 0..(len % 64) / 16 {
+            vst1q_u8(dst_ptr, vld1q_u8(src_ptr));
+            src_ptr = src_ptr.add(16);
+            dst_ptr = dst_ptr.add(16);
+        }
+    }
+
+    let tail = len - len % 16;
+    dst[tail..].copy_from_slice(&src[tail..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather_multi_vector() {
+        for len in [1, 7, 8, 31, 32, 33, 100, 257] {
+            let dim = 1;
+            // Offset by one element to make the source unaligned
+            let data: Vec<f32> = (0..=len).map(|i| i as f32).collect();
+            let multi_vector = TypedMultiDenseVectorRef {
+                flattened_vectors: &data[1..],
+                dim,
+            };
+
+            let gathered = with_gathered_multi_vector(multi_vector, |gathered| {
+                let ptr = gathered.flattened_vectors.as_ptr();
+                assert_eq!(ptr.align_offset(SCRATCH_ALIGNMENT), 0);
+                gathered.flattened_vectors.to_vec()
+            });
+            assert_eq!(gathered, &data[1..]);
+        }
+    }
+}
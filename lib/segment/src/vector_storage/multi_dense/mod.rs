@@ -1,4 +1,98 @@
 pub mod appendable_mmap_multi_dense_vector_storage;
+pub mod gather;
 #[cfg(feature = "rocksdb")]
 pub mod simple_multi_dense_vector_storage;
 pub mod volatile_multi_dense_vector_storage;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::named_vectors::CowMultiVector;
+use crate::data_types::vectors::{
+    TypedMultiDenseVector, TypedMultiDenseVectorRef, VectorElementType,
+};
+use crate::types::{MultiVectorConfig, MultiVectorOverflowPolicy};
+
+/// Apply `max_vectors_per_point` of `config` to a multivector, which is about to be stored
+pub fn limit_vectors_count<'a>(
+    config: &MultiVectorConfig,
+    multi_vector: TypedMultiDenseVectorRef<'a, VectorElementType>,
+) -> OperationResult<CowMultiVector<'a, VectorElementType>> {
+    let vectors_count = multi_vector.vectors_count();
+    let Some(max_vectors) = config
+        .max_vectors_per_point
+        .filter(|&max_vectors| vectors_count > max_vectors)
+    else {
+        return Ok(CowMultiVector::Borrowed(multi_vector));
+    };
+    // Multivector must keep at least one vector
+    let max_vectors = max_vectors.max(1);
+    let dim = multi_vector.dim;
+
+    match config.overflow_policy.unwrap_or_default() {
+        MultiVectorOverflowPolicy::Reject => Err(OperationError::validation_error(format!(
+            "Multivector has {vectors_count} vectors, but at most {max_vectors} are allowed per point",
+        ))),
+        MultiVectorOverflowPolicy::Truncate => {
+            Ok(CowMultiVector::Borrowed(TypedMultiDenseVectorRef {
+                flattened_vectors: &multi_vector.flattened_vectors[..max_vectors * dim],
+                dim,
+            }))
+        }
+        MultiVectorOverflowPolicy::AveragePool => {
+            let (kept, extra) = multi_vector
+                .flattened_vectors
+                .split_at((max_vectors - 1) * dim);
+            let extra_count = extra.len() / dim;
+            let mut flattened_vectors = Vec::with_capacity(max_vectors * dim);
+            flattened_vectors.extend_from_slice(kept);
+            flattened_vectors.extend((0..dim).map(|i| {
+                extra.iter().skip(i).step_by(dim).sum::<VectorElementType>()
+                    / extra_count as VectorElementType
+            }));
+            Ok(CowMultiVector::Owned(TypedMultiDenseVector::new(
+                flattened_vectors,
+                dim,
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_vectors_count() {
+        let vectors = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let multi_vector = TypedMultiDenseVectorRef {
+            flattened_vectors: &vectors,
+            dim: 2,
+        };
+        let config = |max_vectors_per_point, overflow_policy| MultiVectorConfig {
+            max_vectors_per_point,
+            overflow_policy,
+            ..Default::default()
+        };
+        let limited = |config| {
+            limit_vectors_count(&config, multi_vector)
+                .unwrap()
+                .as_vec_ref()
+                .flattened_vectors
+                .to_vec()
+        };
+
+        assert_eq!(limited(config(None, None)), vectors);
+        assert_eq!(limited(config(Some(4), None)), vectors);
+        assert!(limit_vectors_count(&config(Some(3), None), multi_vector).is_err());
+        assert_eq!(
+            limited(config(Some(3), Some(MultiVectorOverflowPolicy::Truncate))),
+            [1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+        );
+        assert_eq!(
+            limited(config(
+                Some(2),
+                Some(MultiVectorOverflowPolicy::AveragePool)
+            )),
+            [1.0, 2.0, 5.0, 6.0],
+        );
+    }
+}
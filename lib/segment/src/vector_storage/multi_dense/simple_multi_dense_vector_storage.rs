@@ -21,6 +21,7 @@ use crate::data_types::vectors::{
 };
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::common::{CHUNK_SIZE, StoredRecord};
+use crate::vector_storage::multi_dense::limit_vectors_count;
 use crate::vector_storage::volatile_chunked_vectors::VolatileChunkedVectors;
 use crate::vector_storage::{
     MultiVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
@@ -100,6 +101,18 @@ pub fn open_simple_multi_dense_vector_storage(
             multi_vector_config,
             stopped,
         ),
+        VectorStorageDatatype::Float64 => Err(OperationError::validation_error(
+            "Float64 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Int8 => Err(OperationError::validation_error(
+            "Int8 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Bfloat16 => Err(OperationError::validation_error(
+            "Bfloat16 datatype is not supported for multivectors",
+        )),
+        VectorStorageDatatype::Int4 => Err(OperationError::validation_error(
+            "Int4 datatype is not supported for multivectors",
+        )),
     }
 }
 
@@ -221,6 +234,11 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
 }
 
 impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
+    /// Number of bytes, allocated for vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -279,7 +297,8 @@ impl<T: PrimitiveVectorElement> SimpleMultiDenseVectorStorage<T> {
         hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
-        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
+        let multi_vector = limit_vectors_count(&self.multi_vector_config, multi_vector)?;
+        let multi_vector = T::from_float_multivector(multi_vector);
         let multi_vector = multi_vector.as_vec_ref();
         assert_eq!(multi_vector.dim, self.dim);
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
@@ -3,7 +3,9 @@ use std::fmt;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
 
-use common::bitvec::{BitSlice, BitSliceExt as _, BitVec, bitvec_set_deleted};
+use common::bitvec::{
+    BitSlice, BitSliceExt as _, BitVec, bitvec_set_deleted, bitvec_set_deleted_batch,
+};
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::generic_consts::AccessPattern;
 use common::types::PointOffsetType;
@@ -15,6 +17,7 @@ use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{TypedMultiDenseVectorRef, VectorElementType, VectorRef};
 use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
 use crate::vector_storage::common::CHUNK_SIZE;
+use crate::vector_storage::multi_dense::limit_vectors_count;
 use crate::vector_storage::volatile_chunked_vectors::VolatileChunkedVectors;
 use crate::vector_storage::{
     MultiVectorStorage, VectorOffsetType, VectorStorage, VectorStorageEnum,
@@ -107,6 +110,11 @@ impl<T: PrimitiveVectorElement> VolatileMultiDenseVectorStorage<T> {
         }
     }
 
+    /// Number of bytes, allocated for vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.vectors.ram_usage_bytes()
+    }
+
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
     fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
@@ -132,7 +140,8 @@ impl<T: PrimitiveVectorElement> VolatileMultiDenseVectorStorage<T> {
         _hw_counter: &HardwareCounterCell,
     ) -> OperationResult<()> {
         let multi_vector: TypedMultiDenseVectorRef<VectorElementType> = vector.try_into()?;
-        let multi_vector = T::from_float_multivector(CowMultiVector::Borrowed(multi_vector));
+        let multi_vector = limit_vectors_count(&self.multi_vector_config, multi_vector)?;
+        let multi_vector = T::from_float_multivector(multi_vector);
         let multi_vector = multi_vector.as_vec_ref();
         assert_eq!(multi_vector.dim, self.dim);
         let multivector_size_in_bytes = std::mem::size_of_val(multi_vector.flattened_vectors);
@@ -298,6 +307,12 @@ impl<T: PrimitiveVectorElement> VectorStorage for VolatileMultiDenseVectorStorag
         Ok(is_deleted)
     }
 
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let deleted = bitvec_set_deleted_batch(&mut self.deleted, keys, |_| {});
+        self.deleted_count += deleted;
+        Ok(deleted)
+    }
+
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
         self.deleted.get_bit(key as usize).unwrap_or(false)
     }
@@ -7,7 +7,8 @@ use super::quantized_vectors::QuantizedVectorStorage;
 use crate::common::operation_error::OperationResult;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
+    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType,
+    VectorElementTypeBfloat16, VectorElementTypeByte, VectorElementTypeDouble,
     VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
@@ -55,7 +56,10 @@ impl<'a> QuantizedScorerBuilder<'a> {
 
     pub fn build(self) -> OperationResult<Box<dyn RawScorer + 'a>> {
         match self.datatype {
-            VectorStorageDatatype::Float32 => match self.distance {
+            // Int8 and Int4 storages dequantize vectors into floats
+            VectorStorageDatatype::Float32
+            | VectorStorageDatatype::Int8
+            | VectorStorageDatatype::Int4 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementType, CosineMetric>(),
                 Distance::Euclid => self.build_with_metric::<VectorElementType, EuclidMetric>(),
                 Distance::Dot => self.build_with_metric::<VectorElementType, DotProductMetric>(),
@@ -83,6 +87,34 @@ impl<'a> QuantizedScorerBuilder<'a> {
                     self.build_with_metric::<VectorElementTypeHalf, ManhattanMetric>()
                 }
             },
+            VectorStorageDatatype::Float64 => match self.distance {
+                Distance::Cosine => {
+                    self.build_with_metric::<VectorElementTypeDouble, CosineMetric>()
+                }
+                Distance::Euclid => {
+                    self.build_with_metric::<VectorElementTypeDouble, EuclidMetric>()
+                }
+                Distance::Dot => {
+                    self.build_with_metric::<VectorElementTypeDouble, DotProductMetric>()
+                }
+                Distance::Manhattan => {
+                    self.build_with_metric::<VectorElementTypeDouble, ManhattanMetric>()
+                }
+            },
+            VectorStorageDatatype::Bfloat16 => match self.distance {
+                Distance::Cosine => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, CosineMetric>()
+                }
+                Distance::Euclid => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, EuclidMetric>()
+                }
+                Distance::Dot => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, DotProductMetric>()
+                }
+                Distance::Manhattan => {
+                    self.build_with_metric::<VectorElementTypeBfloat16, ManhattanMetric>()
+                }
+            },
         }
     }
 
@@ -110,6 +142,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::PQChunkedMmap(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric>(storage)
             }
+            QuantizedVectorStorage::RQRam(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric>(storage)
+            }
+            QuantizedVectorStorage::RQMmap(storage) => {
+                self.new_quantized_scorer::<TElement, TMetric>(storage)
+            }
             QuantizedVectorStorage::BinaryRam(storage) => {
                 self.new_quantized_scorer::<TElement, TMetric>(storage)
             }
@@ -137,6 +175,12 @@ impl<'a> QuantizedScorerBuilder<'a> {
             QuantizedVectorStorage::PQChunkedMmapMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric>(storage)
             }
+            QuantizedVectorStorage::RQRamMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric>(storage)
+            }
+            QuantizedVectorStorage::RQMmapMulti(storage) => {
+                self.new_multi_quantized_scorer::<TElement, TMetric>(storage)
+            }
             QuantizedVectorStorage::BinaryRamMulti(storage) => {
                 self.new_multi_quantized_scorer::<TElement, TMetric>(storage)
             }
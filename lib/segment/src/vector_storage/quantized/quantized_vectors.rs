@@ -8,9 +8,13 @@ use common::counter::hardware_counter::HardwareCounterCell;
 use common::fs::{atomic_save_json, clear_disk_cache, read_json};
 use common::generic_consts::{Random, Sequential};
 use common::types::PointOffsetType;
+use fs_err as fs;
 use quantization::encoded_vectors_binary::EncodedVectorsBin;
 use quantization::encoded_vectors_u8::ScalarQuantizationMethod;
-use quantization::{EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8};
+use quantization::{
+    EncodedStorage, EncodedStorageBuilder, EncodedVectors, EncodedVectorsPQ, EncodedVectorsRQ,
+    EncodedVectorsU8, EncodingError,
+};
 use serde::{Deserialize, Serialize};
 
 use super::quantized_multivector_storage::{
@@ -20,12 +24,13 @@ use super::quantized_scorer_builder::QuantizedScorerBuilder;
 use crate::common::Flusher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
-use crate::data_types::vectors::{QueryVector, VectorElementType, VectorRef};
+use crate::data_types::vectors::{QueryVector, VectorElementType, VectorInternal, VectorRef};
 use crate::types::{
-    BinaryQuantization, BinaryQuantizationConfig, BinaryQuantizationEncoding,
-    BinaryQuantizationQueryEncoding, CompressionRatio, Distance, MultiVectorConfig,
-    ProductQuantization, ProductQuantizationConfig, QuantizationConfig, ScalarQuantization,
-    ScalarQuantizationConfig, ScalarType, VectorStorageDatatype,
+    AnisotropicQuantization, AnisotropicQuantizationConfig, BinaryQuantization,
+    BinaryQuantizationConfig, BinaryQuantizationEncoding, BinaryQuantizationQueryEncoding,
+    CompressionRatio, Distance, MultiVectorConfig, ProductQuantization, ProductQuantizationConfig,
+    QuantizationConfig, QuantizedVectorData, ResidualQuantization, ResidualQuantizationConfig,
+    ScalarQuantization, ScalarQuantizationConfig, ScalarType, VectorStorageDatatype,
 };
 use crate::vector_storage::quantized::quantized_chunked_mmap_storage::{
     QuantizedChunkedMmapStorage, QuantizedChunkedMmapStorageBuilder,
@@ -53,6 +58,8 @@ pub const QUANTIZED_APPENDABLE_DATA_PATH: &str = "quantized_data";
 pub const QUANTIZED_META_PATH: &str = "quantized.meta.json";
 pub const QUANTIZED_OFFSETS_PATH: &str = "quantized.offsets.data";
 pub const QUANTIZED_APPENDABLE_OFFSETS_PATH: &str = "quantized_offsets_data";
+/// Directory, in which quantized vectors are retrained before replacing current ones
+pub const QUANTIZED_REQUANTIZE_PATH: &str = "requantize";
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct QuantizedVectorsConfig {
@@ -112,6 +119,15 @@ type PQChunkedMmapMulti = QuantizedMultivectorStorage<
     MultivectorOffsetsStorageChunkedMmap,
 >;
 
+type RQRamMulti = QuantizedMultivectorStorage<
+    EncodedVectorsRQ<QuantizedRamStorage>,
+    MultivectorOffsetsStorageRam,
+>;
+type RQMmapMulti = QuantizedMultivectorStorage<
+    EncodedVectorsRQ<QuantizedMmapStorage>,
+    MultivectorOffsetsStorageMmap,
+>;
+
 type BinaryRamMulti = QuantizedMultivectorStorage<
     EncodedVectorsBin<u8, QuantizedRamStorage>,
     MultivectorOffsetsStorageRam,
@@ -133,6 +149,8 @@ pub enum QuantizedVectorStorage {
     PQRam(EncodedVectorsPQ<QuantizedRamStorage>),
     PQMmap(EncodedVectorsPQ<QuantizedMmapStorage>),
     PQChunkedMmap(EncodedVectorsPQ<QuantizedChunkedMmapStorage>),
+    RQRam(EncodedVectorsRQ<QuantizedRamStorage>),
+    RQMmap(EncodedVectorsRQ<QuantizedMmapStorage>),
     BinaryRam(EncodedVectorsBin<u128, QuantizedRamStorage>),
     BinaryMmap(EncodedVectorsBin<u128, QuantizedMmapStorage>),
     BinaryChunkedMmap(EncodedVectorsBin<u128, QuantizedChunkedMmapStorage>),
@@ -142,6 +160,8 @@ pub enum QuantizedVectorStorage {
     PQRamMulti(PQRamMulti),
     PQMmapMulti(PQMmapMulti),
     PQChunkedMmapMulti(PQChunkedMmapMulti),
+    RQRamMulti(RQRamMulti),
+    RQMmapMulti(RQMmapMulti),
     BinaryRamMulti(BinaryRamMulti),
     BinaryMmapMulti(BinaryMmapMulti),
     BinaryChunkedMmapMulti(BinaryChunkedMmapMulti),
@@ -156,6 +176,8 @@ impl QuantizedVectorStorage {
             QuantizedVectorStorage::PQRam(q) => q.is_on_disk(),
             QuantizedVectorStorage::PQMmap(q) => q.is_on_disk(),
             QuantizedVectorStorage::PQChunkedMmap(q) => q.is_on_disk(),
+            QuantizedVectorStorage::RQRam(q) => q.is_on_disk(),
+            QuantizedVectorStorage::RQMmap(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryRam(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryMmap(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.is_on_disk(),
@@ -165,6 +187,8 @@ impl QuantizedVectorStorage {
             QuantizedVectorStorage::PQRamMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::PQMmapMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::PQChunkedMmapMulti(q) => q.is_on_disk(),
+            QuantizedVectorStorage::RQRamMulti(q) => q.is_on_disk(),
+            QuantizedVectorStorage::RQMmapMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryRamMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryMmapMulti(q) => q.is_on_disk(),
             QuantizedVectorStorage::BinaryChunkedMmapMulti(q) => q.is_on_disk(),
@@ -193,22 +217,28 @@ impl QuantizedVectors {
     }
 
     pub fn default_rescoring(&self) -> bool {
-        match self.storage_impl {
-            QuantizedVectorStorage::ScalarRam(_) => false,
-            QuantizedVectorStorage::ScalarMmap(_) => false,
-            QuantizedVectorStorage::ScalarChunkedMmap(_) => false,
+        // 4-bit scalar quantization is too coarse to rely on its scores only
+        let int4 = ScalarQuantizationMethod::Int4;
+        match &self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(q) => q.method() == int4,
+            QuantizedVectorStorage::ScalarMmap(q) => q.method() == int4,
+            QuantizedVectorStorage::ScalarChunkedMmap(q) => q.method() == int4,
             QuantizedVectorStorage::PQRam(_) => false,
             QuantizedVectorStorage::PQMmap(_) => false,
             QuantizedVectorStorage::PQChunkedMmap(_) => false,
+            QuantizedVectorStorage::RQRam(_) => false,
+            QuantizedVectorStorage::RQMmap(_) => false,
             QuantizedVectorStorage::BinaryRam(_) => true,
             QuantizedVectorStorage::BinaryMmap(_) => true,
             QuantizedVectorStorage::BinaryChunkedMmap(_) => true,
-            QuantizedVectorStorage::ScalarRamMulti(_) => false,
-            QuantizedVectorStorage::ScalarMmapMulti(_) => false,
-            QuantizedVectorStorage::ScalarChunkedMmapMulti(_) => false,
+            QuantizedVectorStorage::ScalarRamMulti(q) => q.inner_storage().method() == int4,
+            QuantizedVectorStorage::ScalarMmapMulti(q) => q.inner_storage().method() == int4,
+            QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.inner_storage().method() == int4,
             QuantizedVectorStorage::PQRamMulti(_) => false,
             QuantizedVectorStorage::PQMmapMulti(_) => false,
             QuantizedVectorStorage::PQChunkedMmapMulti(_) => false,
+            QuantizedVectorStorage::RQRamMulti(_) => false,
+            QuantizedVectorStorage::RQMmapMulti(_) => false,
             QuantizedVectorStorage::BinaryRamMulti(_) => true,
             QuantizedVectorStorage::BinaryMmapMulti(_) => true,
             QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => true,
@@ -223,6 +253,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(_) => false,
             QuantizedVectorStorage::PQMmap(_) => false,
             QuantizedVectorStorage::PQChunkedMmap(_) => false,
+            QuantizedVectorStorage::RQRam(_) => false,
+            QuantizedVectorStorage::RQMmap(_) => false,
             QuantizedVectorStorage::BinaryRam(_) => false,
             QuantizedVectorStorage::BinaryMmap(_) => false,
             QuantizedVectorStorage::BinaryChunkedMmap(_) => false,
@@ -232,6 +264,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRamMulti(_) => true,
             QuantizedVectorStorage::PQMmapMulti(_) => true,
             QuantizedVectorStorage::PQChunkedMmapMulti(_) => true,
+            QuantizedVectorStorage::RQRamMulti(_) => true,
+            QuantizedVectorStorage::RQMmapMulti(_) => true,
             QuantizedVectorStorage::BinaryRamMulti(_) => true,
             QuantizedVectorStorage::BinaryMmapMulti(_) => true,
             QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => true,
@@ -249,6 +283,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::PQMmap(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::PQChunkedMmap(storage) => Ok(storage.layout()),
+            QuantizedVectorStorage::RQRam(storage) => Ok(storage.layout()),
+            QuantizedVectorStorage::RQMmap(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::BinaryRam(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::BinaryMmap(storage) => Ok(storage.layout()),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => Ok(storage.layout()),
@@ -258,6 +294,8 @@ impl QuantizedVectors {
             | QuantizedVectorStorage::PQRamMulti(_)
             | QuantizedVectorStorage::PQMmapMulti(_)
             | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::RQRamMulti(_)
+            | QuantizedVectorStorage::RQMmapMulti(_)
             | QuantizedVectorStorage::BinaryRamMulti(_)
             | QuantizedVectorStorage::BinaryMmapMulti(_)
             | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => {
@@ -276,6 +314,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::PQMmap(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::PQChunkedMmap(storage) => storage.get_quantized_vector(id),
+            QuantizedVectorStorage::RQRam(storage) => storage.get_quantized_vector(id),
+            QuantizedVectorStorage::RQMmap(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::BinaryRam(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::BinaryMmap(storage) => storage.get_quantized_vector(id),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => storage.get_quantized_vector(id),
@@ -285,6 +325,8 @@ impl QuantizedVectors {
             | QuantizedVectorStorage::PQRamMulti(_)
             | QuantizedVectorStorage::PQMmapMulti(_)
             | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::RQRamMulti(_)
+            | QuantizedVectorStorage::RQMmapMulti(_)
             | QuantizedVectorStorage::BinaryRamMulti(_)
             | QuantizedVectorStorage::BinaryMmapMulti(_)
             | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => {
@@ -293,11 +335,150 @@ impl QuantizedVectors {
         }
     }
 
+    /// Quantized representation of vector `id` with parameters to interpret it.
+    ///
+    /// Returns `None` for multivectors.
+    pub fn get_quantized_vector_data(&self, id: PointOffsetType) -> Option<QuantizedVectorData> {
+        let dim = self.config.vector_parameters.dim;
+        let (codes, scale_and_offset) = match &self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(storage) => Self::scalar_codes(storage, id, dim),
+            QuantizedVectorStorage::ScalarMmap(storage) => Self::scalar_codes(storage, id, dim),
+            QuantizedVectorStorage::ScalarChunkedMmap(storage) => {
+                Self::scalar_codes(storage, id, dim)
+            }
+            QuantizedVectorStorage::PQRam(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::PQMmap(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::PQChunkedMmap(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::RQRam(storage) => (storage.get_quantized_codes(id), None),
+            QuantizedVectorStorage::RQMmap(storage) => (storage.get_quantized_codes(id), None),
+            QuantizedVectorStorage::BinaryRam(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::BinaryMmap(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::BinaryChunkedMmap(storage) => {
+                (storage.get_quantized_vector(id).into_owned(), None)
+            }
+            QuantizedVectorStorage::ScalarRamMulti(_)
+            | QuantizedVectorStorage::ScalarMmapMulti(_)
+            | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
+            | QuantizedVectorStorage::PQRamMulti(_)
+            | QuantizedVectorStorage::PQMmapMulti(_)
+            | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::RQRamMulti(_)
+            | QuantizedVectorStorage::RQMmapMulti(_)
+            | QuantizedVectorStorage::BinaryRamMulti(_)
+            | QuantizedVectorStorage::BinaryMmapMulti(_)
+            | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => return None,
+        };
+        let (scale, offset) = scale_and_offset.unzip();
+
+        Some(QuantizedVectorData {
+            quantization_config: self.config.quantization_config.clone(),
+            dim,
+            codes,
+            scale,
+            offset,
+        })
+    }
+
+    /// Scalar codes of vector `id` without alignment padding, with its scale and offset
+    fn scalar_codes<TStorage: EncodedStorage>(
+        storage: &EncodedVectorsU8<TStorage>,
+        id: PointOffsetType,
+        dim: usize,
+    ) -> (Vec<u8>, Option<(f32, f32)>) {
+        let codes_len = match storage.method() {
+            ScalarQuantizationMethod::Int8 => dim,
+            // Two 4-bit codes are packed into each byte
+            ScalarQuantizationMethod::Int4 => dim.div_ceil(2),
+        };
+        let (_, codes) = storage.get_quantized_vector_offset_and_code(id);
+        (
+            codes[..codes_len].to_vec(),
+            Some(storage.get_scale_and_offset(id)),
+        )
+    }
+
+    pub fn vectors_count(&self) -> usize {
+        match &self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(q) => q.vectors_count(),
+            QuantizedVectorStorage::ScalarMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::ScalarChunkedMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQRam(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQChunkedMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::RQRam(q) => q.vectors_count(),
+            QuantizedVectorStorage::RQMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryRam(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryChunkedMmap(q) => q.vectors_count(),
+            QuantizedVectorStorage::ScalarRamMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::ScalarMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::ScalarChunkedMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQRamMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::PQChunkedMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::RQRamMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::RQMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryRamMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryMmapMulti(q) => q.vectors_count(),
+            QuantizedVectorStorage::BinaryChunkedMmapMulti(q) => q.vectors_count(),
+        }
+    }
+
+    /// Approximate original vector, decoded from its quantized data.
+    ///
+    /// Returns `None` for binary and multivector quantization, which can't be decoded.
+    pub fn reconstruct_vector(&self, id: PointOffsetType) -> Option<Vec<VectorElementType>> {
+        match &self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::ScalarMmap(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::ScalarChunkedMmap(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::PQRam(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::PQMmap(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::PQChunkedMmap(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::RQRam(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::RQMmap(storage) => Some(storage.decode_vector(id)),
+            QuantizedVectorStorage::BinaryRam(_)
+            | QuantizedVectorStorage::BinaryMmap(_)
+            | QuantizedVectorStorage::BinaryChunkedMmap(_)
+            | QuantizedVectorStorage::ScalarRamMulti(_)
+            | QuantizedVectorStorage::ScalarMmapMulti(_)
+            | QuantizedVectorStorage::ScalarChunkedMmapMulti(_)
+            | QuantizedVectorStorage::PQRamMulti(_)
+            | QuantizedVectorStorage::PQMmapMulti(_)
+            | QuantizedVectorStorage::PQChunkedMmapMulti(_)
+            | QuantizedVectorStorage::RQRamMulti(_)
+            | QuantizedVectorStorage::RQMmapMulti(_)
+            | QuantizedVectorStorage::BinaryRamMulti(_)
+            | QuantizedVectorStorage::BinaryMmapMulti(_)
+            | QuantizedVectorStorage::BinaryChunkedMmapMulti(_) => None,
+        }
+    }
+
     pub fn raw_scorer<'a>(
         &'a self,
         query: QueryVector,
         hardware_counter: HardwareCounterCell,
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+        // Vectors of variable dimension are quantized padded with zeros, and so must be queries,
+        // made of stored vectors
+        let dim = self.config.vector_parameters.dim;
+        let query = match query {
+            QueryVector::Nearest(VectorInternal::Dense(mut vector)) if vector.len() < dim => {
+                vector.resize(dim, 0.0);
+                QueryVector::Nearest(VectorInternal::Dense(vector))
+            }
+            query => query,
+        };
         QuantizedScorerBuilder::new(
             &self.storage_impl,
             &self.config.quantization_config,
@@ -341,6 +522,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQChunkedMmap(storage) => {
                 build(point_id, storage, hardware_counter)
             }
+            QuantizedVectorStorage::RQRam(storage) => build(point_id, storage, hardware_counter),
+            QuantizedVectorStorage::RQMmap(storage) => build(point_id, storage, hardware_counter),
             QuantizedVectorStorage::BinaryRam(storage) => {
                 build(point_id, storage, hardware_counter)
             }
@@ -368,6 +551,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQChunkedMmapMulti(storage) => {
                 build(point_id, storage, hardware_counter)
             }
+            QuantizedVectorStorage::RQRamMulti(storage) => {
+                build(point_id, storage, hardware_counter)
+            }
+            QuantizedVectorStorage::RQMmapMulti(storage) => {
+                build(point_id, storage, hardware_counter)
+            }
             QuantizedVectorStorage::BinaryRamMulti(storage) => {
                 build(point_id, storage, hardware_counter)
             }
@@ -410,6 +599,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(q) => q.files(),
             QuantizedVectorStorage::PQMmap(q) => q.files(),
             QuantizedVectorStorage::PQChunkedMmap(q) => q.files(),
+            QuantizedVectorStorage::RQRam(q) => q.files(),
+            QuantizedVectorStorage::RQMmap(q) => q.files(),
             QuantizedVectorStorage::BinaryRam(q) => q.files(),
             QuantizedVectorStorage::BinaryMmap(q) => q.files(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.files(),
@@ -419,6 +610,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRamMulti(q) => q.files(),
             QuantizedVectorStorage::PQMmapMulti(q) => q.files(),
             QuantizedVectorStorage::PQChunkedMmapMulti(q) => q.files(),
+            QuantizedVectorStorage::RQRamMulti(q) => q.files(),
+            QuantizedVectorStorage::RQMmapMulti(q) => q.files(),
             QuantizedVectorStorage::BinaryRamMulti(q) => q.files(),
             QuantizedVectorStorage::BinaryMmapMulti(q) => q.files(),
             QuantizedVectorStorage::BinaryChunkedMmapMulti(q) => q.files(),
@@ -435,6 +628,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(q) => q.immutable_files(),
             QuantizedVectorStorage::PQMmap(q) => q.immutable_files(),
             QuantizedVectorStorage::PQChunkedMmap(q) => q.immutable_files(),
+            QuantizedVectorStorage::RQRam(q) => q.immutable_files(),
+            QuantizedVectorStorage::RQMmap(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryRam(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryMmap(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.immutable_files(),
@@ -444,6 +639,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRamMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::PQMmapMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::PQChunkedMmapMulti(q) => q.immutable_files(),
+            QuantizedVectorStorage::RQRamMulti(q) => q.immutable_files(),
+            QuantizedVectorStorage::RQMmapMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryRamMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryMmapMulti(q) => q.immutable_files(),
             QuantizedVectorStorage::BinaryChunkedMmapMulti(q) => q.immutable_files(),
@@ -538,6 +735,22 @@ impl QuantizedVectors {
                 max_threads,
                 stopped,
             ),
+            VectorStorageEnum::DenseMemmapDouble(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => Self::create_impl(
                 v.as_ref(),
@@ -589,10 +802,59 @@ impl QuantizedVectors {
                 max_threads,
                 stopped,
             ),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseQuantized(v) => Self::create_impl(
+                v.as_ref(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
+            VectorStorageEnum::DenseVersioned(v) => Self::create(
+                v.inner(),
+                quantization_config,
+                storage_type,
+                path,
+                max_threads,
+                stopped,
+            ),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
             VectorStorageEnum::SparseVolatile(_) => Err(OperationError::WrongSparse),
             VectorStorageEnum::SparseMmap(_) => Err(OperationError::WrongSparse),
+            VectorStorageEnum::SparseAppendableWal(_) => Err(OperationError::WrongSparse),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => Self::create_multi_impl(
                 v,
@@ -670,6 +932,9 @@ impl QuantizedVectors {
                 max_threads,
                 stopped,
             ),
+            VectorStorageEnum::Tombstone(v) => {
+                Err(OperationError::vector_name_removed(v.vector_name()))
+            }
         }
     }
 
@@ -730,6 +995,7 @@ impl QuantizedVectors {
                     &vector_parameters,
                     count,
                     pq_config,
+                    None,
                     storage_type,
                     path,
                     on_disk_vector_storage,
@@ -737,6 +1003,33 @@ impl QuantizedVectors {
                     stopped,
                 )?
             }
+            QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                Self::create_pq(
+                    vectors,
+                    &vector_parameters,
+                    count,
+                    &Self::anisotropic_pq_config(anisotropic),
+                    Some(anisotropic.parallel_weight()),
+                    storage_type,
+                    path,
+                    on_disk_vector_storage,
+                    max_threads,
+                    stopped,
+                )?
+            }
+            QuantizationConfig::Residual(ResidualQuantization {
+                residual: rq_config,
+            }) => Self::create_rq(
+                vectors,
+                &vector_parameters,
+                count,
+                rq_config,
+                storage_type,
+                path,
+                on_disk_vector_storage,
+                max_threads,
+                stopped,
+            )?,
             QuantizationConfig::Binary(BinaryQuantization {
                 binary: binary_config,
             }) => Self::create_binary(
@@ -847,6 +1140,7 @@ impl QuantizedVectors {
                     vectors_count,
                     inner_vectors_count,
                     pq_config,
+                    None,
                     storage_type,
                     multi_vector_config,
                     path,
@@ -855,6 +1149,39 @@ impl QuantizedVectors {
                     stopped,
                 )?
             }
+            QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                Self::create_pq_multi(
+                    vectors,
+                    offsets,
+                    &vector_parameters,
+                    vectors_count,
+                    inner_vectors_count,
+                    &Self::anisotropic_pq_config(anisotropic),
+                    Some(anisotropic.parallel_weight()),
+                    storage_type,
+                    multi_vector_config,
+                    path,
+                    on_disk_vector_storage,
+                    max_threads,
+                    stopped,
+                )?
+            }
+            QuantizationConfig::Residual(ResidualQuantization {
+                residual: rq_config,
+            }) => Self::create_rq_multi(
+                vectors,
+                offsets,
+                &vector_parameters,
+                vectors_count,
+                inner_vectors_count,
+                rq_config,
+                storage_type,
+                multi_vector_config,
+                path,
+                on_disk_vector_storage,
+                max_threads,
+                stopped,
+            )?,
             QuantizationConfig::Binary(BinaryQuantization {
                 binary: binary_config,
             }) => Self::create_binary_multi(
@@ -918,6 +1245,43 @@ impl QuantizedVectors {
         Ok(Some(quantized_vectors))
     }
 
+    /// Directory next to quantized vectors in `path`, where they are retrained
+    pub fn get_requantize_path(path: &Path) -> PathBuf {
+        path.join(QUANTIZED_REQUANTIZE_PATH)
+    }
+
+    /// Move quantized vectors, retrained in [`Self::get_requantize_path`], in place of quantized
+    /// vectors in `path`.
+    ///
+    /// Retraining is complete once its config is saved, so the config is moved last. If the move
+    /// was interrupted, it is resumed. Incomplete retraining is discarded.
+    pub fn finish_requantization(path: &Path) -> OperationResult<()> {
+        let requantize_path = Self::get_requantize_path(path);
+        if !requantize_path.exists() {
+            return Ok(());
+        }
+
+        let config_path = Self::get_config_path(&requantize_path);
+        if config_path.exists() {
+            for entry in fs::read_dir(&requantize_path)? {
+                let entry = entry?;
+                let from = entry.path();
+                if from == config_path {
+                    continue;
+                }
+                let to = path.join(entry.file_name());
+                if to.is_dir() {
+                    fs::remove_dir_all(&to)?;
+                }
+                fs::rename(&from, &to)?;
+            }
+            fs::rename(&config_path, Self::get_config_path(path))?;
+        }
+
+        fs::remove_dir_all(&requantize_path)?;
+        Ok(())
+    }
+
     pub fn load_impl(
         config: QuantizedVectorsConfig,
         vector_storage: &VectorStorageEnum,
@@ -939,6 +1303,24 @@ impl QuantizedVectors {
                 QuantizationConfig::Product(ProductQuantization { product }) => {
                     Self::load_pq_multi(vector_storage, path, &config, product, multivector_config)?
                 }
+                QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                    Self::load_pq_multi(
+                        vector_storage,
+                        path,
+                        &config,
+                        &Self::anisotropic_pq_config(anisotropic),
+                        multivector_config,
+                    )?
+                }
+                QuantizationConfig::Residual(ResidualQuantization { residual }) => {
+                    Self::load_rq_multi(
+                        vector_storage,
+                        path,
+                        &config,
+                        residual,
+                        multivector_config,
+                    )?
+                }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     Self::load_binary_multi(
                         vector_storage,
@@ -952,10 +1334,21 @@ impl QuantizedVectors {
         } else {
             match &config.quantization_config {
                 QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
-                    Self::load_scalar(vector_storage, path, &config, scalar)?
+                    Self::load_scalar(vector_storage.is_on_disk(), path, &config, scalar)?
                 }
                 QuantizationConfig::Product(ProductQuantization { product }) => {
-                    Self::load_pq(vector_storage, path, &config, product)?
+                    Self::load_pq(vector_storage.is_on_disk(), path, &config, product)?
+                }
+                QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                    Self::load_pq(
+                        vector_storage.is_on_disk(),
+                        path,
+                        &config,
+                        &Self::anisotropic_pq_config(anisotropic),
+                    )?
+                }
+                QuantizationConfig::Residual(ResidualQuantization { residual }) => {
+                    Self::load_rq(vector_storage.is_on_disk(), path, &config, residual)?
                 }
                 QuantizationConfig::Binary(BinaryQuantization { binary }) => {
                     Self::load_binary(vector_storage, path, &config, binary)?
@@ -974,8 +1367,52 @@ impl QuantizedVectors {
         })
     }
 
+    /// Load scalar, product or residual quantized vectors, which are used as the only storage of vectors.
+    ///
+    /// Original vectors are not available, so vectors can only be reconstructed approximately with
+    /// [`Self::reconstruct_vector`].
+    pub fn load_without_originals(
+        path: &Path,
+        distance: Distance,
+        datatype: VectorStorageDatatype,
+    ) -> OperationResult<Self> {
+        let config: QuantizedVectorsConfig = read_json(&Self::get_config_path(path))?;
+        let quantized_store = match &config.quantization_config {
+            QuantizationConfig::Scalar(ScalarQuantization { scalar }) => {
+                Self::load_scalar(true, path, &config, scalar)?
+            }
+            QuantizationConfig::Product(ProductQuantization { product }) => {
+                Self::load_pq(true, path, &config, product)?
+            }
+            QuantizationConfig::Anisotropic(AnisotropicQuantization { anisotropic }) => {
+                Self::load_pq(
+                    true,
+                    path,
+                    &config,
+                    &Self::anisotropic_pq_config(anisotropic),
+                )?
+            }
+            QuantizationConfig::Residual(ResidualQuantization { residual }) => {
+                Self::load_rq(true, path, &config, residual)?
+            }
+            QuantizationConfig::Binary(_) => {
+                return Err(OperationError::service_error(
+                    "Vectors can't be reconstructed from binary quantized data",
+                ));
+            }
+        };
+
+        Ok(QuantizedVectors {
+            storage_impl: quantized_store,
+            config,
+            path: path.to_path_buf(),
+            distance,
+            datatype,
+        })
+    }
+
     fn load_scalar(
-        vector_storage: &VectorStorageEnum,
+        on_disk_vector_storage: bool,
         path: &Path,
         config: &QuantizedVectorsConfig,
         scalar_config: &ScalarQuantizationConfig,
@@ -986,13 +1423,13 @@ impl QuantizedVectors {
             ));
         }
 
-        let on_disk_vector_storage = vector_storage.is_on_disk();
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
         if Self::is_ram(scalar_config.always_ram, on_disk_vector_storage) {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedRamStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
+                    Self::convert_scalar_encoding(scalar_config.r#type),
                 );
             let quantized_vectors_storage =
                 QuantizedRamStorage::from_file(data_path.as_path(), quantized_vector_size)?;
@@ -1004,6 +1441,7 @@ impl QuantizedVectors {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
+                    Self::convert_scalar_encoding(scalar_config.r#type),
                 );
             let quantized_vectors_storage =
                 QuantizedMmapStorage::from_file(data_path.as_path(), quantized_vector_size)?;
@@ -1035,6 +1473,7 @@ impl QuantizedVectors {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedRamStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
+                    Self::convert_scalar_encoding(scalar_config.r#type),
                 );
             let inner_vectors_storage =
                 QuantizedRamStorage::from_file(data_path.as_path(), quantized_vector_size)?;
@@ -1052,6 +1491,7 @@ impl QuantizedVectors {
             let quantized_vector_size =
                 EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(
                     &config.vector_parameters,
+                    Self::convert_scalar_encoding(scalar_config.r#type),
                 );
             let inner_vectors_storage =
                 QuantizedMmapStorage::from_file(data_path.as_path(), quantized_vector_size)?;
@@ -1069,7 +1509,7 @@ impl QuantizedVectors {
     }
 
     fn load_pq(
-        vector_storage: &VectorStorageEnum,
+        on_disk_vector_storage: bool,
         path: &Path,
         config: &QuantizedVectorsConfig,
         pq_config: &ProductQuantizationConfig,
@@ -1080,7 +1520,6 @@ impl QuantizedVectors {
             ));
         }
 
-        let on_disk_vector_storage = vector_storage.is_on_disk();
         let data_path = Self::get_data_path(path, config.storage_type);
         let meta_path = Self::get_meta_path(path);
         if Self::is_ram(pq_config.always_ram, on_disk_vector_storage) {
@@ -1170,6 +1609,109 @@ impl QuantizedVectors {
         }
     }
 
+    fn load_rq(
+        on_disk_vector_storage: bool,
+        path: &Path,
+        config: &QuantizedVectorsConfig,
+        rq_config: &ResidualQuantizationConfig,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !config.storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable quantized storage is not supported for Residual Quantization",
+            ));
+        }
+
+        let data_path = Self::get_data_path(path, config.storage_type);
+        let meta_path = Self::get_meta_path(path);
+        let chunk_size = Self::get_rq_chunk_size(rq_config);
+        if Self::is_ram(rq_config.always_ram, on_disk_vector_storage) {
+            let quantized_vector_size =
+                EncodedVectorsRQ::<QuantizedRamStorage>::get_quantized_vector_size(
+                    &config.vector_parameters,
+                    chunk_size,
+                    rq_config.stages,
+                );
+            let quantized_vectors_storage =
+                QuantizedRamStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            Ok(QuantizedVectorStorage::RQRam(EncodedVectorsRQ::load(
+                quantized_vectors_storage,
+                &meta_path,
+            )?))
+        } else {
+            let quantized_vector_size =
+                EncodedVectorsRQ::<QuantizedMmapStorage>::get_quantized_vector_size(
+                    &config.vector_parameters,
+                    chunk_size,
+                    rq_config.stages,
+                );
+            let quantized_vectors_storage =
+                QuantizedMmapStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            Ok(QuantizedVectorStorage::RQMmap(EncodedVectorsRQ::load(
+                quantized_vectors_storage,
+                &meta_path,
+            )?))
+        }
+    }
+
+    fn load_rq_multi(
+        vector_storage: &VectorStorageEnum,
+        path: &Path,
+        config: &QuantizedVectorsConfig,
+        rq_config: &ResidualQuantizationConfig,
+        multivector_config: &MultiVectorConfig,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !config.storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable quantized multivector storage is not supported for Residual Quantization",
+            ));
+        }
+
+        let on_disk_vector_storage = vector_storage.is_on_disk();
+        let data_path = Self::get_data_path(path, config.storage_type);
+        let meta_path = Self::get_meta_path(path);
+        let offsets_path = Self::get_offsets_path(path, config.storage_type);
+        let chunk_size = Self::get_rq_chunk_size(rq_config);
+        if Self::is_ram(rq_config.always_ram, on_disk_vector_storage) {
+            let quantized_vector_size =
+                EncodedVectorsRQ::<QuantizedRamStorage>::get_quantized_vector_size(
+                    &config.vector_parameters,
+                    chunk_size,
+                    rq_config.stages,
+                );
+            let inner_vectors_storage =
+                QuantizedRamStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            let inner_vectors_storage = EncodedVectorsRQ::load(inner_vectors_storage, &meta_path)?;
+            let offsets = MultivectorOffsetsStorageRam::load(&offsets_path)?;
+            Ok(QuantizedVectorStorage::RQRamMulti(
+                QuantizedMultivectorStorage::new(
+                    config.vector_parameters.dim,
+                    inner_vectors_storage,
+                    offsets,
+                    *multivector_config,
+                ),
+            ))
+        } else {
+            let quantized_vector_size =
+                EncodedVectorsRQ::<QuantizedMmapStorage>::get_quantized_vector_size(
+                    &config.vector_parameters,
+                    chunk_size,
+                    rq_config.stages,
+                );
+            let inner_vectors_storage =
+                QuantizedMmapStorage::from_file(data_path.as_path(), quantized_vector_size)?;
+            let inner_vectors_storage = EncodedVectorsRQ::load(inner_vectors_storage, &meta_path)?;
+            let offsets = MultivectorOffsetsStorageMmap::load(&offsets_path)?;
+            Ok(QuantizedVectorStorage::RQMmapMulti(
+                QuantizedMultivectorStorage::new(
+                    config.vector_parameters.dim,
+                    inner_vectors_storage,
+                    offsets,
+                    *multivector_config,
+                ),
+            ))
+        }
+    }
+
     fn load_binary(
         vector_storage: &VectorStorageEnum,
         path: &Path,
@@ -1327,7 +1869,10 @@ impl QuantizedVectors {
 
         let encoding = Self::convert_scalar_encoding(scalar_config.r#type);
         let quantized_vector_size =
-            EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
+            EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
         let in_ram = Self::is_ram(scalar_config.always_ram, on_disk_vector_storage);
@@ -1390,7 +1935,10 @@ impl QuantizedVectors {
 
         let encoding = Self::convert_scalar_encoding(scalar_config.r#type);
         let quantized_vector_size =
-            EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(vector_parameters);
+            EncodedVectorsU8::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                encoding,
+            );
         let meta_path = Self::get_meta_path(path);
         let data_path = Self::get_data_path(path, storage_type);
         let offsets_path = Self::get_offsets_path(path, storage_type);
@@ -1449,12 +1997,68 @@ impl QuantizedVectors {
         }
     }
 
+    /// Anisotropic quantization is product quantization with differently trained codebooks,
+    /// encoded vectors are stored and scored the same way
+    fn anisotropic_pq_config(
+        anisotropic_config: &AnisotropicQuantizationConfig,
+    ) -> ProductQuantizationConfig {
+        let AnisotropicQuantizationConfig {
+            compression,
+            parallel_weight: _,
+            always_ram,
+        } = anisotropic_config;
+        ProductQuantizationConfig {
+            compression: *compression,
+            always_ram: *always_ram,
+            drop_originals: None,
+        }
+    }
+
+    /// Encode vectors with product quantization, with anisotropic loss if `parallel_weight` is set
+    #[allow(clippy::too_many_arguments)]
+    fn encode_pq<'a, TStorage: EncodedStorage>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
+        storage_builder: impl EncodedStorageBuilder<Storage = TStorage> + Send,
+        vector_parameters: &quantization::VectorParameters,
+        vectors_count: usize,
+        bucket_size: usize,
+        parallel_weight: Option<f32>,
+        max_threads: usize,
+        meta_path: Option<&Path>,
+        stopped: &AtomicBool,
+    ) -> Result<EncodedVectorsPQ<TStorage>, EncodingError> {
+        match parallel_weight {
+            Some(parallel_weight) => EncodedVectorsPQ::encode_anisotropic(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                vectors_count,
+                bucket_size,
+                parallel_weight,
+                max_threads,
+                meta_path,
+                stopped,
+            ),
+            None => EncodedVectorsPQ::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                vectors_count,
+                bucket_size,
+                max_threads,
+                meta_path,
+                stopped,
+            ),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_pq<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
         vector_parameters: &quantization::VectorParameters,
         vectors_count: usize,
         pq_config: &ProductQuantizationConfig,
+        parallel_weight: Option<f32>,
         storage_type: QuantizedVectorsStorageType,
         path: &Path,
         on_disk_vector_storage: bool,
@@ -1482,12 +2086,13 @@ impl QuantizedVectors {
                 vectors_count,
                 quantized_vector_size,
             )?;
-            Ok(QuantizedVectorStorage::PQRam(EncodedVectorsPQ::encode(
+            Ok(QuantizedVectorStorage::PQRam(Self::encode_pq(
                 vectors,
                 storage_builder,
                 vector_parameters,
                 vectors_count,
                 bucket_size,
+                parallel_weight,
                 max_threads,
                 Some(meta_path.as_path()),
                 stopped,
@@ -1498,12 +2103,13 @@ impl QuantizedVectors {
                 vectors_count,
                 quantized_vector_size,
             )?;
-            Ok(QuantizedVectorStorage::PQMmap(EncodedVectorsPQ::encode(
+            Ok(QuantizedVectorStorage::PQMmap(Self::encode_pq(
                 vectors,
                 storage_builder,
                 vector_parameters,
                 vectors_count,
                 bucket_size,
+                parallel_weight,
                 max_threads,
                 Some(meta_path.as_path()),
                 stopped,
@@ -1519,6 +2125,7 @@ impl QuantizedVectors {
         vectors_count: usize,
         inner_vectors_count: usize,
         pq_config: &ProductQuantizationConfig,
+        parallel_weight: Option<f32>,
         storage_type: QuantizedVectorsStorageType,
         multi_vector_config: MultiVectorConfig,
         path: &Path,
@@ -1548,12 +2155,13 @@ impl QuantizedVectors {
                 inner_vectors_count,
                 quantized_vector_size,
             )?;
-            let quantized_storage = EncodedVectorsPQ::encode(
+            let quantized_storage = Self::encode_pq(
                 vectors,
                 storage_builder,
                 vector_parameters,
                 inner_vectors_count,
                 bucket_size,
+                parallel_weight,
                 max_threads,
                 Some(meta_path.as_path()),
                 stopped,
@@ -1573,12 +2181,13 @@ impl QuantizedVectors {
                 inner_vectors_count,
                 quantized_vector_size,
             )?;
-            let quantized_storage = EncodedVectorsPQ::encode(
+            let quantized_storage = Self::encode_pq(
                 vectors,
                 storage_builder,
                 vector_parameters,
                 inner_vectors_count,
                 bucket_size,
+                parallel_weight,
                 max_threads,
                 Some(meta_path.as_path()),
                 stopped,
@@ -1596,6 +2205,159 @@ impl QuantizedVectors {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    fn create_rq<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
+        vector_parameters: &quantization::VectorParameters,
+        vectors_count: usize,
+        rq_config: &ResidualQuantizationConfig,
+        storage_type: QuantizedVectorsStorageType,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable residual quantization is not supported",
+            ));
+        }
+
+        let chunk_size = Self::get_rq_chunk_size(rq_config);
+        let quantized_vector_size =
+            EncodedVectorsRQ::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                chunk_size,
+                rq_config.stages,
+            );
+        let meta_path = Self::get_meta_path(path);
+        let data_path = Self::get_data_path(path, storage_type);
+        let in_ram = Self::is_ram(rq_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let storage_builder = QuantizedRamStorageBuilder::new(
+                data_path.as_path(),
+                vectors_count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::RQRam(EncodedVectorsRQ::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                vectors_count,
+                chunk_size,
+                rq_config.stages,
+                max_threads,
+                Some(meta_path.as_path()),
+                stopped,
+            )?))
+        } else {
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                data_path.as_path(),
+                vectors_count,
+                quantized_vector_size,
+            )?;
+            Ok(QuantizedVectorStorage::RQMmap(EncodedVectorsRQ::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                vectors_count,
+                chunk_size,
+                rq_config.stages,
+                max_threads,
+                Some(meta_path.as_path()),
+                stopped,
+            )?))
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_rq_multi<'a>(
+        vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone + Send,
+        offsets: impl Iterator<Item = MultivectorOffset>,
+        vector_parameters: &quantization::VectorParameters,
+        vectors_count: usize,
+        inner_vectors_count: usize,
+        rq_config: &ResidualQuantizationConfig,
+        storage_type: QuantizedVectorsStorageType,
+        multi_vector_config: MultiVectorConfig,
+        path: &Path,
+        on_disk_vector_storage: bool,
+        max_threads: usize,
+        stopped: &AtomicBool,
+    ) -> OperationResult<QuantizedVectorStorage> {
+        if !storage_type.is_immutable() {
+            return Err(OperationError::service_error(
+                "Mutable residual quantization is not supported",
+            ));
+        }
+
+        let chunk_size = Self::get_rq_chunk_size(rq_config);
+        let quantized_vector_size =
+            EncodedVectorsRQ::<QuantizedMmapStorage>::get_quantized_vector_size(
+                vector_parameters,
+                chunk_size,
+                rq_config.stages,
+            );
+        let meta_path = Self::get_meta_path(path);
+        let data_path = Self::get_data_path(path, storage_type);
+        let offsets_path = Self::get_offsets_path(path, storage_type);
+        let in_ram = Self::is_ram(rq_config.always_ram, on_disk_vector_storage);
+        if in_ram {
+            let storage_builder = QuantizedRamStorageBuilder::new(
+                data_path.as_path(),
+                inner_vectors_count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsRQ::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                inner_vectors_count,
+                chunk_size,
+                rq_config.stages,
+                max_threads,
+                Some(meta_path.as_path()),
+                stopped,
+            )?;
+            let offsets = MultivectorOffsetsStorageRam::create(&offsets_path, offsets)?;
+            Ok(QuantizedVectorStorage::RQRamMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets,
+                    multi_vector_config,
+                ),
+            ))
+        } else {
+            let storage_builder = QuantizedMmapStorageBuilder::new(
+                data_path.as_path(),
+                inner_vectors_count,
+                quantized_vector_size,
+            )?;
+            let quantized_storage = EncodedVectorsRQ::encode(
+                vectors,
+                storage_builder,
+                vector_parameters,
+                inner_vectors_count,
+                chunk_size,
+                rq_config.stages,
+                max_threads,
+                Some(meta_path.as_path()),
+                stopped,
+            )?;
+            let offsets =
+                MultivectorOffsetsStorageMmap::create(&offsets_path, offsets, vectors_count)?;
+            Ok(QuantizedVectorStorage::RQMmapMulti(
+                QuantizedMultivectorStorage::new(
+                    vector_parameters.dim,
+                    quantized_storage,
+                    offsets,
+                    multi_vector_config,
+                ),
+            ))
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn create_binary<'a>(
         vectors: impl Iterator<Item = impl AsRef<[VectorElementType]> + 'a> + Clone,
@@ -1814,6 +2576,9 @@ impl QuantizedVectors {
             Some(BinaryQuantizationQueryEncoding::Scalar8Bits) => {
                 quantization::encoded_vectors_binary::QueryEncoding::Scalar8bits
             }
+            Some(BinaryQuantizationQueryEncoding::Float32) => {
+                quantization::encoded_vectors_binary::QueryEncoding::Float32
+            }
             Some(BinaryQuantizationQueryEncoding::Binary) => {
                 quantization::encoded_vectors_binary::QueryEncoding::SameAsStorage
             }
@@ -1827,6 +2592,7 @@ impl QuantizedVectors {
     fn convert_scalar_encoding(encoding: ScalarType) -> ScalarQuantizationMethod {
         match encoding {
             ScalarType::Int8 => ScalarQuantizationMethod::Int8,
+            ScalarType::Int4 => ScalarQuantizationMethod::Int4,
         }
     }
 
@@ -1862,6 +2628,12 @@ impl QuantizedVectors {
         }
     }
 
+    /// Each stage takes one byte per chunk, so chunks are `stages` times larger than
+    /// product quantization buckets to keep the same total compression
+    fn get_rq_chunk_size(rq_config: &ResidualQuantizationConfig) -> usize {
+        Self::get_bucket_size(rq_config.compression) * rq_config.stages
+    }
+
     pub fn get_storage(&self) -> &QuantizedVectorStorage {
         &self.storage_impl
     }
@@ -1874,6 +2646,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(_) => {}
             QuantizedVectorStorage::PQMmap(storage) => storage.storage().populate(),
             QuantizedVectorStorage::PQChunkedMmap(storage) => storage.storage().populate()?,
+            QuantizedVectorStorage::RQRam(_) => {}
+            QuantizedVectorStorage::RQMmap(storage) => storage.storage().populate(),
             QuantizedVectorStorage::BinaryRam(_) => {}
             QuantizedVectorStorage::BinaryMmap(storage) => storage.storage().populate(),
             QuantizedVectorStorage::BinaryChunkedMmap(storage) => storage.storage().populate()?,
@@ -1895,6 +2669,11 @@ impl QuantizedVectors {
                 storage.storage().storage().populate()?;
                 storage.offsets_storage().populate()?;
             }
+            QuantizedVectorStorage::RQRamMulti(_) => {}
+            QuantizedVectorStorage::RQMmapMulti(storage) => {
+                storage.storage().storage().populate();
+                storage.offsets_storage().populate()?;
+            }
             QuantizedVectorStorage::BinaryRamMulti(_) => {}
             QuantizedVectorStorage::BinaryMmapMulti(storage) => {
                 storage.storage().storage().populate();
@@ -1923,6 +2702,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRam(q) => q.flusher(),
             QuantizedVectorStorage::PQMmap(q) => q.flusher(),
             QuantizedVectorStorage::PQChunkedMmap(q) => q.flusher(),
+            QuantizedVectorStorage::RQRam(q) => q.flusher(),
+            QuantizedVectorStorage::RQMmap(q) => q.flusher(),
             QuantizedVectorStorage::BinaryRam(q) => q.flusher(),
             QuantizedVectorStorage::BinaryMmap(q) => q.flusher(),
             QuantizedVectorStorage::BinaryChunkedMmap(q) => q.flusher(),
@@ -1932,6 +2713,8 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQRamMulti(q) => q.flusher(),
             QuantizedVectorStorage::PQMmapMulti(q) => q.flusher(),
             QuantizedVectorStorage::PQChunkedMmapMulti(q) => q.flusher(),
+            QuantizedVectorStorage::RQRamMulti(q) => q.flusher(),
+            QuantizedVectorStorage::RQMmapMulti(q) => q.flusher(),
             QuantizedVectorStorage::BinaryRamMulti(q) => q.flusher(),
             QuantizedVectorStorage::BinaryMmapMulti(q) => q.flusher(),
             QuantizedVectorStorage::BinaryChunkedMmapMulti(q) => q.flusher(),
@@ -1964,6 +2747,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQChunkedMmap(q) => {
                 Self::upsert_vector_dense(q, id, vector, hw_counter)
             }
+            QuantizedVectorStorage::RQRam(q) => {
+                Self::upsert_vector_dense(q, id, vector, hw_counter)
+            }
+            QuantizedVectorStorage::RQMmap(q) => {
+                Self::upsert_vector_dense(q, id, vector, hw_counter)
+            }
             QuantizedVectorStorage::BinaryRam(q) => {
                 Self::upsert_vector_dense(q, id, vector, hw_counter)
             }
@@ -1991,6 +2780,12 @@ impl QuantizedVectors {
             QuantizedVectorStorage::PQChunkedMmapMulti(q) => {
                 Self::upsert_vector_multi(q, id, vector, hw_counter)
             }
+            QuantizedVectorStorage::RQRamMulti(q) => {
+                Self::upsert_vector_multi(q, id, vector, hw_counter)
+            }
+            QuantizedVectorStorage::RQMmapMulti(q) => {
+                Self::upsert_vector_multi(q, id, vector, hw_counter)
+            }
             QuantizedVectorStorage::BinaryRamMulti(q) => {
                 Self::upsert_vector_multi(q, id, vector, hw_counter)
             }
@@ -0,0 +1,203 @@
+//! Scoring of int4 vectors without dequantizing them.
+//!
+//! Stored element `q` stands for `scale * (q - zero_point)`, with `scale` and `zero_point` of its
+//! vector. For dot product, and cosine over normalized vectors, the params are applied once per
+//! vector:
+//!
+//! `sum(x_i * scale * (q_i - zero_point)) = scale * (sum(x_i * q_i) - zero_point * sum(x_i))`
+//!
+//! where `sum(x_i)` of the query is computed in advance. Distances, which can't be factored like
+//! that, dequantize elements one by one while unpacking, without allocating a float vector.
+
+use std::marker::PhantomData;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::Random;
+use common::typelevel::False;
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::types::Distance;
+use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::dense::appendable_int4_dense_vector_storage::{
+    AppendableMmapInt4DenseVectorStorage, Int4QuantizationParams, packed_len, unpack_int4,
+};
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::{DenseVectorStorage, VectorStorage};
+
+pub struct Int4QueryScorer<'a, TMetric: Metric<VectorElementType>> {
+    vector_storage: &'a AppendableMmapInt4DenseVectorStorage,
+    /// Preprocessed query, padded with a zero to an even length
+    query: DenseVector,
+    /// Sum of query elements, to apply zero-points of stored vectors
+    query_sum: f32,
+    dim: usize,
+    metric: PhantomData<TMetric>,
+    hardware_counter: HardwareCounterCell,
+}
+
+impl<'a, TMetric: Metric<VectorElementType>> Int4QueryScorer<'a, TMetric> {
+    pub fn new(
+        query: DenseVector,
+        vector_storage: &'a AppendableMmapInt4DenseVectorStorage,
+        mut hardware_counter: HardwareCounterCell,
+    ) -> Self {
+        let dim = query.len();
+        let mut query = TMetric::preprocess(query);
+        let query_sum = query.iter().sum();
+        query.resize(packed_len(dim) * 2, 0.0);
+
+        hardware_counter.set_cpu_multiplier(dim);
+        if vector_storage.is_on_disk() {
+            hardware_counter.set_vector_io_read_multiplier(packed_len(dim));
+        } else {
+            hardware_counter.set_vector_io_read_multiplier(0);
+        }
+
+        Self {
+            vector_storage,
+            query,
+            query_sum,
+            dim,
+            metric: PhantomData,
+            hardware_counter,
+        }
+    }
+
+    fn score_packed(&self, packed: &[u8], params: Int4QuantizationParams) -> ScoreType {
+        let Int4QuantizationParams { scale, zero_point } = params;
+        match TMetric::distance() {
+            Distance::Cosine | Distance::Dot => {
+                let dot: f32 = packed
+                    .iter()
+                    .zip(self.query.chunks_exact(2))
+                    .map(|(&byte, pair)| {
+                        pair[0] * f32::from(byte & 0x0F) + pair[1] * f32::from(byte >> 4)
+                    })
+                    .sum();
+                scale * (dot - zero_point * self.query_sum)
+            }
+            Distance::Euclid => -unpack_int4(packed, self.dim)
+                .zip(&self.query)
+                .map(|(q, x)| (x - scale * (f32::from(q) - zero_point)).powi(2))
+                .sum::<ScoreType>(),
+            Distance::Manhattan => -unpack_int4(packed, self.dim)
+                .zip(&self.query)
+                .map(|(q, x)| (x - scale * (f32::from(q) - zero_point)).abs())
+                .sum::<ScoreType>(),
+        }
+    }
+}
+
+impl<TMetric: Metric<VectorElementType>> QueryScorer for Int4QueryScorer<'_, TMetric> {
+    type TVector = [VectorElementType];
+
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        self.hardware_counter.vector_io_read().incr();
+        let packed = self
+            .vector_storage
+            .get_packed::<Random>(idx)
+            .expect("mmap vector not found");
+        let params = self
+            .vector_storage
+            .get_params::<Random>(idx)
+            .expect("quantization params not found");
+        self.score_packed(&packed, params)
+    }
+
+    fn score_stored_batch(&self, ids: &[PointOffsetType], scores: &mut [ScoreType]) {
+        debug_assert!(ids.len() <= VECTOR_READ_BATCH_SIZE);
+        debug_assert_eq!(ids.len(), scores.len());
+
+        self.hardware_counter.cpu_counter().incr_delta(ids.len());
+        self.hardware_counter.vector_io_read().incr_delta(ids.len());
+
+        self.vector_storage
+            .for_each_packed_in_batch(ids, |idx, packed, params| {
+                scores[idx] = self.score_packed(packed, params);
+            });
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementType]) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        TMetric::similarity(&self.query[..self.dim], v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        let v1 = self.vector_storage.get_dense::<Random>(point_a);
+        let v2 = self.vector_storage.get_dense::<Random>(point_b);
+        TMetric::similarity(&v1, &v2)
+    }
+
+    // Packed bytes don't carry quantization params of the vector
+    type SupportsBytes = False;
+    fn score_bytes(&self, enabled: Self::SupportsBytes, _: &[u8]) -> ScoreType {
+        match enabled {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::mmap::AdviceSetting;
+    use rand::prelude::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::data_types::vectors::VectorRef;
+    use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+    use crate::vector_storage::dense::appendable_int4_dense_vector_storage::open_appendable_memmap_int4_vector_storage_impl;
+
+    fn check_scores_match_dequantized<TMetric: Metric<VectorElementType>>() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let dim = 13;
+
+        let mut storage = open_appendable_memmap_int4_vector_storage_impl(
+            dir.path(),
+            dim,
+            TMetric::distance(),
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+        let num_vectors = 10;
+        for key in 0..num_vectors {
+            let vector: DenseVector = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+            let vector = TMetric::preprocess(vector);
+            storage
+                .insert_vector(key, VectorRef::from(&vector), &hw_counter)
+                .unwrap();
+        }
+
+        let query: DenseVector = (0..dim).map(|_| rng.random_range(-1.0..1.0)).collect();
+        let scorer =
+            Int4QueryScorer::<TMetric>::new(query.clone(), &storage, HardwareCounterCell::new());
+        let preprocessed = TMetric::preprocess(query);
+
+        let ids: Vec<PointOffsetType> = (0..num_vectors).collect();
+        let mut scores = vec![0.0; ids.len()];
+        scorer.score_stored_batch(&ids, &mut scores);
+
+        for (&id, &batch_score) in ids.iter().zip(&scores) {
+            let expected = TMetric::similarity(&preprocessed, &storage.get_dense::<Random>(id));
+            let score = scorer.score_stored(id);
+            assert!((score - expected).abs() < 1e-4, "{score} != {expected}");
+            assert_eq!(score, batch_score);
+        }
+    }
+
+    #[test]
+    fn test_int4_scores_match_dequantized() {
+        check_scores_match_dequantized::<DotProductMetric>();
+        check_scores_match_dequantized::<CosineMetric>();
+        check_scores_match_dequantized::<EuclidMetric>();
+        check_scores_match_dequantized::<ManhattanMetric>();
+    }
+}
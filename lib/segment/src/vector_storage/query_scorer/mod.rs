@@ -10,11 +10,13 @@ use crate::vector_storage::VectorOffset;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
 
 pub mod custom_query_scorer;
+pub mod int4_query_scorer;
 pub mod metric_query_scorer;
 pub mod multi_custom_query_scorer;
 pub mod multi_metric_query_scorer;
 pub mod sparse_custom_query_scorer;
 pub mod sparse_metric_query_scorer;
+pub mod variable_dim_query_scorer;
 
 pub trait QueryScorer {
     type TVector: ?Sized;
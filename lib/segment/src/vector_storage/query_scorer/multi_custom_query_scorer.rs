@@ -15,6 +15,7 @@ use crate::data_types::vectors::{
 use crate::spaces::metric::Metric;
 use crate::vector_storage::MultiVectorStorage;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::multi_dense::gather::with_gathered_multi_vector;
 use crate::vector_storage::query::{Query, TransformInto};
 use crate::vector_storage::query_scorer::QueryScorer;
 
@@ -142,7 +143,8 @@ impl<
             .incr_delta(total_loaded_vectors);
 
         for idx in 0..ids.len() {
-            scores[idx] = self.score_ref(vectors[idx].as_ref());
+            scores[idx] =
+                with_gathered_multi_vector(vectors[idx].as_ref(), |stored| self.score_ref(stored));
         }
     }
 
@@ -15,6 +15,7 @@ use crate::data_types::vectors::{
 use crate::spaces::metric::Metric;
 use crate::vector_storage::MultiVectorStorage;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::multi_dense::gather::with_gathered_multi_vector;
 use crate::vector_storage::query_scorer::QueryScorer;
 
 pub struct MultiMetricQueryScorer<
@@ -127,7 +128,8 @@ impl<
             .incr_delta(total_read);
 
         for idx in 0..ids.len() {
-            scores[idx] = self.score_ref(vectors[idx].as_ref());
+            scores[idx] =
+                with_gathered_multi_vector(vectors[idx].as_ref(), |stored| self.score_ref(stored));
         }
     }
 
@@ -0,0 +1,211 @@
+//! Scoring of vectors of variable dimension, stored padded with zeros.
+//!
+//! A stored vector of length `n` is compared with the first `n` elements of the query only, as if
+//! the query was truncated to `n` dimensions. The truncated query is preprocessed on its own, e.g.
+//! normalized for cosine similarity, so scores match those of a collection of `n`-dimensional
+//! vectors. Truncated queries are built lazily, once for every length of stored vectors.
+
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::generic_consts::Random;
+use common::typelevel::False;
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, TypedDenseVector};
+use crate::spaces::metric::Metric;
+use crate::vector_storage::DenseVectorStorage;
+use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::query_scorer::QueryScorer;
+
+pub struct VariableDimQueryScorer<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+> {
+    vector_storage: &'a TVectorStorage,
+    /// Query as given, before preprocessing
+    query: DenseVector,
+    /// Preprocessed truncated queries, indexed by their length
+    truncated_queries: Box<[OnceLock<TypedDenseVector<TElement>>]>,
+    metric: PhantomData<TMetric>,
+    hardware_counter: HardwareCounterCell,
+}
+
+impl<
+    'a,
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+> VariableDimQueryScorer<'a, TElement, TMetric, TVectorStorage>
+{
+    pub fn new(
+        query: DenseVector,
+        vector_storage: &'a TVectorStorage,
+        mut hardware_counter: HardwareCounterCell,
+    ) -> Self {
+        let dim = query.len();
+
+        hardware_counter.set_cpu_multiplier(dim * size_of::<TElement>());
+        if vector_storage.is_on_disk() {
+            hardware_counter.set_vector_io_read_multiplier(dim * size_of::<TElement>());
+        } else {
+            hardware_counter.set_vector_io_read_multiplier(0);
+        }
+
+        Self {
+            truncated_queries: (0..=dim).map(|_| OnceLock::new()).collect(),
+            query,
+            vector_storage,
+            metric: PhantomData,
+            hardware_counter,
+        }
+    }
+
+    /// Preprocessed query, truncated to `len` elements
+    fn truncated_query(&self, len: usize) -> &[TElement] {
+        let len = len.min(self.query.len());
+        self.truncated_queries[len].get_or_init(|| {
+            let preprocessed = TMetric::preprocess(self.query[..len].to_vec());
+            TElement::slice_from_float_cow(preprocessed.into()).into_owned()
+        })
+    }
+
+    fn score_prefix(&self, len: usize, vector: &[TElement]) -> ScoreType {
+        let query = self.truncated_query(len);
+        TMetric::similarity(query, &vector[..query.len()])
+    }
+}
+
+impl<
+    TElement: PrimitiveVectorElement,
+    TMetric: Metric<TElement>,
+    TVectorStorage: DenseVectorStorage<TElement>,
+> QueryScorer for VariableDimQueryScorer<'_, TElement, TMetric, TVectorStorage>
+{
+    type TVector = [TElement];
+
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        self.hardware_counter.vector_io_read().incr();
+        let len = self.vector_storage.stored_vector_len(idx);
+        self.score_prefix(len, &self.vector_storage.get_dense::<Random>(idx))
+    }
+
+    fn score_stored_batch(&self, ids: &[PointOffsetType], scores: &mut [ScoreType]) {
+        debug_assert!(ids.len() <= VECTOR_READ_BATCH_SIZE);
+        debug_assert_eq!(ids.len(), scores.len());
+
+        self.hardware_counter.cpu_counter().incr_delta(ids.len());
+        self.hardware_counter.vector_io_read().incr_delta(ids.len());
+
+        self.vector_storage
+            .for_each_in_dense_batch(ids, |idx, vector| {
+                let len = self.vector_storage.stored_vector_len(ids[idx]);
+                scores[idx] = self.score_prefix(len, vector);
+            });
+    }
+
+    /// Length of `v2` is unknown, it is compared as a vector of the full dimension
+    #[inline]
+    fn score(&self, v2: &[TElement]) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        self.score_prefix(v2.len(), v2)
+    }
+
+    /// Both vectors are compared over their common prefix, without normalizing it
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.hardware_counter.cpu_counter().incr();
+        let len = self
+            .vector_storage
+            .stored_vector_len(point_a)
+            .min(self.vector_storage.stored_vector_len(point_b));
+        let v1 = self.vector_storage.get_dense::<Random>(point_a);
+        let v2 = self.vector_storage.get_dense::<Random>(point_b);
+        TMetric::similarity(&v1[..len], &v2[..len])
+    }
+
+    // Bytes of a stored vector don't carry its length
+    type SupportsBytes = False;
+    fn score_bytes(&self, enabled: Self::SupportsBytes, _: &[u8]) -> ScoreType {
+        match enabled {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::mmap::AdviceSetting;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::data_types::vectors::{VectorElementType, VectorRef};
+    use crate::spaces::simple::{CosineMetric, EuclidMetric};
+    use crate::types::{Distance, VectorStorageDatatype};
+    use crate::vector_storage::dense::appendable_dense_vector_storage::open_appendable_memmap_variable_dim_vector_storage;
+    use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+    #[test]
+    fn test_score_stored_prefix() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+        let mut storage = open_appendable_memmap_variable_dim_vector_storage(
+            VectorStorageDatatype::Float32,
+            dir.path(),
+            4,
+            Distance::Cosine,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+
+        let full: DenseVector =
+            Distance::Cosine.preprocess_vector::<VectorElementType>(vec![1.0, 2.0, 3.0, 4.0]);
+        let short: DenseVector =
+            Distance::Cosine.preprocess_vector::<VectorElementType>(vec![1.0, 2.0]);
+        storage
+            .insert_vector(0, VectorRef::from(&full), &hw_counter)
+            .unwrap();
+        storage
+            .insert_vector(1, VectorRef::from(&short), &hw_counter)
+            .unwrap();
+
+        // Stored vectors are returned without padding
+        assert_eq!(
+            storage.get_vector::<Random>(1).as_vec_ref(),
+            VectorRef::from(&short)
+        );
+
+        let VectorStorageEnum::DenseAppendableMemmap(storage) = &storage else {
+            panic!("unexpected storage type");
+        };
+        assert!(storage.is_variable_dim());
+        assert_eq!(storage.stored_vector_len(0), 4);
+        assert_eq!(storage.stored_vector_len(1), 2);
+
+        // Query, which matches both vectors over their stored prefixes exactly
+        let scorer = VariableDimQueryScorer::<_, CosineMetric, _>::new(
+            vec![1.0, 2.0, 3.0, 4.0],
+            storage.as_ref(),
+            HardwareCounterCell::new(),
+        );
+        assert!((scorer.score_stored(0) - 1.0).abs() < 1e-5);
+        assert!((scorer.score_stored(1) - 1.0).abs() < 1e-5);
+
+        let mut scores = [0.0; 2];
+        scorer.score_stored_batch(&[0, 1], &mut scores);
+        assert_eq!(scores, [scorer.score_stored(0), scorer.score_stored(1)]);
+
+        // Padding is not counted as a difference
+        let scorer = VariableDimQueryScorer::<_, EuclidMetric, _>::new(
+            full.clone(),
+            storage.as_ref(),
+            HardwareCounterCell::new(),
+        );
+        let expected = EuclidMetric::similarity(&full[..2], &short);
+        assert!((scorer.score_stored(1) - expected).abs() < 1e-5);
+    }
+}
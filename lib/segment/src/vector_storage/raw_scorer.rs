@@ -12,7 +12,9 @@ use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::query_scorer::{QueryScorerBytes, QueryScorerBytesImpl};
-use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
+use super::{
+    DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorage, VectorStorageEnum,
+};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
@@ -22,11 +24,14 @@ use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::Distance;
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
+use crate::vector_storage::dense::appendable_int4_dense_vector_storage::AppendableMmapInt4DenseVectorStorage;
 use crate::vector_storage::query::NaiveFeedbackQuery;
 use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::query_scorer::int4_query_scorer::Int4QueryScorer;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::multi_metric_query_scorer::MultiMetricQueryScorer;
 use crate::vector_storage::query_scorer::sparse_metric_query_scorer::SparseMetricQueryScorer;
+use crate::vector_storage::query_scorer::variable_dim_query_scorer::VariableDimQueryScorer;
 use crate::vector_storage::sparse::volatile_sparse_vector_storage::VolatileSparseVectorStorage;
 
 pub trait RawScorer {
@@ -71,6 +76,8 @@ pub fn new_raw_scorer<'a>(
         VectorStorageEnum::DenseMemmap(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
         VectorStorageEnum::DenseMemmapByte(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
         VectorStorageEnum::DenseMemmapHalf(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
+        VectorStorageEnum::DenseMemmapDouble(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
+        VectorStorageEnum::DenseMemmapBfloat16(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
 
         #[cfg(target_os = "linux")]
         VectorStorageEnum::DenseUring(vs) => super::async_raw_scorer::new(query, vs, hc),
@@ -82,10 +89,23 @@ pub fn new_raw_scorer<'a>(
         VectorStorageEnum::DenseAppendableMemmap(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
         VectorStorageEnum::DenseAppendableMemmapByte(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
+        VectorStorageEnum::DenseAppendableMemmapDouble(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), hc)
+        }
+        VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => {
+            raw_scorer_impl(query, vs.as_ref(), hc)
+        }
+        VectorStorageEnum::DenseAppendableMemmapInt8(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
+        VectorStorageEnum::DenseAppendableMemmapInt4(vs) => {
+            raw_int4_scorer_impl(query, vs.as_ref(), hc)
+        }
+        VectorStorageEnum::DenseQuantized(vs) => raw_scorer_impl(query, vs.as_ref(), hc),
+        VectorStorageEnum::DenseVersioned(vs) => new_raw_scorer(query, vs.inner(), hc),
         #[cfg(feature = "rocksdb")]
         VectorStorageEnum::SparseSimple(vs) => raw_sparse_scorer_impl(query, vs, hc),
         VectorStorageEnum::SparseVolatile(vs) => raw_sparse_scorer_volatile(query, vs, hc),
         VectorStorageEnum::SparseMmap(vs) => raw_sparse_scorer_impl(query, vs, hc),
+        VectorStorageEnum::SparseAppendableWal(vs) => raw_sparse_scorer_impl(query, vs, hc),
         #[cfg(feature = "rocksdb")]
         VectorStorageEnum::MultiDenseSimple(vs) => raw_multi_scorer_impl(query, vs, hc),
         #[cfg(feature = "rocksdb")]
@@ -106,6 +126,9 @@ pub fn new_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
             raw_multi_scorer_impl(query, vs.as_ref(), hc)
         }
+        VectorStorageEnum::Tombstone(vs) => {
+            Err(OperationError::vector_name_removed(vs.vector_name()))
+        }
     }
 }
 
@@ -131,6 +154,39 @@ pub fn raw_sparse_scorer_volatile<'a>(
     raw_scorer_from_query_scorer(query_scorer)
 }
 
+pub fn raw_int4_scorer_impl<'a>(
+    query: QueryVector,
+    vector_storage: &'a AppendableMmapInt4DenseVectorStorage,
+    hardware_counter: HardwareCounterCell,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    // Custom queries are scored on dequantized vectors
+    let QueryVector::Nearest(vector) = query else {
+        return raw_scorer_impl(query, vector_storage, hardware_counter);
+    };
+    let vector: DenseVector = vector.try_into()?;
+
+    match vector_storage.distance() {
+        Distance::Cosine => raw_scorer_from_query_scorer(Int4QueryScorer::<CosineMetric>::new(
+            vector,
+            vector_storage,
+            hardware_counter,
+        )),
+        Distance::Euclid => raw_scorer_from_query_scorer(Int4QueryScorer::<EuclidMetric>::new(
+            vector,
+            vector_storage,
+            hardware_counter,
+        )),
+        Distance::Dot => raw_scorer_from_query_scorer(Int4QueryScorer::<DotProductMetric>::new(
+            vector,
+            vector_storage,
+            hardware_counter,
+        )),
+        Distance::Manhattan => raw_scorer_from_query_scorer(
+            Int4QueryScorer::<ManhattanMetric>::new(vector, vector_storage, hardware_counter),
+        ),
+    }
+}
+
 pub fn raw_sparse_scorer_impl<'a, TVectorStorage: SparseVectorStorage>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -247,6 +303,16 @@ fn new_scorer_with_metric<
     hardware_counter_cell: HardwareCounterCell,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
     match query {
+        // Vectors of variable dimension are scored over their stored prefix. Custom queries
+        // compare them padded with zeros.
+        QueryVector::Nearest(vector) if vector_storage.is_variable_dim() => {
+            let query_scorer = VariableDimQueryScorer::<_, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+                hardware_counter_cell,
+            );
+            raw_scorer_from_query_scorer(query_scorer)
+        }
         QueryVector::Nearest(vector) => {
             let query_scorer = MetricQueryScorer::<_, TMetric, _>::new(
                 vector.try_into()?,
@@ -0,0 +1,94 @@
+//! Read statistics of vector storages.
+//!
+//! Storages, which read vectors from files, count their reads by access pattern along with the
+//! number of bytes read. Batched reads also count major page faults of the reading thread, i.e.
+//! reads of pages which were not in the page cache. Single reads are too short to measure them
+//! without a noticeable overhead.
+
+use std::ops::AddAssign;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::mmap::thread_major_page_faults;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::common::anonymize::Anonymize;
+
+/// Read statistics of a vector storage, or a sum over multiple storages
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema, Anonymize)]
+#[anonymize(false)]
+pub struct VectorStorageStats {
+    /// Number of reads with sequential access pattern
+    pub sequential_reads: usize,
+    /// Number of reads with random access pattern
+    pub random_reads: usize,
+    /// Total number of bytes read
+    pub bytes_read: usize,
+    /// Number of pages, which had to be read from disk during batched reads
+    pub cold_page_faults: usize,
+}
+
+impl AddAssign for VectorStorageStats {
+    fn add_assign(&mut self, other: Self) {
+        let Self {
+            sequential_reads,
+            random_reads,
+            bytes_read,
+            cold_page_faults,
+        } = other;
+
+        self.sequential_reads += sequential_reads;
+        self.random_reads += random_reads;
+        self.bytes_read += bytes_read;
+        self.cold_page_faults += cold_page_faults;
+    }
+}
+
+/// Counters of reads, kept by a single storage
+#[derive(Debug, Default)]
+pub struct VectorStorageReadStats {
+    sequential_reads: AtomicUsize,
+    random_reads: AtomicUsize,
+    bytes_read: AtomicUsize,
+    cold_page_faults: AtomicUsize,
+}
+
+impl VectorStorageReadStats {
+    #[inline]
+    pub fn record_read(&self, sequential: bool, bytes: usize) {
+        if sequential {
+            self.sequential_reads.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.random_reads.fetch_add(1, Ordering::Relaxed);
+        }
+        self.bytes_read.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Run batched read `f`, counting major page faults it causes
+    pub fn measure_batch<R>(&self, f: impl FnOnce() -> R) -> R {
+        let faults_before = thread_major_page_faults();
+        let result = f();
+        let faults = thread_major_page_faults().saturating_sub(faults_before);
+        if faults > 0 {
+            self.cold_page_faults
+                .fetch_add(faults as usize, Ordering::Relaxed);
+        }
+        result
+    }
+
+    pub fn stats(&self) -> VectorStorageStats {
+        let Self {
+            sequential_reads,
+            random_reads,
+            bytes_read,
+            cold_page_faults,
+        } = self;
+
+        VectorStorageStats {
+            sequential_reads: sequential_reads.load(Ordering::Relaxed),
+            random_reads: random_reads.load(Ordering::Relaxed),
+            bytes_read: bytes_read.load(Ordering::Relaxed),
+            cold_page_faults: cold_page_faults.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! In-memory sparse vector storage, persisted in an append-only log.
+//!
+//! Every update is appended to the log file right away, as a record with the new state of a
+//! single vector, so that updates survive a crash of the process between flushes. Flushing only
+//! syncs the log to disk. On open, the log is replayed into memory. A record, which is cut short
+//! or fails its checksum, can only be the last one written before a crash, so it is truncated
+//! and assumed to be recovered by the WAL.
+//!
+//! Overwritten and deleted vectors leave stale records behind. Once the log grows much larger
+//! than the live vectors, it is rewritten on open.
+
+use std::io::{BufReader, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::{BitSlice, BitSliceExt as _, BitVec, bitvec_set_deleted};
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::is_sync_deferred;
+use common::generic_consts::AccessPattern;
+use common::types::PointOffsetType;
+use fs_err as fs;
+use fs_err::File;
+use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use crate::common::Flusher;
+use crate::common::log_records::{open_log, read_record, write_record};
+use crate::common::operation_error::{OperationError, OperationResult, check_process_stopped};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::VectorRef;
+use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::memory_budget::MemoryReservation;
+use crate::vector_storage::{SparseVectorStorage, VectorStorage, VectorStorageEnum};
+
+const LOG_FILE: &str = "sparse_vectors.log";
+const COMPACTED_LOG_FILE: &str = "sparse_vectors.log.tmp";
+
+/// Log is not rewritten while it is smaller than this
+const MIN_COMPACT_LOG_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Log is rewritten if it is this many times larger than the live vectors
+const COMPACT_LOG_RATIO: u64 = 2;
+
+/// State of a single vector, as persisted in the log
+#[derive(Debug, Serialize, Deserialize)]
+struct LogRecord {
+    key: PointOffsetType,
+    deleted: bool,
+    vector: Option<SparseVector>,
+}
+
+/// In-memory vector storage with on-update persistence in an append-only log
+#[derive(Debug)]
+pub struct AppendableWalSparseVectorStorage {
+    log_path: PathBuf,
+    /// Log file, opened for appending. Shared with flushers.
+    log_file: Arc<File>,
+    vectors: Vec<Option<SparseVector>>,
+    /// BitVec for deleted flags. Grows dynamically upto last set flag.
+    deleted: BitVec,
+    /// Current number of deleted vectors.
+    deleted_count: usize,
+    total_vector_count: usize,
+    /// Total number of non-zero elements in all vectors. Used to estimate average vector size.
+    total_sparse_size: usize,
+    /// Estimated memory, occupied by vectors
+    memory_reservation: MemoryReservation,
+}
+
+pub fn open_appendable_wal_sparse_vector_storage(
+    path: &Path,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = AppendableWalSparseVectorStorage::open_or_create(path, stopped)?;
+    Ok(VectorStorageEnum::SparseAppendableWal(storage))
+}
+
+impl AppendableWalSparseVectorStorage {
+    pub fn open_or_create(path: &Path, stopped: &AtomicBool) -> OperationResult<Self> {
+        fs::create_dir_all(path)?;
+        let log_path = path.join(LOG_FILE);
+
+        let mut storage = Self {
+            log_path: log_path.clone(),
+            log_file: Arc::new(open_log(&log_path)?),
+            vectors: Vec::new(),
+            deleted: BitVec::new(),
+            deleted_count: 0,
+            total_vector_count: 0,
+            total_sparse_size: 0,
+            memory_reservation: MemoryReservation::default(),
+        };
+
+        let log_len = storage.replay_log(stopped)?;
+        if log_len >= MIN_COMPACT_LOG_SIZE {
+            storage.compact_log(log_len)?;
+        }
+
+        Ok(storage)
+    }
+
+    /// Apply all records of the log.
+    /// Returns the length of the log, after truncating an incomplete record at its end.
+    fn replay_log(&mut self, stopped: &AtomicBool) -> OperationResult<u64> {
+        let file_len = self.log_file.metadata()?.len();
+        let mut reader = BufReader::new(File::open(&self.log_path)?);
+
+        let mut read_to = 0;
+        while let Some((record, record_size)) = read_record(&mut reader)? {
+            check_process_stopped(stopped)?;
+            self.apply_record(record);
+            read_to += record_size as u64;
+        }
+
+        debug_assert!(read_to <= file_len, "cannot read past the end of the file");
+        if read_to < file_len {
+            log::warn!(
+                "Sparse vector log ends with incomplete record, removing last {} bytes and assuming automatic recovery by WAL",
+                file_len - read_to,
+            );
+            self.log_file.set_len(read_to)?;
+            self.log_file.sync_all()?;
+        }
+
+        Ok(read_to)
+    }
+
+    fn apply_record(&mut self, record: LogRecord) {
+        let LogRecord {
+            key,
+            deleted,
+            vector,
+        } = record;
+        self.total_vector_count = std::cmp::max(self.total_vector_count, key as usize + 1);
+        self.set_deleted(key, deleted);
+        self.update_stored(key, deleted, vector.as_ref());
+    }
+
+    /// Rewrite the log with the current state of all vectors, if it is much larger than that
+    fn compact_log(&mut self, log_len: u64) -> OperationResult<()> {
+        let mut compacted = Vec::new();
+        for key in 0..self.total_vector_count as PointOffsetType {
+            let deleted = self.is_deleted_vector(key);
+            let vector = self.vectors.get(key as usize).cloned().flatten();
+            // Gaps between inserted vectors have no state to persist
+            if !deleted && vector.is_none() {
+                continue;
+            }
+            write_record(
+                &mut compacted,
+                &LogRecord {
+                    key,
+                    deleted,
+                    vector,
+                },
+            )?;
+        }
+
+        if (compacted.len() as u64).saturating_mul(COMPACT_LOG_RATIO) > log_len {
+            return Ok(());
+        }
+
+        log::debug!(
+            "Compacting sparse vector log {} from {log_len} to {} bytes",
+            self.log_path.display(),
+            compacted.len(),
+        );
+
+        let compacted_path = self.log_path.with_file_name(COMPACTED_LOG_FILE);
+        let mut compacted_file = File::create(&compacted_path)?;
+        compacted_file.write_all(&compacted)?;
+        compacted_file.sync_all()?;
+        fs::rename(&compacted_path, &self.log_path)?;
+
+        self.log_file = Arc::new(open_log(&self.log_path)?);
+        Ok(())
+    }
+
+    /// Append the new state of a vector to the log
+    fn append_record(
+        &self,
+        record: &LogRecord,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let mut buffer = Vec::new();
+        write_record(&mut buffer, record)?;
+
+        hw_counter
+            .vector_io_write_counter()
+            .incr_delta(buffer.len());
+
+        // Single write, so that a crash can only cut the last record short
+        (&*self.log_file).write_all(&buffer)?;
+        Ok(())
+    }
+
+    /// Set deleted flag for given key. Returns previous deleted state.
+    #[inline]
+    fn set_deleted(&mut self, key: PointOffsetType, deleted: bool) -> bool {
+        if !deleted && key as usize >= self.total_vector_count {
+            return false;
+        }
+        let was_deleted = bitvec_set_deleted(&mut self.deleted, key, deleted);
+        if was_deleted != deleted {
+            if !was_deleted {
+                self.deleted_count += 1;
+            } else {
+                self.deleted_count = self.deleted_count.saturating_sub(1);
+            }
+        }
+        was_deleted
+    }
+
+    fn update_stored(
+        &mut self,
+        key: PointOffsetType,
+        deleted: bool,
+        vector: Option<&SparseVector>,
+    ) {
+        // Resize sparse vector container if needed
+        if key as usize >= self.vectors.len() {
+            if deleted {
+                return;
+            }
+            self.vectors.resize(key as usize + 1, None);
+        }
+
+        let entry = &mut self.vectors[key as usize];
+
+        // Update bookkeeping of total sparse size
+        let elements_removed = entry.as_ref().map_or(0, |v| v.indices.len());
+        let elements_added = vector
+            .as_ref()
+            .filter(|_| !deleted)
+            .map_or(0, |v| v.indices.len());
+        self.total_sparse_size = self
+            .total_sparse_size
+            .saturating_sub(elements_removed)
+            .saturating_add(elements_added);
+
+        if deleted {
+            entry.take();
+        } else {
+            *entry = vector.cloned();
+        }
+
+        let ram_usage_bytes = self.vectors.capacity() * std::mem::size_of::<Option<SparseVector>>()
+            + self.total_sparse_size
+                * (std::mem::size_of::<DimWeight>() + std::mem::size_of::<DimId>());
+        self.memory_reservation.resize(ram_usage_bytes);
+    }
+
+    /// Estimated number of bytes, occupied by vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.memory_reservation.bytes()
+    }
+
+    pub fn size_of_available_vectors_in_bytes(&self) -> usize {
+        if self.total_vector_count == 0 {
+            return 0;
+        }
+        let available_fraction =
+            (self.total_vector_count - self.deleted_count) as f32 / self.total_vector_count as f32;
+        let available_size = (self.total_sparse_size as f32 * available_fraction) as usize;
+        available_size * (std::mem::size_of::<DimWeight>() + std::mem::size_of::<DimId>())
+    }
+}
+
+impl SparseVectorStorage for AppendableWalSparseVectorStorage {
+    fn get_sparse<P: AccessPattern>(&self, key: PointOffsetType) -> OperationResult<SparseVector> {
+        let vector = self
+            .get_sparse_opt::<P>(key)?
+            .ok_or_else(|| OperationError::service_error("Sparse vector not found in storage"))?;
+        Ok(vector)
+    }
+
+    fn get_sparse_opt<P: AccessPattern>(
+        &self,
+        key: PointOffsetType,
+    ) -> OperationResult<Option<SparseVector>> {
+        // Already in memory, so no sequential optimizations available.
+        let opt_vector = self.vectors.get(key as usize).cloned().flatten();
+        Ok(opt_vector)
+    }
+}
+
+impl VectorStorage for AppendableWalSparseVectorStorage {
+    fn distance(&self) -> Distance {
+        super::SPARSE_VECTOR_DISTANCE
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        VectorStorageDatatype::Float32
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.total_vector_count
+    }
+
+    fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
+        let vector = self.get_vector_opt::<P>(key);
+        vector.unwrap_or_else(CowVector::default_sparse)
+    }
+
+    /// Get vector by key, if it exists.
+    ///
+    /// ignore any error
+    fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
+        // In memory, so no sequential read optimization.
+        match self.get_sparse_opt::<P>(key) {
+            Ok(Some(vector)) => Some(CowVector::from(vector)),
+            _ => None,
+        }
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let vector: &SparseVector = vector.try_into()?;
+        debug_assert!(vector.is_sorted());
+        let record = LogRecord {
+            key,
+            deleted: false,
+            vector: Some(vector.clone()),
+        };
+        self.append_record(&record, hw_counter)?;
+        self.apply_record(record);
+        Ok(())
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.total_vector_count as PointOffsetType;
+        let hw_counter = HardwareCounterCell::disposable(); // This function is only used by internal operations
+        for (other_vector, other_deleted) in other_vectors {
+            check_process_stopped(stopped)?;
+            // Do not perform preprocessing - vectors should be already processed
+            let other_vector: &SparseVector = other_vector.as_vec_ref().try_into()?;
+            let record = LogRecord {
+                key: self.total_vector_count as PointOffsetType,
+                deleted: other_deleted,
+                vector: (!other_deleted).then(|| other_vector.clone()),
+            };
+            self.append_record(&record, &hw_counter)?;
+            self.apply_record(record);
+        }
+        Ok(start_index..self.total_vector_count as PointOffsetType)
+    }
+
+    fn flusher(&self) -> Flusher {
+        let log_file = self.log_file.clone();
+        Box::new(move || {
+            if is_sync_deferred() {
+                return Ok(());
+            }
+            log_file.sync_data().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush sparse vector log: {err}"))
+            })
+        })
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.log_path.clone()]
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        // Nothing is persisted for keys, which were never inserted
+        if key as usize >= self.total_vector_count {
+            return Ok(!self.set_deleted(key, true));
+        }
+        if self.is_deleted_vector(key) {
+            return Ok(false);
+        }
+        let record = LogRecord {
+            key,
+            deleted: true,
+            vector: None,
+        };
+        self.append_record(&record, &HardwareCounterCell::disposable())?;
+        self.apply_record(record);
+        Ok(true)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.deleted.get_bit(key as usize).unwrap_or(false)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted_count
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.deleted.as_bitslice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::generic_consts::Sequential;
+    use rand::rngs::StdRng;
+    use rand::{RngExt, SeedableRng};
+    use sparse::common::sparse_vector_fixture::random_sparse_vector;
+    use tempfile::Builder;
+
+    use super::*;
+
+    const RAND_SEED: u64 = 42;
+
+    #[test]
+    fn test_reopen_without_flush() {
+        const POINT_COUNT: PointOffsetType = 128;
+        const DIM: usize = 1024;
+        const DELETE_PROBABILITY: f64 = 0.1;
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let stopped = AtomicBool::new(false);
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let mut rng = StdRng::seed_from_u64(RAND_SEED);
+        let mut storage =
+            AppendableWalSparseVectorStorage::open_or_create(dir.path(), &stopped).unwrap();
+        for internal_id in 0..POINT_COUNT {
+            let vector = random_sparse_vector(&mut rng, DIM);
+            storage
+                .insert_vector(internal_id, VectorRef::from(&vector), &hw_counter)
+                .unwrap();
+            if rng.random_bool(DELETE_PROBABILITY) {
+                storage.delete_vector(internal_id).unwrap();
+            }
+        }
+        let deleted_vector_count = storage.deleted_vector_count();
+        let total_sparse_size = storage.total_sparse_size;
+        drop(storage);
+
+        // Simulate a crash in the middle of appending a record
+        let log_path = dir.path().join(LOG_FILE);
+        let log_len = fs::metadata(&log_path).unwrap().len();
+        let mut log_file = File::options().append(true).open(&log_path).unwrap();
+        log_file.write_all(&[1, 2, 3]).unwrap();
+        drop(log_file);
+
+        let storage =
+            AppendableWalSparseVectorStorage::open_or_create(dir.path(), &stopped).unwrap();
+        assert_eq!(fs::metadata(&log_path).unwrap().len(), log_len);
+        assert_eq!(storage.total_vector_count(), POINT_COUNT as usize);
+        assert_eq!(storage.deleted_vector_count(), deleted_vector_count);
+        assert_eq!(storage.total_sparse_size, total_sparse_size);
+
+        let mut rng = StdRng::seed_from_u64(RAND_SEED);
+        for internal_id in 0..POINT_COUNT {
+            let vector = random_sparse_vector(&mut rng, DIM);
+            let deleted = storage.is_deleted_vector(internal_id);
+            assert_eq!(deleted, rng.random_bool(DELETE_PROBABILITY));
+            if !deleted {
+                assert_eq!(
+                    storage.get_vector::<Sequential>(internal_id),
+                    CowVector::from(vector),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact_log() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let stopped = AtomicBool::new(false);
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let mut storage =
+            AppendableWalSparseVectorStorage::open_or_create(dir.path(), &stopped).unwrap();
+        let vector = SparseVector::new(vec![1, 2, 3], vec![0.1, 0.2, 0.3]).unwrap();
+        for _ in 0..10 {
+            storage
+                .insert_vector(0, VectorRef::from(&vector), &hw_counter)
+                .unwrap();
+        }
+        storage
+            .insert_vector(2, VectorRef::from(&vector), &hw_counter)
+            .unwrap();
+        storage.delete_vector(2).unwrap();
+        storage.delete_vector(1).unwrap();
+
+        let log_len = fs::metadata(&storage.log_path).unwrap().len();
+        storage.compact_log(log_len).unwrap();
+        assert!(fs::metadata(&storage.log_path).unwrap().len() < log_len);
+        drop(storage);
+
+        let storage =
+            AppendableWalSparseVectorStorage::open_or_create(dir.path(), &stopped).unwrap();
+        assert_eq!(storage.total_vector_count(), 3);
+        assert_eq!(storage.deleted_vector_count(), 2);
+        assert!(storage.is_deleted_vector(1));
+        assert!(storage.is_deleted_vector(2));
+        assert_eq!(storage.get_vector::<Sequential>(0), CowVector::from(vector),);
+    }
+}
@@ -273,6 +273,11 @@ impl VectorStorage for MmapSparseVectorStorage {
         })
     }
 
+    fn estimate_flush_size(&self) -> usize {
+        // Values are written into gridstore pages on update, only pointers are pending
+        self.deleted.estimate_flush_size()
+    }
+
     fn files(&self) -> Vec<PathBuf> {
         let mut files = self.storage.files();
         files.extend(self.deleted.files());
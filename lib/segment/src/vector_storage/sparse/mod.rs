@@ -1,3 +1,4 @@
+pub mod appendable_wal_sparse_vector_storage;
 pub mod mmap_sparse_vector_storage;
 #[cfg(feature = "rocksdb")]
 pub mod simple_sparse_vector_storage;
@@ -247,6 +247,10 @@ impl VectorStorage for SimpleSparseVectorStorage {
         self.db_wrapper.flusher()
     }
 
+    fn estimate_flush_size(&self) -> usize {
+        self.db_wrapper.pending_size_bytes()
+    }
+
     fn files(&self) -> Vec<std::path::PathBuf> {
         vec![]
     }
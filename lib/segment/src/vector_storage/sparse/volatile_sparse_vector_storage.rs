@@ -13,6 +13,7 @@ use crate::common::operation_error::{OperationError, OperationResult, check_proc
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::vectors::VectorRef;
 use crate::types::{Distance, VectorStorageDatatype};
+use crate::vector_storage::memory_budget::MemoryReservation;
 use crate::vector_storage::{SparseVectorStorage, VectorStorage, VectorStorageEnum};
 
 pub const SPARSE_VECTOR_DISTANCE: Distance = Distance::Dot;
@@ -28,6 +29,8 @@ pub struct VolatileSparseVectorStorage {
     total_vector_count: usize,
     /// Total number of non-zero elements in all vectors. Used to estimate average vector size.
     total_sparse_size: usize,
+    /// Estimated memory, occupied by vectors
+    memory_reservation: MemoryReservation,
 }
 
 pub fn new_volatile_sparse_vector_storage() -> VectorStorageEnum {
@@ -84,6 +87,16 @@ impl VolatileSparseVectorStorage {
         } else {
             *entry = vector.cloned();
         }
+
+        let ram_usage_bytes = self.vectors.capacity() * std::mem::size_of::<Option<SparseVector>>()
+            + self.total_sparse_size
+                * (std::mem::size_of::<DimWeight>() + std::mem::size_of::<DimId>());
+        self.memory_reservation.resize(ram_usage_bytes);
+    }
+
+    /// Estimated number of bytes, occupied by vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.memory_reservation.bytes()
     }
 
     pub fn size_of_available_vectors_in_bytes(&self) -> usize {
@@ -0,0 +1,142 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+use tempfile::TempDir;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+
+/// Frozen copy of the files of a vector storage, taken at a single point in time.
+///
+/// Immutable files are hard-linked, so freezing a storage with multi-GB vector files is cheap.
+/// Only the remaining mutable files, such as deleted flags or the tail of appendable storages,
+/// are copied. The storage can be updated while the frozen files are read, e.g. to be packed
+/// into a snapshot archive.
+///
+/// Frozen files are removed once the snapshot is dropped.
+#[derive(Debug)]
+pub struct VectorStorageSnapshot {
+    dir: TempDir,
+    /// Frozen files, along with paths of the original files
+    files: Vec<(PathBuf, PathBuf)>,
+}
+
+impl VectorStorageSnapshot {
+    /// Freeze `files` of a storage in a new directory inside of `temp_dir`.
+    ///
+    /// Files, listed in `immutable_files`, are hard-linked if `temp_dir` is on the same filesystem,
+    /// and copied otherwise.
+    pub fn freeze(
+        temp_dir: &Path,
+        files: Vec<PathBuf>,
+        immutable_files: &[PathBuf],
+    ) -> OperationResult<Self> {
+        fs::create_dir_all(temp_dir)?;
+        let dir = tempfile::Builder::new()
+            .prefix("vector-storage-snapshot-")
+            .tempdir_in(temp_dir)?;
+
+        let frozen_files = files
+            .into_iter()
+            .enumerate()
+            .map(|(idx, original)| {
+                let file_name = original.file_name().ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "vector storage file {} has no name",
+                        original.display(),
+                    ))
+                })?;
+                // Files of a storage may come from different directories and share names
+                let frozen = dir
+                    .path()
+                    .join(format!("{idx}-{}", file_name.to_string_lossy()));
+
+                if immutable_files.contains(&original) {
+                    link_or_copy(&original, &frozen)?;
+                } else {
+                    fs::copy(&original, &frozen)?;
+                }
+                Ok((frozen, original))
+            })
+            .collect::<OperationResult<_>>()?;
+
+        Ok(Self {
+            dir,
+            files: frozen_files,
+        })
+    }
+
+    /// Directory, holding the frozen files
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Frozen files, along with paths of the original files
+    pub fn files(&self) -> impl Iterator<Item = (&Path, &Path)> {
+        self.files
+            .iter()
+            .map(|(frozen, original)| (frozen.as_path(), original.as_path()))
+    }
+}
+
+fn link_or_copy(original: &Path, frozen: &Path) -> io::Result<()> {
+    match fs::hard_link(original, frozen) {
+        Ok(()) => Ok(()),
+        // E.g. if the temp directory is on another filesystem
+        Err(err) => {
+            log::debug!(
+                "Failed to hard-link {} for vector storage snapshot, copying instead: {err}",
+                original.display(),
+            );
+            fs::copy(original, frozen).map(|_| ())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frozen_files_are_not_updated() {
+        let storage_dir = tempfile::Builder::new()
+            .prefix("storage_dir")
+            .tempdir()
+            .unwrap();
+        let temp_dir = tempfile::Builder::new()
+            .prefix("temp_dir")
+            .tempdir()
+            .unwrap();
+
+        let immutable = storage_dir.path().join("vectors");
+        let mutable = storage_dir.path().join("deleted");
+        fs::write(&immutable, b"vectors").unwrap();
+        fs::write(&mutable, b"deleted").unwrap();
+
+        let snapshot = VectorStorageSnapshot::freeze(
+            temp_dir.path(),
+            vec![immutable.clone(), mutable.clone()],
+            std::slice::from_ref(&immutable),
+        )
+        .unwrap();
+
+        // Storage keeps being updated
+        fs::write(&mutable, b"updated").unwrap();
+
+        let frozen: Vec<_> = snapshot
+            .files()
+            .map(|(frozen, original)| (fs::read(frozen).unwrap(), original.to_path_buf()))
+            .collect();
+        assert_eq!(
+            frozen,
+            vec![
+                (b"vectors".to_vec(), immutable),
+                (b"deleted".to_vec(), mutable)
+            ],
+        );
+
+        let snapshot_dir = snapshot.path().to_path_buf();
+        drop(snapshot);
+        assert!(!snapshot_dir.exists());
+    }
+}
@@ -1,6 +1,7 @@
 use common::bitvec::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::generic_consts::Random;
+use common::mmap::AdviceSetting;
 use common::types::PointOffsetType;
 use itertools::Itertools;
 use rand::SeedableRng as _;
@@ -12,10 +13,10 @@ use crate::fixtures::payload_context_fixture::create_id_tracker_fixture;
 use crate::id_tracker::IdTracker;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::types::Distance;
-use crate::vector_storage::VectorStorageEnum;
 use crate::vector_storage::dense::dense_vector_storage::open_dense_vector_storage_with_uring;
 use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
 use crate::vector_storage::vector_storage_base::VectorStorage;
+use crate::vector_storage::{DenseVectorStorage as _, VectorStorageEnum};
 
 #[test]
 fn async_raw_scorer_cosine() -> Result<()> {
@@ -37,6 +38,53 @@ fn async_raw_scorer_dot() -> Result<()> {
     test_async_raw_scorer_defaults(Distance::Dot)
 }
 
+#[test]
+fn async_get_dense_batch() -> Result<()> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+    let dim = 16;
+    let points = 256;
+
+    let dir = tempfile::Builder::new()
+        .prefix("immutable-storage")
+        .tempdir()?;
+    let mut storage = open_dense_vector_storage_with_uring(
+        dir.path(),
+        dim,
+        Distance::Dot,
+        AdviceSetting::Global,
+        false,
+        true,
+    )?;
+
+    {
+        let mut volatile_storage = new_volatile_dense_vector_storage(dim, Distance::Dot);
+        insert_random_vectors(&mut rng, dim, &mut volatile_storage, points)?;
+
+        let mut iter = (0..points).map(|i| {
+            let vec = volatile_storage.get_vector::<Random>(i as PointOffsetType);
+            (vec, false)
+        });
+        storage.update_from(&mut iter, &Default::default())?;
+    }
+
+    let VectorStorageEnum::DenseUring(storage) = &storage else {
+        panic!("io_uring storage expected");
+    };
+
+    let keys = (0..points as PointOffsetType).sample(&mut rng, 64);
+    let mut batch = vec![None; keys.len()];
+    storage.get_dense_batch::<Random>(&keys, |idx, vector| batch[idx] = Some(vector.to_vec()))?;
+
+    for (key, vector) in keys.iter().zip(batch) {
+        assert_eq!(
+            vector.as_deref(),
+            Some(storage.get_dense::<Random>(*key).as_ref())
+        );
+    }
+
+    Ok(())
+}
+
 fn test_async_raw_scorer_defaults(distance: Distance) -> Result<()> {
     test_async_raw_scorer(6942, 128, distance, 1024, 128, 256)
 }
@@ -55,7 +103,14 @@ fn test_async_raw_scorer(
         .prefix("immutable-storage")
         .tempdir()?;
 
-    let mut storage = open_dense_vector_storage_with_uring(dir.path(), dim, distance, false, true)?;
+    let mut storage = open_dense_vector_storage_with_uring(
+        dir.path(),
+        dim,
+        distance,
+        AdviceSetting::Global,
+        false,
+        true,
+    )?;
     let mut id_tracker = create_id_tracker_fixture(points);
 
     {
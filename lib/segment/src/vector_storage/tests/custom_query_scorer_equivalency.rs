@@ -5,6 +5,7 @@ use std::{error, result};
 
 use common::counter::hardware_counter::HardwareCounterCell;
 use common::generic_consts::Random;
+use common::mmap::AdviceSetting;
 use common::types::PointOffsetType;
 use itertools::Itertools;
 use rand::rngs::StdRng;
@@ -62,7 +63,8 @@ fn ram_storage(_dir: &Path) -> VectorStorageEnum {
 
 #[cfg(target_os = "linux")]
 fn async_memmap_storage(dir: &std::path::Path) -> VectorStorageEnum {
-    open_dense_vector_storage_with_uring(dir, DIMS, DISTANCE, false, true).unwrap()
+    open_dense_vector_storage_with_uring(dir, DIMS, DISTANCE, AdviceSetting::Global, false, true)
+        .unwrap()
 }
 
 fn scalar_u8() -> WithQuantization {
@@ -70,6 +72,7 @@ fn scalar_u8() -> WithQuantization {
         r#type: crate::types::ScalarType::Int8,
         quantile: Some(0.5),
         always_ram: Some(true),
+        drop_originals: None,
     }
     .into();
 
@@ -84,6 +87,7 @@ fn product_x4() -> WithQuantization {
     let config = ProductQuantizationConfig {
         compression: crate::types::CompressionRatio::X4,
         always_ram: Some(true),
+        drop_originals: None,
     }
     .into();
 
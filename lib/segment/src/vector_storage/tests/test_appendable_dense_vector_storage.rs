@@ -9,12 +9,18 @@ use tempfile::Builder;
 
 #[cfg(feature = "rocksdb")]
 use crate::common::rocksdb_wrapper::{DB_VECTOR_CF, open_db};
+use crate::data_types::named_vectors::CowVector;
 use crate::data_types::vectors::QueryVector;
 use crate::fixtures::payload_context_fixture::create_id_tracker_fixture;
 use crate::id_tracker::IdTracker;
 use crate::index::hnsw_index::point_scorer::{BatchFilteredSearcher, FilteredScorer};
-use crate::types::{Distance, PointIdType, QuantizationConfig, ScalarQuantizationConfig};
-use crate::vector_storage::dense::appendable_dense_vector_storage::open_appendable_memmap_vector_storage_full;
+use crate::types::{
+    Distance, PointIdType, QuantizationConfig, ScalarQuantizationConfig, VectorStorageDatatype,
+};
+use crate::vector_storage::dense::appendable_dense_vector_storage::{
+    open_appendable_memmap_vector_storage_bfloat16, open_appendable_memmap_vector_storage_double,
+    open_appendable_memmap_vector_storage_full,
+};
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_full_vector_storage;
 use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
@@ -57,6 +63,19 @@ fn do_test_delete_points(storage: &mut VectorStorageEnum) {
         "2 vectors must be deleted",
     );
 
+    let available = storage
+        .iter_vectors()
+        .map(|(key, vector)| {
+            assert_eq!(vector, CowVector::from(points[key as usize].clone()));
+            key
+        })
+        .collect_vec();
+    assert_eq!(
+        available,
+        [0, 1, 4],
+        "must iterate over non-deleted vectors"
+    );
+
     let vector = vec![0.0, 1.0, 1.1, 1.0];
     let query = vector.as_slice().into();
     let searcher = BatchFilteredSearcher::new_for_test(
@@ -320,6 +339,7 @@ fn test_score_quantized_points(storage: &mut VectorStorageEnum) {
         r#type: Default::default(),
         quantile: None,
         always_ram: None,
+        drop_originals: None,
     }
     .into();
 
@@ -629,3 +649,79 @@ fn test_score_quantized_points_appendable_memmap_vector_storages() {
     )
     .unwrap();
 }
+
+// ----------------------------------------------
+
+#[test]
+fn test_score_points_in_appendable_memmap_double_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = open_appendable_memmap_vector_storage_double(
+            dir.path(),
+            4,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+        assert_eq!(storage.datatype(), VectorStorageDatatype::Float64);
+        do_test_score_points(&mut storage);
+        storage.flusher()().unwrap();
+    }
+
+    let storage = open_appendable_memmap_vector_storage_double(
+        dir.path(),
+        4,
+        Distance::Dot,
+        AdviceSetting::Global,
+        false,
+    )
+    .unwrap();
+    let vector = storage.get_vector::<Random>(0);
+    let vector: &[f32] = vector.as_vec_ref().try_into().unwrap();
+    assert_eq!(vector, &[1.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_update_from_delete_points_appendable_memmap_double_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let mut storage = open_appendable_memmap_vector_storage_double(
+        dir.path(),
+        4,
+        Distance::Dot,
+        AdviceSetting::Global,
+        false,
+    )
+    .unwrap();
+    do_test_update_from_delete_points(&mut storage);
+}
+
+#[test]
+fn test_score_points_in_appendable_memmap_bfloat16_vector_storages() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    {
+        let mut storage = open_appendable_memmap_vector_storage_bfloat16(
+            dir.path(),
+            4,
+            Distance::Dot,
+            AdviceSetting::Global,
+            false,
+        )
+        .unwrap();
+        assert_eq!(storage.datatype(), VectorStorageDatatype::Bfloat16);
+        do_test_score_points(&mut storage);
+        storage.flusher()().unwrap();
+    }
+
+    let storage = open_appendable_memmap_vector_storage_bfloat16(
+        dir.path(),
+        4,
+        Distance::Dot,
+        AdviceSetting::Global,
+        false,
+    )
+    .unwrap();
+    let vector = storage.get_vector::<Random>(0);
+    let vector: &[f32] = vector.as_vec_ref().try_into().unwrap();
+    assert_eq!(vector, &[1.0, 0.0, 1.0, 1.0]);
+}
@@ -82,17 +82,26 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
             | VectorStorageEnum::DenseVolatileHalf(_) => unreachable!(),
             VectorStorageEnum::DenseMemmap(_)
             | VectorStorageEnum::DenseMemmapByte(_)
-            | VectorStorageEnum::DenseMemmapHalf(_) => unreachable!(),
+            | VectorStorageEnum::DenseMemmapHalf(_)
+            | VectorStorageEnum::DenseMemmapDouble(_)
+            | VectorStorageEnum::DenseMemmapBfloat16(_) => unreachable!(),
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(_)
             | VectorStorageEnum::DenseUringByte(_)
             | VectorStorageEnum::DenseUringHalf(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmap(_)
             | VectorStorageEnum::DenseAppendableMemmapByte(_)
-            | VectorStorageEnum::DenseAppendableMemmapHalf(_) => unreachable!(),
+            | VectorStorageEnum::DenseAppendableMemmapHalf(_)
+            | VectorStorageEnum::DenseAppendableMemmapDouble(_)
+            | VectorStorageEnum::DenseAppendableMemmapBfloat16(_)
+            | VectorStorageEnum::DenseAppendableMemmapInt8(_)
+            | VectorStorageEnum::DenseAppendableMemmapInt4(_)
+            | VectorStorageEnum::DenseQuantized(_)
+            | VectorStorageEnum::DenseVersioned(_) => unreachable!(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => unreachable!(),
             VectorStorageEnum::SparseMmap(_) => unreachable!(),
+            VectorStorageEnum::SparseAppendableWal(_) => unreachable!(),
             #[cfg(test)]
             VectorStorageEnum::SparseVolatile(_) => unreachable!(),
             #[cfg(feature = "rocksdb")]
@@ -117,7 +126,8 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
                 }
             }
             VectorStorageEnum::MultiDenseAppendableMemmapByte(_)
-            | VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => unreachable!(),
+            | VectorStorageEnum::MultiDenseAppendableMemmapHalf(_)
+            | VectorStorageEnum::Tombstone(_) => unreachable!(),
         };
     }
 
@@ -134,6 +144,21 @@ fn do_test_delete_points(vector_dim: usize, vec_count: usize, storage: &mut Vect
         2,
         "2 vectors must be deleted"
     );
+
+    let available = storage
+        .iter_vectors()
+        .map(|(key, vector)| {
+            let multi_dense: TypedMultiDenseVectorRef<_> = vector.as_vec_ref().try_into().unwrap();
+            assert_eq!(multi_dense.to_owned(), points[key as usize]);
+            key
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        available,
+        [0, 1, 4],
+        "must iterate over non-deleted vectors"
+    );
+
     let vector: Vec<Vec<f32>> = vec![vec![2.0; vector_dim]];
     let query = QueryVector::Nearest(vector.try_into().unwrap());
     let searcher = BatchFilteredSearcher::new_for_test(
@@ -1,5 +1,4 @@
 use std::path::Path;
-#[cfg(feature = "rocksdb")]
 use std::sync::atomic::AtomicBool;
 
 use common::counter::hardware_counter::HardwareCounterCell;
@@ -16,6 +15,7 @@ use crate::fixtures::payload_context_fixture::create_id_tracker_fixture;
 use crate::id_tracker::IdTracker;
 use crate::index::hnsw_index::point_scorer::BatchFilteredSearcher;
 use crate::vector_storage::query::RecoQuery;
+use crate::vector_storage::sparse::appendable_wal_sparse_vector_storage::open_appendable_wal_sparse_vector_storage;
 use crate::vector_storage::sparse::mmap_sparse_vector_storage::MmapSparseVectorStorage;
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::sparse::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
@@ -67,6 +67,20 @@ fn do_test_delete_points(storage: &mut VectorStorageEnum) {
         "2 vectors must be deleted"
     );
 
+    let available = storage
+        .iter_vectors()
+        .map(|(key, vector)| {
+            let sparse: &SparseVector = vector.as_vec_ref().try_into().unwrap();
+            assert_eq!(sparse, &points[key as usize]);
+            key
+        })
+        .collect_vec();
+    assert_eq!(
+        available,
+        [0, 1, 4],
+        "must iterate over non-deleted vectors"
+    );
+
     // Check that deleted points are deleted through raw scorer
     // Because raw scorer for nearest Query is incorrect
     // (nearest search is processed using inverted index),
@@ -340,3 +354,38 @@ fn test_persistence_in_simple_sparse_vector_storage() {
         open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap()
     });
 }
+
+#[test]
+fn test_delete_points_in_appendable_wal_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    let mut storage = open_appendable_wal_sparse_vector_storage(dir.path(), &stopped).unwrap();
+    do_test_delete_points(&mut storage);
+
+    storage.flusher()().unwrap();
+
+    drop(storage);
+
+    let _storage = open_appendable_wal_sparse_vector_storage(dir.path(), &stopped).unwrap();
+}
+
+#[test]
+fn test_update_from_delete_points_appendable_wal_sparse_vector_storage() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    let mut storage = open_appendable_wal_sparse_vector_storage(dir.path(), &stopped).unwrap();
+
+    do_test_update_from_delete_points(&mut storage);
+    storage.flusher()().unwrap();
+
+    drop(storage);
+
+    let _storage = open_appendable_wal_sparse_vector_storage(dir.path(), &stopped).unwrap();
+}
+
+#[test]
+fn test_persistence_in_appendable_wal_sparse_vector_storage() {
+    do_test_persistence(|path| {
+        open_appendable_wal_sparse_vector_storage(path, &AtomicBool::new(false)).unwrap()
+    });
+}
@@ -0,0 +1,253 @@
+//! Placeholder of a removed named vector.
+//!
+//! Removing a named vector must not wait until an optimizer rewrites the segment. Instead, the
+//! storage of the vector is replaced with a tombstone, and a marker file is written into the
+//! storage directory, so that the vector stays removed after a restart. Files of the original
+//! storage are kept on disk until the segment is replaced by optimization.
+//!
+//! All vectors of a tombstone are deleted, so it can still take part in updates and segment
+//! builds. Reads of a removed vector are rejected by the segment with
+//! `OperationError::VectorNameRemoved`.
+//!
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::{BitSlice, BitVec};
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::{atomic_save_json, read_json};
+use common::generic_consts::AccessPattern;
+use common::types::PointOffsetType;
+use serde::{Deserialize, Serialize};
+
+use crate::common::Flusher;
+use crate::common::operation_error::{OperationResult, check_process_stopped};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{VectorInternal, VectorRef};
+use crate::types::{Distance, VectorNameBuf, VectorStorageDatatype};
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+pub const TOMBSTONE_FILE: &str = "tombstone.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TombstoneInfo {
+    distance: Distance,
+    datatype: VectorStorageDatatype,
+    total_vector_count: usize,
+}
+
+#[derive(Debug)]
+pub struct TombstoneVectorStorage {
+    vector_name: VectorNameBuf,
+    marker_path: PathBuf,
+    distance: Distance,
+    datatype: VectorStorageDatatype,
+    /// Returned for every key, so that segment builds can copy from a tombstone
+    default_vector: VectorInternal,
+    /// All vectors are deleted, length is the total number of vectors
+    deleted: BitVec,
+}
+
+/// Check if the vector storage in `path` is removed
+pub fn is_removed_vector_storage(path: &Path) -> bool {
+    path.join(TOMBSTONE_FILE).is_file()
+}
+
+/// Replace `storage` of vector `vector_name` with a tombstone, persisted in `path`
+pub fn create_tombstone_vector_storage(
+    path: &Path,
+    vector_name: &str,
+    storage: &VectorStorageEnum,
+) -> OperationResult<VectorStorageEnum> {
+    let info = TombstoneInfo {
+        distance: storage.distance(),
+        datatype: storage.datatype(),
+        total_vector_count: storage.total_vector_count(),
+    };
+    let marker_path = path.join(TOMBSTONE_FILE);
+    atomic_save_json(&marker_path, &info)?;
+
+    Ok(VectorStorageEnum::Tombstone(Box::new(
+        TombstoneVectorStorage::new(vector_name, marker_path, info, storage.default_vector()),
+    )))
+}
+
+/// Open a tombstone of vector `vector_name`, which was persisted in `path`
+pub fn open_tombstone_vector_storage(
+    path: &Path,
+    vector_name: &str,
+    default_vector: VectorInternal,
+) -> OperationResult<VectorStorageEnum> {
+    let marker_path = path.join(TOMBSTONE_FILE);
+    let info: TombstoneInfo = read_json(&marker_path)?;
+
+    Ok(VectorStorageEnum::Tombstone(Box::new(
+        TombstoneVectorStorage::new(vector_name, marker_path, info, default_vector),
+    )))
+}
+
+impl TombstoneVectorStorage {
+    fn new(
+        vector_name: &str,
+        marker_path: PathBuf,
+        info: TombstoneInfo,
+        default_vector: VectorInternal,
+    ) -> Self {
+        let TombstoneInfo {
+            distance,
+            datatype,
+            total_vector_count,
+        } = info;
+
+        Self {
+            vector_name: vector_name.to_owned(),
+            marker_path,
+            distance,
+            datatype,
+            default_vector,
+            deleted: BitVec::repeat(true, total_vector_count),
+        }
+    }
+
+    pub fn vector_name(&self) -> &str {
+        &self.vector_name
+    }
+
+    pub fn default_vector(&self) -> &VectorInternal {
+        &self.default_vector
+    }
+
+    fn grow_to(&mut self, len: usize) {
+        if self.deleted.len() < len {
+            self.deleted.resize(len, true);
+        }
+    }
+}
+
+impl VectorStorage for TombstoneVectorStorage {
+    fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        self.datatype
+    }
+
+    fn is_on_disk(&self) -> bool {
+        false
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.deleted.len()
+    }
+
+    fn get_vector<P: AccessPattern>(&self, _key: PointOffsetType) -> CowVector<'_> {
+        CowVector::from(VectorRef::from(&self.default_vector))
+    }
+
+    fn get_vector_opt<P: AccessPattern>(&self, _key: PointOffsetType) -> Option<CowVector<'_>> {
+        None
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        _vector: VectorRef,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        // Vector is dropped right away, only the number of vectors is tracked
+        self.grow_to(key as usize + 1);
+        Ok(())
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        let start_index = self.deleted.len() as PointOffsetType;
+        for _ in other_vectors {
+            check_process_stopped(stopped)?;
+            self.deleted.push(true);
+        }
+        Ok(start_index..self.deleted.len() as PointOffsetType)
+    }
+
+    fn flusher(&self) -> Flusher {
+        // Marker is persisted on creation, there is nothing else to persist
+        Box::new(|| Ok(()))
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        vec![self.marker_path.clone()]
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        vec![self.marker_path.clone()]
+    }
+
+    fn delete_vector(&mut self, _key: PointOffsetType) -> OperationResult<bool> {
+        Ok(false)
+    }
+
+    fn delete_vectors(&mut self, _keys: &BitSlice) -> OperationResult<usize> {
+        Ok(0)
+    }
+
+    fn is_deleted_vector(&self, _key: PointOffsetType) -> bool {
+        true
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.deleted.len()
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        &self.deleted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::generic_consts::Random;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
+
+    #[test]
+    fn test_tombstone_persists_removal() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = new_volatile_dense_vector_storage(4, Distance::Dot);
+        for key in 0..5 {
+            let vector = vec![key as f32; 4];
+            storage
+                .insert_vector(key, VectorRef::from(&vector), &hw_counter)
+                .unwrap();
+        }
+        assert!(!is_removed_vector_storage(dir.path()));
+
+        let mut tombstone = create_tombstone_vector_storage(dir.path(), "image", &storage).unwrap();
+        assert!(is_removed_vector_storage(dir.path()));
+        assert_eq!(tombstone.total_vector_count(), 5);
+        assert_eq!(tombstone.available_vector_count(), 0);
+        assert!(tombstone.get_vector_opt::<Random>(2).is_none());
+
+        // Updates are accepted, but vectors are not kept
+        let vector = vec![1.0; 4];
+        tombstone
+            .insert_vector(6, VectorRef::from(&vector), &hw_counter)
+            .unwrap();
+        assert_eq!(tombstone.total_vector_count(), 7);
+        assert!(tombstone.is_deleted_vector(6));
+
+        let reopened =
+            open_tombstone_vector_storage(dir.path(), "image", storage.default_vector()).unwrap();
+        assert_eq!(reopened.total_vector_count(), 5);
+        assert_eq!(reopened.distance(), Distance::Dot);
+        assert_eq!(reopened.deleted_vector_count(), 5);
+    }
+}
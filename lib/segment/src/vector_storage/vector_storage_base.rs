@@ -3,12 +3,13 @@ use std::borrow::Cow;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::ops::Range;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 
 use common::bitvec::BitSlice;
 use common::counter::hardware_counter::HardwareCounterCell;
-use common::generic_consts::{AccessPattern, Random};
+use common::generic_consts::{AccessPattern, Random, Sequential};
 use common::maybe_uninit::maybe_uninit_fill_from;
 use common::types::PointOffsetType;
 #[cfg(target_os = "linux")]
@@ -23,21 +24,30 @@ use super::multi_dense::appendable_mmap_multi_dense_vector_storage::AppendableMm
 #[cfg(feature = "rocksdb")]
 use super::multi_dense::simple_multi_dense_vector_storage::SimpleMultiDenseVectorStorage;
 use super::multi_dense::volatile_multi_dense_vector_storage::VolatileMultiDenseVectorStorage;
+use super::sparse::appendable_wal_sparse_vector_storage::AppendableWalSparseVectorStorage;
 use super::sparse::mmap_sparse_vector_storage::MmapSparseVectorStorage;
 use super::sparse::volatile_sparse_vector_storage::VolatileSparseVectorStorage;
+use super::storage_snapshot::VectorStorageSnapshot;
+use super::tombstone_vector_storage::TombstoneVectorStorage;
+use super::versioned_vector_storage::VersionedDenseVectorStorage;
 use crate::common::Flusher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::{CowMultiVector, CowVector};
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    MultiDenseVectorInternal, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
-    VectorInternal, VectorRef,
+    MultiDenseVectorInternal, VectorElementType, VectorElementTypeBfloat16, VectorElementTypeByte,
+    VectorElementTypeDouble, VectorElementTypeHalf, VectorInternal, VectorRef,
 };
-use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
+use crate::types::{Distance, MultiVectorConfig, SeqNumberType, VectorStorageDatatype};
 use crate::vector_storage::common::VECTOR_READ_BATCH_SIZE;
 use crate::vector_storage::dense::appendable_dense_vector_storage::AppendableMmapDenseVectorStorage;
+use crate::vector_storage::dense::appendable_int4_dense_vector_storage::AppendableMmapInt4DenseVectorStorage;
+use crate::vector_storage::dense::appendable_int8_dense_vector_storage::AppendableMmapInt8DenseVectorStorage;
+use crate::vector_storage::dense::quantized_dense_vector_storage::QuantizedDenseVectorStorage;
+use crate::vector_storage::read_stats::VectorStorageStats;
 #[cfg(feature = "rocksdb")]
 use crate::vector_storage::sparse::simple_sparse_vector_storage::SimpleSparseVectorStorage;
+use crate::vector_storage::write_throttle::WriteThrottle;
 
 /// In case of simple vector storage, vector offset is the same as [`PointOffsetType`].
 /// But in case of multivectors, it requires an additional lookup.
@@ -106,6 +116,15 @@ pub trait VectorStorage {
     /// Get the vector by the given key if it exists
     fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>>;
 
+    /// Iterate over vectors which are not deleted, in order of their keys
+    ///
+    /// Reads vectors sequentially, so it is suitable for full scans of the storage.
+    fn iter_vectors(&self) -> impl Iterator<Item = (PointOffsetType, CowVector<'_>)> {
+        (0..self.total_vector_count() as PointOffsetType)
+            .filter(|&key| !self.is_deleted_vector(key))
+            .filter_map(|key| Some((key, self.get_vector_opt::<Sequential>(key)?)))
+    }
+
     fn insert_vector(
         &mut self,
         key: PointOffsetType,
@@ -127,17 +146,47 @@ pub trait VectorStorage {
 
     fn flusher(&self) -> Flusher;
 
+    /// Estimated number of bytes, written to disk by the next flush.
+    ///
+    /// Allows to check for free disk space before flushing. Storages, which persist changes on
+    /// update or keep them in RAM only, have nothing to write.
+    fn estimate_flush_size(&self) -> usize {
+        0
+    }
+
     fn files(&self) -> Vec<PathBuf>;
 
     fn immutable_files(&self) -> Vec<PathBuf> {
         Vec::new()
     }
 
+    /// Freeze files of the storage in `temp_dir`, so that they can be read while the storage is
+    /// being updated.
+    ///
+    /// Immutable files are hard-linked and only mutable ones are copied, which is much faster
+    /// than copying all files of large storages.
+    fn snapshot_view(&self, temp_dir: &Path) -> OperationResult<VectorStorageSnapshot> {
+        VectorStorageSnapshot::freeze(temp_dir, self.files(), &self.immutable_files())
+    }
+
     /// Flag the vector by the given key as deleted
     ///
     /// Returns true if the vector was not deleted before and is now deleted
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool>;
 
+    /// Flag all vectors, which are set in `keys`, as deleted
+    ///
+    /// Returns the number of vectors which were not deleted before and are now deleted
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        let mut deleted = 0;
+        for key in keys.iter_ones() {
+            if self.delete_vector(key as PointOffsetType)? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
     /// Check whether the vector at the given key is flagged as deleted
     fn is_deleted_vector(&self, key: PointOffsetType) -> bool;
 
@@ -189,6 +238,21 @@ pub trait DenseVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
             .map_err(|_| OperationError::service_error("Layout is too big"))
     }
 
+    /// Read vectors for the given keys as a single batch, and call `f` for each of them.
+    ///
+    /// Storages backed by asynchronous IO submit all reads at once, which reduces latency on a
+    /// cold cache. Other storages read vectors one by one.
+    fn get_dense_batch<P: AccessPattern>(
+        &self,
+        keys: &[PointOffsetType],
+        mut f: impl FnMut(usize, &[T]),
+    ) -> OperationResult<()> {
+        for (idx, &key) in keys.iter().enumerate() {
+            f(idx, &self.get_dense::<P>(key));
+        }
+        Ok(())
+    }
+
     /// Run given function for each vector in the dense batch.
     ///
     /// Implementation can assume that the keys are consecutive
@@ -201,6 +265,19 @@ pub trait DenseVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
     fn size_of_available_vectors_in_bytes(&self) -> usize {
         self.available_vector_count() * self.vector_dim() * std::mem::size_of::<T>()
     }
+
+    /// Whether stored vectors may be shorter than [`Self::vector_dim`]
+    ///
+    /// Shorter vectors are padded with zeros, so [`Self::get_dense`] always returns vectors of
+    /// the full dimension.
+    fn is_variable_dim(&self) -> bool {
+        false
+    }
+
+    /// Number of leading elements of the stored vector, which are not padding
+    fn stored_vector_len(&self, _key: PointOffsetType) -> usize {
+        self.vector_dim()
+    }
 }
 
 pub trait SparseVectorStorage: VectorStorage {
@@ -211,6 +288,30 @@ pub trait SparseVectorStorage: VectorStorage {
     ) -> OperationResult<Option<SparseVector>>;
 }
 
+/// Storage, which keeps previous versions of vectors in addition to the current ones
+pub trait VersionedVectorStorage: VectorStorage {
+    /// Insert the vector, written by the operation with the given version.
+    ///
+    /// The replaced vector is kept in the history, if its version is known.
+    fn insert_vector_at_version(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        version: SeqNumberType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()>;
+
+    /// Get the vector as it was at the given version.
+    ///
+    /// Returns `None` if the vector did not exist or was deleted at that version, or if the
+    /// version is older than the kept history.
+    fn get_vector_at_version(
+        &self,
+        key: PointOffsetType,
+        version: SeqNumberType,
+    ) -> Option<CowVector<'_>>;
+}
+
 pub trait MultiVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
     fn vector_dim(&self) -> usize;
     fn get_multi<P: AccessPattern>(&self, key: PointOffsetType) -> CowMultiVector<'_, T>;
@@ -251,6 +352,8 @@ pub enum VectorStorageEnum {
     DenseMemmap(Box<DenseVectorStorageImpl<VectorElementType>>),
     DenseMemmapByte(Box<DenseVectorStorageImpl<VectorElementTypeByte>>),
     DenseMemmapHalf(Box<DenseVectorStorageImpl<VectorElementTypeHalf>>),
+    DenseMemmapDouble(Box<DenseVectorStorageImpl<VectorElementTypeDouble>>),
+    DenseMemmapBfloat16(Box<DenseVectorStorageImpl<VectorElementTypeBfloat16>>),
 
     #[cfg(target_os = "linux")]
     DenseUring(Box<DenseVectorStorageImpl<VectorElementType, IoUringFile>>),
@@ -262,10 +365,17 @@ pub enum VectorStorageEnum {
     DenseAppendableMemmap(Box<AppendableMmapDenseVectorStorage<VectorElementType>>),
     DenseAppendableMemmapByte(Box<AppendableMmapDenseVectorStorage<VectorElementTypeByte>>),
     DenseAppendableMemmapHalf(Box<AppendableMmapDenseVectorStorage<VectorElementTypeHalf>>),
+    DenseAppendableMemmapDouble(Box<AppendableMmapDenseVectorStorage<VectorElementTypeDouble>>),
+    DenseAppendableMemmapBfloat16(Box<AppendableMmapDenseVectorStorage<VectorElementTypeBfloat16>>),
+    DenseAppendableMemmapInt8(Box<AppendableMmapInt8DenseVectorStorage>),
+    DenseAppendableMemmapInt4(Box<AppendableMmapInt4DenseVectorStorage>),
+    DenseQuantized(Box<QuantizedDenseVectorStorage>),
+    DenseVersioned(Box<VersionedDenseVectorStorage<VectorStorageEnum>>),
     #[cfg(feature = "rocksdb")]
     SparseSimple(SimpleSparseVectorStorage),
     SparseVolatile(VolatileSparseVectorStorage),
     SparseMmap(MmapSparseVectorStorage),
+    SparseAppendableWal(AppendableWalSparseVectorStorage),
     #[cfg(feature = "rocksdb")]
     MultiDenseSimple(SimpleMultiDenseVectorStorage<VectorElementType>),
     #[cfg(feature = "rocksdb")]
@@ -284,6 +394,7 @@ pub enum VectorStorageEnum {
     MultiDenseAppendableMemmapHalf(
         Box<AppendableMmapMultiDenseVectorStorage<VectorElementTypeHalf>>,
     ),
+    Tombstone(Box<TombstoneVectorStorage>),
 }
 
 impl VectorStorageEnum {
@@ -303,6 +414,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(_) => None,
             VectorStorageEnum::DenseMemmapByte(_) => None,
             VectorStorageEnum::DenseMemmapHalf(_) => None,
+            VectorStorageEnum::DenseMemmapDouble(_) => None,
+            VectorStorageEnum::DenseMemmapBfloat16(_) => None,
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(_) => None,
@@ -314,10 +427,17 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(_) => None,
             VectorStorageEnum::DenseAppendableMemmapByte(_) => None,
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapDouble(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapInt8(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapInt4(_) => None,
+            VectorStorageEnum::DenseQuantized(_) => None,
+            VectorStorageEnum::DenseVersioned(_) => None,
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => None,
             VectorStorageEnum::SparseVolatile(_) => None,
             VectorStorageEnum::SparseMmap(_) => None,
+            VectorStorageEnum::SparseAppendableWal(_) => None,
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(s) => Some(s.multi_vector_config()),
             #[cfg(feature = "rocksdb")]
@@ -332,6 +452,7 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::Tombstone(_) => None,
         }
     }
 
@@ -363,6 +484,12 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmapHalf(v) => {
                 VectorInternal::from(vec![1.0; v.vector_dim()])
             }
+            VectorStorageEnum::DenseMemmapDouble(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
+            VectorStorageEnum::DenseMemmapBfloat16(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => VectorInternal::from(vec![1.0; v.vector_dim()]),
@@ -380,10 +507,27 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 VectorInternal::from(vec![1.0; v.vector_dim()])
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                VectorInternal::from(vec![1.0; v.vector_dim()])
+            }
+            VectorStorageEnum::DenseQuantized(v) => VectorInternal::from(vec![1.0; v.vector_dim()]),
+            VectorStorageEnum::DenseVersioned(v) => v.inner().default_vector(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => VectorInternal::from(SparseVector::default()),
             VectorStorageEnum::SparseVolatile(_) => VectorInternal::from(SparseVector::default()),
             VectorStorageEnum::SparseMmap(_) => VectorInternal::from(SparseVector::default()),
+            VectorStorageEnum::SparseAppendableWal(_) => {
+                VectorInternal::from(SparseVector::default())
+            }
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => {
                 VectorInternal::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
@@ -416,6 +560,7 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 VectorInternal::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
+            VectorStorageEnum::Tombstone(v) => v.default_vector().clone(),
         }
     }
 
@@ -435,6 +580,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.size_of_available_vectors_in_bytes(),
             VectorStorageEnum::DenseMemmapByte(v) => v.size_of_available_vectors_in_bytes(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.size_of_available_vectors_in_bytes(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.size_of_available_vectors_in_bytes(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.size_of_available_vectors_in_bytes(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.size_of_available_vectors_in_bytes(),
@@ -450,6 +597,20 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 v.size_of_available_vectors_in_bytes()
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                v.size_of_available_vectors_in_bytes()
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.size_of_available_vectors_in_bytes()
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                v.size_of_available_vectors_in_bytes()
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                v.size_of_available_vectors_in_bytes()
+            }
+            VectorStorageEnum::DenseQuantized(v) => v.size_of_available_vectors_in_bytes(),
+            VectorStorageEnum::DenseVersioned(v) => v.inner().size_of_available_vectors_in_bytes(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.size_of_available_vectors_in_bytes(),
             VectorStorageEnum::SparseVolatile(v) => v.size_of_available_vectors_in_bytes(),
@@ -458,6 +619,7 @@ impl VectorStorageEnum {
                     "Mmap sparse storage does not know its total size, get from index instead"
                 )
             }
+            VectorStorageEnum::SparseAppendableWal(v) => v.size_of_available_vectors_in_bytes(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.size_of_available_vectors_in_bytes(),
             #[cfg(feature = "rocksdb")]
@@ -478,6 +640,126 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.size_of_available_vectors_in_bytes()
             }
+            VectorStorageEnum::Tombstone(_) => 0,
+        }
+    }
+
+    /// Number of bytes, occupied by vectors in RAM and reserved from the global
+    /// [`MemoryBudget`](super::memory_budget::MemoryBudget).
+    ///
+    /// Storages, which are read from disk on demand, occupy no memory.
+    pub fn ram_usage_bytes(&self) -> usize {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(v) => v.ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(v) => v.ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseVolatile(v) => v.ram_usage_bytes(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(v) => v.ram_usage_bytes(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseMemmap(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.ram_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(v) => v.ram_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(v) => v.ram_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseQuantized(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::DenseVersioned(v) => v.inner().ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(_) => 0, // Vectors are read from RocksDB
+            VectorStorageEnum::SparseVolatile(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::SparseMmap(_) => 0,
+            VectorStorageEnum::SparseAppendableWal(v) => v.ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(v) => v.ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.ram_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::MultiDenseVolatile(v) => v.ram_usage_bytes(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(v) => v.ram_usage_bytes(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.ram_usage_bytes(),
+            VectorStorageEnum::Tombstone(_) => 0,
+        }
+    }
+
+    /// Number of bytes of vectors, cached in RAM in addition to the storage itself.
+    ///
+    /// Only dense storages, reading vectors from disk, cache hot vectors.
+    pub fn cache_usage_bytes(&self) -> usize {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(_) => 0,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(_) => 0,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(_) => 0,
+            VectorStorageEnum::DenseVolatile(_) => 0,
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(_) => 0,
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(_) => 0,
+            VectorStorageEnum::DenseMemmap(v) => v.cache_usage_bytes(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.cache_usage_bytes(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.cache_usage_bytes(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.cache_usage_bytes(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.cache_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(v) => v.cache_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(v) => v.cache_usage_bytes(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(v) => v.cache_usage_bytes(),
+            VectorStorageEnum::DenseAppendableMemmap(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapByte(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapHalf(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapDouble(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapInt8(_) => 0,
+            VectorStorageEnum::DenseAppendableMemmapInt4(_) => 0,
+            VectorStorageEnum::DenseQuantized(_) => 0,
+            VectorStorageEnum::DenseVersioned(v) => v.inner().cache_usage_bytes(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(_) => 0,
+            VectorStorageEnum::SparseVolatile(_) => 0,
+            VectorStorageEnum::SparseMmap(_) => 0,
+            VectorStorageEnum::SparseAppendableWal(_) => 0,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(_) => 0,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(_) => 0,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(_) => 0,
+            VectorStorageEnum::MultiDenseVolatile(_) => 0,
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(_) => 0,
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(_) => 0,
+            VectorStorageEnum::MultiDenseAppendableMemmap(_) => 0,
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(_) => 0,
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => 0,
+            VectorStorageEnum::Tombstone(_) => 0,
         }
     }
 
@@ -497,6 +779,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(vs) => vs.populate(),
             VectorStorageEnum::DenseMemmapByte(vs) => vs.populate(),
             VectorStorageEnum::DenseMemmapHalf(vs) => vs.populate(),
+            VectorStorageEnum::DenseMemmapDouble(vs) => vs.populate(),
+            VectorStorageEnum::DenseMemmapBfloat16(vs) => vs.populate(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(vs) => vs.populate(),
@@ -508,17 +792,24 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(vs) => vs.populate()?,
             VectorStorageEnum::DenseAppendableMemmapByte(vs) => vs.populate()?,
             VectorStorageEnum::DenseAppendableMemmapHalf(vs) => vs.populate()?,
+            VectorStorageEnum::DenseAppendableMemmapDouble(vs) => vs.populate()?,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => vs.populate()?,
+            VectorStorageEnum::DenseAppendableMemmapInt8(vs) => vs.populate()?,
+            VectorStorageEnum::DenseAppendableMemmapInt4(vs) => vs.populate()?,
+            VectorStorageEnum::DenseQuantized(vs) => vs.populate()?,
+            VectorStorageEnum::DenseVersioned(vs) => vs.inner().populate()?,
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => {} // Can't populate as it is not mmap
             VectorStorageEnum::SparseVolatile(_) => {} // Can't populate as it is not mmap
             VectorStorageEnum::SparseMmap(vs) => vs.populate()?,
+            VectorStorageEnum::SparseAppendableWal(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimpleByte(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimpleHalf(_) => {} // Can't populate as it is not mmap
-            VectorStorageEnum::MultiDenseVolatile(_) => {} // Can't populate as it is not mmap
+            VectorStorageEnum::MultiDenseVolatile(_) => {}  // Can't populate as it is not mmap
             #[cfg(test)]
             VectorStorageEnum::MultiDenseVolatileByte(_) => {} // Can't populate as it is not mmap
             #[cfg(test)]
@@ -526,6 +817,7 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(vs) => vs.populate()?,
             VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => vs.populate()?,
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => vs.populate()?,
+            VectorStorageEnum::Tombstone(_) => {} // Nothing to populate
         }
         Ok(())
     }
@@ -546,6 +838,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(vs) => vs.clear_cache()?,
             VectorStorageEnum::DenseMemmapByte(vs) => vs.clear_cache()?,
             VectorStorageEnum::DenseMemmapHalf(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseMemmapDouble(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseMemmapBfloat16(vs) => vs.clear_cache()?,
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(vs) => vs.clear_cache()?,
@@ -557,17 +851,24 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(vs) => vs.clear_cache()?,
             VectorStorageEnum::DenseAppendableMemmapByte(vs) => vs.clear_cache()?,
             VectorStorageEnum::DenseAppendableMemmapHalf(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseAppendableMemmapDouble(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseAppendableMemmapInt8(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseAppendableMemmapInt4(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseQuantized(vs) => vs.clear_cache()?,
+            VectorStorageEnum::DenseVersioned(vs) => vs.inner().clear_cache()?,
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => {} // Can't populate as it is not mmap
             VectorStorageEnum::SparseVolatile(_) => {} // Can't populate as it is not mmap
             VectorStorageEnum::SparseMmap(vs) => vs.clear_cache()?,
+            VectorStorageEnum::SparseAppendableWal(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimpleByte(_) => {} // Can't populate as it is not mmap
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimpleHalf(_) => {} // Can't populate as it is not mmap
-            VectorStorageEnum::MultiDenseVolatile(_) => {} // Can't populate as it is not mmap
+            VectorStorageEnum::MultiDenseVolatile(_) => {}  // Can't populate as it is not mmap
             #[cfg(test)]
             VectorStorageEnum::MultiDenseVolatileByte(_) => {} // Can't populate as it is not mmap
             #[cfg(test)]
@@ -575,10 +876,347 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(vs) => vs.clear_cache()?,
             VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => vs.clear_cache()?,
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => vs.clear_cache()?,
+            VectorStorageEnum::Tombstone(_) => {} // Nothing to clear
+        }
+        Ok(())
+    }
+
+    /// Read statistics of this storage, since it was opened.
+    ///
+    /// Only storages, which read vectors from files, count their reads. Statistics of other
+    /// storages are always empty.
+    pub fn read_stats(&self) -> VectorStorageStats {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(_) => VectorStorageStats::default(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(_) => VectorStorageStats::default(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(_) => VectorStorageStats::default(),
+            VectorStorageEnum::DenseVolatile(_) => VectorStorageStats::default(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(_) => VectorStorageStats::default(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(_) => VectorStorageStats::default(),
+            VectorStorageEnum::DenseMemmap(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseMemmapByte(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseMemmapHalf(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseMemmapDouble(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseMemmapBfloat16(vs) => vs.read_stats(),
+
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(vs) => vs.read_stats(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(vs) => vs.read_stats(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(vs) => vs.read_stats(),
+
+            VectorStorageEnum::DenseAppendableMemmap(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapByte(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(vs) => vs.read_stats(),
+            VectorStorageEnum::DenseQuantized(_) => VectorStorageStats::default(),
+            VectorStorageEnum::DenseVersioned(v) => v.inner().read_stats(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(_) => VectorStorageStats::default(),
+            VectorStorageEnum::SparseVolatile(_) => VectorStorageStats::default(),
+            VectorStorageEnum::SparseMmap(_) => VectorStorageStats::default(),
+            VectorStorageEnum::SparseAppendableWal(_) => VectorStorageStats::default(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(_) => VectorStorageStats::default(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(_) => VectorStorageStats::default(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(_) => VectorStorageStats::default(),
+            VectorStorageEnum::MultiDenseVolatile(_) => VectorStorageStats::default(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(_) => VectorStorageStats::default(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(_) => VectorStorageStats::default(),
+            VectorStorageEnum::MultiDenseAppendableMemmap(vs) => vs.read_stats(),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => vs.read_stats(),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => vs.read_stats(),
+            VectorStorageEnum::Tombstone(_) => VectorStorageStats::default(),
+        }
+    }
+
+    /// Apply backpressure from `write_throttle` to inserts into this storage.
+    ///
+    /// Only appendable mmap storages accumulate dirty pages, other storages are not throttled.
+    pub fn set_write_throttle(&mut self, write_throttle: Option<Arc<WriteThrottle>>) {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(_) => {}
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(_) => {}
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(_) => {}
+            VectorStorageEnum::DenseVolatile(_) => {}
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(_) => {}
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(_) => {}
+            VectorStorageEnum::DenseMemmap(_) => {}
+            VectorStorageEnum::DenseMemmapByte(_) => {}
+            VectorStorageEnum::DenseMemmapHalf(_) => {}
+            VectorStorageEnum::DenseMemmapDouble(_) => {}
+            VectorStorageEnum::DenseMemmapBfloat16(_) => {}
+
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(_) => {}
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(_) => {}
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(_) => {}
+
+            VectorStorageEnum::DenseAppendableMemmap(vs) => vs.set_write_throttle(write_throttle),
+            VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseAppendableMemmapDouble(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::DenseQuantized(_) => {}
+            VectorStorageEnum::DenseVersioned(vs) => {
+                vs.inner_mut().set_write_throttle(write_throttle)
+            }
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(_) => {}
+            VectorStorageEnum::SparseVolatile(_) => {}
+            VectorStorageEnum::SparseMmap(_) => {}
+            VectorStorageEnum::SparseAppendableWal(_) => {}
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(_) => {}
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(_) => {}
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(_) => {}
+            VectorStorageEnum::MultiDenseVolatile(_) => {}
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(_) => {}
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(_) => {}
+            VectorStorageEnum::MultiDenseAppendableMemmap(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
+                vs.set_write_throttle(write_throttle)
+            }
+            VectorStorageEnum::Tombstone(_) => {}
+        }
+    }
+
+    /// Storage, which keeps previous versions of vectors, if it is enabled for this vector
+    pub fn as_versioned(&self) -> Option<&VersionedDenseVectorStorage<VectorStorageEnum>> {
+        match self {
+            VectorStorageEnum::DenseVersioned(v) => Some(v.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Set version of the operation, which inserts the next vector into the storage.
+    ///
+    /// Only used by storages, which keep previous versions of vectors.
+    pub fn set_insert_version(&mut self, version: SeqNumberType) {
+        if let VectorStorageEnum::DenseVersioned(v) = self {
+            v.set_insert_version(version);
+        }
+    }
+
+    /// Check files of the storage against checksums, computed at flush
+    ///
+    /// Only chunked mmap storages keep checksums, other storages are not checked.
+    pub fn verify_integrity(&self) -> OperationResult<()> {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(_) => {} // No checksums
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(_) => {} // No checksums
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(_) => {} // No checksums
+            VectorStorageEnum::DenseVolatile(_) => {} // No checksums
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(_) => {} // No checksums
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(_) => {} // No checksums
+            VectorStorageEnum::DenseMemmap(_) => {}   // No checksums
+            VectorStorageEnum::DenseMemmapByte(_) => {} // No checksums
+            VectorStorageEnum::DenseMemmapHalf(_) => {} // No checksums
+            VectorStorageEnum::DenseMemmapDouble(_) => {} // No checksums
+            VectorStorageEnum::DenseMemmapBfloat16(_) => {} // No checksums
+
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(_) => {} // No checksums
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(_) => {} // No checksums
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(_) => {} // No checksums
+
+            VectorStorageEnum::DenseAppendableMemmap(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapByte(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapHalf(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapDouble(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapInt8(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseAppendableMemmapInt4(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseQuantized(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::DenseVersioned(vs) => vs.inner().verify_integrity()?,
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(_) => {} // No checksums
+            VectorStorageEnum::SparseVolatile(_) => {} // No checksums
+            VectorStorageEnum::SparseMmap(_) => {}     // No checksums
+            VectorStorageEnum::SparseAppendableWal(_) => {} // No checksums
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(_) => {} // No checksums
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(_) => {} // No checksums
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(_) => {} // No checksums
+            VectorStorageEnum::MultiDenseVolatile(_) => {} // No checksums
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(_) => {} // No checksums
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(_) => {} // No checksums
+            VectorStorageEnum::MultiDenseAppendableMemmap(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => vs.verify_integrity()?,
+            VectorStorageEnum::Tombstone(_) => {} // Nothing to verify
         }
         Ok(())
     }
 
+    /// Append vectors from `others` with bulk copies, if all storages are appendable mmap dense
+    /// storages of the same datatype and dimension.
+    ///
+    /// `points` are pairs of index in `others` and offset of the vector in that storage.
+    ///
+    /// Returns `None` without changing anything, if the fast path is not applicable. In this case
+    /// vectors should be added with [`VectorStorage::update_from`].
+    pub fn update_from_same(
+        &mut self,
+        others: &[&VectorStorageEnum],
+        points: &[(usize, PointOffsetType)],
+        stopped: &AtomicBool,
+    ) -> OperationResult<Option<Range<PointOffsetType>>> {
+        match self {
+            VectorStorageEnum::DenseAppendableMemmap(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmap(other) => Some(other.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapByte(other) => Some(other.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapHalf(other) => Some(other.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapDouble(other) => {
+                            Some(other.as_ref())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapBfloat16(other) => {
+                            Some(other.as_ref())
+                        }
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapInt8(other) => Some(other.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                let others = others
+                    .iter()
+                    .map(|other| match other {
+                        VectorStorageEnum::DenseAppendableMemmapInt4(other) => Some(other.as_ref()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>();
+                match others {
+                    Some(others) => v.update_from_same(&others, points, stopped),
+                    None => Ok(None),
+                }
+            }
+            // Bulk copy is only implemented for appendable mmap dense storages
+            _ => Ok(None),
+        }
+    }
+
     /// Call `f` with the raw bytes of the vector if it exists.
     pub fn with_vector_bytes_opt<P: AccessPattern, R>(
         &self,
@@ -600,6 +1238,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.with_dense_bytes_opt::<P, R>(key, f),
             VectorStorageEnum::DenseMemmapByte(v) => v.with_dense_bytes_opt::<P, R>(key, f),
             VectorStorageEnum::DenseMemmapHalf(v) => v.with_dense_bytes_opt::<P, R>(key, f),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.with_dense_bytes_opt::<P, R>(key, f),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.with_dense_bytes_opt::<P, R>(key, f),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.with_dense_bytes_opt::<P, R>(key, f),
@@ -615,10 +1255,25 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 v.with_dense_bytes_opt::<P, R>(key, f)
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                v.with_dense_bytes_opt::<P, R>(key, f)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.with_dense_bytes_opt::<P, R>(key, f)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                v.with_dense_bytes_opt::<P, R>(key, f)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                v.with_dense_bytes_opt::<P, R>(key, f)
+            }
+            VectorStorageEnum::DenseQuantized(v) => v.with_dense_bytes_opt::<P, R>(key, f),
+            VectorStorageEnum::DenseVersioned(v) => v.inner().with_vector_bytes_opt::<P, R>(key, f),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => None,
             VectorStorageEnum::SparseVolatile(_) => None,
             VectorStorageEnum::SparseMmap(_) => None,
+            VectorStorageEnum::SparseAppendableWal(_) => None,
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(_) => None,
             #[cfg(feature = "rocksdb")]
@@ -633,6 +1288,7 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(_) => None,
             VectorStorageEnum::MultiDenseAppendableMemmapByte(_) => None,
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => None,
+            VectorStorageEnum::Tombstone(_) => None,
         }
     }
 
@@ -653,6 +1309,8 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => return v.get_dense_vector_layout(),
             VectorStorageEnum::DenseMemmapByte(v) => return v.get_dense_vector_layout(),
             VectorStorageEnum::DenseMemmapHalf(v) => return v.get_dense_vector_layout(),
+            VectorStorageEnum::DenseMemmapDouble(v) => return v.get_dense_vector_layout(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => return v.get_dense_vector_layout(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => return v.get_dense_vector_layout(),
@@ -664,10 +1322,29 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => return v.get_dense_vector_layout(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => return v.get_dense_vector_layout(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => return v.get_dense_vector_layout(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                return v.get_dense_vector_layout();
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                return v.get_dense_vector_layout();
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                return v.get_dense_vector_layout();
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                return v.get_dense_vector_layout();
+            }
+            VectorStorageEnum::DenseQuantized(v) => {
+                return v.get_dense_vector_layout();
+            }
+            VectorStorageEnum::DenseVersioned(v) => {
+                return v.inner().get_vector_layout();
+            }
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(_) => {}
             VectorStorageEnum::SparseVolatile(_) => {}
             VectorStorageEnum::SparseMmap(_) => {}
+            VectorStorageEnum::SparseAppendableWal(_) => {}
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(_) => {}
             #[cfg(feature = "rocksdb")]
@@ -682,6 +1359,7 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(_) => {}
             VectorStorageEnum::MultiDenseAppendableMemmapByte(_) => {}
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => {}
+            VectorStorageEnum::Tombstone(_) => {}
         }
         Err(OperationError::service_error(
             "Vector layout is not implemented for this storage",
@@ -706,6 +1384,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.distance(),
             VectorStorageEnum::DenseMemmapByte(v) => v.distance(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.distance(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.distance(),
@@ -717,10 +1397,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.distance(),
+            VectorStorageEnum::DenseQuantized(v) => v.distance(),
+            VectorStorageEnum::DenseVersioned(v) => v.distance(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.distance(),
             VectorStorageEnum::SparseVolatile(v) => v.distance(),
             VectorStorageEnum::SparseMmap(v) => v.distance(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.distance(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
             #[cfg(feature = "rocksdb")]
@@ -735,6 +1422,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.distance(),
+            VectorStorageEnum::Tombstone(v) => v.distance(),
         }
     }
 
@@ -754,6 +1442,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapByte(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.datatype(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.datatype(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.datatype(),
@@ -765,10 +1455,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.datatype(),
+            VectorStorageEnum::DenseQuantized(v) => v.datatype(),
+            VectorStorageEnum::DenseVersioned(v) => v.datatype(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.datatype(),
             VectorStorageEnum::SparseVolatile(v) => v.datatype(),
             VectorStorageEnum::SparseMmap(v) => v.datatype(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.datatype(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.datatype(),
             #[cfg(feature = "rocksdb")]
@@ -783,6 +1480,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.datatype(),
+            VectorStorageEnum::Tombstone(v) => v.datatype(),
         }
     }
 
@@ -804,6 +1502,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.is_on_disk(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.is_on_disk(),
@@ -815,10 +1515,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseQuantized(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseVersioned(v) => v.is_on_disk(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::SparseVolatile(v) => v.is_on_disk(),
             VectorStorageEnum::SparseMmap(v) => v.is_on_disk(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.is_on_disk(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
             #[cfg(feature = "rocksdb")]
@@ -833,6 +1540,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_on_disk(),
+            VectorStorageEnum::Tombstone(v) => v.is_on_disk(),
         }
     }
 
@@ -852,6 +1560,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.total_vector_count(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.total_vector_count(),
@@ -863,10 +1573,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseQuantized(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseVersioned(v) => v.total_vector_count(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::SparseVolatile(v) => v.total_vector_count(),
             VectorStorageEnum::SparseMmap(v) => v.total_vector_count(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.total_vector_count(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
             #[cfg(feature = "rocksdb")]
@@ -881,6 +1598,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.total_vector_count(),
+            VectorStorageEnum::Tombstone(v) => v.total_vector_count(),
         }
     }
 
@@ -900,6 +1618,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.get_vector::<P>(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector::<P>(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.get_vector::<P>(key),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.get_vector::<P>(key),
@@ -911,10 +1631,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector::<P>(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector::<P>(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseQuantized(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::DenseVersioned(v) => v.get_vector::<P>(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.get_vector::<P>(key),
             VectorStorageEnum::SparseVolatile(v) => v.get_vector::<P>(key),
             VectorStorageEnum::SparseMmap(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::SparseAppendableWal(v) => v.get_vector::<P>(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector::<P>(key),
             #[cfg(feature = "rocksdb")]
@@ -929,6 +1656,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector::<P>(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector::<P>(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector::<P>(key),
+            VectorStorageEnum::Tombstone(v) => v.get_vector::<P>(key),
         }
     }
 
@@ -952,6 +1680,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::DenseMemmapByte(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::DenseMemmapHalf(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.read_vectors::<P>(keys, callback),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.read_vectors::<P>(keys, callback),
@@ -963,10 +1693,21 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                v.read_vectors::<P>(keys, callback)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.read_vectors::<P>(keys, callback)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseQuantized(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::DenseVersioned(v) => v.read_vectors::<P>(keys, callback),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::SparseVolatile(v) => v.read_vectors::<P>(keys, callback),
             VectorStorageEnum::SparseMmap(v) => v.read_vectors::<P>(keys, callback),
+            VectorStorageEnum::SparseAppendableWal(v) => v.read_vectors::<P>(keys, callback),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.read_vectors::<P>(keys, callback),
             #[cfg(feature = "rocksdb")]
@@ -985,6 +1726,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.read_vectors::<P>(keys, callback)
             }
+            VectorStorageEnum::Tombstone(v) => v.read_vectors::<P>(keys, callback),
         }
     }
 
@@ -1004,6 +1746,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.get_vector_opt::<P>(key),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.get_vector_opt::<P>(key),
@@ -1015,10 +1759,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseQuantized(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::DenseVersioned(v) => v.get_vector_opt::<P>(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::SparseVolatile(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::SparseMmap(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::SparseAppendableWal(v) => v.get_vector_opt::<P>(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt::<P>(key),
             #[cfg(feature = "rocksdb")]
@@ -1033,6 +1784,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector_opt::<P>(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector_opt::<P>(key),
+            VectorStorageEnum::Tombstone(v) => v.get_vector_opt::<P>(key),
         }
     }
 
@@ -1057,6 +1809,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.insert_vector(key, vector, hw_counter),
             VectorStorageEnum::DenseMemmapByte(v) => v.insert_vector(key, vector, hw_counter),
             VectorStorageEnum::DenseMemmapHalf(v) => v.insert_vector(key, vector, hw_counter),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.insert_vector(key, vector, hw_counter),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.insert_vector(key, vector, hw_counter),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.insert_vector(key, vector, hw_counter),
@@ -1072,10 +1826,25 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 v.insert_vector(key, vector, hw_counter)
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                v.insert_vector(key, vector, hw_counter)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.insert_vector(key, vector, hw_counter)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                v.insert_vector(key, vector, hw_counter)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                v.insert_vector(key, vector, hw_counter)
+            }
+            VectorStorageEnum::DenseQuantized(v) => v.insert_vector(key, vector, hw_counter),
+            VectorStorageEnum::DenseVersioned(v) => v.insert_vector(key, vector, hw_counter),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector, hw_counter),
             VectorStorageEnum::SparseVolatile(v) => v.insert_vector(key, vector, hw_counter),
             VectorStorageEnum::SparseMmap(v) => v.insert_vector(key, vector, hw_counter),
+            VectorStorageEnum::SparseAppendableWal(v) => v.insert_vector(key, vector, hw_counter),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector, hw_counter),
             #[cfg(feature = "rocksdb")]
@@ -1100,6 +1869,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.insert_vector(key, vector, hw_counter)
             }
+            VectorStorageEnum::Tombstone(v) => v.insert_vector(key, vector, hw_counter),
         }
     }
 
@@ -1123,6 +1893,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.update_from(other_vectors, stopped),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.update_from(other_vectors, stopped),
@@ -1138,10 +1910,25 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 v.update_from(other_vectors, stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                v.update_from(other_vectors, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => {
+                v.update_from(other_vectors, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                v.update_from(other_vectors, stopped)
+            }
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => {
+                v.update_from(other_vectors, stopped)
+            }
+            VectorStorageEnum::DenseQuantized(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::DenseVersioned(v) => v.update_from(other_vectors, stopped),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::SparseVolatile(v) => v.update_from(other_vectors, stopped),
             VectorStorageEnum::SparseMmap(v) => v.update_from(other_vectors, stopped),
+            VectorStorageEnum::SparseAppendableWal(v) => v.update_from(other_vectors, stopped),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other_vectors, stopped),
             #[cfg(feature = "rocksdb")]
@@ -1162,6 +1949,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.update_from(other_vectors, stopped)
             }
+            VectorStorageEnum::Tombstone(v) => v.update_from(other_vectors, stopped),
         }
     }
 
@@ -1181,6 +1969,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapByte(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.flusher(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.flusher(),
@@ -1192,10 +1982,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.flusher(),
+            VectorStorageEnum::DenseQuantized(v) => v.flusher(),
+            VectorStorageEnum::DenseVersioned(v) => v.flusher(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
             VectorStorageEnum::SparseVolatile(v) => v.flusher(),
             VectorStorageEnum::SparseMmap(v) => v.flusher(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.flusher(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
             #[cfg(feature = "rocksdb")]
@@ -1210,6 +2007,65 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.flusher(),
+            VectorStorageEnum::Tombstone(v) => v.flusher(),
+        }
+    }
+
+    fn estimate_flush_size(&self) -> usize {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseVolatile(v) => v.estimate_flush_size(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(v) => v.estimate_flush_size(),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseMemmap(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseMemmapByte(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.estimate_flush_size(),
+
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(v) => v.estimate_flush_size(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(v) => v.estimate_flush_size(),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(v) => v.estimate_flush_size(),
+
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseQuantized(v) => v.estimate_flush_size(),
+            VectorStorageEnum::DenseVersioned(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(v) => v.estimate_flush_size(),
+            VectorStorageEnum::SparseVolatile(v) => v.estimate_flush_size(),
+            VectorStorageEnum::SparseMmap(v) => v.estimate_flush_size(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.estimate_flush_size(),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::MultiDenseVolatile(v) => v.estimate_flush_size(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(v) => v.estimate_flush_size(),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.estimate_flush_size(),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.estimate_flush_size(),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.estimate_flush_size(),
+            VectorStorageEnum::Tombstone(v) => v.estimate_flush_size(),
         }
     }
 
@@ -1229,6 +2085,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.files(),
             VectorStorageEnum::DenseMemmapByte(v) => v.files(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.files(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.files(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.files(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.files(),
@@ -1240,10 +2098,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.files(),
+            VectorStorageEnum::DenseQuantized(v) => v.files(),
+            VectorStorageEnum::DenseVersioned(v) => v.files(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.files(),
             VectorStorageEnum::SparseVolatile(v) => v.files(),
             VectorStorageEnum::SparseMmap(v) => v.files(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.files(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.files(),
             #[cfg(feature = "rocksdb")]
@@ -1258,6 +2123,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.files(),
+            VectorStorageEnum::Tombstone(v) => v.files(),
         }
     }
 
@@ -1277,6 +2143,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.immutable_files(),
             VectorStorageEnum::DenseMemmapByte(v) => v.immutable_files(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.immutable_files(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.immutable_files(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.immutable_files(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.immutable_files(),
@@ -1288,10 +2156,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.immutable_files(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.immutable_files(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.immutable_files(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.immutable_files(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.immutable_files(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.immutable_files(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.immutable_files(),
+            VectorStorageEnum::DenseQuantized(v) => v.immutable_files(),
+            VectorStorageEnum::DenseVersioned(v) => v.immutable_files(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.immutable_files(),
             VectorStorageEnum::SparseVolatile(v) => v.immutable_files(),
             VectorStorageEnum::SparseMmap(v) => v.immutable_files(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.immutable_files(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.immutable_files(),
             #[cfg(feature = "rocksdb")]
@@ -1306,6 +2181,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.immutable_files(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.immutable_files(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.immutable_files(),
+            VectorStorageEnum::Tombstone(v) => v.immutable_files(),
         }
     }
 
@@ -1325,6 +2201,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.delete_vector(key),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.delete_vector(key),
@@ -1336,10 +2214,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseQuantized(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseVersioned(v) => v.delete_vector(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::SparseVolatile(v) => v.delete_vector(key),
             VectorStorageEnum::SparseMmap(v) => v.delete_vector(key),
+            VectorStorageEnum::SparseAppendableWal(v) => v.delete_vector(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
             #[cfg(feature = "rocksdb")]
@@ -1354,6 +2239,65 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.delete_vector(key),
+            VectorStorageEnum::Tombstone(v) => v.delete_vector(key),
+        }
+    }
+
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        match self {
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimple(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleByte(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::DenseSimpleHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseVolatile(v) => v.delete_vectors(keys),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileByte(v) => v.delete_vectors(keys),
+            #[cfg(test)]
+            VectorStorageEnum::DenseVolatileHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseMemmap(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseMemmapByte(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.delete_vectors(keys),
+
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUring(v) => v.delete_vectors(keys),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringByte(v) => v.delete_vectors(keys),
+            #[cfg(target_os = "linux")]
+            VectorStorageEnum::DenseUringHalf(v) => v.delete_vectors(keys),
+
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseQuantized(v) => v.delete_vectors(keys),
+            VectorStorageEnum::DenseVersioned(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::SparseSimple(v) => v.delete_vectors(keys),
+            VectorStorageEnum::SparseVolatile(v) => v.delete_vectors(keys),
+            VectorStorageEnum::SparseMmap(v) => v.delete_vectors(keys),
+            VectorStorageEnum::SparseAppendableWal(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimple(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.delete_vectors(keys),
+            #[cfg(feature = "rocksdb")]
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::MultiDenseVolatile(v) => v.delete_vectors(keys),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileByte(v) => v.delete_vectors(keys),
+            #[cfg(test)]
+            VectorStorageEnum::MultiDenseVolatileHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.delete_vectors(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.delete_vectors(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.delete_vectors(keys),
+            VectorStorageEnum::Tombstone(v) => v.delete_vectors(keys),
         }
     }
 
@@ -1373,6 +2317,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.is_deleted_vector(key),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.is_deleted_vector(key),
@@ -1384,10 +2330,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseQuantized(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseVersioned(v) => v.is_deleted_vector(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseVolatile(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseMmap(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::SparseAppendableWal(v) => v.is_deleted_vector(key),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
             #[cfg(feature = "rocksdb")]
@@ -1402,6 +2355,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::Tombstone(v) => v.is_deleted_vector(key),
         }
     }
 
@@ -1421,6 +2375,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.deleted_vector_count(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.deleted_vector_count(),
@@ -1432,10 +2388,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseQuantized(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseVersioned(v) => v.deleted_vector_count(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseVolatile(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseMmap(v) => v.deleted_vector_count(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.deleted_vector_count(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
             #[cfg(feature = "rocksdb")]
@@ -1450,6 +2413,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
+            VectorStorageEnum::Tombstone(v) => v.deleted_vector_count(),
         }
     }
 
@@ -1469,6 +2433,8 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapDouble(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapBfloat16(v) => v.deleted_vector_bitslice(),
 
             #[cfg(target_os = "linux")]
             VectorStorageEnum::DenseUring(v) => v.deleted_vector_bitslice(),
@@ -1480,10 +2446,17 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapBfloat16(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapInt4(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseQuantized(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseVersioned(v) => v.deleted_vector_bitslice(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseVolatile(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseMmap(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::SparseAppendableWal(v) => v.deleted_vector_bitslice(),
             #[cfg(feature = "rocksdb")]
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
             #[cfg(feature = "rocksdb")]
@@ -1498,6 +2471,7 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::Tombstone(v) => v.deleted_vector_bitslice(),
         }
     }
 }
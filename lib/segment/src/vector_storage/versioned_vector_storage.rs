@@ -0,0 +1,566 @@
+//! Dense vector storage, which keeps previous versions of vectors.
+//!
+//! History of versions is kept in memory, and persisted in an append-only log. Every change of
+//! the history is appended to the log right away, as a single record, so that flushing only syncs
+//! the log to disk. On open, the log is replayed into memory. Once the log grows much larger than
+//! the kept history, it is rewritten on open.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{BufReader, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use common::bitvec::BitSlice;
+use common::counter::hardware_counter::HardwareCounterCell;
+use common::fs::is_sync_deferred;
+use common::generic_consts::{AccessPattern, Random};
+use common::types::PointOffsetType;
+use fs_err as fs;
+use fs_err::File;
+use serde::{Deserialize, Serialize};
+
+use crate::common::Flusher;
+use crate::common::log_records::{open_log, read_record, write_record};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, VectorElementType, VectorRef};
+use crate::types::{Distance, SeqNumberType, VectorStorageDatatype};
+use crate::vector_storage::{VectorStorage, VersionedVectorStorage};
+
+const HISTORY_LOG_FILE: &str = "vector_history.log";
+const COMPACTED_HISTORY_LOG_FILE: &str = "vector_history.log.tmp";
+
+/// Log is not rewritten while it is smaller than this
+const MIN_COMPACT_LOG_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Log is rewritten if it is this many times larger than the kept history
+const COMPACT_LOG_RATIO: u64 = 2;
+
+/// Difference between two versions of a dense vector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VectorDelta {
+    /// Elements of the older version, which differ from the newer one
+    changed: Vec<(u32, VectorElementType)>,
+}
+
+impl VectorDelta {
+    fn new(newer: &[VectorElementType], older: &[VectorElementType]) -> Self {
+        let changed = newer
+            .iter()
+            .zip(older)
+            .enumerate()
+            .filter(|(_, (newer, older))| newer.to_bits() != older.to_bits())
+            .map(|(idx, (_, &older))| (idx as u32, older))
+            .collect();
+        Self { changed }
+    }
+
+    /// Turn the newer version into the older one
+    fn apply(&self, vector: &mut [VectorElementType]) {
+        for &(idx, value) in &self.changed {
+            vector[idx as usize] = value;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryEntry {
+    /// Version of the operation, which wrote this vector
+    version: SeqNumberType,
+    /// Whether this vector was deleted
+    deleted: bool,
+    /// Delta from the next newer version to this one
+    delta: VectorDelta,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PointHistory {
+    /// Version of the operation, which wrote the current vector
+    current_version: SeqNumberType,
+    /// Previous versions of the vector, oldest first
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// Change of the history of a single vector, as persisted in the log
+#[derive(Debug, Serialize, Deserialize)]
+enum LogRecord {
+    /// Vector was written by the operation with `version`, replacing the vector of `replaced`
+    Insert {
+        key: PointOffsetType,
+        version: SeqNumberType,
+        replaced: Option<HistoryEntry>,
+    },
+    /// Vector was written without a version, so its history is dropped
+    Remove { key: PointOffsetType },
+    /// Complete history of the vector, written when the log is rewritten
+    History {
+        key: PointOffsetType,
+        history: PointHistory,
+    },
+}
+
+/// Wrapper around a dense vector storage, which keeps up to `max_versions` previous versions of
+/// every vector.
+///
+/// Previous versions are delta-encoded against the next newer one, so that only changed elements
+/// are kept. Reading an old version replays deltas starting from the current vector.
+///
+/// Only vectors inserted with [`VersionedVectorStorage::insert_vector_at_version`], or after
+/// [`Self::set_insert_version`], have history.
+#[derive(Debug)]
+pub struct VersionedDenseVectorStorage<S: VectorStorage> {
+    storage: S,
+    max_versions: usize,
+    log_path: PathBuf,
+    /// Log file, opened for appending. Shared with flushers.
+    log_file: Arc<File>,
+    history: BTreeMap<PointOffsetType, PointHistory>,
+    /// Version of the operation, which inserts the next vector with [`VectorStorage::insert_vector`]
+    insert_version: Option<SeqNumberType>,
+}
+
+impl<S: VectorStorage> VersionedDenseVectorStorage<S> {
+    /// Wrap `storage`, loading its history from `path`, if it exists
+    pub fn open(path: &Path, storage: S, max_versions: usize) -> OperationResult<Self> {
+        fs::create_dir_all(path)?;
+        let log_path = path.join(HISTORY_LOG_FILE);
+
+        let mut versioned = Self {
+            storage,
+            max_versions,
+            log_path: log_path.clone(),
+            log_file: Arc::new(open_log(&log_path)?),
+            history: BTreeMap::new(),
+            insert_version: None,
+        };
+
+        let log_len = versioned.replay_log()?;
+        if log_len >= MIN_COMPACT_LOG_SIZE {
+            versioned.compact_log(log_len)?;
+        }
+
+        Ok(versioned)
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn inner_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Set version of the operation, which inserts the next vector with
+    /// [`VectorStorage::insert_vector`].
+    ///
+    /// Vectors are inserted by vector indexes, which are not aware of versions, so the version is
+    /// passed to the storage upfront.
+    pub fn set_insert_version(&mut self, version: SeqNumberType) {
+        self.insert_version = Some(version);
+    }
+
+    /// Whether versions of the vector are tracked.
+    ///
+    /// Vectors, inserted without a version, have no history.
+    pub fn has_history(&self, key: PointOffsetType) -> bool {
+        self.history.contains_key(&key)
+    }
+
+    /// Number of previous versions, kept for the vector
+    pub fn history_len(&self, key: PointOffsetType) -> usize {
+        self.history
+            .get(&key)
+            .map_or(0, |history| history.entries.len())
+    }
+
+    /// Apply all records of the log.
+    /// Returns the length of the log, after truncating an incomplete record at its end.
+    fn replay_log(&mut self) -> OperationResult<u64> {
+        let file_len = self.log_file.metadata()?.len();
+        let mut reader = BufReader::new(File::open(&self.log_path)?);
+
+        let mut read_to = 0;
+        while let Some((record, record_size)) = read_record(&mut reader)? {
+            self.apply_record(record);
+            read_to += record_size as u64;
+        }
+
+        debug_assert!(read_to <= file_len, "cannot read past the end of the file");
+        if read_to < file_len {
+            log::warn!(
+                "Vector history log ends with incomplete record, removing last {} bytes and assuming automatic recovery by WAL",
+                file_len - read_to,
+            );
+            self.log_file.set_len(read_to)?;
+            self.log_file.sync_all()?;
+        }
+
+        Ok(read_to)
+    }
+
+    fn apply_record(&mut self, record: LogRecord) {
+        match record {
+            LogRecord::Insert {
+                key,
+                version,
+                replaced,
+            } => {
+                let history = self.history.entry(key).or_default();
+                if let Some(entry) = replaced {
+                    history.entries.push_back(entry);
+                    while history.entries.len() > self.max_versions {
+                        history.entries.pop_front();
+                    }
+                }
+                history.current_version = history.current_version.max(version);
+            }
+            LogRecord::Remove { key } => {
+                self.history.remove(&key);
+            }
+            LogRecord::History { key, history } => {
+                self.history.insert(key, history);
+            }
+        }
+    }
+
+    /// Rewrite the log with the current history, if it is much larger than that
+    fn compact_log(&mut self, log_len: u64) -> OperationResult<()> {
+        let mut compacted = Vec::new();
+        for (&key, history) in &self.history {
+            write_record(
+                &mut compacted,
+                &LogRecord::History {
+                    key,
+                    history: history.clone(),
+                },
+            )?;
+        }
+
+        if (compacted.len() as u64).saturating_mul(COMPACT_LOG_RATIO) > log_len {
+            return Ok(());
+        }
+
+        log::debug!(
+            "Compacting vector history log {} from {log_len} to {} bytes",
+            self.log_path.display(),
+            compacted.len(),
+        );
+
+        let compacted_path = self.log_path.with_file_name(COMPACTED_HISTORY_LOG_FILE);
+        let mut compacted_file = File::create(&compacted_path)?;
+        compacted_file.write_all(&compacted)?;
+        compacted_file.sync_all()?;
+        fs::rename(&compacted_path, &self.log_path)?;
+
+        self.log_file = Arc::new(open_log(&self.log_path)?);
+        Ok(())
+    }
+
+    /// Append the change of the history to the log, and apply it
+    fn append_record(
+        &mut self,
+        record: LogRecord,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let mut buffer = Vec::new();
+        write_record(&mut buffer, &record)?;
+
+        hw_counter
+            .vector_io_write_counter()
+            .incr_delta(buffer.len());
+
+        // Single write, so that a crash can only cut the last record short
+        (&*self.log_file).write_all(&buffer)?;
+        self.apply_record(record);
+        Ok(())
+    }
+}
+
+impl<S: VectorStorage> VersionedVectorStorage for VersionedDenseVectorStorage<S> {
+    fn insert_vector_at_version(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        version: SeqNumberType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        let new_vector: &[VectorElementType] = vector.try_into()?;
+
+        let current_version = self
+            .history
+            .get(&key)
+            .map(|history| history.current_version);
+
+        // Operations replayed from WAL must not duplicate history entries
+        let mut replaced = None;
+        if let Some(current_version) = current_version
+            && current_version < version
+            && self.max_versions > 0
+            && let Some(old_vector) = self.storage.get_vector_opt::<Random>(key)
+        {
+            let old_vector: &[VectorElementType] = old_vector.as_vec_ref().try_into()?;
+            replaced = Some(HistoryEntry {
+                version: current_version,
+                deleted: self.storage.is_deleted_vector(key),
+                delta: VectorDelta::new(new_vector, old_vector),
+            });
+        }
+
+        self.storage.insert_vector(key, vector, hw_counter)?;
+
+        if replaced.is_none() && current_version.is_some_and(|current| current >= version) {
+            return Ok(());
+        }
+        self.append_record(
+            LogRecord::Insert {
+                key,
+                version,
+                replaced,
+            },
+            hw_counter,
+        )
+    }
+
+    fn get_vector_at_version(
+        &self,
+        key: PointOffsetType,
+        version: SeqNumberType,
+    ) -> Option<CowVector<'_>> {
+        let history = self.history.get(&key)?;
+        let current_vector = self.storage.get_vector_opt::<Random>(key)?;
+
+        if version >= history.current_version {
+            return (!self.storage.is_deleted_vector(key)).then_some(current_vector);
+        }
+
+        let current_vector: &[VectorElementType] = current_vector.as_vec_ref().try_into().ok()?;
+        let mut vector: DenseVector = current_vector.to_vec();
+        for entry in history.entries.iter().rev() {
+            entry.delta.apply(&mut vector);
+            if entry.version <= version {
+                return (!entry.deleted).then(|| CowVector::from(vector));
+            }
+        }
+
+        // Version is older than the kept history
+        None
+    }
+}
+
+impl<S: VectorStorage> VectorStorage for VersionedDenseVectorStorage<S> {
+    fn distance(&self) -> Distance {
+        self.storage.distance()
+    }
+
+    fn datatype(&self) -> VectorStorageDatatype {
+        self.storage.datatype()
+    }
+
+    fn is_on_disk(&self) -> bool {
+        self.storage.is_on_disk()
+    }
+
+    fn total_vector_count(&self) -> usize {
+        self.storage.total_vector_count()
+    }
+
+    fn get_vector<P: AccessPattern>(&self, key: PointOffsetType) -> CowVector<'_> {
+        self.storage.get_vector::<P>(key)
+    }
+
+    fn read_vectors<P: AccessPattern>(
+        &self,
+        keys: impl IntoIterator<Item = PointOffsetType>,
+        callback: impl FnMut(PointOffsetType, CowVector<'_>),
+    ) {
+        self.storage.read_vectors::<P>(keys, callback)
+    }
+
+    fn get_vector_opt<P: AccessPattern>(&self, key: PointOffsetType) -> Option<CowVector<'_>> {
+        self.storage.get_vector_opt::<P>(key)
+    }
+
+    fn insert_vector(
+        &mut self,
+        key: PointOffsetType,
+        vector: VectorRef,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<()> {
+        if let Some(version) = self.insert_version.take() {
+            return self.insert_vector_at_version(key, vector, version, hw_counter);
+        }
+
+        // Version of the vector is unknown, so its history can not be continued
+        if self.history.contains_key(&key) {
+            self.append_record(LogRecord::Remove { key }, hw_counter)?;
+        }
+        self.storage.insert_vector(key, vector, hw_counter)
+    }
+
+    fn update_from<'a>(
+        &mut self,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Range<PointOffsetType>> {
+        self.storage.update_from(other_vectors, stopped)
+    }
+
+    fn flusher(&self) -> Flusher {
+        let storage_flusher = self.storage.flusher();
+        let log_file = self.log_file.clone();
+        Box::new(move || {
+            // History refers to the stored vectors, so persist them first
+            storage_flusher()?;
+            if is_sync_deferred() {
+                return Ok(());
+            }
+            log_file.sync_data().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush vector history log: {err}"))
+            })
+        })
+    }
+
+    fn estimate_flush_size(&self) -> usize {
+        self.storage.estimate_flush_size()
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.storage.files();
+        files.push(self.log_path.clone());
+        files
+    }
+
+    fn immutable_files(&self) -> Vec<PathBuf> {
+        self.storage.immutable_files()
+    }
+
+    fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
+        self.storage.delete_vector(key)
+    }
+
+    fn delete_vectors(&mut self, keys: &BitSlice) -> OperationResult<usize> {
+        self.storage.delete_vectors(keys)
+    }
+
+    fn is_deleted_vector(&self, key: PointOffsetType) -> bool {
+        self.storage.is_deleted_vector(key)
+    }
+
+    fn deleted_vector_count(&self) -> usize {
+        self.storage.deleted_vector_count()
+    }
+
+    fn deleted_vector_bitslice(&self) -> &BitSlice {
+        self.storage.deleted_vector_bitslice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::vector_storage::VectorStorageEnum;
+    use crate::vector_storage::dense::volatile_dense_vector_storage::new_volatile_dense_vector_storage;
+
+    fn dense_at_version(
+        storage: &VersionedDenseVectorStorage<VectorStorageEnum>,
+        key: PointOffsetType,
+        version: SeqNumberType,
+    ) -> Option<DenseVector> {
+        let vector = storage.get_vector_at_version(key, version)?;
+        let vector: &[VectorElementType] = vector.as_vec_ref().try_into().unwrap();
+        Some(vector.to_vec())
+    }
+
+    #[test]
+    fn test_get_vector_at_version() {
+        let dir = Builder::new().prefix("history_dir").tempdir().unwrap();
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut storage = VersionedDenseVectorStorage::open(
+            dir.path(),
+            new_volatile_dense_vector_storage(4, Distance::Dot),
+            2,
+        )
+        .unwrap();
+
+        let versions = [
+            (10, vec![1.0, 2.0, 3.0, 4.0]),
+            (20, vec![1.0, 2.0, 3.0, 5.0]),
+            (30, vec![0.0, 2.0, 3.0, 5.0]),
+            (40, vec![0.0, 0.0, 0.0, 0.0]),
+        ];
+        for (version, vector) in &versions {
+            storage
+                .insert_vector_at_version(0, VectorRef::from(vector), *version, &hw_counter)
+                .unwrap();
+        }
+        // Replayed operation does not change the history
+        storage
+            .insert_vector_at_version(0, VectorRef::from(&versions[3].1), 40, &hw_counter)
+            .unwrap();
+        assert_eq!(storage.history_len(0), 2);
+
+        // Only 2 previous versions are kept
+        assert_eq!(dense_at_version(&storage, 0, 15), None);
+        assert_eq!(
+            dense_at_version(&storage, 0, 20),
+            Some(versions[1].1.clone())
+        );
+        assert_eq!(
+            dense_at_version(&storage, 0, 35),
+            Some(versions[2].1.clone())
+        );
+        assert_eq!(
+            dense_at_version(&storage, 0, 40),
+            Some(versions[3].1.clone())
+        );
+        assert_eq!(
+            dense_at_version(&storage, 0, 100),
+            Some(versions[3].1.clone())
+        );
+
+        // Deleted vector is not returned for versions after the deletion
+        storage.delete_vector(0).unwrap();
+        assert_eq!(dense_at_version(&storage, 0, 40), None);
+        assert_eq!(
+            dense_at_version(&storage, 0, 35),
+            Some(versions[2].1.clone())
+        );
+
+        // Vectors inserted without version have no history
+        storage
+            .insert_vector(1, VectorRef::from(&versions[0].1), &hw_counter)
+            .unwrap();
+        assert_eq!(dense_at_version(&storage, 1, 100), None);
+
+        // Version of vectors, inserted by vector indexes, is set upfront
+        storage.set_insert_version(50);
+        storage
+            .insert_vector(2, VectorRef::from(&versions[0].1), &hw_counter)
+            .unwrap();
+        storage.set_insert_version(60);
+        storage
+            .insert_vector(2, VectorRef::from(&versions[1].1), &hw_counter)
+            .unwrap();
+        assert_eq!(
+            dense_at_version(&storage, 2, 55),
+            Some(versions[0].1.clone())
+        );
+
+        // History is replayed from the log
+        storage.flusher()().unwrap();
+        let storage = VersionedDenseVectorStorage::open(
+            dir.path(),
+            new_volatile_dense_vector_storage(4, Distance::Dot),
+            2,
+        )
+        .unwrap();
+        assert_eq!(storage.history_len(0), 2);
+        assert_eq!(storage.history_len(1), 0);
+        assert_eq!(storage.history_len(2), 1);
+    }
+}
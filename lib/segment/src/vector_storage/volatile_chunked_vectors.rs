@@ -5,6 +5,7 @@ use std::mem;
 use crate::common::vector_utils::{TrySetCapacity, TrySetCapacityExact};
 use crate::vector_storage::VectorOffsetType;
 use crate::vector_storage::common::CHUNK_SIZE;
+use crate::vector_storage::memory_budget::MemoryReservation;
 
 #[derive(Debug)]
 pub struct VolatileChunkedVectors<T> {
@@ -17,6 +18,8 @@ pub struct VolatileChunkedVectors<T> {
     /// Maximum number of vectors in each chunk.
     chunk_capacity: usize,
     chunks: Vec<Vec<T>>,
+    /// Memory, allocated for all chunks
+    memory_reservation: MemoryReservation,
 }
 
 impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
@@ -30,6 +33,7 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
             len: 0,
             chunk_capacity,
             chunks: Vec::new(),
+            memory_reservation: MemoryReservation::default(),
         }
     }
 
@@ -41,6 +45,18 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
         self.len == 0
     }
 
+    /// Number of bytes, allocated for vectors
+    pub fn ram_usage_bytes(&self) -> usize {
+        self.memory_reservation.bytes()
+    }
+
+    fn grow_memory_reservation(&mut self, allocated_elements: usize) {
+        if allocated_elements > 0 {
+            let bytes = self.memory_reservation.bytes() + allocated_elements * mem::size_of::<T>();
+            self.memory_reservation.resize(bytes);
+        }
+    }
+
     pub fn get(&self, key: VectorOffsetType) -> &[T] {
         self.get_opt(key).expect("vector not found")
     }
@@ -106,13 +122,17 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
         let desired_capacity = self.chunk_capacity * self.dim;
         let new_len = max(self.len, key + vectors_count);
         let chunks_len = new_len.div_ceil(self.chunk_capacity);
+        // Number of elements, allocated by this insertion
+        let mut allocated_elements = 0;
 
         if chunks_len > self.chunks.len() {
             // All chunks except the last one should be fully allocated.
             // If we are going to add new chunks, resize last one which may be partially allocated.
             if let Some(last_chunk) = self.chunks.last_mut() {
+                let capacity_before = last_chunk.capacity();
                 last_chunk.try_set_capacity_exact(desired_capacity)?;
                 last_chunk.resize_with(desired_capacity, T::default);
+                allocated_elements += last_chunk.capacity().saturating_sub(capacity_before);
             }
 
             self.chunks.try_set_capacity(chunks_len)?;
@@ -125,6 +145,7 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
                 let mut chunk = Vec::new();
                 chunk.try_set_capacity_exact(desired_capacity)?;
                 chunk.resize_with(desired_capacity, T::default);
+                allocated_elements += chunk.capacity();
                 self.chunks.push(chunk);
             }
 
@@ -148,11 +169,13 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
         // All other chunks allocate their capacity in full on first use to prevent expensive
         // reallocations when their data grows.
         if chunk_data.len() < idx + vectors.len() {
+            let capacity_before = chunk_data.capacity();
             // If the chunk is not the first one, allocate it fully on first use
             if chunk_idx != 0 {
                 chunk_data.try_set_capacity_exact(desired_capacity)?;
             }
             chunk_data.resize_with(idx + vectors.len(), T::default);
+            allocated_elements += chunk_data.capacity().saturating_sub(capacity_before);
         }
 
         let data = &mut chunk_data[idx..idx + vectors.len()];
@@ -161,6 +184,7 @@ impl<T: Copy + Clone + Default> VolatileChunkedVectors<T> {
         // Update `self.len` only after the vector is successfully inserted.
         // In case of OOM, `self.len` will not be updated.
         self.len = new_len;
+        self.grow_memory_reservation(allocated_elements);
 
         Ok(())
     }
@@ -181,6 +205,9 @@ impl<T: Clone> TrySetCapacityExact for VolatileChunkedVectors<T> {
                 self.chunks[chunk_idx].try_set_capacity_exact(desired_capacity)?;
             }
         }
+        let allocated: usize = self.chunks.iter().map(Vec::capacity).sum();
+        self.memory_reservation
+            .resize(allocated * mem::size_of::<T>());
         Ok(())
     }
 }
@@ -208,6 +235,10 @@ mod tests {
 
         // check if middle chunk is fully allocated
         assert_eq!(vectors.get(5_000_000), &[0, 0, 0]);
+
+        // all allocated chunks are reserved from the memory budget
+        let allocated: usize = vectors.chunks.iter().map(Vec::capacity).sum();
+        assert_eq!(vectors.ram_usage_bytes(), allocated * mem::size_of::<i32>());
     }
 
     #[test]
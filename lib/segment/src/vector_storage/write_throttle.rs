@@ -0,0 +1,90 @@
+//! Backpressure on writes into mmap vector storages.
+//!
+//! Writes into appendable mmap storages only dirty pages in the page cache, which are written
+//! back to disk by the next flush. If vectors are inserted faster than flushes drain them, dirty
+//! pages pile up, until the kernel stalls all writers at once to write them back. A
+//! [`WriteThrottle`] smooths this out: once a storage has more dirty bytes than allowed, each
+//! further insert has to wait for its share of the configured write rate.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use common::rate_limiting::{RateLimitError, RateLimiter, RetryError};
+use parking_lot::Mutex;
+
+/// Rate limiter of inserts into vector storages, usually shared by all segments of a shard
+#[derive(Debug)]
+pub struct WriteThrottle {
+    /// Number of dirty bytes of a storage, up to which writes are not limited
+    max_dirty_bytes: usize,
+    limiter: Mutex<RateLimiter>,
+    /// Number of writes, which had to wait for the rate limiter
+    throttled_writes: AtomicUsize,
+}
+
+impl WriteThrottle {
+    pub fn new(max_dirty_bytes: usize, max_bytes_per_sec: usize) -> Self {
+        Self {
+            max_dirty_bytes,
+            limiter: Mutex::new(RateLimiter::new_per_second(max_bytes_per_sec.max(1))),
+            throttled_writes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Block until writing `bytes` into a storage with `dirty_bytes` not yet flushed is allowed
+    pub fn throttle(&self, dirty_bytes: usize, bytes: usize) {
+        if dirty_bytes <= self.max_dirty_bytes {
+            return;
+        }
+
+        let mut throttled = false;
+        loop {
+            let result = self.limiter.lock().try_consume(bytes as f64);
+            match result {
+                Ok(()) => break,
+                Err(RateLimitError::Retry(RetryError { retry_after, .. })) => {
+                    throttled = true;
+                    thread::sleep(retry_after);
+                }
+                // Single vector is larger than the rate limit per second, let it through
+                Err(RateLimitError::AlwaysOverBudget(_)) => break,
+            }
+        }
+
+        if throttled {
+            self.throttled_writes.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn throttled_writes(&self) -> usize {
+        self.throttled_writes.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn test_throttle_above_dirty_limit() {
+        let throttle = WriteThrottle::new(1000, 100);
+
+        // Below the limit of dirty bytes, writes are never delayed
+        for _ in 0..10 {
+            throttle.throttle(1000, 100);
+        }
+        assert_eq!(throttle.throttled_writes(), 0);
+
+        // Above it, the initial burst is allowed, further writes wait for the rate limit
+        let started = Instant::now();
+        throttle.throttle(1001, 100);
+        throttle.throttle(1001, 10);
+        assert!(started.elapsed() >= Duration::from_millis(90));
+        assert_eq!(throttle.throttled_writes(), 1);
+
+        // Writes larger than the rate limit per second are not blocked forever
+        throttle.throttle(1001, 1000);
+    }
+}
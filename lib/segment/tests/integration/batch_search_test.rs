@@ -144,6 +144,9 @@ fn test_batch_and_single_request_equivalency() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = get_num_indexing_threads(hnsw_config.max_indexing_threads);
@@ -90,6 +90,10 @@ fn test_byte_storage_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: Some(storage_data_type),
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -175,6 +179,9 @@ fn test_byte_storage_hnsw(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
@@ -43,7 +43,11 @@ where
     R: Rng + ?Sized,
 {
     match data_type {
-        VectorStorageDatatype::Float32 => unreachable!(),
+        VectorStorageDatatype::Float32
+        | VectorStorageDatatype::Float64
+        | VectorStorageDatatype::Int8
+        | VectorStorageDatatype::Bfloat16
+        | VectorStorageDatatype::Int4 => unreachable!(),
         VectorStorageDatatype::Float16 => {
             let mut vector = segment::fixtures::payload_fixtures::random_vector(rnd_gen, dim);
             vector.iter_mut().for_each(|x| *x -= 0.5);
@@ -229,6 +233,10 @@ fn test_byte_storage_binary_quantization_hnsw(
                 quantization_config: None,
                 multivector_config: None,
                 datatype: Some(storage_data_type),
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -288,11 +296,13 @@ fn test_byte_storage_binary_quantization_hnsw(
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
         QuantizationVariant::PQ => ProductQuantizationConfig {
             compression: CompressionRatio::X8,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
@@ -328,6 +338,9 @@ fn test_byte_storage_binary_quantization_hnsw(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
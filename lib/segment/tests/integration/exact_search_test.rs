@@ -82,6 +82,9 @@ fn exact_search_test() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     payload_index_ptr
@@ -35,22 +35,25 @@ fn random_query<R: Rng + ?Sized>(variant: &QueryVariant, rng: &mut R, dim: usize
 }
 
 #[rstest]
-#[case::nearest(QueryVariant::Nearest, 32, 5)]
-#[case::discover(QueryVariant::Discover, 128, 10)] // tests that check better precision are in `hnsw_discover_test.rs`
-#[case::reco_best_score(QueryVariant::RecoBestScore, 64, 10)]
-#[case::reco_sum_scores(QueryVariant::RecoSumScores, 64, 10)]
+#[case::nearest(QueryVariant::Nearest, 32, 5, false)]
+#[case::discover(QueryVariant::Discover, 128, 10, false)] // tests that check better precision are in `hnsw_discover_test.rs`
+#[case::reco_best_score(QueryVariant::RecoBestScore, 64, 10, false)]
+#[case::reco_sum_scores(QueryVariant::RecoSumScores, 64, 10, false)]
+#[case::nearest_acorn(QueryVariant::Nearest, 32, 10, true)]
 fn test_filterable_hnsw(
     #[case] query_variant: QueryVariant,
     #[case] ef: usize,
     #[case] max_failures: usize, // out of 100
+    #[case] acorn: bool,         // filtered search without payload-aware links
 ) {
-    _test_filterable_hnsw(query_variant, ef, max_failures);
+    _test_filterable_hnsw(query_variant, ef, max_failures, acorn);
 }
 
 fn _test_filterable_hnsw(
     query_variant: QueryVariant,
     ef: usize,
     max_failures: usize, // out of 100
+    acorn: bool,
 ) {
     let stopped = AtomicBool::new(false);
 
@@ -102,6 +105,9 @@ fn _test_filterable_hnsw(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: Some(acorn),
+        seed: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -289,6 +295,9 @@ fn test_hnsw_search_top_zero(#[case] num_vectors: u64, #[case] full_scan_thresho
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -142,6 +142,10 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        mmap_advice: None,
+                        dim_range: None,
+                        preserve_norm: None,
+                        max_versions: None,
                     },
                 ),
                 (
@@ -154,6 +158,10 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        mmap_advice: None,
+                        dim_range: None,
+                        preserve_norm: None,
+                        max_versions: None,
                     },
                 ),
                 (
@@ -166,6 +174,10 @@ pub fn build_segment_3(path: &Path) -> Segment {
                         quantization_config: None,
                         multivector_config: None,
                         datatype: None,
+                        mmap_advice: None,
+                        dim_range: None,
+                        preserve_norm: None,
+                        max_versions: None,
                     },
                 ),
             ]),
@@ -265,6 +277,7 @@ pub fn build_segment_sparse_1(path: &Path) -> Segment {
                     index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
                     storage_type: SparseVectorStorageType::default(),
                     modifier: None,
+                    bm25: None,
                 },
             )]),
             payload_storage_type: Default::default(),
@@ -359,6 +372,7 @@ pub fn build_segment_sparse_2(path: &Path) -> Segment {
                     index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
                     storage_type: SparseVectorStorageType::default(),
                     modifier: None,
+                    bm25: None,
                 },
             )]),
             payload_storage_type: Default::default(),
@@ -96,6 +96,9 @@ fn hnsw_discover_precision() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
@@ -224,6 +227,9 @@ fn filtered_hnsw_discover_precision() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = get_num_indexing_threads(hnsw_config.max_indexing_threads);
@@ -14,10 +14,12 @@ use rand::{Rng, RngExt, SeedableRng as _};
 use segment::data_types::vectors::{
     DEFAULT_VECTOR_NAME, QueryVector, VectorElementType, only_default_vector,
 };
-use segment::entry::SegmentEntry as _;
+use segment::entry::{NonAppendableSegmentEntry as _, SegmentEntry as _};
 use segment::fixtures::index_fixtures::random_vector;
+use segment::id_tracker::IdTracker as _;
 use segment::index::VectorIndexEnum;
 use segment::index::hnsw_index::get_num_indexing_threads;
+use segment::index::hnsw_index::graph_layers::GraphLayersBase as _;
 use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
 use segment::segment::Segment;
 use segment::segment_constructor::VectorIndexBuildArgs;
@@ -95,6 +97,84 @@ fn hnsw_incremental_build() {
     }
 }
 
+#[test]
+fn hnsw_heal_graph() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("hnsw_heal_graph").tempdir().unwrap();
+
+    let num_points = NUM_POINTS / ITERATIONS;
+    let ids = std::iter::repeat_with(|| ExtendedPointId::NumId(rng.random()))
+        .unique()
+        .take(num_points)
+        .collect_vec();
+    let vectors = std::iter::repeat_with(|| random_vector(&mut rng, DIM))
+        .take(num_points)
+        .collect_vec();
+    let vector_refs = vectors.iter().map(|v| v.as_slice()).collect_vec();
+
+    let query_vectors: Vec<QueryVector> = (0..10)
+        .map(|_| random_vector(&mut rng, DIM).into())
+        .collect();
+
+    let mut segment = make_segment(&mut rng, &dir.path().join("segment"), &ids, &vector_refs);
+    let index_path = dir.path().join("hnsw");
+    let mut index = build_hnsw_index(&mut rng, &index_path, &segment, &[]);
+    assert_eq!(index.deleted_linked_ratio(), 0.0);
+
+    let hw_counter = HardwareCounterCell::new();
+    let num_deleted = num_points * 3 / 10;
+    for (n, id) in ids.iter().take(num_deleted).enumerate() {
+        segment
+            .delete_point((num_points + n) as SeqNumberType, *id, &hw_counter)
+            .unwrap();
+    }
+    let deleted_ratio = index.deleted_linked_ratio();
+    assert!((deleted_ratio - num_deleted as f64 / num_points as f64).abs() < f64::EPSILON);
+
+    let healed_graph = index
+        .heal_graph(2, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    index.replace_graph(healed_graph).unwrap();
+    assert_eq!(index.deleted_linked_ratio(), 0.0);
+    assert!(!index_path.join("heal").exists());
+
+    let check_links = |index: &HNSWIndex| {
+        let id_tracker = segment.id_tracker.borrow();
+        let graph = index.graph();
+        for point_id in 0..graph.num_points() as u32 {
+            if id_tracker.is_deleted_point(point_id) {
+                continue;
+            }
+            for level in 0..=graph.point_level(point_id) {
+                graph.for_each_link(point_id, level, |link| {
+                    assert!(!id_tracker.is_deleted_point(link));
+                });
+            }
+        }
+    };
+    check_links(&index);
+    check_matches(&query_vectors, &segment, &index, None, 64, 10);
+
+    // Healed graph is loaded on reopen
+    drop(index);
+    let index = HNSWIndex::open(HnswIndexOpenArgs {
+        path: &index_path,
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: Arc::clone(&segment.payload_index),
+        hnsw_config: index_hnsw_config(),
+    })
+    .unwrap();
+    assert_eq!(index.deleted_linked_ratio(), 0.0);
+    check_links(&index);
+    check_matches(&query_vectors, &segment, &index, None, 64, 10);
+}
+
 fn make_segment(
     rng: &mut StdRng,
     path: &Path,
@@ -125,15 +205,7 @@ fn build_hnsw_index<R: Rng + ?Sized>(
 ) -> HNSWIndex {
     log::info!("Building HNSW index for {:?}", path.file_name().unwrap());
 
-    let hnsw_config = HnswConfig {
-        m: M,
-        ef_construct: EF_CONSTRUCT,
-        full_scan_threshold: 1,
-        max_indexing_threads: 0,
-        on_disk: Some(false),
-        payload_m: None,
-        inline_storage: None,
-    };
+    let hnsw_config = index_hnsw_config();
 
     let permit_cpu_count = get_num_indexing_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(ResourcePermit::dummy(permit_cpu_count as u32));
@@ -164,3 +236,18 @@ fn build_hnsw_index<R: Rng + ?Sized>(
     )
     .unwrap()
 }
+
+fn index_hnsw_config() -> HnswConfig {
+    HnswConfig {
+        m: M,
+        ef_construct: EF_CONSTRUCT,
+        full_scan_threshold: 1,
+        max_indexing_threads: 0,
+        on_disk: Some(false),
+        payload_m: None,
+        inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
+    }
+}
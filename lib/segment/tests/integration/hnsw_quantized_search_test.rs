@@ -24,9 +24,9 @@ use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
 use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{
-    CompressionRatio, Condition, Distance, FieldCondition, Filter, HnswConfig, HnswGlobalConfig,
-    Indexes, ProductQuantizationConfig, QuantizationConfig, QuantizationSearchParams,
-    ScalarQuantizationConfig, SearchParams,
+    AnisotropicQuantizationConfig, CompressionRatio, Condition, Distance, FieldCondition, Filter,
+    HnswConfig, HnswGlobalConfig, Indexes, ProductQuantizationConfig, QuantizationConfig,
+    QuantizationSearchParams, ResidualQuantizationConfig, ScalarQuantizationConfig, SearchParams,
 };
 use segment::vector_storage::quantized::quantized_vectors::{
     QuantizedVectors, QuantizedVectorsStorageType,
@@ -118,6 +118,9 @@ fn hnsw_quantized_search_test(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 2;
@@ -328,6 +331,7 @@ fn hnsw_quantized_search_cosine_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
         true,
@@ -344,6 +348,7 @@ fn hnsw_quantized_search_euclid_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
         true,
@@ -360,6 +365,7 @@ fn hnsw_quantized_search_manhattan_test() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
         true,
@@ -375,6 +381,7 @@ fn hnsw_product_quantization_cosine_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            drop_originals: None,
         }
         .into(),
         false,
@@ -390,6 +397,7 @@ fn hnsw_product_quantization_euclid_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            drop_originals: None,
         }
         .into(),
         false,
@@ -405,6 +413,71 @@ fn hnsw_product_quantization_manhattan_test() {
         ProductQuantizationConfig {
             compression: CompressionRatio::X4,
             always_ram: Some(true),
+            drop_originals: None,
+        }
+        .into(),
+        false,
+    );
+}
+
+#[test]
+fn hnsw_residual_quantization_cosine_test() {
+    hnsw_quantized_search_test(
+        Distance::Cosine,
+        1003,
+        64,
+        ResidualQuantizationConfig {
+            compression: CompressionRatio::X4,
+            stages: 2,
+            always_ram: Some(true),
+        }
+        .into(),
+        false,
+    );
+}
+
+#[test]
+fn hnsw_residual_quantization_euclid_test() {
+    hnsw_quantized_search_test(
+        Distance::Euclid,
+        1003,
+        64,
+        ResidualQuantizationConfig {
+            compression: CompressionRatio::X4,
+            stages: 2,
+            always_ram: None,
+        }
+        .into(),
+        false,
+    );
+}
+
+#[test]
+fn hnsw_anisotropic_quantization_dot_test() {
+    hnsw_quantized_search_test(
+        Distance::Dot,
+        1003,
+        64,
+        AnisotropicQuantizationConfig {
+            compression: CompressionRatio::X4,
+            parallel_weight: None,
+            always_ram: Some(true),
+        }
+        .into(),
+        false,
+    );
+}
+
+#[test]
+fn hnsw_anisotropic_quantization_cosine_test() {
+    hnsw_quantized_search_test(
+        Distance::Cosine,
+        1003,
+        64,
+        AnisotropicQuantizationConfig {
+            compression: CompressionRatio::X4,
+            parallel_weight: Some(10.0),
+            always_ram: None,
         }
         .into(),
         false,
@@ -426,6 +499,7 @@ fn test_build_hnsw_using_quantization() {
             r#type: Default::default(),
             quantile: None,
             always_ram: None,
+            drop_originals: None,
         }
         .into(),
     );
@@ -437,6 +511,9 @@ fn test_build_hnsw_using_quantization() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     });
 
     let mut builder =
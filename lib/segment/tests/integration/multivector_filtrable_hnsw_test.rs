@@ -73,6 +73,10 @@ fn test_multi_filterable_hnsw(
                 quantization_config: None,
                 multivector_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -127,6 +131,9 @@ fn test_multi_filterable_hnsw(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
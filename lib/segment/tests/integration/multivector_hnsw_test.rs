@@ -115,6 +115,9 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     // single threaded mode to guarantee equivalency between single and multi hnsw
@@ -222,6 +222,10 @@ fn test_multivector_quantization_hnsw(
                 quantization_config: None,
                 multivector_config: Some(MultiVectorConfig::default()), // uses multivec config
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
@@ -269,11 +273,13 @@ fn test_multivector_quantization_hnsw(
             r#type: Default::default(),
             quantile: None,
             always_ram: Some(false),
+            drop_originals: None,
         }
         .into(),
         QuantizationVariant::PQ => ProductQuantizationConfig {
             compression: CompressionRatio::X8,
             always_ram: Some(false),
+            drop_originals: None,
         }
         .into(),
         QuantizationVariant::Binary => BinaryQuantizationConfig {
@@ -317,6 +323,9 @@ fn test_multivector_quantization_hnsw(
         on_disk: Some(false),
         payload_m: None,
         inline_storage: None,
+        varint_links: None,
+        acorn: None,
+        seed: None,
     };
 
     let permit_cpu_count = 1; // single-threaded for deterministic build
@@ -155,6 +155,7 @@ impl TestSegments {
                         is_principal: None,
                         on_disk: None,
                         enable_hnsw: None,
+                        enable_hnsw_ranges: None,
                     },
                 ))),
                 &hw_counter,
@@ -172,6 +173,7 @@ impl TestSegments {
                         is_principal: None,
                         on_disk: None,
                         enable_hnsw: None,
+                        enable_hnsw_ranges: None,
                     },
                 ))),
                 &hw_counter,
@@ -270,6 +272,10 @@ impl TestSegments {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -323,6 +329,7 @@ impl TestSegments {
                         is_principal: None,
                         on_disk: Some(true),
                         enable_hnsw: None,
+                        enable_hnsw_ranges: None,
                     },
                 ))),
                 &hw_counter,
@@ -340,6 +347,7 @@ impl TestSegments {
                         is_principal: None,
                         on_disk: Some(true),
                         enable_hnsw: None,
+                        enable_hnsw_ranges: None,
                     },
                 ))),
                 &hw_counter,
@@ -357,6 +365,7 @@ impl TestSegments {
                         is_principal: None,
                         on_disk: Some(true),
                         enable_hnsw: None,
+                        enable_hnsw_ranges: None,
                     },
                 ))),
                 &hw_counter,
@@ -371,6 +380,7 @@ impl TestSegments {
                     is_principal: None,
                     on_disk: Some(true),
                     enable_hnsw: None,
+                    enable_hnsw_ranges: None,
                 }))),
                 &hw_counter,
             )
@@ -321,6 +321,10 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: Option<u64>) -> (u6
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
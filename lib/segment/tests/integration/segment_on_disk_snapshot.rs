@@ -99,6 +99,7 @@ fn test_on_disk_segment_snapshot(#[case] format: SnapshotFormat) {
                     is_principal: None,
                     on_disk: Some(true),
                     enable_hnsw: None,
+                    enable_hnsw_ranges: None,
                 }),
             )),
             &hw_counter,
@@ -120,10 +121,17 @@ fn test_on_disk_segment_snapshot(#[case] format: SnapshotFormat) {
                     on_disk: Some(true), // mmap index
                     payload_m: None,
                     inline_storage: None,
+                    varint_links: None,
+                    acorn: None,
+                    seed: None,
                 }),
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         sparse_vector_data: Default::default(),
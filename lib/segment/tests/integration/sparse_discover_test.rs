@@ -131,6 +131,7 @@ fn sparse_index_discover_test() {
                 },
                 storage_type: SparseVectorStorageType::default(),
                 modifier: None,
+                bm25: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -146,6 +147,10 @@ fn sparse_index_discover_test() {
                 quantization_config: None,
                 multivector_config: None,
                 datatype: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                max_versions: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -270,6 +275,7 @@ fn sparse_index_hardware_measurement_test() {
                 },
                 storage_type: SparseVectorStorageType::default(),
                 modifier: None,
+                bm25: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -267,6 +267,59 @@ fn sparse_vector_index_consistent_with_storage() {
     check_index_storage_consistency(&sparse_vector_mmap_index);
 }
 
+#[test]
+fn sparse_vector_index_load_without_doc_lengths() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
+    let sparse_vector_ram_index = fixture_sparse_index::<InvertedIndexCompressedImmutableRam<f32>, _>(
+        &mut rnd,
+        NUM_VECTORS,
+        MAX_SPARSE_DIM,
+        LOW_FULL_SCAN_THRESHOLD,
+        data_dir.path(),
+    );
+
+    let mmap_index_dir = Builder::new().prefix("mmap_index_dir").tempdir().unwrap();
+    let open_mmap_index = || -> SparseVectorIndex<InvertedIndexCompressedMmap<f32>> {
+        let mut sparse_index_config = sparse_vector_ram_index.config();
+        sparse_index_config.index_type = SparseIndexType::Mmap;
+        SparseVectorIndex::open(SparseVectorIndexOpenArgs {
+            config: sparse_index_config,
+            id_tracker: sparse_vector_ram_index.id_tracker().clone(),
+            vector_storage: sparse_vector_ram_index.vector_storage().clone(),
+            payload_index: sparse_vector_ram_index.payload_index().clone(),
+            path: mmap_index_dir.path(),
+            stopped: &stopped,
+            tick_progress: || (),
+            deferred_internal_id: None,
+        })
+        .unwrap()
+    };
+
+    let sparse_vector_mmap_index = open_mmap_index();
+    let total_doc_length = sparse_vector_mmap_index.total_doc_length();
+    assert!(total_doc_length > 0.0);
+    drop(sparse_vector_mmap_index);
+
+    // Simulate an index persisted before document lengths were stored
+    let doc_lengths_path = mmap_index_dir.path().join("doc_lengths.bin");
+    fs::remove_file(&doc_lengths_path).unwrap();
+    // Index directory is removed if the index is rebuilt from scratch
+    let marker_path = mmap_index_dir.path().join("marker");
+    fs::write(&marker_path, b"").unwrap();
+
+    let sparse_vector_mmap_index = open_mmap_index();
+    assert!(marker_path.exists(), "index must not be rebuilt");
+    assert!(doc_lengths_path.exists());
+    assert_eq!(
+        sparse_vector_mmap_index.total_doc_length(),
+        total_doc_length
+    );
+    check_index_storage_consistency(&sparse_vector_mmap_index);
+}
+
 #[test]
 fn sparse_vector_index_load_missing_mmap() {
     let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
@@ -360,6 +413,72 @@ fn sparse_vector_index_ram_deleted_points_search() {
     );
 }
 
+#[test]
+fn sparse_vector_index_compact_deleted_points() {
+    let top = 10;
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
+
+    let mut sparse_vector_index =
+        fixture_sparse_index_from_iter::<InvertedIndexCompressedMmap<f32>>(
+            data_dir.path(),
+            (0..NUM_VECTORS).map(|_| random_sparse_vector(&mut rnd, MAX_SPARSE_DIM)),
+            LOW_FULL_SCAN_THRESHOLD,
+            SparseIndexType::Mmap,
+        )
+        .unwrap();
+    let indexed_vector_count = sparse_vector_index.indexed_vector_count();
+    assert_eq!(sparse_vector_index.deleted_indexed_ratio(), 0.0);
+
+    // delete every other point
+    for idx in (0..NUM_VECTORS as PointOffsetType).step_by(2) {
+        let external_id = sparse_vector_index
+            .id_tracker()
+            .borrow()
+            .external_id(idx)
+            .unwrap();
+        sparse_vector_index
+            .id_tracker()
+            .borrow_mut()
+            .drop(external_id)
+            .unwrap();
+    }
+    assert!(sparse_vector_index.deleted_indexed_ratio() > 0.4);
+
+    let query_vector: QueryVector = random_sparse_vector(&mut rnd, MAX_SPARSE_DIM).into();
+    let before_compaction_results: Vec<_> = sparse_vector_index
+        .search(&[&query_vector], None, top, None, &Default::default())
+        .unwrap();
+
+    assert!(sparse_vector_index.compact().unwrap() > 0);
+    assert_eq!(sparse_vector_index.deleted_indexed_ratio(), 0.0);
+    assert!(sparse_vector_index.indexed_vector_count() <= indexed_vector_count / 2 + 1);
+
+    // deleted points are gone from the postings
+    let inverted_index = sparse_vector_index.inverted_index();
+    let hw_counter = HardwareCounterCell::new();
+    for dim_id in 0..inverted_index.len() as DimId {
+        if let Some(posting) = inverted_index.get(dim_id, &hw_counter) {
+            assert!(
+                posting
+                    .iter()
+                    .into_std_iter()
+                    .all(|element| element.record_id % 2 == 1),
+            );
+        }
+    }
+
+    // results are the same, as deleted points were skipped on search anyway
+    let after_compaction_results: Vec<_> = sparse_vector_index
+        .search(&[&query_vector], None, top, None, &Default::default())
+        .unwrap();
+    assert_eq!(before_compaction_results, after_compaction_results);
+
+    // nothing left to compact
+    assert_eq!(sparse_vector_index.compact().unwrap(), 0);
+}
+
 #[test]
 fn sparse_vector_index_ram_filtered_search() {
     let mut rnd = StdRng::seed_from_u64(42);
@@ -598,6 +717,7 @@ fn sparse_vector_index_persistence_test() {
                 },
                 storage_type: SparseVectorStorageType::default(),
                 modifier: None,
+                bm25: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -749,8 +869,8 @@ fn check_sparse_vector_index_files<I: InvertedIndex>() {
     );
 
     let files = index.files();
-    // sparse index config + version + inverted index config + inverted index data + tracker
-    assert_eq!(files.len(), 5);
+    // sparse index config + version + tracker + doc lengths + inverted index files
+    assert_eq!(files.len(), 4 + I::files(data_dir.path()).len());
     for file in files.iter() {
         assert!(file.exists(), "file {file:?} does not exist");
     }
@@ -771,6 +891,7 @@ fn sparse_vector_test_large_index() {
                 },
                 storage_type: SparseVectorStorageType::Mmap,
                 modifier: None,
+                bm25: None,
             },
         )]),
         payload_storage_type: Default::default(),
@@ -183,6 +183,10 @@ pub fn empty_segment_with_deferred(path: &Path, deferred_internal_id: u32) -> Se
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
@@ -220,6 +224,10 @@ pub fn build_segment_with_deferred_1(path: &Path) -> Segment {
                     quantization_config: None,
                     multivector_config: None,
                     datatype: None,
+                    mmap_advice: None,
+                    dim_range: None,
+                    preserve_norm: None,
+                    max_versions: None,
                 },
             )]),
             sparse_vector_data: Default::default(),
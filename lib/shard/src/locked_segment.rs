@@ -95,6 +95,34 @@ impl LockedSegment {
             }
         }
     }
+
+    /// Consume the LockedSegment and take the underlying segment out of it, unwrapping proxies.
+    /// Operation fails if the segment is used by other thread for longer than `timeout`.
+    pub fn into_segment(self) -> OperationResult<Segment> {
+        match self {
+            LockedSegment::Original(segment) => {
+                match try_unwrap_with_timeout(segment, DROP_SPIN_TIMEOUT, DROP_DATA_TIMEOUT) {
+                    Ok(raw_locked_segment) => Ok(raw_locked_segment.into_inner()),
+                    Err(locked_segment) => Err(OperationError::service_error(format!(
+                        "Taking segment which is still in use: {:?}",
+                        locked_segment.read().data_path(),
+                    ))),
+                }
+            }
+            LockedSegment::Proxy(proxy) => {
+                match try_unwrap_with_timeout(proxy, DROP_SPIN_TIMEOUT, DROP_DATA_TIMEOUT) {
+                    Ok(raw_locked_segment) => raw_locked_segment
+                        .into_inner()
+                        .wrapped_segment
+                        .into_segment(),
+                    Err(locked_segment) => Err(OperationError::service_error(format!(
+                        "Taking proxy segment which is still in use: {:?}",
+                        locked_segment.read().data_path(),
+                    ))),
+                }
+            }
+        }
+    }
 }
 
 impl From<Segment> for LockedSegment {
@@ -177,6 +177,7 @@ impl PointInsertOperationsInternal {
                             id,
                             vector: VectorStructInternal::from(vectors).into(),
                             payload: None,
+                            expires_at: None,
                         })
                         .collect(),
                     Some(payloads) => vectors_iter
@@ -185,6 +186,7 @@ impl PointInsertOperationsInternal {
                             id,
                             vector: VectorStructInternal::from(vectors).into(),
                             payload,
+                            expires_at: None,
                         })
                         .collect(),
                 }
@@ -303,6 +305,7 @@ impl TryFrom<BatchPersisted> for Vec<api::grpc::qdrant::PointStruct> {
                 id,
                 vectors: vectors.map(api::grpc::qdrant::Vectors::from),
                 payload: payload.unwrap_or_default(),
+                ttl: None,
             };
             points.push(point);
         }
@@ -375,6 +378,9 @@ pub struct PointStructPersisted {
     pub vector: VectorStructPersisted,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// Time, after which the point is deleted, in seconds since the Unix epoch (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 impl PointStructPersisted {
@@ -452,6 +458,7 @@ impl TryFrom<api::rest::schema::Record> for PointStructPersisted {
             id,
             payload,
             vector: VectorStructPersisted::from(vector.unwrap()),
+            expires_at: None,
         })
     }
 }
@@ -465,6 +472,7 @@ impl TryFrom<PointStructPersisted> for api::grpc::qdrant::PointStruct {
             id,
             vector,
             payload,
+            expires_at,
         } = value;
 
         let vectors_internal = VectorStructInternal::try_from(vector).map_err(|e| {
@@ -477,10 +485,16 @@ impl TryFrom<PointStructPersisted> for api::grpc::qdrant::PointStruct {
             Some(payload) => api::conversions::json::payload_to_proto(payload),
         };
 
+        // Remaining time to live, the receiver resolves it back into an expiration time
+        let ttl = expires_at.map(|expires_at| {
+            expires_at.saturating_sub(segment::segment::point_expirations::unix_timestamp_now())
+        });
+
         Ok(Self {
             id: Some(id.into()),
             vectors: Some(vectors),
             payload: converted_payload,
+            ttl,
         })
     }
 }
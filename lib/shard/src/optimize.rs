@@ -479,6 +479,16 @@ fn finish_optimization(
     let read_segment_holder = RwLockWriteGuard::downgrade(writable_segment_holder);
     // Can read, but can't yet write updates.
 
+    let retention = read_segment_holder
+        .retention
+        .clone()
+        .filter(|retention| retention.is_enabled());
+    let retired_version = read_segment_holder
+        .iter()
+        .map(|(_, segment)| segment.get_read().read().version())
+        .max()
+        .unwrap_or_default();
+
     let mut deferred_points_set = AHashSet::new();
     for proxy in &proxies {
         deferred_points_set.extend(proxy.get().read().deferred_point_ids());
@@ -508,8 +518,12 @@ fn finish_optimization(
 
     // Only remove data after we ensure the consistency of the collection.
     // If remove fails - we will still have operational collection with reported error.
+    // With segment retention, replaced segments are kept to serve queries pinned to the past.
     for proxy in proxies {
-        proxy.drop_data()?;
+        match &retention {
+            Some(retention) => retention.retire(proxy, &deleted_points, retired_version)?,
+            None => proxy.drop_data()?,
+        }
     }
 
     Ok(point_count)
@@ -524,11 +538,14 @@ fn check_segments_size(
     // Counting up how much space do the segments being optimized actually take on the fs.
     // If there was at least one error while reading the size, this will be `None`.
     let mut space_occupied = Some(0u64);
+    // Pending changes of the segments being optimized, written to disk when they are flushed
+    let mut space_pending_flush = 0u64;
 
     for segment in optimizing_segments {
         match segment {
             LockedSegment::Original(segment) => {
                 let locked_segment = segment.read();
+                space_pending_flush += locked_segment.estimate_vectors_flush_size();
                 space_occupied = space_occupied.and_then(|acc| {
                     match dir_disk_size(locked_segment.data_path()) {
                         Ok(size) => Some(size + acc),
@@ -553,7 +570,7 @@ fn check_segments_size(
         }
     }
 
-    let space_needed = space_occupied.map(|x| 2 * x);
+    let space_needed = space_occupied.map(|x| 2 * x + space_pending_flush);
 
     // Ensure temp_path exists
     if !temp_path.exists() {
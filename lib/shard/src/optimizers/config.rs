@@ -2,12 +2,13 @@ use std::collections::HashMap;
 use std::num::NonZeroUsize;
 
 use segment::common::BYTES_IN_KB;
-use segment::data_types::modifier::Modifier;
+use segment::data_types::modifier::{Bm25Params, Modifier};
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
 use segment::types::{
-    Distance, HnswConfig, Indexes, MultiVectorConfig, PayloadStorageType, QuantizationConfig,
-    SegmentConfig, SparseVectorDataConfig, SparseVectorStorageType, VectorDataConfig,
-    VectorNameBuf, VectorStorageDatatype, VectorStorageType,
+    DimRange, Distance, HnswConfig, Indexes, IvfConfig, MmapAdvice, MultiVectorConfig,
+    PayloadStorageType, QuantizationConfig, SegmentConfig, SparseVectorDataConfig,
+    SparseVectorStorageType, VamanaConfig, VectorDataConfig, VectorNameBuf, VectorStorageDatatype,
+    VectorStorageType,
 };
 
 pub const TEMP_SEGMENTS_PATH: &str = "temp_segments";
@@ -21,6 +22,10 @@ pub const DEFAULT_VACUUM_MIN_VECTOR_NUMBER: usize = 1000;
 pub struct DenseVectorOptimizerConfig {
     pub on_disk: Option<bool>,
     pub hnsw_config: HnswConfig,
+    /// If set, indexed segments use Vamana index instead of HNSW
+    pub vamana_config: Option<VamanaConfig>,
+    /// If set, indexed segments use IVF index instead of HNSW
+    pub ivf_config: Option<IvfConfig>,
     pub quantization_config: Option<QuantizationConfig>,
 }
 
@@ -67,9 +72,15 @@ impl SegmentOptimizerConfig {
                 distance,
                 on_disk,
                 hnsw_config,
+                vamana_config,
+                ivf_config,
                 quantization_config,
                 multivector_config,
                 datatype,
+                mmap_advice,
+                dim_range,
+                preserve_norm,
+                max_versions,
             } = input;
             plain_dense_vector_config.insert(
                 name.clone(),
@@ -83,6 +94,10 @@ impl SegmentOptimizerConfig {
                     ),
                     multivector_config,
                     datatype,
+                    mmap_advice,
+                    dim_range,
+                    preserve_norm,
+                    max_versions,
                 },
             );
             dense_vector.insert(
@@ -90,6 +105,8 @@ impl SegmentOptimizerConfig {
                 DenseVectorOptimizerConfig {
                     on_disk,
                     hnsw_config,
+                    vamana_config,
+                    ivf_config,
                     quantization_config,
                 },
             );
@@ -103,6 +120,7 @@ impl SegmentOptimizerConfig {
                 index_datatype,
                 storage_type,
                 modifier,
+                bm25,
             } = input;
             plain_sparse_vector_config.insert(
                 name.clone(),
@@ -114,6 +132,7 @@ impl SegmentOptimizerConfig {
                     },
                     storage_type,
                     modifier,
+                    bm25,
                 },
             );
             sparse_vector.insert(name, SparseVectorOptimizerConfig { on_disk });
@@ -136,9 +155,15 @@ pub struct DenseVectorOptimizerInput {
     pub distance: Distance,
     pub on_disk: Option<bool>,
     pub hnsw_config: HnswConfig,
+    pub vamana_config: Option<VamanaConfig>,
+    pub ivf_config: Option<IvfConfig>,
     pub quantization_config: Option<QuantizationConfig>,
     pub multivector_config: Option<MultiVectorConfig>,
     pub datatype: Option<VectorStorageDatatype>,
+    pub mmap_advice: Option<MmapAdvice>,
+    pub dim_range: Option<DimRange>,
+    pub preserve_norm: Option<bool>,
+    pub max_versions: Option<usize>,
 }
 
 /// Per-sparse-vector input for the optimizer builder.
@@ -149,6 +174,7 @@ pub struct SparseVectorOptimizerInput {
     pub index_datatype: Option<VectorStorageDatatype>,
     pub storage_type: SparseVectorStorageType,
     pub modifier: Option<Modifier>,
+    pub bm25: Option<Bm25Params>,
 }
 
 /// Target segment count for the merge optimizer.
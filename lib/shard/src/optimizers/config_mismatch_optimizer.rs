@@ -7,7 +7,8 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::entry::ReadSegmentEntry;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
-use segment::types::{HnswConfig, HnswGlobalConfig, Indexes, VectorName};
+use segment::types::{HnswConfig, HnswGlobalConfig, Indexes, VectorName, VectorStorageType};
+use segment::vector_storage::memory_budget::MemoryBudget;
 
 use super::config::SegmentOptimizerConfig;
 use super::segment_optimizer::{OptimizationPlanner, SegmentOptimizer};
@@ -67,6 +68,7 @@ impl ConfigMismatchOptimizer {
 
     fn has_config_mismatch(&self, segment: &dyn ReadSegmentEntry) -> bool {
         let segment_config = segment.config();
+        let is_appendable = segment.is_appendable();
 
         if self
             .segment_optimizer_config
@@ -83,10 +85,19 @@ impl ConfigMismatchOptimizer {
                 .vector_data
                 .iter()
                 .any(|(vector_name, vector_data)| {
-                    // Check HNSW mismatch
-                    match &vector_data.index {
-                        Indexes::Plain {} => {}
-                        Indexes::Hnsw(effective_hnsw) => {
+                    // Check HNSW, Vamana and IVF mismatch
+                    let target_dense = self.segment_optimizer_config.dense_vector.get(vector_name);
+                    let target_vamana = target_dense.and_then(|cfg| cfg.vamana_config);
+                    let target_ivf = target_dense.and_then(|cfg| cfg.ivf_config);
+                    match (&vector_data.index, target_vamana, target_ivf) {
+                        (Indexes::Plain {}, _, _) => {}
+                        // Select segment if it has to be indexed with another index type
+                        (Indexes::Hnsw(_), Some(_), _)
+                        | (Indexes::Hnsw(_), None, Some(_))
+                        | (Indexes::Vamana(_), None, _)
+                        | (Indexes::Ivf(_), Some(_), _)
+                        | (Indexes::Ivf(_), None, None) => return true,
+                        (Indexes::Hnsw(effective_hnsw), None, None) => {
                             // Select segment if we have an HNSW mismatch that requires rebuild
                             let target_hnsw = self
                                 .segment_optimizer_config
@@ -98,10 +109,26 @@ impl ConfigMismatchOptimizer {
                                 return true;
                             }
                         }
+                        (Indexes::Vamana(effective_vamana), Some(target_vamana), _) => {
+                            if effective_vamana.mismatch_requires_rebuild(&target_vamana) {
+                                return true;
+                            }
+                        }
+                        (Indexes::Ivf(effective_ivf), None, Some(target_ivf)) => {
+                            if effective_ivf.mismatch_requires_rebuild(&target_ivf) {
+                                return true;
+                            }
+                        }
                     }
 
                     if let Some(is_required_on_disk) = self.check_if_vectors_on_disk(vector_name)
+                        // Quantized vectors are kept on disk instead of dropped original vectors,
+                        // they would be dropped again once the segment is rebuilt
+                        && vector_data.storage_type != VectorStorageType::Quantized
                         && is_required_on_disk != vector_data.storage_type.is_on_disk()
+                        // Vectors are kept on disk while the memory budget is exceeded,
+                        // rebuilding the segment would not move them into RAM
+                        && (is_required_on_disk || !MemoryBudget::global().is_exceeded())
                     {
                         return true;
                     }
@@ -117,8 +144,12 @@ impl ConfigMismatchOptimizer {
                         .quantization_config
                         .as_ref()
                         .zip(target_quantization)
-                        // Rebuild if current parameters differ from target parameters
-                        .map(|(current, target)| current.mismatch_requires_rebuild(target))
+                        // Rebuild if current parameters differ from target parameters,
+                        // unless only quantized vectors can be rebuilt in place
+                        .map(|(current, target)| {
+                            current.mismatch_requires_rebuild(target)
+                                && (is_appendable || !vector_data.can_requantize_in_place(target))
+                        })
                         // Or rebuild if we now change the enabled state on an indexed segment
                         .unwrap_or_else(|| {
                             let vector_data_quantization_appendable = vector_data
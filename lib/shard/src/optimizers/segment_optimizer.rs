@@ -206,12 +206,16 @@ pub trait SegmentOptimizer: Sync {
         let mut vector_data = segment_optimizer_config.plain_dense_vector_config.clone();
         let mut sparse_vector_data = segment_optimizer_config.plain_sparse_vector_config.clone();
 
-        // If indexing, change to HNSW index and quantization
+        // If indexing, change to HNSW, Vamana or IVF index and quantization
         if threshold_is_indexed {
             vector_data.iter_mut().for_each(|(vector_name, config)| {
                 if let Some(vector_cfg) = segment_optimizer_config.dense_vector.get(vector_name) {
-                    // Assign HNSW index
-                    config.index = Indexes::Hnsw(vector_cfg.hnsw_config);
+                    // Assign HNSW, Vamana or IVF index
+                    config.index = match (vector_cfg.vamana_config, vector_cfg.ivf_config) {
+                        (Some(vamana_config), _) => Indexes::Vamana(vamana_config),
+                        (None, Some(ivf_config)) => Indexes::Ivf(ivf_config),
+                        (None, None) => Indexes::Hnsw(vector_cfg.hnsw_config),
+                    };
                     // Assign quantization config
                     config.quantization_config = vector_cfg.quantization_config.clone();
                 }
@@ -1,5 +1,5 @@
 use std::cmp;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -15,13 +15,19 @@ use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
 use segment::data_types::query_context::{FormulaContext, QueryContext, SegmentQueryContext};
 use segment::data_types::segment_record::SegmentRecord;
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 use segment::data_types::vectors::{QueryVector, VectorInternal};
 use segment::entry::StorageSegmentEntry;
 use segment::entry::entry_point::{NonAppendableSegmentEntry, ReadSegmentEntry, SegmentEntry};
 use segment::index::field_index::{CardinalityEstimation, FieldIndex};
+use segment::index::hybrid_index::HybridQuery;
+use segment::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::*;
+use segment::utils::mem::MappedFilesResidency;
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::DimId;
 use uuid::Uuid;
 
 use super::{ProxyDeletedPoint, ProxyIndexChange, ProxySegment};
@@ -55,6 +61,17 @@ impl ReadSegmentEntry for ProxySegment {
         Some(wrapped_version)
     }
 
+    fn point_expiration(&self, point_id: PointIdType) -> Option<u64> {
+        // Deleted points have no expiration
+        self.point_version(point_id)?;
+        self.wrapped_segment.get().read().point_expiration(point_id)
+    }
+
+    fn has_expired_points(&self, _now: u64) -> bool {
+        // Expired points are not deleted from proxy segments, see `delete_expired_points`
+        false
+    }
+
     fn search_batch(
         &self,
         vector_name: &VectorName,
@@ -165,6 +182,25 @@ impl ReadSegmentEntry for ProxySegment {
         }
     }
 
+    fn vector_at_version(
+        &self,
+        vector_name: &VectorName,
+        point_id: PointIdType,
+        version: SeqNumberType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<VectorInternal>> {
+        if self.deleted_points.contains_key(&point_id) {
+            Ok(None)
+        } else {
+            self.wrapped_segment.get().read().vector_at_version(
+                vector_name,
+                point_id,
+                version,
+                hw_counter,
+            )
+        }
+    }
+
     fn all_vectors(
         &self,
         point_id: PointIdType,
@@ -207,6 +243,40 @@ impl ReadSegmentEntry for ProxySegment {
         }
     }
 
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Option<Vec<u8>>> {
+        if self.deleted_points.contains_key(&point_id) {
+            Ok(None)
+        } else {
+            self.wrapped_segment.get().read().blob(point_id, hw_counter)
+        }
+    }
+
+    fn remap_sparse_vector(
+        &self,
+        vector_name: &VectorName,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector> {
+        self.wrapped_segment
+            .get()
+            .read()
+            .remap_sparse_vector(vector_name, vector, unknown_tokens)
+    }
+
+    fn sparse_vocabulary(
+        &self,
+        vector_name: &VectorName,
+    ) -> OperationResult<BTreeMap<String, DimId>> {
+        self.wrapped_segment
+            .get()
+            .read()
+            .sparse_vocabulary(vector_name)
+    }
+
     fn retrieve(
         &self,
         point_ids: &[PointIdType],
@@ -307,6 +377,85 @@ impl ReadSegmentEntry for ProxySegment {
         Ok(read_points)
     }
 
+    fn text_query_statistics(
+        &self,
+        query: &TextQuery,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<TextQueryStatistics> {
+        // Points deleted in the proxy are still counted, which only slightly skews the statistics
+        self.wrapped_segment
+            .get()
+            .read()
+            .text_query_statistics(query, hw_counter)
+    }
+
+    fn read_text_scored_filtered(
+        &self,
+        limit: usize,
+        filter: Option<&Filter>,
+        query: &TextQuery,
+        statistics: &TextQueryStatistics,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+        deferred_behavior: DeferredBehavior,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        if self.deleted_points.is_empty() {
+            self.wrapped_segment.get().read().read_text_scored_filtered(
+                limit,
+                filter,
+                query,
+                statistics,
+                is_stopped,
+                hw_counter,
+                deferred_behavior,
+            )
+        } else {
+            let wrapped_filter = Self::add_deleted_points_condition_to_filter(
+                filter,
+                self.deleted_points.keys().copied(),
+            );
+            self.wrapped_segment.get().read().read_text_scored_filtered(
+                limit,
+                Some(&wrapped_filter),
+                query,
+                statistics,
+                is_stopped,
+                hw_counter,
+                deferred_behavior,
+            )
+        }
+    }
+
+    fn hybrid_search(
+        &self,
+        query: &HybridQuery,
+        filter: Option<&Filter>,
+        top: usize,
+        params: Option<&SearchParams>,
+        is_stopped: &AtomicBool,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<Vec<ScoredPoint>> {
+        if self.deleted_points.is_empty() {
+            self.wrapped_segment
+                .get()
+                .read()
+                .hybrid_search(query, filter, top, params, is_stopped, hw_counter)
+        } else {
+            let wrapped_filter = Self::add_deleted_points_condition_to_filter(
+                filter,
+                self.deleted_points.keys().copied(),
+            );
+            self.wrapped_segment.get().read().hybrid_search(
+                query,
+                Some(&wrapped_filter),
+                top,
+                params,
+                is_stopped,
+                hw_counter,
+            )
+        }
+    }
+
     fn read_random_filtered<'a>(
         &'a self,
         limit: usize,
@@ -500,6 +649,14 @@ impl ReadSegmentEntry for ProxySegment {
         self.info()
     }
 
+    fn memory_usage(&self, residency: &MappedFilesResidency) -> SegmentMemoryUsage {
+        self.wrapped_segment.get().read().memory_usage(residency)
+    }
+
+    fn disk_usage(&self) -> SegmentDiskUsage {
+        self.wrapped_segment.get().read().disk_usage()
+    }
+
     fn info(&self) -> SegmentInfo {
         let wrapped_info = self.wrapped_segment.get().read().info();
 
@@ -734,6 +891,39 @@ impl SegmentEntry for ProxySegment {
             "Clear payload is disabled for proxy segments: operation {op_num} on point {point_id}",
         )))
     }
+
+    fn set_point_expiration(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        _expires_at: Option<u64>,
+    ) -> OperationResult<bool> {
+        Err(OperationError::service_error(format!(
+            "Set point expiration is disabled for proxy segments: operation {op_num} on point {point_id}",
+        )))
+    }
+
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        _blob: Option<&[u8]>,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<bool> {
+        Err(OperationError::service_error(format!(
+            "Set blob is disabled for proxy segments: operation {op_num} on point {point_id}",
+        )))
+    }
+
+    fn register_sparse_vocabulary(
+        &mut self,
+        vector_name: &VectorName,
+        _mapping: &BTreeMap<String, DimId>,
+    ) -> OperationResult<bool> {
+        Err(OperationError::service_error(format!(
+            "Register sparse vocabulary is disabled for proxy segments: vector {vector_name}",
+        )))
+    }
 }
 
 impl NonAppendableSegmentEntry for ProxySegment {
@@ -817,6 +1007,15 @@ impl NonAppendableSegmentEntry for ProxySegment {
         Ok(was_deleted)
     }
 
+    fn delete_expired_points(
+        &mut self,
+        _now: u64,
+        _hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<usize> {
+        // Wrapped segment is being optimized, the optimized segment skips expired points
+        Ok(0)
+    }
+
     fn delete_field_index(&mut self, op_num: u64, key: PayloadKeyTypeRef) -> OperationResult<bool> {
         if self.version() > op_num {
             return Ok(false);
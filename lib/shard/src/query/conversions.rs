@@ -3,9 +3,11 @@ use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQueryInternal;
 use segment::data_types::vectors::{
     DEFAULT_VECTOR_NAME, NamedQuery, NamedVectorStruct, VectorInternal,
 };
+use segment::index::hybrid_index::HybridQuery;
 use segment::index::query_optimization::rescore_formula::parsed_formula::{
     DecayKind, ParsedFormula,
 };
@@ -357,6 +359,12 @@ impl ScoringQuery {
             grpc::query_shard_points::query::Score::Sample(sample) => {
                 ScoringQuery::Sample(SampleInternal::try_from(sample)?)
             }
+            grpc::query_shard_points::query::Score::Text(text) => {
+                ScoringQuery::Text(TextQueryInternal::try_from(text)?)
+            }
+            grpc::query_shard_points::query::Score::Hybrid(hybrid) => {
+                ScoringQuery::Hybrid(HybridQuery::try_from(hybrid)?)
+            }
             grpc::query_shard_points::query::Score::Formula(formula) => ScoringQuery::Formula(
                 ParsedFormula::try_from(FormulaInternal::try_from(formula)?).map_err(|e| {
                     tonic::Status::invalid_argument(format!("failed to parse formula: {e}"))
@@ -395,6 +403,12 @@ impl From<ScoringQuery> for grpc::query_shard_points::Query {
             ScoringQuery::OrderBy(order_by) => Self {
                 score: Some(Score::OrderBy(grpc::OrderBy::from(order_by))),
             },
+            ScoringQuery::Text(text) => Self {
+                score: Some(Score::Text(grpc::TextQueryInternal::from(text))),
+            },
+            ScoringQuery::Hybrid(hybrid) => Self {
+                score: Some(Score::Hybrid(grpc::HybridQuery::from(hybrid))),
+            },
             ScoringQuery::Formula(parsed_formula) => Self {
                 score: Some(Score::Formula(grpc::Formula::from_parsed(parsed_formula))),
             },
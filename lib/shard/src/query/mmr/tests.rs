@@ -348,6 +348,8 @@ fn test_mmr_multi_vector() {
     // Test multi-vectors with all supported distance metrics
     let multi_vector_config = MultiVectorConfig {
         comparator: MultiVectorComparator::MaxSim,
+        max_vectors_per_point: None,
+        overflow_policy: None,
     };
 
     let multi_vector_name = "multi";
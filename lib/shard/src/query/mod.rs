@@ -14,7 +14,9 @@ mod tests;
 use common::types::ScoreType;
 use ordered_float::OrderedFloat;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQueryInternal;
 use segment::data_types::vectors::VectorInternal;
+use segment::index::hybrid_index::HybridQuery;
 use segment::index::query_optimization::rescore_formula::parsed_formula::ParsedFormula;
 use segment::types::*;
 use serde::Serialize;
@@ -69,6 +71,20 @@ impl ShardQueryRequest {
 
         filters
     }
+
+    /// Text queries of the request and all of its prefetches
+    pub fn text_queries_mut(&mut self) -> Vec<&mut TextQueryInternal> {
+        let mut text_queries = vec![];
+        if let Some(ScoringQuery::Text(text_query)) = &mut self.query {
+            text_queries.push(text_query);
+        }
+
+        for prefetch in &mut self.prefetches {
+            text_queries.extend(prefetch.text_queries_mut())
+        }
+
+        text_queries
+    }
 }
 
 #[derive(Clone, Debug, Hash, Serialize)]
@@ -105,6 +121,19 @@ impl ShardPrefetch {
 
         filters
     }
+
+    fn text_queries_mut(&mut self) -> Vec<&mut TextQueryInternal> {
+        let mut text_queries = vec![];
+        if let Some(ScoringQuery::Text(text_query)) = &mut self.query {
+            text_queries.push(text_query);
+        }
+
+        for prefetch in &mut self.prefetches {
+            text_queries.extend(prefetch.text_queries_mut())
+        }
+
+        text_queries
+    }
 }
 
 /// Same as `Query`, but with the resolved vector references.
@@ -119,6 +148,12 @@ pub enum ScoringQuery {
     /// Order by a payload field
     OrderBy(OrderBy),
 
+    /// Score by BM25 relevance of a full-text indexed payload field
+    Text(TextQueryInternal),
+
+    /// Score by the dense and sparse vectors at once, fused with `alpha` weight
+    Hybrid(HybridQuery),
+
     /// Score boosting via an arbitrary formula
     Formula(ParsedFormula),
 
@@ -150,7 +185,12 @@ impl ScoringQuery {
             },
             // MMR is a nearest neighbors search before computing diversity at collection level
             Self::Mmr(_) => false,
-            Self::Vector(_) | Self::OrderBy(_) | Self::Formula(_) | Self::Sample(_) => false,
+            Self::Vector(_)
+            | Self::OrderBy(_)
+            | Self::Text(_)
+            | Self::Hybrid(_)
+            | Self::Formula(_)
+            | Self::Sample(_) => false,
         }
     }
 
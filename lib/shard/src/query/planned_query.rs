@@ -136,6 +136,8 @@ impl PlannedQuery {
             | Some(ScoringQuery::Vector(_))
             | Some(ScoringQuery::Fusion(_))
             | Some(ScoringQuery::OrderBy(_))
+            | Some(ScoringQuery::Text(_))
+            | Some(ScoringQuery::Hybrid(_))
             | Some(ScoringQuery::Formula(_))
             | Some(ScoringQuery::Sample(_)) => with_vector,
             Some(ScoringQuery::Mmr(mmr)) => with_vector.merge(&WithVector::from(mmr.using.clone())),
@@ -185,6 +187,8 @@ impl PlannedQuery {
             Some(ScoringQuery::Vector(_)) => None,
             Some(ScoringQuery::Fusion(_)) => None, // Expect fusion to have prefetches
             Some(ScoringQuery::OrderBy(_)) => None,
+            Some(ScoringQuery::Text(_)) => None,
+            Some(ScoringQuery::Hybrid(_)) => None,
             Some(ScoringQuery::Formula(_)) => None,
             Some(ScoringQuery::Sample(_)) => None,
             Some(ScoringQuery::Mmr(_)) => Some(RescoreStages::collection_level(RescoreParams {
@@ -268,6 +272,8 @@ impl PlannedQuery {
             }
             rescore @ (ScoringQuery::Vector(_)
             | ScoringQuery::OrderBy(_)
+            | ScoringQuery::Text(_)
+            | ScoringQuery::Hybrid(_)
             | ScoringQuery::Formula(_)
             | ScoringQuery::Sample(_)) => Some(RescoreStages::shard_level(RescoreParams {
                 rescore,
@@ -413,6 +419,41 @@ fn leaf_source_from_scoring_query(
 
             Source::ScrollsIdx(idx)
         }
+        Some(ScoringQuery::Text(query)) => {
+            let scroll = QueryScrollRequestInternal {
+                scroll_order: ScrollOrder::ByText {
+                    query,
+                    score_threshold,
+                },
+                filter,
+                with_vector: WithVector::from(false),
+                with_payload: WithPayloadInterface::from(false),
+                limit,
+            };
+
+            let idx = scrolls.len();
+            scrolls.push(scroll);
+
+            Source::ScrollsIdx(idx)
+        }
+        Some(ScoringQuery::Hybrid(query)) => {
+            let scroll = QueryScrollRequestInternal {
+                scroll_order: ScrollOrder::ByHybrid {
+                    query,
+                    params,
+                    score_threshold,
+                },
+                filter,
+                with_vector: WithVector::from(false),
+                with_payload: WithPayloadInterface::from(false),
+                limit,
+            };
+
+            let idx = scrolls.len();
+            scrolls.push(scroll);
+
+            Source::ScrollsIdx(idx)
+        }
         Some(ScoringQuery::Formula(_)) => {
             return Err(OperationError::validation_error(
                 "cannot apply Formula without prefetches".to_string(),
@@ -4,6 +4,7 @@ use std::time::Duration;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::iterator_ext::IteratorExt;
 use segment::common::operation_error::{OperationError, OperationResult};
+use segment::data_types::modifier::Bm25Params;
 use segment::data_types::query_context::QueryContext;
 use segment::types::VectorName;
 
@@ -18,6 +19,8 @@ pub fn init_query_context(
     is_stopped_guard: &StoppingGuard,
     hw_measurement_acc: HwMeasurementAcc,
     check_idf_required: impl Fn(&VectorName) -> bool,
+    // BM25 parameters of the vectors, which require IDF
+    get_bm25_params: impl Fn(&VectorName) -> Option<Bm25Params>,
 ) -> QueryContext {
     let mut query_context = QueryContext::new(search_optimized_threshold_kb, hw_measurement_acc)
         .with_is_stopped(is_stopped_guard.get_is_stopped());
@@ -28,6 +31,9 @@ pub fn init_query_context(
             .iterate_sparse(|vector_name, sparse_vector| {
                 if check_idf_required(vector_name) {
                     query_context.init_idf(vector_name, &sparse_vector.indices);
+                    if let Some(bm25_params) = get_bm25_params(vector_name) {
+                        query_context.init_bm25(vector_name, bm25_params);
+                    }
                 }
             })
     }
@@ -1,5 +1,8 @@
+use common::types::ScoreType;
 use segment::data_types::order_by::OrderBy;
-use segment::types::{Filter, WithPayloadInterface, WithVector};
+use segment::data_types::text_query::TextQueryInternal;
+use segment::index::hybrid_index::HybridQuery;
+use segment::types::{Filter, SearchParams, WithPayloadInterface, WithVector};
 
 use crate::operation_rate_cost;
 
@@ -38,4 +41,15 @@ pub enum ScrollOrder {
     ById,
     ByField(OrderBy),
     Random,
+    /// Most relevant points to the text query first, scored by BM25
+    ByText {
+        query: TextQueryInternal,
+        score_threshold: Option<ScoreType>,
+    },
+    /// Most relevant points to the hybrid query first, scored by both dense and sparse vectors
+    ByHybrid {
+        query: HybridQuery,
+        params: Option<SearchParams>,
+        score_threshold: Option<ScoreType>,
+    },
 }
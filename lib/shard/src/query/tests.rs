@@ -2,12 +2,15 @@ use ahash::AHashSet;
 use ordered_float::OrderedFloat;
 use segment::common::operation_error::OperationError;
 use segment::common::reciprocal_rank_fusion::DEFAULT_RRF_K;
+use segment::data_types::text_query::{TextQuery, TextQueryInternal};
 use segment::data_types::vectors::{MultiDenseVectorInternal, NamedQuery, VectorInternal};
+use segment::index::hybrid_index::HybridQuery;
 use segment::json_path::JsonPath;
 use segment::types::*;
 use sparse::common::sparse_vector::SparseVector;
 
 use super::planned_query::*;
+use super::scroll::{QueryScrollRequestInternal, ScrollOrder};
 use super::*;
 
 #[test]
@@ -284,6 +287,146 @@ fn test_try_from_hybrid_query() {
     );
 }
 
+#[test]
+fn test_try_from_text_and_vector_fusion() {
+    let dummy_vector = vec![1.0, 2.0, 3.0];
+    let text_query = TextQueryInternal::from(TextQuery {
+        key: "description".try_into().unwrap(),
+        text: "quick brown fox".to_string(),
+    });
+
+    let request = ShardQueryRequest {
+        prefetches: vec![
+            ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(ScoringQuery::Text(text_query.clone())),
+                limit: 100,
+                params: None,
+                filter: None,
+                score_threshold: Some(OrderedFloat(1.5)),
+            },
+            ShardPrefetch {
+                prefetches: Vec::new(),
+                query: Some(ScoringQuery::Vector(QueryEnum::Nearest(NamedQuery::new(
+                    VectorInternal::Dense(dummy_vector.clone()),
+                    "dense",
+                )))),
+                limit: 100,
+                params: None,
+                filter: None,
+                score_threshold: None,
+            },
+        ],
+        query: Some(ScoringQuery::Fusion(FusionInternal::Rrf {
+            k: DEFAULT_RRF_K,
+            weights: None,
+        })),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_payload: WithPayloadInterface::Bool(true),
+        with_vector: WithVector::Bool(false),
+    };
+
+    let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+    // Text query is executed as a scroll, scored by the full-text index
+    assert_eq!(
+        planned_query.scrolls,
+        vec![QueryScrollRequestInternal {
+            limit: 100,
+            filter: None,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Bool(false),
+            scroll_order: ScrollOrder::ByText {
+                query: text_query,
+                score_threshold: Some(1.5),
+            },
+        }]
+    );
+    assert_eq!(planned_query.searches.len(), 1);
+
+    assert_eq!(
+        planned_query.root_plans,
+        vec![RootPlan {
+            with_payload: WithPayloadInterface::Bool(true),
+            with_vector: WithVector::Bool(false),
+            merge_plan: MergePlan {
+                sources: vec![Source::ScrollsIdx(0), Source::SearchesIdx(0)],
+                rescore_stages: Some(RescoreStages::collection_level(RescoreParams {
+                    rescore: ScoringQuery::Fusion(FusionInternal::Rrf {
+                        k: DEFAULT_RRF_K,
+                        weights: None
+                    }),
+                    limit: 10,
+                    score_threshold: None,
+                    params: None,
+                }))
+            }
+        }]
+    );
+}
+
+#[test]
+fn test_try_from_hybrid_query() {
+    let hybrid_query = HybridQuery {
+        dense_using: "dense".to_string(),
+        dense: vec![1.0, 2.0, 3.0],
+        sparse_using: "sparse".to_string(),
+        sparse: SparseVector::new(vec![1, 5], vec![0.5, 1.0]).unwrap(),
+        alpha: 0.7,
+    };
+    let params = SearchParams {
+        hnsw_ef: Some(64),
+        ..Default::default()
+    };
+
+    let request = ShardQueryRequest {
+        prefetches: Vec::new(),
+        query: Some(ScoringQuery::Hybrid(hybrid_query.clone())),
+        filter: None,
+        score_threshold: Some(OrderedFloat(0.5)),
+        limit: 10,
+        offset: 5,
+        params: Some(params),
+        with_payload: WithPayloadInterface::Bool(true),
+        with_vector: WithVector::Bool(false),
+    };
+
+    let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
+
+    // Hybrid query is executed as a scroll, scored by both vectors at once
+    assert_eq!(
+        planned_query.scrolls,
+        vec![QueryScrollRequestInternal {
+            limit: 15,
+            filter: None,
+            with_payload: WithPayloadInterface::Bool(false),
+            with_vector: WithVector::Bool(false),
+            scroll_order: ScrollOrder::ByHybrid {
+                query: hybrid_query,
+                params: Some(params),
+                score_threshold: Some(0.5),
+            },
+        }]
+    );
+    assert!(planned_query.searches.is_empty());
+
+    assert_eq!(
+        planned_query.root_plans,
+        vec![RootPlan {
+            with_payload: WithPayloadInterface::Bool(true),
+            with_vector: WithVector::Bool(false),
+            merge_plan: MergePlan {
+                sources: vec![Source::ScrollsIdx(0)],
+                rescore_stages: None,
+            }
+        }]
+    );
+}
+
 #[test]
 fn test_try_from_rrf_without_source() {
     let request = ShardQueryRequest {
@@ -65,6 +65,8 @@ fn validate_query(query: &ScoringQuery, sources: &[Source]) -> OperationResult<(
         ScoringQuery::Vector(_) => Ok(()),
         ScoringQuery::Fusion(fusion) => validate_fusion(fusion, sources.len()),
         ScoringQuery::OrderBy(_) => Ok(()),
+        ScoringQuery::Text(_) => Ok(()),
+        ScoringQuery::Hybrid(_) => Ok(()),
         ScoringQuery::Formula(_) => Ok(()),
         ScoringQuery::Sample(_) => Ok(()),
         ScoringQuery::Mmr(_) => Ok(()),
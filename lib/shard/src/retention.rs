@@ -0,0 +1,358 @@
+//! Retention of segments, which were replaced by optimizations.
+//!
+//! Retained segments are kept for a configured window, together with points deleted from them
+//! while they were optimized. Combined with the live segments, they serve queries pinned to a past
+//! version of the shard: every segment is wrapped into a proxy, which hides points newer than the
+//! pinned version.
+//!
+//! This view is best-effort. Changes applied in place to live segments, which were never replaced
+//! by an optimization, can not be reverted.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use common::counter::hardware_counter::HardwareCounterCell;
+use fs_err as fs;
+use parking_lot::Mutex;
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::entry::{
+    NonAppendableSegmentEntry as _, ReadSegmentEntry as _, StorageSegmentEntry as _,
+};
+use segment::segment_constructor::load_segment;
+use segment::types::{PointIdType, SeqNumberType};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::locked_segment::LockedSegment;
+use crate::proxy_segment::{DeletedPoints, ProxySegment};
+use crate::segment_holder::SegmentHolder;
+
+const CHECKPOINTS_FILE: &str = "checkpoints.json";
+const RETAINED_SEGMENT_META_EXT: &str = "json";
+
+/// Version of the shard, applied at some moment of time.
+///
+/// Used to resolve timestamps of pinned queries into versions.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Checkpoint {
+    time: DateTime<Utc>,
+    version: SeqNumberType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RetainedSegmentMeta {
+    /// Time, when the segment was replaced
+    retired_at: DateTime<Utc>,
+    /// Max version of the shard, when the segment was replaced
+    retired_version: SeqNumberType,
+    /// Points, deleted from the segment while it was optimized, with versions of the deletions
+    deleted_points: Vec<(PointIdType, SeqNumberType)>,
+}
+
+#[derive(Debug)]
+struct RetainedSegment {
+    segment: LockedSegment,
+    meta_path: PathBuf,
+    meta: RetainedSegmentMeta,
+}
+
+#[derive(Debug)]
+pub struct SegmentRetention {
+    path: PathBuf,
+    /// Retention window in seconds, `0` if retention is disabled
+    window_sec: AtomicU64,
+    segments: Mutex<Vec<RetainedSegment>>,
+    checkpoints: Mutex<VecDeque<Checkpoint>>,
+}
+
+impl SegmentRetention {
+    /// Load retained segments from `path`, and drop ones outside of the retention window
+    pub fn load(path: &Path, window_sec: Option<u64>) -> OperationResult<Self> {
+        let retention = Self {
+            path: path.to_path_buf(),
+            window_sec: AtomicU64::new(window_sec.unwrap_or(0)),
+            segments: Mutex::new(Vec::new()),
+            checkpoints: Mutex::new(VecDeque::new()),
+        };
+
+        if !path.exists() {
+            return Ok(retention);
+        }
+
+        let checkpoints_path = path.join(CHECKPOINTS_FILE);
+        if checkpoints_path.exists() {
+            *retention.checkpoints.lock() = common::fs::read_json(&checkpoints_path)?;
+        }
+
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let meta_path = entry?.path();
+            if meta_path == checkpoints_path
+                || meta_path.extension().and_then(|ext| ext.to_str())
+                    != Some(RETAINED_SEGMENT_META_EXT)
+            {
+                continue;
+            }
+
+            let meta: RetainedSegmentMeta = common::fs::read_json(&meta_path)?;
+            let segment_path = meta_path.with_extension("");
+            let uuid = segment_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| Uuid::parse_str(name).ok())
+                .ok_or_else(|| {
+                    OperationError::service_error(format!(
+                        "Invalid retained segment path: {}",
+                        segment_path.display(),
+                    ))
+                })?;
+
+            let segment = load_segment(&segment_path, uuid, None, &AtomicBool::new(false))?;
+            segments.push(RetainedSegment {
+                segment: LockedSegment::new(segment),
+                meta_path,
+                meta,
+            });
+        }
+        segments.sort_by_key(|retained| retained.meta.retired_at);
+        *retention.segments.lock() = segments;
+
+        retention.expire()?;
+        Ok(retention)
+    }
+
+    pub fn set_window(&self, window_sec: Option<u64>) {
+        self.window_sec
+            .store(window_sec.unwrap_or(0), Ordering::Relaxed);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.window_sec.load(Ordering::Relaxed) > 0
+    }
+
+    /// Keep segment, replaced by an optimization, instead of dropping it.
+    ///
+    /// `deleted_points` are points deleted from the segment while it was optimized,
+    /// `retired_version` is the max version of the shard after the optimization.
+    pub fn retire(
+        &self,
+        segment: LockedSegment,
+        deleted_points: &DeletedPoints,
+        retired_version: SeqNumberType,
+    ) -> OperationResult<()> {
+        let segment = segment.into_segment()?;
+        segment.flush(true)?;
+        let old_path = segment.data_path();
+        drop(segment);
+
+        fs::create_dir_all(&self.path)?;
+        let uuid = Uuid::new_v4();
+        let segment_path = self.path.join(uuid.to_string());
+        fs::rename(&old_path, &segment_path)?;
+
+        let meta = RetainedSegmentMeta {
+            retired_at: Utc::now(),
+            retired_version,
+            deleted_points: deleted_points
+                .iter()
+                .map(|(point_id, deleted)| (*point_id, deleted.operation_version))
+                .collect(),
+        };
+        let meta_path = segment_path.with_extension(RETAINED_SEGMENT_META_EXT);
+        common::fs::atomic_save_json(&meta_path, &meta)?;
+
+        let segment = load_segment(&segment_path, uuid, None, &AtomicBool::new(false))?;
+        self.segments.lock().push(RetainedSegment {
+            segment: LockedSegment::new(segment),
+            meta_path,
+            meta,
+        });
+
+        self.checkpoint(retired_version)
+    }
+
+    /// Remember the version of the shard at the current moment, and drop retained data outside of
+    /// the retention window
+    pub fn checkpoint(&self, version: SeqNumberType) -> OperationResult<()> {
+        if self.is_enabled() {
+            let mut checkpoints = self.checkpoints.lock();
+            if checkpoints
+                .back()
+                .is_none_or(|checkpoint| checkpoint.version < version)
+            {
+                checkpoints.push_back(Checkpoint {
+                    time: Utc::now(),
+                    version,
+                });
+            }
+        }
+
+        self.expire()
+    }
+
+    /// Drop retained segments and checkpoints, which are outside of the retention window
+    fn expire(&self) -> OperationResult<()> {
+        let window_sec = self.window_sec.load(Ordering::Relaxed);
+        let cutoff = i64::try_from(window_sec)
+            .ok()
+            .and_then(TimeDelta::try_seconds)
+            .and_then(|window| Utc::now().checked_sub_signed(window))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let expired: Vec<_> = {
+            let mut segments = self.segments.lock();
+            let (expired, retained) = std::mem::take(&mut *segments)
+                .into_iter()
+                .partition(|retained| window_sec == 0 || retained.meta.retired_at < cutoff);
+            *segments = retained;
+            expired
+        };
+
+        for retained in expired {
+            fs::remove_file(&retained.meta_path)?;
+            retained.segment.drop_data()?;
+        }
+
+        let mut checkpoints = self.checkpoints.lock();
+        let len_before = checkpoints.len();
+        if window_sec == 0 {
+            checkpoints.clear();
+        } else {
+            while checkpoints
+                .front()
+                .is_some_and(|checkpoint| checkpoint.time < cutoff)
+            {
+                checkpoints.pop_front();
+            }
+        }
+
+        if checkpoints.len() != len_before || window_sec > 0 {
+            fs::create_dir_all(&self.path)?;
+            common::fs::atomic_save_json(&self.path.join(CHECKPOINTS_FILE), &*checkpoints)?;
+        }
+
+        Ok(())
+    }
+
+    /// Resolve time into the last version of the shard, applied before it.
+    ///
+    /// Returns `None`, if the time is outside of the retention window.
+    pub fn version_at(&self, time: DateTime<Utc>) -> Option<SeqNumberType> {
+        let checkpoints = self.checkpoints.lock();
+        let idx = checkpoints.partition_point(|checkpoint| checkpoint.time <= time);
+        idx.checked_sub(1).map(|idx| checkpoints[idx].version)
+    }
+
+    /// Oldest version of the shard, which is within the retention window
+    pub fn oldest_version(&self) -> Option<SeqNumberType> {
+        self.checkpoints
+            .lock()
+            .front()
+            .map(|checkpoint| checkpoint.version)
+    }
+
+    /// Build a view over live and retained segments, as they were at `version`
+    pub fn view(
+        &self,
+        live: &SegmentHolder,
+        version: SeqNumberType,
+    ) -> OperationResult<SegmentHolder> {
+        let hw_counter = HardwareCounterCell::disposable();
+        let mut view = SegmentHolder::default();
+
+        for (_, segment) in live.iter() {
+            view.add_new(pinned_proxy(segment, version, [], &hw_counter)?);
+        }
+
+        for retained in self.segments.lock().iter() {
+            if retained.meta.retired_version <= version {
+                // Segment was replaced before the version, live segments cover it
+                continue;
+            }
+
+            // Points deleted while the segment was optimized, before the version
+            let deleted = retained
+                .meta
+                .deleted_points
+                .iter()
+                .copied()
+                .filter(|&(_, deleted_version)| deleted_version <= version);
+            view.add_new(pinned_proxy(
+                &retained.segment,
+                version,
+                deleted,
+                &hw_counter,
+            )?);
+        }
+
+        Ok(view)
+    }
+}
+
+/// Wrap segment into a proxy, which hides points newer than `version` and `deleted` points
+fn pinned_proxy(
+    segment: &LockedSegment,
+    version: SeqNumberType,
+    deleted: impl IntoIterator<Item = (PointIdType, SeqNumberType)>,
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<ProxySegment> {
+    let newer_points: Vec<_> = {
+        let segment = segment.get_read().read();
+        if segment.version() <= version {
+            Vec::new()
+        } else {
+            segment
+                .read_range(None, None)
+                .into_iter()
+                .filter_map(|point_id| {
+                    let point_version = segment.point_version(point_id)?;
+                    (point_version > version).then_some((point_id, point_version))
+                })
+                .collect()
+        }
+    };
+
+    let mut proxy = ProxySegment::new(segment.clone());
+    for (point_id, point_version) in newer_points.into_iter().chain(deleted) {
+        proxy.delete_point(point_version, point_id, hw_counter)?;
+    }
+    Ok(proxy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_at() {
+        let dir = tempfile::Builder::new()
+            .prefix("retained_segments")
+            .tempdir()
+            .unwrap();
+        let retention = SegmentRetention::load(dir.path(), Some(3600)).unwrap();
+        assert_eq!(retention.version_at(Utc::now()), None);
+
+        retention.checkpoint(10).unwrap();
+        let after_first = Utc::now();
+        retention.checkpoint(10).unwrap();
+        retention.checkpoint(20).unwrap();
+
+        assert_eq!(retention.oldest_version(), Some(10));
+        assert_eq!(
+            retention.version_at(after_first - TimeDelta::hours(1)),
+            None
+        );
+        assert_eq!(retention.version_at(Utc::now()), Some(20));
+
+        // Checkpoints are persisted
+        let retention = SegmentRetention::load(dir.path(), Some(3600)).unwrap();
+        assert_eq!(retention.version_at(Utc::now()), Some(20));
+
+        // Disabled retention drops everything
+        retention.set_window(None);
+        retention.checkpoint(30).unwrap();
+        assert_eq!(retention.oldest_version(), None);
+    }
+}
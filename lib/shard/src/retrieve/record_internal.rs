@@ -83,6 +83,7 @@ impl TryFrom<RecordInternal> for PointStructPersisted {
             id,
             payload,
             vector: VectorStructPersisted::from(vector.unwrap()),
+            expires_at: None,
         })
     }
 }
@@ -6,6 +6,7 @@ use ahash::AHashMap;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use common::types::DeferredBehavior;
 use segment::common::operation_error::{OperationError, OperationResult};
+use segment::data_types::segment_record::NamedVectorsOwned;
 use segment::types::{PointIdType, SeqNumberType, WithPayload, WithVector};
 
 use crate::retrieve::record_internal::RecordInternal;
@@ -23,6 +24,43 @@ pub fn retrieve_blocking(
     hw_measurement_acc: HwMeasurementAcc,
     deferred_behavior: DeferredBehavior,
 ) -> OperationResult<AHashMap<PointIdType, RecordInternal>> {
+    retrieve_at_version_blocking(
+        segments,
+        points,
+        with_payload,
+        with_vector,
+        None,
+        timeout,
+        is_stopped,
+        hw_measurement_acc,
+        deferred_behavior,
+    )
+}
+
+/// Retrieve points with vectors as they were after the operation with `at_version`.
+///
+/// Previous versions of vectors are only kept by segments, which have history of vectors
+/// enabled. Payloads are always the latest. If `at_version` is `None`, the latest vectors are
+/// retrieved.
+#[allow(clippy::too_many_arguments)]
+pub fn retrieve_at_version_blocking(
+    segments: LockedSegmentHolder,
+    points: &[PointIdType],
+    with_payload: &WithPayload,
+    with_vector: &WithVector,
+    at_version: Option<SeqNumberType>,
+    timeout: Duration,
+    is_stopped: &AtomicBool,
+    hw_measurement_acc: HwMeasurementAcc,
+    deferred_behavior: DeferredBehavior,
+) -> OperationResult<AHashMap<PointIdType, RecordInternal>> {
+    // Vectors at the version are read separately
+    let without_vector = WithVector::Bool(false);
+    let segment_with_vector = match at_version {
+        Some(_) => &without_vector,
+        None => with_vector,
+    };
+
     let mut point_version: AHashMap<PointIdType, SeqNumberType> = Default::default();
     let mut point_records: AHashMap<PointIdType, RecordInternal> = Default::default();
 
@@ -48,14 +86,32 @@ pub fn retrieve_blocking(
             *version_entry.or_default() = version;
         }
 
-        for (id, record) in segment.retrieve(
+        for (id, mut record) in segment.retrieve(
             &newer_version_points,
             with_payload,
-            with_vector,
+            segment_with_vector,
             &hw_counter,
             is_stopped,
             deferred_behavior,
         )? {
+            if let Some(at_version) = at_version
+                && with_vector.is_enabled()
+            {
+                let vector_names = match with_vector {
+                    WithVector::Bool(_) => segment.vector_names().into_iter().collect(),
+                    WithVector::Selector(selector) => selector.clone(),
+                };
+                let mut vectors = NamedVectorsOwned::default();
+                for vector_name in vector_names {
+                    if let Some(vector) =
+                        segment.vector_at_version(&vector_name, id, at_version, &hw_counter)?
+                    {
+                        vectors.push((vector_name, vector));
+                    }
+                }
+                record.vectors = Some(vectors);
+            }
+
             // We expect all points to be found since we already checked their versions
             point_records.insert(id, RecordInternal::from(record));
             applied += 1;
@@ -66,3 +122,44 @@ pub fn retrieve_blocking(
 
     Ok(point_records)
 }
+
+/// Retrieve binary blobs of points. Points without a blob are not included in the result.
+pub fn retrieve_blobs_blocking(
+    segments: LockedSegmentHolder,
+    points: &[PointIdType],
+    timeout: Duration,
+    is_stopped: &AtomicBool,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> OperationResult<AHashMap<PointIdType, Vec<u8>>> {
+    let mut point_version: AHashMap<PointIdType, SeqNumberType> = Default::default();
+    let mut point_blobs: AHashMap<PointIdType, Vec<u8>> = Default::default();
+
+    let hw_counter = hw_measurement_acc.get_counter_cell();
+
+    SegmentHolder::read_points_locked(&segments, points, is_stopped, timeout, |ids, segment| {
+        let mut applied = 0;
+
+        for &id in ids {
+            let version = segment.point_version(id).ok_or_else(|| {
+                OperationError::service_error(format!("No version for point {id}"))
+            })?;
+            applied += 1;
+
+            // If we already have the latest point version, keep that and continue
+            let version_entry = point_version.entry(id);
+            if matches!(&version_entry, Entry::Occupied(entry) if *entry.get() >= version) {
+                continue;
+            }
+            *version_entry.or_default() = version;
+
+            match segment.blob(id, &hw_counter)? {
+                Some(blob) => point_blobs.insert(id, blob),
+                None => point_blobs.remove(&id),
+            };
+        }
+
+        Ok(applied)
+    })?;
+
+    Ok(point_blobs)
+}
@@ -28,13 +28,18 @@ use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::{
     NonAppendableSegmentEntry, ReadSegmentEntry, SegmentEntry, StorageSegmentEntry,
 };
+use segment::index::sparse_index::sparse_vocabulary::{TokenSparseVector, UnknownTokens};
 use segment::segment::Segment;
 use segment::segment_constructor::build_segment;
-use segment::types::{ExtendedPointId, Payload, PointIdType, SegmentConfig, SeqNumberType};
+use segment::types::{
+    ExtendedPointId, Payload, PointIdType, SegmentConfig, SeqNumberType, VectorName,
+};
 use smallvec::SmallVec;
+use sparse::common::sparse_vector::SparseVector;
 
 use crate::locked_segment::LockedSegment;
 use crate::payload_index_schema::PayloadIndexSchema;
+use crate::retention::SegmentRetention;
 
 pub type SegmentId = usize;
 
@@ -74,6 +79,9 @@ pub struct SegmentHolder {
 
     /// The amount of currently running optimizations.
     pub running_optimizations: ProcessCounter,
+
+    /// Retention of segments replaced by optimizations, if configured for the shard.
+    pub retention: Option<Arc<SegmentRetention>>,
 }
 
 impl Drop for SegmentHolder {
@@ -570,6 +578,26 @@ impl SegmentHolder {
         Ok(())
     }
 
+    /// Delete points, which are expired at `now`, from all segments.
+    ///
+    /// Returns number of deleted points.
+    pub fn delete_expired_points(
+        &self,
+        now: u64,
+        hw_counter: &HardwareCounterCell,
+    ) -> OperationResult<usize> {
+        let mut deleted = 0;
+        for (_, segment) in self.iter() {
+            let segment_arc = segment.get();
+            // Only take the write lock, if there is something to delete
+            if !segment_arc.read().has_expired_points(now) {
+                continue;
+            }
+            deleted += segment_arc.write().delete_expired_points(now, hw_counter)?;
+        }
+        Ok(deleted)
+    }
+
     /// This operation deduplicates subset of points across all segments.
     /// It scans all segments for presence of the points, detects points with the highest version,
     /// and removes all other versions of the points from all segments.
@@ -701,6 +729,8 @@ impl SegmentHolder {
 
                         let mut all_vectors = write_segment.all_vectors(point_id, hw_counter)?;
                         let mut payload = write_segment.payload(point_id, hw_counter)?;
+                        let expires_at = write_segment.point_expiration(point_id);
+                        let blob = write_segment.blob(point_id, hw_counter)?;
 
                         point_cow_operation(point_id, &mut all_vectors, &mut payload);
 
@@ -712,6 +742,18 @@ impl SegmentHolder {
                         )?;
                         appendable_write_segment
                             .set_full_payload(op_num, point_id, &payload, hw_counter)?;
+                        if expires_at.is_some() {
+                            appendable_write_segment
+                                .set_point_expiration(op_num, point_id, expires_at)?;
+                        }
+                        if blob.is_some() {
+                            appendable_write_segment.set_blob(
+                                op_num,
+                                point_id,
+                                blob.as_deref(),
+                                hw_counter,
+                            )?;
+                        }
 
                         // Keep the source of the CoW operation as the deferred point is invisible until indexing.
                         if !appendable_write_segment.point_is_deferred(point_id) {
@@ -840,9 +882,44 @@ impl SegmentHolder {
             segment.create_field_index(0, key, Some(schema), &hw_counter)?;
         }
 
+        // Sparse vocabularies are shared by all segments, copy them from any existing one
+        if let Some((_, source_segment)) = self.iter().next() {
+            let source_segment = source_segment.get();
+            let source_segment = source_segment.read();
+            for vector_name in config.sparse_vector_data.keys() {
+                // Vector may be missing in the existing segment, if it was added later
+                let Ok(mapping) = source_segment.sparse_vocabulary(vector_name) else {
+                    continue;
+                };
+                if !mapping.is_empty() {
+                    segment.register_sparse_vocabulary(vector_name, &mapping)?;
+                }
+            }
+        }
+
         Ok(LockedSegment::new(segment))
     }
 
+    /// Convert token keyed sparse vector of `vector_name` into dimension ids.
+    ///
+    /// All segments share the same vocabulary, so any of them can be used for remapping.
+    pub fn remap_sparse_vector(
+        &self,
+        vector_name: &VectorName,
+        vector: &TokenSparseVector,
+        unknown_tokens: UnknownTokens,
+    ) -> OperationResult<SparseVector> {
+        let Some((_, segment)) = self.iter().next() else {
+            return Err(OperationError::service_error(
+                "No segments to remap sparse vector with",
+            ));
+        };
+        segment
+            .get()
+            .read()
+            .remap_sparse_vector(vector_name, vector, unknown_tokens)
+    }
+
     /// Method tries to remove the segment with the given ID under the following conditions:
     ///
     /// - The segment exists in the holder, if not - it is ignored.
@@ -100,6 +100,7 @@ impl SnapshotUtils {
             newest_clocks_path: from_newest_clocks_path,
             oldest_clocks_path: from_oldest_clocks_path,
             applied_seq_path: from_applied_seq_path,
+            retained_segments_path: _,
         } = get_shard_data_files(snapshot_path);
 
         let ShardDataFiles {
@@ -108,6 +109,7 @@ impl SnapshotUtils {
             newest_clocks_path: to_newest_clocks_path,
             oldest_clocks_path: to_oldest_clocks_path,
             applied_seq_path: to_applied_seq_path,
+            retained_segments_path: _,
         } = get_shard_data_files(shard_path);
 
         merge_directories.push((from_segments_path, to_segments_path));
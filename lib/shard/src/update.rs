@@ -1,5 +1,6 @@
 //! A collection of functions for updating points and payloads stored in segments
 
+use std::collections::BTreeMap;
 use std::sync::atomic::AtomicBool;
 
 use ahash::{AHashMap, AHashSet};
@@ -13,8 +14,9 @@ use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType, VectorNameBuf, WithPayload, WithVector,
+    SeqNumberType, VectorName, VectorNameBuf, WithPayload, WithVector,
 };
+use sparse::common::types::DimId;
 
 use crate::operations::FieldIndexOperations;
 use crate::operations::payload_ops::PayloadOps;
@@ -203,6 +205,7 @@ where
                     id,
                     point.get_vectors(),
                     point.payload.as_ref(),
+                    point.expires_at,
                     hw_counter,
                 )
             },
@@ -218,6 +221,19 @@ where
             hw_counter,
         )?;
 
+        // Points moved into appendable segments carry their previous expiration, replace it
+        segments.apply_points(
+            &updated_points.iter().copied().collect::<Vec<_>>(),
+            hw_counter,
+            |id, _, write_segment| {
+                let expires_at = points_map[&id].expires_at;
+                if write_segment.is_proxy() || write_segment.point_expiration(id) == expires_at {
+                    return Ok(false);
+                }
+                write_segment.set_point_expiration(op_num, id, expires_at)
+            },
+        )?;
+
         res += updated_points.len();
         // Insert new points, which was not updated or existed
         let new_point_ids = ids_chunk
@@ -243,6 +259,7 @@ where
                     point_id,
                     point.get_vectors(),
                     point.payload.as_ref(),
+                    point.expires_at,
                     hw_counter,
                 )?);
             }
@@ -310,6 +327,7 @@ pub fn conditional_upsert(
 /// Upsert to a point ID with the specified vectors and payload in the given segment.
 ///
 /// If the payload is None, the existing payload will be cleared.
+/// If the expiration is None, the point will never expire.
 ///
 /// Returns
 /// - Ok(true) if the operation was successful and point replaced existing value
@@ -321,6 +339,7 @@ fn upsert_with_payload(
     point_id: PointIdType,
     vectors: NamedVectors,
     payload: Option<&Payload>,
+    expires_at: Option<u64>,
     hw_counter: &HardwareCounterCell,
 ) -> OperationResult<bool> {
     let mut res = segment.upsert_point(op_num, point_id, vectors, hw_counter)?;
@@ -329,6 +348,7 @@ fn upsert_with_payload(
     } else {
         res &= segment.clear_payload(op_num, point_id, hw_counter)?;
     }
+    res &= segment.set_point_expiration(op_num, point_id, expires_at)?;
     debug_assert!(
         segment.has_point(point_id),
         "the point {point_id} should be present immediately after the upsert"
@@ -903,6 +923,53 @@ pub fn overwrite_payload_by_filter(
     Ok(points_updated)
 }
 
+/// Set or remove the binary blob of points.
+pub fn set_blob(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    blob: Option<&[u8]>,
+    points: &[PointIdType],
+    hw_counter: &HardwareCounterCell,
+) -> OperationResult<usize> {
+    let mut total_updated_points = 0;
+
+    for chunk in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
+        let updated_points = segments.apply_points_with_conditional_move(
+            op_num,
+            chunk,
+            |id, write_segment| write_segment.set_blob(op_num, id, blob, hw_counter),
+            |_, _, _| {},
+            hw_counter,
+        )?;
+
+        check_unprocessed_points(chunk, &updated_points)?;
+
+        // Points moved into appendable segments carry their previous blob, replace it
+        segments.apply_points(chunk, hw_counter, |id, _, write_segment| {
+            if write_segment.is_proxy() || write_segment.blob(id, hw_counter)?.as_deref() == blob {
+                return Ok(false);
+            }
+            write_segment.set_blob(op_num, id, blob, hw_counter)
+        })?;
+
+        total_updated_points += updated_points.len();
+    }
+
+    Ok(total_updated_points)
+}
+
+/// Register token mappings in the vocabulary of sparse vector `vector_name` of all segments.
+pub fn register_sparse_vocabulary(
+    segments: &SegmentHolder,
+    vector_name: &VectorName,
+    mapping: &BTreeMap<String, DimId>,
+) -> OperationResult<usize> {
+    segments.apply_segments(|write_segment| {
+        write_segment
+            .with_upgraded(|segment| segment.register_sparse_vocabulary(vector_name, mapping))
+    })
+}
+
 pub fn create_field_index(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -271,6 +271,11 @@ impl<R: DeserializeOwned + Serialize> SerdeWal<R> {
         self.wal.last_index()
     }
 
+    /// Closed segment files of the underlying WAL, along with the index of their first entry.
+    pub fn closed_segment_files(&self) -> impl Iterator<Item = (u64, &Path)> {
+        self.wal.closed_segment_files()
+    }
+
     pub fn segment_capacity(&self) -> usize {
         self.options.segment_capacity
     }
@@ -0,0 +1,83 @@
+use crate::common::types::DimWeight;
+
+/// BM25 term frequency saturation and document length normalization.
+///
+/// Applied to the weights stored in the index, which are expected to be term frequencies.
+/// The IDF part of BM25 is applied to the query weights instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bm25 {
+    /// Term frequency saturation
+    pub k1: f32,
+    /// Strength of document length normalization, from 0.0 (none) to 1.0 (full)
+    pub b: f32,
+    /// Average length of the documents in the collection
+    pub avg_doc_length: f32,
+}
+
+impl Bm25 {
+    /// Normalized weight of a term with frequency `tf` in a document of length `doc_length`
+    #[inline]
+    pub fn weight(&self, tf: DimWeight, doc_length: f32) -> DimWeight {
+        // Saturation is only defined for positive frequencies
+        if tf <= 0.0 {
+            return tf;
+        }
+        let length_ratio = if self.avg_doc_length > 0.0 {
+            doc_length / self.avg_doc_length
+        } else {
+            1.0
+        };
+        let norm = self.k1 * (1.0 - self.b + self.b * length_ratio);
+        tf * (self.k1 + 1.0) / (tf + norm)
+    }
+
+    /// Upper bound of the normalized weight of a term with frequency up to `tf`,
+    /// over all document lengths.
+    ///
+    /// The normalized weight grows with the frequency and decreases with the document length,
+    /// so the bound is reached for an empty document.
+    #[inline]
+    pub fn max_weight(&self, tf: DimWeight) -> DimWeight {
+        self.weight(tf, 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BM25: Bm25 = Bm25 {
+        k1: 1.2,
+        b: 0.75,
+        avg_doc_length: 10.0,
+    };
+
+    #[test]
+    fn test_bm25_weight() {
+        // Average length document: tf * (k1 + 1) / (tf + k1)
+        assert!((BM25.weight(1.0, 10.0) - 1.0).abs() < 1e-6);
+        assert!((BM25.weight(2.0, 10.0) - 2.0 * 2.2 / 3.2).abs() < 1e-6);
+
+        // Longer documents get lower weights
+        assert!(BM25.weight(2.0, 20.0) < BM25.weight(2.0, 10.0));
+        // Frequencies saturate at k1 + 1
+        assert!(BM25.weight(1000.0, 10.0) < BM25.k1 + 1.0);
+
+        // Without length normalization the document length does not matter
+        let no_norm = Bm25 { b: 0.0, ..BM25 };
+        assert_eq!(no_norm.weight(2.0, 5.0), no_norm.weight(2.0, 50.0));
+    }
+
+    #[test]
+    fn test_bm25_max_weight() {
+        for tf in [0.5, 1.0, 3.0, 100.0] {
+            for doc_length in [0.0, 1.0, 10.0, 1000.0] {
+                assert!(BM25.weight(tf, doc_length) <= BM25.max_weight(tf));
+            }
+        }
+
+        // Bound is finite even with full normalization
+        let full_norm = Bm25 { b: 1.0, ..BM25 };
+        assert!((full_norm.max_weight(3.0) - (full_norm.k1 + 1.0)).abs() < 1e-6);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod bm25;
 pub mod scores_memory_pool;
 pub mod sparse_vector;
 #[cfg(feature = "testing")]
@@ -10,7 +10,7 @@ use common::types::PointOffsetType;
 use itertools::Itertools as _;
 
 use super::posting_list_common::{
-    GenericPostingElement, PostingElement, PostingElementEx, PostingListIter,
+    BlockMax, GenericPostingElement, PostingElement, PostingElementEx, PostingListIter,
 };
 use crate::common::types::{DimWeight, Weight};
 type BitPackerImpl = bitpacking::BitPacker4x;
@@ -29,6 +29,9 @@ pub struct CompressedPostingList<W: Weight> {
     /// Remainder elements that do not fit into chunks.
     remainders: Vec<GenericPostingElement<W>>,
 
+    /// Max weight of the remainder elements.
+    remainders_max_weight: DimWeight,
+
     /// Id of the last element in the list. Used to avoid unpacking the last chunk.
     last_id: Option<PointOffsetType>,
 
@@ -42,6 +45,7 @@ pub struct CompressedPostingListView<'a, W: Weight> {
     id_data: &'a [u8],
     chunks: &'a [CompressedPostingChunk<W>],
     remainders: &'a [GenericPostingElement<W>],
+    remainders_max_weight: DimWeight,
     last_id: Option<PointOffsetType>,
     multiplier: W::QuantizationParams,
     hw_counter: &'a HardwareCounterCell,
@@ -56,6 +60,9 @@ pub struct CompressedPostingChunk<W> {
     /// An offset within id_data
     offset: u32,
 
+    /// Max weight of the chunk. Used for block-max pruning.
+    max_weight: DimWeight,
+
     /// Weight values for the chunk.
     weights: [W; CHUNK_SIZE],
 }
@@ -69,6 +76,7 @@ impl<W: Weight> CompressedPostingList<W> {
             id_data: &self.id_data,
             chunks: &self.chunks,
             remainders: &self.remainders,
+            remainders_max_weight: self.remainders_max_weight,
             last_id: self.last_id,
             multiplier: self.quantization_params,
             hw_counter,
@@ -126,6 +134,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         id_data: &'a [u8],
         chunks: &'a [CompressedPostingChunk<W>],
         remainders: &'a [GenericPostingElement<W>],
+        remainders_max_weight: DimWeight,
         last_id: Option<PointOffsetType>,
         multiplier: W::QuantizationParams,
         hw_counter: &'a HardwareCounterCell,
@@ -134,6 +143,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
             id_data,
             chunks,
             remainders,
+            remainders_max_weight,
             last_id,
             multiplier,
             hw_counter,
@@ -154,6 +164,10 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         self.last_id
     }
 
+    pub fn remainders_max_weight(&self) -> DimWeight {
+        self.remainders_max_weight
+    }
+
     pub fn multiplier(&self) -> W::QuantizationParams {
         self.multiplier
     }
@@ -171,6 +185,7 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
             id_data: self.id_data.to_vec(),
             chunks: self.chunks.to_vec(),
             remainders: self.remainders.to_vec(),
+            remainders_max_weight: self.remainders_max_weight,
             last_id: self.last_id,
             quantization_params: self.multiplier,
         }
@@ -306,6 +321,50 @@ impl<'a, W: Weight> CompressedPostingListView<'a, W> {
         self.chunks.len()
     }
 
+    /// Block-max metadata of the chunk or of the remainders at the given position.
+    fn block_max(&self, pos: usize) -> Option<BlockMax> {
+        if let Some(chunk_index) = self.chunk_id_by_position(pos) {
+            self.hw_counter
+                .vector_io_read()
+                .incr_delta(size_of::<DimWeight>());
+            let next_block_id = match self.chunks.get(chunk_index + 1) {
+                Some(next_chunk) => Some(next_chunk.initial),
+                None => self.remainders.first().map(|e| e.record_id),
+            };
+            return Some(BlockMax {
+                max_weight: self.chunks[chunk_index].max_weight,
+                next_block_id,
+            });
+        }
+
+        (pos < self.len()).then_some(BlockMax {
+            max_weight: self.remainders_max_weight,
+            next_block_id: None,
+        })
+    }
+
+    /// Upper bound of weights of the elements from the given position to the end.
+    fn max_weight_from(&self, pos: usize) -> Option<DimWeight> {
+        if pos >= self.len() {
+            return None;
+        }
+        let remainders_max_weight = if self.remainders.is_empty() {
+            DimWeight::NEG_INFINITY
+        } else {
+            self.remainders_max_weight
+        };
+        let chunks = self.chunks.get(pos / CHUNK_SIZE..).unwrap_or_default();
+        self.hw_counter
+            .vector_io_read()
+            .incr_delta(size_of::<DimWeight>() * chunks.len());
+        Some(
+            chunks
+                .iter()
+                .map(|chunk| chunk.max_weight)
+                .fold(remainders_max_weight, DimWeight::max),
+        )
+    }
+
     /// Warning: This function panics if the index is out of bounds.
     #[inline]
     fn get_weight(&self, pos: usize) -> W {
@@ -375,15 +434,17 @@ impl CompressedPostingBuilder {
                 let chunk_bits =
                     bitpacker.num_bits_strictly_sorted(initial.checked_sub(1), &this_chunk);
                 let chunk_size = BitPackerImpl::compressed_block_size(chunk_bits);
+                let weights: [W; CHUNK_SIZE] = chunk
+                    .iter()
+                    .map(|e| Weight::from_f32(quantization_params, e.weight))
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .expect("Invalid chunk size");
                 chunks.push(CompressedPostingChunk {
                     initial,
                     offset: data_size as u32,
-                    weights: chunk
-                        .iter()
-                        .map(|e| Weight::from_f32(quantization_params, e.weight))
-                        .collect::<Vec<_>>()
-                        .try_into()
-                        .expect("Invalid chunk size"),
+                    max_weight: max_weight(quantization_params, &weights),
+                    weights,
                 });
                 data_size += chunk_size;
             } else {
@@ -413,10 +474,14 @@ impl CompressedPostingBuilder {
             );
         }
 
+        let remainders_max_weight =
+            max_weight(quantization_params, remainders.iter().map(|e| &e.weight));
+
         CompressedPostingList {
             id_data,
             chunks,
             remainders,
+            remainders_max_weight,
             last_id: self.elements.last().map(|e| e.record_id),
             quantization_params,
         }
@@ -650,6 +715,14 @@ impl<W: Weight> PostingListIter for CompressedPostingListIterator<'_, W> {
         false
     }
 
+    fn block_max(&self) -> Option<BlockMax> {
+        self.list.block_max(self.pos.0)
+    }
+
+    fn max_weight_to_end(&self) -> Option<DimWeight> {
+        self.list.max_weight_from(self.pos.0)
+    }
+
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement> {
         CompressedPostingListStdIterator(self)
     }
@@ -666,6 +739,17 @@ impl<W: Weight> Iterator for CompressedPostingListStdIterator<'_, W> {
     }
 }
 
+/// Max weight of the quantized `weights`, as it would be seen by the search.
+fn max_weight<'a, W: Weight>(
+    params: W::QuantizationParams,
+    weights: impl IntoIterator<Item = &'a W>,
+) -> DimWeight {
+    weights
+        .into_iter()
+        .map(|weight| weight.to_f32(params))
+        .fold(DimWeight::NEG_INFINITY, DimWeight::max)
+}
+
 /// Find the amount of elements in the sorted array that are less or equal to `val`. In other words,
 /// the first index `i` such that `data[i] > val`, or `data.len()` if all elements are less or equal
 /// to `val`.
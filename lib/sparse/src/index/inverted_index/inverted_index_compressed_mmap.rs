@@ -1,5 +1,6 @@
 use std::borrow::Cow;
-use std::io::{BufWriter, Write as _};
+use std::collections::HashSet;
+use std::io::{BufWriter, Seek as _, SeekFrom, Write as _};
 use std::marker::PhantomData;
 use std::mem::size_of;
 use std::path::{Path, PathBuf};
@@ -15,19 +16,23 @@ use common::mmap::{
 };
 use common::storage_version::StorageVersion;
 use common::types::PointOffsetType;
+use fs_err::File;
 use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 
 use super::INDEX_FILE_NAME;
 use super::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use super::inverted_index_spilling_builder::InvertedIndexSpillingBuilder;
+use super::posting_max_weights::PostingMaxWeights;
 use crate::common::sparse_vector::RemappedSparseVector;
-use crate::common::types::{DimId, DimOffset, Weight};
+use crate::common::types::{DimId, DimOffset, DimWeight, Weight};
 use crate::index::compressed_posting_list::{
-    CompressedPostingChunk, CompressedPostingListIterator, CompressedPostingListView,
+    CompressedPostingBuilder, CompressedPostingChunk, CompressedPostingList,
+    CompressedPostingListIterator, CompressedPostingListView,
 };
 use crate::index::inverted_index::InvertedIndex;
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
-use crate::index::posting_list_common::GenericPostingElement;
+use crate::index::posting_list_common::{GenericPostingElement, PostingListIter as _};
 
 const INDEX_CONFIG_FILE_NAME: &str = "inverted_index_config.json";
 
@@ -35,7 +40,7 @@ pub struct Version;
 
 impl StorageVersion for Version {
     fn current_raw() -> &'static str {
-        "0.2.0"
+        "0.3.0"
     }
 }
 
@@ -58,6 +63,8 @@ pub struct InvertedIndexCompressedMmap<W> {
     path: PathBuf,
     mmap: Arc<Mmap>,
     pub file_header: InvertedIndexFileHeader,
+    /// Kept in RAM, so the search can consult it without reading posting lists from disk
+    max_weights: PostingMaxWeights,
     _phantom: PhantomData<W>,
 }
 
@@ -71,6 +78,8 @@ struct PostingListFileHeader<W: Weight> {
     /// Max = 512 = `BLOCK_LEN * size_of::<u32>()` = `128 * 4`.
     pub ids_len: u32,
     pub chunks_count: u32,
+    /// Max weight of the remainder elements, see [`crate::index::posting_list_common::BlockMax`].
+    pub remainders_max_weight: DimWeight,
     pub quantization_params: W::QuantizationParams,
 }
 
@@ -122,6 +131,7 @@ impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
         vec![
             Self::index_file_path(path),
             Self::index_config_file_path(path),
+            PostingMaxWeights::file_path(path),
         ]
     }
 
@@ -151,6 +161,13 @@ impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
         Self::convert_and_save(&index, path)
     }
 
+    fn from_spilling_builder<P: AsRef<Path>>(
+        builder: InvertedIndexSpillingBuilder,
+        path: P,
+    ) -> std::io::Result<Self> {
+        Self::convert_and_save_spilled(builder, path)
+    }
+
     fn vector_count(&self) -> usize {
         self.file_header.vector_count
     }
@@ -169,6 +186,76 @@ impl<W: Weight> InvertedIndex for InvertedIndexCompressedMmap<W> {
             len => Some(len as DimId - 1),
         }
     }
+
+    fn posting_max_weight(&self, id: DimOffset) -> Option<DimWeight> {
+        self.max_weights.get(id)
+    }
+
+    fn compact(&mut self, is_deleted: impl Fn(PointOffsetType) -> bool) -> std::io::Result<usize> {
+        // Ignore HW on maintenance
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let posting_count = self.file_header.posting_count;
+        let file_path = Self::index_file_path(&self.path);
+        let tmp_file_path = file_path.with_extension("tmp");
+
+        let mut removed_elements = 0;
+        let mut removed_vectors = HashSet::new();
+        let mut writer = PostingListsWriter::<W>::new(&tmp_file_path, posting_count)?;
+        for id in 0..posting_count as DimId {
+            let Some(posting) = self.get(id, &hw_counter) else {
+                // Malformed posting, keep an empty one in its place to not shift dimension ids
+                let empty: CompressedPostingList<W> = CompressedPostingBuilder::new().build();
+                writer.write(&empty.view(&hw_counter))?;
+                continue;
+            };
+
+            let has_deleted = posting
+                .iter()
+                .into_std_iter()
+                .any(|element| is_deleted(element.record_id));
+            if !has_deleted {
+                // Copy as is, to not quantize the weights again
+                writer.write(&posting)?;
+                continue;
+            }
+
+            let mut posting_builder = CompressedPostingBuilder::new();
+            for element in posting.iter().into_std_iter() {
+                if is_deleted(element.record_id) {
+                    removed_elements += 1;
+                    removed_vectors.insert(element.record_id);
+                } else {
+                    posting_builder.add(element.record_id, element.weight);
+                }
+            }
+            let compacted: CompressedPostingList<W> = posting_builder.build();
+            writer.write(&compacted.view(&hw_counter))?;
+        }
+        let (total_sparse_size, max_weights) = writer.finish()?;
+
+        if removed_elements == 0 {
+            fs_err::remove_file(&tmp_file_path)?;
+            return Ok(0);
+        }
+
+        // Posting count doesn't change, so the index stays readable with the old header,
+        // if interrupted before the header is saved
+        fs_err::rename(&tmp_file_path, &file_path)?;
+
+        let file_header = InvertedIndexFileHeader {
+            posting_count,
+            vector_count: self
+                .file_header
+                .vector_count
+                .saturating_sub(removed_vectors.len()),
+            total_sparse_size: Some(total_sparse_size),
+        };
+        let path = self.path.clone();
+        *self = Self::save_file_header_and_open(&path, file_header, max_weights)?;
+
+        Ok(removed_elements)
+    }
 }
 
 impl<W: Weight> InvertedIndexCompressedMmap<W> {
@@ -244,6 +331,7 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
                     &self.mmap[remainders_start as usize..remainders_end as usize],
                 )
             },
+            header.remainders_max_weight,
             header.last_id.checked_sub(1),
             header.quantization_params,
             hw_counter,
@@ -265,58 +353,15 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
         index: &InvertedIndexCompressedImmutableRam<W>,
         path: P,
     ) -> std::io::Result<Self> {
-        let total_posting_headers_size =
-            index.postings.as_slice().len() * size_of::<PostingListFileHeader<W>>();
-
         // Ignore HW on load
         let hw_counter = HardwareCounterCell::disposable();
 
-        let file_length = total_posting_headers_size
-            + index
-                .postings
-                .as_slice()
-                .iter()
-                .map(|p| p.view(&hw_counter).store_size().total)
-                .sum::<usize>();
         let file_path = Self::index_file_path(path.as_ref());
-        let file = create_and_ensure_length(file_path.as_ref(), file_length)?;
-
-        let mut buf = BufWriter::new(file);
-
-        // Save posting headers
-        let mut offset: usize = total_posting_headers_size;
-        for posting in index.postings.as_slice() {
-            let store_size = posting.view(&hw_counter).store_size();
-            let posting_header = PostingListFileHeader::<W> {
-                ids_start: offset as u64,
-                ids_len: store_size.id_data_bytes as u32,
-                chunks_count: store_size.chunks_count as u32,
-                last_id: posting.view(&hw_counter).last_id().map_or(0, |id| id + 1),
-                quantization_params: posting.view(&hw_counter).multiplier(),
-            };
-            // TODO Safety
-            #[expect(deprecated, reason = "legacy code")]
-            buf.write_all(unsafe { transmute_to_u8(&posting_header) })?;
-            offset += store_size.total;
-        }
-
-        // Save posting elements
+        let mut writer = PostingListsWriter::<W>::new(&file_path, index.postings.len())?;
         for posting in index.postings.as_slice() {
-            let posting_view = posting.view(&hw_counter);
-            let (id_data, chunks, remainders) = posting_view.parts();
-            buf.write_all(id_data)?;
-            // TODO Safety
-            #[expect(deprecated, reason = "legacy code")]
-            buf.write_all(unsafe { transmute_to_u8_slice(chunks) })?;
-            // TODO Safety
-            #[expect(deprecated, reason = "legacy code")]
-            buf.write_all(unsafe { transmute_to_u8_slice(remainders) })?;
+            writer.write(&posting.view(&hw_counter))?;
         }
-
-        // Explicitly fsync file contents to ensure durability
-        buf.flush()?;
-        let file = buf.into_inner().unwrap();
-        file.sync_all()?;
+        let (_, max_weights) = writer.finish()?;
 
         // save header properties
         let file_header = InvertedIndexFileHeader {
@@ -325,16 +370,60 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
             total_sparse_size: Some(index.total_sparse_size),
         };
 
-        atomic_save_json(&Self::index_config_file_path(path.as_ref()), &file_header)?;
+        Self::save_file_header_and_open(path.as_ref(), file_header, max_weights)
+    }
+
+    /// Build the index from the spilled postings, without keeping all of them in memory.
+    ///
+    /// Only a single posting list is compressed in memory at a time.
+    pub fn convert_and_save_spilled<P: AsRef<Path>>(
+        builder: InvertedIndexSpillingBuilder,
+        path: P,
+    ) -> std::io::Result<Self> {
+        // Ignore HW on load
+        let hw_counter = HardwareCounterCell::disposable();
+
+        let posting_count = builder.posting_count();
+        let vector_count = builder.vector_count();
+
+        let file_path = Self::index_file_path(path.as_ref());
+        let mut writer = PostingListsWriter::<W>::new(&file_path, posting_count)?;
+        builder.for_each_posting(|_dim_id, elements| {
+            let mut posting_builder = CompressedPostingBuilder::new();
+            for element in elements {
+                posting_builder.add(element.record_id, element.weight);
+            }
+            let posting: CompressedPostingList<W> = posting_builder.build();
+            writer.write(&posting.view(&hw_counter))
+        })?;
+        let (total_sparse_size, max_weights) = writer.finish()?;
+
+        let file_header = InvertedIndexFileHeader {
+            posting_count,
+            vector_count,
+            total_sparse_size: Some(total_sparse_size),
+        };
+
+        Self::save_file_header_and_open(path.as_ref(), file_header, max_weights)
+    }
+
+    fn save_file_header_and_open(
+        path: &Path,
+        file_header: InvertedIndexFileHeader,
+        max_weights: PostingMaxWeights,
+    ) -> std::io::Result<Self> {
+        max_weights.save(path)?;
+        atomic_save_json(&Self::index_config_file_path(path), &file_header)?;
 
         Ok(Self {
-            path: path.as_ref().to_owned(),
+            path: path.to_owned(),
             mmap: Arc::new(open_read_mmap(
-                file_path.as_ref(),
+                &Self::index_file_path(path),
                 AdviceSetting::Global,
                 false,
             )?),
             file_header,
+            max_weights,
             _phantom: PhantomData,
         })
     }
@@ -352,10 +441,15 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
             false,
         )?;
 
+        // Earlier versions of the index did not store max weights, compute them on load
+        let max_weights = PostingMaxWeights::open(path.as_ref()).ok();
+        let has_max_weights = max_weights.is_some();
+
         let mut index = Self {
             path: path.as_ref().to_owned(),
             mmap: Arc::new(mmap),
             file_header,
+            max_weights: max_weights.unwrap_or_default(),
             _phantom: PhantomData,
         };
 
@@ -367,9 +461,25 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
             atomic_save_json(&config_file_path, &index.file_header)?;
         }
 
+        if !has_max_weights {
+            index.max_weights = index.calculate_max_weights(&hw_counter);
+            index.max_weights.save(path.as_ref())?;
+        }
+
         Ok(index)
     }
 
+    fn calculate_max_weights(&self, hw_counter: &HardwareCounterCell) -> PostingMaxWeights {
+        let mut max_weights = PostingMaxWeights::default();
+        for id in 0..self.file_header.posting_count as DimId {
+            match self.get(id, hw_counter) {
+                Some(posting) => max_weights.push(&posting.iter()),
+                None => max_weights.push_empty(),
+            }
+        }
+        max_weights
+    }
+
     fn calculate_total_sparse_size(&self, hw_counter: &HardwareCounterCell) -> usize {
         (0..self.file_header.posting_count as DimId)
             .filter_map(|id| {
@@ -392,6 +502,94 @@ impl<W: Weight> InvertedIndexCompressedMmap<W> {
     }
 }
 
+/// Writes posting lists of the index file one by one.
+///
+/// Posting data is written right away, while posting headers are kept in memory, and written at
+/// the beginning of the file on [`PostingListsWriter::finish`].
+struct PostingListsWriter<W: Weight> {
+    buf: BufWriter<File>,
+    headers: Vec<PostingListFileHeader<W>>,
+    max_weights: PostingMaxWeights,
+    posting_count: usize,
+    offset: usize,
+}
+
+impl<W: Weight> PostingListsWriter<W> {
+    fn new(file_path: &Path, posting_count: usize) -> std::io::Result<Self> {
+        let total_posting_headers_size = posting_count * size_of::<PostingListFileHeader<W>>();
+        let file = create_and_ensure_length(file_path, total_posting_headers_size)?;
+
+        let mut buf = BufWriter::new(file);
+        buf.seek(SeekFrom::Start(total_posting_headers_size as u64))?;
+
+        Ok(Self {
+            buf,
+            headers: Vec::with_capacity(posting_count),
+            max_weights: PostingMaxWeights::default(),
+            posting_count,
+            offset: total_posting_headers_size,
+        })
+    }
+
+    fn write(&mut self, posting: &CompressedPostingListView<W>) -> std::io::Result<()> {
+        debug_assert!(self.headers.len() < self.posting_count);
+
+        let store_size = posting.store_size();
+        self.headers.push(PostingListFileHeader::<W> {
+            ids_start: self.offset as u64,
+            ids_len: store_size.id_data_bytes as u32,
+            chunks_count: store_size.chunks_count as u32,
+            remainders_max_weight: posting.remainders_max_weight(),
+            last_id: posting.last_id().map_or(0, |id| id + 1),
+            quantization_params: posting.multiplier(),
+        });
+        self.max_weights.push(&posting.iter());
+        self.offset += store_size.total;
+
+        let (id_data, chunks, remainders) = posting.parts();
+        self.buf.write_all(id_data)?;
+        // TODO Safety
+        #[expect(deprecated, reason = "legacy code")]
+        self.buf
+            .write_all(unsafe { transmute_to_u8_slice(chunks) })?;
+        // TODO Safety
+        #[expect(deprecated, reason = "legacy code")]
+        self.buf
+            .write_all(unsafe { transmute_to_u8_slice(remainders) })?;
+        Ok(())
+    }
+
+    /// Write posting headers, and flush the file.
+    ///
+    /// Returns the total size of posting lists in bytes, and max weights of the posting lists.
+    fn finish(self) -> std::io::Result<(usize, PostingMaxWeights)> {
+        debug_assert_eq!(self.headers.len(), self.posting_count);
+        let Self {
+            mut buf,
+            headers,
+            max_weights,
+            posting_count,
+            offset,
+        } = self;
+
+        // Save posting headers
+        buf.seek(SeekFrom::Start(0))?;
+        for posting_header in &headers {
+            // TODO Safety
+            #[expect(deprecated, reason = "legacy code")]
+            buf.write_all(unsafe { transmute_to_u8(posting_header) })?;
+        }
+
+        // Explicitly fsync file contents to ensure durability
+        buf.flush()?;
+        let file = buf.into_inner().map_err(|err| err.into_error())?;
+        file.sync_all()?;
+
+        let total_sparse_size = offset - posting_count * size_of::<PostingListFileHeader<W>>();
+        Ok((total_sparse_size, max_weights))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::Builder;
@@ -478,5 +676,82 @@ mod tests {
         assert!(inverted_index_mmap.get(6, &hw_counter).is_none());
         assert!(inverted_index_mmap.get(7, &hw_counter).is_none());
         assert!(inverted_index_mmap.get(100, &hw_counter).is_none());
+
+        // max weights are available without reading the posting lists
+        assert_eq!(
+            inverted_index_mmap.posting_max_weight(0),
+            Some(DimWeight::NEG_INFINITY),
+        );
+        assert!(inverted_index_mmap.posting_max_weight(1).unwrap() > 0.0);
+        assert_eq!(
+            inverted_index_mmap.posting_max_weight(4),
+            Some(DimWeight::NEG_INFINITY),
+        );
+        assert_eq!(inverted_index_mmap.posting_max_weight(6), None);
+
+        // indexes without stored max weights get them computed on load
+        let max_weights = inverted_index_mmap.max_weights.clone();
+        drop(inverted_index_mmap);
+        fs_err::remove_file(PostingMaxWeights::file_path(tmp_dir_path.path())).unwrap();
+        let inverted_index_mmap = InvertedIndexCompressedMmap::<W>::load(&tmp_dir_path).unwrap();
+        assert_eq!(inverted_index_mmap.max_weights, max_weights);
+        assert!(PostingMaxWeights::file_path(tmp_dir_path.path()).exists());
+    }
+
+    #[test]
+    fn test_compact() {
+        check_compact::<f32>();
+        check_compact::<QuantizedU8>();
+    }
+
+    fn check_compact<W: Weight>() {
+        let hw_counter = HardwareCounterCell::new();
+
+        let mut builder = InvertedIndexBuilder::new();
+        builder.add(1, [(1, 10.0), (2, 10.0), (3, 10.0), (5, 10.0)].into());
+        builder.add(2, [(1, 20.0), (2, 20.0), (3, 20.0), (5, 20.0)].into());
+        builder.add(3, [(1, 30.0), (2, 30.0), (3, 30.0)].into());
+        builder.add(4, [(1, 1.0), (2, 1.0)].into());
+        builder.add(5, [(1, 2.0)].into());
+        let inverted_index_ram = builder.build();
+
+        let tmp_dir_path = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        let mut index = InvertedIndexCompressedMmap::<W>::from_ram_index(
+            Cow::Borrowed(&inverted_index_ram),
+            &tmp_dir_path,
+        )
+        .unwrap();
+        let total_size = index.total_sparse_vectors_size();
+
+        // Nothing to remove, the index is left as is
+        assert_eq!(index.compact(|id| id > 100).unwrap(), 0);
+        assert_eq!(index.vector_count(), 5);
+
+        assert_eq!(index.compact(|id| id == 1 || id == 4).unwrap(), 6);
+
+        let check = |index: &InvertedIndexCompressedMmap<W>| {
+            assert_eq!(index.len(), 6);
+            assert_eq!(index.vector_count(), 3);
+            assert!(index.total_sparse_vectors_size() < total_size);
+
+            let ids = |dim_id| -> Vec<PointOffsetType> {
+                index
+                    .get(dim_id, &hw_counter)
+                    .unwrap()
+                    .iter()
+                    .into_std_iter()
+                    .map(|element| element.record_id)
+                    .collect()
+            };
+            assert_eq!(ids(1), vec![2, 3, 5]);
+            assert_eq!(ids(2), vec![2, 3]);
+            assert_eq!(ids(3), vec![2, 3]);
+            assert!(ids(4).is_empty());
+            assert_eq!(ids(5), vec![2]);
+        };
+        check(&index);
+
+        let index = InvertedIndexCompressedMmap::<W>::load(&tmp_dir_path).unwrap();
+        check(&index);
     }
 }
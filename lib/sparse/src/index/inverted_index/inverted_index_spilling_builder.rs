@@ -0,0 +1,360 @@
+use std::cmp::{Reverse, max};
+use std::collections::BinaryHeap;
+use std::io::{BufReader, BufWriter, Read as _, Write as _};
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use fs_err::File;
+use log::debug;
+use tempfile::TempDir;
+
+use crate::common::sparse_vector::RemappedSparseVector;
+use crate::common::types::{DimId, DimWeight};
+use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
+use crate::index::posting_list::PostingBuilder;
+use crate::index::posting_list_common::{PostingElement, PostingElementEx};
+
+/// Default amount of memory to buffer postings in, before spilling them to disk.
+pub const DEFAULT_SPILL_MEMORY_BUDGET: usize = 256 * 1024 * 1024;
+
+/// Single posting of a spilled run: dimension id, record id, and weight.
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    dim_id: DimId,
+    record_id: PointOffsetType,
+    weight: DimWeight,
+}
+
+impl Posting {
+    const SIZE: usize = size_of::<DimId>() + size_of::<PointOffsetType>() + size_of::<DimWeight>();
+
+    fn key(&self) -> (DimId, PointOffsetType) {
+        (self.dim_id, self.record_id)
+    }
+
+    fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.dim_id.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.record_id.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.weight.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let [d0, d1, d2, d3, r0, r1, r2, r3, w0, w1, w2, w3] = bytes;
+        Self {
+            dim_id: DimId::from_le_bytes([d0, d1, d2, d3]),
+            record_id: PointOffsetType::from_le_bytes([r0, r1, r2, r3]),
+            weight: DimWeight::from_le_bytes([w0, w1, w2, w3]),
+        }
+    }
+}
+
+/// Reader of a spilled run, sorted by dimension id and record id.
+struct RunReader {
+    reader: BufReader<File>,
+}
+
+impl RunReader {
+    fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(File::open(path)?),
+        })
+    }
+
+    fn read_next(&mut self) -> std::io::Result<Option<Posting>> {
+        let mut bytes = [0; Posting::SIZE];
+        match self.reader.read_exact(&mut bytes) {
+            Ok(()) => Ok(Some(Posting::from_bytes(bytes))),
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Builder for inverted indexes, that don't fit into memory while being built.
+///
+/// Postings are buffered in memory up to the memory budget. Once the budget is exceeded, buffered
+/// postings are sorted and spilled to disk as a run. Posting lists are then produced one by one
+/// by merging the runs, see [`InvertedIndexSpillingBuilder::for_each_posting`].
+pub struct InvertedIndexSpillingBuilder {
+    /// Directory for spilled runs, removed on drop
+    temp_dir: TempDir,
+    /// Max number of postings to buffer in memory
+    buffer_capacity: usize,
+    buffer: Vec<Posting>,
+    runs: Vec<PathBuf>,
+    posting_count: usize,
+    vector_count: usize,
+    total_sparse_size: usize,
+}
+
+impl InvertedIndexSpillingBuilder {
+    /// Create a builder, which spills postings into a temporary directory inside `dir`, once they
+    /// take more than `memory_budget` bytes.
+    pub fn new(dir: &Path, memory_budget: usize) -> std::io::Result<Self> {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("inverted_index_build")
+            .tempdir_in(dir)?;
+        let buffer_capacity = max(memory_budget / size_of::<Posting>(), 1);
+        Ok(Self {
+            temp_dir,
+            buffer_capacity,
+            buffer: Vec::new(),
+            runs: Vec::new(),
+            posting_count: 0,
+            vector_count: 0,
+            total_sparse_size: 0,
+        })
+    }
+
+    /// Add a vector to the inverted index builder
+    pub fn add(
+        &mut self,
+        id: PointOffsetType,
+        vector: RemappedSparseVector,
+    ) -> std::io::Result<()> {
+        let sparse_size = vector.len() * size_of::<PostingElementEx>();
+        for (dim_id, weight) in vector.indices.into_iter().zip(vector.values.into_iter()) {
+            self.posting_count = max(self.posting_count, dim_id as usize + 1);
+            self.buffer.push(Posting {
+                dim_id,
+                record_id: id,
+                weight,
+            });
+            if self.buffer.len() >= self.buffer_capacity {
+                self.spill()?;
+            }
+        }
+        self.vector_count += 1;
+        self.total_sparse_size = self.total_sparse_size.saturating_add(sparse_size);
+        Ok(())
+    }
+
+    /// Number of posting lists, including empty ones
+    pub fn posting_count(&self) -> usize {
+        self.posting_count
+    }
+
+    /// Number of added vectors
+    pub fn vector_count(&self) -> usize {
+        self.vector_count
+    }
+
+    /// Sort buffered postings, and write them to disk as a new run.
+    fn spill(&mut self) -> std::io::Result<()> {
+        self.buffer.sort_unstable_by_key(Posting::key);
+
+        let run_path = self
+            .temp_dir
+            .path()
+            .join(format!("run_{}.bin", self.runs.len()));
+        let mut writer = BufWriter::new(File::create(&run_path)?);
+        for posting in &self.buffer {
+            writer.write_all(&posting.to_bytes())?;
+        }
+        writer.flush()?;
+
+        debug!(
+            "spilled {} postings of inverted index to {run_path:?}",
+            self.buffer.len(),
+        );
+        self.buffer.clear();
+        self.runs.push(run_path);
+        Ok(())
+    }
+
+    /// Consume the builder, and call `f` for each posting list in the order of dimension ids.
+    ///
+    /// Dimensions without postings are passed as empty posting lists.
+    /// Only a single posting list is kept in memory at a time.
+    pub fn for_each_posting(
+        mut self,
+        mut f: impl FnMut(DimId, &[PostingElement]) -> std::io::Result<()>,
+    ) -> std::io::Result<()> {
+        let mut next_dim_id: DimId = 0;
+        let mut elements = Vec::new();
+        let mut emit = |dim_id: DimId, elements: &mut Vec<PostingElement>| -> std::io::Result<()> {
+            // Fill gaps with empty posting lists
+            while next_dim_id < dim_id {
+                f(next_dim_id, &[])?;
+                next_dim_id += 1;
+            }
+            f(dim_id, elements)?;
+            elements.clear();
+            next_dim_id += 1;
+            Ok(())
+        };
+
+        let mut current_dim_id = None;
+        let mut push = |posting: Posting| -> std::io::Result<()> {
+            if let Some(dim_id) = current_dim_id
+                && dim_id != posting.dim_id
+            {
+                emit(dim_id, &mut elements)?;
+            }
+            current_dim_id = Some(posting.dim_id);
+            elements.push(PostingElement {
+                record_id: posting.record_id,
+                weight: posting.weight,
+            });
+            Ok(())
+        };
+
+        if self.runs.is_empty() {
+            // Everything fits into memory
+            self.buffer.sort_unstable_by_key(Posting::key);
+            for posting in std::mem::take(&mut self.buffer) {
+                push(posting)?;
+            }
+        } else {
+            if !self.buffer.is_empty() {
+                self.spill()?;
+            }
+
+            let mut readers = self
+                .runs
+                .iter()
+                .map(|path| RunReader::open(path))
+                .collect::<std::io::Result<Vec<_>>>()?;
+
+            // K-way merge of the runs
+            let mut heads = BinaryHeap::with_capacity(readers.len());
+            let mut weights = vec![0.0; readers.len()];
+            for (run_idx, reader) in readers.iter_mut().enumerate() {
+                if let Some(posting) = reader.read_next()? {
+                    weights[run_idx] = posting.weight;
+                    heads.push(Reverse((posting.key(), run_idx)));
+                }
+            }
+            while let Some(Reverse(((dim_id, record_id), run_idx))) = heads.pop() {
+                push(Posting {
+                    dim_id,
+                    record_id,
+                    weight: weights[run_idx],
+                })?;
+                if let Some(posting) = readers[run_idx].read_next()? {
+                    weights[run_idx] = posting.weight;
+                    heads.push(Reverse((posting.key(), run_idx)));
+                }
+            }
+        }
+
+        if let Some(dim_id) = current_dim_id {
+            emit(dim_id, &mut elements)?;
+        }
+        // Trailing empty posting lists are not possible, as `posting_count` is defined by the
+        // max dimension id
+        debug_assert_eq!(next_dim_id as usize, self.posting_count);
+        Ok(())
+    }
+
+    /// Merge all postings into an [`InvertedIndexRam`].
+    pub fn build_ram(self) -> std::io::Result<InvertedIndexRam> {
+        let vector_count = self.vector_count;
+        let total_sparse_size = self.total_sparse_size;
+
+        let mut postings = Vec::with_capacity(self.posting_count);
+        self.for_each_posting(|_dim_id, elements| {
+            let mut posting_builder = PostingBuilder::new();
+            for element in elements {
+                posting_builder.add(element.record_id, element.weight);
+            }
+            postings.push(posting_builder.build());
+            Ok(())
+        })?;
+
+        Ok(InvertedIndexRam {
+            postings,
+            vector_count,
+            total_sparse_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::sparse_vector_fixture::random_sparse_vector;
+    use crate::common::types::{QuantizedU8, Weight};
+    use crate::index::inverted_index::InvertedIndex;
+    use crate::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+    use crate::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
+    use crate::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
+
+    fn random_vectors(count: u32) -> Vec<(PointOffsetType, RemappedSparseVector)> {
+        let mut rnd_gen = StdRng::seed_from_u64(42);
+        // Enough elements to put some of them into chunks
+        (0..count)
+            .map(|i| (i, random_sparse_vector(&mut rnd_gen, 50).into_remapped()))
+            .collect()
+    }
+
+    fn spilling_builder(
+        dir: &Path,
+        memory_budget: usize,
+        vectors: &[(PointOffsetType, RemappedSparseVector)],
+    ) -> InvertedIndexSpillingBuilder {
+        let mut builder = InvertedIndexSpillingBuilder::new(dir, memory_budget).unwrap();
+        for (id, vector) in vectors {
+            builder.add(*id, vector.clone()).unwrap();
+        }
+        builder
+    }
+
+    #[test]
+    fn test_spilled_matches_ram() {
+        let vectors = random_vectors(2048);
+        let expected = InvertedIndexBuilder::build_from_iterator(vectors.iter().cloned());
+
+        let tmp_dir = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        // Fits into memory, and spills into many runs
+        for (memory_budget, spilled) in [(DEFAULT_SPILL_MEMORY_BUDGET, false), (1024, true)] {
+            let builder = spilling_builder(tmp_dir.path(), memory_budget, &vectors);
+            assert_eq!(!builder.runs.is_empty(), spilled);
+            assert_eq!(builder.build_ram().unwrap(), expected);
+        }
+        // Spilled runs are removed
+        assert_eq!(fs_err::read_dir(tmp_dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_spilled_compressed_mmap() {
+        check_spilled_compressed_mmap::<f32>();
+        check_spilled_compressed_mmap::<half::f16>();
+        check_spilled_compressed_mmap::<u8>();
+        check_spilled_compressed_mmap::<QuantizedU8>();
+    }
+
+    fn check_spilled_compressed_mmap<W: Weight>() {
+        let vectors = random_vectors(2048);
+        let inverted_index_ram = InvertedIndexBuilder::build_from_iterator(vectors.iter().cloned());
+
+        let tmp_dir = Builder::new().prefix("test_index_dir").tempdir().unwrap();
+        let expected = InvertedIndexCompressedImmutableRam::<W>::from_ram_index(
+            Cow::Owned(inverted_index_ram),
+            tmp_dir.path(),
+        )
+        .unwrap();
+
+        let builder = spilling_builder(tmp_dir.path(), 1024, &vectors);
+        let inverted_index_mmap =
+            InvertedIndexCompressedMmap::<W>::from_spilling_builder(builder, tmp_dir.path())
+                .unwrap();
+        assert_eq!(inverted_index_mmap.vector_count(), expected.vector_count());
+        assert_eq!(
+            inverted_index_mmap.total_sparse_vectors_size(),
+            expected.total_sparse_vectors_size(),
+        );
+        drop(inverted_index_mmap);
+
+        let loaded = InvertedIndexCompressedImmutableRam::<W>::open(tmp_dir.path()).unwrap();
+        assert_eq!(loaded, expected);
+    }
+}
@@ -8,8 +8,9 @@ use common::types::PointOffsetType;
 
 use super::posting_list_common::PostingListIter;
 use crate::common::sparse_vector::RemappedSparseVector;
-use crate::common::types::DimOffset;
+use crate::common::types::{DimOffset, DimWeight};
 use crate::index::inverted_index::inverted_index_ram::InvertedIndexRam;
+use crate::index::inverted_index::inverted_index_spilling_builder::InvertedIndexSpillingBuilder;
 
 pub mod inverted_index_compressed_immutable_ram;
 pub mod inverted_index_compressed_mmap;
@@ -17,6 +18,8 @@ pub mod inverted_index_immutable_ram;
 pub mod inverted_index_mmap;
 pub mod inverted_index_ram;
 pub mod inverted_index_ram_builder;
+pub mod inverted_index_spilling_builder;
+pub mod posting_max_weights;
 
 pub const OLD_INDEX_FILE_NAME: &str = "inverted_index.data";
 pub const INDEX_FILE_NAME: &str = "inverted_index.dat";
@@ -75,6 +78,15 @@ pub trait InvertedIndex: Sized + Debug + 'static {
         path: P,
     ) -> std::io::Result<Self>;
 
+    /// Create inverted index from a spilling builder.
+    /// On-disk indexes may override it to avoid building the whole index in memory.
+    fn from_spilling_builder<P: AsRef<Path>>(
+        builder: InvertedIndexSpillingBuilder,
+        path: P,
+    ) -> std::io::Result<Self> {
+        Self::from_ram_index(Cow::Owned(builder.build_ram()?), path)
+    }
+
     /// Number of indexed vectors
     fn vector_count(&self) -> usize;
 
@@ -83,4 +95,26 @@ pub trait InvertedIndex: Sized + Debug + 'static {
 
     /// Get max existed index
     fn max_index(&self) -> Option<DimOffset>;
+
+    /// Upper bound of the weights in the posting list of dimension `id`, if it is kept in memory.
+    ///
+    /// Allows on-disk indexes to estimate the impact of a dimension without reading its posting
+    /// list. Returns [`DimWeight::NEG_INFINITY`] for an empty posting list, and `None` if the
+    /// bound is not known.
+    fn posting_max_weight(&self, id: DimOffset) -> Option<DimWeight> {
+        let _ = id;
+        None
+    }
+
+    /// Rewrite posting lists without the elements of points, for which `is_deleted` is true.
+    ///
+    /// Immutable indexes keep deleted points in their posting lists until the segment is rebuilt,
+    /// which makes searches traverse more elements over time.
+    ///
+    /// Returns the number of removed posting elements, always 0 for indexes which don't support
+    /// compaction.
+    fn compact(&mut self, is_deleted: impl Fn(PointOffsetType) -> bool) -> std::io::Result<usize> {
+        let _ = is_deleted;
+        Ok(0)
+    }
 }
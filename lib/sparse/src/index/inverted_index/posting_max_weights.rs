@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+use common::fs::{atomic_save_bin, read_bin};
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::{DimOffset, DimWeight};
+use crate::index::posting_list_common::PostingListIter;
+
+const POSTING_MAX_WEIGHTS_FILE_NAME: &str = "posting_max_weights.bin";
+
+/// Upper bound of the weights of each posting list of an on-disk index, always kept in RAM.
+///
+/// Allows the search to estimate the impact of a dimension without reading its posting list.
+/// Empty posting lists have a bound of [`DimWeight::NEG_INFINITY`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PostingMaxWeights {
+    max_weights: Vec<DimWeight>,
+}
+
+impl PostingMaxWeights {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(read_bin(&Self::file_path(path))?)
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        Ok(atomic_save_bin(&Self::file_path(path), self)?)
+    }
+
+    pub fn file_path(path: &Path) -> PathBuf {
+        path.join(POSTING_MAX_WEIGHTS_FILE_NAME)
+    }
+
+    /// Append the bound of the next posting list, positioned at its start
+    pub fn push(&mut self, posting: &impl PostingListIter) {
+        self.max_weights.push(
+            posting
+                .max_weight_to_end()
+                .unwrap_or(DimWeight::NEG_INFINITY),
+        );
+    }
+
+    /// Append the bound of an empty posting list
+    pub fn push_empty(&mut self) {
+        self.max_weights.push(DimWeight::NEG_INFINITY);
+    }
+
+    /// Upper bound of the weights of the posting list `id`, `None` if there is no such posting
+    pub fn get(&self, id: DimOffset) -> Option<DimWeight> {
+        self.max_weights.get(id as usize).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.max_weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max_weights.is_empty()
+    }
+}
@@ -4,7 +4,7 @@ use common::types::PointOffsetType;
 use ordered_float::OrderedFloat;
 
 use super::posting_list_common::{
-    DEFAULT_MAX_NEXT_WEIGHT, PostingElement, PostingElementEx, PostingListIter,
+    BlockMax, DEFAULT_MAX_NEXT_WEIGHT, PostingElement, PostingElementEx, PostingListIter,
 };
 use crate::common::types::DimWeight;
 
@@ -229,6 +229,16 @@ impl PostingListIter for PostingListIterator<'_> {
         true
     }
 
+    fn block_max(&self) -> Option<BlockMax> {
+        None
+    }
+
+    fn max_weight_to_end(&self) -> Option<DimWeight> {
+        self.elements
+            .get(self.current_index)
+            .map(|element| element.weight.max(element.max_next_weight))
+    }
+
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement> {
         self.elements.iter().cloned().map(PostingElement::from)
     }
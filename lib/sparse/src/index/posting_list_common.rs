@@ -45,6 +45,17 @@ impl PostingElementEx {
     }
 }
 
+/// Block-max metadata of the block of a posting list.
+///
+/// Allows to skip whole blocks, which can't contribute enough to the score, without reading them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockMax {
+    /// Max weight of the elements in the block.
+    pub max_weight: DimWeight,
+    /// Id of the first element of the next block, `None` for the last block.
+    pub next_block_id: Option<PointOffsetType>,
+}
+
 impl From<PostingElementEx> for PostingElement {
     fn from(element: PostingElementEx) -> PostingElement {
         PostingElement {
@@ -85,5 +96,13 @@ pub trait PostingListIter {
     /// Whether the max_next_weight is reliable.
     fn reliable_max_next_weight() -> bool;
 
+    /// Block-max metadata of the block at the current position.
+    /// Returns `None` if the iterator is exhausted, or the posting list has no block-max metadata.
+    fn block_max(&self) -> Option<BlockMax>;
+
+    /// Upper bound of weights of the elements from the current position to the end.
+    /// Returns `None` if the iterator is exhausted.
+    fn max_weight_to_end(&self) -> Option<DimWeight>;
+
     fn into_std_iter(self) -> impl Iterator<Item = PostingElement>;
 }
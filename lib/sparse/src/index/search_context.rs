@@ -7,6 +7,7 @@ use common::top_k::TopK;
 use common::types::{PointOffsetType, ScoredPointOffset};
 
 use super::posting_list_common::PostingListIter;
+use crate::common::bm25::Bm25;
 use crate::common::scores_memory_pool::PooledScoresHandle;
 use crate::common::sparse_vector::{RemappedSparseVector, score_vectors};
 use crate::common::types::{DimId, DimWeight};
@@ -18,6 +19,28 @@ pub struct IndexedPostingListIterator<T: PostingListIter> {
     posting_list_iterator: T,
     query_index: DimId,
     query_weight: DimWeight,
+    /// Upper bound of the weights of the whole posting list, if kept in RAM by the index
+    max_weight: Option<DimWeight>,
+}
+
+/// BM25 normalization of the posting weights, see [`SearchContext::with_bm25`]
+#[derive(Clone, Copy)]
+struct DocNormalization<'a> {
+    bm25: Bm25,
+    /// Lengths of the documents, by record id
+    doc_lengths: &'a [f32],
+}
+
+impl DocNormalization<'_> {
+    #[inline]
+    fn weight(&self, id: PointOffsetType, weight: DimWeight) -> DimWeight {
+        let doc_length = self
+            .doc_lengths
+            .get(id as usize)
+            .copied()
+            .unwrap_or(self.bm25.avg_doc_length);
+        self.bm25.weight(weight, doc_length)
+    }
 }
 
 /// Making this larger makes the search faster but uses more (pooled) memory
@@ -33,6 +56,11 @@ pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
     max_record_id: PointOffsetType,         // max_record_id ids across all posting lists
     pooled: PooledScoresHandle<'b>,         // handle to pooled scores
     use_pruning: bool,
+    /// Posting lists which are not traversed in full, see [`SearchContext::with_max_posting_length`]
+    heavy_postings_iterators: Vec<IndexedPostingListIterator<T>>,
+    /// Upper bound of the total score contribution of `heavy_postings_iterators`
+    heavy_max_score: f32,
+    doc_normalization: Option<DocNormalization<'a>>,
     hardware_counter: &'a HardwareCounterCell,
 }
 
@@ -51,6 +79,11 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         let mut min_record_id = u32::MAX;
         // iterate over query indices
         for (query_weight_offset, id) in query.indices.iter().enumerate() {
+            let max_weight = inverted_index.posting_max_weight(*id);
+            // skip empty posting lists without reading them from disk
+            if max_weight == Some(DimWeight::NEG_INFINITY) {
+                continue;
+            }
             if let Some(mut it) = inverted_index.get(*id, hardware_counter)
                 && let (Some(first), Some(last_id)) = (it.peek(), it.last_id())
             {
@@ -70,14 +103,16 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                     posting_list_iterator: it,
                     query_index,
                     query_weight,
+                    max_weight,
                 });
             }
         }
         let top_results = TopK::new(top);
-        // Query vectors with negative values can NOT use the pruning mechanism which relies on the pre-computed `max_next_weight`.
+        // Query vectors with negative values can NOT use the pruning mechanism which relies on the pre-computed `max_next_weight`
+        // or on the block-max metadata.
         // The max contribution per posting list that we calculate is not made to compute the max value of two negative numbers.
         // This is a limitation of the current pruning implementation.
-        let use_pruning = T::reliable_max_next_weight() && query.values.iter().all(|v| *v >= 0.0);
+        let use_pruning = query.values.iter().all(|v| *v >= 0.0);
         let min_record_id = Some(min_record_id);
         SearchContext {
             postings_iterators,
@@ -89,10 +124,107 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             max_record_id,
             pooled,
             use_pruning,
+            heavy_postings_iterators: Vec::new(),
+            heavy_max_score: 0.0,
+            doc_normalization: None,
             hardware_counter,
         }
     }
 
+    /// Apply BM25 term frequency saturation and document length normalization to the weights
+    /// of the posting lists.
+    ///
+    /// `doc_lengths` are the lengths of the indexed documents by record id.
+    pub fn with_bm25(mut self, bm25: Bm25, doc_lengths: &'a [f32]) -> Self {
+        self.doc_normalization = Some(DocNormalization { bm25, doc_lengths });
+        self.heavy_max_score = self.heavy_max_score();
+        self
+    }
+
+    /// Traverse in full only posting lists, which are not longer than `max_posting_length`.
+    ///
+    /// Longer posting lists are only used to complete the scores of candidates found in the
+    /// shorter ones. A candidate is completed only if it can make it to the top results with the
+    /// upper bound of the missing score. Points present only in the longer posting lists are not
+    /// found. The shortest posting list is always traversed in full.
+    pub fn with_max_posting_length(mut self, max_posting_length: Option<usize>) -> Self {
+        let Some(max_posting_length) = max_posting_length else {
+            return self;
+        };
+        let Some(shortest_len) = self
+            .postings_iterators
+            .iter()
+            .map(|posting| posting.posting_list_iterator.len_to_end())
+            .min()
+        else {
+            return self;
+        };
+        let max_posting_length = max(max_posting_length, shortest_len);
+
+        let (postings_iterators, heavy_postings_iterators): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.postings_iterators)
+                .into_iter()
+                .partition(|posting| {
+                    posting.posting_list_iterator.len_to_end() <= max_posting_length
+                });
+        self.postings_iterators = postings_iterators;
+        self.heavy_postings_iterators = heavy_postings_iterators;
+        self.heavy_max_score = self.heavy_max_score();
+
+        // Only the traversed posting lists define the range of batches
+        self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
+        self.max_record_id = self
+            .postings_iterators
+            .iter()
+            .filter_map(|posting| posting.posting_list_iterator.last_id())
+            .max()
+            .unwrap_or(0);
+        self
+    }
+
+    /// Upper bound of the total score contribution of `heavy_postings_iterators`
+    fn heavy_max_score(&self) -> f32 {
+        self.heavy_postings_iterators
+            .iter()
+            .map(|posting| {
+                match posting.posting_list_iterator.max_weight_to_end() {
+                    // Weights may be negative, so the bound is only known for positive query weights
+                    Some(max_weight) if posting.query_weight >= 0.0 => {
+                        Self::max_doc_weight(self.doc_normalization.as_ref(), max_weight)
+                            * posting.query_weight
+                    }
+                    Some(_) => f32::INFINITY,
+                    None => 0.0,
+                }
+            })
+            .sum()
+    }
+
+    /// Weight of the posting element of the record `id`, as used for scoring
+    #[inline]
+    fn doc_weight(
+        doc_normalization: Option<&DocNormalization>,
+        id: PointOffsetType,
+        weight: DimWeight,
+    ) -> DimWeight {
+        match doc_normalization {
+            Some(doc_normalization) => doc_normalization.weight(id, weight),
+            None => weight,
+        }
+    }
+
+    /// Upper bound of [`Self::doc_weight`] for the posting elements with weights up to `weight`
+    #[inline]
+    fn max_doc_weight(
+        doc_normalization: Option<&DocNormalization>,
+        weight: DimWeight,
+    ) -> DimWeight {
+        match doc_normalization {
+            Some(doc_normalization) => doc_normalization.bm25.max_weight(weight),
+            None => weight,
+        }
+    }
+
     const DEFAULT_SCORE: f32 = 0.0;
 
     /// Plain search against the given ids without any pruning
@@ -102,6 +234,7 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         sorted_ids.sort_unstable();
 
         let cpu_counter = self.hardware_counter.cpu_counter();
+        let doc_normalization = self.doc_normalization;
 
         let mut indices = Vec::with_capacity(self.query.indices.len());
         let mut values = Vec::with_capacity(self.query.values.len());
@@ -114,14 +247,22 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             indices.clear();
             values.clear();
             // collect indices and values for the current record id from the query's posting lists *only*
-            for posting_iterator in self.postings_iterators.iter_mut() {
+            for posting_iterator in self
+                .postings_iterators
+                .iter_mut()
+                .chain(self.heavy_postings_iterators.iter_mut())
+            {
                 // rely on underlying binary search as the posting lists are sorted by record id
                 match posting_iterator.posting_list_iterator.skip_to(id) {
                     None => {} // no match for posting list
                     Some(element) => {
                         // match for posting list
                         indices.push(posting_iterator.query_index);
-                        values.push(element.weight);
+                        values.push(Self::doc_weight(
+                            doc_normalization.as_ref(),
+                            id,
+                            element.weight,
+                        ));
                     }
                 }
             }
@@ -160,6 +301,7 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         let batch_len = batch_last_id - batch_start_id + 1;
         self.pooled.scores.clear(); // keep underlying allocated memory
         self.pooled.scores.resize(batch_len as usize, 0.0);
+        let doc_normalization = self.doc_normalization;
 
         for posting in self.postings_iterators.iter_mut() {
             posting.posting_list_iterator.for_each_till_id(
@@ -167,7 +309,8 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 self.pooled.scores.as_mut_slice(),
                 #[inline(always)]
                 |scores, id, weight| {
-                    let element_score = weight * posting.query_weight;
+                    let element_score = Self::doc_weight(doc_normalization.as_ref(), id, weight)
+                        * posting.query_weight;
                     let local_id = (id - batch_start_id) as usize;
                     // SAFETY: `id` is within `batch_start_id..=batch_last_id`
                     // Thus, `local_id` is within `0..batch_len`.
@@ -178,12 +321,21 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
 
         for (local_index, &score) in self.pooled.scores.iter().enumerate() {
             // publish only the non-zero scores above the current min to beat
-            if score != 0.0 && score > self.top_results.threshold() {
+            if score != 0.0 && score + self.heavy_max_score > self.top_results.threshold() {
                 let real_id = batch_start_id + local_index as PointOffsetType;
                 // do not score if filter condition is not satisfied
                 if !filter_condition(real_id) {
                     continue;
                 }
+                let score = score
+                    + Self::heavy_score(
+                        &mut self.heavy_postings_iterators,
+                        doc_normalization.as_ref(),
+                        real_id,
+                    );
+                if score <= self.top_results.threshold() {
+                    continue;
+                }
                 let score_point_offset = ScoredPointOffset {
                     score,
                     idx: real_id,
@@ -197,6 +349,10 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
     fn process_last_posting_list<F: Fn(PointOffsetType) -> bool>(&mut self, filter_condition: &F) {
         debug_assert_eq!(self.postings_iterators.len(), 1);
         let posting = &mut self.postings_iterators[0];
+        let heavy_postings_iterators = &mut self.heavy_postings_iterators;
+        let heavy_max_score = self.heavy_max_score;
+        let doc_normalization = self.doc_normalization;
+        let top_results = &mut self.top_results;
         posting.posting_list_iterator.for_each_till_id(
             PointOffsetType::MAX,
             &mut (),
@@ -205,12 +361,37 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 if !filter_condition(id) {
                     return;
                 }
-                let score = weight * posting.query_weight;
-                self.top_results.push(ScoredPointOffset { score, idx: id });
+                let mut score =
+                    Self::doc_weight(doc_normalization.as_ref(), id, weight) * posting.query_weight;
+                if !heavy_postings_iterators.is_empty() {
+                    if score + heavy_max_score <= top_results.threshold() {
+                        return;
+                    }
+                    score +=
+                        Self::heavy_score(heavy_postings_iterators, doc_normalization.as_ref(), id);
+                }
+                top_results.push(ScoredPointOffset { score, idx: id });
             },
         );
     }
 
+    /// Score contribution of the posting lists, which are not traversed in full
+    ///
+    /// Must be called with increasing ids, as the posting lists are only advanced forward.
+    fn heavy_score(
+        heavy_postings_iterators: &mut [IndexedPostingListIterator<T>],
+        doc_normalization: Option<&DocNormalization>,
+        id: PointOffsetType,
+    ) -> f32 {
+        heavy_postings_iterators
+            .iter_mut()
+            .filter_map(|posting| {
+                let element = posting.posting_list_iterator.skip_to(id)?;
+                Some(Self::doc_weight(doc_normalization, id, element.weight) * posting.query_weight)
+            })
+            .sum()
+    }
+
     /// Returns the next min record id from all posting list iterators
     ///
     /// returns None if all posting list iterators are exhausted
@@ -338,6 +519,10 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 } else {
                     best_min_score = new_min_score;
                 }
+                // stop traversing posting lists, which can't bring new points to the top results
+                if self.demote_low_impact_posting_lists(new_min_score) {
+                    self.min_record_id = Self::next_min_id(&mut self.postings_iterators);
+                }
                 // make sure the first posting list is the longest for pruning
                 self.promote_longest_posting_lists_to_the_front();
 
@@ -354,6 +539,61 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         queue.into_vec()
     }
 
+    /// Stop traversing the posting lists with the lowest impact, like in the MaxScore algorithm.
+    ///
+    /// Posting lists are ordered by the upper bound of their score contribution, known from the
+    /// max weights kept in RAM by the index. The lowest ones, which together can't reach
+    /// `min_score`, can't bring a point to the top results on their own. They are only used to
+    /// complete the scores of the points found in the others, see
+    /// [`SearchContext::with_max_posting_length`], but unlike there no results are lost.
+    ///
+    /// Returns true if any posting list was demoted
+    fn demote_low_impact_posting_lists(&mut self, min_score: f32) -> bool {
+        let doc_normalization = self.doc_normalization;
+        let max_score = |posting: &IndexedPostingListIterator<T>| {
+            posting.max_weight.map(|max_weight| {
+                Self::max_doc_weight(doc_normalization.as_ref(), max_weight) * posting.query_weight
+            })
+        };
+
+        // lowest impact first, posting lists with unknown bound can't be demoted
+        self.postings_iterators.sort_by(|a, b| {
+            let a = max_score(a).unwrap_or(f32::INFINITY);
+            let b = max_score(b).unwrap_or(f32::INFINITY);
+            a.total_cmp(&b)
+        });
+
+        let mut heavy_max_score = self.heavy_max_score;
+        let mut demoted = 0;
+        // keep at least one posting list traversed
+        let demotable = self.postings_iterators.len().saturating_sub(1);
+        for posting in &self.postings_iterators[..demotable] {
+            let Some(score) = max_score(posting) else {
+                break;
+            };
+            if heavy_max_score + score > min_score {
+                break;
+            }
+            heavy_max_score += score;
+            demoted += 1;
+        }
+        if demoted == 0 {
+            return false;
+        }
+
+        self.heavy_postings_iterators
+            .extend(self.postings_iterators.drain(..demoted));
+        self.heavy_max_score = heavy_max_score;
+        // only the traversed posting lists define the range of batches
+        self.max_record_id = self
+            .postings_iterators
+            .iter()
+            .filter_map(|posting| posting.posting_list_iterator.last_id())
+            .max()
+            .unwrap_or(0);
+        true
+    }
+
     /// Prune posting lists that cannot possibly contribute to the top results
     /// Assumes longest posting list is at the head of the posting list iterators
     /// Returns true if the longest posting list was pruned
@@ -361,6 +601,9 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         if self.postings_iterators.is_empty() {
             return false;
         }
+        if !T::reliable_max_next_weight() {
+            return self.prune_longest_posting_list_blocks(min_score);
+        }
         // peek first element of longest posting list
         let (longest_posting_iterator, rest_iterators) = self.postings_iterators.split_at_mut(1);
         let longest_posting_iterator = &mut longest_posting_iterator[0];
@@ -383,9 +626,13 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                             // check against the max possible score using the `max_next_weight`
                             // we can under prune as we should actually check the best score up to `next_min_id` - 1 only
                             // instead of the max possible score but it is not possible to know the best score up to `next_min_id` - 1
-                            let max_weight_from_list = element.weight.max(element.max_next_weight);
-                            let max_score_contribution =
-                                max_weight_from_list * longest_posting_iterator.query_weight;
+                            let max_weight_from_list = Self::max_doc_weight(
+                                self.doc_normalization.as_ref(),
+                                element.weight.max(element.max_next_weight),
+                            );
+                            let max_score_contribution = max_weight_from_list
+                                * longest_posting_iterator.query_weight
+                                + self.heavy_max_score;
                             if max_score_contribution <= min_score {
                                 // prune to next_min_id
                                 let longest_posting_iterator =
@@ -404,9 +651,13 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 None => {
                     // the current posting list is the only one left, we can potentially skip it to the end
                     // check against the max possible score using the `max_next_weight`
-                    let max_weight_from_list = element.weight.max(element.max_next_weight);
-                    let max_score_contribution =
-                        max_weight_from_list * longest_posting_iterator.query_weight;
+                    let max_weight_from_list = Self::max_doc_weight(
+                        self.doc_normalization.as_ref(),
+                        element.weight.max(element.max_next_weight),
+                    );
+                    let max_score_contribution = max_weight_from_list
+                        * longest_posting_iterator.query_weight
+                        + self.heavy_max_score;
                     if max_score_contribution <= min_score {
                         // prune to the end!
                         let longest_posting_iterator = &mut self.postings_iterators[0];
@@ -419,4 +670,51 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         // no pruning took place
         false
     }
+
+    /// Prune the longest posting list block by block, using its block-max metadata.
+    ///
+    /// Skips the blocks of the longest posting list, which can't contribute enough to the score,
+    /// up to the next min id in the other posting lists. Elements before that id are present only
+    /// in the longest posting list, so their score is bounded by the max weight of their block.
+    /// Returns true if the longest posting list was pruned
+    fn prune_longest_posting_list_blocks(&mut self, min_score: f32) -> bool {
+        let (longest_posting_iterator, rest_iterators) = self.postings_iterators.split_at_mut(1);
+        let longest_posting_iterator = &mut longest_posting_iterator[0];
+        let next_min_id_in_others = Self::next_min_id(rest_iterators);
+
+        let query_weight = longest_posting_iterator.query_weight;
+        let doc_normalization = self.doc_normalization;
+        let iterator = &mut longest_posting_iterator.posting_list_iterator;
+        let position_before_pruning = iterator.current_index();
+        while let Some(block_max) = iterator.block_max() {
+            let max_score_contribution =
+                Self::max_doc_weight(doc_normalization.as_ref(), block_max.max_weight)
+                    * query_weight
+                    + self.heavy_max_score;
+            if max_score_contribution > min_score {
+                break;
+            }
+            match (block_max.next_block_id, next_min_id_in_others) {
+                (None, None) => {
+                    // the last block, and no other posting list left, prune to the end!
+                    iterator.skip_to_end();
+                    break;
+                }
+                (Some(next_block_id), Some(next_min_id)) if next_block_id <= next_min_id => {
+                    // the whole block is before the other posting lists, skip it
+                    iterator.skip_to(next_block_id);
+                }
+                (_, Some(next_min_id)) => {
+                    // the other posting lists start within the block, prune up to them
+                    iterator.skip_to(next_min_id);
+                    break;
+                }
+                (Some(next_block_id), None) => {
+                    iterator.skip_to(next_block_id);
+                }
+            }
+        }
+        // check if pruning took place
+        position_before_pruning != iterator.current_index()
+    }
 }
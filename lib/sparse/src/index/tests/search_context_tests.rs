@@ -12,6 +12,7 @@ mod tests {
     use rand::Rng;
     use tempfile::TempDir;
 
+    use crate::common::bm25::Bm25;
     use crate::common::scores_memory_pool::{PooledScoresHandle, ScoresMemoryPool};
     use crate::common::sparse_vector::{RemappedSparseVector, SparseVector};
     use crate::common::sparse_vector_fixture::random_sparse_vector;
@@ -486,6 +487,278 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pruning_blocks_test<I: InvertedIndex>() {
+        if I::Iter::reliable_max_next_weight() {
+            return;
+        }
+
+        // 3 full blocks in the first dimension, only the middle one has heavy weights
+        let index = TestIndex::<I>::from_ram({
+            let mut builder = InvertedIndexBuilder::new();
+            for i in 1..=384 {
+                let weight = if (129..=256).contains(&i) {
+                    50.0 + (i % 50) as f32
+                } else {
+                    1.0
+                };
+                if i >= 380 {
+                    builder.add(i * 100, [(1, weight), (2, 10.0)].into());
+                } else {
+                    builder.add(i * 100, [(1, weight)].into());
+                }
+            }
+            builder.build()
+        });
+        let query = RemappedSparseVector {
+            indices: vec![1, 2],
+            values: vec![1.0, 1.0],
+        };
+
+        let is_stopped = AtomicBool::new(false);
+        let hardware_counter = HardwareCounterCell::new();
+        let mut search_context = SearchContext::new(
+            query.clone(),
+            3,
+            &index.index,
+            get_pooled_scores(),
+            &is_stopped,
+            &hardware_counter,
+        );
+        assert_eq!(search_context.posting_list_len(0), 384);
+        // the first block is skipped, the heavy block can't be pruned
+        assert!(search_context.prune_longest_posting_list(30.0));
+        assert_eq!(search_context.posting_list_len(0), 256);
+        assert!(!search_context.prune_longest_posting_list(30.0));
+
+        // pruned search finds the same scores as the exhaustive one
+        let mut search_context = SearchContext::new(
+            query.clone(),
+            3,
+            &index.index,
+            get_pooled_scores(),
+            &is_stopped,
+            &hardware_counter,
+        );
+        let scores = search_context.search(&match_all);
+        let mut search_context = SearchContext::new(
+            query,
+            3,
+            &index.index,
+            get_pooled_scores(),
+            &is_stopped,
+            &hardware_counter,
+        );
+        let all_ids: Vec<_> = (1..=384).map(|i| i * 100).collect();
+        let expected = search_context.plain_search(&all_ids);
+        assert_eq!(
+            round_scores::<I>(scores)
+                .iter()
+                .map(|p| p.score)
+                .collect::<Vec<_>>(),
+            round_scores::<I>(expected)
+                .iter()
+                .map(|p| p.score)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn search_with_max_posting_length_test<I: InvertedIndex>() {
+        let index = TestIndex::<I>::from_ram({
+            let mut builder = InvertedIndexBuilder::new();
+            for id in 1..=100 {
+                let weight = if id == 50 { 10.0 } else { 1.0 };
+                match id {
+                    10 => builder.add(id, [(1, weight), (2, 5.0)].into()),
+                    20 => builder.add(id, [(1, weight), (2, 6.0)].into()),
+                    30 => builder.add(id, [(1, weight), (2, 7.0)].into()),
+                    _ => builder.add(id, [(1, weight)].into()),
+                }
+            }
+            builder.build()
+        });
+
+        let is_stopped = AtomicBool::new(false);
+        let hardware_counter = HardwareCounterCell::new();
+        let search = |max_posting_length| {
+            let mut search_context = SearchContext::new(
+                RemappedSparseVector {
+                    indices: vec![1, 2],
+                    values: vec![1.0, 1.0],
+                },
+                3,
+                &index.index,
+                get_pooled_scores(),
+                &is_stopped,
+                &hardware_counter,
+            )
+            .with_max_posting_length(max_posting_length);
+            round_scores::<I>(search_context.search(&match_all))
+        };
+
+        let full = vec![
+            ScoredPointOffset {
+                score: 10.0,
+                idx: 50,
+            },
+            ScoredPointOffset {
+                score: 8.0,
+                idx: 30,
+            },
+            ScoredPointOffset {
+                score: 7.0,
+                idx: 20,
+            },
+        ];
+        assert_eq!(search(None), full);
+        assert_eq!(search(Some(100)), full);
+
+        // The long posting list only completes scores of the points found in the short one
+        assert_eq!(
+            search(Some(3)),
+            vec![
+                ScoredPointOffset {
+                    score: 8.0,
+                    idx: 30
+                },
+                ScoredPointOffset {
+                    score: 7.0,
+                    idx: 20
+                },
+                ScoredPointOffset {
+                    score: 6.0,
+                    idx: 10
+                },
+            ]
+        );
+        // The shortest posting list is traversed regardless of the limit
+        assert_eq!(search(Some(1)), search(Some(3)));
+    }
+
+    #[test]
+    fn search_with_low_impact_posting_list_test<I: InvertedIndex>() {
+        // the first dimension is frequent but has low weights, the second one is rare
+        let index = TestIndex::<I>::from_ram({
+            let mut builder = InvertedIndexBuilder::new();
+            for id in 1..=30_000 {
+                if id % 1000 == 0 {
+                    builder.add(id, [(1, 1.0), (2, 10.0 + (id / 1000) as f32)].into());
+                } else {
+                    builder.add(id, [(1, 1.0)].into());
+                }
+            }
+            builder.build()
+        });
+        let query = RemappedSparseVector {
+            indices: vec![1, 2],
+            values: vec![1.0, 1.0],
+        };
+
+        let is_stopped = AtomicBool::new(false);
+        let hardware_counter = HardwareCounterCell::new();
+        let mut search_context = SearchContext::new(
+            query.clone(),
+            3,
+            &index.index,
+            get_pooled_scores(),
+            &is_stopped,
+            &hardware_counter,
+        );
+        // once the first dimension can't bring points to the top on its own, it only completes
+        // the scores of the points found in the second one
+        let scores = round_scores::<I>(search_context.search(&match_all));
+        assert_eq!(
+            scores,
+            vec![
+                ScoredPointOffset {
+                    score: 41.0,
+                    idx: 30_000,
+                },
+                ScoredPointOffset {
+                    score: 40.0,
+                    idx: 29_000,
+                },
+                ScoredPointOffset {
+                    score: 39.0,
+                    idx: 28_000,
+                },
+            ],
+        );
+
+        let mut search_context = SearchContext::new(
+            query,
+            3,
+            &index.index,
+            get_pooled_scores(),
+            &is_stopped,
+            &hardware_counter,
+        );
+        let all_ids: Vec<_> = (1..=30_000).collect();
+        assert_eq!(
+            scores,
+            round_scores::<I>(search_context.plain_search(&all_ids)),
+        );
+    }
+
+    #[test]
+    fn search_with_bm25_test<I: InvertedIndex>() {
+        let index = TestIndex::<I>::from_ram({
+            let mut builder = InvertedIndexBuilder::new();
+            builder.add(1, [(1, 2.0), (2, 1.0)].into());
+            builder.add(2, [(1, 3.0), (2, 1.0), (3, 7.0)].into());
+            builder.add(3, [(1, 1.0)].into());
+            builder.add(4, [(2, 3.0), (3, 1.0)].into());
+            builder.build()
+        });
+        let doc_lengths = [0.0, 3.0, 11.0, 1.0, 4.0];
+        let bm25 = Bm25 {
+            k1: 1.2,
+            b: 0.75,
+            avg_doc_length: 4.75,
+        };
+
+        let is_stopped = AtomicBool::new(false);
+        let hardware_counter = HardwareCounterCell::new();
+        let context = |top, bm25: Option<Bm25>| {
+            let search_context = SearchContext::new(
+                RemappedSparseVector {
+                    indices: vec![1, 2],
+                    values: vec![1.0, 1.0],
+                },
+                top,
+                &index.index,
+                get_pooled_scores(),
+                &is_stopped,
+                &hardware_counter,
+            );
+            match bm25 {
+                Some(bm25) => search_context.with_bm25(bm25, &doc_lengths),
+                None => search_context,
+            }
+        };
+        let ids = |scores: &[ScoredPointOffset]| scores.iter().map(|p| p.idx).collect::<Vec<_>>();
+
+        // The longest document wins on raw term frequencies, but not after normalization
+        assert_eq!(ids(&context(1, None).search(&match_all)), vec![2]);
+        assert_eq!(ids(&context(1, Some(bm25)).search(&match_all)), vec![1]);
+
+        let expected = [
+            (1, bm25.weight(2.0, 3.0) + bm25.weight(1.0, 3.0)),
+            (2, bm25.weight(3.0, 11.0) + bm25.weight(1.0, 11.0)),
+            (4, bm25.weight(3.0, 4.0)),
+            (3, bm25.weight(1.0, 1.0)),
+        ];
+        let results = context(4, Some(bm25)).search(&match_all);
+        let plain_results = context(4, Some(bm25)).plain_search(&[1, 2, 3, 4]);
+        for results in [results, plain_results] {
+            assert_eq!(ids(&results), expected.map(|(id, _)| id));
+            for (point, (_, score)) in results.iter().zip(expected) {
+                assert!((point.score - score).abs() < 0.05);
+            }
+        }
+    }
+
     /// Generates a random inverted index with `num_vectors` vectors
     #[allow(dead_code)]
     fn random_inverted_index<R: Rng + ?Sized>(
@@ -63,6 +63,12 @@ pub struct AuditConfig {
     /// Default: false
     #[serde(default)]
     pub trust_forwarded_headers: bool,
+
+    /// If true, only record operations which modify data or cluster state:
+    /// point and payload updates, collection and alias changes, snapshots, etc.
+    /// Default: false
+    #[serde(default)]
+    pub write_operations_only: bool,
 }
 
 fn default_audit_dir() -> PathBuf {
@@ -114,6 +120,9 @@ pub struct AuditEvent {
     /// Tracing ID extracted from request headers, if present.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tracing_id: Option<String>,
+    /// Whether the operation modifies data or cluster state.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write: bool,
     /// Whether the access check passed or was denied.
     pub result: AuditResult,
     /// Error message when the access check failed.
@@ -127,6 +136,7 @@ pub struct AuditEvent {
 
 struct AuditLogger {
     writer: Mutex<NonBlocking>,
+    write_operations_only: bool,
 }
 
 impl AuditLogger {
@@ -155,12 +165,17 @@ impl AuditLogger {
         Ok((
             Self {
                 writer: Mutex::new(non_blocking),
+                write_operations_only: config.write_operations_only,
             },
             guard,
         ))
     }
 
     fn write(&self, event: &AuditEvent) {
+        if self.write_operations_only && !event.write {
+            return;
+        }
+
         // Serialize to a buffer first so the entire event is sent as one
         // atomic message to the non-blocking writer (avoids interleaved
         // partial writes from concurrent callers).
@@ -293,6 +293,7 @@ fn event_field_matches(event: &AuditEvent, key: &str, expected: &str) -> Option<
         remote,
         collection,
         tracing_id,
+        write,
         result,
         error,
     } = event;
@@ -313,6 +314,7 @@ fn event_field_matches(event: &AuditEvent, key: &str, expected: &str) -> Option<
         "collection" => Some(collection.as_deref() == Some(expected)),
         "tracing_id" => Some(tracing_id.as_deref() == Some(expected)),
         "error" => Some(error.as_deref() == Some(expected)),
+        "write" => Some(expected.parse::<bool>().ok()? == *write),
         _ => None,
     }
 }
@@ -332,6 +334,7 @@ mod tests {
             remote: None,
             collection: None,
             tracing_id: None,
+            write: true,
             result: AuditResult::Ok,
             error: None,
         }
@@ -389,6 +392,17 @@ mod tests {
             matches_query_result(&event, &query2),
             MatchResult::NoMatch
         ));
+
+        let query3 = AuditLogQuery::new(
+            None,
+            None,
+            HashMap::from([("write".to_string(), "true".to_string())]),
+            None,
+        );
+        assert!(matches!(
+            matches_query_result(&event, &query3),
+            MatchResult::Match
+        ));
     }
 
     #[test]
@@ -0,0 +1,523 @@
+//! API keys, managed at runtime in addition to the static keys from the config file.
+//!
+//! Only SHA-256 hashes of the keys are persisted. The key itself is returned once, when it is
+//! created or rotated.
+//!
+//! Changes are applied through consensus, same as collection meta operations, so all peers
+//! accept the same keys. Each peer keeps a local copy of the keys, which is also included into
+//! consensus snapshots.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use common::fs::{atomic_save_json, read_json};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+use crate::rbac::Access;
+
+pub const API_KEYS_FILE: &str = "api_keys.json";
+
+/// Prefix of generated keys, to make them recognizable
+const API_KEY_PREFIX: &str = "qk_";
+
+/// Number of random bytes in generated keys
+const API_KEY_BYTES: usize = 32;
+
+/// Description of a managed API key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    pub id: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Access granted by the key, in the same format as the `access` claim of JWT
+    pub access: Access,
+    pub created_at: DateTime<Utc>,
+    /// The key is rejected after this time. Never expires, if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last time the key was used to authenticate a request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// Newly issued API key
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+    /// The API key. It is not stored on the server, and can't be retrieved again
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct CreateApiKey {
+    /// Human-readable name of the key
+    #[validate(length(min = 1, max = 256))]
+    pub name: Option<String>,
+    /// Access granted by the key, in the same format as the `access` claim of JWT
+    pub access: Access,
+    /// Number of seconds the key is valid for. Never expires, if not set
+    #[validate(range(min = 1))]
+    pub expires_in_sec: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Validate)]
+pub struct RotateApiKey {
+    /// Number of seconds the previous key remains valid after rotation.
+    /// The previous key is rejected immediately, if not set
+    pub grace_period_sec: Option<u64>,
+}
+
+/// Managed API key, as stored on every peer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredApiKey {
+    #[serde(flatten)]
+    info: ApiKeyInfo,
+    key_hash: String,
+    /// Key, replaced by rotation, which is still accepted during the grace period
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    previous_key: Option<PreviousApiKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PreviousApiKey {
+    key_hash: String,
+    valid_until: DateTime<Utc>,
+}
+
+impl StoredApiKey {
+    fn matches(&self, key_hash: &str, now: DateTime<Utc>) -> bool {
+        if self
+            .info
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= now)
+        {
+            return false;
+        }
+
+        self.key_hash == key_hash
+            || self
+                .previous_key
+                .as_ref()
+                .is_some_and(|previous| previous.key_hash == key_hash && now < previous.valid_until)
+    }
+}
+
+/// Change of managed API keys.
+///
+/// Keys, hashes and timestamps are generated on the peer which receives the request,
+/// so that every peer applies exactly the same change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyOperation {
+    Create(StoredApiKey),
+    Rotate {
+        id: Uuid,
+        key_hash: String,
+        /// Previous key is still accepted until this time. Rejected immediately, if not set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        previous_valid_until: Option<DateTime<Utc>>,
+    },
+    Revoke {
+        id: Uuid,
+    },
+}
+
+impl ApiKeyOperation {
+    /// Issue a new key.
+    ///
+    /// The key is only returned here, the operation contains just its hash.
+    pub fn create(request: CreateApiKey) -> Result<(Self, CreatedApiKey), StorageError> {
+        let CreateApiKey {
+            name,
+            access,
+            expires_in_sec,
+        } = request;
+
+        if let Some(Err(err)) = access.validate().into_iter().find(Result::is_err) {
+            return Err(StorageError::bad_input(format!("Invalid access: {err}")));
+        }
+
+        let now = Utc::now();
+        let info = ApiKeyInfo {
+            id: Uuid::new_v4(),
+            name,
+            access,
+            created_at: now,
+            expires_at: expires_in_sec.map(|sec| after_seconds(now, sec)),
+            last_used_at: None,
+        };
+
+        let key = generate_key();
+        let stored = StoredApiKey {
+            info: info.clone(),
+            key_hash: hash_key(&key),
+            previous_key: None,
+        };
+
+        Ok((Self::Create(stored), CreatedApiKey { info, key }))
+    }
+
+    /// Issue a new key for an existing key ID, keeping the access and expiration.
+    ///
+    /// Returns the operation and the new key.
+    pub fn rotate(id: Uuid, request: RotateApiKey) -> (Self, String) {
+        let RotateApiKey { grace_period_sec } = request;
+
+        let key = generate_key();
+        let operation = Self::Rotate {
+            id,
+            key_hash: hash_key(&key),
+            previous_valid_until: grace_period_sec.map(|sec| after_seconds(Utc::now(), sec)),
+        };
+
+        (operation, key)
+    }
+
+    pub fn key_id(&self) -> Uuid {
+        match self {
+            Self::Create(stored) => stored.info.id,
+            Self::Rotate { id, .. } | Self::Revoke { id } => *id,
+        }
+    }
+}
+
+// `Access` is not hashable, but keys are uniquely identified by their ID and hash
+impl Hash for ApiKeyOperation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Create(stored) => {
+                stored.info.id.hash(state);
+                stored.key_hash.hash(state);
+            }
+            Self::Rotate {
+                id,
+                key_hash,
+                previous_valid_until,
+            } => {
+                id.hash(state);
+                key_hash.hash(state);
+                previous_valid_until.hash(state);
+            }
+            Self::Revoke { id } => id.hash(state),
+        }
+    }
+}
+
+impl Eq for ApiKeyOperation {}
+
+/// Local copy of managed API keys.
+///
+/// Reads are served from memory, changes are durably saved.
+pub struct ApiKeyStore {
+    path: PathBuf,
+    keys: RwLock<Vec<StoredApiKey>>,
+    /// Last usage time by key ID.
+    /// Tracked in memory, to not write on every request, and persisted along with other changes.
+    /// It is local to the peer, and not replicated.
+    last_used: Mutex<HashMap<Uuid, DateTime<Utc>>>,
+}
+
+impl ApiKeyStore {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        let keys = if path.exists() {
+            read_json(path)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            keys: RwLock::new(keys),
+            last_used: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Find a valid key, and record its usage.
+    pub fn authenticate(&self, key: &str) -> Option<ApiKeyInfo> {
+        if !key.starts_with(API_KEY_PREFIX) {
+            return None;
+        }
+
+        let key_hash = hash_key(key);
+        let now = Utc::now();
+
+        let keys = self.keys.read();
+        let stored = keys.iter().find(|stored| stored.matches(&key_hash, now))?;
+        self.last_used.lock().insert(stored.info.id, now);
+
+        Some(ApiKeyInfo {
+            last_used_at: Some(now),
+            ..stored.info.clone()
+        })
+    }
+
+    pub fn list(&self) -> Vec<ApiKeyInfo> {
+        let keys = self.keys.read();
+        keys.iter().map(|stored| self.info(stored)).collect()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<ApiKeyInfo> {
+        let keys = self.keys.read();
+        keys.iter()
+            .find(|stored| stored.info.id == id)
+            .map(|stored| self.info(stored))
+    }
+
+    fn info(&self, stored: &StoredApiKey) -> ApiKeyInfo {
+        ApiKeyInfo {
+            last_used_at: self
+                .last_used
+                .lock()
+                .get(&stored.info.id)
+                .copied()
+                .or(stored.info.last_used_at),
+            ..stored.info.clone()
+        }
+    }
+
+    /// Apply a change, committed by consensus.
+    ///
+    /// Returns `false` if the key to revoke does not exist.
+    pub fn apply(&self, operation: ApiKeyOperation) -> Result<bool, StorageError> {
+        let mut keys = self.keys.write();
+
+        match operation {
+            ApiKeyOperation::Create(stored) => {
+                keys.retain(|existing| existing.info.id != stored.info.id);
+                keys.push(stored);
+            }
+            ApiKeyOperation::Rotate {
+                id,
+                key_hash,
+                previous_valid_until,
+            } => {
+                let stored = keys
+                    .iter_mut()
+                    .find(|stored| stored.info.id == id)
+                    .ok_or_else(|| StorageError::not_found(format!("API key {id} not found")))?;
+
+                let previous_hash = std::mem::replace(&mut stored.key_hash, key_hash);
+                stored.previous_key = previous_valid_until.map(|valid_until| PreviousApiKey {
+                    key_hash: previous_hash,
+                    valid_until,
+                });
+            }
+            ApiKeyOperation::Revoke { id } => {
+                let Some(position) = keys.iter().position(|stored| stored.info.id == id) else {
+                    return Ok(false);
+                };
+
+                keys.remove(position);
+                self.last_used.lock().remove(&id);
+            }
+        }
+
+        self.save(&mut keys)?;
+        Ok(true)
+    }
+
+    /// Keys to include into consensus snapshot
+    pub fn state(&self) -> Vec<StoredApiKey> {
+        self.keys.read().clone()
+    }
+
+    /// Replace all keys with the ones from consensus snapshot
+    pub fn apply_state(&self, state: Vec<StoredApiKey>) -> Result<(), StorageError> {
+        let mut keys = self.keys.write();
+        if *keys == state {
+            return Ok(());
+        }
+
+        *keys = state;
+        self.last_used
+            .lock()
+            .retain(|id, _| keys.iter().any(|stored| stored.info.id == *id));
+        self.save(&mut keys)
+    }
+
+    fn save(&self, keys: &mut [StoredApiKey]) -> Result<(), StorageError> {
+        let now = Utc::now();
+        let last_used = self.last_used.lock();
+
+        for stored in keys.iter_mut() {
+            if let Some(last_used_at) = last_used.get(&stored.info.id) {
+                stored.info.last_used_at = Some(*last_used_at);
+            }
+            if stored
+                .previous_key
+                .as_ref()
+                .is_some_and(|previous| previous.valid_until <= now)
+            {
+                stored.previous_key = None;
+            }
+        }
+
+        atomic_save_json(&self.path, &keys)?;
+        Ok(())
+    }
+}
+
+fn generate_key() -> String {
+    let bytes: [u8; API_KEY_BYTES] = rand::random();
+    let mut key = String::with_capacity(API_KEY_PREFIX.len() + API_KEY_BYTES * 2);
+    key.push_str(API_KEY_PREFIX);
+    for byte in bytes {
+        key.push_str(&format!("{byte:02x}"));
+    }
+    key
+}
+
+fn hash_key(key: &str) -> String {
+    let hash = Sha256::digest(key.as_bytes());
+    format!("{hash:x}")
+}
+
+fn after_seconds(time: DateTime<Utc>, sec: u64) -> DateTime<Utc> {
+    i64::try_from(sec)
+        .ok()
+        .and_then(TimeDelta::try_seconds)
+        .and_then(|delta| time.checked_add_signed(delta))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::{Builder, TempDir};
+
+    use super::*;
+    use crate::rbac::GlobalAccessMode;
+
+    fn create_request(expires_in_sec: Option<u64>) -> CreateApiKey {
+        CreateApiKey {
+            name: Some("test".to_string()),
+            access: Access::Global(GlobalAccessMode::Read),
+            expires_in_sec,
+        }
+    }
+
+    fn open_store() -> (TempDir, ApiKeyStore) {
+        let dir = Builder::new().prefix("api_keys").tempdir().unwrap();
+        let store = ApiKeyStore::open(&dir.path().join(API_KEYS_FILE)).unwrap();
+        (dir, store)
+    }
+
+    fn create(store: &ApiKeyStore, request: CreateApiKey) -> CreatedApiKey {
+        let (operation, created) = ApiKeyOperation::create(request).unwrap();
+        assert!(store.apply(operation).unwrap());
+        created
+    }
+
+    fn rotate(
+        store: &ApiKeyStore,
+        id: Uuid,
+        request: RotateApiKey,
+    ) -> Result<String, StorageError> {
+        let (operation, key) = ApiKeyOperation::rotate(id, request);
+        store.apply(operation)?;
+        Ok(key)
+    }
+
+    #[test]
+    fn test_create_authenticate_revoke() {
+        let dir = Builder::new().prefix("api_keys").tempdir().unwrap();
+        let path = dir.path().join(API_KEYS_FILE);
+        let store = ApiKeyStore::open(&path).unwrap();
+
+        let created = create(&store, create_request(None));
+        assert!(created.key.starts_with(API_KEY_PREFIX));
+
+        let info = store.authenticate(&created.key).unwrap();
+        assert_eq!(info.id, created.info.id);
+        assert_eq!(info.access, Access::Global(GlobalAccessMode::Read));
+        assert!(store.authenticate("qk_invalid").is_none());
+
+        // Keys are persisted, without the key itself
+        let reopened = ApiKeyStore::open(&path).unwrap();
+        assert!(reopened.authenticate(&created.key).is_some());
+        let persisted = fs_err::read_to_string(&path).unwrap();
+        assert!(!persisted.contains(&created.key));
+
+        let revoke = ApiKeyOperation::Revoke {
+            id: created.info.id,
+        };
+        assert!(store.apply(revoke.clone()).unwrap());
+        assert!(!store.apply(revoke).unwrap());
+        assert!(store.authenticate(&created.key).is_none());
+        assert!(store.list().is_empty());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let (_dir, store) = open_store();
+
+        let created = create(&store, create_request(None));
+
+        let rotated = rotate(
+            &store,
+            created.info.id,
+            RotateApiKey {
+                grace_period_sec: Some(60),
+            },
+        )
+        .unwrap();
+        assert_eq!(store.get(created.info.id).unwrap().id, created.info.id);
+        assert!(store.authenticate(&rotated).is_some());
+        assert!(store.authenticate(&created.key).is_some());
+
+        let rotated_again = rotate(&store, created.info.id, RotateApiKey::default()).unwrap();
+        assert!(store.authenticate(&rotated_again).is_some());
+        assert!(store.authenticate(&rotated).is_none());
+        assert!(store.authenticate(&created.key).is_none());
+
+        assert!(rotate(&store, Uuid::new_v4(), RotateApiKey::default()).is_err());
+    }
+
+    #[test]
+    fn test_expired_key() {
+        let (_dir, store) = open_store();
+
+        let created = create(&store, create_request(Some(1)));
+        store.keys.write()[0].info.expires_at = Some(Utc::now() - TimeDelta::seconds(1));
+
+        assert!(store.authenticate(&created.key).is_none());
+        assert_eq!(store.list().len(), 1);
+    }
+
+    #[test]
+    fn test_same_keys_on_all_peers() {
+        let (_dir_a, peer_a) = open_store();
+        let (_dir_b, peer_b) = open_store();
+
+        // Both peers apply the same committed operations
+        let (operation, created) = ApiKeyOperation::create(create_request(None)).unwrap();
+        peer_a.apply(operation.clone()).unwrap();
+        peer_b.apply(operation).unwrap();
+
+        let (operation, rotated) =
+            ApiKeyOperation::rotate(created.info.id, RotateApiKey::default());
+        peer_a.apply(operation.clone()).unwrap();
+        peer_b.apply(operation).unwrap();
+
+        for peer in [&peer_a, &peer_b] {
+            assert!(peer.authenticate(&rotated).is_some());
+            assert!(peer.authenticate(&created.key).is_none());
+        }
+
+        // New peer receives keys with consensus snapshot
+        let (_dir_c, peer_c) = open_store();
+        create(&peer_c, create_request(None));
+        peer_c.apply_state(peer_a.state()).unwrap();
+
+        assert_eq!(peer_c.list().len(), 1);
+        assert!(peer_c.authenticate(&rotated).is_some());
+    }
+}
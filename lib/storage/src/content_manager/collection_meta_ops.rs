@@ -25,6 +25,7 @@ use validator::Validate;
 // Re-export staging types when the feature is enabled
 #[cfg(feature = "staging")]
 pub use super::staging::TestSlowDown;
+use crate::content_manager::api_keys::ApiKeyOperation;
 use crate::content_manager::errors::{StorageError, StorageResult};
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 
@@ -255,6 +256,10 @@ pub struct UpdateCollection {
     /// To remove metadata, set it to an empty object.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Payload>,
+    /// If true - write operations on points of the collection are rejected, until it is set to false.
+    /// Can be used to freeze a collection for maintenance. If none - it is left unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -279,6 +284,7 @@ impl UpdateCollectionOperation {
                 sparse_vectors: None,
                 strict_mode_config: None,
                 metadata: None,
+                read_only: None,
             },
             shard_replica_changes: None,
         }
@@ -413,6 +419,8 @@ pub enum CollectionMetaOperations {
     DropShardKey(DropShardKey),
     CreatePayloadIndex(CreatePayloadIndex),
     DropPayloadIndex(DropPayloadIndex),
+    /// Create, rotate or revoke a managed API key
+    ChangeApiKeys(ApiKeyOperation),
     Nop {
         token: usize,
     }, // Empty operation
@@ -434,6 +442,8 @@ impl From<CollectionConfigInternal> for CreateCollection {
             strict_mode_config,
             uuid,
             metadata,
+            // Read-only mode is not a part of collection creation
+            read_only: _,
         } = value;
 
         let CollectionParams {
@@ -29,6 +29,7 @@ use tonic::transport::Uri;
 
 use super::CollectionContainer;
 use super::alias_mapping::AliasMapping;
+use super::api_keys::StoredApiKey;
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
 use crate::content_manager::consensus::consensus_wal::ConsensusOpWal;
@@ -64,6 +65,8 @@ pub struct SnapshotData {
 pub struct CollectionsSnapshot {
     pub collections: HashMap<CollectionId, collection_state::State>,
     pub aliases: AliasMapping,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<StoredApiKey>,
 }
 
 impl TryFrom<&[u8]> for SnapshotData {
@@ -757,6 +760,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
     ///
     /// * `operation` - operation to propose
     /// * `wait_timeout` - How long do we need to wait for the confirmation
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn propose_consensus_op_with_await(
         &self,
         operation: ConsensusOperations,
@@ -811,6 +815,17 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().peer_address_by_id()
     }
 
+    /// Address of this peer, as it is registered in consensus
+    pub fn this_peer_address(&self) -> Option<Uri> {
+        let persistent = self.persistent.read();
+        let address = persistent
+            .peer_address_by_id
+            .read()
+            .get(&persistent.this_peer_id)
+            .cloned();
+        address
+    }
+
     pub fn peer_count(&self) -> usize {
         self.persistent.read().peer_address_by_id.read().len()
     }
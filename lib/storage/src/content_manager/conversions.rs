@@ -183,6 +183,7 @@ impl TryFrom<grpc::UpdateCollection> for CollectionMetaOperations {
             sparse_vectors_config,
             strict_mode_config,
             metadata,
+            read_only,
         } = value;
         Ok(Self::UpdateCollection(UpdateCollectionOperation::new(
             collection_name,
@@ -208,6 +209,7 @@ impl TryFrom<grpc::UpdateCollection> for CollectionMetaOperations {
                 } else {
                     Some(json::proto_to_payloads(metadata)?)
                 },
+                read_only,
             },
         )))
     }
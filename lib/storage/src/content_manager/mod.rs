@@ -5,6 +5,7 @@ use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
 
 pub mod alias_mapping;
+pub mod api_keys;
 pub mod collection_meta_ops;
 pub mod collection_verification;
 mod collections_ops;
@@ -12,6 +13,7 @@ pub mod consensus;
 pub mod consensus_manager;
 pub mod conversions;
 pub mod errors;
+pub mod payload_encryption;
 pub mod shard_distribution;
 pub mod snapshots;
 #[cfg(feature = "staging")]
@@ -138,6 +140,7 @@ pub mod consensus_ops {
                     sparse_vectors: None,
                     strict_mode_config: None,
                     metadata: None,
+                    read_only: None,
                 },
             );
             operation
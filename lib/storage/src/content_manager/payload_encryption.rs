@@ -0,0 +1,725 @@
+//! Encryption of selected payload fields at rest.
+//!
+//! Values are encrypted deterministically: equal values of the same field produce equal
+//! ciphertexts. This keeps exact matching possible, so encrypted fields can be filtered with
+//! `match` conditions and indexed with a keyword index. Other conditions, which need the original
+//! value, are rejected for encrypted fields.
+//!
+//! Every element of an array is encrypted separately, so array values can be matched by element.
+//! `null` values are not encrypted, to keep `is_null` and `is_empty` conditions working.
+
+use std::collections::{HashMap, HashSet};
+
+use api::rest::SearchRequestInternal;
+use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
+use collection::grouping::group_by::{GroupRequest, SourceRequest};
+use collection::operations::CollectionUpdateOperations;
+use collection::operations::point_ops::PointInsertOperationsInternal;
+use collection::operations::types::{
+    CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal, PointGroup,
+    RecommendRequestInternal,
+};
+use collection::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest,
+};
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::hmac;
+use segment::data_types::facets::{FacetParams, FacetValue, FacetValueHit};
+use segment::data_types::groups::GroupId;
+use segment::data_types::order_by::OrderByInterface;
+use segment::json_path::{JsonPath, JsonPathItem};
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, Match, MatchAny, MatchExcept, MatchValue,
+    Payload, PayloadSchemaType, ScoredPoint, ValueVariants,
+};
+use serde_json::Value;
+use shard::operations::FieldIndexOperations;
+use shard::operations::payload_ops::PayloadOps;
+use shard::operations::point_ops::PointOperations;
+use shard::operations::vector_ops::VectorOperations;
+use shard::retrieve::record_internal::RecordInternal;
+use shard::scroll::ScrollRequestInternal;
+
+use crate::content_manager::errors::{StorageError, StorageResult};
+use crate::types::PayloadEncryptionConfig;
+
+/// Prefix of encrypted values, followed by hex encoded nonce and ciphertext
+const ENCRYPTED_VALUE_PREFIX: &str = "enc:";
+
+const KEY_LEN: usize = 32;
+
+/// Keys for encrypting payload fields, and encrypted fields by collection name
+pub struct PayloadEncryption {
+    cipher: LessSafeKey,
+    /// Key to derive nonces from values, which makes encryption deterministic
+    nonce_key: hmac::Key,
+    collections: HashMap<String, HashSet<String>>,
+}
+
+impl PayloadEncryption {
+    pub fn new(config: &PayloadEncryptionConfig) -> StorageResult<Self> {
+        let key = decode_hex(&config.key)
+            .filter(|key| key.len() == KEY_LEN)
+            .ok_or_else(|| {
+                StorageError::bad_input(format!(
+                    "Payload encryption key must be {} hexadecimal characters",
+                    KEY_LEN * 2,
+                ))
+            })?;
+
+        // Separate keys are derived for encryption and for nonces
+        let master_key = hmac::Key::new(hmac::HMAC_SHA256, &key);
+        let cipher_key = hmac::sign(&master_key, b"payload-encryption-key");
+        let nonce_key = hmac::sign(&master_key, b"payload-encryption-nonce");
+
+        let cipher_key = UnboundKey::new(&AES_256_GCM, cipher_key.as_ref())
+            .map_err(|_| StorageError::service_error("Failed to initialize payload encryption"))?;
+
+        let collections = config
+            .collections
+            .iter()
+            .filter(|(_, fields)| !fields.is_empty())
+            .map(|(collection, fields)| (collection.clone(), fields.iter().cloned().collect()))
+            .collect();
+
+        Ok(Self {
+            cipher: LessSafeKey::new(cipher_key),
+            nonce_key: hmac::Key::new(hmac::HMAC_SHA256, nonce_key.as_ref()),
+            collections,
+        })
+    }
+
+    /// Encrypted fields of the collection, if there are any.
+    pub fn fields(&self, collection_name: &str) -> Option<EncryptedFields<'_>> {
+        let fields = self.collections.get(collection_name)?;
+        Some(EncryptedFields {
+            encryption: self,
+            fields,
+        })
+    }
+
+    fn encrypt(&self, field: &str, value: &Value) -> String {
+        let mut data = value.to_string().into_bytes();
+
+        let mut nonce_input = Vec::with_capacity(field.len() + 1 + data.len());
+        nonce_input.extend_from_slice(field.as_bytes());
+        nonce_input.push(0);
+        nonce_input.extend_from_slice(&data);
+        let tag = hmac::sign(&self.nonce_key, &nonce_input);
+        let mut nonce = [0; NONCE_LEN];
+        nonce.copy_from_slice(&tag.as_ref()[..NONCE_LEN]);
+
+        self.cipher
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(field.as_bytes()),
+                &mut data,
+            )
+            .expect("payload value is too large to encrypt");
+
+        let mut encrypted =
+            String::with_capacity(ENCRYPTED_VALUE_PREFIX.len() + (NONCE_LEN + data.len()) * 2);
+        encrypted.push_str(ENCRYPTED_VALUE_PREFIX);
+        encode_hex(&mut encrypted, &nonce);
+        encode_hex(&mut encrypted, &data);
+        encrypted
+    }
+
+    /// Decrypt the value, if it was encrypted with the current key.
+    fn decrypt(&self, field: &str, value: &str) -> Option<Value> {
+        let data = decode_hex(value.strip_prefix(ENCRYPTED_VALUE_PREFIX)?)?;
+        if data.len() < NONCE_LEN {
+            return None;
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).ok()?;
+        let mut ciphertext = ciphertext.to_vec();
+        let plaintext = self
+            .cipher
+            .open_in_place(nonce, Aad::from(field.as_bytes()), &mut ciphertext)
+            .ok()?;
+
+        serde_json::from_slice(plaintext).ok()
+    }
+}
+
+/// Encrypted payload fields of a collection
+pub struct EncryptedFields<'a> {
+    encryption: &'a PayloadEncryption,
+    fields: &'a HashSet<String>,
+}
+
+impl EncryptedFields<'_> {
+    /// Name of the encrypted field, referenced by the path.
+    ///
+    /// Only whole fields can be referenced, as the structure of encrypted values is not visible.
+    fn field<'p>(&self, path: &'p JsonPath) -> StorageResult<Option<&'p str>> {
+        if !self.fields.contains(&path.first_key) {
+            return Ok(None);
+        }
+
+        match path.rest.as_slice() {
+            [] | [JsonPathItem::WildcardIndex] => Ok(Some(path.first_key.as_str())),
+            _ => Err(StorageError::bad_input(format!(
+                "Payload key {path} points inside of encrypted field {}, \
+                 only the whole field can be used",
+                path.first_key,
+            ))),
+        }
+    }
+
+    fn encrypt_value(&self, field: &str, value: &mut Value) {
+        match value {
+            Value::Null => {}
+            Value::Array(values) => values
+                .iter_mut()
+                .filter(|value| !value.is_null())
+                .for_each(|value| *value = Value::String(self.encryption.encrypt(field, value))),
+            value => *value = Value::String(self.encryption.encrypt(field, value)),
+        }
+    }
+
+    fn decrypt_value(&self, field: &str, value: &mut Value) {
+        match value {
+            Value::String(encrypted) => {
+                if let Some(decrypted) = self.encryption.decrypt(field, encrypted) {
+                    *value = decrypted;
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| {
+                if let Value::String(encrypted) = value
+                    && let Some(decrypted) = self.encryption.decrypt(field, encrypted)
+                {
+                    *value = decrypted;
+                }
+            }),
+            _ => {}
+        }
+    }
+
+    pub fn encrypt_payload(&self, payload: &mut Payload) {
+        for (field, value) in payload.0.iter_mut() {
+            if self.fields.contains(field) {
+                self.encrypt_value(field, value);
+            }
+        }
+    }
+
+    pub fn decrypt_payload(&self, payload: &mut Payload) {
+        for (field, value) in payload.0.iter_mut() {
+            if self.fields.contains(field) {
+                self.decrypt_value(field, value);
+            }
+        }
+    }
+
+    pub fn decrypt_scored_points(&self, points: &mut [ScoredPoint]) {
+        points
+            .iter_mut()
+            .filter_map(|point| point.payload.as_mut())
+            .for_each(|payload| self.decrypt_payload(payload));
+    }
+
+    pub fn decrypt_records(&self, records: &mut [RecordInternal]) {
+        records
+            .iter_mut()
+            .filter_map(|record| record.payload.as_mut())
+            .for_each(|payload| self.decrypt_payload(payload));
+    }
+
+    /// Decrypt hits of the groups, and group IDs if grouped by an encrypted field.
+    pub fn decrypt_groups(&self, group_by: &JsonPath, groups: &mut [PointGroup]) {
+        let group_by_field = self.field(group_by).ok().flatten();
+
+        for group in groups {
+            group
+                .hits
+                .iter_mut()
+                .filter_map(|hit| hit.payload.as_mut())
+                .for_each(|payload| self.decrypt_payload(payload));
+
+            if let Some(field) = group_by_field
+                && let GroupId::String(encrypted) = &group.id
+                && let Some(decrypted) = self.encryption.decrypt(field, encrypted)
+                && let Ok(id) = serde_json::from_value(decrypted)
+            {
+                group.id = id;
+            }
+        }
+    }
+
+    /// Decrypt values of the facet hits, if the facet key is an encrypted field.
+    pub fn decrypt_facet_hits(&self, key: &JsonPath, hits: &mut [FacetValueHit]) {
+        let Some(field) = self.field(key).ok().flatten() else {
+            return;
+        };
+
+        for hit in hits {
+            let FacetValue::Keyword(encrypted) = &hit.value else {
+                continue;
+            };
+            hit.value = match self.encryption.decrypt(field, encrypted) {
+                Some(Value::String(value)) => FacetValue::Keyword(value),
+                Some(Value::Bool(value)) => FacetValue::Bool(value),
+                Some(Value::Number(number)) if number.is_i64() => {
+                    FacetValue::Int(number.as_i64().unwrap_or_default())
+                }
+                Some(value) => FacetValue::Keyword(value.to_string()),
+                None => continue,
+            };
+        }
+    }
+
+    /// Replace values in the filter conditions on encrypted fields with encrypted values.
+    pub fn encrypt_filter(&self, filter: &mut Filter) -> StorageResult<()> {
+        let Filter {
+            should,
+            min_should,
+            must,
+            must_not,
+        } = filter;
+
+        should
+            .iter_mut()
+            .chain(must.iter_mut())
+            .chain(must_not.iter_mut())
+            .flatten()
+            .chain(
+                min_should
+                    .iter_mut()
+                    .flat_map(|min_should| min_should.conditions.iter_mut()),
+            )
+            .try_for_each(|condition| self.encrypt_condition(condition))
+    }
+
+    fn encrypt_filter_opt(&self, filter: &mut Option<Filter>) -> StorageResult<()> {
+        filter
+            .as_mut()
+            .map_or(Ok(()), |filter| self.encrypt_filter(filter))
+    }
+
+    fn encrypt_condition(&self, condition: &mut Condition) -> StorageResult<()> {
+        match condition {
+            Condition::Field(field_condition) => self.encrypt_field_condition(field_condition),
+            Condition::Nested(nested) => {
+                if self.fields.contains(&nested.nested.key.first_key) {
+                    return Err(StorageError::bad_input(format!(
+                        "Nested filter is not supported for encrypted field {}",
+                        nested.nested.key.first_key,
+                    )));
+                }
+                Ok(())
+            }
+            Condition::Filter(filter) => self.encrypt_filter(filter),
+            Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::HasVector(_)
+            | Condition::CustomIdChecker(_) => Ok(()),
+        }
+    }
+
+    fn encrypt_field_condition(&self, condition: &mut FieldCondition) -> StorageResult<()> {
+        let Some(field) = self.field(&condition.key)?.map(str::to_owned) else {
+            return Ok(());
+        };
+        let field = field.as_str();
+
+        let FieldCondition {
+            key,
+            r#match,
+            range,
+            geo_bounding_box,
+            geo_radius,
+            geo_polygon,
+            values_count: _,
+            is_empty: _,
+            is_null: _,
+        } = condition;
+
+        if range.is_some()
+            || geo_bounding_box.is_some()
+            || geo_radius.is_some()
+            || geo_polygon.is_some()
+        {
+            return Err(StorageError::bad_input(format!(
+                "Only `match` conditions are supported for encrypted field {key}",
+            )));
+        }
+
+        let Some(r#match) = r#match else {
+            return Ok(());
+        };
+
+        match r#match {
+            Match::Value(MatchValue { value }) => {
+                *value = ValueVariants::String(self.encryption.encrypt(field, &value.to_value()));
+            }
+            Match::Any(MatchAny { any: variants })
+            | Match::Except(MatchExcept { except: variants }) => {
+                *variants = self.encrypt_any_variants(field, variants);
+            }
+            Match::Text(_) | Match::TextAny(_) | Match::Phrase(_) => {
+                return Err(StorageError::bad_input(format!(
+                    "Full-text match is not supported for encrypted field {key}",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encrypt_any_variants(&self, field: &str, variants: &AnyVariants) -> AnyVariants {
+        let values: Vec<Value> = match variants {
+            AnyVariants::Strings(strings) => {
+                strings.iter().map(|s| Value::from(s.as_str())).collect()
+            }
+            AnyVariants::Integers(integers) => integers.iter().map(|i| Value::from(*i)).collect(),
+        };
+
+        AnyVariants::Strings(
+            values
+                .iter()
+                .map(|value| self.encryption.encrypt(field, value))
+                .collect(),
+        )
+    }
+
+    /// Encrypt payload values and filters of the update operation.
+    pub fn encrypt_operation(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+    ) -> StorageResult<()> {
+        match operation {
+            CollectionUpdateOperations::PointOperation(op) => match op {
+                PointOperations::UpsertPoints(points_op) => {
+                    self.encrypt_points(points_op);
+                    Ok(())
+                }
+                PointOperations::UpsertPointsConditional(conditional_op) => {
+                    self.encrypt_points(&mut conditional_op.points_op);
+                    self.encrypt_filter(&mut conditional_op.condition)
+                }
+                PointOperations::DeletePoints { .. } => Ok(()),
+                PointOperations::DeletePointsByFilter(filter) => self.encrypt_filter(filter),
+                PointOperations::SyncPoints(sync_op) => {
+                    sync_op
+                        .points
+                        .iter_mut()
+                        .filter_map(|point| point.payload.as_mut())
+                        .for_each(|payload| self.encrypt_payload(payload));
+                    Ok(())
+                }
+            },
+            CollectionUpdateOperations::VectorOperation(op) => match op {
+                VectorOperations::UpdateVectors(update_op) => {
+                    self.encrypt_filter_opt(&mut update_op.update_filter)
+                }
+                VectorOperations::DeleteVectors(..) => Ok(()),
+                VectorOperations::DeleteVectorsByFilter(filter, _) => self.encrypt_filter(filter),
+            },
+            CollectionUpdateOperations::PayloadOperation(op) => match op {
+                PayloadOps::SetPayload(set_op) | PayloadOps::OverwritePayload(set_op) => {
+                    match &set_op.key {
+                        // Payload is set inside of a field, which is either not encrypted,
+                        // or can't be changed partially
+                        Some(key) => {
+                            if self.fields.contains(&key.first_key) {
+                                return Err(StorageError::bad_input(format!(
+                                    "Encrypted field {} can only be set as a whole",
+                                    key.first_key,
+                                )));
+                            }
+                        }
+                        None => self.encrypt_payload(&mut set_op.payload),
+                    }
+                    self.encrypt_filter_opt(&mut set_op.filter)
+                }
+                PayloadOps::DeletePayload(delete_op) => {
+                    for key in &delete_op.keys {
+                        self.field(key)?;
+                    }
+                    self.encrypt_filter_opt(&mut delete_op.filter)
+                }
+                PayloadOps::ClearPayload { .. } => Ok(()),
+                PayloadOps::ClearPayloadByFilter(filter) => self.encrypt_filter(filter),
+            },
+            CollectionUpdateOperations::FieldIndexOperation(op) => match op {
+                FieldIndexOperations::CreateIndex(create_index) => {
+                    let Some(field) = self.field(&create_index.field_name)? else {
+                        return Ok(());
+                    };
+                    match &create_index.field_schema {
+                        Some(schema) if schema.kind() != PayloadSchemaType::Keyword => {
+                            Err(StorageError::bad_input(format!(
+                                "Only keyword index is supported for encrypted field {field}",
+                            )))
+                        }
+                        _ => Ok(()),
+                    }
+                }
+                FieldIndexOperations::DeleteIndex(_) => Ok(()),
+            },
+            #[cfg(feature = "staging")]
+            CollectionUpdateOperations::StagingOperation(_) => Ok(()),
+        }
+    }
+
+    fn encrypt_points(&self, points_op: &mut PointInsertOperationsInternal) {
+        match points_op {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch
+                .payloads
+                .iter_mut()
+                .flatten()
+                .flatten()
+                .for_each(|payload| self.encrypt_payload(payload)),
+            PointInsertOperationsInternal::PointsList(points) => points
+                .iter_mut()
+                .filter_map(|point| point.payload.as_mut())
+                .for_each(|payload| self.encrypt_payload(payload)),
+        }
+    }
+}
+
+/// Read request, which filters need to match encrypted values.
+pub(crate) trait EncryptableRequest {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()>;
+}
+
+impl EncryptableRequest for SearchRequestInternal {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for RecommendRequestInternal {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for CoreSearchRequest {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for CountRequestInternal {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for DiscoverRequestInternal {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for GroupRequest {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        match &mut self.source {
+            SourceRequest::Search(request) => request.encrypt(fields)?,
+            SourceRequest::Recommend(request) => request.encrypt(fields)?,
+            SourceRequest::Query(request) => request.encrypt(fields)?,
+        }
+
+        // Lookup is done by group IDs, which are encrypted values for encrypted fields
+        if self.with_lookup.is_some() && fields.field(&self.group_by)?.is_some() {
+            return Err(StorageError::bad_input(format!(
+                "Lookup is not supported for groups by encrypted field {}",
+                self.group_by,
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl EncryptableRequest for ScrollRequestInternal {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        let order_by_key = match &self.order_by {
+            Some(OrderByInterface::Key(key)) => Some(key),
+            Some(OrderByInterface::Struct(order_by)) => Some(&order_by.key),
+            None => None,
+        };
+        if let Some(key) = order_by_key
+            && fields.field(key)?.is_some()
+        {
+            return Err(StorageError::bad_input(format!(
+                "Ordering by encrypted field {key} is not supported",
+            )));
+        }
+
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for CollectionQueryRequest {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)?;
+        self.prefetch
+            .iter_mut()
+            .try_for_each(|prefetch| prefetch.encrypt(fields))
+    }
+}
+
+impl EncryptableRequest for CollectionPrefetch {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)?;
+        self.prefetch
+            .iter_mut()
+            .try_for_each(|prefetch| prefetch.encrypt(fields))
+    }
+}
+
+impl EncryptableRequest for FacetParams {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.field(&self.key)?;
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+impl EncryptableRequest for CollectionSearchMatrixRequest {
+    fn encrypt(&mut self, fields: &EncryptedFields) -> StorageResult<()> {
+        fields.encrypt_filter_opt(&mut self.filter)
+    }
+}
+
+fn encode_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn encryption() -> PayloadEncryption {
+        PayloadEncryption::new(&PayloadEncryptionConfig {
+            key: "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff".to_string(),
+            collections: HashMap::from([(
+                "test".to_string(),
+                vec!["email".to_string(), "tags".to_string()],
+            )]),
+        })
+        .unwrap()
+    }
+
+    fn payload(value: Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_payload() {
+        let encryption = encryption();
+        let fields = encryption.fields("test").unwrap();
+        assert!(encryption.fields("other").is_none());
+
+        let original = payload(json!({
+            "email": "user@example.com",
+            "tags": ["a", 1, null],
+            "name": "user",
+        }));
+
+        let mut encrypted = original.clone();
+        fields.encrypt_payload(&mut encrypted);
+
+        assert_eq!(encrypted.0["name"], json!("user"));
+        let email = encrypted.0["email"].as_str().unwrap();
+        assert!(email.starts_with(ENCRYPTED_VALUE_PREFIX));
+        assert!(!email.contains("example"));
+        assert!(encrypted.0["tags"][0].as_str().is_some());
+        assert!(encrypted.0["tags"][1].as_str().is_some());
+        assert!(encrypted.0["tags"][2].is_null());
+
+        // Encryption is deterministic
+        let mut encrypted_again = original.clone();
+        fields.encrypt_payload(&mut encrypted_again);
+        assert_eq!(encrypted, encrypted_again);
+
+        let mut decrypted = encrypted;
+        fields.decrypt_payload(&mut decrypted);
+        assert_eq!(decrypted, original);
+    }
+
+    #[test]
+    fn test_encrypt_filter() {
+        let encryption = encryption();
+        let fields = encryption.fields("test").unwrap();
+
+        let mut stored = payload(json!({ "email": "user@example.com", "tags": ["a", 1] }));
+        fields.encrypt_payload(&mut stored);
+
+        let mut filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "email".parse().unwrap(),
+            Match::new_value(ValueVariants::String("user@example.com".to_string())),
+        )));
+        fields.encrypt_filter(&mut filter).unwrap();
+
+        let Some(Condition::Field(condition)) = filter.must.as_ref().and_then(|c| c.first()) else {
+            panic!("unexpected filter: {filter:?}");
+        };
+        let Some(Match::Value(MatchValue {
+            value: ValueVariants::String(value),
+        })) = &condition.r#match
+        else {
+            panic!("unexpected condition: {condition:?}");
+        };
+        assert_eq!(stored.0["email"], json!(value));
+
+        let mut any_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "tags[]".parse().unwrap(),
+            Match::Any(MatchAny {
+                any: AnyVariants::Integers([1].into_iter().collect()),
+            }),
+        )));
+        fields.encrypt_filter(&mut any_filter).unwrap();
+        let Some(Condition::Field(condition)) = any_filter.must.as_ref().and_then(|c| c.first())
+        else {
+            panic!("unexpected filter: {any_filter:?}");
+        };
+        let Some(Match::Any(MatchAny {
+            any: AnyVariants::Strings(values),
+        })) = &condition.r#match
+        else {
+            panic!("unexpected condition: {condition:?}");
+        };
+        assert!(values.contains(stored.0["tags"][1].as_str().unwrap()));
+
+        let mut text_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "email".parse().unwrap(),
+            Match::new_text("user"),
+        )));
+        assert!(fields.encrypt_filter(&mut text_filter).is_err());
+
+        let mut nested_path_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "email.domain".parse().unwrap(),
+            Match::new_text("x"),
+        )));
+        assert!(fields.encrypt_filter(&mut nested_path_filter).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let config = PayloadEncryptionConfig {
+            key: "abc".to_string(),
+            collections: HashMap::new(),
+        };
+        assert!(PayloadEncryption::new(&config).is_err());
+    }
+}
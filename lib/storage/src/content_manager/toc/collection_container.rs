@@ -122,6 +122,7 @@ impl TableOfContent {
         consensus_manager::CollectionsSnapshot {
             collections,
             aliases: self.alias_persistence.read().await.state().clone(),
+            api_keys: self.api_keys.state(),
         }
     }
 
@@ -281,6 +282,9 @@ impl TableOfContent {
                 .await
                 .apply_state(data.aliases)?;
 
+            // Apply managed API keys
+            self.api_keys.apply_state(data.api_keys)?;
+
             Ok(())
         })
     }
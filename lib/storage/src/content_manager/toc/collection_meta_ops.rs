@@ -101,6 +101,10 @@ impl TableOfContent {
                 log::debug!("Set shard replica state {operation:?}");
                 self.set_shard_replica_state(operation).await.map(|()| true)
             }
+            CollectionMetaOperations::ChangeApiKeys(operation) => {
+                log::debug!("Changing API key {}", operation.key_id());
+                self.api_keys.apply(operation)
+            }
             CollectionMetaOperations::Nop { .. } => Ok(true),
             CollectionMetaOperations::CreateShardKey(create_shard_key) => {
                 log::debug!("Create shard key {create_shard_key:?}");
@@ -144,6 +148,7 @@ impl TableOfContent {
             sparse_vectors,
             strict_mode_config: strict_mode,
             metadata,
+            read_only,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -187,6 +192,10 @@ impl TableOfContent {
             collection.update_metadata(metadata).await?;
         }
 
+        if let Some(read_only) = read_only {
+            collection.set_read_only(read_only).await?;
+        }
+
         collection.print_warnings().await;
 
         // Recreate optimizers
@@ -202,6 +202,7 @@ impl TableOfContent {
             strict_mode_config,
             uuid,
             metadata,
+            read_only: false,
         };
 
         // No shard key mapping on creation, shard keys are set up after creating the collection
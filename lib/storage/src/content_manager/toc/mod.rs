@@ -4,6 +4,7 @@ mod create_collection;
 pub mod dispatcher;
 mod point_ops;
 mod point_ops_internal;
+mod recall_estimation;
 pub mod request_hw_counter;
 mod snapshots;
 mod telemetry;
@@ -22,6 +23,7 @@ use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{
     CollectionConfigInternal, default_replication_factor, default_shard_number,
 };
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::replica_set::AbortShardTransfer;
@@ -43,10 +45,12 @@ use tokio::sync::{Mutex, RwLock, Semaphore};
 use self::dispatcher::TocDispatcher;
 use crate::ConsensusOperations;
 use crate::content_manager::alias_mapping::AliasPersistence;
+use crate::content_manager::api_keys::{API_KEYS_FILE, ApiKeyStore};
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::payload_encryption::{EncryptedFields, PayloadEncryption};
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
 use crate::content_manager::toc::telemetry::TocTelemetryCollector;
 use crate::rbac::{Access, AccessRequirements, CollectionMultipass, CollectionPass};
@@ -91,8 +95,14 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Aggregation of all hardware measurements for each alias or collection config.
     collection_hw_metrics: DashMap<CollectionId, Arc<HwSharedDrain>>,
+    /// Aggregation of all hardware measurements for each API key or token subject.
+    subject_hw_metrics: DashMap<String, Arc<HwSharedDrain>>,
     /// Collector for various telemetry/metrics.
     telemetry: TocTelemetryCollector,
+    /// API keys, managed at runtime.
+    api_keys: ApiKeyStore,
+    /// Encryption of payload fields, if configured.
+    payload_encryption: Option<PayloadEncryption>,
 }
 
 impl TableOfContent {
@@ -107,7 +117,7 @@ impl TableOfContent {
         channel_service: ChannelService,
         this_peer_id: PeerId,
         consensus_proposal_sender: Option<OperationSender>,
-    ) -> Self {
+    ) -> Result<Self, StorageError> {
         let collections_path = storage_config.storage_path.join(COLLECTIONS_DIR);
         fs::create_dir_all(&collections_path).expect("Can't create Collections directory");
         if let Some(path) = storage_config.temp_path.as_deref() {
@@ -209,6 +219,15 @@ impl TableOfContent {
         let alias_persistence = AliasPersistence::open(&alias_path)
             .expect("Can't open database by the provided config");
 
+        let api_keys = ApiKeyStore::open(&storage_config.storage_path.join(API_KEYS_FILE))?;
+
+        let payload_encryption = storage_config
+            .payload_encryption
+            .as_ref()
+            .map(PayloadEncryption::new)
+            .transpose()
+            .expect("Invalid payload encryption config");
+
         let rate_limiter = match storage_config.performance.update_rate_limit {
             Some(limit) => Some(Semaphore::new(limit)),
             None => {
@@ -226,7 +245,7 @@ impl TableOfContent {
             }
         };
 
-        TableOfContent {
+        Ok(TableOfContent {
             collections: Arc::new(RwLock::new(collections)),
             storage_config: Arc::new(storage_config.clone()),
             search_runtime,
@@ -241,8 +260,11 @@ impl TableOfContent {
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             collection_hw_metrics: DashMap::new(),
+            subject_hw_metrics: DashMap::new(),
             telemetry,
-        }
+            api_keys,
+            payload_encryption,
+        })
     }
 
     /// Return `true` if service is working in distributed mode.
@@ -254,6 +276,32 @@ impl TableOfContent {
         &self.storage_config.storage_path
     }
 
+    pub fn import_path(&self) -> Option<&Path> {
+        self.storage_config.import_path.as_deref()
+    }
+
+    pub fn export_path(&self) -> Option<&Path> {
+        self.storage_config.export_path.as_deref()
+    }
+
+    pub fn api_keys(&self) -> &ApiKeyStore {
+        &self.api_keys
+    }
+
+    /// Encrypted payload fields of the collection.
+    ///
+    /// Requests to a specific shard come from other peers, and are already encrypted.
+    pub(crate) fn encrypted_fields(
+        &self,
+        collection_name: &str,
+        shard_selection: &ShardSelectorInternal,
+    ) -> Option<EncryptedFields<'_>> {
+        if shard_selection.is_shard_id() {
+            return None;
+        }
+        self.payload_encryption.as_ref()?.fields(collection_name)
+    }
+
     /// List of all collections to which the user has access
     pub async fn all_collections(&self, access: &Access) -> Vec<CollectionPass<'static>> {
         self.all_collections_with_access_requirements(access, AccessRequirements::new())
@@ -732,22 +780,12 @@ impl TableOfContent {
     /// Gets a copy of hardware metrics for all collections that have been collected from operations on this node.
     /// This copy is intentional to prevent 'uncontrolled' modifications of the DashMap, which doesn't need to be mutable for modifications.
     pub fn all_hw_metrics(&self) -> HashMap<String, HardwareUsage> {
-        self.collection_hw_metrics
-            .iter()
-            .map(|i| {
-                let key = i.key().clone();
-                let hw_usage = HardwareUsage {
-                    cpu: i.get_cpu(),
-                    payload_io_read: i.get_payload_io_read(),
-                    payload_io_write: i.get_payload_io_write(),
-                    payload_index_io_read: i.get_payload_index_io_read(),
-                    payload_index_io_write: i.get_payload_index_io_write(),
-                    vector_io_read: i.get_vector_io_read(),
-                    vector_io_write: i.get_vector_io_write(),
-                };
-                (key, hw_usage)
-            })
-            .collect()
+        request_hw_counter::drains_to_hw_usage(&self.collection_hw_metrics)
+    }
+
+    /// Same as [`Self::all_hw_metrics`], but aggregated per API key or token subject.
+    pub fn all_subject_hw_metrics(&self) -> HashMap<String, HardwareUsage> {
+        request_hw_counter::drains_to_hw_usage(&self.subject_hw_metrics)
     }
 
     pub fn general_runtime_handle(&self) -> &Handle {
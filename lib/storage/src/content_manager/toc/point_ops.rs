@@ -10,6 +10,7 @@ use collection::grouping::group_by::GroupRequest;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::point_ops::WriteOrdering;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::time_travel::AsOf;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
@@ -26,6 +27,7 @@ use shard::search::CoreSearchRequestBatch;
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
+use crate::content_manager::payload_encryption::{EncryptableRequest, EncryptedFields};
 use crate::rbac::Auth;
 use crate::rbac::auditable_operation::AuditableOperation;
 
@@ -44,17 +46,22 @@ impl TableOfContent {
     pub async fn recommend(
         &self,
         collection_name: &str,
-        request: RecommendRequestInternal,
+        mut request: RecommendRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         auth: Auth,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<Vec<ScoredPoint>> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "recommend")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "recommend")?;
 
         let collection = self.get_collection(&collection_pass).await?;
-        recommendations::recommend_by(
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selector);
+        if let Some(fields) = &encrypted_fields {
+            request.encrypt(fields)?;
+        }
+
+        let mut points = recommendations::recommend_by(
             request,
             &collection,
             |name| self.get_collection_opt(name),
@@ -63,8 +70,12 @@ impl TableOfContent {
             timeout,
             hw_measurement_acc,
         )
-        .await
-        .map_err(|err| err.into())
+        .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            fields.decrypt_scored_points(&mut points);
+        }
+        Ok(points)
     }
 
     /// Recommend points in a batching fashion using positive and negative example from the request
@@ -96,7 +107,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
-        recommendations::recommend_batch_by(
+        let encrypted_fields = self.encrypt_requests(&collection, &mut requests)?;
+
+        let mut batch = recommendations::recommend_batch_by(
             requests,
             &collection,
             |name| self.get_collection_opt(name),
@@ -104,8 +117,14 @@ impl TableOfContent {
             timeout,
             hw_measurement_acc,
         )
-        .await
-        .map_err(|err| err.into())
+        .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            batch
+                .iter_mut()
+                .for_each(|points| fields.decrypt_scored_points(points));
+        }
+        Ok(batch)
     }
 
     /// Search in a batching fashion for the closest points using vector similarity with given restrictions defined
@@ -143,7 +162,14 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selection);
+        if let Some(fields) = &encrypted_fields {
+            for request in &mut request.searches {
+                request.encrypt(fields)?;
+            }
+        }
+
+        let mut batch = collection
             .core_search_batch(
                 request,
                 read_consistency,
@@ -151,8 +177,14 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            batch
+                .iter_mut()
+                .for_each(|points| fields.decrypt_scored_points(points));
+        }
+        Ok(batch)
     }
 
     /// Count points in the collection.
@@ -171,16 +203,20 @@ impl TableOfContent {
     pub async fn count(
         &self,
         collection_name: &str,
-        request: CountRequestInternal,
+        mut request: CountRequestInternal,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         shard_selection: ShardSelectorInternal,
         auth: Auth,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<CountResult> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "count")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "count")?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        if let Some(fields) = self.encrypted_fields(collection.name(), &shard_selection) {
+            request.encrypt(&fields)?;
+        }
+
         collection
             .count(
                 request,
@@ -208,17 +244,17 @@ impl TableOfContent {
     pub async fn retrieve(
         &self,
         collection_name: &str,
-        request: PointRequestInternal,
+        mut request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         shard_selection: ShardSelectorInternal,
         auth: Auth,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<Vec<RecordInternal>> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "retrieve")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "retrieve")?;
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let mut records = collection
             .retrieve(
                 request,
                 read_consistency,
@@ -226,24 +262,33 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(fields) = self.encrypted_fields(collection.name(), &shard_selection) {
+            fields.decrypt_records(&mut records);
+        }
+        Ok(records)
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn group(
         &self,
         collection_name: &str,
-        request: GroupRequest,
+        mut request: GroupRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         auth: Auth,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<GroupsResult> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "group")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "group")?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selection);
+        if let Some(fields) = &encrypted_fields {
+            request.encrypt(fields)?;
+        }
+        let group_by_key = request.group_by.clone();
 
         let collection_by_name = |name| self.get_collection_opt(name);
 
@@ -252,28 +297,34 @@ impl TableOfContent {
             .set_shard_selection(shard_selection)
             .set_timeout(timeout);
 
-        group_by
-            .execute()
-            .await
-            .map(|groups| GroupsResult { groups })
-            .map_err(|err| err.into())
+        let mut groups = group_by.execute().await?;
+
+        if let Some(fields) = &encrypted_fields {
+            fields.decrypt_groups(&group_by_key, &mut groups);
+        }
+        Ok(GroupsResult { groups })
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn discover(
         &self,
         collection_name: &str,
-        request: DiscoverRequestInternal,
+        mut request: DiscoverRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selector: ShardSelectorInternal,
         auth: Auth,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<Vec<ScoredPoint>> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "discover")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "discover")?;
 
         let collection = self.get_collection(&collection_pass).await?;
-        discovery::discover(
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selector);
+        if let Some(fields) = &encrypted_fields {
+            request.encrypt(fields)?;
+        }
+
+        let mut points = discovery::discover(
             request,
             &collection,
             |name| self.get_collection_opt(name),
@@ -282,8 +333,12 @@ impl TableOfContent {
             timeout,
             hw_measurement_acc,
         )
-        .await
-        .map_err(|err| err.into())
+        .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            fields.decrypt_scored_points(&mut points);
+        }
+        Ok(points)
     }
 
     pub async fn discover_batch(
@@ -305,8 +360,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        let encrypted_fields = self.encrypt_requests(&collection, &mut requests)?;
 
-        discovery::discover_batch(
+        let mut batch = discovery::discover_batch(
             requests,
             &collection,
             |name| self.get_collection_opt(name),
@@ -314,8 +370,14 @@ impl TableOfContent {
             timeout,
             hw_measurement_acc,
         )
-        .await
-        .map_err(|err| err.into())
+        .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            batch
+                .iter_mut()
+                .for_each(|points| fields.decrypt_scored_points(points));
+        }
+        Ok(batch)
     }
 
     /// Paginate over all stored points with given filtering conditions
@@ -333,17 +395,22 @@ impl TableOfContent {
     pub async fn scroll(
         &self,
         collection_name: &str,
-        request: ScrollRequestInternal,
+        mut request: ScrollRequestInternal,
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
         shard_selection: ShardSelectorInternal,
         auth: Auth,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<ScrollResult> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "scroll")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "scroll")?;
 
         let collection = self.get_collection(&collection_pass).await?;
-        collection
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selection);
+        if let Some(fields) = &encrypted_fields {
+            request.encrypt(fields)?;
+        }
+
+        let mut result = collection
             .scroll_by(
                 request,
                 read_consistency,
@@ -351,8 +418,16 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            result
+                .points
+                .iter_mut()
+                .filter_map(|point| point.payload.as_mut())
+                .for_each(|payload| fields.decrypt_payload(payload));
+        }
+        Ok(result)
     }
 
     pub async fn query_batch(
@@ -374,8 +449,9 @@ impl TableOfContent {
         };
 
         let collection = self.get_collection(&collection_pass).await?;
+        let encrypted_fields = self.encrypt_requests(&collection, &mut requests)?;
 
-        collection
+        let mut batch = collection
             .query_batch(
                 requests,
                 |name| self.get_collection_opt(name),
@@ -383,8 +459,55 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(|err| err.into())
+            .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            batch
+                .iter_mut()
+                .for_each(|points| fields.decrypt_scored_points(points));
+        }
+        Ok(batch)
+    }
+
+    /// Query the collection as it was at `as_of`, using segments retained after optimizations.
+    pub async fn query_batch_as_of(
+        &self,
+        collection_name: &str,
+        mut requests: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        as_of: AsOf,
+        auth: Auth,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<Vec<Vec<ScoredPoint>>> {
+        let mut collection_pass = None;
+        for (request, _shard_selector) in &mut requests {
+            collection_pass =
+                Some(auth.check_point_op(collection_name, request, "query_batch_as_of")?);
+        }
+        let Some(collection_pass) = collection_pass else {
+            // This can happen only if there are no requests
+            return Ok(vec![]);
+        };
+
+        let collection = self.get_collection(&collection_pass).await?;
+        let encrypted_fields = self.encrypt_requests(&collection, &mut requests)?;
+
+        let mut batch = collection
+            .query_batch_as_of(
+                requests,
+                |name| self.get_collection_opt(name),
+                as_of,
+                timeout,
+                hw_measurement_acc,
+            )
+            .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            batch
+                .iter_mut()
+                .for_each(|points| fields.decrypt_scored_points(points));
+        }
+        Ok(batch)
     }
 
     // Return unique values for a payload key, and a count of points for each value.
@@ -392,18 +515,23 @@ impl TableOfContent {
     pub async fn facet(
         &self,
         collection_name: &str,
-        request: FacetParams,
+        mut request: FacetParams,
         shard_selection: ShardSelectorInternal,
         read_consistency: Option<ReadConsistency>,
         auth: Auth,
         timeout: Option<Duration>,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<FacetResponse> {
-        let collection_pass = auth.check_point_op(collection_name, &request, "facet")?;
+        let collection_pass = auth.check_point_op(collection_name, &mut request, "facet")?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        let encrypted_fields = self.encrypted_fields(collection.name(), &shard_selection);
+        if let Some(fields) = &encrypted_fields {
+            request.encrypt(fields)?;
+        }
+        let key = request.key.clone();
 
-        collection
+        let mut response = collection
             .facet(
                 request,
                 shard_selection,
@@ -411,15 +539,19 @@ impl TableOfContent {
                 timeout,
                 hw_measurement_acc,
             )
-            .await
-            .map_err(StorageError::from)
+            .await?;
+
+        if let Some(fields) = &encrypted_fields {
+            fields.decrypt_facet_hits(&key, &mut response.hits);
+        }
+        Ok(response)
     }
 
     #[allow(clippy::too_many_arguments)]
     pub async fn search_points_matrix(
         &self,
         collection_name: &str,
-        request: CollectionSearchMatrixRequest,
+        mut request: CollectionSearchMatrixRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         auth: Auth,
@@ -427,9 +559,12 @@ impl TableOfContent {
         hw_measurement_acc: HwMeasurementAcc,
     ) -> Result<CollectionSearchMatrixResponse, StorageError> {
         let collection_pass =
-            auth.check_point_op(collection_name, &request, "search_points_matrix")?;
+            auth.check_point_op(collection_name, &mut request, "search_points_matrix")?;
 
         let collection = self.get_collection(&collection_pass).await?;
+        if let Some(fields) = self.encrypted_fields(collection.name(), &shard_selection) {
+            request.encrypt(&fields)?;
+        }
 
         collection
             .search_points_matrix(
@@ -443,6 +578,28 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    /// Encrypt filters of the batch requests, if the collection has encrypted fields.
+    ///
+    /// Returns encrypted fields to decrypt the results with.
+    fn encrypt_requests<R: EncryptableRequest>(
+        &self,
+        collection: &Collection,
+        requests: &mut [(R, ShardSelectorInternal)],
+    ) -> StorageResult<Option<EncryptedFields<'_>>> {
+        // Batch requests from other peers target a single shard
+        let Some((_, shard_selection)) = requests.first() else {
+            return Ok(None);
+        };
+        let Some(fields) = self.encrypted_fields(collection.name(), shard_selection) else {
+            return Ok(None);
+        };
+
+        for (request, _) in requests {
+            request.encrypt(&fields)?;
+        }
+        Ok(Some(fields))
+    }
+
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -490,7 +647,7 @@ impl TableOfContent {
     pub async fn update(
         &self,
         collection_name: &str,
-        operation: OperationWithClockTag,
+        mut operation: OperationWithClockTag,
         wait: WaitUntil,
         timeout: Option<Duration>,
         ordering: WriteOrdering,
@@ -498,17 +655,20 @@ impl TableOfContent {
         auth: Auth,
         hw_measurement_acc: HwMeasurementAcc,
     ) -> StorageResult<UpdateResult> {
-        let collection_pass = auth.check_point_op(
-            collection_name,
-            &operation.operation,
-            operation.operation.operation_name(),
-        )?;
+        // Access check may rewrite the operation, so the name is taken beforehand
+        let operation_name = operation.operation.operation_name();
+        let collection_pass =
+            auth.check_point_op(collection_name, &mut operation.operation, operation_name)?;
 
         // `TableOfContent::_update_shard_keys` and `Collection::update_from_*` are cancel safe,
         // so this method is cancel safe.
 
         let collection = self.get_collection(&collection_pass).await?;
 
+        if let Some(fields) = self.encrypted_fields(collection.name(), &shard_selector) {
+            fields.encrypt_operation(&mut operation.operation)?;
+        }
+
         // Ordered operation flow:
         //
         // ┌───────────────────┐
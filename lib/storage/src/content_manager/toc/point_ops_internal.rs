@@ -8,6 +8,7 @@ use collection::operations::universal_query::shard_query::{ShardQueryRequest, Sh
 use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::{TextQuery, TextQueryStatistics};
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageResult;
@@ -48,6 +49,23 @@ impl TableOfContent {
         Ok(res)
     }
 
+    pub async fn text_query_statistics_internal(
+        &self,
+        collection_name: &str,
+        query: TextQuery,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        hw_measurement_acc: HwMeasurementAcc,
+    ) -> StorageResult<TextQueryStatistics> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .text_query_statistics(query, shard_selection, None, timeout, hw_measurement_acc)
+            .await?;
+
+        Ok(res)
+    }
+
     pub async fn cleanup_local_shard(
         &self,
         collection_name: &str,
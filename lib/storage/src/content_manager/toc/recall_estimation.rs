@@ -0,0 +1,46 @@
+use std::sync::Weak;
+use std::time::Duration;
+
+use crate::content_manager::toc::TableOfContent;
+use crate::types::RecallEstimationConfig;
+
+impl TableOfContent {
+    /// Periodically estimate recall of approximate search for all collections.
+    ///
+    /// Runs until the table of content is dropped.
+    pub async fn run_recall_estimation(toc: Weak<Self>, config: RecallEstimationConfig) {
+        let RecallEstimationConfig {
+            interval_sec,
+            sample_size,
+            limit,
+        } = config;
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval_sec)).await;
+
+            let Some(toc) = toc.upgrade() else {
+                return;
+            };
+
+            let collections: Vec<_> = toc.collections.read().await.values().cloned().collect();
+            let timeout = toc
+                .storage_config
+                .performance
+                .search_timeout_sec
+                .map(|timeout| Duration::from_secs(timeout as u64));
+            drop(toc);
+
+            for collection in collections {
+                if let Err(err) = collection
+                    .estimate_recall(sample_size, limit, timeout)
+                    .await
+                {
+                    log::warn!(
+                        "Failed to estimate recall of collection {}: {err}",
+                        collection.name(),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,12 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use api::rest::models::HardwareUsage;
 use common::counter::hardware_accumulator::{HwMeasurementAcc, HwSharedDrain};
 use common::counter::hardware_data::HardwareData;
+use common::request_profile::{RequestProfileData, StageProfile};
+use common::search_diagnostics::SearchDiagnosticsData;
+use dashmap::DashMap;
 
 use super::TableOfContent;
 
@@ -12,6 +17,31 @@ impl TableOfContent {
             .or_default()
             .clone()
     }
+
+    pub fn get_subject_hw_metrics(&self, subject: String) -> Arc<HwSharedDrain> {
+        self.subject_hw_metrics.entry(subject).or_default().clone()
+    }
+}
+
+pub(super) fn drains_to_hw_usage(
+    drains: &DashMap<String, Arc<HwSharedDrain>>,
+) -> HashMap<String, HardwareUsage> {
+    drains
+        .iter()
+        .map(|i| {
+            let key = i.key().clone();
+            let hw_usage = HardwareUsage {
+                cpu: i.get_cpu(),
+                payload_io_read: i.get_payload_io_read(),
+                payload_io_write: i.get_payload_io_write(),
+                payload_index_io_read: i.get_payload_index_io_read(),
+                payload_index_io_write: i.get_payload_index_io_write(),
+                vector_io_read: i.get_vector_io_read(),
+                vector_io_write: i.get_vector_io_write(),
+            };
+            (key, hw_usage)
+        })
+        .collect()
 }
 
 #[derive(Clone)]
@@ -30,10 +60,92 @@ impl RequestHwCounter {
         }
     }
 
+    /// Enable collection of per-stage timings, reported by [`Self::to_rest_profile`].
+    pub fn with_profile(self, enabled: bool) -> Self {
+        Self {
+            counter: self.counter.with_profile(enabled),
+            report_to_api: self.report_to_api,
+        }
+    }
+
+    /// Enable collection of vector index search statistics, reported by
+    /// [`Self::to_rest_search_diagnostics`].
+    pub fn with_search_diagnostics(self, enabled: bool) -> Self {
+        Self {
+            counter: self.counter.with_search_diagnostics(enabled),
+            report_to_api: self.report_to_api,
+        }
+    }
+
     pub fn get_counter(&self) -> HwMeasurementAcc {
         self.counter.clone()
     }
 
+    /// Per-stage timings of the request, if profiling was enabled for it.
+    /// Includes measurements of remote shards, reported in internal query responses.
+    pub fn to_rest_profile(&self) -> Option<api::rest::models::QueryProfile> {
+        let RequestProfileData {
+            filter_evaluation,
+            graph_traversal,
+            payload_fetch,
+            rescoring,
+            shard_merge,
+        } = self.counter.request_profile().data()?;
+
+        Some(api::rest::models::QueryProfile {
+            filter_evaluation: stage_to_rest(filter_evaluation),
+            graph_traversal: stage_to_rest(graph_traversal),
+            payload_fetch: stage_to_rest(payload_fetch),
+            rescoring: stage_to_rest(rescoring),
+            shard_merge: stage_to_rest(shard_merge),
+        })
+    }
+
+    /// Per-stage timings of the request, to be reported back in internal query responses.
+    pub fn to_grpc_profile(&self) -> Option<api::grpc::qdrant::QueryProfileInternal> {
+        self.counter
+            .request_profile()
+            .data()
+            .map(api::grpc::qdrant::QueryProfileInternal::from)
+    }
+
+    /// Statistics of vector index searches, if diagnostics were enabled for the request.
+    pub fn to_rest_search_diagnostics(&self) -> Option<api::rest::models::SearchDiagnostics> {
+        let SearchDiagnosticsData {
+            visited_nodes,
+            scored_vectors,
+            hops_per_layer,
+            early_terminations,
+            graph_searches,
+            plain_searches_exact,
+            plain_searches_index_disabled,
+            plain_searches_small_segment,
+            plain_searches_small_cardinality,
+            plain_searches_not_indexed,
+        } = self.counter.search_diagnostics().data()?;
+
+        Some(api::rest::models::SearchDiagnostics {
+            visited_nodes,
+            scored_vectors,
+            hops_per_layer,
+            early_terminations,
+            graph_searches,
+            plain_searches_exact,
+            plain_searches_index_disabled,
+            plain_searches_small_segment,
+            plain_searches_small_cardinality,
+            plain_searches_not_indexed,
+        })
+    }
+
+    /// Same as [`Self::to_rest_search_diagnostics`], for gRPC responses.
+    pub fn to_grpc_search_diagnostics(&self) -> Option<api::grpc::qdrant::SearchDiagnostics> {
+        self.counter
+            .search_diagnostics()
+            .data()
+            .map(api::grpc::qdrant::SearchDiagnostics::from)
+    }
+
     pub fn to_rest_api(self) -> Option<api::rest::models::HardwareUsage> {
         if self.report_to_api {
             let HardwareData {
@@ -86,3 +198,11 @@ impl RequestHwCounter {
         }
     }
 }
+
+fn stage_to_rest(stage: StageProfile) -> api::rest::models::StageProfile {
+    let StageProfile { time, candidates } = stage;
+    api::rest::models::StageProfile {
+        time: time.as_secs_f64(),
+        candidates,
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -60,6 +61,17 @@ impl Dispatcher {
         self.resharding_enabled
     }
 
+    /// Directory with files, which can be imported by `file://` URLs.
+    /// It is not bound to any collection, so no verification pass is required.
+    pub fn import_path(&self) -> Option<&Path> {
+        self.toc.import_path()
+    }
+
+    /// Directory for temporary files, e.g. downloaded files to import
+    pub fn temp_path(&self) -> Result<PathBuf, StorageError> {
+        Ok(self.toc.optional_temp_or_storage_temp_path()?)
+    }
+
     /// If `wait_timeout` is not supplied - then default duration will be used.
     ///
     /// This function needs to be called from a runtime with timers enabled.
@@ -167,6 +179,9 @@ impl Dispatcher {
                     })
                 }
 
+                // Sync nodes after API key changes, so that all peers accept or reject the key
+                CollectionMetaOperations::ChangeApiKeys(_) => true,
+
                 // TODO(resharding): Do we need/want to synchronize `Resharding` operations?
                 CollectionMetaOperations::Resharding(_, _) => false,
 
@@ -342,4 +357,16 @@ impl Dispatcher {
     pub fn get_collection_hw_metrics(&self, collection: String) -> Arc<HwSharedDrain> {
         self.toc.get_collection_hw_metrics(collection)
     }
+
+    pub fn all_subject_hw_metrics(&self) -> HashMap<String, HardwareUsage> {
+        self.toc.all_subject_hw_metrics()
+    }
+
+    /// Hardware metrics drain of the API key or token subject, which issued the request.
+    /// `None` if the request is not authenticated.
+    #[must_use]
+    pub fn get_subject_hw_metrics(&self, auth: &Auth) -> Option<Arc<HwSharedDrain>> {
+        auth.usage_subject()
+            .map(|subject| self.toc.get_subject_hw_metrics(subject))
+    }
 }
@@ -3,6 +3,7 @@ use shard::operations::point_ops::PointOperations;
 use shard::operations::vector_ops::VectorOperations;
 use shard::operations::{CollectionUpdateOperations, FieldIndexOperations};
 
+use crate::content_manager::api_keys::ApiKeyOperation;
 use crate::content_manager::collection_meta_ops::CollectionMetaOperations;
 
 pub trait AuditableOperation {
@@ -55,6 +56,11 @@ impl AuditableOperation for CollectionMetaOperations {
             CollectionMetaOperations::DropShardKey(_) => "drop_shard_key",
             CollectionMetaOperations::CreatePayloadIndex(_) => "create_payload_index",
             CollectionMetaOperations::DropPayloadIndex(_) => "drop_payload_index",
+            CollectionMetaOperations::ChangeApiKeys(operation) => match operation {
+                ApiKeyOperation::Create(_) => "create_api_key",
+                ApiKeyOperation::Rotate { .. } => "rotate_api_key",
+                ApiKeyOperation::Revoke { .. } => "revoke_api_key",
+            },
             CollectionMetaOperations::Nop { .. } => "nop",
             #[cfg(feature = "staging")]
             CollectionMetaOperations::TestSlowDown(_) => "debug",
@@ -1,6 +1,8 @@
 use chrono::Utc;
 
-use super::{Access, AccessRequirements, AuthType, CollectionMultipass, CollectionPass};
+use super::{
+    Access, AccessRequirements, AuthType, CollectionMultipass, CollectionPass, GlobalAccessMode,
+};
 use crate::audit::{AuditEvent, AuditResult, audit_log, is_audit_enabled};
 use crate::content_manager::errors::StorageError;
 
@@ -57,13 +59,34 @@ impl Auth {
 
     /// Borrow the inner [`Access`] object (e.g. to pass into library code that
     /// still expects `&Access`).
-    pub fn access(&self, method: &str) -> &Access {
+    ///
+    /// `requirements` are not checked here, they only describe the operation in the audit log.
+    pub fn access(&self, requirements: AccessRequirements, method: &str) -> &Access {
         // Gives direct access to the inner `Access` object,
         // but also emits an audit log entry with "ok" status.
-        self.emit_audit(method, None, &Ok(()));
+        self.emit_audit(method, None, requirements.is_write(), &Ok(()));
         &self.access
     }
 
+    /// Name, under which hardware usage of this request is aggregated.
+    ///
+    /// JWT tokens are identified by their `subject` claim, static API keys by their kind,
+    /// and managed API keys by their ID.
+    /// Returns `None` for unauthenticated and internal requests.
+    pub fn usage_subject(&self) -> Option<String> {
+        match self.auth_type {
+            AuthType::Jwt => Some(self.subject.clone().unwrap_or_else(|| "jwt".to_string())),
+            AuthType::ApiKey => match (&self.subject, &self.access) {
+                (Some(key_id), _) => Some(format!("api_key:{key_id}")),
+                (None, Access::Global(GlobalAccessMode::Read)) => {
+                    Some("read_only_api_key".to_string())
+                }
+                (None, _) => Some("api_key".to_string()),
+            },
+            AuthType::None | AuthType::Internal => None,
+        }
+    }
+
     // ------------------------------------------------------------------
     // Wrapped access-check methods with audit logging
     // ------------------------------------------------------------------
@@ -75,7 +98,7 @@ impl Auth {
         method: &str,
     ) -> Result<CollectionMultipass, StorageError> {
         let result = self.access.check_global_access(requirements);
-        self.emit_audit(method, None, &result);
+        self.emit_audit(method, None, requirements.is_write(), &result);
         result
     }
 
@@ -89,7 +112,12 @@ impl Auth {
         let result = self
             .access
             .check_collection_access(collection_name, requirements);
-        self.emit_audit(method, Some(collection_name), &result);
+        self.emit_audit(
+            method,
+            Some(collection_name),
+            requirements.is_write(),
+            &result,
+        );
         result
     }
 
@@ -101,6 +129,7 @@ impl Auth {
         &self,
         method: &str,
         collection: Option<&str>,
+        write: bool,
         result: &Result<T, StorageError>,
     ) {
         if !is_audit_enabled() || self.auth_type == AuthType::Internal {
@@ -120,8 +149,50 @@ impl Auth {
             remote: self.remote.clone(),
             collection: collection.map(String::from),
             tracing_id: self.tracing_id.clone(),
+            write,
             result: audit_result,
             error,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(access: Access, subject: Option<&str>, auth_type: AuthType) -> Auth {
+        Auth::new(access, subject.map(str::to_string), None, auth_type, None)
+    }
+
+    #[test]
+    fn test_usage_subject() {
+        let read = Access::Global(GlobalAccessMode::Read);
+        let manage = Access::Global(GlobalAccessMode::Manage);
+
+        assert_eq!(
+            auth(read.clone(), Some("alice"), AuthType::Jwt).usage_subject(),
+            Some("alice".to_string()),
+        );
+        assert_eq!(
+            auth(read.clone(), None, AuthType::Jwt).usage_subject(),
+            Some("jwt".to_string()),
+        );
+        assert_eq!(
+            auth(read.clone(), None, AuthType::ApiKey).usage_subject(),
+            Some("read_only_api_key".to_string()),
+        );
+        assert_eq!(
+            auth(manage.clone(), None, AuthType::ApiKey).usage_subject(),
+            Some("api_key".to_string()),
+        );
+        assert_eq!(
+            auth(read, Some("key-1"), AuthType::ApiKey).usage_subject(),
+            Some("api_key:key-1".to_string()),
+        );
+        assert_eq!(
+            auth(manage.clone(), None, AuthType::None).usage_subject(),
+            None
+        );
+        assert_eq!(Auth::new_internal(manage).usage_subject(), None);
+    }
+}
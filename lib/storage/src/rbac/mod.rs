@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
+use segment::types::Filter;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors};
@@ -10,8 +11,10 @@ use crate::content_manager::errors::StorageError;
 pub mod auditable_operation;
 pub mod auth;
 mod ops_checks;
+mod tenant;
 
 pub use auth::Auth;
+pub use tenant::TenantConstraint;
 
 /// How the request was authenticated.
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -59,6 +62,15 @@ pub struct CollectionAccess {
     #[deprecated(since = "1.15.0")]
     #[validate(custom(function = "validate_payload_empty"))]
     pub payload: Option<Value>, // Value is a placeholder for a now removed type
+
+    /// Restrict access to points of a single tenant.
+    /// An object where each key is a JSON path, and each value is the value of the tenant,
+    /// e.g. `{"tenant_id": "tenant-1"}`.
+    ///
+    /// The constraint is added to filters of all requests, and is enforced on all writes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(custom(function = "validate_tenant_not_empty"))]
+    pub tenant: Option<TenantConstraint>,
 }
 
 fn validate_payload_empty(_payload: &Value) -> Result<(), ValidationError> {
@@ -71,11 +83,25 @@ fn validate_payload_empty(_payload: &Value) -> Result<(), ValidationError> {
     })
 }
 
+fn validate_tenant_not_empty(tenant: &TenantConstraint) -> Result<(), ValidationError> {
+    if !tenant.is_empty() {
+        return Ok(());
+    }
+    Err(ValidationError {
+        code: Cow::from("empty"),
+        message: Some(Cow::from(
+            "The 'tenant' constraint should contain at least one key",
+        )),
+        params: HashMap::new(),
+    })
+}
+
 impl CollectionAccess {
     fn view(&self) -> CollectionAccessView<'_> {
         CollectionAccessView {
             collection: &self.collection,
             access: self.access,
+            tenant: self.tenant.as_ref(),
         }
     }
 }
@@ -181,6 +207,7 @@ impl CollectionAccessList {
 struct CollectionAccessView<'a> {
     pub collection: &'a str,
     pub access: CollectionAccessMode,
+    pub tenant: Option<&'a TenantConstraint>,
 }
 
 impl CollectionAccessView<'_> {
@@ -192,6 +219,12 @@ impl CollectionAccessView<'_> {
         } = requirements;
 
         if extras {
+            if self.tenant.is_some() {
+                return Err(StorageError::forbidden(format!(
+                    "Only points access is allowed for collection {}",
+                    self.collection,
+                )));
+            }
             match self.access {
                 CollectionAccessMode::Read => {}      // Ok
                 CollectionAccessMode::ReadWrite => {} // Ok
@@ -227,6 +260,24 @@ impl CollectionAccessView<'_> {
         }
         Ok(())
     }
+
+    /// Check that the access is not restricted to points of a tenant.
+    fn check_whole_access(&self) -> Result<(), StorageError> {
+        if self.tenant.is_some() {
+            return Err(StorageError::forbidden(format!(
+                "Access to all points of collection {} is required",
+                self.collection,
+            )));
+        }
+        Ok(())
+    }
+
+    /// Restrict the filter to points of the tenant, if access is restricted to a tenant.
+    fn apply_tenant_filter(&self, filter: &mut Option<Filter>) {
+        if let Some(tenant) = self.tenant {
+            tenant.apply_to(filter);
+        }
+    }
 }
 
 /// Creates [CollectionPass] objects for all collections
@@ -293,6 +344,11 @@ impl AccessRequirements {
             ..*self
         }
     }
+
+    /// Whether the operation modifies data or cluster state.
+    pub fn is_write(&self) -> bool {
+        self.write || self.manage
+    }
 }
 
 impl GlobalAccessMode {
@@ -373,6 +429,18 @@ impl AccessCollectionBuilder {
             },
             #[expect(deprecated)]
             payload: None,
+            tenant: None,
+        });
+        self
+    }
+
+    pub(self) fn add_tenant(mut self, name: &str, tenant: TenantConstraint) -> Self {
+        self.0.push(CollectionAccess {
+            collection: name.to_string(),
+            access: CollectionAccessMode::ReadWrite,
+            #[expect(deprecated)]
+            payload: None,
+            tenant: Some(tenant),
         });
         self
     }
@@ -5,17 +5,27 @@ use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
 use collection::operations::CollectionUpdateOperations;
+use collection::operations::point_ops::{
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal,
+};
 use collection::operations::types::{
     CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal, PointRequestInternal,
     RecommendRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
-    CollectionPrefetch, CollectionQueryRequest,
+    CollectionPrefetch, CollectionQueryRequest, Query,
 };
 use segment::data_types::facets::FacetParams;
+use segment::types::{Filter, PointIdType};
+use shard::operations::payload_ops::PayloadOps;
+use shard::operations::point_ops::PointOperations;
+use shard::operations::vector_ops::VectorOperations;
 use shard::scroll::ScrollRequestInternal;
 
-use super::{Access, AccessRequirements, CollectionAccessList, CollectionPass};
+use super::{
+    Access, AccessRequirements, CollectionAccessList, CollectionAccessView, CollectionPass,
+    TenantConstraint,
+};
 use crate::content_manager::collection_meta_ops::CollectionMetaOperations;
 use crate::content_manager::errors::{StorageError, StorageResult};
 use crate::rbac::auditable_operation::AuditableOperation;
@@ -25,7 +35,7 @@ impl Access {
     pub(crate) fn check_point_op<'a>(
         &self,
         collection_name: &'a str,
-        op: &impl CheckableCollectionOperation,
+        op: &mut impl CheckableCollectionOperation,
     ) -> Result<CollectionPass<'a>, StorageError> {
         let requirements = op.access_requirements();
         match self {
@@ -33,7 +43,7 @@ impl Access {
             Access::Collection(list) => {
                 let view = list.find_view(collection_name)?;
                 view.meets_requirements(requirements)?;
-                op.check_access(list)?;
+                op.check_access(&view, list)?;
             }
         }
         Ok(CollectionPass(Cow::Borrowed(collection_name)))
@@ -52,7 +62,8 @@ impl Access {
             | CollectionMetaOperations::TransferShard(_, _)
             | CollectionMetaOperations::SetShardReplicaState(_)
             | CollectionMetaOperations::CreateShardKey(_)
-            | CollectionMetaOperations::DropShardKey(_) => {
+            | CollectionMetaOperations::DropShardKey(_)
+            | CollectionMetaOperations::ChangeApiKeys(_) => {
                 self.check_global_access(AccessRequirements::new().manage())?;
             }
             CollectionMetaOperations::CreatePayloadIndex(op) => {
@@ -81,7 +92,13 @@ trait CheckableCollectionOperation {
     /// Used to distinguish whether the operation is read-only or read-write.
     fn access_requirements(&self) -> AccessRequirements;
 
-    fn check_access(&self, access: &CollectionAccessList) -> Result<(), StorageError>;
+    /// Check access to other collections, used by the operation.
+    /// If access is restricted to a tenant, the operation is restricted to points of the tenant.
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        access: &CollectionAccessList,
+    ) -> Result<(), StorageError>;
 }
 
 impl CollectionAccessList {
@@ -90,14 +107,16 @@ impl CollectionAccessList {
         lookup_location: &Option<LookupLocation>,
     ) -> Result<(), StorageError> {
         if let Some(lookup_location) = lookup_location {
-            self.find_view(&lookup_location.collection)?;
+            self.find_view(&lookup_location.collection)?
+                .check_whole_access()?;
         }
         Ok(())
     }
 
     fn check_with_lookup(&self, with_lookup: &Option<WithLookup>) -> Result<(), StorageError> {
         if let Some(with_lookup) = with_lookup {
-            self.find_view(&with_lookup.collection_name)?;
+            self.find_view(&with_lookup.collection_name)?
+                .check_whole_access()?;
         }
         Ok(())
     }
@@ -112,7 +131,12 @@ impl CheckableCollectionOperation for SearchRequestInternal {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -126,8 +150,23 @@ impl CheckableCollectionOperation for RecommendRequestInternal {
         }
     }
 
-    fn check_access(&self, access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
         access.check_lookup_from(&self.lookup_from)?;
+
+        let uses_point_ids = self
+            .positive
+            .iter()
+            .chain(&self.negative)
+            .any(|example| example.as_point_id().is_some());
+        if uses_point_ids {
+            view.check_whole_access()?;
+        }
+
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -141,8 +180,13 @@ impl CheckableCollectionOperation for PointRequestInternal {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
-        Ok(())
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        // Points are retrieved by IDs, without any filter to restrict them with
+        view.check_whole_access()
     }
 }
 
@@ -155,7 +199,12 @@ impl CheckableCollectionOperation for CoreSearchRequest {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -169,7 +218,12 @@ impl CheckableCollectionOperation for CountRequestInternal {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -183,11 +237,15 @@ impl CheckableCollectionOperation for GroupRequest {
         }
     }
 
-    fn check_access(&self, access: &CollectionAccessList) -> Result<(), StorageError> {
-        match &self.source {
-            SourceRequest::Search(s) => s.check_access(access)?,
-            SourceRequest::Recommend(r) => r.check_access(access)?,
-            SourceRequest::Query(q) => q.check_access(access)?,
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        match &mut self.source {
+            SourceRequest::Search(s) => s.check_access(view, access)?,
+            SourceRequest::Recommend(r) => r.check_access(view, access)?,
+            SourceRequest::Query(q) => q.check_access(view, access)?,
         }
         access.check_with_lookup(&self.with_lookup)?;
         Ok(())
@@ -203,8 +261,28 @@ impl CheckableCollectionOperation for DiscoverRequestInternal {
         }
     }
 
-    fn check_access(&self, access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
         access.check_lookup_from(&self.lookup_from)?;
+
+        let uses_point_ids = self
+            .target
+            .iter()
+            .chain(
+                self.context
+                    .iter()
+                    .flatten()
+                    .flat_map(|pair| [&pair.positive, &pair.negative]),
+            )
+            .any(|example| example.as_point_id().is_some());
+        if uses_point_ids {
+            view.check_whole_access()?;
+        }
+
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -218,7 +296,12 @@ impl CheckableCollectionOperation for ScrollRequestInternal {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -232,11 +315,17 @@ impl CheckableCollectionOperation for CollectionQueryRequest {
         }
     }
 
-    fn check_access(&self, access: &CollectionAccessList) -> Result<(), StorageError> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
         access.check_lookup_from(&self.lookup_from)?;
+        check_query_point_ids(&self.query, view)?;
+        view.apply_tenant_filter(&mut self.filter);
 
-        for prefetch_query in self.prefetch.iter() {
-            check_access_for_prefetch(prefetch_query, access)?;
+        for prefetch_query in self.prefetch.iter_mut() {
+            check_access_for_prefetch(prefetch_query, view, access)?;
         }
 
         Ok(())
@@ -244,19 +333,36 @@ impl CheckableCollectionOperation for CollectionQueryRequest {
 }
 
 fn check_access_for_prefetch(
-    prefetch: &CollectionPrefetch,
+    prefetch: &mut CollectionPrefetch,
+    view: &CollectionAccessView,
     access: &CollectionAccessList,
 ) -> Result<(), StorageError> {
     access.check_lookup_from(&prefetch.lookup_from)?;
+    check_query_point_ids(&prefetch.query, view)?;
+    view.apply_tenant_filter(&mut prefetch.filter);
 
     // Recurse inner prefetches
-    for prefetch_query in prefetch.prefetch.iter() {
-        check_access_for_prefetch(prefetch_query, access)?;
+    for prefetch_query in prefetch.prefetch.iter_mut() {
+        check_access_for_prefetch(prefetch_query, view, access)?;
     }
 
     Ok(())
 }
 
+/// Queries by point IDs are not restricted by filters, so they require access to all points.
+fn check_query_point_ids(
+    query: &Option<Query>,
+    view: &CollectionAccessView,
+) -> Result<(), StorageError> {
+    if query
+        .as_ref()
+        .is_some_and(|query| !query.get_referenced_ids().is_empty())
+    {
+        view.check_whole_access()?;
+    }
+    Ok(())
+}
+
 impl CheckableCollectionOperation for FacetParams {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -266,7 +372,12 @@ impl CheckableCollectionOperation for FacetParams {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> StorageResult<()> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -280,7 +391,12 @@ impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> StorageResult<()> {
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        view.apply_tenant_filter(&mut self.filter);
         Ok(())
     }
 }
@@ -309,8 +425,145 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
         }
     }
 
-    fn check_access(&self, _access: &CollectionAccessList) -> Result<(), StorageError> {
-        Ok(())
+    fn check_access(
+        &mut self,
+        view: &CollectionAccessView,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        let Some(tenant) = view.tenant else {
+            return Ok(());
+        };
+
+        match self {
+            CollectionUpdateOperations::PointOperation(op) => {
+                restrict_point_operation(op, view, tenant)
+            }
+            CollectionUpdateOperations::VectorOperation(op) => {
+                restrict_vector_operation(op, tenant);
+                Ok(())
+            }
+            CollectionUpdateOperations::PayloadOperation(op) => {
+                restrict_payload_operation(op, view, tenant)
+            }
+            CollectionUpdateOperations::FieldIndexOperation(_) => view.check_whole_access(),
+            #[cfg(feature = "staging")]
+            CollectionUpdateOperations::StagingOperation(_) => view.check_whole_access(),
+        }
+    }
+}
+
+/// Restrict point operation to points of the tenant.
+fn restrict_point_operation(
+    op: &mut PointOperations,
+    view: &CollectionAccessView,
+    tenant: &TenantConstraint,
+) -> Result<(), StorageError> {
+    match op {
+        PointOperations::UpsertPoints(points_op) => {
+            check_points_payload(points_op, tenant)?;
+            // Existing points of other tenants must not be overwritten
+            let points_op = std::mem::replace(
+                points_op,
+                PointInsertOperationsInternal::PointsList(Vec::new()),
+            );
+            *op = PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                condition: tenant.to_filter(),
+                update_mode: None,
+            });
+        }
+        PointOperations::UpsertPointsConditional(conditional_op) => {
+            check_points_payload(&conditional_op.points_op, tenant)?;
+            let condition = std::mem::take(&mut conditional_op.condition);
+            conditional_op.condition = condition.merge_owned(tenant.to_filter());
+        }
+        PointOperations::DeletePoints { ids } => {
+            *op = PointOperations::DeletePointsByFilter(tenant.ids_filter(std::mem::take(ids)));
+        }
+        PointOperations::DeletePointsByFilter(filter) => {
+            *filter = std::mem::take(filter).merge_owned(tenant.to_filter());
+        }
+        PointOperations::SyncPoints(_) => view.check_whole_access()?,
+    }
+    Ok(())
+}
+
+fn check_points_payload(
+    points_op: &PointInsertOperationsInternal,
+    tenant: &TenantConstraint,
+) -> Result<(), StorageError> {
+    match points_op {
+        PointInsertOperationsInternal::PointsBatch(batch) => match &batch.payloads {
+            Some(payloads) => payloads
+                .iter()
+                .try_for_each(|payload| tenant.check_payload(payload.as_ref())),
+            None if batch.ids.is_empty() => Ok(()),
+            None => tenant.check_payload(None),
+        },
+        PointInsertOperationsInternal::PointsList(points) => points
+            .iter()
+            .try_for_each(|point| tenant.check_payload(point.payload.as_ref())),
+    }
+}
+
+/// Restrict vector operation to points of the tenant.
+fn restrict_vector_operation(op: &mut VectorOperations, tenant: &TenantConstraint) {
+    match op {
+        VectorOperations::UpdateVectors(update_op) => {
+            tenant.apply_to(&mut update_op.update_filter);
+        }
+        VectorOperations::DeleteVectors(points, vector_names) => {
+            let filter = tenant.ids_filter(std::mem::take(&mut points.points));
+            *op = VectorOperations::DeleteVectorsByFilter(filter, std::mem::take(vector_names));
+        }
+        VectorOperations::DeleteVectorsByFilter(filter, _) => {
+            *filter = std::mem::take(filter).merge_owned(tenant.to_filter());
+        }
+    }
+}
+
+/// Restrict payload operation to points of the tenant, and keep the tenant's payload values.
+fn restrict_payload_operation(
+    op: &mut PayloadOps,
+    view: &CollectionAccessView,
+    tenant: &TenantConstraint,
+) -> Result<(), StorageError> {
+    match op {
+        PayloadOps::SetPayload(set_op) => {
+            tenant.check_payload_set(&set_op.payload, set_op.key.as_ref())?;
+            restrict_points_selection(&mut set_op.points, &mut set_op.filter, tenant);
+        }
+        PayloadOps::OverwritePayload(set_op) => {
+            match &set_op.key {
+                Some(key) => tenant.check_payload_set(&set_op.payload, Some(key))?,
+                None => tenant.check_payload(Some(&set_op.payload))?,
+            }
+            restrict_points_selection(&mut set_op.points, &mut set_op.filter, tenant);
+        }
+        PayloadOps::DeletePayload(delete_op) => {
+            tenant.check_payload_delete(&delete_op.keys)?;
+            restrict_points_selection(&mut delete_op.points, &mut delete_op.filter, tenant);
+        }
+        // Clearing payload removes the tenant's values too
+        PayloadOps::ClearPayload { .. } | PayloadOps::ClearPayloadByFilter(_) => {
+            view.check_whole_access()?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace selection of points by IDs with a filter, and restrict it to points of the tenant.
+///
+/// Selection by IDs takes precedence over the filter, so both are combined into one filter.
+fn restrict_points_selection(
+    points: &mut Option<Vec<PointIdType>>,
+    filter: &mut Option<Filter>,
+    tenant: &TenantConstraint,
+) {
+    if let Some(ids) = points.take() {
+        *filter = Some(tenant.ids_filter(ids));
+    } else {
+        tenant.apply_to(filter);
     }
 }
 
@@ -327,11 +580,16 @@ impl Auth {
     pub(crate) fn check_point_op<'a>(
         &self,
         collection_name: &'a str,
-        op: &impl CheckableCollectionOperation,
+        op: &mut impl CheckableCollectionOperation,
         method: &str,
     ) -> Result<CollectionPass<'a>, StorageError> {
         let result = self.unlogged_access().check_point_op(collection_name, op);
-        self.emit_audit(method, Some(collection_name), &result);
+        self.emit_audit(
+            method,
+            Some(collection_name),
+            op.access_requirements().is_write(),
+            &result,
+        );
         result
     }
 
@@ -343,7 +601,8 @@ impl Auth {
         let result = self
             .unlogged_access()
             .check_collection_meta_operation(operation);
-        self.emit_audit(operation.operation_name(), None, &result);
+        let write = !matches!(operation, CollectionMetaOperations::Nop { .. });
+        self.emit_audit(operation.operation_name(), None, write, &result);
         result
     }
 }
@@ -398,7 +657,24 @@ mod tests_ops {
         op: &Op,
         access: &Access,
     ) {
-        access.check_point_op("col", op).expect("Should be allowed");
+        let mut op_actual = op.clone();
+        access
+            .check_point_op("col", &mut op_actual)
+            .expect("Should be allowed");
+        assert_eq!(op, &op_actual, "Expected op to stay unchanged");
+    }
+
+    /// Operation is allowed with the given access, and is rewritten into the expected one.
+    fn assert_rewritten<Op: Debug + Clone + PartialEq + CheckableCollectionOperation>(
+        op: &Op,
+        access: &Access,
+        expected: &Op,
+    ) {
+        let mut op_actual = op.clone();
+        access
+            .check_point_op("col", &mut op_actual)
+            .expect("Should be allowed");
+        assert_eq!(expected, &op_actual, "Expected op to be rewritten");
     }
 
     /// Operation is forbidden with the given access.
@@ -407,7 +683,7 @@ mod tests_ops {
         access: &Access,
     ) {
         access
-            .check_point_op("col", op)
+            .check_point_op("col", &mut op.clone())
             .expect_err("should be forbidden");
     }
 
@@ -476,6 +752,7 @@ mod tests_ops {
             ids: vec![PointIdType::NumId(12345)],
             with_payload: None,
             with_vector: WithVector::Bool(true),
+            at_version: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -718,6 +995,7 @@ mod tests_ops {
                                 id: ExtendedPointId::NumId(12345),
                                 vector: VectorStructPersisted::Single(vec![0.0, 1.0, 2.0]),
                                 payload: None,
+                                expires_at: None,
                             }])
                         }
                     };
@@ -733,6 +1011,7 @@ mod tests_ops {
                     id: ExtendedPointId::NumId(12345),
                     vector: VectorStructPersisted::Single(vec![0.0, 1.0, 2.0]),
                     payload: None,
+                    expires_at: None,
                 }]);
 
                 let filter = make_filter_from_ids(vec![ExtendedPointId::NumId(12345)]);
@@ -901,4 +1180,182 @@ mod tests_ops {
             );
         }
     }
+
+    fn tenant_access() -> Access {
+        let tenant = serde_json::from_value(serde_json::json!({ "tenant_id": "a" })).unwrap();
+        AccessCollectionBuilder::new()
+            .add_tenant("col", tenant)
+            .into()
+    }
+
+    fn tenant_filter() -> Filter {
+        serde_json::from_value(serde_json::json!({
+            "must": [{ "key": "tenant_id", "match": { "value": "a" } }]
+        }))
+        .unwrap()
+    }
+
+    fn tenant_payload(tenant_id: &str) -> Payload {
+        serde_json::from_value(serde_json::json!({ "tenant_id": tenant_id })).unwrap()
+    }
+
+    #[test]
+    fn test_tenant_read_requests() {
+        let access = tenant_access();
+
+        let op = CountRequestInternal {
+            filter: None,
+            exact: false,
+        };
+        assert_rewritten(
+            &op,
+            &access,
+            &CountRequestInternal {
+                filter: Some(tenant_filter()),
+                ..op.clone()
+            },
+        );
+
+        let user_filter = make_filter_from_ids(vec![ExtendedPointId::NumId(1)]);
+        let op = ScrollRequestInternal {
+            offset: None,
+            limit: Some(10),
+            filter: Some(user_filter.clone()),
+            with_payload: None,
+            with_vector: WithVector::Bool(false),
+            order_by: None,
+        };
+        assert_rewritten(
+            &op,
+            &access,
+            &ScrollRequestInternal {
+                filter: Some(user_filter.merge(&tenant_filter())),
+                ..op.clone()
+            },
+        );
+
+        // Points can't be retrieved by IDs
+        assert_forbidden(
+            &PointRequestInternal {
+                ids: vec![PointIdType::NumId(12345)],
+                with_payload: None,
+                with_vector: WithVector::Bool(true),
+                at_version: None,
+            },
+            &access,
+        );
+    }
+
+    #[test]
+    fn test_tenant_upsert_points() {
+        let access = tenant_access();
+
+        let make_op = |payload: Option<Payload>| {
+            PointInsertOperationsInternal::PointsList(vec![PointStructPersisted {
+                id: ExtendedPointId::NumId(12345),
+                vector: VectorStructPersisted::Single(vec![0.0, 1.0, 2.0]),
+                payload,
+                expires_at: None,
+            }])
+        };
+
+        let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            make_op(Some(tenant_payload("a"))),
+        ));
+        assert_rewritten(
+            &op,
+            &access,
+            &CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+                ConditionalInsertOperationInternal {
+                    points_op: make_op(Some(tenant_payload("a"))),
+                    condition: tenant_filter(),
+                    update_mode: None,
+                },
+            )),
+        );
+
+        for payload in [None, Some(tenant_payload("b"))] {
+            let op = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                make_op(payload),
+            ));
+            assert_forbidden(&op, &access);
+        }
+    }
+
+    #[test]
+    fn test_tenant_delete_points() {
+        let access = tenant_access();
+
+        let op = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: vec![ExtendedPointId::NumId(12345)],
+        });
+        assert_rewritten(
+            &op,
+            &access,
+            &CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+                make_filter_from_ids(vec![ExtendedPointId::NumId(12345)]).merge(&tenant_filter()),
+            )),
+        );
+    }
+
+    #[test]
+    fn test_tenant_payload_operations() {
+        let access = tenant_access();
+
+        let op =
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: serde_json::from_value(serde_json::json!({ "x": 1 })).unwrap(),
+                points: Some(vec![ExtendedPointId::NumId(12345)]),
+                filter: None,
+                key: None,
+            }));
+        assert_rewritten(
+            &op,
+            &access,
+            &CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: serde_json::from_value(serde_json::json!({ "x": 1 })).unwrap(),
+                points: None,
+                filter: Some(
+                    make_filter_from_ids(vec![ExtendedPointId::NumId(12345)])
+                        .merge(&tenant_filter()),
+                ),
+                key: None,
+            })),
+        );
+
+        // Tenant's payload can't be changed or removed
+        assert_forbidden(
+            &CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: tenant_payload("b"),
+                points: None,
+                filter: None,
+                key: None,
+            })),
+            &access,
+        );
+        assert_forbidden(
+            &CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(
+                DeletePayloadOp {
+                    keys: vec!["tenant_id".parse().unwrap()],
+                    points: None,
+                    filter: None,
+                },
+            )),
+            &access,
+        );
+        assert_forbidden(
+            &CollectionUpdateOperations::PayloadOperation(PayloadOps::ClearPayload {
+                points: vec![ExtendedPointId::NumId(12345)],
+            }),
+            &access,
+        );
+    }
+
+    #[test]
+    fn test_tenant_field_index() {
+        let op = CollectionUpdateOperations::FieldIndexOperation(
+            FieldIndexOperations::DeleteIndex("path".parse().unwrap()),
+        );
+        assert_forbidden(&op, &tenant_access());
+    }
 }
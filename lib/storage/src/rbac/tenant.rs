@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use ahash::AHashSet;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, FieldCondition, Filter, HasIdCondition, Payload, PointIdType, ValueVariants,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::content_manager::errors::StorageError;
+
+/// Payload constraint of a tenant.
+///
+/// An object where each key is a JSON path, and each value is the value which payload of
+/// accessible points must have at this path, e.g. `{"tenant_id": "tenant-1"}`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct TenantConstraint(pub BTreeMap<JsonPath, ValueVariants>);
+
+impl TenantConstraint {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Filter, matching only points of the tenant.
+    pub fn to_filter(&self) -> Filter {
+        let conditions = self
+            .0
+            .iter()
+            .map(|(path, value)| {
+                Condition::Field(FieldCondition::new_match(
+                    path.clone(),
+                    value.clone().into(),
+                ))
+            })
+            .collect();
+
+        Filter {
+            must: Some(conditions),
+            ..Default::default()
+        }
+    }
+
+    /// Restrict the filter to points of the tenant.
+    pub fn apply_to(&self, filter: &mut Option<Filter>) {
+        // Tenant filter has only `must` conditions, so merging is equivalent to an intersection
+        let tenant_filter = self.to_filter();
+        *filter = Some(match filter.take() {
+            Some(filter) => filter.merge_owned(tenant_filter),
+            None => tenant_filter,
+        });
+    }
+
+    /// Filter, matching the given points, if they belong to the tenant.
+    pub fn ids_filter(&self, ids: Vec<PointIdType>) -> Filter {
+        let has_id = HasIdCondition::from(ids.into_iter().collect::<AHashSet<_>>());
+        Filter::new_must(Condition::HasId(has_id)).merge_owned(self.to_filter())
+    }
+
+    /// Check that a point with the given payload belongs to the tenant.
+    pub fn check_payload(&self, payload: Option<&Payload>) -> Result<(), StorageError> {
+        for (path, value) in &self.0 {
+            if !payload.is_some_and(|payload| has_value(payload, path, value)) {
+                return Err(StorageError::forbidden(format!(
+                    "Payload of points must have value {} at {path}",
+                    value.to_value(),
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that setting the payload keeps the constrained values of points.
+    pub fn check_payload_set(
+        &self,
+        payload: &Payload,
+        key: Option<&JsonPath>,
+    ) -> Result<(), StorageError> {
+        for (path, value) in &self.0 {
+            if !path.is_affected_by_value_set(&payload.0, key) {
+                continue;
+            }
+            // Top-level keys are replaced as a whole, so the new value is known
+            if key.is_none() && has_value(payload, path, value) {
+                continue;
+            }
+            return Err(StorageError::forbidden(format!(
+                "Payload key {path} can't be changed with the given access",
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check that deleting the payload keys keeps the constrained values of points.
+    pub fn check_payload_delete(&self, keys: &[JsonPath]) -> Result<(), StorageError> {
+        for path in self.0.keys() {
+            if keys.iter().any(|key| path.is_affected_by_value_remove(key)) {
+                return Err(StorageError::forbidden(format!(
+                    "Payload key {path} can't be deleted with the given access",
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn has_value(payload: &Payload, path: &JsonPath, value: &ValueVariants) -> bool {
+    let expected = value.to_value();
+    let values = path.value_get(&payload.0);
+    !values.is_empty() && values.iter().all(|value| **value == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn constraint() -> TenantConstraint {
+        serde_json::from_value(json!({ "tenant_id": "a" })).unwrap()
+    }
+
+    fn payload(value: serde_json::Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_check_payload() {
+        let constraint = constraint();
+
+        assert!(
+            constraint
+                .check_payload(Some(&payload(json!({ "tenant_id": "a", "x": 1 }))))
+                .is_ok(),
+        );
+        assert!(
+            constraint
+                .check_payload(Some(&payload(json!({ "tenant_id": "b" }))))
+                .is_err(),
+        );
+        assert!(
+            constraint
+                .check_payload(Some(&payload(json!({ "x": 1 }))))
+                .is_err(),
+        );
+        assert!(constraint.check_payload(None).is_err());
+    }
+
+    #[test]
+    fn test_check_payload_set() {
+        let constraint = constraint();
+
+        assert!(
+            constraint
+                .check_payload_set(&payload(json!({ "x": 1 })), None)
+                .is_ok(),
+        );
+        assert!(
+            constraint
+                .check_payload_set(&payload(json!({ "tenant_id": "a" })), None)
+                .is_ok(),
+        );
+        assert!(
+            constraint
+                .check_payload_set(&payload(json!({ "tenant_id": "b" })), None)
+                .is_err(),
+        );
+        assert!(
+            constraint
+                .check_payload_set(&payload(json!({ "x": 1 })), Some(&"x".parse().unwrap()))
+                .is_ok(),
+        );
+        assert!(
+            constraint
+                .check_payload_set(&payload(json!({})), Some(&"tenant_id".parse().unwrap()))
+                .is_err(),
+        );
+    }
+
+    #[test]
+    fn test_check_payload_delete() {
+        let constraint = constraint();
+
+        assert!(
+            constraint
+                .check_payload_delete(&["x".parse().unwrap()])
+                .is_ok()
+        );
+        assert!(
+            constraint
+                .check_payload_delete(&["tenant_id".parse().unwrap()])
+                .is_err()
+        );
+    }
+}
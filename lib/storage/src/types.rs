@@ -1,9 +1,11 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::common::object_storage::ObjectStorageConfig;
 use collection::common::snapshots_manager::SnapshotsConfig;
 use collection::config::{WalConfig, default_on_disk_payload};
 use collection::operations::config_diff::OptimizersConfigDiff;
@@ -19,7 +21,10 @@ use common::mmap;
 use schemars::JsonSchema;
 use segment::common::anonymize::{Anonymize, anonymize_collection_values};
 use segment::data_types::collection_defaults::CollectionConfigDefaults;
+use segment::index::hnsw_index::appendable_graph::AppendableHnswConfig;
 use segment::types::{HnswConfig, HnswGlobalConfig};
+use segment::vector_storage::background_populate::BackgroundPopulateConfig;
+use segment::vector_storage::dense::hot_vectors_cache::HotVectorsCacheConfig;
 use serde::{Deserialize, Serialize};
 use tonic::transport::Uri;
 use validator::{Validate, ValidationError};
@@ -42,6 +47,12 @@ pub struct PerformanceConfig {
     /// If positive - use this absolute number of CPUs.
     #[serde(default)]
     pub optimizer_cpu_budget: isize,
+    /// If true - threads of index building are pinned to NUMA nodes, and their memory is
+    /// allocated on the node they run on. Reduces cross-socket memory traffic on multi-socket
+    /// machines, has no effect on machines with a single NUMA node.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer_numa_aware: Option<bool>,
     /// IO budget, how many parallel IO operations to allow for an optimization job.
     /// IO usage per optimization job is equivalent to number of indexing threads.
     /// If 0 - auto selection, one IO operation per each CPU.
@@ -54,6 +65,24 @@ pub struct PerformanceConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub async_scorer: Option<bool>,
+    /// If provided - in-RAM vector storages are populated in background after loading,
+    /// instead of blocking the load until all their pages are resident.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_populate: Option<BackgroundPopulateConfig>,
+    /// If provided - maximum memory in megabytes for in-RAM vector storages of all collections.
+    /// Once exceeded, new segments keep their vectors in mmap instead of RAM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_budget_mb: Option<usize>,
+    /// If provided - frequently read vectors of on-disk dense vector storages are cached in RAM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hot_vectors_cache: Option<HotVectorsCacheConfig>,
+    /// If true - appendable segments maintain an index of exact duplicates of their vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_dedup_index: Option<bool>,
+    /// If provided - appendable segments link inserted vectors into an HNSW graph, so that they
+    /// are searched approximately before the optimizer indexes the segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appendable_hnsw: Option<AppendableHnswConfig>,
     #[serde(default, flatten)]
     pub load_concurrency: LoadConcurrencyConfig,
 }
@@ -72,9 +101,23 @@ pub struct StorageConfig {
     pub snapshots_path: PathBuf,
     #[serde(default)]
     pub snapshots_config: SnapshotsConfig,
+    /// Credentials and retry policy of the object storage providers, used by snapshots and
+    /// WAL archiving.
+    #[serde(default)]
+    pub object_storage: ObjectStorageConfig,
     #[validate(custom(function = validate_path))]
     #[serde(default)]
     pub temp_path: Option<PathBuf>,
+    /// Directory with files, which can be imported into collections by `file://` URLs.
+    /// If not set, points can only be imported from remote URLs.
+    #[validate(custom(function = validate_path))]
+    #[serde(default)]
+    pub import_path: Option<PathBuf>,
+    /// Directory, where collections are exported as datasets.
+    /// If not set, export of datasets is disabled.
+    #[validate(custom(function = validate_path))]
+    #[serde(default)]
+    pub export_path: Option<PathBuf>,
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
     #[validate(nested)]
@@ -115,6 +158,13 @@ pub struct StorageConfig {
     /// Maximum number of collections to allow in the cluster.
     #[serde(default)]
     pub max_collections: Option<usize>,
+    /// If provided - recall of approximate search is periodically measured for all collections.
+    #[validate(nested)]
+    #[serde(default)]
+    pub recall_estimation: Option<RecallEstimationConfig>,
+    /// If provided - values of the listed payload fields are encrypted at rest.
+    #[serde(default)]
+    pub payload_encryption: Option<PayloadEncryptionConfig>,
 }
 
 impl StorageConfig {
@@ -134,6 +184,7 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.object_storage.clone(),
             self.hnsw_global_config.clone(),
             self.performance.load_concurrency.clone(),
             common::defaults::search_thread_count(self.performance.max_search_threads),
@@ -141,6 +192,55 @@ impl StorageConfig {
     }
 }
 
+/// Configuration of the background job, which compares approximate search results with
+/// exact search results on randomly sampled points.
+#[derive(Debug, Deserialize, Serialize, Clone, Validate)]
+pub struct RecallEstimationConfig {
+    /// Interval between estimations, in seconds.
+    #[serde(default = "default_recall_estimation_interval_sec")]
+    #[validate(range(min = 1))]
+    pub interval_sec: u64,
+    /// Number of points to sample as queries, for each vector of a collection.
+    #[serde(default = "default_recall_estimation_sample_size")]
+    #[validate(range(min = 1))]
+    pub sample_size: usize,
+    /// Number of results to compare, for each query.
+    #[serde(default = "default_recall_estimation_limit")]
+    #[validate(range(min = 1))]
+    pub limit: usize,
+}
+
+/// Configuration of payload fields, which are stored encrypted.
+#[derive(Deserialize, Clone)]
+pub struct PayloadEncryptionConfig {
+    /// 256-bit encryption key, as 64 hexadecimal characters.
+    pub key: String,
+    /// Top-level payload keys to encrypt, by collection name.
+    #[serde(default)]
+    pub collections: HashMap<String, Vec<String>>,
+}
+
+impl fmt::Debug for PayloadEncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadEncryptionConfig")
+            .field("key", &"<redacted>")
+            .field("collections", &self.collections)
+            .finish()
+    }
+}
+
+const fn default_recall_estimation_interval_sec() -> u64 {
+    60 * 60 // One hour
+}
+
+const fn default_recall_estimation_sample_size() -> usize {
+    10
+}
+
+const fn default_recall_estimation_limit() -> usize {
+    10
+}
+
 fn default_snapshots_path() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOTS_PATH)
 }
@@ -29,9 +29,12 @@ fn test_alias_operation() {
 
     let config = StorageConfig {
         storage_path: storage_dir.path().to_path_buf(),
-        snapshots_path: storage_dir.path().join("snapshots"),
+        snapshots_config: Default::default(),
+        object_storage: Default::default(),
         snapshots_config: Default::default(),
         temp_path: None,
+        import_path: None,
+        export_path: None,
         on_disk_payload: false,
         optimizers: OptimizersConfig {
             deleted_threshold: 0.5,
@@ -44,6 +47,8 @@ fn test_alias_operation() {
             flush_interval_sec: 2,
             max_optimization_threads: Some(2),
             prevent_unoptimized: None,
+            segment_retention_sec: None,
+            graph_heal_threshold: None,
         },
         optimizers_overwrite: None,
         wal: Default::default(),
@@ -51,12 +56,18 @@ fn test_alias_operation() {
             max_search_threads: 1,
             max_optimization_runtime_threads: 1,
             optimizer_cpu_budget: 0,
+            optimizer_numa_aware: None,
             optimizer_io_budget: 0,
             update_rate_limit: None,
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
             async_scorer: None,
+            background_populate: None,
+            memory_budget_mb: None,
+            hot_vectors_cache: None,
+            vector_dedup_index: None,
+            appendable_hnsw: None,
             load_concurrency: LoadConcurrencyConfig::default(),
         },
         hnsw_index: Default::default(),
@@ -71,6 +82,8 @@ fn test_alias_operation() {
         shard_transfer_method: None,
         collection: None,
         max_collections: None,
+        recall_estimation: None,
+        payload_encryption: None,
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -83,16 +96,19 @@ fn test_alias_operation() {
     let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
     let propose_operation_sender = OperationSender::new(propose_sender);
 
-    let toc = Arc::new(TableOfContent::new(
-        &config,
-        search_runtime,
-        update_runtime,
-        general_runtime,
-        ResourceBudget::default(),
-        ChannelService::new(6333, false, None, None),
-        0,
-        Some(propose_operation_sender),
-    ));
+    let toc = Arc::new(
+        TableOfContent::new(
+            &config,
+            search_runtime,
+            update_runtime,
+            general_runtime,
+            ResourceBudget::default(),
+            ChannelService::new(6333, false, None, None),
+            0,
+            Some(propose_operation_sender),
+        )
+        .unwrap(),
+    );
     let dispatcher = Dispatcher::new(toc);
 
     handle
@@ -462,6 +462,17 @@ impl Wal {
         &self.path
     }
 
+    /// Paths of the non-empty closed segment files, along with the index of their first entry.
+    ///
+    /// Closed segments are never appended to, so their files can be shipped elsewhere (e.g. for
+    /// archiving) while the log is in use.
+    pub fn closed_segment_files(&self) -> impl Iterator<Item = (u64, &Path)> {
+        self.closed_segments
+            .iter()
+            .filter(|segment| !segment.segment.is_empty())
+            .map(|segment| (segment.start_index, segment.segment.path()))
+    }
+
     pub fn num_segments(&self) -> usize {
         self.closed_segments.len() + 1
     }
@@ -1080,6 +1091,53 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_closed_segment_files() {
+        init_logger();
+        let dir = Builder::new().prefix("wal").tempdir().unwrap();
+        // 2 entries should fit in each segment
+        let mut wal = Wal::with_options(
+            dir.path(),
+            &WalOptions {
+                segment_capacity: 4096,
+                segment_queue_len: 3,
+                retain_closed: NonZeroUsize::new(1).unwrap(),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(wal.closed_segment_files().count(), 0);
+
+        let entry: [u8; 2000] = [42u8; 2000];
+        for i in 0..7 {
+            assert_eq!(i, wal.append(&&entry[..]).unwrap());
+        }
+
+        let closed: Vec<_> = wal
+            .closed_segment_files()
+            .map(|(start_index, path)| (start_index, path.to_path_buf()))
+            .collect();
+        assert_eq!(
+            closed.iter().map(|(start, _)| *start).collect::<Vec<_>>(),
+            vec![0, 2, 4],
+        );
+        for (start_index, path) in &closed {
+            assert!(path.exists());
+            assert_eq!(
+                path.file_name().unwrap().to_str().unwrap(),
+                format!("closed-{start_index}"),
+            );
+        }
+
+        wal.prefix_truncate(4).unwrap();
+        assert_eq!(
+            wal.closed_segment_files()
+                .map(|(start, _)| start)
+                .collect::<Vec<_>>(),
+            vec![4],
+        );
+    }
+
     fn run_test_with_retain_closed(retain_closed: usize) {
         init_logger();
         let num_entries = 10;
@@ -3,9 +3,11 @@ use std::sync::Arc;
 
 use actix_web::Error;
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
 use futures_util::future::LocalBoxFuture;
 use parking_lot::Mutex;
 
+use crate::common::request_id::{self, REQUEST_ID_HEADER};
 use crate::common::telemetry_ops::requests_telemetry::{
     ActixTelemetryCollector, ActixWorkerTelemetryCollector,
 };
@@ -40,14 +42,48 @@ where
             .match_pattern()
             .unwrap_or_else(|| "unknown".to_owned());
 
+        let request_id = request_id::request_id(
+            request
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let request_span = request_id::request_span(&request_id);
+
+        #[cfg(feature = "tracing")]
+        let span = {
+            let span = request_span.in_scope(|| {
+                tracing::info_span!(
+                    "http_request",
+                    method = %request.method(),
+                    route = %match_pattern,
+                )
+            });
+            #[cfg(feature = "otlp")]
+            crate::tracing::otlp::set_parent_from_headers(
+                &span,
+                request
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_bytes())),
+            );
+            span
+        };
+
         let request_key = format!("{} {}", request.method(), match_pattern);
-        let future = self.service.call(request);
+        let future = request_span.in_scope(|| self.service.call(request));
         let telemetry_data = self.telemetry_data.clone();
-        Box::pin(async move {
+        let future = async move {
             let instant = std::time::Instant::now();
-            let response = future.await?;
+            let mut response = future.await?;
             let status = response.response().status().as_u16();
 
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+
             let collection_name = response
                 .request()
                 .match_info()
@@ -58,7 +94,13 @@ where
                 .lock()
                 .add_response(request_key, status, instant, collection_name);
             Ok(response)
-        })
+        };
+
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        let future = tracing::Instrument::instrument(future, request_span);
+
+        Box::pin(future)
     }
 }
 
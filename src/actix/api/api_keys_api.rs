@@ -0,0 +1,106 @@
+use actix_web::{HttpResponse, delete, get, post, web};
+use actix_web_validator::Json;
+use collection::operations::verification::new_unchecked_verification_pass;
+use storage::content_manager::api_keys::{
+    ApiKeyOperation, CreateApiKey, CreatedApiKey, RotateApiKey,
+};
+use storage::content_manager::collection_meta_ops::CollectionMetaOperations;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use uuid::Uuid;
+
+use crate::actix::auth::ActixAuth;
+use crate::actix::helpers;
+
+#[get("/api_keys")]
+async fn list_api_keys(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAuth(auth): ActixAuth,
+) -> HttpResponse {
+    let pass = new_unchecked_verification_pass();
+    helpers::time(async move {
+        auth.check_global_access(AccessRequirements::new().manage(), "list_api_keys")?;
+        Ok(dispatcher.toc(&auth, &pass).api_keys().list())
+    })
+    .await
+}
+
+#[post("/api_keys")]
+async fn create_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAuth(auth): ActixAuth,
+    request: Json<CreateApiKey>,
+) -> HttpResponse {
+    helpers::time(async move {
+        let (operation, created) = ApiKeyOperation::create(request.into_inner())?;
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::ChangeApiKeys(operation),
+                auth,
+                None,
+            )
+            .await?;
+        Ok(created)
+    })
+    .await
+}
+
+#[post("/api_keys/{key_id}/rotate")]
+async fn rotate_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAuth(auth): ActixAuth,
+    key_id: web::Path<Uuid>,
+    request: Json<RotateApiKey>,
+) -> HttpResponse {
+    let pass = new_unchecked_verification_pass();
+    helpers::time(async move {
+        let id = key_id.into_inner();
+        let (operation, key) = ApiKeyOperation::rotate(id, request.into_inner());
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::ChangeApiKeys(operation),
+                auth.clone(),
+                None,
+            )
+            .await?;
+
+        // Operation is already applied on this peer
+        let info = dispatcher
+            .toc(&auth, &pass)
+            .api_keys()
+            .get(id)
+            .ok_or_else(|| StorageError::not_found(format!("API key {id} not found")))?;
+        Ok(CreatedApiKey { info, key })
+    })
+    .await
+}
+
+#[delete("/api_keys/{key_id}")]
+async fn revoke_api_key(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAuth(auth): ActixAuth,
+    key_id: web::Path<Uuid>,
+) -> HttpResponse {
+    helpers::time(async move {
+        let operation = ApiKeyOperation::Revoke {
+            id: key_id.into_inner(),
+        };
+        dispatcher
+            .submit_collection_meta_op(
+                CollectionMetaOperations::ChangeApiKeys(operation),
+                auth,
+                None,
+            )
+            .await
+    })
+    .await
+}
+
+// Configure services
+pub fn config_api_keys_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_api_keys)
+        .service(create_api_key)
+        .service(rotate_api_key)
+        .service(revoke_api_key);
+}
@@ -6,12 +6,15 @@ use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::types::CollectionError;
 use collection::operations::verification::new_unchecked_verification_pass;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::PointIdType;
 use serde::Deserialize;
 use shard::operations::optimization::OptimizationsRequestOptions;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
     DeleteCollectionOperation, UpdateCollection, UpdateCollectionOperation,
 };
+use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::AccessRequirements;
 use validator::Validate;
@@ -297,6 +300,154 @@ fn get_optimizations(
     })
 }
 
+#[get("/collections/{collection_name}/memory")]
+fn get_memory_usage(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let pass = new_unchecked_verification_pass();
+        let collection_pass = auth.check_collection_access(
+            &collection.collection_name,
+            AccessRequirements::new(),
+            "get_memory_usage",
+        )?;
+        Ok(dispatcher
+            .toc(&auth, &pass)
+            .get_collection(&collection_pass)
+            .await?
+            .memory_usage()
+            .await?)
+    })
+}
+
+#[get("/collections/{collection_name}/disk")]
+fn get_disk_usage(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let pass = new_unchecked_verification_pass();
+        let collection_pass = auth.check_collection_access(
+            &collection.collection_name,
+            AccessRequirements::new(),
+            "get_disk_usage",
+        )?;
+        Ok(dispatcher
+            .toc(&auth, &pass)
+            .get_collection(&collection_pass)
+            .await?
+            .disk_usage()
+            .await?)
+    })
+}
+
+#[post("/collections/{collection_name}/requantize")]
+fn requantize_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let pass = new_unchecked_verification_pass();
+        let collection_pass = auth.check_collection_access(
+            &collection.collection_name,
+            AccessRequirements::new().manage(),
+            "requantize_collection",
+        )?;
+        let started_shards = dispatcher
+            .toc(&auth, &pass)
+            .get_collection(&collection_pass)
+            .await?
+            .requantize()
+            .await;
+        Ok(started_shards > 0)
+    })
+}
+
+#[derive(Deserialize, Validate)]
+struct QuantizationQualityParam {
+    #[validate(range(min = 1, max = 1000))]
+    sample: Option<usize>,
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<usize>,
+}
+
+const DEFAULT_QUANTIZATION_QUALITY_SAMPLE: usize = 100;
+const DEFAULT_QUANTIZATION_QUALITY_LIMIT: usize = 10;
+
+#[get("/collections/{collection_name}/quantization/quality")]
+fn get_quantization_quality(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<QuantizationQualityParam>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let pass = new_unchecked_verification_pass();
+        let collection_pass = auth.check_collection_access(
+            &collection.collection_name,
+            AccessRequirements::new(),
+            "get_quantization_quality",
+        )?;
+        let sample = params.sample.unwrap_or(DEFAULT_QUANTIZATION_QUALITY_SAMPLE);
+        let limit = params.limit.unwrap_or(DEFAULT_QUANTIZATION_QUALITY_LIMIT);
+        Ok(dispatcher
+            .toc(&auth, &pass)
+            .get_collection(&collection_pass)
+            .await?
+            .quantization_quality(sample, limit)
+            .await?)
+    })
+}
+
+#[derive(Deserialize, Validate)]
+struct QuantizedPointPath {
+    #[validate(length(min = 1))]
+    id: String,
+}
+
+#[derive(Deserialize, Validate)]
+struct QuantizedVectorParam {
+    #[validate(length(min = 1))]
+    vector: Option<String>,
+}
+
+#[get("/collections/{collection_name}/quantization/points/{id}")]
+fn get_quantized_vector(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<QuantizedPointPath>,
+    params: Query<QuantizedVectorParam>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let pass = new_unchecked_verification_pass();
+        let collection_pass = auth.check_collection_access(
+            &collection.collection_name,
+            AccessRequirements::new(),
+            "get_quantized_vector",
+        )?;
+        let Ok(point_id) = point.id.parse::<PointIdType>() else {
+            return Err(StorageError::BadInput {
+                description: format!("Can not recognize \"{}\" as point id", point.id),
+            });
+        };
+        let vector_name = params
+            .into_inner()
+            .vector
+            .unwrap_or_else(|| DEFAULT_VECTOR_NAME.to_owned());
+        Ok(dispatcher
+            .toc(&auth, &pass)
+            .get_collection(&collection_pass)
+            .await?
+            .quantized_vector(vector_name, point_id)
+            .await?)
+    })
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -312,6 +463,11 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_collection_aliases)
         .service(get_cluster_info)
         .service(get_optimizations)
+        .service(get_memory_usage)
+        .service(get_disk_usage)
+        .service(requantize_collection)
+        .service(get_quantization_quality)
+        .service(get_quantized_vector)
         .service(update_collection_cluster);
 }
 
@@ -48,6 +48,7 @@ async fn count_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -51,6 +51,7 @@ async fn discover_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -107,6 +108,7 @@ async fn discover_batch_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
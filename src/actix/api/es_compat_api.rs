@@ -0,0 +1,68 @@
+use actix_web::{HttpResponse, post, web};
+use actix_web_validator::{Json, Path, Query};
+use storage::dispatcher::Dispatcher;
+use tokio::time::Instant;
+
+use super::CollectionPath;
+use super::read_params::ReadParams;
+use super::retrieve_api::do_scroll_points_response;
+use super::search_api::do_search_points_response;
+use crate::actix::auth::ActixAuth;
+use crate::actix::helpers::process_response_error;
+use crate::common::es_query::{EsScrollRequest, EsSearchRequest};
+use crate::settings::ServiceConfig;
+
+#[post("/collections/{collection_name}/points/search/es")]
+async fn es_search_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<EsSearchRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAuth(auth): ActixAuth,
+) -> HttpResponse {
+    let request = match request.into_inner().into_search_request() {
+        Ok(request) => request,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    do_search_points_response(
+        &dispatcher,
+        &collection.collection_name,
+        request,
+        params.into_inner(),
+        &service_config,
+        auth,
+    )
+    .await
+}
+
+#[post("/collections/{collection_name}/points/scroll/es")]
+async fn es_scroll_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<EsScrollRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAuth(auth): ActixAuth,
+) -> HttpResponse {
+    let request = match request.into_inner().into_scroll_request() {
+        Ok(request) => request,
+        Err(err) => return process_response_error(err, Instant::now(), None),
+    };
+
+    do_scroll_points_response(
+        &dispatcher,
+        &collection.collection_name,
+        request,
+        params.into_inner(),
+        &service_config,
+        auth,
+    )
+    .await
+}
+
+// Configure services
+pub fn config_es_compat_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(es_search_points).service(es_scroll_points);
+}
@@ -53,6 +53,7 @@ async fn facet(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
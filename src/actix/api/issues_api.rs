@@ -7,13 +7,12 @@ use crate::actix::auth::ActixAuth;
 #[get("/issues")]
 async fn get_issues(ActixAuth(auth): ActixAuth) -> impl Responder {
     crate::actix::helpers::time(async move {
-        match auth.access("issues") {
+        let requirements = AccessRequirements::new();
+        match auth.access(requirements, "issues") {
             Access::Global(_) => Ok(IssuesReport {
                 issues: issues::all_issues(),
             }),
             Access::Collection(collection_access_list) => {
-                let requirements = AccessRequirements::new();
-
                 let mut allowed_issues = Vec::new();
                 for collection_name in collection_access_list.meeting_requirements(requirements) {
                     let collection_issues = issues::all_collection_issues(collection_name);
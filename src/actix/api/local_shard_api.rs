@@ -48,6 +48,7 @@ async fn get_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -106,6 +107,7 @@ async fn scroll_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -180,6 +182,7 @@ async fn count_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         path.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
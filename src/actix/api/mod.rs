@@ -3,12 +3,14 @@ use common::validation::{validate_collection_name, validate_collection_name_lega
 use serde::Deserialize;
 use validator::Validate;
 
+pub mod api_keys_api;
 pub mod audit_api;
 pub mod cluster_api;
 pub mod collections_api;
 pub mod count_api;
 pub mod debug_api;
 pub mod discover_api;
+pub mod es_compat_api;
 pub mod facet_api;
 pub mod issues_api;
 pub mod local_shard_api;
@@ -17,6 +19,7 @@ pub mod query_api;
 pub mod read_params;
 pub mod recommend_api;
 pub mod retrieve_api;
+pub mod scoring_hooks_api;
 pub mod search_api;
 pub mod service_api;
 pub mod shards_api;
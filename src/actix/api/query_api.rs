@@ -1,14 +1,17 @@
 use actix_web::{Responder, post, web};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::models::InferenceUsage;
+use api::rest::models::{InferenceUsage, Usage};
 use api::rest::{QueryGroupsRequest, QueryRequest, QueryRequestBatch, QueryResponse};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::time_travel::AsOf;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use storage::content_manager::collection_verification::{
     check_strict_mode, check_strict_mode_batch,
 };
 use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Auth;
 use tokio::time::Instant;
 
 use super::CollectionPath;
@@ -22,6 +25,10 @@ use crate::common::inference::query_requests_rest::{
     convert_query_groups_request_from_rest, convert_query_request_from_rest,
 };
 use crate::common::query::do_query_point_groups;
+use crate::common::reranker::Rerankers;
+use crate::common::rescore::RescoreQueryRequest;
+use crate::common::scoring_hooks::ScoringHooks;
+use crate::common::time_travel::QueryAsOfRequest;
 use crate::settings::ServiceConfig;
 
 #[cfg(test)]
@@ -38,72 +45,232 @@ async fn query_points(
     ActixAuth(auth): ActixAuth,
     api_keys: InferenceApiKeys,
 ) -> impl Responder {
-    let QueryRequest {
-        internal: query_request,
-        shard_key,
-    } = request.into_inner();
-
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
-    );
+    )
+    .with_profile(params.profile())
+    .with_search_diagnostics(params.with_search_diagnostics());
     let timing = Instant::now();
 
-    let shard_selection = match shard_key {
-        None => ShardSelectorInternal::All,
-        Some(shard_keys) => shard_keys.into(),
-    };
-    let hw_measurement_acc = request_hw_counter.get_counter();
     let mut inference_usage = InferenceUsage::default();
+    let inference_params = InferenceParams::new(api_keys, params.timeout());
+
+    let result = do_query_points(
+        &dispatcher,
+        &collection.collection_name,
+        request.into_inner(),
+        &params,
+        auth,
+        &inference_params,
+        &mut inference_usage,
+        None,
+        request_hw_counter.get_counter(),
+    )
+    .await
+    .map(|points| QueryResponse { points });
+
+    let usage = Usage {
+        profile: request_hw_counter.to_rest_profile(),
+        search_diagnostics: request_hw_counter.to_rest_search_diagnostics(),
+        hardware: request_hw_counter.to_rest_api(),
+        inference: inference_usage.into_non_empty(),
+    };
+    helpers::process_response_with_usage(result, timing, usage)
+}
+
+#[post("/collections/{collection_name}/points/query/rescore")]
+#[allow(clippy::too_many_arguments)]
+async fn query_points_rescore(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<RescoreQueryRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    scoring_hooks: web::Data<ScoringHooks>,
+    rerankers: web::Data<Rerankers>,
+    ActixAuth(auth): ActixAuth,
+    api_keys: InferenceApiKeys,
+) -> impl Responder {
+    let RescoreQueryRequest { mut query, rescore } = request.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.collection_name.clone(),
+        &auth,
+        service_config.hardware_reporting(),
+        None,
+    )
+    .with_profile(params.profile())
+    .with_search_diagnostics(params.with_search_diagnostics());
+    let timing = Instant::now();
 
+    let mut inference_usage = InferenceUsage::default();
     let inference_params = InferenceParams::new(api_keys, params.timeout());
 
     let result = async {
-        let CollectionQueryRequestWithUsage { request, usage } =
-            convert_query_request_from_rest(query_request, &inference_params).await?;
-
-        inference_usage.merge_opt(usage);
+        let page = rescore.candidates_query(&mut query.internal);
 
-        let pass = check_strict_mode(
-            &request,
-            params.timeout_as_secs(),
-            &collection.collection_name,
+        let candidates = do_query_points(
             &dispatcher,
-            &auth,
+            &collection.collection_name,
+            query,
+            &params,
+            auth,
+            &inference_params,
+            &mut inference_usage,
+            None,
+            request_hw_counter.get_counter(),
         )
         .await?;
 
-        let points = dispatcher
-            .toc(&auth, &pass)
-            .query_batch(
-                &collection.collection_name,
-                vec![(request, shard_selection)],
+        let points = rescore
+            .rescore(
+                collection.collection_name.clone(),
+                candidates,
+                page,
+                scoring_hooks.into_inner(),
+                &rerankers,
+            )
+            .await?;
+
+        Ok(QueryResponse { points })
+    }
+    .await;
+
+    let usage = Usage {
+        profile: request_hw_counter.to_rest_profile(),
+        search_diagnostics: request_hw_counter.to_rest_search_diagnostics(),
+        hardware: request_hw_counter.to_rest_api(),
+        inference: inference_usage.into_non_empty(),
+    };
+    helpers::process_response_with_usage(result, timing, usage)
+}
+
+#[post("/collections/{collection_name}/points/query/as_of")]
+#[allow(clippy::too_many_arguments)]
+async fn query_points_as_of(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryAsOfRequest>,
+    params: Query<ReadParams>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAuth(auth): ActixAuth,
+    api_keys: InferenceApiKeys,
+) -> impl Responder {
+    let QueryAsOfRequest { query, as_of } = request.into_inner();
+
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.collection_name.clone(),
+        &auth,
+        service_config.hardware_reporting(),
+        None,
+    )
+    .with_profile(params.profile())
+    .with_search_diagnostics(params.with_search_diagnostics());
+    let timing = Instant::now();
+
+    let mut inference_usage = InferenceUsage::default();
+    let inference_params = InferenceParams::new(api_keys, params.timeout());
+
+    let result = do_query_points(
+        &dispatcher,
+        &collection.collection_name,
+        query,
+        &params,
+        auth,
+        &inference_params,
+        &mut inference_usage,
+        Some(as_of),
+        request_hw_counter.get_counter(),
+    )
+    .await
+    .map(|points| QueryResponse { points });
+
+    let usage = Usage {
+        profile: request_hw_counter.to_rest_profile(),
+        search_diagnostics: request_hw_counter.to_rest_search_diagnostics(),
+        hardware: request_hw_counter.to_rest_api(),
+        inference: inference_usage.into_non_empty(),
+    };
+    helpers::process_response_with_usage(result, timing, usage)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn do_query_points(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    request: QueryRequest,
+    params: &ReadParams,
+    auth: Auth,
+    inference_params: &InferenceParams,
+    inference_usage: &mut InferenceUsage,
+    as_of: Option<AsOf>,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<Vec<api::rest::ScoredPoint>, StorageError> {
+    let QueryRequest {
+        internal: query_request,
+        shard_key,
+    } = request;
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let CollectionQueryRequestWithUsage { request, usage } =
+        convert_query_request_from_rest(query_request, inference_params).await?;
+
+    inference_usage.merge_opt(usage);
+
+    let pass = check_strict_mode(
+        &request,
+        params.timeout_as_secs(),
+        collection_name,
+        dispatcher,
+        &auth,
+    )
+    .await?;
+
+    let toc = dispatcher.toc(&auth, &pass);
+    let requests = vec![(request, shard_selection)];
+    let mut batch = match as_of {
+        None => {
+            toc.query_batch(
+                collection_name,
+                requests,
                 params.consistency,
                 auth,
                 params.timeout(),
                 hw_measurement_acc,
             )
             .await?
-            .pop()
-            .ok_or_else(|| {
-                StorageError::service_error("Expected at least one response for one query")
-            })?
-            .into_iter()
-            .map(api::rest::ScoredPoint::from)
-            .collect_vec();
+        }
+        Some(as_of) => {
+            toc.query_batch_as_of(
+                collection_name,
+                requests,
+                as_of,
+                auth,
+                params.timeout(),
+                hw_measurement_acc,
+            )
+            .await?
+        }
+    };
 
-        Ok(QueryResponse { points })
-    }
-    .await;
+    let points = batch
+        .pop()
+        .ok_or_else(|| StorageError::service_error("Expected at least one response for one query"))?
+        .into_iter()
+        .map(api::rest::ScoredPoint::from)
+        .collect_vec();
 
-    helpers::process_response_with_inference_usage(
-        result,
-        timing,
-        request_hw_counter.to_rest_api(),
-        inference_usage.into_non_empty(),
-    )
+    Ok(points)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -122,9 +289,12 @@ async fn query_points_batch(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
-    );
+    )
+    .with_profile(params.profile())
+    .with_search_diagnostics(params.with_search_diagnostics());
     let timing = Instant::now();
     let hw_measurement_acc = request_hw_counter.get_counter();
 
@@ -187,12 +357,13 @@ async fn query_points_batch(
     }
     .await;
 
-    helpers::process_response_with_inference_usage(
-        result,
-        timing,
-        request_hw_counter.to_rest_api(),
-        all_usages.into_non_empty(),
-    )
+    let usage = Usage {
+        profile: request_hw_counter.to_rest_profile(),
+        search_diagnostics: request_hw_counter.to_rest_search_diagnostics(),
+        hardware: request_hw_counter.to_rest_api(),
+        inference: all_usages.into_non_empty(),
+    };
+    helpers::process_response_with_usage(result, timing, usage)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -214,6 +385,7 @@ async fn query_points_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -267,6 +439,8 @@ async fn query_points_groups(
 
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
     cfg.service(query_points);
+    cfg.service(query_points_rescore);
+    cfg.service(query_points_as_of);
     cfg.service(query_points_batch);
     cfg.service(query_points_groups);
 }
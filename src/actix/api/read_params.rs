@@ -13,6 +13,12 @@ pub struct ReadParams {
     pub consistency: Option<ReadConsistency>,
     /// If set, overrides global timeout for this request. Unit is seconds.
     pub timeout: Option<NonZeroU64>,
+    /// If true, report time spent in each stage of query processing in the response usage.
+    /// Only supported by query API.
+    pub profile: Option<bool>,
+    /// If true, report statistics of vector index searches in the response usage.
+    /// Only supported by query API.
+    pub with_search_diagnostics: Option<bool>,
 }
 
 impl ReadParams {
@@ -23,6 +29,14 @@ impl ReadParams {
     pub(crate) fn timeout_as_secs(&self) -> Option<usize> {
         self.timeout.map(|i| i.get() as usize)
     }
+
+    pub fn profile(&self) -> bool {
+        self.profile.unwrap_or_default()
+    }
+
+    pub fn with_search_diagnostics(&self) -> bool {
+        self.with_search_diagnostics.unwrap_or_default()
+    }
 }
 
 fn deserialize_read_consistency<'de, D>(
@@ -77,6 +91,21 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserialize_profile() {
+        let params: ReadParams = serde_urlencoded::from_str("profile=true").unwrap();
+        assert!(params.profile());
+        assert!(!ReadParams::default().profile());
+    }
+
+    #[test]
+    fn deserialize_with_search_diagnostics() {
+        let params: ReadParams =
+            serde_urlencoded::from_str("with_search_diagnostics=true").unwrap();
+        assert!(params.with_search_diagnostics());
+        assert!(!ReadParams::default().with_search_diagnostics());
+    }
+
     #[test]
     fn try_deserialize_factor_0() {
         assert!(try_deserialize(&str("0")).is_err());
@@ -60,6 +60,7 @@ async fn recommend_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -147,6 +148,7 @@ async fn recommend_batch_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -212,6 +214,7 @@ async fn recommend_point_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use actix_web::{Responder, get, post, web};
+use actix_web::{HttpResponse, Responder, get, post, web};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
@@ -50,6 +50,7 @@ async fn do_get_point(
         ids: vec![point_id],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        at_version: None,
     };
 
     let shard_selection = ShardSelectorInternal::All;
@@ -98,6 +99,7 @@ async fn get_point(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -157,6 +159,7 @@ async fn get_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -192,16 +195,35 @@ async fn scroll_points(
     service_config: web::Data<ServiceConfig>,
     ActixAuth(auth): ActixAuth,
 ) -> impl Responder {
+    do_scroll_points_response(
+        &dispatcher,
+        &collection.collection_name,
+        request.into_inner(),
+        params.into_inner(),
+        &service_config,
+        auth,
+    )
+    .await
+}
+
+pub(super) async fn do_scroll_points_response(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    request: ScrollRequest,
+    params: ReadParams,
+    service_config: &ServiceConfig,
+    auth: Auth,
+) -> HttpResponse {
     let ScrollRequest {
         scroll_request,
         shard_key,
-    } = request.into_inner();
+    } = request;
 
     let pass = match check_strict_mode(
         &scroll_request,
         params.timeout_as_secs(),
-        &collection.collection_name,
-        &dispatcher,
+        collection_name,
+        dispatcher,
         &auth,
     )
     .await
@@ -216,8 +238,9 @@ async fn scroll_points(
     };
 
     let request_hw_counter = get_request_hardware_counter(
-        &dispatcher,
-        collection.collection_name.clone(),
+        dispatcher,
+        collection_name.to_string(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -226,7 +249,7 @@ async fn scroll_points(
     let res = dispatcher
         .toc(&auth, &pass)
         .scroll(
-            &collection.collection_name,
+            collection_name,
             scroll_request,
             params.consistency,
             params.timeout(),
@@ -0,0 +1,65 @@
+use actix_web::rt::time::Instant;
+use actix_web::{Responder, delete, put, web};
+use actix_web_validator::Path;
+use serde::Deserialize;
+use storage::dispatcher::Dispatcher;
+use validator::Validate;
+
+use super::CollectionPath;
+use crate::actix::auth::ActixAuth;
+use crate::actix::helpers::process_response;
+use crate::common::scoring_hooks::{
+    ScoringHooks, do_register_scoring_hook, do_remove_scoring_hook,
+};
+
+#[derive(Deserialize, Validate)]
+struct HookPath {
+    hook_name: String,
+}
+
+#[put("/collections/{collection_name}/scoring_hooks/{hook_name}")]
+async fn register_scoring_hook(
+    dispatcher: web::Data<Dispatcher>,
+    scoring_hooks: web::Data<ScoringHooks>,
+    collection: Path<CollectionPath>,
+    hook: Path<HookPath>,
+    body: web::Bytes,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_register_scoring_hook(
+        &dispatcher,
+        scoring_hooks.into_inner(),
+        collection.into_inner().collection_name,
+        hook.into_inner().hook_name,
+        body,
+        auth,
+    )
+    .await;
+    process_response(response, timing, None)
+}
+
+#[delete("/collections/{collection_name}/scoring_hooks/{hook_name}")]
+async fn remove_scoring_hook(
+    scoring_hooks: web::Data<ScoringHooks>,
+    collection: Path<CollectionPath>,
+    hook: Path<HookPath>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let response = do_remove_scoring_hook(
+        &scoring_hooks,
+        &collection.collection_name,
+        &hook.hook_name,
+        auth,
+    );
+    process_response(response, timing, None)
+}
+
+// Configure services
+pub fn config_scoring_hooks_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(register_scoring_hook)
+        .service(remove_scoring_hook);
+}
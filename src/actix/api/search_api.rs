@@ -9,6 +9,7 @@ use collection::operations::types::{
 use itertools::Itertools;
 use storage::content_manager::collection_verification::check_strict_mode;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Auth;
 use tokio::time::Instant;
 
 use super::CollectionPath;
@@ -30,17 +31,36 @@ async fn search_points(
     params: Query<ReadParams>,
     service_config: web::Data<ServiceConfig>,
     ActixAuth(auth): ActixAuth,
+) -> HttpResponse {
+    do_search_points_response(
+        &dispatcher,
+        &collection.collection_name,
+        request.into_inner(),
+        params.into_inner(),
+        &service_config,
+        auth,
+    )
+    .await
+}
+
+pub(super) async fn do_search_points_response(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    request: SearchRequest,
+    params: ReadParams,
+    service_config: &ServiceConfig,
+    auth: Auth,
 ) -> HttpResponse {
     let SearchRequest {
         search_request,
         shard_key,
-    } = request.into_inner();
+    } = request;
 
     let pass = match check_strict_mode(
         &search_request,
         params.timeout_as_secs(),
-        &collection.collection_name,
-        &dispatcher,
+        collection_name,
+        dispatcher,
         &auth,
     )
     .await
@@ -55,8 +75,9 @@ async fn search_points(
     };
 
     let request_hw_counter = get_request_hardware_counter(
-        &dispatcher,
-        collection.collection_name.clone(),
+        dispatcher,
+        collection_name.to_string(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -65,7 +86,7 @@ async fn search_points(
 
     let result = do_core_search_points(
         dispatcher.toc(&auth, &pass),
-        &collection.collection_name,
+        collection_name,
         search_request.into(),
         params.consistency,
         shard_selection,
@@ -128,6 +149,7 @@ async fn batch_search_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -194,6 +216,7 @@ async fn search_point_groups(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -249,6 +272,7 @@ async fn search_points_matrix_pairs(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
@@ -305,6 +329,7 @@ async fn search_points_matrix_offsets(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         None,
     );
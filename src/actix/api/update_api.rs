@@ -1,5 +1,8 @@
+use std::num::NonZeroUsize;
+
+use actix_web::http::header;
 use actix_web::rt::time::Instant;
-use actix_web::{Responder, delete, post, put, web};
+use actix_web::{HttpRequest, Responder, delete, post, put, web};
 use actix_web_validator::{Json, Path, Query};
 use api::rest::UpdateVectors;
 use api::rest::schema::PointInsertOperations;
@@ -12,13 +15,21 @@ use serde::Deserialize;
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
-use super::CollectionPath;
+use super::{CollectionPath, StrictCollectionPath};
 use crate::actix::auth::ActixAuth;
 use crate::actix::helpers::{
     get_request_hardware_counter, process_response, process_response_with_inference_usage,
+    time_or_accept,
 };
+use crate::common::backup_import::{ImportBackup, do_import_backup};
+use crate::common::bulk_import::{ImportPoints, do_import_points};
+use crate::common::bulk_upsert::{BulkUpsertFormat, do_bulk_upsert};
+use crate::common::dataset_export::{ExportDataset, do_export_dataset};
+use crate::common::embedding_cache::{UpsertDeduplicated, do_upsert_deduplicated};
+use crate::common::http_client::HttpClient;
 use crate::common::inference::api_keys::InferenceApiKeys;
 use crate::common::inference::params::InferenceParams;
+use crate::common::pgvector_import::{ImportPgvector, do_import_pgvector};
 use crate::common::strict_mode::*;
 use crate::common::update::*;
 use crate::settings::ServiceConfig;
@@ -45,6 +56,7 @@ async fn upsert_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -91,6 +103,7 @@ async fn delete_points(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -126,6 +139,7 @@ async fn update_vectors(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -172,6 +186,7 @@ async fn delete_vectors(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -205,6 +220,7 @@ async fn set_payload(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -238,6 +254,7 @@ async fn overwrite_payload(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -271,6 +288,7 @@ async fn delete_payload(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -304,6 +322,7 @@ async fn clear_payload(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -339,6 +358,7 @@ async fn update_batch(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -386,6 +406,7 @@ async fn create_field_index(
     let request_hw_counter = get_request_hardware_counter(
         &dispatcher,
         collection.collection_name.clone(),
+        &auth,
         service_config.hardware_reporting(),
         Some(params.wait),
     );
@@ -430,6 +451,208 @@ async fn delete_field_index(
     process_response(response, timing, None)
 }
 
+#[derive(Deserialize, Validate)]
+struct WaitParams {
+    wait: Option<bool>,
+}
+
+#[post("/collections/{collection_name}/points/import")]
+async fn import_points(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    collection: Path<CollectionPath>,
+    request: Json<ImportPoints>,
+    params: Query<WaitParams>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let collection_name = collection.into_inner().collection_name;
+
+    // Import may take long, its usage is not reported in the response
+    let hw_measurement_acc =
+        get_request_hardware_counter(&dispatcher, collection_name.clone(), &auth, false, None)
+            .get_counter();
+
+    let future = async move {
+        let http_client = http_client.client(None)?;
+
+        do_import_points(
+            dispatcher.get_ref().clone(),
+            collection_name,
+            request.into_inner(),
+            auth,
+            http_client,
+            hw_measurement_acc,
+        )
+        .await
+    };
+
+    time_or_accept(future, params.wait.unwrap_or(true)).await
+}
+
+#[put("/collections/{collection_name}/import")]
+async fn import_backup(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    collection: Path<StrictCollectionPath>,
+    request: Json<ImportBackup>,
+    params: Query<WaitParams>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let collection_name = collection.into_inner().collection_name;
+
+    // Import may take long, its usage is not reported in the response
+    let hw_measurement_acc =
+        get_request_hardware_counter(&dispatcher, collection_name.clone(), &auth, false, None)
+            .get_counter();
+
+    let future = async move {
+        let http_client = http_client.client(None)?;
+
+        do_import_backup(
+            dispatcher.get_ref().clone(),
+            collection_name,
+            request.into_inner(),
+            auth,
+            http_client,
+            hw_measurement_acc,
+        )
+        .await
+    };
+
+    time_or_accept(future, params.wait.unwrap_or(true)).await
+}
+
+#[post("/collections/{collection_name}/points/import/pgvector")]
+async fn import_pgvector(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ImportPgvector>,
+    params: Query<WaitParams>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let collection_name = collection.into_inner().collection_name;
+
+    // Import may take long, its usage is not reported in the response
+    let hw_measurement_acc =
+        get_request_hardware_counter(&dispatcher, collection_name.clone(), &auth, false, None)
+            .get_counter();
+
+    let future = do_import_pgvector(
+        dispatcher.get_ref().clone(),
+        collection_name,
+        request.into_inner(),
+        auth,
+        hw_measurement_acc,
+    );
+
+    time_or_accept(future, params.wait.unwrap_or(true)).await
+}
+
+#[derive(Deserialize, Validate)]
+struct BulkUpsertParams {
+    format: Option<BulkUpsertFormat>,
+    batch_size: Option<NonZeroUsize>,
+    wait: Option<bool>,
+}
+
+#[post("/collections/{collection_name}/points/bulk")]
+async fn bulk_upsert_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: HttpRequest,
+    body: web::Bytes,
+    params: Query<BulkUpsertParams>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let collection_name = collection.into_inner().collection_name;
+    let BulkUpsertParams {
+        format,
+        batch_size,
+        wait,
+    } = params.into_inner();
+
+    let format = format.or_else(|| {
+        request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(BulkUpsertFormat::from_content_type)
+    });
+
+    // Bulk upsert may take long, its usage is not reported in the response
+    let hw_measurement_acc =
+        get_request_hardware_counter(&dispatcher, collection_name.clone(), &auth, false, None)
+            .get_counter();
+
+    let future = do_bulk_upsert(
+        dispatcher.get_ref().clone(),
+        collection_name,
+        body,
+        format,
+        batch_size,
+        auth,
+        hw_measurement_acc,
+    );
+
+    time_or_accept(future, wait.unwrap_or(true)).await
+}
+
+#[put("/collections/{collection_name}/points/deduplicated")]
+async fn upsert_deduplicated(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<UpsertDeduplicated>,
+    service_config: web::Data<ServiceConfig>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let request_hw_counter = get_request_hardware_counter(
+        &dispatcher,
+        collection.collection_name.clone(),
+        &auth,
+        service_config.hardware_reporting(),
+        Some(true),
+    );
+
+    let timing = Instant::now();
+
+    let result = do_upsert_deduplicated(
+        &dispatcher,
+        &collection.collection_name,
+        request.into_inner(),
+        auth,
+        request_hw_counter.get_counter(),
+    )
+    .await;
+
+    process_response(result, timing, request_hw_counter.to_rest_api())
+}
+
+#[post("/collections/{collection_name}/points/export")]
+async fn export_dataset(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ExportDataset>,
+    params: Query<WaitParams>,
+    ActixAuth(auth): ActixAuth,
+) -> impl Responder {
+    let collection_name = collection.into_inner().collection_name;
+
+    // Export may take long, its usage is not reported in the response
+    let hw_measurement_acc =
+        get_request_hardware_counter(&dispatcher, collection_name.clone(), &auth, false, None)
+            .get_counter();
+
+    let future = do_export_dataset(
+        dispatcher.get_ref().clone(),
+        collection_name,
+        request.into_inner(),
+        auth,
+        hw_measurement_acc,
+    );
+
+    time_or_accept(future, params.wait.unwrap_or(true)).await
+}
+
 /// Staging endpoint for testing and debugging operations.
 /// Accepts any staging operation and executes it on the collection.
 /// Only available when the `staging` feature is enabled.
@@ -482,7 +705,13 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(clear_payload)
         .service(create_field_index)
         .service(delete_field_index)
-        .service(update_batch);
+        .service(update_batch)
+        .service(import_points)
+        .service(import_pgvector)
+        .service(import_backup)
+        .service(bulk_upsert_points)
+        .service(upsert_deduplicated)
+        .service(export_dataset);
 
     #[cfg(feature = "staging")]
     cfg.service(staging_operation);
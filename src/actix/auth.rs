@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use actix_web::body::EitherBody;
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::header::{HeaderName, HeaderValue};
 use actix_web::{Error, FromRequest, HttpMessage, HttpResponse, ResponseError};
 use futures_util::future::LocalBoxFuture;
 use storage::audit::{audit_trust_forwarded_headers, extract_tracing_id};
@@ -12,6 +13,9 @@ use storage::rbac::Access;
 use super::forwarded;
 use super::helpers::HttpError;
 use crate::common::auth::{Auth, AuthError, AuthKeys, AuthType, log_denied_auth};
+use crate::common::subject_rate_limiter::{
+    HEADER_RATE_LIMIT_LIMIT, HEADER_RATE_LIMIT_REMAINING, RateLimitStatus, RequestCost,
+};
 
 /// Actix middleware factory that validates API keys / JWTs and inserts an
 /// [`Auth`] object into request extensions.
@@ -135,19 +139,56 @@ where
                     .map(str::to_string)
             });
 
-            match auth_keys
+            // Allowed networks are checked against the address of the connection itself,
+            // as forwarded headers can be set by the client
+            let peer_ip = req.peer_addr().map(|addr| addr.ip());
+
+            let authenticated = auth_keys
                 .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
                 .await
-            {
-                Ok((access, inference_token, auth_type, subject)) => {
-                    let auth = Auth::new(access, subject, remote, auth_type, tracing_id);
+                .and_then(|(access, inference_token, auth_type, subject)| {
+                    let auth = Auth::new(
+                        access,
+                        subject,
+                        remote.clone(),
+                        auth_type,
+                        tracing_id.clone(),
+                    );
+                    auth_keys.check_allowed_network(&auth, peer_ip)?;
+                    Ok((auth, inference_token))
+                });
+
+            match authenticated {
+                Ok((auth, inference_token)) => {
+                    let cost = RequestCost::of_rest_request(req.method().as_str(), req.path());
+                    let rate_limit = match auth_keys.check_rate_limit(&auth, cost) {
+                        Ok(rate_limit) => rate_limit,
+                        Err(err) => {
+                            let resp = HttpError::from(err).error_response();
+                            return Ok(req.into_response(resp).map_into_right_body());
+                        }
+                    };
+
                     let previous = req.extensions_mut().insert(auth);
                     req.extensions_mut().insert(inference_token);
                     debug_assert!(
                         previous.is_none(),
                         "Previous auth object should not exist in the request"
                     );
-                    service.call(req).await
+
+                    let mut response = service.call(req).await?;
+                    if let Some(RateLimitStatus { limit, remaining }) = rate_limit {
+                        let headers = response.headers_mut();
+                        headers.insert(
+                            HeaderName::from_static(HEADER_RATE_LIMIT_LIMIT),
+                            HeaderValue::from(limit),
+                        );
+                        headers.insert(
+                            HeaderName::from_static(HEADER_RATE_LIMIT_REMAINING),
+                            HeaderValue::from(remaining),
+                        );
+                    }
+                    Ok(response)
                 }
                 Err(e) => {
                     log_denied_auth(req.path(), remote.clone(), tracing_id, &e);
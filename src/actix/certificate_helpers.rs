@@ -6,12 +6,17 @@ use std::time::{Duration, Instant};
 use fs_err::File;
 use parking_lot::RwLock;
 use rustls::client::VerifierBuilderError;
-use rustls::pki_types::CertificateDer;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::pki_types::{CertificateDer, UnixTime};
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
 use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
 use rustls::sign::CertifiedKey;
-use rustls::{RootCertStore, ServerConfig, crypto};
+use rustls::{
+    DigitallySignedStruct, DistinguishedName, RootCertStore, ServerConfig, SignatureScheme, crypto,
+};
 use rustls_pemfile::Item;
 
+use crate::common::client_identity::AllowedClientNames;
 use crate::settings::{Settings, TlsConfig};
 
 type Result<T> = std::result::Result<T, Error>;
@@ -105,6 +110,155 @@ impl CertifiedKeyWithAge {
     }
 }
 
+/// A TTL based reloading client certificate verifier
+///
+/// Reloads CA certificates from disk, and checks that client certificates are issued for one of
+/// the allowed names, if configured.
+#[derive(Debug)]
+struct ReloadingClientCertVerifier {
+    /// Path to CA certificates, used for loading/refreshing the verifier
+    ca_cert: String,
+
+    /// TTL for each reload
+    ttl: Option<Duration>,
+
+    /// Names allowed in client certificates, any name is allowed if not set
+    allowed_names: Option<AllowedClientNames>,
+
+    /// Current verifier
+    verifier: RwLock<ClientCertVerifierWithAge>,
+}
+
+impl ReloadingClientCertVerifier {
+    pub fn new(
+        ca_cert: String,
+        ttl: Option<Duration>,
+        allowed_names: Option<AllowedClientNames>,
+    ) -> Result<Self> {
+        let verifier = load_client_cert_verifier(&ca_cert)?;
+
+        Ok(Self {
+            ca_cert,
+            ttl,
+            allowed_names,
+            verifier: RwLock::new(ClientCertVerifierWithAge::from(verifier)),
+        })
+    }
+
+    /// Get verifier or refresh
+    ///
+    /// The verifier is automatically refreshed when the TTL is reached.
+    /// If refreshing fails, an error is logged and the old verifier is persisted.
+    fn get_verifier_or_refresh(&self) -> Arc<dyn ClientCertVerifier> {
+        let verifier = self.verifier.read();
+        let ttl = match self.ttl {
+            Some(ttl) if verifier.is_expired(ttl) => ttl,
+            _ => return verifier.verifier.clone(),
+        };
+        drop(verifier);
+
+        // Re-check that TTL is expired, to not refresh multiple times from concurrent threads
+        let mut verifier = self.verifier.write();
+        if verifier.is_expired(ttl)
+            && let Err(err) = verifier.refresh(&self.ca_cert)
+        {
+            log::error!("Failed to refresh TLS CA certificate, keeping current: {err}");
+        }
+
+        verifier.verifier.clone()
+    }
+}
+
+impl ClientCertVerifier for ReloadingClientCertVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        // CA certificates may change on reload, so no hints are sent to clients
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> std::result::Result<ClientCertVerified, rustls::Error> {
+        let verified =
+            self.get_verifier_or_refresh()
+                .verify_client_cert(end_entity, intermediates, now)?;
+
+        if let Some(allowed_names) = &self.allowed_names {
+            allowed_names.verify(end_entity)?;
+        }
+
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier
+            .read()
+            .verifier
+            .verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        self.verifier
+            .read()
+            .verifier
+            .verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.verifier.read().verifier.supported_verify_schemes()
+    }
+}
+
+#[derive(Debug)]
+struct ClientCertVerifierWithAge {
+    /// Last time the verifier was updated/replaced
+    last_update: Instant,
+
+    /// Current verifier
+    verifier: Arc<dyn ClientCertVerifier>,
+}
+
+impl ClientCertVerifierWithAge {
+    pub fn from(verifier: Arc<dyn ClientCertVerifier>) -> Self {
+        Self {
+            last_update: Instant::now(),
+            verifier,
+        }
+    }
+
+    pub fn refresh(&mut self, ca_cert: &str) -> Result<()> {
+        *self = Self::from(load_client_cert_verifier(ca_cert)?);
+        Ok(())
+    }
+
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.last_update.elapsed() >= ttl
+    }
+}
+
+/// Load CA certificates and construct client certificate verifier.
+fn load_client_cert_verifier(ca_cert_path: &str) -> Result<Arc<dyn ClientCertVerifier>> {
+    let mut root_cert_store = RootCertStore::empty();
+    let ca_certs: Vec<CertificateDer> =
+        with_buf_read(ca_cert_path, |rd| rustls_pemfile::certs(rd).collect())?;
+    root_cert_store.add_parsable_certificates(ca_certs);
+    WebPkiClientVerifier::builder(root_cert_store.into())
+        .build()
+        .map_err(Error::ClientCertVerifier)
+}
+
 /// Load TLS configuration and construct certified key.
 fn load_certified_key(tls_config: &TlsConfig) -> Result<Arc<CertifiedKey>> {
     // Load certificates
@@ -148,27 +302,24 @@ pub fn actix_tls_server_config(settings: &Settings) -> Result<ServerConfig> {
         .ok_or_else(Settings::tls_config_is_undefined_error)
         .map_err(Error::Io)?;
 
+    // Configure TTL for certificate rotations
+    let ttl = match tls_config.cert_ttl {
+        None | Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(seconds)),
+    };
+
     // Verify client CA or not
     let config = if settings.service.verify_https_client_certificate {
-        let mut root_cert_store = RootCertStore::empty();
-
-        let ca_cert_path = tls_config.ca_cert.as_ref().ok_or(Error::NoCaCert)?;
-        let ca_certs: Vec<CertificateDer> =
-            with_buf_read(ca_cert_path, |rd| rustls_pemfile::certs(rd).collect())?;
-        root_cert_store.add_parsable_certificates(ca_certs);
-        let client_cert_verifier = WebPkiClientVerifier::builder(root_cert_store.into())
-            .build()
-            .map_err(Error::ClientCertVerifier)?;
-        config.with_client_cert_verifier(client_cert_verifier)
+        let ca_cert_path = tls_config.ca_cert.clone().ok_or(Error::NoCaCert)?;
+        let allowed_names = AllowedClientNames::from_tls_config(&tls_config).map_err(Error::Io)?;
+        let client_cert_verifier =
+            ReloadingClientCertVerifier::new(ca_cert_path, ttl, allowed_names)?;
+        config.with_client_cert_verifier(Arc::new(client_cert_verifier))
     } else {
         config.with_no_client_auth()
     };
 
     // Configure rotating certificate resolver
-    let ttl = match tls_config.cert_ttl {
-        None | Some(0) => None,
-        Some(seconds) => Some(Duration::from_secs(seconds)),
-    };
     let cert_resolver = RotatingCertificateResolver::new(tls_config, ttl)?;
     let config = config.with_cert_resolver(Arc::new(cert_resolver));
 
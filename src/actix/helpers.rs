@@ -12,10 +12,12 @@ use serde::Serialize;
 use storage::content_manager::errors::{StorageError, StorageResult};
 use storage::content_manager::toc::request_hw_counter::RequestHwCounter;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Auth;
 
 pub fn get_request_hardware_counter(
     dispatcher: &Dispatcher,
     collection_name: String,
+    auth: &Auth,
     report_to_api: bool,
     wait: Option<bool>,
 ) -> RequestHwCounter {
@@ -23,7 +25,8 @@ pub fn get_request_hardware_counter(
     RequestHwCounter::new(
         HwMeasurementAcc::new_with_metrics_drain(
             dispatcher.get_collection_hw_metrics(collection_name),
-        ),
+        )
+        .with_subject_drain(dispatcher.get_subject_hw_metrics(auth)),
         report_to_api,
     )
 }
@@ -37,6 +40,8 @@ pub fn accepted_response(
         let u = Usage {
             hardware: hardware_usage,
             inference: inference_usage,
+            profile: None,
+            search_diagnostics: None,
         };
         if u.is_empty() { None } else { Some(u) }
     };
@@ -49,11 +54,10 @@ pub fn accepted_response(
     })
 }
 
-pub fn process_response_with_inference_usage<T>(
+pub fn process_response_with_usage<T>(
     response: Result<T, StorageError>,
     timing: Instant,
-    hardware_usage: Option<HardwareUsage>,
-    inference_usage: Option<InferenceUsage>,
+    usage: Usage,
 ) -> HttpResponse
 where
     T: Serialize,
@@ -63,20 +67,30 @@ where
             result: Some(res),
             status: ApiStatus::Ok,
             time: timing.elapsed().as_secs_f64(),
-            usage: Some(Usage {
-                hardware: hardware_usage,
-                inference: inference_usage,
-            }),
+            usage: Some(usage),
         }),
-        Err(err) => process_response_error_with_inference_usage(
-            err,
-            timing,
-            hardware_usage,
-            inference_usage,
-        ),
+        Err(err) => process_response_error_with_usage(err, timing, usage),
     }
 }
 
+pub fn process_response_with_inference_usage<T>(
+    response: Result<T, StorageError>,
+    timing: Instant,
+    hardware_usage: Option<HardwareUsage>,
+    inference_usage: Option<InferenceUsage>,
+) -> HttpResponse
+where
+    T: Serialize,
+{
+    let usage = Usage {
+        hardware: hardware_usage,
+        inference: inference_usage,
+        profile: None,
+        search_diagnostics: None,
+    };
+    process_response_with_usage(response, timing, usage)
+}
+
 pub fn process_response<T>(
     response: Result<T, StorageError>,
     timing: Instant,
@@ -88,11 +102,10 @@ where
     process_response_with_inference_usage(response, timing, hardware_usage, None)
 }
 
-pub fn process_response_error_with_inference_usage(
+pub fn process_response_error_with_usage(
     err: StorageError,
     timing: Instant,
-    hardware_usage: Option<HardwareUsage>,
-    inference_usage: Option<InferenceUsage>,
+    usage: Usage,
 ) -> HttpResponse {
     log_service_error(&err);
 
@@ -103,10 +116,7 @@ pub fn process_response_error_with_inference_usage(
         result: None,
         status: ApiStatus::Error(error.to_string()),
         time: timing.elapsed().as_secs_f64(),
-        usage: Some(Usage {
-            hardware: hardware_usage,
-            inference: inference_usage,
-        }),
+        usage: Some(usage),
     };
 
     let mut response_builder = HttpResponse::build(http_code);
@@ -116,6 +126,21 @@ pub fn process_response_error_with_inference_usage(
     response_builder.json(json_body)
 }
 
+pub fn process_response_error_with_inference_usage(
+    err: StorageError,
+    timing: Instant,
+    hardware_usage: Option<HardwareUsage>,
+    inference_usage: Option<InferenceUsage>,
+) -> HttpResponse {
+    let usage = Usage {
+        hardware: hardware_usage,
+        inference: inference_usage,
+        profile: None,
+        search_diagnostics: None,
+    };
+    process_response_error_with_usage(err, timing, usage)
+}
+
 pub fn process_response_error(
     err: StorageError,
     timing: Instant,
@@ -25,18 +25,21 @@ use collection::operations::verification::new_unchecked_verification_pass;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::{Access, Auth};
 
+use crate::actix::api::api_keys_api::config_api_keys_api;
 use crate::actix::api::audit_api::config_audit_api;
 use crate::actix::api::cluster_api::config_cluster_api;
 use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discover_api::config_discover_api;
+use crate::actix::api::es_compat_api::config_es_compat_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::local_shard_api::config_local_shard_api;
 use crate::actix::api::profiler_api::config_profiler_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
 use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::scoring_hooks_api::config_scoring_hooks_api;
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -48,6 +51,8 @@ use crate::common::auth::AuthKeys;
 use crate::common::debugger::DebuggerState;
 use crate::common::health;
 use crate::common::http_client::HttpClient;
+use crate::common::reranker::Rerankers;
+use crate::common::scoring_hooks::ScoringHooks;
 use crate::common::telemetry::TelemetryCollector;
 use crate::settings::{Settings, max_web_workers};
 use crate::tracing::LoggerHandle;
@@ -63,13 +68,12 @@ pub fn init(
     health_checker: Option<Arc<health::HealthChecker>>,
     settings: Settings,
     logger_handle: LoggerHandle,
+    auth_keys: Option<AuthKeys>,
 ) -> io::Result<()> {
     actix_web::rt::System::new().block_on(async {
         // Nothing to verify here.
         let pass = new_unchecked_verification_pass();
         let auth = Auth::new_internal(Access::full("Service initialization"));
-        let auth_keys =
-            AuthKeys::try_create(&settings.service, dispatcher.toc(&auth, &pass).clone());
         let upload_dir = dispatcher.toc(&auth, &pass).upload_dir().unwrap();
         let dispatcher_data = web::Data::from(dispatcher);
         let actix_telemetry_collector = telemetry_collector
@@ -81,6 +85,19 @@ pub fn init(
         let telemetry_collector_data = web::Data::from(telemetry_collector);
         let logger_handle_data = web::Data::new(logger_handle);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
+        let rerankers = web::Data::new(
+            Rerankers::from_config(
+                settings
+                    .inference
+                    .as_ref()
+                    .map(|inference| inference.rerankers.as_slice())
+                    .unwrap_or_default(),
+            )
+            .map_err(io::Error::other)?,
+        );
+        let scoring_hooks = web::Data::new(
+            ScoringHooks::new(&settings.storage.storage_path).map_err(io::Error::other)?,
+        );
         let health_checker = web::Data::new(health_checker);
         let web_ui_available = web_ui_folder(&settings);
         let service_config = web::Data::new(settings.service.clone());
@@ -136,11 +153,16 @@ pub fn init(
                 .app_data(telemetry_collector_data.clone())
                 .app_data(logger_handle_data.clone())
                 .app_data(http_client.clone())
+                .app_data(scoring_hooks.clone())
+                .app_data(rerankers.clone())
                 .app_data(debugger_state.clone())
                 .app_data(health_checker.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
+                .app_data(web::PayloadConfig::new(
+                    settings.service.max_request_size_mb * 1024 * 1024,
+                ))
                 .app_data(TempFileConfig::default().directory(&upload_dir))
                 .app_data(MultipartFormConfig::default().total_limit(usize::MAX))
                 .app_data(service_config.clone())
@@ -152,6 +174,8 @@ pub fn init(
                 .configure(config_cluster_api)
                 .configure(config_service_api)
                 .configure(config_search_api)
+                .configure(config_es_compat_api)
+                .configure(config_scoring_hooks_api)
                 .configure(config_recommend_api)
                 .configure(config_discover_api)
                 .configure(config_query_api)
@@ -162,6 +186,7 @@ pub fn init(
                 .configure(config_profiler_api)
                 .configure(config_local_shard_api)
                 .configure(config_audit_api)
+                .configure(config_api_keys_api)
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
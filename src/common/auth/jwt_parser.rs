@@ -82,6 +82,7 @@ mod tests {
                 access: CollectionAccessMode::ReadWrite,
                 #[expect(deprecated)]
                 payload: None,
+                tenant: None,
             }])),
             value_exists: None,
             subject: None,
@@ -113,6 +114,7 @@ mod tests {
                     "field2": 42,
                     "field3": true,
                 })),
+                tenant: None,
             }])),
             value_exists: None,
             subject: None,
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::net::IpAddr;
 use std::sync::Arc;
 
 use chrono::Utc;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use ipnet::IpNet;
 use itertools::Itertools;
+use parking_lot::RwLock;
 use segment::types::{WithPayloadInterface, WithVector};
 use shard::scroll::ScrollRequestInternal;
 use storage::audit::{AuditEvent, AuditResult, audit_log, is_audit_enabled};
@@ -15,7 +19,9 @@ use storage::rbac::Access;
 use self::claims::{Claims, ValueExists};
 use self::jwt_parser::JwtParser;
 use super::strings::ct_eq;
+use super::subject_rate_limiter::{RateLimitStatus, RequestCost, SubjectRateLimiter};
 use crate::common::inference::api_keys::InferenceToken;
+use crate::common::secrets::ApiKeys;
 use crate::settings::ServiceConfig;
 pub mod claims;
 pub mod jwt_parser;
@@ -29,23 +35,20 @@ pub const HTTP_HEADER_API_KEY: &str = "api-key";
 /// The API keys used for auth
 #[derive(Clone)]
 pub struct AuthKeys {
-    /// A key allowing Read or Write operations
-    read_write: Option<String>,
-
-    /// Alternative to `read_write` key
-    alt_read_write: Option<String>,
-
-    /// A key allowing Read operations
-    read_only: Option<String>,
+    /// Keys from the config, shared between clones, so they can be rotated at runtime
+    keys: Arc<RwLock<Arc<StaticKeys>>>,
 
-    /// A JWT parser, based on the read_write key
-    jwt_parser: Option<JwtParser>,
-
-    /// Alternative JWT parser, based on the alt_read_write key
-    alt_jwt_parser: Option<JwtParser>,
+    /// Whether to accept JWT, signed with the read-write keys
+    jwt_rbac: bool,
 
     /// Table of content, needed to do stateful validation of JWT
     toc: Arc<TableOfContent>,
+
+    /// Rate limits by subject, shared between REST and gRPC APIs
+    rate_limiter: Option<Arc<SubjectRateLimiter>>,
+
+    /// Networks, from which subjects are allowed to connect
+    allowed_networks: Arc<HashMap<String, Vec<IpNet>>>,
 }
 
 #[derive(Debug)]
@@ -82,55 +85,160 @@ pub fn log_denied_auth(
             remote,
             collection: None,
             tracing_id,
+            write: false,
             result: AuditResult::Denied,
             error: Some(error.to_string()),
         });
     }
 }
 
-impl AuthKeys {
-    fn get_jwt_parser(service_config: &ServiceConfig) -> (Option<JwtParser>, Option<JwtParser>) {
-        if service_config.jwt_rbac.unwrap_or_default() {
+/// Keys from the config
+struct StaticKeys {
+    /// A key allowing Read or Write operations
+    read_write: Option<String>,
+
+    /// Alternative to `read_write` key
+    alt_read_write: Option<String>,
+
+    /// A key allowing Read operations
+    read_only: Option<String>,
+
+    /// A JWT parser, based on the read_write key
+    jwt_parser: Option<JwtParser>,
+
+    /// Alternative JWT parser, based on the alt_read_write key
+    alt_jwt_parser: Option<JwtParser>,
+}
+
+impl StaticKeys {
+    fn new(keys: &ApiKeys, jwt_rbac: bool) -> Self {
+        let ApiKeys {
+            api_key,
+            alt_api_key,
+            read_only_api_key,
+        } = keys;
+
+        let (jwt_parser, alt_jwt_parser) = if jwt_rbac {
             (
-                service_config
-                    .api_key
-                    .as_ref()
-                    .map(|secret| JwtParser::new(secret)),
-                service_config
-                    .alt_api_key
-                    .as_ref()
-                    .map(|secret| JwtParser::new(secret)),
+                api_key.as_ref().map(|secret| JwtParser::new(secret)),
+                alt_api_key.as_ref().map(|secret| JwtParser::new(secret)),
             )
         } else {
             (None, None)
+        };
+
+        Self {
+            read_write: api_key.clone(),
+            alt_read_write: alt_api_key.clone(),
+            read_only: read_only_api_key.clone(),
+            jwt_parser,
+            alt_jwt_parser,
         }
     }
 
+    /// Check if a key is allowed to read
+    #[inline]
+    fn can_read(&self, key: &str) -> bool {
+        self.read_only
+            .as_ref()
+            .is_some_and(|ro_key| ct_eq(ro_key, key))
+    }
+
+    /// Check if a key is allowed to write
+    #[inline]
+    fn can_write(&self, key: &str) -> bool {
+        let can_write = self
+            .read_write
+            .as_ref()
+            .is_some_and(|rw_key| ct_eq(rw_key, key));
+        let alt_can_write = self
+            .alt_read_write
+            .as_ref()
+            .is_some_and(|alt_rw_key| ct_eq(alt_rw_key, key));
+        can_write || alt_can_write
+    }
+}
+
+impl AuthKeys {
     /// Defines the auth scheme given the service config
     ///
     /// Returns None if no scheme is specified.
-    pub fn try_create(service_config: &ServiceConfig, toc: Arc<TableOfContent>) -> Option<Self> {
-        match (
-            service_config.api_key.clone(),
-            service_config.alt_api_key.clone(),
-            service_config.read_only_api_key.clone(),
-        ) {
-            (None, None, None) => None,
-            (read_write, alt_read_write, read_only) => {
-                let (jwt_parser, alt_jwt_parser) = Self::get_jwt_parser(service_config);
+    pub fn try_create(
+        service_config: &ServiceConfig,
+        toc: Arc<TableOfContent>,
+        rate_limiter: Option<Arc<SubjectRateLimiter>>,
+    ) -> Option<Self> {
+        match ApiKeys::from_service_config(service_config) {
+            ApiKeys {
+                api_key: None,
+                alt_api_key: None,
+                read_only_api_key: None,
+            } => None,
+            keys => {
+                let jwt_rbac = service_config.jwt_rbac.unwrap_or_default();
 
                 Some(Self {
-                    read_write,
-                    alt_read_write,
-                    read_only,
-                    jwt_parser,
-                    alt_jwt_parser,
+                    keys: Arc::new(RwLock::new(Arc::new(StaticKeys::new(&keys, jwt_rbac)))),
+                    jwt_rbac,
                     toc,
+                    rate_limiter,
+                    allowed_networks: Arc::new(service_config.allowed_networks.clone()),
                 })
             }
         }
     }
 
+    /// Replace the keys from the config with rotated ones.
+    pub fn update_keys(&self, keys: &ApiKeys) {
+        *self.keys.write() = Arc::new(StaticKeys::new(keys, self.jwt_rbac));
+    }
+
+    /// Check that the credentials of an authenticated request are used from an allowed network.
+    ///
+    /// `peer_ip` must be the address of the connection itself, not one reported in headers,
+    /// which could be forged by the client.
+    pub fn check_allowed_network(
+        &self,
+        auth: &Auth,
+        peer_ip: Option<IpAddr>,
+    ) -> Result<(), AuthError> {
+        let Some(networks) = auth
+            .usage_subject()
+            .and_then(|subject| self.allowed_networks.get(&subject))
+        else {
+            return Ok(());
+        };
+
+        let Some(peer_ip) = peer_ip else {
+            return Err(AuthError::Forbidden(
+                "Credentials are restricted to allowed networks, but client address is unknown"
+                    .to_string(),
+            ));
+        };
+
+        if is_allowed_network(networks, peer_ip) {
+            Ok(())
+        } else {
+            Err(AuthError::Forbidden(format!(
+                "Credentials are not allowed to be used from {peer_ip}"
+            )))
+        }
+    }
+
+    /// Count an authenticated request against the rate limits of its subject.
+    ///
+    /// Returns the remaining requests budget, if the subject is limited.
+    pub fn check_rate_limit(
+        &self,
+        auth: &Auth,
+        cost: RequestCost,
+    ) -> Result<Option<RateLimitStatus>, StorageError> {
+        match (&self.rate_limiter, auth.usage_subject()) {
+            (Some(rate_limiter), Some(subject)) => rate_limiter.check(&subject, cost),
+            _ => Ok(None),
+        }
+    }
+
     /// Validate that the specified request is allowed for given keys.
     ///
     /// Returns `(Access, InferenceToken, AuthType, Option<subject>)`.
@@ -146,7 +254,9 @@ impl AuthKeys {
             ));
         };
 
-        if self.can_write(key) {
+        let keys = self.keys.read().clone();
+
+        if keys.can_write(key) {
             return Ok((
                 Access::full("Read-write access by key"),
                 InferenceToken(None),
@@ -155,7 +265,7 @@ impl AuthKeys {
             ));
         }
 
-        if self.can_read(key) {
+        if keys.can_read(key) {
             return Ok((
                 Access::full_ro("Read-only access by key"),
                 InferenceToken(None),
@@ -164,8 +274,17 @@ impl AuthKeys {
             ));
         }
 
+        if let Some(api_key) = self.toc.api_keys().authenticate(key) {
+            return Ok((
+                api_key.access,
+                InferenceToken(None),
+                AuthType::ApiKey,
+                Some(api_key.id.to_string()),
+            ));
+        }
+
         let (claims, errors): (Vec<_>, Vec<_>) =
-            [self.jwt_parser.as_ref(), self.alt_jwt_parser.as_ref()]
+            [keys.jwt_parser.as_ref(), keys.alt_jwt_parser.as_ref()]
                 .into_iter()
                 .flatten()
                 .filter_map(|p| p.decode(key))
@@ -235,26 +354,34 @@ impl AuthKeys {
 
         Ok(())
     }
+}
 
-    /// Check if a key is allowed to read
-    #[inline]
-    fn can_read(&self, key: &str) -> bool {
-        self.read_only
-            .as_ref()
-            .is_some_and(|ro_key| ct_eq(ro_key, key))
-    }
+fn is_allowed_network(networks: &[IpNet], ip: IpAddr) -> bool {
+    // Clients connecting over IPv4 to a dual-stack socket have IPv4-mapped IPv6 addresses
+    let ip = ip.to_canonical();
+    networks.iter().any(|network| network.contains(&ip))
+}
 
-    /// Check if a key is allowed to write
-    #[inline]
-    fn can_write(&self, key: &str) -> bool {
-        let can_write = self
-            .read_write
-            .as_ref()
-            .is_some_and(|rw_key| ct_eq(rw_key, key));
-        let alt_can_write = self
-            .alt_read_write
-            .as_ref()
-            .is_some_and(|alt_rw_key| ct_eq(alt_rw_key, key));
-        can_write || alt_can_write
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_allowed_network() {
+        let networks: Vec<IpNet> =
+            vec!["10.1.0.0/16".parse().unwrap(), "fd00::/8".parse().unwrap()];
+
+        assert!(is_allowed_network(&networks, "10.1.2.3".parse().unwrap()));
+        assert!(is_allowed_network(
+            &networks,
+            "::ffff:10.1.2.3".parse().unwrap()
+        ));
+        assert!(is_allowed_network(&networks, "fd12::1".parse().unwrap()));
+        assert!(!is_allowed_network(&networks, "10.2.0.1".parse().unwrap()));
+        assert!(!is_allowed_network(
+            &networks,
+            "2001:db8::1".parse().unwrap()
+        ));
+        assert!(!is_allowed_network(&[], "10.1.2.3".parse().unwrap()));
     }
 }
@@ -0,0 +1,760 @@
+//! Import of collections from Milvus and Weaviate exports.
+//!
+//! Schema of the source collection is mapped into a new collection: vector fields become
+//! vectors with the same distance, all other fields become payload. Exported objects are read
+//! from a JSON file and upserted in batches.
+//!
+//! Vector sizes, which are not part of the source schema, like in Weaviate, are derived from
+//! the first batch of objects.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::BufReader;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use api::rest::schema::{PointStruct, Vector, VectorStruct};
+use collection::operations::types::{Datatype, SparseVectorParams, VectorParams, VectorsConfig};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use reqwest::Url;
+use schemars::JsonSchema;
+use segment::types::{Distance, ExtendedPointId, Payload, VectorNameBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::auth::Auth;
+use crate::common::bulk_import::{import_file_path, upsert_points_batch};
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Format of the exported collection
+///
+/// * `milvus` - rows of a Milvus collection, with the collection description as schema
+/// * `weaviate` - objects of a Weaviate class, with the class definition as schema
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFormat {
+    Milvus,
+    Weaviate,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ImportBackup {
+    /// Format of the export
+    pub format: BackupFormat,
+    /// Location of the JSON file with exported objects, same as for the import of points.
+    /// Objects are either on separate lines, or in an array, optionally wrapped into an object:
+    /// `{"rows": [...]}` of Milvus bulk insert, or `{"objects": [...]}` of Weaviate objects API.
+    pub location: Url,
+    /// Schema of the source collection: Milvus collection description, as returned by
+    /// `describe_collection`, or Weaviate class definition, as returned by `/v1/schema/{class}`
+    pub schema: Value,
+    /// Number of points upserted at once. Default: 1000
+    #[serde(default)]
+    pub batch_size: Option<NonZeroUsize>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct ImportBackupResult {
+    /// Number of imported points
+    pub points: usize,
+}
+
+/// Create a new collection from the schema of a Milvus or Weaviate collection, and import
+/// exported objects into it.
+pub async fn do_import_backup(
+    dispatcher: Dispatcher,
+    collection_name: String,
+    request: ImportBackup,
+    auth: Auth,
+    http_client: reqwest::Client,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ImportBackupResult, StorageError> {
+    auth.check_global_access(AccessRequirements::new().manage(), "import_backup")?;
+
+    let ImportBackup {
+        format,
+        location,
+        schema,
+        batch_size,
+    } = request;
+
+    let schema = Arc::new(match format {
+        BackupFormat::Milvus => BackupSchema::from_milvus(&schema)?,
+        BackupFormat::Weaviate => BackupSchema::from_weaviate(&schema)?,
+    });
+
+    // Keep downloaded file until the import is done.
+    // Collection is created by the import, points are checked against its strict mode on upsert.
+    let (path, _downloaded) = import_file_path(&location, &dispatcher, &http_client).await?;
+
+    // Objects are read in a blocking thread, and upserted as soon as a batch is ready
+    let (sender, mut receiver) = mpsc::channel(1);
+    let batch_size = batch_size.map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get);
+    let read_task = tokio::task::spawn_blocking({
+        let schema = schema.clone();
+        move || read_objects(path, &schema, batch_size, sender)
+    });
+
+    let mut points = 0;
+    let mut is_created = false;
+    while let Some(batch) = receiver.recv().await {
+        if !is_created {
+            create_collection(&dispatcher, &collection_name, &schema, &batch, &auth).await?;
+            is_created = true;
+        }
+
+        let batch_len = batch.len();
+        upsert_points_batch(
+            &dispatcher,
+            &collection_name,
+            batch,
+            &auth,
+            &hw_measurement_acc,
+        )
+        .await?;
+        points += batch_len;
+    }
+
+    read_task.await??;
+
+    if !is_created {
+        create_collection(&dispatcher, &collection_name, &schema, &[], &auth).await?;
+    }
+
+    Ok(ImportBackupResult { points })
+}
+
+async fn create_collection(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    schema: &BackupSchema,
+    first_batch: &[PointStruct],
+    auth: &Auth,
+) -> Result<(), StorageError> {
+    let mut vectors = BTreeMap::new();
+    let mut sparse_vectors = BTreeMap::new();
+    for (name, vector) in &schema.vectors {
+        if vector.is_sparse {
+            sparse_vectors.insert(
+                name.clone(),
+                SparseVectorParams {
+                    index: None,
+                    modifier: None,
+                    bm25: None,
+                },
+            );
+            continue;
+        }
+
+        let size = match vector.size {
+            Some(size) => size,
+            None => infer_vector_size(name, first_batch)?,
+        };
+        vectors.insert(
+            name.clone(),
+            VectorParams {
+                size,
+                distance: vector.distance,
+                hnsw_config: None,
+                quantization_config: None,
+                on_disk: None,
+                datatype: vector.datatype,
+                multivector_config: None,
+                mmap_advice: None,
+                dim_range: None,
+                preserve_norm: None,
+                quantization_rescore: None,
+                vamana_config: None,
+                ivf_config: None,
+                max_versions: None,
+            },
+        );
+    }
+
+    let vectors = match vectors.remove("") {
+        Some(default_vector) if vectors.is_empty() => VectorsConfig::Single(default_vector),
+        Some(default_vector) => {
+            vectors.insert(VectorNameBuf::new(), default_vector);
+            VectorsConfig::Multi(vectors)
+        }
+        None => VectorsConfig::Multi(vectors),
+    };
+
+    let create_collection = CreateCollection {
+        vectors,
+        shard_number: None,
+        sharding_method: None,
+        replication_factor: None,
+        write_consistency_factor: None,
+        on_disk_payload: None,
+        hnsw_config: None,
+        wal_config: None,
+        optimizers_config: None,
+        quantization_config: None,
+        sparse_vectors: (!sparse_vectors.is_empty()).then_some(sparse_vectors),
+        strict_mode_config: None,
+        uuid: None,
+        metadata: None,
+    };
+
+    let operation = CreateCollectionOperation::new(collection_name.to_string(), create_collection)?;
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(operation),
+            auth.clone(),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+fn infer_vector_size(name: &str, points: &[PointStruct]) -> Result<NonZeroU64, StorageError> {
+    points
+        .iter()
+        .find_map(|point| match &point.vector {
+            VectorStruct::Named(vectors) => match vectors.get(name) {
+                Some(Vector::Dense(vector)) => NonZeroU64::new(vector.len() as u64),
+                _ => None,
+            },
+            _ => None,
+        })
+        .ok_or_else(|| {
+            StorageError::bad_input(format!(
+                "Size of vector {name:?} can't be derived, none of the first {} objects has it",
+                points.len(),
+            ))
+        })
+}
+
+fn read_objects(
+    path: PathBuf,
+    schema: &BackupSchema,
+    batch_size: usize,
+    sender: mpsc::Sender<Vec<PointStruct>>,
+) -> Result<(), StorageError> {
+    let file = BufReader::new(fs_err::File::open(&path)?);
+
+    let mut number = 0;
+    let mut batch = Vec::with_capacity(batch_size);
+    for value in serde_json::Deserializer::from_reader(file).into_iter::<Value>() {
+        let value = value.map_err(|err| {
+            StorageError::bad_input(format!("Failed to read exported objects: {err}"))
+        })?;
+
+        for object in schema.format.unwrap_objects(value) {
+            number += 1;
+            let point = schema.point(object).map_err(|err| {
+                StorageError::bad_input(format!("Failed to convert object {number}: {err}"))
+            })?;
+
+            batch.push(point);
+            if batch.len() >= batch_size {
+                let batch = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                // Receiver is dropped if the import failed
+                if sender.blocking_send(batch).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    if !batch.is_empty() {
+        let _ = sender.blocking_send(batch);
+    }
+
+    Ok(())
+}
+
+impl BackupFormat {
+    /// Extract objects from a top-level JSON value of the export file
+    fn unwrap_objects(self, value: Value) -> Vec<Value> {
+        let wrapper_key = match self {
+            BackupFormat::Milvus => "rows",
+            BackupFormat::Weaviate => "objects",
+        };
+
+        match value {
+            Value::Array(objects) => objects,
+            Value::Object(object) if object.get(wrapper_key).is_some_and(Value::is_array) => {
+                match object.into_iter().find(|(key, _)| key == wrapper_key) {
+                    Some((_, Value::Array(objects))) => objects,
+                    _ => Vec::new(),
+                }
+            }
+            object => vec![object],
+        }
+    }
+}
+
+/// Mapping of the source collection into points
+#[derive(Debug)]
+struct BackupSchema {
+    format: BackupFormat,
+    /// Field of the objects with their ids
+    id_field: String,
+    vectors: BTreeMap<VectorNameBuf, SourceVector>,
+}
+
+#[derive(Debug)]
+struct SourceVector {
+    /// Path to the vector in the exported objects
+    path: Vec<String>,
+    distance: Distance,
+    /// Size of dense vectors, if known from the schema
+    size: Option<NonZeroU64>,
+    datatype: Option<Datatype>,
+    is_sparse: bool,
+}
+
+fn schema_error(message: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_request(format!(
+        "Unsupported schema of the source collection: {message}"
+    ))
+}
+
+impl BackupSchema {
+    /// Map Milvus collection description.
+    ///
+    /// Both `describe_collection` of the Python client and the `describe` REST API are supported.
+    fn from_milvus(schema: &Value) -> Result<Self, StorageError> {
+        let fields = schema
+            .get("fields")
+            .and_then(Value::as_array)
+            .ok_or_else(|| schema_error("`fields` are not found"))?;
+
+        // Metric is defined by the index of the vector field
+        let mut metrics = HashMap::new();
+        for index in schema
+            .get("indexes")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let field = index.get("fieldName").or_else(|| index.get("field_name"));
+            let metric = index
+                .get("metricType")
+                .or_else(|| index.get("metric_type"))
+                .or_else(|| index.pointer("/params/metric_type"));
+            if let (Some(Value::String(field)), Some(Value::String(metric))) = (field, metric) {
+                metrics.insert(field.as_str(), metric.to_ascii_uppercase());
+            }
+        }
+
+        let mut id_field = None;
+        let mut vectors = BTreeMap::new();
+        for field in fields {
+            let name = field
+                .get("name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| schema_error("field without `name`"))?;
+
+            let is_primary = ["is_primary", "primaryKey"]
+                .iter()
+                .any(|key| field.get(*key).and_then(Value::as_bool) == Some(true));
+            if is_primary {
+                id_field = Some(name.to_string());
+            }
+
+            let field_type = field.get("type").or_else(|| field.get("data_type"));
+            let (datatype, is_sparse) = match field_type.map(milvus_type).as_deref() {
+                Some("FLOATVECTOR") => (None, false),
+                Some("FLOAT16VECTOR") => (Some(Datatype::Float16), false),
+                Some("BFLOAT16VECTOR") => (Some(Datatype::Bfloat16), false),
+                Some("SPARSEFLOATVECTOR") => (None, true),
+                Some(vector_type @ ("BINARYVECTOR" | "INT8VECTOR")) => {
+                    return Err(schema_error(format!(
+                        "field {name} has unsupported vector type {vector_type}",
+                    )));
+                }
+                // Scalar fields are stored as payload
+                _ => continue,
+            };
+
+            let distance = match metrics.get(name).map(String::as_str) {
+                None | Some("COSINE") => Distance::Cosine,
+                Some("IP") => Distance::Dot,
+                Some("L2") => Distance::Euclid,
+                Some(metric) => {
+                    return Err(schema_error(format!(
+                        "field {name} has unsupported metric {metric}",
+                    )));
+                }
+            };
+
+            let size = if is_sparse {
+                None
+            } else {
+                Some(milvus_dim(field).ok_or_else(|| {
+                    schema_error(format!("dimension of vector field {name} is not found"))
+                })?)
+            };
+
+            vectors.insert(
+                name.to_string(),
+                SourceVector {
+                    path: vec![name.to_string()],
+                    distance,
+                    size,
+                    datatype,
+                    is_sparse,
+                },
+            );
+        }
+
+        let id_field = id_field.ok_or_else(|| schema_error("primary key field is not found"))?;
+        if vectors.is_empty() {
+            return Err(schema_error("no vector fields found"));
+        }
+
+        Ok(Self {
+            format: BackupFormat::Milvus,
+            id_field,
+            vectors,
+        })
+    }
+
+    /// Map Weaviate class definition.
+    ///
+    /// Named vectors of `vectorConfig` keep their names, the single vector of a class without
+    /// named vectors becomes the default vector.
+    fn from_weaviate(schema: &Value) -> Result<Self, StorageError> {
+        let mut vectors = BTreeMap::new();
+        match schema.get("vectorConfig").and_then(Value::as_object) {
+            Some(vector_config) => {
+                for (name, config) in vector_config {
+                    let index_config = config.get("vectorIndexConfig");
+                    vectors.insert(
+                        name.clone(),
+                        SourceVector {
+                            path: vec!["vectors".to_string(), name.clone()],
+                            distance: weaviate_distance(name, index_config)?,
+                            size: None,
+                            datatype: None,
+                            is_sparse: false,
+                        },
+                    );
+                }
+            }
+            None => {
+                let index_config = schema.get("vectorIndexConfig");
+                vectors.insert(
+                    VectorNameBuf::new(),
+                    SourceVector {
+                        path: vec!["vector".to_string()],
+                        distance: weaviate_distance("", index_config)?,
+                        size: None,
+                        datatype: None,
+                        is_sparse: false,
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            format: BackupFormat::Weaviate,
+            id_field: "id".to_string(),
+            vectors,
+        })
+    }
+
+    fn point(&self, object: Value) -> Result<PointStruct, String> {
+        let Value::Object(mut object) = object else {
+            return Err("object must be a JSON object".to_string());
+        };
+
+        let id = match object.get(&self.id_field) {
+            Some(id) => point_id(id)?,
+            None => return Err(format!("id field {} is not found", self.id_field)),
+        };
+
+        let mut vectors = HashMap::with_capacity(self.vectors.len());
+        for (name, source) in &self.vectors {
+            let value = take_path(&mut object, &source.path);
+            match value {
+                None | Some(Value::Null) => {}
+                Some(value) if source.is_sparse => {
+                    vectors.insert(name.clone(), sparse_vector(value)?);
+                }
+                Some(value) => {
+                    let vector: Vec<f32> = serde_json::from_value(value)
+                        .map_err(|err| format!("invalid vector {name:?}: {err}"))?;
+                    vectors.insert(name.clone(), Vector::Dense(vector));
+                }
+            }
+        }
+
+        let payload = match self.format {
+            BackupFormat::Milvus => {
+                // Dynamic fields are exported in a separate object
+                if let Some(Value::Object(dynamic_fields)) = object.remove("$meta") {
+                    for (key, value) in dynamic_fields {
+                        object.entry(key).or_insert(value);
+                    }
+                }
+                object
+            }
+            BackupFormat::Weaviate => match object.remove("properties") {
+                Some(Value::Object(properties)) => properties,
+                _ => Map::new(),
+            },
+        };
+
+        Ok(PointStruct {
+            id,
+            vector: VectorStruct::Named(vectors),
+            payload: (!payload.is_empty()).then(|| Payload::from(payload)),
+            ttl: None,
+        })
+    }
+}
+
+/// Normalize Milvus data type: `FloatVector`, `FLOAT_VECTOR`, `DataType.FLOAT_VECTOR`
+/// and code `101` are all `FLOATVECTOR`
+fn milvus_type(value: &Value) -> String {
+    match value {
+        Value::Number(code) => match code.as_u64() {
+            Some(100) => "BINARYVECTOR",
+            Some(101) => "FLOATVECTOR",
+            Some(102) => "FLOAT16VECTOR",
+            Some(103) => "BFLOAT16VECTOR",
+            Some(104) => "SPARSEFLOATVECTOR",
+            Some(105) => "INT8VECTOR",
+            _ => "SCALAR",
+        }
+        .to_string(),
+        Value::String(name) => name
+            .trim_start_matches("DataType.")
+            .chars()
+            .filter(|c| *c != '_')
+            .collect::<String>()
+            .to_ascii_uppercase(),
+        _ => "SCALAR".to_string(),
+    }
+}
+
+/// Dimension of Milvus vector field: `{"params": {"dim": 768}}`,
+/// or `{"params": [{"key": "dim", "value": "768"}]}`
+fn milvus_dim(field: &Value) -> Option<NonZeroU64> {
+    let dim = match field.get("params")? {
+        Value::Object(params) => params.get("dim")?,
+        Value::Array(params) => params
+            .iter()
+            .find(|param| param.get("key").and_then(Value::as_str) == Some("dim"))?
+            .get("value")?,
+        _ => return None,
+    };
+
+    let dim = match dim {
+        Value::Number(dim) => dim.as_u64()?,
+        Value::String(dim) => dim.parse().ok()?,
+        _ => return None,
+    };
+    NonZeroU64::new(dim)
+}
+
+fn weaviate_distance(name: &str, index_config: Option<&Value>) -> Result<Distance, StorageError> {
+    if index_config
+        .and_then(|config| config.pointer("/multivector/enabled"))
+        .and_then(Value::as_bool)
+        == Some(true)
+    {
+        return Err(schema_error(format!(
+            "multi-vector {name:?} is not supported"
+        )));
+    }
+
+    let distance = index_config
+        .and_then(|config| config.get("distance"))
+        .and_then(Value::as_str);
+    match distance {
+        None | Some("cosine") => Ok(Distance::Cosine),
+        Some("dot") => Ok(Distance::Dot),
+        Some("l2-squared") => Ok(Distance::Euclid),
+        Some("manhattan") => Ok(Distance::Manhattan),
+        Some(distance) => Err(schema_error(format!(
+            "vector {name:?} has unsupported distance {distance}",
+        ))),
+    }
+}
+
+/// Non-negative integers and UUIDs are used as is, other ids are converted into UUIDs
+/// deterministically, so that repeated imports produce the same ids.
+fn point_id(value: &Value) -> Result<ExtendedPointId, String> {
+    match value {
+        Value::Number(number) => number
+            .as_u64()
+            .map(ExtendedPointId::NumId)
+            .ok_or_else(|| format!("id {number} is not a non-negative integer")),
+        Value::String(id) => Ok(ExtendedPointId::from_str(id).unwrap_or_else(|()| {
+            ExtendedPointId::Uuid(Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()))
+        })),
+        _ => Err(format!("id {value} must be a number or a string")),
+    }
+}
+
+/// Sparse vector as a map of indices to values `{"1": 0.5}`,
+/// or as `{"indices": [1], "values": [0.5]}`
+fn sparse_vector(value: Value) -> Result<Vector, String> {
+    let value = match value {
+        Value::Object(object) if !object.contains_key("indices") => {
+            let mut indices = Vec::with_capacity(object.len());
+            let mut values = Vec::with_capacity(object.len());
+            for (index, value) in object {
+                indices.push(Value::from(
+                    index
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid index {index} of sparse vector"))?,
+                ));
+                values.push(value);
+            }
+            serde_json::json!({ "indices": indices, "values": values })
+        }
+        value => value,
+    };
+
+    match serde_json::from_value(value) {
+        Ok(vector @ Vector::Sparse(_)) => Ok(vector),
+        Ok(_) => Err("invalid sparse vector".to_string()),
+        Err(err) => Err(format!("invalid sparse vector: {err}")),
+    }
+}
+
+/// Remove value at the path of keys from the object
+fn take_path(object: &mut Map<String, Value>, path: &[String]) -> Option<Value> {
+    let (last, parents) = path.split_last()?;
+    let mut object = object;
+    for key in parents {
+        object = object.get_mut(key)?.as_object_mut()?;
+    }
+    object.remove(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn payload(value: Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_milvus_schema() {
+        // Schema of the REST API
+        let schema = BackupSchema::from_milvus(&json!({
+            "collectionName": "books",
+            "fields": [
+                { "name": "book_id", "type": "VarChar", "primaryKey": true },
+                { "name": "title", "type": "VarChar" },
+                {
+                    "name": "embedding",
+                    "type": "FloatVector",
+                    "params": [{ "key": "dim", "value": "4" }],
+                },
+                { "name": "keywords", "type": "SparseFloatVector" },
+            ],
+            "indexes": [{ "fieldName": "embedding", "metricType": "IP" }],
+        }))
+        .unwrap();
+
+        assert_eq!(schema.id_field, "book_id");
+        let embedding = &schema.vectors["embedding"];
+        assert_eq!(embedding.distance, Distance::Dot);
+        assert_eq!(embedding.size, NonZeroU64::new(4));
+        assert!(schema.vectors["keywords"].is_sparse);
+
+        let point = schema
+            .point(json!({
+                "book_id": "book-1",
+                "title": "Dune",
+                "embedding": [0.1, 0.2, 0.3, 0.4],
+                "keywords": { "3": 0.5, "7": 0.25 },
+                "$meta": { "year": 1965 },
+            }))
+            .unwrap();
+
+        assert_eq!(
+            point.id,
+            ExtendedPointId::Uuid(Uuid::new_v5(&Uuid::NAMESPACE_OID, b"book-1")),
+        );
+        assert_eq!(
+            point.payload,
+            Some(payload(
+                json!({ "book_id": "book-1", "title": "Dune", "year": 1965 })
+            )),
+        );
+        let VectorStruct::Named(vectors) = point.vector else {
+            panic!("expected named vectors");
+        };
+        assert_eq!(
+            vectors["embedding"],
+            Vector::Dense(vec![0.1, 0.2, 0.3, 0.4])
+        );
+        assert!(matches!(&vectors["keywords"], Vector::Sparse(sparse) if sparse.indices == [3, 7]));
+
+        // Schema of the Python client
+        let schema = BackupSchema::from_milvus(&json!({
+            "fields": [
+                { "name": "id", "type": 5, "is_primary": true },
+                { "name": "vector", "type": 101, "params": { "dim": 2 } },
+            ],
+        }))
+        .unwrap();
+        assert_eq!(schema.vectors["vector"].distance, Distance::Cosine);
+        assert_eq!(schema.vectors["vector"].size, NonZeroU64::new(2));
+
+        let binary = json!({
+            "fields": [
+                { "name": "id", "type": "Int64", "primaryKey": true },
+                { "name": "vector", "type": "BinaryVector", "params": { "dim": 8 } },
+            ],
+        });
+        assert!(BackupSchema::from_milvus(&binary).is_err());
+    }
+
+    #[test]
+    fn test_weaviate_schema() {
+        let schema = BackupSchema::from_weaviate(&json!({
+            "class": "Article",
+            "vectorConfig": {
+                "title": { "vectorIndexConfig": { "distance": "l2-squared" } },
+                "body": { "vectorIndexConfig": { "distance": "dot" } },
+            },
+            "properties": [{ "name": "title", "dataType": ["text"] }],
+        }))
+        .unwrap();
+        assert_eq!(schema.vectors["title"].distance, Distance::Euclid);
+        assert_eq!(schema.vectors["body"].distance, Distance::Dot);
+
+        let objects = BackupFormat::Weaviate.unwrap_objects(json!({
+            "objects": [{
+                "class": "Article",
+                "id": "0a4c5b3c-2f3b-4b8e-9c3c-6a1a4a8f4b1e",
+                "properties": { "title": "Hello" },
+                "vectors": { "title": [1.0, 0.0], "body": [0.0, 1.0, 0.0] },
+            }],
+            "totalResults": 1,
+        }));
+        assert_eq!(objects.len(), 1);
+
+        let point = schema.point(objects.into_iter().next().unwrap()).unwrap();
+        assert_eq!(point.payload, Some(payload(json!({ "title": "Hello" }))));
+        assert_eq!(infer_vector_size("body", &[point]).unwrap().get(), 3);
+
+        let schema = BackupSchema::from_weaviate(&json!({ "class": "Article" })).unwrap();
+        assert_eq!(schema.vectors[""].path, ["vector"]);
+    }
+}
@@ -0,0 +1,890 @@
+//! Bulk import of points from Parquet and Arrow IPC files.
+//!
+//! A file is split into chunks: row groups of Parquet, or record batches of Arrow IPC. Chunks are
+//! read and upserted in parallel, and completed chunks are recorded in a checkpoint, so an
+//! interrupted import with the same `import_id` continues from where it stopped.
+
+#[cfg(feature = "parquet")]
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+
+use api::rest::schema::{PointInsertOperations, PointStruct, PointsList};
+#[cfg(feature = "parquet")]
+use api::rest::schema::{Vector, VectorStruct};
+#[cfg(feature = "parquet")]
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch};
+#[cfg(feature = "parquet")]
+use arrow::compute::{CastOptions, cast, cast_with_options};
+#[cfg(feature = "parquet")]
+use arrow::datatypes::{DataType, Field, Float32Type, SchemaRef, UInt64Type};
+#[cfg(feature = "parquet")]
+use arrow::error::ArrowError;
+#[cfg(feature = "parquet")]
+use arrow::ipc::reader::FileReader;
+use collection::collection::Collection;
+use collection::operations::point_ops::WriteOrdering;
+use collection::operations::types::{CollectionError, CollectionResult};
+use collection::operations::verification::StrictModeVerification;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+#[cfg(feature = "parquet")]
+use common::fs::{atomic_save_json, read_json};
+#[cfg(feature = "parquet")]
+use futures::{StreamExt, TryStreamExt};
+#[cfg(feature = "parquet")]
+use itertools::izip;
+#[cfg(feature = "parquet")]
+use parking_lot::Mutex;
+#[cfg(feature = "parquet")]
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use reqwest::Url;
+use schemars::JsonSchema;
+#[cfg(feature = "parquet")]
+use segment::types::{ExtendedPointId, Payload};
+use segment::types::{Filter, SearchParams, StrictModeConfig};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use tempfile::TempPath;
+use tokio::io::AsyncWriteExt;
+use validator::{Validate, ValidationError};
+
+use super::auth::Auth;
+use crate::common::inference::api_keys::InferenceApiKeys;
+use crate::common::inference::params::InferenceParams;
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+use crate::common::update::{InternalUpdateParams, UpdateParams, do_upsert_points};
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+#[cfg(feature = "parquet")]
+const DEFAULT_PARALLELISM: usize = 4;
+
+/// Directory in the storage, where checkpoints of imports are kept
+#[cfg(feature = "parquet")]
+const IMPORTS_DIR: &str = "imports";
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Parquet,
+    Arrow,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ImportPoints {
+    /// Location of the file to import:
+    /// - URL of the file, e.g. pre-signed URL of an object storage `https://bucket.s3.amazonaws.com/points.parquet?...`,
+    ///   requires manage access, as the file is downloaded by the server
+    /// - Local path `file:///qdrant/imports/points.parquet`, must be inside `storage.import_path`
+    pub location: Url,
+    /// Format of the file. If not set, derived from the file extension:
+    /// `.parquet` for Parquet, `.arrow`, `.ipc` or `.feather` for Arrow IPC.
+    #[serde(default)]
+    pub format: Option<ImportFormat>,
+    /// Column with point ids: unsigned integers, or strings with UUIDs or integers
+    pub id_column: String,
+    /// Columns with dense vectors, which are lists of floats, by vector name.
+    /// Use empty name for the default vector.
+    #[validate(length(min = 1))]
+    pub vector_columns: HashMap<String, String>,
+    /// Columns to store as payload. If not set, all columns except id and vectors are stored.
+    #[serde(default)]
+    pub payload_columns: Option<Vec<String>>,
+    /// Number of points upserted at once. Default: 1000
+    #[serde(default)]
+    pub batch_size: Option<NonZeroUsize>,
+    /// Number of chunks of the file imported in parallel. Default: 4
+    #[serde(default)]
+    pub parallelism: Option<NonZeroUsize>,
+    /// Identifier of the import, which allows to continue it, if it was interrupted.
+    /// Chunks of the file, which were already imported with the same identifier, are skipped.
+    #[serde(default)]
+    #[validate(custom(function = validate_import_id))]
+    pub import_id: Option<String>,
+}
+
+fn validate_import_id(import_id: &str) -> Result<(), ValidationError> {
+    let is_valid = !import_id.is_empty()
+        && import_id.len() <= 64
+        && import_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("import_id")
+            .with_message("must be 1 to 64 ASCII letters, digits, dashes or underscores".into()))
+    }
+}
+
+impl StrictModeVerification for ImportPoints {
+    async fn check_custom(
+        &self,
+        _collection: &Collection,
+        strict_mode_config: &StrictModeConfig,
+    ) -> CollectionResult<()> {
+        // Reject the import at once, instead of failing on the first upserted batch
+        let batch_size = self
+            .batch_size
+            .map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get);
+        if let Some(upsert_max_batchsize) = strict_mode_config.upsert_max_batchsize
+            && batch_size > upsert_max_batchsize
+        {
+            return Err(CollectionError::strict_mode(
+                format!(
+                    "Import batch size {batch_size} exceeds the upsert limit {upsert_max_batchsize}"
+                ),
+                format!("Set `batch_size` of the import to {upsert_max_batchsize} or less."),
+            ));
+        }
+        Ok(())
+    }
+
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct ImportResult {
+    /// Number of chunks in the file
+    pub chunks: usize,
+    /// Number of chunks, which were skipped, as they were imported before
+    pub skipped_chunks: usize,
+    /// Number of points imported by this request
+    pub points: usize,
+}
+
+/// Import points into the collection from a Parquet or Arrow IPC file.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe, completed chunks are recorded in the checkpoint.
+pub async fn do_import_points(
+    dispatcher: Dispatcher,
+    collection_name: String,
+    request: ImportPoints,
+    auth: Auth,
+    http_client: reqwest::Client,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ImportResult, StorageError> {
+    auth.check_collection_access(
+        &collection_name,
+        AccessRequirements::new().write(),
+        "import_points",
+    )?;
+
+    // Server downloads the file, so a URL could be used to reach hosts of the internal network.
+    // Require the same access as for recovery of snapshots from URL.
+    if matches!(request.location.scheme(), "http" | "https") {
+        auth.check_global_access(AccessRequirements::new().manage(), "import_points")?;
+    }
+
+    let toc = StrictModeCheckedTocProvider::new(&dispatcher)
+        .check_strict_mode(&request, &collection_name, None, &auth)
+        .await?
+        .clone();
+
+    #[cfg(not(feature = "parquet"))]
+    {
+        let _ = (toc, http_client, hw_measurement_acc);
+        Err(no_parquet_support())
+    }
+
+    #[cfg(feature = "parquet")]
+    {
+        let ImportPoints {
+            location,
+            format,
+            id_column,
+            vector_columns,
+            payload_columns,
+            batch_size,
+            parallelism,
+            import_id,
+        } = request;
+
+        let format = match format {
+            Some(format) => format,
+            None => format_from_extension(&location)?,
+        };
+
+        // Keep downloaded file until the import is done
+        let (path, _downloaded) = import_file_path(&location, &dispatcher, &http_client).await?;
+
+        let file = tokio::task::spawn_blocking(move || ImportFile::open(path, format)).await??;
+        let mapping = ColumnMapping::new(&file.schema, id_column, vector_columns, payload_columns)?;
+
+        let checkpoint = match &import_id {
+            Some(import_id) => Some(ImportCheckpoint::load(
+                toc.storage_path()
+                    .join(IMPORTS_DIR)
+                    .join(format!("{collection_name}-{import_id}.json")),
+                &location,
+            )?),
+            None => None,
+        };
+
+        let pending_chunks: Vec<_> = (0..file.chunks)
+            .filter(|chunk| {
+                checkpoint
+                    .as_ref()
+                    .is_none_or(|checkpoint| !checkpoint.is_completed(*chunk))
+            })
+            .collect();
+        let skipped_chunks = file.chunks - pending_chunks.len();
+
+        let importer = ChunkImporter {
+            dispatcher: &dispatcher,
+            collection_name: &collection_name,
+            auth: &auth,
+            file: Arc::new(file),
+            mapping: Arc::new(mapping),
+            batch_size: batch_size.map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get),
+            hw_measurement_acc: &hw_measurement_acc,
+        };
+
+        let points = futures::stream::iter(pending_chunks)
+            .map(|chunk| importer.import_chunk(chunk))
+            .buffer_unordered(parallelism.map_or(DEFAULT_PARALLELISM, NonZeroUsize::get))
+            .and_then(|(chunk, points)| {
+                let result = match &checkpoint {
+                    Some(checkpoint) => checkpoint.complete(chunk).map(|()| points),
+                    None => Ok(points),
+                };
+                futures::future::ready(result)
+            })
+            .try_fold(0, |total, points| futures::future::ok(total + points))
+            .await?;
+
+        if let Some(checkpoint) = checkpoint {
+            checkpoint.remove()?;
+        }
+
+        Ok(ImportResult {
+            chunks: importer.file.chunks,
+            skipped_chunks,
+            points,
+        })
+    }
+}
+
+#[cfg(not(feature = "parquet"))]
+fn no_parquet_support() -> StorageError {
+    StorageError::bad_request(
+        "Import of Parquet and Arrow files is not supported, Qdrant is built without `parquet` feature",
+    )
+}
+
+#[cfg(feature = "parquet")]
+fn format_from_extension(location: &Url) -> Result<ImportFormat, StorageError> {
+    let extension = Path::new(location.path())
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("parquet") => Ok(ImportFormat::Parquet),
+        Some("arrow" | "ipc" | "feather") => Ok(ImportFormat::Arrow),
+        _ => Err(StorageError::bad_request(format!(
+            "Can't derive format of {location} from the file extension, please specify it",
+        ))),
+    }
+}
+
+/// Resolve location of an import file into a local path.
+///
+/// Files available by HTTP(S) URL are downloaded into a temporary file, which is removed when
+/// the returned [`TempPath`] is dropped.
+pub(crate) async fn import_file_path(
+    location: &Url,
+    dispatcher: &Dispatcher,
+    http_client: &reqwest::Client,
+) -> Result<(PathBuf, Option<TempPath>), StorageError> {
+    match location.scheme() {
+        "file" => Ok((local_import_path(location, dispatcher.import_path())?, None)),
+        "http" | "https" => {
+            let downloaded =
+                download_import_file(http_client, location, &dispatcher.temp_path()?).await?;
+            Ok((downloaded.to_path_buf(), Some(downloaded)))
+        }
+        scheme => Err(StorageError::bad_request(format!(
+            "URL {location} with scheme {scheme} is not supported",
+        ))),
+    }
+}
+
+/// Resolve `file://` URL into a path inside the import directory.
+fn local_import_path(location: &Url, import_path: Option<&Path>) -> Result<PathBuf, StorageError> {
+    let Some(import_path) = import_path else {
+        return Err(StorageError::bad_request(
+            "Import from local files is disabled, set `storage.import_path` to enable it",
+        ));
+    };
+
+    let path = location.to_file_path().map_err(|_| {
+        StorageError::bad_request("Invalid file URI, file path must be absolute or on localhost")
+    })?;
+
+    let canonical_path = fs_err::canonicalize(&path).map_err(|err| {
+        StorageError::bad_request(format!(
+            "Failed to resolve import file path {path:?}: {err}"
+        ))
+    })?;
+    let canonical_import_path = fs_err::canonicalize(import_path).map_err(|err| {
+        StorageError::service_error(format!(
+            "Failed to resolve import directory {import_path:?}: {err}"
+        ))
+    })?;
+
+    if !canonical_path.starts_with(&canonical_import_path) {
+        return Err(StorageError::forbidden(format!(
+            "Import file path must be inside the import directory {canonical_import_path:?}",
+        )));
+    }
+
+    Ok(canonical_path)
+}
+
+async fn download_import_file(
+    client: &reqwest::Client,
+    url: &Url,
+    download_dir: &Path,
+) -> Result<TempPath, StorageError> {
+    let download_start_time = tokio::time::Instant::now();
+
+    let path = tempfile::Builder::new()
+        .prefix("import-")
+        .suffix(".download")
+        .tempfile_in(download_dir)?
+        .into_temp_path();
+
+    let mut response = client.get(url.clone()).send().await?.error_for_status()?;
+
+    let mut file = fs_err::tokio::File::create(&path).await?;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+
+    log::debug!(
+        "Import file download completed: path={path:?}, duration={:.2}s",
+        download_start_time.elapsed().as_secs_f64(),
+    );
+
+    Ok(path)
+}
+
+#[cfg(feature = "parquet")]
+/// Location of the file without query, which may contain credentials, or change between
+/// attempts, e.g. signature of a pre-signed URL.
+fn checkpoint_location(location: &Url) -> String {
+    let mut location = location.clone();
+    location.set_query(None);
+    location.set_fragment(None);
+    location.to_string()
+}
+
+#[cfg(feature = "parquet")]
+/// Progress of an import, persisted after every completed chunk
+struct ImportCheckpoint {
+    path: PathBuf,
+    state: Mutex<CheckpointState>,
+}
+
+#[cfg(feature = "parquet")]
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CheckpointState {
+    location: String,
+    completed_chunks: BTreeSet<usize>,
+}
+
+#[cfg(feature = "parquet")]
+impl ImportCheckpoint {
+    fn load(path: PathBuf, location: &Url) -> Result<Self, StorageError> {
+        let state = if path.exists() {
+            let state: CheckpointState = read_json(&path)?;
+            if state.location != checkpoint_location(location) {
+                return Err(StorageError::bad_request(format!(
+                    "Import with the same id was started from another location {}",
+                    state.location,
+                )));
+            }
+            state
+        } else {
+            if let Some(dir) = path.parent() {
+                fs_err::create_dir_all(dir)?;
+            }
+            CheckpointState {
+                location: checkpoint_location(location),
+                completed_chunks: BTreeSet::new(),
+            }
+        };
+
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    fn is_completed(&self, chunk: usize) -> bool {
+        self.state.lock().completed_chunks.contains(&chunk)
+    }
+
+    fn complete(&self, chunk: usize) -> Result<(), StorageError> {
+        let mut state = self.state.lock();
+        state.completed_chunks.insert(chunk);
+        atomic_save_json(&self.path, &*state)?;
+        Ok(())
+    }
+
+    fn remove(self) -> Result<(), StorageError> {
+        fs_err::remove_file(&self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+/// File to import, with its schema and number of independently readable chunks
+struct ImportFile {
+    path: PathBuf,
+    format: ImportFormat,
+    schema: SchemaRef,
+    chunks: usize,
+}
+
+#[cfg(feature = "parquet")]
+impl ImportFile {
+    fn open(path: PathBuf, format: ImportFormat) -> Result<Self, StorageError> {
+        let file = fs_err::File::open(&path)?.into_parts().0;
+
+        let (schema, chunks) = match format {
+            ImportFormat::Parquet => {
+                let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+                    .map_err(|err| read_error(&path, err))?;
+                (
+                    builder.schema().clone(),
+                    builder.metadata().num_row_groups(),
+                )
+            }
+            ImportFormat::Arrow => {
+                let reader =
+                    FileReader::try_new(file, None).map_err(|err| read_error(&path, err))?;
+                (reader.schema(), reader.num_batches())
+            }
+        };
+
+        Ok(Self {
+            path,
+            format,
+            schema,
+            chunks,
+        })
+    }
+
+    /// Read the chunk in batches of at most `batch_size` rows.
+    fn read_chunk(
+        &self,
+        chunk: usize,
+        batch_size: usize,
+        mut f: impl FnMut(RecordBatch) -> Result<(), StorageError>,
+    ) -> Result<(), StorageError> {
+        let file = fs_err::File::open(&self.path)?.into_parts().0;
+
+        let batches: Box<dyn Iterator<Item = Result<RecordBatch, ArrowError>>> = match self.format {
+            ImportFormat::Parquet => Box::new(
+                ParquetRecordBatchReaderBuilder::try_new(file)
+                    .and_then(|builder| {
+                        builder
+                            .with_row_groups(vec![chunk])
+                            .with_batch_size(batch_size)
+                            .build()
+                    })
+                    .map_err(|err| read_error(&self.path, err))?,
+            ),
+            ImportFormat::Arrow => {
+                let mut reader =
+                    FileReader::try_new(file, None).map_err(|err| read_error(&self.path, err))?;
+                reader
+                    .set_index(chunk)
+                    .map_err(|err| read_error(&self.path, err))?;
+                Box::new(reader.take(1))
+            }
+        };
+
+        for batch in batches {
+            let batch = batch.map_err(|err| read_error(&self.path, err))?;
+            for offset in (0..batch.num_rows()).step_by(batch_size) {
+                f(batch.slice(offset, batch_size.min(batch.num_rows() - offset)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn read_error(path: &Path, err: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_input(format!("Failed to read import file {path:?}: {err}"))
+}
+
+#[cfg(feature = "parquet")]
+/// Mapping of file columns to point ids, vectors and payload
+struct ColumnMapping {
+    id_column: String,
+    vector_columns: HashMap<String, String>,
+    payload_columns: Vec<String>,
+}
+
+#[cfg(feature = "parquet")]
+impl ColumnMapping {
+    fn new(
+        schema: &SchemaRef,
+        id_column: String,
+        vector_columns: HashMap<String, String>,
+        payload_columns: Option<Vec<String>>,
+    ) -> Result<Self, StorageError> {
+        let payload_columns = payload_columns.unwrap_or_else(|| {
+            schema
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .filter(|name| *name != id_column && !vector_columns.values().any(|v| v == name))
+                .collect()
+        });
+
+        let columns = std::iter::once(&id_column)
+            .chain(vector_columns.values())
+            .chain(&payload_columns);
+        for column in columns {
+            if schema.field_with_name(column).is_err() {
+                return Err(StorageError::bad_request(format!(
+                    "Column {column} is not found in the import file",
+                )));
+            }
+        }
+
+        Ok(Self {
+            id_column,
+            vector_columns,
+            payload_columns,
+        })
+    }
+
+    fn points(&self, batch: &RecordBatch) -> Result<Vec<PointStruct>, StorageError> {
+        let ids = point_ids(column(batch, &self.id_column)?)?;
+
+        let mut vectors = vec![HashMap::new(); batch.num_rows()];
+        for (name, column_name) in &self.vector_columns {
+            let column_vectors = dense_vectors(column(batch, column_name)?)?;
+            for (point_vectors, vector) in vectors.iter_mut().zip(column_vectors) {
+                if let Some(vector) = vector {
+                    point_vectors.insert(name.clone(), Vector::Dense(vector));
+                }
+            }
+        }
+
+        let payloads = self.payloads(batch)?;
+
+        Ok(izip!(ids, vectors, payloads)
+            .map(|(id, vectors, payload)| PointStruct {
+                id,
+                vector: VectorStruct::Named(vectors),
+                payload,
+                ttl: None,
+            })
+            .collect())
+    }
+
+    fn payloads(&self, batch: &RecordBatch) -> Result<Vec<Option<Payload>>, StorageError> {
+        if self.payload_columns.is_empty() || batch.num_rows() == 0 {
+            return Ok(vec![None; batch.num_rows()]);
+        }
+
+        let schema = batch.schema();
+        let indices: Vec<_> = self
+            .payload_columns
+            .iter()
+            .map(|name| schema.index_of(name))
+            .collect::<Result<_, _>>()
+            .map_err(convert_error)?;
+        let batch = batch.project(&indices).map_err(convert_error)?;
+
+        // Nulls are omitted from the payload
+        let mut writer = arrow::json::ArrayWriter::new(Vec::new());
+        writer.write(&batch).map_err(convert_error)?;
+        writer.finish().map_err(convert_error)?;
+
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+            serde_json::from_slice(&writer.into_inner())?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Some(Payload::from(row)))
+            .collect())
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn convert_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_input(format!(
+        "Failed to convert import file rows into points: {err}"
+    ))
+}
+
+#[cfg(feature = "parquet")]
+fn column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef, StorageError> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| StorageError::bad_request(format!("Column {name} is not found")))
+}
+
+#[cfg(feature = "parquet")]
+fn point_ids(column: &ArrayRef) -> Result<Vec<ExtendedPointId>, StorageError> {
+    if column.null_count() > 0 {
+        return Err(StorageError::bad_input("Id column must not contain nulls"));
+    }
+
+    match column.data_type() {
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            let ids = cast(column, &DataType::Utf8).map_err(convert_error)?;
+            ids.as_string::<i32>()
+                .iter()
+                .flatten()
+                .map(|id| {
+                    id.parse::<ExtendedPointId>()
+                        .map_err(|()| StorageError::bad_input(format!("Invalid point id {id}")))
+                })
+                .collect()
+        }
+        data_type if data_type.is_integer() => {
+            // Negative ids are not allowed
+            let options = CastOptions {
+                safe: false,
+                ..Default::default()
+            };
+            let ids = cast_with_options(column, &DataType::UInt64, &options)
+                .map_err(|err| StorageError::bad_input(format!("Invalid point id: {err}")))?;
+            Ok(ids
+                .as_primitive::<UInt64Type>()
+                .values()
+                .iter()
+                .map(|&id| ExtendedPointId::NumId(id))
+                .collect())
+        }
+        data_type => Err(StorageError::bad_input(format!(
+            "Id column must contain unsigned integers or strings, but it is {data_type}",
+        ))),
+    }
+}
+
+#[cfg(feature = "parquet")]
+/// Convert a column of lists of floats into dense vectors, nulls are converted into `None`.
+fn dense_vectors(column: &ArrayRef) -> Result<Vec<Option<Vec<f32>>>, StorageError> {
+    if !matches!(
+        column.data_type(),
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _),
+    ) {
+        return Err(StorageError::bad_input(format!(
+            "Vector column must contain lists of floats, but it is {}",
+            column.data_type(),
+        )));
+    }
+
+    let list_type = DataType::List(Arc::new(Field::new_list_field(DataType::Float32, true)));
+    let lists = cast(column, &list_type).map_err(convert_error)?;
+
+    lists
+        .as_list::<i32>()
+        .iter()
+        .map(|values| {
+            values
+                .map(|values| {
+                    if values.null_count() > 0 {
+                        return Err(StorageError::bad_input(
+                            "Vectors must not contain null values",
+                        ));
+                    }
+                    Ok(values.as_primitive::<Float32Type>().values().to_vec())
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+#[cfg(feature = "parquet")]
+struct ChunkImporter<'a> {
+    dispatcher: &'a Dispatcher,
+    collection_name: &'a str,
+    auth: &'a Auth,
+    file: Arc<ImportFile>,
+    mapping: Arc<ColumnMapping>,
+    batch_size: usize,
+    hw_measurement_acc: &'a HwMeasurementAcc,
+}
+
+#[cfg(feature = "parquet")]
+impl ChunkImporter<'_> {
+    /// Import all points of the chunk, returns the chunk and the number of imported points.
+    async fn import_chunk(&self, chunk: usize) -> Result<(usize, usize), StorageError> {
+        // Points are read in a blocking thread, and upserted as soon as a batch is ready
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+
+        let file = self.file.clone();
+        let mapping = self.mapping.clone();
+        let batch_size = self.batch_size;
+        let read_task = tokio::task::spawn_blocking(move || {
+            file.read_chunk(chunk, batch_size, |batch| {
+                let points = mapping.points(&batch)?;
+                sender
+                    .blocking_send(points)
+                    .map_err(|_| StorageError::service_error("Import of points was stopped"))
+            })
+        });
+
+        let mut imported = 0;
+        while let Some(points) = receiver.recv().await {
+            imported += points.len();
+            self.upsert(points).await?;
+        }
+        read_task.await??;
+
+        Ok((chunk, imported))
+    }
+
+    async fn upsert(&self, points: Vec<PointStruct>) -> Result<(), StorageError> {
+        upsert_points_batch(
+            self.dispatcher,
+            self.collection_name,
+            points,
+            self.auth,
+            self.hw_measurement_acc,
+        )
+        .await
+    }
+}
+
+/// Upsert a batch of points, waiting until they are applied.
+pub(crate) async fn upsert_points_batch(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    points: Vec<PointStruct>,
+    auth: &Auth,
+    hw_measurement_acc: &HwMeasurementAcc,
+) -> Result<(), StorageError> {
+    let operation = PointInsertOperations::PointsList(PointsList {
+        points,
+        shard_key: None,
+        update_filter: None,
+        update_mode: None,
+    });
+
+    let params = UpdateParams {
+        wait: true,
+        ordering: WriteOrdering::default(),
+        timeout: None,
+    };
+
+    do_upsert_points(
+        StrictModeCheckedTocProvider::new(dispatcher),
+        collection_name.to_string(),
+        operation,
+        InternalUpdateParams::default(),
+        params,
+        auth.clone(),
+        InferenceParams::new(InferenceApiKeys::default(), None),
+        hw_measurement_acc.clone(),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use arrow::array::{FixedSizeListArray, Float32Array, StringArray, UInt64Array};
+    use arrow::datatypes::Schema;
+
+    use super::*;
+
+    #[test]
+    fn test_record_batch_to_points() {
+        let vectors = FixedSizeListArray::try_new(
+            Arc::new(Field::new_list_field(DataType::Float32, true)),
+            2,
+            Arc::new(Float32Array::from(vec![0.1, 0.2, 0.3, 0.4])),
+            None,
+        )
+        .unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::UInt64, false),
+            Field::new("embedding", vectors.data_type().clone(), true),
+            Field::new("city", DataType::Utf8, true),
+        ]));
+
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(UInt64Array::from(vec![1, 2])),
+                Arc::new(vectors),
+                Arc::new(StringArray::from(vec![Some("Berlin"), None])),
+            ],
+        )
+        .unwrap();
+
+        let mapping = ColumnMapping::new(
+            &schema,
+            "id".to_string(),
+            HashMap::from([(String::new(), "embedding".to_string())]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(mapping.payload_columns, vec!["city".to_string()]);
+
+        let points = mapping.points(&batch).unwrap();
+        assert_eq!(points.len(), 2);
+
+        assert_eq!(points[0].id, ExtendedPointId::NumId(1));
+        assert_eq!(
+            points[0].vector,
+            VectorStruct::Named(HashMap::from([(
+                String::new(),
+                Vector::Dense(vec![0.1, 0.2]),
+            )])),
+        );
+        assert_eq!(
+            points[0].payload,
+            Some(serde_json::from_value(serde_json::json!({ "city": "Berlin" })).unwrap()),
+        );
+        assert_eq!(points[1].payload, Some(Payload::default()));
+
+        let missing_column = ColumnMapping::new(
+            &schema,
+            "uuid".to_string(),
+            HashMap::from([(String::new(), "embedding".to_string())]),
+            None,
+        );
+        assert!(missing_column.is_err());
+    }
+}
@@ -0,0 +1,482 @@
+//! Bulk upsert of points from newline-delimited JSON or CSV request bodies.
+//!
+//! The body is parsed in a blocking thread and upserted in batches. Lines, which can't be
+//! parsed into points, are skipped and reported in the result with their line numbers.
+//!
+//! Bodies compressed with gzip are detected by the gzip header and decompressed.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::num::NonZeroUsize;
+
+use api::rest::schema::{PointStruct, Vector, VectorStruct};
+use bytes::{Buf, Bytes};
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use flate2::read::MultiGzDecoder;
+use schemars::JsonSchema;
+use segment::types::{ExtendedPointId, Payload, VectorNameBuf};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use tokio::sync::mpsc;
+use validator::Validate;
+
+use super::auth::Auth;
+use crate::common::bulk_import::upsert_points_batch;
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Maximum number of errors listed in the result, further errors are only counted
+const MAX_REPORTED_ERRORS: usize = 100;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+const CSV_ID_COLUMN: &str = "id";
+/// Column of the default vector
+const CSV_VECTOR_COLUMN: &str = "vector";
+/// Prefix of the columns of named vectors, e.g. `vector.image`
+const CSV_NAMED_VECTOR_PREFIX: &str = "vector.";
+
+/// Format of the bulk upsert body
+///
+/// * `jsonl` - newline-delimited JSON, a point on each line
+/// * `csv` - CSV with header
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkUpsertFormat {
+    Jsonl,
+    Csv,
+}
+
+impl BulkUpsertFormat {
+    /// Derive format from the `Content-Type` of the body
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        match mime.as_str() {
+            "application/x-ndjson"
+            | "application/jsonl"
+            | "application/json-lines"
+            | "application/jsonlines" => Some(Self::Jsonl),
+            "text/csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct BulkUpsertResult {
+    /// Number of upserted points
+    pub points: usize,
+    /// Number of lines, which were not upserted
+    pub failed: usize,
+    /// Errors of the failed lines, at most 100
+    pub errors: Vec<BulkUpsertLineError>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct BulkUpsertLineError {
+    /// Number of the line in the body, starting from 1
+    pub line: usize,
+    pub error: String,
+}
+
+/// Upsert points from a newline-delimited JSON or CSV body.
+///
+/// Each JSON line is a point, in the same format as in the upsert request.
+/// CSV must have a header with `id` column, `vector` column for the default vector and
+/// `vector.{name}` columns for named vectors, as JSON arrays. Other columns are payload:
+/// values, which are valid JSON, are stored as JSON, others are stored as strings,
+/// and empty values are omitted.
+pub async fn do_bulk_upsert(
+    dispatcher: Dispatcher,
+    collection_name: String,
+    body: Bytes,
+    format: Option<BulkUpsertFormat>,
+    batch_size: Option<NonZeroUsize>,
+    auth: Auth,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<BulkUpsertResult, StorageError> {
+    auth.check_collection_access(
+        &collection_name,
+        AccessRequirements::new().write(),
+        "bulk_upsert_points",
+    )?;
+
+    let Some(format) = format else {
+        return Err(StorageError::bad_request(
+            "Format of the body is not specified, set `format` parameter, \
+             or `Content-Type` header to `application/x-ndjson` or `text/csv`",
+        ));
+    };
+
+    // Points are parsed in a blocking thread, and upserted as soon as a batch is ready
+    let (sender, mut receiver) = mpsc::channel(1);
+    let batcher = PointsBatcher {
+        sender,
+        batch_size: batch_size.map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get),
+        batch: PointsBatch::default(),
+        errors: LineErrors::default(),
+    };
+    let parse_task = tokio::task::spawn_blocking(move || parse_body(body, format, batcher));
+
+    let mut points = 0;
+    let mut upsert_errors = LineErrors::default();
+    while let Some(batch) = receiver.recv().await {
+        let PointsBatch {
+            first_line,
+            last_line,
+            points: batch_points,
+        } = batch;
+        let batch_len = batch_points.len();
+
+        match upsert_points_batch(
+            &dispatcher,
+            &collection_name,
+            batch_points,
+            &auth,
+            &hw_measurement_acc,
+        )
+        .await
+        {
+            Ok(()) => points += batch_len,
+            // Points of a batch are rejected together, so the whole batch is reported
+            Err(err @ (StorageError::BadInput { .. } | StorageError::BadRequest { .. })) => {
+                upsert_errors.add(
+                    first_line,
+                    batch_len,
+                    format!("Points of lines {first_line}-{last_line} are rejected: {err}"),
+                );
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let mut errors = parse_task.await??;
+    errors.merge(upsert_errors);
+
+    Ok(BulkUpsertResult {
+        points,
+        failed: errors.failed,
+        errors: errors.errors,
+    })
+}
+
+fn parse_body(
+    body: Bytes,
+    format: BulkUpsertFormat,
+    mut batcher: PointsBatcher,
+) -> Result<LineErrors, StorageError> {
+    let reader: Box<dyn BufRead> = if body.starts_with(&GZIP_MAGIC) {
+        Box::new(BufReader::new(MultiGzDecoder::new(body.reader())))
+    } else {
+        Box::new(body.reader())
+    };
+
+    match format {
+        BulkUpsertFormat::Jsonl => parse_jsonl(reader, &mut batcher)?,
+        BulkUpsertFormat::Csv => parse_csv(reader, &mut batcher)?,
+    }
+
+    batcher.finish()
+}
+
+fn parse_jsonl(reader: impl BufRead, batcher: &mut PointsBatcher) -> Result<(), StorageError> {
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(body_error)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        batcher.push(index + 1, json_point(&line))?;
+    }
+    Ok(())
+}
+
+fn json_point(line: &str) -> Result<PointStruct, String> {
+    let point: PointStruct = serde_json::from_str(line).map_err(|err| err.to_string())?;
+    point.validate().map_err(|err| err.to_string())?;
+    Ok(point)
+}
+
+fn parse_csv(reader: impl Read, batcher: &mut PointsBatcher) -> Result<(), StorageError> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let columns = CsvColumns::new(reader.headers().map_err(body_error)?)?;
+
+    let mut record = csv::StringRecord::new();
+    loop {
+        match reader.read_record(&mut record) {
+            Ok(false) => break,
+            Ok(true) => {
+                let line = record.position().map_or(0, |position| position.line());
+                batcher.push(line as usize, columns.point(&record))?;
+            }
+            Err(err) if matches!(err.kind(), csv::ErrorKind::Io(_)) => {
+                return Err(body_error(err));
+            }
+            // Malformed rows are skipped, reader continues from the next row
+            Err(err) => {
+                let line = err.position().map_or(0, |position| position.line());
+                batcher.errors.add(line as usize, 1, err.to_string());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn body_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_input(format!("Failed to read request body: {err}"))
+}
+
+/// Mapping of CSV columns to point ids, vectors and payload
+struct CsvColumns {
+    id: usize,
+    vectors: Vec<(usize, VectorNameBuf)>,
+    payload: Vec<(usize, String)>,
+}
+
+impl CsvColumns {
+    fn new(headers: &csv::StringRecord) -> Result<Self, StorageError> {
+        let mut id = None;
+        let mut vectors = Vec::new();
+        let mut payload = Vec::new();
+
+        for (index, header) in headers.iter().enumerate() {
+            if header == CSV_ID_COLUMN {
+                id = Some(index);
+            } else if header == CSV_VECTOR_COLUMN {
+                vectors.push((index, VectorNameBuf::new()));
+            } else if let Some(name) = header.strip_prefix(CSV_NAMED_VECTOR_PREFIX) {
+                vectors.push((index, name.to_string()));
+            } else {
+                payload.push((index, header.to_string()));
+            }
+        }
+
+        let Some(id) = id else {
+            return Err(StorageError::bad_input(format!(
+                "CSV header must contain `{CSV_ID_COLUMN}` column",
+            )));
+        };
+
+        Ok(Self {
+            id,
+            vectors,
+            payload,
+        })
+    }
+
+    fn point(&self, record: &csv::StringRecord) -> Result<PointStruct, String> {
+        let id = record.get(self.id).unwrap_or_default().trim();
+        let id = id
+            .parse::<ExtendedPointId>()
+            .map_err(|()| format!("Invalid point id {id:?}"))?;
+
+        let mut vectors = HashMap::new();
+        for (index, name) in &self.vectors {
+            let value = record.get(*index).unwrap_or_default().trim();
+            if value.is_empty() {
+                continue;
+            }
+            let vector: Vec<f32> = serde_json::from_str(value)
+                .map_err(|err| format!("Invalid vector {name:?}: {err}"))?;
+            vectors.insert(name.clone(), Vector::Dense(vector));
+        }
+
+        let mut payload = serde_json::Map::new();
+        for (index, key) in &self.payload {
+            let value = record.get(*index).unwrap_or_default();
+            if value.is_empty() {
+                continue;
+            }
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            payload.insert(key.clone(), value);
+        }
+
+        Ok(PointStruct {
+            id,
+            vector: VectorStruct::Named(vectors),
+            payload: (!payload.is_empty()).then(|| Payload::from(payload)),
+            ttl: None,
+        })
+    }
+}
+
+/// Errors of the lines, with at most `MAX_REPORTED_ERRORS` listed
+#[derive(Debug, Default)]
+struct LineErrors {
+    failed: usize,
+    errors: Vec<BulkUpsertLineError>,
+}
+
+impl LineErrors {
+    /// Record `lines` failed lines, starting with `line`
+    fn add(&mut self, line: usize, lines: usize, error: String) {
+        self.failed += lines;
+        if self.errors.len() < MAX_REPORTED_ERRORS {
+            self.errors.push(BulkUpsertLineError { line, error });
+        }
+    }
+
+    fn merge(&mut self, other: LineErrors) {
+        self.failed += other.failed;
+        self.errors.extend(other.errors);
+        self.errors.sort_by_key(|error| error.line);
+        self.errors.truncate(MAX_REPORTED_ERRORS);
+    }
+}
+
+#[derive(Debug, Default)]
+struct PointsBatch {
+    first_line: usize,
+    last_line: usize,
+    points: Vec<PointStruct>,
+}
+
+struct PointsBatcher {
+    sender: mpsc::Sender<PointsBatch>,
+    batch_size: usize,
+    batch: PointsBatch,
+    /// Errors of the lines, which can't be parsed
+    errors: LineErrors,
+}
+
+impl PointsBatcher {
+    fn push(
+        &mut self,
+        line: usize,
+        point: Result<PointStruct, String>,
+    ) -> Result<(), StorageError> {
+        let point = match point {
+            Ok(point) => point,
+            Err(error) => {
+                self.errors.add(line, 1, error);
+                return Ok(());
+            }
+        };
+
+        if self.batch.points.is_empty() {
+            self.batch.first_line = line;
+        }
+        self.batch.last_line = line;
+        self.batch.points.push(point);
+
+        if self.batch.points.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), StorageError> {
+        if self.batch.points.is_empty() {
+            return Ok(());
+        }
+        let batch = std::mem::take(&mut self.batch);
+        self.sender
+            .blocking_send(batch)
+            .map_err(|_| StorageError::service_error("Bulk upsert of points was stopped"))
+    }
+
+    fn finish(mut self) -> Result<LineErrors, StorageError> {
+        self.flush()?;
+        Ok(self.errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    fn parse(body: impl Into<Bytes>, format: BulkUpsertFormat) -> (Vec<PointsBatch>, LineErrors) {
+        let (sender, mut receiver) = mpsc::channel(16);
+        let batcher = PointsBatcher {
+            sender,
+            batch_size: 2,
+            batch: PointsBatch::default(),
+            errors: LineErrors::default(),
+        };
+        let errors = parse_body(body.into(), format, batcher).unwrap();
+
+        let mut batches = Vec::new();
+        while let Ok(batch) = receiver.try_recv() {
+            batches.push(batch);
+        }
+        (batches, errors)
+    }
+
+    #[test]
+    fn test_parse_jsonl() {
+        let body = concat!(
+            r#"{"id": 1, "vector": [1.0, 2.0], "payload": {"city": "Berlin"}}"#,
+            "\n\n",
+            "not a point\n",
+            r#"{"id": 2, "vector": {"image": [3.0]}}"#,
+            "\n",
+            r#"{"id": "c2a0a8b0-8d5a-4c1a-9c1a-2b3c4d5e6f70", "vector": [4.0, 5.0]}"#,
+        );
+
+        let mut compressed = GzEncoder::new(Vec::new(), Compression::default());
+        compressed.write_all(body.as_bytes()).unwrap();
+        let compressed = compressed.finish().unwrap();
+
+        for body in [Bytes::from(body), Bytes::from(compressed)] {
+            let (batches, errors) = parse(body, BulkUpsertFormat::Jsonl);
+
+            assert_eq!(batches.len(), 2);
+            assert_eq!((batches[0].first_line, batches[0].last_line), (1, 4));
+            assert_eq!(batches[0].points.len(), 2);
+            assert_eq!((batches[1].first_line, batches[1].last_line), (5, 5));
+
+            assert_eq!(errors.failed, 1);
+            assert_eq!(errors.errors[0].line, 3);
+        }
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let body = concat!(
+            "id,vector,vector.image,city,count\n",
+            "1,\"[1.0, 2.0]\",,Berlin,3\n",
+            "x,\"[1.0]\",,Berlin,3\n",
+            "2,,\"[3.0]\",,\n",
+            "3,\"[1.0]\"\n",
+        );
+
+        let (batches, errors) = parse(body, BulkUpsertFormat::Csv);
+
+        assert_eq!(batches.len(), 1);
+        let points = &batches[0].points;
+        assert_eq!((batches[0].first_line, batches[0].last_line), (2, 4));
+        assert_eq!(points[0].id, ExtendedPointId::NumId(1));
+        assert_eq!(
+            points[0].payload.as_ref().unwrap().0.get("count"),
+            Some(&serde_json::json!(3)),
+        );
+        assert_eq!(
+            points[0].payload.as_ref().unwrap().0.get("city"),
+            Some(&serde_json::json!("Berlin")),
+        );
+        assert!(points[1].payload.is_none());
+
+        let VectorStruct::Named(vectors) = &points[1].vector else {
+            panic!("vectors must be named");
+        };
+        assert_eq!(vectors.keys().collect::<Vec<_>>(), ["image"]);
+
+        assert_eq!(errors.failed, 2);
+        assert_eq!(errors.errors[0].line, 3);
+        assert_eq!(errors.errors[1].line, 5);
+    }
+}
@@ -0,0 +1,101 @@
+//! Identity verification of clients, authenticated with TLS certificates.
+
+use std::io;
+
+use rustls::CertificateError;
+use rustls::client::verify_server_name;
+use rustls::server::ParsedCertificate;
+use rustls_pki_types::{CertificateDer, ServerName};
+
+use crate::settings::TlsConfig;
+
+/// Names, one of which must be present in a client certificate.
+///
+/// Names are matched against DNS names and IP addresses in the subject alternative names of
+/// the certificate, the same way as server certificates are matched against the server name.
+#[derive(Clone, Debug)]
+pub struct AllowedClientNames {
+    names: Vec<ServerName<'static>>,
+}
+
+impl AllowedClientNames {
+    pub fn new(names: &[String]) -> io::Result<Self> {
+        let names = names
+            .iter()
+            .map(|name| {
+                ServerName::try_from(name.as_str())
+                    .map(|name| name.to_owned())
+                    .map_err(|err| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("Invalid allowed client name {name:?}: {err}"),
+                        )
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { names })
+    }
+
+    /// Allowed names from TLS configuration, if restricted.
+    pub fn from_tls_config(tls_config: &TlsConfig) -> io::Result<Option<Self>> {
+        tls_config
+            .allowed_client_names
+            .as_deref()
+            .map(Self::new)
+            .transpose()
+    }
+
+    /// Check that the client certificate is issued for any of the allowed names.
+    ///
+    /// The certificate itself must already be verified against CA.
+    pub fn verify(&self, end_entity: &CertificateDer<'_>) -> Result<(), rustls::Error> {
+        let certificate = ParsedCertificate::try_from(end_entity)?;
+
+        let is_allowed = self
+            .names
+            .iter()
+            .any(|name| verify_server_name(&certificate, name).is_ok());
+
+        if is_allowed {
+            Ok(())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    const TEST_CERT: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/e2e_tests/test_data/cert/cert.pem",
+    );
+
+    fn names(names: &[&str]) -> AllowedClientNames {
+        let names: Vec<_> = names.iter().map(|name| name.to_string()).collect();
+        AllowedClientNames::new(&names).unwrap()
+    }
+
+    #[test]
+    fn test_verify_client_names() {
+        let pem = fs_err::read(TEST_CERT).unwrap();
+        let certificate = rustls_pemfile::certs(&mut BufReader::new(pem.as_slice()))
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert!(names(&["node1.qdrant"]).verify(&certificate).is_ok());
+        assert!(names(&["other", "127.0.0.1"]).verify(&certificate).is_ok());
+        assert!(names(&["node3.qdrant"]).verify(&certificate).is_err());
+        assert!(names(&["10.0.0.1"]).verify(&certificate).is_err());
+
+        assert!(AllowedClientNames::new(&["not a name!".to_string()]).is_err());
+    }
+}
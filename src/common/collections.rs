@@ -87,7 +87,7 @@ pub async fn do_list_collections(
     auth: &Auth,
 ) -> Result<CollectionsResponse, StorageError> {
     let collections = toc
-        .all_collections(auth.access("list_collections"))
+        .all_collections(auth.access(AccessRequirements::new(), "list_collections"))
         .await
         .into_iter()
         .map(|pass| CollectionDescription {
@@ -189,7 +189,9 @@ pub async fn do_list_aliases(
     toc: &TableOfContent,
     auth: &Auth,
 ) -> Result<CollectionsAliasesResponse, StorageError> {
-    let aliases = toc.list_aliases(auth.access("list_aliases")).await?;
+    let aliases = toc
+        .list_aliases(auth.access(AccessRequirements::new(), "list_aliases"))
+        .await?;
     Ok(CollectionsAliasesResponse { aliases })
 }
 
@@ -0,0 +1,801 @@
+//! Export of collections as datasets in Hugging Face layout.
+//!
+//! Points are written into Parquet files `data/{split}-{index}-of-{count}.parquet`, and the
+//! dataset card `README.md` describes the files and the features, so the directory can be
+//! uploaded to the Hub or loaded with `datasets.load_dataset` as is.
+//!
+//! Every point is a row with `id` as string, a column per vector, and `payload` as JSON string.
+
+use std::num::NonZeroUsize;
+#[cfg(feature = "parquet")]
+use std::path::PathBuf;
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+
+#[cfg(feature = "parquet")]
+use api::rest::schema::{Record, VectorOutput, VectorStructOutput};
+#[cfg(feature = "parquet")]
+use arrow::array::{
+    ArrayRef, FixedSizeListArray, Float32Builder, ListArray, ListBuilder, RecordBatch, StringArray,
+    StructArray,
+};
+#[cfg(feature = "parquet")]
+use arrow::buffer::NullBuffer;
+#[cfg(feature = "parquet")]
+use arrow::datatypes::{DataType, Field, Fields, Float32Type, Schema, SchemaRef, UInt32Type};
+#[cfg(feature = "parquet")]
+use collection::config::CollectionParams;
+#[cfg(feature = "parquet")]
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::verification::StrictModeVerification;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+#[cfg(feature = "parquet")]
+use parquet::arrow::ArrowWriter;
+#[cfg(feature = "parquet")]
+use parquet::basic::{Compression, ZstdLevel};
+#[cfg(feature = "parquet")]
+use parquet::file::properties::WriterProperties;
+use schemars::JsonSchema;
+use segment::types::{Filter, SearchParams, VectorNameBuf};
+#[cfg(feature = "parquet")]
+use segment::types::{WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "parquet")]
+use shard::scroll::ScrollRequestInternal;
+#[cfg(feature = "parquet")]
+use sparse::common::sparse_vector::SparseVector;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use validator::{Validate, ValidationError};
+
+use super::auth::Auth;
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+
+#[cfg(feature = "parquet")]
+const DEFAULT_SPLIT: &str = "train";
+#[cfg(feature = "parquet")]
+const DEFAULT_ROWS_PER_FILE: usize = 100_000;
+
+/// Number of points read from the collection at once
+#[cfg(feature = "parquet")]
+const SCROLL_BATCH_SIZE: usize = 1000;
+
+#[cfg(feature = "parquet")]
+const ID_COLUMN: &str = "id";
+#[cfg(feature = "parquet")]
+const PAYLOAD_COLUMN: &str = "payload";
+/// Column of the default vector, which has empty name
+#[cfg(feature = "parquet")]
+const DEFAULT_VECTOR_COLUMN: &str = "vector";
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ExportDataset {
+    /// Name of the dataset directory, created inside `storage.export_path`
+    #[validate(custom(function = validate_dataset_name))]
+    pub name: String,
+    /// Name of the split, which contains the exported points. Default: `train`
+    #[serde(default)]
+    #[validate(custom(function = validate_dataset_name))]
+    pub split: Option<String>,
+    /// Export only points which satisfy this conditions. If not provided - all points.
+    #[serde(default)]
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+    /// Names of the vectors to export. If not set, all vectors are exported.
+    #[serde(default)]
+    pub vectors: Option<Vec<VectorNameBuf>>,
+    /// Whether to export payload. Default: true
+    #[serde(default)]
+    pub with_payload: Option<bool>,
+    /// Maximum number of points in a Parquet file. Default: 100000
+    #[serde(default)]
+    pub rows_per_file: Option<NonZeroUsize>,
+}
+
+fn validate_dataset_name(name: &str) -> Result<(), ValidationError> {
+    let is_valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("dataset_name")
+            .with_message("must be 1 to 64 ASCII letters, digits, dashes or underscores".into()))
+    }
+}
+
+impl StrictModeVerification for ExportDataset {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct ExportResult {
+    /// Path of the dataset directory on the node
+    pub path: String,
+    /// Number of written Parquet files
+    pub files: usize,
+    /// Number of exported points
+    pub points: usize,
+}
+
+/// Export points of the collection into a new dataset directory.
+pub async fn do_export_dataset(
+    dispatcher: Dispatcher,
+    collection_name: String,
+    request: ExportDataset,
+    auth: Auth,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<ExportResult, StorageError> {
+    // Dataset includes whole points, so access to the whole collection is required
+    let collection_pass = auth.check_collection_access(
+        &collection_name,
+        AccessRequirements::new().extras(),
+        "export_dataset",
+    )?;
+
+    let toc = StrictModeCheckedTocProvider::new(&dispatcher)
+        .check_strict_mode(&request, &collection_name, None, &auth)
+        .await?
+        .clone();
+
+    #[cfg(not(feature = "parquet"))]
+    {
+        let _ = (toc, collection_pass, hw_measurement_acc);
+        Err(StorageError::bad_request(
+            "Export of datasets is not supported, Qdrant is built without `parquet` feature",
+        ))
+    }
+
+    #[cfg(feature = "parquet")]
+    {
+        let ExportDataset {
+            name,
+            split,
+            filter,
+            vectors,
+            with_payload,
+            rows_per_file,
+        } = request;
+
+        let Some(export_path) = toc.export_path() else {
+            return Err(StorageError::bad_request(
+                "Export of datasets is disabled, set `storage.export_path` to enable it",
+            ));
+        };
+
+        let dataset_path = export_path.join(&name);
+        if dataset_path.exists() {
+            return Err(StorageError::bad_request(format!(
+                "Dataset {name} already exists in the export directory",
+            )));
+        }
+
+        let collection = toc.get_collection(&collection_pass).await?;
+        let info = collection.info(&ShardSelectorInternal::All).await?;
+        let columns = DatasetColumns::new(
+            &info.config.params,
+            vectors.as_deref(),
+            with_payload.unwrap_or(true),
+        )?;
+        let with_vector = columns.with_vector();
+
+        // Dataset is written into a temporary directory, so incomplete exports are never visible
+        fs_err::create_dir_all(export_path)?;
+        let temp_dir = tempfile::Builder::new()
+            .prefix(&format!(".{name}-"))
+            .tempdir_in(export_path)?;
+
+        let writer = DatasetWriter {
+            dir: temp_dir.path().to_path_buf(),
+            split: split.unwrap_or_else(|| DEFAULT_SPLIT.to_string()),
+            columns: Arc::new(columns),
+            rows_per_file: rows_per_file.map_or(DEFAULT_ROWS_PER_FILE, NonZeroUsize::get),
+        };
+
+        // Points are written in a blocking thread, while the next batch is read
+        let (sender, mut receiver) = tokio::sync::mpsc::channel::<Vec<Record>>(1);
+        let write_task = tokio::task::spawn_blocking(move || {
+            let mut state = writer.start()?;
+            while let Some(records) = receiver.blocking_recv() {
+                state.write(&records)?;
+            }
+            state.finish()
+        });
+
+        let scroll_request = ScrollRequestInternal {
+            offset: None,
+            limit: Some(SCROLL_BATCH_SIZE),
+            filter,
+            with_payload: Some(WithPayloadInterface::Bool(with_payload.unwrap_or(true))),
+            with_vector,
+            order_by: None,
+        };
+
+        let mut offset = None;
+        loop {
+            let result = toc
+                .scroll(
+                    &collection_name,
+                    ScrollRequestInternal {
+                        offset,
+                        ..scroll_request.clone()
+                    },
+                    None,
+                    None,
+                    ShardSelectorInternal::All,
+                    auth.clone(),
+                    hw_measurement_acc.clone(),
+                )
+                .await?;
+
+            if !result.points.is_empty() && sender.send(result.points).await.is_err() {
+                // Writer failed, its error is returned below
+                break;
+            }
+
+            offset = result.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+        drop(sender);
+
+        let (files, points) = write_task.await??;
+
+        fs_err::rename(temp_dir.keep(), &dataset_path)?;
+
+        Ok(ExportResult {
+            path: dataset_path.to_string_lossy().into_owned(),
+            files,
+            points,
+        })
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VectorKind {
+    Dense(usize),
+    MultiDense,
+    Sparse,
+}
+
+#[cfg(feature = "parquet")]
+#[derive(Debug)]
+struct VectorColumn {
+    vector_name: VectorNameBuf,
+    column: String,
+    kind: VectorKind,
+}
+
+#[cfg(feature = "parquet")]
+/// Columns of the dataset, derived from the collection config
+#[derive(Debug)]
+pub(crate) struct DatasetColumns {
+    vectors: Vec<VectorColumn>,
+    with_payload: bool,
+    schema: SchemaRef,
+}
+
+#[cfg(feature = "parquet")]
+impl DatasetColumns {
+    pub(crate) fn new(
+        params: &CollectionParams,
+        vectors: Option<&[VectorNameBuf]>,
+        with_payload: bool,
+    ) -> Result<Self, StorageError> {
+        let mut all_vectors: Vec<_> = params
+            .vectors
+            .params_iter()
+            .map(|(name, params)| {
+                let kind = if params.multivector_config.is_some() {
+                    VectorKind::MultiDense
+                } else {
+                    VectorKind::Dense(params.size.get() as usize)
+                };
+                (name.to_owned(), kind)
+            })
+            .collect();
+        all_vectors.extend(
+            params
+                .sparse_vectors
+                .iter()
+                .flatten()
+                .map(|(name, _)| (name.clone(), VectorKind::Sparse)),
+        );
+
+        if let Some(vectors) = vectors {
+            if let Some(missing) = vectors
+                .iter()
+                .find(|name| !all_vectors.iter().any(|(vector, _)| vector == *name))
+            {
+                return Err(StorageError::bad_request(format!(
+                    "Vector {missing} is not found in the collection",
+                )));
+            }
+            all_vectors.retain(|(name, _)| vectors.contains(name));
+        }
+
+        let vectors: Vec<_> = all_vectors
+            .into_iter()
+            .map(|(vector_name, kind)| VectorColumn {
+                column: if vector_name.is_empty() {
+                    DEFAULT_VECTOR_COLUMN.to_string()
+                } else {
+                    vector_name.clone()
+                },
+                vector_name,
+                kind,
+            })
+            .collect();
+
+        let mut fields = vec![Field::new(ID_COLUMN, DataType::Utf8, false)];
+        fields.extend(
+            vectors
+                .iter()
+                .map(|vector| Field::new(&vector.column, vector_data_type(vector.kind), true)),
+        );
+        if with_payload {
+            fields.push(Field::new(PAYLOAD_COLUMN, DataType::Utf8, true));
+        }
+
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|other| other.name() == field.name()) {
+                return Err(StorageError::bad_request(format!(
+                    "Vector can't be exported into column {}, which is already used",
+                    field.name(),
+                )));
+            }
+        }
+
+        Ok(Self {
+            vectors,
+            with_payload,
+            schema: Arc::new(Schema::new(fields)),
+        })
+    }
+
+    pub(crate) fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Vectors to read from the collection to fill the columns
+    pub(crate) fn with_vector(&self) -> WithVector {
+        if self.vectors.is_empty() {
+            WithVector::Bool(false)
+        } else {
+            WithVector::Selector(
+                self.vectors
+                    .iter()
+                    .map(|vector| vector.vector_name.clone())
+                    .collect(),
+            )
+        }
+    }
+
+    pub(crate) fn record_batch(&self, records: &[Record]) -> Result<RecordBatch, StorageError> {
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(self.schema.fields().len());
+
+        columns.push(Arc::new(StringArray::from_iter_values(
+            records.iter().map(|record| record.id.to_string()),
+        )));
+
+        for vector in &self.vectors {
+            let values = records
+                .iter()
+                .map(|record| record_vector(record, &vector.vector_name));
+            columns.push(vector_array(vector.kind, values)?);
+        }
+
+        if self.with_payload {
+            let payloads = records
+                .iter()
+                .map(|record| {
+                    record
+                        .payload
+                        .as_ref()
+                        .map(serde_json::to_string)
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            columns.push(Arc::new(StringArray::from(payloads)));
+        }
+
+        RecordBatch::try_new(self.schema.clone(), columns).map_err(write_error)
+    }
+
+    /// Features of the dataset in the format of `dataset_info` of the dataset card
+    fn features_yaml(&self) -> String {
+        let mut yaml = format!("  - name: {ID_COLUMN}\n    dtype: string\n");
+        for vector in &self.vectors {
+            yaml.push_str(&format!("  - name: {}\n", yaml_string(&vector.column)));
+            yaml.push_str(match vector.kind {
+                VectorKind::Dense(_) => "    sequence: float32\n",
+                VectorKind::MultiDense => "    sequence:\n      sequence: float32\n",
+                VectorKind::Sparse => concat!(
+                    "    struct:\n",
+                    "    - name: indices\n      sequence: uint32\n",
+                    "    - name: values\n      sequence: float32\n",
+                ),
+            });
+        }
+        if self.with_payload {
+            yaml.push_str(&format!("  - name: {PAYLOAD_COLUMN}\n    dtype: string\n"));
+        }
+        yaml
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn vector_data_type(kind: VectorKind) -> DataType {
+    let floats = || Arc::new(Field::new_list_field(DataType::Float32, true));
+    match kind {
+        VectorKind::Dense(dim) => DataType::FixedSizeList(floats(), dim as i32),
+        VectorKind::MultiDense => DataType::List(Arc::new(Field::new_list_field(
+            DataType::List(floats()),
+            true,
+        ))),
+        VectorKind::Sparse => DataType::Struct(sparse_fields()),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn sparse_fields() -> Fields {
+    Fields::from(vec![
+        Field::new(
+            "indices",
+            DataType::List(Arc::new(Field::new_list_field(DataType::UInt32, true))),
+            true,
+        ),
+        Field::new(
+            "values",
+            DataType::List(Arc::new(Field::new_list_field(DataType::Float32, true))),
+            true,
+        ),
+    ])
+}
+
+#[cfg(feature = "parquet")]
+fn record_vector<'a>(record: &'a Record, name: &str) -> Option<VectorRef<'a>> {
+    match record.vector.as_ref()? {
+        VectorStructOutput::Single(vector) => name.is_empty().then_some(VectorRef::Dense(vector)),
+        VectorStructOutput::MultiDense(vectors) => {
+            name.is_empty().then_some(VectorRef::MultiDense(vectors))
+        }
+        VectorStructOutput::Named(vectors) => match vectors.get(name)? {
+            VectorOutput::Dense(vector) => Some(VectorRef::Dense(vector)),
+            VectorOutput::Sparse(vector) => Some(VectorRef::Sparse(vector)),
+            VectorOutput::MultiDense(vectors) => Some(VectorRef::MultiDense(vectors)),
+        },
+    }
+}
+
+#[cfg(feature = "parquet")]
+enum VectorRef<'a> {
+    Dense(&'a [f32]),
+    MultiDense(&'a [Vec<f32>]),
+    Sparse(&'a SparseVector),
+}
+
+#[cfg(feature = "parquet")]
+/// Build a column of vectors of the same kind, missing vectors are nulls
+fn vector_array<'a>(
+    kind: VectorKind,
+    values: impl Iterator<Item = Option<VectorRef<'a>>>,
+) -> Result<ArrayRef, StorageError> {
+    let mismatch = || StorageError::service_error("Vector type doesn't match collection config");
+
+    let array: ArrayRef = match kind {
+        VectorKind::Dense(dim) => {
+            let vectors = values
+                .map(|vector| match vector {
+                    None => Ok(None),
+                    Some(VectorRef::Dense(vector)) if vector.len() == dim => {
+                        Ok(Some(vector.iter().copied().map(Some)))
+                    }
+                    Some(_) => Err(mismatch()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Arc::new(
+                FixedSizeListArray::from_iter_primitive::<Float32Type, _, _>(vectors, dim as i32),
+            )
+        }
+        VectorKind::MultiDense => {
+            let mut builder = ListBuilder::new(ListBuilder::new(Float32Builder::new()));
+            for vector in values {
+                match vector {
+                    None => builder.append_null(),
+                    Some(VectorRef::MultiDense(vectors)) => {
+                        for vector in vectors {
+                            builder.values().values().append_slice(vector);
+                            builder.values().append(true);
+                        }
+                        builder.append(true);
+                    }
+                    Some(_) => return Err(mismatch()),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        VectorKind::Sparse => {
+            let vectors = values
+                .map(|vector| match vector {
+                    None => Ok(None),
+                    Some(VectorRef::Sparse(vector)) => Ok(Some(vector)),
+                    Some(_) => Err(mismatch()),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let indices = ListArray::from_iter_primitive::<UInt32Type, _, _>(
+                vectors
+                    .iter()
+                    .map(|vector| vector.map(|vector| vector.indices.iter().copied().map(Some))),
+            );
+            let weights = ListArray::from_iter_primitive::<Float32Type, _, _>(
+                vectors
+                    .iter()
+                    .map(|vector| vector.map(|vector| vector.values.iter().copied().map(Some))),
+            );
+            let nulls = NullBuffer::from_iter(vectors.iter().map(Option::is_some));
+
+            Arc::new(
+                StructArray::try_new(
+                    sparse_fields(),
+                    vec![Arc::new(indices), Arc::new(weights)],
+                    Some(nulls),
+                )
+                .map_err(write_error)?,
+            )
+        }
+    };
+
+    Ok(array)
+}
+
+#[cfg(feature = "parquet")]
+fn write_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::service_error(format!("Failed to write dataset: {err}"))
+}
+
+#[cfg(feature = "parquet")]
+/// Quote a string for YAML, JSON strings are valid YAML
+fn yaml_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
+#[cfg(feature = "parquet")]
+struct DatasetWriter {
+    dir: PathBuf,
+    split: String,
+    columns: Arc<DatasetColumns>,
+    rows_per_file: usize,
+}
+
+#[cfg(feature = "parquet")]
+impl DatasetWriter {
+    fn start(self) -> Result<DatasetWriterState, StorageError> {
+        fs_err::create_dir_all(self.dir.join("data"))?;
+        Ok(DatasetWriterState {
+            writer: self,
+            current: None,
+            current_rows: 0,
+            files: Vec::new(),
+            points: 0,
+        })
+    }
+
+    fn file_path(&self, index: usize, count: Option<usize>) -> PathBuf {
+        let name = match count {
+            Some(count) => format!("{}-{index:05}-of-{count:05}.parquet", self.split),
+            None => format!("{}-{index:05}.parquet.tmp", self.split),
+        };
+        self.dir.join("data").join(name)
+    }
+
+    fn dataset_card(&self, points: usize) -> String {
+        let split = yaml_string(&self.split);
+        let data_files = yaml_string(&format!("data/{}-*", self.split));
+        let features = self.columns.features_yaml();
+
+        format!(
+            r#"---
+configs:
+- config_name: default
+  data_files:
+  - split: {split}
+    path: {data_files}
+dataset_info:
+  features:
+{features}  splits:
+  - name: {split}
+    num_examples: {points}
+---
+
+Dataset exported from a Qdrant collection.
+
+Every row is a point: `{ID_COLUMN}` is the point id, vectors are stored in columns named after the
+vectors, `{DEFAULT_VECTOR_COLUMN}` for the default vector, and `{PAYLOAD_COLUMN}` holds the payload as JSON string.
+"#,
+        )
+    }
+}
+
+#[cfg(feature = "parquet")]
+struct DatasetWriterState {
+    writer: DatasetWriter,
+    current: Option<ArrowWriter<std::fs::File>>,
+    current_rows: usize,
+    files: Vec<PathBuf>,
+    points: usize,
+}
+
+#[cfg(feature = "parquet")]
+impl DatasetWriterState {
+    fn write(&mut self, records: &[Record]) -> Result<(), StorageError> {
+        let batch = self.writer.columns.record_batch(records)?;
+        let rows_per_file = self.writer.rows_per_file;
+
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let mut current = match self.current.take() {
+                Some(current) if self.current_rows < rows_per_file => current,
+                full => {
+                    if let Some(full) = full {
+                        full.close().map_err(write_error)?;
+                    }
+                    self.open_file()?
+                }
+            };
+
+            let rows = (rows_per_file - self.current_rows).min(batch.num_rows() - offset);
+            current
+                .write(&batch.slice(offset, rows))
+                .map_err(write_error)?;
+            self.current = Some(current);
+
+            self.current_rows += rows;
+            self.points += rows;
+            offset += rows;
+        }
+
+        Ok(())
+    }
+
+    fn open_file(&mut self) -> Result<ArrowWriter<std::fs::File>, StorageError> {
+        let path = self.writer.file_path(self.files.len(), None);
+        let file = fs_err::File::create(&path)?.into_parts().0;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let writer = ArrowWriter::try_new(file, self.writer.columns.schema(), Some(properties))
+            .map_err(write_error)?;
+
+        self.files.push(path);
+        self.current_rows = 0;
+        Ok(writer)
+    }
+
+    /// Close the last file, give files their final names and write the dataset card.
+    /// Returns number of files and points.
+    fn finish(self) -> Result<(usize, usize), StorageError> {
+        if let Some(current) = self.current {
+            current.close().map_err(write_error)?;
+        }
+
+        let count = self.files.len();
+        for (index, path) in self.files.iter().enumerate() {
+            fs_err::rename(path, self.writer.file_path(index, Some(count)))?;
+        }
+
+        fs_err::write(
+            self.writer.dir.join("README.md"),
+            self.writer.dataset_card(self.points),
+        )?;
+
+        Ok((count, self.points))
+    }
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::array::{Array, AsArray};
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    #[test]
+    fn test_records_to_batch() {
+        let columns = DatasetColumns {
+            vectors: vec![
+                VectorColumn {
+                    vector_name: "dense".into(),
+                    column: "dense".to_string(),
+                    kind: VectorKind::Dense(2),
+                },
+                VectorColumn {
+                    vector_name: "sparse".into(),
+                    column: "sparse".to_string(),
+                    kind: VectorKind::Sparse,
+                },
+            ],
+            with_payload: true,
+            schema: Arc::new(Schema::new(vec![
+                Field::new(ID_COLUMN, DataType::Utf8, false),
+                Field::new("dense", vector_data_type(VectorKind::Dense(2)), true),
+                Field::new("sparse", vector_data_type(VectorKind::Sparse), true),
+                Field::new(PAYLOAD_COLUMN, DataType::Utf8, true),
+            ])),
+        };
+
+        let records = vec![
+            Record {
+                id: ExtendedPointId::NumId(1),
+                payload: Some(serde_json::from_value(serde_json::json!({ "a": 1 })).unwrap()),
+                vector: Some(VectorStructOutput::Named(HashMap::from([
+                    ("dense".into(), VectorOutput::Dense(vec![0.1, 0.2])),
+                    (
+                        "sparse".into(),
+                        VectorOutput::Sparse(SparseVector {
+                            indices: vec![3],
+                            values: vec![0.5],
+                        }),
+                    ),
+                ]))),
+                shard_key: None,
+                order_value: None,
+            },
+            Record {
+                id: ExtendedPointId::NumId(2),
+                payload: None,
+                vector: None,
+                shard_key: None,
+                order_value: None,
+            },
+        ];
+
+        let batch = columns.record_batch(&records).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+
+        let ids = batch.column(0).as_string::<i32>();
+        assert_eq!(ids.value(0), "1");
+
+        let dense = batch.column(1).as_fixed_size_list();
+        assert!(dense.is_valid(0));
+        assert!(dense.is_null(1));
+
+        let sparse = batch.column(2).as_struct();
+        assert!(sparse.is_valid(0));
+        assert!(sparse.is_null(1));
+
+        let payloads = batch.column(3).as_string::<i32>();
+        assert_eq!(payloads.value(0), r#"{"a":1}"#);
+        assert!(payloads.is_null(1));
+
+        let features = columns.features_yaml();
+        assert!(features.contains("  - name: \"dense\"\n    sequence: float32\n"));
+    }
+}
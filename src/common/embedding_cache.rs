@@ -0,0 +1,371 @@
+//! Deduplication of identical embeddings by a hash of the input content.
+//!
+//! Every point carries a hash of the content its vectors are computed from, supplied by the
+//! client or computed from a payload field. The first point with a given hash keeps its vectors,
+//! further points with the same hash are stored without vectors and reference the first one
+//! in payload, so duplicates take no space in vector storage and indexes.
+//!
+//! Search returns only points with vectors, duplicates of a found point can be selected
+//! with a filter by the `content_ref` payload field.
+
+use std::collections::HashMap;
+
+use api::rest::schema::{PointStruct, VectorStruct};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::verification::StrictModeVerification;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, ExtendedPointId, FieldCondition, Filter, IsEmptyCondition, Match, Payload,
+    PayloadField, SearchParams, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use shard::scroll::ScrollRequestInternal;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::AccessRequirements;
+use validator::Validate;
+
+use super::auth::Auth;
+use crate::common::bulk_import::upsert_points_batch;
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+
+/// Payload field with the content hash of the point
+pub const CONTENT_HASH_KEY: &str = "content_hash";
+
+/// Payload field with the id of the point, which stores vectors of the same content
+pub const CONTENT_REF_KEY: &str = "content_ref";
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct UpsertDeduplicated {
+    #[validate(nested)]
+    pub points: Vec<DeduplicatedPoint>,
+    /// Payload field to compute the content hash from, for points without `content_hash`.
+    /// Strings are hashed as is, other values as JSON.
+    #[serde(default)]
+    pub hash_field: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct DeduplicatedPoint {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub point: PointStruct,
+    /// Hash of the content the vectors are computed from.
+    /// Points with equal hashes are assumed to have equal vectors.
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct DeduplicatedResult {
+    /// Number of upserted points
+    pub points: usize,
+    /// Number of points stored as references to the vectors of other points
+    pub deduplicated: usize,
+}
+
+/// Upsert points, storing vectors only once for points with the same content hash.
+///
+/// Points without content hash, neither supplied nor computed, are upserted as is.
+pub async fn do_upsert_deduplicated(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    request: UpsertDeduplicated,
+    auth: Auth,
+    hw_measurement_acc: HwMeasurementAcc,
+) -> Result<DeduplicatedResult, StorageError> {
+    auth.check_collection_access(
+        collection_name,
+        AccessRequirements::new().write(),
+        "upsert_deduplicated",
+    )?;
+
+    let UpsertDeduplicated { points, hash_field } = request;
+
+    let points = points
+        .into_iter()
+        .map(
+            |DeduplicatedPoint {
+                 point,
+                 content_hash,
+             }| {
+                let content_hash = content_hash.or_else(|| {
+                    let field = hash_field.as_deref()?;
+                    point.payload.as_ref()?.0.get(field).map(hash_content)
+                });
+                (point, content_hash)
+            },
+        )
+        .collect::<Vec<_>>();
+
+    let mut hashes = points
+        .iter()
+        .filter_map(|(_, hash)| hash.clone())
+        .collect::<Vec<_>>();
+    hashes.sort_unstable();
+    hashes.dedup();
+
+    let mut stored = find_stored(
+        dispatcher,
+        collection_name,
+        hashes,
+        &auth,
+        &hw_measurement_acc,
+    )
+    .await?;
+
+    let points_count = points.len();
+    let mut deduplicated = 0;
+    let points = points
+        .into_iter()
+        .map(|(point, hash)| {
+            let Some(hash) = hash else {
+                return point;
+            };
+            let point = deduplicate(point, hash, &mut stored);
+            if point
+                .payload
+                .as_ref()
+                .is_some_and(|payload| payload.contains_key(CONTENT_REF_KEY))
+            {
+                deduplicated += 1;
+            }
+            point
+        })
+        .collect();
+
+    upsert_points_batch(
+        dispatcher,
+        collection_name,
+        points,
+        &auth,
+        &hw_measurement_acc,
+    )
+    .await?;
+
+    Ok(DeduplicatedResult {
+        points: points_count,
+        deduplicated,
+    })
+}
+
+/// Hash of the content, as hex-encoded SHA-256
+fn hash_content(value: &Value) -> String {
+    let mut hasher = Sha256::new();
+    match value {
+        Value::String(value) => hasher.update(value.as_bytes()),
+        value => hasher.update(value.to_string().as_bytes()),
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Point with the content hash in payload.
+/// If vectors of the content are already stored by another point, vectors are replaced
+/// with a reference to that point.
+fn deduplicate(
+    mut point: PointStruct,
+    hash: String,
+    stored: &mut HashMap<String, ExtendedPointId>,
+) -> PointStruct {
+    let payload = point.payload.get_or_insert_with(Payload::default);
+    payload.0.remove(CONTENT_REF_KEY);
+
+    match stored.get(&hash) {
+        Some(id) if *id != point.id => {
+            payload.0.insert(
+                CONTENT_REF_KEY.to_string(),
+                serde_json::to_value(id).unwrap(),
+            );
+            point.vector = VectorStruct::Named(HashMap::new());
+        }
+        Some(_) => {}
+        None => {
+            stored.insert(hash.clone(), point.id);
+        }
+    }
+
+    payload
+        .0
+        .insert(CONTENT_HASH_KEY.to_string(), Value::String(hash));
+    point
+}
+
+/// Lookup of points by content hashes, checked against strict mode of the collection.
+/// Filtering by `content_hash` requires a payload index on it, if unindexed filtering is disabled.
+struct StoredLookup {
+    filter: Filter,
+    limit: usize,
+}
+
+impl StrictModeVerification for StoredLookup {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        Some(&self.filter)
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+/// Find points, which store vectors of the given content hashes
+async fn find_stored(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    hashes: Vec<String>,
+    auth: &Auth,
+    hw_measurement_acc: &HwMeasurementAcc,
+) -> Result<HashMap<String, ExtendedPointId>, StorageError> {
+    let mut stored = HashMap::with_capacity(hashes.len());
+    if hashes.is_empty() {
+        return Ok(stored);
+    }
+
+    let filter = Filter {
+        should: None,
+        min_should: None,
+        must: Some(vec![
+            Condition::Field(FieldCondition::new_match(
+                JsonPath::new(CONTENT_HASH_KEY),
+                Match::from(hashes.clone()),
+            )),
+            Condition::IsEmpty(IsEmptyCondition {
+                is_empty: PayloadField {
+                    key: JsonPath::new(CONTENT_REF_KEY),
+                },
+            }),
+        ]),
+        must_not: None,
+    };
+
+    let lookup = StoredLookup {
+        filter,
+        limit: hashes.len(),
+    };
+    let toc = StrictModeCheckedTocProvider::new(dispatcher)
+        .check_strict_mode(&lookup, collection_name, None, auth)
+        .await?
+        .clone();
+    let StoredLookup { filter, limit } = lookup;
+
+    let mut offset = None;
+    loop {
+        let result = toc
+            .scroll(
+                collection_name,
+                ScrollRequestInternal {
+                    offset,
+                    limit: Some(limit),
+                    filter: Some(filter.clone()),
+                    with_payload: Some(WithPayloadInterface::Fields(vec![JsonPath::new(
+                        CONTENT_HASH_KEY,
+                    )])),
+                    with_vector: WithVector::Bool(false),
+                    order_by: None,
+                },
+                None,
+                None,
+                ShardSelectorInternal::All,
+                auth.clone(),
+                hw_measurement_acc.clone(),
+            )
+            .await?;
+
+        for record in result.points {
+            let hash = record
+                .payload
+                .and_then(|mut payload| payload.0.remove(CONTENT_HASH_KEY));
+            if let Some(Value::String(hash)) = hash {
+                stored.entry(hash).or_insert(record.id);
+            }
+        }
+
+        offset = result.next_page_offset;
+        if offset.is_none() {
+            break;
+        }
+    }
+
+    Ok(stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use api::rest::schema::Vector;
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(id: u64) -> PointStruct {
+        PointStruct {
+            id: ExtendedPointId::NumId(id),
+            vector: VectorStruct::Single(vec![1.0, 2.0]),
+            payload: None,
+            ttl: None,
+        }
+    }
+
+    #[test]
+    fn test_deduplicate() {
+        let mut stored = HashMap::from([("a".to_string(), ExtendedPointId::NumId(1))]);
+
+        // Point, which stores vectors of the content, stays as is
+        let first = deduplicate(point(1), "a".to_string(), &mut stored);
+        assert_eq!(first.vector, VectorStruct::Single(vec![1.0, 2.0]));
+        assert_eq!(
+            first.payload.unwrap().0.get(CONTENT_HASH_KEY),
+            Some(&json!("a")),
+        );
+
+        let duplicate = deduplicate(point(2), "a".to_string(), &mut stored);
+        assert_eq!(duplicate.vector, VectorStruct::Named(HashMap::new()));
+        assert_eq!(
+            duplicate.payload.unwrap().0.get(CONTENT_REF_KEY),
+            Some(&json!(1)),
+        );
+
+        // New content is stored by the first point with it
+        let mut new = point(3);
+        new.vector = VectorStruct::Named(HashMap::from([(
+            "text".to_string(),
+            Vector::Dense(vec![0.5]),
+        )]));
+        let new = deduplicate(new, "b".to_string(), &mut stored);
+        assert!(!new.payload.unwrap().contains_key(CONTENT_REF_KEY));
+        assert_eq!(stored.get("b"), Some(&ExtendedPointId::NumId(3)));
+
+        let duplicate = deduplicate(point(4), "b".to_string(), &mut stored);
+        assert_eq!(
+            duplicate.payload.unwrap().0.get(CONTENT_REF_KEY),
+            Some(&json!(3)),
+        );
+    }
+
+    #[test]
+    fn test_hash_content() {
+        assert_eq!(
+            hash_content(&json!("hello")),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824",
+        );
+        assert_eq!(
+            hash_content(&json!(["hello"])),
+            hash_content(&json!(["hello"]))
+        );
+        assert_ne!(hash_content(&json!("1")), hash_content(&json!(1)));
+    }
+}
@@ -0,0 +1,605 @@
+//! Translation of Elasticsearch query DSL into qdrant filters.
+//!
+//! Only the filtering part of the DSL is supported, so existing query templates can be reused
+//! for search and scroll without rewriting. Parameters which only affect scoring, like `boost`,
+//! are accepted and ignored. Everything else is rejected instead of being silently dropped.
+
+use std::str::FromStr;
+
+use collection::operations::types::{ScrollRequest, SearchRequest};
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, ExtendedPointId, Filter, HasIdCondition, MinShould, Nested, NestedCondition,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use storage::content_manager::errors::StorageError;
+use validator::Validate;
+
+/// Field which holds the document id in Elasticsearch
+const ID_FIELD: &str = "_id";
+
+/// Suffix of the sub-field, which Elasticsearch mappings add to the text fields for exact matching
+const KEYWORD_SUFFIX: &str = ".keyword";
+
+/// Parameters of the queries, which only affect scoring or naming of the query
+const IGNORED_PARAMS: &[&str] = &["boost", "_name"];
+
+/// Search request with the filter in Elasticsearch query DSL
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct EsSearchRequest {
+    /// Elasticsearch query, e.g. `{"bool": {"filter": [{"term": {"status": "active"}}]}}`.
+    /// Supported queries: `bool`, `term`, `terms`, `range`, `exists`, `ids`, `match`,
+    /// `match_phrase`, `nested`, `constant_score`, `match_all` and `match_none`.
+    /// Combined with `filter` of the request, if both are specified.
+    pub query: Value,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub search: SearchRequest,
+}
+
+/// Scroll request with the filter in Elasticsearch query DSL
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct EsScrollRequest {
+    /// Elasticsearch query, e.g. `{"bool": {"filter": [{"term": {"status": "active"}}]}}`.
+    /// Supported queries: `bool`, `term`, `terms`, `range`, `exists`, `ids`, `match`,
+    /// `match_phrase`, `nested`, `constant_score`, `match_all` and `match_none`.
+    /// Combined with `filter` of the request, if both are specified.
+    pub query: Value,
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub scroll: ScrollRequest,
+}
+
+impl EsSearchRequest {
+    pub fn into_search_request(self) -> Result<SearchRequest, StorageError> {
+        let Self { query, mut search } = self;
+        let filter = &mut search.search_request.filter;
+        *filter = merge_filter(filter.take(), &query)?;
+        Ok(search)
+    }
+}
+
+impl EsScrollRequest {
+    pub fn into_scroll_request(self) -> Result<ScrollRequest, StorageError> {
+        let Self { query, mut scroll } = self;
+        let filter = &mut scroll.scroll_request.filter;
+        *filter = merge_filter(filter.take(), &query)?;
+        Ok(scroll)
+    }
+}
+
+/// Add translated query to the filter of the request.
+///
+/// Query is added as a single nested condition, so its `should` clauses don't mix with the
+/// `should` clauses of the filter.
+fn merge_filter(filter: Option<Filter>, query: &Value) -> Result<Option<Filter>, StorageError> {
+    let query_filter = es_query_to_filter(query)?;
+    Ok(match filter {
+        None => Some(query_filter),
+        Some(filter) => Some(filter.merge_owned(Filter::new_must(Condition::Filter(query_filter)))),
+    })
+}
+
+/// Translate Elasticsearch query into the filter, which selects the same documents
+pub fn es_query_to_filter(query: &Value) -> Result<Filter, StorageError> {
+    let condition = Translator::default().condition(query)?;
+    Ok(into_filter(condition))
+}
+
+fn into_filter(condition: Condition) -> Filter {
+    match condition {
+        Condition::Filter(filter) => filter,
+        condition => Filter::new_must(condition),
+    }
+}
+
+fn error(message: impl std::fmt::Display) -> StorageError {
+    StorageError::bad_request(format!("Invalid Elasticsearch query: {message}"))
+}
+
+#[derive(Default)]
+struct Translator {
+    /// Path of the enclosing `nested` query. Elasticsearch refers to the fields inside of it
+    /// by full path, while qdrant expects paths relative to the nested object.
+    nested_path: Option<String>,
+}
+
+impl Translator {
+    fn condition(&self, query: &Value) -> Result<Condition, StorageError> {
+        let (query_type, body) = single_entry(query, "query")?;
+        match query_type.as_str() {
+            "bool" => self.bool_query(&body),
+            "term" => self.term_query(&body),
+            "terms" => self.terms_query(&body),
+            "range" => self.range_query(&body),
+            "exists" => self.exists_query(&body),
+            "ids" => self.ids_query(&body),
+            "match" => self.match_query(&body),
+            "match_phrase" => self.match_phrase_query(&body),
+            "nested" => self.nested_query(&body),
+            "constant_score" => {
+                let mut params = query_params(&body, "constant_score")?;
+                let filter = required_param(&mut params, "filter", "constant_score")?;
+                check_no_params(params, "constant_score")?;
+                self.condition(&filter)
+            }
+            "match_all" => {
+                check_no_params(query_params(&body, "match_all")?, "match_all")?;
+                Ok(Condition::Filter(Filter::default()))
+            }
+            "match_none" => {
+                check_no_params(query_params(&body, "match_none")?, "match_none")?;
+                Ok(Condition::HasId(HasIdCondition::from_iter([])))
+            }
+            other => Err(error(format!("query `{other}` is not supported"))),
+        }
+    }
+
+    fn bool_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let mut params = query_params(body, "bool")?;
+        let mut clauses = |name: &str| -> Result<Vec<Condition>, StorageError> {
+            match params.remove(name) {
+                None => Ok(Vec::new()),
+                Some(Value::Array(queries)) => {
+                    queries.iter().map(|query| self.condition(query)).collect()
+                }
+                Some(query) => Ok(vec![self.condition(&query)?]),
+            }
+        };
+
+        // Scoring doesn't matter for filtering, so `filter` clauses are the same as `must`
+        let mut must = clauses("must")?;
+        must.extend(clauses("filter")?);
+        let should = clauses("should")?;
+        let must_not = clauses("must_not")?;
+
+        let min_should_match = match params.remove("minimum_should_match") {
+            Some(value) => minimum_should_match(&value, should.len())?,
+            // Without `must` clauses at least one of `should` clauses must match,
+            // otherwise they only affect the score
+            None if must.is_empty() => 1,
+            None => 0,
+        };
+        check_no_params(params, "bool")?;
+
+        let (should, min_should) = match min_should_match {
+            _ if should.is_empty() => (None, None),
+            // `should` clauses don't affect which points match
+            0 => (None, None),
+            1 => (Some(should), None),
+            min_count => (
+                None,
+                Some(MinShould {
+                    conditions: should,
+                    min_count,
+                }),
+            ),
+        };
+
+        Ok(Condition::Filter(Filter {
+            should,
+            min_should,
+            must: (!must.is_empty()).then_some(must),
+            must_not: (!must_not.is_empty()).then_some(must_not),
+        }))
+    }
+
+    fn term_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let (field, mut params) = field_query(body, "term", Some("value"))?;
+        let value = required_param(&mut params, "value", "term")?;
+        check_no_params(params, "term")?;
+
+        if field == ID_FIELD {
+            return Ok(Condition::HasId(HasIdCondition::from_iter([point_id(
+                &value,
+            )?])));
+        }
+        self.match_value(&field, value)
+    }
+
+    fn terms_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let mut params = query_params(body, "terms")?;
+        let (field, values) = single_entry(&Value::Object(params.clone()), "terms")?;
+        params.remove(&field);
+        check_no_params(params, "terms")?;
+
+        let Value::Array(values) = values else {
+            return Err(error(format!(
+                "values of `terms` on `{field}` must be an array"
+            )));
+        };
+
+        if field == ID_FIELD {
+            let ids = values.iter().map(point_id).collect::<Result<_, _>>()?;
+            return Ok(Condition::HasId(ids));
+        }
+
+        let all_strings = values.iter().all(Value::is_string);
+        let all_integers = values.iter().all(|value| value.is_i64());
+        if all_strings || all_integers {
+            return self.leaf(&field, json!({ "match": { "any": values } }));
+        }
+
+        // Mixed types can't be matched with a single condition
+        let conditions = values
+            .into_iter()
+            .map(|value| self.match_value(&field, value))
+            .collect::<Result<_, _>>()?;
+        Ok(Condition::Filter(Filter {
+            should: Some(conditions),
+            ..Default::default()
+        }))
+    }
+
+    fn range_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let (field, mut params) = field_query(body, "range", None)?;
+        let mut range = Map::new();
+        for bound in ["gt", "gte", "lt", "lte"] {
+            if let Some(value) = params.remove(bound).filter(|value| !value.is_null()) {
+                range.insert(bound.to_string(), value);
+            }
+        }
+        // Dates are parsed in any of the supported formats
+        params.remove("format");
+        check_no_params(params, "range")?;
+
+        self.leaf(&field, json!({ "range": range }))
+    }
+
+    fn exists_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let mut params = query_params(body, "exists")?;
+        let field = required_param(&mut params, "field", "exists")?;
+        check_no_params(params, "exists")?;
+
+        let Value::String(field) = field else {
+            return Err(error("field of `exists` must be a string"));
+        };
+        let is_empty = self.parse_condition(json!({ "is_empty": { "key": self.key(&field) } }))?;
+        Ok(Condition::Filter(Filter::new_must_not(is_empty)))
+    }
+
+    fn ids_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let mut params = query_params(body, "ids")?;
+        let values = required_param(&mut params, "values", "ids")?;
+        check_no_params(params, "ids")?;
+
+        let Value::Array(values) = values else {
+            return Err(error("values of `ids` must be an array"));
+        };
+        let ids = values.iter().map(point_id).collect::<Result<_, _>>()?;
+        Ok(Condition::HasId(ids))
+    }
+
+    fn match_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let (field, mut params) = field_query(body, "match", Some("query"))?;
+        let query = required_param(&mut params, "query", "match")?;
+        let operator = params.remove("operator");
+        check_no_params(params, "match")?;
+
+        let Value::String(text) = query else {
+            // Non-text values are matched exactly, like with `term`
+            return self.match_value(&field, query);
+        };
+
+        let operator = operator
+            .as_ref()
+            .and_then(Value::as_str)
+            .map(str::to_lowercase);
+        match operator.as_deref() {
+            None | Some("or") => self.leaf(&field, json!({ "match": { "text_any": text } })),
+            Some("and") => self.leaf(&field, json!({ "match": { "text": text } })),
+            Some(other) => Err(error(format!(
+                "operator `{other}` of `match` is not supported"
+            ))),
+        }
+    }
+
+    fn match_phrase_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let (field, mut params) = field_query(body, "match_phrase", Some("query"))?;
+        let query = required_param(&mut params, "query", "match_phrase")?;
+        let slop = params.remove("slop");
+        check_no_params(params, "match_phrase")?;
+
+        let mut phrase = json!({ "phrase": query });
+        if let Some(slop) = slop {
+            phrase["slop"] = slop;
+        }
+        self.leaf(&field, json!({ "match": phrase }))
+    }
+
+    fn nested_query(&self, body: &Value) -> Result<Condition, StorageError> {
+        let mut params = query_params(body, "nested")?;
+        let path = required_param(&mut params, "path", "nested")?;
+        let query = required_param(&mut params, "query", "nested")?;
+        params.remove("score_mode");
+        check_no_params(params, "nested")?;
+
+        let Value::String(path) = path else {
+            return Err(error("path of `nested` must be a string"));
+        };
+        let key = JsonPath::from_str(&self.key(&path))
+            .map_err(|()| error(format!("invalid path `{path}` of `nested`")))?;
+
+        let nested = Translator {
+            nested_path: Some(path),
+        };
+        let filter = into_filter(nested.condition(&query)?);
+
+        Ok(Condition::Nested(NestedCondition::new(Nested {
+            key,
+            filter,
+        })))
+    }
+
+    /// Match value exactly, as `term` query does
+    fn match_value(&self, field: &str, value: Value) -> Result<Condition, StorageError> {
+        match value {
+            // Floats can't be matched, but can be selected with a range of a single value
+            Value::Number(number) if number.is_f64() => {
+                self.leaf(field, json!({ "range": { "gte": number, "lte": number } }))
+            }
+            Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+                self.leaf(field, json!({ "match": { "value": value } }))
+            }
+            _ => Err(error(format!(
+                "value of `{field}` must be a string, number or boolean"
+            ))),
+        }
+    }
+
+    /// Build field condition from its JSON representation without the key
+    fn leaf(&self, field: &str, mut condition: Value) -> Result<Condition, StorageError> {
+        condition["key"] = Value::String(self.key(field));
+        self.parse_condition(condition)
+    }
+
+    fn parse_condition(&self, condition: Value) -> Result<Condition, StorageError> {
+        serde_json::from_value(condition.clone())
+            .map_err(|err| error(format!("can't translate into condition {condition}: {err}")))
+    }
+
+    /// Payload key of the Elasticsearch field
+    fn key(&self, field: &str) -> String {
+        let field = field.strip_suffix(KEYWORD_SUFFIX).unwrap_or(field);
+        self.nested_path
+            .as_deref()
+            .and_then(|path| field.strip_prefix(path)?.strip_prefix('.'))
+            .unwrap_or(field)
+            .to_string()
+    }
+}
+
+/// Split `{"name": value}` object into its only entry
+fn single_entry(value: &Value, what: &str) -> Result<(String, Value), StorageError> {
+    let Value::Object(object) = value else {
+        return Err(error(format!("{what} must be an object, got {value}")));
+    };
+    let mut entries = object.iter();
+    match (entries.next(), entries.next()) {
+        (Some((name, value)), None) => Ok((name.clone(), value.clone())),
+        _ => Err(error(format!(
+            "{what} must have exactly one key, got {value}"
+        ))),
+    }
+}
+
+/// Parameters of the query, without the ignored ones
+fn query_params(body: &Value, query_type: &str) -> Result<Map<String, Value>, StorageError> {
+    let Value::Object(params) = body else {
+        return Err(error(format!("`{query_type}` must be an object")));
+    };
+    let mut params = params.clone();
+    for ignored in IGNORED_PARAMS {
+        params.remove(*ignored);
+    }
+    Ok(params)
+}
+
+/// Split body of a query on a single field, like `{"field": {"value": "x", "boost": 2}}`,
+/// into the field and its parameters.
+///
+/// Short form `{"field": "x"}` is expanded into `{"field": {<short_param>: "x"}}`.
+fn field_query(
+    body: &Value,
+    query_type: &str,
+    short_param: Option<&str>,
+) -> Result<(String, Map<String, Value>), StorageError> {
+    let (field, params) = single_entry(body, &format!("`{query_type}`"))?;
+    let params = match (params, short_param) {
+        (params @ Value::Object(_), _) => query_params(&params, query_type)?,
+        (value, Some(short_param)) => Map::from_iter([(short_param.to_string(), value)]),
+        (_, None) => {
+            return Err(error(format!(
+                "`{query_type}` on `{field}` must be an object"
+            )));
+        }
+    };
+    Ok((field, params))
+}
+
+fn required_param(
+    params: &mut Map<String, Value>,
+    name: &str,
+    query_type: &str,
+) -> Result<Value, StorageError> {
+    params
+        .remove(name)
+        .ok_or_else(|| error(format!("`{query_type}` requires `{name}`")))
+}
+
+fn check_no_params(params: Map<String, Value>, query_type: &str) -> Result<(), StorageError> {
+    match params.keys().next() {
+        None => Ok(()),
+        Some(name) => Err(error(format!(
+            "parameter `{name}` of `{query_type}` is not supported",
+        ))),
+    }
+}
+
+/// Number of `should` clauses which must match.
+///
+/// Supports integers and percentages, negative values count the clauses which may not match.
+fn minimum_should_match(value: &Value, clauses: usize) -> Result<usize, StorageError> {
+    let invalid = || error(format!("unsupported `minimum_should_match` {value}"));
+
+    let (number, is_percent) = match value {
+        Value::Number(number) => (number.as_i64().ok_or_else(invalid)?, false),
+        Value::String(string) => match string.trim().strip_suffix('%') {
+            Some(percent) => (percent.parse().map_err(|_| invalid())?, true),
+            None => (string.trim().parse().map_err(|_| invalid())?, false),
+        },
+        _ => return Err(invalid()),
+    };
+
+    let count = if is_percent {
+        clauses * number.unsigned_abs() as usize / 100
+    } else {
+        number.unsigned_abs() as usize
+    };
+
+    if number < 0 {
+        Ok(clauses.saturating_sub(count))
+    } else {
+        Ok(count)
+    }
+}
+
+fn point_id(value: &Value) -> Result<ExtendedPointId, StorageError> {
+    let id = match value {
+        Value::Number(number) => number.as_u64().map(ExtendedPointId::NumId),
+        Value::String(string) => ExtendedPointId::from_str(string).ok(),
+        _ => None,
+    };
+    id.ok_or_else(|| error(format!("`{value}` is not a valid point id")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translate(query: Value) -> Value {
+        serde_json::to_value(es_query_to_filter(&query).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_translate_bool_query() {
+        let filter = translate(json!({
+            "bool": {
+                "must": { "term": { "status.keyword": "active" } },
+                "filter": [
+                    { "range": { "price": { "gte": 10, "lt": 20.5 } } },
+                    { "terms": { "tags": ["a", "b"] } },
+                ],
+                "must_not": [{ "exists": { "field": "deleted_at" } }],
+                "should": [
+                    { "term": { "color": { "value": "red", "boost": 2 } } },
+                    { "term": { "size": 42 } },
+                    { "match": { "title": "red shoes" } },
+                ],
+                "minimum_should_match": 2,
+            }
+        }));
+
+        let expected = json!({
+            "min_should": {
+                "conditions": [
+                    { "key": "color", "match": { "value": "red" } },
+                    { "key": "size", "match": { "value": 42 } },
+                    { "key": "title", "match": { "text_any": "red shoes" } },
+                ],
+                "min_count": 2,
+            },
+            "must": [
+                { "key": "status", "match": { "value": "active" } },
+                { "key": "price", "range": { "gte": 10.0, "lt": 20.5 } },
+                { "key": "tags", "match": { "any": ["a", "b"] } },
+            ],
+            "must_not": [
+                { "must_not": [{ "is_empty": { "key": "deleted_at" } }] },
+            ],
+        });
+
+        assert_eq!(
+            serde_json::from_value::<Filter>(filter).unwrap(),
+            serde_json::from_value::<Filter>(expected).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_translate_should_clauses() {
+        // Without `must` clauses, at least one `should` clause must match
+        let filter = es_query_to_filter(&json!({
+            "bool": { "should": [{ "term": { "a": 1 } }, { "term": { "b": 2 } }] }
+        }))
+        .unwrap();
+        assert_eq!(filter.should.map(|should| should.len()), Some(2));
+
+        // With `must` clauses, `should` clauses only affect scoring
+        let filter = es_query_to_filter(&json!({
+            "bool": {
+                "must": [{ "term": { "a": 1 } }],
+                "should": [{ "term": { "b": 2 } }],
+            }
+        }))
+        .unwrap();
+        assert!(filter.should.is_none());
+        assert!(filter.min_should.is_none());
+
+        assert_eq!(minimum_should_match(&json!("75%"), 3).unwrap(), 2);
+        assert_eq!(minimum_should_match(&json!("-1"), 3).unwrap(), 2);
+        assert!(minimum_should_match(&json!("3<90%"), 3).is_err());
+    }
+
+    #[test]
+    fn test_translate_ids_and_nested() {
+        let filter = es_query_to_filter(&json!({
+            "bool": {
+                "filter": [
+                    { "ids": { "values": ["1", "550e8400-e29b-41d4-a716-446655440000"] } },
+                    {
+                        "nested": {
+                            "path": "comments",
+                            "query": {
+                                "match_phrase": {
+                                    "comments.text": { "query": "great product", "slop": 1 }
+                                }
+                            },
+                        }
+                    },
+                ]
+            }
+        }))
+        .unwrap();
+
+        let must = filter.must.unwrap();
+        let Condition::HasId(has_id) = &must[0] else {
+            panic!("expected has_id condition, got {:?}", must[0]);
+        };
+        assert!(has_id.has_id.contains(&ExtendedPointId::NumId(1)));
+        assert_eq!(has_id.has_id.len(), 2);
+
+        let Condition::Nested(nested) = &must[1] else {
+            panic!("expected nested condition, got {:?}", must[1]);
+        };
+        assert_eq!(nested.raw_key().to_string(), "comments");
+        assert_eq!(
+            serde_json::to_value(&nested.nested.filter).unwrap(),
+            json!({ "must": [{ "key": "text", "match": { "phrase": "great product", "slop": 1 } }] }),
+        );
+    }
+
+    #[test]
+    fn test_reject_unsupported_queries() {
+        for query in [
+            json!({ "wildcard": { "name": "jo*" } }),
+            json!({ "match": { "name": { "query": "john", "fuzziness": "AUTO" } } }),
+            json!({ "range": { "date": { "gte": "now-1d" } } }),
+            json!({ "term": { "a": 1 }, "terms": { "b": [2] } }),
+            json!({ "ids": { "values": ["not an id"] } }),
+        ] {
+            assert!(es_query_to_filter(&query).is_err(), "{query} is accepted");
+        }
+    }
+}
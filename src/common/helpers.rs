@@ -72,9 +72,17 @@ pub fn load_tls_client_config(settings: &Settings) -> io::Result<Option<ClientTl
     }
 }
 
-/// Load server TLS configuration for external gRPC
-pub fn load_tls_external_server_config(tls_config: &TlsConfig) -> io::Result<ServerTlsConfig> {
-    Ok(ServerTlsConfig::new().identity(load_identity(tls_config)?))
+/// Load server TLS configuration for external gRPC, optionally check client certificate against CA
+pub fn load_tls_external_server_config(
+    tls_config: &TlsConfig,
+    verify_client_certificate: bool,
+) -> io::Result<ServerTlsConfig> {
+    let config = ServerTlsConfig::new().identity(load_identity(tls_config)?);
+    if verify_client_certificate {
+        Ok(config.client_ca_root(load_ca_certificate(tls_config)?))
+    } else {
+        Ok(config)
+    }
 }
 
 /// Load server TLS configuration for internal gRPC, check client certificate against CA
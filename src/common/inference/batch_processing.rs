@@ -112,7 +112,9 @@ fn collect_query(query: &Query, batch: &mut BatchAccum) {
         | Query::Fusion(_)
         | Query::Rrf(_)
         | Query::Formula(_)
-        | Query::Sample(_) => {}
+        | Query::Sample(_)
+        | Query::Text(_)
+        | Query::Hybrid(_) => {}
     }
 }
 
@@ -130,6 +132,7 @@ fn collect_prefetch(prefetch: &Prefetch, batch: &mut BatchAccum) {
         using: _,
         filter: _,
         params: _,
+        quantization: _,
         score_threshold: _,
         limit: _,
         lookup_from: _,
@@ -307,6 +310,7 @@ mod tests {
                 using: None,
                 filter: None,
                 params: None,
+                quantization: None,
                 score_threshold: None,
                 limit: None,
                 lookup_from: None,
@@ -314,6 +318,7 @@ mod tests {
             using: None,
             filter: None,
             params: None,
+            quantization: None,
             score_threshold: None,
             limit: None,
             lookup_from: None,
@@ -345,6 +350,7 @@ mod tests {
                 using: None,
                 filter: None,
                 params: None,
+                quantization: None,
                 score_threshold: None,
                 limit: None,
                 lookup_from: None,
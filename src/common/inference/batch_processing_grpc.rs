@@ -186,6 +186,8 @@ pub(crate) fn collect_query(query: &Query, batch: &mut BatchAccumGrpc) -> Result
         query::Variant::Fusion(_) => {}
         query::Variant::Rrf(_) => {}
         query::Variant::Sample(_) => {}
+        query::Variant::Text(_) => {}
+        query::Variant::Hybrid(_) => {}
         query::Variant::Formula(_) => {}
         query::Variant::NearestWithMmr(nearest_with_mmr) => {
             nearest_with_mmr
@@ -210,6 +212,7 @@ pub(crate) fn collect_prefetch(
         using: _,
         filter: _,
         params: _,
+        quantization: _,
         score_threshold: _,
         limit: _,
         lookup_from: _,
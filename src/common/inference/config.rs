@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -5,6 +7,13 @@ pub struct InferenceConfig {
     pub address: Option<String>,
     pub timeout: Option<u64>,
     pub token: Option<String>,
+    /// ONNX models, which are run by Qdrant itself instead of the inference service.
+    /// Requires Qdrant to be built with `onnx` feature.
+    #[serde(default)]
+    pub local_models: Vec<LocalModelConfig>,
+    /// External services, which rerank query candidates
+    #[serde(default)]
+    pub rerankers: Vec<RerankerConfig>,
 }
 
 impl InferenceConfig {
@@ -13,6 +22,85 @@ impl InferenceConfig {
             address,
             timeout: None,
             token: None,
+            local_models: Vec::new(),
+            rerankers: Vec::new(),
         }
     }
 }
+
+const fn default_max_length() -> usize {
+    512
+}
+
+const fn default_normalize() -> bool {
+    true
+}
+
+/// Text embedding model in ONNX format
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalModelConfig {
+    /// Name of the model, used as `model` of the documents
+    pub name: String,
+    /// Path to the model file
+    pub model_path: PathBuf,
+    /// Path to `tokenizer.json` of the model
+    pub tokenizer_path: PathBuf,
+    /// Maximum number of tokens, longer texts are truncated
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+    /// How token embeddings are combined into the text embedding.
+    /// Ignored, if the model already outputs text embeddings.
+    #[serde(default)]
+    pub pooling: LocalModelPooling,
+    /// Normalize embeddings to the unit length
+    #[serde(default = "default_normalize")]
+    pub normalize: bool,
+    /// Prefix added to the texts of search requests, e.g. `query: `
+    #[serde(default)]
+    pub query_prefix: Option<String>,
+    /// Prefix added to the texts of upserted points, e.g. `passage: `
+    #[serde(default)]
+    pub document_prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalModelPooling {
+    /// Average of the token embeddings
+    #[default]
+    Mean,
+    /// Embedding of the first token
+    Cls,
+}
+
+const fn default_reranker_timeout_ms() -> u64 {
+    1000
+}
+
+/// External service, which reranks query candidates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankerConfig {
+    /// Name of the reranker, used in the `reranker` stage of queries
+    pub name: String,
+    /// URL of the HTTP endpoint, or address of the gRPC server
+    pub address: String,
+    #[serde(default)]
+    pub protocol: RerankerProtocol,
+    /// Timeout of reranking requests, in milliseconds.
+    /// If the reranker doesn't respond in time, the original order of candidates is kept.
+    #[serde(default = "default_reranker_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Token, sent in the `Authorization: Bearer` header
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RerankerProtocol {
+    /// JSON over HTTP
+    #[default]
+    Http,
+    /// gRPC
+    Grpc,
+}
@@ -1,7 +1,13 @@
+#[cfg(feature = "onnx")]
+use std::collections::HashMap;
+
 use collection::operations::point_ops::VectorPersisted;
 use storage::content_manager::errors::StorageError;
 
 use super::bm25::Bm25;
+use super::config::LocalModelConfig;
+#[cfg(feature = "onnx")]
+use super::onnx::OnnxModel;
 use super::service::{InferenceInput, InferenceType};
 use crate::common::inference::inference_input::InferenceDataType;
 
@@ -19,65 +25,154 @@ impl LocalModelName {
     }
 }
 
-/// Run inference with only local models.
+/// Models, which are handled by Qdrant and are not forwarded to a remote inference service.
 ///
-/// # Panics
-/// Panics if one inference input did not target a local model.
-pub fn infer_local(
-    inference_inputs: Vec<InferenceInput>,
-    inference_type: InferenceType,
-) -> Result<Vec<VectorPersisted>, StorageError> {
-    let mut out = Vec::with_capacity(inference_inputs.len());
-
-    for input in inference_inputs {
-        let InferenceInput {
-            data,
-            data_type,
-            model,
-            options,
-        } = input;
-
-        let Some(model_name) = LocalModelName::from_str(&model) else {
-            unreachable!(
-                "Non local model has been passed to infer_local(). This can happen if a newly added model wasn't added to infer_local()"
-            )
-        };
+/// Built-in models are always available, ONNX models are loaded from the config.
+#[derive(Default)]
+pub struct LocalModels {
+    #[cfg(feature = "onnx")]
+    onnx_models: HashMap<String, OnnxModel>,
+}
+
+impl LocalModels {
+    pub fn load(configs: &[LocalModelConfig]) -> Result<Self, StorageError> {
+        if let Some(config) = configs
+            .iter()
+            .find(|config| LocalModelName::from_str(&config.name).is_some())
+        {
+            return Err(StorageError::service_error(format!(
+                "Local model {} conflicts with the built-in model",
+                config.name,
+            )));
+        }
+
+        #[cfg(not(feature = "onnx"))]
+        if !configs.is_empty() {
+            return Err(StorageError::service_error(
+                "Local models are configured, but Qdrant is built without `onnx` feature",
+            ));
+        }
 
-        // Validate it is text
-        match data_type {
-            InferenceDataType::Text => {}
-            InferenceDataType::Image | InferenceDataType::Object => {
-                return Err(StorageError::bad_input(format!(
-                    "Only text input is supported for {model}."
-                )));
+        #[cfg(feature = "onnx")]
+        let onnx_models = {
+            let mut onnx_models = HashMap::with_capacity(configs.len());
+            for config in configs {
+                let model = OnnxModel::load(config.clone())?;
+                log::info!("Loaded local model {}", model.name());
+                if onnx_models.insert(config.name.clone(), model).is_some() {
+                    return Err(StorageError::service_error(format!(
+                        "Local model {} is configured more than once",
+                        config.name,
+                    )));
+                }
             }
+            onnx_models
         };
 
-        let input_str = data.as_str().ok_or_else(|| {
-            StorageError::bad_input(format!("Only text input is supported for {model}."))
-        })?;
+        Ok(Self {
+            #[cfg(feature = "onnx")]
+            onnx_models,
+        })
+    }
+
+    /// Returns `true` if the provided `model_name` targets a local model.
+    pub fn is_local_model(&self, model_name: &str) -> bool {
+        #[cfg(feature = "onnx")]
+        if self.onnx_models.contains_key(model_name) {
+            return true;
+        }
+
+        LocalModelName::from_str(model_name).is_some()
+    }
+
+    /// Run inference with only local models.
+    ///
+    /// ONNX models are run on the current thread, so it should be allowed to block.
+    ///
+    /// # Panics
+    /// Panics if one inference input did not target a local model.
+    pub fn infer(
+        &self,
+        inference_inputs: Vec<InferenceInput>,
+        inference_type: InferenceType,
+    ) -> Result<Vec<VectorPersisted>, StorageError> {
+        let mut out = Vec::with_capacity(inference_inputs.len());
+
+        // Texts for each ONNX model with their positions, to run the model on all of them at once
+        #[cfg(feature = "onnx")]
+        let mut onnx_inputs: HashMap<&str, (Vec<usize>, Vec<String>)> = HashMap::new();
 
-        let embedding = match model_name {
-            LocalModelName::Bm25 => {
-                let bm25_config = InferenceInput::parse_bm25_config(options)?;
-                let bm25 = Bm25::new(bm25_config);
+        for input in inference_inputs {
+            let InferenceInput {
+                data,
+                data_type,
+                model,
+                options,
+            } = input;
 
-                match inference_type {
-                    InferenceType::Update => bm25.doc_embed(input_str),
-                    InferenceType::Search => bm25.search_embed(input_str),
+            // Validate it is text
+            match data_type {
+                InferenceDataType::Text => {}
+                InferenceDataType::Image | InferenceDataType::Object => {
+                    return Err(StorageError::bad_input(format!(
+                        "Only text input is supported for {model}."
+                    )));
                 }
+            };
+
+            let input_str = data.as_str().ok_or_else(|| {
+                StorageError::bad_input(format!("Only text input is supported for {model}."))
+            })?;
+
+            #[cfg(feature = "onnx")]
+            if let Some((name, _)) = self.onnx_models.get_key_value(&model) {
+                let (positions, texts) = onnx_inputs.entry(name).or_default();
+                positions.push(out.len());
+                texts.push(input_str.to_string());
+                // Placeholder, replaced once the model is run
+                out.push(VectorPersisted::Dense(Vec::new()));
+                continue;
             }
-        };
 
-        out.push(embedding);
+            let Some(model_name) = LocalModelName::from_str(&model) else {
+                unreachable!(
+                    "Non local model has been passed to LocalModels::infer(). This can happen if a newly added model wasn't added to LocalModels::infer()"
+                )
+            };
+
+            let embedding = match model_name {
+                LocalModelName::Bm25 => {
+                    let bm25_config = InferenceInput::parse_bm25_config(options)?;
+                    let bm25 = Bm25::new(bm25_config);
+
+                    match inference_type {
+                        InferenceType::Update => bm25.doc_embed(input_str),
+                        InferenceType::Search => bm25.search_embed(input_str),
+                    }
+                }
+            };
+
+            out.push(embedding);
+        }
+
+        #[cfg(feature = "onnx")]
+        for (name, (positions, texts)) in onnx_inputs {
+            let embeddings = self.onnx_models[name].embed(texts, inference_type)?;
+            for (position, embedding) in positions.into_iter().zip(embeddings) {
+                out[position] = VectorPersisted::Dense(embedding);
+            }
+        }
+
+        Ok(out)
     }
 
-    Ok(out)
-}
+    /// Whether inference with local models should be moved off the async runtime
+    pub fn is_blocking(&self) -> bool {
+        #[cfg(feature = "onnx")]
+        if !self.onnx_models.is_empty() {
+            return true;
+        }
 
-/// Returns `true` if the provided `model_name` targets a local model. Local models
-/// are models that are handled by Qdrant and are not forwarded to a remote inference service.
-pub fn is_local_model(model_name: &str) -> bool {
-    let model_name = LocalModelName::from_str(model_name);
-    model_name.is_some()
+        false
+    }
 }
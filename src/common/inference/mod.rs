@@ -8,6 +8,8 @@ pub(crate) mod config;
 mod infer_processing;
 pub mod inference_input;
 mod local_model;
+#[cfg(feature = "onnx")]
+mod onnx;
 pub mod params;
 pub mod query_requests_grpc;
 pub mod query_requests_rest;
@@ -0,0 +1,231 @@
+//! Text embedding models in ONNX format, run with ONNX Runtime.
+//!
+//! ONNX Runtime library is loaded dynamically, from the path in `ORT_DYLIB_PATH` environment
+//! variable, or from the default library search paths.
+
+use std::fmt::Display;
+
+use ort::session::Session;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::value::Tensor;
+use parking_lot::Mutex;
+use storage::content_manager::errors::StorageError;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::config::{LocalModelConfig, LocalModelPooling};
+use super::service::InferenceType;
+
+/// Maximum number of texts passed to the model at once
+const ONNX_BATCH_SIZE: usize = 32;
+
+const INPUT_IDS: &str = "input_ids";
+const ATTENTION_MASK: &str = "attention_mask";
+const TOKEN_TYPE_IDS: &str = "token_type_ids";
+
+pub struct OnnxModel {
+    config: LocalModelConfig,
+    tokenizer: Tokenizer,
+    /// Session can only run one inference at a time
+    session: Mutex<Session>,
+    /// BERT-like models also expect ids of the token types
+    has_token_type_ids: bool,
+}
+
+impl OnnxModel {
+    pub fn load(config: LocalModelConfig) -> Result<Self, StorageError> {
+        let load_error = |err: &dyn Display| {
+            StorageError::service_error(format!(
+                "Failed to load local model {}: {err}",
+                config.name,
+            ))
+        };
+
+        let mut tokenizer =
+            Tokenizer::from_file(&config.tokenizer_path).map_err(|err| load_error(&err))?;
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: config.max_length,
+                ..Default::default()
+            }))
+            .map_err(|err| load_error(&err))?;
+        // Pad to the longest text in the batch
+        tokenizer.with_padding(Some(PaddingParams::default()));
+
+        let session = build_session(&config).map_err(|err| load_error(&err))?;
+
+        let input_names: Vec<_> = session
+            .inputs
+            .iter()
+            .map(|input| input.name.as_str())
+            .collect();
+        for required in [INPUT_IDS, ATTENTION_MASK] {
+            if !input_names.contains(&required) {
+                return Err(load_error(&format!("model has no {required} input")));
+            }
+        }
+        let has_token_type_ids = input_names.contains(&TOKEN_TYPE_IDS);
+
+        Ok(Self {
+            config,
+            tokenizer,
+            session: Mutex::new(session),
+            has_token_type_ids,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Embed the texts. Blocks the thread until inference is done.
+    pub fn embed(
+        &self,
+        texts: Vec<String>,
+        inference_type: InferenceType,
+    ) -> Result<Vec<Vec<f32>>, StorageError> {
+        let prefix = match inference_type {
+            InferenceType::Update => &self.config.document_prefix,
+            InferenceType::Search => &self.config.query_prefix,
+        };
+
+        let texts = match prefix {
+            Some(prefix) => texts
+                .into_iter()
+                .map(|text| format!("{prefix}{text}"))
+                .collect(),
+            None => texts,
+        };
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(ONNX_BATCH_SIZE) {
+            embeddings.extend(self.embed_batch(batch)?);
+        }
+
+        Ok(embeddings)
+    }
+
+    fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, StorageError> {
+        let inference_error = |err: &dyn Display| {
+            StorageError::service_error(format!(
+                "Failed to run local model {}: {err}",
+                self.config.name,
+            ))
+        };
+
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.iter().map(String::as_str).collect(), true)
+            .map_err(|err| inference_error(&err))?;
+
+        let batch_size = encodings.len();
+        let seq_len = encodings.first().map_or(0, |encoding| encoding.len());
+
+        let mut input_ids = Vec::with_capacity(batch_size * seq_len);
+        let mut attention_mask = Vec::with_capacity(batch_size * seq_len);
+        let mut token_type_ids = Vec::with_capacity(batch_size * seq_len);
+        for encoding in &encodings {
+            input_ids.extend(encoding.get_ids().iter().map(|&id| i64::from(id)));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|&m| i64::from(m)));
+            token_type_ids.extend(encoding.get_type_ids().iter().map(|&id| i64::from(id)));
+        }
+
+        let tensor = |data: Vec<i64>| {
+            Tensor::from_array(([batch_size, seq_len], data)).map_err(|err| inference_error(&err))
+        };
+
+        let mut inputs = ort::inputs![
+            INPUT_IDS => tensor(input_ids)?,
+            ATTENTION_MASK => tensor(attention_mask.clone())?,
+        ];
+        if self.has_token_type_ids {
+            inputs.push((TOKEN_TYPE_IDS.into(), tensor(token_type_ids)?.into()));
+        }
+
+        let mut session = self.session.lock();
+        let outputs = session.run(inputs).map_err(|err| inference_error(&err))?;
+        let (shape, output) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|err| inference_error(&err))?;
+
+        let mut embeddings: Vec<Vec<f32>> = match **shape {
+            // Model already outputs text embeddings
+            [_, dim] => output.chunks(dim as usize).map(<[f32]>::to_vec).collect(),
+            // Model outputs token embeddings
+            [_, _, dim] => output
+                .chunks(seq_len * dim as usize)
+                .zip(attention_mask.chunks(seq_len))
+                .map(|(tokens, mask)| pool(tokens, mask, dim as usize, self.config.pooling))
+                .collect(),
+            _ => {
+                return Err(inference_error(&format!(
+                    "unexpected shape of the output {shape:?}",
+                )));
+            }
+        };
+
+        if self.config.normalize {
+            embeddings
+                .iter_mut()
+                .for_each(|embedding| normalize(embedding));
+        }
+
+        Ok(embeddings)
+    }
+}
+
+fn build_session(config: &LocalModelConfig) -> ort::Result<Session> {
+    let session = Session::builder()?
+        .with_optimization_level(GraphOptimizationLevel::Level3)?
+        .commit_from_file(&config.model_path)?;
+    Ok(session)
+}
+
+/// Combine embeddings of the tokens, which are not padding, into one
+fn pool(tokens: &[f32], mask: &[i64], dim: usize, pooling: LocalModelPooling) -> Vec<f32> {
+    match pooling {
+        LocalModelPooling::Cls => tokens[..dim].to_vec(),
+        LocalModelPooling::Mean => {
+            let mut sum = vec![0.0; dim];
+            let mut count = 0;
+            for (token, _) in tokens.chunks(dim).zip(mask).filter(|(_, mask)| **mask != 0) {
+                sum.iter_mut()
+                    .zip(token)
+                    .for_each(|(sum, value)| *sum += value);
+                count += 1;
+            }
+            let count = count.max(1) as f32;
+            sum.iter_mut().for_each(|value| *value /= count);
+            sum
+        }
+    }
+}
+
+fn normalize(embedding: &mut [f32]) {
+    let length = embedding
+        .iter()
+        .map(|value| value * value)
+        .sum::<f32>()
+        .sqrt();
+    if length > f32::EPSILON {
+        embedding.iter_mut().for_each(|value| *value /= length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pooling() {
+        // Two tokens of dimension 2 and one padding token
+        let tokens = [1.0, 2.0, 3.0, 4.0, 100.0, 100.0];
+        let mask = [1, 1, 0];
+
+        assert_eq!(pool(&tokens, &mask, 2, LocalModelPooling::Cls), [1.0, 2.0]);
+        assert_eq!(pool(&tokens, &mask, 2, LocalModelPooling::Mean), [2.0, 3.0]);
+
+        let mut embedding = vec![3.0, 4.0];
+        normalize(&mut embedding);
+        assert_eq!(embedding, [0.6, 0.8]);
+    }
+}
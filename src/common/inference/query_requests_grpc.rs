@@ -11,8 +11,10 @@ use collection::operations::universal_query::formula::FormulaInternal;
 use collection::operations::universal_query::shard_query::{FusionInternal, SampleInternal};
 use ordered_float::OrderedFloat;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::text_query::TextQuery;
 use segment::data_types::vectors::{DEFAULT_VECTOR_NAME, MultiDenseVectorInternal, VectorInternal};
-use segment::types::{Filter, PointIdType, SearchParams};
+use segment::index::hybrid_index::HybridQuery;
+use segment::types::{Filter, PointIdType, QuantizationSearchParams, SearchParams};
 use segment::vector_storage::query::{
     ContextPair, ContextQuery, DiscoverQuery, FeedbackItem, RecoQuery,
 };
@@ -48,6 +50,7 @@ pub async fn convert_query_point_groups_from_grpc(
         read_consistency: _,
         timeout: _,
         shard_key_selector: _,
+        with_search_diagnostics: _,
     } = query;
 
     let mut batch = BatchAccumGrpc::new();
@@ -127,6 +130,7 @@ pub async fn convert_query_points_from_grpc(
         shard_key_selector: _,
         lookup_from,
         timeout: _,
+        with_search_diagnostics: _,
     } = query;
 
     let mut batch = BatchAccumGrpc::new();
@@ -192,6 +196,7 @@ fn convert_prefetch_with_inferred(
         using,
         filter,
         params,
+        quantization,
         score_threshold,
         limit,
         lookup_from,
@@ -215,7 +220,10 @@ fn convert_prefetch_with_inferred(
         limit: limit
             .map(|l| l as usize)
             .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
-        params: params.map(SearchParams::from),
+        params: SearchParams::with_quantization_override(
+            params.map(SearchParams::from),
+            quantization.map(QuantizationSearchParams::from),
+        ),
         lookup_from: lookup_from.map(LookupLocation::try_from).transpose()?,
     })
 }
@@ -296,6 +304,8 @@ fn convert_query_with_inferred(
         Variant::Rrf(rrf) => Query::Fusion(FusionInternal::try_from(rrf)?),
         Variant::Formula(formula) => Query::Formula(FormulaInternal::try_from(formula)?),
         Variant::Sample(sample) => Query::Sample(SampleInternal::try_from(sample)?),
+        Variant::Text(text) => Query::Text(TextQuery::try_from(text)?),
+        Variant::Hybrid(hybrid) => Query::Hybrid(HybridQuery::try_from(hybrid)?),
         Variant::NearestWithMmr(grpc::NearestInputWithMmr { nearest, mmr }) => {
             let nearest =
                 nearest.ok_or_else(|| Status::invalid_argument("nearest vector is missing"))?;
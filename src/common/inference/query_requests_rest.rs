@@ -273,6 +273,8 @@ fn convert_query_with_inferred(
         rest::Query::Rrf(rrf) => Ok(Query::Fusion(FusionInternal::from(rrf.rrf))),
         rest::Query::Formula(formula) => Ok(Query::Formula(FormulaInternal::from(formula))),
         rest::Query::Sample(sample) => Ok(Query::Sample(SampleInternal::from(sample.sample))),
+        rest::Query::Text(text) => Ok(Query::Text(text.text)),
+        rest::Query::Hybrid(hybrid) => Ok(Query::Hybrid(hybrid.hybrid)),
         rest::Query::RelevanceFeedback(relevance_feedback) => {
             let rest::RelevanceFeedbackInput {
                 target,
@@ -313,6 +315,7 @@ fn convert_prefetch_with_inferred(
         filter,
         score_threshold,
         params,
+        quantization,
         limit,
         lookup_from,
     } = prefetch;
@@ -337,7 +340,7 @@ fn convert_prefetch_with_inferred(
         filter,
         score_threshold: score_threshold.map(OrderedFloat),
         limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
-        params,
+        params: SearchParams::with_quantization_override(params, quantization),
         lookup_from,
     })
 }
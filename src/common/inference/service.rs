@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use storage::content_manager::errors::StorageError;
 
 pub use super::inference_input::InferenceInput;
-use super::local_model;
+use super::local_model::LocalModels;
 use crate::common::inference::api_keys::{InferenceApiKeys, convert_to_reqwest_headers};
 use crate::common::inference::config::InferenceConfig;
 use crate::common::inference::params::InferenceParams;
@@ -74,6 +74,7 @@ impl InferenceData {
 pub struct InferenceService {
     pub(crate) config: InferenceConfig,
     pub(crate) client: Client,
+    pub(crate) local_models: Arc<LocalModels>,
 }
 
 static INFERENCE_SERVICE: RwLock<Option<Arc<InferenceService>>> = RwLock::new(None);
@@ -90,6 +91,8 @@ impl InferenceService {
             address: _,
             timeout,
             token: _,
+            local_models: _,
+            rerankers: _,
         } = &config;
 
         let timeout = timeout.unwrap_or(DEFAULT_INFERENCE_TIMEOUT_SECS);
@@ -102,13 +105,14 @@ impl InferenceService {
             client: client_builder
                 .build()
                 .expect("Invalid timeout value for HTTP client"),
+            local_models: Arc::new(LocalModels::default()),
         }
     }
 
     pub fn init_global(config: Option<InferenceConfig>) -> Result<(), StorageError> {
         let mut inference_service = INFERENCE_SERVICE.write();
 
-        let service = Self::new(config);
+        let mut service = Self::new(config);
 
         if !service.is_address_valid() {
             return Err(StorageError::service_error(
@@ -116,6 +120,8 @@ impl InferenceService {
             ));
         }
 
+        service.local_models = Arc::new(LocalModels::load(&service.config.local_models)?);
+
         *inference_service = Some(Arc::new(service));
         Ok(())
     }
@@ -148,7 +154,7 @@ impl InferenceService {
             .enumerate()
             .partition_map(|(pos, input)| {
                 // Check if input is targeting a local model or the configured remote server.
-                if local_model::is_local_model(&input.model) {
+                if self.local_models.is_local_model(&input.model) {
                     Either::Left((input, pos))
                 } else {
                     Either::Right((input, pos))
@@ -156,7 +162,17 @@ impl InferenceService {
             });
 
         // Run inference on local models
-        let local_model_results = local_model::infer_local(local_inference_inputs, inference_type)?;
+        let local_model_results =
+            if self.local_models.is_blocking() && !local_inference_inputs.is_empty() {
+                let local_models = self.local_models.clone();
+                tokio::task::spawn_blocking(move || {
+                    local_models.infer(local_inference_inputs, inference_type)
+                })
+                .await??
+            } else {
+                self.local_models
+                    .infer(local_inference_inputs, inference_type)?
+            };
 
         // Early return with the local model's results if no other inference_inputs were passed.
         // If local models is also empty, we automatically return an empty response here.
@@ -556,6 +572,8 @@ mod test {
             address: Some(server.url()), // Use mock's URL as address when doing inference.
             timeout: None,
             token: Some(String::default()),
+            local_models: Vec::new(),
+            rerankers: Vec::new(),
         };
 
         let service = InferenceService::new(Some(config));
@@ -7,6 +7,7 @@ use collection::operations::point_ops::{
     VectorStructPersisted,
 };
 use collection::operations::vector_ops::PointVectorsPersisted;
+use segment::segment::point_expirations::unix_timestamp_now;
 use storage::content_manager::errors::StorageError;
 
 use crate::common::inference::batch_processing::BatchAccum;
@@ -57,6 +58,7 @@ pub async fn convert_point_struct(
             id,
             vector,
             payload,
+            ttl,
         } = point_struct;
 
         let converted_vector_struct = match vector {
@@ -147,6 +149,7 @@ pub async fn convert_point_struct(
             id,
             vector: converted_vector_struct,
             payload,
+            expires_at: ttl_to_expires_at(ttl),
         };
 
         converted_points.push(converted);
@@ -304,6 +307,7 @@ fn convert_point_struct_with_inferred(
                 id,
                 vector,
                 payload,
+                ttl,
             } = point_struct;
             let converted_vector_struct = match vector {
                 VectorStruct::Single(single) => VectorStructPersisted::Single(single),
@@ -365,6 +369,7 @@ fn convert_point_struct_with_inferred(
                 id,
                 vector: converted_vector_struct,
                 payload,
+                expires_at: ttl_to_expires_at(ttl),
             })
         })
         .collect()
@@ -447,3 +452,9 @@ fn convert_vector_with_inferred(
         }
     }
 }
+
+/// Resolve time to live of a point into an absolute expiration time, which is persisted with the
+/// operation, so that it does not shift on WAL replay
+fn ttl_to_expires_at(ttl: Option<u64>) -> Option<u64> {
+    ttl.map(|ttl| unix_timestamp_now().saturating_add(ttl))
+}
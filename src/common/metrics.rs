@@ -6,6 +6,8 @@ use itertools::Itertools;
 use prometheus::TextEncoder;
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use segment::common::operation_time_statistics::OperationDurationStatistics;
+use segment::vector_storage::background_populate::BackgroundPopulateTelemetry;
+use segment::vector_storage::dense::hot_vectors_cache::HotVectorsCacheTelemetry;
 use shard::PeerId;
 use storage::types::ConsensusThreadStatus;
 
@@ -147,6 +149,12 @@ impl MetricsProvider for TelemetryData {
         if let Some(mem) = &self.memory {
             mem.add_metrics(metrics, prefix);
         }
+        if let Some(background_populate) = &self.background_populate {
+            background_populate.add_metrics(metrics, prefix);
+        }
+        if let Some(hot_vectors_cache) = &self.hot_vectors_cache {
+            hot_vectors_cache.add_metrics(metrics, prefix);
+        }
 
         #[cfg(target_os = "linux")]
         match procfs_metrics::ProcFsMetrics::collect() {
@@ -812,6 +820,65 @@ impl MetricsProvider for MemoryTelemetry {
     }
 }
 
+impl MetricsProvider for BackgroundPopulateTelemetry {
+    fn add_metrics(&self, metrics: &mut MetricsData, prefix: Option<&str>) {
+        metrics.push_metric(metric_family(
+            "background_populate_pending_files",
+            "number of vector storage files, waiting to be populated in background",
+            MetricType::GAUGE,
+            vec![gauge(self.pending_files as f64, &[])],
+            prefix,
+        ));
+        metrics.push_metric(metric_family(
+            "background_populate_scheduled_bytes",
+            "total size of vector storage files, scheduled for background population",
+            MetricType::COUNTER,
+            vec![counter(self.scheduled_bytes as f64, &[])],
+            prefix,
+        ));
+        metrics.push_metric(metric_family(
+            "background_populate_populated_bytes",
+            "number of bytes, read into page cache by background population",
+            MetricType::COUNTER,
+            vec![counter(self.populated_bytes as f64, &[])],
+            prefix,
+        ));
+    }
+}
+
+impl MetricsProvider for HotVectorsCacheTelemetry {
+    fn add_metrics(&self, metrics: &mut MetricsData, prefix: Option<&str>) {
+        metrics.push_metric(metric_family(
+            "hot_vectors_cache_hits",
+            "number of on-disk vector reads, served from the hot vectors cache",
+            MetricType::COUNTER,
+            vec![counter(self.hits as f64, &[])],
+            prefix,
+        ));
+        metrics.push_metric(metric_family(
+            "hot_vectors_cache_misses",
+            "number of on-disk vector reads, not found in the hot vectors cache",
+            MetricType::COUNTER,
+            vec![counter(self.misses as f64, &[])],
+            prefix,
+        ));
+        metrics.push_metric(metric_family(
+            "hot_vectors_cache_evictions",
+            "number of cold vectors, evicted from the hot vectors cache",
+            MetricType::COUNTER,
+            vec![counter(self.evictions as f64, &[])],
+            prefix,
+        ));
+        metrics.push_metric(metric_family(
+            "hot_vectors_cache_bytes",
+            "total size of vectors in the hot vectors cache",
+            MetricType::GAUGE,
+            vec![gauge(self.cached_bytes as f64, &[])],
+            prefix,
+        ));
+    }
+}
+
 impl HardwareTelemetry {
     // Helper function to create counter metrics of a single Hw type, like cpu.
     fn make_metric_counters<F: Fn(&HardwareUsage) -> usize>(&self, f: F) -> Vec<Metric> {
@@ -820,10 +887,74 @@ impl HardwareTelemetry {
             .map(|(collection_id, hw_usage)| counter(f(hw_usage) as f64, &[("id", collection_id)]))
             .collect()
     }
+
+    fn make_api_key_metric_counters<F: Fn(&HardwareUsage) -> usize>(&self, f: F) -> Vec<Metric> {
+        self.api_key_data
+            .iter()
+            .map(|(subject, hw_usage)| counter(f(hw_usage) as f64, &[("subject", subject)]))
+            .collect()
+    }
+
+    fn add_api_key_metrics(&self, metrics: &mut MetricsData, prefix: Option<&str>) {
+        // MetricType::COUNTER requires non-empty data.
+        if self.api_key_data.is_empty() {
+            return;
+        }
+
+        let families: [(&str, &str, fn(&HardwareUsage) -> usize); 7] = [
+            (
+                "api_key_hardware_metric_cpu",
+                "CPU measurements of an API key",
+                |hw| hw.cpu,
+            ),
+            (
+                "api_key_hardware_metric_payload_io_read",
+                "Total IO payload read metrics of an API key",
+                |hw| hw.payload_io_read,
+            ),
+            (
+                "api_key_hardware_metric_payload_index_io_read",
+                "Total IO payload index read metrics of an API key",
+                |hw| hw.payload_index_io_read,
+            ),
+            (
+                "api_key_hardware_metric_payload_index_io_write",
+                "Total IO payload index write metrics of an API key",
+                |hw| hw.payload_index_io_write,
+            ),
+            (
+                "api_key_hardware_metric_payload_io_write",
+                "Total IO payload write metrics of an API key",
+                |hw| hw.payload_io_write,
+            ),
+            (
+                "api_key_hardware_metric_vector_io_read",
+                "Total IO vector read metrics of an API key",
+                |hw| hw.vector_io_read,
+            ),
+            (
+                "api_key_hardware_metric_vector_io_write",
+                "Total IO vector write metrics of an API key",
+                |hw| hw.vector_io_write,
+            ),
+        ];
+
+        for (name, help, f) in families {
+            metrics.push_metric(metric_family(
+                name,
+                help,
+                MetricType::COUNTER,
+                self.make_api_key_metric_counters(f),
+                prefix,
+            ));
+        }
+    }
 }
 
 impl MetricsProvider for HardwareTelemetry {
     fn add_metrics(&self, metrics: &mut MetricsData, prefix: Option<&str>) {
+        self.add_api_key_metrics(metrics, prefix);
+
         // MetricType::COUNTER requires non-empty collection data.
         if self.collection_data.is_empty() {
             return;
@@ -1550,4 +1681,33 @@ mod tests {
             "Non-whitelisted endpoint should not appear:\n{output}"
         );
     }
+
+    #[test]
+    fn test_hardware_metrics_per_api_key() {
+        use std::collections::HashMap;
+
+        use api::rest::models::HardwareUsage;
+
+        use super::{HardwareTelemetry, MetricsData, MetricsProvider};
+
+        let telemetry = HardwareTelemetry {
+            collection_data: HashMap::new(),
+            api_key_data: HashMap::from([(
+                "alice".to_string(),
+                HardwareUsage {
+                    cpu: 5,
+                    ..Default::default()
+                },
+            )]),
+        };
+
+        let mut metrics = MetricsData::empty();
+        telemetry.add_metrics(&mut metrics, None);
+        let output = metrics.format_metrics();
+
+        // API key metrics are reported even without any collection data
+        assert!(output.contains("api_key_hardware_metric_cpu"));
+        assert!(output.contains("subject=\"alice\""));
+        assert!(!output.contains("collection_hardware_metric_cpu"));
+    }
 }
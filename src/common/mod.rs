@@ -1,20 +1,36 @@
 pub mod audit;
 pub mod auth;
+pub mod backup_import;
+pub mod bulk_import;
+pub mod bulk_upsert;
+pub mod client_identity;
 pub mod collections;
+pub mod dataset_export;
 pub mod debugger;
+pub mod embedding_cache;
 pub mod error_reporting;
+pub mod es_query;
 pub mod health;
 pub mod helpers;
 pub mod http_client;
 pub mod inference;
 pub mod metrics;
+pub mod pgvector_import;
+pub mod points_changes;
 pub mod pyroscope_state;
 pub mod query;
+pub mod request_id;
+pub mod reranker;
+pub mod rescore;
+pub mod scoring_hooks;
+pub mod secrets;
 pub mod snapshots;
 pub mod stacktrace;
 pub mod strict_mode;
 pub mod strings;
+pub mod subject_rate_limiter;
 pub mod telemetry;
 pub mod telemetry_ops;
 pub mod telemetry_reporting;
+pub mod time_travel;
 pub mod update;
@@ -0,0 +1,153 @@
+//! Stream of changes of points, committed to the shards of a collection.
+//!
+//! Changes are read from WAL of the shard replicas on this peer, so positions of the changes are
+//! specific to the peer. WAL keeps only a limited number of operations, so consumers which fall
+//! too far behind have to resynchronize, e.g. with scroll.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::grpc::qdrant::PointsChange;
+use collection::operations::verification::StrictModeVerification;
+use collection::shards::shard::ShardId;
+use futures::Stream;
+use segment::types::{Filter, SearchParams, SeqNumberType};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{AccessRequirements, Auth, CollectionPass};
+
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+
+/// Maximum number of operations read from a shard at once
+const CHANGES_BATCH_SIZE: usize = 128;
+
+/// How often shards are checked for new changes, once all committed changes are streamed
+const CHANGES_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Stream of changes, which is checked against strict mode of the collection once it is started
+struct ChangesRequest;
+
+impl StrictModeVerification for ChangesRequest {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+/// Stream changes of points in the collection.
+///
+/// `positions` are positions of the last processed changes by shard id, streaming continues
+/// right after them. Other shards are streamed starting from the new changes.
+pub async fn points_changes(
+    dispatcher: Arc<Dispatcher>,
+    collection_name: String,
+    positions: HashMap<ShardId, SeqNumberType>,
+    auth: Auth,
+) -> Result<impl Stream<Item = Result<PointsChange, StorageError>> + Send + 'static, StorageError> {
+    // Changes include whole operations, so access to the whole collection is required
+    let collection_pass = auth
+        .check_collection_access(
+            &collection_name,
+            AccessRequirements::new().extras(),
+            "points_changes",
+        )?
+        .into_static();
+
+    let toc = StrictModeCheckedTocProvider::new(&dispatcher)
+        .check_strict_mode(&ChangesRequest, &collection_name, None, &auth)
+        .await?
+        .clone();
+    let collection = toc.get_collection(&collection_pass).await?;
+
+    let local_shards = collection.get_local_shards().await;
+    if let Some(shard_id) = positions
+        .keys()
+        .find(|shard_id| !local_shards.contains(shard_id))
+    {
+        return Err(StorageError::bad_request(format!(
+            "Shard {shard_id} of collection {collection_name} has no replica on this peer",
+        )));
+    }
+
+    let positions = positions
+        .into_iter()
+        .map(|(shard_id, position)| (shard_id, Some(position + 1)))
+        .collect();
+
+    let mut state = ChangesState {
+        toc,
+        collection_pass,
+        positions,
+        pending: VecDeque::new(),
+    };
+
+    // Fix starting positions of the shards, which are streamed from the new changes
+    state.read_changes().await?;
+
+    Ok(futures::stream::try_unfold(
+        state,
+        ChangesState::next_change,
+    ))
+}
+
+struct ChangesState {
+    toc: Arc<TableOfContent>,
+    collection_pass: CollectionPass<'static>,
+    /// Position to read the next changes from by shard id, or `None` to start from new changes
+    positions: HashMap<ShardId, Option<SeqNumberType>>,
+    /// Changes, which are read, but not streamed yet
+    pending: VecDeque<PointsChange>,
+}
+
+impl ChangesState {
+    async fn next_change(mut self) -> Result<Option<(PointsChange, Self)>, StorageError> {
+        loop {
+            if let Some(change) = self.pending.pop_front() {
+                return Ok(Some((change, self)));
+            }
+
+            self.read_changes().await?;
+
+            if self.pending.is_empty() {
+                tokio::time::sleep(CHANGES_POLL_INTERVAL).await;
+            }
+        }
+    }
+
+    async fn read_changes(&mut self) -> Result<(), StorageError> {
+        let collection = self.toc.get_collection(&self.collection_pass).await?;
+
+        // Shards could be transferred to this peer, after the stream is started
+        for shard_id in collection.get_local_shards().await {
+            self.positions.entry(shard_id).or_insert(None);
+        }
+
+        for (&shard_id, position) in &mut self.positions {
+            let (changes, next) = collection
+                .read_shard_changes(shard_id, *position, CHANGES_BATCH_SIZE)
+                .await?;
+
+            *position = Some(next);
+            self.pending.extend(changes);
+        }
+
+        Ok(())
+    }
+}
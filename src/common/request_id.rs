@@ -0,0 +1,47 @@
+//! Per-request correlation IDs.
+//!
+//! Every request is handled within a `request` span, carrying its correlation ID. Log lines
+//! emitted while handling the request include the span, so they can be matched with the
+//! correlation ID returned to the client in the [`REQUEST_ID_HEADER`] response header.
+
+use storage::audit::MAX_TRACING_ID_LEN;
+use uuid::Uuid;
+
+/// Header with the correlation ID, both in requests and responses.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Correlation ID of a request.
+///
+/// Uses ID provided by the client in [`REQUEST_ID_HEADER`], or generates a new one.
+pub fn request_id(provided: Option<&str>) -> String {
+    match provided.map(str::trim) {
+        Some(id) if !id.is_empty() => id.chars().take(MAX_TRACING_ID_LEN).collect(),
+        _ => Uuid::new_v4().to_string(),
+    }
+}
+
+/// Span, within which a request with the given correlation ID is handled.
+///
+/// Created at `ERROR` level, so the correlation ID is attached to log lines of any level.
+pub fn request_span(request_id: &str) -> tracing::Span {
+    tracing::error_span!("request", request_id = %request_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id() {
+        assert_eq!(request_id(Some("abc-123")), "abc-123");
+
+        let generated = request_id(None);
+        assert!(Uuid::parse_str(&generated).is_ok());
+
+        let generated = request_id(Some("  "));
+        assert!(Uuid::parse_str(&generated).is_ok());
+
+        let long = "a".repeat(MAX_TRACING_ID_LEN * 2);
+        assert_eq!(request_id(Some(&long)).len(), MAX_TRACING_ID_LEN);
+    }
+}
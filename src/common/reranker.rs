@@ -0,0 +1,372 @@
+//! Reranking of query candidates by external services, e.g. cross-encoders.
+//!
+//! Candidates are sent to the service with their ids, original scores and selected payload
+//! fields, together with the query of the reranking stage. The service returns a new score for
+//! every candidate, in the same order. If the service fails or doesn't respond in time,
+//! the original order of the candidates is kept.
+//!
+//! HTTP services receive a JSON `POST` request:
+//! `{"query": ..., "candidates": [{"id": ..., "score": ..., "payload": {...}}]}`,
+//! and respond with `{"scores": [...]}`.
+//!
+//! gRPC services implement `qdrant.reranker.Reranker/Rerank` with messages
+//! `RerankRequest { string query = 1; repeated Candidate candidates = 2; }`,
+//! `Candidate { string id = 1; float score = 2; string payload = 3; }` and
+//! `RerankResponse { repeated float scores = 1; }`, where query and payload are JSON strings.
+
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use api::rest::schema::ScoredPoint;
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{ExtendedPointId, Payload, PayloadSelector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use storage::content_manager::errors::StorageError;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, ClientTlsConfig, Uri};
+use validator::Validate;
+
+use crate::common::inference::config::{RerankerConfig, RerankerProtocol};
+
+const GRPC_RERANK_PATH: &str = "/qdrant.reranker.Reranker/Rerank";
+
+/// Rerank query candidates with an external service, configured in `inference.rerankers`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct RerankerStage {
+    /// Name of the configured reranker
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// Query, which is sent to the reranker as is, e.g. text of the search query
+    pub query: Value,
+    /// Payload fields, which are sent with the candidates
+    #[serde(default)]
+    pub payload_fields: Vec<JsonPath>,
+    /// Number of candidates to rerank. Default: `limit` + `offset` of the query
+    #[serde(default)]
+    pub candidates: Option<NonZeroUsize>,
+}
+
+#[derive(Serialize)]
+struct RerankRequest<'a> {
+    query: &'a Value,
+    candidates: Vec<RerankCandidate>,
+}
+
+#[derive(Serialize)]
+struct RerankCandidate {
+    id: ExtendedPointId,
+    score: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<Payload>,
+}
+
+#[derive(Deserialize)]
+struct RerankResponse {
+    scores: Vec<f32>,
+}
+
+/// Messages of the gRPC reranking API
+mod grpc {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RerankRequest {
+        /// Query of the reranking stage, as JSON
+        #[prost(string, tag = "1")]
+        pub query: String,
+        #[prost(message, repeated, tag = "2")]
+        pub candidates: Vec<Candidate>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Candidate {
+        /// Point id, number or UUID
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(float, tag = "2")]
+        pub score: f32,
+        /// Selected payload fields, as JSON
+        #[prost(string, tag = "3")]
+        pub payload: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct RerankResponse {
+        #[prost(float, repeated, tag = "1")]
+        pub scores: Vec<f32>,
+    }
+}
+
+enum RerankerClient {
+    Http(reqwest::Client),
+    Grpc(Channel),
+}
+
+struct Reranker {
+    config: RerankerConfig,
+    client: RerankerClient,
+}
+
+/// Configured rerankers, by name
+#[derive(Default)]
+pub struct Rerankers {
+    rerankers: HashMap<String, Reranker>,
+}
+
+impl Rerankers {
+    pub fn from_config(configs: &[RerankerConfig]) -> Result<Self, StorageError> {
+        let mut rerankers = HashMap::with_capacity(configs.len());
+        for config in configs {
+            let timeout = Duration::from_millis(config.timeout_ms);
+            let client = match config.protocol {
+                RerankerProtocol::Http => RerankerClient::Http(
+                    reqwest::Client::builder()
+                        .timeout(timeout)
+                        .build()
+                        .map_err(|err| reranker_config_error(config, err))?,
+                ),
+                RerankerProtocol::Grpc => {
+                    let uri: Uri = config
+                        .address
+                        .parse()
+                        .map_err(|err| reranker_config_error(config, err))?;
+                    let mut endpoint = Channel::builder(uri.clone())
+                        .timeout(timeout)
+                        .connect_timeout(timeout);
+                    if uri.scheme_str() == Some("https") {
+                        endpoint = endpoint
+                            .tls_config(ClientTlsConfig::new())
+                            .map_err(|err| reranker_config_error(config, err))?;
+                    }
+                    RerankerClient::Grpc(endpoint.connect_lazy())
+                }
+            };
+
+            let reranker = Reranker {
+                config: config.clone(),
+                client,
+            };
+            if rerankers.insert(config.name.clone(), reranker).is_some() {
+                return Err(StorageError::service_error(format!(
+                    "Reranker {} is configured more than once",
+                    config.name,
+                )));
+            }
+        }
+
+        Ok(Self { rerankers })
+    }
+
+    /// Rerank points and order them by the new scores.
+    ///
+    /// If the reranker fails, the points are left as is.
+    pub async fn rerank(
+        &self,
+        stage: &RerankerStage,
+        points: &mut [ScoredPoint],
+    ) -> Result<(), StorageError> {
+        let reranker = self.rerankers.get(&stage.name).ok_or_else(|| {
+            StorageError::bad_input(format!("Reranker {} is not configured", stage.name))
+        })?;
+
+        if points.is_empty() {
+            return Ok(());
+        }
+
+        let selector = PayloadSelector::new_include(stage.payload_fields.clone());
+        let candidates = points
+            .iter()
+            .map(|point| RerankCandidate {
+                id: point.id,
+                score: point.score,
+                payload: match &point.payload {
+                    Some(payload) if !stage.payload_fields.is_empty() => {
+                        Some(selector.process(payload.clone()))
+                    }
+                    _ => None,
+                },
+            })
+            .collect();
+        let request = RerankRequest {
+            query: &stage.query,
+            candidates,
+        };
+
+        let scores = match reranker.request(request).await {
+            Ok(scores) if scores.len() == points.len() => scores,
+            Ok(scores) => {
+                log::warn!(
+                    "Reranker {} returned {} scores for {} candidates, keeping original order",
+                    stage.name,
+                    scores.len(),
+                    points.len(),
+                );
+                return Ok(());
+            }
+            Err(err) => {
+                log::warn!(
+                    "Reranker {} failed, keeping original order: {err}",
+                    stage.name,
+                );
+                return Ok(());
+            }
+        };
+
+        for (point, score) in points.iter_mut().zip(scores) {
+            point.score = score;
+        }
+        points.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(())
+    }
+}
+
+impl Reranker {
+    async fn request(&self, request: RerankRequest<'_>) -> Result<Vec<f32>, String> {
+        match &self.client {
+            RerankerClient::Http(client) => {
+                let mut builder = client.post(&self.config.address).json(&request);
+                if let Some(token) = &self.config.token {
+                    builder = builder.bearer_auth(token);
+                }
+                let response = builder
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|err| err.to_string())?;
+                let response: RerankResponse =
+                    response.json().await.map_err(|err| err.to_string())?;
+                Ok(response.scores)
+            }
+            RerankerClient::Grpc(channel) => {
+                let request = grpc::RerankRequest {
+                    query: request.query.to_string(),
+                    candidates: request
+                        .candidates
+                        .into_iter()
+                        .map(|candidate| grpc::Candidate {
+                            id: candidate.id.to_string(),
+                            score: candidate.score,
+                            payload: candidate
+                                .payload
+                                .map(|payload| Value::Object(payload.0).to_string())
+                                .unwrap_or_default(),
+                        })
+                        .collect(),
+                };
+
+                let mut request = tonic::Request::new(request);
+                if let Some(token) = &self.config.token {
+                    let value = format!("Bearer {token}")
+                        .parse()
+                        .map_err(|_| "Invalid reranker token".to_string())?;
+                    request.metadata_mut().insert("authorization", value);
+                }
+
+                let mut client = tonic::client::Grpc::new(channel.clone());
+                client.ready().await.map_err(|err| err.to_string())?;
+                let response = client
+                    .unary(
+                        request,
+                        PathAndQuery::from_static(GRPC_RERANK_PATH),
+                        tonic::codec::ProstCodec::<grpc::RerankRequest, grpc::RerankResponse>::default(),
+                    )
+                    .await
+                    .map_err(|status| status.to_string())?;
+                Ok(response.into_inner().scores)
+            }
+        }
+    }
+}
+
+fn reranker_config_error(config: &RerankerConfig, err: impl std::fmt::Display) -> StorageError {
+    StorageError::service_error(format!(
+        "Invalid configuration of reranker {}: {err}",
+        config.name,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn point(id: u64, score: f32) -> ScoredPoint {
+        ScoredPoint {
+            id: ExtendedPointId::NumId(id),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+        }
+    }
+
+    fn point_ids(points: &[ScoredPoint]) -> Vec<ExtendedPointId> {
+        points.iter().map(|point| point.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_http_reranker() {
+        let mut server = mockito::Server::new_async().await;
+        let rerankers = Rerankers::from_config(&[RerankerConfig {
+            name: "cross-encoder".to_string(),
+            address: format!("{}/rerank", server.url()),
+            protocol: RerankerProtocol::Http,
+            timeout_ms: 1000,
+            token: None,
+        }])
+        .unwrap();
+        let stage = RerankerStage {
+            name: "cross-encoder".to_string(),
+            query: json!("what is qdrant"),
+            payload_fields: vec![],
+            candidates: None,
+        };
+
+        let mock = server
+            .mock("POST", "/rerank")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "query": "what is qdrant",
+                "candidates": [{"id": 1, "score": 0.9}, {"id": 2, "score": 0.5}],
+            })))
+            .with_status(200)
+            .with_body(json!({"scores": [0.1, 0.7]}).to_string())
+            .create_async()
+            .await;
+
+        let mut points = vec![point(1, 0.9), point(2, 0.5)];
+        rerankers.rerank(&stage, &mut points).await.unwrap();
+        mock.assert_async().await;
+        assert_eq!(
+            point_ids(&points),
+            [ExtendedPointId::NumId(2), ExtendedPointId::NumId(1)],
+        );
+        assert_eq!(points[0].score, 0.7);
+
+        // Original order is kept, if the reranker fails
+        server.reset_async().await;
+        server
+            .mock("POST", "/rerank")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let mut points = vec![point(1, 0.9), point(2, 0.5)];
+        rerankers.rerank(&stage, &mut points).await.unwrap();
+        assert_eq!(
+            point_ids(&points),
+            [ExtendedPointId::NumId(1), ExtendedPointId::NumId(2)],
+        );
+        assert_eq!(points[0].score, 0.9);
+
+        // Unknown reranker is an error
+        let stage = RerankerStage {
+            name: "unknown".to_string(),
+            ..stage
+        };
+        assert!(rerankers.rerank(&stage, &mut points).await.is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! Rescoring of query results by user-defined logic or external rerankers, as a stage after
+//! the query.
+//!
+//! The query is performed with a larger limit to collect candidates, candidates are rescored
+//! and reordered, and the requested page of them is returned.
+
+use std::sync::Arc;
+
+use api::rest::{QueryRequest, QueryRequestInternal, ScoredPoint};
+use collection::operations::universal_query::collection_query::CollectionQueryRequest;
+use schemars::JsonSchema;
+use segment::types::{WithPayload, WithPayloadInterface};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use validator::Validate;
+
+use crate::common::reranker::{RerankerStage, Rerankers};
+use crate::common::scoring_hooks::{ScoringHookStage, ScoringHooks};
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct RescoreQueryRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub query: QueryRequest,
+    /// Stage, which rescores the candidates found by the query
+    #[validate(nested)]
+    pub rescore: RescoreStage,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum RescoreStage {
+    /// Rescore with a WASM scoring hook, registered for the collection
+    ScoringHook(ScoringHookStage),
+    /// Rerank with an external service, configured in `inference.rerankers`
+    Reranker(RerankerStage),
+}
+
+impl Validate for RescoreStage {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            RescoreStage::ScoringHook(stage) => stage.validate(),
+            RescoreStage::Reranker(stage) => stage.validate(),
+        }
+    }
+}
+
+/// Page of the rescored candidates, which is returned
+#[derive(Debug)]
+pub struct ResultPage {
+    offset: usize,
+    limit: usize,
+    with_payload: WithPayload,
+}
+
+impl RescoreStage {
+    /// Change the query to return all candidates for rescoring, with payload needed by the stage.
+    pub fn candidates_query(&self, query: &mut QueryRequestInternal) -> ResultPage {
+        let offset = query
+            .offset
+            .unwrap_or(CollectionQueryRequest::DEFAULT_OFFSET);
+        let limit = query.limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT);
+        let with_payload = query
+            .with_payload
+            .take()
+            .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD);
+
+        let (candidates, needs_payload) = match self {
+            RescoreStage::ScoringHook(stage) => (
+                stage.candidates.map(|candidates| candidates.get()),
+                !stage.payload_fields.is_empty(),
+            ),
+            RescoreStage::Reranker(stage) => (
+                stage.candidates.map(|candidates| candidates.get()),
+                !stage.payload_fields.is_empty(),
+            ),
+        };
+
+        query.offset = None;
+        query.limit = Some(candidates.unwrap_or(0).max(offset + limit));
+        query.with_payload = Some(if needs_payload {
+            WithPayloadInterface::Bool(true)
+        } else {
+            with_payload.clone()
+        });
+
+        ResultPage {
+            offset,
+            limit,
+            with_payload: WithPayload::from(with_payload),
+        }
+    }
+
+    /// Rescore candidates, and return the requested page of them
+    pub async fn rescore(
+        self,
+        collection_name: String,
+        mut points: Vec<ScoredPoint>,
+        page: ResultPage,
+        scoring_hooks: Arc<ScoringHooks>,
+        rerankers: &Rerankers,
+    ) -> Result<Vec<ScoredPoint>, StorageError> {
+        match self {
+            RescoreStage::ScoringHook(stage) => {
+                points = tokio::task::spawn_blocking(move || {
+                    scoring_hooks.rescore(&collection_name, &stage, &mut points)?;
+                    Ok::<_, StorageError>(points)
+                })
+                .await??;
+            }
+            RescoreStage::Reranker(stage) => rerankers.rerank(&stage, &mut points).await?,
+        }
+
+        let ResultPage {
+            offset,
+            limit,
+            with_payload,
+        } = page;
+
+        let points = points
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|mut point| {
+                point.payload = match (&with_payload, point.payload) {
+                    (WithPayload { enable: false, .. }, _) => None,
+                    (
+                        WithPayload {
+                            payload_selector: Some(selector),
+                            ..
+                        },
+                        Some(payload),
+                    ) => Some(selector.process(payload)),
+                    (_, payload) => payload,
+                };
+                point
+            })
+            .collect();
+
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::JsonPath;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_candidates_query() {
+        let stage: RescoreStage = serde_json::from_value(json!({
+            "scoring_hook": {"name": "boost", "payload_fields": ["price"], "candidates": 50}
+        }))
+        .unwrap();
+
+        let mut query: QueryRequestInternal = serde_json::from_value(json!({
+            "query": [0.1, 0.2],
+            "limit": 5,
+            "offset": 10,
+            "with_payload": ["title"],
+        }))
+        .unwrap();
+
+        let page = stage.candidates_query(&mut query);
+        assert_eq!(query.limit, Some(50));
+        assert_eq!(query.offset, None);
+        assert_eq!(query.with_payload, Some(WithPayloadInterface::Bool(true)));
+        assert_eq!((page.offset, page.limit), (10, 5));
+        assert_eq!(
+            page.with_payload,
+            WithPayload::from(WithPayloadInterface::Fields(vec![JsonPath::new("title")])),
+        );
+
+        // Page, which is deeper than the number of candidates, is still filled
+        let stage: RescoreStage =
+            serde_json::from_value(json!({"scoring_hook": {"name": "boost", "candidates": 8}}))
+                .unwrap();
+        let mut query: QueryRequestInternal =
+            serde_json::from_value(json!({"query": [0.1, 0.2], "offset": 4})).unwrap();
+        stage.candidates_query(&mut query);
+        assert_eq!(query.limit, Some(14));
+        assert_eq!(query.with_payload, Some(WithPayloadInterface::Bool(false)));
+    }
+}
@@ -0,0 +1,445 @@
+//! User-defined scoring hooks, WASM modules which rescore query candidates.
+//!
+//! Modules are registered per collection and stored on the node, which received the
+//! registration, in `{storage_path}/scoring_hooks/{collection}/{hook}.wasm`. In a cluster,
+//! a hook has to be registered on every node, which serves queries with it.
+//!
+//! A module must not import anything, and must export:
+//!
+//! * `memory` - linear memory of the module
+//! * `alloc(len: i32) -> i32` - allocate `len` bytes for the input, return pointer to them
+//! * `score(ptr: i32, len: i32) -> f32` - score a candidate
+//!
+//! Input of `score` is a JSON object `{"id": ..., "score": ..., "payload": {...}, "params": ...}`
+//! with the original score of the candidate, selected payload fields and parameters of the query.
+//! Candidates are ordered by the returned scores, higher is better.
+//!
+//! Execution is sandboxed: memory of the module and number of executed instructions are limited.
+
+#[cfg(feature = "wasm")]
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use api::rest::schema::ScoredPoint;
+use bytes::Bytes;
+use collection::operations::verification::StrictModeVerification;
+#[cfg(feature = "wasm")]
+use parking_lot::RwLock;
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use segment::types::{Filter, Payload, PayloadSelector, SearchParams};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{AccessRequirements, Auth};
+use validator::{Validate, ValidationError};
+
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+
+const SCORING_HOOKS_DIR: &str = "scoring_hooks";
+
+const MAX_HOOK_NAME_LENGTH: usize = 64;
+
+/// Maximum size of the linear memory of a module
+#[cfg(feature = "wasm")]
+const MAX_MEMORY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Number of WASM instructions, which may be executed per scored candidate
+#[cfg(feature = "wasm")]
+const FUEL_PER_CANDIDATE: u64 = 10_000_000;
+
+/// Rescore query candidates with a registered scoring hook
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ScoringHookStage {
+    /// Name of the scoring hook, registered for the collection
+    #[validate(custom(function = validate_hook_name))]
+    pub name: String,
+    /// Payload fields, which are passed to the hook
+    #[serde(default)]
+    pub payload_fields: Vec<JsonPath>,
+    /// Parameters, which are passed to the hook as is
+    #[serde(default)]
+    pub params: Option<Value>,
+    /// Number of candidates to rescore. Default: `limit` + `offset` of the query
+    #[serde(default)]
+    pub candidates: Option<NonZeroUsize>,
+}
+
+/// Input of the `score` function of a scoring hook
+#[derive(Serialize)]
+struct ScoreInput<'a> {
+    id: segment::types::ExtendedPointId,
+    score: f32,
+    payload: Option<Payload>,
+    params: &'a Option<Value>,
+}
+
+/// Registry of scoring hooks of all collections, with compiled modules cached in memory
+pub struct ScoringHooks {
+    path: PathBuf,
+    #[cfg(feature = "wasm")]
+    engine: wasmtime::Engine,
+    #[cfg(feature = "wasm")]
+    modules: RwLock<HashMap<(String, String), wasmtime::Module>>,
+}
+
+impl ScoringHooks {
+    pub fn new(storage_path: &Path) -> Result<Self, StorageError> {
+        #[cfg(feature = "wasm")]
+        let engine = {
+            let mut config = wasmtime::Config::new();
+            config.consume_fuel(true);
+            wasmtime::Engine::new(&config).map_err(|err| {
+                StorageError::service_error(format!("Failed to initialize WASM engine: {err}"))
+            })?
+        };
+
+        Ok(Self {
+            path: storage_path.join(SCORING_HOOKS_DIR),
+            #[cfg(feature = "wasm")]
+            engine,
+            #[cfg(feature = "wasm")]
+            modules: RwLock::new(HashMap::new()),
+        })
+    }
+
+    fn module_path(&self, collection_name: &str, hook_name: &str) -> PathBuf {
+        self.path
+            .join(collection_name)
+            .join(format!("{hook_name}.wasm"))
+    }
+
+    /// Compile and store the module of a scoring hook, replacing the previous one.
+    ///
+    /// Blocking: compilation of large modules takes a while.
+    pub fn register(
+        &self,
+        collection_name: &str,
+        hook_name: &str,
+        module: &[u8],
+    ) -> Result<(), StorageError> {
+        validate_hook_name(hook_name)
+            .map_err(|err| StorageError::bad_input(format!("Invalid scoring hook name: {err}")))?;
+
+        #[cfg(not(feature = "wasm"))]
+        {
+            let _ = (collection_name, module);
+            Err(no_wasm_support())
+        }
+
+        #[cfg(feature = "wasm")]
+        {
+            let compiled = wasm::compile(&self.engine, module)?;
+
+            let path = self.module_path(collection_name, hook_name);
+            let dir = path.parent().expect("module path has parent");
+            fs_err::create_dir_all(dir)?;
+            let temp_path = path.with_extension("wasm.tmp");
+            fs_err::write(&temp_path, module)?;
+            fs_err::rename(&temp_path, &path)?;
+
+            self.modules.write().insert(
+                (collection_name.to_string(), hook_name.to_string()),
+                compiled,
+            );
+            Ok(())
+        }
+    }
+
+    /// Remove a scoring hook. Returns `false` if there was no such hook.
+    pub fn remove(&self, collection_name: &str, hook_name: &str) -> Result<bool, StorageError> {
+        validate_hook_name(hook_name)
+            .map_err(|err| StorageError::bad_input(format!("Invalid scoring hook name: {err}")))?;
+
+        #[cfg(feature = "wasm")]
+        self.modules
+            .write()
+            .remove(&(collection_name.to_string(), hook_name.to_string()));
+
+        let path = self.module_path(collection_name, hook_name);
+        match fs_err::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Rescore points with the scoring hook and order them by the new scores.
+    ///
+    /// Blocking: runs the module for every point.
+    pub fn rescore(
+        &self,
+        collection_name: &str,
+        stage: &ScoringHookStage,
+        points: &mut [ScoredPoint],
+    ) -> Result<(), StorageError> {
+        let selector = PayloadSelector::new_include(stage.payload_fields.clone());
+        let inputs = points
+            .iter()
+            .map(|point| {
+                let payload = match &point.payload {
+                    Some(payload) if !stage.payload_fields.is_empty() => {
+                        Some(selector.process(payload.clone()))
+                    }
+                    _ => None,
+                };
+                serde_json::to_vec(&ScoreInput {
+                    id: point.id,
+                    score: point.score,
+                    payload,
+                    params: &stage.params,
+                })
+                .map_err(|err| StorageError::service_error(err.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        #[cfg(not(feature = "wasm"))]
+        {
+            let _ = (collection_name, inputs);
+            Err(no_wasm_support())
+        }
+
+        #[cfg(feature = "wasm")]
+        {
+            let module = self.module(collection_name, &stage.name)?;
+            let scores = wasm::score(&self.engine, &module, &inputs)?;
+
+            for (point, score) in points.iter_mut().zip(scores) {
+                point.score = score;
+            }
+            points.sort_by(|a, b| b.score.total_cmp(&a.score));
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    fn module(
+        &self,
+        collection_name: &str,
+        hook_name: &str,
+    ) -> Result<wasmtime::Module, StorageError> {
+        let key = (collection_name.to_string(), hook_name.to_string());
+        if let Some(module) = self.modules.read().get(&key) {
+            return Ok(module.clone());
+        }
+
+        let path = self.module_path(collection_name, hook_name);
+        let bytes = match fs_err::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound {
+                    description: format!(
+                        "Scoring hook {hook_name} is not registered for collection {collection_name}",
+                    ),
+                });
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let module = wasm::compile(&self.engine, &bytes)?;
+        self.modules.write().insert(key, module.clone());
+        Ok(module)
+    }
+}
+
+/// Registration of a scoring hook, which is checked against strict mode of the collection
+struct RegisterScoringHook;
+
+impl StrictModeVerification for RegisterScoringHook {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+/// Register a scoring hook for an existing collection
+pub async fn do_register_scoring_hook(
+    dispatcher: &Dispatcher,
+    scoring_hooks: Arc<ScoringHooks>,
+    collection_name: String,
+    hook_name: String,
+    module: Bytes,
+    auth: Auth,
+) -> Result<bool, StorageError> {
+    let collection_pass = auth.check_collection_access(
+        &collection_name,
+        AccessRequirements::new().write().manage(),
+        "register_scoring_hook",
+    )?;
+    StrictModeCheckedTocProvider::new(dispatcher)
+        .check_strict_mode(&RegisterScoringHook, &collection_name, None, &auth)
+        .await?
+        .get_collection(&collection_pass)
+        .await?;
+
+    tokio::task::spawn_blocking(move || {
+        scoring_hooks.register(&collection_name, &hook_name, &module)
+    })
+    .await??;
+
+    Ok(true)
+}
+
+/// Remove a scoring hook of a collection
+pub fn do_remove_scoring_hook(
+    scoring_hooks: &ScoringHooks,
+    collection_name: &str,
+    hook_name: &str,
+    auth: Auth,
+) -> Result<bool, StorageError> {
+    auth.check_collection_access(
+        collection_name,
+        AccessRequirements::new().write().manage(),
+        "remove_scoring_hook",
+    )?;
+
+    scoring_hooks.remove(collection_name, hook_name)
+}
+
+#[cfg(not(feature = "wasm"))]
+fn no_wasm_support() -> StorageError {
+    StorageError::bad_request(
+        "Scoring hooks are not supported, Qdrant is built without `wasm` feature",
+    )
+}
+
+fn validate_hook_name(name: &str) -> Result<(), ValidationError> {
+    let is_valid = !name.is_empty()
+        && name.len() <= MAX_HOOK_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("scoring_hook_name").with_message(
+            format!(
+                "must be 1 to {MAX_HOOK_NAME_LENGTH} characters long, \
+                 and contain only letters, digits, '_' and '-'",
+            )
+            .into(),
+        ))
+    }
+}
+
+#[cfg(feature = "wasm")]
+mod wasm {
+    use storage::content_manager::errors::StorageError;
+    use wasmtime::{Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+    use super::{FUEL_PER_CANDIDATE, MAX_MEMORY_BYTES};
+
+    pub fn compile(engine: &Engine, bytes: &[u8]) -> Result<Module, StorageError> {
+        let module = Module::new(engine, bytes)
+            .map_err(|err| StorageError::bad_input(format!("Invalid WASM module: {err:#}")))?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(StorageError::bad_input(format!(
+                "Scoring hook must not import anything, but imports {}::{}",
+                import.module(),
+                import.name(),
+            )));
+        }
+
+        for (name, expected) in [("memory", "memory"), ("alloc", "func"), ("score", "func")] {
+            let export = module.get_export(name);
+            let kind = match &export {
+                Some(wasmtime::ExternType::Memory(_)) => "memory",
+                Some(wasmtime::ExternType::Func(_)) => "func",
+                Some(_) => "other",
+                None => {
+                    return Err(StorageError::bad_input(format!(
+                        "Scoring hook must export {expected} `{name}`",
+                    )));
+                }
+            };
+            if kind != expected {
+                return Err(StorageError::bad_input(format!(
+                    "Export `{name}` of scoring hook must be {expected}",
+                )));
+            }
+        }
+
+        Ok(module)
+    }
+
+    /// Call `score` of a new instance of the module for every input
+    pub fn score(
+        engine: &Engine,
+        module: &Module,
+        inputs: &[Vec<u8>],
+    ) -> Result<Vec<f32>, StorageError> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .instances(1)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(engine, limits);
+        store.limiter(|limits| limits);
+        store
+            .set_fuel(FUEL_PER_CANDIDATE.saturating_mul(inputs.len().max(1) as u64))
+            .map_err(|err| StorageError::service_error(err.to_string()))?;
+
+        let instance = Instance::new(&mut store, module, &[]).map_err(hook_error)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| StorageError::bad_request("Scoring hook does not export memory"))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "alloc")
+            .map_err(hook_error)?;
+        let score = instance
+            .get_typed_func::<(i32, i32), f32>(&mut store, "score")
+            .map_err(hook_error)?;
+
+        let mut scores = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let len = i32::try_from(input.len())
+                .map_err(|_| StorageError::bad_request("Scoring hook input is too large"))?;
+            let ptr = alloc.call(&mut store, len).map_err(hook_error)?;
+            memory
+                .write(&mut store, ptr as u32 as usize, input)
+                .map_err(|err| {
+                    StorageError::bad_request(format!(
+                        "Scoring hook allocated invalid memory: {err}"
+                    ))
+                })?;
+            scores.push(score.call(&mut store, (ptr, len)).map_err(hook_error)?);
+        }
+
+        Ok(scores)
+    }
+
+    fn hook_error(err: wasmtime::Error) -> StorageError {
+        StorageError::bad_request(format!("Scoring hook failed: {err:#}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_hook_name() {
+        assert!(validate_hook_name("boost_recent-v2").is_ok());
+        assert!(validate_hook_name("").is_err());
+        assert!(validate_hook_name("../hook").is_err());
+        assert!(validate_hook_name(&"a".repeat(MAX_HOOK_NAME_LENGTH + 1)).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+//! API keys, sourced from external secret providers.
+//!
+//! Secrets are fetched on startup and refreshed periodically, so credentials can be rotated
+//! without restarting the node.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use collection::shards::channel_service::ChannelService;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::common::auth::AuthKeys;
+use crate::settings::ServiceConfig;
+
+const VAULT_TOKEN_ENV: &str = "VAULT_TOKEN";
+const VAULT_TOKEN_HEADER: &str = "X-Vault-Token";
+
+const fn default_refresh_interval_sec() -> u64 {
+    60
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+/// Sources of the API keys, which take precedence over values in the config
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct SecretsConfig {
+    /// How often secrets are fetched again, to pick up rotated values
+    #[serde(default = "default_refresh_interval_sec")]
+    #[validate(range(min = 1))]
+    pub refresh_interval_sec: u64,
+    /// Source of `service.api_key`, which is also the secret of JWT
+    #[serde(default)]
+    pub api_key: Option<SecretSource>,
+    /// Source of `service.alt_api_key`
+    #[serde(default)]
+    pub alt_api_key: Option<SecretSource>,
+    /// Source of `service.read_only_api_key`
+    #[serde(default)]
+    pub read_only_api_key: Option<SecretSource>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretSource {
+    /// Content of a file, without surrounding whitespace
+    File(PathBuf),
+    /// Environment variable
+    Env(String),
+    /// Field of a secret in the KV version 2 engine of HashiCorp Vault
+    Vault(VaultSecret),
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VaultSecret {
+    /// Address of Vault, e.g. `https://vault.example.com:8200`
+    pub address: String,
+    /// Mount path of the KV engine
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+    /// Path of the secret in the engine
+    pub path: String,
+    /// Field of the secret, which holds the value
+    pub field: String,
+    /// File with Vault token, read on every refresh, so it can be renewed by an agent.
+    /// If not set, token is taken from `VAULT_TOKEN` environment variable
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretError {
+    #[error("Failed to read secret from file {path:?}: {source}")]
+    File {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Environment variable {0} with secret is not set")]
+    Env(String),
+    #[error("Failed to fetch secret {path} from Vault: {description}")]
+    Vault { path: String, description: String },
+}
+
+/// Values of the API keys
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApiKeys {
+    pub api_key: Option<String>,
+    pub alt_api_key: Option<String>,
+    pub read_only_api_key: Option<String>,
+}
+
+impl ApiKeys {
+    pub fn from_service_config(service_config: &ServiceConfig) -> Self {
+        Self {
+            api_key: service_config.api_key.clone(),
+            alt_api_key: service_config.alt_api_key.clone(),
+            read_only_api_key: service_config.read_only_api_key.clone(),
+        }
+    }
+
+    pub fn apply_to(&self, service_config: &mut ServiceConfig) {
+        service_config.api_key = self.api_key.clone();
+        service_config.alt_api_key = self.alt_api_key.clone();
+        service_config.read_only_api_key = self.read_only_api_key.clone();
+    }
+}
+
+/// Fetches the API keys from their sources
+pub struct SecretsProvider {
+    config: SecretsConfig,
+    /// Keys from the config, used for keys without external source
+    config_keys: ApiKeys,
+    client: reqwest::Client,
+}
+
+impl SecretsProvider {
+    pub fn new(config: SecretsConfig, service_config: &ServiceConfig) -> Self {
+        Self {
+            config,
+            config_keys: ApiKeys::from_service_config(service_config),
+            // Secrets are fetched rarely, and the first fetch is done by a short-lived runtime,
+            // so connections are not kept for reuse
+            client: reqwest::Client::builder()
+                .pool_max_idle_per_host(0)
+                .build()
+                .expect("Failed to create HTTP client for secret providers"),
+        }
+    }
+
+    pub async fn fetch(&self) -> Result<ApiKeys, SecretError> {
+        let SecretsConfig {
+            refresh_interval_sec: _,
+            api_key,
+            alt_api_key,
+            read_only_api_key,
+        } = &self.config;
+
+        Ok(ApiKeys {
+            api_key: self.fetch_or(api_key, &self.config_keys.api_key).await?,
+            alt_api_key: self
+                .fetch_or(alt_api_key, &self.config_keys.alt_api_key)
+                .await?,
+            read_only_api_key: self
+                .fetch_or(read_only_api_key, &self.config_keys.read_only_api_key)
+                .await?,
+        })
+    }
+
+    async fn fetch_or(
+        &self,
+        source: &Option<SecretSource>,
+        config_value: &Option<String>,
+    ) -> Result<Option<String>, SecretError> {
+        match source {
+            Some(source) => self.fetch_secret(source).await.map(Some),
+            None => Ok(config_value.clone()),
+        }
+    }
+
+    async fn fetch_secret(&self, source: &SecretSource) -> Result<String, SecretError> {
+        match source {
+            SecretSource::File(path) => read_secret_file(path),
+            SecretSource::Env(name) => {
+                std::env::var(name).map_err(|_| SecretError::Env(name.clone()))
+            }
+            SecretSource::Vault(secret) => self.fetch_vault_secret(secret).await,
+        }
+    }
+
+    async fn fetch_vault_secret(&self, secret: &VaultSecret) -> Result<String, SecretError> {
+        let VaultSecret {
+            address,
+            mount,
+            path,
+            field,
+            token_file,
+        } = secret;
+
+        let vault_error = |description: String| SecretError::Vault {
+            path: path.clone(),
+            description,
+        };
+
+        let token = match token_file {
+            Some(token_file) => read_secret_file(token_file)?,
+            None => std::env::var(VAULT_TOKEN_ENV)
+                .map_err(|_| SecretError::Env(VAULT_TOKEN_ENV.to_string()))?,
+        };
+
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            address.trim_end_matches('/'),
+            mount.trim_matches('/'),
+            path.trim_start_matches('/'),
+        );
+
+        let response = self
+            .client
+            .get(url)
+            .header(VAULT_TOKEN_HEADER, token)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| vault_error(err.to_string()))?;
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|err| vault_error(err.to_string()))?;
+
+        // KV version 2 engine wraps secret data into `data.data`
+        body.pointer("/data/data")
+            .and_then(|data| data.get(field))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| vault_error(format!("secret has no string field {field:?}")))
+    }
+
+    /// Periodically fetch the keys, and apply changed ones to authentication and to requests
+    /// to other peers.
+    ///
+    /// Errors are logged, and the current keys are kept until the next successful fetch.
+    pub async fn run_refresh(
+        self,
+        mut current: ApiKeys,
+        auth_keys: Option<AuthKeys>,
+        channel_service: ChannelService,
+    ) {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.refresh_interval_sec));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        // First tick completes immediately, keys were just fetched on startup
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let keys = match self.fetch().await {
+                Ok(keys) => keys,
+                Err(err) => {
+                    log::warn!("Failed to refresh API keys, keeping the current ones: {err}");
+                    continue;
+                }
+            };
+
+            if keys == current {
+                continue;
+            }
+
+            if let Some(auth_keys) = &auth_keys {
+                auth_keys.update_keys(&keys);
+            }
+            channel_service.set_local_api_keys(keys.api_key.clone(), keys.alt_api_key.clone());
+
+            log::info!("API keys are updated from secret providers");
+            current = keys;
+        }
+    }
+}
+
+fn read_secret_file(path: &Path) -> Result<String, SecretError> {
+    fs_err::read_to_string(path)
+        .map(|secret| secret.trim().to_string())
+        .map_err(|source| SecretError::File {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fetch_from_file() {
+        let dir = tempfile::Builder::new()
+            .prefix("secrets")
+            .tempdir()
+            .unwrap();
+        let key_path = dir.path().join("api_key");
+        fs_err::write(&key_path, "first_key\n").unwrap();
+
+        let config: SecretsConfig = serde_json::from_value(serde_json::json!({
+            "api_key": { "file": key_path },
+        }))
+        .unwrap();
+
+        let mut service_config = crate::Settings::new(None)
+            .expect("Can't read config.")
+            .service;
+        service_config.read_only_api_key = Some("read_only_key".to_string());
+        let provider = SecretsProvider::new(config, &service_config);
+
+        let keys = provider.fetch().await.unwrap();
+        assert_eq!(keys.api_key.as_deref(), Some("first_key"));
+        assert_eq!(keys.read_only_api_key.as_deref(), Some("read_only_key"));
+
+        // Rotated key is picked up on the next fetch
+        fs_err::write(&key_path, "second_key").unwrap();
+        let keys = provider.fetch().await.unwrap();
+        assert_eq!(keys.api_key.as_deref(), Some("second_key"));
+
+        fs_err::remove_file(&key_path).unwrap();
+        assert!(provider.fetch().await.is_err());
+    }
+}
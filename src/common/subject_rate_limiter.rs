@@ -0,0 +1,366 @@
+//! Rate limiting of requests by authenticated subject: API key or JWT subject.
+//!
+//! Every subject has its own budget of requests per second, and a separate budget of expensive
+//! requests, such as snapshot operations, so one client can't exhaust a shared cluster.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use common::rate_limiting::{RateLimitError, RateLimiter};
+use parking_lot::Mutex;
+use storage::content_manager::errors::StorageError;
+
+use crate::settings::{RateLimitConfig, SubjectRateLimitsConfig};
+
+pub const HEADER_RATE_LIMIT_LIMIT: &str = "x-ratelimit-limit";
+pub const HEADER_RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+
+/// Minimal interval between removals of idle subjects
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Budget, which a request is counted against
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestCost {
+    /// Counted against the requests per second budget only
+    Regular,
+    /// Counted against both requests per second and expensive requests budgets
+    Expensive,
+}
+
+impl RequestCost {
+    pub fn of_rest_request(method: &str, path: &str) -> Self {
+        if method == "GET" || method == "HEAD" {
+            return Self::Regular;
+        }
+
+        let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+        let is_expensive = match segments.as_slice() {
+            // Snapshot creation, recovery, upload and deletion
+            segments if segments.contains(&"snapshots") => true,
+            // Collection creation, update and deletion
+            ["collections", name] => *name != "aliases",
+            // Payload index creation and deletion
+            ["collections", _, "index", ..] => true,
+            // Shard key and cluster setup changes
+            ["collections", _, "shards" | "cluster", ..] => true,
+            _ => false,
+        };
+
+        if is_expensive {
+            Self::Expensive
+        } else {
+            Self::Regular
+        }
+    }
+
+    pub fn of_grpc_request(path: &str) -> Self {
+        let Some((service, method)) = path.trim_start_matches('/').split_once('/') else {
+            return Self::Regular;
+        };
+
+        let is_expensive = match service {
+            "qdrant.Snapshots" => !method.starts_with("List"),
+            "qdrant.Collections" => matches!(
+                method,
+                "Create"
+                    | "Update"
+                    | "Delete"
+                    | "UpdateCollectionClusterSetup"
+                    | "CreateShardKey"
+                    | "DeleteShardKey",
+            ),
+            "qdrant.Points" => matches!(method, "CreateFieldIndex" | "DeleteFieldIndex"),
+            _ => false,
+        };
+
+        if is_expensive {
+            Self::Expensive
+        } else {
+            Self::Regular
+        }
+    }
+}
+
+/// Remaining requests budget of a subject, reported to the client in response headers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+}
+
+struct SubjectBuckets {
+    requests: Option<RateLimiter>,
+    expensive: Option<RateLimiter>,
+}
+
+impl SubjectBuckets {
+    fn new(limits: &RateLimitConfig) -> Self {
+        Self {
+            requests: limits
+                .requests_per_sec
+                .map(|limit| RateLimiter::new_per_second(limit.get())),
+            expensive: limits
+                .expensive_requests_per_min
+                .map(|limit| RateLimiter::new_per_minute(limit.get())),
+        }
+    }
+
+    /// All budgets are refilled, so the subject can be forgotten until its next request
+    fn is_idle(&self) -> bool {
+        [&self.requests, &self.expensive]
+            .into_iter()
+            .flatten()
+            .all(RateLimiter::is_full)
+    }
+}
+
+/// Rate limiter, shared by REST and gRPC APIs
+pub struct SubjectRateLimiter {
+    config: SubjectRateLimitsConfig,
+    buckets: Mutex<HashMap<String, SubjectBuckets>>,
+    /// Last time idle subjects were removed from `buckets`
+    last_cleanup: Mutex<Instant>,
+    cleanup_interval: Duration,
+}
+
+impl SubjectRateLimiter {
+    pub fn new(config: SubjectRateLimitsConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            last_cleanup: Mutex::new(Instant::now()),
+            cleanup_interval: CLEANUP_INTERVAL,
+        }
+    }
+
+    /// Count a request of the subject against its budgets.
+    ///
+    /// Returns the remaining requests budget, if the subject has one.
+    pub fn check(
+        &self,
+        subject: &str,
+        cost: RequestCost,
+    ) -> Result<Option<RateLimitStatus>, StorageError> {
+        let Some(limits) = self
+            .config
+            .subjects
+            .get(subject)
+            .or(self.config.default.as_ref())
+        else {
+            return Ok(None);
+        };
+
+        let mut buckets = self.buckets.lock();
+        self.remove_idle_subjects(&mut buckets);
+
+        let buckets = buckets
+            .entry(subject.to_string())
+            .or_insert_with(|| SubjectBuckets::new(limits));
+
+        let mut expensive = match &mut buckets.expensive {
+            Some(expensive) if cost == RequestCost::Expensive => Some(expensive),
+            _ => None,
+        };
+
+        // Check all budgets first, so a rejected request is not counted against any of them
+        if let Some(requests) = &mut buckets.requests {
+            requests
+                .check(1.0)
+                .map_err(|err| rate_limit_error(err, "Requests"))?;
+        }
+        if let Some(expensive) = &mut expensive {
+            expensive
+                .check(1.0)
+                .map_err(|err| rate_limit_error(err, "Expensive requests"))?;
+        }
+
+        if let Some(expensive) = expensive {
+            expensive
+                .try_consume(1.0)
+                .map_err(|err| rate_limit_error(err, "Expensive requests"))?;
+        }
+
+        let status = match &mut buckets.requests {
+            Some(requests) => {
+                requests
+                    .try_consume(1.0)
+                    .map_err(|err| rate_limit_error(err, "Requests"))?;
+                Some(RateLimitStatus {
+                    limit: requests.capacity(),
+                    remaining: requests.tokens_available().floor() as u64,
+                })
+            }
+            None => None,
+        };
+
+        Ok(status)
+    }
+
+    /// Forget subjects with fully refilled budgets, so the map doesn't grow with every subject
+    /// ever seen. Buckets of such subjects are equivalent to new ones.
+    fn remove_idle_subjects(&self, buckets: &mut HashMap<String, SubjectBuckets>) {
+        let mut last_cleanup = self.last_cleanup.lock();
+        if last_cleanup.elapsed() < self.cleanup_interval {
+            return;
+        }
+        *last_cleanup = Instant::now();
+
+        buckets.retain(|_, buckets| !buckets.is_idle());
+    }
+}
+
+fn rate_limit_error(err: RateLimitError, budget: &str) -> StorageError {
+    match err {
+        RateLimitError::AlwaysOverBudget(msg) => {
+            StorageError::rate_limit_exceeded(format!("{budget} rate limit exceeded: {msg}"), None)
+        }
+        RateLimitError::Retry(retry) => StorageError::rate_limit_exceeded(
+            format!("{budget} rate limit exceeded, retry later"),
+            Some(retry.retry_after),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    fn limits(requests_per_sec: usize, expensive_requests_per_min: usize) -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_sec: NonZeroUsize::new(requests_per_sec),
+            expensive_requests_per_min: NonZeroUsize::new(expensive_requests_per_min),
+        }
+    }
+
+    #[test]
+    fn test_subject_budgets() {
+        let limiter = SubjectRateLimiter::new(SubjectRateLimitsConfig {
+            default: Some(limits(2, 1)),
+            subjects: HashMap::from([("unlimited".to_string(), limits(0, 0))]),
+        });
+
+        let status = limiter.check("alice", RequestCost::Expensive).unwrap();
+        assert_eq!(
+            status,
+            Some(RateLimitStatus {
+                limit: 2,
+                remaining: 1,
+            }),
+        );
+
+        // Expensive budget is exhausted
+        let err = limiter.check("alice", RequestCost::Expensive).unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::RateLimitExceeded {
+                retry_after: Some(_),
+                ..
+            },
+        ));
+
+        // Rejected expensive request is not counted against the requests budget
+        let status = limiter.check("alice", RequestCost::Regular).unwrap();
+        assert_eq!(
+            status,
+            Some(RateLimitStatus {
+                limit: 2,
+                remaining: 0,
+            }),
+        );
+        assert!(limiter.check("alice", RequestCost::Regular).is_err());
+
+        // Budgets are tracked per subject
+        assert!(limiter.check("bob", RequestCost::Regular).is_ok());
+
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.check("unlimited", RequestCost::Expensive).unwrap(),
+                None,
+            );
+        }
+    }
+
+    #[test]
+    fn test_remove_idle_subjects() {
+        let mut limiter = SubjectRateLimiter::new(SubjectRateLimitsConfig {
+            default: Some(limits(1000, 1)),
+            subjects: HashMap::new(),
+        });
+        limiter.cleanup_interval = Duration::ZERO;
+
+        limiter.check("alice", RequestCost::Regular).unwrap();
+        limiter.check("bob", RequestCost::Expensive).unwrap();
+        assert_eq!(limiter.buckets.lock().len(), 2);
+
+        // Requests budget refills in a millisecond, expensive requests budget in a minute
+        std::thread::sleep(Duration::from_millis(10));
+        limiter.check("carol", RequestCost::Regular).unwrap();
+
+        let buckets = limiter.buckets.lock();
+        assert!(!buckets.contains_key("alice"));
+        assert!(buckets.contains_key("bob"));
+        assert!(buckets.contains_key("carol"));
+    }
+
+    #[test]
+    fn test_request_cost() {
+        use RequestCost::*;
+
+        assert_eq!(
+            RequestCost::of_rest_request("GET", "/collections/c"),
+            Regular
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("PUT", "/collections/c"),
+            Expensive
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("POST", "/collections/aliases"),
+            Regular,
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("PUT", "/collections/c/points"),
+            Regular,
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("POST", "/collections/c/points/query"),
+            Regular,
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("PUT", "/collections/c/index"),
+            Expensive,
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("POST", "/collections/c/snapshots/recover"),
+            Expensive,
+        );
+        assert_eq!(
+            RequestCost::of_rest_request("POST", "/snapshots"),
+            Expensive
+        );
+
+        assert_eq!(
+            RequestCost::of_grpc_request("/qdrant.Points/Search"),
+            Regular,
+        );
+        assert_eq!(
+            RequestCost::of_grpc_request("/qdrant.Points/CreateFieldIndex"),
+            Expensive,
+        );
+        assert_eq!(
+            RequestCost::of_grpc_request("/qdrant.Snapshots/List"),
+            Regular,
+        );
+        assert_eq!(
+            RequestCost::of_grpc_request("/qdrant.Snapshots/CreateFull"),
+            Expensive,
+        );
+        assert_eq!(
+            RequestCost::of_grpc_request("/qdrant.Collections/Delete"),
+            Expensive,
+        );
+    }
+}
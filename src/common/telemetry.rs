@@ -10,6 +10,12 @@ use itertools::Itertools;
 use parking_lot::Mutex;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::vector_storage::background_populate::{
+    BackgroundPopulateTelemetry, background_populate_telemetry,
+};
+use segment::vector_storage::dense::hot_vectors_cache::{
+    HotVectorsCacheTelemetry, hot_vectors_cache_telemetry,
+};
 use serde::Serialize;
 use shard::common::stopping_guard::StoppingGuard;
 use storage::content_manager::errors::{StorageError, StorageResult};
@@ -60,6 +66,10 @@ pub struct TelemetryData {
     pub(crate) memory: Option<MemoryTelemetry>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) hardware: Option<HardwareTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) background_populate: Option<BackgroundPopulateTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hot_vectors_cache: Option<HotVectorsCacheTelemetry>,
 }
 
 impl TelemetryCollector {
@@ -145,6 +155,12 @@ impl TelemetryCollector {
                 .flatten(),
             hardware: (detail.level > DetailsLevel::Level0)
                 .then(|| HardwareTelemetry::new(&self.dispatcher, access)),
+            background_populate: (detail.level > DetailsLevel::Level0)
+                .then(background_populate_telemetry)
+                .flatten(),
+            hot_vectors_cache: (detail.level > DetailsLevel::Level0)
+                .then(hot_vectors_cache_telemetry)
+                .flatten(),
         })
     }
 }
@@ -185,6 +201,8 @@ impl TryFrom<grpc::PeerTelemetry> for TelemetryData {
             requests: None,
             memory: None,
             hardware: None,
+            background_populate: None,
+            hot_vectors_cache: None,
         })
     }
 }
@@ -201,6 +219,8 @@ impl TryFrom<TelemetryData> for grpc::PeerTelemetry {
             requests: _,
             memory: _,
             hardware: _,
+            background_populate: _,
+            hot_vectors_cache: _,
         } = telemetry_data;
 
         let app = app.map(grpc::AppTelemetry::from);
@@ -10,12 +10,20 @@ use storage::rbac::{Access, AccessRequirements};
 #[derive(Serialize, Clone, Debug, JsonSchema, Anonymize)]
 pub struct HardwareTelemetry {
     pub(crate) collection_data: HashMap<String, HardwareUsage>,
+    /// Usage per API key or JWT subject, only reported with global access
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub(crate) api_key_data: HashMap<String, HardwareUsage>,
 }
 
 impl HardwareTelemetry {
     pub(crate) fn new(dispatcher: &Dispatcher, access: &Access) -> Self {
         let mut all_hw_metrics = dispatcher.all_hw_metrics();
 
+        let api_key_data = match access {
+            Access::Global(_) => dispatcher.all_subject_hw_metrics(),
+            Access::Collection(_) => HashMap::new(),
+        };
+
         let collection_data = match access {
             Access::Global(_) => all_hw_metrics,
             Access::Collection(collection_access_list) => {
@@ -32,6 +40,9 @@ impl HardwareTelemetry {
             }
         };
 
-        Self { collection_data }
+        Self {
+            collection_data,
+            api_key_data,
+        }
     }
 }
@@ -0,0 +1,20 @@
+//! Queries pinned to a past state of the collection.
+//!
+//! Past states are served from segments, which were retained after optimizations for
+//! `segment_retention_sec`. Changes applied in place to segments, which were not optimized since,
+//! can not be reverted, so the view of the past is best-effort.
+
+use api::rest::QueryRequest;
+use collection::operations::time_travel::AsOf;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct QueryAsOfRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub query: QueryRequest,
+    /// Past state of the collection to query
+    pub as_of: AsOf,
+}
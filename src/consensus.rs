@@ -7,9 +7,10 @@ use std::{cmp, thread};
 
 use anyhow::{Context as _, anyhow};
 use api::grpc::dynamic_channel_pool::make_grpc_channel;
+use api::grpc::peer_signature::PeerRequestSigner;
 use api::grpc::qdrant::raft_client::RaftClient;
 use api::grpc::qdrant::{AllPeers, PeerId as GrpcPeerId, RaftMessage as GrpcRaftMessage};
-use api::grpc::transport_channel_pool::TransportChannelPool;
+use api::grpc::transport_channel_pool::{TransportChannelPool, peer_channel};
 use collection::shards::channel_service::ChannelService;
 use collection::shards::shard::PeerId;
 #[cfg(target_os = "linux")]
@@ -212,6 +213,7 @@ impl Consensus {
         raft_config.validate()?;
         // bounded channel for backpressure
         let (sender, receiver) = tokio::sync::mpsc::channel(config.max_message_queue_size);
+        let request_signer = channel_service.channel_pool.request_signer().cloned();
         // State might be initialized but the node might be shutdown without actually syncing or committing anything.
         if state_ref.is_new_deployment() || reinit {
             let leader_established_in_ms =
@@ -223,6 +225,7 @@ impl Consensus {
                 p2p_port,
                 &config,
                 tls_config.clone(),
+                request_signer,
                 &runtime,
                 leader_established_in_ms,
             )
@@ -235,6 +238,7 @@ impl Consensus {
                     p2p_port,
                     &config,
                     tls_config.clone(),
+                    request_signer,
                 ))
                 .context("Failed to recover Consensus from existing Raft state")?;
 
@@ -291,6 +295,7 @@ impl Consensus {
         p2p_port: u16,
         config: &ConsensusConfig,
         tls_config: Option<ClientTlsConfig>,
+        request_signer: Option<PeerRequestSigner>,
         runtime: &Handle,
         leader_established_in_ms: u64,
     ) -> anyhow::Result<()> {
@@ -303,6 +308,7 @@ impl Consensus {
                 p2p_port,
                 config,
                 tls_config,
+                request_signer,
             ))?;
             Ok(())
         } else {
@@ -330,17 +336,23 @@ impl Consensus {
         p2p_port: u16,
         config: &ConsensusConfig,
         tls_config: Option<ClientTlsConfig>,
+        request_signer: Option<PeerRequestSigner>,
     ) -> anyhow::Result<AllPeers> {
         // Use dedicated transport channel for bootstrapping because of specific timeout
         let channel = make_grpc_channel(
             Duration::from_secs(config.bootstrap_timeout_sec),
             Duration::from_secs(config.bootstrap_timeout_sec),
-            cluster_uri,
+            cluster_uri.clone(),
             tls_config,
         )
         .await
         .context("Failed to create timeout channel")?;
-        let mut client = RaftClient::new(channel);
+        let mut client = RaftClient::new(peer_channel(
+            channel,
+            &cluster_uri,
+            Duration::from_secs(config.bootstrap_timeout_sec),
+            request_signer,
+        ));
         let all_peers = client
             .add_peer_to_known(tonic::Request::new(
                 api::grpc::qdrant::AddPeerToKnownMessage {
@@ -363,6 +375,7 @@ impl Consensus {
         p2p_port: u16,
         config: &ConsensusConfig,
         tls_config: Option<ClientTlsConfig>,
+        request_signer: Option<PeerRequestSigner>,
     ) -> anyhow::Result<()> {
         let this_peer_id = state_ref.this_peer_id();
         let mut peer_to_uri = state_ref
@@ -390,6 +403,7 @@ impl Consensus {
                         p2p_port,
                         config,
                         tls_config.clone(),
+                        request_signer.clone(),
                     )
                     .await;
                     if res.is_err() {
@@ -431,6 +445,7 @@ impl Consensus {
         p2p_port: u16,
         config: &ConsensusConfig,
         tls_config: Option<ClientTlsConfig>,
+        request_signer: Option<PeerRequestSigner>,
     ) -> anyhow::Result<()> {
         let this_peer_id = state_ref.this_peer_id();
         let all_peers = Self::add_peer_to_known_for(
@@ -440,6 +455,7 @@ impl Consensus {
             p2p_port,
             config,
             tls_config,
+            request_signer,
         )
         .await?;
 
@@ -1412,18 +1428,24 @@ impl RaftMessageSender {
         let channel = make_grpc_channel(
             bootstrap_timeout,
             bootstrap_timeout,
-            bootstrap_uri,
+            bootstrap_uri.clone(),
             self.tls_config.clone(),
         )
         .await
         .context("Failed to create who-is channel")?;
 
-        let uri = RaftClient::new(channel)
-            .who_is(tonic::Request::new(GrpcPeerId { id: peer_id }))
-            .await?
-            .into_inner()
-            .uri
-            .parse()?;
+        let request_signer = self.transport_channel_pool.request_signer().cloned();
+        let uri = RaftClient::new(peer_channel(
+            channel,
+            &bootstrap_uri,
+            bootstrap_timeout,
+            request_signer,
+        ))
+        .who_is(tonic::Request::new(GrpcPeerId { id: peer_id }))
+        .await?
+        .into_inner()
+        .uri
+        .parse()?;
 
         Ok(uri)
     }
@@ -1493,7 +1515,8 @@ mod tests {
             ),
             persistent_state.this_peer_id(),
             Some(operation_sender.clone()),
-        );
+        )
+        .unwrap();
         let toc_arc = Arc::new(toc);
         let storage_path = toc_arc.storage_path();
         let consensus_state: ConsensusStateRef = ConsensusManager::new(
@@ -0,0 +1,553 @@
+//! Arrow Flight service, streaming points of collections as Arrow record batches.
+//!
+//! Requests of points are JSON objects, passed as the command of a flight descriptor to
+//! `GetFlightInfo` and `GetSchema`, or as the ticket to `DoGet`:
+//!
+//! ```json
+//! {"collection_name": "books", "filter": {...}, "vectors": ["text"], "limit": 100000}
+//! ```
+//!
+//! Columns of the batches are the same as in exported datasets: `id` as string, a column per
+//! vector, and `payload` as JSON string.
+
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use api::rest::Record;
+use arrow::array::RecordBatch;
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::PointRequestInternal;
+use collection::operations::verification::StrictModeVerification;
+use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::{Stream, TryStreamExt};
+use segment::types::{ExtendedPointId, Filter, SearchParams, VectorNameBuf, WithPayloadInterface};
+use serde::Deserialize;
+use shard::scroll::ScrollRequestInternal;
+use storage::audit::extract_tracing_id;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use tokio::runtime::Handle;
+use tonic_flight::metadata::MetadataMap;
+use tonic_flight::transport::Server;
+use tonic_flight::{Request, Response, Status, Streaming};
+use validator::Validate;
+
+use crate::common::auth::{Auth, AuthError, AuthKeys, AuthType, log_denied_auth};
+use crate::common::dataset_export::DatasetColumns;
+use crate::common::strict_mode::{CheckedTocProvider, StrictModeCheckedTocProvider};
+use crate::common::subject_rate_limiter::RequestCost;
+use crate::settings::Settings;
+use crate::tonic::wait_stop_signal;
+
+/// Number of points in a record batch, if not specified in the request
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+type BoxStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send + 'static>>;
+
+/// Request of points, encoded as JSON in tickets and flight descriptors
+#[derive(Debug, Deserialize, Validate, Clone)]
+#[serde(deny_unknown_fields)]
+struct FlightRequest {
+    collection_name: String,
+    /// Read only points with these ids, in the same order. If not set, points are scrolled.
+    #[serde(default)]
+    ids: Option<Vec<ExtendedPointId>>,
+    /// Read only points which satisfy this conditions. Ignored, if `ids` are set.
+    #[serde(default)]
+    #[validate(nested)]
+    filter: Option<Filter>,
+    /// Names of the vectors to read. If not set, all vectors are read.
+    #[serde(default)]
+    vectors: Option<Vec<VectorNameBuf>>,
+    /// Whether to read payload. Default: true
+    #[serde(default)]
+    with_payload: Option<bool>,
+    /// Maximum number of points to read. If not set, all points are read.
+    #[serde(default)]
+    limit: Option<usize>,
+    /// Maximum number of points in a record batch. Default: 1000
+    #[serde(default)]
+    batch_size: Option<NonZeroUsize>,
+}
+
+impl FlightRequest {
+    fn parse(bytes: &[u8]) -> Result<Self, Status> {
+        let request: Self = serde_json::from_slice(bytes)
+            .map_err(|err| Status::invalid_argument(format!("Invalid request: {err}")))?;
+        request
+            .validate()
+            .map_err(|err| Status::invalid_argument(format!("Invalid request: {err}")))?;
+        Ok(request)
+    }
+
+    fn from_descriptor(descriptor: &FlightDescriptor) -> Result<Self, Status> {
+        if descriptor.r#type != arrow_flight::flight_descriptor::DescriptorType::Cmd as i32 {
+            return Err(Status::invalid_argument(
+                "Only command descriptors are supported",
+            ));
+        }
+        Self::parse(&descriptor.cmd)
+    }
+}
+
+impl StrictModeVerification for FlightRequest {
+    /// Points are read by requests, which are limited by the batch size
+    fn query_limit(&self) -> Option<usize> {
+        Some(
+            self.batch_size
+                .map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get),
+        )
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&SearchParams> {
+        None
+    }
+}
+
+pub struct QdrantFlightService {
+    dispatcher: Arc<Dispatcher>,
+    auth_keys: Option<Arc<AuthKeys>>,
+}
+
+impl QdrantFlightService {
+    async fn authenticate(
+        &self,
+        metadata: &MetadataMap,
+        remote_addr: Option<SocketAddr>,
+        method: &str,
+    ) -> Result<Auth, Status> {
+        let remote = remote_addr.map(|addr| addr.ip().to_string());
+        let tracing_id = extract_tracing_id(|h| {
+            metadata
+                .get(h)
+                .and_then(|val| val.to_str().ok())
+                .map(str::to_string)
+        });
+
+        let Some(auth_keys) = &self.auth_keys else {
+            return Ok(Auth::new(
+                Access::full(
+                    "All requests have full by default access when API key is not configured",
+                ),
+                None,
+                remote,
+                AuthType::None,
+                tracing_id,
+            ));
+        };
+
+        let auth = auth_keys
+            .validate_request(|key| metadata.get(key).and_then(|val| val.to_str().ok()))
+            .await
+            .and_then(|(access, _inference_token, auth_type, subject)| {
+                let auth = Auth::new(
+                    access,
+                    subject,
+                    remote.clone(),
+                    auth_type,
+                    tracing_id.clone(),
+                );
+                auth_keys.check_allowed_network(&auth, remote_addr.map(|addr| addr.ip()))?;
+                Ok(auth)
+            })
+            .map_err(|err| {
+                log_denied_auth(method, remote.clone(), tracing_id.clone(), &err);
+                match err {
+                    AuthError::Unauthorized(err) => Status::unauthenticated(err),
+                    AuthError::Forbidden(err) => Status::permission_denied(err),
+                    AuthError::StorageError(err) => storage_error_to_status(err),
+                }
+            })?;
+
+        auth_keys
+            .check_rate_limit(&auth, RequestCost::Regular)
+            .map_err(storage_error_to_status)?;
+
+        Ok(auth)
+    }
+
+    async fn toc(
+        &self,
+        request: &FlightRequest,
+        auth: &Auth,
+    ) -> Result<Arc<TableOfContent>, Status> {
+        StrictModeCheckedTocProvider::new(&self.dispatcher)
+            .check_strict_mode(request, &request.collection_name, None, auth)
+            .await
+            .cloned()
+            .map_err(storage_error_to_status)
+    }
+
+    async fn columns(
+        toc: &TableOfContent,
+        request: &FlightRequest,
+        auth: &Auth,
+    ) -> Result<DatasetColumns, StorageError> {
+        let collection_pass = auth.check_collection_access(
+            &request.collection_name,
+            AccessRequirements::new(),
+            "flight",
+        )?;
+        let collection = toc.get_collection(&collection_pass).await?;
+        let info = collection.info(&ShardSelectorInternal::All).await?;
+        DatasetColumns::new(
+            &info.config.params,
+            request.vectors.as_deref(),
+            request.with_payload.unwrap_or(true),
+        )
+    }
+}
+
+#[tonic_flight::async_trait]
+impl FlightService for QdrantFlightService {
+    type HandshakeStream = BoxStream<HandshakeResponse>;
+    type ListFlightsStream = BoxStream<FlightInfo>;
+    type DoGetStream = BoxStream<FlightData>;
+    type DoPutStream = BoxStream<PutResult>;
+    type DoActionStream = BoxStream<arrow_flight::Result>;
+    type ListActionsStream = BoxStream<ActionType>;
+    type DoExchangeStream = BoxStream<FlightData>;
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let auth = self
+            .authenticate(request.metadata(), request.remote_addr(), "get_flight_info")
+            .await?;
+        let descriptor = request.into_inner();
+        let flight_request = FlightRequest::from_descriptor(&descriptor)?;
+        let toc = self.toc(&flight_request, &auth).await?;
+        let columns = Self::columns(&toc, &flight_request, &auth)
+            .await
+            .map_err(storage_error_to_status)?;
+
+        // Points are read with the same request, so the command is used as the ticket as is
+        let endpoint = FlightEndpoint::new().with_ticket(Ticket::new(descriptor.cmd.clone()));
+        let info = FlightInfo::new()
+            .try_with_schema(&columns.schema())
+            .map_err(|err| Status::internal(err.to_string()))?
+            .with_endpoint(endpoint)
+            .with_descriptor(descriptor)
+            .with_ordered(true);
+
+        Ok(Response::new(info))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let auth = self
+            .authenticate(request.metadata(), request.remote_addr(), "get_schema")
+            .await?;
+        let flight_request = FlightRequest::from_descriptor(request.get_ref())?;
+        let toc = self.toc(&flight_request, &auth).await?;
+        let columns = Self::columns(&toc, &flight_request, &auth)
+            .await
+            .map_err(storage_error_to_status)?;
+
+        let schema = columns.schema();
+        let result = SchemaAsIpc::new(&schema, &IpcWriteOptions::default())
+            .try_into()
+            .map_err(|err: arrow::error::ArrowError| Status::internal(err.to_string()))?;
+
+        Ok(Response::new(result))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let auth = self
+            .authenticate(request.metadata(), request.remote_addr(), "do_get")
+            .await?;
+        let flight_request = FlightRequest::parse(&request.get_ref().ticket)?;
+        let toc = self.toc(&flight_request, &auth).await?;
+        let columns = Arc::new(
+            Self::columns(&toc, &flight_request, &auth)
+                .await
+                .map_err(storage_error_to_status)?,
+        );
+
+        let batches = read_batches(toc, flight_request, columns.clone(), auth)
+            .map_err(|err| FlightError::Tonic(Box::new(storage_error_to_status(err))));
+
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(columns.schema())
+            .build(batches)
+            .map_err(Status::from);
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented(
+            "Handshake is not required, authenticate with `api-key` or `authorization` header",
+        ))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Err(Status::unimplemented("ListFlights is not supported"))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("PollFlightInfo is not supported"))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("DoPut is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("DoAction is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(Box::pin(futures::stream::empty())))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("DoExchange is not supported"))
+    }
+}
+
+/// State of reading points for record batches
+struct ReadState {
+    toc: Arc<TableOfContent>,
+    request: FlightRequest,
+    columns: Arc<DatasetColumns>,
+    auth: Auth,
+    /// Scroll offset, or position in `ids` of the request
+    position: ReadPosition,
+    /// Number of points, which can still be read
+    remaining: usize,
+}
+
+enum ReadPosition {
+    Offset(Option<ExtendedPointId>),
+    Index(usize),
+    Done,
+}
+
+fn read_batches(
+    toc: Arc<TableOfContent>,
+    request: FlightRequest,
+    columns: Arc<DatasetColumns>,
+    auth: Auth,
+) -> impl Stream<Item = Result<RecordBatch, StorageError>> + Send + 'static {
+    let position = if request.ids.is_some() {
+        ReadPosition::Index(0)
+    } else {
+        ReadPosition::Offset(None)
+    };
+    let remaining = request.limit.unwrap_or(usize::MAX);
+
+    let state = ReadState {
+        toc,
+        request,
+        columns,
+        auth,
+        position,
+        remaining,
+    };
+
+    futures::stream::try_unfold(state, ReadState::next_batch)
+}
+
+impl ReadState {
+    async fn next_batch(mut self) -> Result<Option<(RecordBatch, Self)>, StorageError> {
+        let batch_size = self
+            .request
+            .batch_size
+            .map_or(DEFAULT_BATCH_SIZE, NonZeroUsize::get)
+            .min(self.remaining);
+        if batch_size == 0 {
+            return Ok(None);
+        }
+
+        let with_payload = Some(WithPayloadInterface::Bool(
+            self.request.with_payload.unwrap_or(true),
+        ));
+
+        let records: Vec<Record> = match self.position {
+            ReadPosition::Done => return Ok(None),
+            ReadPosition::Offset(offset) => {
+                let result = self
+                    .toc
+                    .scroll(
+                        &self.request.collection_name,
+                        ScrollRequestInternal {
+                            offset,
+                            limit: Some(batch_size),
+                            filter: self.request.filter.clone(),
+                            with_payload,
+                            with_vector: self.columns.with_vector(),
+                            order_by: None,
+                        },
+                        None,
+                        None,
+                        ShardSelectorInternal::All,
+                        self.auth.clone(),
+                        HwMeasurementAcc::disposable(),
+                    )
+                    .await?;
+
+                self.position = match result.next_page_offset {
+                    Some(offset) => ReadPosition::Offset(Some(offset)),
+                    None => ReadPosition::Done,
+                };
+                result.points
+            }
+            ReadPosition::Index(index) => {
+                let ids = self.request.ids.as_deref().unwrap_or_default();
+                let end = (index + batch_size).min(ids.len());
+                if index >= end {
+                    return Ok(None);
+                }
+
+                let records = self
+                    .toc
+                    .retrieve(
+                        &self.request.collection_name,
+                        PointRequestInternal {
+                            ids: ids[index..end].to_vec(),
+                            with_payload,
+                            with_vector: self.columns.with_vector(),
+                            at_version: None,
+                        },
+                        None,
+                        None,
+                        ShardSelectorInternal::All,
+                        self.auth.clone(),
+                        HwMeasurementAcc::disposable(),
+                    )
+                    .await?;
+
+                self.position = ReadPosition::Index(end);
+                records.into_iter().map(Record::from).collect()
+            }
+        };
+
+        self.remaining -= records.len().min(self.remaining);
+
+        let columns = self.columns.clone();
+        let batch = tokio::task::spawn_blocking(move || columns.record_batch(&records)).await??;
+
+        Ok(Some((batch, self)))
+    }
+}
+
+fn storage_error_to_status(error: StorageError) -> Status {
+    // Same codes as in the gRPC API, which uses another version of tonic
+    let status = ::tonic::Status::from(error);
+    Status::new(
+        tonic_flight::Code::from(status.code() as i32),
+        status.message(),
+    )
+}
+
+pub fn init(
+    dispatcher: Arc<Dispatcher>,
+    settings: Settings,
+    flight_port: u16,
+    runtime: Handle,
+    auth_keys: Option<AuthKeys>,
+) -> io::Result<()> {
+    runtime.block_on(async {
+        let socket = SocketAddr::from((
+            settings.service.host.parse::<IpAddr>().unwrap(),
+            flight_port,
+        ));
+
+        // Refuse to serve points in plain text, when the rest of the API requires TLS
+        if settings.service.enable_tls {
+            return Err(io::Error::other(
+                "TLS is not supported for Arrow Flight API, disable `service.flight_port` or TLS",
+            ));
+        }
+
+        log::info!("Qdrant Arrow Flight listening on {flight_port}");
+
+        let flight_service = QdrantFlightService {
+            dispatcher,
+            auth_keys: auth_keys.map(Arc::new),
+        };
+
+        Server::builder()
+            .add_service(
+                FlightServiceServer::new(flight_service).max_decoding_message_size(usize::MAX),
+            )
+            .serve_with_shutdown(socket, async {
+                wait_stop_signal("Arrow Flight service").await;
+            })
+            .await
+            .map_err(io::Error::other)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let request = FlightRequest::parse(
+            br#"{"collection_name": "books", "ids": [1, "c2a0a8b0-8d5a-4c1a-9c1a-2b3c4d5e6f70"], "limit": 10}"#,
+        )
+        .unwrap();
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.ids.unwrap().len(), 2);
+        assert_eq!(request.limit, Some(10));
+
+        let error =
+            FlightRequest::parse(br#"{"collection_name": "books", "offset": 1}"#).unwrap_err();
+        assert_eq!(error.code(), tonic_flight::Code::InvalidArgument);
+    }
+}
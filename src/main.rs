@@ -1,6 +1,8 @@
 mod actix;
 mod common;
 mod consensus;
+#[cfg(feature = "flight")]
+mod flight;
 mod greeting;
 mod issues_setup;
 mod migrations;
@@ -23,6 +25,7 @@ use ::common::fs::{FsCheckResult, check_fs_info, check_mmap_functionality};
 use ::common::mmap::MULTI_MMAP_SUPPORT_CHECK_RESULT;
 use ::common::mmap::advice::set_global;
 use ::tonic::transport::Uri;
+use api::grpc::peer_signature::PeerRequestSigner;
 use api::grpc::transport_channel_pool::TransportChannelPool;
 use clap::Parser;
 use collection::profiling::interface::init_requests_profile_collector;
@@ -44,11 +47,14 @@ use storage::rbac::Access;
 ))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::auth::AuthKeys;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
     load_tls_client_config,
 };
 use crate::common::inference::service::InferenceService;
+use crate::common::secrets::SecretsProvider;
+use crate::common::subject_rate_limiter::SubjectRateLimiter;
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
 use crate::greeting::welcome;
@@ -157,7 +163,7 @@ fn main() -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let settings = Settings::new(args.config_path)?;
+    let mut settings = Settings::new(args.config_path)?;
 
     // Set global feature flags, sourced from configuration
     init_feature_flags(settings.feature_flags);
@@ -185,6 +191,31 @@ fn main() -> anyhow::Result<()> {
             .async_scorer
             .unwrap_or_default(),
     );
+    if let Some(background_populate) = settings.storage.performance.background_populate {
+        segment::vector_storage::background_populate::init_background_populate(background_populate);
+    }
+    segment::vector_storage::memory_budget::MemoryBudget::global().set_limit(
+        settings
+            .storage
+            .performance
+            .memory_budget_mb
+            .map(|megabytes| megabytes * 1024 * 1024),
+    );
+    if let Some(hot_vectors_cache) = settings.storage.performance.hot_vectors_cache {
+        segment::vector_storage::dense::hot_vectors_cache::init_hot_vectors_cache(
+            hot_vectors_cache,
+        );
+    }
+    segment::vector_storage::dedup_index::set_vector_dedup_index(
+        settings
+            .storage
+            .performance
+            .vector_dedup_index
+            .unwrap_or_default(),
+    );
+    if let Some(appendable_hnsw) = settings.storage.performance.appendable_hnsw {
+        segment::index::hnsw_index::appendable_graph::init_appendable_hnsw(appendable_hnsw);
+    }
     welcome(&settings);
 
     // If audit logging is enabled, but failed to initialize,
@@ -198,7 +229,7 @@ fn main() -> anyhow::Result<()> {
         use segment::index::hnsw_index::gpu::*;
 
         // initialize GPU devices manager.
-        if settings_gpu.indexing {
+        if settings_gpu.indexing || settings_gpu.rescoring {
             set_gpu_force_half_precision(settings_gpu.force_half_precision);
             set_gpu_groups_count(settings_gpu.groups_count);
 
@@ -209,7 +240,13 @@ fn main() -> anyhow::Result<()> {
                 settings_gpu.allow_integrated,
                 settings_gpu.allow_emulated,
                 true, // Currently we always wait for the free gpu device.
-                settings_gpu.parallel_indexes.unwrap_or(1),
+                // Without indexing, devices are used for rescoring only.
+                if settings_gpu.indexing {
+                    settings_gpu.parallel_indexes.unwrap_or(1)
+                } else {
+                    0
+                },
+                settings_gpu.rescoring,
             ) {
                 Ok(gpu_device_manager) => Some(gpu_device_manager),
                 Err(err) => {
@@ -227,6 +264,22 @@ fn main() -> anyhow::Result<()> {
         );
     }
 
+    // API keys from external secret providers take precedence over the config.
+    // Fetched before anything uses the keys, on a separate runtime, as the general one
+    // is not created yet.
+    let secrets = match settings.service.secrets.clone() {
+        Some(secrets_config) => {
+            let provider = SecretsProvider::new(secrets_config, &settings.service);
+            let keys = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?
+                .block_on(provider.fetch())?;
+            keys.apply_to(&mut settings.service);
+            Some((provider, keys))
+        }
+        None => None,
+    };
+
     // Validate as soon as possible, but we must initialize logging first
     settings.validate_and_warn();
 
@@ -345,7 +398,13 @@ fn main() -> anyhow::Result<()> {
     // Use global CPU budget for optimizations based on settings
     let cpu_budget = get_cpu_budget(settings.storage.performance.optimizer_cpu_budget);
     let io_budget = get_io_budget(settings.storage.performance.optimizer_io_budget, cpu_budget);
-    let optimizer_resource_budget = ResourceBudget::new(cpu_budget, io_budget);
+    let optimizer_resource_budget = ResourceBudget::new(cpu_budget, io_budget).with_numa_aware(
+        settings
+            .storage
+            .performance
+            .optimizer_numa_aware
+            .unwrap_or_default(),
+    );
 
     // Create a signal sender and receiver. It is used to communicate with the consensus thread.
     let (propose_sender, propose_receiver) = std::sync::mpsc::channel();
@@ -380,6 +439,12 @@ fn main() -> anyhow::Result<()> {
             connection_timeout,
             settings.cluster.p2p.connection_pool_size,
             tls_config,
+            settings
+                .cluster
+                .p2p
+                .signing_key
+                .as_deref()
+                .map(PeerRequestSigner::new),
         ));
         channel_service.id_to_address = persistent_consensus_state.peer_address_by_id.clone();
         channel_service.id_to_metadata = persistent_consensus_state.peer_metadata_by_id.clone();
@@ -396,7 +461,7 @@ fn main() -> anyhow::Result<()> {
         channel_service.clone(),
         persistent_consensus_state.this_peer_id(),
         propose_operation_sender.clone(),
-    );
+    )?;
 
     toc.clear_all_tmp_directories()?;
 
@@ -544,6 +609,15 @@ fn main() -> anyhow::Result<()> {
         log::info!("Telemetry reporting disabled");
     }
 
+    if let Some(recall_estimation) = settings.storage.recall_estimation.clone() {
+        log::info!("Recall estimation enabled");
+
+        runtime_handle.spawn(TableOfContent::run_recall_estimation(
+            Arc::downgrade(&toc_arc),
+            recall_estimation,
+        ));
+    }
+
     if settings.service.hardware_reporting == Some(true) {
         log::info!("Hardware reporting enabled");
     }
@@ -568,12 +642,31 @@ fn main() -> anyhow::Result<()> {
         log::error!("Inference service init failed: {err}");
     }
 
+    // Rate limits by subject are shared between REST and gRPC APIs
+    let subject_rate_limiter = settings
+        .service
+        .rate_limits
+        .clone()
+        .map(|config| Arc::new(SubjectRateLimiter::new(config)));
+
+    // Keys are shared between REST and gRPC APIs, so rotated keys are applied to both
+    let auth_keys = AuthKeys::try_create(&settings.service, toc_arc.clone(), subject_rate_limiter);
+
+    if let Some((provider, keys)) = secrets {
+        runtime_handle.spawn(provider.run_refresh(
+            keys,
+            auth_keys.clone(),
+            toc_arc.get_channel_service().clone(),
+        ));
+    }
+
     //
     // REST API server
     //
 
     {
         let dispatcher_arc = dispatcher_arc.clone();
+        let auth_keys = auth_keys.clone();
         let telemetry_collector = telemetry_collector.clone();
         let settings = settings.clone();
         let handle = thread::Builder::new()
@@ -587,6 +680,7 @@ fn main() -> anyhow::Result<()> {
                         health_checker,
                         settings,
                         logger_handle,
+                        auth_keys,
                     ),
                 )
             })
@@ -617,6 +711,41 @@ fn main() -> anyhow::Result<()> {
         handles.push(handle);
     }
 
+    //
+    // Arrow Flight server
+    //
+
+    #[cfg(not(feature = "flight"))]
+    if let Some(flight_port) = settings.service.flight_port {
+        log::warn!(
+            "Arrow Flight port {flight_port} is configured, but Qdrant is built without `flight` feature",
+        );
+    }
+
+    #[cfg(feature = "flight")]
+    if let Some(flight_port) = settings.service.flight_port {
+        let dispatcher_arc = dispatcher_arc.clone();
+        let settings = settings.clone();
+        let runtime_handle = runtime_handle.clone();
+        let auth_keys = auth_keys.clone();
+        let handle = thread::Builder::new()
+            .name("flight".to_string())
+            .spawn(move || {
+                log_err_if_any(
+                    "Arrow Flight",
+                    flight::init(
+                        dispatcher_arc,
+                        settings,
+                        flight_port,
+                        runtime_handle,
+                        auth_keys,
+                    ),
+                )
+            })
+            .unwrap();
+        handles.push(handle);
+    }
+
     //
     // gRPC server
     //
@@ -634,6 +763,7 @@ fn main() -> anyhow::Result<()> {
                         settings,
                         grpc_port,
                         runtime_handle,
+                        auth_keys,
                     ),
                 )
             })
@@ -691,5 +821,6 @@ fn main() -> anyhow::Result<()> {
     }
     drop(toc_arc);
     drop(settings);
+    tracing::otlp::shutdown();
     Ok(())
 }
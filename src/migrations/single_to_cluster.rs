@@ -6,7 +6,7 @@ use collection::shards::replica_set::replica_set_state::ReplicaState;
 use collection::shards::shard::PeerId;
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateCollection, CreateCollectionOperation, CreateShardKey,
-    SetShardReplicaState,
+    SetShardReplicaState, UpdateCollectionOperation,
 };
 use storage::content_manager::consensus_manager::ConsensusStateRef;
 use storage::content_manager::shard_distribution::ShardDistributionProposal;
@@ -57,6 +57,7 @@ pub async fn handle_existing_collections(
             strict_mode_config,
             uuid,
             metadata,
+            read_only,
         } = config;
 
         let shards_number = params.shard_number.get();
@@ -131,6 +132,13 @@ pub async fn handle_existing_collections(
             }
         }
 
+        if read_only {
+            let mut update_operation =
+                UpdateCollectionOperation::new_empty(collection_name.clone());
+            update_operation.update_collection.read_only = Some(true);
+            consensus_operations.push(CollectionMetaOperations::UpdateCollection(update_operation));
+        }
+
         for operation in consensus_operations {
             let _res = dispatcher_arc
                 .submit_collection_meta_op(operation, full_auth.clone(), None)
@@ -15,7 +15,8 @@ use collection::operations::snapshot_ops::{
     ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
+    AliasDescription, CollectionClusterInfo, CollectionDiskUsage, CollectionExistence,
+    CollectionInfo, CollectionMemoryUsage, CollectionQuantizationQuality,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
     GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
     RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
@@ -24,6 +25,7 @@ use collection::operations::types::{
 use collection::operations::vector_ops::DeleteVectors;
 use schemars::JsonSchema;
 use schemars::r#gen::SchemaSettings;
+use segment::types::QuantizedVectorData;
 use serde::Serialize;
 use shard::operations::optimization::OptimizationsResponse;
 use storage::content_manager::collection_meta_ops::{
@@ -31,8 +33,17 @@ use storage::content_manager::collection_meta_ops::{
 };
 use storage::types::ClusterStatus;
 
+use crate::common::backup_import::{ImportBackup, ImportBackupResult};
+use crate::common::bulk_import::{ImportPoints, ImportResult};
+use crate::common::bulk_upsert::{BulkUpsertFormat, BulkUpsertResult};
+use crate::common::dataset_export::{ExportDataset, ExportResult};
+use crate::common::embedding_cache::{DeduplicatedResult, UpsertDeduplicated};
+use crate::common::es_query::{EsScrollRequest, EsSearchRequest};
+use crate::common::pgvector_import::{ImportPgvector, PgvectorImportResult};
+use crate::common::rescore::RescoreQueryRequest;
 use crate::common::telemetry::TelemetryData;
 use crate::common::telemetry_ops::distributed_telemetry::DistributedTelemetryData;
+use crate::common::time_travel::QueryAsOfRequest;
 use crate::common::update::{CreateFieldIndex, UpdateOperations};
 
 mod actix;
@@ -101,6 +112,26 @@ struct AllDefinitions {
     bo: ShardKeysResponse,
     bp: OptimizationsResponse,
     bq: DistributedTelemetryData,
+    br: CollectionMemoryUsage,
+    bs: CollectionDiskUsage,
+    bt: ImportPoints,
+    bu: ImportResult,
+    bv: ExportDataset,
+    bw: ExportResult,
+    bx: BulkUpsertFormat,
+    by: BulkUpsertResult,
+    bz: EsSearchRequest,
+    ca: EsScrollRequest,
+    cb: ImportPgvector,
+    cc: PgvectorImportResult,
+    cd: ImportBackup,
+    ce: ImportBackupResult,
+    cf: UpsertDeduplicated,
+    cg: DeduplicatedResult,
+    ch: RescoreQueryRequest,
+    ci: QueryAsOfRequest,
+    cj: CollectionQuantizationQuality,
+    ck: QuantizedVectorData,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -1,4 +1,6 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::{env, io};
 
 use api::grpc::transport_channel_pool::{
@@ -8,6 +10,7 @@ use collection::operations::validation;
 use collection::shards::shard::PeerId;
 use common::flags::FeatureFlags;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
+use ipnet::IpNet;
 use serde::Deserialize;
 use storage::types::StorageConfig;
 use validator::{Validate, ValidationError};
@@ -15,6 +18,7 @@ use validator::{Validate, ValidationError};
 use crate::common::audit::AuditConfig;
 use crate::common::debugger::DebuggerConfig;
 use crate::common::inference::config::InferenceConfig;
+use crate::common::secrets::SecretsConfig;
 use crate::tracing;
 
 const MAX_PEER_ID: u64 = (1 << 53) - 1;
@@ -28,6 +32,10 @@ pub struct ServiceConfig {
     pub http_port: u16,
     pub grpc_port: Option<u16>, // None means that gRPC is disabled
 
+    /// If specified, qdrant will serve Arrow Flight API on this port.
+    #[serde(default)]
+    pub flight_port: Option<u16>,
+
     /// If specified, qdrant will serve a separate service for `/metrics` on this port.
     /// Separate port is not protected by API keys and dedicated for internal monitoring systems.
     /// This port should not be exposed to untrusted networks.
@@ -54,6 +62,8 @@ pub struct ServiceConfig {
     pub enable_tls: bool,
     #[serde(default)]
     pub verify_https_client_certificate: bool,
+    #[serde(default)]
+    pub verify_grpc_client_certificate: bool,
     pub api_key: Option<String>,
 
     /// Same as `api_key`, can be used for rolling key rotation.
@@ -87,6 +97,20 @@ pub struct ServiceConfig {
     #[serde(default)]
     #[validate(custom(function = validate_metrics_prefix))]
     pub metrics_prefix: Option<String>,
+
+    /// Rate limits of authenticated requests, by API key or JWT subject.
+    #[serde(default)]
+    pub rate_limits: Option<SubjectRateLimitsConfig>,
+
+    /// Networks, from which credentials are allowed to be used, by API key or JWT subject.
+    /// Subjects, which are not listed, are allowed to connect from any address.
+    #[serde(default)]
+    pub allowed_networks: HashMap<String, Vec<IpNet>>,
+
+    /// External sources of the API keys, refreshed at runtime.
+    #[serde(default)]
+    #[validate(nested)]
+    pub secrets: Option<SecretsConfig>,
 }
 
 impl ServiceConfig {
@@ -126,6 +150,9 @@ pub struct P2pConfig {
     pub connection_pool_size: usize,
     #[serde(default)]
     pub enable_tls: bool,
+    /// Key shared by all peers, used to sign internal requests and reject unsigned or replayed ones
+    #[serde(default)]
+    pub signing_key: Option<String>,
 }
 
 impl Default for P2pConfig {
@@ -134,6 +161,7 @@ impl Default for P2pConfig {
             port: None,
             connection_pool_size: default_connection_pool_size(),
             enable_tls: false,
+            signing_key: None,
         }
     }
 }
@@ -168,6 +196,31 @@ impl Default for ConsensusConfig {
     }
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SubjectRateLimitsConfig {
+    /// Limits of subjects, which are not listed in `subjects`
+    #[serde(default)]
+    pub default: Option<RateLimitConfig>,
+
+    /// Limits by subject: `subject` claim of JWT, `api_key:<id>` for managed API keys,
+    /// `api_key` or `read_only_api_key` for the keys from the config
+    #[serde(default)]
+    pub subjects: HashMap<String, RateLimitConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Maximum number of requests per second. Unlimited, if not set
+    #[serde(default)]
+    pub requests_per_sec: Option<NonZeroUsize>,
+
+    /// Maximum number of expensive requests per minute, counted in addition to `requests_per_sec`.
+    /// Expensive requests are snapshot operations and changes of collections and payload indexes.
+    /// Unlimited, if not set
+    #[serde(default)]
+    pub expensive_requests_per_min: Option<NonZeroUsize>,
+}
+
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct TlsConfig {
     pub cert: String,
@@ -176,6 +229,11 @@ pub struct TlsConfig {
     #[serde(default = "default_tls_cert_ttl")]
     #[validate(range(min = 1))]
     pub cert_ttl: Option<u64>,
+    /// Names, one of which must be present in the subject alternative names of verified client
+    /// certificates. Any certificate signed by CA is accepted, if not set.
+    #[serde(default)]
+    #[validate(length(min = 1))]
+    pub allowed_client_names: Option<Vec<String>>,
 }
 
 #[allow(dead_code)]
@@ -184,6 +242,11 @@ pub struct GpuConfig {
     /// Enable GPU indexing.
     #[serde(default)]
     pub indexing: bool,
+    /// Keep vectors of indexed segments in GPU memory, and score exact search and rescoring there.
+    /// Only dense `float32` and `float16` vectors are supported.
+    /// Segments, which don't fit in GPU memory, are scored on CPU.
+    #[serde(default)]
+    pub rescoring: bool,
     /// Force half precision for `f32` values while indexing.
     /// `f16` conversion will take place only inside GPU memory and won't affect storage type.
     #[serde(default)]
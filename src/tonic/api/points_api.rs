@@ -1,3 +1,4 @@
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -7,19 +8,21 @@ use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
     DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
-    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
-    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
-    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
-    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
-    SearchBatchResponse, SearchGroupsResponse, SearchMatrixOffsets, SearchMatrixOffsetsResponse,
-    SearchMatrixPairs, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse,
-    UpdatePointVectors, UpsertPoints,
+    FacetResponse, GetPoints, GetResponse, PointsChange, PointsChangesRequest,
+    PointsOperationResponse, QueryBatchPoints, QueryBatchResponse, QueryGroupsResponse,
+    QueryPointGroups, QueryPoints, QueryResponse, RecommendBatchPoints, RecommendBatchResponse,
+    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
+    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
+    SearchMatrixOffsets, SearchMatrixOffsetsResponse, SearchMatrixPairs, SearchMatrixPairsResponse,
+    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use collection::operations::types::CoreSearchRequest;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
+use futures::{Stream, TryStreamExt};
 use storage::content_manager::toc::request_hw_counter::RequestHwCounter;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Auth;
 use tonic::{Request, Response, Status};
 
 use super::query_common::*;
@@ -27,6 +30,7 @@ use super::update_common::*;
 use super::validate;
 use crate::common::inference::api_keys::extract_inference_auth;
 use crate::common::inference::params::InferenceParams;
+use crate::common::points_changes::points_changes;
 use crate::common::strict_mode::*;
 use crate::common::update::InternalUpdateParams;
 use crate::settings::ServiceConfig;
@@ -48,11 +52,13 @@ impl PointsService {
     fn get_request_collection_hw_usage_counter(
         &self,
         collection_name: String,
+        auth: &Auth,
         wait: Option<bool>,
     ) -> RequestHwCounter {
         let counter = HwMeasurementAcc::new_with_metrics_drain(
             self.dispatcher.get_collection_hw_metrics(collection_name),
-        );
+        )
+        .with_subject_drain(self.dispatcher.get_subject_hw_metrics(auth));
 
         let waiting = wait != Some(false);
         RequestHwCounter::new(counter, self.service_config.hardware_reporting() && waiting)
@@ -61,6 +67,8 @@ impl PointsService {
 
 #[tonic::async_trait]
 impl Points for PointsService {
+    type ChangesStream = Pin<Box<dyn Stream<Item = Result<PointsChange, Status>> + Send>>;
+
     async fn upsert(
         &self,
         mut request: Request<UpsertPoints>,
@@ -74,7 +82,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         upsert(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -97,7 +105,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         delete(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -116,7 +124,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let inner_request = request.into_inner();
         let collection_name = inner_request.collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
 
         get(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -143,7 +151,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         update_vectors(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -166,7 +174,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
 
         delete_vectors(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -189,7 +197,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         set_payload(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -212,7 +220,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         overwrite_payload(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -235,7 +243,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         delete_payload(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -258,7 +266,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         clear_payload(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -284,7 +292,7 @@ impl Points for PointsService {
 
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         update_batch(
             &self.dispatcher,
@@ -306,7 +314,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
         let wait = Some(request.get_ref().wait.unwrap_or(false));
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, wait);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, wait);
 
         create_field_index(
             self.dispatcher.clone(),
@@ -345,7 +353,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
 
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
 
         let res = search(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -388,7 +396,7 @@ impl Points for PointsService {
         }
 
         let hw_metrics =
-            self.get_request_collection_hw_usage_counter(collection_name.clone(), None);
+            self.get_request_collection_hw_usage_counter(collection_name.clone(), &auth, None);
 
         let res = core_search_batch(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -411,7 +419,7 @@ impl Points for PointsService {
         validate(request.get_ref())?;
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let res = search_groups(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -435,7 +443,7 @@ impl Points for PointsService {
         let inner_request = request.into_inner();
         let collection_name = inner_request.collection_name.clone();
 
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
 
         scroll(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -454,7 +462,7 @@ impl Points for PointsService {
         validate(request.get_ref())?;
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let res = recommend(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -480,7 +488,7 @@ impl Points for PointsService {
         } = request.into_inner();
 
         let hw_metrics =
-            self.get_request_collection_hw_usage_counter(collection_name.clone(), None);
+            self.get_request_collection_hw_usage_counter(collection_name.clone(), &auth, None);
 
         let res = recommend_batch(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -503,7 +511,7 @@ impl Points for PointsService {
         validate(request.get_ref())?;
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
 
         let res = recommend_groups(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -524,7 +532,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
 
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let res = discover(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -550,7 +558,7 @@ impl Points for PointsService {
         } = request.into_inner();
 
         let hw_metrics =
-            self.get_request_collection_hw_usage_counter(collection_name.clone(), None);
+            self.get_request_collection_hw_usage_counter(collection_name.clone(), &auth, None);
         let res = discover_batch(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             &collection_name,
@@ -573,7 +581,7 @@ impl Points for PointsService {
 
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let res = count(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -596,7 +604,10 @@ impl Points for PointsService {
         let api_keys = extract_inference_auth(&request);
         let inference_params = InferenceParams::new(api_keys, timeout);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let with_search_diagnostics = request.get_ref().with_search_diagnostics;
+        let hw_metrics = self
+            .get_request_collection_hw_usage_counter(collection_name, &auth, None)
+            .with_search_diagnostics(with_search_diagnostics.unwrap_or_default());
 
         let res = query(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -627,10 +638,12 @@ impl Points for PointsService {
             query_points,
             read_consistency,
             timeout,
+            with_search_diagnostics,
         } = request;
         let timeout = timeout.map(Duration::from_secs);
-        let hw_metrics =
-            self.get_request_collection_hw_usage_counter(collection_name.clone(), None);
+        let hw_metrics = self
+            .get_request_collection_hw_usage_counter(collection_name.clone(), &auth, None)
+            .with_search_diagnostics(with_search_diagnostics.unwrap_or_default());
         let res = query_batch(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             &collection_name,
@@ -656,7 +669,10 @@ impl Points for PointsService {
         let api_keys = extract_inference_auth(&request);
         let inference_params = InferenceParams::new(api_keys, timeout);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let with_search_diagnostics = request.get_ref().with_search_diagnostics;
+        let hw_metrics = self
+            .get_request_collection_hw_usage_counter(collection_name, &auth, None)
+            .with_search_diagnostics(with_search_diagnostics.unwrap_or_default());
 
         let res = query_groups(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
@@ -677,7 +693,7 @@ impl Points for PointsService {
         validate(request.get_ref())?;
         let auth = extract_auth(&mut request);
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         facet(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -695,7 +711,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let timing = Instant::now();
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let search_matrix_response = search_points_matrix(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -721,7 +737,7 @@ impl Points for PointsService {
         let auth = extract_auth(&mut request);
         let timing = Instant::now();
         let collection_name = request.get_ref().collection_name.clone();
-        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, None);
+        let hw_metrics = self.get_request_collection_hw_usage_counter(collection_name, &auth, None);
         let search_matrix_response = search_points_matrix(
             StrictModeCheckedTocProvider::new(&self.dispatcher),
             request.into_inner(),
@@ -738,4 +754,21 @@ impl Points for PointsService {
 
         Ok(Response::new(offsets_response))
     }
+
+    async fn changes(
+        &self,
+        mut request: Request<PointsChangesRequest>,
+    ) -> Result<Response<Self::ChangesStream>, Status> {
+        validate(request.get_ref())?;
+        let auth = extract_auth(&mut request);
+        let PointsChangesRequest {
+            collection_name,
+            positions,
+        } = request.into_inner();
+
+        let changes =
+            points_changes(self.dispatcher.clone(), collection_name, positions, auth).await?;
+
+        Ok(Response::new(Box::pin(changes.map_err(Status::from))))
+    }
 }
@@ -13,7 +13,8 @@ use api::grpc::qdrant::{
     PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
     QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
     ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateBatchInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    SyncPointsInternal, TextQueryStatisticsInternal, TextQueryStatisticsResponseInternal,
+    UpdateBatchInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
 use api::grpc::update_operation::Update;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
@@ -22,6 +23,7 @@ use collection::shards::shard::ShardId;
 use common::counter::hardware_accumulator::HwMeasurementAcc;
 use itertools::Itertools;
 use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::text_query::TextQuery;
 use segment::json_path::JsonPath;
 use segment::types::Filter;
 use storage::content_manager::toc::TableOfContent;
@@ -396,6 +398,8 @@ pub async fn query_batch_internal(
             })
             .collect(),
         time: timing.elapsed().as_secs_f64(),
+        search_diagnostics: request_hw_data.to_grpc_search_diagnostics(),
+        profile: request_hw_data.to_grpc_profile(),
         hardware_usage: request_hw_data.to_grpc_api(),
         inference_usage: None, // No inference in internal API
     };
@@ -451,6 +455,43 @@ async fn facet_counts_internal(
     Ok(Response::new(response))
 }
 
+async fn text_query_statistics_internal(
+    toc: &TableOfContent,
+    request: TextQueryStatisticsInternal,
+    request_hw_data: RequestHwCounter,
+) -> Result<Response<TextQueryStatisticsResponseInternal>, Status> {
+    let timing = Instant::now();
+
+    let TextQueryStatisticsInternal {
+        collection_name,
+        query,
+        shard_id,
+        timeout,
+    } = request;
+
+    let shard_selection = ShardSelectorInternal::ShardId(shard_id);
+
+    let query = TextQuery::try_from(extract_internal_request(query)?)?;
+
+    let statistics = toc
+        .text_query_statistics_internal(
+            &collection_name,
+            query,
+            shard_selection,
+            timeout.map(Duration::from_secs),
+            request_hw_data.get_counter(),
+        )
+        .await?;
+
+    let response = TextQueryStatisticsResponseInternal {
+        statistics: Some(statistics.into()),
+        time: timing.elapsed().as_secs_f64(),
+        usage: request_hw_data.to_grpc_api(),
+    };
+
+    Ok(Response::new(response))
+}
+
 impl PointsInternalService {
     /// Generates a new `RequestHwCounter` for the request.
     /// This counter is indented to be used for internal requests.
@@ -871,12 +912,16 @@ impl PointsInternal for PointsInternalService {
             shard_id,
             query_points,
             timeout,
+            with_search_diagnostics,
+            with_profile,
         } = request.into_inner();
 
         let timeout = timeout.map(Duration::from_secs);
 
-        let hw_data =
-            self.get_request_collection_hw_usage_counter_for_internal(collection_name.clone());
+        let hw_data = self
+            .get_request_collection_hw_usage_counter_for_internal(collection_name.clone())
+            .with_search_diagnostics(with_search_diagnostics.unwrap_or_default())
+            .with_profile(with_profile.unwrap_or_default());
 
         query_batch_internal(
             self.toc.as_ref(),
@@ -901,6 +946,19 @@ impl PointsInternal for PointsInternalService {
         );
         facet_counts_internal(self.toc.as_ref(), request_inner, hw_data).await
     }
+
+    async fn text_query_statistics(
+        &self,
+        request: Request<TextQueryStatisticsInternal>,
+    ) -> Result<Response<TextQueryStatisticsResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let request_inner = request.into_inner();
+        let hw_data = self.get_request_collection_hw_usage_counter_for_internal(
+            request_inner.collection_name.clone(),
+        );
+        text_query_statistics_internal(self.toc.as_ref(), request_inner, hw_data).await
+    }
 }
 
 fn extract_internal_request<T>(request: Option<T>) -> Result<T, tonic::Status> {
@@ -722,6 +722,7 @@ pub async fn get(
         read_consistency,
         shard_key_selector,
         timeout,
+        at_version,
     } = get_points;
 
     let point_request = PointRequestInternal {
@@ -733,6 +734,7 @@ pub async fn get(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        at_version,
     };
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
@@ -816,13 +818,16 @@ pub async fn query(
     )
     .await?;
 
+    let search_diagnostics = request_hw_counter.to_grpc_search_diagnostics();
     let response = QueryResponse {
         result: scored_points
             .into_iter()
             .map(|point| point.into())
             .collect(),
         time: timing.elapsed().as_secs_f64(),
-        usage: Usage::new(request_hw_counter.to_grpc_api(), Some(inference_usage)).into_non_empty(),
+        usage: Usage::new(request_hw_counter.to_grpc_api(), Some(inference_usage))
+            .with_search_diagnostics(search_diagnostics)
+            .into_non_empty(),
     };
 
     Ok(Response::new(response))
@@ -874,6 +879,7 @@ pub async fn query_batch(
     )
     .await?;
 
+    let search_diagnostics = request_hw_counter.to_grpc_search_diagnostics();
     let response = QueryBatchResponse {
         result: scored_points
             .into_iter()
@@ -886,6 +892,7 @@ pub async fn query_batch(
             request_hw_counter.to_grpc_api(),
             total_inference_usage.into_non_empty(),
         )
+        .with_search_diagnostics(search_diagnostics)
         .into_non_empty(),
     };
 
@@ -939,10 +946,13 @@ pub async fn query_groups(
     let grpc_group_result = GroupsResult::try_from(groups_result)
         .map_err(|err| Status::internal(format!("failed to convert result: {err}")))?;
 
+    let search_diagnostics = request_hw_counter.to_grpc_search_diagnostics();
     let response = QueryGroupsResponse {
         result: Some(grpc_group_result),
         time: timing.elapsed().as_secs_f64(),
-        usage: Usage::new(request_hw_counter.to_grpc_api(), Some(inference_usage)).into_non_empty(),
+        usage: Usage::new(request_hw_counter.to_grpc_api(), Some(inference_usage))
+            .with_search_diagnostics(search_diagnostics)
+            .into_non_empty(),
     };
 
     Ok(Response::new(response))
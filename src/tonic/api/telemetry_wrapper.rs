@@ -15,11 +15,11 @@ use api::grpc::qdrant::{
     DeleteSnapshotRequest, DeleteSnapshotResponse, DiscoverBatchPoints, DiscoverBatchResponse,
     DiscoverPoints, DiscoverResponse, FacetCounts, FacetResponse, GetPoints, GetResponse,
     ListFullSnapshotsRequest, ListShardSnapshotsRequest, ListSnapshotsRequest,
-    ListSnapshotsResponse, PointsOperationResponse, QueryBatchPoints, QueryBatchResponse,
-    QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse, RecommendBatchPoints,
-    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
-    RecommendResponse, RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints,
-    ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
+    ListSnapshotsResponse, PointsChangesRequest, PointsOperationResponse, QueryBatchPoints,
+    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
+    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
+    RecommendPoints, RecommendResponse, RecoverShardSnapshotRequest, RecoverSnapshotResponse,
+    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
     SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixPoints, SearchPointGroups,
     SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse,
     UpdatePointVectors, UpsertPoints,
@@ -41,6 +41,8 @@ impl<T> PointsTelemetryWrapper<T> {
 
 #[tonic::async_trait]
 impl<T: Points> Points for PointsTelemetryWrapper<T> {
+    type ChangesStream = T::ChangesStream;
+
     async fn upsert(
         &self,
         request: Request<UpsertPoints>,
@@ -317,6 +319,16 @@ impl<T: Points> Points for PointsTelemetryWrapper<T> {
         resp.extensions_mut().insert(CollectionName(cn));
         Ok(resp)
     }
+
+    async fn changes(
+        &self,
+        request: Request<PointsChangesRequest>,
+    ) -> Result<Response<Self::ChangesStream>, Status> {
+        let cn = request.get_ref().collection_name.clone();
+        let mut resp = self.inner.changes(request).await?;
+        resp.extensions_mut().insert(CollectionName(cn));
+        Ok(resp)
+    }
 }
 
 /// Wraps a [`Snapshots`] service, attaching `collection_name` to every
@@ -458,6 +470,15 @@ mod tests {
             #[tonic::async_trait]
             #[allow(unused_variables)]
             impl Points for MockPoints {
+                type ChangesStream = futures::stream::Empty<Result<PointsChange, Status>>;
+
+                async fn changes(
+                    &self,
+                    r: Request<PointsChangesRequest>,
+                ) -> Result<Response<Self::ChangesStream>, Status> {
+                    Ok(Response::new(futures::stream::empty()))
+                }
+
                 $(
                     async fn $method(&self, r: Request<$req>) -> Result<Response<$resp>, Status> {
                         Ok(Response::new(Default::default()))
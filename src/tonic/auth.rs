@@ -1,3 +1,4 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
@@ -6,11 +7,16 @@ use storage::audit::{audit_trust_forwarded_headers, extract_tracing_id};
 use storage::rbac::Access;
 use tonic::Status;
 use tonic::body::BoxBody;
+use tonic::codegen::http::{HeaderName, HeaderValue};
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
 use tower::{Layer, Service};
 
 use super::forwarded;
 use crate::common::auth::{Auth, AuthError, AuthKeys, AuthType, log_denied_auth};
 use crate::common::inference::api_keys::InferenceToken;
+use crate::common::subject_rate_limiter::{
+    HEADER_RATE_LIMIT_LIMIT, HEADER_RATE_LIMIT_REMAINING, RateLimitStatus, RequestCost,
+};
 
 type Request = tonic::codegen::http::Request<tonic::transport::Body>;
 type Response = tonic::codegen::http::Response<BoxBody>;
@@ -21,7 +27,23 @@ pub struct AuthMiddleware<S> {
     service: S,
 }
 
-async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, Status> {
+/// Address of the connection, with or without TLS
+fn peer_addr(req: &Request) -> Option<SocketAddr> {
+    let extensions = req.extensions();
+    extensions
+        .get::<TcpConnectInfo>()
+        .or_else(|| {
+            extensions
+                .get::<TlsConnectInfo<TcpConnectInfo>>()
+                .map(|info| info.get_ref())
+        })
+        .and_then(|info| info.remote_addr())
+}
+
+async fn check(
+    auth_keys: Arc<AuthKeys>,
+    mut req: Request,
+) -> Result<(Request, Option<RateLimitStatus>), Status> {
     // When the audit logger trusts forwarded headers, prefer the raw
     // `X-Forwarded-For` value so audit entries record the real client address
     // rather than the proxy address.  Fall back to the TCP peer address.
@@ -30,12 +52,7 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
     } else {
         None
     }
-    .or_else(|| {
-        req.extensions()
-            .get::<tonic::transport::server::TcpConnectInfo>()
-            .and_then(|info| info.remote_addr())
-            .map(|addr| addr.ip().to_string())
-    });
+    .or_else(|| peer_addr(&req).map(|addr| addr.ip().to_string()));
 
     let tracing_id = extract_tracing_id(|h| {
         req.headers()
@@ -60,12 +77,27 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
         req.extensions_mut().insert(auth);
         req.extensions_mut().insert(inference_token);
 
-        return Ok(req);
+        return Ok((req, None));
     }
 
-    let (access, inference_token, auth_type, subject) = auth_keys
+    // Allowed networks are checked against the address of the connection itself,
+    // as forwarded headers can be set by the client
+    let peer_ip = peer_addr(&req).map(|addr| addr.ip());
+
+    let (auth, inference_token) = auth_keys
         .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
         .await
+        .and_then(|(access, inference_token, auth_type, subject)| {
+            let auth = Auth::new(
+                access,
+                subject,
+                remote.clone(),
+                auth_type,
+                tracing_id.clone(),
+            );
+            auth_keys.check_allowed_network(&auth, peer_ip)?;
+            Ok((auth, inference_token))
+        })
         .map_err(|e| {
             log_denied_auth(path, remote.clone(), tracing_id.clone(), &e);
             match e {
@@ -75,7 +107,9 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
             }
         })?;
 
-    let auth = Auth::new(access, subject, remote, auth_type, tracing_id);
+    let rate_limit = auth_keys
+        .check_rate_limit(&auth, RequestCost::of_grpc_request(path))
+        .map_err(Status::from)?;
 
     let previous = req.extensions_mut().insert(auth);
 
@@ -91,7 +125,7 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
         "Previous inference token should not exist in the request"
     );
 
-    Ok(req)
+    Ok((req, rate_limit))
 }
 
 impl<S> Service<Request> for AuthMiddleware<S>
@@ -113,7 +147,21 @@ where
 
         Box::pin(async move {
             match check(auth_keys, request).await {
-                Ok(req) => service.call(req).await,
+                Ok((req, rate_limit)) => {
+                    let mut response = service.call(req).await?;
+                    if let Some(RateLimitStatus { limit, remaining }) = rate_limit {
+                        let headers = response.headers_mut();
+                        headers.insert(
+                            HeaderName::from_static(HEADER_RATE_LIMIT_LIMIT),
+                            HeaderValue::from(limit),
+                        );
+                        headers.insert(
+                            HeaderName::from_static(HEADER_RATE_LIMIT_REMAINING),
+                            HeaderValue::from(remaining),
+                        );
+                    }
+                    Ok(response)
+                }
                 Err(e) => Ok(e.to_http()),
             }
         })
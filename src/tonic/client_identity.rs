@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use rustls_pki_types::CertificateDer;
+use tonic::Status;
+use tonic::body::BoxBody;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tower::{Layer, Service};
+
+use crate::common::client_identity::AllowedClientNames;
+
+type Request = tonic::codegen::http::Request<tonic::transport::Body>;
+type Response = tonic::codegen::http::Response<BoxBody>;
+
+/// Rejects requests over connections, whose client certificate is not issued for any of the
+/// allowed names.
+///
+/// The client certificate itself is verified against CA during the TLS handshake.
+#[derive(Clone)]
+pub struct ClientIdentityMiddleware<S> {
+    allowed_names: Arc<AllowedClientNames>,
+    service: S,
+}
+
+fn check(allowed_names: &AllowedClientNames, req: &Request) -> Result<(), Status> {
+    let peer_certs = req
+        .extensions()
+        .get::<TlsConnectInfo<TcpConnectInfo>>()
+        .and_then(|info| info.peer_certs());
+
+    let Some(end_entity) = peer_certs.as_ref().and_then(|certs| certs.first()) else {
+        return Err(Status::unauthenticated("Client certificate is required"));
+    };
+
+    allowed_names
+        .verify(&CertificateDer::from(end_entity.get_ref()))
+        .map_err(|err| {
+            Status::permission_denied(format!("Client certificate is not allowed: {err}"))
+        })
+}
+
+impl<S> Service<Request> for ClientIdentityMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let checked = check(&self.allowed_names, &request);
+        let mut service = self.service.clone();
+
+        Box::pin(async move {
+            match checked {
+                Ok(()) => service.call(request).await,
+                Err(e) => Ok(e.to_http()),
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct ClientIdentityLayer {
+    allowed_names: Arc<AllowedClientNames>,
+}
+
+impl ClientIdentityLayer {
+    pub fn new(allowed_names: AllowedClientNames) -> Self {
+        Self {
+            allowed_names: Arc::new(allowed_names),
+        }
+    }
+}
+
+impl<S> Layer<S> for ClientIdentityLayer {
+    type Service = ClientIdentityMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Self::Service {
+            allowed_names: self.allowed_names.clone(),
+            service,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod api;
 mod auth;
+mod client_identity;
 mod forwarded;
 mod logging;
+mod peer_signature;
 mod tonic_telemetry;
 
 use std::io;
@@ -25,11 +27,9 @@ use ::api::grpc::qdrant::shard_snapshots_server::ShardSnapshotsServer;
 use ::api::grpc::qdrant::snapshots_server::SnapshotsServer;
 use ::api::grpc::qdrant::{HealthCheckReply, HealthCheckRequest};
 use ::api::rest::models::VersionInfo;
-use collection::operations::verification::new_unchecked_verification_pass;
 use storage::content_manager::consensus_manager::ConsensusStateRef;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::{Access, Auth};
 use tokio::runtime::Handle;
 use tokio::signal;
 use tonic::codec::CompressionEncoding;
@@ -37,6 +37,7 @@ use tonic::transport::{Server, ServerTlsConfig};
 use tonic::{Request, Response, Status};
 
 use crate::common::auth::AuthKeys;
+use crate::common::client_identity::AllowedClientNames;
 use crate::common::helpers;
 use crate::common::http_client::HttpClient;
 use crate::common::telemetry::TelemetryCollector;
@@ -84,13 +85,13 @@ impl Health for HealthService {
 }
 
 #[cfg(not(unix))]
-async fn wait_stop_signal(for_what: &str) {
+pub(crate) async fn wait_stop_signal(for_what: &str) {
     signal::ctrl_c().await.unwrap();
     log::debug!("Stopping {for_what} on SIGINT");
 }
 
 #[cfg(unix)]
-async fn wait_stop_signal(for_what: &str) {
+pub(crate) async fn wait_stop_signal(for_what: &str) {
     let mut term = signal::unix::signal(signal::unix::SignalKind::terminate()).unwrap();
     let mut inrt = signal::unix::signal(signal::unix::SignalKind::interrupt()).unwrap();
 
@@ -106,6 +107,7 @@ pub fn init(
     settings: Settings,
     grpc_port: u16,
     runtime: Handle,
+    auth_keys: Option<AuthKeys>,
 ) -> io::Result<()> {
     runtime.block_on(async {
         let socket =
@@ -133,10 +135,16 @@ pub fn init(
 
         let mut server = Server::builder();
 
+        let verify_client_certificate =
+            settings.service.enable_tls && settings.service.verify_grpc_client_certificate;
+
         if settings.service.enable_tls {
             log::info!("TLS enabled for gRPC API (TTL not supported)");
 
-            let tls_server_config = helpers::load_tls_external_server_config(settings.tls()?)?;
+            let tls_server_config = helpers::load_tls_external_server_config(
+                settings.tls()?,
+                verify_client_certificate,
+            )?;
 
             server = server
                 .tls_config(tls_server_config)
@@ -145,7 +153,11 @@ pub fn init(
             log::info!("TLS disabled for gRPC API");
         }
 
-        let auth = Auth::new_internal(Access::full("For tonic auth middleware"));
+        let allowed_client_names = if verify_client_certificate {
+            AllowedClientNames::from_tls_config(settings.tls()?)?
+        } else {
+            None
+        };
 
         // The stack of middleware that our service will be wrapped in
         let middleware_layer = tower::ServiceBuilder::new()
@@ -153,15 +165,8 @@ pub fn init(
             .layer(tonic_telemetry::TonicTelemetryLayer::new(
                 telemetry_collector,
             ))
-            .option_layer({
-                AuthKeys::try_create(
-                    &settings.service,
-                    dispatcher
-                        .toc(&auth, &new_unchecked_verification_pass())
-                        .clone(),
-                )
-                .map(auth::AuthLayer::new)
-            })
+            .option_layer(allowed_client_names.map(client_identity::ClientIdentityLayer::new))
+            .option_layer(auth_keys.map(auth::AuthLayer::new))
             .into_inner();
 
         server
@@ -226,6 +231,21 @@ pub fn init_internal(
 
     let http_client = HttpClient::from_settings(&settings)?;
 
+    let peer_signature_layer = settings
+        .cluster
+        .p2p
+        .signing_key
+        .as_deref()
+        .map(|signing_key| {
+            peer_signature::PeerSignatureLayer::new(signing_key, consensus_state.clone())
+        });
+
+    // Peers are verified against CA, when internal TLS is enabled
+    let allowed_client_names = match (&tls_config, &settings.tls) {
+        (Some(_), Some(tls)) => AllowedClientNames::from_tls_config(tls)?,
+        _ => None,
+    };
+
     runtime
         .block_on(async {
             let socket = SocketAddr::from((host.parse::<IpAddr>().unwrap(), internal_grpc_port));
@@ -265,6 +285,8 @@ pub fn init_internal(
                 .layer(tonic_telemetry::TonicTelemetryLayer::new(
                     tonic_telemetry_collector,
                 ))
+                .option_layer(allowed_client_names.map(client_identity::ClientIdentityLayer::new))
+                .option_layer(peer_signature_layer)
                 .into_inner();
 
             server
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use api::grpc::peer_signature::{PeerRequestVerifier, PeerSignatureError, collect_body};
+use futures::future::BoxFuture;
+use storage::content_manager::consensus_manager::ConsensusStateRef;
+use tonic::Status;
+use tonic::body::BoxBody;
+use tonic::transport::Body;
+use tower::{Layer, Service};
+
+type Request = tonic::codegen::http::Request<Body>;
+type Response = tonic::codegen::http::Response<BoxBody>;
+
+/// Rejects internal requests, which are not signed with the key shared by the peers, which were
+/// signed for another peer, or which were already received.
+///
+/// Signature covers the body of the request, so the body is buffered before verification.
+#[derive(Clone)]
+pub struct PeerSignatureMiddleware<S> {
+    verifier: Arc<PeerRequestVerifier>,
+    consensus_state: ConsensusStateRef,
+    service: S,
+}
+
+impl<S> Service<Request> for PeerSignatureMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let verifier = self.verifier.clone();
+        // Requests are signed for the address of this peer, registered in consensus
+        let authority = self
+            .consensus_state
+            .this_peer_address()
+            .and_then(|uri| uri.authority().map(|authority| authority.to_string()));
+        let mut service = self.service.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = match collect_body(body).await {
+                Ok(body) => body,
+                Err(err) => {
+                    return Ok(Status::invalid_argument(format!(
+                        "Failed to read request body: {err}"
+                    ))
+                    .to_http());
+                }
+            };
+
+            let verified = match &authority {
+                Some(authority) => {
+                    verifier.verify(&parts.headers, authority, parts.uri.path(), &body)
+                }
+                None => Err(PeerSignatureError::UnknownAuthority),
+            };
+
+            match verified {
+                Ok(()) => {
+                    service
+                        .call(Request::from_parts(parts, Body::from(body)))
+                        .await
+                }
+                Err(err) => {
+                    log::warn!("Rejected internal request {}: {err}", parts.uri.path());
+                    Ok(Status::unauthenticated(err.to_string()).to_http())
+                }
+            }
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct PeerSignatureLayer {
+    verifier: Arc<PeerRequestVerifier>,
+    consensus_state: ConsensusStateRef,
+}
+
+impl PeerSignatureLayer {
+    pub fn new(signing_key: &str, consensus_state: ConsensusStateRef) -> Self {
+        Self {
+            verifier: Arc::new(PeerRequestVerifier::new(signing_key)),
+            consensus_state,
+        }
+    }
+}
+
+impl<S> Layer<S> for PeerSignatureLayer {
+    type Service = PeerSignatureMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Self::Service {
+            verifier: self.verifier.clone(),
+            consensus_state: self.consensus_state.clone(),
+            service,
+        }
+    }
+}
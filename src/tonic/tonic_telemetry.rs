@@ -3,9 +3,11 @@ use std::task::{Context, Poll};
 
 use futures_util::future::BoxFuture;
 use tonic::body::BoxBody;
+use tonic::codegen::http::header::{HeaderName, HeaderValue};
 use tower::Service;
 use tower_layer::Layer;
 
+use crate::common::request_id::{self, REQUEST_ID_HEADER};
 use crate::common::telemetry_ops::requests_telemetry::{
     CollectionName, TonicTelemetryCollector, TonicWorkerTelemetryCollector,
 };
@@ -49,11 +51,46 @@ where
 
     fn call(&mut self, request: Request) -> Self::Future {
         let method_name = request.uri().path().to_string();
-        let future = self.service.call(request);
+
+        let request_id = request_id::request_id(
+            request
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|value| value.to_str().ok()),
+        );
+        let request_span = request_id::request_span(&request_id);
+
+        #[cfg(feature = "tracing")]
+        let span = {
+            let span = request_span
+                .in_scope(|| tracing::info_span!("grpc_request", method = %method_name));
+            #[cfg(feature = "otlp")]
+            crate::tracing::otlp::set_parent_from_headers(
+                &span,
+                request
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_bytes())),
+            );
+            span
+        };
+
+        let future = request_span.in_scope(|| {
+            #[cfg(feature = "tracing")]
+            let _entered = span.enter();
+            self.service.call(request)
+        });
+
         let telemetry_data = self.telemetry_data.clone();
-        Box::pin(async move {
+        let future = async move {
             let instant = std::time::Instant::now();
-            let response = future.await?;
+            let mut response = future.await?;
+
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
 
             // For gRPC, HTTP status is usually 200, check grpc-status header instead
             // grpc-status: 0 = OK, non-zero = error
@@ -81,7 +118,13 @@ where
                 .lock()
                 .add_response(method_name, instant, status_code, collection_name);
             Ok(response)
-        })
+        };
+
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        let future = tracing::Instrument::instrument(future, request_span);
+
+        Box::pin(future)
     }
 }
 
@@ -2,6 +2,7 @@ pub mod config;
 pub mod default;
 pub mod handle;
 pub mod on_disk;
+pub mod otlp;
 
 #[cfg(test)]
 mod test;
@@ -75,6 +76,15 @@ pub fn setup(mut config: config::LoggerConfig) -> anyhow::Result<LoggerHandle> {
         ),
     );
 
+    let otlp_layer = match otlp::new_layer(&config.otlp) {
+        Ok(layer) => layer,
+        Err(err) => {
+            eprintln!("failed to enable OTLP trace export: {err}");
+            None
+        }
+    };
+    let reg = reg.with(otlp_layer);
+
     tracing::subscriber::set_global_default(reg)?;
     tracing_log::LogTracer::init()?;
 
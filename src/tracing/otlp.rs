@@ -0,0 +1,159 @@
+//! Export of `tracing` spans to an OpenTelemetry collector over OTLP/HTTP.
+
+use common::ext::OptionExt;
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::{Layer, registry};
+
+#[cfg(feature = "otlp")]
+const DEFAULT_SERVICE_NAME: &str = "qdrant";
+
+/// Headers carrying W3C trace context, see <https://www.w3.org/TR/trace-context/>
+#[cfg(feature = "otlp")]
+const TRACE_CONTEXT_HEADERS: &[&str] = &["traceparent", "tracestate"];
+
+/// Provider of the exporting tracer, kept to flush batched spans on [`shutdown`]
+#[cfg(feature = "otlp")]
+static TRACER_PROVIDER: std::sync::OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> =
+    std::sync::OnceLock::new();
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub enabled: Option<bool>,
+    /// OTLP/HTTP traces endpoint, e.g. `http://localhost:4318/v1/traces`
+    pub endpoint: Option<String>,
+    pub service_name: Option<String>,
+    /// Filter for exported spans, same syntax as `log_level`
+    pub span_level: Option<String>,
+}
+
+impl Config {
+    pub fn merge(&mut self, other: Self) {
+        let Self {
+            enabled,
+            endpoint,
+            service_name,
+            span_level,
+        } = other;
+
+        self.enabled.replace_if_some(enabled);
+        self.endpoint.replace_if_some(endpoint);
+        self.service_name.replace_if_some(service_name);
+        self.span_level.replace_if_some(span_level);
+    }
+}
+
+#[cfg(feature = "otlp")]
+pub fn new_layer<S>(config: &Config) -> anyhow::Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+{
+    use anyhow::Context as _;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig as _;
+
+    use super::filter;
+
+    if !config.enabled.unwrap_or_default() {
+        return Ok(None);
+    }
+
+    let mut exporter = opentelemetry_otlp::SpanExporter::builder().with_http();
+    if let Some(endpoint) = &config.endpoint {
+        exporter = exporter.with_endpoint(endpoint);
+    }
+    let exporter = exporter
+        .build()
+        .context("failed to create OTLP span exporter")?;
+
+    let resource = opentelemetry_sdk::Resource::builder()
+        .with_service_name(
+            config
+                .service_name
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string()),
+        )
+        .build();
+
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_resource(resource)
+        .with_batch_exporter(exporter)
+        .build();
+
+    let tracer = provider.tracer(DEFAULT_SERVICE_NAME);
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    if TRACER_PROVIDER.set(provider).is_err() {
+        anyhow::bail!("OTLP trace export is already initialized");
+    }
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter(config.span_level.as_deref().unwrap_or("")));
+
+    Ok(Some(Box::new(layer)))
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn new_layer<S>(config: &Config) -> anyhow::Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+{
+    if config.enabled.unwrap_or_default() {
+        return Err(anyhow::format_err!(
+            "qdrant is built without `otlp` feature"
+        ));
+    }
+
+    Ok(None)
+}
+
+/// Export remaining batched spans and stop the exporter.
+///
+/// Must be called on graceful shutdown, otherwise spans not yet exported are dropped.
+#[cfg(feature = "otlp")]
+pub fn shutdown() {
+    let Some(provider) = TRACER_PROVIDER.get() else {
+        return;
+    };
+
+    if let Err(err) = provider.shutdown() {
+        log::warn!("Failed to shut down OTLP trace export: {err}");
+    }
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn shutdown() {}
+
+/// Continue the trace propagated in request headers (if any) in the given span.
+#[cfg(feature = "otlp")]
+pub fn set_parent_from_headers<'a>(
+    span: &tracing::Span,
+    headers: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) {
+    use std::collections::HashMap;
+
+    use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+    let carrier: HashMap<String, String> = headers
+        .into_iter()
+        .filter(|(name, _)| TRACE_CONTEXT_HEADERS.contains(name))
+        .filter_map(|(name, value)| {
+            let value = std::str::from_utf8(value).ok()?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect();
+
+    if carrier.is_empty() {
+        return;
+    }
+
+    let context =
+        opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+
+    if let Err(err) = span.set_parent(context) {
+        log::trace!("Failed to set parent of request span: {err}");
+    }
+}
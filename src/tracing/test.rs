@@ -44,6 +44,8 @@ fn deserialize_logger_config() {
             format: None,
             buffer_size_bytes: Some(1024),
         },
+
+        otlp: otlp::Config::default(),
     };
 
     assert_eq!(config, expected);
@@ -91,6 +93,33 @@ fn deserialize_json_logger_config() {
             format: Some(config::LogFormat::Text),
             buffer_size_bytes: Some(1024),
         },
+
+        otlp: otlp::Config::default(),
+    };
+
+    assert_eq!(config, expected);
+}
+
+#[test]
+fn deserialize_otlp_logger_config() {
+    let json = json!({
+        "otlp": {
+            "enabled": true,
+            "endpoint": "http://localhost:4318/v1/traces",
+            "span_level": "info",
+        }
+    });
+
+    let config = deserialize_config(json);
+
+    let expected = LoggerConfig {
+        otlp: otlp::Config {
+            enabled: Some(true),
+            endpoint: Some("http://localhost:4318/v1/traces".into()),
+            service_name: None,
+            span_level: Some("info".into()),
+        },
+        ..Default::default()
     };
 
     assert_eq!(config, expected);
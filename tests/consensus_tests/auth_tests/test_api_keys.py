@@ -0,0 +1,82 @@
+import pytest
+import requests
+from consensus_tests import fixtures
+
+from .utils import API_KEY_HEADERS, REST_URI
+
+COLL_NAME = "api_keys_test_collection"
+OTHER_COLL_NAME = "api_keys_other_test_collection"
+
+
+@pytest.fixture(scope="module", autouse=True)
+def setup(jwt_cluster):
+    for collection in (COLL_NAME, OTHER_COLL_NAME):
+        fixtures.create_collection(REST_URI, collection=collection, headers=API_KEY_HEADERS)
+
+    yield
+
+    for collection in (COLL_NAME, OTHER_COLL_NAME):
+        fixtures.drop_collection(REST_URI, collection, headers=API_KEY_HEADERS)
+
+
+def create_api_key(body):
+    res = requests.post(f"{REST_URI}/api_keys", json=body, headers=API_KEY_HEADERS)
+    res.raise_for_status()
+    return res.json()["result"]
+
+
+def get_collection(collection, key):
+    return requests.get(f"{REST_URI}/collections/{collection}", headers={"api-key": key})
+
+
+def test_collection_scoped_key():
+    created = create_api_key(
+        {"name": "scoped", "access": [{"collection": COLL_NAME, "access": "r"}]}
+    )
+    key = created["key"]
+
+    assert get_collection(COLL_NAME, key).status_code == 200
+    assert get_collection(OTHER_COLL_NAME, key).status_code == 403
+
+    res = requests.get(f"{REST_URI}/api_keys", headers=API_KEY_HEADERS)
+    res.raise_for_status()
+    listed = {api_key["id"]: api_key for api_key in res.json()["result"]}
+    assert "key" not in listed[created["id"]]
+    assert listed[created["id"]]["last_used_at"] is not None
+
+
+def test_revoke_key():
+    created = create_api_key({"access": "r"})
+    assert get_collection(COLL_NAME, created["key"]).status_code == 200
+
+    res = requests.delete(f"{REST_URI}/api_keys/{created['id']}", headers=API_KEY_HEADERS)
+    res.raise_for_status()
+    assert res.json()["result"] is True
+
+    assert get_collection(COLL_NAME, created["key"]).status_code == 401
+
+
+def test_rotate_key():
+    created = create_api_key({"access": "r"})
+
+    res = requests.post(
+        f"{REST_URI}/api_keys/{created['id']}/rotate",
+        json={"grace_period_sec": 600},
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    rotated = res.json()["result"]
+    assert rotated["id"] == created["id"]
+    assert rotated["key"] != created["key"]
+
+    # Previous key is valid during the grace period
+    assert get_collection(COLL_NAME, rotated["key"]).status_code == 200
+    assert get_collection(COLL_NAME, created["key"]).status_code == 200
+
+    res = requests.post(
+        f"{REST_URI}/api_keys/{created['id']}/rotate",
+        json={},
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    assert get_collection(COLL_NAME, rotated["key"]).status_code == 401
@@ -119,12 +119,19 @@ ACTION_ACCESS = {
     "create_collection": EndpointAccess(
         False, False, True, "PUT /collections/{collection_name}", "qdrant.Collections/Create"
     ),
+    "import_backup": EndpointAccess(False, False, True, "PUT /collections/{collection_name}/import"),
     "delete_collection": EndpointAccess(
         False, False, True, "DELETE /collections/{collection_name}", "qdrant.Collections/Delete"
     ),
     "update_collection_params": EndpointAccess(
         False, False, True, "PATCH /collections/{collection_name}", "qdrant.Collections/Update"
     ),
+    "register_scoring_hook": EndpointAccess(
+        False, False, True, "PUT /collections/{collection_name}/scoring_hooks/{hook_name}"
+    ),
+    "remove_scoring_hook": EndpointAccess(
+        False, False, True, "DELETE /collections/{collection_name}/scoring_hooks/{hook_name}"
+    ),
     "get_collection_cluster_info": EndpointAccess(
         True,
         True,
@@ -145,6 +152,36 @@ ACTION_ACCESS = {
         True,
         "GET /collections/{collection_name}/optimizations",
     ),
+    "get_memory_usage": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/memory",
+    ),
+    "get_disk_usage": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/disk",
+    ),
+    "requantize_collection": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/requantize",
+    ),
+    "get_quantization_quality": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/quantization/quality",
+    ),
+    "get_quantized_vector": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/quantization/points/{id}",
+    ),
     "replicate_shard_operation": EndpointAccess(
         False,
         False,
@@ -418,6 +455,40 @@ ACTION_ACCESS = {
         "qdrant.Points/UpdateBatch",
         coll_prw=True,
     ),
+    "import_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/import",
+        coll_prw=True,
+    ),
+    "import_pgvector": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/import/pgvector",
+        coll_prw=True,
+    ),
+    "bulk_upsert_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/bulk",
+        coll_prw=True,
+    ),
+    "upsert_deduplicated": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/points/deduplicated",
+        coll_prw=True,
+    ),
+    "export_dataset": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/export",
+    ),
     "delete_points": EndpointAccess(
         False,
         True,
@@ -482,6 +553,13 @@ ACTION_ACCESS = {
         "qdrant.Points/Scroll",
         coll_prw=True,
     ),
+    "es_scroll_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/scroll/es",
+        coll_prw=True,
+    ),
     "search_points": EndpointAccess(
         True,
         True,
@@ -490,6 +568,13 @@ ACTION_ACCESS = {
         "qdrant.Points/Search",
         coll_prw=True,
     ),
+    "es_search_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/search/es",
+        coll_prw=True,
+    ),
     "search_points_batch": EndpointAccess(
         True,
         True,
@@ -552,6 +637,12 @@ ACTION_ACCESS = {
     "query_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/query", "qdrant.Points/Query"
     ),
+    "query_points_rescore": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/query/rescore"
+    ),
+    "query_points_as_of": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/query/as_of"
+    ),
     "query_batch_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/query/batch", "qdrant.Points/QueryBatch"
     ),
@@ -578,6 +669,7 @@ ACTION_ACCESS = {
     "facet": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/facet", "qdrant.Points/Facet"
     ),
+    "points_changes": EndpointAccess(True, True, True, None, "qdrant.Points/Changes"),
     ### Service ###
     "root": EndpointAccess(True, True, True, "GET /", "qdrant.Qdrant/HealthCheck", everything=True),
     "readyz": EndpointAccess(True, True, True, "GET /readyz", "grpc.health.v1.Health/Check", everything=True),
@@ -589,6 +681,11 @@ ACTION_ACCESS = {
     "clear_issues": EndpointAccess(False, False, True, "DELETE /issues"),
     "get_logger_config": EndpointAccess(True, False, True, "GET /logger", coll_r=False),
     "update_logger_config": EndpointAccess(False, False, True, "POST /logger"),
+    ### API keys ###
+    "list_api_keys": EndpointAccess(False, False, True, "GET /api_keys"),
+    "create_api_key": EndpointAccess(False, False, True, "POST /api_keys"),
+    "rotate_api_key": EndpointAccess(False, False, True, "POST /api_keys/{key_id}/rotate"),
+    "revoke_api_key": EndpointAccess(False, False, True, "DELETE /api_keys/{key_id}"),
 }
 
 
@@ -892,6 +989,23 @@ def test_create_collection():
             requests.delete(f"{REST_URI}/collections/{collection_name}", headers=API_KEY_HEADERS)
 
 
+def test_import_backup():
+    check_access(
+        "import_backup",
+        rest_request={
+            "format": "milvus",
+            "location": "file:///qdrant/imports/objects.jsonl",
+            "schema": {
+                "fields": [
+                    {"name": "id", "type": "INT64", "is_primary": True},
+                    {"name": "vector", "type": "FLOAT_VECTOR", "params": {"dim": 4}},
+                ]
+            },
+        },
+        path_params={"collection_name": random_str()},
+    )
+
+
 def test_delete_collection():
     fake_name = random_str()
     check_access(
@@ -910,6 +1024,21 @@ def test_update_collection_params():
     )
 
 
+def test_register_scoring_hook():
+    check_access(
+        "register_scoring_hook",
+        path_params={"collection_name": COLL_NAME, "hook_name": "hook"},
+        rest_req_kwargs={"data": b"\0asm\1\0\0\0"},
+    )
+
+
+def test_remove_scoring_hook():
+    check_access(
+        "remove_scoring_hook",
+        path_params={"collection_name": COLL_NAME, "hook_name": "hook"},
+    )
+
+
 def test_create_alias():
     def req():
         return {
@@ -999,6 +1128,41 @@ def test_get_optimizations():
     )
 
 
+def test_get_memory_usage():
+    check_access(
+        "get_memory_usage",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_disk_usage():
+    check_access(
+        "get_disk_usage",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_requantize_collection():
+    check_access(
+        "requantize_collection",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_quantization_quality():
+    check_access(
+        "get_quantization_quality",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_get_quantized_vector():
+    check_access(
+        "get_quantized_vector",
+        path_params={"collection_name": COLL_NAME, "id": 1},
+    )
+
+
 def test_replicate_shard_operation():
     peer_ids = [PEER_ID + 5, PEER_ID + 3]
     replicate_shard = {
@@ -1484,6 +1648,60 @@ def test_upsert_points():
     )
 
 
+def test_import_points():
+    check_access(
+        "import_points",
+        rest_request={
+            "location": "file:///qdrant/imports/points.parquet",
+            "id_column": "id",
+            "vector_columns": {"": "vector"},
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_import_pgvector():
+    check_access(
+        "import_pgvector",
+        rest_request={
+            "connection": "postgresql://postgres@127.0.0.1:1/postgres?sslmode=disable",
+            "table": "items",
+            "id_column": "id",
+            "vector_columns": {"": "embedding"},
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_bulk_upsert_points():
+    check_access(
+        "bulk_upsert_points",
+        path_params={"collection_name": COLL_NAME},
+        rest_req_kwargs={
+            "data": '{"id": 1, "vector": [1, 2, 3, 4]}\n',
+            "params": {"format": "jsonl"},
+        },
+    )
+
+
+def test_upsert_deduplicated():
+    check_access(
+        "upsert_deduplicated",
+        rest_request={
+            "points": [{"id": 1, "vector": [1, 2, 3, 4], "content_hash": "hash"}],
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_export_dataset():
+    check_access(
+        "export_dataset",
+        rest_request={"name": "dataset"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_points_batch():
     rest_operations = [
         {"upsert": {"shard_key": SHARD_KEY, "points": [{"id": 1, "vector": [1, 2, 3, 4]}]}},
@@ -1674,6 +1892,14 @@ def test_scroll_points():
     )
 
 
+def test_es_scroll_points():
+    check_access(
+        "es_scroll_points",
+        rest_request={"limit": 10, "query": {"match_all": {}}},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_points():
     check_access(
         "search_points",
@@ -1683,6 +1909,18 @@ def test_search_points():
     )
 
 
+def test_es_search_points():
+    check_access(
+        "es_search_points",
+        rest_request={
+            "vector": [1, 2, 3, 4],
+            "limit": 10,
+            "query": {"bool": {"filter": [{"term": {FIELD_NAME: "keyword"}}]}},
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_points_batch():
     query = {"vector": [1, 2, 3, 4], "limit": 10}
     check_access(
@@ -1806,6 +2044,28 @@ def test_query_points():
     )
 
 
+def test_query_points_rescore():
+    check_access(
+        "query_points_rescore",
+        rest_request={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "rescore": {"scoring_hook": {"name": "missing"}},
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_query_points_as_of():
+    check_access(
+        "query_points_as_of",
+        rest_request={
+            "query": [0.1, 0.2, 0.3, 0.4],
+            "as_of": {"operation": 0},
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_query_batch_points():
     check_access(
         "query_batch_points",
@@ -1886,6 +2146,45 @@ def test_facet():
     )
 
 
+def check_grpc_stream_access(
+        client: grpc_requests.Client,
+        service: str,
+        method: str,
+        request: dict,
+        should_succeed: bool,
+):
+    # Stream doesn't end by itself, so it is considered opened if it is still running at the deadline
+    try:
+        for _change in client.request(service=service, method=method, request=request, timeout=1):
+            break
+    except grpc.RpcError as e:
+        if should_succeed:
+            if e.code() != grpc.StatusCode.DEADLINE_EXCEEDED:
+                pytest.fail(f"{service}/{method} failed with {e.code()}: {e.details()}")
+        else:
+            assert (
+                    e.code() == grpc.StatusCode.PERMISSION_DENIED or e.code() == grpc.StatusCode.UNAUTHENTICATED
+            ), f"{service}/{method} should've gotten `PERMISSION_DENIED` or `UNAUTHENTICATED` status code, but got `{e.code()}: {e.details()}`"
+    else:
+        assert should_succeed, f"{service}/{method} should've been rejected"
+
+
+def test_points_changes():
+    # Streaming endpoint is only available in gRPC
+    service, method = ACTION_ACCESS["points_changes"].grpc_endpoint.split("/")
+    allowed_for = ACTION_ACCESS["points_changes"].access
+    request = {"collection_name": COLL_NAME}
+
+    grpc = get_auth_grpc_clients()
+
+    check_grpc_stream_access(grpc.r, service, method, request, allowed_for.read)
+    check_grpc_stream_access(grpc.coll_r, service, method, request, allowed_for.coll_r)
+    check_grpc_stream_access(grpc.coll_rw, service, method, request, allowed_for.coll_rw)
+    check_grpc_stream_access(grpc.m, service, method, request, allowed_for.manage)
+    check_grpc_stream_access(grpc.api_key, service, method, request, allowed_for.manage)
+    check_grpc_stream_access(grpc.read_only_api_key, service, method, request, allowed_for.read)
+
+
 def test_root():
     check_access("root")
 
@@ -1923,3 +2222,33 @@ def test_get_logger_config():
 
 def test_update_logger_config():
     check_access("update_logger_config", {})
+
+
+def test_list_api_keys():
+    check_access("list_api_keys")
+
+
+def test_create_api_key():
+    check_access("create_api_key", {"name": random_str(), "access": "r"})
+
+
+def create_api_key_id():
+    res = requests.post(
+        f"{REST_URI}/api_keys",
+        json={"name": random_str(), "access": "r"},
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    return res.json()["result"]["id"]
+
+
+def test_rotate_api_key():
+    check_access(
+        "rotate_api_key",
+        {"grace_period_sec": 10},
+        path_params={"key_id": create_api_key_id()},
+    )
+
+
+def test_revoke_api_key():
+    check_access("revoke_api_key", path_params={"key_id": create_api_key_id})
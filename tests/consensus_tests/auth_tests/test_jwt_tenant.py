@@ -0,0 +1,110 @@
+import pytest
+import requests
+from consensus_tests import fixtures
+
+from .utils import API_KEY_HEADERS, REST_URI, SECRET, encode_jwt
+
+COLL_NAME = "jwt_tenant_test_collection"
+VECTOR = [0.1] * fixtures.DENSE_VECTOR_SIZE
+
+TOKEN_TENANT_A = encode_jwt(
+    {"access": [{"collection": COLL_NAME, "access": "rw", "tenant": {"tenant_id": "a"}}]},
+    SECRET,
+)
+
+
+def auth_headers(token):
+    return {"Authorization": f"Bearer {token}"}
+
+
+@pytest.fixture(scope="module", autouse=True)
+def setup(jwt_cluster):
+    fixtures.create_collection(
+        REST_URI,
+        collection=COLL_NAME,
+        headers=API_KEY_HEADERS,
+        sparse_vectors=False,
+    )
+
+    res = requests.put(
+        f"{REST_URI}/collections/{COLL_NAME}/points?wait=true",
+        json={
+            "points": [
+                {"id": 1, "vector": VECTOR, "payload": {"tenant_id": "a"}},
+                {"id": 2, "vector": VECTOR, "payload": {"tenant_id": "b"}},
+            ]
+        },
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+
+    yield
+
+    fixtures.drop_collection(REST_URI, COLL_NAME, headers=API_KEY_HEADERS)
+
+
+def scroll_ids(headers):
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/scroll",
+        json={"limit": 10},
+        headers=headers,
+    )
+    res.raise_for_status()
+    return sorted(point["id"] for point in res.json()["result"]["points"])
+
+
+def test_tenant_reads_only_own_points():
+    assert scroll_ids(auth_headers(TOKEN_TENANT_A)) == [1]
+
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/query",
+        json={"query": VECTOR, "limit": 10},
+        headers=auth_headers(TOKEN_TENANT_A),
+    )
+    res.raise_for_status()
+    assert [point["id"] for point in res.json()["result"]["points"]] == [1]
+
+    # Points can't be retrieved by ID
+    res = requests.get(
+        f"{REST_URI}/collections/{COLL_NAME}/points/2",
+        headers=auth_headers(TOKEN_TENANT_A),
+    )
+    assert res.status_code == 403
+
+
+def test_tenant_writes_only_own_points():
+    headers = auth_headers(TOKEN_TENANT_A)
+
+    # Points of other tenants can't be created
+    res = requests.put(
+        f"{REST_URI}/collections/{COLL_NAME}/points?wait=true",
+        json={"points": [{"id": 3, "vector": VECTOR, "payload": {"tenant_id": "b"}}]},
+        headers=headers,
+    )
+    assert res.status_code == 403
+
+    # Points of other tenants are not overwritten
+    res = requests.put(
+        f"{REST_URI}/collections/{COLL_NAME}/points?wait=true",
+        json={"points": [{"id": 2, "vector": VECTOR, "payload": {"tenant_id": "a"}}]},
+        headers=headers,
+    )
+    res.raise_for_status()
+
+    # Points of other tenants are not deleted
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points/delete?wait=true",
+        json={"points": [1, 2]},
+        headers=headers,
+    )
+    res.raise_for_status()
+
+    assert scroll_ids(API_KEY_HEADERS) == [2]
+
+    res = requests.post(
+        f"{REST_URI}/collections/{COLL_NAME}/points",
+        json={"ids": [2], "with_payload": True},
+        headers=API_KEY_HEADERS,
+    )
+    res.raise_for_status()
+    assert res.json()["result"][0]["payload"] == {"tenant_id": "b"}
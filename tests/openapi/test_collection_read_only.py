@@ -0,0 +1,74 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(
+        collection_name=collection_name, on_disk_vectors=on_disk_vectors
+    )
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_read_only(collection_name, read_only):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="PATCH",
+        path_params={"collection_name": collection_name},
+        body={"read_only": read_only},
+    )
+    assert response.ok
+
+
+def upsert_point(collection_name):
+    return request_with_validation(
+        api="/collections/{collection_name}/points",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "points": [
+                {
+                    "id": 100,
+                    "vector": [0.1, 0.2, 0.3, 0.4],
+                    "payload": {"city": "Berlin"},
+                }
+            ]
+        },
+    )
+
+
+def get_config(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    return response.json()["result"]["config"]
+
+
+def test_collection_read_only(collection_name):
+    set_read_only(collection_name, True)
+    assert get_config(collection_name)["read_only"] is True
+
+    response = upsert_point(collection_name)
+    assert response.status_code == 400
+    assert "read-only" in response.json()["status"]["error"]
+
+    # Reads are still allowed
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 1},
+    )
+    assert response.ok
+
+    set_read_only(collection_name, False)
+    assert "read_only" not in get_config(collection_name)
+
+    response = upsert_point(collection_name)
+    assert response.ok
@@ -0,0 +1,47 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(collection_name):
+    basic_collection_setup(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_disk_usage(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/index",
+        method="PUT",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={"field_name": "city", "field_schema": "keyword"},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/disk",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    result = response.json()["result"]
+
+    components = [
+        "vector_storage_bytes",
+        "quantized_vectors_bytes",
+        "hnsw_index_bytes",
+        "sparse_index_bytes",
+        "payload_storage_bytes",
+        "id_tracker_bytes",
+        "wal_bytes",
+        "snapshots_bytes",
+    ]
+    total = sum(result[component] for component in components)
+    total += sum(result["payload_index_bytes"].values())
+    assert result["total_bytes"] == total
+    assert result["vector_storage_bytes"] > 0
+    assert result["wal_bytes"] > 0
+    assert "city" in result["payload_index_bytes"]
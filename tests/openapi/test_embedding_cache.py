@@ -0,0 +1,68 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_deduplicated(collection_name, body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/deduplicated',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok, response.text
+    return response.json()['result']
+
+
+def test_upsert_deduplicated(collection_name):
+    result = upsert_deduplicated(collection_name, {
+        "hash_field": "text",
+        "points": [
+            {"id": 100, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"text": "hello"}},
+            {"id": 101, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"text": "hello"}},
+            {"id": 102, "vector": [0.4, 0.3, 0.2, 0.1], "payload": {"text": "world"}},
+        ],
+    })
+    assert result == {"points": 3, "deduplicated": 1}
+
+    # Content is already stored by point 100
+    result = upsert_deduplicated(collection_name, {
+        "points": [
+            {"id": 103, "vector": [0.1, 0.2, 0.3, 0.4], "content_hash": result_hash(collection_name, 100)},
+        ],
+    })
+    assert result == {"points": 1, "deduplicated": 1}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": [100, 101, 102, 103], "with_vector": True, "with_payload": True},
+    )
+    assert response.ok
+    points = {point['id']: point for point in response.json()['result']}
+
+    assert points[100]['vector']
+    assert 'content_ref' not in points[100]['payload']
+    assert points[102]['vector']
+    for duplicate in [101, 103]:
+        assert not points[duplicate]['vector']
+        assert points[duplicate]['payload']['content_ref'] == 100
+
+
+def result_hash(collection_name, point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['payload']['content_hash']
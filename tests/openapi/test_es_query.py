@@ -0,0 +1,89 @@
+import pytest
+
+from .helpers.collection_setup import multipayload_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors, collection_name):
+    multipayload_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll_ids(collection_name, body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll/es',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100, **body},
+    )
+    assert response.ok, response.text
+    return sorted(point['id'] for point in response.json()['result']['points'])
+
+
+def test_es_scroll_bool_query(collection_name):
+    ids = scroll_ids(collection_name, {
+        "query": {
+            "bool": {
+                "filter": [
+                    {"term": {"color.keyword": "red"}},
+                    {"range": {"price": {"gte": 50}}},
+                ]
+            }
+        }
+    })
+    assert ids == [7, 8]
+
+    ids = scroll_ids(collection_name, {
+        "query": {
+            "bool": {
+                "must": {"term": {"color": {"value": "red", "boost": 2.0}}},
+                "must_not": [{"exists": {"field": "price"}}],
+            }
+        }
+    })
+    assert ids == [1, 4, 5]
+
+    ids = scroll_ids(collection_name, {
+        "query": {
+            "bool": {
+                "should": [
+                    {"term": {"city": "Berlin"}},
+                    {"term": {"color": "red"}},
+                    {"ids": {"values": ["2", "3", "4"]}},
+                ],
+                "minimum_should_match": 2,
+            }
+        }
+    })
+    assert ids == [1, 2, 3, 4, 6]
+
+
+def test_es_search_with_filter(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search/es',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 10,
+            "query": {"terms": {"city": ["Seoul", "Moscow"]}},
+            "filter": {"must": [{"key": "color", "match": {"value": "red"}}]},
+        },
+    )
+    assert response.ok, response.text
+
+    ids = sorted(point['id'] for point in response.json()['result'])
+    assert ids == [4, 5, 8]
+
+
+def test_es_unsupported_query(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll/es',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"query": {"wildcard": {"city": "Ber*"}}},
+    )
+    assert response.status_code == 400
+    assert "wildcard" in response.json()['status']['error']
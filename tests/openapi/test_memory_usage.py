@@ -0,0 +1,33 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(collection_name):
+    basic_collection_setup(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_memory_usage(collection_name):
+    response = request_with_validation(
+        api="/collections/{collection_name}/memory",
+        method="GET",
+        path_params={"collection_name": collection_name},
+    )
+    assert response.ok
+    result = response.json()["result"]
+
+    components = [
+        "vector_storage_bytes",
+        "quantized_vectors_bytes",
+        "vector_index_bytes",
+        "payload_index_bytes",
+        "payload_storage_bytes",
+        "id_tracker_bytes",
+        "cache_bytes",
+    ]
+    assert result["total_bytes"] == sum(result[component] for component in components)
+    assert result["vector_storage_bytes"] > 0
@@ -0,0 +1,21 @@
+import uuid
+
+import requests
+
+from openapi.helpers.helpers import qdrant_host_headers
+from openapi.helpers.settings import QDRANT_HOST
+
+
+def test_generated_request_id():
+    response = requests.get(f"{QDRANT_HOST}/collections", headers=qdrant_host_headers())
+    assert response.ok
+
+    # Generated ID is a UUID
+    uuid.UUID(response.headers["x-request-id"])
+
+
+def test_provided_request_id():
+    headers = {**qdrant_host_headers(), "x-request-id": "my-request-42"}
+    response = requests.get(f"{QDRANT_HOST}/collections", headers=headers)
+    assert response.ok
+    assert response.headers["x-request-id"] == "my-request-42"
@@ -0,0 +1,40 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors, collection_name):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def query_as_of(collection_name, as_of):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/query/as_of',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3, "as_of": as_of},
+    )
+
+
+def test_query_as_of_requires_retention(collection_name):
+    response = query_as_of(collection_name, {"operation": 0})
+    assert response.status_code == 400
+    assert "segment_retention_sec" in response.json()["status"]["error"]
+
+
+def test_query_as_of_outside_of_window(collection_name):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={"optimizers_config": {"segment_retention_sec": 3600}},
+    )
+    assert response.ok
+
+    response = query_as_of(collection_name, {"timestamp": "2000-01-01T00:00:00Z"})
+    assert response.status_code == 400
+    assert "retention window" in response.json()["status"]["error"]